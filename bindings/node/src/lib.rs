@@ -0,0 +1,208 @@
+//! Node.js bindings for the Onyx IDL compiler, built as a native addon with napi-rs.
+//!
+//! `npm run build` builds the addon with `napi build` and writes `index.js` and `index.d.ts`.
+//!
+//! ```js
+//! const onyx = require("onyx-idl");
+//!
+//! const module = onyx.parse("struct Point { x i32, y i32, }");
+//! module.definitions[0].sizeBits;                 // 64
+//! onyx.compile(source, "cpp", "point.onyx");      // [{ path: "point.hpp", contents: "..." }, ...]
+//! ```
+
+use std::path::PathBuf;
+
+use napi::bindgen_prelude::BigInt;
+use napi_derive::napi;
+
+use onyx::{
+    LineIndex,
+    ast::{Definition, Field, OnyxModule, WireEndianness},
+    diagnostic::Diagnostic,
+    generators,
+    parser::Parser,
+};
+
+/// A parsed and resolved schema.
+#[napi(object)]
+pub struct Module {
+    /// `"big"`, `"little"`, or `"host"`.
+    pub endianness: String,
+    /// The definitions, in the order they appear in the source.
+    pub definitions: Vec<DefinitionInfo>,
+    /// The schema version, from the `version = N` directive.
+    pub version: Option<u32>,
+    /// The module at each earlier version, from version 1 up.
+    pub history: Vec<Module>,
+}
+
+/// A message, struct, or enum definition.
+#[napi(object)]
+pub struct DefinitionInfo {
+    pub name: String,
+    /// `"message"`, `"struct"`, or `"enum"`.
+    pub kind: String,
+    /// The 1-indexed line the definition starts on.
+    pub line: u32,
+    /// The encoded size in bits; for enums, the width of the underlying type.
+    pub size_bits: Option<u32>,
+    /// The ID of a message marked `@id(N)`.
+    pub id: Option<u32>,
+    /// The fields of a message or struct.
+    pub fields: Vec<FieldInfo>,
+    /// The underlying type of an enum.
+    pub underlying_type: Option<String>,
+    /// Whether an enum is a flags enum.
+    pub flags: bool,
+    /// Whether an enum is marked `@open`.
+    pub open: bool,
+    /// The variants of an enum.
+    pub variants: Vec<VariantInfo>,
+}
+
+/// A field of a message or struct.
+#[napi(object)]
+pub struct FieldInfo {
+    pub name: String,
+    /// The field type as written in the schema, e.g. `u8` or `Header`.
+    pub r#type: String,
+    /// The bit field width, if the field declares one.
+    pub bits: Option<u32>,
+    /// The schema version that added the field, from `@since(N)`.
+    pub since: Option<u32>,
+    /// The schema version that removed the field, from `@removed(N)`.
+    pub removed: Option<u32>,
+    /// The condition of a conditional field, e.g. `kind == Kind.Data`.
+    pub condition: Option<String>,
+}
+
+/// An enum variant.
+#[napi(object)]
+pub struct VariantInfo {
+    pub name: String,
+    /// The explicit value, if the variant declares one.
+    pub value: Option<BigInt>,
+    /// Whether the variant is marked `@composite`.
+    pub composite: bool,
+}
+
+/// A generated file, identified by its virtual path.
+#[napi(object)]
+pub struct CompiledFile {
+    pub path: String,
+    pub contents: String,
+}
+
+/// Parses and resolves a schema.
+#[napi]
+pub fn parse(source: String) -> napi::Result<Module> {
+    let module = Parser::new(&source)
+        .and_then(|p| p.parse_module())
+        .map_err(to_napi_error)?;
+    Ok(module_info(&module, &LineIndex::new(&source)))
+}
+
+/// Compiles a schema for `target` (`"cpp"` or `"rust"`). `name` is the virtual path of the
+/// schema, used to name the outputs; nothing is written to disk.
+#[napi]
+pub fn compile(source: String, target: String, name: String) -> napi::Result<Vec<CompiledFile>> {
+    compile_files(&source, &target, &name).map_err(to_napi_error)
+}
+
+fn compile_files(source: &str, target: &str, name: &str) -> Result<Vec<CompiledFile>, Diagnostic> {
+    let module = Parser::new(source)?.parse_module()?;
+    let files = generators::generator_for_target(target, PathBuf::from(name))?.generate(&module)?;
+
+    Ok(files
+        .into_iter()
+        .map(|(path, contents)| CompiledFile {
+            path: path.to_string_lossy().into_owned(),
+            contents,
+        })
+        .collect())
+}
+
+fn module_info(module: &OnyxModule, index: &LineIndex) -> Module {
+    let endianness = match module.endianness {
+        WireEndianness::Big => "big",
+        WireEndianness::Little => "little",
+        WireEndianness::Host => "host",
+        _ => "unknown",
+    };
+    let mut definitions: Vec<&Definition> = module.definitions.values().collect();
+    definitions.sort_by_key(|def| def.span());
+    Module {
+        endianness: endianness.to_string(),
+        definitions: definitions
+            .into_iter()
+            .map(|def| definition_info(def, index))
+            .collect(),
+        version: module.version,
+        history: module
+            .history
+            .iter()
+            .map(|old| module_info(old, index))
+            .collect(),
+    }
+}
+
+fn definition_info(def: &Definition, index: &LineIndex) -> DefinitionInfo {
+    let mut info = DefinitionInfo {
+        name: def.name().to_string(),
+        kind: String::new(),
+        line: (index.position(def.span().start()).line() + 1) as u32,
+        size_bits: def.size().map(|size| size as u32),
+        id: None,
+        fields: Vec::new(),
+        underlying_type: None,
+        flags: false,
+        open: false,
+        variants: Vec::new(),
+    };
+    match def {
+        Definition::Message(m) => {
+            info.kind = "message".to_string();
+            info.id = m.id.map(u32::from);
+            info.fields = fields_info(&m.fields);
+        }
+        Definition::Struct(s) => {
+            info.kind = "struct".to_string();
+            info.fields = fields_info(&s.fields);
+        }
+        Definition::Enum(e) => {
+            info.kind = "enum".to_string();
+            info.underlying_type = Some(e.underlying_type.to_string());
+            info.flags = e.flags;
+            info.open = e.open;
+            info.variants = e
+                .variants
+                .iter()
+                .map(|variant| VariantInfo {
+                    name: variant.name.clone(),
+                    value: variant.value.map(BigInt::from),
+                    composite: variant.composite,
+                })
+                .collect();
+        }
+        _ => info.kind = "unknown".to_string(),
+    }
+    info
+}
+
+fn fields_info(fields: &[Field]) -> Vec<FieldInfo> {
+    fields
+        .iter()
+        .map(|field| FieldInfo {
+            name: field.name.to_string(),
+            r#type: field.type_info.to_string(),
+            bits: field.bit_field_size.map(|bits| bits as u32),
+            since: field.since,
+            removed: field.removed,
+            condition: field.condition.as_ref().map(ToString::to_string),
+        })
+        .collect()
+}
+
+fn to_napi_error(diagnostic: Diagnostic) -> napi::Error {
+    napi::Error::from_reason(diagnostic.summary())
+}
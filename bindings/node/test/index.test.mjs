@@ -0,0 +1,22 @@
+import assert from "node:assert/strict";
+import { createRequire } from "node:module";
+import test from "node:test";
+
+const onyx = createRequire(import.meta.url)("../index.js");
+
+test("parse returns definitions in source order", () => {
+  const module = onyx.parse("@packed_bits struct P { x i32, y u8 : 3, }\nenum E : u8 { A = 7, }");
+  assert.deepEqual(module.definitions.map((d) => d.name), ["P", "E"]);
+  assert.equal(module.definitions[0].sizeBits, 40);
+  assert.equal(module.definitions[1].line, 2);
+  assert.equal(module.definitions[1].variants[0].value, 7n);
+});
+
+test("compile uses virtual paths", () => {
+  const files = onyx.compile("struct S { f u8, }", "cpp", "schemas/s.onyx");
+  assert.deepEqual(files.map((f) => f.path), ["schemas/s.hpp", "schemas/s.cpp"]);
+});
+
+test("errors carry the diagnostic", () => {
+  assert.throws(() => onyx.parse("message A { hdr B, }"), /custom type 'B' not defined/);
+});
@@ -0,0 +1,29 @@
+//! Lexes arbitrary text and checks that the tokens lie in order within the source.
+//!
+//! ```text
+//! cargo +nightly fuzz run lexer corpus/lexer corpus/schemas
+//! ```
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use onyx::lexer::{Lexer, TokenKind};
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(source) = std::str::from_utf8(data) else {
+        return;
+    };
+    let mut lexer = Lexer::new(source);
+    let mut end = 0;
+    for token in lexer.by_ref() {
+        let span = token.span;
+        // Tokens are in order, do not overlap, and start and end on character boundaries.
+        assert!(end <= span.start() && span.start() <= span.end());
+        let text = &source[span.start()..span.end()];
+        if let TokenKind::Error(error) = token.kind {
+            let _ = error.message(text);
+        }
+        end = span.end();
+    }
+    assert!(lexer.next().is_none(), "token after the end of the source");
+});
@@ -0,0 +1,48 @@
+//! Parses and lowers arbitrary text as a service would, with and without
+//! [`ParseLimits::untrusted`], generates every target from what parses, and renders every
+//! diagnostic against the source.
+//!
+//! ```text
+//! cargo +nightly fuzz run parse_module corpus/parse_module corpus/schemas
+//! ```
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use onyx::{
+    generators::{self, TARGETS},
+    hir,
+    limits::ParseLimits,
+    parser::Parser,
+};
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(source) = std::str::from_utf8(data) else {
+        return;
+    };
+    for limits in [ParseLimits::default(), ParseLimits::untrusted()] {
+        let parser = || Parser::new(source).map(|p| p.with_limits(limits.clone()));
+        match parser().and_then(|p| p.parse_module()) {
+            Ok(module) => {
+                if let Err(error) = hir::lower(&module) {
+                    let _ = error.render(source);
+                }
+                for target in TARGETS {
+                    let generator = generators::generator_for_target(target, "fuzz".into());
+                    if let Err(error) = generator.and_then(|mut g| g.generate(&module)) {
+                        let _ = error.render(source);
+                    }
+                }
+            }
+            Err(error) => {
+                let _ = error.render(source);
+            }
+        }
+        if let Ok(p) = parser() {
+            let (_, errors) = p.parse_module_recovering();
+            for error in errors {
+                let _ = error.render(source);
+            }
+        }
+    }
+});
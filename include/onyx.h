@@ -0,0 +1,170 @@
+#ifndef ONYX_H
+#define ONYX_H
+
+/* Generated by cbindgen from src/capi.rs. Do not edit by hand. */
+
+#include <stdbool.h>
+#include <stddef.h>
+
+// Result codes returned by the C API.
+typedef enum OnyxStatus {
+  // Compilation succeeded and every output was passed to the callback.
+  ONYX_STATUS_OK = 0,
+  // An argument was null or not valid UTF-8.
+  ONYX_STATUS_INVALID_ARGUMENT = 1,
+  // The schema failed to parse, resolve, or generate.
+  ONYX_STATUS_COMPILE_ERROR = 2,
+} OnyxStatus;
+
+// How serious a diagnostic is.
+typedef enum OnyxSeverity {
+  // Reported but does not fail compilation.
+  ONYX_SEVERITY_WARNING = 0,
+  // Fails compilation.
+  ONYX_SEVERITY_ERROR = 1,
+} OnyxSeverity;
+
+// A diagnostic reported by [`onyx_parse`] or [`onyx_generate`], released with
+// [`onyx_diagnostic_free`].
+typedef struct OnyxDiagnostic OnyxDiagnostic;
+
+// A parsed schema, created by [`onyx_parse`] and released with [`onyx_schema_free`].
+typedef struct OnyxSchema OnyxSchema;
+
+// Receives each generated file. `path` and `contents` are NUL-terminated UTF-8 strings that
+// are only valid for the duration of the call.
+typedef void (*OnyxOutputCallback)(const char *path, const char *contents, void *user_data);
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+// Compiles `source` for `target`, one of `"cpp"`, `"rust"`, `"kotlin"`, `"systemverilog"`,
+// `"matlab"`, and `"arrow"`, and passes every output to `out`.
+//
+// `name` is the path of the schema, used to name the outputs; nothing is written to disk.
+// `user_data` is passed through to `out` unchanged. On failure, if `error` is non-null it
+// receives a message that must be released with [`onyx_string_free`].
+//
+// # Safety
+//
+// `source`, `target`, and `name` must be valid NUL-terminated strings, and `error` must be
+// null or point to writable storage for a pointer.
+enum OnyxStatus onyx_compile(const char *source,
+                             const char *target,
+                             const char *name,
+                             OnyxOutputCallback out,
+                             void *user_data,
+                             char **error);
+
+// Parses `source` and stores the schema in `*schema`, to be released with
+// [`onyx_schema_free`].
+//
+// On failure `*schema` is set to null and, if `diagnostic` is non-null, `*diagnostic` receives
+// the error, to be released with [`onyx_diagnostic_free`]. On success `*diagnostic` is set to
+// null.
+//
+// # Safety
+//
+// `source` must be a valid NUL-terminated string, `schema` must point to writable storage for
+// a pointer, and `diagnostic` must be null or point to writable storage for a pointer.
+enum OnyxStatus onyx_parse(const char *source,
+                           struct OnyxSchema **schema,
+                           struct OnyxDiagnostic **diagnostic);
+
+// Generates code for `schema` with `target`, such as `"cpp"`, and passes every output to
+// `out`, as [`onyx_compile`] does.
+//
+// On failure, if `diagnostic` is non-null, `*diagnostic` receives the error, to be released
+// with [`onyx_diagnostic_free`]. On success `*diagnostic` is set to null. The schema is not
+// consumed and can be generated again.
+//
+// # Safety
+//
+// `schema` must be a schema returned by [`onyx_parse`] that has not been freed yet, `target`
+// and `name` must be valid NUL-terminated strings, and `diagnostic` must be null or point to
+// writable storage for a pointer.
+enum OnyxStatus onyx_generate(const struct OnyxSchema *schema,
+                              const char *target,
+                              const char *name,
+                              OnyxOutputCallback out,
+                              void *user_data,
+                              struct OnyxDiagnostic **diagnostic);
+
+// Releases a schema returned by [`onyx_parse`]. Passing null is a no-op.
+//
+// # Safety
+//
+// `schema` must be null or a schema returned by [`onyx_parse`] that has not been freed yet.
+void onyx_schema_free(struct OnyxSchema *schema);
+
+// Returns the primary message of `diagnostic`, valid until the diagnostic is freed.
+//
+// # Safety
+//
+// `diagnostic` must be a diagnostic returned by this library that has not been freed yet.
+const char *onyx_diagnostic_message(const struct OnyxDiagnostic *diagnostic);
+
+// Returns the stable error code of `diagnostic`, such as `"E0001"`, or null if it has none. The
+// code is valid until the diagnostic is freed.
+//
+// # Safety
+//
+// `diagnostic` must be a diagnostic returned by this library that has not been freed yet.
+const char *onyx_diagnostic_code(const struct OnyxDiagnostic *diagnostic);
+
+// Returns how serious `diagnostic` is.
+//
+// # Safety
+//
+// `diagnostic` must be a diagnostic returned by this library that has not been freed yet.
+enum OnyxSeverity onyx_diagnostic_severity(const struct OnyxDiagnostic *diagnostic);
+
+// Stores the 0-indexed line and column where `diagnostic` was found in `*line` and `*column`,
+// and returns true. Returns false, leaving both unchanged, if the diagnostic has no location.
+//
+// # Safety
+//
+// `diagnostic` must be a diagnostic returned by this library that has not been freed yet, and
+// `line` and `column` must point to writable storage.
+bool onyx_diagnostic_position(const struct OnyxDiagnostic *diagnostic,
+                              size_t *line,
+                              size_t *column);
+
+// Stores the byte range of the schema where `diagnostic` was found in `*start` and `*end`, and
+// returns true. Returns false, leaving both unchanged, if the diagnostic has no location.
+//
+// # Safety
+//
+// `diagnostic` must be a diagnostic returned by this library that has not been freed yet, and
+// `start` and `end` must point to writable storage.
+bool onyx_diagnostic_span(const struct OnyxDiagnostic *diagnostic, size_t *start, size_t *end);
+
+// Returns `diagnostic` rendered as uncolored text, with an excerpt of the schema, notes, and
+// help. The string must be released with [`onyx_string_free`].
+//
+// # Safety
+//
+// `diagnostic` must be a diagnostic returned by this library that has not been freed yet.
+char *onyx_diagnostic_render(const struct OnyxDiagnostic *diagnostic);
+
+// Releases a diagnostic returned by this library. Passing null is a no-op.
+//
+// # Safety
+//
+// `diagnostic` must be null or a diagnostic returned by this library that has not been freed
+// yet.
+void onyx_diagnostic_free(struct OnyxDiagnostic *diagnostic);
+
+// Releases a string returned by the C API. Passing null is a no-op.
+//
+// # Safety
+//
+// `s` must be null or a pointer returned by this library that has not been freed yet.
+void onyx_string_free(char *s);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* ONYX_H */
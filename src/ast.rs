@@ -1,7 +1,81 @@
-use std::collections::HashMap;
+use std::{
+    borrow::Cow,
+    collections::HashMap,
+    fmt::{self, Write as _},
+};
+
+use crate::{span::Span, symbol::Symbol};
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+/// Returns `name` as it must be written in Onyx source: as a raw identifier when it would
+/// otherwise read as a keyword, or as `true`, `false`, or `if` where those words are expected.
+fn escaped(name: &str) -> Cow<'_, str> {
+    if crate::lexer::keyword(name).is_some() || matches!(name, "true" | "false" | "if") {
+        Cow::Owned(format!("`{name}`"))
+    } else {
+        Cow::Borrowed(name)
+    }
+}
+
+/// Writes `doc` as `///` lines, each after `indent`.
+fn write_doc(out: &mut String, doc: Option<&str>, indent: &str) {
+    for line in doc.iter().flat_map(|doc| doc.lines()) {
+        match line.is_empty() {
+            true => writeln!(out, "{indent}///").unwrap(),
+            false => writeln!(out, "{indent}/// {line}").unwrap(),
+        }
+    }
+}
+
+/// Accessors shared by every named node in the AST.
+///
+/// This trait is sealed and cannot be implemented outside of this crate, which allows methods to
+/// be added to it without breaking downstream code.
+pub trait AstNode: sealed::Sealed {
+    /// Returns the name of the node as written in the source.
+    fn name(&self) -> &str;
+    /// Returns the byte range of the node's name in the source.
+    fn span(&self) -> Span;
+}
+
+macro_rules! impl_ast_node {
+    ($($ty:ty),*) => {
+        $(
+            impl sealed::Sealed for $ty {}
+
+            impl AstNode for $ty {
+                fn name(&self) -> &str {
+                    self.name.as_ref()
+                }
+
+                fn span(&self) -> Span {
+                    self.span
+                }
+            }
+        )*
+    };
+}
+
+impl_ast_node!(Field, EnumVariant, EnumDef, StructDef, MessageDef);
+
+impl sealed::Sealed for Definition {}
+
+impl AstNode for Definition {
+    fn name(&self) -> &str {
+        Definition::name(self)
+    }
+
+    fn span(&self) -> Span {
+        Definition::span(self)
+    }
+}
 
 /// Represents the primitive types supported by Onyx.
-#[derive(Debug, PartialEq, Clone)]
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[non_exhaustive]
 pub enum PrimitiveType {
     /// Boolean type (1 byte).
     Bool,
@@ -47,29 +121,184 @@ impl PrimitiveType {
             PrimitiveType::U64 | PrimitiveType::I64 | PrimitiveType::F64 => 8,
         }
     }
+
+    /// Returns true for the signed integer types, `i8` through `i64`.
+    pub fn is_signed(&self) -> bool {
+        matches!(
+            self,
+            PrimitiveType::I8 | PrimitiveType::I16 | PrimitiveType::I32 | PrimitiveType::I64
+        )
+    }
+}
+
+impl fmt::Display for PrimitiveType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            PrimitiveType::Bool => "bool",
+            PrimitiveType::U8 => "u8",
+            PrimitiveType::U16 => "u16",
+            PrimitiveType::U32 => "u32",
+            PrimitiveType::U64 => "u64",
+            PrimitiveType::I8 => "i8",
+            PrimitiveType::I16 => "i16",
+            PrimitiveType::I32 => "i32",
+            PrimitiveType::I64 => "i64",
+            PrimitiveType::F32 => "f32",
+            PrimitiveType::F64 => "f64",
+        };
+        write!(f, "{name}")
+    }
 }
 
 /// Represents a type in Onyx, which can be a primitive or a custom user-defined type.
 #[derive(Debug, PartialEq, Clone)]
+#[non_exhaustive]
 pub enum Type {
     /// A built-in primitive type.
     Primitive(PrimitiveType),
     /// A user-defined type (struct or enum), identified by its name.
-    Custom(String), // For user-defined types (structs, enums)
+    Custom(Symbol), // For user-defined types (structs, enums)
+    /// A bounded list, `vec<T, N>`, holding up to `capacity` elements.
+    ///
+    /// On the wire it is a length prefix (see [`Type::length_type`]) followed by room for
+    /// `capacity` elements, whether or not they are in use.
+    Vector {
+        /// The element type; never itself a vector.
+        element: Box<Type>,
+        /// The maximum number of elements.
+        capacity: usize,
+    },
+    /// A fixed-point number, `fixed<T, S>`: the integer `T` on the wire, standing for that
+    /// integer divided by `scale`.
+    Fixed {
+        /// The integer type on the wire.
+        base: PrimitiveType,
+        /// The number of wire units per whole unit; at least 1.
+        scale: u64,
+    },
+    /// A 16-byte UUID, `uuid`, stored as its bytes in order.
+    Uuid,
+    /// An array of bytes, `bytes[N]`, of a fixed nonzero length.
+    Bytes(usize),
+}
+
+impl Type {
+    /// Returns the type of a vector's length prefix: the narrowest unsigned integer that can
+    /// hold its capacity. Returns `None` for other types.
+    pub fn length_type(&self) -> Option<PrimitiveType> {
+        match self {
+            Type::Vector { capacity, .. } => Some(match *capacity {
+                0..=0xFF => PrimitiveType::U8,
+                0x100..=0xFFFF => PrimitiveType::U16,
+                _ => PrimitiveType::U32,
+            }),
+            _ => None,
+        }
+    }
+
+    /// Returns the length of a byte array (`uuid` or `bytes[N]`). Returns `None` for other
+    /// types.
+    pub fn byte_len(&self) -> Option<usize> {
+        match self {
+            Type::Uuid => Some(16),
+            Type::Bytes(len) => Some(*len),
+            _ => None,
+        }
+    }
+
+    /// Returns the element type of a vector, or the type itself for other types.
+    pub fn element_type(&self) -> &Type {
+        match self {
+            Type::Vector { element, .. } => element,
+            _ => self,
+        }
+    }
+
+    /// Calculates the wire width of the type in bits, saturating at `usize::MAX` for a type too
+    /// large to count.
+    ///
+    /// For custom types, it queries the provided `module` to find the definition and its size.
+    pub fn get_bit_width(&self, module: &OnyxModule) -> usize {
+        match self {
+            Type::Primitive(primitive_type) => primitive_type.get_bit_width(),
+            Type::Fixed { base, .. } => base.get_bit_width(),
+            Type::Uuid => 128,
+            Type::Bytes(len) => len.saturating_mul(8),
+            Type::Custom(s) => module.definitions.get(s).unwrap().size().unwrap(),
+            Type::Vector { element, capacity } => {
+                let items = capacity.saturating_mul(element.get_bit_width(module));
+                items.saturating_add(self.length_type().unwrap().get_bit_width())
+            }
+        }
+    }
+}
+
+impl fmt::Display for Type {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Type::Primitive(p) => write!(f, "{p}"),
+            Type::Custom(name) => write!(f, "{}", escaped(name.as_str())),
+            Type::Vector { element, capacity } => write!(f, "vec<{element}, {capacity}>"),
+            Type::Fixed { base, scale } => write!(f, "fixed<{base}, {scale}>"),
+            Type::Uuid => write!(f, "uuid"),
+            Type::Bytes(len) => write!(f, "bytes[{len}]"),
+        }
+    }
 }
 
 /// Represents a field within a struct or message.
 #[derive(Debug, PartialEq, Clone)]
+#[non_exhaustive]
 pub struct Field {
     /// The name of the field.
-    pub name: String,
+    pub name: Symbol,
     /// The type of the field.
     pub type_info: Type,
     /// Optional size for bit fields. If present, specifies the width in bits.
+    ///
+    /// A bit-field of a signed type holds a two's complement number of that many bits: writers
+    /// keep the low bits of the value, and readers sign-extend them, so `temp i16 : 12` holds
+    /// -2048 through 2047.
     pub bit_field_size: Option<usize>,
+    /// The schema version that added the field, from `@since(N)`.
+    pub since: Option<u32>,
+    /// The schema version that removed the field, from `@removed(N)`.
+    pub removed: Option<u32>,
+    /// The condition under which the field is present, from `if kind == Kind.Data`. An absent
+    /// field keeps its slot in the layout, zeroed.
+    pub condition: Option<Condition>,
+    /// The note from `@deprecated("use v2")`, which generated code shows to users of the field.
+    pub deprecated: Option<String>,
+    /// The doc comment from the `///` lines before it, without the markers, one line per
+    /// comment line.
+    pub doc: Option<String>,
+    /// The byte range of the field name in the source.
+    pub span: Span,
 }
 
 impl Field {
+    /// Creates a new field. Set `bit_field_size` to pack the field into the given number of bits.
+    pub fn new(name: impl Into<Symbol>, type_info: Type, bit_field_size: Option<usize>) -> Self {
+        Field {
+            name: name.into(),
+            type_info,
+            bit_field_size,
+            since: None,
+            removed: None,
+            condition: None,
+            deprecated: None,
+            doc: None,
+            span: Span::default(),
+        }
+    }
+
+    /// Returns true if the field is part of the layout of schema version `version`: added at
+    /// or before it, and not removed at or before it.
+    pub fn present_in(&self, version: u32) -> bool {
+        self.since.is_none_or(|since| since <= version)
+            && self.removed.is_none_or(|removed| removed > version)
+    }
+
     /// Calculates the bit width of the field.
     ///
     /// If `bit_field_size` is set, it returns that value.
@@ -78,10 +307,91 @@ impl Field {
     pub fn get_bit_width(&self, module: &OnyxModule) -> usize {
         match self.bit_field_size {
             Some(x) => x,
-            None => match &self.type_info {
-                Type::Primitive(primitive_type) => primitive_type.get_bit_width(),
-                Type::Custom(s) => module.definitions.get(s).unwrap().size().unwrap(),
-            },
+            None => self.type_info.get_bit_width(module),
+        }
+    }
+}
+
+/// A condition on another field of the same struct or message, e.g. `kind == Kind.Data`.
+#[derive(Debug, PartialEq, Clone)]
+#[non_exhaustive]
+pub struct Condition {
+    /// The field the condition reads.
+    pub field: Symbol,
+    /// True for `!=`, false for `==`.
+    pub negated: bool,
+    /// The value the field is compared with.
+    pub value: ConditionValue,
+    /// The byte range of the condition in the source.
+    pub span: Span,
+}
+
+impl Condition {
+    /// Creates a condition that holds when `field` equals `value`, or differs from it if
+    /// `negated`.
+    pub fn new(field: impl Into<Symbol>, negated: bool, value: ConditionValue) -> Self {
+        Condition {
+            field: field.into(),
+            negated,
+            value,
+            span: Span::default(),
+        }
+    }
+
+    /// Returns the raw value the field is compared with. Enum variants are looked up in
+    /// `module`; returns `None` if the variant does not exist.
+    pub fn raw_value(&self, module: &OnyxModule) -> Option<u64> {
+        match &self.value {
+            ConditionValue::Integer(value) => Some(*value),
+            ConditionValue::Bool(value) => Some(*value as u64),
+            ConditionValue::Variant { enum_name, variant } => {
+                let Some(Definition::Enum(e)) = module.definitions.get(enum_name) else {
+                    return None;
+                };
+                let index = e.variants.iter().position(|v| &v.name == variant)?;
+                Some(e.variant_values()[index])
+            }
+        }
+    }
+
+    /// Returns true if the condition holds when the field it reads has the raw value `raw`.
+    pub fn holds(&self, module: &OnyxModule, raw: u64) -> bool {
+        (self.raw_value(module) == Some(raw)) != self.negated
+    }
+}
+
+impl fmt::Display for Condition {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let op = if self.negated { "!=" } else { "==" };
+        write!(f, "{} {op} {}", escaped(self.field.as_str()), self.value)
+    }
+}
+
+/// The value on the right of a [`Condition`].
+#[derive(Debug, PartialEq, Clone)]
+#[non_exhaustive]
+pub enum ConditionValue {
+    /// An integer literal.
+    Integer(u64),
+    /// `true` or `false`.
+    Bool(bool),
+    /// An enum variant, e.g. `Kind.Data`.
+    Variant {
+        /// The name of the enum.
+        enum_name: Symbol,
+        /// The name of the variant.
+        variant: String,
+    },
+}
+
+impl fmt::Display for ConditionValue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ConditionValue::Integer(value) => write!(f, "{value}"),
+            ConditionValue::Bool(value) => write!(f, "{value}"),
+            ConditionValue::Variant { enum_name, variant } => {
+                write!(f, "{}.{}", escaped(enum_name.as_str()), escaped(variant))
+            }
         }
     }
 }
@@ -90,52 +400,200 @@ impl Field {
 
 /// Represents a variant within an enum.
 #[derive(Debug, PartialEq, Clone)]
+#[non_exhaustive]
 pub struct EnumVariant {
     /// The name of the variant.
     pub name: String,
     /// The optional assigned constant value for the variant.
     pub value: Option<u64>,
+    /// Whether the variant of a flags enum is marked `@composite`: a combination of other
+    /// flags rather than a flag of its own.
+    pub composite: bool,
+    /// The doc comment from the `///` lines before it, without the markers, one line per
+    /// comment line.
+    pub doc: Option<String>,
+    /// The byte range of the variant name in the source.
+    pub span: Span,
 }
 
 /// Represents an enum definition.
 #[derive(Debug, PartialEq, Clone)]
+#[non_exhaustive]
 pub struct EnumDef {
     /// The name of the enum.
-    pub name: String,
+    pub name: Symbol,
     /// The underlying primitive integer type for the enum.
     pub underlying_type: PrimitiveType,
     /// The list of variants in the enum.
     pub variants: Vec<EnumVariant>,
+    /// Whether the enum was declared with `flags`, so that its values are bits that combine.
+    pub flags: bool,
+    /// Whether the enum was marked `@open`, so that decoders keep values no variant declares
+    /// instead of rejecting them. Enums are closed unless marked.
+    pub open: bool,
+    /// The note from `@deprecated("use v2")`, which generated code shows to users of the enum.
+    pub deprecated: Option<String>,
+    /// The doc comment from the `///` lines before it, without the markers, one line per
+    /// comment line.
+    pub doc: Option<String>,
+    /// The byte range of the enum name in the source.
+    pub span: Span,
+}
+
+impl EnumVariant {
+    /// Creates a new enum variant with an optional explicit value.
+    pub fn new(name: impl Into<String>, value: Option<u64>) -> Self {
+        EnumVariant {
+            name: name.into(),
+            value,
+            composite: false,
+            doc: None,
+            span: Span::default(),
+        }
+    }
+}
+
+impl EnumDef {
+    /// Creates a new enum definition.
+    pub fn new(
+        name: impl Into<Symbol>,
+        underlying_type: PrimitiveType,
+        variants: Vec<EnumVariant>,
+    ) -> Self {
+        EnumDef {
+            name: name.into(),
+            underlying_type,
+            variants,
+            flags: false,
+            open: false,
+            deprecated: None,
+            doc: None,
+            span: Span::default(),
+        }
+    }
+
+    /// Returns the value of each variant, in order. A variant without an explicit value takes
+    /// the previous variant's value plus one, starting from zero.
+    ///
+    /// In a flags enum, a variant without an explicit value instead takes the next bit after
+    /// the previous flag, starting from 1. Composite variants are skipped when counting.
+    pub fn variant_values(&self) -> Vec<u64> {
+        let mut next = if self.flags { 1 } else { 0 };
+        self.variants
+            .iter()
+            .map(|variant| {
+                let value = variant.value.unwrap_or(next);
+                if !self.flags {
+                    next = value.wrapping_add(1);
+                } else if !variant.composite {
+                    next = value.wrapping_shl(1);
+                }
+                value
+            })
+            .collect()
+    }
+
+    /// Returns true if `value` is the value of a variant.
+    pub fn is_known(&self, value: u64) -> bool {
+        self.variant_values().contains(&value)
+    }
 }
 
 // --- Struct/Message Definitions ---
 
 /// Represents a struct definition.
 #[derive(Debug, PartialEq, Clone)]
+#[non_exhaustive]
 pub struct StructDef {
     /// The name of the struct.
-    pub name: String,
+    pub name: Symbol,
     /// The fields contained in the struct.
     pub fields: Vec<Field>,
-    /// Optional explicit size for the struct in bytes.
+    /// The size of the struct in bits as [`hir::lower`](crate::hir::lower) lays it out, set
+    /// when the module is resolved.
     pub size: Option<usize>,
+    /// The budget in bytes from `@max_size(N)`, which resolution fails if the struct outgrows.
+    pub max_size: Option<usize>,
+    /// Whether the struct is marked `@packed_bits`, which pads a run of bit-fields that ends
+    /// off a byte boundary instead of rejecting it.
+    pub packed_bits: bool,
+    /// The note from `@deprecated("use v2")`, which generated code shows to users of the struct.
+    pub deprecated: Option<String>,
+    /// The doc comment from the `///` lines before it, without the markers, one line per
+    /// comment line.
+    pub doc: Option<String>,
+    /// The byte range of the struct name in the source.
+    pub span: Span,
 }
 
 /// Represents a message definition.
 #[derive(Debug, PartialEq, Clone)]
+#[non_exhaustive]
 pub struct MessageDef {
     /// The name of the message.
-    pub name: String,
+    pub name: Symbol,
     /// The fields contained in the message.
     pub fields: Vec<Field>,
-    /// Optional explicit size for the message in bytes.
+    /// The size of the message in bits as [`hir::lower`](crate::hir::lower) lays it out, set
+    /// when the module is resolved.
     pub size: Option<usize>,
+    /// The ID from `@id(N)`, which precedes the message on the wire so that receivers can
+    /// tell messages apart.
+    pub id: Option<u16>,
+    /// The budget in bytes from `@max_size(N)`, such as a transport MTU or DMA buffer, which
+    /// resolution fails if the message outgrows.
+    pub max_size: Option<usize>,
+    /// Whether the message is marked `@packed_bits`, which pads a run of bit-fields that ends
+    /// off a byte boundary instead of rejecting it.
+    pub packed_bits: bool,
+    /// The note from `@deprecated("use v2")`, which generated code shows to users of the
+    /// message.
+    pub deprecated: Option<String>,
+    /// The doc comment from the `///` lines before it, without the markers, one line per
+    /// comment line.
+    pub doc: Option<String>,
+    /// The byte range of the message name in the source.
+    pub span: Span,
+}
+
+impl StructDef {
+    /// Creates a new, unresolved struct definition.
+    pub fn new(name: impl Into<Symbol>, fields: Vec<Field>) -> Self {
+        StructDef {
+            name: name.into(),
+            fields,
+            size: None,
+            max_size: None,
+            packed_bits: false,
+            deprecated: None,
+            doc: None,
+            span: Span::default(),
+        }
+    }
+}
+
+impl MessageDef {
+    /// Creates a new, unresolved message definition.
+    pub fn new(name: impl Into<Symbol>, fields: Vec<Field>) -> Self {
+        MessageDef {
+            name: name.into(),
+            fields,
+            size: None,
+            id: None,
+            max_size: None,
+            packed_bits: false,
+            deprecated: None,
+            doc: None,
+            span: Span::default(),
+        }
+    }
 }
 
 // --- Top-Level Definitions and Module ---
 
 /// Represents a top-level definition in an Onyx module.
 #[derive(Debug, PartialEq, Clone)]
+#[non_exhaustive]
 pub enum Definition {
     /// A message definition.
     Message(MessageDef),
@@ -148,17 +606,51 @@ pub enum Definition {
 impl Definition {
     /// Returns the name of the definition.
     pub fn name(&self) -> &str {
+        self.symbol().as_str()
+    }
+
+    /// Returns the interned name of the definition, its key in [`OnyxModule::definitions`].
+    pub fn symbol(&self) -> Symbol {
         match self {
-            Definition::Message(m) => &m.name,
-            Definition::Struct(s) => &s.name,
-            Definition::Enum(e) => &e.name,
+            Definition::Message(m) => m.name,
+            Definition::Struct(s) => s.name,
+            Definition::Enum(e) => e.name,
+        }
+    }
+
+    /// Returns the byte range of the definition name in the source.
+    pub fn span(&self) -> Span {
+        match self {
+            Definition::Message(m) => m.span,
+            Definition::Struct(s) => s.span,
+            Definition::Enum(e) => e.span,
+        }
+    }
+
+    /// Returns the note of the definition's `@deprecated` attribute, if it has one.
+    pub fn deprecated(&self) -> Option<&str> {
+        match self {
+            Definition::Message(m) => m.deprecated.as_deref(),
+            Definition::Struct(s) => s.deprecated.as_deref(),
+            Definition::Enum(e) => e.deprecated.as_deref(),
+        }
+    }
+
+    /// Returns the doc comment of the definition, if it has one.
+    pub fn doc(&self) -> Option<&str> {
+        match self {
+            Definition::Message(m) => m.doc.as_deref(),
+            Definition::Struct(s) => s.doc.as_deref(),
+            Definition::Enum(e) => e.doc.as_deref(),
         }
     }
 
     /// Returns the size of the definition in bits, if available.
     ///
     /// For enums, it returns the bit width of the underlying type.
-    /// For structs and messages, it returns the explicit size if set.
+    /// For structs and messages, it returns the size set when the module was resolved, which
+    /// pads each bit-field container to whole bytes. [`Hir::size_of`](crate::hir::Hir::size_of)
+    /// gives sizes in bytes.
     pub fn size(&self) -> Option<usize> {
         match self {
             Definition::Message(message_def) => message_def.size,
@@ -169,30 +661,289 @@ impl Definition {
 }
 
 /// Specifies the endianness for wire transmission.
-#[derive(Debug, Default, PartialEq, Clone)]
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
+#[non_exhaustive]
 pub enum WireEndianness {
     /// Little-endian byte order.
     #[default]
     Little,
     /// Big-endian byte order.
     Big,
+    /// The native byte order of the machine, for buffers that never leave it, such as shared
+    /// memory between processes. Nothing is byte-swapped.
+    Host,
+}
+
+/// How fields are aligned on the wire, from the `align` directive.
+///
+/// Under the XCDR rules of DDS, each field starts at a multiple of its alignment, after
+/// padding bytes if needed, so that the layout matches what a DDS implementation writes for
+/// the same IDL. A struct is aligned like its most aligned field, and its size ends at its
+/// last field, without trailing padding.
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
+#[non_exhaustive]
+pub enum Alignment {
+    /// No padding: each field starts where the previous one ends.
+    #[default]
+    Packed,
+    /// The rules of XCDR version 1, or classic CDR: a number is aligned to its size.
+    Xcdr1,
+    /// The rules of XCDR version 2: a number is aligned to its size, but at most to 4 bytes.
+    Xcdr2,
+}
+
+impl Alignment {
+    /// Returns the alignment in bytes of a number, or of a bit-field container, that is `size`
+    /// bytes long.
+    pub fn of(&self, size: usize) -> usize {
+        match self {
+            Alignment::Packed => 1,
+            Alignment::Xcdr1 => size.clamp(1, 8),
+            Alignment::Xcdr2 => size.clamp(1, 4),
+        }
+    }
+}
+
+impl fmt::Display for Alignment {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            Alignment::Packed => "packed",
+            Alignment::Xcdr1 => "xcdr1",
+            Alignment::Xcdr2 => "xcdr2",
+        })
+    }
+}
+
+impl WireEndianness {
+    /// Returns false for [`WireEndianness::Host`], whose buffers are only understood by
+    /// machines with the same byte order.
+    pub fn is_portable(&self) -> bool {
+        *self != WireEndianness::Host
+    }
 }
 
 /// Represents a parsed Onyx module containing definitions.
 #[derive(Debug, Default, PartialEq, Clone)]
+#[non_exhaustive]
 pub struct OnyxModule {
     /// A map of definitions by name.
-    pub definitions: HashMap<String, Definition>,
+    pub definitions: HashMap<Symbol, Definition>,
     /// The endianness used for this module.
     pub endianness: WireEndianness,
-    /// The order in which definitions appeared in the source.
-    pub order: Vec<String>,
+    /// The definitions to generate, in dependency order: each after the definitions its fields
+    /// use, and otherwise in source order. Generators emit types in this order.
+    pub order: Vec<Symbol>,
+    /// The schema version, from the `version = N` directive.
+    ///
+    /// The definitions have the layout of this version: fields removed at or before it are
+    /// left out.
+    pub version: Option<u32>,
+    /// Whether resolution rejects layout surprises, from the `strict = true` directive: a
+    /// bit-field that narrows a signed type, a closed enum whose underlying type is wider than
+    /// its values need, and bit-fields that leave padding bits in their container.
+    pub strict: bool,
+    /// Whether a `bool` field takes one bit rather than a byte, from the `bool_width = 1`
+    /// directive.
+    ///
+    /// Resolution gives each `bool` field that has no bit-field size a size of 1, so it packs
+    /// with the bit-fields around it.
+    pub packed_bools: bool,
+    /// How fields are aligned on the wire, from the `align` directive.
+    pub alignment: Alignment,
+    /// The resolved modules of the earlier versions, from version 1 up, when the module has a
+    /// version.
+    pub history: Vec<OnyxModule>,
+}
+
+impl OnyxModule {
+    /// Returns the module with the layout of schema version `version`, or `None` if the
+    /// module has no such version. A module without a version directive only has its own.
+    pub fn layout(&self, version: u32) -> Option<&OnyxModule> {
+        match self.version {
+            Some(current) if version == current => Some(self),
+            Some(_) => self.history.get((version as usize).checked_sub(1)?),
+            None => None,
+        }
+    }
+
+    /// Prints the module as Onyx source that parses back to the same definitions.
+    ///
+    /// Definitions are printed in source order, or by name when they have no source (such as
+    /// modules built in code). Doc comments are kept, and other comments are not.
+    pub fn to_idl(&self) -> String {
+        let mut definitions: Vec<&Definition> = self.definitions.values().collect();
+        definitions.sort_by(|a, b| (a.span().start, a.name()).cmp(&(b.span().start, b.name())));
+
+        let endianness = match self.endianness {
+            WireEndianness::Big => "big",
+            WireEndianness::Little => "little",
+            WireEndianness::Host => "host",
+        };
+        let mut out = format!("endian = {endianness}\n");
+        if let Some(version) = self.version {
+            writeln!(out, "version = {version}").unwrap();
+        }
+        if self.strict {
+            writeln!(out, "strict = true").unwrap();
+        }
+        if self.packed_bools {
+            writeln!(out, "bool_width = 1").unwrap();
+        }
+        if self.alignment != Alignment::Packed {
+            writeln!(out, "align = {}", self.alignment).unwrap();
+        }
+        for def in definitions {
+            out.push('\n');
+            write_doc(&mut out, def.doc(), "");
+            if let Some(note) = def.deprecated() {
+                write!(out, "@deprecated(\"{note}\") ").unwrap();
+            }
+            match def {
+                Definition::Message(m) => {
+                    if let Some(id) = m.id {
+                        write!(out, "@id({id}) ").unwrap();
+                    }
+                    if let Some(max_size) = m.max_size {
+                        write!(out, "@max_size({max_size}) ").unwrap();
+                    }
+                    if m.packed_bits {
+                        out.push_str("@packed_bits ");
+                    }
+                    writeln!(out, "message {} {{", escaped(m.name.as_str())).unwrap()
+                }
+                Definition::Struct(s) => {
+                    if let Some(max_size) = s.max_size {
+                        write!(out, "@max_size({max_size}) ").unwrap();
+                    }
+                    if s.packed_bits {
+                        out.push_str("@packed_bits ");
+                    }
+                    writeln!(out, "struct {} {{", escaped(s.name.as_str())).unwrap()
+                }
+                Definition::Enum(e) => {
+                    let keyword = match (e.flags, e.open) {
+                        (true, _) => "flags",
+                        (false, true) => "@open enum",
+                        (false, false) => "enum",
+                    };
+                    let name = escaped(e.name.as_str());
+                    writeln!(out, "{keyword} {name} : {} {{", e.underlying_type).unwrap();
+                    for variant in &e.variants {
+                        write_doc(&mut out, variant.doc.as_deref(), "    ");
+                        let attribute = if variant.composite { "@composite " } else { "" };
+                        let name = escaped(&variant.name);
+                        match variant.value {
+                            Some(value) => {
+                                writeln!(out, "    {attribute}{name} = {value},").unwrap()
+                            }
+                            None => writeln!(out, "    {attribute}{name},").unwrap(),
+                        }
+                    }
+                    out.push_str("}\n");
+                    continue;
+                }
+            }
+            for field in self.declared_fields(def) {
+                write_doc(&mut out, field.doc.as_deref(), "    ");
+                out.push_str("    ");
+                if let Some(since) = field.since {
+                    write!(out, "@since({since}) ").unwrap();
+                }
+                if let Some(removed) = field.removed {
+                    write!(out, "@removed({removed}) ").unwrap();
+                }
+                if let Some(note) = &field.deprecated {
+                    write!(out, "@deprecated(\"{note}\") ").unwrap();
+                }
+                write!(out, "{} {}", escaped(field.name.as_str()), field.type_info).unwrap();
+                if let Some(bits) = field.bit_field_size {
+                    write!(out, " : {bits}").unwrap();
+                }
+                if let Some(condition) = &field.condition {
+                    write!(out, " if {condition}").unwrap();
+                }
+                out.push_str(",\n");
+            }
+            out.push_str("}\n");
+        }
+        out
+    }
+
+    /// Returns the messages marked `@id`, ordered by ID.
+    ///
+    /// On the wire, the ID precedes the message as a `u16` in the module's byte order.
+    pub fn identified_messages(&self) -> Vec<&MessageDef> {
+        let mut messages: Vec<&MessageDef> = self
+            .definitions
+            .values()
+            .filter_map(|def| match def {
+                Definition::Message(m) if m.id.is_some() => Some(m),
+                _ => None,
+            })
+            .collect();
+        messages.sort_by_key(|m| (m.id, m.span.start));
+        messages
+    }
+
+    /// Returns the fields of a struct or message in every version, including the ones removed
+    /// before the current version, in source order.
+    pub(crate) fn declared_fields<'a>(&'a self, def: &'a Definition) -> Vec<&'a Field> {
+        let fields_of = |def: &'a Definition| match def {
+            Definition::Message(m) => m.fields.as_slice(),
+            Definition::Struct(s) => s.fields.as_slice(),
+            Definition::Enum(_) => &[],
+        };
+        let mut fields: Vec<&Field> = fields_of(def).iter().collect();
+        let removed: Vec<&Field> = self
+            .history
+            .iter()
+            .filter_map(|old| old.definitions.get(&def.symbol()))
+            .flat_map(fields_of)
+            .filter(|field| field.removed.is_some())
+            .collect();
+        for field in removed {
+            if !fields.iter().any(|f| f.name == field.name) {
+                fields.push(field);
+            }
+        }
+        if !self.history.is_empty() {
+            fields.sort_by_key(|field| field.span.start);
+        }
+        fields
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_type_display() {
+        assert_eq!(PrimitiveType::Bool.to_string(), "bool");
+        assert_eq!(PrimitiveType::I16.to_string(), "i16");
+        assert_eq!(Type::Primitive(PrimitiveType::F64).to_string(), "f64");
+        assert_eq!(Type::Custom("Header".into()).to_string(), "Header");
+        let vector = Type::Vector {
+            element: Box::new(Type::Primitive(PrimitiveType::U16)),
+            capacity: 300,
+        };
+        assert_eq!(vector.to_string(), "vec<u16, 300>");
+        assert_eq!(vector.length_type(), Some(PrimitiveType::U16));
+        assert_eq!(vector.get_bit_width(&OnyxModule::default()), 16 + 300 * 16);
+        let fixed = Type::Fixed {
+            base: PrimitiveType::I32,
+            scale: 1000,
+        };
+        assert_eq!(fixed.to_string(), "fixed<i32, 1000>");
+        assert_eq!(fixed.get_bit_width(&OnyxModule::default()), 32);
+        assert_eq!(Type::Uuid.to_string(), "uuid");
+        assert_eq!(Type::Bytes(6).to_string(), "bytes[6]");
+        assert_eq!(Type::Bytes(6).byte_len(), Some(6));
+        assert_eq!(Type::Uuid.get_bit_width(&OnyxModule::default()), 128);
+        let huge = Type::Bytes(usize::MAX / 4);
+        assert_eq!(huge.get_bit_width(&OnyxModule::default()), usize::MAX);
+    }
+
     #[test]
     fn test_primitive_type_sizes() {
         assert_eq!(PrimitiveType::Bool.get_bit_width(), 8);
@@ -210,9 +961,15 @@ mod tests {
     fn test_field_bit_width_primitive() {
         let module = OnyxModule::default();
         let field = Field {
-            name: "test".to_string(),
+            name: "test".into(),
             type_info: Type::Primitive(PrimitiveType::U32),
             bit_field_size: None,
+            since: None,
+            removed: None,
+            condition: None,
+            deprecated: None,
+            doc: None,
+            span: Span::default(),
         };
         assert_eq!(field.get_bit_width(&module), 32);
     }
@@ -221,9 +978,15 @@ mod tests {
     fn test_field_bit_width_bitfield() {
         let module = OnyxModule::default();
         let field = Field {
-            name: "test".to_string(),
+            name: "test".into(),
             type_info: Type::Primitive(PrimitiveType::U32),
             bit_field_size: Some(12),
+            since: None,
+            removed: None,
+            condition: None,
+            deprecated: None,
+            doc: None,
+            span: Span::default(),
         };
         assert_eq!(field.get_bit_width(&module), 12);
     }
@@ -232,18 +995,29 @@ mod tests {
     fn test_field_bit_width_custom() {
         let mut module = OnyxModule::default();
         let enum_def = EnumDef {
-            name: "MyEnum".to_string(),
+            name: "MyEnum".into(),
             underlying_type: PrimitiveType::U16,
             variants: vec![],
+            flags: false,
+            open: false,
+            deprecated: None,
+            doc: None,
+            span: Span::default(),
         };
         module
             .definitions
-            .insert("MyEnum".to_string(), Definition::Enum(enum_def));
+            .insert("MyEnum".into(), Definition::Enum(enum_def));
 
         let field = Field {
-            name: "test".to_string(),
-            type_info: Type::Custom("MyEnum".to_string()),
+            name: "test".into(),
+            type_info: Type::Custom("MyEnum".into()),
             bit_field_size: None,
+            since: None,
+            removed: None,
+            condition: None,
+            deprecated: None,
+            doc: None,
+            span: Span::default(),
         };
 
         // Enum underlying type is U16 (2 bytes), so size is 2 * 8 = 16 bits
@@ -253,9 +1027,15 @@ mod tests {
     #[test]
     fn test_definition_helpers() {
         let msg_def = MessageDef {
-            name: "MyMsg".to_string(),
+            name: "MyMsg".into(),
             fields: vec![],
             size: Some(100),
+            id: None,
+            max_size: None,
+            packed_bits: false,
+            deprecated: None,
+            doc: None,
+            span: Span::default(),
         };
         let def = Definition::Message(msg_def);
 
@@ -263,11 +1043,113 @@ mod tests {
         assert_eq!(def.size(), Some(100));
 
         let enum_def = EnumDef {
-            name: "MyEnum".to_string(),
+            name: "MyEnum".into(),
             underlying_type: PrimitiveType::U8,
             variants: vec![],
+            flags: false,
+            open: false,
+            deprecated: None,
+            doc: None,
+            span: Span::default(),
         };
         let def_enum = Definition::Enum(enum_def);
         assert_eq!(def_enum.size(), Some(8)); // 1 byte = 8 bits
     }
+
+    #[test]
+    fn test_ast_node_accessors() {
+        let field = Field::new("id", Type::Primitive(PrimitiveType::U64), None);
+        let def = Definition::Message(MessageDef::new("MyMsg", vec![field]));
+
+        let nodes: Vec<&dyn AstNode> = match &def {
+            Definition::Message(m) => vec![&def, m, &m.fields[0]],
+            _ => unreachable!(),
+        };
+        let names: Vec<&str> = nodes.iter().map(|n| n.name()).collect();
+        assert_eq!(names, vec!["MyMsg", "MyMsg", "id"]);
+    }
+
+    #[test]
+    fn test_to_idl_round_trip() {
+        let source = "endian = big\n\
+                      // Comments are dropped.\n\
+                      @open enum Status : u8 { Active = 1, Inactive, }\n\
+                      flags Access : u8 { Read, Write, @composite All = 3, }\n\
+                      @deprecated(\"send a User\") @packed_bits struct Header { version u32 : 4, tag Status, }\n\
+                      version = 3\n\
+                      @id(7) message User {\n\
+                          id uuid, @removed(2) key bytes[6], hdr Header, access Access,\n\
+                          @since(2) @deprecated(\"use access\") tags vec<Status, 3>,\n\
+                          gain fixed<i16, 100>,\n\
+                          detail u32 if hdr_tag == Status.Inactive, hdr_tag Status,\n\
+                          rare u8 : 3 if id_kind != 2, id_kind u8 : 5,\n\
+                      }";
+        let module = crate::parser::Parser::new(source)
+            .unwrap()
+            .parse_module()
+            .unwrap();
+        let idl = module.to_idl();
+        assert!(idl.starts_with(
+            "endian = big\nversion = 3\n\n@open enum Status : u8 {\n    Active = 1,\n"
+        ));
+        assert!(idl.contains("    @composite All = 3,\n"));
+        assert!(idl.contains("\n@id(7) message User {\n"));
+        assert!(idl.contains("\n@deprecated(\"send a User\") @packed_bits struct Header {\n"));
+        assert!(idl.contains("    version u32 : 4,\n"));
+        assert!(idl.contains("    detail u32 if hdr_tag == Status.Inactive,\n"));
+        assert!(idl.contains("    rare u8 : 3 if id_kind != 2,\n    id_kind u8 : 5,\n}\n"));
+        assert!(idl.contains("version = 3\n"));
+        assert!(idl.contains("    id uuid,\n    @removed(2) key bytes[6],\n    hdr Header,\n"));
+        assert!(idl.contains("    @since(2) @deprecated(\"use access\") tags vec<Status, 3>,\n"));
+
+        let reparsed = crate::parser::Parser::new(&idl)
+            .unwrap()
+            .parse_module()
+            .unwrap();
+        assert_eq!(reparsed.to_idl(), idl);
+        for (id, def) in &module.definitions {
+            assert_eq!(reparsed.definitions[id].size(), def.size());
+        }
+    }
+
+    #[test]
+    fn test_to_idl_built_module() {
+        let mut module = OnyxModule {
+            endianness: WireEndianness::Little,
+            ..OnyxModule::default()
+        };
+        let kind = EnumDef::new(
+            "Kind",
+            PrimitiveType::U8,
+            vec![EnumVariant::new("A", None), EnumVariant::new("B", Some(5))],
+        );
+        let fields = vec![
+            Field::new("kind", Type::Custom("Kind".into()), None),
+            Field::new("flag", Type::Primitive(PrimitiveType::Bool), Some(1)),
+        ];
+        module
+            .definitions
+            .insert("Kind".into(), Definition::Enum(kind));
+        let mut event = MessageDef::new("Event", fields);
+        event.packed_bits = true;
+        module
+            .definitions
+            .insert("Event".into(), Definition::Message(event));
+
+        let idl = module.to_idl();
+        assert_eq!(
+            idl,
+            "endian = little\n\
+             \n\
+             @packed_bits message Event {\n    kind Kind,\n    flag bool : 1,\n}\n\
+             \n\
+             enum Kind : u8 {\n    A,\n    B = 5,\n}\n"
+        );
+        assert!(
+            crate::parser::Parser::new(&idl)
+                .unwrap()
+                .parse_module()
+                .is_ok()
+        );
+    }
 }
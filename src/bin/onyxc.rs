@@ -0,0 +1,1085 @@
+//! Command line driver for the Onyx IDL compiler.
+
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+    process::ExitCode,
+    thread,
+};
+
+use onyx::{
+    ColorChoice, Symbol,
+    ast::OnyxModule,
+    build_rules::{self, BuildRulesConfig, BuildSystem},
+    diagnostic::{Diagnostic, Renderer, codes},
+    dynamic,
+    features::{self, Feature},
+    generators::{
+        self, Banner, CodeGenerator, SchemaSource,
+        arrow::ArrowGenerator,
+        cpp::{CppGenerator, PackageConfig},
+        kotlin::KotlinGenerator,
+        matlab::MatlabGenerator,
+        naming::NamingConvention,
+        plugin::PluginGenerator,
+        rust::{CrateConfig, RustGenerator},
+        systemverilog::SystemVerilogGenerator,
+        template::TemplateGenerator,
+    },
+    inspect,
+    limits::{self, Limits},
+    lint::{self, LintConfig, LintLevel, LintRule, Severity},
+    mavlink, obfuscate,
+    parser::{self, Parser},
+    prune,
+};
+
+const USAGE: &str = "\
+Usage: onyxc <command> [options] <file.onyx>
+
+Commands:
+  compile      Generate code for a target language
+  lint         Check a schema against lint rules
+  build-rules  Print Bazel or Buck rules that compile a schema
+  features     Report the IDL features a schema uses and which targets support them
+  obfuscate    Print a schema with opaque names, for sharing with external partners
+  import-mavlink
+               Print the schema of MAVLink XML message definitions, following their
+               includes
+  decode       Print a buffer field by field: offsets, raw bytes, and values
+  encode       Build a buffer from a JSON description of its fields
+
+Common options:
+  --error-format <human|json>  How diagnostics are printed (default: human)
+  --color <auto|always|never>  When human diagnostics are colored; auto colors a terminal
+                               unless NO_COLOR is set (default: auto)
+
+Compile options:
+  --target <cpp|rust|kotlin|systemverilog|matlab|arrow>
+                            Target language, or arrow for the Arrow columns of each message
+                            (required unless --template or --plugin is given)
+  --template <path>         Render a template instead of a target; the output takes the
+                            template's extension
+  --plugin <path>           Run a plugin program instead of a target; its files are written
+                            next to the output path
+  --out <path>              Output path, without extension (default: input path)
+  --crate-name <name>       With --target rust, emit a complete crate in the --out directory
+  --crate-version <version> Version for --crate-name (default: 0.1.0)
+  --crate-license <spdx>    License for --crate-name, such as 'MIT OR Apache-2.0'
+  --package-name <name>     With --target cpp, emit a CMake project with a pkg-config file in
+                            the --out directory
+  --package-version <version>
+                            Version for --package-name (default: 0.1.0)
+  --amalgamate              With --target cpp, emit a single header-only file
+  --kotlin-package <name>   With --target kotlin, the package of the generated file
+                            (default: onyx)
+  --trace                   Emit per-field decode tracing: compiled in by ONYX_TRACE in C++,
+                            a trace_fields method on each view in Rust
+  --shared-memory           Emit helpers that place messages on raw memory: at and from_mmap
+                            in C++, from_raw_parts in Rust
+  --message-arena           Emit MessageArena, a fixed pool of buffers sized for the largest
+                            message
+  --reflection              Emit a table of field descriptors for each struct and message:
+                            k<Name>Fields and visit_fields in C++, FIELDS on each view in Rust
+  --constants               Emit <out>_constants.hpp or .rs: every enum value, message ID, size,
+                            and fingerprint, for code that does not use the generated types
+  --vectors                 Emit <out>.vectors.json: field values and expected wire bytes of
+                            sample messages, and a test that checks them in a crate or package
+  --source-map              Emit a comment before each type and field accessor naming the
+                            schema line that declared it
+  --simd                    With --target cpp, byte-swap vectors of multi-byte numbers in bulk
+                            with SSSE3 or NEON where available
+  --copyright <text>        Open every generated file with a copyright or license notice
+  --stamp                   Name the compiler version, schema file, and schema fingerprint at
+                            the top of every generated file
+  --field-naming <convention>
+                            Name field accessors in preserve, snake, camel, pascal, or
+                            screaming-snake case (default: preserve)
+  --max-message-bytes <n>   Reject messages larger than n bytes
+  --max-nesting-depth <n>   Reject structs and messages nested more than n deep
+  --max-fields <n>          Reject structs and messages with more than n fields
+  --features <a,b,...>      Enable features for @cfg attributes (default: none)
+  --verify-deterministic    Generate twice with differently seeded hash maps and fail, writing
+                            nothing, if the outputs differ
+  --prune                   Generate only the structs and enums an entry point uses
+  --entry-point <name>      With --prune, a definition to keep along with what it uses; may be
+                            repeated (default: every message)
+
+Lint options:
+  -A, --allow <rule>        Disable a rule
+  -W, --warn <rule>         Report a rule as a warning (default for all rules)
+  -D, --deny <rule>         Report a rule as an error
+  --max-message-bytes <n>   Size limit for the oversized-message rule (default: 1024)
+  --entry-point <name>      A definition the dead-definition rule starts from; may be repeated
+                            (default: every message)
+
+Obfuscate options:
+  --mapping <path>          Where to write the opaque-to-original name mapping (required)
+  --out <path>              Where to write the schema (default: stdout)
+
+Import MAVLink options:
+  --out <path>              Where to write the schema (default: stdout)
+
+Decode options:
+  --message <name>          The struct or message the buffer holds (required)
+  --hex <digits>            The buffer as hex digits, which may be separated by whitespace
+  --input <path>            A file holding the buffer; one of --hex and --input is required
+
+Encode options:
+  --message <name>          The struct or message to build (required)
+  --json <document>         The field values as JSON, such as '{\"id\": 5}'
+  --input <path>            A file holding the JSON; one of --json and --input is required
+  --output <path>           Write the buffer to this file instead of printing it as hex
+
+Build rules options:
+  --build-system <bazel|buck>  Build system to emit rules for (default: bazel)
+  --target <cpp|rust|kotlin|systemverilog|matlab|arrow>
+                               Include a target; may be repeated (default: all targets)
+  --onyxc <label>              Label of the onyxc binary (default: @onyx//:onyxc for Bazel,
+                               onyx//:onyxc for Buck)
+";
+
+/// How diagnostics are printed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+enum ErrorFormat {
+    /// Messages with source context, colored as `--color` allows.
+    #[default]
+    Human,
+    /// One JSON object per line, for editors and CI annotation tools.
+    Json,
+}
+
+impl ErrorFormat {
+    fn parse(value: &str) -> Result<Self, String> {
+        match value {
+            "human" => Ok(ErrorFormat::Human),
+            "json" => Ok(ErrorFormat::Json),
+            _ => Err(format!("unknown error format '{value}'")),
+        }
+    }
+}
+
+/// Where the diagnostics of a command go: their format, and whether they are colored.
+#[derive(Debug, Clone, Copy, Default)]
+struct Reporter {
+    format: ErrorFormat,
+    color: ColorChoice,
+}
+
+impl Reporter {
+    /// Prints diagnostics about `file` to stderr.
+    fn emit(self, file: &Path, diagnostics: &[Diagnostic]) {
+        let renderer = Renderer::new().with_color_choice(self.color);
+        for diagnostic in diagnostics {
+            match self.format {
+                ErrorFormat::Human => eprintln!("{}", renderer.render(diagnostic)),
+                ErrorFormat::Json => {
+                    eprintln!("{}", diagnostic.to_json(Some(&file.to_string_lossy())))
+                }
+            }
+        }
+    }
+}
+
+fn main() -> ExitCode {
+    let args = split_option_values(env::args().skip(1));
+    match run(&args) {
+        Ok(code) => code,
+        Err(e) => {
+            eprintln!("{e}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Splits `--option=value` arguments into `--option value`.
+fn split_option_values(args: impl Iterator<Item = String>) -> Vec<String> {
+    let mut split = Vec::new();
+    for arg in args {
+        match arg.split_once('=') {
+            Some((option, value)) if option.starts_with("--") => {
+                split.push(option.to_string());
+                split.push(value.to_string());
+            }
+            _ => split.push(arg),
+        }
+    }
+    split
+}
+
+fn run(args: &[String]) -> Result<ExitCode, String> {
+    let Some((command, rest)) = args.split_first() else {
+        return Err(USAGE.to_string());
+    };
+
+    match command.as_str() {
+        "compile" => compile(rest),
+        "lint" => lint(rest),
+        "build-rules" => build_rules(rest),
+        "features" => features(rest),
+        "obfuscate" => obfuscate(rest),
+        "import-mavlink" => import_mavlink(rest),
+        "decode" => decode(rest),
+        "encode" => encode(rest),
+        "-h" | "--help" | "help" => {
+            print!("{USAGE}");
+            Ok(ExitCode::SUCCESS)
+        }
+        _ => Err(format!("unknown command '{command}'\n\n{USAGE}")),
+    }
+}
+
+/// Parses the value of `--color`.
+fn parse_color(value: &str) -> Result<ColorChoice, String> {
+    ColorChoice::from_name(value).ok_or_else(|| format!("unknown color choice '{value}'"))
+}
+
+/// Returns the value following an option flag.
+fn option_value<'a>(
+    flag: &str,
+    iter: &mut impl Iterator<Item = &'a String>,
+) -> Result<&'a String, String> {
+    iter.next()
+        .ok_or_else(|| format!("missing value for '{flag}'"))
+}
+
+/// Reads a schema file.
+fn read_source(path: &Path) -> Result<String, Vec<Diagnostic>> {
+    fs::read_to_string(path).map_err(|e| {
+        vec![
+            Diagnostic::error(format!("failed to read {}: {e}", path.display()))
+                .with_code(codes::IO)
+                .with_cause(e),
+        ]
+    })
+}
+
+/// Parses and resolves a schema. Every lexical error is reported; otherwise parsing stops at
+/// the first error.
+fn load_module(source: &str) -> Result<OnyxModule, Vec<Diagnostic>> {
+    load_module_with_features(source, &[])
+}
+
+/// Parses and resolves a schema like [`load_module`], with `features` enabled for its `@cfg`
+/// attributes.
+fn load_module_with_features(
+    source: &str,
+    features: &[String],
+) -> Result<OnyxModule, Vec<Diagnostic>> {
+    let errors = parser::lexical_errors(source);
+    if !errors.is_empty() {
+        return Err(errors);
+    }
+    Parser::new(source)
+        .and_then(|parser| parser.with_features(features).parse_module())
+        .map_err(|e| vec![e])
+}
+
+fn compile(args: &[String]) -> Result<ExitCode, String> {
+    let mut input: Option<PathBuf> = None;
+    let mut target: Option<String> = None;
+    let mut template: Option<PathBuf> = None;
+    let mut plugin: Option<PathBuf> = None;
+    let mut out: Option<PathBuf> = None;
+    let mut crate_name: Option<String> = None;
+    let mut crate_version = "0.1.0".to_string();
+    let mut crate_license: Option<String> = None;
+    let mut package_name: Option<String> = None;
+    let mut package_version = "0.1.0".to_string();
+    let mut options = TargetOptions::default();
+    let mut limits = Limits::default();
+    let mut features: Vec<String> = Vec::new();
+    let mut prune_unused = false;
+    let mut stamp = false;
+    let mut verify_deterministic = false;
+    let mut entry_points: Vec<Symbol> = Vec::new();
+    let mut reporter = Reporter::default();
+
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--max-message-bytes" => limits.max_message_bytes = Some(limit(arg, &mut iter)?),
+            "--max-nesting-depth" => limits.max_nesting_depth = Some(limit(arg, &mut iter)?),
+            "--max-fields" => limits.max_fields = Some(limit(arg, &mut iter)?),
+            "--features" => features.extend(
+                option_value(arg, &mut iter)?
+                    .split(',')
+                    .filter(|feature| !feature.is_empty())
+                    .map(str::to_string),
+            ),
+            "--prune" => prune_unused = true,
+            "--entry-point" => entry_points.push(option_value(arg, &mut iter)?.into()),
+            "--target" => target = Some(option_value(arg, &mut iter)?.clone()),
+            "--template" => template = Some(PathBuf::from(option_value(arg, &mut iter)?)),
+            "--plugin" => plugin = Some(PathBuf::from(option_value(arg, &mut iter)?)),
+            "--out" => out = Some(PathBuf::from(option_value(arg, &mut iter)?)),
+            "--crate-name" => crate_name = Some(option_value(arg, &mut iter)?.clone()),
+            "--crate-version" => crate_version = option_value(arg, &mut iter)?.clone(),
+            "--crate-license" => crate_license = Some(option_value(arg, &mut iter)?.clone()),
+            "--package-name" => package_name = Some(option_value(arg, &mut iter)?.clone()),
+            "--package-version" => package_version = option_value(arg, &mut iter)?.clone(),
+            "--amalgamate" => options.amalgamate = true,
+            "--kotlin-package" => {
+                options.kotlin_package = Some(option_value(arg, &mut iter)?.clone())
+            }
+            "--trace" => options.trace = true,
+            "--shared-memory" => options.shared_memory = true,
+            "--message-arena" => options.message_arena = true,
+            "--reflection" => options.reflection = true,
+            "--constants" => options.constants = true,
+            "--vectors" => options.vectors = true,
+            "--source-map" => options.source_map = Some(SchemaSource::default()),
+            "--simd" => options.simd = true,
+            "--copyright" => options.banner.copyright = Some(option_value(arg, &mut iter)?.clone()),
+            "--stamp" => stamp = true,
+            "--verify-deterministic" => verify_deterministic = true,
+            "--field-naming" => {
+                let value = option_value(arg, &mut iter)?;
+                options.field_naming = NamingConvention::from_name(value)
+                    .ok_or_else(|| format!("unknown naming convention '{value}'"))?;
+            }
+            "--error-format" => {
+                reporter.format = ErrorFormat::parse(option_value(arg, &mut iter)?)?
+            }
+            "--color" => reporter.color = parse_color(option_value(arg, &mut iter)?)?,
+            _ if arg.starts_with('-') => return Err(format!("unknown option '{arg}'")),
+            _ => input = Some(PathBuf::from(arg)),
+        }
+    }
+
+    let input = input.ok_or("missing input file")?;
+    let out = out.unwrap_or_else(|| input.with_extension(""));
+    match (target.is_some(), template.is_some(), plugin.is_some()) {
+        (false, false, false) => return Err("missing '--target'".to_string()),
+        (true, false, false) | (false, true, false) | (false, false, true) => {}
+        _ => {
+            return Err(
+                "only one of '--target', '--template', and '--plugin' may be given".to_string(),
+            );
+        }
+    }
+    let target = target.unwrap_or_default();
+    if !entry_points.is_empty() && !prune_unused {
+        return Err("'--entry-point' requires '--prune'".to_string());
+    }
+    if crate_name.is_some() && target != "rust" {
+        return Err("'--crate-name' requires '--target rust'".to_string());
+    }
+    if crate_license.is_some() && crate_name.is_none() {
+        return Err("'--crate-license' requires '--crate-name'".to_string());
+    }
+    if package_name.is_some() && target != "cpp" {
+        return Err("'--package-name' requires '--target cpp'".to_string());
+    }
+    if options.kotlin_package.is_some() && target != "kotlin" {
+        return Err("'--kotlin-package' requires '--target kotlin'".to_string());
+    }
+    if options.field_naming != NamingConvention::default()
+        && !["cpp", "rust", "kotlin"].contains(&target.as_str())
+    {
+        return Err(
+            "'--field-naming' requires '--target cpp', '--target rust', or '--target kotlin'"
+                .to_string(),
+        );
+    }
+    for (option, given) in [
+        ("--amalgamate", options.amalgamate),
+        ("--simd", options.simd),
+    ] {
+        if given && target != "cpp" {
+            return Err(format!("'{option}' requires '--target cpp'"));
+        }
+    }
+    for (option, given) in [
+        ("--trace", options.trace),
+        ("--shared-memory", options.shared_memory),
+        ("--message-arena", options.message_arena),
+        ("--reflection", options.reflection),
+        ("--constants", options.constants),
+        ("--vectors", options.vectors),
+        ("--source-map", options.source_map.is_some()),
+    ] {
+        if given && target != "cpp" && target != "rust" {
+            return Err(format!(
+                "'{option}' requires '--target cpp' or '--target rust'"
+            ));
+        }
+    }
+
+    if stamp {
+        options.banner = options
+            .banner
+            .with_version()
+            .with_source(input.display().to_string())
+            .with_fingerprint();
+    }
+
+    let source = match read_source(&input) {
+        Ok(source) => source,
+        Err(diagnostics) => {
+            reporter.emit(&input, &diagnostics);
+            return Ok(ExitCode::FAILURE);
+        }
+    };
+    if let Some(map) = &mut options.source_map {
+        *map = SchemaSource::new(input.display().to_string(), source.as_str());
+    }
+    // Parses the schema afresh on each call, so each run has its own hash maps.
+    let generate = || -> Result<Vec<(PathBuf, String)>, Vec<Diagnostic>> {
+        let module = load_module_with_features(&source, &features)?;
+        limits::check(&module, &limits).map_err(|errors| {
+            errors
+                .into_iter()
+                .map(|(_, error)| error.with_source(&source))
+                .collect::<Vec<_>>()
+        })?;
+        let module = if prune_unused {
+            prune::prune(&module, &entry_points).map_err(|e| vec![e])?
+        } else {
+            module
+        };
+        let out = out.clone();
+        let generator = match &crate_name {
+            _ if let Some(template) = &template => {
+                template_generator(template, &out, &options.banner)
+            }
+            _ if let Some(plugin) = &plugin => plugin_generator(plugin, out, &options.banner),
+            // The crate is rooted at `out`, with the module named after the schema.
+            Some(name) => {
+                let mut config = CrateConfig::new(name, &crate_version);
+                if let Some(license) = &crate_license {
+                    config = config.with_license(license);
+                }
+                crate_generator(
+                    out.join(input.file_name().unwrap_or_default()),
+                    config,
+                    &options,
+                )
+            }
+            // The project is rooted at `out`, with the files named after the schema.
+            None if let Some(name) = &package_name => package_generator(
+                out.join(input.file_name().unwrap_or_default()),
+                name,
+                &package_version,
+                &options,
+            ),
+            None if options != TargetOptions::default() => {
+                configured_generator(&target, out, &options)
+            }
+            None => generators::generator_for_target(&target, out),
+        };
+        generator
+            .and_then(|mut generator| generator.generate(&module))
+            .map_err(|e| vec![e])
+    };
+    let result = generate().and_then(|files| {
+        if verify_deterministic {
+            // Another thread seeds its hash maps from different random keys.
+            let again = thread::scope(|scope| scope.spawn(generate).join())
+                .map_err(|_| vec![Diagnostic::error("the second generation panicked")])??;
+            generators::check_deterministic(&files, &again).map_err(|e| vec![e])?;
+        }
+        generators::write_outputs(files, Path::new("")).map_err(|e| vec![e])
+    });
+    match result {
+        Ok(_) => Ok(ExitCode::SUCCESS),
+        Err(diagnostics) => {
+            reporter.emit(&input, &diagnostics);
+            Ok(ExitCode::FAILURE)
+        }
+    }
+}
+
+/// Reads the value of the limit option `arg`.
+fn limit<'a>(arg: &str, iter: &mut impl Iterator<Item = &'a String>) -> Result<usize, String> {
+    let value = option_value(arg, iter)?;
+    value
+        .parse()
+        .map_err(|_| format!("invalid limit '{value}' for '{arg}'"))
+}
+
+/// Creates a Rust generator that emits a complete crate.
+fn crate_generator(
+    file_path: PathBuf,
+    config: CrateConfig,
+    options: &TargetOptions,
+) -> Result<Box<dyn CodeGenerator>, Diagnostic> {
+    let mut generator = RustGenerator::default();
+    generator.add_file_path(file_path)?;
+    generator.set_crate(config)?;
+    generator.set_trace(options.trace);
+    generator.set_shared_memory(options.shared_memory);
+    generator.set_message_arena(options.message_arena);
+    generator.set_reflection(options.reflection);
+    generator.set_constants(options.constants);
+    generator.set_vectors(options.vectors);
+    generator.set_field_naming(options.field_naming);
+    if let Some(source) = &options.source_map {
+        generator.set_source_map(source.clone());
+    }
+    generator.set_banner(options.banner.clone());
+    Ok(Box::new(generator))
+}
+
+/// Creates a C++ generator that emits a CMake project.
+fn package_generator(
+    file_path: PathBuf,
+    name: &str,
+    version: &str,
+    options: &TargetOptions,
+) -> Result<Box<dyn CodeGenerator>, Diagnostic> {
+    let mut generator = cpp_generator(file_path, options)?;
+    generator.set_package(PackageConfig::new(name, version))?;
+    Ok(Box::new(generator))
+}
+
+/// Creates a generator that renders the template at `template_path` to `out` plus the
+/// template's extension.
+fn template_generator(
+    template_path: &Path,
+    out: &Path,
+    banner: &Banner,
+) -> Result<Box<dyn CodeGenerator>, Diagnostic> {
+    let template = fs::read_to_string(template_path).map_err(|e| {
+        Diagnostic::error(format!("failed to read {}: {e}", template_path.display()))
+            .with_code(codes::IO)
+            .with_cause(e)
+    })?;
+    let mut file_path = out.as_os_str().to_owned();
+    if let Some(extension) = template_path.extension() {
+        file_path.push(".");
+        file_path.push(extension);
+    }
+    let mut generator = TemplateGenerator::new(&template)?;
+    generator.add_file_path(PathBuf::from(file_path))?;
+    generator.set_banner(banner.clone());
+    Ok(Box::new(generator))
+}
+
+/// Creates a generator that runs the plugin program at `program`.
+fn plugin_generator(
+    program: &Path,
+    out: PathBuf,
+    banner: &Banner,
+) -> Result<Box<dyn CodeGenerator>, Diagnostic> {
+    let mut generator = PluginGenerator::new(program);
+    generator.add_file_path(out)?;
+    generator.set_banner(banner.clone());
+    Ok(Box::new(generator))
+}
+
+/// The code generation options of the targets.
+#[derive(Debug, Default, PartialEq)]
+struct TargetOptions {
+    amalgamate: bool,
+    trace: bool,
+    shared_memory: bool,
+    message_arena: bool,
+    reflection: bool,
+    constants: bool,
+    vectors: bool,
+    simd: bool,
+    source_map: Option<SchemaSource>,
+    banner: Banner,
+    field_naming: NamingConvention,
+    kotlin_package: Option<String>,
+}
+
+/// Creates the generator for `target` with `options` applied.
+fn configured_generator(
+    target: &str,
+    file_path: PathBuf,
+    options: &TargetOptions,
+) -> Result<Box<dyn CodeGenerator>, Diagnostic> {
+    if target == "kotlin" {
+        let mut generator = KotlinGenerator::default();
+        generator.add_file_path(file_path)?;
+        if let Some(package) = &options.kotlin_package {
+            generator.set_package(package)?;
+        }
+        generator.set_field_naming(options.field_naming);
+        generator.set_banner(options.banner.clone());
+        return Ok(Box::new(generator));
+    }
+    if target == "systemverilog" {
+        let mut generator = SystemVerilogGenerator::default();
+        generator.add_file_path(file_path)?;
+        generator.set_banner(options.banner.clone());
+        return Ok(Box::new(generator));
+    }
+    if target == "arrow" {
+        let mut generator = ArrowGenerator::default();
+        generator.add_file_path(file_path)?;
+        return Ok(Box::new(generator));
+    }
+    if target == "matlab" {
+        let mut generator = MatlabGenerator::default();
+        generator.add_file_path(file_path)?;
+        generator.set_banner(options.banner.clone());
+        return Ok(Box::new(generator));
+    }
+    if target == "rust" {
+        let mut generator = RustGenerator::default();
+        generator.add_file_path(file_path)?;
+        generator.set_trace(options.trace);
+        generator.set_shared_memory(options.shared_memory);
+        generator.set_message_arena(options.message_arena);
+        generator.set_reflection(options.reflection);
+        generator.set_constants(options.constants);
+        generator.set_vectors(options.vectors);
+        generator.set_field_naming(options.field_naming);
+        if let Some(source) = &options.source_map {
+            generator.set_source_map(source.clone());
+        }
+        generator.set_banner(options.banner.clone());
+        return Ok(Box::new(generator));
+    }
+    Ok(Box::new(cpp_generator(file_path, options)?))
+}
+
+/// Creates a C++ generator with `options` applied.
+fn cpp_generator(file_path: PathBuf, options: &TargetOptions) -> Result<CppGenerator, Diagnostic> {
+    let mut generator = CppGenerator::default();
+    generator.add_file_path(file_path)?;
+    generator.set_amalgamate(options.amalgamate);
+    generator.set_trace(options.trace);
+    generator.set_shared_memory(options.shared_memory);
+    generator.set_message_arena(options.message_arena);
+    generator.set_reflection(options.reflection);
+    generator.set_constants(options.constants);
+    generator.set_vectors(options.vectors);
+    generator.set_simd(options.simd);
+    generator.set_field_naming(options.field_naming);
+    if let Some(source) = &options.source_map {
+        generator.set_source_map(source.clone());
+    }
+    generator.set_banner(options.banner.clone());
+    Ok(generator)
+}
+
+fn lint(args: &[String]) -> Result<ExitCode, String> {
+    let mut input: Option<PathBuf> = None;
+    let mut config = LintConfig::default();
+    let mut reporter = Reporter::default();
+
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        let level = match arg.as_str() {
+            "-A" | "--allow" => LintLevel::Allow,
+            "-W" | "--warn" => LintLevel::Warn,
+            "-D" | "--deny" => LintLevel::Deny,
+            "--max-message-bytes" => {
+                let value = option_value(arg, &mut iter)?;
+                config.max_message_bytes = value
+                    .parse()
+                    .map_err(|_| format!("invalid byte count '{value}'"))?;
+                continue;
+            }
+            "--entry-point" => {
+                config
+                    .entry_points
+                    .push(option_value(arg, &mut iter)?.into());
+                continue;
+            }
+            "--error-format" => {
+                reporter.format = ErrorFormat::parse(option_value(arg, &mut iter)?)?;
+                continue;
+            }
+            "--color" => {
+                reporter.color = parse_color(option_value(arg, &mut iter)?)?;
+                continue;
+            }
+            _ if arg.starts_with('-') => return Err(format!("unknown option '{arg}'")),
+            _ => {
+                input = Some(PathBuf::from(arg));
+                continue;
+            }
+        };
+        let name = option_value(arg, &mut iter)?;
+        let rule =
+            LintRule::from_name(name).ok_or_else(|| format!("unknown lint rule '{name}'"))?;
+        config.set_level(rule, level);
+    }
+
+    let input = input.ok_or("missing input file")?;
+    let loaded = read_source(&input).and_then(|source| {
+        let module = load_module(&source)?;
+        Ok((source, module))
+    });
+    let (source, module) = match loaded {
+        Ok(loaded) => loaded,
+        Err(diagnostics) => {
+            reporter.emit(&input, &diagnostics);
+            return Ok(ExitCode::FAILURE);
+        }
+    };
+
+    let diagnostics = lint::lint_module(&module, &config);
+    for lint in &diagnostics {
+        let diagnostic = Diagnostic::from(lint.clone()).with_source(&source);
+        match reporter.format {
+            ErrorFormat::Human => eprintln!("{}: {}", input.display(), diagnostic.summary()),
+            ErrorFormat::Json => reporter.emit(&input, &[diagnostic]),
+        }
+    }
+
+    if diagnostics.iter().any(|d| d.severity == Severity::Error) {
+        Ok(ExitCode::FAILURE)
+    } else {
+        Ok(ExitCode::SUCCESS)
+    }
+}
+
+fn obfuscate(args: &[String]) -> Result<ExitCode, String> {
+    let mut input: Option<PathBuf> = None;
+    let mut out: Option<PathBuf> = None;
+    let mut mapping: Option<PathBuf> = None;
+    let mut reporter = Reporter::default();
+
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--out" => out = Some(PathBuf::from(option_value(arg, &mut iter)?)),
+            "--mapping" => mapping = Some(PathBuf::from(option_value(arg, &mut iter)?)),
+            "--error-format" => {
+                reporter.format = ErrorFormat::parse(option_value(arg, &mut iter)?)?
+            }
+            "--color" => reporter.color = parse_color(option_value(arg, &mut iter)?)?,
+            _ if arg.starts_with('-') => return Err(format!("unknown option '{arg}'")),
+            _ => input = Some(PathBuf::from(arg)),
+        }
+    }
+
+    let input = input.ok_or("missing input file")?;
+    // Without the mapping the exported names could never be traced back, so it is required.
+    let mapping = mapping.ok_or("missing '--mapping'")?;
+    let module = match read_source(&input).and_then(|source| load_module(&source)) {
+        Ok(module) => module,
+        Err(diagnostics) => {
+            reporter.emit(&input, &diagnostics);
+            return Ok(ExitCode::FAILURE);
+        }
+    };
+
+    let obfuscated = obfuscate::obfuscate(&module);
+    fs::write(&mapping, obfuscated.mapping_json())
+        .map_err(|e| format!("failed to write {}: {e}", mapping.display()))?;
+    match out {
+        Some(out) => fs::write(&out, obfuscated.schema())
+            .map_err(|e| format!("failed to write {}: {e}", out.display()))?,
+        None => print!("{}", obfuscated.schema()),
+    }
+    Ok(ExitCode::SUCCESS)
+}
+
+fn import_mavlink(args: &[String]) -> Result<ExitCode, String> {
+    let mut input: Option<PathBuf> = None;
+    let mut out: Option<PathBuf> = None;
+    let mut reporter = Reporter::default();
+
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--out" => out = Some(PathBuf::from(option_value(arg, &mut iter)?)),
+            "--error-format" => {
+                reporter.format = ErrorFormat::parse(option_value(arg, &mut iter)?)?
+            }
+            "--color" => reporter.color = parse_color(option_value(arg, &mut iter)?)?,
+            _ if arg.starts_with('-') => return Err(format!("unknown option '{arg}'")),
+            _ => input = Some(PathBuf::from(arg)),
+        }
+    }
+
+    let input = input.ok_or("missing input file")?;
+    let mut documents = Vec::new();
+    if let Err((path, diagnostics)) = read_mavlink(&input, &mut documents) {
+        reporter.emit(&path, &diagnostics);
+        return Ok(ExitCode::FAILURE);
+    }
+    let sources: Vec<&str> = documents
+        .iter()
+        .map(|(_, source)| source.as_str())
+        .collect();
+    let imported = match mavlink::import(&sources) {
+        Ok(imported) => imported,
+        Err((index, diagnostic)) => {
+            let (path, source) = &documents[index];
+            reporter.emit(path, &[diagnostic.with_source(source)]);
+            return Ok(ExitCode::FAILURE);
+        }
+    };
+    for (index, warning) in &imported.warnings {
+        let (path, source) = &documents[*index];
+        reporter.emit(path, &[warning.clone().with_source(source)]);
+    }
+
+    match out {
+        Some(out) => fs::write(&out, imported.schema())
+            .map_err(|e| format!("failed to write {}: {e}", out.display()))?,
+        None => print!("{}", imported.schema()),
+    }
+    Ok(ExitCode::SUCCESS)
+}
+
+/// Reads the MAVLink document at `path` into `documents`, after the documents it includes,
+/// skipping any already read.
+fn read_mavlink(
+    path: &Path,
+    documents: &mut Vec<(PathBuf, String)>,
+) -> Result<(), (PathBuf, Vec<Diagnostic>)> {
+    if documents.iter().any(|(read, _)| read == path) {
+        return Ok(());
+    }
+    let source = read_source(path).map_err(|diagnostics| (path.to_path_buf(), diagnostics))?;
+    let includes = mavlink::includes(&source)
+        .map_err(|diagnostic| (path.to_path_buf(), vec![diagnostic.with_source(&source)]))?;
+    // Claim the path before reading includes, so that a cycle of includes ends.
+    documents.push((path.to_path_buf(), String::new()));
+    let at = documents.len() - 1;
+    for include in includes {
+        read_mavlink(&path.with_file_name(include), documents)?;
+    }
+    let (read, _) = documents.remove(at);
+    documents.push((read, source));
+    Ok(())
+}
+
+fn decode(args: &[String]) -> Result<ExitCode, String> {
+    let mut input: Option<PathBuf> = None;
+    let mut message: Option<String> = None;
+    let mut buffer: Option<Vec<u8>> = None;
+    let mut reporter = Reporter::default();
+
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--message" => message = Some(option_value(arg, &mut iter)?.clone()),
+            "--hex" if buffer.is_none() => buffer = Some(parse_hex(option_value(arg, &mut iter)?)?),
+            "--input" if buffer.is_none() => {
+                let path = option_value(arg, &mut iter)?;
+                buffer = Some(fs::read(path).map_err(|e| format!("failed to read {path}: {e}"))?);
+            }
+            "--hex" | "--input" => {
+                return Err("only one of '--hex' and '--input' may be given".to_string());
+            }
+            "--error-format" => {
+                reporter.format = ErrorFormat::parse(option_value(arg, &mut iter)?)?
+            }
+            "--color" => reporter.color = parse_color(option_value(arg, &mut iter)?)?,
+            _ if arg.starts_with('-') => return Err(format!("unknown option '{arg}'")),
+            _ => input = Some(PathBuf::from(arg)),
+        }
+    }
+
+    let input = input.ok_or("missing input file")?;
+    let message = message.ok_or("missing '--message'")?;
+    let buffer = buffer.ok_or("missing '--hex' or '--input'")?;
+    let result = read_source(&input).and_then(|source| {
+        let module = load_module(&source)?;
+        inspect::inspect(&module, &message, &buffer).map_err(|e| vec![e])
+    });
+    match result {
+        Ok(fields) => {
+            print!("{}", inspect::render(&fields));
+            Ok(ExitCode::SUCCESS)
+        }
+        Err(diagnostics) => {
+            reporter.emit(&input, &diagnostics);
+            Ok(ExitCode::FAILURE)
+        }
+    }
+}
+
+fn encode(args: &[String]) -> Result<ExitCode, String> {
+    let mut input: Option<PathBuf> = None;
+    let mut message: Option<String> = None;
+    let mut document: Option<String> = None;
+    let mut output: Option<PathBuf> = None;
+    let mut reporter = Reporter::default();
+
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--message" => message = Some(option_value(arg, &mut iter)?.clone()),
+            "--json" if document.is_none() => {
+                document = Some(option_value(arg, &mut iter)?.clone())
+            }
+            "--input" if document.is_none() => {
+                let path = option_value(arg, &mut iter)?;
+                document = Some(
+                    fs::read_to_string(path).map_err(|e| format!("failed to read {path}: {e}"))?,
+                );
+            }
+            "--json" | "--input" => {
+                return Err("only one of '--json' and '--input' may be given".to_string());
+            }
+            "--output" => output = Some(PathBuf::from(option_value(arg, &mut iter)?)),
+            "--error-format" => {
+                reporter.format = ErrorFormat::parse(option_value(arg, &mut iter)?)?
+            }
+            "--color" => reporter.color = parse_color(option_value(arg, &mut iter)?)?,
+            _ if arg.starts_with('-') => return Err(format!("unknown option '{arg}'")),
+            _ => input = Some(PathBuf::from(arg)),
+        }
+    }
+
+    let input = input.ok_or("missing input file")?;
+    let message = message.ok_or("missing '--message'")?;
+    let document = document.ok_or("missing '--json' or '--input'")?;
+    let result = read_source(&input).and_then(|source| {
+        let module = load_module(&source)?;
+        dynamic::from_json(&module, &message, &document)
+            .and_then(|value| dynamic::encode(&module, &message, &value))
+            .map_err(|e| vec![e])
+    });
+    let buffer = match result {
+        Ok(buffer) => buffer,
+        Err(diagnostics) => {
+            reporter.emit(&input, &diagnostics);
+            return Ok(ExitCode::FAILURE);
+        }
+    };
+    match output {
+        Some(path) => fs::write(&path, &buffer)
+            .map_err(|e| format!("failed to write {}: {e}", path.display()))?,
+        None => {
+            let hex: Vec<String> = buffer.iter().map(|b| format!("{b:02x}")).collect();
+            println!("{}", hex.join(" "));
+        }
+    }
+    Ok(ExitCode::SUCCESS)
+}
+
+/// Parses hex digits, ignoring whitespace, into bytes.
+fn parse_hex(digits: &str) -> Result<Vec<u8>, String> {
+    let digits: Vec<char> = digits.chars().filter(|c| !c.is_whitespace()).collect();
+    if !digits.len().is_multiple_of(2) {
+        return Err("hex buffer has an odd number of digits".to_string());
+    }
+    digits
+        .chunks(2)
+        .map(|pair| {
+            let pair: String = pair.iter().collect();
+            u8::from_str_radix(&pair, 16).map_err(|_| format!("invalid hex byte '{pair}'"))
+        })
+        .collect()
+}
+
+fn build_rules(args: &[String]) -> Result<ExitCode, String> {
+    let mut input: Option<PathBuf> = None;
+    let mut config = BuildRulesConfig::default();
+    let mut targets = Vec::new();
+    let mut reporter = Reporter::default();
+
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--build-system" => {
+                let name = option_value(arg, &mut iter)?;
+                config.build_system = BuildSystem::from_name(name)
+                    .ok_or_else(|| format!("unknown build system '{name}'"))?;
+            }
+            "--target" => targets.push(option_value(arg, &mut iter)?.clone()),
+            "--onyxc" => config.onyxc = Some(option_value(arg, &mut iter)?.clone()),
+            "--error-format" => {
+                reporter.format = ErrorFormat::parse(option_value(arg, &mut iter)?)?
+            }
+            "--color" => reporter.color = parse_color(option_value(arg, &mut iter)?)?,
+            _ if arg.starts_with('-') => return Err(format!("unknown option '{arg}'")),
+            _ => input = Some(PathBuf::from(arg)),
+        }
+    }
+    if !targets.is_empty() {
+        config.targets = targets;
+    }
+
+    let input = input.ok_or("missing input file")?;
+    let result = read_source(&input).and_then(|source| {
+        let module = load_module(&source)?;
+        build_rules::build_rules(&module, &input, &config).map_err(|e| vec![e])
+    });
+    match result {
+        Ok(rules) => {
+            print!("{rules}");
+            Ok(ExitCode::SUCCESS)
+        }
+        Err(diagnostics) => {
+            reporter.emit(&input, &diagnostics);
+            Ok(ExitCode::FAILURE)
+        }
+    }
+}
+
+fn features(args: &[String]) -> Result<ExitCode, String> {
+    let mut input: Option<PathBuf> = None;
+    let mut reporter = Reporter::default();
+
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--error-format" => {
+                reporter.format = ErrorFormat::parse(option_value(arg, &mut iter)?)?
+            }
+            "--color" => reporter.color = parse_color(option_value(arg, &mut iter)?)?,
+            _ if arg.starts_with('-') => return Err(format!("unknown option '{arg}'")),
+            _ => input = Some(PathBuf::from(arg)),
+        }
+    }
+
+    let input = input.ok_or("missing input file")?;
+    let result = read_source(&input).and_then(|source| {
+        let module = load_module(&source)?;
+        let used = features::used_features(&module);
+        let mut backends = Vec::new();
+        for target in generators::TARGETS {
+            let generator = generators::generator_for_target(target, input.with_extension(""))
+                .map_err(|e| vec![e])?;
+            backends.push((*target, generator));
+        }
+        Ok(feature_matrix(&used, &backends))
+    });
+    match result {
+        Ok(report) => {
+            print!("{report}");
+            Ok(ExitCode::SUCCESS)
+        }
+        Err(diagnostics) => {
+            reporter.emit(&input, &diagnostics);
+            Ok(ExitCode::FAILURE)
+        }
+    }
+}
+
+/// Formats a table with a row per feature and a column per backend.
+fn feature_matrix(used: &[Feature], backends: &[(&str, Box<dyn CodeGenerator>)]) -> String {
+    let width = Feature::ALL
+        .iter()
+        .map(|f| f.name().len())
+        .max()
+        .unwrap_or(0);
+    let mark = |yes: bool| if yes { "yes" } else { "no" };
+
+    let mut report = format!("{:width$}  used", "feature");
+    for (target, _) in backends {
+        report.push_str(&format!("  {target:4}"));
+    }
+    report = report.trim_end().to_string() + "\n";
+    for feature in Feature::ALL {
+        let mut row = format!(
+            "{:width$}  {:4}",
+            feature.name(),
+            mark(used.contains(&feature))
+        );
+        for (target, generator) in backends {
+            let cell = if used.contains(&feature) {
+                mark(generator.supports(feature))
+            } else {
+                "-"
+            };
+            row.push_str(&format!("  {cell:w$}", w = target.len().max(4)));
+        }
+        report.push_str(row.trim_end());
+        report.push('\n');
+    }
+    report
+}
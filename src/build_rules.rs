@@ -0,0 +1,421 @@
+//! Build system integration.
+//!
+//! Emits a `BUILD`/`BUCK` snippet that regenerates a schema's outputs with `onyxc` and wraps
+//! them in a library per target, so a monorepo can depend on the schema like any other
+//! source. The snippet is meant to live in the package that contains the schema.
+
+use std::{
+    fmt::{self, Write},
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    ast::OnyxModule,
+    diagnostic::{Diagnostic, codes},
+    generators::{self, CompileError},
+    json,
+};
+
+/// The build systems rules can be emitted for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum BuildSystem {
+    /// Bazel, using `rules_cc`, `rules_rust`, and `rules_kotlin`.
+    Bazel,
+    /// Buck2, using the prelude rules.
+    Buck,
+}
+
+impl BuildSystem {
+    /// All supported build systems.
+    pub const ALL: [BuildSystem; 2] = [BuildSystem::Bazel, BuildSystem::Buck];
+
+    /// Returns the name used to refer to the build system on the command line.
+    pub fn name(&self) -> &'static str {
+        match self {
+            BuildSystem::Bazel => "bazel",
+            BuildSystem::Buck => "buck",
+        }
+    }
+
+    /// Looks up a build system by name.
+    pub fn from_name(name: &str) -> Option<BuildSystem> {
+        Self::ALL.into_iter().find(|system| system.name() == name)
+    }
+
+    /// Returns the label of the `onyxc` binary used when none is configured.
+    pub fn default_onyxc(&self) -> &'static str {
+        match self {
+            BuildSystem::Bazel => "@onyx//:onyxc",
+            BuildSystem::Buck => "onyx//:onyxc",
+        }
+    }
+}
+
+impl fmt::Display for BuildSystem {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+/// Configuration for [`build_rules`].
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct BuildRulesConfig {
+    /// The build system to emit rules for.
+    pub build_system: BuildSystem,
+    /// The targets to generate code for (see [`generators::TARGETS`]).
+    pub targets: Vec<String>,
+    /// The label of the `onyxc` binary. Defaults to [`BuildSystem::default_onyxc`].
+    pub onyxc: Option<String>,
+}
+
+impl Default for BuildRulesConfig {
+    fn default() -> Self {
+        BuildRulesConfig {
+            build_system: BuildSystem::Bazel,
+            targets: generators::TARGETS.iter().map(|t| t.to_string()).collect(),
+            onyxc: None,
+        }
+    }
+}
+
+/// Returns build rules for `module`, whose source is the file `schema`.
+///
+/// Rules are emitted for:
+/// - the schema itself (a `filegroup` in Bazel, an `export_file` in Buck), named
+///   `<stem>_schema`;
+/// - a `genrule` per target that runs `onyxc compile`, named `<stem>_<target>_gen`;
+/// - a library per target wrapping the generated sources, named `<stem>_<target>`.
+///
+/// The generated file names come from running each generator over `module`, so they always
+/// match what `onyxc` will write.
+pub fn build_rules(
+    module: &OnyxModule,
+    schema: &Path,
+    config: &BuildRulesConfig,
+) -> Result<String, CompileError> {
+    let file_name = schema.file_name().map(|name| name.to_string_lossy());
+    let (Some(file_name), Some(stem)) = (file_name, schema.file_stem()) else {
+        return Err(Diagnostic::error(format!(
+            "schema path '{}' has no file name",
+            schema.display()
+        ))
+        .with_code(codes::CODEGEN));
+    };
+    let stem = stem.to_string_lossy();
+    let prefix = rule_name(&stem);
+
+    let mut rules = Vec::new();
+    for target in &config.targets {
+        let outputs = generators::generator_for_target(target, PathBuf::from(&*file_name))?
+            .generate(module)?
+            .into_iter()
+            .map(|(path, _)| path.to_string_lossy().into_owned())
+            .collect();
+        rules.push(TargetRules {
+            target,
+            outputs,
+            gen_name: format!("{prefix}_{target}_gen"),
+            lib_name: format!("{prefix}_{target}"),
+        });
+    }
+
+    let onyxc = config
+        .onyxc
+        .as_deref()
+        .unwrap_or(config.build_system.default_onyxc());
+    let emitter = Emitter {
+        file_name: &file_name,
+        stem: &stem,
+        schema_name: format!("{prefix}_schema"),
+        onyxc,
+        rules: &rules,
+    };
+    Ok(match config.build_system {
+        BuildSystem::Bazel => emitter.bazel(),
+        BuildSystem::Buck => emitter.buck(),
+    })
+}
+
+/// The rules generated for a single target.
+struct TargetRules<'a> {
+    target: &'a str,
+    /// The generated file names, relative to the package.
+    outputs: Vec<String>,
+    gen_name: String,
+    lib_name: String,
+}
+
+impl TargetRules<'_> {
+    fn headers(&self) -> impl Iterator<Item = &String> {
+        self.outputs.iter().filter(|o| is_header(o))
+    }
+
+    fn sources(&self) -> impl Iterator<Item = &String> {
+        self.outputs.iter().filter(|o| !is_header(o))
+    }
+}
+
+struct Emitter<'a> {
+    file_name: &'a str,
+    stem: &'a str,
+    schema_name: String,
+    onyxc: &'a str,
+    rules: &'a [TargetRules<'a>],
+}
+
+impl Emitter<'_> {
+    fn bazel(&self) -> String {
+        let mut out = String::new();
+        self.header(&mut out);
+        if ["cpp", "rust", "kotlin"].iter().any(|t| self.has_target(t)) {
+            writeln!(out).unwrap();
+        }
+        if self.has_target("cpp") {
+            writeln!(out, "load(\"@rules_cc//cc:defs.bzl\", \"cc_library\")").unwrap();
+        }
+        if self.has_target("rust") {
+            writeln!(
+                out,
+                "load(\"@rules_rust//rust:defs.bzl\", \"rust_library\")"
+            )
+            .unwrap();
+        }
+        if self.has_target("kotlin") {
+            writeln!(
+                out,
+                "load(\"@rules_kotlin//kotlin:jvm.bzl\", \"kt_jvm_library\")"
+            )
+            .unwrap();
+        }
+
+        writeln!(out, "\nfilegroup(").unwrap();
+        writeln!(out, "    name = {},", json::string(&self.schema_name)).unwrap();
+        writeln!(out, "    srcs = [{}],", json::string(self.file_name)).unwrap();
+        writeln!(out, ")").unwrap();
+
+        for rules in self.rules {
+            let cmd = format!(
+                "$(execpath {onyxc}) compile --target {target} --out $(RULEDIR)/{stem} \
+                 $(execpath :{schema})",
+                onyxc = self.onyxc,
+                target = rules.target,
+                stem = self.stem,
+                schema = self.schema_name,
+            );
+            writeln!(out, "\ngenrule(").unwrap();
+            writeln!(out, "    name = {},", json::string(&rules.gen_name)).unwrap();
+            writeln!(out, "    srcs = [\":{}\"],", self.schema_name).unwrap();
+            writeln!(out, "    outs = {},", list(rules.outputs.iter())).unwrap();
+            writeln!(out, "    cmd = {},", json::string(&cmd)).unwrap();
+            writeln!(out, "    tools = [{}],", json::string(self.onyxc)).unwrap();
+            writeln!(out, ")").unwrap();
+
+            match rules.target {
+                "cpp" => {
+                    writeln!(out, "\ncc_library(").unwrap();
+                    writeln!(out, "    name = {},", json::string(&rules.lib_name)).unwrap();
+                    writeln!(out, "    srcs = {},", list(rules.sources())).unwrap();
+                    writeln!(out, "    hdrs = {},", list(rules.headers())).unwrap();
+                    writeln!(out, ")").unwrap();
+                }
+                "rust" => {
+                    writeln!(out, "\nrust_library(").unwrap();
+                    writeln!(out, "    name = {},", json::string(&rules.lib_name)).unwrap();
+                    writeln!(out, "    srcs = {},", list(rules.outputs.iter())).unwrap();
+                    writeln!(out, ")").unwrap();
+                }
+                "kotlin" => {
+                    writeln!(out, "\nkt_jvm_library(").unwrap();
+                    writeln!(out, "    name = {},", json::string(&rules.lib_name)).unwrap();
+                    writeln!(out, "    srcs = {},", list(rules.outputs.iter())).unwrap();
+                    writeln!(out, ")").unwrap();
+                }
+                _ => {}
+            }
+        }
+        out
+    }
+
+    fn buck(&self) -> String {
+        let mut out = String::new();
+        self.header(&mut out);
+
+        writeln!(out, "\nexport_file(").unwrap();
+        writeln!(out, "    name = {},", json::string(&self.schema_name)).unwrap();
+        writeln!(out, "    src = {},", json::string(self.file_name)).unwrap();
+        writeln!(out, ")").unwrap();
+
+        for rules in self.rules {
+            let cmd = format!(
+                "$(exe {onyxc}) compile --target {target} --out $OUT/{stem} \
+                 $(location :{schema})",
+                onyxc = self.onyxc,
+                target = rules.target,
+                stem = self.stem,
+                schema = self.schema_name,
+            );
+            writeln!(out, "\ngenrule(").unwrap();
+            writeln!(out, "    name = {},", json::string(&rules.gen_name)).unwrap();
+            writeln!(out, "    outs = {{").unwrap();
+            for output in &rules.outputs {
+                let name = json::string(output);
+                writeln!(out, "        {name}: [{name}],").unwrap();
+            }
+            writeln!(out, "    }},").unwrap();
+            writeln!(out, "    cmd = {},", json::string(&cmd)).unwrap();
+            writeln!(out, ")").unwrap();
+
+            let output_label = |output: &String| format!(":{}[{output}]", rules.gen_name);
+            match rules.target {
+                "cpp" => {
+                    writeln!(out, "\ncxx_library(").unwrap();
+                    writeln!(out, "    name = {},", json::string(&rules.lib_name)).unwrap();
+                    writeln!(
+                        out,
+                        "    srcs = {},",
+                        list(rules.sources().map(output_label))
+                    )
+                    .unwrap();
+                    writeln!(out, "    exported_headers = {{").unwrap();
+                    for header in rules.headers() {
+                        let label = json::string(&output_label(header));
+                        writeln!(out, "        {}: {label},", json::string(header)).unwrap();
+                    }
+                    writeln!(out, "    }},").unwrap();
+                    writeln!(out, ")").unwrap();
+                }
+                "rust" => {
+                    writeln!(out, "\nrust_library(").unwrap();
+                    writeln!(out, "    name = {},", json::string(&rules.lib_name)).unwrap();
+                    writeln!(out, "    mapped_srcs = {{").unwrap();
+                    for output in &rules.outputs {
+                        let label = json::string(&output_label(output));
+                        writeln!(out, "        {label}: {},", json::string(output)).unwrap();
+                    }
+                    writeln!(out, "    }},").unwrap();
+                    if let Some(root) = rules.outputs.first() {
+                        writeln!(out, "    crate_root = {},", json::string(root)).unwrap();
+                    }
+                    writeln!(out, ")").unwrap();
+                }
+                "kotlin" => {
+                    writeln!(out, "\nkotlin_library(").unwrap();
+                    writeln!(out, "    name = {},", json::string(&rules.lib_name)).unwrap();
+                    writeln!(
+                        out,
+                        "    srcs = {},",
+                        list(rules.outputs.iter().map(output_label))
+                    )
+                    .unwrap();
+                    writeln!(out, ")").unwrap();
+                }
+                _ => {}
+            }
+        }
+        out
+    }
+
+    fn header(&self, out: &mut String) {
+        writeln!(
+            out,
+            "# Generated by `onyxc build-rules` for {}. Place next to the schema.",
+            self.file_name
+        )
+        .unwrap();
+    }
+
+    fn has_target(&self, target: &str) -> bool {
+        self.rules.iter().any(|r| r.target == target)
+    }
+}
+
+/// Returns a Starlark list of string literals.
+fn list<S: AsRef<str>>(items: impl Iterator<Item = S>) -> String {
+    let items: Vec<String> = items.map(|item| json::string(item.as_ref())).collect();
+    format!("[{}]", items.join(", "))
+}
+
+fn is_header(path: &str) -> bool {
+    Path::new(path)
+        .extension()
+        .is_some_and(|ext| ext == "h" || ext == "hpp")
+}
+
+/// Turns a file stem into a valid rule (and Rust crate) name.
+fn rule_name(stem: &str) -> String {
+    stem.chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::Parser;
+
+    fn module() -> OnyxModule {
+        Parser::new("struct Point { x i32, y i32, }")
+            .unwrap()
+            .parse_module()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_bazel_rules() {
+        let rules = build_rules(
+            &module(),
+            Path::new("schemas/point-v2.onyx"),
+            &BuildRulesConfig::default(),
+        )
+        .unwrap();
+
+        assert!(rules.contains("load(\"@rules_cc//cc:defs.bzl\", \"cc_library\")"));
+        assert!(rules.contains("    name = \"point_v2_schema\",\n    srcs = [\"point-v2.onyx\"],"));
+        assert!(rules.contains(
+            "    outs = [\"point-v2.hpp\", \"point-v2.cpp\"],\n    cmd = \"$(execpath @onyx//:onyxc) \
+             compile --target cpp --out $(RULEDIR)/point-v2 $(execpath :point_v2_schema)\","
+        ));
+        assert!(rules.contains(
+            "cc_library(\n    name = \"point_v2_cpp\",\n    srcs = [\"point-v2.cpp\"],\n    \
+             hdrs = [\"point-v2.hpp\"],\n)"
+        ));
+        assert!(rules.contains(
+            "rust_library(\n    name = \"point_v2_rust\",\n    srcs = [\"point-v2.rs\"],\n)"
+        ));
+        assert!(rules.contains(
+            "kt_jvm_library(\n    name = \"point_v2_kotlin\",\n    srcs = [\"point-v2.kt\"],\n)"
+        ));
+    }
+
+    #[test]
+    fn test_buck_rules() {
+        let config = BuildRulesConfig {
+            build_system: BuildSystem::Buck,
+            targets: vec!["rust".to_string()],
+            onyxc: Some("//tools:onyxc".to_string()),
+        };
+        let rules = build_rules(&module(), Path::new("point.onyx"), &config).unwrap();
+
+        assert!(!rules.contains("cxx_library"));
+        assert!(rules.contains(
+            "    cmd = \"$(exe //tools:onyxc) compile --target rust --out $OUT/point \
+             $(location :point_schema)\","
+        ));
+        assert!(rules.contains(
+            "    mapped_srcs = {\n        \":point_rust_gen[point.rs]\": \"point.rs\",\n    },\n    \
+             crate_root = \"point.rs\","
+        ));
+    }
+
+    #[test]
+    fn test_unknown_target() {
+        let config = BuildRulesConfig {
+            targets: vec!["go".to_string()],
+            ..BuildRulesConfig::default()
+        };
+        let err = build_rules(&module(), Path::new("point.onyx"), &config).unwrap_err();
+        assert!(err.message().contains("unknown target 'go'"));
+    }
+}
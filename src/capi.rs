@@ -0,0 +1,615 @@
+//! C API for driving the compiler in-process.
+//!
+//! Enabled with the `capi` feature. The matching header is `include/onyx.h`, generated with
+//! `cbindgen --config cbindgen.toml --output include/onyx.h`. Link against the `staticlib` or
+//! `cdylib` build of this crate.
+//!
+//! [`onyx_compile`] parses and generates in one call. To generate several targets from one
+//! parse, or to inspect failures, parse with [`onyx_parse`] and pass the schema to
+//! [`onyx_generate`]: both report failures as an [`OnyxDiagnostic`] whose message, code,
+//! severity, and location can be read with the `onyx_diagnostic_*` accessors.
+
+use std::{
+    ffi::{CStr, CString, c_char, c_void},
+    path::PathBuf,
+    ptr,
+};
+
+use crate::{
+    ast,
+    diagnostic::{Diagnostic, Severity, codes},
+    generators,
+    parser::Parser,
+};
+
+/// Result codes returned by the C API.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OnyxStatus {
+    /// Compilation succeeded and every output was passed to the callback.
+    Ok = 0,
+    /// An argument was null or not valid UTF-8.
+    InvalidArgument = 1,
+    /// The schema failed to parse, resolve, or generate.
+    CompileError = 2,
+}
+
+/// How serious a diagnostic is.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OnyxSeverity {
+    /// Reported but does not fail compilation.
+    Warning = 0,
+    /// Fails compilation.
+    Error = 1,
+}
+
+/// A parsed schema, created by [`onyx_parse`] and released with [`onyx_schema_free`].
+pub struct OnyxSchema {
+    module: ast::OnyxModule,
+    /// The schema text, which diagnostics from [`onyx_generate`] are located in.
+    source: String,
+}
+
+/// A diagnostic reported by [`onyx_parse`] or [`onyx_generate`], released with
+/// [`onyx_diagnostic_free`].
+pub struct OnyxDiagnostic {
+    diagnostic: Diagnostic,
+    message: CString,
+    code: Option<CString>,
+}
+
+impl OnyxDiagnostic {
+    fn new(diagnostic: Diagnostic, source: &str) -> Self {
+        let diagnostic = diagnostic.with_source(source);
+        OnyxDiagnostic {
+            message: c_string(diagnostic.message()),
+            code: diagnostic.code().map(c_string),
+            diagnostic,
+        }
+    }
+}
+
+/// Receives each generated file. `path` and `contents` are NUL-terminated UTF-8 strings that
+/// are only valid for the duration of the call.
+pub type OnyxOutputCallback =
+    Option<extern "C" fn(path: *const c_char, contents: *const c_char, user_data: *mut c_void)>;
+
+/// Compiles `source` for `target`, one of `"cpp"`, `"rust"`, `"kotlin"`, `"systemverilog"`,
+/// `"matlab"`, and `"arrow"`, and passes every output to `out`.
+///
+/// `name` is the path of the schema, used to name the outputs; nothing is written to disk.
+/// `user_data` is passed through to `out` unchanged. On failure, if `error` is non-null it
+/// receives a message that must be released with [`onyx_string_free`].
+///
+/// # Safety
+///
+/// `source`, `target`, and `name` must be valid NUL-terminated strings, and `error` must be
+/// null or point to writable storage for a pointer.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn onyx_compile(
+    source: *const c_char,
+    target: *const c_char,
+    name: *const c_char,
+    out: OnyxOutputCallback,
+    user_data: *mut c_void,
+    error: *mut *mut c_char,
+) -> OnyxStatus {
+    let set_error = |message: &str| {
+        if !error.is_null() {
+            // SAFETY: the caller guarantees a non-null `error` is writable.
+            unsafe { *error = c_string(message).into_raw() };
+        }
+    };
+
+    // SAFETY: the caller guarantees the strings are valid and NUL-terminated.
+    let (Some(source), Some(target), Some(name), Some(out)) = (
+        unsafe { to_str(source) },
+        unsafe { to_str(target) },
+        unsafe { to_str(name) },
+        out,
+    ) else {
+        set_error("arguments must be non-null UTF-8 strings and a callback");
+        return OnyxStatus::InvalidArgument;
+    };
+
+    let files = match Parser::new(source)
+        .and_then(|p| p.parse_module())
+        .and_then(|module| generate_files(&module, target, name))
+    {
+        Ok(files) => files,
+        Err(diagnostic) => {
+            set_error(&diagnostic.summary());
+            return OnyxStatus::CompileError;
+        }
+    };
+
+    emit(files, out, user_data);
+    if !error.is_null() {
+        // SAFETY: the caller guarantees a non-null `error` is writable.
+        unsafe { *error = ptr::null_mut() };
+    }
+    OnyxStatus::Ok
+}
+
+/// Parses `source` and stores the schema in `*schema`, to be released with
+/// [`onyx_schema_free`].
+///
+/// On failure `*schema` is set to null and, if `diagnostic` is non-null, `*diagnostic` receives
+/// the error, to be released with [`onyx_diagnostic_free`]. On success `*diagnostic` is set to
+/// null.
+///
+/// # Safety
+///
+/// `source` must be a valid NUL-terminated string, `schema` must point to writable storage for
+/// a pointer, and `diagnostic` must be null or point to writable storage for a pointer.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn onyx_parse(
+    source: *const c_char,
+    schema: *mut *mut OnyxSchema,
+    diagnostic: *mut *mut OnyxDiagnostic,
+) -> OnyxStatus {
+    // SAFETY: the caller guarantees a non-null `diagnostic` is writable.
+    unsafe { set_diagnostic(diagnostic, None) };
+    if schema.is_null() {
+        return OnyxStatus::InvalidArgument;
+    }
+    // SAFETY: the caller guarantees `schema` is writable.
+    unsafe { *schema = ptr::null_mut() };
+    // SAFETY: the caller guarantees `source` is valid and NUL-terminated.
+    let Some(source) = (unsafe { to_str(source) }) else {
+        return OnyxStatus::InvalidArgument;
+    };
+    match Parser::new(source).and_then(|p| p.parse_module()) {
+        Ok(parsed) => {
+            let parsed = Box::new(OnyxSchema {
+                module: parsed,
+                source: source.to_string(),
+            });
+            // SAFETY: the caller guarantees `schema` is writable.
+            unsafe { *schema = Box::into_raw(parsed) };
+            OnyxStatus::Ok
+        }
+        Err(error) => {
+            let error = OnyxDiagnostic::new(error, source);
+            // SAFETY: the caller guarantees a non-null `diagnostic` is writable.
+            unsafe { set_diagnostic(diagnostic, Some(error)) };
+            OnyxStatus::CompileError
+        }
+    }
+}
+
+/// Generates code for `schema` with `target`, such as `"cpp"`, and passes every output to
+/// `out`, as [`onyx_compile`] does.
+///
+/// On failure, if `diagnostic` is non-null, `*diagnostic` receives the error, to be released
+/// with [`onyx_diagnostic_free`]. On success `*diagnostic` is set to null. The schema is not
+/// consumed and can be generated again.
+///
+/// # Safety
+///
+/// `schema` must be a schema returned by [`onyx_parse`] that has not been freed yet, `target`
+/// and `name` must be valid NUL-terminated strings, and `diagnostic` must be null or point to
+/// writable storage for a pointer.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn onyx_generate(
+    schema: *const OnyxSchema,
+    target: *const c_char,
+    name: *const c_char,
+    out: OnyxOutputCallback,
+    user_data: *mut c_void,
+    diagnostic: *mut *mut OnyxDiagnostic,
+) -> OnyxStatus {
+    // SAFETY: the caller guarantees a non-null `diagnostic` is writable.
+    unsafe { set_diagnostic(diagnostic, None) };
+    // SAFETY: the caller guarantees the schema is live and the strings are valid.
+    let (Some(schema), Some(target), Some(name), Some(out)) = (
+        unsafe { schema.as_ref() },
+        unsafe { to_str(target) },
+        unsafe { to_str(name) },
+        out,
+    ) else {
+        return OnyxStatus::InvalidArgument;
+    };
+    match generate_files(&schema.module, target, name) {
+        Ok(files) => {
+            emit(files, out, user_data);
+            OnyxStatus::Ok
+        }
+        Err(error) => {
+            let error = OnyxDiagnostic::new(error, &schema.source);
+            // SAFETY: the caller guarantees a non-null `diagnostic` is writable.
+            unsafe { set_diagnostic(diagnostic, Some(error)) };
+            OnyxStatus::CompileError
+        }
+    }
+}
+
+/// Releases a schema returned by [`onyx_parse`]. Passing null is a no-op.
+///
+/// # Safety
+///
+/// `schema` must be null or a schema returned by [`onyx_parse`] that has not been freed yet.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn onyx_schema_free(schema: *mut OnyxSchema) {
+    if !schema.is_null() {
+        // SAFETY: the caller guarantees `schema` came from `Box::into_raw`.
+        drop(unsafe { Box::from_raw(schema) });
+    }
+}
+
+/// Returns the primary message of `diagnostic`, valid until the diagnostic is freed.
+///
+/// # Safety
+///
+/// `diagnostic` must be a diagnostic returned by this library that has not been freed yet.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn onyx_diagnostic_message(
+    diagnostic: *const OnyxDiagnostic,
+) -> *const c_char {
+    // SAFETY: the caller guarantees `diagnostic` is live.
+    unsafe { &*diagnostic }.message.as_ptr()
+}
+
+/// Returns the stable error code of `diagnostic`, such as `"E0001"`, or null if it has none. The
+/// code is valid until the diagnostic is freed.
+///
+/// # Safety
+///
+/// `diagnostic` must be a diagnostic returned by this library that has not been freed yet.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn onyx_diagnostic_code(diagnostic: *const OnyxDiagnostic) -> *const c_char {
+    // SAFETY: the caller guarantees `diagnostic` is live.
+    match &unsafe { &*diagnostic }.code {
+        Some(code) => code.as_ptr(),
+        None => ptr::null(),
+    }
+}
+
+/// Returns how serious `diagnostic` is.
+///
+/// # Safety
+///
+/// `diagnostic` must be a diagnostic returned by this library that has not been freed yet.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn onyx_diagnostic_severity(
+    diagnostic: *const OnyxDiagnostic,
+) -> OnyxSeverity {
+    // SAFETY: the caller guarantees `diagnostic` is live.
+    match unsafe { &*diagnostic }.diagnostic.severity() {
+        Severity::Warning => OnyxSeverity::Warning,
+        Severity::Error => OnyxSeverity::Error,
+    }
+}
+
+/// Stores the 0-indexed line and column where `diagnostic` was found in `*line` and `*column`,
+/// and returns true. Returns false, leaving both unchanged, if the diagnostic has no location.
+///
+/// # Safety
+///
+/// `diagnostic` must be a diagnostic returned by this library that has not been freed yet, and
+/// `line` and `column` must point to writable storage.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn onyx_diagnostic_position(
+    diagnostic: *const OnyxDiagnostic,
+    line: *mut usize,
+    column: *mut usize,
+) -> bool {
+    // SAFETY: the caller guarantees `diagnostic` is live.
+    let Some(position) = unsafe { &*diagnostic }.diagnostic.position() else {
+        return false;
+    };
+    // SAFETY: the caller guarantees `line` and `column` are writable.
+    unsafe {
+        *line = position.line();
+        *column = position.column();
+    }
+    true
+}
+
+/// Stores the byte range of the schema where `diagnostic` was found in `*start` and `*end`, and
+/// returns true. Returns false, leaving both unchanged, if the diagnostic has no location.
+///
+/// # Safety
+///
+/// `diagnostic` must be a diagnostic returned by this library that has not been freed yet, and
+/// `start` and `end` must point to writable storage.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn onyx_diagnostic_span(
+    diagnostic: *const OnyxDiagnostic,
+    start: *mut usize,
+    end: *mut usize,
+) -> bool {
+    // SAFETY: the caller guarantees `diagnostic` is live.
+    let Some(span) = unsafe { &*diagnostic }.diagnostic.span() else {
+        return false;
+    };
+    // SAFETY: the caller guarantees `start` and `end` are writable.
+    unsafe {
+        *start = span.start();
+        *end = span.end();
+    }
+    true
+}
+
+/// Returns `diagnostic` rendered as uncolored text, with an excerpt of the schema, notes, and
+/// help. The string must be released with [`onyx_string_free`].
+///
+/// # Safety
+///
+/// `diagnostic` must be a diagnostic returned by this library that has not been freed yet.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn onyx_diagnostic_render(diagnostic: *const OnyxDiagnostic) -> *mut c_char {
+    // SAFETY: the caller guarantees `diagnostic` is live.
+    c_string(&unsafe { &*diagnostic }.diagnostic.to_string()).into_raw()
+}
+
+/// Releases a diagnostic returned by this library. Passing null is a no-op.
+///
+/// # Safety
+///
+/// `diagnostic` must be null or a diagnostic returned by this library that has not been freed
+/// yet.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn onyx_diagnostic_free(diagnostic: *mut OnyxDiagnostic) {
+    if !diagnostic.is_null() {
+        // SAFETY: the caller guarantees `diagnostic` came from `Box::into_raw`.
+        drop(unsafe { Box::from_raw(diagnostic) });
+    }
+}
+
+/// Releases a string returned by the C API. Passing null is a no-op.
+///
+/// # Safety
+///
+/// `s` must be null or a pointer returned by this library that has not been freed yet.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn onyx_string_free(s: *mut c_char) {
+    if !s.is_null() {
+        // SAFETY: the caller guarantees `s` came from `CString::into_raw`.
+        drop(unsafe { CString::from_raw(s) });
+    }
+}
+
+/// Converts a C string to `&str`, returning `None` for null or invalid UTF-8.
+unsafe fn to_str<'a>(s: *const c_char) -> Option<&'a str> {
+    if s.is_null() {
+        return None;
+    }
+    // SAFETY: the caller guarantees `s` is a valid NUL-terminated string.
+    unsafe { CStr::from_ptr(s) }.to_str().ok()
+}
+
+/// Converts `s` to a C string, replacing interior NULs with spaces.
+fn c_string(s: &str) -> CString {
+    CString::new(s.replace('\0', " ")).unwrap_or_default()
+}
+
+/// Stores `value` in `*diagnostic` if `diagnostic` is non-null.
+///
+/// # Safety
+///
+/// `diagnostic` must be null or point to writable storage for a pointer.
+unsafe fn set_diagnostic(diagnostic: *mut *mut OnyxDiagnostic, value: Option<OnyxDiagnostic>) {
+    if !diagnostic.is_null() {
+        let value = value.map_or(ptr::null_mut(), |value| Box::into_raw(Box::new(value)));
+        // SAFETY: the caller guarantees a non-null `diagnostic` is writable.
+        unsafe { *diagnostic = value };
+    }
+}
+
+/// Passes each of `files` to `out`.
+fn emit(
+    files: Vec<(CString, CString)>,
+    out: extern "C" fn(*const c_char, *const c_char, *mut c_void),
+    user_data: *mut c_void,
+) {
+    for (path, contents) in files {
+        out(path.as_ptr(), contents.as_ptr(), user_data);
+    }
+}
+
+fn generate_files(
+    module: &ast::OnyxModule,
+    target: &str,
+    name: &str,
+) -> Result<Vec<(CString, CString)>, Diagnostic> {
+    let files = generators::generator_for_target(target, PathBuf::from(name))?.generate(module)?;
+    c_files(files)
+}
+
+/// Converts generated files to C strings, failing on a file whose path or contents hold a NUL,
+/// which would cut the string short.
+fn c_files(files: Vec<(PathBuf, String)>) -> Result<Vec<(CString, CString)>, Diagnostic> {
+    files
+        .into_iter()
+        .map(|(path, contents)| {
+            let path = path.to_string_lossy().into_owned();
+            let nul = || {
+                Diagnostic::error(format!("generated file '{path}' contains a NUL byte"))
+                    .with_code(codes::CODEGEN)
+                    .with_note("the C API passes files as NUL-terminated strings")
+            };
+            let contents = CString::new(contents).map_err(|_| nul())?;
+            Ok((CString::new(path.as_str()).map_err(|_| nul())?, contents))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    extern "C" fn collect(path: *const c_char, contents: *const c_char, user_data: *mut c_void) {
+        let files = unsafe { &mut *(user_data as *mut Vec<(String, String)>) };
+        let path = unsafe { CStr::from_ptr(path) }
+            .to_str()
+            .unwrap()
+            .to_string();
+        let contents = unsafe { CStr::from_ptr(contents) }
+            .to_str()
+            .unwrap()
+            .to_string();
+        files.push((path, contents));
+    }
+
+    fn compile(
+        source: &CStr,
+        target: &CStr,
+    ) -> (OnyxStatus, Vec<(String, String)>, Option<String>) {
+        let mut files: Vec<(String, String)> = Vec::new();
+        let mut error: *mut c_char = ptr::null_mut();
+        let status = unsafe {
+            onyx_compile(
+                source.as_ptr(),
+                target.as_ptr(),
+                c"schema.onyx".as_ptr(),
+                Some(collect),
+                &mut files as *mut _ as *mut c_void,
+                &mut error,
+            )
+        };
+        let message = (!error.is_null()).then(|| {
+            let message = unsafe { CStr::from_ptr(error) }
+                .to_str()
+                .unwrap()
+                .to_string();
+            unsafe { onyx_string_free(error) };
+            message
+        });
+        (status, files, message)
+    }
+
+    #[test]
+    fn test_compile_calls_back_for_each_file() {
+        let (status, files, error) = compile(c"struct S { f u8, }", c"cpp");
+        assert_eq!(status, OnyxStatus::Ok);
+        assert_eq!(error, None);
+        let paths: Vec<&str> = files.iter().map(|(path, _)| path.as_str()).collect();
+        assert_eq!(paths, ["schema.hpp", "schema.cpp"]);
+        assert!(files[0].1.contains("class S"));
+    }
+
+    #[test]
+    fn test_compile_reports_errors() {
+        let (status, files, error) = compile(c"message A { hdr B, }", c"rust");
+        assert_eq!(status, OnyxStatus::CompileError);
+        assert!(files.is_empty());
+        assert!(error.unwrap().contains("custom type 'B' not defined"));
+
+        let (status, _, error) = compile(c"struct S { f u8, }", c"java");
+        assert_eq!(status, OnyxStatus::CompileError);
+        assert!(error.unwrap().contains("unknown target 'java'"));
+
+        let error = c_files(vec![("a.rs".into(), "a\0b".to_string())]).unwrap_err();
+        assert_eq!(error.message(), "generated file 'a.rs' contains a NUL byte");
+        assert_eq!(error.code(), Some(codes::CODEGEN));
+    }
+
+    #[test]
+    fn test_null_arguments() {
+        let status = unsafe {
+            onyx_compile(
+                ptr::null(),
+                c"cpp".as_ptr(),
+                c"schema.onyx".as_ptr(),
+                Some(collect),
+                ptr::null_mut(),
+                ptr::null_mut(),
+            )
+        };
+        assert_eq!(status, OnyxStatus::InvalidArgument);
+    }
+
+    #[test]
+    fn test_parse_then_generate() {
+        let mut module = ptr::null_mut();
+        let mut diagnostic = ptr::null_mut();
+        let status =
+            unsafe { onyx_parse(c"struct S { f u8, }".as_ptr(), &mut module, &mut diagnostic) };
+        assert_eq!(status, OnyxStatus::Ok);
+        assert!(diagnostic.is_null());
+
+        // One parse serves several targets.
+        for (target, expected) in [(c"cpp", 2), (c"rust", 1)] {
+            let mut files: Vec<(String, String)> = Vec::new();
+            let status = unsafe {
+                onyx_generate(
+                    module,
+                    target.as_ptr(),
+                    c"schema.onyx".as_ptr(),
+                    Some(collect),
+                    &mut files as *mut _ as *mut c_void,
+                    &mut diagnostic,
+                )
+            };
+            assert_eq!(status, OnyxStatus::Ok);
+            assert!(diagnostic.is_null());
+            assert_eq!(files.len(), expected, "{target:?}");
+        }
+
+        let status = unsafe {
+            onyx_generate(
+                module,
+                c"java".as_ptr(),
+                c"schema.onyx".as_ptr(),
+                Some(collect),
+                ptr::null_mut(),
+                &mut diagnostic,
+            )
+        };
+        assert_eq!(status, OnyxStatus::CompileError);
+        let message = unsafe { CStr::from_ptr(onyx_diagnostic_message(diagnostic)) };
+        assert!(message.to_str().unwrap().contains("unknown target 'java'"));
+        unsafe {
+            onyx_diagnostic_free(diagnostic);
+            onyx_schema_free(module);
+        }
+    }
+
+    #[test]
+    fn test_diagnostic_accessors() {
+        let mut module = ptr::null_mut();
+        let mut diagnostic = ptr::null_mut();
+        let status = unsafe {
+            onyx_parse(
+                c"message A {\n  hdr B,\n}".as_ptr(),
+                &mut module,
+                &mut diagnostic,
+            )
+        };
+        assert_eq!(status, OnyxStatus::CompileError);
+        assert!(module.is_null());
+
+        let message = unsafe { CStr::from_ptr(onyx_diagnostic_message(diagnostic)) };
+        assert_eq!(message.to_str().unwrap(), "custom type 'B' not defined");
+        let code = unsafe { CStr::from_ptr(onyx_diagnostic_code(diagnostic)) };
+        assert!(code.to_str().unwrap().starts_with('E'));
+        assert_eq!(
+            unsafe { onyx_diagnostic_severity(diagnostic) },
+            OnyxSeverity::Error
+        );
+
+        let (mut line, mut column, mut start, mut end) = (0, 0, 0, 0);
+        assert!(unsafe { onyx_diagnostic_position(diagnostic, &mut line, &mut column) });
+        assert_eq!((line, column), (1, 2));
+        assert!(unsafe { onyx_diagnostic_span(diagnostic, &mut start, &mut end) });
+        assert_eq!(start, 14);
+        assert!(end > start);
+
+        let rendered = unsafe { onyx_diagnostic_render(diagnostic) };
+        assert!(
+            unsafe { CStr::from_ptr(rendered) }
+                .to_str()
+                .unwrap()
+                .contains("hdr B")
+        );
+        unsafe {
+            onyx_string_free(rendered);
+            onyx_diagnostic_free(diagnostic);
+        }
+
+        let status = unsafe { onyx_parse(ptr::null(), &mut module, ptr::null_mut()) };
+        assert_eq!(status, OnyxStatus::InvalidArgument);
+    }
+}
@@ -1,2 +1,66 @@
-pub const END: &str = "\x1b[0m";
-pub const RED: &str = "\x1b[1;91;49m";
+//! ANSI colors for human-readable output, and when to use them.
+
+use std::env;
+
+pub(crate) const END: &str = "\x1b[0m";
+pub(crate) const RED: &str = "\x1b[1;91;49m";
+
+/// When output is colored with ANSI escape codes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[non_exhaustive]
+pub enum ColorChoice {
+    /// Color output to a terminal, unless the `NO_COLOR` environment variable is set.
+    #[default]
+    Auto,
+    /// Always color, even when output is piped to a file or CI log.
+    Always,
+    /// Never color.
+    Never,
+}
+
+impl ColorChoice {
+    /// All choices, in declaration order.
+    pub const ALL: [ColorChoice; 3] = [ColorChoice::Auto, ColorChoice::Always, ColorChoice::Never];
+
+    /// Returns the name of the choice, as accepted by `onyxc --color`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            ColorChoice::Auto => "auto",
+            ColorChoice::Always => "always",
+            ColorChoice::Never => "never",
+        }
+    }
+
+    /// Looks up a choice by name.
+    pub fn from_name(name: &str) -> Option<ColorChoice> {
+        Self::ALL.into_iter().find(|choice| choice.name() == name)
+    }
+
+    /// Returns true if output to a stream should be colored, given whether the stream is a
+    /// terminal, as told by [`std::io::IsTerminal`].
+    pub fn enabled(&self, is_terminal: bool) -> bool {
+        match self {
+            ColorChoice::Auto => {
+                is_terminal && env::var_os("NO_COLOR").is_none_or(|v| v.is_empty())
+            }
+            ColorChoice::Always => true,
+            ColorChoice::Never => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_choices() {
+        for choice in ColorChoice::ALL {
+            assert_eq!(ColorChoice::from_name(choice.name()), Some(choice));
+        }
+        assert_eq!(ColorChoice::from_name("sometimes"), None);
+        assert!(ColorChoice::Always.enabled(false));
+        assert!(!ColorChoice::Never.enabled(true));
+        assert!(!ColorChoice::Auto.enabled(false));
+    }
+}
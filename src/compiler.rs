@@ -0,0 +1,719 @@
+//! Multi-file compilation.
+//!
+//! [`Compiler`] compiles a set of schema files as one module: each file is parsed on its own,
+//! the definitions are merged so that fields may refer to types from any file, and every
+//! requested target is generated from the merged module. With the `parallel` feature, files
+//! are parsed and targets are generated concurrently on the rayon thread pool.
+//!
+//! An optional on-disk cache ([`Compiler::set_cache_dir`]) skips all of this when the sources
+//! and generator configuration are unchanged since an earlier run.
+
+use std::{collections::HashMap, fs, path::PathBuf};
+
+use crate::{
+    ast::OnyxModule,
+    diagnostic::{Diagnostic, codes},
+    generators,
+    limits::{self, Limits},
+    parser::{self, Parser},
+    prune,
+    span::LineIndex,
+    symbol::Symbol,
+    trace,
+};
+
+/// A file produced by a [`Compiler`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Artifact {
+    /// The target that produced the file, e.g. `"cpp"`.
+    pub target: String,
+    /// Where the file should be written.
+    pub path: PathBuf,
+    /// The file contents.
+    pub contents: String,
+}
+
+/// Diagnostics paired with the file they refer to: a schema file for parse and resolution
+/// errors, or the output path for generation errors.
+pub type CompilerErrors = Vec<(PathBuf, Diagnostic)>;
+
+/// A schema file, read from disk when compilation starts unless its source was given.
+#[derive(Debug, Clone)]
+struct Input {
+    path: PathBuf,
+    source: Option<String>,
+}
+
+/// Compiles many schema files into a combined set of artifacts.
+///
+/// ```rust
+/// use onyx::compiler::Compiler;
+///
+/// let mut compiler = Compiler::new("out/schema");
+/// compiler.add_source("header.onyx", "struct Header { version u8, }");
+/// compiler.add_source("packet.onyx", "message Packet { header Header, length u16, }");
+/// compiler.add_target("cpp");
+///
+/// let artifacts = compiler.compile().unwrap();
+/// assert_eq!(artifacts.len(), 2); // out/schema.hpp and out/schema.cpp
+/// ```
+#[derive(Debug, Clone)]
+pub struct Compiler {
+    inputs: Vec<Input>,
+    targets: Vec<String>,
+    output: PathBuf,
+    cache_dir: Option<PathBuf>,
+    limits: Limits,
+    features: Vec<String>,
+    entry_points: Option<Vec<Symbol>>,
+}
+
+impl Compiler {
+    /// Creates a compiler whose generators write next to `output` (a path without extension,
+    /// as for [`generators::generator_for_target`]).
+    pub fn new(output: impl Into<PathBuf>) -> Self {
+        Compiler {
+            inputs: Vec::new(),
+            targets: Vec::new(),
+            output: output.into(),
+            cache_dir: None,
+            limits: Limits::default(),
+            features: Vec::new(),
+            entry_points: None,
+        }
+    }
+
+    /// Adds a schema file, read from disk when [`compile`](Compiler::compile) runs.
+    pub fn add_file(&mut self, path: impl Into<PathBuf>) {
+        self.inputs.push(Input {
+            path: path.into(),
+            source: None,
+        });
+    }
+
+    /// Adds a schema whose source is already in memory. `path` is used in diagnostics.
+    pub fn add_source(&mut self, path: impl Into<PathBuf>, source: impl Into<String>) {
+        self.inputs.push(Input {
+            path: path.into(),
+            source: Some(source.into()),
+        });
+    }
+
+    /// Adds a target to generate (see [`generators::TARGETS`]).
+    pub fn add_target(&mut self, target: impl Into<String>) {
+        self.targets.push(target.into());
+    }
+
+    /// Caches artifacts in `dir`, keyed by a hash of the schema sources and the generator
+    /// configuration. When nothing has changed since an earlier run, [`compile`] returns the
+    /// cached artifacts without parsing or generating. The directory is created on first use.
+    ///
+    /// [`compile`]: Compiler::compile
+    pub fn set_cache_dir(&mut self, dir: impl Into<PathBuf>) {
+        self.cache_dir = Some(dir.into());
+    }
+
+    /// Rejects schemas over `limits` before generating anything. There are no limits by
+    /// default.
+    pub fn set_limits(&mut self, limits: Limits) {
+        self.limits = limits;
+    }
+
+    /// Enables `features` for the `@cfg` attributes of every file, as
+    /// [`Parser::with_features`] does. None are enabled by default.
+    pub fn set_features<S: Into<String>>(&mut self, features: impl IntoIterator<Item = S>) {
+        self.features = features.into_iter().map(Into::into).collect();
+    }
+
+    /// Generates only the definitions `entry_points` use, as [`prune::prune`] does; when it is
+    /// empty, every message is an entry point. Everything is generated by default.
+    pub fn set_prune<S: Into<Symbol>>(&mut self, entry_points: impl IntoIterator<Item = S>) {
+        self.entry_points = Some(entry_points.into_iter().map(Into::into).collect());
+    }
+
+    /// Parses every file, merges and resolves the definitions, checks them against the
+    /// limits, and runs each target's generator.
+    ///
+    /// Every file is parsed even if another fails, so all of their errors are reported
+    /// together. Artifacts are returned in the order the targets were added.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(level = "info", skip_all, fields(files = self.inputs.len()))
+    )]
+    pub fn compile(&self) -> Result<Vec<Artifact>, CompilerErrors> {
+        let mut errors = Vec::new();
+        let mut sources = Vec::new();
+        for (input, result) in self.inputs.iter().zip(map(&self.inputs, read)) {
+            match result {
+                Ok(source) => sources.push(source),
+                Err(diagnostic) => errors.push((input.path.clone(), diagnostic)),
+            }
+        }
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+
+        let cache_entry = self
+            .cache_dir
+            .as_ref()
+            .map(|dir| dir.join(format!("{:016x}", self.cache_key(&sources))));
+        if let Some(entry) = &cache_entry
+            && let Some(artifacts) = cache::load(entry)
+        {
+            trace::debug!(entry = %entry.display(), "using cached artifacts");
+            return Ok(artifacts);
+        }
+
+        let mut modules = Vec::new();
+        for (input, result) in self
+            .inputs
+            .iter()
+            .zip(map(&sources, |s| parse(s, &self.features)))
+        {
+            match result {
+                Ok(module) => modules.push(module),
+                Err(diagnostics) => {
+                    errors.extend(diagnostics.into_iter().map(|d| (input.path.clone(), d)))
+                }
+            }
+        }
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+
+        let names: Vec<String> = self
+            .inputs
+            .iter()
+            .map(|input| input.path.display().to_string())
+            .collect();
+        let module = merge(&names, &sources, &modules).map_err(|errors| {
+            errors
+                .into_iter()
+                .map(|(index, error)| (self.inputs[index].path.clone(), error))
+                .collect::<CompilerErrors>()
+        })?;
+        trace::debug!(definitions = module.definitions.len(), "merged modules");
+        limits::check(&module, &self.limits).map_err(|errors| {
+            errors
+                .into_iter()
+                .map(|(owner, error)| {
+                    let index = modules
+                        .iter()
+                        .position(|m| m.definitions.contains_key(&owner))
+                        .unwrap_or_default();
+                    (
+                        self.inputs[index].path.clone(),
+                        error.with_source(&sources[index]),
+                    )
+                })
+                .collect::<CompilerErrors>()
+        })?;
+        let module = match &self.entry_points {
+            Some(entry_points) => prune::prune(&module, entry_points)
+                .map_err(|error| vec![(self.output.clone(), error)])?,
+            None => module,
+        };
+
+        let generated = map(&self.targets, |target| {
+            let mut generator = generators::generator_for_target(target, self.output.clone())?;
+            let files = generator.generate(&module)?;
+            Ok(files
+                .into_iter()
+                .map(|(path, contents)| Artifact {
+                    target: target.clone(),
+                    path,
+                    contents,
+                })
+                .collect::<Vec<_>>())
+        });
+
+        let mut artifacts = Vec::new();
+        for result in generated {
+            match result {
+                Ok(files) => artifacts.extend(files),
+                Err(diagnostic) => errors.push((self.output.clone(), diagnostic)),
+            }
+        }
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+
+        if let Some(entry) = cache_entry {
+            cache::store(&entry, &artifacts).map_err(|e| vec![(entry, e)])?;
+        }
+        Ok(artifacts)
+    }
+
+    /// Hashes everything that determines the artifacts: the compiler version, the generator
+    /// configuration, and each file's path and source.
+    fn cache_key(&self, sources: &[String]) -> u64 {
+        let mut hasher = cache::Hasher::default();
+        hasher.write(env!("CARGO_PKG_VERSION").as_bytes());
+        hasher.write(self.output.to_string_lossy().as_bytes());
+        for target in &self.targets {
+            hasher.write(target.as_bytes());
+        }
+        hasher.write(format!("{:?}", self.limits).as_bytes());
+        hasher.write(format!("{:?}", self.features).as_bytes());
+        hasher.write(format!("{:?}", self.entry_points).as_bytes());
+        for (input, source) in self.inputs.iter().zip(sources) {
+            hasher.write(input.path.to_string_lossy().as_bytes());
+            hasher.write(source.as_bytes());
+        }
+        hasher.finish()
+    }
+}
+
+/// Returns the source of a file, reading it from disk unless it was given.
+fn read(input: &Input) -> Result<String, Diagnostic> {
+    match &input.source {
+        Some(source) => Ok(source.clone()),
+        None => fs::read_to_string(&input.path).map_err(|e| {
+            Diagnostic::error(format!("failed to read {}: {e}", input.path.display()))
+                .with_code(codes::IO)
+                .with_cause(e)
+        }),
+    }
+}
+
+/// Parses one file with `features` enabled, without resolving it. Every lexical error is
+/// reported, or failing that every syntax error the parser can recover from.
+pub(crate) fn parse(source: &str, features: &[String]) -> Result<OnyxModule, Vec<Diagnostic>> {
+    let errors = parser::lexical_errors(source);
+    if !errors.is_empty() {
+        return Err(errors);
+    }
+    let (module, errors) = Parser::new(source)
+        .map_err(|e| vec![e])?
+        .with_features(features)
+        .parse_module_recovering();
+    match errors.is_empty() {
+        true => Ok(module),
+        false => Err(errors),
+    }
+}
+
+/// Formats a module version for messages.
+fn describe_version(version: Option<u32>) -> String {
+    version.map_or_else(|| "none".to_string(), |v| v.to_string())
+}
+
+/// Merges the definitions of every file into one module and resolves it.
+///
+/// `names` describe the files in messages. Errors are paired with the index of the file they
+/// refer to.
+pub(crate) fn merge(
+    names: &[String],
+    sources: &[String],
+    modules: &[OnyxModule],
+) -> Result<OnyxModule, Vec<(usize, Diagnostic)>> {
+    let mut errors = Vec::new();
+    let mut merged = OnyxModule::default();
+    // The index of the file each definition came from.
+    let mut origin: HashMap<Symbol, usize> = HashMap::new();
+
+    for (index, module) in modules.iter().enumerate() {
+        // A strict file holds every definition it is merged with to its rules.
+        merged.strict |= module.strict;
+        if index == 0 {
+            merged.endianness = module.endianness;
+            merged.version = module.version;
+            merged.packed_bools = module.packed_bools;
+            merged.alignment = module.alignment;
+        } else {
+            if module.endianness != merged.endianness {
+                errors.push((
+                    index,
+                    Diagnostic::error(format!(
+                        "module is {:?} endian, but {} is {:?} endian",
+                        module.endianness, names[0], merged.endianness
+                    ))
+                    .with_code(codes::INVALID_ENDIANNESS)
+                    .with_help("use the same 'endian' directive in every file"),
+                ));
+            }
+            if module.version != merged.version {
+                errors.push((
+                    index,
+                    Diagnostic::error(format!(
+                        "module has version {}, but {} has version {}",
+                        describe_version(module.version),
+                        names[0],
+                        describe_version(merged.version)
+                    ))
+                    .with_code(codes::INVALID_VERSION)
+                    .with_help("use the same 'version' directive in every file"),
+                ));
+            }
+            if module.packed_bools != merged.packed_bools {
+                let width = |packed| if packed { 1 } else { 8 };
+                errors.push((
+                    index,
+                    Diagnostic::error(format!(
+                        "module has bool width {}, but {} has bool width {}",
+                        width(module.packed_bools),
+                        names[0],
+                        width(merged.packed_bools)
+                    ))
+                    .with_code(codes::INVALID_BIT_FIELD)
+                    .with_help("use the same 'bool_width' directive in every file"),
+                ));
+            }
+            if module.alignment != merged.alignment {
+                errors.push((
+                    index,
+                    Diagnostic::error(format!(
+                        "module has alignment {}, but {} has alignment {}",
+                        module.alignment, names[0], merged.alignment
+                    ))
+                    .with_code(codes::INVALID_ALIGNMENT)
+                    .with_help("use the same 'align' directive in every file"),
+                ));
+            }
+        }
+
+        for (id, def) in &module.definitions {
+            if let Some(&first) = origin.get(id) {
+                let existing = &merged.definitions[id];
+                errors.push((
+                    index,
+                    Diagnostic::error(format!(
+                        "{} already exists, found second definition",
+                        def.name()
+                    ))
+                    .with_code(codes::DUPLICATE_DEFINITION)
+                    .with_span(def.span())
+                    .with_source(&sources[index])
+                    .with_note(format!(
+                        "first defined in {} at position {}",
+                        names[first],
+                        LineIndex::new(&sources[first]).position(existing.span().start)
+                    )),
+                ));
+                continue;
+            }
+            origin.insert(*id, index);
+            merged.definitions.insert(*id, def.clone());
+        }
+    }
+    if !errors.is_empty() {
+        return Err(errors);
+    }
+
+    parser::resolve(merged).map_err(|(owner, error)| {
+        let index = origin[&owner];
+        vec![(index, error.with_source(&sources[index]))]
+    })
+}
+
+/// Applies `f` to every item, concurrently with the `parallel` feature, keeping the order.
+fn map<T: Sync, R: Send>(items: &[T], f: impl Fn(&T) -> R + Sync + Send) -> Vec<R> {
+    #[cfg(feature = "parallel")]
+    {
+        use rayon::prelude::*;
+        items.par_iter().map(f).collect()
+    }
+    #[cfg(not(feature = "parallel"))]
+    {
+        items.iter().map(f).collect()
+    }
+}
+
+/// The on-disk artifact cache used by [`Compiler::set_cache_dir`].
+///
+/// Each entry is one file named after the cache key. It holds a header line followed by every
+/// artifact's target, path, and contents, each prefixed with its length in bytes.
+mod cache {
+    use std::{fs, path::Path};
+
+    use super::Artifact;
+    use crate::diagnostic::{Diagnostic, codes};
+
+    /// 64-bit FNV-1a, stable across Rust releases, so cache entries stay valid when the
+    /// compiler is rebuilt.
+    pub(super) use crate::fingerprint::Hasher;
+
+    const HEADER: &str = "onyx-cache 1\n";
+
+    /// Reads the artifacts cached in `entry`. A missing or malformed entry is a miss.
+    pub(super) fn load(entry: &Path) -> Option<Vec<Artifact>> {
+        let data = fs::read_to_string(entry).ok()?;
+        let mut rest = data.strip_prefix(HEADER)?;
+        let mut artifacts = Vec::new();
+        while !rest.is_empty() {
+            let target = field(&mut rest)?;
+            let path = field(&mut rest)?;
+            let contents = field(&mut rest)?;
+            artifacts.push(Artifact {
+                target: target.to_string(),
+                path: path.into(),
+                contents: contents.to_string(),
+            });
+        }
+        Some(artifacts)
+    }
+
+    /// Splits one `<length>:<text>` field off the front of `rest`.
+    fn field<'a>(rest: &mut &'a str) -> Option<&'a str> {
+        let (len, tail) = rest.split_once(':')?;
+        let len: usize = len.parse().ok()?;
+        let text = tail.get(..len)?;
+        *rest = &tail[len..];
+        Some(text)
+    }
+
+    /// Writes `artifacts` to `entry`, creating the cache directory if needed. The entry is
+    /// written to a temporary file first so that concurrent builds never read a partial one.
+    pub(super) fn store(entry: &Path, artifacts: &[Artifact]) -> Result<(), Diagnostic> {
+        let mut data = HEADER.to_string();
+        for artifact in artifacts {
+            let path = artifact.path.to_string_lossy();
+            for text in [artifact.target.as_str(), &path, &artifact.contents] {
+                data.push_str(&format!("{}:{text}", text.len()));
+            }
+        }
+
+        let io_error = |e: std::io::Error| {
+            Diagnostic::error(format!(
+                "failed to write cache entry {}: {e}",
+                entry.display()
+            ))
+            .with_code(codes::IO)
+            .with_cause(e)
+        };
+        if let Some(dir) = entry.parent() {
+            fs::create_dir_all(dir).map_err(io_error)?;
+        }
+        let temp = entry.with_extension(format!("tmp{}", std::process::id()));
+        fs::write(&temp, data).map_err(io_error)?;
+        fs::rename(&temp, entry).map_err(io_error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::Definition;
+
+    #[test]
+    fn test_cross_file_types() {
+        let mut compiler = Compiler::new("out/schema");
+        compiler.add_source(
+            "packet.onyx",
+            "message Packet { header Header, length u16, }",
+        );
+        compiler.add_source(
+            "header.onyx",
+            "@packed_bits struct Header { version u8, flags u8 : 4, }",
+        );
+        compiler.add_target("cpp");
+        compiler.add_target("rust");
+
+        let artifacts = compiler.compile().unwrap();
+        let paths: Vec<_> = artifacts.iter().map(|a| a.path.clone()).collect();
+        assert_eq!(
+            paths,
+            [
+                PathBuf::from("out/schema.hpp"),
+                PathBuf::from("out/schema.cpp"),
+                PathBuf::from("out/schema.rs"),
+            ]
+        );
+        assert!(artifacts[0].contents.contains("class Packet"));
+        assert!(artifacts[0].contents.contains("class Header"));
+    }
+
+    #[test]
+    fn test_errors_name_their_file() {
+        let mut compiler = Compiler::new("out/schema");
+        compiler.add_source("a.onyx", "struct A { x u8 }");
+        compiler.add_source("b.onyx", "struct B { y u8, }");
+        compiler.add_source("c.onyx", "struct C { z u8 }\nstruct D { w u8 }");
+
+        // Parse errors from every file are reported together, and every one in a file.
+        let errors = compiler.compile().unwrap_err();
+        let files: Vec<_> = errors.iter().map(|(file, _)| file.clone()).collect();
+        assert_eq!(
+            files,
+            [
+                PathBuf::from("a.onyx"),
+                PathBuf::from("c.onyx"),
+                PathBuf::from("c.onyx")
+            ]
+        );
+
+        let mut compiler = Compiler::new("out/schema");
+        compiler.add_source("a.onyx", "struct A { x u8, }\nstruct Dup { x u8, }");
+        compiler.add_source("b.onyx", "struct Dup { y u8, }");
+        let errors = compiler.compile().unwrap_err();
+        assert_eq!(errors[0].0, PathBuf::from("b.onyx"));
+        assert_eq!(errors[0].1.code(), Some(codes::DUPLICATE_DEFINITION));
+        assert_eq!(
+            errors[0].1.notes(),
+            ["first defined in a.onyx at position 2:8"]
+        );
+
+        let mut compiler = Compiler::new("out/schema");
+        compiler.add_source("a.onyx", "struct A { x u8, }");
+        compiler.add_source("b.onyx", "\nstruct B { missing Missing, }");
+        let errors = compiler.compile().unwrap_err();
+        assert_eq!(errors[0].0, PathBuf::from("b.onyx"));
+        assert_eq!(errors[0].1.code(), Some(codes::UNDEFINED_TYPE));
+        assert_eq!(errors[0].1.position().unwrap().to_string(), "2:12");
+    }
+
+    #[test]
+    fn test_endianness_must_match() {
+        let mut compiler = Compiler::new("out/schema");
+        compiler.add_source("a.onyx", "endian = big\nstruct A { x u8, }");
+        compiler.add_source("b.onyx", "struct B { x u8, }");
+        let errors = compiler.compile().unwrap_err();
+        assert_eq!(errors[0].0, PathBuf::from("b.onyx"));
+        assert_eq!(errors[0].1.code(), Some(codes::INVALID_ENDIANNESS));
+
+        let mut compiler = Compiler::new("out/schema");
+        compiler.add_source("a.onyx", "endian = big\nstruct A { x u8, }");
+        compiler.add_source("b.onyx", "endian = big\nstruct B { a A, }");
+        let sources: Vec<_> = compiler.inputs.iter().map(|i| read(i).unwrap()).collect();
+        let modules: Vec<_> = sources.iter().map(|s| parse(s, &[]).unwrap()).collect();
+        let names = ["a.onyx".to_string(), "b.onyx".to_string()];
+        let module = merge(&names, &sources, &modules).unwrap();
+        assert!(matches!(
+            &module.definitions[&Symbol::intern("B")],
+            Definition::Struct(s) if s.size == Some(8)
+        ));
+    }
+
+    #[test]
+    fn test_version_must_match() {
+        let mut compiler = Compiler::new("out/schema");
+        compiler.add_source("a.onyx", "version = 2\nstruct A { x u8, }");
+        compiler.add_source("b.onyx", "struct B { x u8, }");
+        let errors = compiler.compile().unwrap_err();
+        assert_eq!(errors[0].0, PathBuf::from("b.onyx"));
+        assert_eq!(errors[0].1.code(), Some(codes::INVALID_VERSION));
+    }
+
+    #[test]
+    fn test_bool_width_must_match() {
+        let mut compiler = Compiler::new("out/schema");
+        compiler.add_source("a.onyx", "bool_width = 1\nstruct A { x bool, y u8 : 7, }");
+        compiler.add_source("b.onyx", "struct B { a A, ok bool, }");
+        let errors = compiler.compile().unwrap_err();
+        assert_eq!(errors[0].0, PathBuf::from("b.onyx"));
+        assert_eq!(errors[0].1.code(), Some(codes::INVALID_BIT_FIELD));
+        assert_eq!(
+            errors[0].1.message(),
+            "module has bool width 8, but a.onyx has bool width 1"
+        );
+    }
+
+    #[test]
+    fn test_alignment_must_match() {
+        let mut compiler = Compiler::new("out/schema");
+        compiler.add_source("a.onyx", "align = xcdr2\nstruct A { x u8, y u32, }");
+        compiler.add_source("b.onyx", "struct B { a A, }");
+        let errors = compiler.compile().unwrap_err();
+        assert_eq!(errors[0].0, PathBuf::from("b.onyx"));
+        assert_eq!(errors[0].1.code(), Some(codes::INVALID_ALIGNMENT));
+        assert_eq!(
+            errors[0].1.message(),
+            "module has alignment packed, but a.onyx has alignment xcdr2"
+        );
+    }
+
+    #[test]
+    fn test_limits() {
+        let mut compiler = Compiler::new("out/schema");
+        compiler.add_source("a.onyx", "struct A { x u8, }");
+        compiler.add_source("b.onyx", "message B { a A, payload bytes[64], }");
+        compiler.add_target("cpp");
+        compiler.compile().unwrap();
+
+        compiler.set_limits(Limits {
+            max_message_bytes: Some(32),
+            ..Limits::default()
+        });
+        let errors = compiler.compile().unwrap_err();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].0, PathBuf::from("b.onyx"));
+        assert_eq!(errors[0].1.code(), Some(codes::LIMIT_EXCEEDED));
+        assert_eq!(errors[0].1.position().unwrap().to_string(), "1:9");
+    }
+
+    #[test]
+    fn test_features() {
+        let mut compiler = Compiler::new("out/schema");
+        compiler.add_source(
+            "a.onyx",
+            "struct A { @cfg(feature = \"gps\") fix u32, x u8, }",
+        );
+        compiler.add_source("b.onyx", "@cfg(not(feature = \"gps\")) message B { a A, }");
+        compiler.add_target("rust");
+        let artifacts = compiler.compile().unwrap();
+        assert!(artifacts[0].contents.contains("pub struct BView"));
+        assert!(!artifacts[0].contents.contains("fn fix("));
+
+        compiler.set_features(["gps"]);
+        let artifacts = compiler.compile().unwrap();
+        assert!(!artifacts[0].contents.contains("pub struct BView"));
+        assert!(artifacts[0].contents.contains("fn fix("));
+    }
+
+    #[test]
+    fn test_prune() {
+        let mut compiler = Compiler::new("out/schema");
+        compiler.add_source("a.onyx", "struct A { x u8, } struct Legacy { y u8, }");
+        compiler.add_source("b.onyx", "message B { a A, } message Debug { l Legacy, }");
+        compiler.add_target("rust");
+        compiler.set_prune(["B"]);
+        let artifacts = compiler.compile().unwrap();
+        assert!(artifacts[0].contents.contains("pub struct AView"));
+        assert!(!artifacts[0].contents.contains("pub struct LegacyView"));
+        assert!(!artifacts[0].contents.contains("pub struct DebugView"));
+
+        compiler.set_prune(["Missing"]);
+        let errors = compiler.compile().unwrap_err();
+        assert_eq!(errors[0].0, PathBuf::from("out/schema"));
+        assert_eq!(
+            errors[0].1.message(),
+            "entry point 'Missing' is not defined"
+        );
+    }
+
+    #[test]
+    fn test_cache() {
+        let dir = std::env::temp_dir().join(format!("onyx-cache-test-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+
+        let mut compiler = Compiler::new("out/schema");
+        compiler.add_source("a.onyx", "struct A { x u8, }");
+        compiler.add_target("cpp");
+        compiler.set_cache_dir(&dir);
+
+        let artifacts = compiler.compile().unwrap();
+        let entries: Vec<_> = fs::read_dir(&dir)
+            .unwrap()
+            .map(|e| e.unwrap().path())
+            .collect();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(cache::load(&entries[0]), Some(artifacts.clone()));
+
+        // An unchanged compilation is served from the cache.
+        let mut cached = artifacts.clone();
+        cached[0].contents = "// cached".to_string();
+        cache::store(&entries[0], &cached).unwrap();
+        assert_eq!(compiler.compile().unwrap(), cached);
+
+        // Changing a source or the targets misses.
+        let mut changed = compiler.clone();
+        changed.add_target("rust");
+        assert_eq!(changed.compile().unwrap().len(), 3);
+        compiler.inputs[0].source = Some("struct A { x u16, }".to_string());
+        assert_ne!(compiler.compile().unwrap(), cached);
+        assert_eq!(fs::read_dir(&dir).unwrap().count(), 3);
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,1050 @@
+//! Lossless concrete syntax tree (CST) for Onyx source.
+//!
+//! Unlike the AST produced by [`crate::parser::Parser`], the CST keeps every byte of the input:
+//! whitespace, comments, and even unrecognized characters are stored as tokens with their byte
+//! ranges, so concatenating the tokens of a tree reproduces the original source exactly. This
+//! makes it suitable for tools that need to edit a schema without destroying its formatting,
+//! such as formatters, language servers, and refactoring tools.
+//!
+//! The CST parser never fails. Input it does not understand is wrapped in
+//! [`SyntaxKind::Error`] nodes and parsing continues with the next definition.
+
+use std::{fmt, iter::Peekable, ops::Range, str::CharIndices};
+
+use crate::lexer::{self, TokenKind};
+
+/// The kind of a token or node in the syntax tree.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SyntaxKind {
+    // Trivia
+    /// A run of whitespace characters.
+    Whitespace,
+    /// A `//` line comment, excluding the trailing newline.
+    Comment,
+
+    // Tokens
+    /// The `endian` keyword.
+    EndianKw,
+    /// The `import` keyword.
+    ImportKw,
+    /// The `message` keyword.
+    MessageKw,
+    /// The `struct` keyword.
+    StructKw,
+    /// The `enum` keyword.
+    EnumKw,
+    /// A primitive type keyword (e.g., `u8`, `bool`).
+    PrimitiveType,
+    /// An identifier.
+    Identifier,
+    /// An integer literal.
+    Integer,
+    /// A string literal, quotes included.
+    String,
+    /// Open brace `{`.
+    OpenBrace,
+    /// Close brace `}`.
+    CloseBrace,
+    /// Comma `,`.
+    Comma,
+    /// Colon `:`.
+    Colon,
+    /// Open angle bracket `<`.
+    OpenAngle,
+    /// Close angle bracket `>`.
+    CloseAngle,
+    /// Open square bracket `[`.
+    OpenBracket,
+    /// Close square bracket `]`.
+    CloseBracket,
+    /// Semicolon `;`.
+    Semicolon,
+    /// Assignment operator `=`.
+    Assign,
+    /// At sign `@`, which starts an attribute.
+    At,
+    /// Open parenthesis `(`.
+    OpenParen,
+    /// Close parenthesis `)`.
+    CloseParen,
+    /// Equality operator `==`.
+    Equal,
+    /// Inequality operator `!=`.
+    NotEqual,
+    /// Dot `.`.
+    Dot,
+    /// Plus `+`.
+    Plus,
+    /// Minus `-`.
+    Minus,
+    /// Asterisk `*`.
+    Star,
+    /// Slash `/`.
+    Slash,
+    /// Percent `%`.
+    Percent,
+    /// Left shift `<<`.
+    ShiftLeft,
+    /// Right shift `>>`.
+    ShiftRight,
+    /// A character that does not start any valid token.
+    Unknown,
+
+    // Nodes
+    /// The root of the tree.
+    Root,
+    /// An `endian = ...` directive.
+    EndianDirective,
+    /// A `version = ...` directive.
+    VersionDirective,
+    /// A `strict = ...` directive.
+    StrictDirective,
+    /// A `bool_width = ...` directive.
+    BoolWidthDirective,
+    /// An `align = ...` directive.
+    AlignDirective,
+    /// A `const NAME = ...` declaration.
+    ConstDeclaration,
+    /// A message definition.
+    Message,
+    /// A struct definition.
+    Struct,
+    /// An enum definition.
+    Enum,
+    /// A field inside a message or struct.
+    Field,
+    /// A variant inside an enum.
+    Variant,
+    /// The `if ...` condition of a field.
+    Condition,
+    /// A region the parser could not make sense of.
+    Error,
+}
+
+impl SyntaxKind {
+    /// Returns true for whitespace and comments.
+    pub fn is_trivia(self) -> bool {
+        matches!(self, SyntaxKind::Whitespace | SyntaxKind::Comment)
+    }
+}
+
+/// A leaf of the syntax tree.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SyntaxToken {
+    /// The kind of the token.
+    pub kind: SyntaxKind,
+    /// The byte range of the token in the source.
+    pub range: Range<usize>,
+}
+
+/// An interior node of the syntax tree.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SyntaxNode {
+    /// The kind of the node.
+    pub kind: SyntaxKind,
+    /// The byte range covered by the node, including any trivia it contains.
+    pub range: Range<usize>,
+    /// The child nodes and tokens, in source order.
+    pub children: Vec<SyntaxElement>,
+}
+
+/// Either a node or a token.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SyntaxElement {
+    /// An interior node.
+    Node(SyntaxNode),
+    /// A leaf token.
+    Token(SyntaxToken),
+}
+
+impl SyntaxElement {
+    /// Returns the kind of the element.
+    pub fn kind(&self) -> SyntaxKind {
+        match self {
+            SyntaxElement::Node(n) => n.kind,
+            SyntaxElement::Token(t) => t.kind,
+        }
+    }
+
+    /// Returns the byte range of the element.
+    pub fn range(&self) -> Range<usize> {
+        match self {
+            SyntaxElement::Node(n) => n.range.clone(),
+            SyntaxElement::Token(t) => t.range.clone(),
+        }
+    }
+
+    /// Shifts every range in the element by `delta` bytes.
+    fn shift(&mut self, delta: isize) {
+        let shift = |r: &mut Range<usize>| {
+            r.start = r.start.wrapping_add_signed(delta);
+            r.end = r.end.wrapping_add_signed(delta);
+        };
+        match self {
+            SyntaxElement::Node(n) => {
+                shift(&mut n.range);
+                for child in &mut n.children {
+                    child.shift(delta);
+                }
+            }
+            SyntaxElement::Token(t) => shift(&mut t.range),
+        }
+    }
+}
+
+impl SyntaxNode {
+    /// Returns an iterator over the child nodes.
+    pub fn nodes(&self) -> impl Iterator<Item = &SyntaxNode> {
+        self.children.iter().filter_map(|c| match c {
+            SyntaxElement::Node(n) => Some(n),
+            SyntaxElement::Token(_) => None,
+        })
+    }
+
+    /// Returns an iterator over the direct child tokens.
+    pub fn tokens(&self) -> impl Iterator<Item = &SyntaxToken> {
+        self.children.iter().filter_map(|c| match c {
+            SyntaxElement::Token(t) => Some(t),
+            SyntaxElement::Node(_) => None,
+        })
+    }
+
+    /// Returns the first direct child token of the given kind.
+    pub fn token(&self, kind: SyntaxKind) -> Option<&SyntaxToken> {
+        self.tokens().find(|t| t.kind == kind)
+    }
+
+    /// Returns true if this node or any descendant is an error node.
+    pub fn has_errors(&self) -> bool {
+        self.kind == SyntaxKind::Error || self.nodes().any(SyntaxNode::has_errors)
+    }
+}
+
+/// A lossless syntax tree together with the source it was parsed from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SyntaxTree {
+    source: String,
+    root: SyntaxNode,
+}
+
+impl SyntaxTree {
+    /// Parses source into a lossless syntax tree. This never fails.
+    pub fn parse(source: &str) -> SyntaxTree {
+        SyntaxTree {
+            source: source.to_string(),
+            root: parse_root(source, 0),
+        }
+    }
+
+    /// Returns the root node of the tree.
+    pub fn root(&self) -> &SyntaxNode {
+        &self.root
+    }
+
+    /// Returns the full source text.
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    /// Returns the source text covered by a byte range.
+    pub fn text(&self, range: Range<usize>) -> &str {
+        &self.source[range]
+    }
+
+    /// Replaces the bytes in `range` with `replacement` and updates the tree.
+    ///
+    /// When the edit falls strictly inside a single top-level definition, only that definition is
+    /// reparsed and the rest of the tree is reused with shifted ranges. Otherwise the whole source
+    /// is reparsed. Returns `true` if the incremental path was taken.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `range` is out of bounds or does not lie on character boundaries.
+    pub fn edit(&mut self, range: Range<usize>, replacement: &str) -> bool {
+        self.source.replace_range(range.clone(), replacement);
+        let delta = replacement.len() as isize - range.len() as isize;
+
+        let index = self.root.children.iter().position(|child| {
+            let r = child.range();
+            matches!(child, SyntaxElement::Node(_)) && r.start < range.start && range.end < r.end
+        });
+
+        if let Some(index) = index {
+            let old = self.root.children[index].range();
+            let new_range = old.start..old.end.wrapping_add_signed(delta);
+            let reparsed = parse_root(&self.source[new_range.clone()], new_range.start);
+
+            let mut nodes = reparsed.children.into_iter();
+            if let (Some(SyntaxElement::Node(node)), None) = (nodes.next(), nodes.next())
+                && node.kind == self.root.children[index].kind()
+                && node.range == new_range
+                && !node.has_errors()
+            {
+                self.root.children[index] = SyntaxElement::Node(node);
+                for child in &mut self.root.children[index + 1..] {
+                    child.shift(delta);
+                }
+                self.root.range = 0..self.source.len();
+                return true;
+            }
+        }
+
+        self.root = parse_root(&self.source, 0);
+        false
+    }
+}
+
+impl fmt::Display for SyntaxTree {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write_element_text(f, &self.source, &self.root)
+    }
+}
+
+/// Writes the text of every token under `node`, reconstructing the source it covers.
+fn write_element_text(f: &mut fmt::Formatter, source: &str, node: &SyntaxNode) -> fmt::Result {
+    for child in &node.children {
+        match child {
+            SyntaxElement::Node(n) => write_element_text(f, source, n)?,
+            SyntaxElement::Token(t) => write!(f, "{}", &source[t.range.clone()])?,
+        }
+    }
+    Ok(())
+}
+
+// --- Scanning ---
+
+/// Splits the source into tokens, including trivia. Ranges are offset by `base`.
+fn scan(source: &str, base: usize) -> Vec<SyntaxToken> {
+    let mut tokens = Vec::new();
+    let mut chars = source.char_indices().peekable();
+
+    while let Some((start, c)) = chars.next() {
+        let mut end = start + c.len_utf8();
+        let kind = match c {
+            c if c.is_whitespace() => {
+                end = take_while(&mut chars, end, char::is_whitespace);
+                SyntaxKind::Whitespace
+            }
+            '/' if source[end..].starts_with('/') => {
+                end = take_while(&mut chars, end, |c| c != '\n');
+                SyntaxKind::Comment
+            }
+            c if lexer::is_ident_start(c) || c.is_alphabetic() => {
+                end = take_while(&mut chars, end, lexer::is_word_char);
+                let word = &source[start..end];
+                match lexer::keyword(word) {
+                    Some(TokenKind::Endianness) => SyntaxKind::EndianKw,
+                    Some(TokenKind::Import) => SyntaxKind::ImportKw,
+                    Some(TokenKind::Message) => SyntaxKind::MessageKw,
+                    Some(TokenKind::Struct) => SyntaxKind::StructKw,
+                    Some(TokenKind::Enum) => SyntaxKind::EnumKw,
+                    Some(_) => SyntaxKind::PrimitiveType,
+                    None if word.chars().all(lexer::is_ident_continue) => SyntaxKind::Identifier,
+                    // Outside the identifier policy; the lexer reports why.
+                    None => SyntaxKind::Unknown,
+                }
+            }
+            '`' => {
+                end = take_while(&mut chars, end, lexer::is_word_char);
+                let name = &source[start + 1..end];
+                if source[end..].starts_with('`') {
+                    end += 1;
+                    chars.next();
+                    match name.chars().next() {
+                        Some(c)
+                            if lexer::is_ident_start(c)
+                                && name.chars().all(lexer::is_ident_continue) =>
+                        {
+                            SyntaxKind::Identifier
+                        }
+                        _ => SyntaxKind::Unknown,
+                    }
+                } else {
+                    SyntaxKind::Unknown
+                }
+            }
+            c if c.is_ascii_digit() => {
+                end = take_while(&mut chars, end, |c| c.is_ascii_digit());
+                SyntaxKind::Integer
+            }
+            '"' => {
+                end = take_while(&mut chars, end, |c| c != '"' && c != '\n');
+                if source[end..].starts_with('"') {
+                    end += 1;
+                    chars.next();
+                    SyntaxKind::String
+                } else {
+                    SyntaxKind::Unknown
+                }
+            }
+            '{' => SyntaxKind::OpenBrace,
+            '}' => SyntaxKind::CloseBrace,
+            ',' => SyntaxKind::Comma,
+            ':' => SyntaxKind::Colon,
+            '<' if source[end..].starts_with('<') => {
+                end += 1;
+                chars.next();
+                SyntaxKind::ShiftLeft
+            }
+            '>' if source[end..].starts_with('>') => {
+                end += 1;
+                chars.next();
+                SyntaxKind::ShiftRight
+            }
+            '<' => SyntaxKind::OpenAngle,
+            '>' => SyntaxKind::CloseAngle,
+            '[' => SyntaxKind::OpenBracket,
+            ']' => SyntaxKind::CloseBracket,
+            ';' => SyntaxKind::Semicolon,
+            '=' if source[end..].starts_with('=') => {
+                end += 1;
+                chars.next();
+                SyntaxKind::Equal
+            }
+            '!' if source[end..].starts_with('=') => {
+                end += 1;
+                chars.next();
+                SyntaxKind::NotEqual
+            }
+            '=' => SyntaxKind::Assign,
+            '.' => SyntaxKind::Dot,
+            '@' => SyntaxKind::At,
+            '+' => SyntaxKind::Plus,
+            '-' => SyntaxKind::Minus,
+            '*' => SyntaxKind::Star,
+            '/' => SyntaxKind::Slash,
+            '%' => SyntaxKind::Percent,
+            '(' => SyntaxKind::OpenParen,
+            ')' => SyntaxKind::CloseParen,
+            _ => SyntaxKind::Unknown,
+        };
+
+        tokens.push(SyntaxToken {
+            kind,
+            range: base + start..base + end,
+        });
+    }
+
+    tokens
+}
+
+/// Consumes characters while `pred` holds and returns the byte offset after the last one.
+fn take_while(
+    chars: &mut Peekable<CharIndices>,
+    mut end: usize,
+    pred: impl Fn(char) -> bool,
+) -> usize {
+    while let Some(&(i, c)) = chars.peek() {
+        if !pred(c) {
+            break;
+        }
+        end = i + c.len_utf8();
+        chars.next();
+    }
+    end
+}
+
+// --- Parsing ---
+
+/// Parses source into a root node. Ranges are offset by `base`.
+fn parse_root(source: &str, base: usize) -> SyntaxNode {
+    let mut parser = CstParser {
+        source,
+        base,
+        tokens: scan(source, base),
+        pos: 0,
+    };
+    let mut children = Vec::new();
+
+    loop {
+        parser.eat_trivia(&mut children);
+        let Some(kind) = parser.peek() else {
+            break;
+        };
+        let node = match kind {
+            SyntaxKind::EndianKw => parser.parse_endian(),
+            SyntaxKind::MessageKw => parser.parse_struct_like(SyntaxKind::Message, Vec::new()),
+            SyntaxKind::StructKw => parser.parse_struct_like(SyntaxKind::Struct, Vec::new()),
+            SyntaxKind::EnumKw => parser.parse_enum(Vec::new()),
+            SyntaxKind::Identifier if parser.at_word("flags") => parser.parse_enum(Vec::new()),
+            SyntaxKind::Identifier if parser.at_word("version") => parser.parse_version(),
+            SyntaxKind::Identifier if parser.at_word("strict") => parser.parse_strict(),
+            SyntaxKind::Identifier if parser.at_word("bool_width") => parser.parse_bool_width(),
+            SyntaxKind::Identifier if parser.at_word("align") => parser.parse_align(),
+            SyntaxKind::Identifier if parser.at_word("const") => parser.parse_const(),
+            SyntaxKind::At => parser.parse_attributed(),
+            _ => parser.parse_error(),
+        };
+        children.push(SyntaxElement::Node(node));
+    }
+
+    SyntaxNode {
+        kind: SyntaxKind::Root,
+        range: base..base + source.len(),
+        children,
+    }
+}
+
+struct CstParser<'a> {
+    source: &'a str,
+    base: usize,
+    tokens: Vec<SyntaxToken>,
+    pos: usize,
+}
+
+/// Builds a node from collected children.
+fn node(kind: SyntaxKind, children: Vec<SyntaxElement>) -> SyntaxNode {
+    let start = children.first().map_or(0, |c| c.range().start);
+    let end = children.last().map_or(start, |c| c.range().end);
+    SyntaxNode {
+        kind,
+        range: start..end,
+        children,
+    }
+}
+
+impl CstParser<'_> {
+    /// Returns the kind of the next non-trivia token.
+    fn peek(&self) -> Option<SyntaxKind> {
+        self.tokens[self.pos..]
+            .iter()
+            .map(|t| t.kind)
+            .find(|k| !k.is_trivia())
+    }
+
+    /// Returns true if the next non-trivia token is the identifier `word`, which is a keyword
+    /// only where it appears, like `flags`.
+    fn at_word(&self, word: &str) -> bool {
+        self.tokens[self.pos..]
+            .iter()
+            .find(|t| !t.kind.is_trivia())
+            .is_some_and(|t| {
+                t.kind == SyntaxKind::Identifier
+                    && &self.source[t.range.start - self.base..t.range.end - self.base] == word
+            })
+    }
+
+    /// Moves leading trivia into `children`.
+    fn eat_trivia(&mut self, children: &mut Vec<SyntaxElement>) {
+        while let Some(token) = self.tokens.get(self.pos) {
+            if !token.kind.is_trivia() {
+                break;
+            }
+            children.push(SyntaxElement::Token(token.clone()));
+            self.pos += 1;
+        }
+    }
+
+    /// Consumes trivia and then the next token into `children` if it has the expected kind.
+    fn eat(&mut self, kind: SyntaxKind, children: &mut Vec<SyntaxElement>) -> bool {
+        if self.peek() != Some(kind) {
+            return false;
+        }
+        self.eat_trivia(children);
+        children.push(SyntaxElement::Token(self.tokens[self.pos].clone()));
+        self.pos += 1;
+        true
+    }
+
+    /// Consumes trivia and then the next token into `children`, whatever its kind.
+    fn bump(&mut self, children: &mut Vec<SyntaxElement>) {
+        self.eat_trivia(children);
+        if let Some(token) = self.tokens.get(self.pos) {
+            children.push(SyntaxElement::Token(token.clone()));
+            self.pos += 1;
+        }
+    }
+
+    /// Returns true if the next token starts a top-level item.
+    fn at_item_start(&self) -> bool {
+        matches!(
+            self.peek(),
+            Some(
+                SyntaxKind::EndianKw
+                    | SyntaxKind::MessageKw
+                    | SyntaxKind::StructKw
+                    | SyntaxKind::EnumKw
+                    | SyntaxKind::At
+            )
+        ) || self.at_word("flags")
+            || self.at_word("version")
+            || self.at_word("strict")
+            || self.at_word("bool_width")
+            || self.at_word("align")
+            || self.at_word("const")
+    }
+
+    /// Wraps tokens up to the next top-level keyword in an error node.
+    fn parse_error(&mut self) -> SyntaxNode {
+        let mut children = Vec::new();
+        self.bump(&mut children);
+        while self.peek().is_some() && !self.at_item_start() {
+            self.bump(&mut children);
+        }
+        node(SyntaxKind::Error, children)
+    }
+
+    /// Finishes a node, turning it into an error node when `ok` is false.
+    ///
+    /// On failure, tokens up to the next top-level keyword are swallowed into the error node.
+    fn finish(
+        &mut self,
+        kind: SyntaxKind,
+        mut children: Vec<SyntaxElement>,
+        ok: bool,
+    ) -> SyntaxNode {
+        if ok {
+            return node(kind, children);
+        }
+        while self.peek().is_some() && !self.at_item_start() {
+            self.bump(&mut children);
+        }
+        node(SyntaxKind::Error, children)
+    }
+
+    fn parse_endian(&mut self) -> SyntaxNode {
+        let mut children = Vec::new();
+        let ok = self.eat(SyntaxKind::EndianKw, &mut children)
+            && self.eat(SyntaxKind::Assign, &mut children)
+            && self.eat(SyntaxKind::Identifier, &mut children);
+        self.finish(SyntaxKind::EndianDirective, children, ok)
+    }
+
+    fn parse_version(&mut self) -> SyntaxNode {
+        let mut children = Vec::new();
+        let ok = self.eat(SyntaxKind::Identifier, &mut children)
+            && self.eat(SyntaxKind::Assign, &mut children)
+            && self.eat(SyntaxKind::Integer, &mut children);
+        self.finish(SyntaxKind::VersionDirective, children, ok)
+    }
+
+    fn parse_strict(&mut self) -> SyntaxNode {
+        let mut children = Vec::new();
+        let ok = self.eat(SyntaxKind::Identifier, &mut children)
+            && self.eat(SyntaxKind::Assign, &mut children)
+            && self.eat(SyntaxKind::Identifier, &mut children);
+        self.finish(SyntaxKind::StrictDirective, children, ok)
+    }
+
+    fn parse_bool_width(&mut self) -> SyntaxNode {
+        let mut children = Vec::new();
+        let ok = self.eat(SyntaxKind::Identifier, &mut children)
+            && self.eat(SyntaxKind::Assign, &mut children)
+            && self.eat(SyntaxKind::Integer, &mut children);
+        self.finish(SyntaxKind::BoolWidthDirective, children, ok)
+    }
+
+    fn parse_align(&mut self) -> SyntaxNode {
+        let mut children = Vec::new();
+        let ok = self.eat(SyntaxKind::Identifier, &mut children)
+            && self.eat(SyntaxKind::Assign, &mut children)
+            && self.eat(SyntaxKind::Identifier, &mut children);
+        self.finish(SyntaxKind::AlignDirective, children, ok)
+    }
+
+    fn parse_const(&mut self) -> SyntaxNode {
+        let mut children = Vec::new();
+        let ok = self.eat(SyntaxKind::Identifier, &mut children)
+            && self.eat(SyntaxKind::Identifier, &mut children)
+            && self.eat(SyntaxKind::Assign, &mut children)
+            && self.eat_expression(&mut children);
+        self.finish(SyntaxKind::ConstDeclaration, children, ok)
+    }
+
+    /// Consumes a constant expression, such as `1 << 4` or `(LEN + 1) * 2`, into `children`.
+    fn eat_expression(&mut self, children: &mut Vec<SyntaxElement>) -> bool {
+        loop {
+            let operand = if self.eat(SyntaxKind::OpenParen, children) {
+                self.eat_expression(children) && self.eat(SyntaxKind::CloseParen, children)
+            } else {
+                self.eat(SyntaxKind::Integer, children)
+                    || self.eat(SyntaxKind::Identifier, children)
+            };
+            if !operand {
+                return false;
+            }
+            let operator = matches!(
+                self.peek(),
+                Some(
+                    SyntaxKind::Plus
+                        | SyntaxKind::Minus
+                        | SyntaxKind::Star
+                        | SyntaxKind::Slash
+                        | SyntaxKind::Percent
+                        | SyntaxKind::ShiftLeft
+                        | SyntaxKind::ShiftRight
+                )
+            );
+            if !operator {
+                return true;
+            }
+            self.bump(children);
+        }
+    }
+
+    /// Consumes a `@cfg` predicate, such as `feature = "gps"` or `not(any(...))`, into
+    /// `children`.
+    fn eat_cfg_predicate(&mut self, children: &mut Vec<SyntaxElement>) -> bool {
+        if !self.eat(SyntaxKind::Identifier, children) {
+            return false;
+        }
+        if self.eat(SyntaxKind::Assign, children) {
+            return self.eat(SyntaxKind::String, children);
+        }
+        if !self.eat(SyntaxKind::OpenParen, children) {
+            return false;
+        }
+        while !self.eat(SyntaxKind::CloseParen, children) {
+            if !self.eat_cfg_predicate(children) {
+                return false;
+            }
+            if !self.eat(SyntaxKind::Comma, children) {
+                return self.eat(SyntaxKind::CloseParen, children);
+            }
+        }
+        true
+    }
+
+    /// Parses a definition marked with attributes, like `@id(1) message` or `@open enum`.
+    fn parse_attributed(&mut self) -> SyntaxNode {
+        let mut children = Vec::new();
+        let mut ok = true;
+        while ok && self.eat(SyntaxKind::At, &mut children) {
+            let cfg = self.at_word("cfg");
+            ok = self.eat(SyntaxKind::Identifier, &mut children);
+            if ok && self.eat(SyntaxKind::OpenParen, &mut children) {
+                ok = (cfg && self.eat_cfg_predicate(&mut children)
+                    || !cfg
+                        && (self.eat(SyntaxKind::String, &mut children)
+                            || self.eat_expression(&mut children)))
+                    && self.eat(SyntaxKind::CloseParen, &mut children);
+            }
+        }
+        if !ok {
+            return self.finish(SyntaxKind::Error, children, false);
+        }
+        match self.peek() {
+            Some(SyntaxKind::MessageKw) => self.parse_struct_like(SyntaxKind::Message, children),
+            Some(SyntaxKind::StructKw) => self.parse_struct_like(SyntaxKind::Struct, children),
+            _ => self.parse_enum(children),
+        }
+    }
+
+    fn parse_struct_like(
+        &mut self,
+        kind: SyntaxKind,
+        mut children: Vec<SyntaxElement>,
+    ) -> SyntaxNode {
+        self.bump(&mut children); // `message` or `struct`
+        let mut ok = self.eat(SyntaxKind::Identifier, &mut children)
+            && self.eat(SyntaxKind::OpenBrace, &mut children);
+
+        while ok && !matches!(self.peek(), Some(SyntaxKind::CloseBrace) | None) {
+            let field = self.parse_field();
+            ok = field.kind == SyntaxKind::Field;
+            children.push(SyntaxElement::Node(field));
+        }
+
+        let ok = ok && self.eat(SyntaxKind::CloseBrace, &mut children);
+        self.finish(kind, children, ok)
+    }
+
+    fn parse_field(&mut self) -> SyntaxNode {
+        let mut children = Vec::new();
+        let mut ok = true;
+        // `@since(N)`, `@removed(N)`, `@deprecated("note")`, and `@cfg(predicate)`
+        while ok && self.eat(SyntaxKind::At, &mut children) {
+            let cfg = self.at_word("cfg");
+            ok = self.eat(SyntaxKind::Identifier, &mut children)
+                && self.eat(SyntaxKind::OpenParen, &mut children)
+                && (cfg && self.eat_cfg_predicate(&mut children)
+                    || !cfg
+                        && (self.eat(SyntaxKind::Integer, &mut children)
+                            || self.eat(SyntaxKind::String, &mut children)))
+                && self.eat(SyntaxKind::CloseParen, &mut children);
+        }
+        ok = ok
+            && self.eat(SyntaxKind::Identifier, &mut children)
+            && (self.eat(SyntaxKind::PrimitiveType, &mut children)
+                || self.eat(SyntaxKind::Identifier, &mut children));
+        // `vec<T, N>`
+        if ok && self.eat(SyntaxKind::OpenAngle, &mut children) {
+            ok = (self.eat(SyntaxKind::PrimitiveType, &mut children)
+                || self.eat(SyntaxKind::Identifier, &mut children))
+                && self.eat(SyntaxKind::Comma, &mut children)
+                && self.eat_expression(&mut children)
+                && self.eat(SyntaxKind::CloseAngle, &mut children);
+        }
+        // `bytes[N]`
+        if ok && self.eat(SyntaxKind::OpenBracket, &mut children) {
+            ok = self.eat_expression(&mut children)
+                && self.eat(SyntaxKind::CloseBracket, &mut children);
+        }
+        if ok && self.eat(SyntaxKind::Colon, &mut children) {
+            ok = self.eat_expression(&mut children);
+        }
+        if ok && self.at_word("if") {
+            let condition = self.parse_condition();
+            ok = condition.kind == SyntaxKind::Condition;
+            children.push(SyntaxElement::Node(condition));
+        }
+        let ok = ok && self.eat(SyntaxKind::Comma, &mut children);
+        if !ok {
+            // Don't swallow the closing brace of the enclosing definition.
+            if children.is_empty() && self.peek() != Some(SyntaxKind::CloseBrace) {
+                self.bump(&mut children);
+            }
+            return node(SyntaxKind::Error, children);
+        }
+        node(SyntaxKind::Field, children)
+    }
+
+    /// Parses `if field == value` or `if field != value`, where the value is an integer or a
+    /// word, optionally qualified as `Enum.Variant`.
+    fn parse_condition(&mut self) -> SyntaxNode {
+        let mut children = Vec::new();
+        let mut ok = self.eat(SyntaxKind::Identifier, &mut children)
+            && self.eat(SyntaxKind::Identifier, &mut children)
+            && (self.eat(SyntaxKind::Equal, &mut children)
+                || self.eat(SyntaxKind::NotEqual, &mut children));
+        if ok && !self.eat(SyntaxKind::Integer, &mut children) {
+            ok = self.eat(SyntaxKind::Identifier, &mut children)
+                && (!self.eat(SyntaxKind::Dot, &mut children)
+                    || self.eat(SyntaxKind::Identifier, &mut children));
+        }
+        if !ok {
+            return node(SyntaxKind::Error, children);
+        }
+        node(SyntaxKind::Condition, children)
+    }
+
+    fn parse_enum(&mut self, mut children: Vec<SyntaxElement>) -> SyntaxNode {
+        let is_flags = self.at_word("flags");
+        let mut ok = self.eat(SyntaxKind::EnumKw, &mut children)
+            || is_flags && self.eat(SyntaxKind::Identifier, &mut children);
+        ok = ok
+            && self.eat(SyntaxKind::Identifier, &mut children)
+            && self.eat(SyntaxKind::Colon, &mut children)
+            && self.eat(SyntaxKind::PrimitiveType, &mut children)
+            && self.eat(SyntaxKind::OpenBrace, &mut children);
+
+        while ok && !matches!(self.peek(), Some(SyntaxKind::CloseBrace) | None) {
+            let mut variant = Vec::new();
+            // `@composite`
+            ok = !self.eat(SyntaxKind::At, &mut variant)
+                || self.eat(SyntaxKind::Identifier, &mut variant);
+            ok = ok && self.eat(SyntaxKind::Identifier, &mut variant);
+            if ok && self.eat(SyntaxKind::Assign, &mut variant) {
+                ok = self.eat_expression(&mut variant);
+            }
+            ok = ok && self.eat(SyntaxKind::Comma, &mut variant);
+            if !variant.is_empty() {
+                let kind = if ok {
+                    SyntaxKind::Variant
+                } else {
+                    SyntaxKind::Error
+                };
+                children.push(SyntaxElement::Node(node(kind, variant)));
+            }
+        }
+
+        let ok = ok && self.eat(SyntaxKind::CloseBrace, &mut children);
+        self.finish(SyntaxKind::Enum, children, ok)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SOURCE: &str = "// Header comment\nendian = big\n\n@open enum Status : u8 {\n    Active = 1, // on\n    Inactive,\n}\n\nmessage User {\n    id   u64,\n    name u8 : 7,\n    tags vec<Status, 4>,\n    key  bytes[8],\n}\n\nflags Access : u8 {\n    Read = 1,\n    Write,\n    @composite All = 3,\n}\n";
+
+    #[test]
+    fn test_lossless_round_trip() {
+        let tree = SyntaxTree::parse(SOURCE);
+        assert_eq!(tree.to_string(), SOURCE);
+        assert_eq!(tree.root().range, 0..SOURCE.len());
+    }
+
+    #[test]
+    fn test_tree_structure() {
+        let tree = SyntaxTree::parse(SOURCE);
+        let kinds: Vec<SyntaxKind> = tree.root().nodes().map(|n| n.kind).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                SyntaxKind::EndianDirective,
+                SyntaxKind::Enum,
+                SyntaxKind::Message,
+                SyntaxKind::Enum
+            ]
+        );
+
+        let message = tree.root().nodes().nth(2).unwrap();
+        let name = message.token(SyntaxKind::Identifier).unwrap();
+        assert_eq!(tree.text(name.range.clone()), "User");
+
+        let fields: Vec<&str> = message
+            .nodes()
+            .map(|f| tree.text(f.token(SyntaxKind::Identifier).unwrap().range.clone()))
+            .collect();
+        assert_eq!(fields, vec!["id", "name", "tags", "key"]);
+
+        let comments = tree
+            .root()
+            .tokens()
+            .filter(|t| t.kind == SyntaxKind::Comment)
+            .count();
+        assert_eq!(comments, 1);
+    }
+
+    #[test]
+    fn test_versions() {
+        let source = "version = 2\nstrict = true\nbool_width = 1\nalign = xcdr2\n@deprecated(\"use N\") @cfg(not(feature = \"lite\")) message M {\n    \
+             @since(2) @removed(3) a u8,\n    @deprecated(\"use a\") version u8,\n    \
+             @cfg(any(feature = \"gps\", all())) fix u8,\n}\n";
+        let tree = SyntaxTree::parse(source);
+        assert_eq!(tree.to_string(), source);
+        assert!(!tree.root().has_errors());
+
+        let kinds: Vec<SyntaxKind> = tree.root().nodes().map(|n| n.kind).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                SyntaxKind::VersionDirective,
+                SyntaxKind::StrictDirective,
+                SyntaxKind::BoolWidthDirective,
+                SyntaxKind::AlignDirective,
+                SyntaxKind::Message
+            ]
+        );
+        let fields: Vec<SyntaxKind> = tree
+            .root()
+            .nodes()
+            .nth(4)
+            .unwrap()
+            .nodes()
+            .map(|n| n.kind)
+            .collect();
+        assert_eq!(fields, vec![SyntaxKind::Field; 3]);
+        assert!(
+            SyntaxTree::parse("@cfg(feature) struct S { }")
+                .root()
+                .has_errors()
+        );
+    }
+
+    #[test]
+    fn test_conditions() {
+        let source = "message M {\n    kind Kind,\n    body Body if kind == Kind.Data,\n    \
+                      n u8 : 3 if kind != 2,\n}\n";
+        let tree = SyntaxTree::parse(source);
+        assert_eq!(tree.to_string(), source);
+        assert!(!tree.root().has_errors());
+
+        let message = tree.root().nodes().next().unwrap();
+        let conditions: Vec<SyntaxKind> = message
+            .nodes()
+            .flat_map(|field| field.nodes())
+            .map(|n| n.kind)
+            .collect();
+        assert_eq!(
+            conditions,
+            vec![SyntaxKind::Condition, SyntaxKind::Condition]
+        );
+
+        let tree = SyntaxTree::parse("message M { body Body if kind = 1, }");
+        assert!(tree.root().has_errors());
+    }
+
+    #[test]
+    fn test_expressions() {
+        let source = "const WIDTH = 1 << 2 // bits\n\
+                      flags F : u32 { A = 1 << 4, B = (WIDTH + 1) * 2, }\n\
+                      struct S { f u8 : WIDTH - 1, k bytes[WIDTH * 2], v vec<u8, 16 >> WIDTH>, }\n";
+        let tree = SyntaxTree::parse(source);
+        assert_eq!(tree.to_string(), source);
+        assert!(!tree.root().has_errors());
+        let kinds: Vec<SyntaxKind> = tree.root().nodes().map(|n| n.kind).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                SyntaxKind::ConstDeclaration,
+                SyntaxKind::Enum,
+                SyntaxKind::Struct
+            ]
+        );
+
+        let tree = SyntaxTree::parse("const N = 1 <<\nstruct S { f u8, }");
+        let kinds: Vec<SyntaxKind> = tree.root().nodes().map(|n| n.kind).collect();
+        assert_eq!(kinds, vec![SyntaxKind::Error, SyntaxKind::Struct]);
+    }
+
+    #[test]
+    fn test_message_ids() {
+        let source = "@id(N + 1) message M { }\n@open enum E : u8 { A, }\n@id() message B { }\n\
+                      @max_size(8) @packed_bits struct S { }\n";
+        let tree = SyntaxTree::parse(source);
+        assert_eq!(tree.to_string(), source);
+        let kinds: Vec<SyntaxKind> = tree.root().nodes().map(|n| n.kind).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                SyntaxKind::Message,
+                SyntaxKind::Enum,
+                SyntaxKind::Error,
+                SyntaxKind::Message,
+                SyntaxKind::Struct
+            ]
+        );
+    }
+
+    #[test]
+    fn test_error_recovery() {
+        let source = "struct A { a u8 } # message B { b u8, }";
+        let tree = SyntaxTree::parse(source);
+        assert_eq!(tree.to_string(), source);
+
+        let kinds: Vec<SyntaxKind> = tree.root().nodes().map(|n| n.kind).collect();
+        assert_eq!(kinds, vec![SyntaxKind::Error, SyntaxKind::Message]);
+    }
+
+    #[test]
+    fn test_non_ascii_comments() {
+        let source = "// café\nstruct A { a u8, }";
+        let tree = SyntaxTree::parse(source);
+        assert_eq!(tree.to_string(), source);
+        assert_eq!(tree.root().nodes().next().unwrap().kind, SyntaxKind::Struct);
+
+        let tree = SyntaxTree::parse("struct Café { a u8, }");
+        assert_eq!(tree.to_string(), "struct Café { a u8, }");
+        assert!(tree.root().has_errors());
+    }
+
+    #[test]
+    fn test_incremental_edit() {
+        let mut tree = SyntaxTree::parse(SOURCE);
+        let offset = SOURCE.find("Inactive").unwrap();
+
+        // Renaming a variant only reparses the enum.
+        assert!(tree.edit(offset..offset + "Inactive".len(), "Disabled"));
+        assert_eq!(
+            tree,
+            SyntaxTree::parse(&SOURCE.replace("Inactive", "Disabled"))
+        );
+
+        // Growing a definition shifts everything after it.
+        let offset = tree.source().find("Disabled").unwrap();
+        assert!(tree.edit(offset..offset, "Very"));
+        assert_eq!(
+            tree,
+            SyntaxTree::parse(&SOURCE.replace("Inactive", "VeryDisabled"))
+        );
+
+        // An edit that breaks the definition falls back to a full reparse.
+        let offset = tree.source().find('{').unwrap();
+        assert!(!tree.edit(offset..offset + 1, ""));
+        assert_eq!(tree.to_string(), tree.source());
+        assert!(tree.root().has_errors());
+    }
+}
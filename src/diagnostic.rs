@@ -0,0 +1,641 @@
+use std::{
+    error::Error,
+    fmt,
+    io::{self, IsTerminal},
+    sync::Arc,
+};
+
+use crate::{
+    ColorChoice, color, json,
+    span::{LineIndex, Position, Span},
+};
+
+/// Stable error codes attached to diagnostics produced by the compiler.
+pub mod codes {
+    /// The source file contains no tokens.
+    pub const EMPTY_SOURCE: &str = "E0001";
+    /// A token did not match what the grammar expects at this point.
+    pub const UNEXPECTED_TOKEN: &str = "E0002";
+    /// The endianness directive was given more than once or with an invalid value.
+    pub const INVALID_ENDIANNESS: &str = "E0003";
+    /// Two definitions share the same name.
+    pub const DUPLICATE_DEFINITION: &str = "E0004";
+    /// A bit-field size is missing or wider than its underlying type, a bit-field narrows a
+    /// float or gives a bool other than one bit, or a run of bit-fields ends off a byte
+    /// boundary in a definition not marked `@packed_bits`.
+    pub const INVALID_BIT_FIELD: &str = "E0005";
+    /// A field refers to a type that is not defined.
+    pub const UNDEFINED_TYPE: &str = "E0006";
+    /// Definitions contain each other, so their sizes cannot be resolved.
+    pub const CIRCULAR_DEPENDENCY: &str = "E0007";
+    /// The source contains a character or literal that cannot be tokenized.
+    pub const INVALID_TOKEN: &str = "E0008";
+    /// A bounded vector has a zero capacity, or a vector, fixed-point, or byte-array element type.
+    pub const INVALID_VECTOR: &str = "E0009";
+    /// A fixed-point type has a base that is not an integer, or a zero scale.
+    pub const INVALID_FIXED_POINT: &str = "E0010";
+    /// A byte array has a zero length.
+    pub const INVALID_BYTE_ARRAY: &str = "E0011";
+    /// A flags enum has a signed underlying type, a flag that is not a distinct power of two,
+    /// or a composite that includes undeclared bits.
+    pub const INVALID_FLAGS: &str = "E0012";
+    /// An attribute is unknown or not allowed where it is written.
+    pub const INVALID_ATTRIBUTE: &str = "E0013";
+    /// The version directive is repeated or zero, or a field's `@since` or `@removed` version
+    /// is out of range.
+    pub const INVALID_VERSION: &str = "E0014";
+    /// A field's condition reads a field that does not exist or cannot be compared, or
+    /// compares it with a value of another type.
+    pub const INVALID_CONDITION: &str = "E0015";
+    /// A constant expression refers to an undefined constant, or its value does not fit in a
+    /// `u64`.
+    pub const INVALID_EXPRESSION: &str = "E0016";
+    /// A message's `@id` is out of range or already belongs to another message.
+    pub const INVALID_MESSAGE_ID: &str = "E0017";
+    /// Two definitions, or two fields of one definition, have names that generated code cannot
+    /// tell apart: they differ only in case, or convert to the same name in a target.
+    pub const NAME_CONFLICT: &str = "E0018";
+    /// A definition is over a resource limit, such as the largest message size, set for the
+    /// compilation.
+    pub const LIMIT_EXCEEDED: &str = "E0019";
+    /// The `strict` directive is repeated or has a value other than `true` or `false`, or a
+    /// strict module has a layout surprise: a bit-field that narrows a signed type, an enum
+    /// wider than its values need, or bit-fields that leave padding bits, even under
+    /// `@packed_bits`.
+    pub const STRICT_LAYOUT: &str = "E0020";
+    /// A definition is too large for the compiler to lay out: its size or a field's offset, in
+    /// bytes or bits, overflows a `usize`.
+    pub const SCHEMA_TOO_LARGE: &str = "E0021";
+    /// The `align` directive is repeated or names no known rules, or a module aligned for CDR
+    /// has a field CDR cannot lay out: a vector, or bit-fields whose container is not 1, 2,
+    /// 4, or 8 bytes.
+    pub const INVALID_ALIGNMENT: &str = "E0022";
+    /// An enum variant has a value that does not fit the enum's underlying type.
+    pub const INVALID_ENUM_VALUE: &str = "E0023";
+    /// A code generator could not produce output for the module.
+    pub const CODEGEN: &str = "E0100";
+    /// Generated output could not be written.
+    pub const IO: &str = "E0101";
+    /// A name reserved in the target language cannot be renamed without taking another name.
+    pub const NAME_COLLISION: &str = "E0102";
+    /// A value does not match the schema type it is encoded or decoded as.
+    pub const VALUE_MISMATCH: &str = "E0200";
+    /// A definition file in another format, such as MAVLink XML, is malformed or holds
+    /// something Onyx cannot represent.
+    pub const INVALID_IMPORT: &str = "E0300";
+    /// An invariant inside the compiler was violated.
+    pub const INTERNAL: &str = "E0900";
+}
+
+/// The stage of compilation a diagnostic comes from, derived from its [code](codes).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ErrorKind {
+    /// The source could not be tokenized or does not follow the grammar.
+    Syntax,
+    /// A name refers to a type or constant that is missing, duplicated, or circular.
+    Resolution,
+    /// The schema parses and resolves but breaks a rule, such as a bit-field wider than its type.
+    Validation,
+    /// A code generator could not produce output.
+    Codegen,
+    /// A file could not be read or written.
+    Io,
+    /// A value does not match its schema type.
+    Value,
+    /// A definition file in another format could not be imported.
+    Import,
+    /// An invariant inside the compiler was violated.
+    Internal,
+    /// Any other diagnostic, such as a lint or one without a code.
+    Other,
+}
+
+impl ErrorKind {
+    /// Returns the kind of diagnostics with the given code.
+    pub fn of(code: Option<&str>) -> Self {
+        match code {
+            Some(codes::EMPTY_SOURCE | codes::UNEXPECTED_TOKEN | codes::INVALID_TOKEN) => {
+                ErrorKind::Syntax
+            }
+            Some(
+                codes::DUPLICATE_DEFINITION
+                | codes::UNDEFINED_TYPE
+                | codes::CIRCULAR_DEPENDENCY
+                | codes::INVALID_EXPRESSION
+                | codes::NAME_CONFLICT,
+            ) => ErrorKind::Resolution,
+            Some(
+                codes::INVALID_ENDIANNESS
+                | codes::INVALID_BIT_FIELD
+                | codes::INVALID_VECTOR
+                | codes::INVALID_FIXED_POINT
+                | codes::INVALID_BYTE_ARRAY
+                | codes::INVALID_FLAGS
+                | codes::INVALID_ATTRIBUTE
+                | codes::INVALID_VERSION
+                | codes::INVALID_CONDITION
+                | codes::INVALID_MESSAGE_ID
+                | codes::LIMIT_EXCEEDED
+                | codes::STRICT_LAYOUT
+                | codes::SCHEMA_TOO_LARGE
+                | codes::INVALID_ALIGNMENT
+                | codes::INVALID_ENUM_VALUE,
+            ) => ErrorKind::Validation,
+            Some(codes::CODEGEN | codes::NAME_COLLISION) => ErrorKind::Codegen,
+            Some(codes::IO) => ErrorKind::Io,
+            Some(codes::VALUE_MISMATCH) => ErrorKind::Value,
+            Some(codes::INVALID_IMPORT) => ErrorKind::Import,
+            Some(codes::INTERNAL) => ErrorKind::Internal,
+            _ => ErrorKind::Other,
+        }
+    }
+}
+
+/// How serious a diagnostic is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[non_exhaustive]
+pub enum Severity {
+    /// Reported but does not fail compilation.
+    Warning,
+    /// Fails compilation.
+    Error,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Severity::Warning => write!(f, "warning"),
+            Severity::Error => write!(f, "error"),
+        }
+    }
+}
+
+/// A message reported by the compiler, with an optional location in the source.
+///
+/// Diagnostics are built with [`Diagnostic::error`] or [`Diagnostic::warning`] and the
+/// `with_*` methods. A diagnostic points at a [`Span`] of the source; attaching the source
+/// with [`Diagnostic::with_source`] resolves the span to a line and column and captures the
+/// excerpt shown by `Display`. [`Diagnostic::render`] renders against any source text.
+///
+/// `Display` is plain text; a [`Renderer`] with color enabled adds ANSI escape codes for a
+/// terminal. An underlying error, such as the [`std::io::Error`] behind a failed read, is kept
+/// with [`Diagnostic::with_cause`] and returned by [`Error::source`].
+///
+/// The contents are boxed so that `Result<T, Diagnostic>` stays small on the happy path.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic(Box<Inner>);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Inner {
+    code: Option<&'static str>,
+    severity: Severity,
+    message: String,
+    span: Option<Span>,
+    notes: Vec<String>,
+    help: Vec<String>,
+    /// The start and end of `span`, resolved when the source was attached.
+    location: Option<(Position, Position)>,
+    /// The source excerpt rendered when the source was attached.
+    snippet: Option<Excerpt>,
+    cause: Option<Cause>,
+}
+
+/// The error a diagnostic was caused by. Causes compare by their messages.
+#[derive(Clone)]
+struct Cause(Arc<dyn Error + Send + Sync>);
+
+impl fmt::Debug for Cause {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(&self.0, f)
+    }
+}
+
+impl PartialEq for Cause {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.to_string() == other.0.to_string()
+    }
+}
+
+impl Eq for Cause {}
+
+/// The lines of source around a span, with the span underlined.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Excerpt {
+    /// The lines up to and including the line where the span starts.
+    before: String,
+    /// The column of the underline.
+    column: usize,
+    /// The number of characters underlined.
+    width: usize,
+    /// The lines after the underline, each preceded by a newline.
+    after: String,
+}
+
+impl Excerpt {
+    /// Renders the excerpt, with the underline in red if `color` is set.
+    pub(crate) fn render(&self, color: bool) -> String {
+        format!(
+            "{}\n{}{}{}",
+            self.before,
+            " ".repeat(self.column),
+            paint(color, &"^".repeat(self.width)),
+            self.after
+        )
+    }
+}
+
+/// Returns `text` in red if `color` is set.
+fn paint(color: bool, text: &str) -> String {
+    match color {
+        true => format!("{}{text}{}", color::RED, color::END),
+        false => text.to_string(),
+    }
+}
+
+impl Diagnostic {
+    /// Creates a diagnostic with the given severity and message.
+    pub fn new(severity: Severity, message: impl Into<String>) -> Self {
+        Diagnostic(Box::new(Inner {
+            code: None,
+            severity,
+            message: message.into(),
+            span: None,
+            notes: Vec::new(),
+            help: Vec::new(),
+            location: None,
+            snippet: None,
+            cause: None,
+        }))
+    }
+
+    /// Creates an error diagnostic.
+    pub fn error(message: impl Into<String>) -> Self {
+        Self::new(Severity::Error, message)
+    }
+
+    /// Creates a warning diagnostic.
+    pub fn warning(message: impl Into<String>) -> Self {
+        Self::new(Severity::Warning, message)
+    }
+
+    /// Returns the stable error code (see [`codes`]), if any.
+    pub fn code(&self) -> Option<&'static str> {
+        self.0.code
+    }
+
+    /// Returns the stage of compilation the diagnostic comes from, derived from its code.
+    pub fn kind(&self) -> ErrorKind {
+        ErrorKind::of(self.0.code)
+    }
+
+    /// Returns how serious the diagnostic is.
+    pub fn severity(&self) -> Severity {
+        self.0.severity
+    }
+
+    /// Returns the primary message.
+    pub fn message(&self) -> &str {
+        &self.0.message
+    }
+
+    /// Returns the byte range in the source where the problem was found.
+    pub fn span(&self) -> Option<Span> {
+        self.0.span
+    }
+
+    /// Returns the line and column where the problem was found, if the source was attached.
+    pub fn position(&self) -> Option<Position> {
+        self.0.location.map(|(start, _)| start)
+    }
+
+    /// Returns additional context about the problem.
+    pub fn notes(&self) -> &[String] {
+        &self.0.notes
+    }
+
+    /// Returns suggestions for fixing the problem.
+    pub fn help(&self) -> &[String] {
+        &self.0.help
+    }
+
+    /// Sets the error code.
+    pub fn with_code(mut self, code: &'static str) -> Self {
+        self.0.code = Some(code);
+        self
+    }
+
+    /// Sets the byte range in the source where the problem was found.
+    pub fn with_span(mut self, span: Span) -> Self {
+        self.0.span = Some(span);
+        self
+    }
+
+    /// Adds a note.
+    pub fn with_note(mut self, note: impl Into<String>) -> Self {
+        self.0.notes.push(note.into());
+        self
+    }
+
+    /// Adds a help suggestion.
+    pub fn with_help(mut self, help: impl Into<String>) -> Self {
+        self.0.help.push(help.into());
+        self
+    }
+
+    /// Records the error that caused the diagnostic, returned by [`Error::source`].
+    pub fn with_cause(mut self, cause: impl Error + Send + Sync + 'static) -> Self {
+        self.0.cause = Some(Cause(Arc::new(cause)));
+        self
+    }
+
+    /// Resolves the diagnostic's span against `source` and captures the surrounding excerpt,
+    /// so that `Display` can show them without access to the source.
+    pub fn with_source(mut self, source: &str) -> Self {
+        if let Some(span) = self.0.span {
+            let index = LineIndex::new(source);
+            self.0.location = Some(locate(&index, span));
+            self.0.snippet = Some(snippet(&index, span));
+        }
+        self
+    }
+
+    /// Renders the diagnostic with the excerpt of `source` around its span.
+    pub fn render(&self, source: &str) -> String {
+        self.clone().with_source(source).to_string()
+    }
+
+    /// Returns a one-line description without colors or source excerpt, e.g.
+    /// `error[E0006]: custom type 'B' not defined at position 1:13`.
+    pub fn summary(&self) -> String {
+        self.describe(&self.0.severity.to_string())
+    }
+
+    /// Renders the diagnostic as a single line of JSON, for editors and CI annotation tools.
+    ///
+    /// Lines and columns are 1-based and only present once the source has been attached;
+    /// `end_column` is exclusive. Byte offsets are 0-based. Location fields are `null` when
+    /// unknown. `file` is included when given.
+    pub fn to_json(&self, file: Option<&str>) -> String {
+        let number = |n: Option<usize>| n.map_or("null".to_string(), |n| n.to_string());
+        let location = self.0.location;
+        format!(
+            r#"{{"file":{},"severity":{},"code":{},"message":{},"line":{},"column":{},"end_line":{},"end_column":{},"byte_start":{},"byte_end":{},"notes":{},"help":{}}}"#,
+            file.map_or("null".to_string(), json::string),
+            json::string(&self.0.severity.to_string()),
+            self.0.code.map_or("null".to_string(), json::string),
+            json::string(&self.0.message),
+            number(location.map(|(start, _)| start.line + 1)),
+            number(location.map(|(start, _)| start.column + 1)),
+            number(location.map(|(_, end)| end.line + 1)),
+            number(location.map(|(_, end)| end.column + 1)),
+            number(self.0.span.map(|span| span.start)),
+            number(self.0.span.map(|span| span.end)),
+            json::string_array(&self.0.notes),
+            json::string_array(&self.0.help),
+        )
+    }
+
+    /// Writes the header, excerpt, notes, and help, with ANSI colors if `color` is set.
+    fn write(&self, f: &mut impl fmt::Write, color: bool) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            self.describe(&paint(color, &self.0.severity.to_string()))
+        )?;
+        if let Some(snippet) = &self.0.snippet {
+            write!(f, "\n{}", snippet.render(color))?;
+        }
+        write!(f, "{}", self.footer())
+    }
+
+    fn describe(&self, severity: &str) -> String {
+        let mut header = severity.to_string();
+        if let Some(code) = self.0.code {
+            header.push_str(&format!("[{code}]"));
+        }
+        header.push_str(&format!(": {}", self.0.message));
+        match (self.0.location, self.0.span) {
+            (Some((start, _)), _) => header.push_str(&format!(" at position {start}")),
+            (None, Some(span)) => {
+                header.push_str(&format!(" at bytes {}..{}", span.start, span.end))
+            }
+            (None, None) => {}
+        }
+        header
+    }
+
+    fn footer(&self) -> String {
+        let mut footer = String::new();
+        for note in &self.0.notes {
+            footer.push_str(&format!("\n  = note: {note}"));
+        }
+        for help in &self.0.help {
+            footer.push_str(&format!("\n  = help: {help}"));
+        }
+        footer
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.write(f, false)
+    }
+}
+
+impl Error for Diagnostic {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        self.0
+            .cause
+            .as_ref()
+            .map(|cause| &*cause.0 as &(dyn Error + 'static))
+    }
+}
+
+/// Renders diagnostics as text, optionally colored for a terminal.
+///
+/// ```
+/// use onyx::diagnostic::{Diagnostic, Renderer};
+///
+/// let diagnostic = Diagnostic::error("custom type 'B' not defined");
+/// assert_eq!(Renderer::new().render(&diagnostic), diagnostic.to_string());
+/// assert!(Renderer::new().with_color(true).render(&diagnostic).contains("\x1b["));
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Renderer {
+    color: bool,
+}
+
+impl Renderer {
+    /// Creates a renderer of plain text, the same as `Display`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Highlights the severity and the underline with ANSI escape codes if `color` is set.
+    pub fn with_color(mut self, color: bool) -> Self {
+        self.color = color;
+        self
+    }
+
+    /// Colors if `choice` allows it for standard error, where diagnostics are usually printed.
+    pub fn with_color_choice(self, choice: ColorChoice) -> Self {
+        self.with_color(choice.enabled(io::stderr().is_terminal()))
+    }
+
+    /// Renders a diagnostic, with the source excerpt if the source was attached.
+    pub fn render(&self, diagnostic: &Diagnostic) -> String {
+        let mut out = String::new();
+        diagnostic.write(&mut out, self.color).unwrap();
+        out
+    }
+}
+
+/// Resolves the start and end of a span to positions.
+fn locate(index: &LineIndex, span: Span) -> (Position, Position) {
+    (index.position(span.start), index.position(span.end))
+}
+
+/// Captures the lines around `span` with it underlined.
+///
+/// Shows up to four lines before and after the line containing the start of the span. Spans
+/// that continue onto later lines are underlined to the end of their first line.
+pub(crate) fn snippet(index: &LineIndex, span: Span) -> Excerpt {
+    let (start, end) = locate(index, span);
+    let first = start.line.saturating_sub(4);
+    let mut last = (start.line + 4).min(index.line_count() - 1);
+    // A trailing newline leaves an empty final line; don't show it.
+    if last > start.line && index.line(last).is_empty() && last == index.line_count() - 1 {
+        last -= 1;
+    }
+
+    let before = (first..=start.line)
+        .map(|line| index.line(line))
+        .collect::<Vec<_>>()
+        .join("\n");
+    let end_column = if end.line == start.line {
+        end.column
+    } else {
+        index.line(start.line).chars().count()
+    };
+    let after = (start.line + 1..=last)
+        .map(|line| format!("\n{}", index.line(line)))
+        .collect();
+    Excerpt {
+        before,
+        column: start.column,
+        width: end_column.saturating_sub(start.column).max(1),
+        after,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display_includes_code_notes_and_help() {
+        let diagnostic = Diagnostic::error("custom type 'B' not defined")
+            .with_code(codes::UNDEFINED_TYPE)
+            .with_note("referenced by field 'hdr'")
+            .with_help("define 'B' or use a primitive type");
+
+        assert_eq!(
+            diagnostic.to_string(),
+            "error[E0006]: custom type 'B' not defined\n  = note: referenced by field 'hdr'\n  = help: define 'B' or use a primitive type"
+        );
+    }
+
+    #[test]
+    fn test_renderer_colors() {
+        let source = "struct A {\n    hdr B,\n}";
+        let diagnostic = Diagnostic::error("custom type 'B' not defined")
+            .with_span(Span::new(19, 20))
+            .with_source(source);
+
+        assert_eq!(Renderer::new().render(&diagnostic), diagnostic.to_string());
+        let colored = Renderer::new().with_color(true).render(&diagnostic);
+        assert!(colored.starts_with(&format!("{}error{}:", color::RED, color::END)));
+        assert!(colored.contains(&format!("        {}^{}\n}}", color::RED, color::END)));
+    }
+
+    #[test]
+    fn test_kind_and_cause() {
+        let cause = std::io::Error::new(std::io::ErrorKind::NotFound, "no such file");
+        let diagnostic = Diagnostic::error("failed to read a.onyx: no such file")
+            .with_code(codes::IO)
+            .with_cause(cause);
+
+        assert_eq!(diagnostic.kind(), ErrorKind::Io);
+        assert_eq!(diagnostic.source().unwrap().to_string(), "no such file");
+        assert!(Diagnostic::error("e").source().is_none());
+        assert_eq!(
+            Diagnostic::error("e")
+                .with_code(codes::UNEXPECTED_TOKEN)
+                .kind(),
+            ErrorKind::Syntax
+        );
+        assert_eq!(
+            Diagnostic::error("e")
+                .with_code(codes::UNDEFINED_TYPE)
+                .kind(),
+            ErrorKind::Resolution
+        );
+        assert_eq!(Diagnostic::error("e").kind(), ErrorKind::Other);
+    }
+
+    #[test]
+    fn test_render_underlines_span() {
+        let source = "struct A {\n    hdr B,\n}";
+        let diagnostic =
+            Diagnostic::error("custom type 'B' not defined").with_span(Span::new(19, 20));
+        assert!(diagnostic.summary().ends_with("at bytes 19..20"));
+
+        let rendered = diagnostic.render(source);
+        assert!(rendered.contains("at position 2:9"));
+        assert!(rendered.contains("    hdr B,\n        ^\n}"));
+        assert_eq!(rendered, diagnostic.with_source(source).to_string());
+    }
+
+    #[test]
+    fn test_render_multi_byte_line() {
+        // The caret lines up under 'B' even though the line starts with multi-byte characters.
+        let source = "/*é*/ hdr B,";
+        let span = Span::new(source.find('B').unwrap(), source.find('B').unwrap() + 1);
+        let rendered = Diagnostic::error("e").with_span(span).render(source);
+
+        assert!(rendered.contains("at position 1:11"));
+        assert!(rendered.ends_with(&format!("\n{}^", " ".repeat(10))));
+    }
+
+    #[test]
+    fn test_to_json() {
+        let diagnostic = Diagnostic::error("custom type \"B\" not defined")
+            .with_code(codes::UNDEFINED_TYPE)
+            .with_span(Span::new(19, 20))
+            .with_help("define it")
+            .with_source("struct A {\n    hdr B,\n}");
+
+        assert_eq!(
+            diagnostic.to_json(Some("a.onyx")),
+            r#"{"file":"a.onyx","severity":"error","code":"E0006","message":"custom type \"B\" not defined","line":2,"column":9,"end_line":2,"end_column":10,"byte_start":19,"byte_end":20,"notes":[],"help":["define it"]}"#
+        );
+        assert_eq!(
+            Diagnostic::warning("w").to_json(None),
+            r#"{"file":null,"severity":"warning","code":null,"message":"w","line":null,"column":null,"end_line":null,"end_column":null,"byte_start":null,"byte_end":null,"notes":[],"help":[]}"#
+        );
+    }
+
+    #[test]
+    fn test_snippet_limits_context() {
+        let source = (0..12)
+            .map(|i| format!("line{i}"))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let start = source.find("line6").unwrap();
+        let snippet = snippet(&LineIndex::new(&source), Span::new(start, start + 5)).render(false);
+
+        assert!(snippet.starts_with("line2\n"));
+        assert!(snippet.ends_with("line10"));
+    }
+}
@@ -0,0 +1,1346 @@
+//! Schema-driven encoding without generated code.
+//!
+//! [`encode`] and [`decode`] convert between [`Value`]s and wire bytes by walking a resolved
+//! module at runtime. They are slow and are written to follow the wire format as literally as
+//! possible, so they serve as the reference that generated backends are tested against:
+//!
+//! - Fields are laid out in declaration order, without padding.
+//! - A run of consecutive bit-fields shares one container of `ceil(bits / 8)` bytes, read as an
+//!   unsigned integer in the module's endianness. The first field occupies the least significant
+//!   bits. Signed bit-fields are sign-extended when decoded.
+//! - Other primitives take their full width in the module's endianness; a `bool` is one byte
+//!   that is true when nonzero.
+//! - An enum is encoded as its underlying type, and a struct or message inline. Decoding a
+//!   value that no variant declares is an error, unless the enum is `@open` or `flags`.
+//! - A `vec<T, N>` is its length, as the narrowest unsigned integer that holds `N`, followed by
+//!   room for `N` elements. Unused element slots are zeroed when encoded and ignored when
+//!   decoded.
+//! - A `fixed<T, S>` is its raw integer `T`, and its [`Value`] is that raw integer, unscaled.
+//! - A `uuid` or `bytes[N]` is its bytes in order, regardless of endianness.
+//! - A field with a condition keeps its slot. When the condition does not hold, the field is
+//!   left out of the record, and its slot is zeroed when encoded and ignored when decoded.
+//!
+//! [`from_json`] builds a [`Value`] from a JSON document, for writing test fixtures by hand.
+//!
+//! [`DynamicMessage`] reads and writes single fields of a buffer in place, for gateways and
+//! tools that handle schemas they were not compiled with.
+//!
+//! ```rust
+//! use onyx::dynamic::{DynamicMessage, Value};
+//! use onyx::parser::Parser;
+//!
+//! let module = Parser::new("struct Header { version u8 : 4, flags u8 : 4, }\n\
+//!                           message User { id u32, header Header, }")
+//!     .and_then(|p| p.parse_module())
+//!     .unwrap();
+//! let mut buf = [0; 5];
+//! let mut user = DynamicMessage::new(&module, "User", &mut buf).unwrap();
+//! user.set("id", &Value::Unsigned(5)).unwrap();
+//! user.set("header.flags", &Value::Unsigned(3)).unwrap();
+//! assert_eq!(user.get("header.flags").unwrap(), Some(Value::Unsigned(3)));
+//! ```
+
+use crate::{
+    ast::{
+        Alignment, Definition, EnumDef, Field, MessageDef, OnyxModule, PrimitiveType, StructDef,
+        Type,
+    },
+    diagnostic::{Diagnostic, codes},
+    json,
+    symbol::Symbol,
+    wire,
+};
+
+/// A value of an Onyx type.
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub enum Value {
+    /// A `bool`.
+    Bool(bool),
+    /// An unsigned integer, or an enum with an unsigned underlying type.
+    Unsigned(u64),
+    /// A signed integer, or an enum with a signed underlying type.
+    Signed(i64),
+    /// An `f32`.
+    F32(f32),
+    /// An `f64`.
+    F64(f64),
+    /// A struct or message: its fields' names and values, in declaration order. Fields whose
+    /// condition does not hold are left out.
+    Record(Vec<(Symbol, Value)>),
+    /// The elements in use of a bounded vector.
+    List(Vec<Value>),
+    /// A `uuid` or `bytes[N]`.
+    Bytes(Vec<u8>),
+}
+
+/// Encodes `value` as the definition `name`.
+pub fn encode(module: &OnyxModule, name: &str, value: &Value) -> Result<Vec<u8>, Diagnostic> {
+    let mut out = Vec::new();
+    Codec { module }.encode_definition(lookup(module, name)?, value, &mut out)?;
+    Ok(out)
+}
+
+/// Decodes the definition `name` from `bytes`, which must be exactly its wire size.
+pub fn decode(module: &OnyxModule, name: &str, bytes: &[u8]) -> Result<Value, Diagnostic> {
+    let def = lookup(module, name)?;
+    let mut rest = bytes;
+    let value = Codec { module }.decode_definition(def, &mut rest)?;
+    if !rest.is_empty() {
+        return Err(mismatch(format!(
+            "expected {} bytes for '{name}', found {}",
+            bytes.len() - rest.len(),
+            bytes.len()
+        )));
+    }
+    Ok(value)
+}
+
+/// Returns the wire size of the definition `name` in bytes.
+pub fn wire_size(module: &OnyxModule, name: &str) -> Result<usize, Diagnostic> {
+    Ok(Codec { module }.definition_size(lookup(module, name)?))
+}
+
+/// Reads a value of the definition `name` from a JSON document, to pass to [`encode`].
+///
+/// Structs and messages are objects keyed by field name, vectors are arrays, and `bool`s are
+/// `true` or `false`. Other fields take the JSON a person would write rather than the raw
+/// [`Value`]:
+///
+/// - An integer is a number, read exactly from its digits, or a string of decimal digits. A
+///   number past 2^53 written with a fraction or exponent is an error, as it may be rounded.
+/// - An enum is a variant name or a number. A flags enum may join names with `|`.
+/// - A `fixed<T, S>` is its scaled value, such as `1.5`, rounded to the nearest raw integer.
+/// - A `uuid` or `bytes[N]` is a string of hex digits or an array of byte values. A
+///   `bytes[N]` shorter than `N` is padded with zeros.
+///
+/// A field that is left out or `null` is zero, and a vector empty. Conditional fields are
+/// included when their condition holds, and must be left out when it does not.
+pub fn from_json(module: &OnyxModule, name: &str, document: &str) -> Result<Value, Diagnostic> {
+    let document = json::parse(document).map_err(|e| mismatch(format!("invalid JSON: {e}")))?;
+    Codec { module }.json_definition(lookup(module, name)?, &document, name)
+}
+
+fn lookup<'m>(module: &'m OnyxModule, name: &str) -> Result<&'m Definition, Diagnostic> {
+    module
+        .definitions
+        .get(&Symbol::intern(name))
+        .ok_or_else(|| mismatch(format!("no definition named '{name}'")))
+}
+
+fn mismatch(message: impl Into<String>) -> Diagnostic {
+    Diagnostic::error(message).with_code(codes::VALUE_MISMATCH)
+}
+
+/// A struct or message encoded in a caller's buffer, read and written one field at a time.
+///
+/// Fields are named by their path from the outermost definition, such as `header.version`.
+/// Only the bytes of the named field are read or written, so a buffer may be filled in any
+/// order, and a closed enum field holding an undeclared value fails only when it is read.
+#[derive(Debug)]
+pub struct DynamicMessage<'m, 'b> {
+    module: &'m OnyxModule,
+    name: Symbol,
+    fields: &'m [Field],
+    buf: &'b mut [u8],
+}
+
+impl<'m, 'b> DynamicMessage<'m, 'b> {
+    /// Wraps `buf`, which holds the struct or message `name` in its first bytes.
+    pub fn new(module: &'m OnyxModule, name: &str, buf: &'b mut [u8]) -> Result<Self, Diagnostic> {
+        let def = lookup(module, name)?;
+        let fields = match def {
+            Definition::Struct(s) => &s.fields,
+            Definition::Message(m) => &m.fields,
+            Definition::Enum(_) => {
+                return Err(mismatch(format!("'{name}' is not a struct or message")));
+            }
+        };
+        let size = Codec { module }.definition_size(def);
+        if buf.len() < size {
+            return Err(mismatch(format!(
+                "expected at least {size} bytes for '{name}', found {}",
+                buf.len()
+            )));
+        }
+        Ok(DynamicMessage {
+            module,
+            name: def.symbol(),
+            fields,
+            buf,
+        })
+    }
+
+    /// Returns the value of the field at `path`, or `None` if a condition on it, or on a
+    /// record holding it, does not hold.
+    pub fn get(&self, path: &str) -> Result<Option<Value>, Diagnostic> {
+        let mut fields = self.fields;
+        let mut base = 0;
+        let mut names = path.split('.').peekable();
+        while let Some(name) = names.next() {
+            let location = self.locate(fields, name, path)?;
+            if !self.is_present(fields, base, location.field)? {
+                return Ok(None);
+            }
+            if names.peek().is_none() {
+                return self.read(base, &location).map(Some);
+            }
+            fields = self.inner_fields(&location, path)?;
+            base += location.offset;
+        }
+        unreachable!("a path has at least one name")
+    }
+
+    /// Writes `value` into the field at `path`. Conditions are not checked, so that fields
+    /// may be written before the fields their conditions read.
+    pub fn set(&mut self, path: &str, value: &Value) -> Result<(), Diagnostic> {
+        let codec = Codec {
+            module: self.module,
+        };
+        let mut fields = self.fields;
+        let mut base = 0;
+        let mut names = path.split('.').peekable();
+        while let Some(name) = names.next() {
+            let location = self.locate(fields, name, path)?;
+            if names.peek().is_some() {
+                fields = self.inner_fields(&location, path)?;
+                base += location.offset;
+                continue;
+            }
+            let start = base + location.offset;
+            let slot = &mut self.buf[start..start + location.size];
+            match location.shift {
+                Some(shift) => {
+                    let field = location.field;
+                    let bits = field.bit_field_size.unwrap_or(0);
+                    let raw = bit_field_bits(field, value, bits)? as u128;
+                    wire::write_bits(slot, self.module.endianness, shift, bits, raw);
+                }
+                None => {
+                    let mut out = Vec::with_capacity(location.size);
+                    codec.encode_type(&location.field.type_info, value, &mut out)?;
+                    slot.copy_from_slice(&out);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Decodes the whole struct or message.
+    pub fn to_value(&self) -> Result<Value, Diagnostic> {
+        let size = Codec {
+            module: self.module,
+        }
+        .fields_size(self.fields);
+        decode(self.module, self.name.as_str(), &self.buf[..size])
+    }
+
+    /// Returns the underlying buffer.
+    pub fn as_bytes(&self) -> &[u8] {
+        self.buf
+    }
+
+    fn locate(
+        &self,
+        fields: &'m [Field],
+        name: &str,
+        path: &str,
+    ) -> Result<Location<'m>, Diagnostic> {
+        Codec {
+            module: self.module,
+        }
+        .locate(fields, name)
+        .ok_or_else(|| mismatch(format!("'{}' has no field '{path}'", self.name)))
+    }
+
+    /// Returns the fields of the struct or message a non-final path segment names.
+    fn inner_fields(&self, location: &Location<'m>, path: &str) -> Result<&'m [Field], Diagnostic> {
+        if let Type::Custom(name) = location.field.type_info {
+            match (Codec {
+                module: self.module,
+            })
+            .custom(name)?
+            {
+                Definition::Struct(s) => return Ok(&s.fields),
+                Definition::Message(m) => return Ok(&m.fields),
+                Definition::Enum(_) => {}
+            }
+        }
+        Err(mismatch(format!("'{}' has no field '{path}'", self.name)))
+    }
+
+    /// Returns true if `field` of the record at `base` has no condition, or it holds.
+    fn is_present(
+        &self,
+        fields: &'m [Field],
+        base: usize,
+        field: &Field,
+    ) -> Result<bool, Diagnostic> {
+        let Some(condition) = &field.condition else {
+            return Ok(true);
+        };
+        let codec = Codec {
+            module: self.module,
+        };
+        let target = codec
+            .locate(fields, condition.field.as_str())
+            .ok_or_else(|| mismatch(format!("no field named '{}'", condition.field)))?;
+        // An enum is read as its raw integer, so that an undeclared value still decides it.
+        let value = match target.field.type_info {
+            Type::Custom(name) => match codec.custom(name)? {
+                Definition::Enum(e) => {
+                    let mut bytes = &self.buf[base + target.offset..];
+                    codec.decode_primitive(e.underlying_type, &mut bytes)?
+                }
+                _ => self.read(base, &target)?,
+            },
+            _ => self.read(base, &target)?,
+        };
+        Ok(codec.is_present(field, &[(condition.field, value)]))
+    }
+
+    fn read(&self, base: usize, location: &Location) -> Result<Value, Diagnostic> {
+        let codec = Codec {
+            module: self.module,
+        };
+        let mut bytes = &self.buf[base + location.offset..];
+        match location.shift {
+            Some(shift) => {
+                let bits = location.field.bit_field_size.unwrap_or(0);
+                let container = &bytes[..location.size];
+                let raw = wire::read_bits(container, self.module.endianness, shift, bits);
+                bit_field_value(location.field, raw as u64, bits)
+            }
+            None => codec.decode_type(&location.field.type_info, &mut bytes),
+        }
+    }
+}
+
+/// Where a field is stored within the encoding of its struct or message.
+struct Location<'a> {
+    field: &'a Field,
+    /// The byte offset of the field, or of its bit-field container.
+    offset: usize,
+    /// The size in bytes of the field, or of its bit-field container.
+    size: usize,
+    /// The position of a bit-field's lowest bit within its container.
+    shift: Option<usize>,
+}
+
+struct Codec<'m> {
+    module: &'m OnyxModule,
+}
+
+impl<'m> Codec<'m> {
+    fn custom(&self, name: Symbol) -> Result<&'m Definition, Diagnostic> {
+        self.module
+            .definitions
+            .get(&name)
+            .ok_or_else(|| mismatch(format!("no definition named '{name}'")))
+    }
+
+    fn definition_size(&self, def: &Definition) -> usize {
+        match def {
+            Definition::Enum(e) => e.underlying_type.get_byte_size(),
+            Definition::Struct(s) => self.fields_size(&s.fields),
+            Definition::Message(m) => self.fields_size(&m.fields),
+        }
+    }
+
+    /// Finds the field `name` among `fields`.
+    fn locate<'a>(&self, fields: &'a [Field], name: &str) -> Option<Location<'a>> {
+        let mut offset: usize = 0;
+        for run in runs(fields) {
+            offset = offset.next_multiple_of(self.run_align(&run));
+            match run {
+                Run::BitFields(group) => {
+                    let size = container_bytes(group);
+                    let mut shift = 0;
+                    for field in group {
+                        if field.name == name {
+                            let shift = Some(shift);
+                            return Some(Location {
+                                field,
+                                offset,
+                                size,
+                                shift,
+                            });
+                        }
+                        shift += field.bit_field_size.unwrap_or(0);
+                    }
+                    offset += size;
+                }
+                Run::Field(field) => {
+                    let size = self.type_size(&field.type_info);
+                    if field.name == name {
+                        return Some(Location {
+                            field,
+                            offset,
+                            size,
+                            shift: None,
+                        });
+                    }
+                    offset += size;
+                }
+            }
+        }
+        None
+    }
+
+    fn fields_size(&self, fields: &[Field]) -> usize {
+        runs(fields).fold(0, |offset, run| {
+            let offset = offset.next_multiple_of(self.run_align(&run));
+            offset
+                + match run {
+                    Run::BitFields(group) => container_bytes(group),
+                    Run::Field(field) => self.type_size(&field.type_info),
+                }
+        })
+    }
+
+    /// Returns the alignment in bytes of a run under the module's `align` directive.
+    fn run_align(&self, run: &Run) -> usize {
+        match run {
+            Run::BitFields(group) => self.module.alignment.of(container_bytes(group)),
+            Run::Field(field) => self.type_align(&field.type_info),
+        }
+    }
+
+    fn type_align(&self, type_info: &Type) -> usize {
+        let alignment = self.module.alignment;
+        if alignment == Alignment::Packed {
+            return 1;
+        }
+        match type_info {
+            Type::Primitive(p) | Type::Fixed { base: p, .. } => alignment.of(p.get_byte_size()),
+            Type::Uuid | Type::Bytes(_) => 1,
+            Type::Custom(name) => match self.module.definitions.get(name) {
+                Some(Definition::Enum(e)) => alignment.of(e.underlying_type.get_byte_size()),
+                Some(Definition::Struct(StructDef { fields, .. }))
+                | Some(Definition::Message(MessageDef { fields, .. })) => runs(fields)
+                    .map(|run| self.run_align(&run))
+                    .max()
+                    .unwrap_or(1),
+                None => 1,
+            },
+            Type::Vector { element, .. } => {
+                let length = type_info.length_type().unwrap().get_byte_size();
+                alignment.of(length).max(self.type_align(element))
+            }
+        }
+    }
+
+    fn type_size(&self, type_info: &Type) -> usize {
+        match type_info {
+            Type::Primitive(p) | Type::Fixed { base: p, .. } => p.get_byte_size(),
+            Type::Uuid | Type::Bytes(_) => type_info.byte_len().unwrap(),
+            Type::Custom(name) => self
+                .module
+                .definitions
+                .get(name)
+                .map_or(0, |def| self.definition_size(def)),
+            Type::Vector { element, capacity } => {
+                type_info.length_type().unwrap().get_byte_size()
+                    + capacity * self.type_size(element)
+            }
+        }
+    }
+
+    fn encode_definition(
+        &self,
+        def: &Definition,
+        value: &Value,
+        out: &mut Vec<u8>,
+    ) -> Result<(), Diagnostic> {
+        let fields = match def {
+            Definition::Enum(e) => return self.encode_primitive(e.underlying_type, value, out),
+            Definition::Struct(s) => &s.fields,
+            Definition::Message(m) => &m.fields,
+        };
+        let Value::Record(values) = value else {
+            return Err(mismatch(format!(
+                "expected a record for '{}', found {value:?}",
+                def.name()
+            )));
+        };
+        let present: Vec<&Field> = fields
+            .iter()
+            .filter(|field| self.is_present(field, values))
+            .collect();
+        if values.len() != present.len()
+            || present
+                .iter()
+                .zip(values)
+                .any(|(f, (name, _))| f.name != *name)
+        {
+            return Err(mismatch(format!(
+                "expected the present fields of '{}' in declaration order",
+                def.name()
+            )));
+        }
+
+        let mut values = values.iter().map(|(_, value)| value);
+        let mut next = |field: &Field| {
+            present
+                .iter()
+                .any(|f| f.name == field.name)
+                .then(|| values.next().unwrap())
+        };
+        let start = out.len();
+        for run in runs(fields) {
+            let offset = (out.len() - start).next_multiple_of(self.run_align(&run));
+            out.resize(start + offset, 0);
+            match run {
+                Run::BitFields(group) => {
+                    let mut container: u128 = 0;
+                    let mut shift = 0;
+                    for field in group {
+                        let bits = field.bit_field_size.unwrap_or(0);
+                        if let Some(value) = next(field) {
+                            container |= (bit_field_bits(field, value, bits)? as u128) << shift;
+                        }
+                        shift += bits;
+                    }
+                    self.write_uint(container, container_bytes(group), out);
+                }
+                Run::Field(field) => match next(field) {
+                    Some(value) => self.encode_type(&field.type_info, value, out)?,
+                    None => out.resize(out.len() + self.type_size(&field.type_info), 0),
+                },
+            }
+        }
+        Ok(())
+    }
+
+    fn encode_type(
+        &self,
+        type_info: &Type,
+        value: &Value,
+        out: &mut Vec<u8>,
+    ) -> Result<(), Diagnostic> {
+        match type_info {
+            Type::Primitive(p) | Type::Fixed { base: p, .. } => {
+                self.encode_primitive(*p, value, out)
+            }
+            Type::Uuid | Type::Bytes(_) => match value {
+                Value::Bytes(b) if b.len() == type_info.byte_len().unwrap() => {
+                    out.extend_from_slice(b);
+                    Ok(())
+                }
+                _ => Err(mismatch(format!("{value:?} is not a valid {type_info}"))),
+            },
+            Type::Custom(name) => self.encode_definition(self.custom(*name)?, value, out),
+            Type::Vector { element, capacity } => {
+                let Value::List(items) = value else {
+                    return Err(mismatch(format!(
+                        "expected a list for {type_info}, found {value:?}"
+                    )));
+                };
+                if items.len() > *capacity {
+                    return Err(mismatch(format!(
+                        "{} elements do not fit in {type_info}",
+                        items.len()
+                    )));
+                }
+                let length_type = type_info.length_type().unwrap();
+                self.write_uint(items.len() as u128, length_type.get_byte_size(), out);
+                for item in items {
+                    self.encode_type(element, item, out)?;
+                }
+                let unused = (capacity - items.len()) * self.type_size(element);
+                out.resize(out.len() + unused, 0);
+                Ok(())
+            }
+        }
+    }
+
+    fn encode_primitive(
+        &self,
+        p: PrimitiveType,
+        value: &Value,
+        out: &mut Vec<u8>,
+    ) -> Result<(), Diagnostic> {
+        let bits = p.get_bit_width();
+        let raw = match (p, value) {
+            (PrimitiveType::Bool, Value::Bool(b)) => *b as u64,
+            (PrimitiveType::F32, Value::F32(f)) => f.to_bits() as u64,
+            (PrimitiveType::F64, Value::F64(f)) => f.to_bits(),
+            (_, Value::Unsigned(v)) if is_unsigned(p) && fits_unsigned(*v, bits) => *v,
+            (_, Value::Signed(v)) if is_signed(p) && fits_signed(*v, bits) => {
+                *v as u64 & mask(bits)
+            }
+            _ => return Err(mismatch(format!("{value:?} is not a valid {p}"))),
+        };
+        self.write_uint(raw as u128, p.get_byte_size(), out);
+        Ok(())
+    }
+
+    fn json_definition(
+        &self,
+        def: &Definition,
+        document: &json::Value,
+        path: &str,
+    ) -> Result<Value, Diagnostic> {
+        let fields = match def {
+            Definition::Enum(e) => return json_enum(e, document, path),
+            Definition::Struct(s) => &s.fields,
+            Definition::Message(m) => &m.fields,
+        };
+        let entries = match document {
+            json::Value::Object(entries) => entries.as_slice(),
+            json::Value::Null => &[],
+            _ => return Err(mismatch(format!("expected an object for '{path}'"))),
+        };
+        if let Some((key, _)) = entries
+            .iter()
+            .find(|(key, _)| !fields.iter().any(|f| f.name == key.as_str()))
+        {
+            return Err(mismatch(format!("'{}' has no field '{key}'", def.name())));
+        }
+        let given = |field: &Field| {
+            entries
+                .iter()
+                .find(|(key, _)| field.name == key.as_str())
+                .map_or(&json::Value::Null, |(_, value)| value)
+        };
+
+        // Conditions read unconditional fields, which may come later, so those are read first.
+        let mut targets = Vec::new();
+        for field in fields.iter().filter(|f| f.condition.is_none()) {
+            let path = format!("{path}.{}", field.name);
+            targets.push((
+                field.name,
+                self.json_type(&field.type_info, given(field), &path)?,
+            ));
+        }
+        let mut values = Vec::new();
+        for field in fields {
+            let path = format!("{path}.{}", field.name);
+            if field.condition.is_none() {
+                let (_, value) = targets
+                    .iter()
+                    .find(|(name, _)| *name == field.name)
+                    .unwrap();
+                values.push((field.name, value.clone()));
+            } else if self.is_present(field, &targets) {
+                values.push((
+                    field.name,
+                    self.json_type(&field.type_info, given(field), &path)?,
+                ));
+            } else if *given(field) != json::Value::Null {
+                return Err(mismatch(format!(
+                    "'{path}' is given, but its condition does not hold"
+                )));
+            }
+        }
+        Ok(Value::Record(values))
+    }
+
+    fn json_type(
+        &self,
+        type_info: &Type,
+        document: &json::Value,
+        path: &str,
+    ) -> Result<Value, Diagnostic> {
+        match (type_info, document) {
+            (Type::Primitive(p), _) => json_primitive(*p, document, path),
+            (Type::Fixed { base, scale }, json::Value::Number(n)) => json_primitive(
+                *base,
+                &json::Value::Number((n.as_f64() * *scale as f64).round().into()),
+                path,
+            ),
+            (Type::Fixed { base, .. }, _) => json_primitive(*base, document, path),
+            (Type::Uuid | Type::Bytes(_), _) => {
+                let len = type_info.byte_len().unwrap();
+                let mut bytes = match document {
+                    json::Value::Null => Some(vec![0; len]),
+                    json::Value::String(hex) => parse_hex(hex),
+                    json::Value::Array(items) => items
+                        .iter()
+                        .map(|item| match item {
+                            json::Value::Number(n) => n.as_u64()?.try_into().ok(),
+                            _ => None,
+                        })
+                        .collect(),
+                    _ => None,
+                }
+                .ok_or_else(|| {
+                    mismatch(format!("expected hex digits or byte values for '{path}'"))
+                })?;
+                let padded = matches!(type_info, Type::Bytes(_)) && bytes.len() < len;
+                if bytes.len() != len && !padded {
+                    return Err(mismatch(format!(
+                        "'{path}' is a {type_info}, but {} bytes were given",
+                        bytes.len()
+                    )));
+                }
+                bytes.resize(len, 0);
+                Ok(Value::Bytes(bytes))
+            }
+            (Type::Custom(name), _) => self.json_definition(self.custom(*name)?, document, path),
+            (Type::Vector { element, .. }, json::Value::Array(items)) => items
+                .iter()
+                .enumerate()
+                .map(|(i, item)| self.json_type(element, item, &format!("{path}[{i}]")))
+                .collect::<Result<_, _>>()
+                .map(Value::List),
+            (Type::Vector { .. }, json::Value::Null) => Ok(Value::List(Vec::new())),
+            (Type::Vector { .. }, _) => Err(mismatch(format!("expected an array for '{path}'"))),
+        }
+    }
+
+    fn decode_definition(&self, def: &Definition, bytes: &mut &[u8]) -> Result<Value, Diagnostic> {
+        let fields = match def {
+            Definition::Enum(e) => {
+                let value = self.decode_primitive(e.underlying_type, bytes)?;
+                let raw = match value {
+                    Value::Unsigned(raw) => raw,
+                    Value::Signed(raw) => raw as u64,
+                    _ => unreachable!("enums have integer underlying types"),
+                };
+                if !e.open && !e.flags && !e.is_known(raw) {
+                    return Err(mismatch(format!(
+                        "{value:?} is not a variant of closed enum '{}'",
+                        e.name
+                    )));
+                }
+                return Ok(value);
+            }
+            Definition::Struct(s) => &s.fields,
+            Definition::Message(m) => &m.fields,
+        };
+
+        // Every slot is decoded before conditions are evaluated, since a condition may read a
+        // later field. An absent field's slot may hold anything, so its errors are dropped.
+        let mut decoded = Vec::new();
+        let end = bytes.len();
+        for run in runs(fields) {
+            let offset = end - bytes.len();
+            let padding = offset.next_multiple_of(self.run_align(&run)) - offset;
+            if bytes.len() < padding {
+                return Err(mismatch("unexpected end of input"));
+            }
+            *bytes = &bytes[padding..];
+            match run {
+                Run::BitFields(group) => {
+                    let container = self.read_uint(container_bytes(group), bytes)?;
+                    let mut shift = 0;
+                    for field in group {
+                        let bits = field.bit_field_size.unwrap_or(0);
+                        let raw = (container >> shift) as u64 & mask(bits);
+                        decoded.push((field, bit_field_value(field, raw, bits)));
+                        shift += bits;
+                    }
+                }
+                Run::Field(field) => {
+                    let start = *bytes;
+                    let value = self.decode_type(&field.type_info, bytes);
+                    if value.is_err() {
+                        // Skip the slot so the following fields stay aligned.
+                        let size = self.type_size(&field.type_info);
+                        if start.len() < size {
+                            return Err(mismatch("unexpected end of input"));
+                        }
+                        *bytes = &start[size..];
+                    }
+                    decoded.push((field, value));
+                }
+            }
+        }
+
+        let targets: Vec<(Symbol, Value)> = decoded
+            .iter()
+            .filter(|(field, _)| field.condition.is_none())
+            .filter_map(|(field, value)| Some((field.name, value.as_ref().ok()?.clone())))
+            .collect();
+        let mut values = Vec::new();
+        for (field, value) in decoded {
+            if self.is_present(field, &targets) {
+                values.push((field.name, value?));
+            }
+        }
+        Ok(Value::Record(values))
+    }
+
+    /// Returns true if `field` has no condition, or its condition holds for the field it reads
+    /// in `values`.
+    fn is_present(&self, field: &Field, values: &[(Symbol, Value)]) -> bool {
+        let Some(condition) = &field.condition else {
+            return true;
+        };
+        let raw = match values.iter().find(|(name, _)| *name == condition.field) {
+            Some((_, Value::Bool(b))) => *b as u64,
+            Some((_, Value::Unsigned(v))) => *v,
+            Some((_, Value::Signed(v))) => *v as u64,
+            _ => 0,
+        };
+        condition.holds(self.module, raw)
+    }
+
+    fn decode_type(&self, type_info: &Type, bytes: &mut &[u8]) -> Result<Value, Diagnostic> {
+        match type_info {
+            Type::Primitive(p) | Type::Fixed { base: p, .. } => self.decode_primitive(*p, bytes),
+            Type::Uuid | Type::Bytes(_) => {
+                let len = type_info.byte_len().unwrap();
+                if bytes.len() < len {
+                    return Err(mismatch("unexpected end of input"));
+                }
+                let (head, tail) = bytes.split_at(len);
+                *bytes = tail;
+                Ok(Value::Bytes(head.to_vec()))
+            }
+            Type::Custom(name) => self.decode_definition(self.custom(*name)?, bytes),
+            Type::Vector { element, capacity } => {
+                let length_type = type_info.length_type().unwrap();
+                let len = self.read_uint(length_type.get_byte_size(), bytes)? as usize;
+                if len > *capacity {
+                    return Err(mismatch(format!(
+                        "length {len} exceeds the capacity of {type_info}"
+                    )));
+                }
+                let items = (0..len)
+                    .map(|_| self.decode_type(element, bytes))
+                    .collect::<Result<_, _>>()?;
+                let unused = (capacity - len) * self.type_size(element);
+                if bytes.len() < unused {
+                    return Err(mismatch("unexpected end of input"));
+                }
+                *bytes = &bytes[unused..];
+                Ok(Value::List(items))
+            }
+        }
+    }
+
+    fn decode_primitive(&self, p: PrimitiveType, bytes: &mut &[u8]) -> Result<Value, Diagnostic> {
+        let bits = p.get_bit_width();
+        let raw = self.read_uint(p.get_byte_size(), bytes)? as u64;
+        Ok(match p {
+            PrimitiveType::Bool => Value::Bool(raw != 0),
+            PrimitiveType::F32 => Value::F32(f32::from_bits(raw as u32)),
+            PrimitiveType::F64 => Value::F64(f64::from_bits(raw)),
+            _ if is_signed(p) => Value::Signed(sign_extend(raw, bits)),
+            _ => Value::Unsigned(raw),
+        })
+    }
+
+    /// Appends the low `len` bytes of `value` in the module's endianness.
+    fn write_uint(&self, value: u128, len: usize, out: &mut Vec<u8>) {
+        let start = out.len();
+        out.resize(start + len, 0);
+        wire::write_uint(value, self.module.endianness, &mut out[start..]);
+    }
+
+    /// Reads a `len`-byte unsigned integer in the module's endianness from the front of `bytes`.
+    fn read_uint(&self, len: usize, bytes: &mut &[u8]) -> Result<u128, Diagnostic> {
+        if bytes.len() < len {
+            return Err(mismatch("unexpected end of input"));
+        }
+        let (head, tail) = bytes.split_at(len);
+        *bytes = tail;
+        Ok(wire::read_uint(head, self.module.endianness))
+    }
+}
+
+/// Reads an enum from a variant name, names joined with `|` for flags, or a number.
+fn json_enum(e: &EnumDef, document: &json::Value, path: &str) -> Result<Value, Diagnostic> {
+    let json::Value::String(names) = document else {
+        return json_primitive(e.underlying_type, document, path);
+    };
+    let values = e.variant_values();
+    let mut raw = 0;
+    let names: Vec<&str> = match e.flags {
+        true => names.split('|').map(str::trim).collect(),
+        false => vec![names.as_str()],
+    };
+    for name in names {
+        let Some(i) = e.variants.iter().position(|v| v.name == name) else {
+            return Err(mismatch(format!(
+                "'{}' has no variant '{name}', for '{path}'",
+                e.name
+            )));
+        };
+        raw |= values[i];
+    }
+    Ok(match is_signed(e.underlying_type) {
+        true => Value::Signed(raw as i64),
+        false => Value::Unsigned(raw),
+    })
+}
+
+/// Reads a primitive from a bool, a number, or a string of decimal digits. `null` is zero.
+fn json_primitive(
+    p: PrimitiveType,
+    document: &json::Value,
+    path: &str,
+) -> Result<Value, Diagnostic> {
+    let bits = p.get_bit_width();
+    let value = match (p, document) {
+        (PrimitiveType::Bool, json::Value::Null) => Some(Value::Bool(false)),
+        (PrimitiveType::Bool, json::Value::Bool(b)) => Some(Value::Bool(*b)),
+        (PrimitiveType::F32, json::Value::Null) => Some(Value::F32(0.0)),
+        (PrimitiveType::F32, json::Value::Number(n)) => Some(Value::F32(n.as_f64() as f32)),
+        (PrimitiveType::F64, json::Value::Null) => Some(Value::F64(0.0)),
+        (PrimitiveType::F64, json::Value::Number(n)) => Some(Value::F64(n.as_f64())),
+        (_, json::Value::Null) if is_signed(p) => Some(Value::Signed(0)),
+        (_, json::Value::Null) if is_unsigned(p) => Some(Value::Unsigned(0)),
+        (_, json::Value::Number(n)) if n.is_inexact_integer() => {
+            return Err(mismatch(format!(
+                "'{path}' is too large to read exactly from a JSON number"
+            ))
+            .with_help(
+                "write it as a string of decimal digits, such as \"18446744073709551615\"",
+            ));
+        }
+        (_, json::Value::Number(n)) if is_signed(p) => n.as_i64().map(Value::Signed),
+        (_, json::Value::Number(n)) if is_unsigned(p) => n.as_u64().map(Value::Unsigned),
+        (_, json::Value::String(s)) if is_signed(p) => s.parse().ok().map(Value::Signed),
+        (_, json::Value::String(s)) if is_unsigned(p) => s.parse().ok().map(Value::Unsigned),
+        _ => None,
+    };
+    match value {
+        Some(Value::Signed(v)) if !fits_signed(v, bits) => None,
+        Some(Value::Unsigned(v)) if !fits_unsigned(v, bits) => None,
+        value => value,
+    }
+    .ok_or_else(|| mismatch(format!("'{path}' is not a valid {p}")))
+}
+
+/// Parses a string of hex digits, which may be separated by whitespace.
+fn parse_hex(hex: &str) -> Option<Vec<u8>> {
+    let digits: Vec<u8> = hex.bytes().filter(|b| !b.is_ascii_whitespace()).collect();
+    if !digits.len().is_multiple_of(2) {
+        return None;
+    }
+    digits
+        .chunks(2)
+        .map(|pair| u8::from_str_radix(std::str::from_utf8(pair).ok()?, 16).ok())
+        .collect()
+}
+
+/// A bit-field group or a single ordinary field.
+enum Run<'a> {
+    BitFields(&'a [Field]),
+    Field(&'a Field),
+}
+
+/// Splits fields into runs of consecutive bit-fields and single ordinary fields.
+fn runs(fields: &[Field]) -> impl Iterator<Item = Run<'_>> {
+    let mut rest = fields;
+    std::iter::from_fn(move || {
+        let first = rest.first()?;
+        if first.bit_field_size.is_none() {
+            rest = &rest[1..];
+            return Some(Run::Field(first));
+        }
+        let len = rest
+            .iter()
+            .take_while(|f| f.bit_field_size.is_some())
+            .count();
+        let (group, tail) = rest.split_at(len);
+        rest = tail;
+        Some(Run::BitFields(group))
+    })
+}
+
+fn container_bytes(group: &[Field]) -> usize {
+    group
+        .iter()
+        .map(|f| f.bit_field_size.unwrap_or(0))
+        .sum::<usize>()
+        .div_ceil(8)
+}
+
+/// Returns the low `bits` bits that store `value` in a bit-field.
+fn bit_field_bits(field: &Field, value: &Value, bits: usize) -> Result<u64, Diagnostic> {
+    let Type::Primitive(p) = field.type_info else {
+        return Err(mismatch(format!(
+            "bit-field '{}' is not primitive",
+            field.name
+        )));
+    };
+    match (p, value) {
+        (PrimitiveType::Bool, Value::Bool(b)) => Ok(*b as u64),
+        (_, Value::Unsigned(v)) if is_unsigned(p) && fits_unsigned(*v, bits) => Ok(*v),
+        (_, Value::Signed(v)) if is_signed(p) && fits_signed(*v, bits) => {
+            Ok(*v as u64 & mask(bits))
+        }
+        _ => Err(mismatch(format!(
+            "{value:?} does not fit bit-field '{}' ({p} : {bits})",
+            field.name
+        ))),
+    }
+}
+
+/// Interprets the low `bits` bits of a bit-field.
+fn bit_field_value(field: &Field, raw: u64, bits: usize) -> Result<Value, Diagnostic> {
+    match field.type_info {
+        Type::Primitive(PrimitiveType::Bool) => Ok(Value::Bool(raw != 0)),
+        Type::Primitive(p) if is_signed(p) => Ok(Value::Signed(sign_extend(raw, bits))),
+        Type::Primitive(p) if is_unsigned(p) => Ok(Value::Unsigned(raw)),
+        _ => Err(mismatch(format!(
+            "bit-field '{}' has no integer encoding",
+            field.name
+        ))),
+    }
+}
+
+fn is_unsigned(p: PrimitiveType) -> bool {
+    matches!(
+        p,
+        PrimitiveType::U8 | PrimitiveType::U16 | PrimitiveType::U32 | PrimitiveType::U64
+    )
+}
+
+fn is_signed(p: PrimitiveType) -> bool {
+    matches!(
+        p,
+        PrimitiveType::I8 | PrimitiveType::I16 | PrimitiveType::I32 | PrimitiveType::I64
+    )
+}
+
+fn mask(bits: usize) -> u64 {
+    wire::mask(bits.min(64)) as u64
+}
+
+fn fits_unsigned(value: u64, bits: usize) -> bool {
+    value & !mask(bits) == 0
+}
+
+fn fits_signed(value: i64, bits: usize) -> bool {
+    bits >= 64 || sign_extend(value as u64 & mask(bits), bits) == value
+}
+
+fn sign_extend(raw: u64, bits: usize) -> i64 {
+    wire::sign_extend(raw as u128, bits.min(64)) as i64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::Parser;
+
+    fn record(fields: &[(&str, Value)]) -> Value {
+        Value::Record(
+            fields
+                .iter()
+                .map(|(name, value)| (Symbol::intern(name), value.clone()))
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn test_reference_layout() {
+        let source = "endian = big\n\
+                      enum Status : u8 { Active = 1, Inactive, Error = 10, }\n\
+                      @packed_bits struct Header { version u32 : 4, checksum u16, tag Status, }\n\
+                      message User { id u64, name u8 : 7, yes bool : 1, email u32, hdr Header, }";
+        let module = Parser::new(source).unwrap().parse_module().unwrap();
+        let bytes = [8, 7, 6, 5, 4, 3, 2, 1, 0x89, 13, 12, 11, 10, 14, 16, 15, 1];
+        let user = record(&[
+            ("id", Value::Unsigned(578437695752307201)),
+            ("name", Value::Unsigned(9)),
+            ("yes", Value::Bool(true)),
+            ("email", Value::Unsigned(218893066)),
+            (
+                "hdr",
+                record(&[
+                    ("version", Value::Unsigned(14)),
+                    ("checksum", Value::Unsigned(4111)),
+                    ("tag", Value::Unsigned(1)),
+                ]),
+            ),
+        ]);
+
+        assert_eq!(wire_size(&module, "User").unwrap(), 17);
+        assert_eq!(decode(&module, "User", &bytes).unwrap(), user);
+        assert_eq!(encode(&module, "User", &user).unwrap(), bytes);
+    }
+
+    #[test]
+    fn test_unknown_enum_values() {
+        let source = "enum Closed : u8 { A = 1, }\n\
+                      @open enum Open : u8 { A = 1, }\n\
+                      flags Access : u8 { Read, }";
+        let module = Parser::new(source).unwrap().parse_module().unwrap();
+
+        let error = decode(&module, "Closed", &[7]).unwrap_err();
+        assert_eq!(error.code(), Some(codes::VALUE_MISMATCH));
+        assert_eq!(decode(&module, "Closed", &[1]).unwrap(), Value::Unsigned(1));
+        assert_eq!(decode(&module, "Open", &[7]).unwrap(), Value::Unsigned(7));
+        assert_eq!(decode(&module, "Access", &[7]).unwrap(), Value::Unsigned(7));
+    }
+
+    #[test]
+    fn test_conditional_fields() {
+        let source = "enum Kind : u8 { Ping = 1, Data, }\n\
+                      enum Code : u8 { Ok = 1, }\n\
+                      message Packet {\n\
+                          code Code if kind == Kind.Data,\n\
+                          kind Kind,\n\
+                          more bool : 1,\n\
+                          extra u8 : 7 if more == true,\n\
+                      }";
+        let module = Parser::new(source).unwrap().parse_module().unwrap();
+
+        // Absent fields are zeroed, even when zero is not a valid value.
+        let ping = record(&[("kind", Value::Unsigned(1)), ("more", Value::Bool(false))]);
+        let bytes = encode(&module, "Packet", &ping).unwrap();
+        assert_eq!(bytes, [0, 1, 0]);
+        assert_eq!(decode(&module, "Packet", &bytes).unwrap(), ping);
+
+        let data = record(&[
+            ("code", Value::Unsigned(1)),
+            ("kind", Value::Unsigned(2)),
+            ("more", Value::Bool(true)),
+            ("extra", Value::Unsigned(5)),
+        ]);
+        let bytes = encode(&module, "Packet", &data).unwrap();
+        assert_eq!(bytes, [1, 2, 0b1011]);
+        assert_eq!(decode(&module, "Packet", &bytes).unwrap(), data);
+
+        // A present field must be given, and an absent one must not.
+        let error = encode(&module, "Packet", &record(&[("kind", Value::Unsigned(2))]));
+        assert_eq!(error.unwrap_err().code(), Some(codes::VALUE_MISMATCH));
+        let mut extra = ping.clone();
+        let Value::Record(fields) = &mut extra else {
+            unreachable!()
+        };
+        fields.push((Symbol::intern("extra"), Value::Unsigned(1)));
+        assert!(encode(&module, "Packet", &extra).is_err());
+        // A present field is still checked when decoded.
+        assert!(decode(&module, "Packet", &[0, 2, 0]).is_err());
+    }
+
+    #[test]
+    fn test_signed_bit_fields_and_errors() {
+        let source = "struct S { a i8 : 3, b u8 : 5, c i16, }";
+        let module = Parser::new(source).unwrap().parse_module().unwrap();
+        let value = record(&[
+            ("a", Value::Signed(-2)),
+            ("b", Value::Unsigned(31)),
+            ("c", Value::Signed(-300)),
+        ]);
+
+        let bytes = encode(&module, "S", &value).unwrap();
+        assert_eq!(bytes, [0b1111_1110, 0xD4, 0xFE]);
+        assert_eq!(decode(&module, "S", &bytes).unwrap(), value);
+
+        let too_wide = record(&[
+            ("a", Value::Signed(4)),
+            ("b", Value::Unsigned(0)),
+            ("c", Value::Signed(0)),
+        ]);
+        let error = encode(&module, "S", &too_wide).unwrap_err();
+        assert_eq!(error.code(), Some(codes::VALUE_MISMATCH));
+        assert!(decode(&module, "S", &bytes[..2]).is_err());
+        assert!(encode(&module, "Missing", &value).is_err());
+    }
+
+    #[test]
+    fn test_vectors() {
+        let source = "endian = little\n\
+                      struct Point { x u8, y u8, }\n\
+                      message Path { id u16, points vec<Point, 3>, }";
+        let module = Parser::new(source).unwrap().parse_module().unwrap();
+        let point = |x, y| record(&[("x", Value::Unsigned(x)), ("y", Value::Unsigned(y))]);
+        let path = record(&[
+            ("id", Value::Unsigned(0x0102)),
+            ("points", Value::List(vec![point(1, 2), point(3, 4)])),
+        ]);
+
+        let bytes = encode(&module, "Path", &path).unwrap();
+        assert_eq!(bytes, [0x02, 0x01, 2, 1, 2, 3, 4, 0, 0]);
+        assert_eq!(wire_size(&module, "Path").unwrap(), bytes.len());
+        assert_eq!(decode(&module, "Path", &bytes).unwrap(), path);
+
+        // Unused slots are ignored, but the length must fit the capacity.
+        let mut stale = bytes;
+        stale[7] = 9;
+        assert_eq!(decode(&module, "Path", &stale).unwrap(), path);
+        stale[2] = 4;
+        assert!(decode(&module, "Path", &stale).is_err());
+
+        let full = record(&[
+            ("id", Value::Unsigned(0)),
+            ("points", Value::List(vec![point(0, 0); 4])),
+        ]);
+        assert!(encode(&module, "Path", &full).is_err());
+    }
+
+    #[test]
+    fn test_byte_arrays() {
+        let source = "endian = little\n\
+                      message Key { id uuid, tag bytes[3], }";
+        let module = Parser::new(source).unwrap().parse_module().unwrap();
+        let id: Vec<u8> = (0..16).collect();
+        let key = record(&[
+            ("id", Value::Bytes(id.clone())),
+            ("tag", Value::Bytes(vec![0xAA, 0xBB, 0xCC])),
+        ]);
+
+        // Bytes keep their order whatever the endianness.
+        let bytes = encode(&module, "Key", &key).unwrap();
+        assert_eq!(bytes[..16], id[..]);
+        assert_eq!(bytes[16..], [0xAA, 0xBB, 0xCC]);
+        assert_eq!(decode(&module, "Key", &bytes).unwrap(), key);
+
+        let short = record(&[("id", Value::Bytes(id)), ("tag", Value::Bytes(vec![0xAA]))]);
+        assert!(encode(&module, "Key", &short).is_err());
+    }
+
+    #[test]
+    fn test_from_json() {
+        let source = "enum Kind : u8 { Ping = 1, Data, }\n\
+                      flags Access : u8 { Read, Write, }\n\
+                      struct Point { x i8, y fixed<u16, 100>, }\n\
+                      message Packet {\n\
+                          kind Kind,\n\
+                          access Access,\n\
+                          big u64,\n\
+                          payload vec<Point, 2> if kind == Kind.Data,\n\
+                          tag bytes[3],\n\
+                      }";
+        let module = Parser::new(source).unwrap().parse_module().unwrap();
+
+        let value = from_json(
+            &module,
+            "Packet",
+            r#"{"kind": "Data", "access": "Read | Write", "big": "18446744073709551615",
+                "payload": [{"x": -1, "y": 1.25}], "tag": "aa bb"}"#,
+        )
+        .unwrap();
+        let point = record(&[("x", Value::Signed(-1)), ("y", Value::Unsigned(125))]);
+        assert_eq!(
+            value,
+            record(&[
+                ("kind", Value::Unsigned(2)),
+                ("access", Value::Unsigned(3)),
+                ("big", Value::Unsigned(u64::MAX)),
+                ("payload", Value::List(vec![point])),
+                ("tag", Value::Bytes(vec![0xAA, 0xBB, 0])),
+            ])
+        );
+
+        // Left-out fields are zero, and an absent conditional field is dropped.
+        let ping = from_json(&module, "Packet", r#"{"kind": 1}"#).unwrap();
+        assert_eq!(
+            ping,
+            record(&[
+                ("kind", Value::Unsigned(1)),
+                ("access", Value::Unsigned(0)),
+                ("big", Value::Unsigned(0)),
+                ("tag", Value::Bytes(vec![0; 3])),
+            ])
+        );
+        assert_eq!(encode(&module, "Packet", &ping).unwrap().len(), 20);
+
+        let message = |json| {
+            from_json(&module, "Packet", json)
+                .unwrap_err()
+                .message()
+                .to_string()
+        };
+        assert_eq!(message(r#"{"size": 1}"#), "'Packet' has no field 'size'");
+        assert_eq!(
+            message(r#"{"kind": "Pong"}"#),
+            "'Kind' has no variant 'Pong', for 'Packet.kind'"
+        );
+        assert_eq!(
+            message(r#"{"payload": []}"#),
+            "'Packet.payload' is given, but its condition does not hold"
+        );
+        assert_eq!(
+            message(r#"{"kind": 2, "payload": [{"x": 128}]}"#),
+            "'Packet.payload[0].x' is not a valid i8"
+        );
+        assert_eq!(
+            message(r#"{"tag": "00112233"}"#),
+            "'Packet.tag' is a bytes[3], but 4 bytes were given"
+        );
+        assert!(message("{").starts_with("invalid JSON"));
+    }
+
+    #[test]
+    fn test_from_json_large_integers() {
+        let source = "endian = big\nmessage M { a u64, b i64, }";
+        let module = Parser::new(source).unwrap().parse_module().unwrap();
+        let read = |json| from_json(&module, "M", json);
+
+        // Integers past 2^53 are read from their digits, not rounded through an f64.
+        let value = read(r#"{"a": 9007199254740993, "b": -9223372036854775808}"#).unwrap();
+        assert_eq!(
+            value,
+            record(&[
+                ("a", Value::Unsigned(9007199254740993)),
+                ("b", Value::Signed(i64::MIN)),
+            ])
+        );
+        assert_eq!(
+            encode(&module, "M", &value).unwrap()[..8],
+            [0x00, 0x20, 0, 0, 0, 0, 0, 0x01]
+        );
+        let value = read(r#"{"a": 18446744073709551615}"#).unwrap();
+        assert_eq!(
+            value,
+            record(&[("a", Value::Unsigned(u64::MAX)), ("b", Value::Signed(0))])
+        );
+
+        let error = read(r#"{"a": 9007199254740993.0}"#).unwrap_err();
+        assert_eq!(
+            error.message(),
+            "'M.a' is too large to read exactly from a JSON number"
+        );
+        assert_eq!(
+            error.help(),
+            ["write it as a string of decimal digits, such as \"18446744073709551615\""]
+        );
+        let error = read(r#"{"a": 18446744073709551616}"#).unwrap_err();
+        assert_eq!(error.message(), "'M.a' is not a valid u64");
+    }
+
+    #[test]
+    fn test_dynamic_message() {
+        let source = "endian = big\n\
+                      enum Kind : u8 { Ping = 1, Data, }\n\
+                      struct Body { len u16, more bool : 1, seq u8 : 7, }\n\
+                      message Packet { kind Kind, body Body if kind == Kind.Data, crc u16, }";
+        let module = Parser::new(source).unwrap().parse_module().unwrap();
+        let mut buf = [0; 6];
+        let mut packet = DynamicMessage::new(&module, "Packet", &mut buf).unwrap();
+
+        // Fields may be written before the fields their conditions read.
+        packet.set("body.len", &Value::Unsigned(0x0102)).unwrap();
+        packet.set("body.seq", &Value::Unsigned(5)).unwrap();
+        packet.set("body.more", &Value::Bool(true)).unwrap();
+        packet.set("crc", &Value::Unsigned(0xBEEF)).unwrap();
+        assert_eq!(packet.get("body.seq").unwrap(), None);
+        packet.set("kind", &Value::Unsigned(2)).unwrap();
+        assert_eq!(packet.get("body.seq").unwrap(), Some(Value::Unsigned(5)));
+        assert_eq!(packet.as_bytes(), [2, 1, 2, 0b1011, 0xBE, 0xEF]);
+
+        // Rewriting a bit-field leaves its neighbours alone.
+        packet.set("body.seq", &Value::Unsigned(127)).unwrap();
+        assert_eq!(packet.get("body.more").unwrap(), Some(Value::Bool(true)));
+        let body = record(&[
+            ("len", Value::Unsigned(0x0102)),
+            ("more", Value::Bool(true)),
+            ("seq", Value::Unsigned(127)),
+        ]);
+        assert_eq!(
+            packet.to_value().unwrap(),
+            record(&[
+                ("kind", Value::Unsigned(2)),
+                ("body", body),
+                ("crc", Value::Unsigned(0xBEEF)),
+            ])
+        );
+
+        let error = packet.set("body.seq", &Value::Unsigned(128)).unwrap_err();
+        assert_eq!(error.code(), Some(codes::VALUE_MISMATCH));
+        let error = packet.get("body.size").unwrap_err();
+        assert_eq!(error.message(), "'Packet' has no field 'body.size'");
+        assert!(packet.get("kind.value").is_err());
+        assert!(DynamicMessage::new(&module, "Packet", &mut [0; 5]).is_err());
+        assert!(DynamicMessage::new(&module, "Kind", &mut [0; 7]).is_err());
+    }
+}
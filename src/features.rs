@@ -0,0 +1,220 @@
+use std::fmt;
+
+use crate::ast::{Definition, OnyxModule, PrimitiveType, Type};
+
+/// An IDL construct that a module may use and a backend may support.
+///
+/// Backends report what they support through [`CodeGenerator::supports`], so the features used
+/// by a module can be checked against each target before generating code.
+///
+/// [`CodeGenerator::supports`]: crate::generators::CodeGenerator::supports
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Feature {
+    /// Message definitions.
+    Messages,
+    /// Enum definitions.
+    Enums,
+    /// Enum variants with an explicit `= value`.
+    ExplicitEnumValues,
+    /// Fields packed into a number of bits (`name u8 : 3`).
+    BitFields,
+    /// Fields whose type is another struct or message.
+    NestedTypes,
+    /// Fields of type `f32` or `f64`.
+    FloatingPoint,
+    /// Bounded vector fields (`name vec<u8, 16>`).
+    BoundedVectors,
+    /// Fixed-point fields (`name fixed<i32, 1000>`).
+    FixedPoint,
+    /// Fields of type `uuid` or `bytes[N]`.
+    ByteArrays,
+    /// Flags enums (`flags Access : u8 { ... }`).
+    Flags,
+    /// Enums marked `@open`, which keep values that no variant declares.
+    OpenEnums,
+    /// Fields marked `@since` or `@removed`, which give the module a layout per version.
+    Versions,
+    /// Fields present only when a condition on another field holds (`body Body if kind == 1`).
+    Conditions,
+    /// Messages marked `@id(N)`, which get a dispatch table.
+    MessageIds,
+    /// `endian = host`, which lays buffers out in the byte order of the machine using them.
+    HostEndianness,
+    /// Definitions and fields marked `@deprecated("note")`.
+    Deprecations,
+}
+
+impl Feature {
+    /// All features, in the order they are reported.
+    pub const ALL: [Feature; 16] = [
+        Feature::Messages,
+        Feature::Enums,
+        Feature::ExplicitEnumValues,
+        Feature::BitFields,
+        Feature::NestedTypes,
+        Feature::FloatingPoint,
+        Feature::BoundedVectors,
+        Feature::FixedPoint,
+        Feature::ByteArrays,
+        Feature::Flags,
+        Feature::OpenEnums,
+        Feature::Versions,
+        Feature::Conditions,
+        Feature::MessageIds,
+        Feature::HostEndianness,
+        Feature::Deprecations,
+    ];
+
+    /// Returns the kebab-case name of the feature.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Feature::Messages => "messages",
+            Feature::Enums => "enums",
+            Feature::ExplicitEnumValues => "explicit-enum-values",
+            Feature::BitFields => "bit-fields",
+            Feature::NestedTypes => "nested-types",
+            Feature::FloatingPoint => "floating-point",
+            Feature::BoundedVectors => "bounded-vectors",
+            Feature::FixedPoint => "fixed-point",
+            Feature::ByteArrays => "byte-arrays",
+            Feature::Flags => "flags",
+            Feature::OpenEnums => "open-enums",
+            Feature::Versions => "versions",
+            Feature::Conditions => "conditions",
+            Feature::MessageIds => "message-ids",
+            Feature::HostEndianness => "host-endianness",
+            Feature::Deprecations => "deprecations",
+        }
+    }
+
+    /// Looks up a feature by its kebab-case name.
+    pub fn from_name(name: &str) -> Option<Feature> {
+        Self::ALL.into_iter().find(|feature| feature.name() == name)
+    }
+}
+
+impl fmt::Display for Feature {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+/// Returns the features used by a resolved module, in [`Feature::ALL`] order.
+///
+/// Features used only by the layouts of earlier versions count too.
+pub fn used_features(module: &OnyxModule) -> Vec<Feature> {
+    let mut used = Vec::new();
+    for layout in std::iter::once(module).chain(&module.history) {
+        collect_features(layout, &mut used);
+    }
+    Feature::ALL
+        .into_iter()
+        .filter(|feature| used.contains(feature))
+        .collect()
+}
+
+/// Adds the features used by one layout of a module to `used`.
+fn collect_features(module: &OnyxModule, used: &mut Vec<Feature>) {
+    if !module.endianness.is_portable() {
+        used.push(Feature::HostEndianness);
+    }
+    for def in module.definitions.values() {
+        if def.deprecated().is_some() {
+            used.push(Feature::Deprecations);
+        }
+        let fields = match def {
+            Definition::Message(m) => {
+                used.push(Feature::Messages);
+                if m.id.is_some() {
+                    used.push(Feature::MessageIds);
+                }
+                &m.fields
+            }
+            Definition::Struct(s) => &s.fields,
+            Definition::Enum(e) => {
+                used.push(Feature::Enums);
+                if e.variants.iter().any(|v| v.value.is_some()) {
+                    used.push(Feature::ExplicitEnumValues);
+                }
+                if e.flags {
+                    used.push(Feature::Flags);
+                }
+                if e.open {
+                    used.push(Feature::OpenEnums);
+                }
+                continue;
+            }
+        };
+        for field in fields {
+            if field.bit_field_size.is_some() {
+                used.push(Feature::BitFields);
+            }
+            if field.since.is_some() || field.removed.is_some() {
+                used.push(Feature::Versions);
+            }
+            if field.condition.is_some() {
+                used.push(Feature::Conditions);
+            }
+            if field.deprecated.is_some() {
+                used.push(Feature::Deprecations);
+            }
+            if let Type::Vector { .. } = field.type_info {
+                used.push(Feature::BoundedVectors);
+            }
+            match field.type_info.element_type() {
+                Type::Primitive(PrimitiveType::F32 | PrimitiveType::F64) => {
+                    used.push(Feature::FloatingPoint)
+                }
+                Type::Fixed { .. } => used.push(Feature::FixedPoint),
+                Type::Uuid | Type::Bytes(_) => used.push(Feature::ByteArrays),
+                Type::Custom(name)
+                    if !matches!(module.definitions.get(name), Some(Definition::Enum(_))) =>
+                {
+                    used.push(Feature::NestedTypes)
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::Parser;
+
+    #[test]
+    fn test_used_features() {
+        let source = "endian = host\n\
+                      version = 2\n\
+                      @open enum Kind : u8 { A, B = 4, }\n\
+                      @packed_bits struct Inner { kind Kind, flags u8 : 3 if kind == Kind.B, }\n\
+                      struct Outer { @deprecated(\"use Inner\") inner Inner, }\n\
+                      @id(1) message Log { levels vec<f32, 8>, gain fixed<u16, 100>, @removed(2) id uuid, }\n\
+                      flags Access : u8 { Read, Write, }";
+        let module = Parser::new(source).unwrap().parse_module().unwrap();
+        assert_eq!(
+            used_features(&module),
+            [
+                Feature::Messages,
+                Feature::Enums,
+                Feature::ExplicitEnumValues,
+                Feature::BitFields,
+                Feature::NestedTypes,
+                Feature::FloatingPoint,
+                Feature::BoundedVectors,
+                Feature::FixedPoint,
+                Feature::ByteArrays,
+                Feature::Flags,
+                Feature::OpenEnums,
+                Feature::Versions,
+                Feature::Conditions,
+                Feature::MessageIds,
+                Feature::HostEndianness,
+                Feature::Deprecations,
+            ]
+        );
+        assert_eq!(Feature::from_name("bit-fields"), Some(Feature::BitFields));
+    }
+}
@@ -0,0 +1,189 @@
+//! Stable fingerprints of definitions and modules.
+//!
+//! A fingerprint is a 64-bit hash of everything that shapes a definition on the wire and in
+//! generated code: its name, each field's name, type, offset, and width, message IDs, and enum
+//! values. The fingerprint of a struct or message covers the definitions its fields use, so
+//! changing a nested struct changes every definition that embeds it. Peers can exchange
+//! fingerprints to check that they were built from the same schema.
+//!
+//! Fingerprints use 64-bit FNV-1a and depend only on the schema, so they are the same on every
+//! platform and across releases of the compiler. Comments, `@deprecated` notes, and the order
+//! of definitions in the source do not affect them.
+//!
+//! ```rust
+//! use onyx::fingerprint;
+//! use onyx::hir;
+//! use onyx::parser::Parser;
+//!
+//! let parse = |source| Parser::new(source).and_then(|p| p.parse_module()).unwrap();
+//! let v1 = parse("struct Point { x i32, y i32, } message Shape { at Point, }");
+//! let v2 = parse("struct Point { x i32, y i64, } message Shape { at Point, }");
+//! let (v1, v2) = (hir::lower(&v1).unwrap(), hir::lower(&v2).unwrap());
+//!
+//! assert_ne!(fingerprint::definition(&v1, "Shape"), fingerprint::definition(&v2, "Shape"));
+//! assert_ne!(fingerprint::module(&v1), fingerprint::module(&v2));
+//! assert_eq!(fingerprint::definition(&v1, "Missing"), None);
+//! ```
+
+use std::collections::HashMap;
+
+use crate::{
+    ast::{Definition, Type, WireEndianness},
+    hir::Hir,
+    symbol::Symbol,
+};
+
+/// 64-bit FNV-1a. Unlike `DefaultHasher`, its output is stable across Rust releases.
+pub(crate) struct Hasher(u64);
+
+impl Default for Hasher {
+    fn default() -> Self {
+        Hasher(0xcbf2_9ce4_8422_2325)
+    }
+}
+
+impl Hasher {
+    /// Hashes `bytes`, prefixed with their length so consecutive writes stay distinct.
+    pub(crate) fn write(&mut self, bytes: &[u8]) {
+        for &byte in (bytes.len() as u64).to_le_bytes().iter().chain(bytes) {
+            self.0 ^= byte as u64;
+            self.0 = self.0.wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+
+    pub(crate) fn finish(&self) -> u64 {
+        self.0
+    }
+}
+
+/// Returns the fingerprint of the definition named `name`, or `None` if the module does not
+/// define it.
+pub fn definition(hir: &Hir, name: impl Into<Symbol>) -> Option<u64> {
+    hash_definition(hir, name.into(), &mut HashMap::new())
+}
+
+/// Returns the fingerprint of the module: its endianness, version, and the fingerprints of its
+/// own definitions.
+///
+/// Definitions the module only refers to, such as those of another module of a
+/// [`Workspace`](crate::workspace::Workspace), count only through the definitions that use
+/// them.
+pub fn module(hir: &Hir) -> u64 {
+    let mut hasher = Hasher::default();
+    let endianness = match hir.module.endianness {
+        WireEndianness::Little => "little",
+        WireEndianness::Big => "big",
+        WireEndianness::Host => "host",
+    };
+    hasher.write(endianness.as_bytes());
+    hasher.write(&hir.module.version.unwrap_or(0).to_le_bytes());
+    let mut names = hir.module.order.clone();
+    names.sort_by_key(|name| name.as_str());
+    let mut memo = HashMap::new();
+    for name in names {
+        if let Some(fingerprint) = hash_definition(hir, name, &mut memo) {
+            hasher.write(&fingerprint.to_le_bytes());
+        }
+    }
+    hasher.finish()
+}
+
+/// Returns the fingerprint of the definition `name`, memoized in `memo`.
+fn hash_definition(hir: &Hir, name: Symbol, memo: &mut HashMap<Symbol, u64>) -> Option<u64> {
+    if let Some(&fingerprint) = memo.get(&name) {
+        return Some(fingerprint);
+    }
+    let mut hasher = Hasher::default();
+    hasher.write(name.as_str().as_bytes());
+    match hir.module.definitions.get(&name)? {
+        Definition::Enum(e) => {
+            hasher.write(if e.flags { b"flags" } else { b"enum" });
+            hasher.write(e.underlying_type.to_string().as_bytes());
+            hasher.write(&[e.open as u8]);
+            for (variant, value) in e.variants.iter().zip(e.variant_values()) {
+                hasher.write(variant.name.as_bytes());
+                hasher.write(&value.to_le_bytes());
+            }
+        }
+        def => {
+            if let Definition::Message(m) = def {
+                hasher.write(b"message");
+                hasher.write(&m.id.map_or(-1, i32::from).to_le_bytes());
+            } else {
+                hasher.write(b"struct");
+            }
+            let layout = hir.layout(name)?;
+            hasher.write(&(layout.size as u64).to_le_bytes());
+            for field in layout.fields() {
+                hasher.write(field.name.as_str().as_bytes());
+                hasher.write(field.type_info.to_string().as_bytes());
+                for value in [field.offset, field.bit_offset, field.bits] {
+                    hasher.write(&(value as u64).to_le_bytes());
+                }
+                let condition = field.condition.as_ref().map(ToString::to_string);
+                hasher.write(condition.unwrap_or_default().as_bytes());
+                if let Type::Custom(used) = field.type_info.element_type()
+                    && let Some(fingerprint) = hash_definition(hir, *used, memo)
+                {
+                    hasher.write(&fingerprint.to_le_bytes());
+                }
+            }
+        }
+    }
+    let fingerprint = hasher.finish();
+    memo.insert(name, fingerprint);
+    Some(fingerprint)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{hir, parser::Parser};
+
+    #[test]
+    fn test_fingerprints() {
+        let fingerprints = |source: &str| {
+            let parsed = Parser::new(source).unwrap().parse_module().unwrap();
+            let hir = hir::lower(&parsed).unwrap();
+            (
+                definition(&hir, "Kind").unwrap(),
+                definition(&hir, "Packet").unwrap(),
+                module(&hir),
+            )
+        };
+        let base = fingerprints(
+            "enum Kind : u8 { A, B, }\n@id(1) @packed_bits message Packet { kind Kind, n u8 : 3, }",
+        );
+        // Comments, notes, and the order of definitions do not count.
+        assert_eq!(
+            fingerprints(
+                "// Packets\n@id(1) @packed_bits message Packet { kind Kind, n u8 : 3, }\n\
+                 @deprecated(\"use v2\") enum Kind : u8 { A, B = 1, }"
+            ),
+            base
+        );
+        // A changed enum value changes the enum, the message using it, and the module.
+        let changed = fingerprints(
+            "enum Kind : u8 { A, B = 2, }\n@id(1) @packed_bits message Packet { kind Kind, n u8 : 3, }",
+        );
+        assert_ne!(changed.0, base.0);
+        assert_ne!(changed.1, base.1);
+        assert_ne!(changed.2, base.2);
+
+        for source in [
+            "enum Kind : u8 { A, B, }\n@id(2) @packed_bits message Packet { kind Kind, n u8 : 3, }",
+            "enum Kind : u8 { A, B, }\n@id(1) @packed_bits message Packet { kind Kind, n u8 : 4, }",
+            "enum Kind : u8 { A, B, }\n@id(1) @packed_bits message Packet { kind Kind, m u8 : 3, }",
+            "enum Kind : u8 { A, B, }\n@packed_bits message Packet { kind Kind, n u8 : 3, }",
+        ] {
+            let changed = fingerprints(source);
+            assert_eq!(changed.0, base.0, "{source}");
+            assert_ne!(changed.1, base.1, "{source}");
+        }
+        let big = fingerprints(
+            "endian = big\nenum Kind : u8 { A, B, }\n@id(1) @packed_bits message Packet { kind Kind, n u8 : 3, }",
+        );
+        assert_eq!(big.1, base.1);
+        assert_ne!(big.2, base.2);
+    }
+}
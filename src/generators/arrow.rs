@@ -0,0 +1,307 @@
+use std::{fmt::Write, path::PathBuf};
+
+use crate::{
+    ast::{Definition, OnyxModule, PrimitiveType},
+    diagnostic::{Diagnostic, codes},
+    features::Feature,
+    fingerprint,
+    generators::{CodeGenerator, CompileError, check_order},
+    hir::{self, FieldLayout, Hir, Layout, TypeRef},
+    json,
+};
+
+#[derive(Debug, Default)]
+/// The Arrow schema generator.
+///
+/// Generates a JSON file (.arrow.json) giving every message the columns a stream of it maps
+/// to, as a schema in Arrow's JSON format, so that logged messages can be converted to Arrow,
+/// Parquet, or CSV in bulk. The module fingerprint identifies the schema the columns belong
+/// to.
+///
+/// Each field is a column, named after it. A nested struct or message is flattened into a
+/// column per field, named with its path such as `pose.x`, so that messages of numbers and
+/// enums give flat rows; a vector is a list column, of structs if its elements are structs.
+/// Enums and flags are integer columns of their underlying type, with their variants in the
+/// column metadata, and a fixed-point number is a decimal if its scale is a power of ten and
+/// a double otherwise. Conditional fields, and the fields flattened from them, are nullable.
+pub struct ArrowGenerator {
+    output: String,
+    file_path: PathBuf,
+}
+
+impl ArrowGenerator {
+    /// Adds a file path to the generator configuration.
+    pub fn add_file_path(&mut self, file_path: PathBuf) -> Result<(), CompileError> {
+        if file_path.file_stem().is_none() {
+            return Err(Diagnostic::error(format!(
+                "output path '{}' has no file name",
+                file_path.display()
+            ))
+            .with_code(codes::CODEGEN));
+        }
+        self.file_path = file_path;
+        Ok(())
+    }
+}
+
+impl CodeGenerator for ArrowGenerator {
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(level = "info", name = "ArrowGenerator::generate", skip_all, fields(path = %self.file_path.display()))
+    )]
+    fn generate(&mut self, module: &OnyxModule) -> Result<Vec<(PathBuf, String)>, CompileError> {
+        check_order(module)?;
+        let hir = hir::lower(module)?;
+
+        let messages: Vec<&Layout> = module
+            .order
+            .iter()
+            .filter(|name| matches!(module.definitions.get(name), Some(Definition::Message(_))))
+            .filter_map(|name| hir.layout(*name))
+            .collect();
+        self.output.clear();
+        writeln!(self.output, "{{").unwrap();
+        writeln!(
+            self.output,
+            "  \"fingerprint\": \"{:016x}\",",
+            fingerprint::module(&hir)
+        )
+        .unwrap();
+        writeln!(self.output, "  \"messages\": [").unwrap();
+        for (i, layout) in messages.iter().enumerate() {
+            let id = match layout.definition {
+                Definition::Message(m) => m.id.map_or("null".to_string(), |id| id.to_string()),
+                _ => "null".to_string(),
+            };
+            let mut fields = Vec::new();
+            flatten(layout, &hir, "", false, &mut fields);
+            writeln!(self.output, "    {{").unwrap();
+            writeln!(
+                self.output,
+                "      \"name\": {},",
+                json::string(layout.name().as_str())
+            )
+            .unwrap();
+            writeln!(self.output, "      \"id\": {id},").unwrap();
+            writeln!(self.output, "      \"schema\": {{").unwrap();
+            writeln!(self.output, "        \"fields\": [").unwrap();
+            for (j, field) in fields.iter().enumerate() {
+                let comma = if j + 1 < fields.len() { "," } else { "" };
+                writeln!(self.output, "          {field}{comma}").unwrap();
+            }
+            writeln!(self.output, "        ]").unwrap();
+            writeln!(self.output, "      }}").unwrap();
+            let comma = if i + 1 < messages.len() { "," } else { "" };
+            writeln!(self.output, "    }}{comma}").unwrap();
+        }
+        writeln!(self.output, "  ]").unwrap();
+        writeln!(self.output, "}}").unwrap();
+
+        let mut output_path = self.file_path.clone();
+        output_path.set_extension("arrow.json");
+        Ok(vec![(output_path, self.output.clone())])
+    }
+
+    fn supports(&self, feature: Feature) -> bool {
+        // Columns follow the latest layout; converting older messages is up to the reader.
+        !matches!(feature, Feature::Versions)
+    }
+}
+
+/// Appends the columns of the fields of `layout` to `columns`, flattening nested structs and
+/// messages into columns named with `prefix` and their path.
+fn flatten(layout: &Layout, hir: &Hir, prefix: &str, nullable: bool, columns: &mut Vec<String>) {
+    for field in layout.fields() {
+        let name = format!("{prefix}{}", field.name);
+        let nullable = nullable || field.condition.is_some();
+        match field.ty {
+            TypeRef::Struct(s) => {
+                let nested = hir.layout(s.name).unwrap();
+                flatten(nested, hir, &format!("{name}."), nullable, columns);
+            }
+            TypeRef::Message(m) => {
+                let nested = hir.layout(m.name).unwrap();
+                flatten(nested, hir, &format!("{name}."), nullable, columns);
+            }
+            ty => columns.push(column(&name, ty, nullable, field, hir)),
+        }
+    }
+}
+
+/// Returns the Arrow field of a column of `ty`, with the deprecation note of `field`.
+fn column(name: &str, ty: TypeRef, nullable: bool, field: &FieldLayout, hir: &Hir) -> String {
+    let mut metadata = Vec::new();
+    if let Some(note) = &field.deprecated {
+        metadata.push(("onyx.deprecated", note.clone()));
+    }
+    arrow_field(name, ty, nullable, metadata, hir)
+}
+
+/// Returns an Arrow field in JSON, with `metadata` added to that of its type.
+fn arrow_field(
+    name: &str,
+    ty: TypeRef,
+    nullable: bool,
+    mut metadata: Vec<(&str, String)>,
+    hir: &Hir,
+) -> String {
+    let mut children = Vec::new();
+    let ty = match ty {
+        TypeRef::Primitive(p) => primitive(&p),
+        TypeRef::Fixed { base, scale } => match decimal_scale(scale) {
+            Some(digits) => format!(
+                "{{\"name\": \"decimal\", \"precision\": {}, \"scale\": {digits}, \"bitWidth\": 128}}",
+                integer_digits(&base).max(digits)
+            ),
+            None => {
+                metadata.push(("onyx.scale", scale.to_string()));
+                "{\"name\": \"floatingpoint\", \"precision\": \"DOUBLE\"}".to_string()
+            }
+        },
+        TypeRef::Uuid => {
+            metadata.push(("ARROW:extension:name", "arrow.uuid".to_string()));
+            "{\"name\": \"fixedsizebinary\", \"byteWidth\": 16}".to_string()
+        }
+        TypeRef::Bytes(len) => format!("{{\"name\": \"fixedsizebinary\", \"byteWidth\": {len}}}"),
+        TypeRef::Enum(e) => {
+            let kind = if e.flags { "onyx.flags" } else { "onyx.enum" };
+            let variants: Vec<String> = e
+                .variants
+                .iter()
+                .zip(e.variant_values())
+                .map(|(variant, value)| format!("{}={value}", variant.name))
+                .collect();
+            metadata.push((kind, e.name.to_string()));
+            metadata.push(("onyx.variants", variants.join(",")));
+            primitive(&e.underlying_type)
+        }
+        TypeRef::Struct(s) => {
+            struct_children(hir.layout(s.name).unwrap(), hir, &mut children);
+            "{\"name\": \"struct\"}".to_string()
+        }
+        TypeRef::Message(m) => {
+            struct_children(hir.layout(m.name).unwrap(), hir, &mut children);
+            "{\"name\": \"struct\"}".to_string()
+        }
+        TypeRef::Vector { element, .. } => {
+            let element = hir.resolve(element).unwrap();
+            children.push(arrow_field("item", element, false, Vec::new(), hir));
+            "{\"name\": \"list\"}".to_string()
+        }
+    };
+    let mut out = format!(
+        "{{\"name\": {}, \"nullable\": {nullable}, \"type\": {ty}, \"children\": [{}]",
+        json::string(name),
+        children.join(", ")
+    );
+    if !metadata.is_empty() {
+        let entries: Vec<String> = metadata
+            .iter()
+            .map(|(key, value)| {
+                format!(
+                    "{{\"key\": {}, \"value\": {}}}",
+                    json::string(key),
+                    json::string(value)
+                )
+            })
+            .collect();
+        write!(out, ", \"metadata\": [{}]", entries.join(", ")).unwrap();
+    }
+    out.push('}');
+    out
+}
+
+/// Appends the fields of `layout` to `children`, as the children of a struct column.
+fn struct_children(layout: &Layout, hir: &Hir, children: &mut Vec<String>) {
+    for field in layout.fields() {
+        children.push(column(
+            field.name.as_str(),
+            field.ty,
+            field.condition.is_some(),
+            field,
+            hir,
+        ));
+    }
+}
+
+/// Returns the Arrow type of a value of `p`.
+fn primitive(p: &PrimitiveType) -> String {
+    match p {
+        PrimitiveType::Bool => "{\"name\": \"bool\"}".to_string(),
+        PrimitiveType::F32 => {
+            "{\"name\": \"floatingpoint\", \"precision\": \"SINGLE\"}".to_string()
+        }
+        PrimitiveType::F64 => {
+            "{\"name\": \"floatingpoint\", \"precision\": \"DOUBLE\"}".to_string()
+        }
+        p => format!(
+            "{{\"name\": \"int\", \"bitWidth\": {}, \"isSigned\": {}}}",
+            p.get_bit_width(),
+            p.is_signed()
+        ),
+    }
+}
+
+/// Returns `n` if `scale` is 10 to the power of `n`.
+fn decimal_scale(scale: u64) -> Option<u32> {
+    (0..20).find(|&n| 10u64.pow(n) == scale)
+}
+
+/// Returns the number of decimal digits of the largest magnitude a value of `p` holds.
+fn integer_digits(p: &PrimitiveType) -> u32 {
+    let max = match p.is_signed() {
+        true => 1u64 << (p.get_bit_width() - 1),
+        false => u64::MAX >> (64 - p.get_bit_width()),
+    };
+    max.ilog10() + 1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_columns() {
+        let module = crate::parser::Parser::new(
+            "enum Kind : u8 { Ping, Data = 4, }\n\
+             struct Pose { x fixed<i32, 1000>, heading fixed<u16, 180>, }\n\
+             struct Point { x i16, y i16, }\n\
+             @id(7) message Track { kind Kind, pose Pose if kind == Kind.Data, \
+             points vec<Point, 2>, tag uuid, }\n\
+             struct Unused { a u8, }",
+        )
+        .unwrap()
+        .parse_module()
+        .unwrap();
+        let mut generator = ArrowGenerator::default();
+        generator.add_file_path(PathBuf::from("out/track")).unwrap();
+        let files = generator.generate(&module).unwrap();
+        assert_eq!(files[0].0, PathBuf::from("out/track.arrow.json"));
+        let output = &files[0].1;
+
+        assert!(output.contains("\"name\": \"Track\",\n      \"id\": 7,"));
+        assert!(!output.contains("Unused"));
+        assert!(output.contains(
+            "{\"name\": \"kind\", \"nullable\": false, \"type\": {\"name\": \"int\", \"bitWidth\": 8, \
+             \"isSigned\": false}, \"children\": [], \"metadata\": [{\"key\": \"onyx.enum\", \
+             \"value\": \"Kind\"}, {\"key\": \"onyx.variants\", \"value\": \"Ping=0,Data=4\"}]}"
+        ));
+        // Nested structs are flattened, and conditional ones make their columns nullable.
+        assert!(output.contains(
+            "{\"name\": \"pose.x\", \"nullable\": true, \"type\": {\"name\": \"decimal\", \
+             \"precision\": 10, \"scale\": 3, \"bitWidth\": 128}, \"children\": []}"
+        ));
+        assert!(output.contains(
+            "{\"name\": \"pose.heading\", \"nullable\": true, \"type\": {\"name\": \
+             \"floatingpoint\", \"precision\": \"DOUBLE\"}, \"children\": [], \"metadata\": \
+             [{\"key\": \"onyx.scale\", \"value\": \"180\"}]}"
+        ));
+        assert!(output.contains(
+            "{\"name\": \"points\", \"nullable\": false, \"type\": {\"name\": \"list\"}, \
+             \"children\": [{\"name\": \"item\", \"nullable\": false, \"type\": {\"name\": \
+             \"struct\"}, \"children\": [{\"name\": \"x\", \"nullable\": false, \"type\": \
+             {\"name\": \"int\", \"bitWidth\": 16, \"isSigned\": true}, \"children\": []}, "
+        ));
+        assert!(output.contains("\"value\": \"arrow.uuid\""));
+    }
+}
@@ -1,13 +1,120 @@
 use std::{
     fmt::Write,
-    path::{MAIN_SEPARATOR, PathBuf},
+    path::{MAIN_SEPARATOR, Path, PathBuf},
 };
 
 use crate::{
-    ast::{Definition, EnumDef, Field, OnyxModule, PrimitiveType, Type, WireEndianness},
-    generators::{CodeGenerator, CompileError},
+    Span,
+    ast::{
+        Condition, Definition, EnumDef, Field, MessageDef, OnyxModule, PrimitiveType, Type,
+        WireEndianness,
+    },
+    diagnostic::{Diagnostic, codes},
+    dynamic::Value,
+    features::{Feature, used_features},
+    fingerprint,
+    generators::{
+        Banner, CodeGenerator, CompileError, SchemaSource, accessor_deprecation, accessor_field,
+        bit_mask, check_constant_names, check_order, comment_lines, definition_constants,
+        doc_lines, external_types, largest_message_size,
+        mangle::{apply_field_naming, mangle_reserved},
+        naming::NamingConvention,
+        own_identified_messages, same_layout, upper_camel_case,
+    },
+    hir::{self, FieldLayout, Hir, Slot, TypeRef},
+    symbol::Symbol,
+    trace,
+    vectors::{self, LeafKind, Step, raw_bits},
 };
 
+/// C++ keywords, which schema names are renamed away from with a trailing `_`.
+const RESERVED: &[&str] = &[
+    "alignas",
+    "alignof",
+    "and",
+    "and_eq",
+    "asm",
+    "auto",
+    "bitand",
+    "bitor",
+    "bool",
+    "break",
+    "case",
+    "catch",
+    "char",
+    "char16_t",
+    "char32_t",
+    "class",
+    "compl",
+    "const",
+    "const_cast",
+    "constexpr",
+    "continue",
+    "decltype",
+    "default",
+    "delete",
+    "do",
+    "double",
+    "dynamic_cast",
+    "else",
+    "enum",
+    "explicit",
+    "export",
+    "extern",
+    "false",
+    "float",
+    "for",
+    "friend",
+    "goto",
+    "if",
+    "inline",
+    "int",
+    "long",
+    "mutable",
+    "namespace",
+    "new",
+    "noexcept",
+    "not",
+    "not_eq",
+    "nullptr",
+    "operator",
+    "or",
+    "or_eq",
+    "private",
+    "protected",
+    "public",
+    "register",
+    "reinterpret_cast",
+    "return",
+    "short",
+    "signed",
+    "sizeof",
+    "static",
+    "static_assert",
+    "static_cast",
+    "struct",
+    "switch",
+    "template",
+    "this",
+    "thread_local",
+    "throw",
+    "true",
+    "try",
+    "typedef",
+    "typeid",
+    "typename",
+    "union",
+    "unsigned",
+    "using",
+    "virtual",
+    "void",
+    "volatile",
+    "wchar_t",
+    "while",
+    "xor",
+    "xor_eq",
+];
+
 /// Configuration settings specific to Python code generation
 #[derive(Debug, Clone)]
 pub struct CppConfig {
@@ -27,16 +134,70 @@ impl CppConfig {
     }
 }
 
+/// Package metadata for emitting a CMake project around the generated code.
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct PackageConfig {
+    /// The CMake project and library name, which also names the header directory.
+    pub name: String,
+    /// The project version, as `major[.minor[.patch[.tweak]]]`.
+    pub version: String,
+}
+
+impl PackageConfig {
+    /// Creates a package configuration with the given name and version.
+    pub fn new(name: impl Into<String>, version: impl Into<String>) -> Self {
+        PackageConfig {
+            name: name.into(),
+            version: version.into(),
+        }
+    }
+}
+
 #[derive(Debug, Default)]
 /// The C++ code generator.
 ///
-/// Generates a header (.hpp) and source (.cpp) file for the given Onyx module.
+/// Generates a header (.hpp) and source (.cpp) file for the given Onyx module, or a single
+/// self-contained header when [amalgamation](CppGenerator::set_amalgamate) is enabled.
 pub struct CppGenerator {
     config: CppConfig,
     header_output: String,
     source_output: String,
+    /// The protocol constants header, when [enabled](CppGenerator::set_constants).
+    constants_output: Option<String>,
+    /// The test vectors, and in a package the test that checks them, when
+    /// [enabled](CppGenerator::set_vectors).
+    vectors_output: Option<(String, Option<String>)>,
     file_path: PathBuf,
     file_stem: String,
+    amalgamate: bool,
+    trace: bool,
+    shared_memory: bool,
+    message_arena: bool,
+    reflection: bool,
+    constants: bool,
+    vectors: bool,
+    simd: bool,
+    source_map: Option<SchemaSource>,
+    banner: Banner,
+    /// The lines of `banner` for the module being generated.
+    banner_lines: Vec<String>,
+    field_naming: NamingConvention,
+    includes: Vec<String>,
+    dependencies: Vec<(String, Vec<String>)>,
+    include_prefix: String,
+    package: Option<PackageConfig>,
+}
+
+/// How a module refers to the types other headers define.
+#[derive(Debug, Default)]
+struct DependencyIncludes {
+    /// Headers the generated header includes.
+    header: Vec<String>,
+    /// Headers only the generated source includes.
+    source: Vec<String>,
+    /// Opaque declarations of enums, written ahead of the classes that store them.
+    declarations: Vec<String>,
 }
 
 impl CppGenerator {
@@ -44,6 +205,22 @@ impl CppGenerator {
     const LITTLE_ENDIAN_DEFINE: &str = "ONYX_LITTLE_ENDIAN";
     const NETWORK_ENDIAN_DEFINE: &str = "ONYX_NETWORK_ORDER";
     const HOST_ENDIAN_DEFINE: &str = "ONYX_HOST_ORDER";
+    /// Silences deprecation warnings until [`Self::RESTORE_DEPRECATIONS`]. The generated code
+    /// uses the deprecated declarations it defines; only code outside it is warned.
+    const IGNORE_DEPRECATIONS: &str = "#if defined(__GNUC__)
+#pragma GCC diagnostic push
+#pragma GCC diagnostic ignored \"-Wdeprecated-declarations\"
+#elif defined(_MSC_VER)
+#pragma warning(push)
+#pragma warning(disable : 4996)
+#endif
+";
+    const RESTORE_DEPRECATIONS: &str = "#if defined(__GNUC__)
+#pragma GCC diagnostic pop
+#elif defined(_MSC_VER)
+#pragma warning(pop)
+#endif
+";
 
     /// Adds a file path to the generator configuration.
     /// This path is used to determine the output filename and the include guard.
@@ -51,487 +228,2314 @@ impl CppGenerator {
         self.file_path = file_path;
         self.file_stem = match self.file_path.file_stem() {
             Some(s) => s.to_string_lossy().to_string(),
-            None => return Err(CompileError(String::new())),
+            None => {
+                return Err(Diagnostic::error(format!(
+                    "output path '{}' has no file name",
+                    self.file_path.display()
+                ))
+                .with_code(codes::CODEGEN));
+            }
         };
         Ok(())
     }
 
-    /// Generates a define string based on the file stem (e.g., "EXAMPLE_ONYX").
-    fn file_stem_define(&self) -> String {
-        self.file_path
-            .to_string_lossy()
-            .to_ascii_uppercase()
-            .replace(MAIN_SEPARATOR, "_")
-            .replace("/", "_")
-            .replace("\\", "_")
-            .replace("-", "_")
-            .replace(".", "_")
+    /// Emits a CMake project instead of bare files.
+    ///
+    /// The project is rooted in the directory of the file path: `CMakeLists.txt`, the header in
+    /// `include/<name>/`, the source in `src/`, and a pkg-config template that CMake fills in
+    /// on install. The library target is `<name>`, also exported as `<name>::<name>`; it is an
+    /// interface library when [amalgamated](CppGenerator::set_amalgamate).
+    pub fn set_package(&mut self, config: PackageConfig) -> Result<(), CompileError> {
+        let valid_name = !config.name.is_empty()
+            && config
+                .name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+        if !valid_name {
+            return Err(
+                Diagnostic::error(format!("invalid package name '{}'", config.name))
+                    .with_code(codes::CODEGEN)
+                    .with_help("package names may only use ASCII letters, digits, '-', and '_'"),
+            );
+        }
+        let parts: Vec<&str> = config.version.split('.').collect();
+        let valid_version = parts.len() <= 4
+            && parts
+                .iter()
+                .all(|part| !part.is_empty() && part.chars().all(|c| c.is_ascii_digit()));
+        if !valid_version {
+            return Err(
+                Diagnostic::error(format!("invalid package version '{}'", config.version))
+                    .with_code(codes::CODEGEN)
+                    .with_help("CMake versions are up to four numbers, such as '1.2.0'"),
+            );
+        }
+        self.package = Some(config);
+        Ok(())
     }
 
-    /// Maps Onyx PrimitiveType to C++ type strings.
-    fn map_primitive_type_to_cpp(&self, p: &PrimitiveType) -> &'static str {
-        match p {
-            PrimitiveType::Bool => "bool",
-            PrimitiveType::U8 => "uint8_t",
-            PrimitiveType::U16 => "uint16_t",
-            PrimitiveType::U32 => "uint32_t",
-            PrimitiveType::U64 => "uint64_t",
-            PrimitiveType::I8 => "int8_t",
-            PrimitiveType::I16 => "int16_t",
-            PrimitiveType::I32 => "int32_t",
-            PrimitiveType::I64 => "int64_t",
-            PrimitiveType::F32 => "float",
-            PrimitiveType::F64 => "double",
-        }
+    /// Emits one header-only file instead of a header and source pair.
+    ///
+    /// The serialization methods are defined `inline` in the header, which depends on nothing
+    /// but the C standard headers, so it can be vendored into a project as a single file.
+    pub fn set_amalgamate(&mut self, amalgamate: bool) {
+        self.amalgamate = amalgamate;
     }
 
-    fn map_byte_width_to_cpp(&self, size: &usize) -> &'static str {
-        assert!(*size > 0);
-        assert!(*size < 9);
-        if *size == 1 {
-            return "uint8_t";
-        } else if *size >= 2 && *size < 4 {
-            return "uint16_t";
-        } else if *size >= 4 && *size < 8 {
-            return "uint32_t";
-        } else if *size == 8 {
-            return "uint64_t";
-        }
-        panic!("unexpected size");
+    /// Emits a trace of every field `Deserialize` decodes, compiled in when `ONYX_TRACE` is
+    /// defined.
+    ///
+    /// Each field is reported with its message, name, byte offset, and host-order value through
+    /// `ONYX_TRACE_FIELD(message, field, offset, value)`, which prints to stderr unless defined
+    /// before the header is included. Nested structs report their own fields, and vectors
+    /// report their length.
+    pub fn set_trace(&mut self, trace: bool) {
+        self.trace = trace;
     }
 
-    fn map_endianness_to_define(&self, endianness: &WireEndianness) -> &'static str {
-        match endianness {
-            WireEndianness::Big => Self::BIG_ENDIAN_DEFINE,
-            WireEndianness::Little => Self::LITTLE_ENDIAN_DEFINE,
-        }
+    /// Emits `at(addr)` and `from_mmap(ptr, len)` on each class, which place it directly on
+    /// memory such as a shared memory ring.
+    ///
+    /// The bytes are used in place, so they hold host byte order: the module is `endian = host`,
+    /// or `Deserialize` has converted them.
+    pub fn set_shared_memory(&mut self, shared_memory: bool) {
+        self.shared_memory = shared_memory;
     }
 
-    fn get_primitive_cpp_type(&self, type_info: &Type) -> String {
-        match type_info {
-            Type::Primitive(p) => self.map_primitive_type_to_cpp(p).to_string(),
-            Type::Custom(s) => s.clone(),
-        }
+    /// Emits `MessageArena<N>`, a pool of `N` buffers sized for the largest message, so hot
+    /// paths can obtain zeroed message buffers without heap allocation. Modules without
+    /// messages get no arena.
+    pub fn set_message_arena(&mut self, message_arena: bool) {
+        self.message_arena = message_arena;
     }
 
-    fn get_field_groups<'a>(&self, fields: &'a Vec<Field>) -> Vec<Vec<&'a Field>> {
-        let mut field_groups: Vec<Vec<&Field>> = Vec::new();
-        let mut current_group: Vec<&Field> = Vec::new();
+    /// Emits a `constexpr std::array<FieldInfo, N>` table, `k<Name>Fields`, ahead of each
+    /// class, and a `visit_fields(visitor)` method that calls `visitor(info, value)` for each
+    /// field present, so that generic logging and serialization layers can walk any class.
+    pub fn set_reflection(&mut self, reflection: bool) {
+        self.reflection = reflection;
+    }
 
-        for field in fields {
-            if field.bit_field_size.is_some() {
-                // Group consecutive bit-fields
-                current_group.push(field);
-            } else {
-                // Non-bit-field encountered. Process the current group, then the standalone field.
-                if !current_group.is_empty() {
-                    field_groups.push(std::mem::take(&mut current_group));
+    /// Emits `<stem>_constants.hpp` next to the header: the protocol numbers of every
+    /// definition in one place, for code that does not use the generated classes.
+    ///
+    /// It depends only on `<stdint.h>` and `<stddef.h>`. `onyx::constants` has a namespace per
+    /// definition holding the value of each enum variant as `k<Variant>`, a message's `kId`,
+    /// the `kSize` in bytes, and the definition's [`kFingerprint`](crate::fingerprint), and a
+    /// `kSchemaFingerprint` of the whole module.
+    pub fn set_constants(&mut self, constants: bool) {
+        self.constants = constants;
+    }
+
+    /// Emits `<stem>.vectors.json` next to the header, or at the root of a package: the
+    /// [test vectors](crate::vectors) of every struct and message.
+    ///
+    /// A package also gets `tests/vectors.cpp`, registered with CTest, which deserializes each
+    /// vector's bytes and checks every field, then sets the fields of a zeroed object and checks
+    /// the bytes it serializes to. With `endian = host`, the vectors hold the byte order of the
+    /// machine that generated them.
+    pub fn set_vectors(&mut self, vectors: bool) {
+        self.vectors = vectors;
+    }
+
+    /// Converts bounded vectors of multi-byte numbers with one bulk byte swap, vectorized with
+    /// SSSE3 or NEON where the compiler targets them and scalar otherwise, instead of element by
+    /// element. Only modules whose wire order can differ from the host's are affected.
+    pub fn set_simd(&mut self, simd: bool) {
+        self.simd = simd;
+    }
+
+    /// Emits a comment before each type and field accessor in the header naming the line of
+    /// `source` that declared it, so that compiler errors and profiler hits in generated code
+    /// can be traced back to the schema.
+    pub fn set_source_map(&mut self, source: SchemaSource) {
+        self.source_map = Some(source);
+    }
+
+    /// Opens every file with `banner` rather than the default one.
+    pub fn set_banner(&mut self, banner: Banner) {
+        self.banner = banner;
+    }
+
+    /// Writes the accessors of each field in `convention`, such as `startTime()` for a field
+    /// `start_time` in camel case. Names are used as written by default.
+    pub fn set_field_naming(&mut self, convention: NamingConvention) {
+        self.field_naming = convention;
+    }
+
+    /// Includes another header as `#include "path"`, whether or not the module uses it. See
+    /// [`add_dependency`](CppGenerator::add_dependency) for headers that define types.
+    pub fn add_include(&mut self, path: impl Into<String>) {
+        self.includes.push(path.into());
+    }
+
+    /// Declares that the header at `path` defines the types named `types`, such as the header
+    /// generated for another module of a workspace.
+    ///
+    /// A module that uses any of them includes the header. An enum only needs its underlying
+    /// type in the generated header, so it is declared there instead and its header included
+    /// by the source; amalgamated headers include it directly.
+    pub fn add_dependency<S: Into<String>>(
+        &mut self,
+        path: impl Into<String>,
+        types: impl IntoIterator<Item = S>,
+    ) {
+        let types = types.into_iter().map(Into::into).collect();
+        self.dependencies.push((path.into(), types));
+    }
+
+    /// Prefixes the path of every [dependency](CppGenerator::add_dependency) in its
+    /// `#include`, such as `generated` for headers found under `generated/` on the include
+    /// path.
+    pub fn set_include_prefix(&mut self, prefix: impl Into<String>) {
+        self.include_prefix = prefix.into();
+    }
+
+    /// Resolves the types `module` uses but does not define to the headers of its
+    /// dependencies. `original` is the module before renaming, whose type names the
+    /// dependencies are declared with.
+    fn dependency_includes(
+        &self,
+        original: &OnyxModule,
+        module: &OnyxModule,
+    ) -> Result<DependencyIncludes, CompileError> {
+        let mut includes = DependencyIncludes::default();
+        // Renaming is one to one, so both list the same types in the same order.
+        for (name, renamed) in external_types(original)
+            .into_iter()
+            .zip(external_types(module))
+        {
+            let Some((path, _)) = self
+                .dependencies
+                .iter()
+                .find(|(_, types)| types.iter().any(|t| t == name.as_str()))
+            else {
+                return Err(Diagnostic::error(format!(
+                    "type '{name}' is not defined by this module or any of its dependencies"
+                ))
+                .with_code(codes::CODEGEN));
+            };
+            let path = match self.include_prefix.trim_end_matches('/') {
+                "" => path.clone(),
+                prefix => format!("{prefix}/{path}"),
+            };
+            match module.definitions.get(&renamed) {
+                Some(Definition::Enum(e)) if !self.amalgamate => {
+                    let underlying_type = self.map_primitive_type_to_cpp(&e.underlying_type);
+                    includes
+                        .declarations
+                        .push(format!("enum class {renamed} : {underlying_type};"));
+                    if !includes.source.contains(&path) {
+                        includes.source.push(path);
+                    }
+                }
+                _ => {
+                    includes.source.retain(|p| *p != path);
+                    if !includes.header.contains(&path) {
+                        includes.header.push(path);
+                    }
                 }
-                field_groups.push(vec![field]); // Non-bit-field is a group of size 1
             }
         }
-        if !current_group.is_empty() {
-            field_groups.push(current_group);
-        }
-        field_groups
+        includes.source.retain(|p| !includes.header.contains(p));
+        Ok(includes)
     }
 
-    fn write_header_includes(&mut self) {
+    /// Returns the generated files, laid out as a CMake project if one is configured.
+    fn output_files(&self) -> Vec<(PathBuf, String)> {
+        let Some(package) = &self.package else {
+            let mut files = vec![(
+                self.file_path.with_extension("hpp"),
+                self.header_output.clone(),
+            )];
+            if !self.amalgamate {
+                files.push((
+                    self.file_path.with_extension("cpp"),
+                    self.source_output.clone(),
+                ));
+            }
+            if let Some(constants) = &self.constants_output {
+                let path = self
+                    .file_path
+                    .with_file_name(format!("{}_constants.hpp", self.file_stem));
+                files.push((path, constants.clone()));
+            }
+            if let Some((vectors, _)) = &self.vectors_output {
+                let path = self
+                    .file_path
+                    .with_file_name(format!("{}.vectors.json", self.file_stem));
+                files.push((path, vectors.clone()));
+            }
+            return files;
+        };
+
+        let root = self.file_path.parent().unwrap_or(Path::new(""));
+        let name = &package.name;
+        let stem = &self.file_stem;
+        let mut files = vec![
+            (root.join("CMakeLists.txt"), self.cmake_lists(package)),
+            (
+                root.join("include").join(name).join(format!("{stem}.hpp")),
+                self.header_output.clone(),
+            ),
+        ];
+        if !self.amalgamate {
+            files.push((
+                root.join("src").join(format!("{stem}.cpp")),
+                self.source_output.clone(),
+            ));
+        }
+        if let Some(constants) = &self.constants_output {
+            let path = root
+                .join("include")
+                .join(name)
+                .join(format!("{stem}_constants.hpp"));
+            files.push((path, constants.clone()));
+        }
+        if let Some((vectors, test)) = &self.vectors_output {
+            files.push((root.join(format!("{stem}.vectors.json")), vectors.clone()));
+            if let Some(test) = test {
+                files.push((root.join("tests").join("vectors.cpp"), test.clone()));
+            }
+        }
+
+        let mut pkg_config = String::new();
+        pkg_config.push_str(&comment_lines(&self.banner_lines, "#"));
+        writeln!(pkg_config, "prefix=@CMAKE_INSTALL_PREFIX@").unwrap();
         writeln!(
-            self.header_output,
-            "// Automatically generated by Onyx IDL compiler"
+            pkg_config,
+            "includedir=${{prefix}}/@CMAKE_INSTALL_INCLUDEDIR@"
         )
         .unwrap();
+        if !self.amalgamate {
+            writeln!(pkg_config, "libdir=${{prefix}}/@CMAKE_INSTALL_LIBDIR@").unwrap();
+        }
+        writeln!(pkg_config, "\nName: {name}").unwrap();
         writeln!(
-            self.header_output,
-            "#ifndef ONYX_{}_H_",
-            self.file_stem_define()
+            pkg_config,
+            "Description: Generated from {stem}.onyx by the Onyx IDL compiler."
         )
         .unwrap();
+        writeln!(pkg_config, "Version: {}", package.version).unwrap();
+        writeln!(pkg_config, "Cflags: -I${{includedir}}").unwrap();
+        if !self.amalgamate {
+            writeln!(pkg_config, "Libs: -L${{libdir}} -l{name}").unwrap();
+        }
+        files.push((root.join(format!("{name}.pc.in")), pkg_config));
+        files
+    }
+
+    /// Returns the `CMakeLists.txt` of a package, which builds the library and installs it
+    /// with its headers, CMake package configuration, and pkg-config file.
+    fn cmake_lists(&self, package: &PackageConfig) -> String {
+        let name = &package.name;
+        let (kind, scope) = match self.amalgamate {
+            true => ("INTERFACE".to_string(), "INTERFACE"),
+            false => (format!("STATIC src/{}.cpp", self.file_stem), "PUBLIC"),
+        };
+        let i1 = self.config.get_indent(1);
+        let mut out = String::new();
+        out.push_str(&comment_lines(&self.banner_lines, "#"));
+        writeln!(out, "cmake_minimum_required(VERSION 3.14)").unwrap();
         writeln!(
-            self.header_output,
-            "#define ONYX_{}_H_\n",
-            self.file_stem_define()
+            out,
+            "project({name} VERSION {} LANGUAGES CXX)\n",
+            package.version
         )
         .unwrap();
-        writeln!(self.header_output, "#include <stdint.h>").unwrap();
-        writeln!(self.header_output, "#include <string.h>\n").unwrap();
-        writeln!(self.header_output, "#pragma pack(push, 1)\n").unwrap();
-    }
-
-    fn write_header_footer(&mut self) {
-        writeln!(self.header_output, "\n#pragma pack(pop)").unwrap();
+        writeln!(out, "include(GNUInstallDirs)\n").unwrap();
+        writeln!(out, "add_library({name} {kind})").unwrap();
+        writeln!(out, "add_library({name}::{name} ALIAS {name})").unwrap();
+        writeln!(out, "target_compile_features({name} {scope} cxx_std_11)").unwrap();
+        writeln!(out, "target_include_directories({name} {scope}").unwrap();
         writeln!(
-            self.header_output,
-            "#endif // ONYX_{}_H_",
-            self.file_stem_define()
+            out,
+            "{i1}$<BUILD_INTERFACE:${{CMAKE_CURRENT_SOURCE_DIR}}/include>"
         )
         .unwrap();
-    }
-
-    fn write_source_includes(&mut self) {
         writeln!(
-            self.source_output,
-            "// Automatically generated by Onyx IDL compiler"
+            out,
+            "{i1}$<INSTALL_INTERFACE:${{CMAKE_INSTALL_INCLUDEDIR}}>)\n"
         )
         .unwrap();
-        writeln!(self.source_output, "#include \"{}.hpp\"\n", self.file_stem).unwrap();
-        writeln!(self.source_output, "#include <string.h>\n").unwrap();
-    }
-
-    fn write_enum(&mut self, e: &EnumDef) -> Result<(), CompileError> {
-        let underlying_type = self.map_primitive_type_to_cpp(&e.underlying_type);
+        writeln!(out, "install(TARGETS {name} EXPORT {name}Targets").unwrap();
+        writeln!(out, "{i1}ARCHIVE DESTINATION ${{CMAKE_INSTALL_LIBDIR}})").unwrap();
         writeln!(
-            self.header_output,
-            "enum class {} : {} {{",
-            e.name, underlying_type
+            out,
+            "install(DIRECTORY include/ DESTINATION ${{CMAKE_INSTALL_INCLUDEDIR}})"
         )
         .unwrap();
-
-        let mut current_value: u64 = 0;
-        for variant in &e.variants {
-            let assigned_value = if let Some(val) = variant.value {
-                current_value = val;
-                format!(" = {val}")
-            } else {
-                let s = if current_value == 0 {
-                    String::new()
-                } else {
-                    format!(" = {current_value}")
-                };
-                current_value += 1;
-                s
-            };
+        writeln!(out, "install(EXPORT {name}Targets").unwrap();
+        writeln!(out, "{i1}NAMESPACE {name}::").unwrap();
+        writeln!(out, "{i1}FILE {name}Config.cmake").unwrap();
+        writeln!(
+            out,
+            "{i1}DESTINATION ${{CMAKE_INSTALL_LIBDIR}}/cmake/{name})"
+        )
+        .unwrap();
+        writeln!(out, "configure_file({name}.pc.in {name}.pc @ONLY)").unwrap();
+        writeln!(out, "install(FILES ${{CMAKE_CURRENT_BINARY_DIR}}/{name}.pc").unwrap();
+        writeln!(out, "{i1}DESTINATION ${{CMAKE_INSTALL_LIBDIR}}/pkgconfig)").unwrap();
+        if self.vectors {
+            writeln!(out, "\ninclude(CTest)").unwrap();
+            writeln!(out, "if(BUILD_TESTING)").unwrap();
+            writeln!(out, "{i1}add_executable({name}_vectors tests/vectors.cpp)").unwrap();
             writeln!(
-                self.header_output,
-                "{}{}{},",
-                self.config.get_indent(1),
-                variant.name,
-                assigned_value
+                out,
+                "{i1}target_link_libraries({name}_vectors PRIVATE {name})"
+            )
+            .unwrap();
+            writeln!(
+                out,
+                "{i1}add_test(NAME {name}_vectors COMMAND {name}_vectors)"
             )
             .unwrap();
+            writeln!(out, "endif()").unwrap();
         }
-        writeln!(self.header_output, "}};").unwrap();
-        Ok(())
+        out
     }
 
-    fn write_class_declaration(
-        &mut self,
-        class_name: &String,
-        field_groups: &Vec<Vec<&Field>>,
+    /// Returns `tests/vectors.cpp` of a package, which checks every vector of `module`, the
+    /// module as renamed for C++.
+    fn vectors_test(
+        &self,
         module: &OnyxModule,
-        size: usize,
-    ) {
-        writeln!(self.header_output, "class {class_name} {{").unwrap();
-        writeln!(self.header_output, "private:").unwrap();
-
-        self.write_class_members(field_groups, module);
-
-        // Public accessors
-        writeln!(self.header_output, "\npublic:").unwrap();
+        package: &PackageConfig,
+    ) -> Result<String, CompileError> {
+        let (i1, i2) = (self.config.get_indent(1), self.config.get_indent(2));
+        let mut out = String::new();
+        writeln!(out, "{}", comment_lines(&self.banner_lines, "//")).unwrap();
         writeln!(
-            self.header_output,
-            "{}static const size_t kSizeOf = {size};",
-            self.config.get_indent(1)
+            out,
+            "#include \"{}/{}.hpp\"\n",
+            package.name, self.file_stem
         )
         .unwrap();
+        writeln!(out, "#include <stdio.h>\n#include <string.h>\n").unwrap();
+        writeln!(out, "using namespace onyx;\n").unwrap();
+        for (ty, bits) in [("float", 32), ("double", 64)] {
+            let size = bits / 8;
+            writeln!(
+                out,
+                "static uint{bits}_t bits{bits}({ty} f) {{ uint{bits}_t b; memcpy(&b, &f, {size}); return b; }}"
+            )
+            .unwrap();
+            writeln!(
+                out,
+                "static {ty} from_bits{bits}(uint{bits}_t b) {{ {ty} f; memcpy(&f, &b, {size}); return f; }}"
+            )
+            .unwrap();
+        }
+        writeln!(out, "\n#define CHECK(cond) \\").unwrap();
         writeln!(
-            self.header_output,
-            "{}using Buffer = uint8_t[kSizeOf];\n",
-            self.config.get_indent(1)
+            out,
+            "{i1}if (!(cond)) {{ fprintf(stderr, \"%s: %s\\n\", vector, #cond); return 1; }}\n"
         )
         .unwrap();
-
-        self.write_class_accessors(field_groups, module);
-        self.write_class_method_declarations(class_name);
-
-        writeln!(self.header_output, "}};").unwrap();
-    }
-
-    fn write_class_members(&mut self, field_groups: &Vec<Vec<&Field>>, module: &OnyxModule) {
-        for group in field_groups {
-            let first_field = group[0];
-            let mut container_bits = 0;
-            for field in group {
-                container_bits += field.get_bit_width(module);
+        writeln!(out, "int main() {{").unwrap();
+        for vector in vectors::vectors(module)? {
+            let name = vector.definition;
+            let def = &module.definitions[&name];
+            let bytes: Vec<String> = vector.bytes.iter().map(|b| format!("{b:#04x}")).collect();
+            writeln!(out, "{i1}{{").unwrap();
+            writeln!(out, "{i2}const char* vector = \"{name} {}\";", vector.name).unwrap();
+            writeln!(
+                out,
+                "{i2}static const uint8_t bytes[] = {{{}}};",
+                bytes.join(", ")
+            )
+            .unwrap();
+            writeln!(out, "{i2}{name}::Buffer buf;").unwrap();
+            writeln!(out, "{i2}memcpy(buf, bytes, sizeof(buf));").unwrap();
+            writeln!(out, "{i2}{name}* view = {name}::Deserialize(buf);").unwrap();
+            writeln!(out, "{i2}CHECK(view != nullptr);").unwrap();
+            let leaves = vectors::leaves(module, def, &vector.value);
+            for leaf in &leaves {
+                let getter = format!("view{}", cpp_path(&leaf.path));
+                let raw = || raw_bits(&leaf.value);
+                let condition = match &leaf.kind {
+                    LeafKind::Primitive(PrimitiveType::F32) => {
+                        format!("bits32({getter}) == {}U", raw())
+                    }
+                    LeafKind::Primitive(PrimitiveType::F64) => {
+                        format!("bits64({getter}) == {}ULL", raw())
+                    }
+                    LeafKind::Enum(_) => format!("static_cast<uint64_t>({getter}) == {}ULL", raw()),
+                    LeafKind::Length(_) => format!("{getter}.len() == {}U", raw()),
+                    LeafKind::Fixed(base) => format!(
+                        "{}_raw() == {}",
+                        getter.strip_suffix("()").unwrap(),
+                        self.cpp_vector_value(*base, &leaf.value)
+                    ),
+                    LeafKind::Bytes => format!("({getter} == {})", cpp_byte_array(&leaf.value)),
+                    LeafKind::Primitive(p) => {
+                        format!("{getter} == {}", self.cpp_vector_value(*p, &leaf.value))
+                    }
+                };
+                writeln!(out, "{i2}CHECK({condition});").unwrap();
             }
-            let container_bytes = container_bits.div_ceil(8);
 
-            if group.len() > 1 || first_field.bit_field_size.is_some() {
-                // This is a bit-field group, replace with a raw container field (byte array)
-                let container_name = format!("__raw_{}", first_field.name);
-                writeln!(
-                    self.header_output,
-                    "{}uint8_t {container_name}[{container_bytes}];",
-                    self.config.get_indent(1)
-                )
-                .unwrap();
-            } else {
-                // Non-bit-field, or a single primitive/custom type (use raw prefix for consistency)
-                let type_str = self.get_primitive_cpp_type(&first_field.type_info);
-                writeln!(
-                    self.header_output,
-                    "{}{type_str} __raw_{name};",
-                    self.config.get_indent(1),
-                    name = first_field.name,
-                )
-                .unwrap();
+            // A zeroed buffer reads the same in either endianness.
+            writeln!(out, "{i2}{name}::Buffer zeroed = {{0}};").unwrap();
+            writeln!(out, "{i2}{name}* obj = ({name}*)zeroed;").unwrap();
+            for leaf in &leaves {
+                let (last, parents) = leaf.path.split_last().unwrap();
+                let parents = cpp_path(parents);
+                let dot = if parents.is_empty() { "->" } else { "." };
+                let value = match &leaf.kind {
+                    LeafKind::Primitive(PrimitiveType::F32) => {
+                        format!("from_bits32({}U)", raw_bits(&leaf.value))
+                    }
+                    LeafKind::Primitive(PrimitiveType::F64) => {
+                        format!("from_bits64({}ULL)", raw_bits(&leaf.value))
+                    }
+                    LeafKind::Enum(e) => {
+                        format!("static_cast<{}>({}ULL)", e.name, raw_bits(&leaf.value))
+                    }
+                    LeafKind::Bytes => cpp_byte_array(&leaf.value),
+                    LeafKind::Primitive(p) | LeafKind::Fixed(p) => {
+                        self.cpp_vector_value(*p, &leaf.value)
+                    }
+                    LeafKind::Length(None) => continue,
+                    LeafKind::Length(Some(record)) => {
+                        let Step::Field(f) = last else { unreachable!() };
+                        writeln!(
+                            out,
+                            "{i2}for (int i = 0; i < {}; ++i) CHECK(obj{parents}{dot}{f}().push({record}()));",
+                            raw_bits(&leaf.value)
+                        )
+                        .unwrap();
+                        continue;
+                    }
+                };
+                match (last, &leaf.kind) {
+                    (Step::Field(f), LeafKind::Fixed(_)) => {
+                        writeln!(out, "{i2}obj{parents}{dot}{f}_raw({value});").unwrap()
+                    }
+                    (Step::Field(f), _) => {
+                        writeln!(out, "{i2}obj{parents}{dot}{f}({value});").unwrap()
+                    }
+                    (Step::Index(_), _) => {
+                        writeln!(out, "{i2}CHECK(obj{parents}.push({value}));").unwrap()
+                    }
+                }
             }
+            writeln!(out, "{i2}{name}::Buffer wire;").unwrap();
+            writeln!(out, "{i2}obj->Serialize(wire);").unwrap();
+            writeln!(out, "{i2}CHECK(memcmp(wire, bytes, sizeof(wire)) == 0);").unwrap();
+            writeln!(out, "{i1}}}").unwrap();
         }
+        writeln!(out, "{i1}return 0;").unwrap();
+        writeln!(out, "}}").unwrap();
+        Ok(out)
     }
 
-    fn write_class_accessors(&mut self, field_groups: &Vec<Vec<&Field>>, module: &OnyxModule) {
-        for group in field_groups {
-            let first_field = group[0];
-            if group.len() > 1 || first_field.bit_field_size.is_some() {
-                self.write_bitfield_accessors(group);
-            } else {
-                self.write_regular_accessor(group[0], module);
-            }
+    /// Returns the C++ literal of the integer or bool value of a test vector leaf of type `p`.
+    fn cpp_vector_value(&self, p: PrimitiveType, value: &Value) -> String {
+        match value {
+            Value::Bool(b) => b.to_string(),
+            _ => format!(
+                "static_cast<{}>({}ULL)",
+                self.map_primitive_type_to_cpp(&p),
+                raw_bits(value)
+            ),
         }
     }
 
-    fn write_bitfield_accessors(&mut self, group: &Vec<&Field>) {
-        let first_field = group[0];
-        let container_name = format!("__raw_{}", first_field.name);
-        let mut current_bit_offset: usize = 0;
+    /// Returns true if the bounded vectors of `module` are swapped in bulk.
+    fn swaps_in_bulk(&self, module: &OnyxModule) -> bool {
+        self.simd
+            && module.endianness.is_portable()
+            && used_features(module).contains(&Feature::BoundedVectors)
+    }
 
-        for field in group {
-            let field_type_str = self.get_primitive_cpp_type(&field.type_info);
-            let bits = field.bit_field_size.unwrap_or(0);
-            let mask = (1u64 << bits).saturating_sub(1);
-            let current_byte_offset = current_bit_offset / 8;
-            let local_bit_offset = current_bit_offset % 8;
-            let temp_bit_width = local_bit_offset + bits;
-            let temp_byte_width = temp_bit_width.div_ceil(8);
-            let temp_container = self.map_byte_width_to_cpp(&temp_byte_width);
+    /// Writes the `MessageId` enum of the messages marked `@id`, ahead of their classes.
+    fn write_message_ids(&mut self, module: &OnyxModule) {
+        let messages = own_identified_messages(module);
+        if messages.is_empty() {
+            return;
+        }
+        let i1 = self.config.get_indent(1);
+        let out = &mut self.header_output;
+        writeln!(
+            out,
+            "/// Size of the ID that precedes an identified message, in bytes."
+        )
+        .unwrap();
+        writeln!(out, "static const size_t kMessageIdSize = 2;\n").unwrap();
+        writeln!(
+            out,
+            "/// The IDs of the messages that DecodeAny dispatches on."
+        )
+        .unwrap();
+        writeln!(out, "enum class MessageId : uint16_t {{").unwrap();
+        for m in &messages {
+            writeln!(out, "{i1}{} = {},", m.name, m.id.unwrap_or_default()).unwrap();
+        }
+        writeln!(out, "}};\n").unwrap();
+        writeln!(
+            out,
+            "/// Writes a message ID to the first kMessageIdSize bytes of the buffer."
+        )
+        .unwrap();
+        writeln!(
+            out,
+            "inline void WriteMessageId(MessageId id, uint8_t* buffer) {{"
+        )
+        .unwrap();
+        writeln!(
+            out,
+            "{i1}uint16_t raw = utils::byteswap_if_needed(static_cast<uint16_t>(id));"
+        )
+        .unwrap();
+        writeln!(out, "{i1}memcpy(buffer, &raw, kMessageIdSize);").unwrap();
+        writeln!(out, "}}\n").unwrap();
+    }
 
-            // Accessor logic
-            writeln!(
-                self.header_output,
-                "{}/// Accessor for {field_name}",
-                self.config.get_indent(1),
-                field_name = field.name
-            )
-            .unwrap();
-            writeln!(
-                self.header_output,
-                "{}inline const {field_type_str} {field_name}() const {{",
-                self.config.get_indent(1),
-                field_name = field.name,
-            )
-            .unwrap();
-            writeln!(
-                self.header_output,
-                "{}auto raw_value = reinterpret_cast<const {temp_container}*>(&{container_name}[{current_byte_offset}]);", self.config.get_indent(2)
-            ).unwrap();
-            writeln!(
-                self.header_output,
-                "{}return static_cast<const {field_type_str}>(((*raw_value >> {current_bit_offset}) & 0x{mask:X}));", self.config.get_indent(2)
+    /// Declares the class of every struct and message ahead of the definitions, so that a class
+    /// can name one defined after it. Classes embed each other by value and so are still
+    /// defined in dependency order.
+    fn write_forward_declarations(&mut self, module: &OnyxModule) {
+        let classes: Vec<&Symbol> = module
+            .order
+            .iter()
+            .filter(|id| !matches!(module.definitions.get(id), Some(Definition::Enum(_)) | None))
+            .collect();
+        if classes.is_empty() {
+            return;
+        }
+        for id in classes {
+            writeln!(self.header_output, "class {id};").unwrap();
+        }
+        writeln!(self.header_output).unwrap();
+    }
+
+    /// Writes `AnyMessage` and `DecodeAny`, which deserializes a message preceded by its ID.
+    fn write_dispatch(&mut self, module: &OnyxModule) {
+        let messages = own_identified_messages(module);
+        if messages.is_empty() {
+            return;
+        }
+        let (i1, i2) = (self.config.get_indent(1), self.config.get_indent(2));
+        let specifier = self.definition_specifier();
+        let out = &mut self.header_output;
+        writeln!(
+            out,
+            "/// A message deserialized by DecodeAny: its ID and the message within the buffer."
+        )
+        .unwrap();
+        writeln!(out, "struct AnyMessage {{").unwrap();
+        writeln!(out, "{i1}MessageId id;").unwrap();
+        writeln!(out, "{i1}void* message;").unwrap();
+        writeln!(out).unwrap();
+        writeln!(
+            out,
+            "{i1}/// Returns the message as a T, or nullptr if it is another message."
+        )
+        .unwrap();
+        writeln!(out, "{i1}template <typename T>").unwrap();
+        writeln!(out, "{i1}T* as() const {{").unwrap();
+        writeln!(
+            out,
+            "{i2}return id == T::kId ? static_cast<T*>(message) : nullptr;"
+        )
+        .unwrap();
+        writeln!(out, "{i1}}}").unwrap();
+        writeln!(out, "}};\n").unwrap();
+        writeln!(
+            out,
+            "/// Deserializes the message that follows a message ID in the buffer, in place."
+        )
+        .unwrap();
+        writeln!(
+            out,
+            "/// Returns false if the buffer is too short, no message has the ID, or the message's"
+        )
+        .unwrap();
+        writeln!(out, "/// Deserialize returns nullptr.").unwrap();
+        writeln!(
+            out,
+            "bool DecodeAny(uint8_t* buffer, size_t size, AnyMessage& out);\n"
+        )
+        .unwrap();
+
+        let out = &mut self.source_output;
+        writeln!(
+            out,
+            "{specifier}bool DecodeAny(uint8_t* buffer, size_t size, AnyMessage& out) {{"
+        )
+        .unwrap();
+        writeln!(out, "{i1}if (size < kMessageIdSize) return false;").unwrap();
+        writeln!(out, "{i1}uint16_t raw;").unwrap();
+        writeln!(out, "{i1}memcpy(&raw, buffer, kMessageIdSize);").unwrap();
+        writeln!(
+            out,
+            "{i1}out.id = static_cast<MessageId>(utils::byteswap_if_needed(raw));"
+        )
+        .unwrap();
+        writeln!(out, "{i1}uint8_t* body = buffer + kMessageIdSize;").unwrap();
+        writeln!(out, "{i1}size -= kMessageIdSize;").unwrap();
+        writeln!(out, "{i1}switch (out.id) {{").unwrap();
+        for m in &messages {
+            let name = m.name;
+            writeln!(out, "{i1}case MessageId::{name}:").unwrap();
+            writeln!(out, "{i2}if (size < {name}::kSizeOf) return false;").unwrap();
+            writeln!(
+                out,
+                "{i2}out.message = {name}::Deserialize(*reinterpret_cast<{name}::Buffer*>(body));"
             )
             .unwrap();
-            writeln!(self.header_output, "{}}}\n", self.config.get_indent(1)).unwrap();
+            writeln!(out, "{i2}return out.message != nullptr;").unwrap();
+        }
+        writeln!(out, "{i1}}}").unwrap();
+        writeln!(out, "{i1}return false;").unwrap();
+        writeln!(out, "}}\n").unwrap();
+    }
 
-            // Mutator logic
+    /// Returns the specifier for out-of-line method definitions.
+    fn definition_specifier(&self) -> &'static str {
+        if self.amalgamate { "inline " } else { "" }
+    }
+
+    /// Generates a define string based on the file stem (e.g., "EXAMPLE_ONYX").
+    fn file_stem_define(&self) -> String {
+        self.file_path
+            .to_string_lossy()
+            .to_ascii_uppercase()
+            .replace(MAIN_SEPARATOR, "_")
+            .replace("/", "_")
+            .replace("\\", "_")
+            .replace("-", "_")
+            .replace(".", "_")
+    }
+
+    /// Returns the protocol constants header of `module`, the module as parsed.
+    fn constants_file(&self, module: &OnyxModule) -> Result<String, CompileError> {
+        let define = format!("ONYX_{}_CONSTANTS_H_", self.file_stem_define());
+        let i1 = self.config.get_indent(1);
+        let mut out = String::new();
+        out.push_str(&comment_lines(&self.banner_lines, "//"));
+        writeln!(out, "#ifndef {define}\n#define {define}\n").unwrap();
+        writeln!(out, "#include <stddef.h>\n#include <stdint.h>\n").unwrap();
+        writeln!(out, "namespace onyx {{\nnamespace constants {{\n").unwrap();
+        writeln!(
+            out,
+            "/// The fingerprint of the whole schema, which changes with any of its definitions."
+        )
+        .unwrap();
+        let schema = fingerprint::module(&hir::lower(module)?);
+        writeln!(
+            out,
+            "static const uint64_t kSchemaFingerprint = UINT64_C({schema:#018x});"
+        )
+        .unwrap();
+
+        let definitions = definition_constants(module)?;
+        let scope = |def: &Definition| match RESERVED.contains(&def.name()) {
+            true => format!("{}_", def.name()),
+            false => def.name().to_string(),
+        };
+        let scopes: Vec<String> = definitions.iter().map(|(def, ..)| scope(def)).collect();
+        check_constant_names(
+            "namespace onyx::constants",
+            scopes.iter().map(String::as_str),
+        )?;
+        for ((def, size, fingerprint), scope) in definitions.into_iter().zip(scopes) {
+            let mut constants = Vec::new();
+            let kind = match def {
+                Definition::Enum(e) => {
+                    let ty = self.map_primitive_type_to_cpp(&e.underlying_type);
+                    for (variant, value) in e.variants.iter().zip(e.variant_values()) {
+                        let name = NamingConvention::PascalCase.apply(&variant.name);
+                        constants.push((format!("k{name}"), ty, value.to_string()));
+                    }
+                    if e.flags { "flags" } else { "enum" }
+                }
+                Definition::Message(m) => {
+                    if let Some(id) = m.id {
+                        constants.push(("kId".to_string(), "uint16_t", id.to_string()));
+                    }
+                    "message"
+                }
+                Definition::Struct(_) => "struct",
+            };
+            constants.push(("kSize".to_string(), "size_t", size.to_string()));
+            constants.push((
+                "kFingerprint".to_string(),
+                "uint64_t",
+                format!("UINT64_C({fingerprint:#018x})"),
+            ));
+            check_constant_names(
+                &format!("the constants of {kind} '{}'", def.name()),
+                constants.iter().map(|(name, ..)| name.as_str()),
+            )?;
+
+            writeln!(out, "\n/// The constants of the {kind} {}.", def.name()).unwrap();
+            writeln!(out, "namespace {scope} {{").unwrap();
+            for (name, ty, value) in constants {
+                writeln!(out, "{i1}static const {ty} {name} = {value};").unwrap();
+            }
+            writeln!(out, "}} // namespace {scope}").unwrap();
+        }
+
+        writeln!(out, "\n}} // namespace constants\n}} // namespace onyx\n").unwrap();
+        writeln!(out, "#endif // {define}").unwrap();
+        Ok(out)
+    }
+
+    /// Maps Onyx PrimitiveType to C++ type strings.
+    fn map_primitive_type_to_cpp(&self, p: &PrimitiveType) -> &'static str {
+        match p {
+            PrimitiveType::Bool => "bool",
+            PrimitiveType::U8 => "uint8_t",
+            PrimitiveType::U16 => "uint16_t",
+            PrimitiveType::U32 => "uint32_t",
+            PrimitiveType::U64 => "uint64_t",
+            PrimitiveType::I8 => "int8_t",
+            PrimitiveType::I16 => "int16_t",
+            PrimitiveType::I32 => "int32_t",
+            PrimitiveType::I64 => "int64_t",
+            PrimitiveType::F32 => "float",
+            PrimitiveType::F64 => "double",
+        }
+    }
+
+    /// Returns the smallest unsigned type that holds `size` bytes.
+    fn map_byte_width_to_cpp(&self, size: &usize) -> &'static str {
+        assert!(*size > 0);
+        assert!(*size < 9);
+        if *size == 1 {
+            return "uint8_t";
+        } else if *size == 2 {
+            return "uint16_t";
+        } else if *size > 2 && *size <= 4 {
+            return "uint32_t";
+        } else if *size > 4 && *size <= 8 {
+            return "uint64_t";
+        }
+        panic!("unexpected size");
+    }
+
+    /// Writes the swap of a bit-field container of `bytes` bytes between the wire order and
+    /// the order its accessors read it in.
+    ///
+    /// Containers of 2, 4 or 8 bytes are held in host order. Any other width has no integer
+    /// type of its own, so it is held in little-endian order and its accessors assemble it a
+    /// byte at a time.
+    fn write_bit_field_container_swap(&mut self, object: &str, name: &Symbol, bytes: usize) {
+        let i1 = self.config.get_indent(1);
+        writeln!(
+            self.source_output,
+            "{i1}// SWAP: Bit-field container {name}"
+        )
+        .unwrap();
+        if bytes.is_power_of_two() {
+            let container_type = self.map_byte_width_to_cpp(&bytes);
             writeln!(
-                self.header_output,
-                "{}/// Mutator for {field_name}",
-                self.config.get_indent(1),
-                field_name = field.name
+                self.source_output,
+                "{i1}*({container_type}*){object}->__raw_{name} = utils::byteswap_if_needed(*({container_type}*){object}->__raw_{name});"
             )
             .unwrap();
+            return;
+        }
+        let i2 = self.config.get_indent(2);
+        let i3 = self.config.get_indent(3);
+        writeln!(
+            self.source_output,
+            "{i1}if ({} == {}) {{",
+            Self::NETWORK_ENDIAN_DEFINE,
+            Self::BIG_ENDIAN_DEFINE
+        )
+        .unwrap();
+        writeln!(
+            self.source_output,
+            "{i2}for (size_t i = 0; i < {}; ++i) {{",
+            bytes / 2
+        )
+        .unwrap();
+        writeln!(
+            self.source_output,
+            "{i3}uint8_t byte = {object}->__raw_{name}[i];"
+        )
+        .unwrap();
+        writeln!(
+            self.source_output,
+            "{i3}{object}->__raw_{name}[i] = {object}->__raw_{name}[{} - i];",
+            bytes - 1
+        )
+        .unwrap();
+        writeln!(
+            self.source_output,
+            "{i3}{object}->__raw_{name}[{} - i] = byte;",
+            bytes - 1
+        )
+        .unwrap();
+        writeln!(self.source_output, "{i2}}}").unwrap();
+        writeln!(self.source_output, "{i1}}}").unwrap();
+    }
+
+    fn map_endianness_to_define(&self, endianness: &WireEndianness) -> &'static str {
+        match endianness {
+            WireEndianness::Big => Self::BIG_ENDIAN_DEFINE,
+            WireEndianness::Little => Self::LITTLE_ENDIAN_DEFINE,
+            // The wire order follows the host, so byteswap_if_needed never swaps.
+            WireEndianness::Host => Self::HOST_ENDIAN_DEFINE,
+        }
+    }
+
+    fn get_primitive_cpp_type(&self, type_info: &Type) -> String {
+        match type_info {
+            Type::Primitive(p) | Type::Fixed { base: p, .. } => {
+                self.map_primitive_type_to_cpp(p).to_string()
+            }
+            Type::Uuid | Type::Bytes(_) => {
+                format!("std::array<uint8_t, {}>", type_info.byte_len().unwrap())
+            }
+            Type::Custom(s) => s.to_string(),
+            Type::Vector { element, capacity } => format!(
+                "utils::BoundedVec<{}, {}, {capacity}>",
+                self.get_primitive_cpp_type(element),
+                self.map_primitive_type_to_cpp(&type_info.length_type().unwrap())
+            ),
+        }
+    }
+
+    /// Returns the name of the offset constant of `field`: `kStartTimeOffset` for `start_time`.
+    fn offset_constant(field: &Field) -> String {
+        format!("k{}Offset", upper_camel_case(field.name.as_str()))
+    }
+
+    /// Returns `ONYX_DEPRECATED("note") ` for the note of a `@deprecated` attribute, or an empty
+    /// string if there is none.
+    fn deprecated_attribute(note: Option<&str>) -> String {
+        note.map(|note| {
+            let note = note.replace('\\', "\\\\").replace('"', "\\\"");
+            format!("ONYX_DEPRECATED(\"{note}\") ")
+        })
+        .unwrap_or_default()
+    }
+
+    fn write_header_includes(&mut self, module: &OnyxModule, dependencies: &[String]) {
+        self.header_output
+            .push_str(&comment_lines(&self.banner_lines, "//"));
+        writeln!(
+            self.header_output,
+            "#ifndef ONYX_{}_H_",
+            self.file_stem_define()
+        )
+        .unwrap();
+        writeln!(
+            self.header_output,
+            "#define ONYX_{}_H_\n",
+            self.file_stem_define()
+        )
+        .unwrap();
+        writeln!(self.header_output, "#include <stddef.h>").unwrap();
+        writeln!(self.header_output, "#include <stdint.h>").unwrap();
+        writeln!(self.header_output, "#include <string.h>").unwrap();
+        if self.trace {
+            writeln!(self.header_output, "\n#ifdef ONYX_TRACE").unwrap();
+            writeln!(self.header_output, "#include <stdio.h>").unwrap();
+            writeln!(self.header_output, "#endif").unwrap();
+        }
+        if self.swaps_in_bulk(module) {
+            writeln!(self.header_output, "\n#if defined(__SSSE3__)").unwrap();
+            writeln!(self.header_output, "#include <tmmintrin.h>").unwrap();
+            writeln!(self.header_output, "#elif defined(__ARM_NEON)").unwrap();
+            writeln!(self.header_output, "#include <arm_neon.h>").unwrap();
+            writeln!(self.header_output, "#endif").unwrap();
+        }
+        if used_features(module).contains(&Feature::ByteArrays) {
+            writeln!(self.header_output, "\n#include <array>").unwrap();
+            writeln!(self.header_output, "#include <string>").unwrap();
+        } else if self.reflection {
+            writeln!(self.header_output, "\n#include <array>").unwrap();
+        }
+        if !self.includes.is_empty() || !dependencies.is_empty() {
+            writeln!(self.header_output).unwrap();
+        }
+        for include in self.includes.iter().chain(dependencies) {
+            writeln!(self.header_output, "#include \"{include}\"").unwrap();
+        }
+        writeln!(self.header_output, "\n#pragma pack(push, 1)\n").unwrap();
+        if used_features(module).contains(&Feature::Deprecations) {
+            writeln!(self.header_output, "{}", Self::IGNORE_DEPRECATIONS).unwrap();
+        }
+    }
+
+    fn write_header_footer(&mut self, module: &OnyxModule) {
+        if used_features(module).contains(&Feature::Deprecations) {
+            write!(self.header_output, "\n{}", Self::RESTORE_DEPRECATIONS).unwrap();
+        }
+        writeln!(self.header_output, "\n#pragma pack(pop)").unwrap();
+        writeln!(
+            self.header_output,
+            "#endif // ONYX_{}_H_",
+            self.file_stem_define()
+        )
+        .unwrap();
+    }
+
+    fn write_source_includes(&mut self, module: &OnyxModule, dependencies: &[String]) {
+        self.source_output
+            .push_str(&comment_lines(&self.banner_lines, "//"));
+        let own_header = match &self.package {
+            Some(package) => format!("{}/{}.hpp", package.name, self.file_stem),
+            None => format!("{}.hpp", self.file_stem),
+        };
+        writeln!(self.source_output, "#include \"{own_header}\"").unwrap();
+        for include in dependencies {
+            writeln!(self.source_output, "#include \"{include}\"").unwrap();
+        }
+        writeln!(self.source_output).unwrap();
+        writeln!(self.source_output, "#include <string.h>\n").unwrap();
+        if used_features(module).contains(&Feature::Deprecations) {
+            writeln!(self.source_output, "{}", Self::IGNORE_DEPRECATIONS).unwrap();
+        }
+    }
+
+    fn write_enum(&mut self, e: &EnumDef) -> Result<(), CompileError> {
+        let underlying_type = self.map_primitive_type_to_cpp(&e.underlying_type);
+        self.header_output
+            .push_str(&doc_lines(e.doc.as_deref(), ""));
+        writeln!(
+            self.header_output,
+            "enum class {}{} : {} {{",
+            Self::deprecated_attribute(e.deprecated.as_deref()),
+            e.name,
+            underlying_type
+        )
+        .unwrap();
+
+        for (variant, value) in e.variants.iter().zip(e.variant_values()) {
+            let assigned_value = if variant.value.is_none() && value == 0 {
+                String::new()
+            } else {
+                format!(" = {value}")
+            };
+            let indent = self.config.get_indent(1);
+            self.header_output
+                .push_str(&doc_lines(variant.doc.as_deref(), &indent));
             writeln!(
                 self.header_output,
-                "{}inline void {field_name}({field_type_str} value) {{",
+                "{}{}{},",
                 self.config.get_indent(1),
-                field_name = field.name
+                variant.name,
+                assigned_value
+            )
+            .unwrap();
+        }
+        writeln!(self.header_output, "}};").unwrap();
+        if e.flags {
+            self.write_flag_operators(e);
+        } else {
+            self.write_is_known(e);
+        }
+        Ok(())
+    }
+
+    /// Writes `is_known`, which tells whether a value of an enum is declared by a variant.
+    fn write_is_known(&mut self, e: &EnumDef) {
+        let name = e.name.as_str();
+        let condition = if e.variants.is_empty() {
+            "false".to_string()
+        } else {
+            e.variants
+                .iter()
+                .map(|variant| format!("value == {name}::{}", variant.name))
+                .collect::<Vec<_>>()
+                .join(" || ")
+        };
+        let out = &mut self.header_output;
+        writeln!(
+            out,
+            "\n/// Returns true if `value` is declared by a variant of {name}."
+        )
+        .unwrap();
+        writeln!(out, "inline bool is_known({name} value) {{").unwrap();
+        writeln!(out, "{}return {condition};", self.config.get_indent(1)).unwrap();
+        writeln!(out, "}}").unwrap();
+    }
+
+    /// Returns true if `type_info` is a struct or message, or a vector of them, whose
+    /// deserialization checks enum values.
+    fn nests_checked_class(module: &OnyxModule, type_info: &Type) -> bool {
+        let Type::Custom(name) = type_info.element_type() else {
+            return false;
+        };
+        !matches!(module.definitions.get(name), Some(Definition::Enum(_)))
+            && Self::checks_enums(module, type_info)
+    }
+
+    /// Returns true if a value of `type_info` holds a closed enum, directly or within a nested
+    /// type or vector, so that deserializing it checks enum values.
+    fn checks_enums(module: &OnyxModule, type_info: &Type) -> bool {
+        let Type::Custom(name) = type_info.element_type() else {
+            return false;
+        };
+        match module.definitions.get(name) {
+            Some(Definition::Enum(e)) => !e.open && !e.flags,
+            Some(Definition::Struct(s)) => Self::fields_check_enums(module, &s.fields),
+            Some(Definition::Message(m)) => Self::fields_check_enums(module, &m.fields),
+            None => false,
+        }
+    }
+
+    fn fields_check_enums(module: &OnyxModule, fields: &[Field]) -> bool {
+        fields
+            .iter()
+            .any(|field| Self::checks_enums(module, &field.type_info))
+    }
+
+    /// Writes the bitwise operators of a flags enum. `~` clears the bits no variant declares.
+    fn write_flag_operators(&mut self, e: &EnumDef) {
+        let name = e.name.as_str();
+        let t = self.map_primitive_type_to_cpp(&e.underlying_type);
+        let all = e
+            .variants
+            .iter()
+            .zip(e.variant_values())
+            .filter(|(variant, _)| !variant.composite)
+            .fold(0u64, |bits, (_, value)| bits | value);
+        let i1 = self.config.get_indent(1);
+        let out = &mut self.header_output;
+        for op in ["|", "&", "^"] {
+            writeln!(
+                out,
+                "\ninline constexpr {name} operator{op}({name} a, {name} b) {{"
             )
             .unwrap();
-            writeln!(self.header_output, "{}auto raw_container = reinterpret_cast<{temp_container}*>(&{container_name}[{current_byte_offset}]);", self.config.get_indent(2)).unwrap();
             writeln!(
-                self.header_output,
-                "{}*raw_container &= ~((({temp_container})0x{mask:X}) << {current_bit_offset});",
-                self.config.get_indent(2)
+                out,
+                "{i1}return static_cast<{name}>(static_cast<{t}>(a) {op} static_cast<{t}>(b));"
             )
             .unwrap();
-            writeln!(self.header_output,"{}*raw_container |= (((({temp_container})value) & 0x{mask:X}) << {current_bit_offset});", self.config.get_indent(2)).unwrap();
-            writeln!(self.header_output, "{}}}\n", self.config.get_indent(1)).unwrap();
-
-            current_bit_offset += bits;
+            writeln!(out, "}}").unwrap();
+            writeln!(
+                out,
+                "\ninline {name}& operator{op}=({name}& a, {name} b) {{"
+            )
+            .unwrap();
+            writeln!(out, "{i1}return a = a {op} b;").unwrap();
+            writeln!(out, "}}").unwrap();
         }
+        writeln!(out, "\ninline constexpr {name} operator~({name} a) {{").unwrap();
+        writeln!(
+            out,
+            "{i1}return static_cast<{name}>(~static_cast<{t}>(a) & {all}u);"
+        )
+        .unwrap();
+        writeln!(out, "}}").unwrap();
     }
 
-    fn write_regular_accessor(&mut self, field: &Field, module: &OnyxModule) {
-        let type_str = self.get_primitive_cpp_type(&field.type_info);
-        let is_class = match &field.type_info {
-            Type::Custom(s) => matches!(
-                module.definitions.get(s),
-                Some(Definition::Struct(_)) | Some(Definition::Message(_))
-            ),
-            _ => false,
+    fn write_class_declaration(
+        &mut self,
+        class_name: &str,
+        slots: &[Slot],
+        module: &OnyxModule,
+        size: usize,
+        upgrade_to: Option<&str>,
+    ) {
+        // Earlier versions are only used to upgrade, so they are not deprecated or documented
+        // themselves.
+        let def = match upgrade_to {
+            None => module.definitions.get(&Symbol::intern(class_name)),
+            Some(_) => None,
         };
-        let accessor_const = if is_class {
-            String::new()
-        } else {
-            "const ".to_string()
+        let attribute = Self::deprecated_attribute(def.and_then(Definition::deprecated));
+        self.header_output
+            .push_str(&doc_lines(def.and_then(Definition::doc), ""));
+        writeln!(self.header_output, "class {attribute}{class_name} {{").unwrap();
+        writeln!(self.header_output, "private:").unwrap();
+
+        self.write_class_members(slots);
+
+        // Public accessors
+        writeln!(self.header_output, "\npublic:").unwrap();
+        writeln!(
+            self.header_output,
+            "{}static const size_t kSizeOf = {size};",
+            self.config.get_indent(1)
+        )
+        .unwrap();
+        writeln!(
+            self.header_output,
+            "{}using Buffer = uint8_t[kSizeOf];\n",
+            self.config.get_indent(1)
+        )
+        .unwrap();
+        self.write_offset_constants(slots);
+        if upgrade_to.is_none()
+            && let Some(Definition::Message(MessageDef { id: Some(_), .. })) =
+                module.definitions.get(&Symbol::intern(class_name))
+        {
+            let indent = self.config.get_indent(1);
+            writeln!(
+                self.header_output,
+                "{indent}/// The ID that precedes {class_name} on the wire."
+            )
+            .unwrap();
+            writeln!(
+                self.header_output,
+                "{indent}static const MessageId kId = MessageId::{class_name};\n"
+            )
+            .unwrap();
+        }
+
+        self.write_class_accessors(slots, module);
+        let checks = slots
+            .iter()
+            .flat_map(|slot| &slot.fields)
+            .any(|field| Self::checks_enums(module, &field.type_info));
+        self.write_class_method_declarations(class_name, checks);
+        if self.shared_memory {
+            self.write_placement_helpers(class_name);
+        }
+        if self.reflection && upgrade_to.is_none() {
+            self.write_field_visitor(class_name, slots);
+        }
+        if let Some(target) = upgrade_to {
+            let indent = self.config.get_indent(1);
+            writeln!(self.header_output).unwrap();
+            writeln!(
+                self.header_output,
+                "{indent}/// Converts the host-endian object to the current version of {class_name}."
+            )
+            .unwrap();
+            writeln!(
+                self.header_output,
+                "{indent}/// Fields added since are zeroed."
+            )
+            .unwrap();
+            writeln!(
+                self.header_output,
+                "{indent}void Upgrade({target}& out) const;"
+            )
+            .unwrap();
+        }
+        self.write_layout_check(class_name, slots, size);
+
+        writeln!(self.header_output, "}};").unwrap();
+    }
+
+    /// Writes `k<Name>Fields`, the table of the fields of `class_name` that `visit_fields`
+    /// passes to its visitor.
+    fn write_field_table(&mut self, class_name: &str, slots: &[Slot]) {
+        let indent = self.config.get_indent(1);
+        let fields: Vec<&FieldLayout> = slots.iter().flat_map(|slot| &slot.fields).collect();
+        let out = &mut self.header_output;
+        writeln!(out, "/// The fields of {class_name}, in wire order.").unwrap();
+        write!(
+            out,
+            "constexpr std::array<FieldInfo, {}> k{class_name}Fields = {{{{",
+            fields.len()
+        )
+        .unwrap();
+        for (i, field) in fields.iter().enumerate() {
+            let kind = match field.ty {
+                TypeRef::Primitive(PrimitiveType::Bool) => "Bool",
+                TypeRef::Primitive(
+                    PrimitiveType::U8
+                    | PrimitiveType::U16
+                    | PrimitiveType::U32
+                    | PrimitiveType::U64,
+                ) => "Unsigned",
+                TypeRef::Primitive(
+                    PrimitiveType::I8
+                    | PrimitiveType::I16
+                    | PrimitiveType::I32
+                    | PrimitiveType::I64,
+                ) => "Signed",
+                TypeRef::Primitive(PrimitiveType::F32 | PrimitiveType::F64) => "Float",
+                TypeRef::Fixed { .. } => "Fixed",
+                TypeRef::Uuid => "Uuid",
+                TypeRef::Bytes(_) => "Bytes",
+                TypeRef::Enum(_) => "Enum",
+                TypeRef::Struct(_) | TypeRef::Message(_) => "Struct",
+                TypeRef::Vector { .. } => "Vector",
+            };
+            let separator = if i + 1 < fields.len() { "," } else { "" };
+            write!(
+                out,
+                "\n{indent}{{\"{}\", {}, {}, {}, FieldKind::{kind}, \"{}\", {}}}{separator}",
+                field.name,
+                field.offset,
+                field.bit_offset,
+                field.bits,
+                field.type_info,
+                field.condition.is_some()
+            )
+            .unwrap();
+        }
+        writeln!(out, "\n}}}};\n").unwrap();
+    }
+
+    /// Writes `visit_fields`, which passes each field present to a visitor with its entry in
+    /// the field table.
+    fn write_field_visitor(&mut self, class_name: &str, slots: &[Slot]) {
+        let i1 = self.config.get_indent(1);
+        let i2 = self.config.get_indent(2);
+        let out = &mut self.header_output;
+        writeln!(out).unwrap();
+        writeln!(
+            out,
+            "{i1}/// Calls `visitor(info, value)` with the entry in k{class_name}Fields and the value of"
+        )
+        .unwrap();
+        writeln!(
+            out,
+            "{i1}/// each field present, in wire order. Call it on a host-endian object."
+        )
+        .unwrap();
+        writeln!(out, "{i1}template <typename Visitor>").unwrap();
+        writeln!(out, "{i1}void visit_fields(Visitor&& visitor) const {{").unwrap();
+        let fields: Vec<&FieldLayout> = slots.iter().flat_map(|slot| &slot.fields).collect();
+        if fields.is_empty() {
+            writeln!(out, "{i2}(void)visitor;").unwrap();
+        }
+        for (i, field) in fields.iter().enumerate() {
+            let name = field.name;
+            let value = match (&field.type_info, field.bit_field_size) {
+                (Type::Fixed { .. }, _) | (_, Some(_)) => format!("{name}()"),
+                _ => format!("__raw_{name}"),
+            };
+            let call = format!("visitor(k{class_name}Fields[{i}], {value});");
+            match field.condition {
+                Some(_) => writeln!(out, "{i2}if (has_{name}()) {{ {call} }}"),
+                None => writeln!(out, "{i2}{call}"),
+            }
+            .unwrap();
+        }
+        writeln!(out, "{i1}}}").unwrap();
+    }
+
+    /// Writes `FieldInfo` and `FieldKind`, which the field tables of classes list.
+    fn write_reflection_types(&mut self) {
+        const REFLECTION: &str = "\
+/// The kind of value a field holds.
+enum class FieldKind : uint8_t {
+    Bool,      ///< A bool.
+    Unsigned,  ///< An unsigned integer.
+    Signed,    ///< A signed integer.
+    Float,     ///< A floating-point number.
+    Fixed,     ///< A fixed-point number, visited scaled as a double.
+    Uuid,      ///< A 16-byte UUID.
+    Bytes,     ///< A byte array.
+    Enum,      ///< An enum, carried as its underlying type.
+    Struct,    ///< A struct or message, embedded in place.
+    Vector,    ///< A bounded vector: a length prefix followed by its elements.
+};
+
+/// Where a field sits in its class, as listed in a field table.
+struct FieldInfo {
+    const char* name;       ///< The name of the field's accessors.
+    size_t offset;          ///< The byte offset of the field, or of its bit-field container.
+    size_t bit_offset;      ///< The offset of a bit-field within its container.
+    size_t bits;            ///< The width of the field in bits.
+    FieldKind kind;         ///< The kind of value the field holds.
+    const char* type_name;  ///< The type of the field as written in the schema.
+    bool conditional;       ///< Whether the field is only present when its condition holds.
+};
+";
+        self.header_output.push_str(REFLECTION);
+        writeln!(self.header_output).unwrap();
+    }
+
+    /// Writes the byte offset of each field that is not a bit-field, as `kNameOffset`.
+    fn write_offset_constants(&mut self, slots: &[Slot]) {
+        let indent = self.config.get_indent(1);
+        let mut any = false;
+        for slot in slots {
+            if slot.is_bit_field_container() {
+                continue;
+            }
+            if !any {
+                writeln!(
+                    self.header_output,
+                    "{indent}/// Byte offsets of the fields within the buffer."
+                )
+                .unwrap();
+                any = true;
+            }
+            let constant = Self::offset_constant(slot.first());
+            writeln!(
+                self.header_output,
+                "{indent}static const size_t {constant} = {};",
+                slot.offset
+            )
+            .unwrap();
+        }
+        if any {
+            writeln!(self.header_output).unwrap();
+        }
+    }
+
+    /// Writes `__check_layout`, whose static_asserts fail to compile if the compiler lays the
+    /// class out differently from the Onyx layout its size and offset constants describe.
+    fn write_layout_check(&mut self, class_name: &str, slots: &[Slot], size: usize) {
+        let i1 = self.config.get_indent(1);
+        let i2 = self.config.get_indent(2);
+        let out = &mut self.header_output;
+        writeln!(out, "\nprivate:").unwrap();
+        writeln!(
+            out,
+            "{i1}/// Fails to compile if {class_name} does not match its Onyx layout."
+        )
+        .unwrap();
+        writeln!(out, "{i1}static inline void __check_layout() {{").unwrap();
+        // C++ gives an empty class a size of one byte.
+        if size > 0 {
+            writeln!(
+                out,
+                "{i2}static_assert(sizeof({class_name}) == kSizeOf, \"{class_name} does not match its Onyx size\");"
+            )
+            .unwrap();
+        }
+        for slot in slots {
+            if slot.is_bit_field_container() {
+                continue;
+            }
+            let name = slot.first().name;
+            let constant = Self::offset_constant(slot.first());
+            writeln!(
+                out,
+                "{i2}static_assert(offsetof({class_name}, __raw_{name}) == {constant}, \"{class_name}::{name} does not match its Onyx offset\");"
+            )
+            .unwrap();
+        }
+        writeln!(out, "{i1}}}").unwrap();
+    }
+
+    fn write_class_members(&mut self, slots: &[Slot]) {
+        let mut end = 0;
+        for (index, slot) in slots.iter().enumerate() {
+            let first_field = slot.first();
+            let container_bytes = slot.size;
+            // Padding an `align` directive puts before the slot.
+            if slot.offset > end {
+                writeln!(
+                    self.header_output,
+                    "{}uint8_t __padding{index}[{}];",
+                    self.config.get_indent(1),
+                    slot.offset - end
+                )
+                .unwrap();
+            }
+            end = slot.offset + container_bytes;
+
+            if slot.is_bit_field_container() {
+                // This is a bit-field group, replace with a raw container field (byte array)
+                let container_name = format!("__raw_{}", first_field.name);
+                writeln!(
+                    self.header_output,
+                    "{}uint8_t {container_name}[{container_bytes}];",
+                    self.config.get_indent(1)
+                )
+                .unwrap();
+            } else {
+                // Non-bit-field, or a single primitive/custom type (use raw prefix for consistency)
+                let type_str = self.get_primitive_cpp_type(&first_field.type_info);
+                writeln!(
+                    self.header_output,
+                    "{}{type_str} __raw_{name};",
+                    self.config.get_indent(1),
+                    name = first_field.name,
+                )
+                .unwrap();
+            }
+        }
+    }
+
+    fn write_class_accessors(&mut self, slots: &[Slot], module: &OnyxModule) {
+        for slot in slots {
+            for field in &slot.fields {
+                self.write_source_comment(field.span, 1);
+            }
+            for field in &slot.fields {
+                if let Some(condition) = &field.condition {
+                    self.write_presence_accessor(field, condition, slots, module);
+                }
+            }
+            let start = self.header_output.len();
+            if slot.is_bit_field_container() {
+                self.write_bitfield_accessors(slot);
+            } else {
+                self.write_regular_accessor(slot.first(), module);
+            }
+            self.annotate_accessors(start, slot);
+        }
+    }
+
+    /// Gives the accessors written to the header since `start` the doc comments of the fields
+    /// of `slot` they belong to, and marks those of deprecated fields `ONYX_DEPRECATED`.
+    fn annotate_accessors(&mut self, start: usize, slot: &Slot) {
+        if slot
+            .fields
+            .iter()
+            .all(|field| field.deprecated.is_none() && field.doc.is_none())
+        {
+            return;
+        }
+        let indent = self.config.get_indent(1);
+        let signature = format!("{indent}inline ");
+        let comment = format!("{indent}///");
+        let mut accessors = String::new();
+        let mut previous = "";
+        for line in self.header_output[start..].split_inclusive('\n') {
+            if line.starts_with(&signature) {
+                let declarator = line.split('(').next().unwrap_or_default();
+                let name = declarator.rsplit(' ').next().unwrap_or_default();
+                if let Some(doc) = accessor_field(slot, name).and_then(|f| f.doc.as_deref()) {
+                    // Set the field's own description apart from the accessor's.
+                    if previous.starts_with(&comment) {
+                        writeln!(accessors, "{comment}").unwrap();
+                    }
+                    accessors.push_str(&doc_lines(Some(doc), &indent));
+                }
+                if let Some(note) = accessor_deprecation(slot, name) {
+                    writeln!(
+                        accessors,
+                        "{indent}{}",
+                        Self::deprecated_attribute(Some(note)).trim_end()
+                    )
+                    .unwrap();
+                }
+            }
+            accessors.push_str(line);
+            previous = line;
+        }
+        self.header_output.truncate(start);
+        self.header_output.push_str(&accessors);
+    }
+
+    /// Writes `ONYX_DEPRECATED(note)`, which marks a declaration deprecated: the standard
+    /// `[[deprecated]]` from C++14 on, and the GCC attribute before it.
+    fn write_deprecated_macro(&mut self) {
+        self.header_output.push_str(
+            "#if __cplusplus >= 201402L || defined(_MSC_VER)
+#define ONYX_DEPRECATED(note) [[deprecated(note)]]
+#elif defined(__GNUC__)
+#define ONYX_DEPRECATED(note) __attribute__((deprecated(note)))
+#else
+#define ONYX_DEPRECATED(note)
+#endif
+
+",
+        );
+    }
+
+    /// Writes `has_{name}`, which evaluates the condition of a conditional field.
+    fn write_presence_accessor(
+        &mut self,
+        field: &Field,
+        condition: &Condition,
+        slots: &[Slot],
+        module: &OnyxModule,
+    ) {
+        let Some(target) = slots
+            .iter()
+            .flat_map(|slot| &slot.fields)
+            .find(|f| f.name == condition.field)
+        else {
+            return;
         };
-        let pass_by_ref = if is_class {
-            "&".to_string()
+        let raw = condition.raw_value(module).unwrap_or_default();
+        let op = if condition.negated { "!=" } else { "==" };
+        let read = format!("{}()", target.name);
+        let test = match &target.type_info {
+            Type::Primitive(PrimitiveType::Bool) if (raw != 0) != condition.negated => read,
+            Type::Primitive(PrimitiveType::Bool) => format!("!{read}"),
+            Type::Primitive(p) => format!(
+                "{read} {op} static_cast<{}>({raw}ULL)",
+                self.map_primitive_type_to_cpp(p)
+            ),
+            Type::Custom(name) => match module.definitions.get(name) {
+                Some(Definition::Enum(e)) => {
+                    let t = self.map_primitive_type_to_cpp(&e.underlying_type);
+                    format!("static_cast<{t}>({read}) {op} static_cast<{t}>({raw}ULL)")
+                }
+                _ => return,
+            },
+            _ => return,
+        };
+
+        let indent = self.config.get_indent(1);
+        writeln!(
+            self.header_output,
+            "{indent}/// Returns true if {} is present: `{condition}`",
+            field.name
+        )
+        .unwrap();
+        writeln!(
+            self.header_output,
+            "{indent}inline bool has_{}() const {{ return {test}; }}\n",
+            field.name
+        )
+        .unwrap();
+    }
+
+    fn write_bitfield_accessors(&mut self, slot: &Slot) {
+        let container_name = format!("__raw_{}", slot.first().name);
+        // The container is in host order once deserialized, so each field is read from the
+        // whole container rather than from the bytes it happens to overlap.
+        let bytes = slot.size;
+        let temp_container = self.map_byte_width_to_cpp(&bytes);
+        // Other widths are in little-endian order and are assembled a byte at a time, as
+        // reading them through a wider type would overrun the container.
+        let assembled = !bytes.is_power_of_two();
+        let i2 = self.config.get_indent(2);
+        let i3 = self.config.get_indent(3);
+        let assemble = format!(
+            "{i2}{temp_container} raw_value = 0;\n\
+             {i2}for (size_t i = 0; i < {bytes}; ++i) {{\n\
+             {i3}raw_value |= static_cast<{temp_container}>({container_name}[i]) << (8 * i);\n\
+             {i2}}}"
+        );
+        for field in &slot.fields {
+            let field_type_str = self.get_primitive_cpp_type(&field.type_info);
+            let current_bit_offset = field.bit_offset;
+            let mask = bit_mask(field.bits);
+            // A signed field is sign-extended by flipping its sign bit and subtracting it again,
+            // which avoids implementation-defined conversions and shifts of negative numbers.
+            let extend = |value: String| match field.type_info {
+                Type::Primitive(p)
+                    if p.is_signed() && (1..p.get_bit_width()).contains(&field.bits) =>
+                {
+                    let sign = 1u64 << (field.bits - 1);
+                    format!("static_cast<int64_t>({value} ^ 0x{sign:X}) - 0x{sign:X}")
+                }
+                _ => value,
+            };
+
+            // Accessor logic
+            writeln!(
+                self.header_output,
+                "{}/// Accessor for {field_name}",
+                self.config.get_indent(1),
+                field_name = field.name
+            )
+            .unwrap();
+            writeln!(
+                self.header_output,
+                "{}inline const {field_type_str} {field_name}() const {{",
+                self.config.get_indent(1),
+                field_name = field.name,
+            )
+            .unwrap();
+            if assembled {
+                writeln!(self.header_output, "{assemble}").unwrap();
+                let value = extend(format!(
+                    "((raw_value >> {current_bit_offset}) & 0x{mask:X})"
+                ));
+                writeln!(
+                    self.header_output,
+                    "{i2}return static_cast<const {field_type_str}>({value});"
+                )
+                .unwrap();
+            } else {
+                writeln!(
+                    self.header_output,
+                    "{}auto raw_value = reinterpret_cast<const {temp_container}*>(&{container_name}[0]);", self.config.get_indent(2)
+                ).unwrap();
+                let value = extend(format!(
+                    "((*raw_value >> {current_bit_offset}) & 0x{mask:X})"
+                ));
+                writeln!(
+                    self.header_output,
+                    "{i2}return static_cast<const {field_type_str}>({value});"
+                )
+                .unwrap();
+            }
+            writeln!(self.header_output, "{}}}\n", self.config.get_indent(1)).unwrap();
+
+            // Mutator logic
+            writeln!(
+                self.header_output,
+                "{}/// Mutator for {field_name}",
+                self.config.get_indent(1),
+                field_name = field.name
+            )
+            .unwrap();
+            writeln!(
+                self.header_output,
+                "{}inline void {field_name}({field_type_str} value) {{",
+                self.config.get_indent(1),
+                field_name = field.name
+            )
+            .unwrap();
+            if assembled {
+                writeln!(self.header_output, "{assemble}").unwrap();
+                writeln!(
+                    self.header_output,
+                    "{i2}raw_value &= ~((({temp_container})0x{mask:X}) << {current_bit_offset});"
+                )
+                .unwrap();
+                writeln!(
+                    self.header_output,
+                    "{i2}raw_value |= (((({temp_container})value) & 0x{mask:X}) << {current_bit_offset});"
+                )
+                .unwrap();
+                writeln!(
+                    self.header_output,
+                    "{i2}for (size_t i = 0; i < {bytes}; ++i) {{"
+                )
+                .unwrap();
+                writeln!(
+                    self.header_output,
+                    "{i3}{container_name}[i] = static_cast<uint8_t>(raw_value >> (8 * i));"
+                )
+                .unwrap();
+                writeln!(self.header_output, "{i2}}}").unwrap();
+            } else {
+                writeln!(
+                    self.header_output,
+                    "{}auto raw_container = reinterpret_cast<{temp_container}*>(&{container_name}[0]);",
+                    self.config.get_indent(2)
+                )
+                .unwrap();
+                writeln!(
+                    self.header_output,
+                    "{}*raw_container &= ~((({temp_container})0x{mask:X}) << {current_bit_offset});",
+                    self.config.get_indent(2)
+                )
+                .unwrap();
+                writeln!(self.header_output,"{}*raw_container |= (((({temp_container})value) & 0x{mask:X}) << {current_bit_offset});", self.config.get_indent(2)).unwrap();
+            }
+            writeln!(self.header_output, "{}}}\n", self.config.get_indent(1)).unwrap();
+        }
+    }
+
+    fn write_regular_accessor(&mut self, field: &Field, module: &OnyxModule) {
+        if let Type::Fixed { base, scale } = &field.type_info {
+            self.write_fixed_accessors(field, base, *scale);
+            return;
+        }
+        let type_str = self.get_primitive_cpp_type(&field.type_info);
+        let is_class = match &field.type_info {
+            Type::Custom(s) => matches!(
+                module.definitions.get(s),
+                Some(Definition::Struct(_)) | Some(Definition::Message(_))
+            ),
+            Type::Vector { .. } | Type::Uuid | Type::Bytes(_) => true,
+            _ => false,
+        };
+        let accessor_const = if is_class {
+            String::new()
+        } else {
+            "const ".to_string()
+        };
+        let pass_by_ref = if is_class {
+            "&".to_string()
+        } else {
+            String::new()
+        };
+
+        // Accessor
+        writeln!(
+            self.header_output,
+            "{}/// Accessor for {name}",
+            self.config.get_indent(1),
+            name = field.name
+        )
+        .unwrap();
+        writeln!(
+            self.header_output,
+            "{}inline {accessor_const}{type_str}{pass_by_ref} {name}() {accessor_const}{{ return __raw_{name}; }}\n",
+            self.config.get_indent(1),
+            name = field.name
+        )
+        .unwrap();
+
+        // Mutator
+        writeln!(
+            self.header_output,
+            "{}/// Mutator for {name}",
+            self.config.get_indent(1),
+            name = field.name
+        )
+        .unwrap();
+        writeln!(
+            self.header_output,
+            "{}inline void {name}(const {type_str} value) {{ __raw_{name} = value; }}\n",
+            self.config.get_indent(1),
+            name = field.name
+        )
+        .unwrap();
+
+        if let Some(len) = field.type_info.byte_len() {
+            writeln!(
+                self.header_output,
+                "{}/// Formats {name} as hex",
+                self.config.get_indent(1),
+                name = field.name
+            )
+            .unwrap();
+            writeln!(
+                self.header_output,
+                "{}inline std::string {name}_hex() const {{ return utils::to_hex(__raw_{name}.data(), {len}, {uuid}); }}\n",
+                self.config.get_indent(1),
+                name = field.name,
+                uuid = field.type_info == Type::Uuid
+            )
+            .unwrap();
+        }
+    }
+
+    /// Writes the raw and scaled accessors of a fixed-point field.
+    ///
+    /// The scaled mutator rounds half away from zero and returns false, leaving the field
+    /// unchanged, when the value is out of range for the base type.
+    fn write_fixed_accessors(&mut self, field: &Field, base: &PrimitiveType, scale: u64) {
+        let name = field.name;
+        let type_str = self.map_primitive_type_to_cpp(base);
+        let bits = base.get_bit_width() as u32;
+        let (min, max) = match base {
+            PrimitiveType::I8 | PrimitiveType::I16 | PrimitiveType::I32 | PrimitiveType::I64 => {
+                (-(1i128 << (bits - 1)), (1i128 << (bits - 1)) - 1)
+            }
+            _ => (0, (1i128 << bits) - 1),
+        };
+
+        writeln!(
+            self.header_output,
+            "{}/// Accessor for the raw value of {name}",
+            self.config.get_indent(1)
+        )
+        .unwrap();
+        writeln!(
+            self.header_output,
+            "{}inline const {type_str} {name}_raw() const {{ return __raw_{name}; }}\n",
+            self.config.get_indent(1)
+        )
+        .unwrap();
+        writeln!(
+            self.header_output,
+            "{}/// Mutator for the raw value of {name}",
+            self.config.get_indent(1)
+        )
+        .unwrap();
+        writeln!(
+            self.header_output,
+            "{}inline void {name}_raw(const {type_str} value) {{ __raw_{name} = value; }}\n",
+            self.config.get_indent(1)
+        )
+        .unwrap();
+
+        writeln!(
+            self.header_output,
+            "{}/// Accessor for {name}, scaled by 1/{scale}",
+            self.config.get_indent(1)
+        )
+        .unwrap();
+        writeln!(
+            self.header_output,
+            "{}inline double {name}() const {{ return static_cast<double>(__raw_{name}) / {scale}.0; }}\n",
+            self.config.get_indent(1)
+        )
+        .unwrap();
+        writeln!(
+            self.header_output,
+            "{}/// Mutator for {name}, rounding to the nearest step of 1/{scale}",
+            self.config.get_indent(1)
+        )
+        .unwrap();
+        writeln!(
+            self.header_output,
+            "{}inline bool {name}(const double value) {{",
+            self.config.get_indent(1)
+        )
+        .unwrap();
+        writeln!(
+            self.header_output,
+            "{}const double scaled = value * {scale}.0;",
+            self.config.get_indent(2)
+        )
+        .unwrap();
+        writeln!(
+            self.header_output,
+            "{}if (!(scaled > {min}.0 - 0.5 && scaled < {max}.0 + 0.5)) {{ return false; }}",
+            self.config.get_indent(2)
+        )
+        .unwrap();
+        writeln!(
+            self.header_output,
+            "{}__raw_{name} = static_cast<{type_str}>(scaled < 0 ? scaled - 0.5 : scaled + 0.5);",
+            self.config.get_indent(2)
+        )
+        .unwrap();
+        writeln!(
+            self.header_output,
+            "{}return true;",
+            self.config.get_indent(2)
+        )
+        .unwrap();
+        writeln!(self.header_output, "{}}}\n", self.config.get_indent(1)).unwrap();
+    }
+
+    /// Writes `at` and `from_mmap`, which return the class placed at an address.
+    fn write_placement_helpers(&mut self, class_name: &str) {
+        let i1 = self.config.get_indent(1);
+        let i2 = self.config.get_indent(2);
+        let out = &mut self.header_output;
+        writeln!(out).unwrap();
+        writeln!(
+            out,
+            "{i1}/// Returns the {class_name} at `addr`, which must hold kSizeOf bytes in host byte order."
+        )
+        .unwrap();
+        writeln!(
+            out,
+            "{i1}static inline {class_name}* at(void* addr) {{ return static_cast<{class_name}*>(addr); }}"
+        )
+        .unwrap();
+        writeln!(out).unwrap();
+        writeln!(
+            out,
+            "{i1}/// Returns the {class_name} at the start of `len` mapped bytes, such as from mmap, or nullptr"
+        )
+        .unwrap();
+        writeln!(
+            out,
+            "{i1}/// if `ptr` is null, misaligned for {class_name}, or `len` is less than kSizeOf."
+        )
+        .unwrap();
+        writeln!(
+            out,
+            "{i1}static inline {class_name}* from_mmap(void* ptr, size_t len) {{"
+        )
+        .unwrap();
+        writeln!(
+            out,
+            "{i2}if (ptr == nullptr || len < kSizeOf || reinterpret_cast<uintptr_t>(ptr) % alignof({class_name}) != 0) return nullptr;"
+        )
+        .unwrap();
+        writeln!(out, "{i2}return static_cast<{class_name}*>(ptr);").unwrap();
+        writeln!(out, "{i1}}}").unwrap();
+    }
+
+    fn write_class_method_declarations(&mut self, class_name: &str, checks_enums: bool) {
+        // Declare the static deserialization method
+        writeln!(
+            self.header_output,
+            "{}/// Deserializes the network-endian buffer to {class_name} in-place by casting",
+            self.config.get_indent(1)
+        )
+        .unwrap();
+        writeln!(
+            self.header_output,
+            "{}/// and applying endianness correction to match the host-endianness.",
+            self.config.get_indent(1)
+        )
+        .unwrap();
+        writeln!(
+            self.header_output,
+            "{}/// NOTE: This mutates the input buffer to host-endianness.",
+            self.config.get_indent(1)
+        )
+        .unwrap();
+        writeln!(
+            self.header_output,
+            "{}/// Returns a pointer to the object within the buffer.",
+            self.config.get_indent(1),
+        )
+        .unwrap();
+        if checks_enums {
+            writeln!(
+                self.header_output,
+                "{}/// Returns nullptr if a closed enum holds a value that no variant declares;",
+                self.config.get_indent(1),
+            )
+            .unwrap();
+            writeln!(
+                self.header_output,
+                "{}/// the buffer is converted either way.",
+                self.config.get_indent(1),
+            )
+            .unwrap();
+        }
+        writeln!(
+            self.header_output,
+            "{}static {class_name}* Deserialize(Buffer& buffer);",
+            self.config.get_indent(1)
+        )
+        .unwrap();
+        writeln!(self.header_output).unwrap();
+
+        // Decalre the serialization method
+        writeln!(
+            self.header_output,
+            "{}/// Serializes the host-endian object into the provided buffer,",
+            self.config.get_indent(1)
+        )
+        .unwrap();
+        writeln!(
+            self.header_output,
+            "{}/// applying endianness correction to match the network-endianness.",
+            self.config.get_indent(1)
+        )
+        .unwrap();
+        writeln!(
+            self.header_output,
+            "{}void Serialize(Buffer& buffer) const;",
+            self.config.get_indent(1)
+        )
+        .unwrap();
+    }
+
+    fn write_class_definition(&mut self, module: &OnyxModule, class_name: &str, slots: &[Slot]) {
+        self.write_deserialize_impl(module, class_name, slots);
+        self.write_serialize_impl(module, class_name, slots);
+    }
+
+    fn write_deserialize_impl(&mut self, module: &OnyxModule, class_name: &str, slots: &[Slot]) {
+        // Implementation of the Deserialize method
+        writeln!(
+            self.source_output,
+            "{}{class_name}* {class_name}::Deserialize(Buffer& buffer) {{",
+            self.definition_specifier()
+        )
+        .unwrap();
+
+        writeln!(
+            self.source_output,
+            "{}// Overlay the class structure onto the buffer memory. This is the zero-copy step.",
+            self.config.get_indent(1)
+        )
+        .unwrap();
+        writeln!(
+            self.source_output,
+            "{}{class_name}* result = ({class_name}*)buffer;",
+            self.config.get_indent(1)
+        )
+        .unwrap();
+        let checks = slots
+            .iter()
+            .flat_map(|slot| &slot.fields)
+            .any(|field| Self::checks_enums(module, &field.type_info));
+        if checks {
+            writeln!(
+                self.source_output,
+                "{}bool known = true;",
+                self.config.get_indent(1)
+            )
+            .unwrap();
+        }
+        writeln!(self.source_output).unwrap();
+
+        let host = !module.endianness.is_portable();
+        if host {
+            writeln!(
+                self.source_output,
+                "{}// INFO: The buffer is in host byte order, no swap needed.",
+                self.config.get_indent(1)
+            )
+            .unwrap();
+        }
+
+        // Iterate and apply in-place swapping
+        for slot in slots {
+            let first_field = slot.first();
+            let name = &first_field.name;
+            // In host order only the enums of nested classes are left to check.
+            if host
+                && (slot.is_bit_field_container()
+                    || !Self::nests_checked_class(module, &first_field.type_info))
+            {
+                continue;
+            }
+
+            if slot.is_bit_field_container() {
+                // CASE 1: Bit-Field Container (uint8_t __raw_{name}[N])
+                let bytes = slot.size;
+                if bytes > 1 {
+                    self.write_bit_field_container_swap("result", name, bytes);
+                } else {
+                    writeln!(
+                        self.source_output,
+                        "{}// INFO: Bit-field container {name} (1 byte), no swap needed.",
+                        self.config.get_indent(1)
+                    )
+                    .unwrap();
+                }
+            } else {
+                // CASE 2: Primitive or Custom Field (__raw_{name})
+                match &first_field.type_info {
+                    Type::Primitive(p) | Type::Fixed { base: p, .. } => {
+                        if p.get_bit_width().div_ceil(8) > 1 {
+                            // Primitive: In-place swap
+                            writeln!(
+                                self.source_output,
+                                "{}// SWAP: Primitive field {name}",
+                                self.config.get_indent(1)
+                            )
+                            .unwrap();
+                            writeln!(
+                                self.source_output,
+                                "{}result->__raw_{name} = utils::byteswap_if_needed(result->__raw_{name});", self.config.get_indent(1)
+                            ).unwrap();
+                        } else {
+                            writeln!(
+                                self.source_output,
+                                "{}// INFO: Primitive field {name} (1 byte), no swap needed.",
+                                self.config.get_indent(1)
+                            )
+                            .unwrap();
+                        }
+                    }
+                    Type::Uuid | Type::Bytes(_) => {
+                        writeln!(
+                            self.source_output,
+                            "{}// INFO: Byte array field {name}, no swap needed.",
+                            self.config.get_indent(1)
+                        )
+                        .unwrap();
+                    }
+                    Type::Custom(s) => {
+                        match module.definitions.get(s).unwrap() {
+                            Definition::Struct(_) | Definition::Message(_)
+                                if Self::checks_enums(module, &first_field.type_info)
+                                    && first_field.condition.is_some() =>
+                            {
+                                // Checked once every field is in host order, if present.
+                                writeln!(
+                                    self.source_output,
+                                    "{}bool {name}_known = {s}::Deserialize(*({s}::Buffer*) &result->__raw_{name}) != nullptr;",
+                                    self.config.get_indent(1)
+                                )
+                                .unwrap();
+                            }
+                            Definition::Struct(_) | Definition::Message(_)
+                                if Self::checks_enums(module, &first_field.type_info) =>
+                            {
+                                writeln!(
+                                    self.source_output,
+                                    "{}known = {s}::Deserialize(*({s}::Buffer*) &result->__raw_{name}) != nullptr && known;",
+                                    self.config.get_indent(1)
+                                )
+                                .unwrap();
+                            }
+                            Definition::Struct(_) | Definition::Message(_) => {
+                                writeln!(
+                                    self.source_output,
+                                    "{}{s}::Deserialize(*({s}::Buffer*) &result->__raw_{name});",
+                                    self.config.get_indent(1)
+                                )
+                                .unwrap();
+                            }
+                            Definition::Enum(e) if e.underlying_type.get_byte_size() > 1 => {
+                                let underlying_type =
+                                    self.map_primitive_type_to_cpp(&e.underlying_type);
+                                writeln!(
+                                    self.source_output,
+                                    "{}// SWAP: Enum field {name}",
+                                    self.config.get_indent(1)
+                                )
+                                .unwrap();
+                                writeln!(
+                                    self.source_output,
+                                    "{}result->__raw_{name} = static_cast<{s}>(utils::byteswap_if_needed(static_cast<{underlying_type}>(result->__raw_{name})));",
+                                    self.config.get_indent(1)
+                                )
+                                .unwrap();
+                            }
+                            Definition::Enum(_) => {}
+                        }
+                        // Call Deserialize on the memory block where the nested struct resides
+                    }
+                    Type::Vector { .. } => self.write_vector_swap(
+                        module,
+                        "result",
+                        name.as_str(),
+                        &first_field.type_info,
+                        true,
+                    ),
+                }
+            }
+        }
+
+        if self.trace {
+            self.write_field_trace(module, class_name, slots);
+        }
+        if checks {
+            self.write_enum_checks(module, slots);
+        }
+
+        writeln!(self.source_output).unwrap();
+        let returned = if checks {
+            "known ? result : nullptr"
         } else {
-            String::new()
+            "result"
         };
-
-        // Accessor
-        writeln!(
-            self.header_output,
-            "{}/// Accessor for {name}",
-            self.config.get_indent(1),
-            name = field.name
-        )
-        .unwrap();
-        writeln!(
-            self.header_output,
-            "{}inline {accessor_const}{type_str}{pass_by_ref} {name}() {accessor_const}{{ return __raw_{name}; }}\n",
-            self.config.get_indent(1),
-            name = field.name
-        )
-        .unwrap();
-
-        // Mutator
-        writeln!(
-            self.header_output,
-            "{}/// Mutator for {name}",
-            self.config.get_indent(1),
-            name = field.name
-        )
-        .unwrap();
         writeln!(
-            self.header_output,
-            "{}inline void {name}(const {type_str} value) {{ __raw_{name} = value; }}\n",
-            self.config.get_indent(1),
-            name = field.name
+            self.source_output,
+            "{}return {returned};",
+            self.config.get_indent(1)
         )
         .unwrap();
+        writeln!(self.source_output, "}}\n").unwrap();
     }
 
-    fn write_class_method_declarations(&mut self, class_name: &String) {
-        // Declare the static deserialization method
-        writeln!(
-            self.header_output,
-            "{}/// Deserializes the network-endian buffer to {class_name} in-place by casting",
-            self.config.get_indent(1)
-        )
-        .unwrap();
-        writeln!(
-            self.header_output,
-            "{}/// and applying endianness correction to match the host-endianness.",
-            self.config.get_indent(1)
-        )
-        .unwrap();
-        writeln!(
-            self.header_output,
-            "{}/// NOTE: This mutates the input buffer to host-endianness.",
-            self.config.get_indent(1)
-        )
-        .unwrap();
-        writeln!(
-            self.header_output,
-            "{}/// Returns a pointer to the object within the buffer.",
-            self.config.get_indent(1),
-        )
-        .unwrap();
-        writeln!(
-            self.header_output,
-            "{}static {class_name}* Deserialize(Buffer& buffer);",
-            self.config.get_indent(1)
-        )
-        .unwrap();
-        writeln!(self.header_output).unwrap();
+    /// Writes the `ONYX_TRACE_FIELD` call of each field present in a deserialized `result`.
+    fn write_field_trace(&mut self, module: &OnyxModule, class_name: &str, slots: &[Slot]) {
+        let i1 = self.config.get_indent(1);
+        writeln!(self.source_output, "#ifdef ONYX_TRACE").unwrap();
+        for slot in slots {
+            let bit_field = slot.is_bit_field_container();
+            let offset = slot.offset;
+            for field in &slot.fields {
+                let name = &field.name;
+                let value = match &field.type_info {
+                    Type::Fixed { .. } => format!("result->{name}()"),
+                    Type::Uuid | Type::Bytes(_) => format!("result->{name}_hex().c_str()"),
+                    Type::Vector { .. } => {
+                        format!("static_cast<uint64_t>(result->__raw_{name}.len())")
+                    }
+                    Type::Custom(s)
+                        if !matches!(module.definitions.get(s), Some(Definition::Enum(_))) =>
+                    {
+                        format!("\"{s}\"")
+                    }
+                    type_info => {
+                        let raw = match bit_field {
+                            true => format!("result->{name}()"),
+                            false => format!("result->__raw_{name}"),
+                        };
+                        format!(
+                            "static_cast<{}>({raw})",
+                            Self::trace_value_type(module, type_info)
+                        )
+                    }
+                };
+                let call =
+                    format!("ONYX_TRACE_FIELD(\"{class_name}\", \"{name}\", {offset}, {value});");
+                match field.condition {
+                    Some(_) => writeln!(
+                        self.source_output,
+                        "{i1}if (result->has_{name}()) {{ {call} }}"
+                    ),
+                    None => writeln!(self.source_output, "{i1}{call}"),
+                }
+                .unwrap();
+            }
+        }
+        writeln!(self.source_output, "#endif").unwrap();
+    }
 
-        // Decalre the serialization method
-        writeln!(
-            self.header_output,
-            "{}/// Serializes the host-endian object into the provided buffer,",
-            self.config.get_indent(1)
-        )
-        .unwrap();
-        writeln!(
-            self.header_output,
-            "{}/// applying endianness correction to match the network-endianness.",
-            self.config.get_indent(1)
-        )
-        .unwrap();
-        writeln!(
-            self.header_output,
-            "{}void Serialize(Buffer& buffer) const;",
-            self.config.get_indent(1)
-        )
-        .unwrap();
+    /// Returns the type a traced scalar is converted to, which selects its `trace_field`.
+    fn trace_value_type(module: &OnyxModule, type_info: &Type) -> &'static str {
+        let primitive = match type_info {
+            Type::Custom(s) => match module.definitions.get(s) {
+                Some(Definition::Enum(e)) => e.underlying_type,
+                _ => PrimitiveType::U64,
+            },
+            Type::Primitive(p) => *p,
+            _ => PrimitiveType::U64,
+        };
+        match primitive {
+            PrimitiveType::I8 | PrimitiveType::I16 | PrimitiveType::I32 | PrimitiveType::I64 => {
+                "int64_t"
+            }
+            PrimitiveType::F32 | PrimitiveType::F64 => "double",
+            _ => "uint64_t",
+        }
     }
 
-    fn write_class_definition(
-        &mut self,
-        module: &OnyxModule,
-        class_name: &String,
-        field_groups: &Vec<Vec<&Field>>,
-    ) {
-        self.write_deserialize_impl(module, class_name, field_groups);
-        self.write_serialize_impl(module, class_name, field_groups);
+    /// Writes the checks that the closed enum fields of a deserialized `result`, and the
+    /// elements in use of its closed enum vectors, hold declared values.
+    fn write_enum_checks(&mut self, module: &OnyxModule, slots: &[Slot]) {
+        let i1 = self.config.get_indent(1);
+        let i2 = self.config.get_indent(2);
+        for field in slots.iter().flat_map(|slot| &slot.fields) {
+            let name = &field.name;
+            let Type::Custom(s) = field.type_info.element_type() else {
+                continue;
+            };
+            if !Self::checks_enums(module, &field.type_info) {
+                continue;
+            }
+            let out = &mut self.source_output;
+            if !matches!(module.definitions.get(s), Some(Definition::Enum(_))) {
+                if field.condition.is_some() {
+                    writeln!(out, "{i1}// CHECK: Conditional field {name}").unwrap();
+                    writeln!(
+                        out,
+                        "{i1}known = (!result->has_{name}() || {name}_known) && known;"
+                    )
+                    .unwrap();
+                }
+                continue;
+            }
+            writeln!(out, "{i1}// CHECK: Closed enum field {name}").unwrap();
+            if let Type::Vector { .. } = field.type_info {
+                let vector = format!("result->__raw_{name}");
+                let len = match field.condition {
+                    Some(_) => format!("(result->has_{name}() ? {vector}.len() : 0)"),
+                    None => format!("{vector}.len()"),
+                };
+                writeln!(out, "{i1}for (size_t i = 0; i < {len}; ++i) {{").unwrap();
+                writeln!(out, "{i2}known = is_known({vector}.__items[i]) && known;").unwrap();
+                writeln!(out, "{i1}}}").unwrap();
+            } else if field.condition.is_some() {
+                writeln!(
+                    out,
+                    "{i1}known = (!result->has_{name}() || is_known(result->__raw_{name})) && known;"
+                )
+                .unwrap();
+            } else {
+                writeln!(out, "{i1}known = is_known(result->__raw_{name}) && known;").unwrap();
+            }
+        }
     }
 
-    fn write_deserialize_impl(
-        &mut self,
-        module: &OnyxModule,
-        class_name: &String,
-        field_groups: &Vec<Vec<&Field>>,
-    ) {
-        // Implementation of the Deserialize method
+    fn write_serialize_impl(&mut self, module: &OnyxModule, class_name: &str, slots: &[Slot]) {
+        // Implementation of the Serialize method
         writeln!(
             self.source_output,
-            "{class_name}* {class_name}::Deserialize(Buffer& buffer) {{"
+            "{}void {class_name}::Serialize(Buffer& buffer) const {{",
+            self.definition_specifier()
         )
         .unwrap();
 
         writeln!(
             self.source_output,
-            "{}// Overlay the class structure onto the buffer memory. This is the zero-copy step.",
+            "{}// Copy the host-endian object into the buffer first.",
             self.config.get_indent(1)
         )
         .unwrap();
         writeln!(
             self.source_output,
-            "{}{class_name}* result = ({class_name}*)buffer;",
+            "{}memcpy(buffer, this, kSizeOf);",
+            self.config.get_indent(1)
+        )
+        .unwrap();
+        if !module.endianness.is_portable() {
+            writeln!(
+                self.source_output,
+                "{}// INFO: The buffer is in host byte order, no swap needed.",
+                self.config.get_indent(1)
+            )
+            .unwrap();
+            writeln!(self.source_output, "}}\n").unwrap();
+            return;
+        }
+        writeln!(
+            self.source_output,
+            "{}{class_name}* wire_format_data = ({class_name}*)buffer;",
             self.config.get_indent(1)
         )
         .unwrap();
         writeln!(self.source_output).unwrap();
 
-        // Iterate and apply in-place swapping
-        for group in field_groups {
-            let first_field = group[0];
+        // Iterate and apply in-place swapping (wire -> host)
+        for slot in slots {
+            let first_field = slot.first();
             let name = &first_field.name;
 
-            if group.len() > 1 || first_field.bit_field_size.is_some() {
+            if slot.is_bit_field_container() {
                 // CASE 1: Bit-Field Container (uint8_t __raw_{name}[N])
-                let bytes = first_field.get_bit_width(module).div_ceil(8);
+                let bytes = slot.size;
                 if bytes > 1 {
-                    let container_type = match bytes {
-                        2 => "uint16_t",
-                        4 => "uint32_t",
-                        8 => "uint64_t",
-                        _ => "void",
-                    };
-                    writeln!(
-                        self.source_output,
-                        "{}// SWAP: Bit-field container {name}",
-                        self.config.get_indent(1)
-                    )
-                    .unwrap();
-                    writeln!(
-                        self.source_output,
-                        "{}*({container_type}*)result->__raw_{name} = utils::byteswap_if_needed(*({container_type}*)result->__raw_{name});",
-                        self.config.get_indent(1)
-                    ).unwrap();
+                    self.write_bit_field_container_swap("wire_format_data", name, bytes);
                 } else {
                     writeln!(
                         self.source_output,
@@ -543,18 +2547,18 @@ impl CppGenerator {
             } else {
                 // CASE 2: Primitive or Custom Field (__raw_{name})
                 match &first_field.type_info {
-                    Type::Primitive(p) => {
+                    Type::Primitive(p) | Type::Fixed { base: p, .. } => {
                         if p.get_bit_width().div_ceil(8) > 1 {
                             // Primitive: In-place swap
                             writeln!(
                                 self.source_output,
                                 "{}// SWAP: Primitive field {name}",
-                                self.config.get_indent(1)
+                                self.config.get_indent(1),
                             )
                             .unwrap();
                             writeln!(
                                 self.source_output,
-                                "{}result->__raw_{name} = utils::byteswap_if_needed(result->__raw_{name});", self.config.get_indent(1)
+                                "{}wire_format_data->__raw_{name} = utils::byteswap_if_needed(wire_format_data->__raw_{name});", self.config.get_indent(1)
                             ).unwrap();
                         } else {
                             writeln!(
@@ -565,12 +2569,35 @@ impl CppGenerator {
                             .unwrap();
                         }
                     }
+                    Type::Uuid | Type::Bytes(_) => {
+                        writeln!(
+                            self.source_output,
+                            "{}// INFO: Byte array field {name}, no swap needed.",
+                            self.config.get_indent(1)
+                        )
+                        .unwrap();
+                    }
                     Type::Custom(s) => {
                         match module.definitions.get(s).unwrap() {
                             Definition::Struct(_) | Definition::Message(_) => {
                                 writeln!(
                                     self.source_output,
-                                    "{}{s}::Deserialize(*({s}::Buffer*) &result->__raw_{name});",
+                                    "{}wire_format_data->__raw_{name}.Serialize(*({s}::Buffer*) &wire_format_data->__raw_{name});", self.config.get_indent(1)
+                                )
+                                .unwrap();
+                            }
+                            Definition::Enum(e) if e.underlying_type.get_byte_size() > 1 => {
+                                let underlying_type =
+                                    self.map_primitive_type_to_cpp(&e.underlying_type);
+                                writeln!(
+                                    self.source_output,
+                                    "{}// SWAP: Enum field {name}",
+                                    self.config.get_indent(1)
+                                )
+                                .unwrap();
+                                writeln!(
+                                    self.source_output,
+                                    "{}wire_format_data->__raw_{name} = static_cast<{s}>(utils::byteswap_if_needed(static_cast<{underlying_type}>(wire_format_data->__raw_{name})));",
                                     self.config.get_indent(1)
                                 )
                                 .unwrap();
@@ -579,130 +2606,401 @@ impl CppGenerator {
                         }
                         // Call Deserialize on the memory block where the nested struct resides
                     }
+                    Type::Vector { .. } => self.write_vector_swap(
+                        module,
+                        "wire_format_data",
+                        name.as_str(),
+                        &first_field.type_info,
+                        false,
+                    ),
                 }
             }
         }
 
-        writeln!(self.source_output).unwrap();
-        writeln!(
-            self.source_output,
-            "{}return result;",
-            self.config.get_indent(1)
-        )
-        .unwrap();
         writeln!(self.source_output, "}}\n").unwrap();
     }
 
-    fn write_serialize_impl(
+    /// Writes the endianness correction of the bounded vector field `name` of `object`.
+    ///
+    /// Every element slot is converted, in use or not, so that converting twice restores the
+    /// original bytes. `deserialize` selects the direction for nested classes.
+    fn write_vector_swap(
         &mut self,
         module: &OnyxModule,
-        class_name: &String,
-        field_groups: &Vec<Vec<&Field>>,
+        object: &str,
+        name: &str,
+        type_info: &Type,
+        deserialize: bool,
     ) {
-        // Implementation of the Serialize method
+        let Type::Vector { element, capacity } = type_info else {
+            return;
+        };
+        let indent = self.config.get_indent(1);
+        let vector = format!("{object}->__raw_{name}");
+        let action = match module.endianness.is_portable() {
+            true => "SWAP",
+            false => "CHECK",
+        };
         writeln!(
             self.source_output,
-            "void {class_name}::Serialize(Buffer& buffer) const {{"
+            "{indent}// {action}: Vector field {name}"
         )
         .unwrap();
+        if type_info.length_type().unwrap().get_byte_size() > 1 && module.endianness.is_portable() {
+            writeln!(
+                self.source_output,
+                "{indent}{vector}.__length = utils::byteswap_if_needed({vector}.__length);"
+            )
+            .unwrap();
+        }
 
+        let item = format!("{vector}.__items[i]");
+        let swap = match &**element {
+            Type::Primitive(p) if p.get_byte_size() > 1 && self.swaps_in_bulk(module) => {
+                let width = p.get_byte_size();
+                writeln!(
+                    self.source_output,
+                    "{indent}utils::byteswap_array_if_needed({vector}.__items, {capacity}, {width});"
+                )
+                .unwrap();
+                return;
+            }
+            Type::Primitive(p) if p.get_byte_size() > 1 => {
+                format!("{item} = utils::byteswap_if_needed({item});")
+            }
+            Type::Custom(s) => match module.definitions.get(s) {
+                Some(Definition::Struct(_) | Definition::Message(_))
+                    if deserialize && Self::checks_enums(module, element) =>
+                {
+                    format!(
+                        "if (!{s}::Deserialize(*({s}::Buffer*) &{item}) && i < {vector}.len()) known = false;"
+                    )
+                }
+                Some(Definition::Struct(_) | Definition::Message(_)) if deserialize => {
+                    format!("{s}::Deserialize(*({s}::Buffer*) &{item});")
+                }
+                Some(Definition::Struct(_) | Definition::Message(_)) => {
+                    format!("{item}.Serialize(*({s}::Buffer*) &{item});")
+                }
+                Some(Definition::Enum(e)) if e.underlying_type.get_byte_size() > 1 => {
+                    let underlying_type = self.map_primitive_type_to_cpp(&e.underlying_type);
+                    format!(
+                        "{item} = static_cast<{s}>(utils::byteswap_if_needed(static_cast<{underlying_type}>({item})));"
+                    )
+                }
+                _ => return,
+            },
+            _ => return,
+        };
         writeln!(
             self.source_output,
-            "{}// Copy the host-endian object into the buffer first.",
-            self.config.get_indent(1)
+            "{indent}for (size_t i = 0; i < {capacity}; ++i) {{"
         )
         .unwrap();
+        writeln!(self.source_output, "{}{swap}", self.config.get_indent(2)).unwrap();
+        writeln!(self.source_output, "{indent}}}").unwrap();
+    }
+
+    /// Writes the `utils::BoundedVec` template that bounded vector fields are declared with.
+    /// Writes `namespace v{N}` for an earlier version of the schema. The namespace redeclares
+    /// the structs and messages whose layout changed, each with an `Upgrade` to the current
+    /// class, and brings every other definition in with a using-declaration.
+    fn write_version(&mut self, namespace: &str, module: &OnyxModule, layouts: &hir::Hir) {
+        let old = layouts.module;
+        let version = old.version.unwrap_or_default();
+        writeln!(self.header_output, "namespace v{version} {{\n").unwrap();
+        writeln!(self.source_output, "namespace v{version} {{\n").unwrap();
+        for name in &old.order {
+            if same_layout(old, module, &Type::Custom(*name)) {
+                writeln!(self.header_output, "using ::{namespace}::{name};\n").unwrap();
+                continue;
+            }
+            let Some(layout) = layouts.layout(*name) else {
+                continue;
+            };
+            let fields = match layout.definition {
+                Definition::Struct(s) => &s.fields,
+                Definition::Message(m) => &m.fields,
+                Definition::Enum(_) => continue,
+            };
+            let target = format!("::{namespace}::{name}");
+            let slots = &layout.slots;
+            self.write_class_declaration(name.as_str(), slots, old, layout.size, Some(&target));
+            writeln!(self.header_output).unwrap();
+            self.write_class_definition(old, name.as_str(), slots);
+            self.write_upgrade_impl(module, old, name.as_str(), &target, fields);
+        }
+        writeln!(self.header_output, "}} // namespace v{version}\n").unwrap();
+        writeln!(self.source_output, "}} // namespace v{version}\n").unwrap();
+    }
+
+    /// Writes `Upgrade`, which copies every field present in both versions into `out`.
+    fn write_upgrade_impl(
+        &mut self,
+        module: &OnyxModule,
+        old: &OnyxModule,
+        class_name: &str,
+        target: &str,
+        fields: &[Field],
+    ) {
+        let new_fields = match module.definitions.get(&Symbol::intern(class_name)) {
+            Some(Definition::Struct(s)) => &s.fields,
+            Some(Definition::Message(m)) => &m.fields,
+            _ => return,
+        };
+        let (i1, i2) = (self.config.get_indent(1), self.config.get_indent(2));
+        let out = &mut self.source_output;
         writeln!(
-            self.source_output,
-            "{}memcpy(buffer, this, kSizeOf);",
-            self.config.get_indent(1)
+            out,
+            "{}void {class_name}::Upgrade({target}& out) const {{",
+            if self.amalgamate { "inline " } else { "" }
         )
         .unwrap();
+        writeln!(out, "{i1}memset(static_cast<void*>(&out), 0, sizeof(out));").unwrap();
+        for field in fields {
+            if !new_fields.iter().any(|f| f.name == field.name) {
+                continue;
+            }
+            let name = field.name;
+            if field.bit_field_size.is_some() {
+                writeln!(out, "{i1}out.{name}({name}());").unwrap();
+                continue;
+            }
+            match &field.type_info {
+                Type::Fixed { .. } => writeln!(out, "{i1}out.{name}_raw(__raw_{name});").unwrap(),
+                ty if same_layout(old, module, ty) => {
+                    writeln!(out, "{i1}out.{name}(__raw_{name});").unwrap()
+                }
+                Type::Vector { capacity, .. } => {
+                    writeln!(out, "{i1}out.{name}().__length = __raw_{name}.__length;").unwrap();
+                    writeln!(out, "{i1}for (size_t i = 0; i < {capacity}; ++i) {{").unwrap();
+                    writeln!(
+                        out,
+                        "{i2}__raw_{name}.__items[i].Upgrade(out.{name}().__items[i]);"
+                    )
+                    .unwrap();
+                    writeln!(out, "{i1}}}").unwrap();
+                }
+                _ => writeln!(out, "{i1}__raw_{name}.Upgrade(out.{name}());").unwrap(),
+            }
+        }
+        writeln!(out, "}}\n").unwrap();
+    }
+
+    fn write_bounded_vector_template(&mut self) {
+        const TEMPLATE: &str = "\
+/// A bounded vector: a length followed by room for N elements, all of them inline.
+template <typename T, typename LenT, size_t N>
+class BoundedVec {
+public:
+    LenT __length;
+    T __items[N];
+
+    /// Returns the number of elements, clamped to the capacity.
+    inline size_t len() const { return __length < N ? __length : N; }
+    /// Returns the maximum number of elements.
+    static inline size_t capacity() { return N; }
+    /// Returns true if the vector has no elements.
+    inline bool empty() const { return len() == 0; }
+    /// Returns the element at `i`, which must be less than len().
+    inline const T& get(size_t i) const { return __items[i]; }
+    inline T& get(size_t i) { return __items[i]; }
+    /// Replaces the element at `i`, which must be less than len().
+    inline void set(size_t i, const T& value) { __items[i] = value; }
+    /// Appends an element. Returns false, leaving the vector unchanged, if it is full.
+    inline bool push(const T& value) {
+        size_t n = len();
+        if (n == N) return false;
+        __items[n] = value;
+        __length = static_cast<LenT>(n + 1);
+        return true;
+    }
+    /// Removes all elements.
+    inline void clear() { __length = 0; }
+
+    inline const T* begin() const { return __items; }
+    inline const T* end() const { return __items + len(); }
+    inline T* begin() { return __items; }
+    inline T* end() { return __items + len(); }
+};
+";
+        writeln!(self.header_output, "namespace utils {{\n").unwrap();
+        self.header_output.push_str(TEMPLATE);
+        writeln!(self.header_output, "\n}} // namespace utils\n").unwrap();
+    }
+
+    /// Writes `utils::to_hex`, which the `_hex` accessors of byte array fields call.
+    fn write_hex_formatter(&mut self) {
+        const FORMATTER: &str = "\
+/// Formats bytes as lowercase hex; a UUID (`uuid` true) is grouped 8-4-4-4-12.
+inline std::string to_hex(const uint8_t* data, size_t size, bool uuid) {
+    static const char digits[] = \"0123456789abcdef\";
+    std::string out;
+    for (size_t i = 0; i < size; ++i) {
+        if (uuid && (i == 4 || i == 6 || i == 8 || i == 10)) out += '-';
+        out += digits[data[i] >> 4];
+        out += digits[data[i] & 0xF];
+    }
+    return out;
+}
+";
+        writeln!(self.header_output, "namespace utils {{\n").unwrap();
+        self.header_output.push_str(FORMATTER);
+        writeln!(self.header_output, "\n}} // namespace utils\n").unwrap();
+    }
+
+    /// Writes `MessageArena`, a fixed pool of buffers as large as the largest message.
+    fn write_message_arena(&mut self, module: &OnyxModule) {
+        const ARENA: &str = "\
+/// A pool of N buffers, each as large as the largest message, for obtaining zeroed message
+/// buffers without heap allocation. The pool lives wherever it is declared, such as the stack.
+template <size_t N>
+class MessageArena {
+public:
+    static const size_t kSlotSize = SLOT_SIZE;
+
+    MessageArena() { memset(__used, 0, sizeof(__used)); }
+
+    /// Returns a zeroed buffer for the message T, or nullptr if all N slots are in use.
+    template <typename T>
+    typename T::Buffer* acquire() {
+        static_assert(sizeof(typename T::Buffer) <= kSlotSize, \"T is larger than an arena slot\");
+        for (size_t i = 0; i < N; ++i) {
+            if (!__used[i]) {
+                __used[i] = true;
+                memset(__slots[i], 0, kSlotSize);
+                return reinterpret_cast<typename T::Buffer*>(__slots[i]);
+            }
+        }
+        return nullptr;
+    }
+
+    /// Returns a buffer obtained from acquire to the pool.
+    void release(const void* buffer) {
+        for (size_t i = 0; i < N; ++i) {
+            if (buffer == __slots[i]) __used[i] = false;
+        }
+    }
+
+    /// Returns the number of free slots.
+    size_t available() const {
+        size_t count = 0;
+        for (size_t i = 0; i < N; ++i) count += __used[i] ? 0 : 1;
+        return count;
+    }
+
+private:
+    uint8_t __slots[N][kSlotSize];
+    bool __used[N];
+};
+";
+        let Some(size) = largest_message_size(module) else {
+            return;
+        };
+        // A zero-length array is not valid C++, so empty messages still get a byte.
+        let arena = ARENA.replace("SLOT_SIZE", &size.max(1).to_string());
+        self.header_output.push_str(&arena);
+        writeln!(self.header_output).unwrap();
+    }
+
+    /// Writes `utils::byteswap_array_if_needed`, which converts a run of equally sized elements
+    /// 16 bytes at a time with SSSE3 or NEON where the compiler targets them.
+    fn write_simd_utilities(&mut self) {
+        const SIMD: &str = "\
+// Swaps the bytes of `count` elements of `width` bytes each, 16 bytes at a time where SSSE3 or
+// NEON is available. The elements need not be aligned.
+inline void byteswap_array(void* data, size_t count, size_t width) {
+    uint8_t* bytes = static_cast<uint8_t*>(data);
+    size_t size = count * width;
+    size_t i = 0;
+#if defined(__SSSE3__)
+    const __m128i mask = width == 2 ? _mm_setr_epi8(1, 0, 3, 2, 5, 4, 7, 6, 9, 8, 11, 10, 13, 12, 15, 14)
+        : width == 4 ? _mm_setr_epi8(3, 2, 1, 0, 7, 6, 5, 4, 11, 10, 9, 8, 15, 14, 13, 12)
+        : _mm_setr_epi8(7, 6, 5, 4, 3, 2, 1, 0, 15, 14, 13, 12, 11, 10, 9, 8);
+    for (; i + 16 <= size; i += 16) {
+        __m128i block = _mm_loadu_si128(reinterpret_cast<const __m128i*>(bytes + i));
+        _mm_storeu_si128(reinterpret_cast<__m128i*>(bytes + i), _mm_shuffle_epi8(block, mask));
+    }
+#elif defined(__ARM_NEON)
+    for (; i + 16 <= size; i += 16) {
+        uint8x16_t block = vld1q_u8(bytes + i);
+        block = width == 2 ? vrev16q_u8(block) : width == 4 ? vrev32q_u8(block) : vrev64q_u8(block);
+        vst1q_u8(bytes + i, block);
+    }
+#endif
+    for (; i < size; i += width) {
+        for (size_t lo = i, hi = i + width - 1; lo < hi; ++lo, --hi) {
+            uint8_t byte = bytes[lo];
+            bytes[lo] = bytes[hi];
+            bytes[hi] = byte;
+        }
+    }
+}
+
+// Swaps the bytes of `count` elements of `width` bytes each if the host order does not match
+// the network order.
+inline void byteswap_array_if_needed(void* data, size_t count, size_t width) {
+    if (ONYX_HOST_ORDER != ONYX_NETWORK_ORDER) {
+        byteswap_array(data, count, width);
+    }
+}
+";
+        writeln!(self.header_output, "namespace utils {{\n").unwrap();
+        self.header_output.push_str(SIMD);
+        writeln!(self.header_output, "\n}} // namespace utils\n").unwrap();
+    }
+
+    /// Writes the default `ONYX_TRACE_FIELD`, which prints each traced field to stderr.
+    fn write_trace_utilities(&mut self) {
+        const TRACE: &str = "\
+/// Prints a decoded field to stderr; define ONYX_TRACE_FIELD(message, field, offset, value)
+/// before including this header to report fields elsewhere.
+inline void trace_field(const char* message, const char* field, size_t offset, uint64_t value) {
+    fprintf(stderr, \"%s.%s at %zu: %llu\\n\", message, field, offset, (unsigned long long)value);
+}
+
+inline void trace_field(const char* message, const char* field, size_t offset, int64_t value) {
+    fprintf(stderr, \"%s.%s at %zu: %lld\\n\", message, field, offset, (long long)value);
+}
+
+inline void trace_field(const char* message, const char* field, size_t offset, double value) {
+    fprintf(stderr, \"%s.%s at %zu: %g\\n\", message, field, offset, value);
+}
+
+inline void trace_field(const char* message, const char* field, size_t offset, const char* value) {
+    fprintf(stderr, \"%s.%s at %zu: %s\\n\", message, field, offset, value);
+}
+";
+        writeln!(self.header_output, "#ifdef ONYX_TRACE").unwrap();
+        writeln!(self.header_output, "namespace utils {{\n").unwrap();
+        self.header_output.push_str(TRACE);
+        writeln!(self.header_output, "\n}} // namespace utils\n").unwrap();
+        writeln!(self.header_output, "#ifndef ONYX_TRACE_FIELD").unwrap();
         writeln!(
-            self.source_output,
-            "{}{class_name}* wire_format_data = ({class_name}*)buffer;",
-            self.config.get_indent(1)
+            self.header_output,
+            "#define ONYX_TRACE_FIELD(message, field, offset, value) utils::trace_field(message, field, offset, value)"
         )
         .unwrap();
-        writeln!(self.source_output).unwrap();
-
-        // Iterate and apply in-place swapping (wire -> host)
-        for group in field_groups {
-            let first_field = group[0];
-            let name = &first_field.name;
+        writeln!(self.header_output, "#endif").unwrap();
+        writeln!(self.header_output, "#endif // ONYX_TRACE\n").unwrap();
+    }
 
-            if group.len() > 1 || first_field.bit_field_size.is_some() {
-                // CASE 1: Bit-Field Container (uint8_t __raw_{name}[N])
-                let bytes = first_field.get_bit_width(module).div_ceil(8);
-                if bytes > 1 {
-                    let container_type = match bytes {
-                        2 => "uint16_t",
-                        4 => "uint32_t",
-                        8 => "uint64_t",
-                        _ => "void",
-                    };
-                    writeln!(
-                        self.source_output,
-                        "{}// SWAP: Bit-field container {name}",
-                        self.config.get_indent(1)
-                    )
-                    .unwrap();
-                    writeln!(
-                        self.source_output,
-                        "{}*({container_type}*)wire_format_data->__raw_{name} = utils::byteswap_if_needed(*({container_type}*)wire_format_data->__raw_{name});",
-                        self.config.get_indent(1)
-                    ).unwrap();
-                } else {
-                    writeln!(
-                        self.source_output,
-                        "{}// INFO: Bit-field container {name} (1 byte), no swap needed.",
-                        self.config.get_indent(1)
-                    )
-                    .unwrap();
-                }
-            } else {
-                // CASE 2: Primitive or Custom Field (__raw_{name})
-                match &first_field.type_info {
-                    Type::Primitive(p) => {
-                        if p.get_bit_width().div_ceil(8) > 1 {
-                            // Primitive: In-place swap
-                            writeln!(
-                                self.source_output,
-                                "{}// SWAP: Primitive field {name}",
-                                self.config.get_indent(1),
-                            )
-                            .unwrap();
-                            writeln!(
-                                self.source_output,
-                                "{}wire_format_data->__raw_{name} = utils::byteswap_if_needed(wire_format_data->__raw_{name});", self.config.get_indent(1)
-                            ).unwrap();
-                        } else {
-                            writeln!(
-                                self.source_output,
-                                "{}// INFO: Primitive field {name} (1 byte), no swap needed.",
-                                self.config.get_indent(1)
-                            )
-                            .unwrap();
-                        }
-                    }
-                    Type::Custom(s) => {
-                        match module.definitions.get(s).unwrap() {
-                            Definition::Struct(_) | Definition::Message(_) => {
-                                writeln!(
-                                    self.source_output,
-                                    "{}wire_format_data->__raw_{name}.Serialize(*({s}::Buffer*) &wire_format_data->__raw_{name});", self.config.get_indent(1)
-                                )
-                                .unwrap();
-                            }
-                            Definition::Enum(_) => {}
-                        }
-                        // Call Deserialize on the memory block where the nested struct resides
-                    }
-                }
-            }
+    /// Writes the source map comment for the declaration at `span` to the header, if a source
+    /// map is set.
+    fn write_source_comment(&mut self, span: Span, indent: usize) {
+        if let Some(comment) = self.source_map.as_ref().and_then(|s| s.comment(span)) {
+            let indent = self.config.get_indent(indent);
+            writeln!(self.header_output, "{indent}{comment}").unwrap();
         }
+    }
 
-        writeln!(self.source_output, "}}\n").unwrap();
+    /// Writes utilities with an `ONYX_UTILS_<name>_` guard, so that headers generated for
+    /// several modules of a workspace can be included together.
+    fn write_guarded(&mut self, name: &str, write: impl FnOnce(&mut Self)) {
+        writeln!(self.header_output, "#ifndef ONYX_UTILS_{name}_").unwrap();
+        writeln!(self.header_output, "#define ONYX_UTILS_{name}_\n").unwrap();
+        write(self);
+        writeln!(self.header_output, "#endif // ONYX_UTILS_{name}_\n").unwrap();
     }
 
     /// Writes the C++ utility functions for endianness handling.
@@ -897,7 +3195,11 @@ impl CppGenerator {
         .unwrap();
 
         // Generate byteswap_if_needed for all multi-byte types
-        for (cpp_type, _, _) in swap_types {
+        let multi_byte_types = swap_types
+            .iter()
+            .flat_map(|(u_type, i_type, _)| [*u_type, *i_type])
+            .chain(["float", "double"]);
+        for cpp_type in multi_byte_types {
             writeln!(
                 self.header_output,
                 "inline {cpp_type} byteswap_if_needed({cpp_type} value) {{"
@@ -932,84 +3234,185 @@ impl CppGenerator {
 }
 
 impl CodeGenerator for CppGenerator {
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(level = "info", name = "CppGenerator::generate", skip_all, fields(path = %self.file_path.display()))
+    )]
     fn generate(&mut self, module: &OnyxModule) -> Result<Vec<(PathBuf, String)>, CompileError> {
+        self.banner_lines = self.banner.lines(module)?;
+        self.constants_output = match self.constants {
+            true => Some(self.constants_file(module)?),
+            false => None,
+        };
+        let vectors_json = match self.vectors {
+            true => Some(vectors::to_json(module, &vectors::vectors(module)?)?),
+            false => None,
+        };
+        check_order(module)?;
+        let named = apply_field_naming(module, self.field_naming)?;
+        let renamed = mangle_reserved(&named, RESERVED)?;
+        let dependencies = self.dependency_includes(module, renamed.as_ref())?;
+        let module = renamed.as_ref();
+        let hir = hir::lower(module)?;
+        check_containers(&hir)?;
         self.header_output.clear();
         self.source_output.clear();
 
         let namespace = "onyx";
 
-        self.write_header_includes();
-        self.write_source_includes();
-
+        self.write_header_includes(module, &dependencies.header);
         writeln!(self.header_output, "namespace {namespace} {{").unwrap();
-        writeln!(self.source_output, "namespace {namespace} {{\n").unwrap();
+        for declaration in &dependencies.declarations {
+            writeln!(self.header_output, "{declaration}").unwrap();
+        }
+        if !dependencies.declarations.is_empty() {
+            writeln!(self.header_output).unwrap();
+        }
+        if !self.amalgamate {
+            self.write_source_includes(module, &dependencies.source);
+            writeln!(self.source_output, "namespace {namespace} {{\n").unwrap();
+        }
 
-        self.write_endianness_utilities(&module.endianness);
-        writeln!(self.header_output).unwrap();
+        let endianness = match module.endianness {
+            WireEndianness::Big => "ENDIANNESS_BIG",
+            WireEndianness::Little => "ENDIANNESS_LITTLE",
+            WireEndianness::Host => "ENDIANNESS_HOST",
+        };
+        self.write_guarded(endianness, |g| {
+            g.write_endianness_utilities(&module.endianness)
+        });
+        if used_features(module).contains(&Feature::BoundedVectors) {
+            self.write_guarded("BOUNDED_VEC", Self::write_bounded_vector_template);
+        }
+        if used_features(module).contains(&Feature::ByteArrays) {
+            self.write_guarded("TO_HEX", Self::write_hex_formatter);
+        }
+        if self.swaps_in_bulk(module) {
+            self.write_guarded("BYTESWAP_ARRAY", Self::write_simd_utilities);
+        }
+        if self.trace {
+            self.write_guarded("TRACE", Self::write_trace_utilities);
+        }
+        if self.reflection {
+            self.write_guarded("REFLECTION", Self::write_reflection_types);
+        }
+        if used_features(module).contains(&Feature::Deprecations) {
+            self.write_guarded("DEPRECATED", Self::write_deprecated_macro);
+        }
+        self.write_message_ids(module);
+        self.write_forward_declarations(module);
 
         for id in &module.order {
+            trace::debug!(definition = %id, "generating definition");
             let def = match module.definitions.get(id) {
                 Some(def) => def,
                 None => {
-                    return Err(CompileError(format!("expected to find type {id} in AST")));
+                    return Err(
+                        Diagnostic::error(format!("expected to find type {id} in AST"))
+                            .with_code(codes::INTERNAL),
+                    );
                 }
             };
 
             match def {
                 Definition::Enum(e) => {
                     // Enums go entirely in the header
+                    self.write_source_comment(e.span, 0);
                     self.write_enum(e)?;
                     writeln!(self.header_output).unwrap();
                 }
-                Definition::Struct(s) => {
-                    let struct_size = match s.size {
-                        Some(struct_size) => struct_size.div_ceil(8),
-                        None => {
-                            return Err(CompileError(format!(
-                                "expected {} to have size, found none",
-                                s.name
-                            )));
-                        }
-                    };
-                    let groups = self.get_field_groups(&s.fields);
-                    self.write_class_declaration(&s.name, &groups, module, struct_size);
-                    writeln!(self.header_output).unwrap();
-                    self.write_class_definition(module, &s.name, &groups);
-                }
-                Definition::Message(m) => {
-                    let msg_size = match m.size {
-                        Some(msg_size) => msg_size.div_ceil(8),
-                        None => {
-                            return Err(CompileError(format!(
-                                "expected {} to have size, found none",
-                                m.name
-                            )));
-                        }
-                    };
-                    let groups = self.get_field_groups(&m.fields);
-                    self.write_class_declaration(&m.name, &groups, module, msg_size);
+                Definition::Struct(_) | Definition::Message(_) => {
+                    let layout = hir.layout(*id).unwrap();
+                    let slots = &layout.slots;
+                    self.write_source_comment(def.span(), 0);
+                    if self.reflection {
+                        self.write_field_table(id.as_str(), slots);
+                    }
+                    self.write_class_declaration(id.as_str(), slots, module, layout.size, None);
                     writeln!(self.header_output).unwrap();
-                    self.write_class_definition(module, &m.name, &groups);
+                    self.write_class_definition(module, id.as_str(), slots);
                 }
             }
         }
 
+        if self.message_arena {
+            self.write_message_arena(module);
+        }
+        self.write_dispatch(module);
+        for old in &module.history {
+            self.write_version(namespace, module, &hir::lower(old)?);
+        }
+
+        if self.amalgamate {
+            // Every class is declared by now, so the inline definitions can follow them.
+            writeln!(self.header_output).unwrap();
+            self.header_output.push_str(&self.source_output);
+            writeln!(self.header_output, "}} // namespace {namespace}").unwrap();
+            self.write_header_footer(module);
+            return Ok(self.output_files());
+        }
+
         writeln!(self.header_output, "}} // namespace {namespace}").unwrap();
         writeln!(self.source_output, "}} // namespace {namespace}").unwrap();
+        if used_features(module).contains(&Feature::Deprecations) {
+            write!(self.source_output, "\n{}", Self::RESTORE_DEPRECATIONS).unwrap();
+        }
 
-        self.write_header_footer();
+        self.write_header_footer(module);
+        self.vectors_output = match (vectors_json, &self.package) {
+            (Some(json), Some(package)) => Some((json, Some(self.vectors_test(module, package)?))),
+            (Some(json), None) => Some((json, None)),
+            (None, _) => None,
+        };
 
-        Ok(vec![
-            (
-                self.file_path.with_extension("hpp"),
-                self.header_output.clone(),
-            ),
-            (
-                self.file_path.with_extension("cpp"),
-                self.source_output.clone(),
-            ),
-        ])
+        Ok(self.output_files())
+    }
+}
+
+/// Returns the C++ accessor chain reaching `path` from a pointer to its definition.
+fn cpp_path(path: &[Step]) -> String {
+    path.iter()
+        .enumerate()
+        .map(|(i, step)| match step {
+            Step::Field(f) => format!("{}{f}()", if i == 0 { "->" } else { "." }),
+            Step::Index(i) => format!(".get({i})"),
+        })
+        .collect()
+}
+
+/// Returns the `std::array` literal of a byte array test vector leaf.
+fn cpp_byte_array(value: &Value) -> String {
+    let Value::Bytes(bytes) = value else {
+        unreachable!("{value:?} is not a byte array");
+    };
+    let bytes: Vec<String> = bytes.iter().map(|b| format!("{b:#04x}")).collect();
+    format!(
+        "std::array<uint8_t, {}>{{{{{}}}}}",
+        bytes.len(),
+        bytes.join(", ")
+    )
+}
+
+/// Checks that every run of bit-fields fits a container of at most 8 bytes, the widest
+/// integer its accessors can read it into.
+fn check_containers(hir: &Hir) -> Result<(), CompileError> {
+    for name in &hir.order {
+        let Some(layout) = hir.layout(*name) else {
+            continue;
+        };
+        for slot in &layout.slots {
+            if slot.is_bit_field_container() && slot.size > 8 {
+                return Err(Diagnostic::error(format!(
+                    "the bit-fields from '{}' in '{name}' share a {}-byte container",
+                    slot.first().name,
+                    slot.size
+                ))
+                .with_code(codes::CODEGEN)
+                .with_help("the C++ target reads bit-fields from containers of up to 8 bytes"));
+            }
+        }
     }
+    Ok(())
 }
 
 #[cfg(test)]
@@ -1059,10 +3462,525 @@ mod tests {
         let generator = CppGenerator::default();
         assert_eq!(generator.map_byte_width_to_cpp(&1), "uint8_t");
         assert_eq!(generator.map_byte_width_to_cpp(&2), "uint16_t");
+        assert_eq!(generator.map_byte_width_to_cpp(&3), "uint32_t");
         assert_eq!(generator.map_byte_width_to_cpp(&4), "uint32_t");
+        assert_eq!(generator.map_byte_width_to_cpp(&5), "uint64_t");
         assert_eq!(generator.map_byte_width_to_cpp(&8), "uint64_t");
     }
 
+    #[test]
+    fn test_constants_output() {
+        let source = "flags Perm : u16 { Read, Write, }\n\
+                      struct class { x u8, }\n\
+                      @id(7) message Packet { perm Perm, len u32, }";
+        let module = crate::parser::Parser::new(source)
+            .and_then(|p| p.parse_module())
+            .unwrap();
+        let mut generator = CppGenerator::default();
+        generator.add_file_path(PathBuf::from("ping")).unwrap();
+        generator.set_constants(true);
+        let files = generator.generate(&module).unwrap();
+        assert_eq!(files[2].0, PathBuf::from("ping_constants.hpp"));
+        let constants = &files[2].1;
+        assert!(!constants.contains("#include \"ping.hpp\""));
+        let hir = hir::lower(&module).unwrap();
+        assert!(constants.contains(&format!(
+            "static const uint64_t kSchemaFingerprint = UINT64_C({:#018x});",
+            fingerprint::module(&hir)
+        )));
+        assert!(constants.contains(
+            "namespace Perm {\n    static const uint16_t kRead = 1;\n    \
+             static const uint16_t kWrite = 2;\n    static const size_t kSize = 2;\n"
+        ));
+        assert!(constants.contains("namespace class_ {\n    static const size_t kSize = 1;\n"));
+        assert!(constants.contains(&format!(
+            "namespace Packet {{\n    static const uint16_t kId = 7;\n    \
+             static const size_t kSize = 6;\n    \
+             static const uint64_t kFingerprint = UINT64_C({:#018x});\n}} // namespace Packet",
+            fingerprint::definition(&hir, "Packet").unwrap()
+        )));
+    }
+
+    #[test]
+    fn test_amalgamated_output() {
+        let source = "struct Point { x u16, y u16, }";
+        let module = crate::parser::Parser::new(source)
+            .and_then(|p| p.parse_module())
+            .unwrap();
+        let mut generator = CppGenerator::default();
+        generator.add_file_path(PathBuf::from("point")).unwrap();
+        generator.set_amalgamate(true);
+
+        let files = generator.generate(&module).unwrap();
+        assert_eq!(files.len(), 1);
+        let (path, header) = &files[0];
+        assert_eq!(path, &PathBuf::from("point.hpp"));
+        assert!(!header.contains("#include \"point.hpp\""));
+        assert!(header.contains("inline Point* Point::Deserialize(Buffer& buffer) {"));
+        assert!(header.contains("inline void Point::Serialize(Buffer& buffer) const {"));
+        assert!(
+            header.find("inline Point*").unwrap() < header.find("} // namespace onyx").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_traced_output() {
+        let source = "struct Point { x u16, y i8, } message Line { a Point, b Point if flag == true, flag bool, }";
+        let module = crate::parser::Parser::new(source)
+            .and_then(|p| p.parse_module())
+            .unwrap();
+        let mut generator = CppGenerator::default();
+        generator.add_file_path(PathBuf::from("line")).unwrap();
+        let files = generator.generate(&module).unwrap();
+        assert!(!files[0].1.contains("ONYX_TRACE") && !files[1].1.contains("ONYX_TRACE"));
+
+        generator.set_trace(true);
+        let files = generator.generate(&module).unwrap();
+        let (header, source) = (&files[0].1, &files[1].1);
+        assert!(header.contains("#ifndef ONYX_TRACE_FIELD"));
+        assert!(source.contains(
+            "ONYX_TRACE_FIELD(\"Point\", \"x\", 0, static_cast<uint64_t>(result->__raw_x));"
+        ));
+        assert!(source.contains(
+            "ONYX_TRACE_FIELD(\"Point\", \"y\", 2, static_cast<int64_t>(result->__raw_y));"
+        ));
+        assert!(
+            source.contains(
+                "if (result->has_b()) { ONYX_TRACE_FIELD(\"Line\", \"b\", 3, \"Point\"); }"
+            )
+        );
+    }
+
+    #[test]
+    fn test_shared_memory_output() {
+        let source = "struct Point { x u16, y u16, }";
+        let module = crate::parser::Parser::new(source)
+            .and_then(|p| p.parse_module())
+            .unwrap();
+        let mut generator = CppGenerator::default();
+        generator.add_file_path(PathBuf::from("point")).unwrap();
+        assert!(
+            !generator.generate(&module).unwrap()[0]
+                .1
+                .contains("from_mmap")
+        );
+
+        generator.set_shared_memory(true);
+        let header = &generator.generate(&module).unwrap()[0].1;
+        assert!(
+            header.contains(
+                "static inline Point* at(void* addr) { return static_cast<Point*>(addr); }"
+            )
+        );
+        assert!(header.contains("static inline Point* from_mmap(void* ptr, size_t len) {"));
+        assert!(header.contains("% alignof(Point) != 0) return nullptr;"));
+    }
+
+    #[test]
+    fn test_message_arena_output() {
+        let source =
+            "struct Point { x u16, } message Ping { seq u16, } message Move { to Point, at u64, }";
+        let module = crate::parser::Parser::new(source)
+            .and_then(|p| p.parse_module())
+            .unwrap();
+        let mut generator = CppGenerator::default();
+        generator.add_file_path(PathBuf::from("arena")).unwrap();
+        generator.set_message_arena(true);
+        let header = &generator.generate(&module).unwrap()[0].1;
+        assert!(header.contains("class MessageArena {"));
+        assert!(header.contains("static const size_t kSlotSize = 10;"));
+
+        let module = crate::parser::Parser::new("struct Point { x u16, }")
+            .and_then(|p| p.parse_module())
+            .unwrap();
+        assert!(
+            !generator.generate(&module).unwrap()[0]
+                .1
+                .contains("MessageArena")
+        );
+    }
+
+    #[test]
+    fn test_reflection_output() {
+        let source = "struct Point { x i16, y i16, } \
+                      message Shape { ready bool : 1, level u8 : 7, origin Point if ready == true, scale fixed<i16, 10>, }";
+        let module = crate::parser::Parser::new(source)
+            .and_then(|p| p.parse_module())
+            .unwrap();
+        let mut generator = CppGenerator::default();
+        generator.add_file_path(PathBuf::from("shape")).unwrap();
+        assert!(
+            !generator.generate(&module).unwrap()[0]
+                .1
+                .contains("FieldInfo")
+        );
+
+        generator.set_reflection(true);
+        let header = &generator.generate(&module).unwrap()[0].1;
+        assert!(header.contains("#include <array>"));
+        assert!(header.contains("struct FieldInfo {"));
+        assert!(header.contains(
+            "constexpr std::array<FieldInfo, 4> kShapeFields = {{\n    \
+             {\"ready\", 0, 0, 1, FieldKind::Bool, \"bool\", false},\n    \
+             {\"level\", 0, 1, 7, FieldKind::Unsigned, \"u8\", false},\n    \
+             {\"origin\", 1, 0, 32, FieldKind::Struct, \"Point\", true},\n    \
+             {\"scale\", 5, 0, 16, FieldKind::Fixed, \"fixed<i16, 10>\", false}\n}};"
+        ));
+        assert!(header.contains("visitor(kShapeFields[1], level());"));
+        assert!(header.contains("if (has_origin()) { visitor(kShapeFields[2], __raw_origin); }"));
+        assert!(header.contains("visitor(kShapeFields[3], scale());"));
+    }
+
+    #[test]
+    fn test_source_map_output() {
+        let source = "enum Kind : u8 { A, }\n@packed_bits message Ping {\n    kind Kind,\n    ready bool : 1,\n}\n";
+        let module = crate::parser::Parser::new(source)
+            .and_then(|p| p.parse_module())
+            .unwrap();
+        let mut generator = CppGenerator::default();
+        generator.add_file_path(PathBuf::from("ping")).unwrap();
+        assert!(
+            !generator.generate(&module).unwrap()[0]
+                .1
+                .contains("// onyx:")
+        );
+
+        generator.set_source_map(SchemaSource::new("schemas/ping.onyx", source));
+        let header = &generator.generate(&module).unwrap()[0].1;
+        assert!(header.contains("\n// onyx: schemas/ping.onyx:1:6\nenum class Kind"));
+        assert!(header.contains("\n// onyx: schemas/ping.onyx:2:22\nclass Ping {"));
+        assert!(header.contains("    // onyx: schemas/ping.onyx:3:5\n    /// Accessor for kind\n"));
+        assert!(
+            header.contains("    // onyx: schemas/ping.onyx:4:5\n    /// Accessor for ready\n")
+        );
+    }
+
+    #[test]
+    fn test_banner_output() {
+        let module = crate::parser::Parser::new("struct Point { x i32, }")
+            .and_then(|p| p.parse_module())
+            .unwrap();
+        let mut generator = CppGenerator::default();
+        generator.add_file_path(PathBuf::from("point")).unwrap();
+        generator
+            .set_package(PackageConfig::new("geo", "1.0.0"))
+            .unwrap();
+        generator.set_constants(true);
+        generator.set_banner(
+            Banner::new()
+                .with_copyright("Copyright 2026 Example Corp.\nSPDX-License-Identifier: MIT")
+                .with_source("point.onyx")
+                .with_fingerprint(),
+        );
+        let fingerprint = fingerprint::module(&hir::lower(&module).unwrap());
+        let banner = format!(
+            "Copyright 2026 Example Corp.\n\
+             SPDX-License-Identifier: MIT\n\
+             Automatically generated by Onyx IDL compiler from point.onyx. Do not edit.\n\
+             Schema fingerprint: {fingerprint:#018x}\n"
+        );
+        let comment = |marker: &str| {
+            banner
+                .lines()
+                .map(|line| format!("{marker} {line}\n"))
+                .collect::<String>()
+        };
+
+        for (path, contents) in generator.generate(&module).unwrap() {
+            let marker = match path.extension().and_then(|e| e.to_str()) {
+                Some("txt" | "in") => "#",
+                _ => "//",
+            };
+            assert!(contents.starts_with(&comment(marker)), "{}", path.display());
+        }
+    }
+
+    #[test]
+    fn test_deprecated_output() {
+        let source = "@deprecated(\"use Kind\") enum Old : u8 { A, }\n\
+                      @deprecated(\"see C:\\docs\") struct Header { len u8, }\n\
+                      message Ping { @deprecated(\"use ready\") old Old, ready bool : 1, \
+                      @deprecated(\"unused\") spare u8 : 7, }";
+        let module = crate::parser::Parser::new(source)
+            .and_then(|p| p.parse_module())
+            .unwrap();
+        let mut generator = CppGenerator::default();
+        generator.add_file_path(PathBuf::from("ping")).unwrap();
+        let files = generator.generate(&module).unwrap();
+        let (header, source) = (&files[0].1, &files[1].1);
+        assert!(header.contains("#define ONYX_DEPRECATED(note) [[deprecated(note)]]\n"));
+        assert!(header.contains("enum class ONYX_DEPRECATED(\"use Kind\") Old : uint8_t {"));
+        assert!(header.contains("class ONYX_DEPRECATED(\"see C:\\\\docs\") Header {"));
+        assert_eq!(
+            header
+                .matches("    ONYX_DEPRECATED(\"use ready\")\n")
+                .count(),
+            2
+        );
+        assert!(header.contains("    ONYX_DEPRECATED(\"unused\")\n    inline void spare("));
+        assert!(header.contains("    /// Accessor for ready\n    inline const bool ready()"));
+        // The generated code uses what it deprecates without warnings.
+        for file in [header, source] {
+            assert!(file.contains("#pragma GCC diagnostic ignored \"-Wdeprecated-declarations\""));
+            assert!(file.contains("#pragma GCC diagnostic pop"));
+        }
+    }
+
+    #[test]
+    fn test_doc_comment_output() {
+        let source = "/// Link quality.\nenum Level : u8 { /// No signal.\n Low, High, }\n\
+                      /// A ping.\n///\n/// Sent once a second. \\\n\
+                      message Ping { /// Signal level.\n @deprecated(\"use ready\") level Level, \
+                      ready bool : 1, /// Unused.\n spare u8 : 7, }";
+        let module = crate::parser::Parser::new(source)
+            .and_then(|p| p.parse_module())
+            .unwrap();
+        let mut generator = CppGenerator::default();
+        generator.add_file_path(PathBuf::from("ping")).unwrap();
+        let header = &generator.generate(&module).unwrap()[0].1;
+        assert!(header.contains(
+            "/// Link quality.\nenum class Level : uint8_t {\n    /// No signal.\n    Low,\n"
+        ));
+        // A trailing backslash would continue the comment onto the class.
+        assert!(header.contains("/// A ping.\n///\n/// Sent once a second.\nclass Ping {"));
+        // Each accessor of a field has its description, before any deprecation.
+        assert_eq!(
+            header
+                .matches(
+                    "    ///\n    /// Signal level.\n    ONYX_DEPRECATED(\"use ready\")\n    inline "
+                )
+                .count(),
+            2
+        );
+        assert!(header.contains("    /// Unused.\n    inline void spare("));
+        assert!(header.contains("    /// Accessor for ready\n    inline const bool ready()"));
+    }
+
+    #[test]
+    fn test_signed_bit_fields() {
+        let source = "struct Reading { temp i16 : 12, trend i8 : 4, full i8 : 8, level u8 : 8, \
+                      stamp u32, wide i64 : 64, }";
+        let module = crate::parser::Parser::new(source)
+            .and_then(|p| p.parse_module())
+            .unwrap();
+        let mut generator = CppGenerator::default();
+        generator.add_file_path(PathBuf::from("reading")).unwrap();
+        let header = &generator.generate(&module).unwrap()[0].1;
+        assert!(header.contains(
+            "return static_cast<const int16_t>(static_cast<int64_t>(((*raw_value >> 0) & 0xFFF) \
+             ^ 0x800) - 0x800);"
+        ));
+        assert!(header.contains(
+            "return static_cast<const int8_t>(static_cast<int64_t>(((*raw_value >> 12) & 0xF) \
+             ^ 0x8) - 0x8);"
+        ));
+        // Fields as wide as their type need no sign extension.
+        assert!(header.contains("return static_cast<const int8_t>(((*raw_value >> 16) & 0xFF));"));
+        assert!(header.contains(
+            "return static_cast<const int64_t>(((*raw_value >> 0) & 0xFFFFFFFFFFFFFFFF));"
+        ));
+    }
+
+    #[test]
+    fn test_simd_output() {
+        let source = "endian = big message Samples { levels vec<u16, 9>, tags vec<u8, 4>, }";
+        let module = crate::parser::Parser::new(source)
+            .and_then(|p| p.parse_module())
+            .unwrap();
+        let mut generator = CppGenerator::default();
+        generator.add_file_path(PathBuf::from("samples")).unwrap();
+        generator.set_simd(true);
+        let files = generator.generate(&module).unwrap();
+        let (header, source) = (&files[0].1, &files[1].1);
+        assert!(header.contains("#include <tmmintrin.h>"));
+        assert!(header.contains(
+            "inline void byteswap_array_if_needed(void* data, size_t count, size_t width) {"
+        ));
+        assert!(
+            source.contains("utils::byteswap_array_if_needed(result->__raw_levels.__items, 9, 2);")
+        );
+        assert!(!source.contains("__raw_tags.__items"));
+
+        // Host order never swaps, so there is nothing to vectorize.
+        let module =
+            crate::parser::Parser::new("endian = host message Samples { levels vec<u16, 9>, }")
+                .and_then(|p| p.parse_module())
+                .unwrap();
+        assert!(
+            !generator.generate(&module).unwrap()[0]
+                .1
+                .contains("byteswap_array")
+        );
+    }
+
+    #[test]
+    fn test_layout_checks() {
+        let source = "struct Sample { start_time u32, ready bool:1, level u8:7, value f64, }";
+        let module = crate::parser::Parser::new(source)
+            .and_then(|p| p.parse_module())
+            .unwrap();
+        let mut generator = CppGenerator::default();
+        generator.add_file_path(PathBuf::from("sample")).unwrap();
+        let header = &generator.generate(&module).unwrap()[0].1;
+        assert!(header.contains("static const size_t kStartTimeOffset = 0;"));
+        assert!(header.contains("static const size_t kValueOffset = 5;"));
+        assert!(!header.contains("kReadyOffset"));
+        assert!(header.contains(
+            "static_assert(sizeof(Sample) == kSizeOf, \"Sample does not match its Onyx size\");"
+        ));
+        assert!(header.contains("static_assert(offsetof(Sample, __raw_value) == kValueOffset, \"Sample::value does not match its Onyx offset\");"));
+    }
+
+    #[test]
+    fn test_field_naming() {
+        let source =
+            "message Reading { HTTP_status u16, flag bool : 1 if HTTP_status == 2, rest u8 : 7, }";
+        let module = crate::parser::Parser::new(source)
+            .and_then(|p| p.parse_module())
+            .unwrap();
+        let mut generator = CppGenerator::default();
+        generator.add_file_path(PathBuf::from("reading")).unwrap();
+        generator.set_field_naming(NamingConvention::CamelCase);
+        let header = &generator.generate(&module).unwrap()[0].1;
+        assert!(
+            header
+                .contains("inline const uint16_t httpStatus() const { return __raw_httpStatus; }")
+        );
+        assert!(header.contains("inline void httpStatus(const uint16_t value) {"));
+        assert!(header.contains("return httpStatus() == static_cast<uint16_t>(2ULL);"));
+        assert!(!header.contains("HTTP_status"));
+    }
+
+    #[test]
+    fn test_dependencies() {
+        let source = "enum Mode : u16 { On, }\nstruct Point { x u8, }\nenum Color : u8 { Red, }\n\
+                      message Shape { mode Mode, points vec<Point, 2>, }";
+        let mut module = crate::parser::Parser::new(source)
+            .and_then(|p| p.parse_module())
+            .unwrap();
+        // Shape is this module's own; the others come from dependencies.
+        module.order.retain(|id| id.as_str() == "Shape");
+        let generate = |amalgamate: bool| {
+            let mut generator = CppGenerator::default();
+            generator.add_file_path(PathBuf::from("shape")).unwrap();
+            generator.set_amalgamate(amalgamate);
+            generator.set_include_prefix("gen/");
+            generator.add_dependency("colors.hpp", ["Color"]);
+            generator.add_dependency("modes.hpp", ["Mode"]);
+            generator.add_dependency("points.hpp", ["Point"]);
+            generator.generate(&module).unwrap()
+        };
+
+        let files = generate(false);
+        let (header, source) = (&files[0].1, &files[1].1);
+        assert!(header.contains("enum class Mode : uint16_t;\n"));
+        assert!(!header.contains("modes.hpp"));
+        assert!(header.contains("#include \"gen/points.hpp\"\n\n#pragma pack"));
+        assert!(source.contains("#include \"shape.hpp\"\n#include \"gen/modes.hpp\"\n\n"));
+        assert!(!header.contains("colors.hpp") && !source.contains("colors.hpp"));
+
+        let header = &generate(true)[0].1;
+        assert!(header.contains("#include \"gen/modes.hpp\"\n#include \"gen/points.hpp\"\n"));
+        assert!(!header.contains("enum class Mode : uint16_t;"));
+
+        let mut generator = CppGenerator::default();
+        generator.add_file_path(PathBuf::from("shape")).unwrap();
+        let error = generator.generate(&module).unwrap_err();
+        assert_eq!(error.code(), Some(codes::CODEGEN));
+        assert_eq!(
+            error.message(),
+            "type 'Mode' is not defined by this module or any of its dependencies"
+        );
+    }
+
+    #[test]
+    fn test_dependency_order() {
+        let mut module = crate::parser::Parser::new(
+            "message Outer { middle Middle, } struct Middle { inner Inner, } struct Inner { x u8, }",
+        )
+        .and_then(|p| p.parse_module())
+        .unwrap();
+        let generate = |module: &OnyxModule| {
+            let mut generator = CppGenerator::default();
+            generator.add_file_path(PathBuf::from("nested")).unwrap();
+            generator.generate(module)
+        };
+
+        let header = generate(&module).unwrap().remove(0).1;
+        assert!(header.contains("class Inner;\nclass Middle;\nclass Outer;\n\n"));
+        let defined: Vec<usize> = ["class Inner {", "class Middle {", "class Outer {"]
+            .iter()
+            .map(|class| header.find(class).unwrap())
+            .collect();
+        assert!(defined.is_sorted());
+
+        module.order.reverse();
+        let error = generate(&module).unwrap_err();
+        assert_eq!(error.code(), Some(codes::CODEGEN));
+        assert_eq!(
+            error.message(),
+            "'Outer' is ordered before 'Middle', which its field 'middle' uses"
+        );
+    }
+
+    #[test]
+    fn test_package_output() {
+        let module = crate::parser::Parser::new("struct Point { x i32, y i32, }")
+            .and_then(|p| p.parse_module())
+            .unwrap();
+        let mut generator = CppGenerator::default();
+        generator
+            .add_file_path(PathBuf::from("out/point.onyx"))
+            .unwrap();
+        generator
+            .set_package(PackageConfig::new("geo", "2.1.0"))
+            .unwrap();
+        generator.set_amalgamate(true);
+
+        let files = generator.generate(&module).unwrap();
+        let paths: Vec<PathBuf> = files.iter().map(|(path, _)| path.clone()).collect();
+        assert_eq!(
+            paths,
+            [
+                "out/CMakeLists.txt",
+                "out/include/geo/point.hpp",
+                "out/geo.pc.in"
+            ]
+            .map(PathBuf::from)
+        );
+        assert!(
+            files[0]
+                .1
+                .contains("project(geo VERSION 2.1.0 LANGUAGES CXX)")
+        );
+        assert!(files[0].1.contains("add_library(geo INTERFACE)\n"));
+        assert!(
+            files[0]
+                .1
+                .contains("target_compile_features(geo INTERFACE cxx_std_11)")
+        );
+        assert!(
+            files[2]
+                .1
+                .contains("Version: 2.1.0\nCflags: -I${includedir}\n")
+        );
+        assert!(!files[2].1.contains("Libs:"));
+
+        generator.set_amalgamate(false);
+        let files = generator.generate(&module).unwrap();
+        assert_eq!(files[2].0, PathBuf::from("out/src/point.cpp"));
+        assert!(files[2].1.contains("#include \"geo/point.hpp\""));
+        assert!(files[0].1.contains("add_library(geo STATIC src/point.cpp)"));
+        assert!(files[3].1.contains("Libs: -L${libdir} -lgeo"));
+
+        for (name, version) in [("bad name", "1.0"), ("geo", "1.x"), ("geo", "1.2.3.4.5")] {
+            let err = generator
+                .set_package(PackageConfig::new(name, version))
+                .unwrap_err();
+            assert_eq!(err.code(), Some(codes::CODEGEN));
+        }
+    }
+
     #[test]
     #[should_panic]
     fn test_byte_width_mapping_invalid() {
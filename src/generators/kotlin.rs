@@ -0,0 +1,882 @@
+use std::{fmt::Write, path::PathBuf};
+
+use crate::{
+    ast::{Condition, Definition, EnumDef, OnyxModule, PrimitiveType, WireEndianness},
+    diagnostic::{Diagnostic, codes},
+    features::{Feature, used_features},
+    generators::{
+        Banner, CodeGenerator, CompileError, check_order, comment_lines,
+        mangle::{apply_field_naming, mangle_reserved},
+        naming::NamingConvention,
+    },
+    hir::{self, FieldLayout, Hir, Layout, Slot, TypeRef},
+    json,
+};
+
+/// Kotlin hard keywords, and `buffer`, the property of every view, which schema names are
+/// renamed away from with a trailing `_`.
+const RESERVED: &[&str] = &[
+    "as",
+    "break",
+    "buffer",
+    "class",
+    "continue",
+    "do",
+    "else",
+    "false",
+    "for",
+    "fun",
+    "if",
+    "in",
+    "interface",
+    "is",
+    "null",
+    "object",
+    "package",
+    "return",
+    "super",
+    "this",
+    "throw",
+    "true",
+    "try",
+    "typealias",
+    "typeof",
+    "val",
+    "var",
+    "when",
+    "while",
+];
+
+/// The package of generated files unless [`KotlinGenerator::set_package`] names another.
+const DEFAULT_PACKAGE: &str = "onyx";
+
+/// The file-private helpers every view reads and writes its buffer through. Fields are read
+/// byte by byte in `BYTE_ORDER`, so a view works whatever the order of its buffer.
+const HELPERS: &str = r#"
+/** Returns the [size] bytes of the buffer from [offset] as a buffer of their own. */
+private fun ByteBuffer.view(offset: Int, size: Int): ByteBuffer =
+    (duplicate().clear().position(offset).limit(offset + size) as ByteBuffer).slice()
+
+/** Reads the [size] bytes from [offset] as an unsigned integer in [BYTE_ORDER]. */
+private fun ByteBuffer.readBits(offset: Int, size: Int): Long {
+    var raw = 0L
+    for (i in 0 until size) {
+        val index = if (BYTE_ORDER == ByteOrder.BIG_ENDIAN) i else size - 1 - i
+        raw = (raw shl 8) or (get(offset + index).toLong() and 0xFFL)
+    }
+    return raw
+}
+
+/** Writes the low [size] bytes of [raw] from [offset] in [BYTE_ORDER]. */
+private fun ByteBuffer.writeBits(offset: Int, size: Int, raw: Long) {
+    for (i in 0 until size) {
+        val shift = if (BYTE_ORDER == ByteOrder.BIG_ENDIAN) 8 * (size - 1 - i) else 8 * i
+        put(offset + i, (raw ushr shift).toByte())
+    }
+}
+
+/** Copies the [size] bytes from [offset]. */
+private fun ByteBuffer.readBytes(offset: Int, size: Int): ByteArray =
+    ByteArray(size).also { view(offset, size).get(it) }
+
+/** Writes [value], which must hold exactly [size] bytes, from [offset]. */
+private fun ByteBuffer.writeBytes(offset: Int, size: Int, value: ByteArray) {
+    require(value.size == size) { "expected $size bytes, got ${value.size}" }
+    view(offset, size).put(value)
+}
+
+/** Returns [index] if it is below [count], and throws otherwise. */
+private fun checkIndex(index: Int, count: Int): Int {
+    if (index !in 0 until count) {
+        throw IndexOutOfBoundsException("index $index is out of bounds for $count elements")
+    }
+    return index
+}
+"#;
+
+/// How a value in the buffer of a view is read and written.
+struct Access {
+    /// The Kotlin type of the value.
+    ty: String,
+    /// An expression reading the value.
+    get: String,
+    /// A statement writing `value`, or `None` for nested views, which are written through.
+    set: Option<String>,
+}
+
+#[derive(Debug, Default)]
+/// The Kotlin code generator.
+///
+/// Generates a Kotlin source file (.kt) for JVM and Android code. Each struct and message
+/// becomes a `@JvmInline` value class wrapping the `ByteBuffer` it is read from, with a
+/// property per field and a companion object holding its size and field offsets, so views
+/// cost no allocation. Enums become enum classes, and flags enums value classes with a
+/// constant per flag.
+pub struct KotlinGenerator {
+    output: String,
+    file_path: PathBuf,
+    package: Option<String>,
+    banner: Banner,
+    field_naming: NamingConvention,
+}
+
+impl KotlinGenerator {
+    /// Adds a file path to the generator configuration.
+    pub fn add_file_path(&mut self, file_path: PathBuf) -> Result<(), CompileError> {
+        if file_path.file_stem().is_none() {
+            return Err(Diagnostic::error(format!(
+                "output path '{}' has no file name",
+                file_path.display()
+            ))
+            .with_code(codes::CODEGEN));
+        }
+        self.file_path = file_path;
+        Ok(())
+    }
+
+    /// Declares the generated file in `package`, such as `com.example.protocol`, rather than
+    /// in `onyx`.
+    pub fn set_package(&mut self, package: impl Into<String>) -> Result<(), CompileError> {
+        let package = package.into();
+        let valid = package.split('.').all(|segment| {
+            segment.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+                && segment
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '_')
+                && !RESERVED.contains(&segment)
+        });
+        if !valid {
+            return Err(
+                Diagnostic::error(format!("invalid Kotlin package name '{package}'"))
+                    .with_code(codes::CODEGEN)
+                    .with_help(
+                        "package names are identifiers separated by '.', such as \
+                         'com.example.protocol'",
+                    ),
+            );
+        }
+        self.package = Some(package);
+        Ok(())
+    }
+
+    /// Opens the file with `banner` rather than the default one.
+    pub fn set_banner(&mut self, banner: Banner) {
+        self.banner = banner;
+    }
+
+    /// Names the properties of each field in `convention`, such as `startTime` for a field
+    /// `start_time` in camelCase. Names are used as written by default.
+    pub fn set_field_naming(&mut self, convention: NamingConvention) {
+        self.field_naming = convention;
+    }
+
+    fn write_header(&mut self, module: &OnyxModule, banner: &[String]) {
+        self.output.push_str(&comment_lines(banner, "//"));
+        let features = used_features(module);
+        if features.contains(&Feature::Deprecations) {
+            // The generated code itself uses deprecated items; only their users are warned.
+            writeln!(self.output, "@file:Suppress(\"DEPRECATION\")\n").unwrap();
+        }
+        let package = self.package.as_deref().unwrap_or(DEFAULT_PACKAGE);
+        writeln!(self.output, "package {package}\n").unwrap();
+        writeln!(self.output, "import java.nio.ByteBuffer").unwrap();
+        writeln!(self.output, "import java.nio.ByteOrder").unwrap();
+        if features.contains(&Feature::FixedPoint) {
+            writeln!(self.output, "import kotlin.math.roundToLong").unwrap();
+        }
+        let order = match module.endianness {
+            WireEndianness::Big => "ByteOrder.BIG_ENDIAN",
+            WireEndianness::Little => "ByteOrder.LITTLE_ENDIAN",
+            WireEndianness::Host => "ByteOrder.nativeOrder()",
+        };
+        writeln!(
+            self.output,
+            "\n/** The byte order of every field in this file. */"
+        )
+        .unwrap();
+        writeln!(self.output, "private val BYTE_ORDER: ByteOrder = {order}").unwrap();
+        self.output.push_str(HELPERS);
+    }
+
+    fn write_deprecated(&mut self, indent: &str, note: Option<&str>) {
+        if let Some(note) = note {
+            writeln!(self.output, "{indent}@Deprecated({})", string(note)).unwrap();
+        }
+    }
+
+    fn write_enum(&mut self, e: &EnumDef) {
+        if e.flags {
+            return self.write_flags(e);
+        }
+        let t = kotlin_type(&e.underlying_type);
+        writeln!(
+            self.output,
+            "\n/** {}, carried on the wire as {}. */",
+            e.name, e.underlying_type
+        )
+        .unwrap();
+        self.write_deprecated("", e.deprecated.as_deref());
+        writeln!(self.output, "enum class {}(val value: {t}) {{", e.name).unwrap();
+        for (variant, value) in e.variants.iter().zip(e.variant_values()) {
+            let value = literal(&e.underlying_type, value);
+            writeln!(self.output, "    {}({value}),", variant.name).unwrap();
+        }
+        writeln!(self.output, "    ;\n").unwrap();
+        writeln!(self.output, "    companion object {{").unwrap();
+        self.write_size_constant(e.name.as_str(), e.underlying_type.get_byte_size());
+        writeln!(
+            self.output,
+            "\n        /** Returns the variant whose value is [value], or null if there is none. */"
+        )
+        .unwrap();
+        writeln!(
+            self.output,
+            "        fun fromValue(value: {t}): {}? = values().firstOrNull {{ it.value == value }}",
+            e.name
+        )
+        .unwrap();
+        writeln!(self.output, "    }}\n}}").unwrap();
+    }
+
+    fn write_flags(&mut self, e: &EnumDef) {
+        let name = e.name.as_str();
+        let t = kotlin_type(&e.underlying_type);
+        writeln!(
+            self.output,
+            "\n/** {name}, a set of flags carried on the wire as {}. */",
+            e.underlying_type
+        )
+        .unwrap();
+        self.write_deprecated("", e.deprecated.as_deref());
+        writeln!(self.output, "@JvmInline").unwrap();
+        writeln!(self.output, "value class {name}(val bits: {t}) {{").unwrap();
+        writeln!(
+            self.output,
+            "    /** Returns true if every flag of [other] is set. */"
+        )
+        .unwrap();
+        writeln!(
+            self.output,
+            "    operator fun contains(other: {name}): Boolean = (bits and other.bits) == other.bits"
+        )
+        .unwrap();
+        for (method, doc) in [("or", "either"), ("and", "both")] {
+            writeln!(self.output, "\n    /** Returns the flags set in {doc}. */").unwrap();
+            writeln!(
+                self.output,
+                "    infix fun {method}(other: {name}): {name} = {name}(bits {method} other.bits)"
+            )
+            .unwrap();
+        }
+        writeln!(self.output, "\n    companion object {{").unwrap();
+        self.write_size_constant(name, e.underlying_type.get_byte_size());
+        writeln!(self.output).unwrap();
+        for (variant, value) in e.variants.iter().zip(e.variant_values()) {
+            let value = literal(&e.underlying_type, value);
+            writeln!(
+                self.output,
+                "        val {}: {name} = {name}({value})",
+                variant.name
+            )
+            .unwrap();
+        }
+        writeln!(self.output, "    }}\n}}").unwrap();
+    }
+
+    fn write_size_constant(&mut self, name: &str, size: usize) {
+        writeln!(self.output, "        /** The size of {name} in bytes. */").unwrap();
+        writeln!(self.output, "        const val SIZE_BYTES: Int = {size}").unwrap();
+    }
+
+    fn write_view(&mut self, layout: &Layout, hir: &Hir) -> Result<(), CompileError> {
+        let name = layout.name();
+        writeln!(
+            self.output,
+            "\n/** {name}, viewed in [buffer] from its index 0. */"
+        )
+        .unwrap();
+        self.write_deprecated("", layout.definition.deprecated());
+        writeln!(self.output, "@JvmInline").unwrap();
+        writeln!(self.output, "value class {name}(val buffer: ByteBuffer) {{").unwrap();
+        let start = self.output.len();
+        for slot in &layout.slots {
+            if slot.is_bit_field_container() && slot.size > 8 {
+                return Err(Diagnostic::error(format!(
+                    "the bit-fields from '{}' in '{name}' share a {}-byte container",
+                    slot.first().name,
+                    slot.size
+                ))
+                .with_code(codes::CODEGEN)
+                .with_help("the Kotlin target reads bit-fields from containers of up to 8 bytes"));
+            }
+            for field in &slot.fields {
+                if let Some(condition) = &field.condition {
+                    self.write_presence(field, condition, layout, hir.module);
+                }
+                self.write_field(field, slot, hir);
+            }
+        }
+        // Drop the blank line before the first property, which follows the class header.
+        if self.output[start..].starts_with('\n') {
+            self.output.remove(start);
+        }
+        if self.output.len() > start {
+            writeln!(self.output).unwrap();
+        }
+
+        writeln!(self.output, "    companion object {{").unwrap();
+        self.write_size_constant(name.as_str(), layout.size);
+        if let Definition::Message(m) = layout.definition
+            && let Some(id) = m.id
+        {
+            writeln!(
+                self.output,
+                "\n        /** The ID of the message, which precedes it on the wire. */"
+            )
+            .unwrap();
+            writeln!(self.output, "        const val ID: Int = {id}").unwrap();
+        }
+        for field in layout.fields() {
+            let doc = match field.bit_field_size {
+                Some(_) => "byte offset of the bit-field container holding",
+                None => "byte offset of",
+            };
+            writeln!(self.output, "\n        /** The {doc} {}. */", field.name).unwrap();
+            writeln!(
+                self.output,
+                "        const val {}: Int = {}",
+                offset_constant(field),
+                field.offset
+            )
+            .unwrap();
+        }
+        writeln!(
+            self.output,
+            "\n        /** Returns a view of {name} from the position of [buffer]. */"
+        )
+        .unwrap();
+        writeln!(
+            self.output,
+            "        fun wrap(buffer: ByteBuffer): {name} = {name}(buffer.view(buffer.position(), SIZE_BYTES))"
+        )
+        .unwrap();
+        writeln!(
+            self.output,
+            "\n        /** Returns a view of {name} in a new, zeroed buffer. */"
+        )
+        .unwrap();
+        writeln!(
+            self.output,
+            "        fun allocate(): {name} = {name}(ByteBuffer.allocate(SIZE_BYTES))"
+        )
+        .unwrap();
+        writeln!(self.output, "    }}\n}}").unwrap();
+        Ok(())
+    }
+
+    /// Writes `has{Name}`, which evaluates the condition of a conditional field.
+    fn write_presence(
+        &mut self,
+        field: &FieldLayout,
+        condition: &Condition,
+        layout: &Layout,
+        module: &OnyxModule,
+    ) {
+        let Some(target) = layout.field(condition.field.as_str()) else {
+            return;
+        };
+        let size = container_size(layout, target);
+        let raw = condition.raw_value(module).unwrap_or_default() & mask(target.bits);
+        let read = read_bits(
+            &offset_constant(target),
+            size,
+            target.bit_offset,
+            target.bits,
+        );
+        let op = if condition.negated { "!=" } else { "==" };
+        writeln!(
+            self.output,
+            "\n    /** True if {} is present: `{condition}`. */",
+            field.name
+        )
+        .unwrap();
+        self.write_deprecated("    ", field.deprecated.as_deref());
+        writeln!(
+            self.output,
+            "    val has{}: Boolean\n        get() = {read} {op} {}",
+            capitalize(field.name.as_str()),
+            long_literal(raw as i64)
+        )
+        .unwrap();
+    }
+
+    fn write_field(&mut self, field: &FieldLayout, slot: &Slot, hir: &Hir) {
+        let name = field.name.as_str();
+        let at = offset_constant(field);
+        let deprecated = field.deprecated.as_deref();
+        let doc = format!("Accessor for {name}.");
+        match field.ty {
+            TypeRef::Primitive(p) => {
+                let access =
+                    primitive_access(&p, &at, slot.size, field.bit_offset, field.bits, "value");
+                self.write_property(name, &doc, &access, deprecated);
+            }
+            TypeRef::Fixed { base, scale } => {
+                let access =
+                    primitive_access(&base, &at, slot.size, field.bit_offset, field.bits, "value");
+                let raw = format!("{name}Raw");
+                let raw_doc = format!("The raw value of {name}, in units of 1/{scale}.");
+                self.write_property(&raw, &raw_doc, &access, deprecated);
+                let fixed = Access {
+                    ty: "Double".to_string(),
+                    get: format!("{raw}.toDouble() / {scale}.0"),
+                    set: Some(format!("{raw} = {}", to_fixed(&base, scale, "value"))),
+                };
+                self.write_property(name, &doc, &fixed, deprecated);
+            }
+            TypeRef::Enum(e) if !e.flags => {
+                let access = value_access(&field.ty, &at, "value");
+                let raw = format!("{name}Raw");
+                let raw_doc = format!("The raw value of {name}, which may name no variant.");
+                self.write_property(&raw, &raw_doc, &access, deprecated);
+                let typed = Access {
+                    ty: format!("{}?", e.name),
+                    get: format!("{}.fromValue({raw})", e.name),
+                    set: None,
+                };
+                let doc = format!("Accessor for {name}, or null if it names no variant.");
+                self.write_property(name, &doc, &typed, deprecated);
+            }
+            TypeRef::Vector {
+                element,
+                capacity,
+                length,
+            } => {
+                let count = format!("{name}Count");
+                let length_size = length.get_byte_size();
+                let access = Access {
+                    ty: "Int".to_string(),
+                    get: format!("buffer.readBits({at}, {length_size}).toInt()"),
+                    set: Some(format!(
+                        "require(value in 0..{capacity}) {{ \"{name} holds at most {capacity} elements\" }}\n\
+                         buffer.writeBits({at}, {length_size}, value.toLong())"
+                    )),
+                };
+                let count_doc = format!("The number of elements of {name} in use.");
+                self.write_property(&count, &count_doc, &access, deprecated);
+
+                let element_size = hir.size_of(element).unwrap_or_default();
+                let at =
+                    format!("{at} + {length_size} + {element_size} * checkIndex(index, {count})");
+                let Some(element) = hir.resolve(element) else {
+                    return;
+                };
+                let what = format!("element [index] of {name}");
+                let raw_what = format!("the raw value of element [index] of {name}");
+                match element {
+                    TypeRef::Fixed { base, scale } => {
+                        let raw = format!("{name}Raw");
+                        let access = value_access(&element, &at, "value");
+                        self.write_element(&raw, &raw_what, &access, deprecated);
+                        let fixed = Access {
+                            ty: "Double".to_string(),
+                            get: format!("{raw}(index).toDouble() / {scale}.0"),
+                            set: Some(format!(
+                                "set{}(index, {})",
+                                capitalize(&raw),
+                                to_fixed(&base, scale, "value")
+                            )),
+                        };
+                        self.write_element(name, &what, &fixed, deprecated);
+                    }
+                    TypeRef::Enum(e) if !e.flags => {
+                        let raw = format!("{name}Raw");
+                        let access = value_access(&element, &at, "value");
+                        self.write_element(&raw, &raw_what, &access, deprecated);
+                        let typed = Access {
+                            ty: format!("{}?", e.name),
+                            get: format!("{}.fromValue({raw}(index))", e.name),
+                            set: None,
+                        };
+                        self.write_element(name, &what, &typed, deprecated);
+                    }
+                    _ => {
+                        let access = value_access(&element, &at, "value");
+                        self.write_element(name, &what, &access, deprecated);
+                    }
+                }
+            }
+            _ => {
+                let access = value_access(&field.ty, &at, "value");
+                self.write_property(name, &doc, &access, deprecated);
+            }
+        }
+    }
+
+    fn write_property(&mut self, name: &str, doc: &str, access: &Access, deprecated: Option<&str>) {
+        writeln!(self.output, "\n    /** {doc} */").unwrap();
+        self.write_deprecated("    ", deprecated);
+        let kind = if access.set.is_some() { "var" } else { "val" };
+        writeln!(self.output, "    {kind} {name}: {}", access.ty).unwrap();
+        writeln!(self.output, "        get() = {}", access.get).unwrap();
+        if let Some(set) = &access.set {
+            writeln!(self.output, "        set(value) {{").unwrap();
+            for line in set.lines() {
+                writeln!(self.output, "            {line}").unwrap();
+            }
+            writeln!(self.output, "        }}").unwrap();
+        }
+    }
+
+    /// Writes the functions reading and writing element `index` of a vector, described by
+    /// `what` in their docs.
+    fn write_element(&mut self, name: &str, what: &str, access: &Access, deprecated: Option<&str>) {
+        writeln!(self.output, "\n    /** Returns {what}. */").unwrap();
+        self.write_deprecated("    ", deprecated);
+        writeln!(
+            self.output,
+            "    fun {name}(index: Int): {} = {}",
+            access.ty, access.get
+        )
+        .unwrap();
+        if let Some(set) = &access.set {
+            writeln!(self.output, "\n    /** Sets {what}. */").unwrap();
+            self.write_deprecated("    ", deprecated);
+            writeln!(
+                self.output,
+                "    fun set{}(index: Int, value: {}) {{",
+                capitalize(name),
+                access.ty
+            )
+            .unwrap();
+            for line in set.lines() {
+                writeln!(self.output, "        {line}").unwrap();
+            }
+            writeln!(self.output, "    }}").unwrap();
+        }
+    }
+}
+
+impl CodeGenerator for KotlinGenerator {
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(level = "info", name = "KotlinGenerator::generate", skip_all, fields(path = %self.file_path.display()))
+    )]
+    fn generate(&mut self, module: &OnyxModule) -> Result<Vec<(PathBuf, String)>, CompileError> {
+        let banner = self.banner.lines(module)?;
+        check_order(module)?;
+        let named = apply_field_naming(module, self.field_naming)?;
+        let module = mangle_reserved(&named, RESERVED)?;
+        let module = module.as_ref();
+        let hir = hir::lower(module)?;
+        self.output.clear();
+        self.write_header(module, &banner);
+
+        for name in &module.order {
+            match module.definitions.get(name) {
+                Some(Definition::Enum(e)) => self.write_enum(e),
+                Some(Definition::Struct(_) | Definition::Message(_)) => {
+                    self.write_view(hir.layout(*name).unwrap(), &hir)?;
+                }
+                None => {}
+            }
+        }
+
+        let mut output_path = self.file_path.clone();
+        output_path.set_extension("kt");
+        Ok(vec![(output_path, self.output.clone())])
+    }
+
+    fn supports(&self, feature: Feature) -> bool {
+        // Views read the latest layout only, and messages get an `ID` but no dispatch.
+        !matches!(feature, Feature::Versions | Feature::MessageIds)
+    }
+}
+
+/// Returns the Kotlin type of a primitive.
+fn kotlin_type(p: &PrimitiveType) -> &'static str {
+    match p {
+        PrimitiveType::Bool => "Boolean",
+        PrimitiveType::U8 => "UByte",
+        PrimitiveType::U16 => "UShort",
+        PrimitiveType::U32 => "UInt",
+        PrimitiveType::U64 => "ULong",
+        PrimitiveType::I8 => "Byte",
+        PrimitiveType::I16 => "Short",
+        PrimitiveType::I32 => "Int",
+        PrimitiveType::I64 => "Long",
+        PrimitiveType::F32 => "Float",
+        PrimitiveType::F64 => "Double",
+    }
+}
+
+/// Returns the Kotlin literal of an enum value of `p`.
+fn literal(p: &PrimitiveType, value: u64) -> String {
+    match p {
+        PrimitiveType::I64 => format!("{value}L"),
+        _ if p.is_signed() => value.to_string(),
+        _ => format!("{value}u"),
+    }
+}
+
+/// Returns the Kotlin literal of a `Long`.
+fn long_literal(value: i64) -> String {
+    match value {
+        // The literal of its magnitude does not fit in a `Long`.
+        i64::MIN => "Long.MIN_VALUE".to_string(),
+        _ => format!("{value}L"),
+    }
+}
+
+/// Returns a Kotlin string literal.
+fn string(text: &str) -> String {
+    json::string(text).replace('$', "\\$")
+}
+
+/// Returns the mask of the low `bits` bits.
+fn mask(bits: usize) -> u64 {
+    u64::MAX >> (64 - bits.clamp(1, 64))
+}
+
+/// Returns the `Long` literal of the mask of the low `bits` bits.
+fn mask_literal(bits: usize) -> String {
+    match bits {
+        64.. => "-1L".to_string(),
+        _ => format!("0x{:X}L", mask(bits)),
+    }
+}
+
+/// Returns the name of the constant holding the offset of `field`.
+fn offset_constant(field: &FieldLayout) -> String {
+    let name = NamingConvention::ScreamingSnakeCase.apply(field.name.as_str());
+    format!("{name}_OFFSET")
+}
+
+/// Returns the size in bytes of the slot holding `field`.
+fn container_size(layout: &Layout, field: &FieldLayout) -> usize {
+    layout
+        .slots
+        .iter()
+        .find(|slot| slot.fields.iter().any(|f| f.name == field.name))
+        .map_or(0, |slot| slot.size)
+}
+
+fn capitalize(name: &str) -> String {
+    let mut chars = name.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+/// Returns an expression reading the `bits` bits from `shift` of the `size`-byte container at
+/// `at`, as an unsigned `Long`.
+fn read_bits(at: &str, size: usize, shift: usize, bits: usize) -> String {
+    let read = format!("buffer.readBits({at}, {size})");
+    if shift == 0 && bits == size * 8 {
+        return read;
+    }
+    format!("({} and {})", shift_right(&read, shift), mask_literal(bits))
+}
+
+fn shift_right(value: &str, shift: usize) -> String {
+    match shift {
+        0 => value.to_string(),
+        _ => format!("({value} ushr {shift})"),
+    }
+}
+
+fn shift_left(value: &str, shift: usize) -> String {
+    match shift {
+        0 => value.to_string(),
+        _ => format!("({value} shl {shift})"),
+    }
+}
+
+/// Returns an expression converting the `Long` `raw` to a value of `p`.
+fn from_long(p: &PrimitiveType, raw: &str) -> String {
+    match p {
+        PrimitiveType::Bool => format!("{raw} != 0L"),
+        PrimitiveType::U8 => format!("{raw}.toUByte()"),
+        PrimitiveType::U16 => format!("{raw}.toUShort()"),
+        PrimitiveType::U32 => format!("{raw}.toUInt()"),
+        PrimitiveType::U64 => format!("{raw}.toULong()"),
+        PrimitiveType::I8 => format!("{raw}.toByte()"),
+        PrimitiveType::I16 => format!("{raw}.toShort()"),
+        PrimitiveType::I32 => format!("{raw}.toInt()"),
+        PrimitiveType::I64 => raw.to_string(),
+        PrimitiveType::F32 => format!("Float.fromBits({raw}.toInt())"),
+        PrimitiveType::F64 => format!("Double.fromBits({raw})"),
+    }
+}
+
+/// Returns an expression converting `value`, a value of `p`, to its bits in a `Long`.
+fn to_long(p: &PrimitiveType, value: &str) -> String {
+    match p {
+        PrimitiveType::Bool => format!("(if ({value}) 1L else 0L)"),
+        PrimitiveType::I64 => value.to_string(),
+        PrimitiveType::F32 => format!("{value}.toRawBits().toLong()"),
+        PrimitiveType::F64 => format!("{value}.toRawBits()"),
+        _ => format!("{value}.toLong()"),
+    }
+}
+
+/// Returns an expression converting the `Double` `value` to the raw `base` of a fixed-point
+/// number with `scale`, rounding to the nearest unit.
+fn to_fixed(base: &PrimitiveType, scale: u64, value: &str) -> String {
+    from_long(base, &format!("({value} * {scale}.0).roundToLong()"))
+}
+
+/// Returns the access to a value of `p` held in the `bits` bits from `shift` of the
+/// `size`-byte container at `at`, written from `value`.
+fn primitive_access(
+    p: &PrimitiveType,
+    at: &str,
+    size: usize,
+    shift: usize,
+    bits: usize,
+    value: &str,
+) -> Access {
+    let mut raw = read_bits(at, size, shift, bits);
+    if p.is_signed() && bits < p.get_bit_width() {
+        // Sign-extend from the top bit of the field.
+        let unused = 64 - bits;
+        raw = format!("(({raw} shl {unused}) shr {unused})");
+    }
+    let set = if shift == 0 && bits == size * 8 {
+        format!("buffer.writeBits({at}, {size}, {})", to_long(p, value))
+    } else {
+        let mask = mask_literal(bits);
+        let kept = format!("{}.inv()", shift_left(&mask, shift));
+        let field = format!("({} and {mask})", to_long(p, value));
+        format!(
+            "buffer.writeBits({at}, {size}, (buffer.readBits({at}, {size}) and {kept}) or {})",
+            shift_left(&field, shift)
+        )
+    };
+    Access {
+        ty: kotlin_type(p).to_string(),
+        get: from_long(p, &raw),
+        set: Some(set),
+    }
+}
+
+/// Returns the access to a whole value of type `ty` at `at`, written from `value`. Enums and
+/// fixed-point numbers are accessed as their raw integers, except flags, which are wrapped.
+fn value_access(ty: &TypeRef, at: &str, value: &str) -> Access {
+    let whole = |p: &PrimitiveType, value: &str| {
+        primitive_access(p, at, p.get_byte_size(), 0, p.get_bit_width(), value)
+    };
+    match ty {
+        TypeRef::Primitive(p) | TypeRef::Fixed { base: p, .. } => whole(p, value),
+        TypeRef::Enum(e) if e.flags => {
+            let bits = whole(&e.underlying_type, &format!("{value}.bits"));
+            Access {
+                ty: e.name.to_string(),
+                get: format!("{}({})", e.name, bits.get),
+                set: bits.set,
+            }
+        }
+        TypeRef::Enum(e) => whole(&e.underlying_type, value),
+        TypeRef::Uuid | TypeRef::Bytes(_) => {
+            let size = match ty {
+                TypeRef::Bytes(size) => *size,
+                _ => 16,
+            };
+            Access {
+                ty: "ByteArray".to_string(),
+                get: format!("buffer.readBytes({at}, {size})"),
+                set: Some(format!("buffer.writeBytes({at}, {size}, {value})")),
+            }
+        }
+        TypeRef::Struct(s) => view_access(s.name.as_str(), at),
+        TypeRef::Message(m) => view_access(m.name.as_str(), at),
+        TypeRef::Vector { .. } => unreachable!("vectors are never nested"),
+    }
+}
+
+fn view_access(name: &str, at: &str) -> Access {
+    Access {
+        ty: name.to_string(),
+        get: format!("{name}(buffer.view({at}, {name}.SIZE_BYTES))"),
+        set: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn generate(source: &str) -> String {
+        let module = crate::parser::Parser::new(source)
+            .unwrap()
+            .parse_module()
+            .unwrap();
+        let mut generator = KotlinGenerator::default();
+        generator
+            .add_file_path(PathBuf::from("out/schema"))
+            .unwrap();
+        let files = generator.generate(&module).unwrap();
+        assert_eq!(files[0].0, PathBuf::from("out/schema.kt"));
+        files[0].1.clone()
+    }
+
+    #[test]
+    fn test_view_accessors() {
+        let output = generate(
+            "endian = little\n\
+             message Reading { id u16, level i8 : 4, on bool : 1, mode u8 : 3, temp fixed<i16, 100>, }",
+        );
+        assert!(output.contains("private val BYTE_ORDER: ByteOrder = ByteOrder.LITTLE_ENDIAN"));
+        assert!(output.contains("@JvmInline\nvalue class Reading(val buffer: ByteBuffer) {"));
+        assert!(output.contains(
+            "    var id: UShort\n        get() = buffer.readBits(ID_OFFSET, 2).toUShort()\n"
+        ));
+        // Signed bit-fields are sign-extended from their top bit.
+        assert!(output.contains(
+            "get() = (((buffer.readBits(LEVEL_OFFSET, 1) and 0xFL) shl 60) shr 60).toByte()"
+        ));
+        assert!(output.contains(
+            "buffer.writeBits(MODE_OFFSET, 1, (buffer.readBits(MODE_OFFSET, 1) and (0x7L shl 5).inv()) or ((value.toLong() and 0x7L) shl 5))"
+        ));
+        assert!(output.contains(
+            "set(value) {\n            tempRaw = (value * 100.0).roundToLong().toShort()\n"
+        ));
+        assert!(output.contains("const val SIZE_BYTES: Int = 5"));
+        assert!(output.contains("const val TEMP_OFFSET: Int = 3"));
+    }
+
+    #[test]
+    fn test_enums_and_flags() {
+        let output = generate(
+            "enum Kind : i8 { Low = 1, High, }\n\
+             flags Access : u8 { Read, Write, }\n\
+             struct S { kind Kind, access Access, kinds vec<Kind, 2>, }",
+        );
+        assert!(output.contains("enum class Kind(val value: Byte) {\n    Low(1),\n    High(2),\n"));
+        assert!(output.contains("        val Write: Access = Access(2u)"));
+        assert!(output.contains("    val kind: Kind?\n        get() = Kind.fromValue(kindRaw)"));
+        assert!(output.contains(
+            "    var access: Access\n        get() = Access(buffer.readBits(ACCESS_OFFSET, 1).toUByte())"
+        ));
+        assert!(output.contains(
+            "    fun kindsRaw(index: Int): Byte = buffer.readBits(KINDS_OFFSET + 1 + 1 * checkIndex(index, kindsCount), 1).toByte()"
+        ));
+        assert!(
+            output.contains("    fun kinds(index: Int): Kind? = Kind.fromValue(kindsRaw(index))")
+        );
+    }
+
+    #[test]
+    fn test_package() {
+        let mut generator = KotlinGenerator::default();
+        assert!(generator.set_package("com.example.protocol").is_ok());
+        for invalid in ["", "com..example", "com.1st", "com.package"] {
+            let error = generator.set_package(invalid).unwrap_err();
+            assert_eq!(
+                error.message(),
+                format!("invalid Kotlin package name '{invalid}'")
+            );
+        }
+    }
+}
@@ -0,0 +1,305 @@
+//! Renaming schema names before code generation.
+//!
+//! A schema may name a field `class` or an enum `match` (using raw identifiers for Onyx's own
+//! keywords, e.g. `` `struct` ``). Before generating code, each generator appends `_` to every
+//! definition, field, and enum variant name in its list of reserved words, after converting
+//! field names to its [`NamingConvention`].
+
+use std::{borrow::Cow, collections::HashMap};
+
+use crate::{
+    ast::{ConditionValue, Definition, OnyxModule, Type},
+    diagnostic::{Diagnostic, codes},
+    span::Span,
+    symbol::Symbol,
+};
+
+use super::{CompileError, naming::NamingConvention};
+
+/// How each kind of name is renamed; `None` keeps a name.
+struct Renames<'r> {
+    definition: &'r dyn Fn(&str) -> Option<String>,
+    field: &'r dyn Fn(&str) -> Option<String>,
+    variant: &'r dyn Fn(&str) -> Option<String>,
+    /// Why names are renamed, for collision errors: "to follow ...".
+    reason: &'r str,
+}
+
+/// Returns the module with every reserved definition, field, and enum variant name suffixed
+/// with `_`, or the module itself when no name is reserved.
+///
+/// Fails when a renamed item would take the name of another item in the same scope, such as a
+/// variant `type` next to a variant `type_` when generating Rust.
+pub(crate) fn mangle_reserved<'a>(
+    module: &'a OnyxModule,
+    reserved: &[&str],
+) -> Result<Cow<'a, OnyxModule>, CompileError> {
+    let mangle = |name: &str| reserved.contains(&name).then(|| format!("{name}_"));
+    let renames = Renames {
+        definition: &mangle,
+        field: &mangle,
+        variant: &mangle,
+        reason: "to avoid a reserved word of the target language",
+    };
+    rename_module(module, &renames)
+}
+
+/// Returns the module with every field name written in `convention`, or the module itself when
+/// no name changes.
+///
+/// Fails when two fields of a definition would get the same name, such as `HTTPServer` and
+/// `http_server` in snake case.
+pub(crate) fn apply_field_naming(
+    module: &OnyxModule,
+    convention: NamingConvention,
+) -> Result<Cow<'_, OnyxModule>, CompileError> {
+    let convert = |name: &str| Some(convention.apply(name)).filter(|converted| converted != name);
+    let keep = |_: &str| None;
+    let reason = format!("to follow the {} naming convention", convention.name());
+    let renames = Renames {
+        definition: &keep,
+        field: &convert,
+        variant: &keep,
+        reason: &reason,
+    };
+    rename_module(module, &renames)
+}
+
+/// Renames one module and each of its earlier layouts.
+fn rename_module<'a>(
+    module: &'a OnyxModule,
+    renames: &Renames,
+) -> Result<Cow<'a, OnyxModule>, CompileError> {
+    if !std::iter::once(module)
+        .chain(&module.history)
+        .any(|layout| renames_any(layout, renames))
+    {
+        return Ok(Cow::Borrowed(module));
+    }
+    let mut result = rename_layout(module, renames)?;
+    result.history = module
+        .history
+        .iter()
+        .map(|old| rename_layout(old, renames))
+        .collect::<Result<_, _>>()?;
+    Ok(Cow::Owned(result))
+}
+
+/// Returns true if any definition, field, or variant of one layout is renamed.
+fn renames_any(module: &OnyxModule, renames: &Renames) -> bool {
+    module.definitions.values().any(|def| {
+        (renames.definition)(def.name()).is_some()
+            || match def {
+                Definition::Message(m) => m
+                    .fields
+                    .iter()
+                    .any(|f| (renames.field)(f.name.as_str()).is_some()),
+                Definition::Struct(s) => s
+                    .fields
+                    .iter()
+                    .any(|f| (renames.field)(f.name.as_str()).is_some()),
+                Definition::Enum(e) => e
+                    .variants
+                    .iter()
+                    .any(|v| (renames.variant)(&v.name).is_some()),
+            }
+    })
+}
+
+/// Renames the names of one layout of a module.
+fn rename_layout(module: &OnyxModule, renames: &Renames) -> Result<OnyxModule, CompileError> {
+    let rename = |rule: &dyn Fn(&str) -> Option<String>, name: Symbol| -> Symbol {
+        rule(name.as_str()).map_or(name, |renamed| Symbol::intern(&renamed))
+    };
+    let definition = |name: Symbol| rename(renames.definition, name);
+    let field = |name: Symbol| rename(renames.field, name);
+
+    let mut definitions: Vec<&Definition> = module.definitions.values().collect();
+    definitions.sort_by_key(|def| def.span().start);
+    let mut scope = Scope::new("type", renames.reason);
+    for def in &definitions {
+        scope.insert(def.name(), definition(def.symbol()).as_str(), def.span())?;
+    }
+
+    let mut result = OnyxModule {
+        endianness: module.endianness,
+        order: module.order.iter().map(|&id| definition(id)).collect(),
+        version: module.version,
+        strict: module.strict,
+        packed_bools: module.packed_bools,
+        alignment: module.alignment,
+        ..OnyxModule::default()
+    };
+    for def in definitions {
+        let name = definition(def.symbol());
+        let mut def = def.clone();
+        let def_fields = match &mut def {
+            Definition::Message(m) => {
+                m.name = name;
+                &mut m.fields
+            }
+            Definition::Struct(s) => {
+                s.name = name;
+                &mut s.fields
+            }
+            Definition::Enum(e) => {
+                e.name = name;
+                let mut scope = Scope::new("variant", renames.reason);
+                for variant in &mut e.variants {
+                    let renamed = (renames.variant)(&variant.name);
+                    let renamed = renamed.unwrap_or_else(|| variant.name.clone());
+                    scope.insert(&variant.name, &renamed, variant.span)?;
+                    variant.name = renamed;
+                }
+                result.definitions.insert(name, def);
+                continue;
+            }
+        };
+        let mut scope = Scope::new("field", renames.reason);
+        for f in def_fields.iter_mut() {
+            let renamed = field(f.name);
+            scope.insert(f.name.as_str(), renamed.as_str(), f.span)?;
+            f.name = renamed;
+            let type_info = match &mut f.type_info {
+                Type::Vector { element, .. } => element.as_mut(),
+                other => other,
+            };
+            if let Type::Custom(name) = type_info {
+                *name = definition(*name);
+            }
+            if let Some(condition) = &mut f.condition {
+                condition.field = field(condition.field);
+                if let ConditionValue::Variant { enum_name, variant } = &mut condition.value {
+                    *enum_name = definition(*enum_name);
+                    if let Some(renamed) = (renames.variant)(variant) {
+                        *variant = renamed;
+                    }
+                }
+            }
+        }
+        result.definitions.insert(name, def);
+    }
+    Ok(result)
+}
+
+/// The names given to the items of one scope, such as the fields of a struct, for reporting
+/// items that end up with the same name.
+struct Scope<'r> {
+    what: &'static str,
+    reason: &'r str,
+    /// The original name and span of the item given each name.
+    names: HashMap<String, (String, Span)>,
+}
+
+impl<'r> Scope<'r> {
+    fn new(what: &'static str, reason: &'r str) -> Self {
+        Scope {
+            what,
+            reason,
+            names: HashMap::new(),
+        }
+    }
+
+    /// Records that the item `original` at `span` is named `renamed`, failing if another item
+    /// already has that name.
+    fn insert(&mut self, original: &str, renamed: &str, span: Span) -> Result<(), CompileError> {
+        let Some((first, first_span)) = self.names.get(renamed) else {
+            self.names
+                .insert(renamed.to_string(), (original.to_string(), span));
+            return Ok(());
+        };
+        // Blame the item that was renamed into the other's name.
+        let (original, span) = match original == renamed {
+            true => (first.as_str(), *first_span),
+            false => (original, span),
+        };
+        let what = self.what;
+        Err(Diagnostic::error(format!(
+            "{what} '{original}' cannot be renamed to '{renamed}' {}, as another {what} \
+             already has that name",
+            self.reason
+        ))
+        .with_code(codes::NAME_COLLISION)
+        .with_span(span)
+        .with_help(format!("rename the {what} '{original}' in the schema")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{diagnostic::ErrorKind, parser::Parser};
+
+    const RESERVED: &[&str] = &["class", "match", "struct"];
+
+    #[test]
+    fn test_mangle_reserved() {
+        let source = "enum `match` : u8 { class, Other, }\n\
+                      struct `struct` { kind `match`, class u8 if kind == `match`.class, }\n\
+                      message Event { items vec<`struct`, 2>, }";
+        let module = Parser::new(source).unwrap().parse_module().unwrap();
+        let mangled = mangle_reserved(&module, RESERVED).unwrap();
+
+        assert_eq!(
+            mangled.to_idl(),
+            "endian = little\n\
+             \n\
+             enum match_ : u8 {\n    class_,\n    Other,\n}\n\
+             \n\
+             struct struct_ {\n    kind match_,\n    class_ u8 if kind == match_.class_,\n}\n\
+             \n\
+             message Event {\n    items vec<struct_, 2>,\n}\n"
+        );
+        let order: Vec<&str> = mangled.order.iter().map(|id| id.as_str()).collect();
+        assert_eq!(order, ["match_", "struct_", "Event"]);
+
+        let plain = Parser::new("struct A { b u8, }")
+            .unwrap()
+            .parse_module()
+            .unwrap();
+        assert!(matches!(
+            mangle_reserved(&plain, RESERVED),
+            Ok(Cow::Borrowed(_))
+        ));
+    }
+
+    #[test]
+    fn test_apply_field_naming() {
+        let source = "struct A { startTime u32, HTTPCode u16 if startTime == 1, }";
+        let module = Parser::new(source).unwrap().parse_module().unwrap();
+        let named = apply_field_naming(&module, NamingConvention::SnakeCase).unwrap();
+        assert_eq!(
+            named.to_idl(),
+            "endian = little\n\nstruct A {\n    start_time u32,\n    http_code u16 if start_time == 1,\n}\n"
+        );
+        assert!(matches!(
+            apply_field_naming(&module, NamingConvention::Preserve),
+            Ok(Cow::Borrowed(_))
+        ));
+
+        let source = "struct A { http_server u8, HTTPServer u8, }";
+        let module = Parser::new(source).unwrap().parse_module().unwrap();
+        let err = apply_field_naming(&module, NamingConvention::SnakeCase).unwrap_err();
+        assert_eq!(err.code(), Some(codes::NAME_COLLISION));
+        assert_eq!(
+            err.message(),
+            "field 'HTTPServer' cannot be renamed to 'http_server' to follow the snake naming \
+             convention, as another field already has that name"
+        );
+    }
+
+    #[test]
+    fn test_mangle_collision() {
+        let source = "enum A : u8 { class, class_, }";
+        let module = Parser::new(source).unwrap().parse_module().unwrap();
+        let err = mangle_reserved(&module, RESERVED).unwrap_err();
+        assert_eq!(err.code(), Some(codes::NAME_COLLISION));
+        assert_eq!(err.kind(), ErrorKind::Codegen);
+        assert_eq!(
+            err.message(),
+            "variant 'class' cannot be renamed to 'class_' to avoid a reserved word of the target \
+             language, as another variant already has that name"
+        );
+        assert_eq!(err.span(), Some(Span::new(14, 19)));
+    }
+}
@@ -0,0 +1,400 @@
+use std::{fmt::Write, path::PathBuf};
+
+use crate::{
+    ast::{Definition, EnumDef, OnyxModule, PrimitiveType},
+    diagnostic::{Diagnostic, codes},
+    features::Feature,
+    generators::{
+        Banner, CodeGenerator, CompileError, check_order, comment_lines, mangle::mangle_reserved,
+    },
+    hir::{self, FieldLayout, Hir, Layout, TypeRef},
+};
+
+/// MATLAB keywords, which schema names are renamed away from with a trailing `_`.
+const RESERVED: &[&str] = &[
+    "break",
+    "case",
+    "catch",
+    "classdef",
+    "continue",
+    "else",
+    "elseif",
+    "end",
+    "for",
+    "function",
+    "global",
+    "if",
+    "otherwise",
+    "parfor",
+    "persistent",
+    "return",
+    "spmd",
+    "switch",
+    "try",
+    "while",
+];
+
+/// The local function that every bus element is created with.
+const ELEMENT_FUNCTION: &str = "
+function e = element(name, type, dimensions, description)
+% Returns a bus element.
+e = Simulink.BusElement;
+e.Name = name;
+e.DataType = type;
+e.Dimensions = dimensions;
+e.Description = description;
+end
+";
+
+#[derive(Debug, Default)]
+/// The MATLAB code generator.
+///
+/// Generates a MATLAB function file (.m) that defines the Simulink types of a schema, so
+/// that simulation models consume telemetry with the layout the schema gives it. Running it
+/// defines each enum with `Simulink.defineIntEnumType` and assigns a `Simulink.Bus` per
+/// struct and message in the base workspace, named after it.
+///
+/// Bus elements describe values rather than bytes: a bit-field is a `fixdt` integer of its
+/// width, a fixed-point number a `fixdt` with its scale as slope, and a vector two elements,
+/// its `_count` and an array of its capacity. Each element's description gives its place on
+/// the wire. The function is named after the output file, with characters MATLAB does not
+/// allow in names replaced by `_`, as the file name must match it.
+pub struct MatlabGenerator {
+    output: String,
+    file_path: PathBuf,
+    function: String,
+    banner: Banner,
+}
+
+impl MatlabGenerator {
+    /// Adds a file path to the generator configuration.
+    pub fn add_file_path(&mut self, file_path: PathBuf) -> Result<(), CompileError> {
+        let Some(stem) = file_path.file_stem() else {
+            return Err(Diagnostic::error(format!(
+                "output path '{}' has no file name",
+                file_path.display()
+            ))
+            .with_code(codes::CODEGEN));
+        };
+        let mut function: String = stem
+            .to_string_lossy()
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+            .collect();
+        if !function.starts_with(|c: char| c.is_ascii_alphabetic()) {
+            function.insert_str(0, "onyx_");
+        }
+        self.function = function;
+        self.file_path = file_path;
+        Ok(())
+    }
+
+    /// Opens the file with `banner` rather than the default one.
+    pub fn set_banner(&mut self, banner: Banner) {
+        self.banner = banner;
+    }
+
+    fn write_enum(&mut self, e: &EnumDef) {
+        let kind = if e.flags { "a set of flags " } else { "" };
+        let description = format!(
+            "{}, {kind}carried on the wire as {}.",
+            e.name, e.underlying_type
+        );
+        writeln!(self.output, "\n% {description}").unwrap();
+        if !is_simulink_enum(e) {
+            let reason = if e.flags {
+                "Simulink enum values do not combine"
+            } else if e.open {
+                "it is open"
+            } else {
+                "Simulink enums are at most 32 bits"
+            };
+            writeln!(
+                self.output,
+                "% Carried as {} in buses, as {reason}.",
+                numeric_type(&e.underlying_type)
+            )
+            .unwrap();
+            return;
+        }
+        let names: Vec<String> = e.variants.iter().map(|v| string(&v.name)).collect();
+        let values: Vec<String> = e.variant_values().iter().map(u64::to_string).collect();
+        writeln!(
+            self.output,
+            "Simulink.defineIntEnumType({}, {{{}}}, [{}], ...",
+            string(e.name.as_str()),
+            names.join(", "),
+            values.join("; ")
+        )
+        .unwrap();
+        writeln!(
+            self.output,
+            "    'StorageType', {}, 'Description', {});",
+            string(numeric_type(&e.underlying_type)),
+            string(&deprecated(description, e.deprecated.as_deref()))
+        )
+        .unwrap();
+    }
+
+    fn write_bus(&mut self, layout: &Layout, hir: &Hir) {
+        let name = layout.name();
+        let mut description = format!("{name}: {} bytes.", layout.size);
+        if let Definition::Message(m) = layout.definition
+            && let Some(id) = m.id
+        {
+            write!(description, " Message ID {id}.").unwrap();
+        }
+        let description = deprecated(description, layout.definition.deprecated());
+        writeln!(self.output, "\n% {description}").unwrap();
+        writeln!(self.output, "elements = Simulink.BusElement.empty;").unwrap();
+        for field in layout.fields() {
+            self.write_elements(field, hir);
+        }
+        writeln!(self.output, "bus = Simulink.Bus;").unwrap();
+        writeln!(self.output, "bus.Description = {};", string(&description)).unwrap();
+        writeln!(self.output, "bus.Elements = elements;").unwrap();
+        writeln!(
+            self.output,
+            "assignin('base', {}, bus);",
+            string(name.as_str())
+        )
+        .unwrap();
+    }
+
+    /// Writes the bus elements of a field: one, or two for a vector.
+    fn write_elements(&mut self, field: &FieldLayout, hir: &Hir) {
+        let mut place = format!("byte {}", field.offset);
+        if field.bit_field_size.is_some() {
+            match field.bits {
+                // The parser rejects bit-fields without bits, but a module built in code can
+                // still hold one.
+                0 => {}
+                1 => write!(place, ", bit {}", field.bit_offset).unwrap(),
+                bits => {
+                    let last = field.bit_offset + bits - 1;
+                    write!(place, ", bits {}..{last}", field.bit_offset).unwrap();
+                }
+            }
+        }
+        if let Some(condition) = &field.condition {
+            write!(place, "; present if {condition}").unwrap();
+        }
+        let place = deprecated(place, field.deprecated.as_deref());
+        let name = field.name.as_str();
+        let bits = field.bit_field_size.map(|_| field.bits);
+        let (data_type, dimensions) = match field.ty {
+            TypeRef::Vector {
+                element,
+                capacity,
+                length,
+            } => {
+                self.write_element(
+                    &format!("{name}_count"),
+                    numeric_type(&length),
+                    1,
+                    &format!("{place}; the number of elements of {name} in use"),
+                );
+                let element = hir
+                    .resolve(element)
+                    .map(|element| data_type(&element, None))
+                    .unwrap_or_default();
+                let length_size = length.get_byte_size();
+                let place = format!("byte {}", field.offset + length_size);
+                return self.write_element(name, &element, capacity, &place);
+            }
+            TypeRef::Uuid => ("uint8".to_string(), 16),
+            TypeRef::Bytes(len) => ("uint8".to_string(), len),
+            ty => (data_type(&ty, bits), 1),
+        };
+        self.write_element(name, &data_type, dimensions, &place);
+    }
+
+    fn write_element(&mut self, name: &str, data_type: &str, dimensions: usize, place: &str) {
+        writeln!(
+            self.output,
+            "elements(end + 1) = element({}, {}, {dimensions}, {});",
+            string(name),
+            string(data_type),
+            string(place)
+        )
+        .unwrap();
+    }
+}
+
+impl CodeGenerator for MatlabGenerator {
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(level = "info", name = "MatlabGenerator::generate", skip_all, fields(path = %self.file_path.display()))
+    )]
+    fn generate(&mut self, module: &OnyxModule) -> Result<Vec<(PathBuf, String)>, CompileError> {
+        let banner = self.banner.lines(module)?;
+        check_order(module)?;
+        let module = mangle_reserved(module, RESERVED)?;
+        let module = module.as_ref();
+        let hir = hir::lower(module)?;
+
+        let function = self.function.clone();
+        self.output.clear();
+        self.output.push_str(&comment_lines(&banner, "%"));
+        writeln!(self.output, "function {function}()").unwrap();
+        writeln!(
+            self.output,
+            "%{} Defines the Simulink enums and bus objects of the schema.",
+            function.to_ascii_uppercase()
+        )
+        .unwrap();
+        writeln!(
+            self.output,
+            "%   Run {function} before loading a model that uses them. Each bus object is\n\
+             %   assigned in the base workspace, named after its struct or message."
+        )
+        .unwrap();
+        for name in &module.order {
+            match module.definitions.get(name) {
+                Some(Definition::Enum(e)) => self.write_enum(e),
+                Some(Definition::Struct(_) | Definition::Message(_)) => {
+                    self.write_bus(hir.layout(*name).unwrap(), &hir);
+                }
+                None => {}
+            }
+        }
+        writeln!(self.output, "\nend").unwrap();
+        self.output.push_str(ELEMENT_FUNCTION);
+
+        let output_path = self.file_path.with_file_name(format!("{function}.m"));
+        Ok(vec![(output_path, self.output.clone())])
+    }
+
+    fn supports(&self, feature: Feature) -> bool {
+        // Buses hold every field of the latest layout, present or not, and no message ID.
+        !matches!(
+            feature,
+            Feature::Versions | Feature::Conditions | Feature::MessageIds
+        )
+    }
+}
+
+/// Returns true if `e` can be a Simulink enum: one that is neither flags nor open, with a
+/// storage type Simulink enums allow.
+fn is_simulink_enum(e: &EnumDef) -> bool {
+    !e.flags && !e.open && e.underlying_type.get_bit_width() <= 32
+}
+
+/// Returns the Simulink name of a primitive type.
+fn numeric_type(p: &PrimitiveType) -> &'static str {
+    match p {
+        PrimitiveType::Bool => "boolean",
+        PrimitiveType::U8 => "uint8",
+        PrimitiveType::U16 => "uint16",
+        PrimitiveType::U32 => "uint32",
+        PrimitiveType::U64 => "uint64",
+        PrimitiveType::I8 => "int8",
+        PrimitiveType::I16 => "int16",
+        PrimitiveType::I32 => "int32",
+        PrimitiveType::I64 => "int64",
+        PrimitiveType::F32 => "single",
+        PrimitiveType::F64 => "double",
+    }
+}
+
+/// Returns the Simulink data type of a value of `ty` that is not a vector, narrowed to `bits`
+/// if it is a bit-field.
+fn data_type(ty: &TypeRef, bits: Option<usize>) -> String {
+    match ty {
+        TypeRef::Primitive(PrimitiveType::Bool) => "boolean".to_string(),
+        TypeRef::Primitive(p) => match bits {
+            Some(bits) if bits < p.get_bit_width() => {
+                format!("fixdt({},{bits},0)", p.is_signed() as u8)
+            }
+            _ => numeric_type(p).to_string(),
+        },
+        TypeRef::Fixed { base, scale } => {
+            let bits = bits.unwrap_or(base.get_bit_width());
+            format!("fixdt({},{bits},1/{scale},0)", base.is_signed() as u8)
+        }
+        TypeRef::Enum(e) if is_simulink_enum(e) => format!("Enum: {}", e.name),
+        TypeRef::Enum(e) => numeric_type(&e.underlying_type).to_string(),
+        TypeRef::Uuid | TypeRef::Bytes(_) => "uint8".to_string(),
+        TypeRef::Struct(s) => format!("Bus: {}", s.name),
+        TypeRef::Message(m) => format!("Bus: {}", m.name),
+        TypeRef::Vector { .. } => unreachable!("vectors are never nested"),
+    }
+}
+
+/// Returns `description` followed by the note of a `@deprecated` attribute, if there is one.
+fn deprecated(description: String, note: Option<&str>) -> String {
+    match note {
+        Some(note) => format!("{description} Deprecated: {note}"),
+        None => description,
+    }
+}
+
+/// Returns a MATLAB character vector literal.
+fn string(text: &str) -> String {
+    let text = text.replace('\'', "''").replace('\n', " ");
+    format!("'{text}'")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::symbol::Symbol;
+
+    #[test]
+    fn test_bus_objects() {
+        let module = crate::parser::Parser::new(
+            "enum Kind : u8 { Ping, Data, }\n\
+             @open enum Level : u8 { Low, }\n\
+             struct Point { x i32, y fixed<i16, 100>, }\n\
+             message Track { kind Kind, level Level, ready bool : 1, seq u8 : 7, \
+             points vec<Point, 4>, }",
+        )
+        .unwrap()
+        .parse_module()
+        .unwrap();
+        let mut generator = MatlabGenerator::default();
+        generator
+            .add_file_path(PathBuf::from("out/2d-track"))
+            .unwrap();
+        let files = generator.generate(&module).unwrap();
+        assert_eq!(files[0].0, PathBuf::from("out/onyx_2d_track.m"));
+        let output = &files[0].1;
+
+        assert!(output.contains("function onyx_2d_track()\n%ONYX_2D_TRACK Defines"));
+        assert!(output.contains(
+            "Simulink.defineIntEnumType('Kind', {'Ping', 'Data'}, [0; 1], ...\n    \
+             'StorageType', 'uint8', 'Description', 'Kind, carried on the wire as u8.');"
+        ));
+        assert!(
+            output
+                .contains("elements(end + 1) = element('y', 'fixdt(1,16,1/100,0)', 1, 'byte 4');")
+        );
+        assert!(output.contains("elements(end + 1) = element('kind', 'Enum: Kind', 1, 'byte 0');"));
+        // Open enums keep values Simulink enums cannot hold.
+        assert!(output.contains("elements(end + 1) = element('level', 'uint8', 1, 'byte 1');"));
+        assert!(output.contains(
+            "elements(end + 1) = element('seq', 'fixdt(0,7,0)', 1, 'byte 2, bits 1..7');"
+        ));
+        assert!(
+            output.contains("elements(end + 1) = element('points', 'Bus: Point', 4, 'byte 4');")
+        );
+        assert!(output.contains("assignin('base', 'Track', bus);"));
+    }
+
+    #[test]
+    fn test_zero_width_bit_field() {
+        let mut module = crate::parser::Parser::new("message M { a u8 : 4, b u8 : 4, }")
+            .unwrap()
+            .parse_module()
+            .unwrap();
+        let Some(Definition::Message(m)) = module.definitions.get_mut(&Symbol::intern("M")) else {
+            panic!("expected message M");
+        };
+        m.fields[1].bit_field_size = Some(0);
+        let mut generator = MatlabGenerator::default();
+        generator.add_file_path(PathBuf::from("m")).unwrap();
+        let output = &generator.generate(&module).unwrap()[0].1;
+        assert!(output.contains("element('a', 'fixdt(0,4,0)', 1, 'byte 0, bits 0..3');"));
+        assert!(output.contains("element('b', 'fixdt(0,0,0)', 1, 'byte 0');"));
+    }
+}
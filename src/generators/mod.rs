@@ -1,10 +1,41 @@
-use std::{error::Error, fmt, path::PathBuf};
+use std::{
+    collections::HashSet,
+    fs,
+    path::{Path, PathBuf},
+};
 
-use crate::ast::OnyxModule;
+use crate::{
+    LineIndex, Span,
+    ast::{Definition, Field, MessageDef, OnyxModule, Type},
+    diagnostic::{Diagnostic, codes},
+    features::Feature,
+    fingerprint,
+    hir::{self, FieldLayout, Slot},
+    symbol::Symbol,
+};
 
+pub mod arrow;
 pub mod cpp;
-pub mod py;
+pub mod kotlin;
+mod mangle;
+pub mod matlab;
+pub mod naming;
+pub mod plugin;
 pub mod rust;
+pub mod systemverilog;
+pub mod template;
+
+#[cfg(feature = "unstable-api")]
+pub mod py;
+#[cfg(not(feature = "unstable-api"))]
+#[allow(dead_code)]
+mod py;
+
+mod sealed {
+    pub trait Sealed {}
+
+    impl<T: super::CodeGenerator + ?Sized> Sealed for T {}
+}
 
 /// A trait for code generators that translate the Onyx AST into target language code.
 pub trait CodeGenerator {
@@ -13,16 +44,520 @@ pub trait CodeGenerator {
     /// Returns a vector of (filename, content) tuples, allowing a single module
     /// to generate multiple files (e.g., header and source).
     fn generate(&mut self, module: &OnyxModule) -> Result<Vec<(PathBuf, String)>, CompileError>;
+
+    /// Returns whether the generator fully supports an IDL feature.
+    ///
+    /// Used to report which targets can compile a module (see
+    /// [`used_features`](crate::features::used_features)). The built-in generators support
+    /// every feature; custom backends should override this for the ones they do not.
+    fn supports(&self, feature: Feature) -> bool {
+        let _ = feature;
+        true
+    }
 }
 
-/// Minimal error type for compilation/generation failures.
-#[derive(Debug)]
-pub struct CompileError(pub String);
+/// Convenience methods available on every [`CodeGenerator`].
+///
+/// This trait is sealed and implemented automatically for all generators, so methods can be
+/// added to it without breaking downstream code.
+pub trait CodeGeneratorExt: CodeGenerator + sealed::Sealed {
+    /// Generates the module and writes every output file to disk, creating parent directories
+    /// as needed. Relative output paths are resolved against `root`.
+    ///
+    /// Returns the paths of the files that were written.
+    fn write_files(
+        &mut self,
+        module: &OnyxModule,
+        root: &Path,
+    ) -> Result<Vec<PathBuf>, CompileError> {
+        write_outputs(self.generate(module)?, root)
+    }
+}
+
+impl<T: CodeGenerator + ?Sized> CodeGeneratorExt for T {}
+
+/// Writes generated files to disk, creating parent directories as needed. Relative paths are
+/// resolved against `root`.
+///
+/// Returns the paths of the files that were written.
+pub fn write_outputs(
+    files: Vec<(PathBuf, String)>,
+    root: &Path,
+) -> Result<Vec<PathBuf>, CompileError> {
+    let mut written = Vec::new();
+    for (path, content) in files {
+        let path = root.join(path);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(|e| {
+                Diagnostic::error(format!("failed to create {}: {e}", parent.display()))
+                    .with_code(codes::IO)
+                    .with_cause(e)
+            })?;
+        }
+        fs::write(&path, content).map_err(|e| {
+            Diagnostic::error(format!("failed to write {}: {e}", path.display()))
+                .with_code(codes::IO)
+                .with_cause(e)
+        })?;
+        written.push(path);
+    }
+    Ok(written)
+}
+
+/// Fails if two generations of the same module differ, naming the first file and line that
+/// does.
+///
+/// Each `HashMap` is seeded afresh, so generating twice from modules parsed separately, such
+/// as on two threads, exposes output that depends on iteration order. Builds are only
+/// reproducible when this passes.
+///
+/// ```rust
+/// use onyx::generators::check_deterministic;
+/// use std::path::PathBuf;
+///
+/// let first = [(PathBuf::from("a.rs"), "use a;\nuse b;\n".to_string())];
+/// let second = [(PathBuf::from("a.rs"), "use b;\nuse a;\n".to_string())];
+/// assert!(check_deterministic(&first, &first).is_ok());
+///
+/// let error = check_deterministic(&first, &second).unwrap_err();
+/// assert_eq!(
+///     error.message(),
+///     "generated output is not deterministic: a.rs differs at line 1"
+/// );
+/// assert_eq!(error.notes(), ["first run:  use a;", "second run: use b;"]);
+/// ```
+pub fn check_deterministic(
+    first: &[(PathBuf, String)],
+    second: &[(PathBuf, String)],
+) -> Result<(), CompileError> {
+    let error = |message: String| {
+        Diagnostic::error(format!("generated output is not deterministic: {message}"))
+            .with_code(codes::CODEGEN)
+    };
+    for index in 0..first.len().max(second.len()) {
+        let ((path, a), (other, b)) = match (first.get(index), second.get(index)) {
+            (Some(a), Some(b)) => (a, b),
+            (Some((path, _)), None) | (None, Some((path, _))) => {
+                return Err(error(format!("only one run wrote {}", path.display())));
+            }
+            (None, None) => unreachable!("index is within one of the runs"),
+        };
+        if path != other {
+            return Err(error(format!(
+                "one run wrote {} where the other wrote {}",
+                path.display(),
+                other.display()
+            )));
+        }
+        if a == b {
+            continue;
+        }
+        let (a, b): (Vec<&str>, Vec<&str>) = (a.lines().collect(), b.lines().collect());
+        let line = (0..a.len().max(b.len()))
+            .find(|&i| a.get(i) != b.get(i))
+            .unwrap_or(a.len());
+        let shown = |lines: &[&str]| {
+            lines
+                .get(line)
+                .copied()
+                .unwrap_or("<end of file>")
+                .to_string()
+        };
+        return Err(
+            error(format!("{} differs at line {}", path.display(), line + 1))
+                .with_note(format!("first run:  {}", shown(&a)))
+                .with_note(format!("second run: {}", shown(&b))),
+        );
+    }
+    Ok(())
+}
 
-impl fmt::Display for CompileError {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "Compile Error: {}", self.0)
+/// The targets accepted by [`generator_for_target`].
+pub const TARGETS: &[&str] = &["cpp", "rust", "kotlin", "systemverilog", "matlab", "arrow"];
+
+/// Creates the generator for a target name (see [`TARGETS`]), configured to write outputs
+/// next to `file_path`.
+pub fn generator_for_target(
+    target: &str,
+    file_path: PathBuf,
+) -> Result<Box<dyn CodeGenerator>, CompileError> {
+    match target {
+        "cpp" => {
+            let mut generator = cpp::CppGenerator::default();
+            generator.add_file_path(file_path)?;
+            Ok(Box::new(generator))
+        }
+        "rust" => {
+            let mut generator = rust::RustGenerator::default();
+            generator.add_file_path(file_path)?;
+            Ok(Box::new(generator))
+        }
+        "kotlin" => {
+            let mut generator = kotlin::KotlinGenerator::default();
+            generator.add_file_path(file_path)?;
+            Ok(Box::new(generator))
+        }
+        "systemverilog" => {
+            let mut generator = systemverilog::SystemVerilogGenerator::default();
+            generator.add_file_path(file_path)?;
+            Ok(Box::new(generator))
+        }
+        "matlab" => {
+            let mut generator = matlab::MatlabGenerator::default();
+            generator.add_file_path(file_path)?;
+            Ok(Box::new(generator))
+        }
+        "arrow" => {
+            let mut generator = arrow::ArrowGenerator::default();
+            generator.add_file_path(file_path)?;
+            Ok(Box::new(generator))
+        }
+        _ => Err(Diagnostic::error(format!(
+            "unknown target '{target}', expected one of: {}",
+            TARGETS.join(", ")
+        ))),
+    }
+}
+
+/// The schema a module was parsed from, so that generated code can point back at it.
+///
+/// Given to a generator's `set_source_map`, it makes the generator emit a comment such as
+/// `// onyx: user.onyx:12:5` before each generated type and field accessor, naming the
+/// declaration it came from.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct SchemaSource {
+    /// The path of the schema, as it appears in the comments.
+    pub path: String,
+    /// The text of the schema.
+    pub text: String,
+}
+
+impl SchemaSource {
+    /// Creates the source of the schema at `path`.
+    pub fn new(path: impl Into<String>, text: impl Into<String>) -> Self {
+        SchemaSource {
+            path: path.into(),
+            text: text.into(),
+        }
+    }
+
+    /// Returns the comment that points at `span`, or `None` for a declaration without a
+    /// source, such as one built in code.
+    pub(crate) fn comment(&self, span: Span) -> Option<String> {
+        let position = LineIndex::new(&self.text).position(span.start());
+        (!span.is_empty()).then(|| format!("// onyx: {}:{position}", self.path))
+    }
+}
+
+/// The comment at the top of every generated file.
+///
+/// Every backend opens its files with the same lines, in the comment syntax of the file:
+/// any copyright notice, then a line saying the file is generated and should not be edited,
+/// optionally naming the compiler version and the schema, then optionally the schema's
+/// [fingerprint](crate::fingerprint::module).
+///
+/// ```rust
+/// use onyx::generators::Banner;
+/// use onyx::parser::Parser;
+///
+/// let module = Parser::new("struct Point { x u8, }")
+///     .and_then(|p| p.parse_module())
+///     .unwrap();
+/// let banner = Banner::new()
+///     .with_copyright("Copyright 2026 Example Corp.")
+///     .with_source("point.onyx");
+/// assert_eq!(
+///     banner.lines(&module).unwrap(),
+///     [
+///         "Copyright 2026 Example Corp.",
+///         "Automatically generated by Onyx IDL compiler from point.onyx. Do not edit.",
+///     ]
+/// );
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Banner {
+    /// A copyright or license notice, which may span several lines.
+    pub copyright: Option<String>,
+    /// The path of the schema, as named in the banner.
+    pub source: Option<String>,
+    /// Whether the banner names the compiler version.
+    pub version: bool,
+    /// Whether the banner holds the fingerprint of the schema.
+    pub fingerprint: bool,
+}
+
+impl Banner {
+    /// Creates the default banner, which only says the file is generated.
+    pub fn new() -> Self {
+        Banner::default()
+    }
+
+    /// Puts `copyright` at the top of the banner.
+    pub fn with_copyright(mut self, copyright: impl Into<String>) -> Self {
+        self.copyright = Some(copyright.into());
+        self
+    }
+
+    /// Names the schema at `path` as the source of the files.
+    pub fn with_source(mut self, path: impl Into<String>) -> Self {
+        self.source = Some(path.into());
+        self
+    }
+
+    /// Names the compiler version that generated the files.
+    pub fn with_version(mut self) -> Self {
+        self.version = true;
+        self
+    }
+
+    /// Adds the fingerprint of the schema, so a file can be matched to the schema it came from.
+    pub fn with_fingerprint(mut self) -> Self {
+        self.fingerprint = true;
+        self
+    }
+
+    /// Returns the lines of the banner for `module`, the module as parsed, without comment
+    /// markers.
+    pub fn lines(&self, module: &OnyxModule) -> Result<Vec<String>, CompileError> {
+        let mut lines: Vec<String> = self
+            .copyright
+            .iter()
+            .flat_map(|copyright| copyright.lines())
+            .map(|line| line.trim_end().to_string())
+            .collect();
+        let mut generated = "Automatically generated by Onyx IDL compiler".to_string();
+        if self.version {
+            generated.push_str(concat!(" v", env!("CARGO_PKG_VERSION")));
+        }
+        if let Some(source) = &self.source {
+            generated.push_str(&format!(" from {source}"));
+        }
+        lines.push(format!("{generated}. Do not edit."));
+        if self.fingerprint {
+            let fingerprint = fingerprint::module(&hir::lower(module)?);
+            lines.push(format!("Schema fingerprint: {fingerprint:#018x}"));
+        }
+        Ok(lines)
+    }
+}
+
+/// Returns `lines` as line comments starting with `marker`, such as `//` or `#`.
+pub(crate) fn comment_lines(lines: &[String], marker: &str) -> String {
+    lines
+        .iter()
+        .map(|line| match line.is_empty() {
+            true => format!("{marker}\n"),
+            false => format!("{marker} {line}\n"),
+        })
+        .collect()
+}
+
+/// Returns the messages marked `@id` that `module` generates, ordered by ID.
+///
+/// Definitions left out of `order` are only there to be referred to, such as the types of
+/// other modules of a [`Workspace`](crate::workspace::Workspace), and are generated elsewhere.
+pub(crate) fn own_identified_messages(module: &OnyxModule) -> Vec<&MessageDef> {
+    let mut messages = module.identified_messages();
+    messages.retain(|m| module.order.contains(&m.name));
+    messages
+}
+
+/// Fails unless every definition in the `order` of `module`, and of each earlier version, comes
+/// after the definitions it uses, so that generators can emit types in that order.
+///
+/// Parsing and [`resolve_module`](crate::resolve::resolve_module) set such an order; one edited
+/// in code may not be. Types left out of `order` are defined elsewhere and may be used anywhere.
+pub(crate) fn check_order(module: &OnyxModule) -> Result<(), CompileError> {
+    for layout in std::iter::once(module).chain(&module.history) {
+        for (index, id) in layout.order.iter().enumerate() {
+            let fields = match layout.definitions.get(id) {
+                Some(Definition::Message(m)) => &m.fields,
+                Some(Definition::Struct(s)) => &s.fields,
+                _ => continue,
+            };
+            for field in fields {
+                if let Type::Custom(used) = field.type_info.element_type()
+                    && layout.order[index..].contains(used)
+                {
+                    return Err(Diagnostic::error(format!(
+                        "'{id}' is ordered before '{used}', which its field '{}' uses",
+                        field.name
+                    ))
+                    .with_code(codes::CODEGEN)
+                    .with_help("resolve the module with `resolve::resolve_module` to order it"));
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Returns the types the fields of `module`'s own definitions use but that it does not
+/// define itself, such as those of another module in a workspace, in order of first use.
+pub(crate) fn external_types(module: &OnyxModule) -> Vec<Symbol> {
+    let layouts: Vec<&OnyxModule> = std::iter::once(module).chain(&module.history).collect();
+    let own = |id: &Symbol| layouts.iter().any(|layout| layout.order.contains(id));
+    let mut used = Vec::new();
+    for layout in &layouts {
+        for id in &layout.order {
+            let fields = match layout.definitions.get(id) {
+                Some(Definition::Message(m)) => &m.fields,
+                Some(Definition::Struct(s)) => &s.fields,
+                _ => continue,
+            };
+            for field in fields {
+                if let Type::Custom(name) = field.type_info.element_type()
+                    && !own(name)
+                    && !used.contains(name)
+                {
+                    used.push(*name);
+                }
+            }
+        }
+    }
+    used
+}
+
+/// Returns the size in bytes of the largest message `module` generates, or `None` if it has
+/// no messages.
+pub(crate) fn largest_message_size(module: &OnyxModule) -> Option<usize> {
+    module
+        .order
+        .iter()
+        .filter_map(|id| match module.definitions.get(id) {
+            Some(Definition::Message(m)) => m.size,
+            _ => None,
+        })
+        .max()
+        .map(|bits| bits.div_ceil(8))
+}
+
+/// Returns each definition `module` generates with its size in bytes and its
+/// [fingerprint](crate::fingerprint), in order, for a table of protocol constants.
+///
+/// `module` is the module as parsed, before any renaming, so the fingerprints match those
+/// computed from the schema.
+pub(crate) fn definition_constants(
+    module: &OnyxModule,
+) -> Result<Vec<(&Definition, usize, u64)>, CompileError> {
+    let hir = hir::lower(module)?;
+    Ok(module
+        .order
+        .iter()
+        .filter_map(|id| {
+            let def = module.definitions.get(id)?;
+            let size = hir.size_of(&Type::Custom(*id))?;
+            Some((def, size, fingerprint::definition(&hir, *id)?))
+        })
+        .collect())
+}
+
+/// Fails if two constants of a protocol constants table share a name in `scope`, such as the
+/// constants of enum variants named `Fingerprint` and `FINGERPRINT`.
+pub(crate) fn check_constant_names<'n>(
+    scope: &str,
+    names: impl IntoIterator<Item = &'n str>,
+) -> Result<(), CompileError> {
+    let mut seen = HashSet::new();
+    for name in names {
+        if !seen.insert(name) {
+            return Err(Diagnostic::error(format!(
+                "two protocol constants are named '{name}' in {scope}"
+            ))
+            .with_code(codes::NAME_COLLISION)
+            .with_help("rename one of the definitions or variants they are generated from"));
+        }
+    }
+    Ok(())
+}
+
+/// Converts a snake_case name to UpperCamelCase (`start_time` becomes `StartTime`), leaving the
+/// rest of each word as written.
+pub(crate) fn upper_camel_case(name: &str) -> String {
+    name.split('_')
+        .map(|word| {
+            let mut chars = word.chars();
+            chars
+                .next()
+                .map(|c| c.to_ascii_uppercase().to_string() + chars.as_str())
+                .unwrap_or_default()
+        })
+        .collect()
+}
+
+/// Returns a mask of the low `bits` bits of a 64-bit container, all of them for a bit-field
+/// as wide as `u64` itself.
+pub(crate) fn bit_mask(bits: usize) -> u64 {
+    u64::MAX.checked_shr(64 - bits as u32).unwrap_or(0)
+}
+
+/// Returns the field of `slot` that the accessor `name` belongs to.
+///
+/// Every accessor of a regular slot belongs to its one field. In a bit-field container, an
+/// accessor belongs to the field it is named after, with or without a `set_` or `has_` prefix.
+pub(crate) fn accessor_field<'s>(slot: &'s Slot, name: &str) -> Option<&'s FieldLayout<'s>> {
+    let unprefixed = name
+        .strip_prefix("set_")
+        .or_else(|| name.strip_prefix("has_"));
+    slot.fields.iter().find(|field| {
+        !slot.is_bit_field_container()
+            || field.name == name
+            || unprefixed == Some(field.name.as_str())
+    })
+}
+
+/// Returns the `@deprecated` note of the field of `slot` that the accessor `name` belongs to,
+/// if that field is deprecated.
+pub(crate) fn accessor_deprecation<'s>(slot: &'s Slot, name: &str) -> Option<&'s str> {
+    accessor_field(slot, name).and_then(|field| field.deprecated.as_deref())
+}
+
+/// Returns the doc comment `doc` as `///` lines, each after `indent`, for the C-family
+/// targets whose tools read them: rustdoc, and Doxygen in C++.
+pub(crate) fn doc_lines(doc: Option<&str>, indent: &str) -> String {
+    // A backslash at the end of a C++ line comment would continue it onto the next line.
+    let lines: Vec<String> = doc
+        .iter()
+        .flat_map(|doc| doc.lines())
+        .map(|line| line.trim_end_matches('\\').trim_end().to_string())
+        .collect();
+    comment_lines(&lines, "///")
+        .lines()
+        .map(|line| format!("{indent}{line}\n"))
+        .collect()
+}
+
+/// Returns true if `type_info` has the same layout in two versions of a module: it is not a
+/// struct or message, or its fields and their layouts are the same in both.
+pub(crate) fn same_layout(old: &OnyxModule, new: &OnyxModule, type_info: &Type) -> bool {
+    fn fields<'a>(module: &'a OnyxModule, name: &Symbol) -> Option<&'a Vec<Field>> {
+        match module.definitions.get(name) {
+            Some(Definition::Message(m)) => Some(&m.fields),
+            Some(Definition::Struct(s)) => Some(&s.fields),
+            _ => None,
+        }
+    }
+    match type_info {
+        Type::Vector { element, .. } => same_layout(old, new, element),
+        Type::Custom(name) => match (fields(old, name), fields(new, name)) {
+            (Some(a), Some(b)) => {
+                a.len() == b.len()
+                    && a.iter().zip(b).all(|(a, b)| {
+                        a.name == b.name
+                            && a.type_info == b.type_info
+                            && a.bit_field_size == b.bit_field_size
+                            && same_layout(old, new, &a.type_info)
+                    })
+            }
+            _ => true,
+        },
+        _ => true,
     }
 }
 
-impl Error for CompileError {}
+/// The error type for compilation/generation failures.
+///
+/// Kept as an alias of [`Diagnostic`] so existing signatures continue to compile.
+pub type CompileError = Diagnostic;
@@ -0,0 +1,186 @@
+//! Naming conventions for the names generators emit.
+//!
+//! Onyx names are used as written by default. A generator can instead convert them to the
+//! convention of its target language, such as snake_case accessors in Rust or camelCase getters
+//! in C++. Names are split into words at underscores and at case changes; a run of capitals is
+//! kept as one word, so `HTTPServer` splits into `HTTP` and `Server`.
+
+/// How an Onyx name is written in generated code.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[non_exhaustive]
+pub enum NamingConvention {
+    /// Keep the name as written in the schema.
+    #[default]
+    Preserve,
+    /// `http_server_port`
+    SnakeCase,
+    /// `httpServerPort`
+    CamelCase,
+    /// `HttpServerPort`
+    PascalCase,
+    /// `HTTP_SERVER_PORT`
+    ScreamingSnakeCase,
+}
+
+impl NamingConvention {
+    /// All conventions, in declaration order.
+    pub const ALL: [NamingConvention; 5] = [
+        NamingConvention::Preserve,
+        NamingConvention::SnakeCase,
+        NamingConvention::CamelCase,
+        NamingConvention::PascalCase,
+        NamingConvention::ScreamingSnakeCase,
+    ];
+
+    /// Returns the name of the convention, as accepted by `onyxc --field-naming`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            NamingConvention::Preserve => "preserve",
+            NamingConvention::SnakeCase => "snake",
+            NamingConvention::CamelCase => "camel",
+            NamingConvention::PascalCase => "pascal",
+            NamingConvention::ScreamingSnakeCase => "screaming-snake",
+        }
+    }
+
+    /// Looks up a convention by name.
+    pub fn from_name(name: &str) -> Option<NamingConvention> {
+        Self::ALL
+            .into_iter()
+            .find(|convention| convention.name() == name)
+    }
+
+    /// Writes `name` in this convention.
+    ///
+    /// Digits stay with the word before them, so `ipv4_addr` becomes `ipv4Addr` in camelCase.
+    pub fn apply(&self, name: &str) -> String {
+        let words = words(name);
+        if words.is_empty() {
+            return name.to_string();
+        }
+        match self {
+            NamingConvention::Preserve => name.to_string(),
+            NamingConvention::SnakeCase => join(&words, "_", str::to_ascii_lowercase),
+            NamingConvention::ScreamingSnakeCase => join(&words, "_", str::to_ascii_uppercase),
+            NamingConvention::PascalCase => join(&words, "", capitalize),
+            NamingConvention::CamelCase => {
+                let mut out = words
+                    .first()
+                    .map(|w| w.to_ascii_lowercase())
+                    .unwrap_or_default();
+                out.push_str(&join(words.get(1..).unwrap_or_default(), "", capitalize));
+                out
+            }
+        }
+    }
+}
+
+/// Splits a name into words at underscores and case changes.
+///
+/// A new word starts at a capital that follows a lowercase letter or digit, and at the last
+/// capital of a run that is followed by a lowercase letter (`XMLParser` is `XML` and `Parser`).
+pub fn words(name: &str) -> Vec<&str> {
+    let mut words = Vec::new();
+    for part in name.split('_').filter(|part| !part.is_empty()) {
+        let chars: Vec<(usize, char)> = part.char_indices().collect();
+        let mut start = 0;
+        for (i, &(index, c)) in chars.iter().enumerate().skip(1) {
+            let prev = chars[i - 1].1;
+            let next_lower = chars.get(i + 1).is_some_and(|&(_, n)| n.is_lowercase());
+            let boundary = c.is_uppercase()
+                && (prev.is_lowercase()
+                    || prev.is_ascii_digit()
+                    || (prev.is_uppercase() && next_lower));
+            if boundary {
+                words.push(&part[start..index]);
+                start = index;
+            }
+        }
+        words.push(&part[start..]);
+    }
+    words
+}
+
+fn join(words: &[&str], separator: &str, case: impl Fn(&str) -> String) -> String {
+    words
+        .iter()
+        .map(|word| case(word))
+        .collect::<Vec<_>>()
+        .join(separator)
+}
+
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    chars
+        .next()
+        .map(|c| c.to_ascii_uppercase().to_string() + &chars.as_str().to_ascii_lowercase())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_words() {
+        assert_eq!(words("start_time"), ["start", "time"]);
+        assert_eq!(words("startTime"), ["start", "Time"]);
+        assert_eq!(words("HTTPServer"), ["HTTP", "Server"]);
+        assert_eq!(words("parseURL"), ["parse", "URL"]);
+        assert_eq!(words("ipv4Addr"), ["ipv4", "Addr"]);
+        assert_eq!(words("__private__field"), ["private", "field"]);
+        assert_eq!(words("X"), ["X"]);
+        assert!(words("_").is_empty());
+    }
+
+    #[test]
+    fn test_conventions() {
+        let cases = [
+            (
+                "HTTPServer",
+                [
+                    "HTTPServer",
+                    "http_server",
+                    "httpServer",
+                    "HttpServer",
+                    "HTTP_SERVER",
+                ],
+            ),
+            (
+                "user_id",
+                ["user_id", "user_id", "userId", "UserId", "USER_ID"],
+            ),
+            (
+                "parseURL2",
+                [
+                    "parseURL2",
+                    "parse_url2",
+                    "parseUrl2",
+                    "ParseUrl2",
+                    "PARSE_URL2",
+                ],
+            ),
+            (
+                "MAX_LEN",
+                ["MAX_LEN", "max_len", "maxLen", "MaxLen", "MAX_LEN"],
+            ),
+            ("a", ["a", "a", "a", "A", "A"]),
+        ];
+        for (name, expected) in cases {
+            for (convention, expected) in NamingConvention::ALL.into_iter().zip(expected) {
+                assert_eq!(convention.apply(name), expected, "{name} as {convention:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_names() {
+        for convention in NamingConvention::ALL {
+            assert_eq!(
+                NamingConvention::from_name(convention.name()),
+                Some(convention)
+            );
+        }
+        assert_eq!(NamingConvention::from_name("kebab"), None);
+    }
+}
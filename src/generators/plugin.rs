@@ -0,0 +1,445 @@
+//! Generators implemented as external programs.
+//!
+//! A plugin is an executable that reads one JSON request from stdin and writes one JSON response
+//! to stdout, so backends can be written in any language. The request is:
+//!
+//! ```json
+//! {
+//!   "compiler_version": "0.1.0",
+//!   "stem": "schema",
+//!   "banner": ["Automatically generated by Onyx IDL compiler. Do not edit."],
+//!   "module": {
+//!     "endianness": "big",
+//!     "portable": true,
+//!     "version": 2,
+//!     "definitions": [
+//!       { "kind": "enum", "name": "Status", "size": 1, "underlying_type": "u8", "flags": false,
+//!         "open": false,
+//!         "variants": [{ "name": "Active", "value": 1, "composite": false }] },
+//!       { "kind": "message", "name": "Ping", "size": 0, "id": 1, "fields": [] },
+//!       { "kind": "struct", "name": "Header", "size": 4,
+//!         "fields": [{ "name": "version", "type": "u32", "bit_field_size": 4,
+//!                      "element": null, "capacity": null, "scale": null,
+//!                      "since": 2, "removed": null, "condition": null }] }
+//!     ],
+//!     "history": [{ "endianness": "big", "portable": true, "version": 1, "definitions": [...], "history": [] }]
+//!   }
+//! }
+//! ```
+//!
+//! Definitions are in source order and sizes are in bytes; `bit_field_size` is `null` for
+//! ordinary fields, `element` and `capacity` are `null` except for bounded vectors, and `scale` is
+//! `null` except for fixed-point numbers. `flags` is `true` for flags enums, whose `composite`
+//! variants combine other bits rather than naming one. `open` is `true` for enums marked `@open`,
+//! whose decoders keep values that no variant declares. `id` is a message's `@id`, or `null`.
+//!
+//! `endianness` is `"big"`, `"little"`, or `"host"`. A `host` module is laid out in the byte
+//! order of whichever machine uses it, so `portable` is `false` and its buffers must not cross
+//! machines.
+//!
+//! `version` is the schema version, or `null` without a `version` directive. The definitions
+//! have the layout of that version, and `history` holds the module at each earlier version,
+//! from version 1 up. `since` and `removed` are a field's `@since` and `@removed` versions, or
+//! `null`, and `condition` is the condition of a conditional field, such as `"kind == Kind.Data"`,
+//! or `null`. `stem` is the output file name without an extension,
+//! for naming the files the plugin emits. `banner` holds the lines of the
+//! [banner](super::Banner), without comment markers, for the plugin to open its files with.
+//!
+//! The response lists the files to write, with paths relative to the output directory:
+//!
+//! ```json
+//! { "files": [{ "name": "schema.go", "content": "package schema\n" }] }
+//! ```
+//!
+//! A plugin reports a problem with the module by responding with `{ "error": "message" }`. Its
+//! stderr is passed through, and a nonzero exit status is an error.
+
+use std::{
+    fmt::Write as _,
+    io::Write as _,
+    path::{Component, Path, PathBuf},
+    process::{Command, Stdio},
+    thread,
+};
+
+use crate::{
+    ast::{Definition, OnyxModule, Type, WireEndianness},
+    diagnostic::{Diagnostic, codes},
+    generators::{Banner, CodeGenerator, CompileError, check_order},
+    json,
+};
+
+/// A generator that runs an external plugin program.
+#[derive(Debug)]
+pub struct PluginGenerator {
+    program: PathBuf,
+    file_path: PathBuf,
+    file_stem: String,
+    banner: Banner,
+}
+
+impl PluginGenerator {
+    /// Creates a generator that runs `program`.
+    pub fn new(program: impl Into<PathBuf>) -> Self {
+        PluginGenerator {
+            program: program.into(),
+            file_path: PathBuf::new(),
+            file_stem: String::new(),
+            banner: Banner::default(),
+        }
+    }
+
+    /// Adds a file path to the generator configuration.
+    ///
+    /// The plugin's files are written next to this path, and its file stem is passed to the
+    /// plugin as `stem`.
+    pub fn add_file_path(&mut self, file_path: PathBuf) -> Result<(), CompileError> {
+        self.file_path = file_path;
+        self.file_stem = match self.file_path.file_stem() {
+            Some(s) => s.to_string_lossy().to_string(),
+            None => {
+                return Err(Diagnostic::error(format!(
+                    "output path '{}' has no file name",
+                    self.file_path.display()
+                ))
+                .with_code(codes::CODEGEN));
+            }
+        };
+        Ok(())
+    }
+
+    /// Sets the banner passed to the plugin as `banner`.
+    pub fn set_banner(&mut self, banner: Banner) {
+        self.banner = banner;
+    }
+
+    fn error(&self, message: impl std::fmt::Display) -> Diagnostic {
+        Diagnostic::error(format!("plugin {}: {message}", self.program.display()))
+            .with_code(codes::CODEGEN)
+    }
+
+    /// Reads the files from a plugin response.
+    fn parse_response(&self, response: &str) -> Result<Vec<(PathBuf, String)>, CompileError> {
+        let response =
+            json::parse(response).map_err(|e| self.error(format!("invalid response: {e}")))?;
+        if let Some(message) = response.get("error") {
+            return Err(self.error(message.as_str().unwrap_or("reported an error")));
+        }
+        let files = response
+            .get("files")
+            .and_then(json::Value::as_array)
+            .ok_or_else(|| self.error("response has no 'files' array"))?;
+
+        let dir = self.file_path.parent().unwrap_or(Path::new(""));
+        files
+            .iter()
+            .map(|file| {
+                let (Some(name), Some(content)) = (
+                    file.get("name").and_then(json::Value::as_str),
+                    file.get("content").and_then(json::Value::as_str),
+                ) else {
+                    return Err(self.error("each file needs a 'name' and 'content' string"));
+                };
+                let relative = Path::new(name);
+                if !relative
+                    .components()
+                    .all(|c| matches!(c, Component::Normal(_)))
+                {
+                    return Err(self.error(format!(
+                        "file name '{name}' must be a relative path inside the output directory"
+                    )));
+                }
+                Ok((dir.join(relative), content.to_string()))
+            })
+            .collect()
+    }
+}
+
+impl CodeGenerator for PluginGenerator {
+    fn generate(&mut self, module: &OnyxModule) -> Result<Vec<(PathBuf, String)>, CompileError> {
+        check_order(module)?;
+        let request = format!(
+            "{{\"compiler_version\":{},\"stem\":{},\"banner\":{},\"module\":{}}}",
+            json::string(env!("CARGO_PKG_VERSION")),
+            json::string(&self.file_stem),
+            json::string_array(&self.banner.lines(module)?),
+            module_json(module)
+        );
+
+        let mut child = Command::new(&self.program)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::inherit())
+            .spawn()
+            .map_err(|e| self.error(format!("failed to start: {e}")))?;
+
+        // Write the request on another thread so a plugin that answers before reading all of
+        // its input cannot deadlock on a full pipe.
+        let mut stdin = child.stdin.take().expect("stdin is piped");
+        let writer = thread::spawn(move || stdin.write_all(request.as_bytes()));
+        let output = child
+            .wait_with_output()
+            .map_err(|e| self.error(format!("failed to run: {e}")))?;
+        // A plugin may exit without reading its input, so a broken pipe is not an error in itself.
+        let _ = writer.join();
+
+        if !output.status.success() {
+            return Err(self.error(format!("exited with {}", output.status)));
+        }
+        let response = String::from_utf8(output.stdout)
+            .map_err(|_| self.error("response is not valid UTF-8"))?;
+        self.parse_response(&response)
+    }
+}
+
+/// Serializes a resolved module as the `module` object of a plugin request.
+pub fn module_json(module: &OnyxModule) -> String {
+    let endianness = match module.endianness {
+        WireEndianness::Big => "big",
+        WireEndianness::Little => "little",
+        WireEndianness::Host => "host",
+    };
+    let portable = module.endianness.is_portable();
+    let definitions: Vec<String> = module
+        .order
+        .iter()
+        .filter_map(|id| module.definitions.get(id))
+        .map(definition_json)
+        .collect();
+    let version = module
+        .version
+        .map_or("null".to_string(), |version| version.to_string());
+    let history: Vec<String> = module.history.iter().map(module_json).collect();
+    format!(
+        "{{\"endianness\":\"{endianness}\",\"portable\":{portable},\"version\":{version},\"definitions\":[{}],\"history\":[{}]}}",
+        definitions.join(","),
+        history.join(",")
+    )
+}
+
+fn definition_json(def: &Definition) -> String {
+    let mut out = String::new();
+    let size = def.size().unwrap_or(0).div_ceil(8);
+    let (kind, fields, id) = match def {
+        Definition::Message(m) => (
+            "message",
+            &m.fields,
+            format!(
+                ",\"id\":{}",
+                m.id.map_or("null".to_string(), |id| id.to_string())
+            ),
+        ),
+        Definition::Struct(s) => ("struct", &s.fields, String::new()),
+        Definition::Enum(e) => {
+            let variants: Vec<String> = e
+                .variants
+                .iter()
+                .zip(e.variant_values())
+                .map(|(variant, value)| {
+                    format!(
+                        "{{\"name\":{},\"value\":{value},\"composite\":{}}}",
+                        json::string(&variant.name),
+                        variant.composite
+                    )
+                })
+                .collect();
+            write!(
+                out,
+                "{{\"kind\":\"enum\",\"name\":{},\"size\":{size},\"underlying_type\":\"{}\",\"flags\":{},\"open\":{},\"variants\":[{}]}}",
+                json::string(def.name()),
+                e.underlying_type,
+                e.flags,
+                e.open,
+                variants.join(",")
+            )
+            .unwrap();
+            return out;
+        }
+    };
+    let fields: Vec<String> = fields
+        .iter()
+        .map(|field| {
+            let bits = field
+                .bit_field_size
+                .map_or("null".to_string(), |bits| bits.to_string());
+            let (element, capacity) = match &field.type_info {
+                Type::Vector { element, capacity } => {
+                    (json::string(&element.to_string()), capacity.to_string())
+                }
+                _ => ("null".to_string(), "null".to_string()),
+            };
+            let scale = match &field.type_info {
+                Type::Fixed { scale, .. } => scale.to_string(),
+                _ => "null".to_string(),
+            };
+            let [since, removed] = [field.since, field.removed]
+                .map(|version| version.map_or("null".to_string(), |v| v.to_string()));
+            let condition = field
+                .condition
+                .as_ref()
+                .map_or("null".to_string(), |c| json::string(&c.to_string()));
+            format!(
+                "{{\"name\":{},\"type\":{},\"bit_field_size\":{bits},\"element\":{element},\"capacity\":{capacity},\"scale\":{scale},\"since\":{since},\"removed\":{removed},\"condition\":{condition}}}",
+                json::string(field.name.as_str()),
+                json::string(&field.type_info.to_string())
+            )
+        })
+        .collect();
+    write!(
+        out,
+        "{{\"kind\":\"{kind}\",\"name\":{},\"size\":{size}{id},\"fields\":[{}]}}",
+        json::string(def.name()),
+        fields.join(",")
+    )
+    .unwrap();
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::Parser;
+
+    const SOURCE: &str = "endian = big\n\
+                          enum Status : u8 { Active = 1, Inactive, }\n\
+                          @packed_bits struct Header { version u32 : 4, tag Status, }\n\
+                          message Log { tags vec<Status, 4>, level fixed<i16, 10>, }";
+
+    #[test]
+    fn test_module_json() {
+        let module = Parser::new(SOURCE).unwrap().parse_module().unwrap();
+        let text = module_json(&module);
+        let value = json::parse(&text).unwrap();
+        let definitions = value
+            .get("definitions")
+            .and_then(json::Value::as_array)
+            .unwrap();
+        assert_eq!(definitions.len(), 3);
+        assert_eq!(
+            definitions[0].get("name").and_then(json::Value::as_str),
+            Some("Status")
+        );
+        assert!(text.contains(r#""underlying_type":"u8","flags":false,"open":false,"#));
+        assert!(text.contains(r#"{"name":"Inactive","value":2,"composite":false}"#));
+        assert!(text.contains(
+            r#"{"kind":"struct","name":"Header","size":2,"fields":[{"name":"version","type":"u32","bit_field_size":4,"element":null,"capacity":null,"scale":null,"since":null,"removed":null,"condition":null},{"name":"tag","type":"Status","bit_field_size":null,"element":null,"capacity":null,"scale":null,"since":null,"removed":null,"condition":null}]}"#
+        ));
+        assert!(text.contains(
+            r#"{"name":"tags","type":"vec<Status, 4>","bit_field_size":null,"element":"Status","capacity":4,"scale":null,"since":null,"removed":null,"condition":null}"#
+        ));
+        assert!(text.contains(
+            r#"{"name":"level","type":"fixed<i16, 10>","bit_field_size":null,"element":null,"capacity":null,"scale":10,"since":null,"removed":null,"condition":null}"#
+        ));
+        assert!(text.starts_with(r#"{"endianness":"big","portable":true,"version":null,"#));
+        assert!(text.ends_with(r#""history":[]}"#));
+
+        let module = Parser::new("version = 2 struct A { @since(2) b u8, }")
+            .unwrap()
+            .parse_module()
+            .unwrap();
+        let text = module_json(&module);
+        assert!(text.contains(r#""version":2,"#));
+        assert!(text.contains(r#""since":2,"removed":null,"condition":null"#));
+        assert!(text.contains(
+            r#""history":[{"endianness":"little","portable":true,"version":1,"definitions":[{"kind":"struct","name":"A","size":0,"fields":[]}],"history":[]}]"#
+        ));
+
+        let module = Parser::new("struct A { a bool : 1, b u8 : 7 if a == true, }")
+            .unwrap()
+            .parse_module()
+            .unwrap();
+        assert!(module_json(&module).contains(r#""condition":"a == true"}"#));
+
+        let module = Parser::new("@id(3) message M { }")
+            .unwrap()
+            .parse_module()
+            .unwrap();
+        assert!(
+            module_json(&module)
+                .contains(r#"{"kind":"message","name":"M","size":0,"id":3,"fields":[]}"#)
+        );
+
+        let module = Parser::new("endian = host struct A { }")
+            .unwrap()
+            .parse_module()
+            .unwrap();
+        assert!(module_json(&module).starts_with(r#"{"endianness":"host","portable":false,"#));
+    }
+
+    #[test]
+    fn test_parse_response() {
+        let mut generator = PluginGenerator::new("plugin");
+        generator
+            .add_file_path(PathBuf::from("out/schema"))
+            .unwrap();
+
+        let files = generator
+            .parse_response(r#"{"files": [{"name": "gen/schema.txt", "content": "hi\n"}]}"#)
+            .unwrap();
+        assert_eq!(
+            files,
+            [(PathBuf::from("out/gen/schema.txt"), "hi\n".to_string())]
+        );
+
+        let error = generator
+            .parse_response(r#"{"error": "unsupported"}"#)
+            .unwrap_err();
+        assert!(error.to_string().contains("plugin plugin: unsupported"));
+        assert!(
+            generator
+                .parse_response(r#"{"files": [{"name": "../escape", "content": ""}]}"#)
+                .is_err()
+        );
+        assert!(generator.parse_response("not json").is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_run_plugin() {
+        use std::{fs, os::unix::fs::PermissionsExt};
+
+        let dir = std::env::temp_dir().join(format!("onyx-plugin-test-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        let script = dir.join("plugin.sh");
+        fs::write(
+            &script,
+            format!(
+                "#!/bin/sh\ncat > {}\nprintf '{{\"files\":[{{\"name\":\"schema.txt\",\"content\":\"ok\"}}]}}'\n",
+                dir.join("request.json").display()
+            ),
+        )
+        .unwrap();
+        fs::set_permissions(&script, fs::Permissions::from_mode(0o755)).unwrap();
+
+        let module = Parser::new(SOURCE).unwrap().parse_module().unwrap();
+        let mut generator = PluginGenerator::new(&script);
+        generator.add_file_path(dir.join("schema")).unwrap();
+        let files = generator.generate(&module).unwrap();
+        assert_eq!(files, [(dir.join("schema.txt"), "ok".to_string())]);
+
+        let request = json::parse(&fs::read_to_string(dir.join("request.json")).unwrap()).unwrap();
+        assert_eq!(
+            request.get("stem").and_then(json::Value::as_str),
+            Some("schema")
+        );
+        let banner = request
+            .get("banner")
+            .and_then(json::Value::as_array)
+            .unwrap();
+        assert_eq!(
+            banner[0].as_str(),
+            Some("Automatically generated by Onyx IDL compiler. Do not edit.")
+        );
+        assert_eq!(
+            request.get("module").map(|m| m.get("endianness")),
+            Some(Some(&json::Value::String("big".into())))
+        );
+
+        let mut missing = PluginGenerator::new(dir.join("missing"));
+        missing.add_file_path(dir.join("schema")).unwrap();
+        assert!(missing.generate(&module).is_err());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -71,8 +71,14 @@ impl<W: Write> PythonGenerator<W> {
         }
     }
 
-    /// Generates a simple Python class definition.
-    pub fn generate_class(&mut self, name: &str, bases: &[&str]) -> io::Result<()> {
+    /// Generates a simple Python class definition, with `doc`, the doc comment of the
+    /// definition it is generated from, as its docstring.
+    pub fn generate_class(
+        &mut self,
+        name: &str,
+        bases: &[&str],
+        doc: Option<&str>,
+    ) -> io::Result<()> {
         let base_list = if bases.is_empty() {
             "".to_string()
         } else {
@@ -83,15 +89,8 @@ impl<W: Write> PythonGenerator<W> {
         self.increase_indent();
 
         // Add a docstring
-        let quote = if self.config.use_double_quotes {
-            "\"\"\""
-        } else {
-            "'''"
-        };
-        self.write_line(&format!(
-            "{}A generated Python class.{}{}",
-            quote, quote, "\n"
-        ))?;
+        self.write_docstring(doc.unwrap_or("A generated Python class."))?;
+        writeln!(&mut self.writer)?;
 
         // Add an initializer
         self.write_line("def __init__(self, value):")?;
@@ -108,6 +107,34 @@ impl<W: Write> PythonGenerator<W> {
         self.decrease_indent(); // Return to top level indentation
         Ok(())
     }
+
+    /// Writes `doc` as a docstring at the current indentation: its first line after the
+    /// opening quotes, and any others on lines of their own before the closing ones, so that
+    /// IDEs show it as the description of the class or function it opens.
+    pub fn write_docstring(&mut self, doc: &str) -> io::Result<()> {
+        let quote = if self.config.use_double_quotes {
+            "\"\"\""
+        } else {
+            "'''"
+        };
+        // Backslashes would start escapes, and the quotes would end the docstring early.
+        let escaped_quote: String = quote.chars().map(|c| format!("\\{c}")).collect();
+        let doc = doc.replace('\\', "\\\\").replace(quote, &escaped_quote);
+        let mut lines = doc.lines();
+        let first = lines.next().unwrap_or_default();
+        let rest: Vec<&str> = lines.collect();
+        if rest.is_empty() {
+            return self.write_line(&format!("{quote}{first}{quote}"));
+        }
+        self.write_line(&format!("{quote}{first}"))?;
+        for line in rest {
+            match line.is_empty() {
+                true => writeln!(&mut self.writer)?,
+                false => self.write_line(line)?,
+            }
+        }
+        self.write_line(quote)
+    }
 }
 
 impl<W: Write> CodeGenerator for PythonGenerator<W> {
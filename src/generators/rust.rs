@@ -1,10 +1,39 @@
-use std::{fmt::Write, path::PathBuf};
+use std::{
+    collections::BTreeMap,
+    fmt::Write,
+    path::{Component, Path, PathBuf},
+};
 
 use crate::{
-    ast::{Definition, EnumDef, Field, OnyxModule, PrimitiveType, Type, WireEndianness},
-    generators::{CodeGenerator, CompileError},
+    Span,
+    ast::{Condition, Definition, EnumDef, Field, OnyxModule, PrimitiveType, Type, WireEndianness},
+    diagnostic::{Diagnostic, codes},
+    dynamic::Value,
+    features::{Feature, used_features},
+    fingerprint,
+    generators::{
+        Banner, CodeGenerator, CompileError, SchemaSource, accessor_deprecation, accessor_field,
+        bit_mask, check_constant_names, check_order, comment_lines, definition_constants,
+        doc_lines, largest_message_size,
+        mangle::{apply_field_naming, mangle_reserved},
+        naming::NamingConvention,
+        own_identified_messages, same_layout,
+    },
+    hir::{self, Hir, Layout, Slot},
+    json, trace,
+    vectors::{self, LeafKind, Step, raw_bits},
 };
 
+/// Rust keywords, including those reserved for future use, which schema names are renamed away
+/// from with a trailing `_`.
+const RESERVED: &[&str] = &[
+    "Self", "abstract", "as", "async", "await", "become", "box", "break", "const", "continue",
+    "crate", "do", "dyn", "else", "enum", "extern", "false", "final", "fn", "for", "gen", "if",
+    "impl", "in", "let", "loop", "macro", "match", "mod", "move", "mut", "override", "priv", "pub",
+    "ref", "return", "self", "static", "struct", "super", "trait", "true", "try", "type", "typeof",
+    "unsafe", "unsized", "use", "virtual", "where", "while", "yield",
+];
+
 /// Configuration settings specific to Rust code generation
 #[derive(Debug, Clone)]
 pub struct RustConfig {
@@ -24,15 +53,59 @@ impl RustConfig {
     }
 }
 
+/// Package metadata for emitting a complete crate around the generated module.
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct CrateConfig {
+    /// The package name written to `Cargo.toml`.
+    pub name: String,
+    /// The package version written to `Cargo.toml`.
+    pub version: String,
+    /// The SPDX license expression written to `Cargo.toml`, which crates.io requires.
+    pub license: Option<String>,
+}
+
+impl CrateConfig {
+    /// Creates a crate configuration with the given package name and version.
+    pub fn new(name: impl Into<String>, version: impl Into<String>) -> Self {
+        CrateConfig {
+            name: name.into(),
+            version: version.into(),
+            license: None,
+        }
+    }
+
+    /// Sets the license of the crate, such as `MIT OR Apache-2.0`.
+    pub fn with_license(mut self, license: impl Into<String>) -> Self {
+        self.license = Some(license.into());
+        self
+    }
+}
+
 #[derive(Debug, Default)]
 /// The Rust code generator.
 ///
-/// Generates a Rust source file (.rs) for the given Onyx module.
+/// Generates a Rust source file (.rs) for the given Onyx module, or a complete crate when
+/// configured with [`RustGenerator::set_crate`].
 pub struct RustGenerator {
     config: RustConfig,
     output: String,
     file_path: PathBuf,
     file_stem: String,
+    crate_config: Option<CrateConfig>,
+    trace: bool,
+    shared_memory: bool,
+    message_arena: bool,
+    reflection: bool,
+    constants: bool,
+    vectors: bool,
+    source_map: Option<SchemaSource>,
+    banner: Banner,
+    /// The lines of `banner` for the module being generated.
+    banner_lines: Vec<String>,
+    field_naming: NamingConvention,
+    uses: Vec<String>,
+    lib_root: bool,
 }
 
 impl RustGenerator {
@@ -41,11 +114,206 @@ impl RustGenerator {
         self.file_path = file_path;
         self.file_stem = match self.file_path.file_stem() {
             Some(s) => s.to_string_lossy().to_string(),
-            None => return Err(CompileError(String::new())),
+            None => {
+                return Err(Diagnostic::error(format!(
+                    "output path '{}' has no file name",
+                    self.file_path.display()
+                ))
+                .with_code(codes::CODEGEN));
+            }
         };
         Ok(())
     }
 
+    /// Emits a complete crate instead of a single file.
+    ///
+    /// The crate is rooted in the directory of the file path: `Cargo.toml`, a `README.md`
+    /// listing the messages, structs, and enums, `src/lib.rs`, the generated module in `src/`,
+    /// and a layout test in `tests/`. Generated code only depends on `std`, so the manifest has
+    /// no dependencies or features.
+    pub fn set_crate(&mut self, config: CrateConfig) -> Result<(), CompileError> {
+        let valid = !config.name.is_empty()
+            && config
+                .name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+        if !valid {
+            return Err(
+                Diagnostic::error(format!("invalid crate name '{}'", config.name))
+                    .with_code(codes::CODEGEN)
+                    .with_help("crate names may only use ASCII letters, digits, '-', and '_'"),
+            );
+        }
+        self.crate_config = Some(config);
+        Ok(())
+    }
+
+    /// Emits a `trace_fields` method on each read-only view, which reports every field present
+    /// to a callback with its name, byte offset, and value.
+    ///
+    /// Nested structs report their fields under a dotted path, such as `start.x`, which makes
+    /// the trace of a whole message readable when debugging a malformed buffer.
+    pub fn set_trace(&mut self, trace: bool) {
+        self.trace = trace;
+    }
+
+    /// Emits an unsafe `from_raw_parts` constructor on each view, which places it directly on
+    /// memory such as a shared memory ring after checking the pointer, length, and alignment.
+    pub fn set_shared_memory(&mut self, shared_memory: bool) {
+        self.shared_memory = shared_memory;
+    }
+
+    /// Emits `MessageArena<N>`, a pool of `N` buffers sized for the largest message whose slots
+    /// return to the pool when dropped, so hot paths can obtain zeroed message buffers without
+    /// heap allocation. Modules without messages get no arena.
+    pub fn set_message_arena(&mut self, message_arena: bool) {
+        self.message_arena = message_arena;
+    }
+
+    /// Emits a `FIELDS` table of `FieldDescriptor`s on each read-only view, giving the name,
+    /// byte offset, bit width, and kind of every field, so that frameworks can reflect over a
+    /// layout at runtime without the schema.
+    pub fn set_reflection(&mut self, reflection: bool) {
+        self.reflection = reflection;
+    }
+
+    /// Emits `<stem>_constants.rs` next to the module, also declared in `lib.rs` in a crate: the
+    /// protocol numbers of every definition in one place, for code that does not use the
+    /// generated types.
+    ///
+    /// It has a module per definition, named in snake case, holding the value of each enum
+    /// variant, a message's `ID`, the `SIZE` in bytes, and the definition's
+    /// [`FINGERPRINT`](crate::fingerprint), and a `SCHEMA_FINGERPRINT` of the whole module.
+    pub fn set_constants(&mut self, constants: bool) {
+        self.constants = constants;
+    }
+
+    /// Emits `<stem>.vectors.json` next to the module, or at the root of a crate: the
+    /// [test vectors](crate::vectors) of every struct and message.
+    ///
+    /// A crate also gets `tests/vectors.rs`, which reads each vector's bytes through the views
+    /// and checks every field, then writes the value through the mutable views and checks the
+    /// bytes. With `endian = host`, the vectors hold the byte order of the machine that
+    /// generated them, so the test only runs on machines of that byte order.
+    pub fn set_vectors(&mut self, vectors: bool) {
+        self.vectors = vectors;
+    }
+
+    /// Emits a comment before each type and field accessor naming the line of `source` that
+    /// declared it, so that compiler errors and profiler hits in generated code can be traced
+    /// back to the schema.
+    pub fn set_source_map(&mut self, source: SchemaSource) {
+        self.source_map = Some(source);
+    }
+
+    /// Opens every file with `banner` rather than the default one.
+    pub fn set_banner(&mut self, banner: Banner) {
+        self.banner = banner;
+    }
+
+    /// Writes the accessors of each field in `convention`, such as `start_time()` and
+    /// `set_start_time()` for a field `startTime` in snake case. Names are used as written by
+    /// default.
+    pub fn set_field_naming(&mut self, convention: NamingConvention) {
+        self.field_naming = convention;
+    }
+
+    /// Imports another module, such as the one generated for a module whose types this one
+    /// uses, as `use path;`.
+    pub fn add_use(&mut self, path: impl Into<String>) {
+        self.uses.push(path.into());
+    }
+
+    /// Names the root of a [module tree](RustGenerator::module_tree) `lib.rs`, making it a
+    /// crate root, instead of `mod.rs`.
+    pub fn set_lib_root(&mut self, lib_root: bool) {
+        self.lib_root = lib_root;
+    }
+
+    /// Returns the module files that wire generated `.rs` files, given by their paths relative
+    /// to the root of the tree, into one module tree.
+    ///
+    /// Every directory gets a `mod.rs` that declares its files and subdirectories with
+    /// `pub mod` and re-exports their items with `pub use`, so the root re-exports the items of
+    /// every file. Each file and directory name must be a valid Rust module name.
+    pub fn module_tree(&self, files: &[PathBuf]) -> Result<Vec<(PathBuf, String)>, CompileError> {
+        let root_file = if self.lib_root { "lib.rs" } else { "mod.rs" };
+        // The children of each directory, and whether each is itself a directory.
+        let mut directories: BTreeMap<PathBuf, BTreeMap<String, bool>> = BTreeMap::new();
+        directories.insert(PathBuf::new(), BTreeMap::new());
+        for file in files {
+            let invalid = |reason: String| {
+                Diagnostic::error(format!(
+                    "cannot add '{}' to a module tree, as {reason}",
+                    file.display()
+                ))
+                .with_code(codes::CODEGEN)
+            };
+            if file.extension().is_none_or(|extension| extension != "rs") {
+                return Err(invalid("it is not a .rs file".to_string()));
+            }
+            if !file.components().all(|c| matches!(c, Component::Normal(_))) {
+                return Err(invalid(
+                    "it is not relative to the root of the tree".to_string(),
+                ));
+            }
+            if file.as_path() == Path::new(root_file) {
+                return Err(invalid(format!("the root of the tree is {root_file}")));
+            }
+            let mut child = file.with_extension("");
+            let mut is_dir = false;
+            while let (Some(parent), Some(name)) = (child.parent(), child.file_name()) {
+                let name = name.to_string_lossy().into_owned();
+                if !is_module_name(&name) {
+                    return Err(invalid(format!("'{name}' is not a valid module name")));
+                }
+                let children = directories.entry(parent.to_path_buf()).or_default();
+                match children.insert(name.clone(), is_dir) {
+                    Some(true) if is_dir => break,
+                    Some(false) if !is_dir => {
+                        return Err(invalid("it is given more than once".to_string()));
+                    }
+                    Some(_) => {
+                        return Err(invalid(format!(
+                            "module '{name}' would be both a file and a directory"
+                        )));
+                    }
+                    None => {}
+                }
+                child = parent.to_path_buf();
+                is_dir = true;
+            }
+        }
+
+        // The tree serves many schemas, so its banner names none of them.
+        let banner = Banner {
+            source: None,
+            fingerprint: false,
+            ..self.banner.clone()
+        }
+        .lines(&OnyxModule::default())?;
+        let mut tree = Vec::new();
+        for (directory, children) in directories {
+            let mut out = String::new();
+            writeln!(out, "{}", comment_lines(&banner, "//")).unwrap();
+            for name in children.keys() {
+                writeln!(out, "pub mod {name};").unwrap();
+            }
+            if !children.is_empty() {
+                writeln!(out).unwrap();
+            }
+            for name in children.keys() {
+                writeln!(out, "#[allow(unused_imports)]\npub use {name}::*;").unwrap();
+            }
+            let file = match directory.as_os_str().is_empty() {
+                true => root_file,
+                false => "mod.rs",
+            };
+            tree.push((directory.join(file), out));
+        }
+        Ok(tree)
+    }
+
     /// Maps Onyx PrimitiveType to Rust type strings.
     fn map_primitive_type_to_rust(&self, p: &PrimitiveType) -> &'static str {
         match p {
@@ -65,91 +333,331 @@ impl RustGenerator {
 
     fn get_primitive_rust_type(&self, type_info: &Type) -> String {
         match type_info {
-            Type::Primitive(p) => self.map_primitive_type_to_rust(p).to_string(),
-            Type::Custom(s) => s.clone(),
+            Type::Primitive(p) | Type::Fixed { base: p, .. } => {
+                self.map_primitive_type_to_rust(p).to_string()
+            }
+            Type::Uuid | Type::Bytes(_) => format!("[u8; {}]", type_info.byte_len().unwrap()),
+            Type::Custom(s) => s.to_string(),
+            Type::Vector { element, .. } => self.get_primitive_rust_type(element),
         }
     }
 
-    fn get_field_groups<'a>(&self, fields: &'a Vec<Field>) -> Vec<Vec<&'a Field>> {
-        let mut field_groups: Vec<Vec<&Field>> = Vec::new();
-        let mut current_group: Vec<&Field> = Vec::new();
+    /// Returns a Rust expression for the size of `type_info` in bytes, built from the sizes of
+    /// the Rust types and buffers it is made of rather than from the computed layout.
+    fn type_size_expr(&self, module: &OnyxModule, type_info: &Type) -> String {
+        let size_of = |p: &PrimitiveType| {
+            format!(
+                "std::mem::size_of::<{}>()",
+                self.map_primitive_type_to_rust(p)
+            )
+        };
+        match type_info {
+            Type::Primitive(p) | Type::Fixed { base: p, .. } => size_of(p),
+            Type::Uuid | Type::Bytes(_) => type_info.byte_len().unwrap().to_string(),
+            Type::Custom(name) => match module.definitions.get(name) {
+                Some(Definition::Enum(e)) => size_of(&e.underlying_type),
+                _ => format!("{}_SIZE", name.as_str().to_ascii_uppercase()),
+            },
+            Type::Vector { element, capacity } => format!(
+                "({} + {capacity} * {})",
+                size_of(&type_info.length_type().unwrap()),
+                self.type_size_expr(module, element)
+            ),
+        }
+    }
 
-        for field in fields {
-            if field.bit_field_size.is_some() {
-                // Group consecutive bit-fields
-                current_group.push(field);
-            } else {
-                // Non-bit-field encountered. Process the current group, then the standalone field.
-                if !current_group.is_empty() {
-                    field_groups.push(std::mem::take(&mut current_group));
-                }
-                field_groups.push(vec![field]); // Non-bit-field is a group of size 1
+    /// Writes the source map comment for the declaration at `span`, if a source map is set,
+    /// after a blank line so that it heads the code generated for it.
+    fn write_source_comment(&mut self, span: Span, indent: usize) {
+        if let Some(comment) = self.source_map.as_ref().and_then(|s| s.comment(span)) {
+            if !self.output.ends_with("\n\n") {
+                self.output.push('\n');
             }
+            let indent = self.config.get_indent(indent);
+            writeln!(self.output, "{indent}{comment}").unwrap();
         }
-        if !current_group.is_empty() {
-            field_groups.push(current_group);
+    }
+
+    /// Writes the `SIZE_BYTES` and `FIELD_OFFSET_*` constants of a view.
+    fn write_layout_constants(&mut self, struct_name: &str, layout: &Layout) {
+        let indent = self.config.get_indent(1);
+        let out = &mut self.output;
+        writeln!(out, "{indent}/// Size of {struct_name} in bytes.").unwrap();
+        writeln!(
+            out,
+            "{indent}pub const SIZE_BYTES: usize = {}_SIZE;",
+            struct_name.to_ascii_uppercase()
+        )
+        .unwrap();
+        for field in layout.fields() {
+            let (name, offset) = (field.name.as_str(), field.offset);
+            let doc = match field.bit_field_size {
+                Some(_) => format!("Byte offset of the bit-field container holding {name}."),
+                None => format!("Byte offset of {name}."),
+            };
+            writeln!(out, "{indent}/// {doc}").unwrap();
+            writeln!(
+                out,
+                "{indent}pub const FIELD_OFFSET_{}: usize = {offset};",
+                name.to_ascii_uppercase()
+            )
+            .unwrap();
         }
-        field_groups
+        writeln!(out).unwrap();
     }
 
-    fn get_serialized_size(&self, module: &OnyxModule, def: &Definition) -> usize {
-        match def {
-            Definition::Enum(e) => e.underlying_type.get_bit_width() / 8,
-            Definition::Struct(s) => self.get_fields_size(module, &s.fields),
-            Definition::Message(m) => self.get_fields_size(module, &m.fields),
+    /// Writes the `FIELDS` table of the view of `struct_name`.
+    fn write_field_descriptors(&mut self, struct_name: &str, layout: &Layout) {
+        let i1 = self.config.get_indent(1);
+        let i2 = self.config.get_indent(2);
+        let out = &mut self.output;
+        writeln!(out, "{i1}/// The fields of {struct_name}, in wire order.").unwrap();
+        writeln!(out, "{i1}pub const FIELDS: &'static [FieldDescriptor] = &[").unwrap();
+        for field in layout.fields() {
+            let kind = match field.ty {
+                hir::TypeRef::Primitive(PrimitiveType::Bool) => "Bool",
+                hir::TypeRef::Primitive(
+                    PrimitiveType::U8
+                    | PrimitiveType::U16
+                    | PrimitiveType::U32
+                    | PrimitiveType::U64,
+                ) => "Unsigned",
+                hir::TypeRef::Primitive(
+                    PrimitiveType::I8
+                    | PrimitiveType::I16
+                    | PrimitiveType::I32
+                    | PrimitiveType::I64,
+                ) => "Signed",
+                hir::TypeRef::Primitive(PrimitiveType::F32 | PrimitiveType::F64) => "Float",
+                hir::TypeRef::Fixed { .. } => "Fixed",
+                hir::TypeRef::Uuid => "Uuid",
+                hir::TypeRef::Bytes(_) => "Bytes",
+                hir::TypeRef::Enum(_) => "Enum",
+                hir::TypeRef::Struct(_) | hir::TypeRef::Message(_) => "Struct",
+                hir::TypeRef::Vector { .. } => "Vector",
+            };
+            writeln!(
+                out,
+                "{i2}FieldDescriptor {{ name: {:?}, offset: {}, bit_offset: {}, bits: {}, kind: FieldKind::{kind}, type_name: {:?}, conditional: {} }},",
+                field.name.as_str(),
+                field.offset,
+                field.bit_offset,
+                field.bits,
+                field.type_info.to_string(),
+                field.condition.is_some()
+            )
+            .unwrap();
         }
+        writeln!(out, "{i1}];\n").unwrap();
     }
 
-    fn get_fields_size(&self, module: &OnyxModule, fields: &Vec<Field>) -> usize {
-        let groups = self.get_field_groups(fields);
-        let mut size = 0;
-        for group in groups {
-            let first_field = group[0];
-            if group.len() > 1 || first_field.bit_field_size.is_some() {
-                let mut container_bits = 0;
-                for field in group {
-                    container_bits += field.get_bit_width(module);
-                }
-                size += container_bits.div_ceil(8);
-            } else {
-                match &first_field.type_info {
-                    Type::Primitive(p) => size += p.get_bit_width() / 8,
-                    Type::Custom(name) => {
-                        if let Some(def) = module.definitions.get(name) {
-                            size += self.get_serialized_size(module, def);
-                        }
-                    }
-                }
+    /// Writes `FieldDescriptor` and `FieldKind`, which the `FIELDS` tables of views list.
+    fn write_field_descriptor_types(&mut self) {
+        let i1 = self.config.get_indent(1);
+        let out = &mut self.output;
+        writeln!(
+            out,
+            "\n/// Where a field sits in its struct or message, as listed in the `FIELDS` table of a view."
+        )
+        .unwrap();
+        writeln!(out, "#[derive(Debug, Clone, Copy, PartialEq, Eq)]").unwrap();
+        writeln!(out, "pub struct FieldDescriptor {{").unwrap();
+        for (doc, field) in [
+            ("The name of the field's accessors.", "name: &'static str"),
+            (
+                "The byte offset of the field, or of the container holding it if it is a bit-field.",
+                "offset: usize",
+            ),
+            (
+                "The offset of a bit-field within its container, from the least significant bit.",
+                "bit_offset: usize",
+            ),
+            ("The width of the field in bits.", "bits: usize"),
+            ("The kind of value the field holds.", "kind: FieldKind"),
+            (
+                "The type of the field as written in the schema, such as `vec<Point, 4>`.",
+                "type_name: &'static str",
+            ),
+            (
+                "Whether the field is only present when its condition holds.",
+                "conditional: bool",
+            ),
+        ] {
+            writeln!(out, "{i1}/// {doc}").unwrap();
+            writeln!(out, "{i1}pub {field},").unwrap();
+        }
+        writeln!(out, "}}").unwrap();
+
+        writeln!(out, "\n/// The kind of value a field holds.").unwrap();
+        writeln!(out, "#[derive(Debug, Clone, Copy, PartialEq, Eq)]").unwrap();
+        writeln!(out, "pub enum FieldKind {{").unwrap();
+        for (doc, kind) in [
+            ("A `bool`.", "Bool"),
+            ("An unsigned integer.", "Unsigned"),
+            ("A signed integer.", "Signed"),
+            ("A floating-point number.", "Float"),
+            ("A fixed-point number, carried as an integer.", "Fixed"),
+            ("A 16-byte UUID.", "Uuid"),
+            ("A byte array.", "Bytes"),
+            ("An enum, carried as its underlying type.", "Enum"),
+            ("A struct or message, embedded in place.", "Struct"),
+            (
+                "A bounded vector: a length prefix followed by its elements.",
+                "Vector",
+            ),
+        ] {
+            writeln!(out, "{i1}/// {doc}").unwrap();
+            writeln!(out, "{i1}{kind},").unwrap();
+        }
+        writeln!(out, "}}").unwrap();
+    }
+
+    /// Writes compile-time assertions that each field of a view starts where the one before it
+    /// ends, after any padding of the `align` directive, and that the last one ends at the size
+    /// of the buffer.
+    fn write_layout_assertions(&mut self, struct_name: &str, layout: &Layout, module: &OnyxModule) {
+        let view = format!("{struct_name}View");
+        if !layout.slots.is_empty() {
+            writeln!(
+                self.output,
+                "\n// Fails to compile if {struct_name} does not match its Onyx layout."
+            )
+            .unwrap();
+        }
+        let mut end: Option<String> = None;
+        let mut padded_from = 0;
+        for slot in &layout.slots {
+            let name = slot.first().name.as_str();
+            let offset = format!("{view}::FIELD_OFFSET_{}", name.to_ascii_uppercase());
+            if let Some(end) = &mut end
+                && slot.offset > padded_from
+            {
+                write!(end, " + {}", slot.offset - padded_from).unwrap();
+            }
+            padded_from = slot.offset + slot.size;
+            if let Some(end) = &end {
+                writeln!(
+                    self.output,
+                    "const _: () = assert!({offset} == {end}, \"{struct_name}.{name} does not match its Onyx offset\");"
+                )
+                .unwrap();
             }
+            let size = match slot.is_bit_field_container() {
+                true => slot.size.to_string(),
+                false => self.type_size_expr(module, &slot.first().type_info),
+            };
+            end = Some(format!("{offset} + {size}"));
+        }
+        if let Some(end) = end {
+            writeln!(
+                self.output,
+                "const _: () = assert!({end} == {view}::SIZE_BYTES, \"{struct_name} does not match its Onyx size\");"
+            )
+            .unwrap();
+        }
+    }
+
+    fn write_header(&mut self, module: &OnyxModule) {
+        self.output
+            .push_str(&comment_lines(&self.banner_lines, "//"));
+        if used_features(module).contains(&Feature::Deprecations) {
+            // The generated code itself uses deprecated items; only their users are warned.
+            writeln!(self.output, "#![allow(deprecated)]").unwrap();
+        }
+        writeln!(self.output, "\nuse std::convert::TryInto;").unwrap();
+        for path in &self.uses {
+            writeln!(self.output, "#[allow(unused_imports)]\nuse {path};").unwrap();
         }
-        size
+        writeln!(self.output).unwrap();
     }
 
-    fn write_header(&mut self) {
+    /// Returns the protocol constants file of `module`, the module as parsed.
+    fn constants_file(&self, module: &OnyxModule) -> Result<String, CompileError> {
+        let i1 = self.config.get_indent(1);
+        let mut out = String::new();
+        writeln!(out, "{}", comment_lines(&self.banner_lines, "//")).unwrap();
         writeln!(
-            self.output,
-            "// Automatically generated by Onyx IDL compiler"
+            out,
+            "/// The fingerprint of the whole schema, which changes with any of its definitions."
         )
         .unwrap();
-        writeln!(self.output, "\nuse std::convert::TryInto;\n").unwrap();
+        let schema = fingerprint::module(&hir::lower(module)?);
+        writeln!(out, "pub const SCHEMA_FINGERPRINT: u64 = {schema:#018x};").unwrap();
+
+        let definitions = definition_constants(module)?;
+        let scope = |def: &Definition| {
+            let name = NamingConvention::SnakeCase.apply(def.name());
+            match RESERVED.contains(&name.as_str()) {
+                true => format!("{name}_"),
+                false => name,
+            }
+        };
+        let scopes: Vec<String> = definitions.iter().map(|(def, ..)| scope(def)).collect();
+        check_constant_names("the constants module", scopes.iter().map(String::as_str))?;
+        for ((def, size, fingerprint), scope) in definitions.into_iter().zip(scopes) {
+            let mut constants = Vec::new();
+            let kind = match def {
+                Definition::Enum(e) => {
+                    let ty = self.map_primitive_type_to_rust(&e.underlying_type);
+                    for (variant, value) in e.variants.iter().zip(e.variant_values()) {
+                        let name = NamingConvention::ScreamingSnakeCase.apply(&variant.name);
+                        constants.push((name, ty, value.to_string()));
+                    }
+                    if e.flags { "flags" } else { "enum" }
+                }
+                Definition::Message(m) => {
+                    if let Some(id) = m.id {
+                        constants.push(("ID".to_string(), "u16", id.to_string()));
+                    }
+                    "message"
+                }
+                Definition::Struct(_) => "struct",
+            };
+            constants.push(("SIZE".to_string(), "usize", size.to_string()));
+            constants.push((
+                "FINGERPRINT".to_string(),
+                "u64",
+                format!("{fingerprint:#018x}"),
+            ));
+            check_constant_names(
+                &format!("the constants of {kind} '{}'", def.name()),
+                constants.iter().map(|(name, ..)| name.as_str()),
+            )?;
+
+            writeln!(out, "\n/// The constants of the {kind} `{}`.", def.name()).unwrap();
+            writeln!(out, "pub mod {scope} {{").unwrap();
+            for (name, ty, value) in constants {
+                writeln!(out, "{i1}pub const {name}: {ty} = {value};").unwrap();
+            }
+            writeln!(out, "}}").unwrap();
+        }
+        Ok(out)
     }
 
     fn write_enum(&mut self, e: &EnumDef) -> Result<(), CompileError> {
+        if e.flags {
+            return self.write_flags(e);
+        }
         let underlying_type = self.map_primitive_type_to_rust(&e.underlying_type);
-        writeln!(self.output, "#[repr({underlying_type})]").unwrap();
+        let values = e.variant_values();
+        self.output.push_str(&doc_lines(e.doc.as_deref(), ""));
+        self.write_deprecated(e.deprecated.as_deref());
+        if !e.open {
+            writeln!(self.output, "#[repr({underlying_type})]").unwrap();
+        }
         writeln!(self.output, "#[derive(Debug, Clone, Copy, PartialEq, Eq)]").unwrap();
         writeln!(self.output, "pub enum {} {{", e.name).unwrap();
 
-        let mut current_value: u64 = 0;
-        for variant in &e.variants {
-            let assigned_value = if let Some(val) = variant.value {
-                current_value = val + 1;
-                format!(" = {val}")
+        for (variant, value) in e.variants.iter().zip(&values) {
+            // An open enum has a field in `Unknown`, so its values are mapped by `From` instead.
+            let assigned_value = if e.open {
+                String::new()
             } else {
-                let val = current_value;
-                current_value += 1;
-                format!(" = {val}")
+                format!(" = {value}")
             };
+            let indent = self.config.get_indent(1);
+            self.output
+                .push_str(&doc_lines(variant.doc.as_deref(), &indent));
             writeln!(
                 self.output,
                 "{}{}{},",
@@ -159,6 +667,20 @@ impl RustGenerator {
             )
             .unwrap();
         }
+        if e.open {
+            writeln!(
+                self.output,
+                "{}/// A value that no variant declares.",
+                self.config.get_indent(1)
+            )
+            .unwrap();
+            writeln!(
+                self.output,
+                "{}Unknown({underlying_type}),",
+                self.config.get_indent(1)
+            )
+            .unwrap();
+        }
         writeln!(self.output, "}}").unwrap();
 
         // Implement Default for Enum (first variant)
@@ -181,28 +703,206 @@ impl RustGenerator {
             writeln!(self.output, "}}").unwrap();
         }
 
+        self.write_enum_conversions(e, &values);
         Ok(())
     }
 
-    fn write_view_structs(
-        &mut self,
-        struct_name: &String,
-        field_groups: &Vec<Vec<&Field>>,
-        module: &OnyxModule,
-        def: &Definition,
-    ) {
+    /// Writes the conversions from the underlying integer: `From` both ways for an open enum,
+    /// which maps undeclared values to `Unknown`, and `TryFrom` for a closed enum, which returns
+    /// them as the error.
+    fn write_enum_conversions(&mut self, e: &EnumDef, values: &[u64]) {
+        let t = self.map_primitive_type_to_rust(&e.underlying_type);
+        let name = e.name.as_str();
+        let indent = |level| self.config.get_indent(level);
+        let (i1, i2, i3) = (indent(1), indent(2), indent(3));
+        let out = &mut self.output;
+        if e.open {
+            writeln!(out, "\nimpl From<{t}> for {name} {{").unwrap();
+            writeln!(out, "{i1}fn from(raw: {t}) -> Self {{").unwrap();
+            writeln!(out, "{i2}match raw {{").unwrap();
+            for (variant, value) in e.variants.iter().zip(values) {
+                writeln!(out, "{i3}{value} => Self::{},", variant.name).unwrap();
+            }
+            writeln!(out, "{i3}_ => Self::Unknown(raw),").unwrap();
+            writeln!(out, "{i2}}}").unwrap();
+            writeln!(out, "{i1}}}").unwrap();
+            writeln!(out, "}}").unwrap();
+
+            writeln!(out, "\nimpl From<{name}> for {t} {{").unwrap();
+            writeln!(out, "{i1}fn from(value: {name}) -> Self {{").unwrap();
+            writeln!(out, "{i2}match value {{").unwrap();
+            for (variant, value) in e.variants.iter().zip(values) {
+                writeln!(out, "{i3}{name}::{} => {value},", variant.name).unwrap();
+            }
+            writeln!(out, "{i3}{name}::Unknown(raw) => raw,").unwrap();
+            writeln!(out, "{i2}}}").unwrap();
+            writeln!(out, "{i1}}}").unwrap();
+            writeln!(out, "}}").unwrap();
+        } else {
+            writeln!(out, "\nimpl TryFrom<{t}> for {name} {{").unwrap();
+            writeln!(out, "{i1}type Error = {t};").unwrap();
+            writeln!(out, "\n{i1}fn try_from(raw: {t}) -> Result<Self, {t}> {{").unwrap();
+            writeln!(out, "{i2}match raw {{").unwrap();
+            for (variant, value) in e.variants.iter().zip(values) {
+                writeln!(out, "{i3}{value} => Ok(Self::{}),", variant.name).unwrap();
+            }
+            writeln!(out, "{i3}_ => Err(raw),").unwrap();
+            writeln!(out, "{i2}}}").unwrap();
+            writeln!(out, "{i1}}}").unwrap();
+            writeln!(out, "}}").unwrap();
+
+            writeln!(out, "\nimpl From<{name}> for {t} {{").unwrap();
+            writeln!(out, "{i1}fn from(value: {name}) -> Self {{").unwrap();
+            writeln!(out, "{i2}value as {t}").unwrap();
+            writeln!(out, "{i1}}}").unwrap();
+            writeln!(out, "}}").unwrap();
+        }
+    }
+
+    /// Returns the type an enum accessor returns, and the expression that converts `raw`, a
+    /// value of the underlying type, to it. Reading a closed enum fails on undeclared values.
+    fn enum_read(&self, e: &EnumDef, raw: &str) -> (String, String) {
+        let name = e.name.as_str();
+        if e.flags {
+            (name.to_string(), format!("{name}::from_bits_retain({raw})"))
+        } else if e.open {
+            (name.to_string(), format!("{name}::from({raw})"))
+        } else {
+            let t = self.map_primitive_type_to_rust(&e.underlying_type);
+            (
+                format!("Result<{name}, {t}>"),
+                format!("{name}::try_from({raw})"),
+            )
+        }
+    }
+
+    /// Returns the expression that converts the enum `value` to its underlying type.
+    fn enum_write(&self, e: &EnumDef, value: &str) -> String {
+        if e.flags {
+            format!("{value}.bits()")
+        } else {
+            let t = self.map_primitive_type_to_rust(&e.underlying_type);
+            format!("{t}::from({value})")
+        }
+    }
+
+    /// Writes a flags enum as a transparent wrapper over its underlying integer, with an
+    /// associated constant per variant and the bitwise operators.
+    fn write_flags(&mut self, e: &EnumDef) -> Result<(), CompileError> {
+        let t = self.map_primitive_type_to_rust(&e.underlying_type);
+        let name = e.name.as_str();
+        let all = e
+            .variants
+            .iter()
+            .zip(e.variant_values())
+            .filter(|(variant, _)| !variant.composite)
+            .fold(0u64, |bits, (_, value)| bits | value);
+        self.output.push_str(&doc_lines(e.doc.as_deref(), ""));
+        self.write_deprecated(e.deprecated.as_deref());
+        let indent = |level| self.config.get_indent(level);
+        let (i1, i2, i3) = (indent(1), indent(2), indent(3));
+        let out = &mut self.output;
+        writeln!(out, "#[repr(transparent)]").unwrap();
+        writeln!(
+            out,
+            "#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]"
+        )
+        .unwrap();
+        writeln!(out, "pub struct {name}({t});").unwrap();
+
+        writeln!(
+            out,
+            "
+#[allow(non_upper_case_globals)]"
+        )
+        .unwrap();
+        writeln!(out, "impl {name} {{").unwrap();
+        for (variant, value) in e.variants.iter().zip(e.variant_values()) {
+            out.push_str(&doc_lines(variant.doc.as_deref(), &i1));
+            writeln!(out, "{i1}pub const {}: Self = Self({value});", variant.name).unwrap();
+        }
+        let methods = [
+            "pub const fn empty() -> Self {\n\tSelf(0)\n}".to_string(),
+            format!("pub const fn all() -> Self {{\n\tSelf({all})\n}}"),
+            format!("pub const fn bits(self) -> {t} {{\n\tself.0\n}}"),
+            format!(
+                "/// Returns `None` if any bit is set that no variant declares.\n\
+                 pub const fn from_bits(bits: {t}) -> Option<Self> {{\n\
+                 \tif bits & !Self::all().0 == 0 {{\n\t\tSome(Self(bits))\n\
+                 \t}} else {{\n\t\tNone\n\t}}\n}}"
+            ),
+            format!(
+                "pub const fn from_bits_truncate(bits: {t}) -> Self {{\n\tSelf(bits & Self::all().0)\n}}"
+            ),
+            format!("pub const fn from_bits_retain(bits: {t}) -> Self {{\n\tSelf(bits)\n}}"),
+            "pub const fn is_empty(self) -> bool {\n\tself.0 == 0\n}".to_string(),
+            "pub const fn contains(self, other: Self) -> bool {\n\tself.0 & other.0 == other.0\n}"
+                .to_string(),
+            "pub const fn intersects(self, other: Self) -> bool {\n\tself.0 & other.0 != 0\n}"
+                .to_string(),
+            "pub fn insert(&mut self, other: Self) {\n\tself.0 |= other.0;\n}".to_string(),
+            "pub fn remove(&mut self, other: Self) {\n\tself.0 &= !other.0;\n}".to_string(),
+            "pub fn toggle(&mut self, other: Self) {\n\tself.0 ^= other.0;\n}".to_string(),
+        ];
+        for method in methods {
+            out.push('\n');
+            for line in method.lines() {
+                let depth = line.len() - line.trim_start_matches('\t').len();
+                let body = line.trim_start_matches('\t');
+                writeln!(out, "{}{body}", [&i1, &i2, &i3][depth]).unwrap();
+            }
+        }
+        writeln!(out, "}}").unwrap();
+
+        for (op, method, symbol, expr) in [
+            ("BitOr", "bitor", "|", "self.0 | rhs.0"),
+            ("BitAnd", "bitand", "&", "self.0 & rhs.0"),
+            ("BitXor", "bitxor", "^", "self.0 ^ rhs.0"),
+            ("Sub", "sub", "-", "self.0 & !rhs.0"),
+        ] {
+            writeln!(out, "\nimpl std::ops::{op} for {name} {{").unwrap();
+            writeln!(out, "{i1}type Output = Self;").unwrap();
+            writeln!(out, "{i1}fn {method}(self, rhs: Self) -> Self {{").unwrap();
+            writeln!(out, "{i2}Self({expr})").unwrap();
+            writeln!(out, "{i1}}}").unwrap();
+            writeln!(out, "}}").unwrap();
+            writeln!(out, "\nimpl std::ops::{op}Assign for {name} {{").unwrap();
+            writeln!(out, "{i1}fn {method}_assign(&mut self, rhs: Self) {{").unwrap();
+            writeln!(out, "{i2}*self = *self {symbol} rhs;").unwrap();
+            writeln!(out, "{i1}}}").unwrap();
+            writeln!(out, "}}").unwrap();
+        }
+        writeln!(out, "\nimpl std::ops::Not for {name} {{").unwrap();
+        writeln!(out, "{i1}type Output = Self;").unwrap();
+        writeln!(out, "{i1}fn not(self) -> Self {{").unwrap();
+        writeln!(out, "{i2}Self::from_bits_truncate(!self.0)").unwrap();
+        writeln!(out, "{i1}}}").unwrap();
+        writeln!(out, "}}").unwrap();
+        Ok(())
+    }
+
+    fn write_view_structs(&mut self, struct_name: &str, layout: &Layout, hir: &Hir) {
         let const_struct_name = struct_name.to_ascii_uppercase();
-        let size = self.get_serialized_size(module, def);
+        let size = layout.size;
+        let def = hir.module.definitions.get(&struct_name.into());
+        let deprecated = def.and_then(Definition::deprecated);
+        // The description from the schema follows the one of each view.
+        let doc = match def.and_then(Definition::doc) {
+            Some(doc) => format!("///\n{}", doc_lines(Some(doc), "")),
+            None => String::new(),
+        };
 
         writeln!(self.output, "\n/// Size of {struct_name} in bytes.").unwrap();
+        // Visible to the other modules of a tree, whose layout checks use it.
         writeln!(
             self.output,
-            "const {const_struct_name}_SIZE: usize = {size};"
+            "pub(crate) const {const_struct_name}_SIZE: usize = {size};"
         )
         .unwrap();
 
         // Buffer type alias
         writeln!(self.output, "/// Buffer type alias for {struct_name}.").unwrap();
+        self.write_deprecated(deprecated);
         writeln!(
             self.output,
             "pub type {struct_name}Buffer = [u8; {const_struct_name}_SIZE];"
@@ -211,6 +911,8 @@ impl RustGenerator {
 
         // View (Read-only)
         writeln!(self.output, "\n/// Read-only view of {struct_name}.").unwrap();
+        self.output.push_str(&doc);
+        self.write_deprecated(deprecated);
         writeln!(self.output, "#[derive(Debug, Clone, Copy)]").unwrap();
         writeln!(self.output, "pub struct {struct_name}View<'a> {{").unwrap();
         writeln!(
@@ -224,6 +926,8 @@ impl RustGenerator {
 
         // MutView (Read-Write)
         writeln!(self.output, "\n/// Read-write view of {struct_name}.").unwrap();
+        self.output.push_str(&doc);
+        self.write_deprecated(deprecated);
         writeln!(self.output, "#[derive(Debug)]").unwrap();
         writeln!(self.output, "pub struct {struct_name}MutView<'a> {{").unwrap();
         writeln!(
@@ -237,6 +941,10 @@ impl RustGenerator {
 
         // Impl View
         writeln!(self.output, "\nimpl<'a> {struct_name}View<'a> {{").unwrap();
+        self.write_layout_constants(struct_name, layout);
+        if self.reflection {
+            self.write_field_descriptors(struct_name, layout);
+        }
 
         writeln!(
             self.output,
@@ -253,9 +961,16 @@ impl RustGenerator {
         .unwrap();
         writeln!(self.output, "{}Self {{ data }}", self.config.get_indent(2)).unwrap();
         writeln!(self.output, "{}}}", self.config.get_indent(1)).unwrap();
+        if self.shared_memory {
+            self.write_from_raw_parts(struct_name, false);
+        }
 
-        self.write_accessors(field_groups, module, false);
+        self.write_accessors(struct_name, layout, hir.module, false);
+        if self.trace {
+            self.write_field_trace(struct_name, layout, hir);
+        }
         writeln!(self.output, "}}").unwrap();
+        self.write_layout_assertions(struct_name, layout, hir.module);
 
         // Impl MutView
         writeln!(self.output, "\nimpl<'a> {struct_name}MutView<'a> {{").unwrap();
@@ -275,173 +990,226 @@ impl RustGenerator {
         .unwrap();
         writeln!(self.output, "{}Self {{ data }}", self.config.get_indent(2)).unwrap();
         writeln!(self.output, "{}}}", self.config.get_indent(1)).unwrap();
+        if self.shared_memory {
+            self.write_from_raw_parts(struct_name, true);
+        }
 
-        self.write_accessors(field_groups, module, true);
+        self.write_accessors(struct_name, layout, hir.module, true);
         writeln!(self.output, "}}").unwrap();
+
+        for field in layout.fields() {
+            if let Type::Vector { element, capacity } = &field.type_info {
+                self.write_vector_views(struct_name, field, element, *capacity, hir);
+            }
+        }
+    }
+
+    /// Writes the `from_raw_parts` constructor of the read-only or read-write view of
+    /// `struct_name`.
+    fn write_from_raw_parts(&mut self, struct_name: &str, is_mut: bool) {
+        let i1 = self.config.get_indent(1);
+        let i2 = self.config.get_indent(2);
+        let i3 = self.config.get_indent(3);
+        let size = format!("{}_SIZE", struct_name.to_ascii_uppercase());
+        let (ptr, reference, access, others, view) = match is_mut {
+            true => ("*mut u8", "&mut *", "reads and writes", "access", "MutView"),
+            false => ("*const u8", "&*", "reads", "write", "View"),
+        };
+        let out = &mut self.output;
+        writeln!(
+            out,
+            "\n{i1}/// Creates a {struct_name}{view} of the first {size} of `len` bytes at `ptr`, such as"
+        )
+        .unwrap();
+        writeln!(
+            out,
+            "{i1}/// a shared memory mapping. Returns `None` if `ptr` is null, misaligned, or `len`"
+        )
+        .unwrap();
+        writeln!(out, "{i1}/// is too short.").unwrap();
+        writeln!(out, "{i1}///").unwrap();
+        writeln!(out, "{i1}/// # Safety").unwrap();
+        writeln!(out, "{i1}///").unwrap();
+        writeln!(
+            out,
+            "{i1}/// `ptr` must be valid for {access} of `len` bytes for `'a`, and no other reference"
+        )
+        .unwrap();
+        writeln!(out, "{i1}/// may {others} them while the view exists.").unwrap();
+        writeln!(
+            out,
+            "{i1}pub unsafe fn from_raw_parts(ptr: {ptr}, len: usize) -> Option<Self> {{"
+        )
+        .unwrap();
+        writeln!(
+            out,
+            "{i2}if ptr.is_null() || len < {size} || ptr as usize % std::mem::align_of::<{struct_name}Buffer>() != 0 {{"
+        )
+        .unwrap();
+        writeln!(out, "{i3}return None;").unwrap();
+        writeln!(out, "{i2}}}").unwrap();
+        writeln!(
+            out,
+            "{i2}Some(Self::new(unsafe {{ {reference}ptr.cast::<{struct_name}Buffer>() }}))"
+        )
+        .unwrap();
+        writeln!(out, "{i1}}}").unwrap();
+    }
+
+    /// Writes `trace_fields` and the `trace_fields_at` it recurses through into nested structs.
+    fn write_field_trace(&mut self, struct_name: &str, layout: &Layout, hir: &Hir) {
+        let i1 = self.config.get_indent(1);
+        let i2 = self.config.get_indent(2);
+        let i3 = self.config.get_indent(3);
+        let callback = "&mut dyn FnMut(&str, usize, &dyn std::fmt::Debug)";
+        let mut out = String::new();
+        writeln!(
+            out,
+            "\n{i1}/// Calls `f` with the name, byte offset, and value of each field present in"
+        )
+        .unwrap();
+        writeln!(out, "{i1}/// {struct_name}, in wire order.").unwrap();
+        writeln!(out, "{i1}pub fn trace_fields(&self, f: {callback}) {{").unwrap();
+        writeln!(out, "{i2}self.trace_fields_at(\"\", 0, f);").unwrap();
+        writeln!(out, "{i1}}}").unwrap();
+        writeln!(
+            out,
+            "\n{i1}fn trace_fields_at(&self, path: &str, offset: usize, f: {callback}) {{"
+        )
+        .unwrap();
+        for field in layout.fields() {
+            let (name, offset) = (&field.name, field.offset);
+            let at = match offset {
+                0 => "offset".to_string(),
+                _ => format!("offset + {offset}"),
+            };
+            let indent = match field.condition {
+                Some(_) => {
+                    writeln!(out, "{i2}if self.has_{name}() {{").unwrap();
+                    &i3
+                }
+                None => &i2,
+            };
+            let nested = |type_info: &Type| {
+                matches!(
+                    hir.resolve(type_info),
+                    Some(hir::TypeRef::Struct(_) | hir::TypeRef::Message(_))
+                )
+            };
+            match &field.type_info {
+                Type::Custom(_) if nested(&field.type_info) => writeln!(
+                    out,
+                    "{indent}self.{name}().trace_fields_at(&format!(\"{{path}}{name}.\"), {at}, f);"
+                ),
+                Type::Vector { element, .. } if nested(element) => {
+                    let items = offset + field.type_info.length_type().unwrap().get_byte_size();
+                    let size = hir.size_of(element).unwrap();
+                    writeln!(
+                        out,
+                        "{indent}for (i, item) in self.{name}().iter().enumerate() {{"
+                    )
+                    .unwrap();
+                    writeln!(
+                        out,
+                        "{indent}{i1}item.trace_fields_at(&format!(\"{{path}}{name}[{{i}}].\"), offset + {items} + i * {size}, f);"
+                    )
+                    .unwrap();
+                    writeln!(out, "{indent}}}")
+                }
+                Type::Vector { .. } => writeln!(
+                    out,
+                    "{indent}f(&format!(\"{{path}}{name}\"), {at}, &self.{name}().iter().collect::<Vec<_>>());"
+                ),
+                Type::Uuid | Type::Bytes(_) => writeln!(
+                    out,
+                    "{indent}f(&format!(\"{{path}}{name}\"), {at}, &format_args!(\"{{}}\", self.{name}_hex()));"
+                ),
+                _ => writeln!(
+                    out,
+                    "{indent}f(&format!(\"{{path}}{name}\"), {at}, &self.{name}());"
+                ),
+            }
+            .unwrap();
+            if field.condition.is_some() {
+                writeln!(out, "{i2}}}").unwrap();
+            }
+        }
+        writeln!(out, "{i1}}}").unwrap();
+        self.output.push_str(&out);
     }
 
     fn write_accessors(
         &mut self,
-        field_groups: &Vec<Vec<&Field>>,
+        struct_name: &str,
+        layout: &Layout,
         module: &OnyxModule,
         is_mut: bool,
     ) {
         let from_method = match module.endianness {
             WireEndianness::Big => "from_be_bytes",
             WireEndianness::Little => "from_le_bytes",
+            WireEndianness::Host => "from_ne_bytes",
         };
         let to_method = match module.endianness {
             WireEndianness::Big => "to_be_bytes",
             WireEndianness::Little => "to_le_bytes",
+            WireEndianness::Host => "to_ne_bytes",
         };
 
-        let mut current_offset = 0;
-
-        for group in field_groups {
-            let first_field = group[0];
-
-            if group.len() > 1 || first_field.bit_field_size.is_some() {
-                // Bit-field group
-                let mut container_bits = 0;
-                for field in group {
-                    container_bits += field.get_bit_width(module);
+        for slot in &layout.slots {
+            let current_offset = slot.offset;
+            for field in &slot.fields {
+                self.write_source_comment(field.span, 1);
+            }
+            let start = self.output.len();
+            for field in &slot.fields {
+                if let Some(condition) = &field.condition {
+                    self.write_presence_accessor(field, condition, layout, module);
                 }
-                let container_bytes = container_bits.div_ceil(8);
-
-                self.write_bitfield_accessors(
-                    group,
-                    current_offset,
-                    container_bytes,
-                    is_mut,
-                    from_method,
-                    to_method,
-                );
-                current_offset += container_bytes;
+            }
+            if slot.is_bit_field_container() {
+                self.write_bitfield_accessors(slot, is_mut, from_method, to_method);
             } else {
                 // Regular field
-                let field = first_field;
-                let type_str = self.get_primitive_rust_type(&field.type_info);
+                let field = slot.first().field;
 
                 match &field.type_info {
                     Type::Primitive(p) => {
-                        let size = p.get_bit_width() / 8;
-                        let rust_type = self.map_primitive_type_to_rust(p);
-
-                        // Accessor
-                        writeln!(
-                            self.output,
-                            "\n{}/// Accessor for {}.",
-                            self.config.get_indent(1),
-                            field.name
-                        )
-                        .unwrap();
-                        writeln!(
-                            self.output,
-                            "{}pub fn {}(&self) -> {} {{",
-                            self.config.get_indent(1),
-                            field.name,
-                            type_str
-                        )
-                        .unwrap();
-
-                        if rust_type == "u8" || rust_type == "i8" {
-                            writeln!(
-                                self.output,
-                                "{}self.data[{}] as {}",
-                                self.config.get_indent(2),
-                                current_offset,
-                                rust_type
-                            )
-                            .unwrap();
-                        } else if rust_type == "bool" {
-                            writeln!(
-                                self.output,
-                                "{}self.data[{}] != 0",
-                                self.config.get_indent(2),
-                                current_offset
-                            )
-                            .unwrap();
-                        } else {
-                            writeln!(
-                                self.output,
-                                "{}let bytes = self.data[{}..{}].try_into().unwrap();",
-                                self.config.get_indent(2),
-                                current_offset,
-                                current_offset + size
-                            )
-                            .unwrap();
-                            writeln!(
-                                self.output,
-                                "{}{}::{}(bytes)",
-                                self.config.get_indent(2),
-                                rust_type,
-                                from_method
-                            )
-                            .unwrap();
-                        }
-                        writeln!(self.output, "{}}}", self.config.get_indent(1)).unwrap();
-
-                        // Mutator (only if mutable)
-                        if is_mut {
-                            writeln!(
-                                self.output,
-                                "\n{}/// Mutator for {}.",
-                                self.config.get_indent(1),
-                                field.name
-                            )
-                            .unwrap();
-                            writeln!(
-                                self.output,
-                                "{}pub fn set_{}(&mut self, value: {}) {{",
-                                self.config.get_indent(1),
-                                field.name,
-                                type_str
-                            )
-                            .unwrap();
-
-                            if rust_type == "u8" || rust_type == "i8" {
-                                writeln!(
-                                    self.output,
-                                    "{}self.data[{}] = value as u8;",
-                                    self.config.get_indent(2),
-                                    current_offset
-                                )
-                                .unwrap();
-                            } else if rust_type == "bool" {
-                                writeln!(
-                                    self.output,
-                                    "{}self.data[{}] = if value {{ 1 }} else {{ 0 }};",
-                                    self.config.get_indent(2),
-                                    current_offset
-                                )
-                                .unwrap();
-                            } else {
-                                writeln!(
-                                    self.output,
-                                    "{}let bytes = value.{}();",
-                                    self.config.get_indent(2),
-                                    to_method
-                                )
-                                .unwrap();
-                                writeln!(
-                                    self.output,
-                                    "{}self.data[{}..{}].copy_from_slice(&bytes);",
-                                    self.config.get_indent(2),
-                                    current_offset,
-                                    current_offset + size
-                                )
-                                .unwrap();
-                            }
-                            writeln!(self.output, "{}}}", self.config.get_indent(1)).unwrap();
-                        }
-
-                        current_offset += size;
-                    }
-                    Type::Custom(s) => {
-                        if let Some(def) = module.definitions.get(s) {
-                            match def {
-                                Definition::Struct(_) | Definition::Message(_) => {
-                                    let size = self.get_serialized_size(module, def);
+                        self.write_primitive_accessors(
+                            field.name.as_str(),
+                            p,
+                            current_offset,
+                            is_mut,
+                            from_method,
+                            to_method,
+                        );
+                    }
+                    Type::Fixed { base, scale } => {
+                        self.write_primitive_accessors(
+                            &format!("{}_raw", field.name),
+                            base,
+                            current_offset,
+                            is_mut,
+                            from_method,
+                            to_method,
+                        );
+                        self.write_fixed_accessors(field.name.as_str(), base, *scale, is_mut);
+                    }
+                    Type::Uuid | Type::Bytes(_) => {
+                        let len = field.type_info.byte_len().unwrap();
+                        self.write_byte_array_accessors(
+                            field.name.as_str(),
+                            len,
+                            field.type_info == Type::Uuid,
+                            current_offset,
+                            is_mut,
+                        );
+                    }
+                    Type::Custom(s) => {
+                        if let Some(def) = module.definitions.get(s) {
+                            match def {
+                                Definition::Struct(_) | Definition::Message(_) => {
+                                    let size = slot.size;
 
                                     // Accessor returns View
                                     writeln!(
@@ -512,12 +1280,12 @@ impl RustGenerator {
                                         writeln!(self.output, "{}}}", self.config.get_indent(1))
                                             .unwrap();
                                     }
-                                    current_offset += size;
                                 }
                                 Definition::Enum(e) => {
                                     let size = e.underlying_type.get_bit_width() / 8;
                                     let underlying =
                                         self.map_primitive_type_to_rust(&e.underlying_type);
+                                    let (return_type, read) = self.enum_read(e, "val");
 
                                     // Accessor
                                     writeln!(
@@ -532,7 +1300,7 @@ impl RustGenerator {
                                         "{}pub fn {}(&self) -> {} {{",
                                         self.config.get_indent(1),
                                         field.name,
-                                        s
+                                        return_type
                                     )
                                     .unwrap();
 
@@ -552,12 +1320,8 @@ impl RustGenerator {
                                         from_method
                                     )
                                     .unwrap();
-                                    writeln!(
-                                        self.output,
-                                        "{}unsafe {{ std::mem::transmute(val) }}",
-                                        self.config.get_indent(2)
-                                    )
-                                    .unwrap();
+                                    writeln!(self.output, "{}{}", self.config.get_indent(2), read)
+                                        .unwrap();
                                     writeln!(self.output, "{}}}", self.config.get_indent(1))
                                         .unwrap();
 
@@ -580,9 +1344,9 @@ impl RustGenerator {
                                         .unwrap();
                                         writeln!(
                                             self.output,
-                                            "{}let val = value as {};",
+                                            "{}let val = {};",
                                             self.config.get_indent(2),
-                                            underlying
+                                            self.enum_write(e, "value")
                                         )
                                         .unwrap();
                                         writeln!(
@@ -603,156 +1367,1397 @@ impl RustGenerator {
                                         writeln!(self.output, "{}}}", self.config.get_indent(1))
                                             .unwrap();
                                     }
-                                    current_offset += size;
                                 }
                             }
                         }
                     }
+                    Type::Vector { .. } => {
+                        let size = slot.size;
+                        let view = vector_view_name(struct_name, field);
+
+                        writeln!(
+                            self.output,
+                            "\n{}/// Accessor for {}.",
+                            self.config.get_indent(1),
+                            field.name
+                        )
+                        .unwrap();
+                        writeln!(
+                            self.output,
+                            "{}pub fn {}(&self) -> {view}View {{",
+                            self.config.get_indent(1),
+                            field.name
+                        )
+                        .unwrap();
+                        writeln!(
+                            self.output,
+                            "{}let slice = &self.data[{}..{}];",
+                            self.config.get_indent(2),
+                            current_offset,
+                            current_offset + size
+                        )
+                        .unwrap();
+                        writeln!(
+                            self.output,
+                            "{}{view}View {{ data: slice.try_into().unwrap() }}",
+                            self.config.get_indent(2)
+                        )
+                        .unwrap();
+                        writeln!(self.output, "{}}}", self.config.get_indent(1)).unwrap();
+
+                        if is_mut {
+                            writeln!(
+                                self.output,
+                                "\n{}/// Mutable accessor for {}.",
+                                self.config.get_indent(1),
+                                field.name
+                            )
+                            .unwrap();
+                            writeln!(
+                                self.output,
+                                "{}pub fn {}_mut(&mut self) -> {view}MutView {{",
+                                self.config.get_indent(1),
+                                field.name
+                            )
+                            .unwrap();
+                            writeln!(
+                                self.output,
+                                "{}let slice = &mut self.data[{}..{}];",
+                                self.config.get_indent(2),
+                                current_offset,
+                                current_offset + size
+                            )
+                            .unwrap();
+                            writeln!(
+                                self.output,
+                                "{}{view}MutView {{ data: slice.try_into().unwrap() }}",
+                                self.config.get_indent(2)
+                            )
+                            .unwrap();
+                            writeln!(self.output, "{}}}", self.config.get_indent(1)).unwrap();
+                        }
+                    }
                 }
             }
+            self.annotate_accessors(start, slot);
         }
     }
 
-    fn write_bitfield_accessors(
+    /// Gives the accessors written since `start` the doc comments of the fields of `slot` they
+    /// belong to, and marks those of deprecated fields `#[deprecated]`.
+    fn annotate_accessors(&mut self, start: usize, slot: &Slot) {
+        if slot
+            .fields
+            .iter()
+            .all(|field| field.deprecated.is_none() && field.doc.is_none())
+        {
+            return;
+        }
+        let indent = self.config.get_indent(1);
+        let signature = format!("{indent}pub fn ");
+        let comment = format!("{indent}///");
+        let mut accessors = String::new();
+        let mut previous = "";
+        for line in self.output[start..].split_inclusive('\n') {
+            if let Some(rest) = line.strip_prefix(&signature) {
+                let name = rest.split('(').next().unwrap_or_default();
+                if let Some(doc) = accessor_field(slot, name).and_then(|f| f.doc.as_deref()) {
+                    // Set the field's own description apart from the accessor's.
+                    if previous.starts_with(&comment) {
+                        writeln!(accessors, "{comment}").unwrap();
+                    }
+                    accessors.push_str(&doc_lines(Some(doc), &indent));
+                }
+                if let Some(note) = accessor_deprecation(slot, name) {
+                    writeln!(accessors, "{indent}#[deprecated(note = {note:?})]").unwrap();
+                }
+            }
+            accessors.push_str(line);
+            previous = line;
+        }
+        self.output.truncate(start);
+        self.output.push_str(&accessors);
+    }
+
+    /// Writes `#[deprecated]` with the note of a `@deprecated` attribute, if there is one.
+    fn write_deprecated(&mut self, note: Option<&str>) {
+        if let Some(note) = note {
+            writeln!(self.output, "#[deprecated(note = {note:?})]").unwrap();
+        }
+    }
+
+    /// Writes `has_{name}`, which evaluates the condition of a conditional field.
+    fn write_presence_accessor(
+        &mut self,
+        field: &Field,
+        condition: &Condition,
+        layout: &Layout,
+        module: &OnyxModule,
+    ) {
+        let Some(target) = layout.field(condition.field.as_str()) else {
+            return;
+        };
+        let raw = condition.raw_value(module).unwrap_or_default();
+        let op = if condition.negated { "!=" } else { "==" };
+        let read = format!("self.{}()", target.name);
+        let test = match &target.type_info {
+            Type::Primitive(PrimitiveType::Bool) if (raw != 0) != condition.negated => read,
+            Type::Primitive(PrimitiveType::Bool) => format!("!{read}"),
+            Type::Custom(name) => match module.definitions.get(name) {
+                Some(Definition::Enum(e)) if e.flags => format!("{read}.bits() {op} {raw}"),
+                Some(Definition::Enum(e)) => {
+                    let t = self.map_primitive_type_to_rust(&e.underlying_type);
+                    if e.open {
+                        format!("{t}::from({read}) {op} {raw}")
+                    } else {
+                        format!("{read}.map_or_else(|raw| raw, {t}::from) {op} {raw}")
+                    }
+                }
+                _ => return,
+            },
+            _ => format!("{read} {op} {raw}"),
+        };
+
+        let (i1, i2) = (self.config.get_indent(1), self.config.get_indent(2));
+        writeln!(
+            self.output,
+            "\n{i1}/// Returns true if {} is present: `{condition}`.",
+            field.name
+        )
+        .unwrap();
+        writeln!(
+            self.output,
+            "{i1}pub fn has_{}(&self) -> bool {{",
+            field.name
+        )
+        .unwrap();
+        writeln!(self.output, "{i2}{test}").unwrap();
+        writeln!(self.output, "{i1}}}").unwrap();
+    }
+
+    /// Writes the accessor, and the mutator if `is_mut`, of a primitive field at `offset`.
+    fn write_primitive_accessors(
         &mut self,
-        group: &Vec<&Field>,
+        name: &str,
+        p: &PrimitiveType,
         offset: usize,
-        container_bytes: usize,
         is_mut: bool,
         from_method: &str,
         to_method: &str,
     ) {
-        let mut current_bit_offset: usize = 0;
-        let int_type = match container_bytes {
-            1 => "u8",
-            2 => "u16",
-            4 => "u32",
-            8 => "u64",
-            _ => "u128",
-        };
+        let size = p.get_bit_width() / 8;
+        let rust_type = self.map_primitive_type_to_rust(p);
 
-        for field in group {
-            let field_type_str = self.get_primitive_rust_type(&field.type_info);
-            let bits = field.bit_field_size.unwrap_or(0);
-            let mask = (1u64 << bits).saturating_sub(1);
-            let shift = current_bit_offset;
+        // Accessor
+        writeln!(
+            self.output,
+            "\n{}/// Accessor for {}.",
+            self.config.get_indent(1),
+            name
+        )
+        .unwrap();
+        writeln!(
+            self.output,
+            "{}pub fn {}(&self) -> {} {{",
+            self.config.get_indent(1),
+            name,
+            rust_type
+        )
+        .unwrap();
 
-            // Accessor
+        if rust_type == "u8" || rust_type == "i8" {
             writeln!(
                 self.output,
-                "\n{}/// Accessor for {}.",
+                "{}self.data[{}] as {}",
+                self.config.get_indent(2),
+                offset,
+                rust_type
+            )
+            .unwrap();
+        } else if rust_type == "bool" {
+            writeln!(
+                self.output,
+                "{}self.data[{}] != 0",
+                self.config.get_indent(2),
+                offset
+            )
+            .unwrap();
+        } else {
+            writeln!(
+                self.output,
+                "{}let bytes = self.data[{}..{}].try_into().unwrap();",
+                self.config.get_indent(2),
+                offset,
+                offset + size
+            )
+            .unwrap();
+            writeln!(
+                self.output,
+                "{}{}::{}(bytes)",
+                self.config.get_indent(2),
+                rust_type,
+                from_method
+            )
+            .unwrap();
+        }
+        writeln!(self.output, "{}}}", self.config.get_indent(1)).unwrap();
+
+        // Mutator (only if mutable)
+        if is_mut {
+            writeln!(
+                self.output,
+                "\n{}/// Mutator for {}.",
                 self.config.get_indent(1),
-                field.name
+                name
             )
             .unwrap();
             writeln!(
                 self.output,
-                "{}pub fn {}(&self) -> {} {{",
+                "{}pub fn set_{}(&mut self, value: {}) {{",
                 self.config.get_indent(1),
-                field.name,
-                field_type_str
+                name,
+                rust_type
             )
             .unwrap();
 
-            // Read container
-            if container_bytes == 1 {
+            if rust_type == "u8" || rust_type == "i8" {
                 writeln!(
                     self.output,
-                    "{}let raw = self.data[{}] as {};",
+                    "{}self.data[{}] = value as u8;",
                     self.config.get_indent(2),
-                    offset,
-                    int_type
+                    offset
                 )
                 .unwrap();
-            } else {
+            } else if rust_type == "bool" {
                 writeln!(
                     self.output,
-                    "{}let bytes = self.data[{}..{}].try_into().unwrap();",
+                    "{}self.data[{}] = if value {{ 1 }} else {{ 0 }};",
                     self.config.get_indent(2),
-                    offset,
-                    offset + container_bytes
+                    offset
                 )
                 .unwrap();
+            } else {
                 writeln!(
                     self.output,
-                    "{}let raw = {}::{}(bytes);",
+                    "{}let bytes = value.{}();",
                     self.config.get_indent(2),
-                    int_type,
-                    from_method
+                    to_method
                 )
                 .unwrap();
-            }
-
-            writeln!(
-                self.output,
-                "{}let val = (raw >> {}) & 0x{:X};",
-                self.config.get_indent(2),
-                shift,
-                mask
-            )
-            .unwrap();
-
-            if field_type_str == "bool" {
-                writeln!(self.output, "{}val != 0", self.config.get_indent(2)).unwrap();
-            } else {
                 writeln!(
                     self.output,
-                    "{}val as {}",
+                    "{}self.data[{}..{}].copy_from_slice(&bytes);",
                     self.config.get_indent(2),
-                    field_type_str
+                    offset,
+                    offset + size
                 )
                 .unwrap();
             }
             writeln!(self.output, "{}}}", self.config.get_indent(1)).unwrap();
+        }
+    }
 
-            // Mutator
-            if is_mut {
-                writeln!(
-                    self.output,
-                    "\n{}/// Mutator for {}.",
-                    self.config.get_indent(1),
-                    field.name
-                )
-                .unwrap();
-                writeln!(
-                    self.output,
-                    "{}pub fn set_{}(&mut self, value: {}) {{",
-                    self.config.get_indent(1),
-                    field.name,
-                    field_type_str
-                )
-                .unwrap();
+    /// Writes the scaled accessor, and the range-checked scaled mutator if `is_mut`, of a
+    /// fixed-point field whose raw accessors are already written.
+    fn write_fixed_accessors(
+        &mut self,
+        name: &str,
+        base: &PrimitiveType,
+        scale: u64,
+        is_mut: bool,
+    ) {
+        let rust_type = self.map_primitive_type_to_rust(base);
 
-                // Read container
-                if container_bytes == 1 {
-                    writeln!(
-                        self.output,
-                        "{}let mut raw = self.data[{}] as {};",
-                        self.config.get_indent(2),
-                        offset,
-                        int_type
-                    )
-                    .unwrap();
-                } else {
-                    writeln!(
-                        self.output,
-                        "{}let bytes = self.data[{}..{}].try_into().unwrap();",
-                        self.config.get_indent(2),
-                        offset,
-                        offset + container_bytes
-                    )
-                    .unwrap();
-                    writeln!(
-                        self.output,
-                        "{}let mut raw = {}::{}(bytes);",
-                        self.config.get_indent(2),
-                        int_type,
-                        from_method
-                    )
-                    .unwrap();
-                }
+        writeln!(
+            self.output,
+            "\n{}/// Accessor for {}, scaled by 1/{}.",
+            self.config.get_indent(1),
+            name,
+            scale
+        )
+        .unwrap();
+        writeln!(
+            self.output,
+            "{}pub fn {}(&self) -> f64 {{",
+            self.config.get_indent(1),
+            name
+        )
+        .unwrap();
+        writeln!(
+            self.output,
+            "{}self.{}_raw() as f64 / {}_f64",
+            self.config.get_indent(2),
+            name,
+            scale
+        )
+        .unwrap();
+        writeln!(self.output, "{}}}", self.config.get_indent(1)).unwrap();
 
-                // Modify
+        if is_mut {
+            writeln!(
+                self.output,
+                "\n{}/// Mutator for {}, rounding to the nearest step of 1/{}.",
+                self.config.get_indent(1),
+                name,
+                scale
+            )
+            .unwrap();
+            writeln!(
+                self.output,
+                "{}///\n{}/// Returns the value back if it is out of range for the field.",
+                self.config.get_indent(1),
+                self.config.get_indent(1)
+            )
+            .unwrap();
+            writeln!(
+                self.output,
+                "{}pub fn set_{}(&mut self, value: f64) -> Result<(), f64> {{",
+                self.config.get_indent(1),
+                name
+            )
+            .unwrap();
+            writeln!(
+                self.output,
+                "{}let scaled = (value * {}_f64).round();",
+                self.config.get_indent(2),
+                scale
+            )
+            .unwrap();
+            writeln!(
+                self.output,
+                "{}if !(scaled >= {rust_type}::MIN as f64 && scaled < {rust_type}::MAX as f64 + 1.0) {{",
+                self.config.get_indent(2)
+            )
+            .unwrap();
+            writeln!(
+                self.output,
+                "{}return Err(value);",
+                self.config.get_indent(3)
+            )
+            .unwrap();
+            writeln!(self.output, "{}}}", self.config.get_indent(2)).unwrap();
+            writeln!(
+                self.output,
+                "{}self.set_{}_raw(scaled as {});",
+                self.config.get_indent(2),
+                name,
+                rust_type
+            )
+            .unwrap();
+            writeln!(self.output, "{}Ok(())", self.config.get_indent(2)).unwrap();
+            writeln!(self.output, "{}}}", self.config.get_indent(1)).unwrap();
+        }
+    }
+
+    /// Writes the accessors, hex formatter, and mutator if `is_mut`, of a `uuid` or `bytes[N]`
+    /// field of `len` bytes at `offset`.
+    fn write_byte_array_accessors(
+        &mut self,
+        name: &str,
+        len: usize,
+        uuid: bool,
+        offset: usize,
+        is_mut: bool,
+    ) {
+        let range = format!("{offset}..{}", offset + len);
+
+        writeln!(
+            self.output,
+            "\n{}/// Accessor for {}.",
+            self.config.get_indent(1),
+            name
+        )
+        .unwrap();
+        writeln!(
+            self.output,
+            "{}pub fn {}(&self) -> [u8; {}] {{",
+            self.config.get_indent(1),
+            name,
+            len
+        )
+        .unwrap();
+        writeln!(
+            self.output,
+            "{}self.data[{range}].try_into().unwrap()",
+            self.config.get_indent(2)
+        )
+        .unwrap();
+        writeln!(self.output, "{}}}", self.config.get_indent(1)).unwrap();
+
+        writeln!(
+            self.output,
+            "\n{}/// Formats {} as hex.",
+            self.config.get_indent(1),
+            name
+        )
+        .unwrap();
+        writeln!(
+            self.output,
+            "{}pub fn {}_hex(&self) -> Hex<'_> {{",
+            self.config.get_indent(1),
+            name
+        )
+        .unwrap();
+        writeln!(
+            self.output,
+            "{}Hex {{ bytes: &self.data[{range}], uuid: {uuid} }}",
+            self.config.get_indent(2)
+        )
+        .unwrap();
+        writeln!(self.output, "{}}}", self.config.get_indent(1)).unwrap();
+
+        if is_mut {
+            writeln!(
+                self.output,
+                "\n{}/// Mutator for {}.",
+                self.config.get_indent(1),
+                name
+            )
+            .unwrap();
+            writeln!(
+                self.output,
+                "{}pub fn set_{}(&mut self, value: [u8; {}]) {{",
+                self.config.get_indent(1),
+                name,
+                len
+            )
+            .unwrap();
+            writeln!(
+                self.output,
+                "{}self.data[{range}].copy_from_slice(&value);",
+                self.config.get_indent(2)
+            )
+            .unwrap();
+            writeln!(self.output, "{}}}", self.config.get_indent(1)).unwrap();
+        }
+    }
+
+    /// Writes `Hex`, which the `_hex` accessors of byte array fields return.
+    fn write_hex_formatter(&mut self) {
+        let indent = |level| self.config.get_indent(level);
+        let (i1, i2, i3, i4) = (indent(1), indent(2), indent(3), indent(4));
+        let out = &mut self.output;
+        writeln!(
+            out,
+            "\n/// Formats a `uuid` or `bytes[N]` field as lowercase hex; a UUID is grouped 8-4-4-4-12."
+        )
+        .unwrap();
+        writeln!(out, "#[derive(Clone, Copy)]").unwrap();
+        writeln!(out, "pub struct Hex<'a> {{").unwrap();
+        writeln!(out, "{i1}bytes: &'a [u8],").unwrap();
+        writeln!(out, "{i1}uuid: bool,").unwrap();
+        writeln!(out, "}}").unwrap();
+        writeln!(out, "\nimpl std::fmt::Debug for Hex<'_> {{").unwrap();
+        writeln!(
+            out,
+            "{i1}fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {{"
+        )
+        .unwrap();
+        writeln!(out, "{i2}for (i, b) in self.bytes.iter().enumerate() {{").unwrap();
+        writeln!(out, "{i3}if self.uuid && matches!(i, 4 | 6 | 8 | 10) {{").unwrap();
+        writeln!(out, "{i4}f.write_str(\"-\")?;").unwrap();
+        writeln!(out, "{i3}}}").unwrap();
+        writeln!(out, "{i3}write!(f, \"{{:02x}}\", b)?;").unwrap();
+        writeln!(out, "{i2}}}").unwrap();
+        writeln!(out, "{i2}Ok(())").unwrap();
+        writeln!(out, "{i1}}}").unwrap();
+        writeln!(out, "}}").unwrap();
+        writeln!(out, "\nimpl std::fmt::Display for Hex<'_> {{").unwrap();
+        writeln!(
+            out,
+            "{i1}fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {{"
+        )
+        .unwrap();
+        writeln!(out, "{i2}std::fmt::Debug::fmt(self, f)").unwrap();
+        writeln!(out, "{i1}}}").unwrap();
+        writeln!(out, "}}").unwrap();
+    }
+
+    /// Writes `MessageArena` and the `ArenaBuffer` slots it lends out.
+    fn write_message_arena(&mut self, module: &OnyxModule) {
+        let Some(size) = largest_message_size(module) else {
+            return;
+        };
+        let indent = |level| self.config.get_indent(level);
+        let (i1, i2, i3) = (indent(1), indent(2), indent(3));
+        let slot = "[u8; MESSAGE_ARENA_SLOT_SIZE]";
+        let out = &mut self.output;
+        writeln!(
+            out,
+            "\n/// Size of the largest message, and of each MessageArena slot, in bytes."
+        )
+        .unwrap();
+        writeln!(out, "pub const MESSAGE_ARENA_SLOT_SIZE: usize = {size};").unwrap();
+
+        writeln!(
+            out,
+            "\n/// A pool of `N` buffers, each as large as the largest message, for obtaining zeroed"
+        )
+        .unwrap();
+        writeln!(
+            out,
+            "/// message buffers without heap allocation. The pool lives wherever it is declared,"
+        )
+        .unwrap();
+        writeln!(out, "/// such as the stack.").unwrap();
+        writeln!(out, "pub struct MessageArena<const N: usize> {{").unwrap();
+        writeln!(out, "{i1}slots: [std::cell::UnsafeCell<{slot}>; N],").unwrap();
+        writeln!(out, "{i1}used: [std::cell::Cell<bool>; N],").unwrap();
+        writeln!(out, "}}").unwrap();
+
+        writeln!(out, "\nimpl<const N: usize> MessageArena<N> {{").unwrap();
+        writeln!(out, "{i1}/// Creates an arena with every slot free.").unwrap();
+        writeln!(out, "{i1}pub fn new() -> Self {{").unwrap();
+        writeln!(out, "{i2}Self {{").unwrap();
+        writeln!(
+            out,
+            "{i3}slots: std::array::from_fn(|_| std::cell::UnsafeCell::new([0; MESSAGE_ARENA_SLOT_SIZE])),"
+        )
+        .unwrap();
+        writeln!(
+            out,
+            "{i3}used: std::array::from_fn(|_| std::cell::Cell::new(false)),"
+        )
+        .unwrap();
+        writeln!(out, "{i2}}}").unwrap();
+        writeln!(out, "{i1}}}").unwrap();
+        writeln!(
+            out,
+            "\n{i1}/// Takes a free slot and zeroes it, or returns `None` if all `N` are in use. The slot"
+        )
+        .unwrap();
+        writeln!(
+            out,
+            "{i1}/// returns to the arena when the buffer is dropped."
+        )
+        .unwrap();
+        writeln!(
+            out,
+            "{i1}pub fn acquire(&self) -> Option<ArenaBuffer<'_>> {{"
+        )
+        .unwrap();
+        writeln!(
+            out,
+            "{i2}let index = self.used.iter().position(|used| !used.get())?;"
+        )
+        .unwrap();
+        writeln!(out, "{i2}self.used[index].set(true);").unwrap();
+        writeln!(
+            out,
+            "{i2}// The slot was free, so no other buffer refers to it until this one is dropped."
+        )
+        .unwrap();
+        writeln!(
+            out,
+            "{i2}let data = unsafe {{ &mut *self.slots[index].get() }};"
+        )
+        .unwrap();
+        writeln!(out, "{i2}data.fill(0);").unwrap();
+        writeln!(
+            out,
+            "{i2}Some(ArenaBuffer {{ data, used: &self.used[index] }})"
+        )
+        .unwrap();
+        writeln!(out, "{i1}}}").unwrap();
+        writeln!(out, "\n{i1}/// Returns the number of free slots.").unwrap();
+        writeln!(out, "{i1}pub fn available(&self) -> usize {{").unwrap();
+        writeln!(
+            out,
+            "{i2}self.used.iter().filter(|used| !used.get()).count()"
+        )
+        .unwrap();
+        writeln!(out, "{i1}}}").unwrap();
+        writeln!(out, "}}").unwrap();
+
+        writeln!(out, "\nimpl<const N: usize> Default for MessageArena<N> {{").unwrap();
+        writeln!(out, "{i1}fn default() -> Self {{").unwrap();
+        writeln!(out, "{i2}Self::new()").unwrap();
+        writeln!(out, "{i1}}}").unwrap();
+        writeln!(out, "}}").unwrap();
+
+        writeln!(
+            out,
+            "\n/// A zeroed slot of a MessageArena, which returns to the arena when dropped."
+        )
+        .unwrap();
+        writeln!(out, "pub struct ArenaBuffer<'a> {{").unwrap();
+        writeln!(out, "{i1}data: &'a mut {slot},").unwrap();
+        writeln!(out, "{i1}used: &'a std::cell::Cell<bool>,").unwrap();
+        writeln!(out, "}}").unwrap();
+
+        writeln!(out, "\nimpl ArenaBuffer<'_> {{").unwrap();
+        writeln!(
+            out,
+            "{i1}/// Returns the start of the slot as the buffer of a message, such as a `PingBuffer`."
+        )
+        .unwrap();
+        writeln!(
+            out,
+            "{i1}/// Panics if `S` is larger than a slot, which no message of this module is."
+        )
+        .unwrap();
+        writeln!(
+            out,
+            "{i1}pub fn as_buffer<const S: usize>(&self) -> &[u8; S] {{"
+        )
+        .unwrap();
+        writeln!(out, "{i2}(&self.data[..S]).try_into().unwrap()").unwrap();
+        writeln!(out, "{i1}}}").unwrap();
+        writeln!(
+            out,
+            "\n{i1}/// Returns the start of the slot as the mutable buffer of a message."
+        )
+        .unwrap();
+        writeln!(
+            out,
+            "{i1}/// Panics if `S` is larger than a slot, which no message of this module is."
+        )
+        .unwrap();
+        writeln!(
+            out,
+            "{i1}pub fn as_buffer_mut<const S: usize>(&mut self) -> &mut [u8; S] {{"
+        )
+        .unwrap();
+        writeln!(out, "{i2}(&mut self.data[..S]).try_into().unwrap()").unwrap();
+        writeln!(out, "{i1}}}").unwrap();
+        writeln!(out, "}}").unwrap();
+
+        writeln!(out, "\nimpl Drop for ArenaBuffer<'_> {{").unwrap();
+        writeln!(out, "{i1}fn drop(&mut self) {{").unwrap();
+        writeln!(out, "{i2}self.used.set(false);").unwrap();
+        writeln!(out, "{i1}}}").unwrap();
+        writeln!(out, "}}").unwrap();
+    }
+
+    /// Writes the read-only and read-write views of the bounded vector `field` of `struct_name`.
+    ///
+    /// The views borrow the field's bytes: the length prefix followed by `capacity` element
+    /// slots. Elements past the length are never read.
+    fn write_vector_views(
+        &mut self,
+        struct_name: &str,
+        field: &Field,
+        element: &Type,
+        capacity: usize,
+        hir: &Hir,
+    ) {
+        let module = hir.module;
+        let (from_method, to_method) = match module.endianness {
+            WireEndianness::Big => ("from_be_bytes", "to_be_bytes"),
+            WireEndianness::Little => ("from_le_bytes", "to_le_bytes"),
+            WireEndianness::Host => ("from_ne_bytes", "to_ne_bytes"),
+        };
+        let view = vector_view_name(struct_name, field);
+        let size = hir.size_of(&field.type_info).unwrap();
+        let length_type = field.type_info.length_type().unwrap();
+        let length_size = length_type.get_byte_size();
+        let length_rust = self.map_primitive_type_to_rust(&length_type);
+        let element_size = hir.size_of(element).unwrap();
+        let element_def = match element {
+            Type::Custom(name) => module.definitions.get(name),
+            _ => None,
+        };
+        let is_record = matches!(
+            element_def,
+            Some(Definition::Struct(_) | Definition::Message(_))
+        );
+        let element_type = self.get_primitive_rust_type(element);
+        let (item, item_ref) = if is_record {
+            (
+                format!("{element_type}View<'a>"),
+                format!("{element_type}View<'_>"),
+            )
+        } else if let Some(Definition::Enum(e)) = element_def {
+            let item = self.enum_read(e, "").0;
+            (item.clone(), item)
+        } else {
+            (element_type.clone(), element_type.clone())
+        };
+        let i1 = self.config.get_indent(1);
+        let i2 = self.config.get_indent(2);
+        let i3 = self.config.get_indent(3);
+        let slot = format!("{length_size} + index * {element_size}");
+        let slot_range = format!("{slot}..{slot} + {element_size}");
+
+        let read_length = if length_size == 1 {
+            "self.data[0] as usize".to_string()
+        } else {
+            format!(
+                "{length_rust}::{from_method}(self.data[..{length_size}].try_into().unwrap()) as usize"
+            )
+        };
+        let read_element = match (element, element_def) {
+            (_, Some(Definition::Struct(_) | Definition::Message(_))) => {
+                format!("{element_type}View::new(self.data[{slot_range}].try_into().unwrap())")
+            }
+            (_, Some(Definition::Enum(e))) => {
+                let underlying = self.map_primitive_type_to_rust(&e.underlying_type);
+                self.enum_read(
+                    e,
+                    &format!(
+                        "{underlying}::{from_method}(self.data[{slot_range}].try_into().unwrap())"
+                    ),
+                )
+                .1
+            }
+            (Type::Primitive(PrimitiveType::Bool), _) => format!("self.data[{slot}] != 0"),
+            (Type::Primitive(PrimitiveType::U8 | PrimitiveType::I8), _) => {
+                format!("self.data[{slot}] as {element_type}")
+            }
+            _ => format!(
+                "{element_type}::{from_method}(self.data[{slot_range}].try_into().unwrap())"
+            ),
+        };
+        let write_element = match (element, element_def) {
+            (_, Some(Definition::Enum(e))) => format!(
+                "self.data[{slot_range}].copy_from_slice(&{}.{to_method}());",
+                self.enum_write(e, "value")
+            ),
+            (Type::Primitive(PrimitiveType::Bool), _) => {
+                format!("self.data[{slot}] = value as u8;")
+            }
+            (Type::Primitive(PrimitiveType::U8 | PrimitiveType::I8), _) => {
+                format!("self.data[{slot}] = value as u8;")
+            }
+            _ => format!("self.data[{slot_range}].copy_from_slice(&value.{to_method}());"),
+        };
+
+        let out = &mut self.output;
+
+        // View (Read-only)
+        writeln!(
+            out,
+            "\n/// Read-only view of the bounded vector {struct_name}.{}.",
+            field.name
+        )
+        .unwrap();
+        writeln!(out, "#[derive(Debug, Clone, Copy)]").unwrap();
+        writeln!(out, "pub struct {view}View<'a> {{").unwrap();
+        writeln!(out, "{i1}data: &'a [u8; {size}],").unwrap();
+        writeln!(out, "}}").unwrap();
+
+        writeln!(out, "\nimpl<'a> {view}View<'a> {{").unwrap();
+        writeln!(out, "{i1}/// The maximum number of elements.").unwrap();
+        writeln!(out, "{i1}pub const CAPACITY: usize = {capacity};").unwrap();
+        writeln!(
+            out,
+            "\n{i1}/// Returns the number of elements, clamped to the capacity."
+        )
+        .unwrap();
+        writeln!(out, "{i1}pub fn len(&self) -> usize {{").unwrap();
+        writeln!(out, "{i2}({read_length}).min(Self::CAPACITY)").unwrap();
+        writeln!(out, "{i1}}}").unwrap();
+        writeln!(out, "\n{i1}/// Returns true if the vector has no elements.").unwrap();
+        writeln!(out, "{i1}pub fn is_empty(&self) -> bool {{").unwrap();
+        writeln!(out, "{i2}self.len() == 0").unwrap();
+        writeln!(out, "{i1}}}").unwrap();
+        writeln!(
+            out,
+            "\n{i1}/// Returns the element at `index`, or `None` if it is out of range."
+        )
+        .unwrap();
+        writeln!(
+            out,
+            "{i1}pub fn get(&self, index: usize) -> Option<{item}> {{"
+        )
+        .unwrap();
+        writeln!(out, "{i2}if index >= self.len() {{").unwrap();
+        writeln!(out, "{i3}return None;").unwrap();
+        writeln!(out, "{i2}}}").unwrap();
+        if is_record {
+            // Reborrow for 'a rather than the lifetime of `&self`.
+            writeln!(out, "{i2}let data: &'a [u8; {size}] = self.data;").unwrap();
+            writeln!(
+                out,
+                "{i2}Some({})",
+                read_element.replace("self.data", "data")
+            )
+            .unwrap();
+        } else {
+            writeln!(out, "{i2}Some({read_element})").unwrap();
+        }
+        writeln!(out, "{i1}}}").unwrap();
+        writeln!(out, "\n{i1}/// Returns an iterator over the elements.").unwrap();
+        writeln!(
+            out,
+            "{i1}pub fn iter(&self) -> impl Iterator<Item = {item}> + 'a {{"
+        )
+        .unwrap();
+        writeln!(out, "{i2}let view = *self;").unwrap();
+        writeln!(
+            out,
+            "{i2}(0..self.len()).map(move |index| view.get(index).unwrap())"
+        )
+        .unwrap();
+        writeln!(out, "{i1}}}").unwrap();
+        writeln!(out, "}}").unwrap();
+
+        // MutView (Read-Write)
+        writeln!(
+            out,
+            "\n/// Read-write view of the bounded vector {struct_name}.{}.",
+            field.name
+        )
+        .unwrap();
+        writeln!(out, "#[derive(Debug)]").unwrap();
+        writeln!(out, "pub struct {view}MutView<'a> {{").unwrap();
+        writeln!(out, "{i1}data: &'a mut [u8; {size}],").unwrap();
+        writeln!(out, "}}").unwrap();
+
+        writeln!(out, "\nimpl<'a> {view}MutView<'a> {{").unwrap();
+        writeln!(out, "{i1}/// The maximum number of elements.").unwrap();
+        writeln!(out, "{i1}pub const CAPACITY: usize = {capacity};").unwrap();
+        writeln!(out, "\n{i1}/// Returns a read-only view of the vector.").unwrap();
+        writeln!(out, "{i1}pub fn as_view(&self) -> {view}View<'_> {{").unwrap();
+        writeln!(out, "{i2}{view}View {{ data: self.data }}").unwrap();
+        writeln!(out, "{i1}}}").unwrap();
+        writeln!(
+            out,
+            "\n{i1}/// Returns the number of elements, clamped to the capacity."
+        )
+        .unwrap();
+        writeln!(out, "{i1}pub fn len(&self) -> usize {{").unwrap();
+        writeln!(out, "{i2}self.as_view().len()").unwrap();
+        writeln!(out, "{i1}}}").unwrap();
+        writeln!(out, "\n{i1}/// Returns true if the vector has no elements.").unwrap();
+        writeln!(out, "{i1}pub fn is_empty(&self) -> bool {{").unwrap();
+        writeln!(out, "{i2}self.len() == 0").unwrap();
+        writeln!(out, "{i1}}}").unwrap();
+        writeln!(
+            out,
+            "\n{i1}/// Returns the element at `index`, or `None` if it is out of range."
+        )
+        .unwrap();
+        writeln!(
+            out,
+            "{i1}pub fn get(&self, index: usize) -> Option<{item_ref}> {{"
+        )
+        .unwrap();
+        writeln!(out, "{i2}self.as_view().get(index)").unwrap();
+        writeln!(out, "{i1}}}").unwrap();
+        writeln!(out, "\n{i1}/// Returns an iterator over the elements.").unwrap();
+        writeln!(
+            out,
+            "{i1}pub fn iter(&self) -> impl Iterator<Item = {item_ref}> + '_ {{"
+        )
+        .unwrap();
+        writeln!(out, "{i2}self.as_view().iter()").unwrap();
+        writeln!(out, "{i1}}}").unwrap();
+        writeln!(out, "\n{i1}fn set_len(&mut self, len: usize) {{").unwrap();
+        if length_size == 1 {
+            writeln!(out, "{i2}self.data[0] = len as u8;").unwrap();
+        } else {
+            writeln!(
+                out,
+                "{i2}self.data[..{length_size}].copy_from_slice(&(len as {length_rust}).{to_method}());"
+            )
+            .unwrap();
+        }
+        writeln!(out, "{i1}}}").unwrap();
+        writeln!(out, "\n{i1}/// Removes all elements.").unwrap();
+        writeln!(out, "{i1}pub fn clear(&mut self) {{").unwrap();
+        writeln!(out, "{i2}self.set_len(0);").unwrap();
+        writeln!(out, "{i1}}}").unwrap();
+
+        if is_record {
+            writeln!(
+                out,
+                "\n{i1}/// Returns a mutable view of the element at `index`, or `None` if it is out of range."
+            )
+            .unwrap();
+            writeln!(
+                out,
+                "{i1}pub fn get_mut(&mut self, index: usize) -> Option<{element_type}MutView<'_>> {{"
+            )
+            .unwrap();
+            writeln!(out, "{i2}if index >= self.len() {{").unwrap();
+            writeln!(out, "{i3}return None;").unwrap();
+            writeln!(out, "{i2}}}").unwrap();
+            writeln!(
+                out,
+                "{i2}Some({element_type}MutView::new((&mut self.data[{slot_range}]).try_into().unwrap()))"
+            )
+            .unwrap();
+            writeln!(out, "{i1}}}").unwrap();
+            writeln!(
+                out,
+                "\n{i1}/// Appends a zeroed element and returns a mutable view of it, or `None` if the vector is full."
+            )
+            .unwrap();
+            writeln!(
+                out,
+                "{i1}pub fn push(&mut self) -> Option<{element_type}MutView<'_>> {{"
+            )
+            .unwrap();
+            writeln!(out, "{i2}let index = self.len();").unwrap();
+            writeln!(out, "{i2}if index == Self::CAPACITY {{").unwrap();
+            writeln!(out, "{i3}return None;").unwrap();
+            writeln!(out, "{i2}}}").unwrap();
+            writeln!(out, "{i2}self.set_len(index + 1);").unwrap();
+            writeln!(out, "{i2}self.data[{slot_range}].fill(0);").unwrap();
+            writeln!(out, "{i2}self.get_mut(index)").unwrap();
+            writeln!(out, "{i1}}}").unwrap();
+        } else {
+            writeln!(out, "\n{i1}/// Replaces the element at `index`.").unwrap();
+            writeln!(out, "{i1}///").unwrap();
+            writeln!(out, "{i1}/// # Panics").unwrap();
+            writeln!(out, "{i1}///").unwrap();
+            writeln!(out, "{i1}/// Panics if `index` is not less than `len()`.").unwrap();
+            writeln!(
+                out,
+                "{i1}pub fn set(&mut self, index: usize, value: {element_type}) {{"
+            )
+            .unwrap();
+            writeln!(
+                out,
+                "{i2}assert!(index < self.len(), \"index {{index}} out of range\");"
+            )
+            .unwrap();
+            writeln!(out, "{i2}{write_element}").unwrap();
+            writeln!(out, "{i1}}}").unwrap();
+            writeln!(
+                out,
+                "\n{i1}/// Appends an element, or returns it back if the vector is full."
+            )
+            .unwrap();
+            writeln!(
+                out,
+                "{i1}pub fn push(&mut self, value: {element_type}) -> Result<(), {element_type}> {{"
+            )
+            .unwrap();
+            writeln!(out, "{i2}let index = self.len();").unwrap();
+            writeln!(out, "{i2}if index == Self::CAPACITY {{").unwrap();
+            writeln!(out, "{i3}return Err(value);").unwrap();
+            writeln!(out, "{i2}}}").unwrap();
+            writeln!(out, "{i2}self.set_len(index + 1);").unwrap();
+            writeln!(out, "{i2}self.set(index, value);").unwrap();
+            writeln!(out, "{i2}Ok(())").unwrap();
+            writeln!(out, "{i1}}}").unwrap();
+        }
+        writeln!(out, "}}").unwrap();
+    }
+
+    /// Writes the message IDs and `decode_any`, which decodes a message preceded by its ID into
+    /// the `AnyMessage` of its type.
+    fn write_dispatch(&mut self, module: &OnyxModule) {
+        let messages = own_identified_messages(module);
+        if messages.is_empty() {
+            return;
+        }
+        let indent = |level| self.config.get_indent(level);
+        let (i1, i2, i3) = (indent(1), indent(2), indent(3));
+        let (from_method, to_method) = match module.endianness {
+            WireEndianness::Big => ("from_be_bytes", "to_be_bytes"),
+            WireEndianness::Little => ("from_le_bytes", "to_le_bytes"),
+            WireEndianness::Host => ("from_ne_bytes", "to_ne_bytes"),
+        };
+        let out = &mut self.output;
+
+        writeln!(
+            out,
+            "\n/// Size of the ID that precedes an identified message, in bytes."
+        )
+        .unwrap();
+        writeln!(out, "pub const MESSAGE_ID_SIZE: usize = 2;").unwrap();
+        for m in &messages {
+            let id = m.id.unwrap_or_default();
+            writeln!(out, "/// ID of {}.", m.name).unwrap();
+            writeln!(
+                out,
+                "pub const {}_ID: u16 = {id};",
+                m.name.as_str().to_ascii_uppercase()
+            )
+            .unwrap();
+        }
+
+        writeln!(
+            out,
+            "\n/// Returns the bytes of a message ID, which precede the message on the wire."
+        )
+        .unwrap();
+        writeln!(
+            out,
+            "pub fn message_id_bytes(id: u16) -> [u8; MESSAGE_ID_SIZE] {{"
+        )
+        .unwrap();
+        writeln!(out, "{i1}id.{to_method}()").unwrap();
+        writeln!(out, "}}").unwrap();
+
+        writeln!(out, "\n/// A message decoded by `decode_any`, by type.").unwrap();
+        writeln!(out, "#[derive(Debug, Clone, Copy)]").unwrap();
+        writeln!(out, "pub enum AnyMessage<'a> {{").unwrap();
+        for m in &messages {
+            writeln!(out, "{i1}{0}({0}View<'a>),", m.name).unwrap();
+        }
+        writeln!(out, "}}").unwrap();
+        writeln!(out, "\nimpl AnyMessage<'_> {{").unwrap();
+        writeln!(out, "{i1}/// Returns the ID of the message.").unwrap();
+        writeln!(out, "{i1}pub fn id(&self) -> u16 {{").unwrap();
+        writeln!(out, "{i2}match self {{").unwrap();
+        for m in &messages {
+            writeln!(
+                out,
+                "{i3}AnyMessage::{}(_) => {}_ID,",
+                m.name,
+                m.name.as_str().to_ascii_uppercase()
+            )
+            .unwrap();
+        }
+        writeln!(out, "{i2}}}").unwrap();
+        writeln!(out, "{i1}}}").unwrap();
+        writeln!(out, "}}").unwrap();
+
+        writeln!(out, "\n/// Why `decode_any` could not decode a message.").unwrap();
+        writeln!(out, "#[derive(Debug, Clone, Copy, PartialEq, Eq)]").unwrap();
+        writeln!(out, "pub enum DecodeAnyError {{").unwrap();
+        writeln!(
+            out,
+            "{i1}/// The buffer ends before the ID or the message it names."
+        )
+        .unwrap();
+        writeln!(out, "{i1}Truncated,").unwrap();
+        writeln!(out, "{i1}/// No message has the ID.").unwrap();
+        writeln!(out, "{i1}UnknownId(u16),").unwrap();
+        writeln!(out, "}}").unwrap();
+
+        writeln!(
+            out,
+            "\n/// Decodes a message preceded by its ID. Bytes after the message are ignored."
+        )
+        .unwrap();
+        writeln!(
+            out,
+            "pub fn decode_any(buf: &[u8]) -> Result<AnyMessage<'_>, DecodeAnyError> {{"
+        )
+        .unwrap();
+        writeln!(
+            out,
+            "{i1}let id = buf.get(..MESSAGE_ID_SIZE).ok_or(DecodeAnyError::Truncated)?;"
+        )
+        .unwrap();
+        writeln!(
+            out,
+            "{i1}let id = u16::{from_method}(id.try_into().unwrap());"
+        )
+        .unwrap();
+        writeln!(out, "{i1}let body = &buf[MESSAGE_ID_SIZE..];").unwrap();
+        writeln!(out, "{i1}let message = match id {{").unwrap();
+        for m in &messages {
+            let upper = m.name.as_str().to_ascii_uppercase();
+            writeln!(
+                out,
+                "{i2}{upper}_ID => body.get(..{upper}_SIZE).map(|data| AnyMessage::{0}({0}View::new(data.try_into().unwrap()))),",
+                m.name
+            )
+            .unwrap();
+        }
+        writeln!(out, "{i2}_ => return Err(DecodeAnyError::UnknownId(id)),").unwrap();
+        writeln!(out, "{i1}}};").unwrap();
+        writeln!(out, "{i1}message.ok_or(DecodeAnyError::Truncated)").unwrap();
+        writeln!(out, "}}").unwrap();
+    }
+
+    /// Writes `mod v{N}` for an earlier version of the schema. The module redefines the views of
+    /// the structs and messages whose layout changed, with functions upgrading them to the
+    /// current layout, and re-exports everything else.
+    fn write_version(&mut self, hir: &Hir, old: &Hir) {
+        let version = old.module.version.unwrap_or_default();
+        let outer = std::mem::take(&mut self.output);
+        for name in &old.module.order {
+            let Some(layout) = old.layout(*name) else {
+                continue;
+            };
+            if same_layout(old.module, hir.module, &Type::Custom(*name)) {
+                continue;
+            }
+            self.write_view_structs(name.as_str(), layout, old);
+            self.write_upgrade(hir, old, layout);
+        }
+        let body = std::mem::replace(&mut self.output, outer);
+
+        writeln!(self.output, "\n/// Version {version} of the schema.").unwrap();
+        writeln!(self.output, "pub mod v{version} {{").unwrap();
+        let indent = self.config.get_indent(1);
+        writeln!(self.output, "{indent}#[allow(unused_imports)]").unwrap();
+        writeln!(self.output, "{indent}pub use super::*;").unwrap();
+        for line in body.lines() {
+            if line.is_empty() {
+                writeln!(self.output).unwrap();
+            } else {
+                writeln!(self.output, "{indent}{line}").unwrap();
+            }
+        }
+        writeln!(self.output, "}}").unwrap();
+    }
+
+    /// Writes `upgrade` for a definition whose layout changed. Fields present in both layouts
+    /// are copied, and fields added since are left zeroed.
+    fn write_upgrade(&mut self, hir: &Hir, old: &Hir, from_layout: &Layout) {
+        let name = from_layout.name();
+        let Some(to_layout) = hir.layout(name) else {
+            return;
+        };
+        if std::mem::discriminant(from_layout.definition)
+            != std::mem::discriminant(to_layout.definition)
+        {
+            return;
+        }
+        let const_name = name.as_str().to_ascii_uppercase();
+        let (i1, i2, i3) = (
+            self.config.get_indent(1),
+            self.config.get_indent(2),
+            self.config.get_indent(3),
+        );
+
+        let mut body = Vec::new();
+        for field in from_layout.fields() {
+            let Some(to) = to_layout.field(field.name.as_str()).map(|f| f.offset) else {
+                continue;
+            };
+            let from = field.offset;
+            let field_name = field.name;
+            if field.bit_field_size.is_some() {
+                body.push(format!("{i2}target.set_{field_name}(self.{field_name}());"));
+                continue;
+            }
+            let (old_size, new_size) = (
+                old.size_of(&field.type_info).unwrap_or_default(),
+                hir.size_of(&field.type_info).unwrap_or_default(),
+            );
+            // Upgrades nested structs in place, as a statement indented by `indent`.
+            let upgrade = |ty: &str, from: &str, to: &str, old: usize, new: usize, indent: &str| {
+                format!(
+                    "{indent}{ty}View::new(self.data[{from}..{from} + {old}].try_into().unwrap())\n\
+                     {indent}{i1}.upgrade_into(&mut super::{ty}MutView::new(\n\
+                     {indent}{i1}{i1}(&mut target.data[{to}..{to} + {new}]).try_into().unwrap(),\n\
+                     {indent}{i1}));"
+                )
+            };
+            match &field.type_info {
+                ty if same_layout(old.module, hir.module, ty) => body.push(format!(
+                    "{i2}target.data[{to}..{}].copy_from_slice(&self.data[{from}..{}]);",
+                    to + new_size,
+                    from + old_size
+                )),
+                Type::Custom(ty) => body.push(upgrade(
+                    ty.as_str(),
+                    &from.to_string(),
+                    &to.to_string(),
+                    old_size,
+                    new_size,
+                    &i2,
+                )),
+                Type::Vector { element, capacity } => {
+                    let Type::Custom(ty) = element.as_ref() else {
+                        continue;
+                    };
+                    let length = field.type_info.length_type().unwrap().get_byte_size();
+                    let (old_size, new_size) = (
+                        old.size_of(element).unwrap_or_default(),
+                        hir.size_of(element).unwrap_or_default(),
+                    );
+                    body.push(format!(
+                        "{i2}target.data[{to}..{}].copy_from_slice(&self.data[{from}..{}]);",
+                        to + length,
+                        from + length
+                    ));
+                    body.push(format!("{i2}for i in 0..{capacity} {{"));
+                    body.push(format!(
+                        "{i3}let (from, to) = ({} + i * {old_size}, {} + i * {new_size});",
+                        from + length,
+                        to + length
+                    ));
+                    body.push(upgrade(ty.as_str(), "from", "to", old_size, new_size, &i3));
+                    body.push(format!("{i2}}}"));
+                }
+                _ => {}
+            }
+        }
+        if body.is_empty() {
+            body.push(format!("{i2}let _ = target;"));
+        }
+
+        let out = &mut self.output;
+        writeln!(out, "\nimpl {name}View<'_> {{").unwrap();
+        writeln!(
+            out,
+            "{i1}/// Converts to the current layout. Fields added since are zeroed."
+        )
+        .unwrap();
+        writeln!(out, "{i1}pub fn upgrade(&self) -> super::{name}Buffer {{").unwrap();
+        writeln!(out, "{i2}let mut buffer = [0; super::{const_name}_SIZE];").unwrap();
+        writeln!(
+            out,
+            "{i2}self.upgrade_into(&mut super::{name}MutView::new(&mut buffer));"
+        )
+        .unwrap();
+        writeln!(out, "{i2}buffer").unwrap();
+        writeln!(out, "{i1}}}").unwrap();
+        writeln!(
+            out,
+            "\n{i1}fn upgrade_into(&self, target: &mut super::{name}MutView<'_>) {{"
+        )
+        .unwrap();
+        for line in body {
+            writeln!(out, "{line}").unwrap();
+        }
+        writeln!(out, "{i1}}}").unwrap();
+        writeln!(out, "}}").unwrap();
+    }
+
+    fn write_bitfield_accessors(
+        &mut self,
+        slot: &Slot,
+        is_mut: bool,
+        from_method: &str,
+        to_method: &str,
+    ) {
+        let (offset, container_bytes) = (slot.offset, slot.size);
+        let (int_type, width) = match container_bytes {
+            1 => ("u8", 1),
+            2 => ("u16", 2),
+            3 | 4 => ("u32", 4),
+            5..=8 => ("u64", 8),
+            _ => ("u128", 16),
+        };
+        // A container narrower than its integer occupies the least significant bytes of it.
+        let padding = width - container_bytes;
+        let (at, at_line) = match from_method {
+            _ if padding == 0 => (String::new(), None),
+            "from_be_bytes" => (padding.to_string(), None),
+            "from_le_bytes" => ("0".to_string(), None),
+            _ => (
+                "at".to_string(),
+                Some(format!(
+                    "let at = if cfg!(target_endian = \"big\") {{ {padding} }} else {{ 0 }};"
+                )),
+            ),
+        };
+        let read_container = |raw: &str| {
+            let range = format!("{offset}..{}", offset + container_bytes);
+            let mut lines = Vec::new();
+            if padding == 0 {
+                lines.push(format!(
+                    "let bytes = self.data[{range}].try_into().unwrap();"
+                ));
+            } else {
+                lines.extend(at_line.clone());
+                lines.push(format!("let mut bytes = [0u8; {width}];"));
+                lines.push(format!(
+                    "bytes[{at}..{at} + {container_bytes}].copy_from_slice(&self.data[{range}]);"
+                ));
+            }
+            lines.push(format!("let {raw} = {int_type}::{from_method}(bytes);"));
+            lines
+        };
+
+        for field in &slot.fields {
+            let field_type_str = self.get_primitive_rust_type(&field.type_info);
+            let mask = bit_mask(field.bits);
+            let shift = field.bit_offset;
+
+            // Accessor
+            writeln!(
+                self.output,
+                "\n{}/// Accessor for {}.",
+                self.config.get_indent(1),
+                field.name
+            )
+            .unwrap();
+            writeln!(
+                self.output,
+                "{}pub fn {}(&self) -> {} {{",
+                self.config.get_indent(1),
+                field.name,
+                field_type_str
+            )
+            .unwrap();
+
+            // Read container
+            if container_bytes == 1 {
+                writeln!(
+                    self.output,
+                    "{}let raw = self.data[{}] as {};",
+                    self.config.get_indent(2),
+                    offset,
+                    int_type
+                )
+                .unwrap();
+            } else {
+                for line in read_container("raw") {
+                    writeln!(self.output, "{}{line}", self.config.get_indent(2)).unwrap();
+                }
+            }
+
+            writeln!(
+                self.output,
+                "{}let val = (raw >> {}) & 0x{:X};",
+                self.config.get_indent(2),
+                shift,
+                mask
+            )
+            .unwrap();
+
+            if field_type_str == "bool" {
+                writeln!(self.output, "{}val != 0", self.config.get_indent(2)).unwrap();
+            } else if let Type::Primitive(p) = field.type_info
+                && p.is_signed()
+                && (1..p.get_bit_width()).contains(&field.bits)
+            {
+                // Shifting the field's sign bit to the top of the type and back sign-extends it.
+                let unused = p.get_bit_width() - field.bits;
+                writeln!(
+                    self.output,
+                    "{}((val as {field_type_str}) << {unused}) >> {unused}",
+                    self.config.get_indent(2),
+                )
+                .unwrap();
+            } else {
+                writeln!(
+                    self.output,
+                    "{}val as {}",
+                    self.config.get_indent(2),
+                    field_type_str
+                )
+                .unwrap();
+            }
+            writeln!(self.output, "{}}}", self.config.get_indent(1)).unwrap();
+
+            // Mutator
+            if is_mut {
+                writeln!(
+                    self.output,
+                    "\n{}/// Mutator for {}.",
+                    self.config.get_indent(1),
+                    field.name
+                )
+                .unwrap();
+                writeln!(
+                    self.output,
+                    "{}pub fn set_{}(&mut self, value: {}) {{",
+                    self.config.get_indent(1),
+                    field.name,
+                    field_type_str
+                )
+                .unwrap();
+
+                // Read container
+                if container_bytes == 1 {
+                    writeln!(
+                        self.output,
+                        "{}let mut raw = self.data[{}] as {};",
+                        self.config.get_indent(2),
+                        offset,
+                        int_type
+                    )
+                    .unwrap();
+                } else {
+                    for line in read_container("mut raw") {
+                        writeln!(self.output, "{}{line}", self.config.get_indent(2)).unwrap();
+                    }
+                }
+
+                // Modify
                 writeln!(
                     self.output,
                     "{}raw &= !((0x{:X} as {}) << {});",
@@ -790,9 +2795,13 @@ impl RustGenerator {
                         to_method
                     )
                     .unwrap();
+                    let bytes = match padding {
+                        0 => "&bytes".to_string(),
+                        _ => format!("&bytes[{at}..{at} + {container_bytes}]"),
+                    };
                     writeln!(
                         self.output,
-                        "{}self.data[{}..{}].copy_from_slice(&bytes);",
+                        "{}self.data[{}..{}].copy_from_slice({bytes});",
                         self.config.get_indent(2),
                         offset,
                         offset + container_bytes
@@ -801,37 +2810,382 @@ impl RustGenerator {
                 }
                 writeln!(self.output, "{}}}", self.config.get_indent(1)).unwrap();
             }
+        }
+    }
+
+    /// Returns `tests/vectors.rs` of a crate, with a test per vector of `module`, the module as
+    /// renamed for Rust.
+    fn vectors_test(&self, module: &OnyxModule, crate_name: &str) -> Result<String, CompileError> {
+        let i1 = self.config.get_indent(1);
+        let mut out = String::new();
+        writeln!(out, "{}", comment_lines(&self.banner_lines, "//")).unwrap();
+        if module.endianness == WireEndianness::Host {
+            let endian = match cfg!(target_endian = "little") {
+                true => "little",
+                false => "big",
+            };
+            writeln!(out, "#![cfg(target_endian = \"{endian}\")]").unwrap();
+        }
+        if used_features(module).contains(&Feature::Deprecations) {
+            writeln!(out, "#![allow(deprecated)]").unwrap();
+        }
+        writeln!(out, "\nuse {crate_name}::*;").unwrap();
+        for vector in vectors::vectors(module)? {
+            let name = vector.definition;
+            let def = &module.definitions[&name];
+            let bytes: Vec<String> = vector.bytes.iter().map(|b| format!("{b:#04x}")).collect();
+            writeln!(out, "\n#[test]").unwrap();
+            writeln!(
+                out,
+                "fn {}_{}_vector() {{",
+                name.as_str().to_ascii_lowercase(),
+                vector.name
+            )
+            .unwrap();
+            writeln!(out, "{i1}let bytes: {name}Buffer = [{}];", bytes.join(", ")).unwrap();
+            writeln!(out, "{i1}let view = {name}View::new(&bytes);").unwrap();
+            let leaves = vectors::leaves(module, def, &vector.value);
+            for leaf in &leaves {
+                let getter: String = leaf
+                    .path
+                    .iter()
+                    .map(|step| match step {
+                        Step::Field(f) => format!(".{f}()"),
+                        Step::Index(i) => format!(".get({i}).unwrap()"),
+                    })
+                    .collect();
+                let (actual, expected) = match &leaf.kind {
+                    LeafKind::Primitive(PrimitiveType::F32 | PrimitiveType::F64) => (
+                        format!("view{getter}.to_bits()"),
+                        format!("{:#x}", raw_bits(&leaf.value)),
+                    ),
+                    LeafKind::Enum(e) if !e.flags && !e.open => (
+                        format!("view{getter}"),
+                        format!("Ok({})", rust_vector_value(&leaf.kind, &leaf.value)),
+                    ),
+                    LeafKind::Fixed(_) => (
+                        format!("view{}_raw()", getter.strip_suffix("()").unwrap()),
+                        rust_vector_value(&leaf.kind, &leaf.value),
+                    ),
+                    LeafKind::Length(_) => (
+                        format!("view{getter}.len()"),
+                        raw_bits(&leaf.value).to_string(),
+                    ),
+                    kind => (
+                        format!("view{getter}"),
+                        rust_vector_value(kind, &leaf.value),
+                    ),
+                };
+                writeln!(out, "{i1}assert_eq!({actual}, {expected});").unwrap();
+            }
+
+            let mut writes = Vec::new();
+            for leaf in &leaves {
+                let (last, parents) = leaf.path.split_last().unwrap();
+                let parents: String = parents
+                    .iter()
+                    .map(|step| match step {
+                        Step::Field(f) => format!(".{f}_mut()"),
+                        Step::Index(i) => format!(".get_mut({i}).unwrap()"),
+                    })
+                    .collect();
+                let value = || rust_vector_value(&leaf.kind, &leaf.value);
+                writes.push(match (&leaf.kind, last) {
+                    // Struct elements are pushed zeroed, then filled in through `get_mut`.
+                    (LeafKind::Length(Some(_)), Step::Field(f)) => format!(
+                        "for _ in 0..{} {{ view{parents}.{f}_mut().push().unwrap(); }}",
+                        raw_bits(&leaf.value)
+                    ),
+                    (LeafKind::Length(_), _) => continue,
+                    (LeafKind::Fixed(_), Step::Field(f)) => {
+                        format!("view{parents}.set_{f}_raw({});", value())
+                    }
+                    (_, Step::Field(f)) => format!("view{parents}.set_{f}({});", value()),
+                    (_, Step::Index(_)) => format!("view{parents}.push({}).unwrap();", value()),
+                });
+            }
+            let size = vector.bytes.len();
+            match writes.is_empty() {
+                true => writeln!(out, "{i1}let out: {name}Buffer = [0; {size}];").unwrap(),
+                false => {
+                    writeln!(out, "{i1}let mut out: {name}Buffer = [0; {size}];").unwrap();
+                    writeln!(out, "{i1}let mut view = {name}MutView::new(&mut out);").unwrap();
+                }
+            }
+            for write in writes {
+                writeln!(out, "{i1}{write}").unwrap();
+            }
+            writeln!(out, "{i1}assert_eq!(out, bytes);").unwrap();
+            writeln!(out, "}}").unwrap();
+        }
+        Ok(out)
+    }
+
+    /// Returns the `README.md` of a crate, which lists what the schema defines.
+    fn crate_readme(&self, config: &CrateConfig, hir: &Hir) -> String {
+        let module = hir.module;
+        let mut out = String::new();
+        writeln!(out, "# {}\n", config.name).unwrap();
+        writeln!(
+            out,
+            "Rust bindings generated from `{}.onyx` by the Onyx IDL compiler. Regenerate them \
+             from the schema rather than editing them by hand.\n",
+            self.file_stem
+        )
+        .unwrap();
+        writeln!(out, "```toml\n[dependencies]").unwrap();
+        writeln!(out, "{} = {}", config.name, json::string(&config.version)).unwrap();
+        writeln!(out, "```").unwrap();
+
+        let (mut messages, mut structs, mut enums) = (Vec::new(), Vec::new(), Vec::new());
+        for name in &module.order {
+            match module.definitions.get(name) {
+                Some(Definition::Message(m)) => {
+                    let size = hir.layout(*name).unwrap().size;
+                    messages.push(match m.id {
+                        Some(id) => format!("`{name}`: {size} bytes, ID {id}"),
+                        None => format!("`{name}`: {size} bytes"),
+                    });
+                }
+                Some(Definition::Struct(_)) => {
+                    let size = hir.layout(*name).unwrap().size;
+                    structs.push(format!("`{name}`: {size} bytes"));
+                }
+                Some(Definition::Enum(e)) => {
+                    enums.push(format!("`{name}`: {}", e.underlying_type));
+                }
+                None => {}
+            }
+        }
+        for (heading, items) in [
+            ("Messages", messages),
+            ("Structs", structs),
+            ("Enums", enums),
+        ] {
+            if items.is_empty() {
+                continue;
+            }
+            writeln!(out, "\n## {heading}\n").unwrap();
+            for item in items {
+                writeln!(out, "- {item}").unwrap();
+            }
+        }
+        out
+    }
+}
+
+/// Returns the prefix of the view types of the vector `field` of `struct_name`, e.g.
+/// `PathPoints` for `Path.points`.
+fn vector_view_name(struct_name: &str, field: &Field) -> String {
+    let mut name = struct_name.to_string();
+    for word in field.name.as_str().split('_') {
+        let mut chars = word.chars();
+        if let Some(first) = chars.next() {
+            name.push(first.to_ascii_uppercase());
+            name.extend(chars);
+        }
+    }
+    name
+}
 
-            current_bit_offset += bits;
+/// Returns the Rust expression of the value of a test vector leaf.
+fn rust_vector_value(kind: &LeafKind, value: &Value) -> String {
+    match (kind, value) {
+        (_, Value::Bool(b)) => b.to_string(),
+        (_, Value::F32(f)) => format!("f32::from_bits({:#x})", f.to_bits()),
+        (_, Value::F64(f)) => format!("f64::from_bits({:#x})", f.to_bits()),
+        (_, Value::Bytes(bytes)) => {
+            let bytes: Vec<String> = bytes.iter().map(|b| format!("{b:#04x}")).collect();
+            format!("[{}]", bytes.join(", "))
+        }
+        (LeafKind::Enum(e), value) if e.flags => format!(
+            "{}::from_bits_retain({})",
+            e.name,
+            rust_integer(e.underlying_type, value)
+        ),
+        (LeafKind::Enum(e), value) if e.open => {
+            format!(
+                "{}::from({})",
+                e.name,
+                rust_integer(e.underlying_type, value)
+            )
+        }
+        (LeafKind::Enum(e), value) => {
+            format!("{}::{}", e.name, vectors::variant_name(e, value).unwrap())
         }
+        (LeafKind::Primitive(p) | LeafKind::Fixed(p), value) => rust_integer(*p, value),
+        (_, value) => unreachable!("{value:?} is not the value of a field"),
+    }
+}
+
+/// Returns an integer literal of type `p`, such as `-5i8`.
+fn rust_integer(p: PrimitiveType, value: &Value) -> String {
+    match value {
+        Value::Signed(v) => format!("{v}{p}"),
+        _ => format!("{}{p}", raw_bits(value)),
     }
 }
 
+/// Returns true if `name` can name a module without a raw identifier.
+fn is_module_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+        && name != "_"
+        && !RESERVED.contains(&name)
+}
+
 impl CodeGenerator for RustGenerator {
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(level = "info", name = "RustGenerator::generate", skip_all, fields(path = %self.file_path.display()))
+    )]
     fn generate(&mut self, module: &OnyxModule) -> Result<Vec<(PathBuf, String)>, CompileError> {
+        self.banner_lines = self.banner.lines(module)?;
+        let constants = match self.constants {
+            true => Some(self.constants_file(module)?),
+            false => None,
+        };
+        let vectors_json = match self.vectors {
+            true => Some(vectors::to_json(module, &vectors::vectors(module)?)?),
+            false => None,
+        };
+        check_order(module)?;
+        let named = apply_field_naming(module, self.field_naming)?;
+        let module = mangle_reserved(&named, RESERVED)?;
+        let module = module.as_ref();
+        let hir = hir::lower(module)?;
         self.output.clear();
-        self.write_header();
+        self.write_header(module);
 
         for name in &module.order {
+            trace::debug!(definition = %name, "generating definition");
             if let Some(def) = module.definitions.get(name) {
+                self.write_source_comment(def.span(), 0);
                 match def {
                     Definition::Enum(e) => self.write_enum(e)?,
-                    Definition::Struct(s) => {
-                        let field_groups = self.get_field_groups(&s.fields);
-                        self.write_view_structs(&s.name, &field_groups, module, def);
-                    }
-                    Definition::Message(m) => {
-                        let field_groups = self.get_field_groups(&m.fields);
-                        self.write_view_structs(&m.name, &field_groups, module, def);
+                    Definition::Struct(_) | Definition::Message(_) => {
+                        self.write_view_structs(name.as_str(), hir.layout(*name).unwrap(), &hir);
                     }
                 }
             }
         }
+        if used_features(module).contains(&Feature::ByteArrays) {
+            self.write_hex_formatter();
+        }
+        if self.message_arena {
+            self.write_message_arena(module);
+        }
+        if self.reflection && module.order.iter().any(|name| hir.layout(*name).is_some()) {
+            self.write_field_descriptor_types();
+        }
+        self.write_dispatch(module);
+        for old in &module.history {
+            self.write_version(&hir, &hir::lower(old)?);
+        }
+
+        let Some(config) = &self.crate_config else {
+            let mut output_path = self.file_path.clone();
+            output_path.set_extension("rs");
+            let mut files = vec![(output_path, self.output.clone())];
+            if let Some(constants) = constants {
+                let path = self
+                    .file_path
+                    .with_file_name(format!("{}_constants.rs", self.file_stem));
+                files.push((path, constants));
+            }
+            if let Some(vectors_json) = vectors_json {
+                let path = self
+                    .file_path
+                    .with_file_name(format!("{}.vectors.json", self.file_stem));
+                files.push((path, vectors_json));
+            }
+            return Ok(files);
+        };
+
+        let root = self.file_path.parent().unwrap_or(Path::new(""));
+        let module_name: String = self
+            .file_stem
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+            .collect();
+        let crate_name = config.name.replace('-', "_");
+
+        let mut manifest = comment_lines(&self.banner_lines, "#");
+        writeln!(manifest, "[package]").unwrap();
+        writeln!(manifest, "name = {}", json::string(&config.name)).unwrap();
+        writeln!(manifest, "version = {}", json::string(&config.version)).unwrap();
+        writeln!(manifest, "edition = \"2021\"").unwrap();
+        writeln!(
+            manifest,
+            "description = {}",
+            json::string(&format!(
+                "Generated from {}.onyx by the Onyx IDL compiler.",
+                self.file_stem
+            ))
+        )
+        .unwrap();
+        if let Some(license) = &config.license {
+            writeln!(manifest, "license = {}", json::string(license)).unwrap();
+        }
+        writeln!(manifest, "readme = \"README.md\"").unwrap();
+        writeln!(manifest, "categories = [\"encoding\"]").unwrap();
 
-        let mut output_path = self.file_path.clone();
-        output_path.set_extension("rs");
+        let mut lib = String::new();
+        writeln!(lib, "{}", comment_lines(&self.banner_lines, "//")).unwrap();
+        writeln!(lib, "pub mod {module_name};").unwrap();
+        if constants.is_some() {
+            writeln!(lib, "pub mod {module_name}_constants;").unwrap();
+        }
+        writeln!(lib, "\npub use {module_name}::*;").unwrap();
+
+        let mut tests = String::new();
+        writeln!(tests, "{}", comment_lines(&self.banner_lines, "//")).unwrap();
+        if used_features(module).contains(&Feature::Deprecations) {
+            writeln!(tests, "#![allow(deprecated)]\n").unwrap();
+        }
+        writeln!(tests, "use {crate_name}::*;").unwrap();
+        for name in &module.order {
+            let Some(size) = hir.layout(*name).map(|layout| layout.size) else {
+                continue;
+            };
+            writeln!(tests, "\n#[test]").unwrap();
+            writeln!(
+                tests,
+                "fn {}_layout() {{",
+                name.as_str().to_ascii_lowercase()
+            )
+            .unwrap();
+            let indent = self.config.get_indent(1);
+            writeln!(tests, "{indent}let mut buf: {name}Buffer = [0; {size}];").unwrap();
+            writeln!(tests, "{indent}{name}MutView::new(&mut buf);").unwrap();
+            writeln!(tests, "{indent}{name}View::new(&buf);").unwrap();
+            writeln!(tests, "}}").unwrap();
+        }
 
-        Ok(vec![(output_path, self.output.clone())])
+        let mut files = vec![
+            (root.join("Cargo.toml"), manifest),
+            (root.join("README.md"), self.crate_readme(config, &hir)),
+            (root.join("src").join("lib.rs"), lib),
+            (
+                root.join("src").join(format!("{module_name}.rs")),
+                self.output.clone(),
+            ),
+            (root.join("tests").join("layout.rs"), tests),
+        ];
+        if let Some(constants) = constants {
+            let path = root.join("src").join(format!("{module_name}_constants.rs"));
+            files.push((path, constants));
+        }
+        if let Some(vectors_json) = vectors_json {
+            let path = root.join(format!("{}.vectors.json", self.file_stem));
+            files.push((path, vectors_json));
+            let tests = self.vectors_test(module, &crate_name)?;
+            files.push((root.join("tests").join("vectors.rs"), tests));
+        }
+        Ok(files)
     }
 }
 
@@ -846,6 +3200,440 @@ mod tests {
         assert_eq!(config.get_indent(2), "        ");
     }
 
+    #[test]
+    fn test_crate_output() {
+        let module =
+            crate::parser::Parser::new("enum E : u8 { A, } struct Point { x i32, y i32, }")
+                .unwrap()
+                .parse_module()
+                .unwrap();
+        let mut generator = RustGenerator::default();
+        generator
+            .add_file_path(PathBuf::from("out/point-v2.onyx"))
+            .unwrap();
+        generator
+            .set_crate(CrateConfig::new("point-schema", "1.2.0").with_license("MIT"))
+            .unwrap();
+
+        let files = generator.generate(&module).unwrap();
+        let paths: Vec<PathBuf> = files.iter().map(|(path, _)| path.clone()).collect();
+        assert_eq!(
+            paths,
+            [
+                "out/Cargo.toml",
+                "out/README.md",
+                "out/src/lib.rs",
+                "out/src/point_v2.rs",
+                "out/tests/layout.rs"
+            ]
+            .map(PathBuf::from)
+        );
+        assert!(
+            files[0]
+                .1
+                .contains("name = \"point-schema\"\nversion = \"1.2.0\"\n")
+        );
+        assert!(
+            files[0]
+                .1
+                .contains("license = \"MIT\"\nreadme = \"README.md\"\n")
+        );
+        assert!(files[1].1.starts_with("# point-schema\n"));
+        assert!(files[1].1.contains("point-schema = \"1.2.0\"\n"));
+        assert!(
+            files[1]
+                .1
+                .ends_with("## Structs\n\n- `Point`: 8 bytes\n\n## Enums\n\n- `E`: u8\n")
+        );
+        assert!(!files[1].1.contains("## Messages"));
+        assert!(files[2].1.contains("pub mod point_v2;"));
+        assert!(files[4].1.contains("use point_schema::*;"));
+        assert!(files[4].1.contains("let mut buf: PointBuffer = [0; 8];"));
+        assert!(!files[4].1.contains("fn e_layout"));
+
+        let err = generator
+            .set_crate(CrateConfig::new("bad name", "0.1.0"))
+            .unwrap_err();
+        assert_eq!(err.code(), Some(codes::CODEGEN));
+    }
+
+    #[test]
+    fn test_traced_output() {
+        let source = "struct Point { x u16, y u16, } message Line { a Point, b Point if flag == true, flag bool, }";
+        let module = crate::parser::Parser::new(source)
+            .and_then(|p| p.parse_module())
+            .unwrap();
+        let mut generator = RustGenerator::default();
+        generator.add_file_path(PathBuf::from("line")).unwrap();
+        assert!(
+            !generator.generate(&module).unwrap()[0]
+                .1
+                .contains("trace_fields")
+        );
+
+        generator.set_trace(true);
+        let output = &generator.generate(&module).unwrap()[0].1;
+        assert_eq!(output.matches("pub fn trace_fields(").count(), 2);
+        assert!(output.contains("f(&format!(\"{path}x\"), offset, &self.x());"));
+        assert!(output.contains("f(&format!(\"{path}y\"), offset + 2, &self.y());"));
+        assert!(output.contains(
+            "if self.has_b() {\n            self.b().trace_fields_at(&format!(\"{path}b.\"), offset + 4, f);"
+        ));
+    }
+
+    #[test]
+    fn test_shared_memory_output() {
+        let module = crate::parser::Parser::new("struct Point { x u16, y u16, }")
+            .and_then(|p| p.parse_module())
+            .unwrap();
+        let mut generator = RustGenerator::default();
+        generator.add_file_path(PathBuf::from("point")).unwrap();
+        assert!(
+            !generator.generate(&module).unwrap()[0]
+                .1
+                .contains("from_raw_parts")
+        );
+
+        generator.set_shared_memory(true);
+        let output = &generator.generate(&module).unwrap()[0].1;
+        assert!(output.contains(
+            "pub unsafe fn from_raw_parts(ptr: *const u8, len: usize) -> Option<Self> {"
+        ));
+        assert!(
+            output.contains(
+                "pub unsafe fn from_raw_parts(ptr: *mut u8, len: usize) -> Option<Self> {"
+            )
+        );
+        assert!(output.contains("Some(Self::new(unsafe { &mut *ptr.cast::<PointBuffer>() }))"));
+        assert!(output.contains("std::mem::align_of::<PointBuffer>() != 0"));
+    }
+
+    #[test]
+    fn test_message_arena_output() {
+        let source =
+            "struct Point { x u16, } message Ping { seq u16, } message Move { to Point, at u64, }";
+        let module = crate::parser::Parser::new(source)
+            .and_then(|p| p.parse_module())
+            .unwrap();
+        let mut generator = RustGenerator::default();
+        generator.add_file_path(PathBuf::from("arena")).unwrap();
+        generator.set_message_arena(true);
+        let output = &generator.generate(&module).unwrap()[0].1;
+        assert!(output.contains("pub const MESSAGE_ARENA_SLOT_SIZE: usize = 10;"));
+        assert!(output.contains("pub fn acquire(&self) -> Option<ArenaBuffer<'_>> {"));
+        assert!(output.contains("impl Drop for ArenaBuffer<'_> {"));
+
+        let module = crate::parser::Parser::new("struct Point { x u16, }")
+            .and_then(|p| p.parse_module())
+            .unwrap();
+        assert!(
+            !generator.generate(&module).unwrap()[0]
+                .1
+                .contains("MessageArena")
+        );
+    }
+
+    #[test]
+    fn test_reflection_output() {
+        let source = "enum Kind : u8 { A, } struct Point { x i16, y i16, } \
+                      message Shape { kind Kind, ready bool : 1, level u8 : 7, corners vec<Point, 4>, }";
+        let module = crate::parser::Parser::new(source)
+            .and_then(|p| p.parse_module())
+            .unwrap();
+        let mut generator = RustGenerator::default();
+        generator.add_file_path(PathBuf::from("shape")).unwrap();
+        assert!(
+            !generator.generate(&module).unwrap()[0]
+                .1
+                .contains("FieldDescriptor")
+        );
+
+        generator.set_reflection(true);
+        let output = &generator.generate(&module).unwrap()[0].1;
+        assert_eq!(
+            output
+                .matches("pub const FIELDS: &'static [FieldDescriptor] = &[")
+                .count(),
+            2
+        );
+        assert!(output.contains("FieldDescriptor { name: \"level\", offset: 1, bit_offset: 1, bits: 7, kind: FieldKind::Unsigned, type_name: \"u8\", conditional: false },"));
+        assert!(output.contains("FieldDescriptor { name: \"corners\", offset: 2, bit_offset: 0, bits: 136, kind: FieldKind::Vector, type_name: \"vec<Point, 4>\", conditional: false },"));
+        assert_eq!(output.matches("pub struct FieldDescriptor {").count(), 1);
+
+        let module = crate::parser::Parser::new("enum Kind : u8 { A, }")
+            .and_then(|p| p.parse_module())
+            .unwrap();
+        assert!(
+            !generator.generate(&module).unwrap()[0]
+                .1
+                .contains("FieldDescriptor")
+        );
+    }
+
+    #[test]
+    fn test_source_map_output() {
+        let source = "enum Kind : u8 { A, }\n@packed_bits message Ping {\n    kind Kind,\n    ready bool : 1,\n}\n";
+        let module = crate::parser::Parser::new(source)
+            .and_then(|p| p.parse_module())
+            .unwrap();
+        let mut generator = RustGenerator::default();
+        generator.add_file_path(PathBuf::from("ping")).unwrap();
+        assert!(
+            !generator.generate(&module).unwrap()[0]
+                .1
+                .contains("// onyx:")
+        );
+
+        generator.set_source_map(SchemaSource::new("schemas/ping.onyx", source));
+        let output = &generator.generate(&module).unwrap()[0].1;
+        assert!(output.contains("\n\n// onyx: schemas/ping.onyx:1:6\n#[repr(u8)]\n"));
+        assert!(output.contains("}\n\n// onyx: schemas/ping.onyx:2:22\n\n/// Size of Ping"));
+        // The read-only and mutable views each point their accessors at the field.
+        for field in ["3:5", "4:5"] {
+            let comment = format!("}}\n\n    // onyx: schemas/ping.onyx:{field}\n");
+            assert_eq!(output.matches(&comment).count(), 2);
+        }
+    }
+
+    #[test]
+    fn test_constants_output() {
+        let source = "enum Kind : u8 { Data, DATA_READY = 4, }\n\
+                      struct type { x u8, }\n\
+                      @id(7) message Packet { kind Kind, len u32, }";
+        let module = crate::parser::Parser::new(source)
+            .and_then(|p| p.parse_module())
+            .unwrap();
+        let mut generator = RustGenerator::default();
+        generator.add_file_path(PathBuf::from("out/ping")).unwrap();
+        generator.set_constants(true);
+        let files = generator.generate(&module).unwrap();
+        assert_eq!(files[1].0, PathBuf::from("out/ping_constants.rs"));
+        let constants = &files[1].1;
+        let hir = hir::lower(&module).unwrap();
+        assert!(constants.contains(&format!(
+            "pub const SCHEMA_FINGERPRINT: u64 = {:#018x};",
+            fingerprint::module(&hir)
+        )));
+        assert!(constants.contains(
+            "pub mod kind {\n    pub const DATA: u8 = 0;\n    pub const DATA_READY: u8 = 4;\n    \
+             pub const SIZE: usize = 1;\n"
+        ));
+        assert!(constants.contains("pub mod type_ {\n    pub const SIZE: usize = 1;\n"));
+        assert!(constants.contains(&format!(
+            "pub mod packet {{\n    pub const ID: u16 = 7;\n    pub const SIZE: usize = 5;\n    \
+             pub const FINGERPRINT: u64 = {:#018x};\n}}",
+            fingerprint::definition(&hir, "Packet").unwrap()
+        )));
+
+        generator
+            .set_crate(CrateConfig::new("ping", "0.1.0"))
+            .unwrap();
+        let files = generator.generate(&module).unwrap();
+        let lib = &files
+            .iter()
+            .find(|(path, _)| path.ends_with("lib.rs"))
+            .unwrap()
+            .1;
+        assert!(lib.contains("pub mod ping_constants;"));
+        assert!(
+            files
+                .iter()
+                .any(|(path, _)| path.ends_with("src/ping_constants.rs"))
+        );
+
+        let module = crate::parser::Parser::new("enum Kind : u8 { A, Fingerprint, }")
+            .and_then(|p| p.parse_module())
+            .unwrap();
+        let err = generator.generate(&module).unwrap_err();
+        assert_eq!(
+            err.message(),
+            "two protocol constants are named 'FINGERPRINT' in the constants of enum 'Kind'"
+        );
+        assert_eq!(err.code(), Some(codes::NAME_COLLISION));
+    }
+
+    #[test]
+    fn test_deprecated_output() {
+        let source = "@deprecated(\"use Kind\") enum Old : u8 { A, }\n\
+                      @deprecated(\"see C:\\docs\") struct Header { len u8, }\n\
+                      message Ping { @deprecated(\"use ready\") old Old, ready bool : 1, \
+                      @deprecated(\"unused\") spare u8 : 7, }";
+        let module = crate::parser::Parser::new(source)
+            .and_then(|p| p.parse_module())
+            .unwrap();
+        let mut generator = RustGenerator::default();
+        generator.add_file_path(PathBuf::from("ping")).unwrap();
+        let output = &generator.generate(&module).unwrap()[0].1;
+        // The generated code uses what it deprecates without warnings.
+        assert!(output.starts_with(
+            "// Automatically generated by Onyx IDL compiler. Do not edit.\n#![allow(deprecated)]\n"
+        ));
+        assert!(output.contains("#[deprecated(note = \"use Kind\")]\n#[repr(u8)]\n"));
+        // The buffer alias and both views.
+        let header = "#[deprecated(note = \"see C:\\\\docs\")]\n";
+        assert_eq!(output.matches(header).count(), 3);
+        assert!(output.contains(&format!("{header}pub type HeaderBuffer")));
+        assert_eq!(
+            output
+                .matches("    #[deprecated(note = \"use ready\")]\n")
+                .count(),
+            3
+        );
+        assert!(output.contains("    #[deprecated(note = \"unused\")]\n    pub fn set_spare("));
+        assert!(output.contains("    /// Accessor for ready.\n    pub fn ready(&self)"));
+    }
+
+    #[test]
+    fn test_doc_comment_output() {
+        let source = "/// Link quality.\nenum Level : u8 { /// No signal.\n Low, High, }\n\
+                      /// Access rights.\nflags Access : u8 { /// May read.\n Read, Write, }\n\
+                      /// A ping.\n///\n/// Sent once a second.\n\
+                      message Ping { /// Signal level.\n @deprecated(\"use ready\") level Level, \
+                      ready bool : 1, /// Unused.\n spare u8 : 7, }";
+        let module = crate::parser::Parser::new(source)
+            .and_then(|p| p.parse_module())
+            .unwrap();
+        let mut generator = RustGenerator::default();
+        generator.add_file_path(PathBuf::from("ping")).unwrap();
+        let output = &generator.generate(&module).unwrap()[0].1;
+        assert!(output.contains("/// Link quality.\n#[repr(u8)]\n"));
+        assert!(output.contains("    /// No signal.\n    Low = 0,\n    High = 1,\n"));
+        assert!(output.contains("/// Access rights.\n#[repr(transparent)]\n"));
+        assert!(output.contains("    /// May read.\n    pub const Read: Self = Self(1);\n"));
+        // The description follows that of each view.
+        let doc = "///\n/// A ping.\n///\n/// Sent once a second.\n#[derive(";
+        assert!(output.contains(&format!("/// Read-only view of Ping.\n{doc}")));
+        assert!(output.contains(&format!("/// Read-write view of Ping.\n{doc}")));
+        // Each accessor of a field has its description, before any deprecation.
+        assert_eq!(
+            output
+                .matches(
+                    "    /// Accessor for level.\n    ///\n    /// Signal level.\n    \
+                     #[deprecated(note = \"use ready\")]\n    pub fn level("
+                )
+                .count(),
+            2
+        );
+        assert!(output.contains(
+            "    /// Mutator for spare.\n    ///\n    /// Unused.\n    pub fn set_spare("
+        ));
+        assert!(output.contains("    /// Accessor for ready.\n    pub fn ready(&self)"));
+    }
+
+    #[test]
+    fn test_signed_bit_fields() {
+        let source = "struct Reading { temp i16 : 12, trend i8 : 4, full i8 : 8, level u8 : 8, \
+                      stamp u32, wide i64 : 64, }";
+        let module = crate::parser::Parser::new(source)
+            .and_then(|p| p.parse_module())
+            .unwrap();
+        let mut generator = RustGenerator::default();
+        generator.add_file_path(PathBuf::from("reading")).unwrap();
+        let output = &generator.generate(&module).unwrap()[0].1;
+        assert!(
+            output.contains("let val = (raw >> 0) & 0xFFF;\n        ((val as i16) << 4) >> 4\n")
+        );
+        assert!(output.contains("let val = (raw >> 12) & 0xF;\n        ((val as i8) << 4) >> 4\n"));
+        // Fields as wide as their type need no sign extension.
+        assert!(output.contains("let val = (raw >> 16) & 0xFF;\n        val as i8\n"));
+        assert!(
+            output.contains("let val = (raw >> 0) & 0xFFFFFFFFFFFFFFFF;\n        val as i64\n")
+        );
+        assert!(output.contains("raw |= ((value as u32 & 0xFFF) as u32) << 0;"));
+    }
+
+    #[test]
+    fn test_layout_assertions() {
+        let source =
+            "struct Sample { start_time u32, ready bool:1, level u8:7, values vec<f64, 2>, }";
+        let module = crate::parser::Parser::new(source)
+            .and_then(|p| p.parse_module())
+            .unwrap();
+        let mut generator = RustGenerator::default();
+        generator.add_file_path(PathBuf::from("sample")).unwrap();
+        let output = &generator.generate(&module).unwrap()[0].1;
+        assert!(output.contains("pub const SIZE_BYTES: usize = SAMPLE_SIZE;"));
+        assert!(output.contains("pub const FIELD_OFFSET_START_TIME: usize = 0;"));
+        assert!(output.contains("pub const FIELD_OFFSET_LEVEL: usize = 4;"));
+        assert!(output.contains("pub const FIELD_OFFSET_VALUES: usize = 5;"));
+        assert!(output.contains("const _: () = assert!(SampleView::FIELD_OFFSET_VALUES == SampleView::FIELD_OFFSET_READY + 1, \"Sample.values does not match its Onyx offset\");"));
+        assert!(output.contains("const _: () = assert!(SampleView::FIELD_OFFSET_VALUES + (std::mem::size_of::<u8>() + 2 * std::mem::size_of::<f64>()) == SampleView::SIZE_BYTES, \"Sample does not match its Onyx size\");"));
+    }
+
+    #[test]
+    fn test_reserved_names() {
+        let source = "enum Kind : u8 { Self, Other, } struct `struct` { type u8, ref Kind, }";
+        let module = crate::parser::Parser::new(source)
+            .and_then(|p| p.parse_module())
+            .unwrap();
+        let mut generator = RustGenerator::default();
+        generator.add_file_path(PathBuf::from("reserved")).unwrap();
+        let output = &generator.generate(&module).unwrap()[0].1;
+        assert!(output.contains("pub struct struct_View<'a>"));
+        assert!(output.contains("pub fn type_(&self) -> u8 {"));
+        assert!(output.contains("pub fn ref_(&self) -> Result<Kind, u8> {"));
+        assert!(output.contains("    Self_ = 0,"));
+    }
+
+    #[test]
+    fn test_module_tree() {
+        let mut generator = RustGenerator::default();
+        generator.set_lib_root(true);
+        let files = [PathBuf::from("net/ip/v4.rs"), PathBuf::from("net/tcp.rs")];
+        let tree = generator.module_tree(&files).unwrap();
+        let paths: Vec<_> = tree.iter().map(|(path, _)| path.clone()).collect();
+        assert_eq!(
+            paths,
+            [
+                PathBuf::from("lib.rs"),
+                PathBuf::from("net/mod.rs"),
+                PathBuf::from("net/ip/mod.rs"),
+            ]
+        );
+        assert_eq!(
+            tree[1].1,
+            "// Automatically generated by Onyx IDL compiler. Do not edit.\n\n\
+             pub mod ip;\npub mod tcp;\n\n\
+             #[allow(unused_imports)]\npub use ip::*;\n\
+             #[allow(unused_imports)]\npub use tcp::*;\n"
+        );
+
+        let error = |files: &[&str]| {
+            let files: Vec<PathBuf> = files.iter().map(PathBuf::from).collect();
+            let error = generator.module_tree(&files).unwrap_err();
+            assert_eq!(error.code(), Some(codes::CODEGEN));
+            error.message().to_string()
+        };
+        assert_eq!(
+            error(&["net.rs", "net/tcp.rs"]),
+            "cannot add 'net/tcp.rs' to a module tree, as module 'net' would be both a file and a directory"
+        );
+        assert_eq!(
+            error(&["a.rs", "a.rs"]),
+            "cannot add 'a.rs' to a module tree, as it is given more than once"
+        );
+        assert_eq!(
+            error(&["lib.rs"]),
+            "cannot add 'lib.rs' to a module tree, as the root of the tree is lib.rs"
+        );
+        assert_eq!(
+            error(&["type/a.rs"]),
+            "cannot add 'type/a.rs' to a module tree, as 'type' is not a valid module name"
+        );
+        assert_eq!(
+            error(&["my-mod.rs"]),
+            "cannot add 'my-mod.rs' to a module tree, as 'my-mod' is not a valid module name"
+        );
+        assert_eq!(
+            error(&["../a.rs"]),
+            "cannot add '../a.rs' to a module tree, as it is not relative to the root of the tree"
+        );
+        assert_eq!(
+            error(&["a.hpp"]),
+            "cannot add 'a.hpp' to a module tree, as it is not a .rs file"
+        );
+    }
+
     #[test]
     fn test_primitive_mapping() {
         let generator = RustGenerator::default();
@@ -0,0 +1,512 @@
+use std::{fmt::Write, path::PathBuf};
+
+use crate::{
+    ast::{Definition, EnumDef, OnyxModule, PrimitiveType, WireEndianness},
+    diagnostic::{Diagnostic, codes},
+    features::Feature,
+    generators::{
+        Banner, CodeGenerator, CompileError, check_order, comment_lines, mangle::mangle_reserved,
+    },
+    hir::{self, FieldLayout, Hir, Layout, TypeRef},
+};
+
+/// SystemVerilog keywords, which schema names are renamed away from with a trailing `_`.
+const RESERVED: &[&str] = &[
+    "alias",
+    "always",
+    "always_comb",
+    "always_ff",
+    "always_latch",
+    "and",
+    "assert",
+    "assign",
+    "assume",
+    "automatic",
+    "before",
+    "begin",
+    "bind",
+    "bins",
+    "bit",
+    "break",
+    "buf",
+    "byte",
+    "case",
+    "casex",
+    "casez",
+    "cell",
+    "chandle",
+    "class",
+    "clocking",
+    "config",
+    "const",
+    "constraint",
+    "context",
+    "continue",
+    "cover",
+    "covergroup",
+    "coverpoint",
+    "deassign",
+    "default",
+    "defparam",
+    "design",
+    "disable",
+    "do",
+    "edge",
+    "else",
+    "end",
+    "endcase",
+    "endclass",
+    "endfunction",
+    "endgenerate",
+    "endmodule",
+    "endpackage",
+    "endtask",
+    "enum",
+    "event",
+    "export",
+    "extends",
+    "extern",
+    "final",
+    "for",
+    "force",
+    "foreach",
+    "forever",
+    "fork",
+    "function",
+    "generate",
+    "genvar",
+    "if",
+    "import",
+    "initial",
+    "inout",
+    "input",
+    "inside",
+    "int",
+    "integer",
+    "interface",
+    "join",
+    "local",
+    "localparam",
+    "logic",
+    "longint",
+    "module",
+    "nand",
+    "negedge",
+    "new",
+    "nor",
+    "not",
+    "null",
+    "or",
+    "output",
+    "package",
+    "packed",
+    "parameter",
+    "posedge",
+    "priority",
+    "program",
+    "property",
+    "protected",
+    "pure",
+    "rand",
+    "real",
+    "reg",
+    "repeat",
+    "return",
+    "shortint",
+    "shortreal",
+    "signed",
+    "static",
+    "string",
+    "struct",
+    "super",
+    "task",
+    "this",
+    "time",
+    "type",
+    "typedef",
+    "union",
+    "unique",
+    "unsigned",
+    "var",
+    "virtual",
+    "void",
+    "wait",
+    "while",
+    "wire",
+    "with",
+    "xnor",
+    "xor",
+];
+
+/// Returns the name of the SystemVerilog package generated for the schema whose outputs are
+/// named `stem`, such as `telemetry_pkg` for `telemetry`.
+pub fn package_name(stem: &str) -> String {
+    let mut name: String = stem
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect();
+    if name.starts_with(|c: char| c.is_ascii_digit()) {
+        name.insert(0, '_');
+    }
+    format!("{name}_pkg")
+}
+
+#[derive(Debug, Default)]
+/// The SystemVerilog code generator.
+///
+/// Generates a SystemVerilog package (.sv) describing the layout of every definition, so that
+/// FPGA designs and software decode packets from one schema. Each struct and message gets
+/// localparams giving its size and the byte offset and width of every field, enums become
+/// enum typedefs, and flags a typedef with a localparam per flag.
+///
+/// A schema with `endian = big` also gets a packed struct per struct and message: the wire
+/// image itself, with the first byte in the most significant bits, so a design can cast a
+/// received vector to it. Other byte orders get no packed structs, as their multi-byte fields
+/// would read byte-swapped.
+pub struct SystemVerilogGenerator {
+    output: String,
+    file_path: PathBuf,
+    package: String,
+    imports: Vec<String>,
+    banner: Banner,
+}
+
+impl SystemVerilogGenerator {
+    /// Adds a file path to the generator configuration.
+    pub fn add_file_path(&mut self, file_path: PathBuf) -> Result<(), CompileError> {
+        let Some(stem) = file_path.file_stem() else {
+            return Err(Diagnostic::error(format!(
+                "output path '{}' has no file name",
+                file_path.display()
+            ))
+            .with_code(codes::CODEGEN));
+        };
+        self.package = package_name(&stem.to_string_lossy());
+        self.file_path = file_path;
+        Ok(())
+    }
+
+    /// Imports another package, such as the one generated for a module whose types this one
+    /// uses, as `import package::*;`.
+    pub fn add_import(&mut self, package: impl Into<String>) {
+        self.imports.push(package.into());
+    }
+
+    /// Opens the file with `banner` rather than the default one.
+    pub fn set_banner(&mut self, banner: Banner) {
+        self.banner = banner;
+    }
+
+    fn write_enum(&mut self, e: &EnumDef) {
+        let width = e.underlying_type.get_bit_width();
+        let values = e.variant_values();
+        let kind = if e.flags { "a set of flags " } else { "" };
+        writeln!(
+            self.output,
+            "\n  // {}, {kind}carried on the wire as {}.",
+            e.name, e.underlying_type
+        )
+        .unwrap();
+        write_deprecation(&mut self.output, "  ", e.deprecated.as_deref());
+        if e.flags || e.variants.is_empty() {
+            writeln!(self.output, "  typedef logic [{}:0] {};", width - 1, e.name).unwrap();
+            for (variant, value) in e.variants.iter().zip(values) {
+                writeln!(
+                    self.output,
+                    "  localparam {0} {0}_{1} = {width}'d{value};",
+                    e.name, variant.name
+                )
+                .unwrap();
+            }
+            return;
+        }
+        writeln!(self.output, "  typedef enum logic [{}:0] {{", width - 1).unwrap();
+        let last = e.variants.len() - 1;
+        for (index, (variant, value)) in e.variants.iter().zip(values).enumerate() {
+            let comma = if index == last { "" } else { "," };
+            writeln!(
+                self.output,
+                "    {}_{} = {width}'d{value}{comma}",
+                e.name, variant.name
+            )
+            .unwrap();
+        }
+        writeln!(self.output, "  }} {};", e.name).unwrap();
+    }
+
+    fn write_layout(&mut self, layout: &Layout, hir: &Hir) {
+        let name = layout.name();
+        writeln!(self.output, "\n  // {name}: {} bytes.", layout.size).unwrap();
+        write_deprecation(&mut self.output, "  ", layout.definition.deprecated());
+        writeln!(
+            self.output,
+            "  localparam int {name}_SIZE_BYTES = {};",
+            layout.size
+        )
+        .unwrap();
+        if let Definition::Message(m) = layout.definition
+            && let Some(id) = m.id
+        {
+            writeln!(self.output, "  localparam int {name}_ID = {id};").unwrap();
+        }
+        for slot in &layout.slots {
+            for field in &slot.fields {
+                let prefix = format!("{name}_{}", field.name);
+                writeln!(
+                    self.output,
+                    "  localparam int {prefix}_OFFSET = {};",
+                    field.offset
+                )
+                .unwrap();
+                if slot.is_bit_field_container() {
+                    writeln!(
+                        self.output,
+                        "  localparam int {prefix}_BIT_OFFSET = {};",
+                        field.bit_offset
+                    )
+                    .unwrap();
+                }
+                let width = match field.ty {
+                    TypeRef::Primitive(_) | TypeRef::Fixed { .. } | TypeRef::Enum(_) => field.bits,
+                    _ => slot.size * 8,
+                };
+                writeln!(self.output, "  localparam int {prefix}_WIDTH = {width};").unwrap();
+                match field.ty {
+                    TypeRef::Fixed { scale, .. } => {
+                        writeln!(
+                            self.output,
+                            "  localparam longint unsigned {prefix}_SCALE = 64'd{scale};"
+                        )
+                        .unwrap();
+                    }
+                    TypeRef::Vector { capacity, .. } => {
+                        writeln!(
+                            self.output,
+                            "  localparam int {prefix}_CAPACITY = {capacity};"
+                        )
+                        .unwrap();
+                    }
+                    _ => {}
+                }
+            }
+        }
+        if hir.module.endianness == WireEndianness::Big && layout.size > 0 {
+            self.write_packed_struct(layout, hir);
+        }
+    }
+
+    /// Writes the packed struct of a layout, its first byte in the most significant bits.
+    fn write_packed_struct(&mut self, layout: &Layout, hir: &Hir) {
+        let mut padding = 0;
+        let mut end = 0;
+        writeln!(self.output, "  typedef struct packed {{").unwrap();
+        for slot in &layout.slots {
+            // Padding an `align` directive puts before the slot.
+            if slot.offset > end {
+                writeln!(
+                    self.output,
+                    "    logic [0:{}][7:0] _padding{padding};",
+                    slot.offset - end - 1
+                )
+                .unwrap();
+                padding += 1;
+            }
+            end = slot.offset + slot.size;
+            if !slot.is_bit_field_container() {
+                let field = slot.first();
+                self.write_member(field, hir, None);
+                continue;
+            }
+            // Members run from the most significant bit of the container down.
+            let mut fields: Vec<&FieldLayout> = slot.fields.iter().collect();
+            fields.sort_by_key(|field| std::cmp::Reverse(field.bit_offset));
+            let mut next = slot.size * 8;
+            for field in fields {
+                let top = field.bit_offset + field.bits;
+                if top < next {
+                    writeln!(
+                        self.output,
+                        "    logic [{}:0] _padding{padding};",
+                        next - top - 1
+                    )
+                    .unwrap();
+                    padding += 1;
+                }
+                self.write_member(field, hir, Some(field.bits));
+                next = field.bit_offset;
+            }
+            if next > 0 {
+                writeln!(self.output, "    logic [{}:0] _padding{padding};", next - 1).unwrap();
+                padding += 1;
+            }
+        }
+        writeln!(self.output, "  }} {};", layout.name()).unwrap();
+    }
+
+    /// Writes the member of a packed struct holding `field`, narrowed to `bits` if it is a
+    /// bit-field.
+    fn write_member(&mut self, field: &FieldLayout, hir: &Hir, bits: Option<usize>) {
+        let name = field.name.as_str();
+        let member = match field.ty {
+            TypeRef::Primitive(p) => {
+                let comment = match p {
+                    PrimitiveType::F32 | PrimitiveType::F64 => format!(" // {p} bits"),
+                    _ => String::new(),
+                };
+                format!("{} {name};{comment}", logic(&p, bits))
+            }
+            TypeRef::Fixed { base, scale } => {
+                format!("{} {name}; // fixed<{base}, {scale}>", logic(&base, bits))
+            }
+            TypeRef::Enum(e) => format!("{} {name};", e.name),
+            TypeRef::Uuid => format!("logic [0:15][7:0] {name};"),
+            TypeRef::Bytes(len) => format!("logic [0:{}][7:0] {name};", len - 1),
+            TypeRef::Struct(s) => format!("{} {name};", s.name),
+            TypeRef::Message(m) => format!("{} {name};", m.name),
+            TypeRef::Vector {
+                element,
+                capacity,
+                length,
+            } => {
+                let count = format!("{} {name}_count;", logic(&length, None));
+                let items = match hir.resolve(element) {
+                    Some(TypeRef::Enum(e)) => format!("{} [0:{}] {name};", e.name, capacity - 1),
+                    Some(TypeRef::Struct(s)) => {
+                        format!("{} [0:{}] {name};", s.name, capacity - 1)
+                    }
+                    Some(TypeRef::Message(m)) => {
+                        format!("{} [0:{}] {name};", m.name, capacity - 1)
+                    }
+                    _ => {
+                        let bits = hir.bit_width_of(element).unwrap_or(8);
+                        format!("logic [0:{}][{}:0] {name};", capacity - 1, bits - 1)
+                    }
+                };
+                format!("{count}\n    {items}")
+            }
+        };
+        write_deprecation(&mut self.output, "    ", field.deprecated.as_deref());
+        writeln!(self.output, "    {member}").unwrap();
+    }
+}
+
+impl CodeGenerator for SystemVerilogGenerator {
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(level = "info", name = "SystemVerilogGenerator::generate", skip_all, fields(path = %self.file_path.display()))
+    )]
+    fn generate(&mut self, module: &OnyxModule) -> Result<Vec<(PathBuf, String)>, CompileError> {
+        let banner = self.banner.lines(module)?;
+        check_order(module)?;
+        let module = mangle_reserved(module, RESERVED)?;
+        let module = module.as_ref();
+        let hir = hir::lower(module)?;
+
+        let guard = format!("ONYX_{}_SV", self.package.to_ascii_uppercase());
+        self.output.clear();
+        self.output.push_str(&comment_lines(&banner, "//"));
+        writeln!(self.output, "`ifndef {guard}\n`define {guard}\n").unwrap();
+        writeln!(self.output, "package {};", self.package).unwrap();
+        if !self.imports.is_empty() {
+            writeln!(self.output).unwrap();
+        }
+        for package in &self.imports {
+            writeln!(self.output, "  import {package}::*;").unwrap();
+        }
+        for name in &module.order {
+            match module.definitions.get(name) {
+                Some(Definition::Enum(e)) => self.write_enum(e),
+                Some(Definition::Struct(_) | Definition::Message(_)) => {
+                    self.write_layout(hir.layout(*name).unwrap(), &hir);
+                }
+                None => {}
+            }
+        }
+        writeln!(self.output, "\nendpackage\n\n`endif // {guard}").unwrap();
+
+        let mut output_path = self.file_path.clone();
+        output_path.set_extension("sv");
+        Ok(vec![(output_path, self.output.clone())])
+    }
+
+    fn supports(&self, feature: Feature) -> bool {
+        // Designs get offsets and widths, not accessors: nothing evaluates conditions, reads
+        // older layouts, or dispatches on IDs, and the host byte order means nothing to them.
+        !matches!(
+            feature,
+            Feature::Versions | Feature::Conditions | Feature::MessageIds | Feature::HostEndianness
+        )
+    }
+}
+
+/// Returns the packed type holding a value of `p`, narrowed to `bits` if given.
+fn logic(p: &PrimitiveType, bits: Option<usize>) -> String {
+    let bits = bits.unwrap_or(p.get_bit_width());
+    let signed = if p.is_signed() { " signed" } else { "" };
+    match (p, bits) {
+        (PrimitiveType::Bool, _) | (_, 1) if !p.is_signed() => "logic".to_string(),
+        _ => format!("logic{signed} [{}:0]", bits - 1),
+    }
+}
+
+/// Writes a comment with the note of a `@deprecated` attribute, if there is one.
+fn write_deprecation(output: &mut String, indent: &str, note: Option<&str>) {
+    if let Some(note) = note {
+        let note = note.replace('\n', " ");
+        writeln!(output, "{indent}// Deprecated: {note}").unwrap();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn generate(source: &str) -> String {
+        let module = crate::parser::Parser::new(source)
+            .unwrap()
+            .parse_module()
+            .unwrap();
+        let mut generator = SystemVerilogGenerator::default();
+        generator
+            .add_file_path(PathBuf::from("out/link-v2"))
+            .unwrap();
+        let files = generator.generate(&module).unwrap();
+        assert_eq!(files[0].0, PathBuf::from("out/link-v2.sv"));
+        files[0].1.clone()
+    }
+
+    #[test]
+    fn test_packed_struct() {
+        let output = generate(
+            "endian = big\n\
+             enum Kind : u8 { Ping, Data, }\n\
+             @packed_bits message Frame { kind Kind, ready bool : 1, level i8 : 4, seq u16, }",
+        );
+        assert!(output.contains("`ifndef ONYX_LINK_V2_PKG_SV\n`define ONYX_LINK_V2_PKG_SV\n"));
+        assert!(output.contains("package link_v2_pkg;"));
+        assert!(output.contains(
+            "  typedef enum logic [7:0] {\n    Kind_Ping = 8'd0,\n    Kind_Data = 8'd1\n  } Kind;"
+        ));
+        assert!(output.contains(
+            "  localparam int Frame_level_OFFSET = 1;\n  localparam int Frame_level_BIT_OFFSET = 1;\n  \
+             localparam int Frame_level_WIDTH = 4;"
+        ));
+        // The first bit-field is the least significant bit of its container.
+        assert!(output.contains(
+            "  typedef struct packed {\n    Kind kind;\n    logic [2:0] _padding0;\n    \
+             logic signed [3:0] level;\n    logic ready;\n    logic [15:0] seq;\n  } Frame;"
+        ));
+    }
+
+    #[test]
+    fn test_little_endian_has_no_packed_structs() {
+        let output =
+            generate("endian = little\nflags Access : u8 { Read, Write, } struct S { a Access, }");
+        assert!(
+            output
+                .contains("  typedef logic [7:0] Access;\n  localparam Access Access_Read = 8'd1;")
+        );
+        assert!(output.contains("  localparam int S_a_WIDTH = 8;"));
+        assert!(!output.contains("typedef struct packed"));
+    }
+}
@@ -0,0 +1,810 @@
+//! A generator that renders a user-supplied text template.
+//!
+//! Templates use a small subset of Jinja syntax, so bespoke artifacts such as SQL DDL, YAML, or
+//! documentation can be produced without writing a Rust backend:
+//!
+//! - `{{ path }}` prints a value, optionally through filters: `{{ def.name | upper }}`. The
+//!   filters are `upper`, `lower`, and `length`.
+//! - `{% for item in path %} ... {% endfor %}` repeats its body for each element of a list.
+//!   Inside the body, `loop.index` (from 1), `loop.index0`, `loop.first`, and `loop.last` are
+//!   available.
+//! - `{% if path %} ... {% else %} ... {% endif %}` renders one branch depending on whether a
+//!   value is truthy: true, a nonzero number, or a nonempty string or list. `if not path`
+//!   negates the test.
+//! - `{# ... #}` is a comment.
+//!
+//! A `{% ... %}` tag or comment alone on its line is removed along with that line, and a `-`
+//! inside a delimiter (`{%-`, `-%}`, `{{-`, `-}}`) strips the whitespace on that side.
+//!
+//! # Context
+//!
+//! The module is exposed as:
+//!
+//! - `endianness`: `"big"`, `"little"`, or `"host"`.
+//! - `portable`: false for `host` modules, whose buffers are only understood by machines with
+//!   the same byte order.
+//! - `version`: the schema version, or 0 without a `version` directive. The definitions have
+//!   the layout of that version; `history` holds the module at each earlier version, from
+//!   version 1 up, with the same keys.
+//! - `definitions`: every definition in source order; `messages`, `structs`, and `enums` hold
+//!   the definitions of each kind.
+//! - Each definition has `name`, `kind` (`"message"`, `"struct"`, or `"enum"`), `is_message`,
+//!   `is_struct`, `is_enum`, `size` in bytes, `fields`, and `variants`. Enums also have
+//!   `underlying_type`, `is_flags`, and `is_open`. Messages also have `has_id`, set when they
+//!   are marked `@id`, and `id` (0 when unmarked).
+//! - Each field has `name`, `type` (as written in the schema), `offset` (the byte offset of the
+//!   field, or of the container a bit-field shares), `size` (bytes of that field or container),
+//!   `bits`, `bit_offset` (the position of a bit-field within its container, otherwise 0),
+//!   `is_bit_field`, `is_primitive`, `is_enum`, `is_nested`, and `is_vector`. A bounded vector
+//!   also has `element` (its element type) and `capacity`; the `is_primitive`, `is_enum`, and
+//!   `is_nested` flags then describe its element. For other fields `element` is the field type
+//!   and `capacity` is 0. A fixed-point field is primitive, with `is_fixed` set and its `scale`;
+//!   other fields have a `scale` of 1. A `uuid` or `bytes[N]` field is primitive too, with
+//!   `is_bytes` set. `since` and `removed` are the field's `@since` and `@removed` versions,
+//!   or 0 when it is not marked. A conditional field has `is_conditional` set and its
+//!   `condition` as written, such as `kind == Kind.Data`; other fields have an empty `condition`.
+//! - Each enum variant has `name`, `value`, and `is_composite`.
+//! - `banner`: the lines of the [banner](super::Banner), without comment markers, to write in
+//!   the comment syntax of the output. It is only set on the module itself, not its `history`.
+
+use std::path::PathBuf;
+
+use crate::{
+    ast::{Definition, OnyxModule, Type, WireEndianness},
+    diagnostic::{Diagnostic, codes},
+    generators::{Banner, CodeGenerator, CompileError, check_order},
+    hir::{self, Hir},
+};
+
+/// A generator that renders the module through a text template.
+///
+/// The template is parsed by [`TemplateGenerator::new`], so syntax errors are reported before
+/// any module is generated. The output is written to the path given to
+/// [`TemplateGenerator::add_file_path`] as is.
+#[derive(Debug)]
+pub struct TemplateGenerator {
+    template: Vec<Node>,
+    file_path: PathBuf,
+    banner: Banner,
+}
+
+impl TemplateGenerator {
+    /// Parses a template.
+    pub fn new(template: &str) -> Result<Self, CompileError> {
+        Ok(TemplateGenerator {
+            template: parse(template)?,
+            file_path: PathBuf::new(),
+            banner: Banner::default(),
+        })
+    }
+
+    /// Sets the path of the rendered file.
+    pub fn add_file_path(&mut self, file_path: PathBuf) -> Result<(), CompileError> {
+        if file_path.file_name().is_none() {
+            return Err(Diagnostic::error(format!(
+                "output path '{}' has no file name",
+                file_path.display()
+            ))
+            .with_code(codes::CODEGEN));
+        }
+        self.file_path = file_path;
+        Ok(())
+    }
+
+    /// Sets the banner exposed to the template as `banner`.
+    pub fn set_banner(&mut self, banner: Banner) {
+        self.banner = banner;
+    }
+
+    /// Renders the template against a module.
+    pub fn render(&self, module: &OnyxModule) -> Result<String, CompileError> {
+        let mut context = module_context(module)?;
+        if let Value::Object(entries) = &mut context {
+            let banner = self.banner.lines(module)?;
+            entries.push((
+                "banner",
+                Value::List(banner.into_iter().map(Value::Str).collect()),
+            ));
+        }
+        let mut out = String::new();
+        Renderer {
+            scopes: vec![("", &context)],
+        }
+        .render(&self.template, &mut out)?;
+        Ok(out)
+    }
+}
+
+impl CodeGenerator for TemplateGenerator {
+    fn generate(&mut self, module: &OnyxModule) -> Result<Vec<(PathBuf, String)>, CompileError> {
+        check_order(module)?;
+        Ok(vec![(self.file_path.clone(), self.render(module)?)])
+    }
+}
+
+fn template_error(line: usize, message: impl std::fmt::Display) -> Diagnostic {
+    Diagnostic::error(format!("template line {line}: {message}")).with_code(codes::CODEGEN)
+}
+
+// --- Context ---
+
+/// A value in the template context.
+#[derive(Debug)]
+enum Value {
+    Str(String),
+    Int(u64),
+    Bool(bool),
+    List(Vec<Value>),
+    Object(Vec<(&'static str, Value)>),
+}
+
+impl Value {
+    fn get(&self, key: &str) -> Option<&Value> {
+        match self {
+            Value::Object(entries) => entries.iter().find(|(k, _)| *k == key).map(|(_, v)| v),
+            _ => None,
+        }
+    }
+
+    fn is_truthy(&self) -> bool {
+        match self {
+            Value::Str(s) => !s.is_empty(),
+            Value::Int(n) => *n != 0,
+            Value::Bool(b) => *b,
+            Value::List(items) => !items.is_empty(),
+            Value::Object(_) => true,
+        }
+    }
+}
+
+fn module_context(module: &OnyxModule) -> Result<Value, CompileError> {
+    let hir = hir::lower(module)?;
+    let definitions: Vec<&Definition> = module
+        .order
+        .iter()
+        .filter_map(|id| module.definitions.get(id))
+        .collect();
+    let of_kind = |kind: &str| {
+        Value::List(
+            definitions
+                .iter()
+                .filter(|def| kind_name(def) == kind)
+                .map(|def| definition_context(&hir, def))
+                .collect(),
+        )
+    };
+    let endianness = match module.endianness {
+        WireEndianness::Big => "big",
+        WireEndianness::Little => "little",
+        WireEndianness::Host => "host",
+    };
+    let history = module
+        .history
+        .iter()
+        .map(module_context)
+        .collect::<Result<_, _>>()?;
+    Ok(Value::Object(vec![
+        ("endianness", Value::Str(endianness.to_string())),
+        ("portable", Value::Bool(module.endianness.is_portable())),
+        ("version", Value::Int(module.version.unwrap_or(0).into())),
+        ("history", Value::List(history)),
+        (
+            "definitions",
+            Value::List(
+                definitions
+                    .iter()
+                    .map(|def| definition_context(&hir, def))
+                    .collect(),
+            ),
+        ),
+        ("messages", of_kind("message")),
+        ("structs", of_kind("struct")),
+        ("enums", of_kind("enum")),
+    ]))
+}
+
+fn kind_name(def: &Definition) -> &'static str {
+    match def {
+        Definition::Message(_) => "message",
+        Definition::Struct(_) => "struct",
+        Definition::Enum(_) => "enum",
+    }
+}
+
+fn definition_context(hir: &Hir, def: &Definition) -> Value {
+    let kind = kind_name(def);
+    let size = hir
+        .layout(def.symbol())
+        .map_or(def.size().unwrap_or(0).div_ceil(8), |layout| layout.size);
+    let mut entries = vec![
+        ("name", Value::Str(def.name().to_string())),
+        ("kind", Value::Str(kind.to_string())),
+        ("is_message", Value::Bool(kind == "message")),
+        ("is_struct", Value::Bool(kind == "struct")),
+        ("is_enum", Value::Bool(kind == "enum")),
+        ("size", Value::Int(size as u64)),
+    ];
+    match def {
+        Definition::Message(m) => {
+            entries.push(("fields", fields_context(hir, def)));
+            entries.push(("has_id", Value::Bool(m.id.is_some())));
+            entries.push(("id", Value::Int(m.id.unwrap_or(0).into())));
+        }
+        Definition::Struct(_) => entries.push(("fields", fields_context(hir, def))),
+        Definition::Enum(e) => {
+            entries.push(("fields", Value::List(Vec::new())));
+            entries.push(("underlying_type", Value::Str(e.underlying_type.to_string())));
+            entries.push(("is_flags", Value::Bool(e.flags)));
+            entries.push(("is_open", Value::Bool(e.open)));
+            entries.push((
+                "variants",
+                Value::List(
+                    e.variants
+                        .iter()
+                        .zip(e.variant_values())
+                        .map(|(variant, value)| {
+                            Value::Object(vec![
+                                ("name", Value::Str(variant.name.clone())),
+                                ("value", Value::Int(value)),
+                                ("is_composite", Value::Bool(variant.composite)),
+                            ])
+                        })
+                        .collect(),
+                ),
+            ));
+            return Value::Object(entries);
+        }
+    }
+    entries.push(("variants", Value::List(Vec::new())));
+    Value::Object(entries)
+}
+
+fn fields_context(hir: &Hir, def: &Definition) -> Value {
+    let module = hir.module;
+    let mut contexts = Vec::new();
+    for slot in hir
+        .layout(def.symbol())
+        .map_or(&[][..], |layout| &layout.slots)
+    {
+        for field in &slot.fields {
+            let (is_primitive, is_enum) = match field.type_info.element_type() {
+                Type::Custom(name) => (
+                    false,
+                    matches!(module.definitions.get(name), Some(Definition::Enum(_))),
+                ),
+                _ => (true, false),
+            };
+            let capacity = match field.type_info {
+                Type::Vector { capacity, .. } => capacity,
+                _ => 0,
+            };
+            let scale = match field.type_info {
+                Type::Fixed { scale, .. } => scale,
+                _ => 1,
+            };
+            contexts.push(Value::Object(vec![
+                ("name", Value::Str(field.name.to_string())),
+                ("type", Value::Str(field.type_info.to_string())),
+                ("offset", Value::Int(field.offset as u64)),
+                ("size", Value::Int(slot.size as u64)),
+                ("bits", Value::Int(field.bits as u64)),
+                ("bit_offset", Value::Int(field.bit_offset as u64)),
+                ("is_bit_field", Value::Bool(field.bit_field_size.is_some())),
+                ("is_primitive", Value::Bool(is_primitive)),
+                ("is_enum", Value::Bool(is_enum)),
+                ("is_nested", Value::Bool(!is_primitive && !is_enum)),
+                ("is_vector", Value::Bool(capacity > 0)),
+                (
+                    "element",
+                    Value::Str(field.type_info.element_type().to_string()),
+                ),
+                ("capacity", Value::Int(capacity as u64)),
+                (
+                    "is_fixed",
+                    Value::Bool(matches!(field.type_info, Type::Fixed { .. })),
+                ),
+                ("scale", Value::Int(scale)),
+                (
+                    "is_bytes",
+                    Value::Bool(field.type_info.byte_len().is_some()),
+                ),
+                ("since", Value::Int(field.since.unwrap_or(0).into())),
+                ("removed", Value::Int(field.removed.unwrap_or(0).into())),
+                ("is_conditional", Value::Bool(field.condition.is_some())),
+                (
+                    "condition",
+                    Value::Str(
+                        field
+                            .condition
+                            .as_ref()
+                            .map_or_else(String::new, ToString::to_string),
+                    ),
+                ),
+            ]));
+        }
+    }
+    Value::List(contexts)
+}
+
+// --- Parsing ---
+
+#[derive(Debug)]
+enum Node {
+    Text(String),
+    Print(Expr),
+    For {
+        var: String,
+        iterable: Expr,
+        body: Vec<Node>,
+    },
+    If {
+        negate: bool,
+        condition: Expr,
+        then: Vec<Node>,
+        otherwise: Vec<Node>,
+    },
+}
+
+#[derive(Debug)]
+struct Expr {
+    path: Vec<String>,
+    filters: Vec<String>,
+    line: usize,
+}
+
+#[derive(Debug)]
+enum Token {
+    Text(String),
+    Print(String, usize),
+    Tag(String, usize),
+}
+
+/// Splits a template into text, `{{ }}` and `{% %}` tokens, applying whitespace control and
+/// dropping comments.
+fn tokenize(source: &str) -> Result<Vec<Token>, Diagnostic> {
+    let mut tokens = Vec::new();
+    let mut pos = 0;
+    let mut trim_next = false;
+
+    loop {
+        let start = ["{{", "{%", "{#"]
+            .iter()
+            .filter_map(|open| source[pos..].find(open).map(|i| pos + i))
+            .min();
+        let mut text = &source[pos..start.unwrap_or(source.len())];
+        if trim_next {
+            text = text.trim_start();
+        }
+        let Some(start) = start else {
+            if !text.is_empty() {
+                tokens.push(Token::Text(text.to_string()));
+            }
+            return Ok(tokens);
+        };
+
+        let line = source[..start].matches('\n').count() + 1;
+        let open = &source[start..start + 2];
+        let close = match open {
+            "{{" => "}}",
+            "{%" => "%}",
+            _ => "#}",
+        };
+        let Some(len) = source[start + 2..].find(close) else {
+            return Err(template_error(line, format!("unclosed '{open}'")));
+        };
+        let mut inner = &source[start + 2..start + 2 + len];
+        let mut next = start + 2 + len + 2;
+
+        // A tag or comment alone on its line removes the whole line.
+        if open != "{{" {
+            let line_start = source[..start].rfind('\n').map_or(0, |i| i + 1);
+            let line_end = source[next..]
+                .find('\n')
+                .map_or(source.len(), |i| next + i + 1);
+            if source[line_start..start].trim().is_empty()
+                && source[next..line_end].trim().is_empty()
+            {
+                text = text.trim_end_matches([' ', '\t']);
+                next = line_end;
+            }
+        }
+        if let Some(stripped) = inner.strip_prefix('-') {
+            text = text.trim_end();
+            inner = stripped;
+        }
+        trim_next = false;
+        if let Some(stripped) = inner.strip_suffix('-') {
+            trim_next = true;
+            inner = stripped;
+        }
+
+        if !text.is_empty() {
+            tokens.push(Token::Text(text.to_string()));
+        }
+        match open {
+            "{{" => tokens.push(Token::Print(inner.trim().to_string(), line)),
+            "{%" => tokens.push(Token::Tag(inner.trim().to_string(), line)),
+            _ => {}
+        }
+        pos = next;
+    }
+}
+
+fn parse(source: &str) -> Result<Vec<Node>, Diagnostic> {
+    let mut tokens = tokenize(source)?.into_iter();
+    let (nodes, end) = parse_block(&mut tokens)?;
+    match end {
+        None => Ok(nodes),
+        Some((tag, line)) => Err(template_error(line, format!("unexpected '{{% {tag} %}}'"))),
+    }
+}
+
+/// The tag that ended a block, and its line.
+type BlockEnd = Option<(String, usize)>;
+
+/// Parses nodes up to the next `else`, `endfor` or `endif` tag.
+fn parse_block(
+    tokens: &mut impl Iterator<Item = Token>,
+) -> Result<(Vec<Node>, BlockEnd), Diagnostic> {
+    let mut nodes = Vec::new();
+    while let Some(token) = tokens.next() {
+        match token {
+            Token::Text(text) => nodes.push(Node::Text(text)),
+            Token::Print(expr, line) => nodes.push(Node::Print(parse_expr(&expr, line)?)),
+            Token::Tag(tag, line) => {
+                let words: Vec<&str> = tag.split_whitespace().collect();
+                match words.as_slice() {
+                    ["for", var, "in", ..] => {
+                        let iterable = parse_expr(&words[3..].join(" "), line)?;
+                        let (body, end) = parse_block(tokens)?;
+                        expect_end(end, "endfor", line)?;
+                        nodes.push(Node::For {
+                            var: var.to_string(),
+                            iterable,
+                            body,
+                        });
+                    }
+                    ["if", "not", ..] | ["if", ..] => {
+                        let negate = words.get(1) == Some(&"not");
+                        let skip = if negate { 2 } else { 1 };
+                        let condition = parse_expr(&words[skip..].join(" "), line)?;
+                        let (then, end) = parse_block(tokens)?;
+                        let otherwise = match end {
+                            Some((tag, _)) if tag == "else" => {
+                                let (otherwise, end) = parse_block(tokens)?;
+                                expect_end(end, "endif", line)?;
+                                otherwise
+                            }
+                            end => {
+                                expect_end(end, "endif", line)?;
+                                Vec::new()
+                            }
+                        };
+                        nodes.push(Node::If {
+                            negate,
+                            condition,
+                            then,
+                            otherwise,
+                        });
+                    }
+                    ["else" | "endfor" | "endif"] => return Ok((nodes, Some((tag, line)))),
+                    _ => return Err(template_error(line, format!("unknown tag '{tag}'"))),
+                }
+            }
+        }
+    }
+    Ok((nodes, None))
+}
+
+fn expect_end(end: BlockEnd, expected: &str, line: usize) -> Result<(), Diagnostic> {
+    match end {
+        Some((tag, _)) if tag == expected => Ok(()),
+        Some((tag, line)) => Err(template_error(
+            line,
+            format!("expected '{{% {expected} %}}', found '{{% {tag} %}}'"),
+        )),
+        None => Err(template_error(
+            line,
+            format!("missing '{{% {expected} %}}'"),
+        )),
+    }
+}
+
+fn parse_expr(source: &str, line: usize) -> Result<Expr, Diagnostic> {
+    let mut parts = source.split('|').map(str::trim);
+    let path: Vec<String> = parts
+        .next()
+        .unwrap_or_default()
+        .split('.')
+        .map(str::to_string)
+        .collect();
+    let is_identifier =
+        |s: &str| !s.is_empty() && s.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+    if !path.iter().all(|segment| is_identifier(segment)) {
+        return Err(template_error(
+            line,
+            format!("invalid expression '{source}'"),
+        ));
+    }
+    let filters: Vec<String> = parts.map(str::to_string).collect();
+    if let Some(filter) = filters
+        .iter()
+        .find(|f| !matches!(f.as_str(), "upper" | "lower" | "length"))
+    {
+        return Err(template_error(line, format!("unknown filter '{filter}'")));
+    }
+    Ok(Expr {
+        path,
+        filters,
+        line,
+    })
+}
+
+// --- Rendering ---
+
+/// A value produced while rendering: either part of the context or computed by a filter or
+/// a loop.
+enum Rendered<'v> {
+    Ref(&'v Value),
+    Owned(Value),
+}
+
+impl Rendered<'_> {
+    fn value(&self) -> &Value {
+        match self {
+            Rendered::Ref(value) => value,
+            Rendered::Owned(value) => value,
+        }
+    }
+}
+
+struct Renderer<'v> {
+    /// Variables in scope, innermost last. The root context has an empty name and is searched
+    /// by key.
+    scopes: Vec<(&'v str, &'v Value)>,
+}
+
+impl<'v> Renderer<'v> {
+    fn render(&mut self, nodes: &'v [Node], out: &mut String) -> Result<(), Diagnostic> {
+        for node in nodes {
+            match node {
+                Node::Text(text) => out.push_str(text),
+                Node::Print(expr) => match self.eval(expr)?.value() {
+                    Value::Str(s) => out.push_str(s),
+                    Value::Int(n) => out.push_str(&n.to_string()),
+                    Value::Bool(b) => out.push_str(&b.to_string()),
+                    Value::List(_) | Value::Object(_) => {
+                        return Err(template_error(
+                            expr.line,
+                            format!("'{}' cannot be printed", expr.path.join(".")),
+                        ));
+                    }
+                },
+                Node::For {
+                    var,
+                    iterable,
+                    body,
+                } => {
+                    let Some(Value::List(items)) = self.lookup(&iterable.path) else {
+                        return Err(template_error(
+                            iterable.line,
+                            format!("'{}' is not a list", iterable.path.join(".")),
+                        ));
+                    };
+                    for (i, item) in items.iter().enumerate() {
+                        let state = Value::Object(vec![
+                            ("index", Value::Int(i as u64 + 1)),
+                            ("index0", Value::Int(i as u64)),
+                            ("first", Value::Bool(i == 0)),
+                            ("last", Value::Bool(i + 1 == items.len())),
+                        ]);
+                        let mut inner = Renderer {
+                            scopes: self.scopes.clone(),
+                        };
+                        inner.scopes.push((var, item));
+                        inner.scopes.push(("loop", &state));
+                        inner.render(body, out)?;
+                    }
+                }
+                Node::If {
+                    negate,
+                    condition,
+                    then,
+                    otherwise,
+                } => {
+                    let truthy = self.eval(condition)?.value().is_truthy();
+                    let branch = if truthy != *negate { then } else { otherwise };
+                    self.render(branch, out)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn lookup(&self, path: &[String]) -> Option<&'v Value> {
+        let (first, rest) = path.split_first()?;
+        let mut value = self.scopes.iter().rev().find_map(|(name, value)| {
+            if name.is_empty() {
+                value.get(first)
+            } else {
+                (name == first).then_some(*value)
+            }
+        })?;
+        for key in rest {
+            value = value.get(key)?;
+        }
+        Some(value)
+    }
+
+    fn eval(&self, expr: &Expr) -> Result<Rendered<'v>, Diagnostic> {
+        let Some(value) = self.lookup(&expr.path) else {
+            return Err(template_error(
+                expr.line,
+                format!("undefined value '{}'", expr.path.join(".")),
+            ));
+        };
+        let mut result = Rendered::Ref(value);
+        for filter in &expr.filters {
+            let value = match (filter.as_str(), result.value()) {
+                ("upper", Value::Str(s)) => Value::Str(s.to_uppercase()),
+                ("lower", Value::Str(s)) => Value::Str(s.to_lowercase()),
+                ("length", Value::Str(s)) => Value::Int(s.chars().count() as u64),
+                ("length", Value::List(items)) => Value::Int(items.len() as u64),
+                _ => {
+                    return Err(template_error(
+                        expr.line,
+                        format!(
+                            "filter '{filter}' does not apply to '{}'",
+                            expr.path.join(".")
+                        ),
+                    ));
+                }
+            };
+            result = Rendered::Owned(value);
+        }
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::Parser;
+
+    const SOURCE: &str = "endian = big\n\
+                          enum Status : u8 { Active = 1, Inactive, }\n\
+                          struct Header { version u32 : 4, flags u8 : 4, tag Status, }\n\
+                          message User { id u64, hdr Header, }";
+
+    fn render(template: &str) -> Result<String, Diagnostic> {
+        let module = Parser::new(SOURCE).unwrap().parse_module().unwrap();
+        TemplateGenerator::new(template)?.render(&module)
+    }
+
+    #[test]
+    fn test_render_module() {
+        let template = "\
+{# SQL tables for every message #}
+{% for msg in messages %}
+CREATE TABLE {{ msg.name | lower }} ( -- {{ msg.size }} bytes
+  {% for field in msg.fields %}
+    {{ field.name }} {% if field.is_nested %}BLOB{% else %}INTEGER{% endif %}{% if not loop.last %},{% endif %}
+  {% endfor %}
+);
+{% endfor %}
+{% for e in enums %}
+{{ e.name }}: {% for v in e.variants %}{{ v.name }}={{ v.value }} {% endfor %}
+{% endfor %}
+{% for def in structs %}{% for f in def.fields %}{{ f.name }}@{{ f.offset }}+{{ f.bit_offset }}/{{ f.bits }} {% endfor %}{% endfor %}";
+
+        assert_eq!(
+            render(template).unwrap(),
+            "CREATE TABLE user ( -- 10 bytes\n    id INTEGER,\n    hdr BLOB\n);\n\
+             Status: Active=1 Inactive=2 \n\
+             version@0+0/4 flags@0+4/4 tag@1+0/8 "
+        );
+    }
+
+    #[test]
+    fn test_render_banner() {
+        let module = Parser::new(SOURCE).unwrap().parse_module().unwrap();
+        let mut generator =
+            TemplateGenerator::new("{% for line in banner %}-- {{ line }}\n{% endfor %}").unwrap();
+        generator.set_banner(
+            Banner::new()
+                .with_copyright("Copyright Example")
+                .with_version(),
+        );
+        assert_eq!(
+            generator.render(&module).unwrap(),
+            format!(
+                "-- Copyright Example\n\
+                 -- Automatically generated by Onyx IDL compiler v{}. Do not edit.\n",
+                env!("CARGO_PKG_VERSION")
+            )
+        );
+    }
+
+    #[test]
+    fn test_render_versions() {
+        let module = Parser::new("version = 2 message M { @removed(2) a u8, @since(2) b u16, }")
+            .unwrap()
+            .parse_module()
+            .unwrap();
+        let template = "\
+{% for old in history %}{% for msg in old.messages %}v{{ old.version }}: {% for f in msg.fields %}{{ f.name }}-{{ f.removed }} {% endfor %}{% endfor %}{% endfor %}
+v{{ version }}: {% for msg in messages %}{% for f in msg.fields %}{{ f.name }}+{{ f.since }} {% endfor %}{% endfor %}";
+        assert_eq!(
+            TemplateGenerator::new(template)
+                .unwrap()
+                .render(&module)
+                .unwrap(),
+            "v1: a-2 \nv2: b+2 "
+        );
+    }
+
+    #[test]
+    fn test_render_message_ids() {
+        let module = Parser::new("@id(9) message A { } message B { }")
+            .unwrap()
+            .parse_module()
+            .unwrap();
+        let template = "{% for m in messages %}{{ m.name }}{% if m.has_id %}={{ m.id }}{% endif %};{% endfor %}";
+        assert_eq!(
+            TemplateGenerator::new(template)
+                .unwrap()
+                .render(&module)
+                .unwrap(),
+            "A=9;B;"
+        );
+    }
+
+    #[test]
+    fn test_render_host_endianness() {
+        let module = Parser::new("endian = host struct S { }")
+            .unwrap()
+            .parse_module()
+            .unwrap();
+        let template = "{{ endianness }}{% if portable %} portable{% endif %}";
+        assert_eq!(
+            TemplateGenerator::new(template)
+                .unwrap()
+                .render(&module)
+                .unwrap(),
+            "host"
+        );
+    }
+
+    #[test]
+    fn test_render_conditions() {
+        let module = Parser::new("struct S { a bool : 1, b u8 : 7 if a == true, }")
+            .unwrap()
+            .parse_module()
+            .unwrap();
+        let template = "{% for s in structs %}{% for f in s.fields %}{{ f.name }}{% if f.is_conditional %} if {{ f.condition }}{% endif %};{% endfor %}{% endfor %}";
+        assert_eq!(
+            TemplateGenerator::new(template)
+                .unwrap()
+                .render(&module)
+                .unwrap(),
+            "a;b if a == true;"
+        );
+    }
+
+    #[test]
+    fn test_template_errors() {
+        let error = render("{% for x in messages %}").unwrap_err();
+        assert_eq!(error.code(), Some(codes::CODEGEN));
+        assert!(error.to_string().contains("missing '{% endfor %}'"));
+
+        assert!(render("{{ messages | reverse }}").is_err());
+        assert!(render("{% while x %}").is_err());
+        assert!(render("{{ endianness").is_err());
+
+        let error = render("\n{{ nothing.here }}").unwrap_err();
+        assert!(error.to_string().contains("template line 2"));
+        assert!(render("{{ messages }}").is_err());
+        assert_eq!(render("{{ endianness | upper }}").unwrap(), "BIG");
+        assert_eq!(render("{{ endianness -}}\n  !").unwrap(), "big!");
+    }
+}
@@ -0,0 +1,505 @@
+//! The semantic model of a resolved module.
+//!
+//! Parsing resolves the types of a module and checks that it is well formed, but the AST
+//! still names custom types rather than pointing at them, and says nothing of where fields
+//! sit on the wire. [`lower`] works that out once: it resolves every field type to the
+//! definition it names and places every field at its byte offset, packing runs of bit-fields
+//! into a shared container and padding fields as the module's `align` directive requires.
+//! Generators read the layout from here rather than each computing
+//! it again.
+//!
+//! ```rust
+//! use onyx::parser::Parser;
+//!
+//! let module = Parser::new("struct Flags { ready bool : 1, level u8 : 7, count u16, }")
+//!     .and_then(|p| p.parse_module())
+//!     .unwrap();
+//! let hir = onyx::hir::lower(&module).unwrap();
+//! let layout = hir.layout("Flags").unwrap();
+//! assert_eq!(layout.size, 3);
+//! let level = layout.field("level").unwrap();
+//! assert_eq!((level.offset, level.bit_offset, level.bits), (0, 1, 7));
+//! assert_eq!(layout.field("count").unwrap().offset, 1);
+//! ```
+
+use std::{collections::HashMap, ops::Deref};
+
+use crate::{
+    ast::{Definition, EnumDef, Field, MessageDef, OnyxModule, PrimitiveType, StructDef, Type},
+    diagnostic::{Diagnostic, codes},
+    symbol::Symbol,
+};
+
+/// A field type with any custom type resolved to its definition.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[non_exhaustive]
+pub enum TypeRef<'m> {
+    /// A built-in primitive type.
+    Primitive(PrimitiveType),
+    /// A fixed-point number: the integer `base` standing for itself divided by `scale`.
+    Fixed {
+        /// The integer type on the wire.
+        base: PrimitiveType,
+        /// The number of wire units per whole unit.
+        scale: u64,
+    },
+    /// A 16-byte UUID.
+    Uuid,
+    /// An array of bytes of the given length.
+    Bytes(usize),
+    /// An enum, carried as its underlying type.
+    Enum(&'m EnumDef),
+    /// A struct, embedded in place.
+    Struct(&'m StructDef),
+    /// A message, embedded in place.
+    Message(&'m MessageDef),
+    /// A bounded vector: a length prefix followed by room for `capacity` elements.
+    Vector {
+        /// The element type, which is never itself a vector.
+        element: &'m Type,
+        /// The maximum number of elements.
+        capacity: usize,
+        /// The type of the length prefix.
+        length: PrimitiveType,
+    },
+}
+
+/// A field placed in the layout of its struct or message.
+///
+/// Dereferences to the [`Field`] it was lowered from.
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub struct FieldLayout<'m> {
+    /// The field in the AST.
+    pub field: &'m Field,
+    /// The resolved type of the field.
+    pub ty: TypeRef<'m>,
+    /// The byte offset of the field, or of the container holding it if it is a bit-field.
+    pub offset: usize,
+    /// The offset of a bit-field within its container, counting from the least significant
+    /// bit of the container read as an integer. Zero for other fields.
+    pub bit_offset: usize,
+    /// The width of the field in bits.
+    pub bits: usize,
+}
+
+impl Deref for FieldLayout<'_> {
+    type Target = Field;
+
+    fn deref(&self) -> &Field {
+        self.field
+    }
+}
+
+/// A run of bytes in a layout: one ordinary field, or a container of consecutive bit-fields.
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub struct Slot<'m> {
+    /// The byte offset of the slot.
+    pub offset: usize,
+    /// The size of the slot in bytes.
+    pub size: usize,
+    /// The fields in the slot, in source order; never empty.
+    pub fields: Vec<FieldLayout<'m>>,
+}
+
+impl<'m> Slot<'m> {
+    /// Returns the first field of the slot, which names a bit-field container.
+    pub fn first(&self) -> &FieldLayout<'m> {
+        &self.fields[0]
+    }
+
+    /// Returns true if the slot is a container of bit-fields.
+    pub fn is_bit_field_container(&self) -> bool {
+        self.fields[0].bit_field_size.is_some()
+    }
+}
+
+/// The wire layout of a struct or message.
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub struct Layout<'m> {
+    /// The struct or message in the AST.
+    pub definition: &'m Definition,
+    /// The size in bytes.
+    pub size: usize,
+    /// The alignment in bytes: that of its most aligned slot, or 1 without an `align`
+    /// directive.
+    pub align: usize,
+    /// The slots of the layout, in order. Padding separates two slots only when the module's
+    /// `align` directive calls for it.
+    pub slots: Vec<Slot<'m>>,
+}
+
+impl<'m> Layout<'m> {
+    /// Returns the name of the struct or message.
+    pub fn name(&self) -> Symbol {
+        self.definition.symbol()
+    }
+
+    /// Returns every field of the layout, in source order.
+    pub fn fields(&self) -> impl Iterator<Item = &FieldLayout<'m>> {
+        self.slots.iter().flat_map(|slot| &slot.fields)
+    }
+
+    /// Returns the field named `name`.
+    pub fn field(&self, name: &str) -> Option<&FieldLayout<'m>> {
+        self.fields().find(|field| field.name == name)
+    }
+}
+
+/// A module with the layout of each of its structs and messages.
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub struct Hir<'m> {
+    /// The module the layouts were lowered from.
+    pub module: &'m OnyxModule,
+    layouts: HashMap<Symbol, Layout<'m>>,
+    /// Every definition, each after the definitions it uses.
+    pub(crate) order: Vec<Symbol>,
+}
+
+impl<'m> Hir<'m> {
+    /// Returns the layout of the struct or message named `name`.
+    pub fn layout(&self, name: impl Into<Symbol>) -> Option<&Layout<'m>> {
+        self.layouts.get(&name.into())
+    }
+
+    /// Returns the width in bits of a value of type `ty`, or `None` if it uses a type the
+    /// module does not define or is too large to count in bits. Always a whole number of bytes,
+    /// as bit-field containers are.
+    pub fn bit_width_of(&self, ty: &Type) -> Option<usize> {
+        self.size_of(ty)?.checked_mul(8)
+    }
+
+    /// Returns the size in bytes of a value of type `ty`, or `None` if it uses a type the
+    /// module does not define or is too large to count in bytes.
+    pub fn size_of(&self, ty: &Type) -> Option<usize> {
+        Some(match ty {
+            Type::Primitive(p) | Type::Fixed { base: p, .. } => p.get_byte_size(),
+            Type::Uuid | Type::Bytes(_) => ty.byte_len().unwrap(),
+            Type::Custom(name) => match self.module.definitions.get(name)? {
+                Definition::Enum(e) => e.underlying_type.get_byte_size(),
+                _ => self.layouts.get(name)?.size,
+            },
+            Type::Vector { element, capacity } => {
+                let items = capacity.checked_mul(self.size_of(element)?)?;
+                items.checked_add(ty.length_type().unwrap().get_byte_size())?
+            }
+        })
+    }
+
+    /// Returns the alignment in bytes of a value of type `ty` under the module's `align`
+    /// directive, or `None` if it uses a type the module does not define.
+    pub fn align_of(&self, ty: &Type) -> Option<usize> {
+        let alignment = self.module.alignment;
+        Some(match ty {
+            Type::Primitive(p) | Type::Fixed { base: p, .. } => alignment.of(p.get_byte_size()),
+            // Arrays of octets.
+            Type::Uuid | Type::Bytes(_) => 1,
+            Type::Custom(name) => match self.module.definitions.get(name)? {
+                Definition::Enum(e) => alignment.of(e.underlying_type.get_byte_size()),
+                _ => self.layouts.get(name)?.align,
+            },
+            Type::Vector { element, .. } => {
+                let length = alignment.of(ty.length_type().unwrap().get_byte_size());
+                length.max(self.align_of(element)?)
+            }
+        })
+    }
+
+    /// Resolves `ty`, a type used by the module, to its definition.
+    pub fn resolve(&self, ty: &'m Type) -> Option<TypeRef<'m>> {
+        resolve(self.module, ty)
+    }
+}
+
+/// Lowers a resolved module: resolves the type of every field of its structs and messages and
+/// lays them out.
+///
+/// The parser runs this pass to size definitions and order them, so a parsed module always
+/// lowers. It fails if a field uses a type the module does not define, or a definition
+/// contains itself, as in a module that was built in code.
+pub fn lower(module: &OnyxModule) -> Result<Hir<'_>, Diagnostic> {
+    lower_module(module).map_err(|(_, error)| error)
+}
+
+/// Like [`lower`], but pairs an error with the definition being lowered, so that callers
+/// merging several files can attach the right source.
+pub(crate) fn lower_module(module: &OnyxModule) -> Result<Hir<'_>, (Symbol, Diagnostic)> {
+    let mut hir = Hir {
+        module,
+        layouts: HashMap::new(),
+        order: Vec::new(),
+    };
+    // In source order, so that `order` is deterministic.
+    let mut definitions: Vec<&Definition> = module.definitions.values().collect();
+    definitions.sort_by(|a, b| (a.span().start, a.name()).cmp(&(b.span().start, b.name())));
+    for definition in definitions {
+        lower_definition(&mut hir, definition.symbol(), &mut Vec::new())?;
+    }
+    Ok(hir)
+}
+
+/// Lays out the definition named `name` after the definitions it uses. `stack` holds the
+/// definitions being laid out, to catch a definition that contains itself.
+fn lower_definition<'m>(
+    hir: &mut Hir<'m>,
+    name: Symbol,
+    stack: &mut Vec<Symbol>,
+) -> Result<(), (Symbol, Diagnostic)> {
+    let module = hir.module;
+    let definition = &module.definitions[&name];
+    if hir.order.contains(&name) {
+        return Ok(());
+    }
+    let fields = match definition {
+        Definition::Struct(s) => &s.fields,
+        Definition::Message(m) => &m.fields,
+        Definition::Enum(_) => {
+            hir.order.push(name);
+            return Ok(());
+        }
+    };
+    if stack.contains(&name) {
+        let cycle = stack
+            .iter()
+            .chain(std::iter::once(&name))
+            .map(|id| id.as_str())
+            .collect::<Vec<_>>()
+            .join(" -> ");
+        return Err((
+            name,
+            Diagnostic::error(format!("circular dependency detected: '{cycle}'."))
+                .with_code(codes::CIRCULAR_DEPENDENCY)
+                .with_span(definition.span())
+                .with_help("break the cycle by removing one of the fields"),
+        ));
+    }
+    stack.push(name);
+
+    let mut slots: Vec<Slot<'m>> = Vec::new();
+    let mut offset: usize = 0;
+    let mut align = 1;
+    let mut rest = fields.as_slice();
+    while let Some(first) = rest.first() {
+        let len = match first.bit_field_size {
+            Some(_) => rest
+                .iter()
+                .take_while(|f| f.bit_field_size.is_some())
+                .count(),
+            None => 1,
+        };
+        let (run, tail) = rest.split_at(len);
+        rest = tail;
+
+        for field in run {
+            resolve(module, &field.type_info).ok_or_else(|| (name, undefined(field)))?;
+            if let Type::Custom(used) = field.type_info.element_type() {
+                lower_definition(hir, *used, stack)?;
+            }
+        }
+        let slot_align = match first.bit_field_size {
+            Some(_) => {
+                let bits = run.iter().map(|f| f.bit_field_size.unwrap_or(0));
+                let bits = bits.fold(0usize, usize::saturating_add);
+                module.alignment.of(bits.div_ceil(8))
+            }
+            None => hir.align_of(&first.type_info).unwrap_or(1),
+        };
+        align = align.max(slot_align);
+        offset = offset
+            .checked_next_multiple_of(slot_align)
+            .ok_or_else(|| (name, too_large(definition)))?;
+
+        let mut slot = Slot {
+            offset,
+            size: 0,
+            fields: Vec::new(),
+        };
+        let mut bit_offset = 0;
+        for field in run {
+            let ty = resolve(module, &field.type_info).ok_or_else(|| (name, undefined(field)))?;
+            // The types the field uses are laid out, so only its size can be missing.
+            let bits = match field.bit_field_size {
+                Some(bits) => bits,
+                None => hir
+                    .bit_width_of(&field.type_info)
+                    .ok_or_else(|| (name, too_large(definition)))?,
+            };
+            // An ordinary field is alone in its slot, so its bit offset is zero.
+            slot.fields.push(FieldLayout {
+                field,
+                ty,
+                offset,
+                bit_offset,
+                bits,
+            });
+            bit_offset = bit_offset
+                .checked_add(bits)
+                .ok_or_else(|| (name, too_large(definition)))?;
+        }
+        slot.size = bit_offset.div_ceil(8);
+        offset = offset
+            .checked_add(slot.size)
+            .filter(|size| size.checked_mul(8).is_some())
+            .ok_or_else(|| (name, too_large(definition)))?;
+        slots.push(slot);
+    }
+
+    stack.pop();
+    hir.order.push(name);
+    hir.layouts.insert(
+        name,
+        Layout {
+            definition,
+            size: offset,
+            align,
+            slots,
+        },
+    );
+    Ok(())
+}
+
+fn resolve<'m>(module: &'m OnyxModule, ty: &'m Type) -> Option<TypeRef<'m>> {
+    Some(match ty {
+        Type::Primitive(p) => TypeRef::Primitive(*p),
+        Type::Fixed { base, scale } => TypeRef::Fixed {
+            base: *base,
+            scale: *scale,
+        },
+        Type::Uuid => TypeRef::Uuid,
+        Type::Bytes(len) => TypeRef::Bytes(*len),
+        Type::Custom(name) => match module.definitions.get(name)? {
+            Definition::Enum(e) => TypeRef::Enum(e),
+            Definition::Struct(s) => TypeRef::Struct(s),
+            Definition::Message(m) => TypeRef::Message(m),
+        },
+        Type::Vector { element, capacity } => {
+            resolve(module, element)?;
+            TypeRef::Vector {
+                element,
+                capacity: *capacity,
+                length: ty.length_type().unwrap(),
+            }
+        }
+    })
+}
+
+fn too_large(definition: &Definition) -> Diagnostic {
+    Diagnostic::error(format!(
+        "schema too large: the size of '{}' in bits overflows",
+        definition.name()
+    ))
+    .with_code(codes::SCHEMA_TOO_LARGE)
+    .with_span(definition.span())
+    .with_help("reduce the capacities of its vectors and the lengths of its byte arrays")
+}
+
+fn undefined(field: &Field) -> Diagnostic {
+    let name = field.type_info.element_type();
+    Diagnostic::error(format!("custom type '{name}' not defined"))
+        .with_code(codes::UNDEFINED_TYPE)
+        .with_span(field.span)
+        .with_help(format!("define '{name}' or use a primitive type"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::Parser;
+
+    fn parse(source: &str) -> OnyxModule {
+        Parser::new(source).unwrap().parse_module().unwrap()
+    }
+
+    #[test]
+    fn test_layout() {
+        let module = parse(
+            "enum Kind : u16 { A, B, }
+             struct Point { x i32, y i32, }
+             @packed_bits message M {
+                 kind Kind,
+                 a u8 : 3,
+                 b u16 : 6,
+                 at Point,
+                 points vec<Point, 2>,
+                 c bool : 1,
+             }",
+        );
+        let hir = lower(&module).unwrap();
+        assert_eq!(hir.layout("Point").unwrap().size, 8);
+        assert!(hir.layout("Kind").is_none());
+
+        let layout = hir.layout("M").unwrap();
+        assert_eq!(layout.name(), "M");
+        let slots: Vec<(usize, usize, usize)> = layout
+            .slots
+            .iter()
+            .map(|slot| (slot.offset, slot.size, slot.fields.len()))
+            .collect();
+        assert_eq!(
+            slots,
+            [(0, 2, 1), (2, 2, 2), (4, 8, 1), (12, 17, 1), (29, 1, 1)]
+        );
+        assert_eq!(layout.size, 30);
+        // The parser sizes definitions from the same layout, padding included.
+        assert_eq!(layout.definition.size(), Some(30 * 8));
+        assert_eq!(hir.order, ["Kind", "Point", "M"]);
+        assert!(layout.slots[1].is_bit_field_container());
+        assert_eq!(layout.slots[1].first().name, "a");
+        assert!(!layout.slots[2].is_bit_field_container());
+
+        let b = layout.field("b").unwrap();
+        assert_eq!((b.offset, b.bit_offset, b.bits), (2, 3, 6));
+        assert_eq!(b.ty, TypeRef::Primitive(PrimitiveType::U16));
+        assert!(matches!(layout.field("kind").unwrap().ty, TypeRef::Enum(e) if e.name == "Kind"));
+        let at = layout.field("at").unwrap();
+        assert!(matches!(at.ty, TypeRef::Struct(s) if s.name == "Point"));
+        assert_eq!(at.bits, 64);
+        let points = layout.field("points").unwrap();
+        assert!(matches!(
+            points.ty,
+            TypeRef::Vector {
+                capacity: 2,
+                length: PrimitiveType::U8,
+                ..
+            }
+        ));
+        assert_eq!(hir.size_of(&points.type_info), Some(17));
+        assert_eq!(hir.size_of(&Type::Custom("Missing".into())), None);
+    }
+
+    #[test]
+    fn test_deep_nesting_order() {
+        let module = parse(
+            "message A { b B, e E, }
+             struct B { c C, }
+             struct C { d vec<D, 2>, }
+             struct D { e E, k K, }
+             struct E { x u8, }
+             enum K : u8 { X, }",
+        );
+        let hir = lower(&module).unwrap();
+        // Each definition follows the ones it uses, whatever the source order.
+        assert_eq!(hir.order, ["E", "K", "D", "C", "B", "A"]);
+        assert_eq!(module.order, hir.order);
+        assert_eq!(hir.layout("A").unwrap().size, 6);
+    }
+
+    #[test]
+    fn test_undefined_type() {
+        let mut module = parse("struct S { a u8, }");
+        let Some(Definition::Struct(s)) = module.definitions.get_mut(&Symbol::intern("S")) else {
+            panic!("expected a struct");
+        };
+        s.fields
+            .push(Field::new("b", Type::Custom("Missing".into()), None));
+        let err = lower(&module).unwrap_err();
+        assert_eq!(err.code(), Some(codes::UNDEFINED_TYPE));
+        assert!(
+            err.to_string()
+                .contains("custom type 'Missing' not defined")
+        );
+    }
+}
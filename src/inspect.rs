@@ -0,0 +1,421 @@
+//! Field-by-field decoding of buffers, for debugging.
+//!
+//! [`inspect`] walks the [layout](crate::hir) of a struct or message over a buffer and reports
+//! every field with its offset, the bytes it occupies, and its value: enums by variant name,
+//! fixed-point numbers scaled, and byte arrays in hex. Unlike
+//! [`dynamic::decode`](crate::dynamic::decode), it accepts values no variant of a closed enum
+//! declares, as a malformed buffer is usually why it is being inspected. [`render`] formats the fields as a
+//! table, as `onyxc decode` prints them.
+//!
+//! ```rust
+//! use onyx::inspect;
+//! use onyx::parser::Parser;
+//!
+//! let module = Parser::new("enum Kind : u8 { Ping, Data, } message Packet { kind Kind, len u16, }")
+//!     .and_then(|p| p.parse_module())
+//!     .unwrap();
+//! let fields = inspect::inspect(&module, "Packet", &[1, 0x34, 0x12]).unwrap();
+//! assert_eq!(fields[0].value.as_deref(), Some("Data (1)"));
+//! assert_eq!((fields[1].offset, fields[1].raw.as_slice()), (1, &[0x34, 0x12][..]));
+//! assert_eq!(fields[1].value.as_deref(), Some("4660"));
+//! ```
+
+use std::{collections::HashMap, fmt::Write};
+
+use crate::{
+    ast::{EnumDef, OnyxModule, PrimitiveType},
+    diagnostic::{Diagnostic, codes},
+    hir::{self, Hir, Layout, TypeRef},
+    symbol::Symbol,
+    wire,
+};
+
+/// The most bytes [`render`] shows of a field before eliding the rest.
+const SHOWN_BYTES: usize = 16;
+
+/// A field of an inspected buffer.
+#[derive(Debug, Clone, Default, PartialEq)]
+#[non_exhaustive]
+pub struct DecodedField {
+    /// The path of the field from the inspected definition, such as `start.x` for a field of
+    /// a nested struct or `points[1]` for an element of a vector.
+    pub path: String,
+    /// The byte offset of the field in the buffer, or of the container holding it if it is a
+    /// bit-field.
+    pub offset: usize,
+    /// The offset of a bit-field within its container, from the least significant bit. Zero
+    /// for other fields.
+    pub bit_offset: usize,
+    /// The width of the field in bits.
+    pub bits: usize,
+    /// The bytes the field occupies, or its whole container if it is a bit-field. For a vector,
+    /// its length prefix.
+    pub raw: Vec<u8>,
+    /// The value of the field, or `None` if its condition does not hold.
+    pub value: Option<String>,
+}
+
+/// Decodes `bytes` as the struct or message `name`, which must be exactly its size, and
+/// returns its fields in wire order.
+///
+/// A nested struct is reported through its fields. A vector is reported as its length, then
+/// each element in use.
+pub fn inspect(
+    module: &OnyxModule,
+    name: &str,
+    bytes: &[u8],
+) -> Result<Vec<DecodedField>, Diagnostic> {
+    let hir = hir::lower(module)?;
+    let layout = hir.layout(name).ok_or_else(|| {
+        Diagnostic::error(format!("no struct or message named '{name}'"))
+            .with_code(codes::UNDEFINED_TYPE)
+    })?;
+    if bytes.len() != layout.size {
+        return Err(Diagnostic::error(format!(
+            "expected {} bytes for '{name}', found {}",
+            layout.size,
+            bytes.len()
+        ))
+        .with_code(codes::VALUE_MISMATCH));
+    }
+    let mut fields = Vec::new();
+    Inspector {
+        hir: &hir,
+        bytes,
+        fields: &mut fields,
+    }
+    .layout(layout, 0, "");
+    Ok(fields)
+}
+
+/// Formats `fields` as a table with a row per field: its offset, the bytes it occupies in
+/// hex, its path, and its value.
+///
+/// A bit-field's offset is written `byte.bit`. Fields whose condition does not hold have the
+/// value `(absent)`.
+pub fn render(fields: &[DecodedField]) -> String {
+    let rows: Vec<[String; 4]> = fields
+        .iter()
+        .map(|field| {
+            let offset = match field.bit_offset {
+                0 if field.bits % 8 == 0 => field.offset.to_string(),
+                bit => format!("{}.{bit}", field.offset),
+            };
+            let mut raw: Vec<String> = field
+                .raw
+                .iter()
+                .take(SHOWN_BYTES)
+                .map(|b| format!("{b:02x}"))
+                .collect();
+            if field.raw.len() > SHOWN_BYTES {
+                raw.push("..".to_string());
+            }
+            let value = field.value.as_deref().unwrap_or("(absent)");
+            [offset, raw.join(" "), field.path.clone(), value.to_string()]
+        })
+        .collect();
+    let header = ["offset", "bytes", "field", "value"].map(String::from);
+    let mut widths = header.clone().map(|cell| cell.len());
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.len());
+        }
+    }
+    let mut out = String::new();
+    for row in std::iter::once(&header).chain(&rows) {
+        let line = format!(
+            "{:>w0$}  {:w1$}  {:w2$}  {}",
+            row[0],
+            row[1],
+            row[2],
+            row[3],
+            w0 = widths[0],
+            w1 = widths[1],
+            w2 = widths[2]
+        );
+        writeln!(out, "{}", line.trim_end()).unwrap();
+    }
+    out
+}
+
+struct Inspector<'a, 'm> {
+    hir: &'a Hir<'m>,
+    bytes: &'a [u8],
+    fields: &'a mut Vec<DecodedField>,
+}
+
+impl Inspector<'_, '_> {
+    /// Reports the fields of `layout`, which starts at `base`, under `prefix`.
+    fn layout(&mut self, layout: &Layout, base: usize, prefix: &str) {
+        // Conditions read fields without conditions of their own, at any position.
+        let mut raws: HashMap<Symbol, u64> = HashMap::new();
+        for slot in &layout.slots {
+            for field in &slot.fields {
+                if field.condition.is_some() {
+                    continue;
+                }
+                let raw = match field.bit_field_size {
+                    Some(_) => {
+                        self.bit_field(base + slot.offset, slot.size, field.bit_offset, field.bits)
+                    }
+                    None => match scalar_type(field.ty) {
+                        Some(p) => self.uint(base + field.offset, p.get_byte_size()),
+                        None => continue,
+                    },
+                };
+                raws.insert(field.name, extend(field.ty, raw, field.bits) as u64);
+            }
+        }
+
+        for slot in &layout.slots {
+            for field in &slot.fields {
+                let path = format!("{prefix}{}", field.name);
+                let offset = base + field.offset;
+                let present = field.condition.as_ref().is_none_or(|condition| {
+                    let raw = raws.get(&condition.field).copied().unwrap_or(0);
+                    condition.holds(self.hir.module, raw)
+                });
+                if field.bit_field_size.is_some() {
+                    let raw =
+                        self.bit_field(base + slot.offset, slot.size, field.bit_offset, field.bits);
+                    self.fields.push(DecodedField {
+                        path,
+                        offset,
+                        bit_offset: field.bit_offset,
+                        bits: field.bits,
+                        raw: self.bytes[offset..offset + slot.size].to_vec(),
+                        value: present.then(|| self.format(field.ty, raw, field.bits)),
+                    });
+                    continue;
+                }
+                if !present {
+                    self.fields.push(DecodedField {
+                        raw: self.bytes[offset..offset + field.bits / 8].to_vec(),
+                        path,
+                        offset,
+                        bits: field.bits,
+                        ..DecodedField::default()
+                    });
+                    continue;
+                }
+                self.value(field.ty, offset, &path);
+            }
+        }
+    }
+
+    /// Reports the value of type `ty` at `offset` as `path`.
+    fn value(&mut self, ty: TypeRef, offset: usize, path: &str) {
+        match ty {
+            TypeRef::Struct(_) | TypeRef::Message(_) => {
+                let name = match ty {
+                    TypeRef::Struct(s) => s.name,
+                    TypeRef::Message(m) => m.name,
+                    _ => unreachable!(),
+                };
+                let layout = self.hir.layout(name).unwrap();
+                self.layout(layout, offset, &format!("{path}."));
+            }
+            TypeRef::Vector {
+                element,
+                capacity,
+                length,
+            } => {
+                let prefix = length.get_byte_size();
+                let len = self.uint(offset, prefix) as usize;
+                self.fields.push(DecodedField {
+                    path: path.to_string(),
+                    offset,
+                    bits: prefix * 8,
+                    raw: self.bytes[offset..offset + prefix].to_vec(),
+                    value: Some(format!("{len} of {capacity}")),
+                    ..DecodedField::default()
+                });
+                let element_ty = self.hir.resolve(element).unwrap();
+                let size = self.hir.size_of(element).unwrap();
+                for i in 0..len.min(capacity) {
+                    self.value(
+                        element_ty,
+                        offset + prefix + i * size,
+                        &format!("{path}[{i}]"),
+                    );
+                }
+            }
+            _ => {
+                let size = match ty {
+                    TypeRef::Uuid => 16,
+                    TypeRef::Bytes(len) => len,
+                    _ => scalar_type(ty).unwrap().get_byte_size(),
+                };
+                let raw = match ty {
+                    TypeRef::Uuid | TypeRef::Bytes(_) => 0,
+                    _ => self.uint(offset, size),
+                };
+                let bytes = &self.bytes[offset..offset + size];
+                let value = match ty {
+                    TypeRef::Uuid => hex(bytes, true),
+                    TypeRef::Bytes(_) => hex(bytes, false),
+                    _ => self.format(ty, raw, size * 8),
+                };
+                self.fields.push(DecodedField {
+                    path: path.to_string(),
+                    offset,
+                    bits: size * 8,
+                    raw: bytes.to_vec(),
+                    value: Some(value),
+                    ..DecodedField::default()
+                });
+            }
+        }
+    }
+
+    /// Formats the `bits`-wide integer `raw` as a value of the scalar type `ty`.
+    fn format(&self, ty: TypeRef, raw: u128, bits: usize) -> String {
+        match ty {
+            TypeRef::Primitive(PrimitiveType::Bool) => (raw != 0).to_string(),
+            TypeRef::Primitive(PrimitiveType::F32) => f32::from_bits(raw as u32).to_string(),
+            TypeRef::Primitive(PrimitiveType::F64) => f64::from_bits(raw as u64).to_string(),
+            TypeRef::Fixed { scale, .. } => {
+                let raw = extend(ty, raw, bits);
+                format!("{} (raw {raw})", raw as f64 / scale as f64)
+            }
+            TypeRef::Enum(e) => variant_names(e, raw as u64, bits),
+            _ => extend(ty, raw, bits).to_string(),
+        }
+    }
+
+    /// Reads the `len`-byte unsigned integer at `offset` in the module's endianness.
+    fn uint(&self, offset: usize, len: usize) -> u128 {
+        wire::read_uint(
+            &self.bytes[offset..offset + len],
+            self.hir.module.endianness,
+        )
+    }
+
+    /// Reads the `bits`-wide bit-field at `bit_offset` in the `size`-byte container at
+    /// `offset`.
+    fn bit_field(&self, offset: usize, size: usize, bit_offset: usize, bits: usize) -> u128 {
+        let container = &self.bytes[offset..offset + size];
+        wire::read_bits(container, self.hir.module.endianness, bit_offset, bits)
+    }
+}
+
+/// Returns the integer type a scalar of type `ty` is carried as, or `None` if it is not a
+/// scalar.
+fn scalar_type(ty: TypeRef) -> Option<PrimitiveType> {
+    match ty {
+        TypeRef::Primitive(p) | TypeRef::Fixed { base: p, .. } => Some(p),
+        TypeRef::Enum(e) => Some(e.underlying_type),
+        _ => None,
+    }
+}
+
+/// Sign-extends the `bits`-wide `raw` if `ty` is signed.
+fn extend(ty: TypeRef, raw: u128, bits: usize) -> i128 {
+    let signed = matches!(
+        scalar_type(ty),
+        Some(PrimitiveType::I8 | PrimitiveType::I16 | PrimitiveType::I32 | PrimitiveType::I64)
+    );
+    match signed {
+        true => wire::sign_extend(raw, bits),
+        false => raw as i128,
+    }
+}
+
+/// Formats the value of the enum `e` by the variant, or for flags the variants, it holds.
+fn variant_names(e: &EnumDef, raw: u64, bits: usize) -> String {
+    let number = extend(TypeRef::Enum(e), raw as u128, bits);
+    let width_mask = wire::mask(bits.min(64)) as u64;
+    let values: Vec<u64> = e.variant_values().iter().map(|v| v & width_mask).collect();
+    if !e.flags {
+        return match values.iter().position(|&v| v == raw) {
+            Some(i) => format!("{} ({number})", e.variants[i].name),
+            None => format!("{number} (unknown)"),
+        };
+    }
+    let mut names = Vec::new();
+    let mut rest = raw;
+    for (variant, value) in e.variants.iter().zip(values) {
+        if !variant.composite && value != 0 && raw & value == value {
+            names.push(variant.name.to_string());
+            rest &= !value;
+        }
+    }
+    if rest != 0 {
+        names.push(format!("{rest:#x}"));
+    }
+    match names.is_empty() {
+        true => format!("empty ({number})"),
+        false => format!("{} ({number})", names.join(" | ")),
+    }
+}
+
+/// Formats bytes as lowercase hex; a UUID is grouped 8-4-4-4-12.
+fn hex(bytes: &[u8], uuid: bool) -> String {
+    let mut out = String::new();
+    for (i, b) in bytes.iter().enumerate() {
+        if uuid && matches!(i, 4 | 6 | 8 | 10) {
+            out.push('-');
+        }
+        write!(out, "{b:02x}").unwrap();
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::Parser;
+
+    #[test]
+    fn test_inspect() {
+        let source = "enum Kind : u8 { Ping, Data, }
+                      flags Access : u8 { Read, Write, }
+                      struct Point { x i16, y i16, }
+                      message Packet {
+                          kind Kind,
+                          ready bool : 1,
+                          level i8 : 7,
+                          at Point if kind == Kind.Data,
+                          access Access,
+                          gain fixed<u16, 100>,
+                          path vec<Point, 2>,
+                      }";
+        let module = Parser::new(source).unwrap().parse_module().unwrap();
+        let bytes = [
+            7, 0xfd, 1, 0, 2, 0, 0x13, 0xe8, 0x03, 1, 0xff, 0xff, 0x05, 0, 0, 0, 0, 0,
+        ];
+        let fields = inspect(&module, "Packet", &bytes).unwrap();
+        let rows: Vec<(&str, usize, Option<&str>)> = fields
+            .iter()
+            .map(|f| (f.path.as_str(), f.offset, f.value.as_deref()))
+            .collect();
+        assert_eq!(
+            rows,
+            [
+                ("kind", 0, Some("7 (unknown)")),
+                ("ready", 1, Some("true")),
+                ("level", 1, Some("-2")),
+                ("at", 2, None),
+                ("access", 6, Some("Read | Write | 0x10 (19)")),
+                ("gain", 7, Some("10 (raw 1000)")),
+                ("path", 9, Some("1 of 2")),
+                ("path[0].x", 10, Some("-1")),
+                ("path[0].y", 12, Some("5")),
+            ]
+        );
+        assert_eq!(fields[2].bit_offset, 1);
+
+        let table = render(&fields);
+        assert!(table.starts_with("offset  bytes        field      value\n"));
+        assert!(table.contains("\n   1.1  fd           level      -2\n"));
+        assert!(table.contains("\n     2  01 00 02 00  at         (absent)\n"));
+
+        assert_eq!(
+            inspect(&module, "Packet", &bytes[1..])
+                .unwrap_err()
+                .message(),
+            "expected 18 bytes for 'Packet', found 17"
+        );
+        assert!(inspect(&module, "Kind", &[0]).is_err());
+    }
+}
@@ -0,0 +1,350 @@
+//! Minimal JSON helpers, to keep the crate free of serialization dependencies.
+
+use std::fmt::Write;
+
+/// Returns `s` as a quoted JSON string literal.
+pub(crate) fn string(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => write!(out, "\\u{:04x}", c as u32).unwrap(),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+/// Returns a JSON array of string literals.
+pub(crate) fn string_array(items: &[String]) -> String {
+    let items: Vec<String> = items.iter().map(|item| string(item)).collect();
+    format!("[{}]", items.join(","))
+}
+
+/// A parsed JSON value.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Value {
+    Null,
+    Bool(bool),
+    Number(Number),
+    String(String),
+    Array(Vec<Value>),
+    Object(Vec<(String, Value)>),
+}
+
+/// A JSON number, kept as written so that integers an `f64` cannot hold read back exactly.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Number(String);
+
+/// The smallest integer an `f64` may have rounded another to: 2^53 + 1 reads as 2^53.
+const MAX_EXACT: f64 = (1u64 << 53) as f64;
+
+impl Number {
+    /// Returns the number as an `f64`, rounded to the nearest one it can hold.
+    pub(crate) fn as_f64(&self) -> f64 {
+        self.0.parse().unwrap_or(f64::NAN)
+    }
+
+    /// Returns the number if it is an integer that fits a `u64`.
+    pub(crate) fn as_u64(&self) -> Option<u64> {
+        self.0
+            .parse()
+            .ok()
+            .or_else(|| self.exact().filter(|n| *n >= 0.0).map(|n| n as u64))
+    }
+
+    /// Returns the number if it is an integer that fits an `i64`.
+    pub(crate) fn as_i64(&self) -> Option<i64> {
+        self.0
+            .parse()
+            .ok()
+            .or_else(|| self.exact().map(|n| n as i64))
+    }
+
+    /// Returns true if the number is a whole number too large for an `f64` to hold exactly,
+    /// written in a form that does not give its digits, such as with a fraction or exponent.
+    pub(crate) fn is_inexact_integer(&self) -> bool {
+        let n = self.as_f64();
+        let digits = self.0.strip_prefix('-').unwrap_or(&self.0);
+        n.fract() == 0.0 && n.abs() >= MAX_EXACT && !digits.bytes().all(|b| b.is_ascii_digit())
+    }
+
+    /// Returns the number as an integer if it is one, written in any form, that an `f64`
+    /// holds exactly.
+    fn exact(&self) -> Option<f64> {
+        let n = self.as_f64();
+        (n.fract() == 0.0 && n.abs() < MAX_EXACT).then_some(n)
+    }
+}
+
+impl From<f64> for Number {
+    fn from(n: f64) -> Self {
+        Number(n.to_string())
+    }
+}
+
+impl Value {
+    /// Returns the value of `key` if this is an object that contains it.
+    pub(crate) fn get(&self, key: &str) -> Option<&Value> {
+        match self {
+            Value::Object(entries) => entries.iter().find(|(k, _)| k == key).map(|(_, v)| v),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn as_str(&self) -> Option<&str> {
+        match self {
+            Value::String(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn as_array(&self) -> Option<&[Value]> {
+        match self {
+            Value::Array(items) => Some(items),
+            _ => None,
+        }
+    }
+}
+
+/// Parses a JSON document. Errors carry the byte offset where parsing failed.
+pub(crate) fn parse(input: &str) -> Result<Value, String> {
+    let mut reader = Reader { input, pos: 0 };
+    let value = reader.value()?;
+    reader.skip_whitespace();
+    if reader.pos != input.len() {
+        return Err(reader.error("trailing characters"));
+    }
+    Ok(value)
+}
+
+struct Reader<'a> {
+    input: &'a str,
+    pos: usize,
+}
+
+impl Reader<'_> {
+    fn error(&self, message: &str) -> String {
+        format!("{message} at byte {}", self.pos)
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.input.as_bytes().get(self.pos).copied()
+    }
+
+    fn skip_whitespace(&mut self) {
+        while matches!(self.peek(), Some(b' ' | b'\t' | b'\n' | b'\r')) {
+            self.pos += 1;
+        }
+    }
+
+    fn expect(&mut self, literal: &str) -> Result<(), String> {
+        if self.input[self.pos..].starts_with(literal) {
+            self.pos += literal.len();
+            Ok(())
+        } else {
+            Err(self.error(&format!("expected '{literal}'")))
+        }
+    }
+
+    fn value(&mut self) -> Result<Value, String> {
+        self.skip_whitespace();
+        match self.peek() {
+            Some(b'n') => self.expect("null").map(|_| Value::Null),
+            Some(b't') => self.expect("true").map(|_| Value::Bool(true)),
+            Some(b'f') => self.expect("false").map(|_| Value::Bool(false)),
+            Some(b'"') => self.string().map(Value::String),
+            Some(b'[') => {
+                self.pos += 1;
+                let mut items = Vec::new();
+                self.skip_whitespace();
+                if self.peek() == Some(b']') {
+                    self.pos += 1;
+                    return Ok(Value::Array(items));
+                }
+                loop {
+                    items.push(self.value()?);
+                    self.skip_whitespace();
+                    match self.peek() {
+                        Some(b',') => self.pos += 1,
+                        Some(b']') => {
+                            self.pos += 1;
+                            return Ok(Value::Array(items));
+                        }
+                        _ => return Err(self.error("expected ',' or ']'")),
+                    }
+                }
+            }
+            Some(b'{') => {
+                self.pos += 1;
+                let mut entries = Vec::new();
+                self.skip_whitespace();
+                if self.peek() == Some(b'}') {
+                    self.pos += 1;
+                    return Ok(Value::Object(entries));
+                }
+                loop {
+                    self.skip_whitespace();
+                    if self.peek() != Some(b'"') {
+                        return Err(self.error("expected a string key"));
+                    }
+                    let key = self.string()?;
+                    self.skip_whitespace();
+                    self.expect(":")?;
+                    entries.push((key, self.value()?));
+                    self.skip_whitespace();
+                    match self.peek() {
+                        Some(b',') => self.pos += 1,
+                        Some(b'}') => {
+                            self.pos += 1;
+                            return Ok(Value::Object(entries));
+                        }
+                        _ => return Err(self.error("expected ',' or '}'")),
+                    }
+                }
+            }
+            Some(b'-' | b'0'..=b'9') => {
+                let start = self.pos;
+                while matches!(
+                    self.peek(),
+                    Some(b'-' | b'+' | b'.' | b'e' | b'E' | b'0'..=b'9')
+                ) {
+                    self.pos += 1;
+                }
+                let text = &self.input[start..self.pos];
+                match text.parse::<f64>() {
+                    Ok(_) => Ok(Value::Number(Number(text.to_string()))),
+                    Err(_) => Err(self.error("invalid number")),
+                }
+            }
+            _ => Err(self.error("expected a value")),
+        }
+    }
+
+    /// Reads a string literal, starting at its opening quote.
+    fn string(&mut self) -> Result<String, String> {
+        self.pos += 1;
+        let mut out = String::new();
+        loop {
+            let rest = &self.input[self.pos..];
+            let Some(c) = rest.chars().next() else {
+                return Err(self.error("unterminated string"));
+            };
+            self.pos += c.len_utf8();
+            match c {
+                '"' => return Ok(out),
+                '\\' => {
+                    let escape = self
+                        .peek()
+                        .ok_or_else(|| self.error("unterminated string"))?;
+                    self.pos += 1;
+                    match escape {
+                        b'"' => out.push('"'),
+                        b'\\' => out.push('\\'),
+                        b'/' => out.push('/'),
+                        b'b' => out.push('\u{8}'),
+                        b'f' => out.push('\u{c}'),
+                        b'n' => out.push('\n'),
+                        b'r' => out.push('\r'),
+                        b't' => out.push('\t'),
+                        b'u' => {
+                            let mut code = self.hex4()?;
+                            if (0xD800..0xDC00).contains(&code) {
+                                self.expect("\\u")?;
+                                let low = self.hex4()?;
+                                code =
+                                    0x10000 + ((code - 0xD800) << 10) + (low.wrapping_sub(0xDC00));
+                            }
+                            out.push(
+                                char::from_u32(code)
+                                    .ok_or_else(|| self.error("invalid unicode escape"))?,
+                            );
+                        }
+                        _ => return Err(self.error("invalid escape")),
+                    }
+                }
+                c => out.push(c),
+            }
+        }
+    }
+
+    fn hex4(&mut self) -> Result<u32, String> {
+        let digits = self
+            .input
+            .get(self.pos..self.pos + 4)
+            .ok_or_else(|| self.error("invalid unicode escape"))?;
+        let code =
+            u32::from_str_radix(digits, 16).map_err(|_| self.error("invalid unicode escape"))?;
+        self.pos += 4;
+        Ok(code)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_string_escapes() {
+        assert_eq!(string("a\"b\\c\nd\u{1b}"), r#""a\"b\\c\nd\u001b""#);
+        assert_eq!(
+            string_array(&["x".to_string(), "y".to_string()]),
+            r#"["x","y"]"#
+        );
+        assert_eq!(string_array(&[]), "[]");
+    }
+
+    #[test]
+    fn test_parse() {
+        let value =
+            parse(r#" {"a": [1, -2.5e1, true, null], "b": "x\"\u00e9\ud83d\ude00\n", "c": {}} "#)
+                .unwrap();
+        assert_eq!(
+            value.get("a").and_then(Value::as_array).unwrap(),
+            [
+                Value::Number(Number("1".to_string())),
+                Value::Number(Number("-2.5e1".to_string())),
+                Value::Bool(true),
+                Value::Null
+            ]
+        );
+        assert_eq!(value.get("b").and_then(Value::as_str), Some("x\"é😀\n"));
+        assert_eq!(value.get("c"), Some(&Value::Object(Vec::new())));
+
+        // Strings written by `string` read back unchanged.
+        let text = "tab\t quote\" \u{1b}";
+        assert_eq!(parse(&string(text)).unwrap().as_str(), Some(text));
+
+        assert!(parse("[1, 2").is_err());
+        assert!(parse("{\"a\" 1}").is_err());
+        assert!(parse("1 2").unwrap_err().contains("byte 2"));
+    }
+
+    #[test]
+    fn test_parse_numbers() {
+        let number = |text: &str| match parse(text).unwrap() {
+            Value::Number(n) => n,
+            other => panic!("expected a number, found {other:?}"),
+        };
+        // Integers read back exactly, even where an f64 would round them.
+        assert_eq!(number("9007199254740993").as_u64(), Some(9007199254740993));
+        assert_eq!(number("18446744073709551615").as_u64(), Some(u64::MAX));
+        assert_eq!(number("-9223372036854775808").as_i64(), Some(i64::MIN));
+        assert_eq!(number("18446744073709551616").as_u64(), None);
+        assert_eq!(number("-1").as_u64(), None);
+        // Whole numbers in other forms count while an f64 holds them exactly.
+        assert_eq!(number("1e3").as_u64(), Some(1000));
+        assert_eq!(number("-2.0").as_i64(), Some(-2));
+        assert_eq!(number("2.5").as_i64(), None);
+        assert_eq!(number("1e17").as_u64(), None);
+        assert!(number("1e17").is_inexact_integer());
+        assert!(!number("9007199254740993").is_inexact_integer());
+        assert_eq!(number("-2.5e1").as_f64(), -25.0);
+    }
+}
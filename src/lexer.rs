@@ -1,34 +1,17 @@
-use std::{fmt::Display, iter::Peekable, str::Chars};
+use std::{iter::Peekable, str::Chars};
 
-use crate::{ast::PrimitiveType, color};
-
-/// A minimal struct to track location in the source file for better errors.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
-pub struct Span {
-    /// The starting byte index of the span (inclusive).
-    pub start: usize,
-    /// The ending byte index of the span (exclusive).
-    pub end: usize,
-}
-
-/// Tracks token location with line number and span.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
-pub struct Position {
-    /// The 0-indexed line number.
-    pub line: usize,
-    /// The span of the token within the source.
-    pub span: Span,
-}
-
-impl Display for Position {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}:{}", self.line + 1, self.span.start + 1)
-    }
-}
+use crate::{
+    ast::PrimitiveType,
+    diagnostic::{Diagnostic, codes},
+    span::Span,
+    trace,
+};
 
 /// The core token definition for the Onyx IDL.
-#[derive(Debug, PartialEq)]
-pub enum TokenKind {
+///
+/// Identifiers borrow from the source, so lexing does not allocate and tokens are `Copy`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TokenKind<'a> {
     // Keywords
     /// The `endian` keyword.
     Endianness,
@@ -40,6 +23,8 @@ pub enum TokenKind {
     Struct,
     /// The `enum` keyword.
     Enum,
+    /// The `uuid` type keyword.
+    Uuid,
     // Primitive Types
     /// A primitive type keyword (e.g., `u8`, `i32`, `bool`).
     Primitive(PrimitiveType),
@@ -52,102 +37,143 @@ pub enum TokenKind {
     Comma, // ,
     /// Colon `:`.
     Colon, // :
+    /// Open angle bracket `<`.
+    OpenAngle, // <
+    /// Close angle bracket `>`.
+    CloseAngle, // >
+    /// Open square bracket `[`.
+    OpenBracket, // [
+    /// Close square bracket `]`.
+    CloseBracket, // ]
+    /// Open parenthesis `(`.
+    OpenParen, // (
+    /// Close parenthesis `)`.
+    CloseParen, // )
     /// Semicolon `;`.
     Semicolon, // ;
     /// Assignment operator `=`.
     Assign, // =
+    /// Equality operator `==`.
+    Equal, // ==
+    /// Inequality operator `!=`.
+    NotEqual, // !=
+    /// Dot `.`.
+    Dot, // .
+    /// Plus `+`.
+    Plus, // +
+    /// Minus `-`.
+    Minus, // -
+    /// Asterisk `*`.
+    Star, // *
+    /// Slash `/`.
+    Slash, // /
+    /// Percent `%`.
+    Percent, // %
+    /// Left shift `<<`.
+    ShiftLeft, // <<
+    /// Right shift `>>`.
+    ShiftRight, // >>
+    /// At sign `@`, which starts an attribute.
+    At, // @
     /// Custom type identifier that assigns an id to something like a message or struct.
     /// e.g. `MyStructName`, `my_field`
-    Identifier(String),
+    Identifier(&'a str),
+    /// An identifier written in backticks, such as `` `struct` ``, so that a keyword can be
+    /// used as a name. Holds the name without the backticks; it is never a keyword.
+    RawIdentifier(&'a str),
     /// Integer literal (e.g. `123`).
     LiteralInt(u64),
+    /// String literal (e.g. `"use v2"`). Holds the text without the quotes; strings have no
+    /// escapes and end at the line.
+    LiteralString(&'a str),
     /// End of File marker.
     Eof,
-    /// Error token with a message, indicating a lexical error.
-    Error(String),
+    /// Error token indicating a lexical error. The offending text is the token's span.
+    Error(LexError),
+}
+
+/// A lexical error carried by [`TokenKind::Error`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum LexError {
+    /// A character that cannot start any token.
+    UnrecognizedChar(char),
+    /// A word containing a character outside the identifier policy (see [`is_ident_continue`]).
+    InvalidIdentifierChar(char),
+    /// An integer literal too large for a `u64`.
+    IntegerOverflow,
+    /// A backtick that does not enclose a name, as in `` `struct `` or ``` `` ```.
+    InvalidRawIdentifier,
+    /// A string literal with no closing quote on its line.
+    UnterminatedString,
+}
+
+impl LexError {
+    /// Returns a description of the error, given the text of the token that produced it.
+    pub fn message(&self, text: &str) -> String {
+        match self {
+            LexError::UnrecognizedChar(c) => format!("unrecognized {}", describe_char(*c)),
+            LexError::InvalidIdentifierChar(c) => format!(
+                "identifier '{text}' contains {}; identifiers may only use ASCII letters, \
+                 digits, and '_'",
+                describe_char(*c)
+            ),
+            LexError::IntegerOverflow => format!("integer literal {text} does not fit in a u64"),
+            LexError::InvalidRawIdentifier => format!(
+                "raw identifier '{text}' must be a name between backticks, such as `struct`"
+            ),
+            LexError::UnterminatedString => {
+                format!("string literal {text} is missing its closing quote")
+            }
+        }
+    }
 }
 
 /// A full token, including its kind and its location (span).
-#[derive(Debug, PartialEq)]
-pub struct Token {
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Token<'a> {
     /// The kind of the token.
-    pub kind: TokenKind,
-    /// The position of the token in the source file.
-    pub position: Position,
+    pub kind: TokenKind<'a>,
+    /// The byte range of the token in the source file.
+    pub span: Span,
 }
 
 /// Lexer for tokenizing Onyx IDL source code.
 pub struct Lexer<'a> {
     source: &'a str,
     chars: Peekable<Chars<'a>>,
-    start_line_indices: Vec<usize>,
+    /// The byte offset of the next character.
     absolute_pos: usize,
-    current_line: usize,
-    current_col: usize,
 }
 
 impl<'a> Lexer<'a> {
-    /// Creates a new Lexer from the input source string.
+    /// Creates a new Lexer from the input source string. A leading byte order mark is skipped.
     pub fn new(source: &'a str) -> Self {
-        Lexer {
-            source,
-            chars: source.chars().peekable(),
-            start_line_indices: vec![0],
-            absolute_pos: 0,
-            current_line: 0,
-            current_col: 0,
-        }
-    }
-
-    /// Generates a formatted string displaying the token within its context in the source code.
-    /// Useful for error messages.
-    pub fn display_token_in_context(&self, token: &Token) -> String {
-        let left_index = if token.position.line > 3 {
-            self.start_line_indices[token.position.line - 4]
+        let absolute_pos = if source.starts_with('\u{feff}') {
+            '\u{feff}'.len_utf8()
         } else {
             0
         };
-        let right_index = self.start_line_indices[token.position.line] + token.position.span.end;
-
-        let mut result = self.source[left_index..right_index].to_string();
-        let token_size = token.position.span.end - token.position.span.start;
-        let point_str = '^'.to_string().repeat(token_size);
-        let space_str = ' '.to_string().repeat(token.position.span.start);
-
-        let right_slice = &self.source[right_index..];
-        for (index, slice) in right_slice.lines().enumerate() {
-            if index == 0 {
-                result.push_str(slice);
-                result.push_str(&format!(
-                    "\n{space_str}{}{point_str}{}",
-                    color::RED,
-                    color::END
-                ));
-            } else if index <= 4 {
-                result.push('\n');
-                result.push_str(slice);
-            } else {
-                break;
-            }
+        Lexer {
+            source,
+            chars: source[absolute_pos..].chars().peekable(),
+            absolute_pos,
         }
+    }
 
-        result
+    /// Lexes the rest of the source and returns a diagnostic for every error token, so that all
+    /// lexical errors can be reported up front instead of stopping at the first one.
+    pub fn collect_errors(self) -> Vec<Diagnostic> {
+        let source = self.source;
+        self.filter_map(|token| token_error(source, &token))
+            .collect()
     }
 
     /// Advances the internal position and consumes the current character.
     fn advance(&mut self) -> Option<char> {
-        self.absolute_pos += 1;
-        self.current_col += 1;
-        self.chars.next()
-    }
-
-    /// Advances the internal position and consumes the current character, handling new lines.
-    fn advance_new_line(&mut self) -> Option<char> {
-        self.current_col = 0;
-        self.absolute_pos += 1;
-        self.current_line += 1;
-        self.start_line_indices.push(self.absolute_pos);
-        self.chars.next()
+        let c = self.chars.next()?;
+        self.absolute_pos += c.len_utf8();
+        Some(c)
     }
 
     /// Peeks at the next character without consuming it.
@@ -155,13 +181,16 @@ impl<'a> Lexer<'a> {
         self.chars.peek()
     }
 
-    /// Skips all whitespace characters.
+    /// Skips all whitespace characters and `//` line comments.
     fn skip_whitespace(&mut self) {
         while let Some(&c) = self.peek() {
-            if c == '\n' {
-                self.advance_new_line();
-            } else if c.is_ascii_whitespace() {
+            if c.is_ascii_whitespace() {
                 self.advance();
+            } else if self.source[self.absolute_pos..].starts_with("//") {
+                // Comments run to the end of the line; the newline is skipped as whitespace.
+                while self.peek().is_some_and(|&c| c != '\n') {
+                    self.advance();
+                }
             } else {
                 break;
             }
@@ -169,10 +198,13 @@ impl<'a> Lexer<'a> {
     }
 
     /// Parses an identifier or keyword.
-    fn take_identifier(&mut self) -> TokenKind {
+    ///
+    /// The whole word is consumed, including letters outside the identifier policy, so that a
+    /// name like `café` is reported once rather than split into `caf` and a stray character.
+    fn take_identifier(&mut self) -> TokenKind<'a> {
         let start = self.absolute_pos;
         while let Some(&c) = self.peek() {
-            if c.is_ascii_alphanumeric() || c == '_' {
+            if is_word_char(c) {
                 self.advance();
             } else {
                 break;
@@ -181,30 +213,54 @@ impl<'a> Lexer<'a> {
         let end = self.absolute_pos;
         let ident_str = &self.source[start..end];
 
+        if let Some(c) = ident_str.chars().find(|&c| !is_ident_continue(c)) {
+            return TokenKind::Error(LexError::InvalidIdentifierChar(c));
+        }
+
         // Check if it's a reserved keyword or type
-        match ident_str {
-            "import" => TokenKind::Import,
-            "endian" => TokenKind::Endianness,
-            "message" => TokenKind::Message,
-            "struct" => TokenKind::Struct,
-            "enum" => TokenKind::Enum,
-            "bool" => TokenKind::Primitive(PrimitiveType::Bool),
-            "u8" => TokenKind::Primitive(PrimitiveType::U8),
-            "u16" => TokenKind::Primitive(PrimitiveType::U16),
-            "u32" => TokenKind::Primitive(PrimitiveType::U32),
-            "u64" => TokenKind::Primitive(PrimitiveType::U64),
-            "i8" => TokenKind::Primitive(PrimitiveType::I8),
-            "i16" => TokenKind::Primitive(PrimitiveType::I16),
-            "i32" => TokenKind::Primitive(PrimitiveType::I32),
-            "i64" => TokenKind::Primitive(PrimitiveType::I64),
-            "f32" => TokenKind::Primitive(PrimitiveType::F32),
-            "f64" => TokenKind::Primitive(PrimitiveType::F64),
-            _ => TokenKind::Identifier(ident_str.to_string()),
+        keyword(ident_str).unwrap_or(TokenKind::Identifier(ident_str))
+    }
+
+    /// Parses a raw identifier, a name between backticks. The opening backtick is next.
+    fn take_raw_identifier(&mut self) -> TokenKind<'a> {
+        self.advance();
+        let start = self.absolute_pos;
+        while self.peek().is_some_and(|&c| is_word_char(c)) {
+            self.advance();
+        }
+        let name = &self.source[start..self.absolute_pos];
+        if self.peek() != Some(&'`') {
+            return TokenKind::Error(LexError::InvalidRawIdentifier);
+        }
+        self.advance();
+
+        match name.chars().next() {
+            Some(c) if is_ident_start(c) => {}
+            _ => return TokenKind::Error(LexError::InvalidRawIdentifier),
+        }
+        if let Some(c) = name.chars().find(|&c| !is_ident_continue(c)) {
+            return TokenKind::Error(LexError::InvalidIdentifierChar(c));
+        }
+        TokenKind::RawIdentifier(name)
+    }
+
+    /// Parses a string literal. The opening quote is next.
+    fn take_string(&mut self) -> TokenKind<'a> {
+        self.advance();
+        let start = self.absolute_pos;
+        while self.peek().is_some_and(|&c| c != '"' && c != '\n') {
+            self.advance();
         }
+        let text = &self.source[start..self.absolute_pos];
+        if self.peek() != Some(&'"') {
+            return TokenKind::Error(LexError::UnterminatedString);
+        }
+        self.advance();
+        TokenKind::LiteralString(text)
     }
 
     /// Parses an integer literal.
-    fn take_number(&mut self) -> TokenKind {
+    fn take_number(&mut self) -> TokenKind<'a> {
         let start = self.absolute_pos;
         while let Some(&c) = self.peek() {
             if c.is_ascii_digit() {
@@ -216,15 +272,84 @@ impl<'a> Lexer<'a> {
         let end = self.absolute_pos;
         let num_str = &self.source[start..end];
 
-        // Safely parse the number; use unwrap_or_else for a clean error token if parse fails.
-        // Since we only checked for digits, this parse should typically succeed unless overflow occurs.
+        // Since we only checked for digits, this parse can only fail on overflow.
         match num_str.parse::<u64>() {
             Ok(val) => TokenKind::LiteralInt(val),
-            Err(_) => TokenKind::Error(format!("Invalid or oversized integer literal: {num_str}")),
+            Err(_) => TokenKind::Error(LexError::IntegerOverflow),
         }
     }
 }
 
+/// Returns the diagnostic for a [`TokenKind::Error`] token, or `None` for any other token.
+pub(crate) fn token_error(source: &str, token: &Token) -> Option<Diagnostic> {
+    match token.kind {
+        TokenKind::Error(error) => Some(
+            Diagnostic::error(error.message(&source[token.span.start..token.span.end]))
+                .with_code(codes::INVALID_TOKEN)
+                .with_span(token.span)
+                .with_source(source),
+        ),
+        _ => None,
+    }
+}
+
+/// Returns true if `c` can start an identifier.
+///
+/// Identifiers are emitted verbatim as type, field, and variant names in every target language,
+/// so they are limited to the characters all targets accept: an ASCII letter or `_`, followed by
+/// ASCII letters, digits, or `_`.
+pub fn is_ident_start(c: char) -> bool {
+    c.is_ascii_alphabetic() || c == '_'
+}
+
+/// Returns true if `c` can appear after the first character of an identifier.
+pub fn is_ident_continue(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '_'
+}
+
+/// Returns true if `c` belongs to a word, which is lexed as a unit and then checked against the
+/// identifier policy.
+pub(crate) fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+/// Describes a character for a diagnostic, including its code point when it is not plain ASCII
+/// (e.g. a non-breaking space, which looks like an ordinary one).
+fn describe_char(c: char) -> String {
+    if c.is_ascii_graphic() {
+        format!("character '{c}'")
+    } else if c.is_control() || c.is_whitespace() {
+        format!("character U+{:04X}", c as u32)
+    } else {
+        format!("non-ASCII character '{c}' (U+{:04X})", c as u32)
+    }
+}
+
+/// Returns the token kind for a reserved keyword or primitive type name, if `ident` is one.
+pub(crate) fn keyword(ident: &str) -> Option<TokenKind<'static>> {
+    let kind = match ident {
+        "import" => TokenKind::Import,
+        "endian" => TokenKind::Endianness,
+        "message" => TokenKind::Message,
+        "struct" => TokenKind::Struct,
+        "enum" => TokenKind::Enum,
+        "uuid" => TokenKind::Uuid,
+        "bool" => TokenKind::Primitive(PrimitiveType::Bool),
+        "u8" => TokenKind::Primitive(PrimitiveType::U8),
+        "u16" => TokenKind::Primitive(PrimitiveType::U16),
+        "u32" => TokenKind::Primitive(PrimitiveType::U32),
+        "u64" => TokenKind::Primitive(PrimitiveType::U64),
+        "i8" => TokenKind::Primitive(PrimitiveType::I8),
+        "i16" => TokenKind::Primitive(PrimitiveType::I16),
+        "i32" => TokenKind::Primitive(PrimitiveType::I32),
+        "i64" => TokenKind::Primitive(PrimitiveType::I64),
+        "f32" => TokenKind::Primitive(PrimitiveType::F32),
+        "f64" => TokenKind::Primitive(PrimitiveType::F64),
+        _ => return None,
+    };
+    Some(kind)
+}
+
 impl<'a> From<&'a str> for Lexer<'a> {
     fn from(source: &'a str) -> Self {
         Lexer::new(source)
@@ -232,14 +357,13 @@ impl<'a> From<&'a str> for Lexer<'a> {
 }
 
 impl<'a> Iterator for Lexer<'a> {
-    type Item = Token;
+    type Item = Token<'a>;
 
     fn next(&mut self) -> Option<Self::Item> {
         // 1. Skip whitespace before processing the next token
         self.skip_whitespace();
 
         let start_pos = self.absolute_pos;
-        let start_col = self.current_col;
         let kind = match self.peek() {
             Some('{') => {
                 self.advance();
@@ -261,13 +385,95 @@ impl<'a> Iterator for Lexer<'a> {
                 self.advance();
                 TokenKind::Semicolon
             }
+            Some('<') => {
+                self.advance();
+                if self.peek() == Some(&'<') {
+                    self.advance();
+                    TokenKind::ShiftLeft
+                } else {
+                    TokenKind::OpenAngle
+                }
+            }
+            Some('>') => {
+                self.advance();
+                if self.peek() == Some(&'>') {
+                    self.advance();
+                    TokenKind::ShiftRight
+                } else {
+                    TokenKind::CloseAngle
+                }
+            }
+            Some('[') => {
+                self.advance();
+                TokenKind::OpenBracket
+            }
+            Some(']') => {
+                self.advance();
+                TokenKind::CloseBracket
+            }
+            Some('(') => {
+                self.advance();
+                TokenKind::OpenParen
+            }
+            Some(')') => {
+                self.advance();
+                TokenKind::CloseParen
+            }
             Some('=') => {
                 self.advance();
-                TokenKind::Assign
+                if self.peek() == Some(&'=') {
+                    self.advance();
+                    TokenKind::Equal
+                } else {
+                    TokenKind::Assign
+                }
+            }
+            Some('!') => {
+                self.advance();
+                if self.peek() == Some(&'=') {
+                    self.advance();
+                    TokenKind::NotEqual
+                } else {
+                    TokenKind::Error(LexError::UnrecognizedChar('!'))
+                }
+            }
+            Some('.') => {
+                self.advance();
+                TokenKind::Dot
+            }
+            Some('@') => {
+                self.advance();
+                TokenKind::At
+            }
+            Some('+') => {
+                self.advance();
+                TokenKind::Plus
+            }
+            Some('-') => {
+                self.advance();
+                TokenKind::Minus
+            }
+            Some('*') => {
+                self.advance();
+                TokenKind::Star
+            }
+            Some('/') => {
+                self.advance();
+                TokenKind::Slash
+            }
+            Some('%') => {
+                self.advance();
+                TokenKind::Percent
             }
 
             // Handle identifiers/keywords
-            Some(c) if c.is_ascii_alphabetic() || *c == '_' => self.take_identifier(),
+            // Handle identifiers/keywords. Other letters start a word too, so that non-ASCII
+            // names are reported against the identifier policy.
+            Some(&c) if is_ident_start(c) || c.is_alphabetic() => self.take_identifier(),
+
+            Some('`') => self.take_raw_identifier(),
+
+            Some('"') => self.take_string(),
 
             // Handle numbers
             Some(c) if c.is_ascii_digit() => self.take_number(),
@@ -279,13 +485,7 @@ impl<'a> Iterator for Lexer<'a> {
                     // Should only happen if there was trailing whitespace
                     return Some(Token {
                         kind: TokenKind::Eof,
-                        position: Position {
-                            line: self.current_line,
-                            span: Span {
-                                start: start_pos,
-                                end: start_pos,
-                            },
-                        },
+                        span: Span::new(start_pos, start_pos),
                     });
                 }
                 return None;
@@ -295,22 +495,82 @@ impl<'a> Iterator for Lexer<'a> {
             Some(c) => {
                 let char = *c;
                 self.advance();
-                TokenKind::Error(format!("Unrecognized character: '{char}'"))
+                TokenKind::Error(LexError::UnrecognizedChar(char))
             }
         };
 
-        let end_col = self.current_col;
+        let span = Span::new(start_pos, self.absolute_pos);
+        trace::trace!(kind = ?kind, start = span.start, end = span.end, "lexed token");
 
-        Some(Token {
-            kind,
-            position: Position {
-                line: self.current_line,
-                span: Span {
-                    start: start_col,
-                    end: end_col,
-                },
-            },
-        })
+        Some(Token { kind, span })
+    }
+}
+
+/// A stream of tokens with arbitrary lookahead and backtracking, lexed on demand.
+///
+/// Tokens are kept once lexed, so the stream can look ahead of where it is and return to a
+/// [`Checkpoint`] it passed.
+pub struct TokenStream<'a> {
+    lexer: Lexer<'a>,
+    /// The tokens lexed so far.
+    tokens: Vec<Token<'a>>,
+    /// The index in `tokens` of the next token.
+    position: usize,
+}
+
+/// A position in a [`TokenStream`], to return to with [`TokenStream::rewind`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Checkpoint(usize);
+
+impl<'a> TokenStream<'a> {
+    /// Creates a stream of the tokens of `source`.
+    pub fn new(source: &'a str) -> Self {
+        TokenStream {
+            lexer: Lexer::new(source),
+            tokens: Vec::new(),
+            position: 0,
+        }
+    }
+
+    /// Returns the next token without consuming it, or `None` at the end of the source.
+    pub fn peek(&mut self) -> Option<Token<'a>> {
+        self.peek_nth(0)
+    }
+
+    /// Returns the token `n` places after the next one without consuming anything, or `None`
+    /// if the source ends before it. `peek_nth(0)` is the next token.
+    pub fn peek_nth(&mut self, n: usize) -> Option<Token<'a>> {
+        let index = self.position + n;
+        while self.tokens.len() <= index {
+            self.tokens.push(self.lexer.next()?);
+        }
+        Some(self.tokens[index])
+    }
+
+    /// Returns the current position, to rewind to later.
+    pub fn checkpoint(&self) -> Checkpoint {
+        Checkpoint(self.position)
+    }
+
+    /// Returns to `checkpoint`, so that the tokens consumed since are read again.
+    pub fn rewind(&mut self, checkpoint: Checkpoint) {
+        self.position = checkpoint.0;
+    }
+}
+
+impl<'a> From<&'a str> for TokenStream<'a> {
+    fn from(source: &'a str) -> Self {
+        TokenStream::new(source)
+    }
+}
+
+impl<'a> Iterator for TokenStream<'a> {
+    type Item = Token<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let token = self.peek()?;
+        self.position += 1;
+        Some(token)
     }
 }
 
@@ -320,7 +580,7 @@ mod tests {
 
     #[test]
     fn test_keywords_and_symbols() {
-        let source = "struct message enum import endian { } , : ; =";
+        let source = "struct message enum import endian uuid { } , : ; = < > [ ] @ ( ) == != . + - * / % << >>";
         let mut lexer = Lexer::new(source);
 
         assert_eq!(lexer.next().unwrap().kind, TokenKind::Struct);
@@ -328,12 +588,30 @@ mod tests {
         assert_eq!(lexer.next().unwrap().kind, TokenKind::Enum);
         assert_eq!(lexer.next().unwrap().kind, TokenKind::Import);
         assert_eq!(lexer.next().unwrap().kind, TokenKind::Endianness);
+        assert_eq!(lexer.next().unwrap().kind, TokenKind::Uuid);
         assert_eq!(lexer.next().unwrap().kind, TokenKind::OpenBrace);
         assert_eq!(lexer.next().unwrap().kind, TokenKind::CloseBrace);
         assert_eq!(lexer.next().unwrap().kind, TokenKind::Comma);
         assert_eq!(lexer.next().unwrap().kind, TokenKind::Colon);
         assert_eq!(lexer.next().unwrap().kind, TokenKind::Semicolon);
         assert_eq!(lexer.next().unwrap().kind, TokenKind::Assign);
+        assert_eq!(lexer.next().unwrap().kind, TokenKind::OpenAngle);
+        assert_eq!(lexer.next().unwrap().kind, TokenKind::CloseAngle);
+        assert_eq!(lexer.next().unwrap().kind, TokenKind::OpenBracket);
+        assert_eq!(lexer.next().unwrap().kind, TokenKind::CloseBracket);
+        assert_eq!(lexer.next().unwrap().kind, TokenKind::At);
+        assert_eq!(lexer.next().unwrap().kind, TokenKind::OpenParen);
+        assert_eq!(lexer.next().unwrap().kind, TokenKind::CloseParen);
+        assert_eq!(lexer.next().unwrap().kind, TokenKind::Equal);
+        assert_eq!(lexer.next().unwrap().kind, TokenKind::NotEqual);
+        assert_eq!(lexer.next().unwrap().kind, TokenKind::Dot);
+        assert_eq!(lexer.next().unwrap().kind, TokenKind::Plus);
+        assert_eq!(lexer.next().unwrap().kind, TokenKind::Minus);
+        assert_eq!(lexer.next().unwrap().kind, TokenKind::Star);
+        assert_eq!(lexer.next().unwrap().kind, TokenKind::Slash);
+        assert_eq!(lexer.next().unwrap().kind, TokenKind::Percent);
+        assert_eq!(lexer.next().unwrap().kind, TokenKind::ShiftLeft);
+        assert_eq!(lexer.next().unwrap().kind, TokenKind::ShiftRight);
     }
 
     #[test]
@@ -404,30 +682,155 @@ mod tests {
         assert_eq!(lexer.next().unwrap().kind, TokenKind::LiteralInt(456));
     }
 
+    #[test]
+    fn test_token_stream() {
+        let mut tokens = TokenStream::new("a b c");
+        assert_eq!(tokens.peek_nth(2).unwrap().kind, TokenKind::Identifier("c"));
+        assert_eq!(tokens.peek_nth(3), None);
+
+        assert_eq!(tokens.next().unwrap().kind, TokenKind::Identifier("a"));
+        let after_a = tokens.checkpoint();
+        assert_eq!(tokens.next().unwrap().kind, TokenKind::Identifier("b"));
+        assert_eq!(tokens.next().unwrap().kind, TokenKind::Identifier("c"));
+        assert_eq!(tokens.next(), None);
+
+        tokens.rewind(after_a);
+        assert_eq!(tokens.peek().unwrap().kind, TokenKind::Identifier("b"));
+        assert_eq!(tokens.peek().unwrap().span, Span::new(2, 3));
+    }
+
     #[test]
     fn test_whitespace_and_position() {
         let source = "a\n  b";
         let mut lexer = Lexer::new(source);
 
         let token_a = lexer.next().unwrap();
-        assert_eq!(token_a.kind, TokenKind::Identifier("a".to_string()));
-        assert_eq!(token_a.position.line, 0);
-        assert_eq!(token_a.position.span.start, 0);
+        assert_eq!(token_a.kind, TokenKind::Identifier("a"));
+        assert_eq!(token_a.span, Span::new(0, 1));
 
         let token_b = lexer.next().unwrap();
-        assert_eq!(token_b.kind, TokenKind::Identifier("b".to_string()));
-        assert_eq!(token_b.position.line, 1);
-        assert_eq!(token_b.position.span.start, 2);
+        assert_eq!(token_b.kind, TokenKind::Identifier("b"));
+        assert_eq!(token_b.span, Span::new(4, 5));
+    }
+
+    #[test]
+    fn test_line_comments() {
+        let source = "// leading comment\nstruct // trailing\n  S";
+        let mut lexer = Lexer::new(source);
+
+        let token = lexer.next().unwrap();
+        assert_eq!(token.kind, TokenKind::Struct);
+        assert_eq!(token.span, Span::new(19, 25));
+
+        let token = lexer.next().unwrap();
+        assert_eq!(token.kind, TokenKind::Identifier("S"));
+        assert_eq!(token.span, Span::new(40, 41));
+    }
+
+    #[test]
+    fn test_spans_are_byte_offsets() {
+        let source = "// héllo → wörld\nstruct";
+        let token = Lexer::new(source).next().unwrap();
+
+        assert_eq!(token.kind, TokenKind::Struct);
+        assert_eq!(&source[token.span.start..token.span.end], "struct");
     }
 
     #[test]
     fn test_error_handling() {
-        let source = "@";
+        let source = "#";
         let mut lexer = Lexer::new(source);
 
-        match lexer.next().unwrap().kind {
-            TokenKind::Error(msg) => assert!(msg.contains("Unrecognized character")),
-            _ => panic!("Expected error token"),
+        assert_eq!(
+            lexer.next().unwrap().kind,
+            TokenKind::Error(LexError::UnrecognizedChar('#'))
+        );
+        assert_eq!(
+            LexError::UnrecognizedChar('#').message("#"),
+            "unrecognized character '#'"
+        );
+        assert_eq!(
+            LexError::UnrecognizedChar('\u{a0}').message("\u{a0}"),
+            "unrecognized character U+00A0"
+        );
+    }
+
+    #[test]
+    fn test_identifier_policy() {
+        let source = "\u{feff}café _ok\u{a0}x";
+        let mut lexer = Lexer::new(source);
+
+        let token = lexer.next().unwrap();
+        assert_eq!(
+            token.kind,
+            TokenKind::Error(LexError::InvalidIdentifierChar('é'))
+        );
+        let text = &source[token.span.start..token.span.end];
+        assert_eq!(text, "café");
+        assert!(
+            LexError::InvalidIdentifierChar('é')
+                .message(text)
+                .starts_with("identifier 'café' contains non-ASCII character 'é' (U+00E9)")
+        );
+
+        assert_eq!(lexer.next().unwrap().kind, TokenKind::Identifier("_ok"));
+        assert_eq!(
+            lexer.next().unwrap().kind,
+            TokenKind::Error(LexError::UnrecognizedChar('\u{a0}'))
+        );
+        assert_eq!(lexer.next().unwrap().kind, TokenKind::Identifier("x"));
+    }
+
+    #[test]
+    fn test_integer_overflow() {
+        let mut lexer = Lexer::new("18446744073709551615 18446744073709551616");
+
+        assert_eq!(lexer.next().unwrap().kind, TokenKind::LiteralInt(u64::MAX));
+        assert_eq!(
+            lexer.next().unwrap().kind,
+            TokenKind::Error(LexError::IntegerOverflow)
+        );
+    }
+
+    #[test]
+    fn test_raw_identifiers() {
+        let mut lexer = Lexer::new("`struct` `u8`x `` `1a` `caf\u{e9}` `open");
+
+        let token = lexer.next().unwrap();
+        assert_eq!(token.kind, TokenKind::RawIdentifier("struct"));
+        assert_eq!(token.span, Span::new(0, 8));
+        assert_eq!(lexer.next().unwrap().kind, TokenKind::RawIdentifier("u8"));
+        assert_eq!(lexer.next().unwrap().kind, TokenKind::Identifier("x"));
+        for _ in 0..2 {
+            assert_eq!(
+                lexer.next().unwrap().kind,
+                TokenKind::Error(LexError::InvalidRawIdentifier)
+            );
         }
+        assert_eq!(
+            lexer.next().unwrap().kind,
+            TokenKind::Error(LexError::InvalidIdentifierChar('\u{e9}'))
+        );
+        assert_eq!(
+            lexer.next().unwrap().kind,
+            TokenKind::Error(LexError::InvalidRawIdentifier)
+        );
+    }
+
+    #[test]
+    fn test_string_literals() {
+        let mut lexer = Lexer::new("\"use v2\" \"\" \"open\nx");
+
+        let token = lexer.next().unwrap();
+        assert_eq!(token.kind, TokenKind::LiteralString("use v2"));
+        assert_eq!(token.span, Span::new(0, 8));
+        assert_eq!(lexer.next().unwrap().kind, TokenKind::LiteralString(""));
+        let token = lexer.next().unwrap();
+        assert_eq!(token.kind, TokenKind::Error(LexError::UnterminatedString));
+        assert_eq!(
+            LexError::UnterminatedString.message("\"open"),
+            "string literal \"open is missing its closing quote"
+        );
+        assert_eq!(lexer.next().unwrap().kind, TokenKind::Identifier("x"));
     }
 }
@@ -9,17 +9,62 @@
 //!
 //! This library is composed of several modules that work together to compile Onyx source files:
 //!
-//! - **Lexer** (`lexer`): Tokenizes the input Onyx source code into a stream of tokens.
+//! - **Lexer** (`lexer`, unstable): Tokenizes the input Onyx source code into a stream of tokens.
 //! - **Parser** (`parser`): Consumes tokens to build an Abstract Syntax Tree (AST), validating syntax and resolving types.
+//! - **CST** (`cst`, unstable): A lossless syntax tree preserving whitespace and comments, for tools that edit source.
 //! - **AST** (`ast`): Defines the internal representation of the parsed code (Definitions, Fields, Types).
-//! - **Generators** (`generators`): Takes the AST and generates code for target languages (e.g., C++).
+//! - **HIR** (`hir`): Lays out a resolved module, giving every field its resolved type, offset, and width.
+//! - **Resolve** (`resolve`): Resolves modules built in code and answers size and offset queries without generating code.
+//! - **Compiler** (`compiler`): Compiles many files as one module, resolving types across files.
+//! - **Workspace** (`workspace`): Holds several named modules that use each other's types, each generated on its own.
+//! - **Generators** (`generators`): Takes the AST and generates code for target languages (C++, Rust, Kotlin, SystemVerilog, and MATLAB) or column schemas (Arrow), renders a user-supplied template, or runs an external plugin program.
+//! - **Dynamic** (`dynamic`): Encodes and decodes values by walking a schema at runtime, and reads and writes single fields of a buffer by name; the reference for generated code.
+//! - **Wire** (`wire`): Reads and writes integers and bit ranges in either endianness, as the wire format stores them.
+//! - **Vectors** (`vectors`): Builds canonical test vectors, values with their expected wire bytes, for checking backends against each other.
+//! - **Inspect** (`inspect`): Decodes a buffer field by field, with offsets and raw bytes, for debugging.
+//! - **Features** (`features`): Reports which IDL features a module uses, for checking them against each backend.
+//! - **Fingerprint** (`fingerprint`): Hashes definitions and modules so peers can check they were built from the same schema.
+//! - **MAVLink** (`mavlink`): Imports MAVLink XML message definitions as a module with the same wire layout.
+//! - **Obfuscate** (`obfuscate`): Renames a schema to opaque names for sharing with external partners.
+//! - **Limits** (`limits`): Enforces caps on message size, nesting depth, and field count for fixed buffer budgets, and on the work parsing does for untrusted schemas.
+//! - **Prune** (`prune`): Finds structs and enums no entry point uses, and removes them before generating.
+//! - **Lint** (`lint`): Checks a parsed module against configurable style and layout rules.
+//! - **Build Rules** (`build_rules`): Emits Bazel and Buck rules that run `onyxc` and wrap its outputs.
+//! - **Diagnostics** (`diagnostic`): Structured errors and warnings with codes, notes, and source excerpts.
+//! - **Testing** (`testing`): Finds toolchains and runs commands for tests that compile generated code.
 //!
 //! ## Usage
 //!
 //! The typical compilation flow is:
-//! 1.  **Lexing**: `Lexer::new(source)` creates a token stream.
-//! 2.  **Parsing**: `Parser::new(source)?.parse_module()?` creates a resolved `OnyxModule`.
-//! 3.  **Generation**: A `CodeGenerator` (like `CppGenerator`) takes the `OnyxModule` and produces output files.
+//! 1.  **Parsing**: `Parser::new(source)?.parse_module()?` lexes the source and creates a resolved `OnyxModule`.
+//! 2.  **Generation**: A `CodeGenerator` (like `CppGenerator`) takes the `OnyxModule` and produces output files.
+//!
+//! ## API Stability
+//!
+//! The `ast`, `compiler`, `diagnostic`, `dynamic`, `features`, `fingerprint`, `hir`, `inspect`, `limits`, `parser`, `generators`, `lint`, `mavlink`, `obfuscate`, `prune`, `resolve`, `testing`, `vectors`, `wire`, and `workspace` modules, along with [`Span`],
+//! [`Position`], [`LineIndex`], [`Symbol`], and [`ColorChoice`], form the stable API and follow semantic versioning:
+//!
+//! - AST nodes, diagnostics, and configuration types are `#[non_exhaustive]`, so new fields and
+//!   variants can be added in minor releases. Construct AST nodes with their `new` functions.
+//! - Extension traits such as [`ast::AstNode`] and [`generators::CodeGeneratorExt`] are sealed,
+//!   so methods can be added to them without breaking downstream code.
+//! - [`generators::CodeGenerator`] is the supported extension point for custom backends.
+//!
+//! Modules that are still evolving (`lexer`, `cst`, and the Python generator) are only public
+//! with the `unstable-api` feature enabled and may change in any release.
+//!
+//! ## Cargo Features
+//!
+//! - `capi`: Exports a C API (the `capi` module, header in `include/onyx.h`) for driving the
+//!   compiler in-process from other languages.
+//! - `parallel`: Parses files and runs generators concurrently in [`compiler::Compiler`],
+//!   using rayon.
+//! - `pyo3`: Builds the `onyx` Python extension module (the `python` module); see
+//!   `pyproject.toml`.
+//! - `tracing`: Emits `tracing` spans and events for each compiler phase.
+//! - `unstable-api`: Exposes the `lexer`, `cst`, and Python generator modules.
+//! - `wasm`: Adds `wasm_bindgen` bindings (the `wasm` module) for running the compiler in the
+//!   browser; build with `--target wasm32-unknown-unknown`.
 //!
 //! ## Example
 //!
@@ -52,8 +97,52 @@
 //! ```
 
 pub mod ast;
+pub mod build_rules;
+pub mod compiler;
+pub mod diagnostic;
+pub mod dynamic;
+pub mod features;
+pub mod fingerprint;
 pub mod generators;
-pub mod lexer;
+pub mod hir;
+pub mod inspect;
+pub mod limits;
+pub mod lint;
+pub mod mavlink;
+pub mod obfuscate;
 pub mod parser;
+pub mod prune;
+pub mod resolve;
+pub mod testing;
+pub mod vectors;
+pub mod wire;
+pub mod workspace;
+
+#[cfg(feature = "unstable-api")]
+pub mod cst;
+#[cfg(not(feature = "unstable-api"))]
+#[allow(dead_code)]
+mod cst;
+
+#[cfg(feature = "unstable-api")]
+pub mod lexer;
+#[cfg(not(feature = "unstable-api"))]
+#[allow(dead_code)]
+mod lexer;
+
+pub use color::ColorChoice;
+pub use span::{LineIndex, Position, Span};
+pub use symbol::Symbol;
+
+#[cfg(feature = "capi")]
+pub mod capi;
+#[cfg(feature = "pyo3")]
+pub mod python;
+#[cfg(feature = "wasm")]
+pub mod wasm;
 
 mod color;
+mod json;
+mod span;
+mod symbol;
+mod trace;
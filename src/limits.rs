@@ -0,0 +1,247 @@
+//! Resource limits a module must fit within.
+//!
+//! Consumers with fixed buffer budgets, such as embedded targets, can cap the size of messages,
+//! how deeply structs nest, and how many fields a definition has. [`check`] reports every
+//! definition over a limit; [`Compiler::set_limits`](crate::compiler::Compiler::set_limits)
+//! runs it before generating code.
+//!
+//! Services that parse schemas from untrusted sources can also cap the work parsing does with
+//! [`ParseLimits`], given to [`Parser::with_limits`](crate::parser::Parser::with_limits).
+//! Names are interned in a table that lives as long as the process, so a long-running service
+//! also needs `max_symbol_bytes` to bound the memory that parses add up to over time.
+//!
+//! ```rust
+//! use onyx::limits::{self, Limits};
+//! use onyx::parser::Parser;
+//!
+//! let module = Parser::new("message Frame { payload bytes[512], }")
+//!     .and_then(|p| p.parse_module())
+//!     .unwrap();
+//! let mut limits = Limits::default();
+//! limits.max_message_bytes = Some(256);
+//!
+//! let errors = limits::check(&module, &limits).unwrap_err();
+//! assert_eq!(
+//!     errors[0].1.message(),
+//!     "message 'Frame' is 512 bytes, over the limit of 256"
+//! );
+//! ```
+
+use std::collections::HashMap;
+
+use crate::{
+    ast::{Definition, OnyxModule, Type},
+    diagnostic::{Diagnostic, codes},
+    hir::{self, Hir},
+    symbol::Symbol,
+};
+
+/// The limits to enforce. Each is unlimited when `None`, as all are by default.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub struct Limits {
+    /// The largest size of a message in bytes.
+    pub max_message_bytes: Option<usize>,
+    /// The deepest nesting of structs and messages, counting the outermost: a definition whose
+    /// fields are all primitives has depth 1, and one that embeds it has depth 2.
+    pub max_nesting_depth: Option<usize>,
+    /// The most fields a struct or message may have.
+    pub max_fields: Option<usize>,
+}
+
+/// Limits on the schema text a [`Parser`](crate::parser::Parser) accepts. Each is unlimited
+/// when `None`, as all but `max_version` are by default.
+///
+/// Whatever the limits, the parser reports errors rather than panicking, and caps nesting at a
+/// depth that cannot exhaust its stack.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub struct ParseLimits {
+    /// The largest schema in bytes.
+    pub max_source_bytes: Option<usize>,
+    /// The most tokens the parser reads, not counting whitespace and comments.
+    pub max_tokens: Option<usize>,
+    /// The deepest nesting of parentheses in expressions and `@cfg` predicates, and of struct
+    /// and message definitions within each other.
+    pub max_depth: Option<usize>,
+    /// The most definitions a schema may have.
+    pub max_definitions: Option<usize>,
+    /// The highest `version` a schema may declare, 1024 by default. Parsing lays out the schema
+    /// at every version up to it, and generators write code for each.
+    pub max_version: Option<u32>,
+    /// The most bytes of names the process-wide [`Symbol`] table may hold. A parse that would
+    /// intern a new name past it fails, while names already in the table are still accepted;
+    /// the table is never freed, so the limit holds across every parse in the process.
+    pub max_symbol_bytes: Option<usize>,
+}
+
+impl Default for ParseLimits {
+    fn default() -> Self {
+        ParseLimits {
+            max_source_bytes: None,
+            max_tokens: None,
+            max_depth: None,
+            max_definitions: None,
+            max_version: Some(1024),
+            max_symbol_bytes: None,
+        }
+    }
+}
+
+impl ParseLimits {
+    /// Returns limits for schemas uploaded by users: 64 KiB of text, 16384 tokens, nesting 32
+    /// deep, 256 definitions, version 256, and 16 MiB of interned names in the process.
+    ///
+    /// ```rust
+    /// use onyx::limits::ParseLimits;
+    /// use onyx::parser::Parser;
+    ///
+    /// let source = format!("const N = {}1{}\nstruct S {{ b bytes[N], }}", "(".repeat(40), ")".repeat(40));
+    /// let error = Parser::new(&source)
+    ///     .unwrap()
+    ///     .with_limits(ParseLimits::untrusted())
+    ///     .parse_module()
+    ///     .unwrap_err();
+    /// assert_eq!(error.message(), "expression nests more than 32 deep");
+    /// ```
+    pub fn untrusted() -> Self {
+        ParseLimits {
+            max_source_bytes: Some(64 * 1024),
+            max_tokens: Some(16 * 1024),
+            max_depth: Some(32),
+            max_definitions: Some(256),
+            max_version: Some(256),
+            max_symbol_bytes: Some(16 * 1024 * 1024),
+        }
+    }
+}
+
+/// Checks every struct and message of `module` against `limits`.
+///
+/// Each error is paired with the definition over the limit, in source order.
+pub fn check(module: &OnyxModule, limits: &Limits) -> Result<(), Vec<(Symbol, Diagnostic)>> {
+    let hir = hir::lower_module(module).map_err(|error| vec![error])?;
+    let mut definitions: Vec<&Definition> = module.definitions.values().collect();
+    definitions.sort_by(|a, b| (a.span().start, a.name()).cmp(&(b.span().start, b.name())));
+
+    let mut depths = HashMap::new();
+    let mut errors = Vec::new();
+    for def in definitions {
+        let Some(layout) = hir.layout(def.symbol()) else {
+            continue;
+        };
+        let kind = match def {
+            Definition::Message(_) => "message",
+            _ => "struct",
+        };
+        let mut exceeded = |message: String, help: &str| {
+            errors.push((
+                def.symbol(),
+                Diagnostic::error(message)
+                    .with_code(codes::LIMIT_EXCEEDED)
+                    .with_span(def.span())
+                    .with_help(help.to_string()),
+            ));
+        };
+
+        if let (Definition::Message(_), Some(max)) = (def, limits.max_message_bytes)
+            && layout.size > max
+        {
+            exceeded(
+                format!(
+                    "message '{}' is {} bytes, over the limit of {max}",
+                    def.name(),
+                    layout.size
+                ),
+                "shrink its vectors and byte arrays, or split it into several messages",
+            );
+        }
+        let depth = nesting_depth(&hir, def.symbol(), &mut depths);
+        if let Some(max) = limits.max_nesting_depth
+            && depth > max
+        {
+            exceeded(
+                format!(
+                    "{kind} '{}' nests {depth} deep, over the limit of {max}",
+                    def.name()
+                ),
+                "embed the fields of an inner struct directly",
+            );
+        }
+        let fields = layout.fields().count();
+        if let Some(max) = limits.max_fields
+            && fields > max
+        {
+            exceeded(
+                format!(
+                    "{kind} '{}' has {fields} fields, over the limit of {max}",
+                    def.name()
+                ),
+                "group related fields into a struct",
+            );
+        }
+    }
+    match errors.is_empty() {
+        true => Ok(()),
+        false => Err(errors),
+    }
+}
+
+/// Returns the nesting depth of the struct or message `name`, memoized in `depths`.
+fn nesting_depth(hir: &Hir, name: Symbol, depths: &mut HashMap<Symbol, usize>) -> usize {
+    if let Some(&depth) = depths.get(&name) {
+        return depth;
+    }
+    let Some(layout) = hir.layout(name) else {
+        return 0;
+    };
+    let inner = layout
+        .fields()
+        .filter_map(|field| match field.type_info.element_type() {
+            Type::Custom(used) => Some(*used),
+            _ => None,
+        })
+        .map(|used| nesting_depth(hir, used, depths))
+        .max()
+        .unwrap_or(0);
+    depths.insert(name, inner + 1);
+    inner + 1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::Parser;
+
+    #[test]
+    fn test_limits() {
+        let module = Parser::new(
+            "struct Point { x i32, y i32, }
+             struct Box { min Point, max Point, }
+             message Shape { corners vec<Box, 4>, id u16, name bytes[8], }",
+        )
+        .and_then(|p| p.parse_module())
+        .unwrap();
+        assert_eq!(check(&module, &Limits::default()), Ok(()));
+
+        let limits = Limits {
+            max_message_bytes: Some(64),
+            max_nesting_depth: Some(2),
+            max_fields: Some(2),
+        };
+        let errors = check(&module, &limits).unwrap_err();
+        let messages: Vec<(&str, &str)> = errors
+            .iter()
+            .map(|(name, error)| (name.as_str(), error.message()))
+            .collect();
+        assert_eq!(
+            messages,
+            [
+                ("Shape", "message 'Shape' is 75 bytes, over the limit of 64"),
+                ("Shape", "message 'Shape' nests 3 deep, over the limit of 2"),
+                ("Shape", "message 'Shape' has 3 fields, over the limit of 2"),
+            ]
+        );
+        assert_eq!(errors[0].1.code(), Some(codes::LIMIT_EXCEEDED));
+    }
+}
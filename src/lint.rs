@@ -0,0 +1,504 @@
+use std::{collections::HashMap, fmt};
+
+pub use crate::diagnostic::Severity;
+use crate::{
+    ast::{Definition, Field, OnyxModule, Type},
+    diagnostic::Diagnostic,
+    prune,
+    span::Span,
+    symbol::Symbol,
+};
+
+/// How a lint rule is reported.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum LintLevel {
+    /// The rule is disabled.
+    Allow,
+    /// Violations are reported as warnings.
+    Warn,
+    /// Violations are reported as errors.
+    Deny,
+}
+
+/// The lint rules that can be run over a module.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum LintRule {
+    /// Type names (messages, structs, enums) and enum variants should be PascalCase.
+    TypeNaming,
+    /// Field names should be snake_case.
+    FieldNaming,
+    /// Messages should not exceed the configured maximum size.
+    OversizedMessage,
+    /// Structs should be referenced by at least one field.
+    UnusedStruct,
+    /// Bit-field groups should pack into a 1, 2, 4, or 8 byte container.
+    NonPowerOfTwoBitField,
+    /// Fields should not use types marked `@deprecated`, unless the field or the definition
+    /// holding it is deprecated too.
+    DeprecatedUse,
+    /// Structs, enums, and messages should be used by an entry point; see [`crate::prune`].
+    DeadDefinition,
+}
+
+impl LintRule {
+    /// All available rules, in the order they are run.
+    pub const ALL: [LintRule; 7] = [
+        LintRule::TypeNaming,
+        LintRule::FieldNaming,
+        LintRule::OversizedMessage,
+        LintRule::UnusedStruct,
+        LintRule::NonPowerOfTwoBitField,
+        LintRule::DeprecatedUse,
+        LintRule::DeadDefinition,
+    ];
+
+    /// Returns the kebab-case name used to refer to the rule on the command line.
+    pub fn name(&self) -> &'static str {
+        match self {
+            LintRule::TypeNaming => "type-naming",
+            LintRule::FieldNaming => "field-naming",
+            LintRule::OversizedMessage => "oversized-message",
+            LintRule::UnusedStruct => "unused-struct",
+            LintRule::NonPowerOfTwoBitField => "non-power-of-two-bit-field",
+            LintRule::DeprecatedUse => "deprecated-use",
+            LintRule::DeadDefinition => "dead-definition",
+        }
+    }
+
+    /// Looks up a rule by its kebab-case name.
+    pub fn from_name(name: &str) -> Option<LintRule> {
+        Self::ALL.into_iter().find(|rule| rule.name() == name)
+    }
+}
+
+impl fmt::Display for LintRule {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+/// Configuration for the lint pass.
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct LintConfig {
+    /// Per-rule level overrides. Rules without an entry default to `LintLevel::Warn`.
+    pub levels: HashMap<LintRule, LintLevel>,
+    /// The largest message size in bytes accepted by `LintRule::OversizedMessage`.
+    pub max_message_bytes: usize,
+    /// The definitions `LintRule::DeadDefinition` starts from. When empty, every message is an
+    /// entry point; names that are not defined are ignored.
+    pub entry_points: Vec<Symbol>,
+}
+
+impl Default for LintConfig {
+    fn default() -> Self {
+        LintConfig {
+            levels: HashMap::new(),
+            max_message_bytes: 1024,
+            entry_points: Vec::new(),
+        }
+    }
+}
+
+impl LintConfig {
+    /// Returns the configured level for a rule.
+    pub fn level(&self, rule: LintRule) -> LintLevel {
+        self.levels.get(&rule).copied().unwrap_or(LintLevel::Warn)
+    }
+
+    /// Sets the level for a rule.
+    pub fn set_level(&mut self, rule: LintRule, level: LintLevel) {
+        self.levels.insert(rule, level);
+    }
+}
+
+/// A single finding produced by the lint pass.
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub struct LintDiagnostic {
+    /// The rule that produced this diagnostic.
+    pub rule: LintRule,
+    /// The severity derived from the rule's configured level.
+    pub severity: Severity,
+    /// A human readable description of the problem.
+    pub message: String,
+    /// The byte range of the offending name in the source.
+    pub span: Span,
+}
+
+impl fmt::Display for LintDiagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}[{}]: {}", self.severity, self.rule, self.message)
+    }
+}
+
+impl From<LintDiagnostic> for Diagnostic {
+    fn from(lint: LintDiagnostic) -> Self {
+        Diagnostic::new(lint.severity, lint.message)
+            .with_code(lint.rule.name())
+            .with_span(lint.span)
+    }
+}
+
+/// Runs every enabled lint rule over a module.
+///
+/// The module need not be resolved, as from
+/// [`Parser::parse_module_unresolved`](crate::parser::Parser::parse_module_unresolved), in
+/// which case the size rules skip definitions that have no size yet. Diagnostics are returned
+/// in source definition order.
+pub fn lint_module(module: &OnyxModule, config: &LintConfig) -> Vec<LintDiagnostic> {
+    let mut linter = Linter {
+        module,
+        config,
+        diagnostics: Vec::new(),
+    };
+
+    let mut definitions: Vec<&Definition> = module.definitions.values().collect();
+    definitions.sort_by_key(|def| def.span());
+
+    for def in &definitions {
+        linter.check_type_naming(def);
+        match def {
+            Definition::Message(m) => {
+                linter.check_fields(&m.fields);
+                linter.check_deprecated_use(def, &m.fields);
+                linter.check_message_size(def);
+            }
+            Definition::Struct(s) => {
+                linter.check_fields(&s.fields);
+                linter.check_deprecated_use(def, &s.fields);
+                linter.check_unused_struct(def);
+            }
+            Definition::Enum(_) => {}
+        }
+    }
+    linter.check_dead_definitions();
+
+    linter.diagnostics
+}
+
+/// Returns true if the name is PascalCase (e.g. `MyMessage`).
+fn is_pascal_case(name: &str) -> bool {
+    name.starts_with(|c: char| c.is_ascii_uppercase())
+        && name.chars().all(|c| c.is_ascii_alphanumeric())
+}
+
+/// Returns true if the name is snake_case (e.g. `my_field`).
+fn is_snake_case(name: &str) -> bool {
+    name.starts_with(|c: char| c.is_ascii_lowercase())
+        && name
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+}
+
+struct Linter<'a> {
+    module: &'a OnyxModule,
+    config: &'a LintConfig,
+    diagnostics: Vec<LintDiagnostic>,
+}
+
+impl Linter<'_> {
+    /// Records a diagnostic for the rule, unless the rule is allowed.
+    fn report(&mut self, rule: LintRule, message: String, span: Span) {
+        let severity = match self.config.level(rule) {
+            LintLevel::Allow => return,
+            LintLevel::Warn => Severity::Warning,
+            LintLevel::Deny => Severity::Error,
+        };
+        self.diagnostics.push(LintDiagnostic {
+            rule,
+            severity,
+            message,
+            span,
+        });
+    }
+
+    fn check_type_naming(&mut self, def: &Definition) {
+        if !is_pascal_case(def.name()) {
+            self.report(
+                LintRule::TypeNaming,
+                format!("type name '{}' should be PascalCase", def.name()),
+                def.span(),
+            );
+        }
+
+        if let Definition::Enum(e) = def {
+            for variant in &e.variants {
+                if !is_pascal_case(&variant.name) {
+                    self.report(
+                        LintRule::TypeNaming,
+                        format!(
+                            "variant name '{}::{}' should be PascalCase",
+                            e.name, variant.name
+                        ),
+                        variant.span,
+                    );
+                }
+            }
+        }
+    }
+
+    fn check_fields(&mut self, fields: &[Field]) {
+        for field in fields {
+            if !is_snake_case(field.name.as_str()) {
+                self.report(
+                    LintRule::FieldNaming,
+                    format!("field name '{}' should be snake_case", field.name),
+                    field.span,
+                );
+            }
+        }
+
+        // Consecutive bit-fields share a container, mirroring the generators' grouping.
+        let mut group: Vec<&Field> = Vec::new();
+        for field in fields {
+            if field.bit_field_size.is_some() {
+                group.push(field);
+            } else {
+                self.check_bit_field_group(&group);
+                group.clear();
+            }
+        }
+        self.check_bit_field_group(&group);
+    }
+
+    fn check_bit_field_group(&mut self, group: &[&Field]) {
+        let Some(first) = group.first() else {
+            return;
+        };
+        let bits: usize = group.iter().map(|f| f.get_bit_width(self.module)).sum();
+        let bytes = bits.div_ceil(8);
+        if !matches!(bytes, 1 | 2 | 4 | 8) {
+            self.report(
+                LintRule::NonPowerOfTwoBitField,
+                format!(
+                    "bit-field group starting at '{}' packs into {bytes} bytes, which is not a power-of-two container width",
+                    first.name
+                ),
+                first.span,
+            );
+        }
+    }
+
+    fn check_deprecated_use(&mut self, def: &Definition, fields: &[Field]) {
+        if def.deprecated().is_some() {
+            return;
+        }
+        for field in fields.iter().filter(|f| f.deprecated.is_none()) {
+            let Type::Custom(name) = field.type_info.element_type() else {
+                continue;
+            };
+            if let Some(note) = self
+                .module
+                .definitions
+                .get(name)
+                .and_then(|d| d.deprecated())
+            {
+                self.report(
+                    LintRule::DeprecatedUse,
+                    format!(
+                        "field '{}.{}' uses deprecated type '{name}': {note}",
+                        def.name(),
+                        field.name
+                    ),
+                    field.span,
+                );
+            }
+        }
+    }
+
+    fn check_message_size(&mut self, def: &Definition) {
+        let Some(bits) = def.size() else {
+            return;
+        };
+        let bytes = bits.div_ceil(8);
+        if bytes > self.config.max_message_bytes {
+            self.report(
+                LintRule::OversizedMessage,
+                format!(
+                    "message '{}' is {bytes} bytes, exceeding the maximum of {} bytes",
+                    def.name(),
+                    self.config.max_message_bytes
+                ),
+                def.span(),
+            );
+        }
+    }
+
+    fn check_unused_struct(&mut self, def: &Definition) {
+        if !self.is_referenced(def) {
+            self.report(
+                LintRule::UnusedStruct,
+                format!("struct '{}' is never used", def.name()),
+                def.span(),
+            );
+        }
+    }
+
+    /// Returns true if a field of some definition has the type `def`.
+    fn is_referenced(&self, def: &Definition) -> bool {
+        self.module.definitions.values().any(|other| {
+            let fields = match other {
+                Definition::Message(m) => &m.fields,
+                Definition::Struct(s) => &s.fields,
+                Definition::Enum(_) => return false,
+            };
+            fields
+                .iter()
+                .any(|f| matches!(f.type_info.element_type(), Type::Custom(name) if *name == def.symbol()))
+        })
+    }
+
+    /// Reports definitions no entry point uses, other than structs `LintRule::UnusedStruct`
+    /// covers.
+    fn check_dead_definitions(&mut self) {
+        let entry_points: Vec<Symbol> = self
+            .config
+            .entry_points
+            .iter()
+            .filter(|name| self.module.definitions.contains_key(*name))
+            .copied()
+            .collect();
+        let Ok(dead) = prune::dead_definitions(self.module, &entry_points) else {
+            return;
+        };
+        for name in dead {
+            let def = &self.module.definitions[&name];
+            if !matches!(def, Definition::Struct(_)) || self.is_referenced(def) {
+                self.report(
+                    LintRule::DeadDefinition,
+                    format!("'{name}' is not used by any entry point"),
+                    def.span(),
+                );
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::Parser;
+
+    fn lint(source: &str, config: &LintConfig) -> Vec<LintDiagnostic> {
+        let module = Parser::new(source).unwrap().parse_module().unwrap();
+        lint_module(&module, config)
+    }
+
+    #[test]
+    fn test_clean_module() {
+        let source = "struct Header { version u8, } message Msg { hdr Header, }";
+        assert!(lint(source, &LintConfig::default()).is_empty());
+    }
+
+    #[test]
+    fn test_naming_rules() {
+        let source = "enum status : u8 { active, } message Msg { myField u8, s status, }";
+        let diagnostics = lint(source, &LintConfig::default());
+
+        let rules: Vec<LintRule> = diagnostics.iter().map(|d| d.rule).collect();
+        assert_eq!(
+            rules,
+            vec![
+                LintRule::TypeNaming,
+                LintRule::TypeNaming,
+                LintRule::FieldNaming
+            ]
+        );
+        assert_eq!(diagnostics[2].span.start, 43);
+    }
+
+    #[test]
+    fn test_unused_struct_and_levels() {
+        let source = "struct Unused { a u8, } message Msg { b u8, }";
+        let mut config = LintConfig::default();
+
+        let diagnostics = lint(source, &config);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].rule, LintRule::UnusedStruct);
+        assert_eq!(diagnostics[0].severity, Severity::Warning);
+
+        config.set_level(LintRule::UnusedStruct, LintLevel::Deny);
+        assert_eq!(lint(source, &config)[0].severity, Severity::Error);
+
+        config.set_level(LintRule::UnusedStruct, LintLevel::Allow);
+        assert!(lint(source, &config).is_empty());
+    }
+
+    #[test]
+    fn test_oversized_message() {
+        let source = "message Msg { a u64, b u64, }";
+        let config = LintConfig {
+            max_message_bytes: 8,
+            ..Default::default()
+        };
+        let diagnostics = lint(source, &config);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].rule, LintRule::OversizedMessage);
+    }
+
+    #[test]
+    fn test_bit_field_container_width() {
+        let source = "message Msg { a u16 : 12, b u16 : 12, c u8, }";
+        let diagnostics = lint(source, &LintConfig::default());
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].rule, LintRule::NonPowerOfTwoBitField);
+    }
+
+    #[test]
+    fn test_deprecated_use() {
+        let source = "@deprecated(\"use Kind\") enum Old : u8 { A, } \
+                      @deprecated(\"use Msg\") struct Legacy { old Old, } \
+                      message Msg { a Old, @deprecated(\"use a\") b Old, c vec<Old, 2>, l Legacy, }";
+        let messages: Vec<String> = lint(source, &LintConfig::default())
+            .into_iter()
+            .filter(|d| d.rule == LintRule::DeprecatedUse)
+            .map(|d| d.message)
+            .collect();
+        assert_eq!(
+            messages,
+            [
+                "field 'Msg.a' uses deprecated type 'Old': use Kind",
+                "field 'Msg.c' uses deprecated type 'Old': use Kind",
+                "field 'Msg.l' uses deprecated type 'Legacy': use Msg",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_dead_definition() {
+        let source = "struct Unused { a u8, } enum Kind : u8 { A, } struct Spare { kind Kind, } \
+                      message Msg { b u8, } message Debug { spare Spare, }";
+        let dead = |config: &LintConfig| -> Vec<String> {
+            lint(source, config)
+                .into_iter()
+                .filter(|d| d.rule == LintRule::DeadDefinition)
+                .map(|d| d.message)
+                .collect()
+        };
+        assert!(dead(&LintConfig::default()).is_empty());
+
+        let config = LintConfig {
+            entry_points: vec!["Msg".into(), "Missing".into()],
+            ..Default::default()
+        };
+        assert_eq!(
+            dead(&config),
+            [
+                "'Kind' is not used by any entry point",
+                "'Spare' is not used by any entry point",
+                "'Debug' is not used by any entry point",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_rule_names_round_trip() {
+        for rule in LintRule::ALL {
+            assert_eq!(LintRule::from_name(rule.name()), Some(rule));
+        }
+        assert_eq!(LintRule::from_name("nope"), None);
+    }
+}
@@ -0,0 +1,933 @@
+//! Importing MAVLink XML message definitions.
+//!
+//! [`import`] reads MAVLink message definition files, such as `common.xml` and a dialect that
+//! includes it, into one [`OnyxModule`] with the same wire layout, so that a team can move a
+//! message catalog to Onyx one consumer at a time while the rest keep their MAVLink code.
+//!
+//! A message keeps its MAVLink ID and its payload layout: a little-endian module whose fields
+//! are in wire order, sorted by the size of their type, largest first, with extension fields
+//! last in declared order. A `char` or `uint8_t` array becomes `bytes[N]`, and any other array
+//! a field per element, `name_0` through `name_{N-1}`. Names become UpperCamelCase, with the
+//! enum name dropped from the front of each entry, so `MAV_STATE_ACTIVE` of `MAV_STATE` is
+//! `MavState.Active`; fields keep theirs.
+//!
+//! Enums are `@open`, as a peer built from a newer catalog can send entries this one lacks,
+//! and a `bitmask` enum whose entries are bits is a `flags`. An enum takes the type of the
+//! fields that name it, so that they can hold it; a field of another type stays an integer.
+//! Descriptions, units, and the checksum seed MAVLink derives from each message are not
+//! carried over, and MAVLink 2 truncation of trailing zero bytes is up to the transport.
+//!
+//! ```rust
+//! use onyx::mavlink;
+//!
+//! let xml = r#"<mavlink>
+//!   <enums>
+//!     <enum name="MAV_STATE">
+//!       <entry value="3" name="MAV_STATE_STANDBY"/>
+//!       <entry value="4" name="MAV_STATE_ACTIVE"/>
+//!     </enum>
+//!   </enums>
+//!   <messages>
+//!     <message id="0" name="HEARTBEAT">
+//!       <field type="uint8_t" name="system_status" enum="MAV_STATE">System status</field>
+//!       <field type="uint32_t" name="custom_mode">Autopilot mode</field>
+//!     </message>
+//!   </messages>
+//! </mavlink>"#;
+//!
+//! let imported = mavlink::import(&[xml]).unwrap();
+//! assert!(imported.schema().contains("@id(0) message Heartbeat {\n    custom_mode u32,\n"));
+//! ```
+
+use std::collections::HashMap;
+
+use crate::{
+    ast::{
+        Definition, EnumDef, EnumVariant, Field, MessageDef, OnyxModule, PrimitiveType, Type,
+        WireEndianness,
+    },
+    diagnostic::{Diagnostic, codes},
+    generators::upper_camel_case,
+    parser::Parser,
+    span::Span,
+    symbol::Symbol,
+};
+
+/// An import error, paired with the index of the document it is about.
+pub type ImportError = (usize, Diagnostic);
+
+/// A module imported from MAVLink, and what could not be carried over exactly.
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub struct Imported {
+    /// The resolved module.
+    pub module: OnyxModule,
+    /// Warnings about definitions that were imported with less than MAVLink gives them, each
+    /// paired with the index of the document it is about.
+    pub warnings: Vec<ImportError>,
+}
+
+impl Imported {
+    /// Returns the module as Onyx source.
+    pub fn schema(&self) -> String {
+        self.module.to_idl()
+    }
+}
+
+/// Returns the files a MAVLink document includes, as written in its `<include>` elements:
+/// paths relative to the document.
+pub fn includes(xml: &str) -> Result<Vec<String>, Diagnostic> {
+    let root = parse_root(xml)?;
+    Ok(root
+        .children("include")
+        .map(|include| include.text.trim().to_string())
+        .collect())
+}
+
+/// Imports MAVLink documents as one module.
+///
+/// Pass every document the catalog needs, included files first; entries of an enum split
+/// across documents are merged. Spans in the diagnostics are byte ranges of the document they
+/// are paired with.
+pub fn import(documents: &[&str]) -> Result<Imported, ImportError> {
+    let mut catalog = Catalog::default();
+    for (index, xml) in documents.iter().enumerate() {
+        let root = parse_root(xml).map_err(|e| (index, e))?;
+        catalog.read(index, &root).map_err(|e| (index, e))?;
+    }
+    catalog.finish(documents.len().saturating_sub(1))
+}
+
+// ------ MAVLink ------
+
+/// An enum as MAVLink declares it, possibly across several documents.
+#[derive(Debug)]
+struct MavEnum {
+    name: String,
+    bitmask: bool,
+    deprecated: Option<String>,
+    /// Entry names and values, in declared order.
+    entries: Vec<(String, u64)>,
+    document: usize,
+    span: Span,
+}
+
+/// A message field as MAVLink declares it.
+#[derive(Debug)]
+struct MavField {
+    name: String,
+    base: PrimitiveType,
+    /// The array length, for a field such as `float[4]`.
+    len: Option<usize>,
+    /// Whether the MAVLink type is `char`, so that an array of it is text.
+    char: bool,
+    enum_name: Option<String>,
+    span: Span,
+}
+
+/// A message as MAVLink declares it.
+#[derive(Debug)]
+struct MavMessage {
+    name: String,
+    id: u32,
+    deprecated: Option<String>,
+    /// The fields before `<extensions/>`, in declared order.
+    fields: Vec<MavField>,
+    /// The fields after `<extensions/>`.
+    extensions: Vec<MavField>,
+    document: usize,
+    span: Span,
+}
+
+/// The enums and messages of every document read so far.
+#[derive(Debug, Default)]
+struct Catalog {
+    enums: Vec<MavEnum>,
+    messages: Vec<MavMessage>,
+}
+
+impl Catalog {
+    /// Adds the enums and messages of the document `index`.
+    fn read(&mut self, index: usize, root: &Element) -> Result<(), Diagnostic> {
+        for element in root
+            .children("enums")
+            .flat_map(|enums| enums.children("enum"))
+        {
+            self.read_enum(index, element)?;
+        }
+        for element in root
+            .children("messages")
+            .flat_map(|messages| messages.children("message"))
+        {
+            let message = read_message(index, element)?;
+            self.messages.push(message);
+        }
+        Ok(())
+    }
+
+    /// Adds an enum, or its entries to the enum of the same name read before.
+    fn read_enum(&mut self, index: usize, element: &Element) -> Result<(), Diagnostic> {
+        let name = element.required("name")?;
+        let position = match self.enums.iter().position(|e| e.name == name) {
+            Some(position) => position,
+            None => {
+                self.enums.push(MavEnum {
+                    name: name.to_string(),
+                    bitmask: false,
+                    deprecated: None,
+                    entries: Vec::new(),
+                    document: index,
+                    span: element.span,
+                });
+                self.enums.len() - 1
+            }
+        };
+        let mav_enum = &mut self.enums[position];
+        mav_enum.bitmask |= element.attribute("bitmask") == Some("true");
+        if let Some(deprecated) = element.children("deprecated").next() {
+            mav_enum.deprecated = Some(deprecation_note(deprecated));
+        }
+        for entry in element.children("entry") {
+            let entry_name = entry.required("name")?;
+            // As mavgen numbers them, an entry without a value takes one more than the
+            // highest value before it, starting from 1.
+            let value = match entry.attribute("value") {
+                Some(value) => parse_value(value).ok_or_else(|| {
+                    invalid(
+                        entry.span,
+                        format!("entry '{entry_name}' has value '{value}', which is not a non-negative integer"),
+                    )
+                })?,
+                None => mav_enum.entries.iter().map(|&(_, v)| v).max().unwrap_or(0) + 1,
+            };
+            mav_enum.entries.push((entry_name.to_string(), value));
+        }
+        Ok(())
+    }
+
+    /// Builds the module, resolving it by parsing its printed schema so that it is checked
+    /// as a schema written by hand would be. Errors the schema has are paired with `last`, the
+    /// document that completes the catalog.
+    fn finish(self, last: usize) -> Result<Imported, ImportError> {
+        let mut warnings = Vec::new();
+        let mut module = OnyxModule {
+            endianness: WireEndianness::Little,
+            ..OnyxModule::default()
+        };
+        // Names already taken, with the MAVLink name and document of their definition.
+        let mut taken: HashMap<Symbol, (String, usize)> = HashMap::new();
+        let mut take = |name: Symbol, original: &str, document: usize, span: Span| match taken
+            .insert(name, (original.to_string(), document))
+        {
+            Some((other, _)) => Err((
+                document,
+                invalid(
+                    span,
+                    format!("'{original}' and '{other}' would both be named '{name}'"),
+                ),
+            )),
+            None => Ok(()),
+        };
+
+        let mut enum_types: HashMap<&str, (Symbol, PrimitiveType)> = HashMap::new();
+        for mav_enum in &self.enums {
+            if mav_enum.entries.is_empty() {
+                warnings.push((
+                    mav_enum.document,
+                    Diagnostic::warning(format!(
+                        "enum '{}' has no entries, so fields of it are imported as integers",
+                        mav_enum.name
+                    ))
+                    .with_code(codes::INVALID_IMPORT)
+                    .with_span(mav_enum.span),
+                ));
+                continue;
+            }
+            let def = self.build_enum(mav_enum);
+            take(def.name, &mav_enum.name, mav_enum.document, mav_enum.span)?;
+            enum_types.insert(&mav_enum.name, (def.name, def.underlying_type));
+            module.definitions.insert(def.name, Definition::Enum(def));
+        }
+
+        for message in &self.messages {
+            let mut def = MessageDef::new(type_name(&message.name), Vec::new());
+            take(def.name, &message.name, message.document, message.span)?;
+            match u16::try_from(message.id) {
+                Ok(id) => def.id = Some(id),
+                Err(_) => warnings.push((
+                    message.document,
+                    Diagnostic::warning(format!(
+                        "message '{}' has ID {}, past the largest Onyx message ID, so it is imported without one",
+                        message.name, message.id
+                    ))
+                    .with_code(codes::INVALID_IMPORT)
+                    .with_span(message.span)
+                    .with_help("frame it with its MAVLink ID outside the payload"),
+                )),
+            }
+            def.deprecated = message.deprecated.clone();
+
+            let mut fields: Vec<&MavField> = message.fields.iter().collect();
+            // MAVLink sends the fields of a message largest type first, keeping declared
+            // order among fields of the same size; extensions follow as declared.
+            fields.sort_by_key(|field| std::cmp::Reverse(field.base.get_byte_size()));
+            fields.extend(&message.extensions);
+            for field in fields {
+                let ty = match field.enum_name.as_deref() {
+                    None => Type::Primitive(field.base),
+                    Some(name) => match enum_types.get(name) {
+                        Some(&(symbol, underlying)) if underlying == field.base => {
+                            Type::Custom(symbol)
+                        }
+                        Some(&(symbol, underlying)) => {
+                            warnings.push((
+                                message.document,
+                                Diagnostic::warning(format!(
+                                    "field '{}' of '{}' is {}, but enum '{symbol}' is {underlying}, so the field is imported as an integer",
+                                    field.name, message.name, field.base
+                                ))
+                                .with_code(codes::INVALID_IMPORT)
+                                .with_span(field.span),
+                            ));
+                            Type::Primitive(field.base)
+                        }
+                        None => {
+                            if !self.enums.iter().any(|e| e.name == name) {
+                                warnings.push((
+                                    message.document,
+                                    Diagnostic::warning(format!(
+                                        "field '{}' of '{}' names enum '{name}', which no document declares, so the field is imported as an integer",
+                                        field.name, message.name
+                                    ))
+                                    .with_code(codes::INVALID_IMPORT)
+                                    .with_span(field.span)
+                                    .with_help("pass the document that declares it"),
+                                ));
+                            }
+                            Type::Primitive(field.base)
+                        }
+                    },
+                };
+                match field.len {
+                    Some(len) if field.char || field.base == PrimitiveType::U8 => def
+                        .fields
+                        .push(Field::new(field.name.as_str(), Type::Bytes(len), None)),
+                    Some(len) => def.fields.extend(
+                        (0..len)
+                            .map(|i| Field::new(format!("{}_{i}", field.name), ty.clone(), None)),
+                    ),
+                    None => def.fields.push(Field::new(field.name.as_str(), ty, None)),
+                }
+            }
+            module
+                .definitions
+                .insert(def.name, Definition::Message(def));
+        }
+
+        let schema = module.to_idl();
+        let module = Parser::new(&schema)
+            .and_then(|parser| parser.parse_module())
+            .map_err(|e| {
+                let diagnostic = Diagnostic::error(format!(
+                    "the imported definitions do not form a valid schema: {}",
+                    e.message()
+                ))
+                .with_code(codes::INVALID_IMPORT);
+                (last, diagnostic)
+            })?;
+        Ok(Imported { module, warnings })
+    }
+
+    /// Builds the enum or flags of `mav_enum`.
+    fn build_enum(&self, mav_enum: &MavEnum) -> EnumDef {
+        let max = mav_enum.entries.iter().map(|&(_, v)| v).max().unwrap_or(0);
+        let flags = mav_enum.bitmask && is_flags(&mav_enum.entries);
+        let underlying = self
+            .field_type(&mav_enum.name, max, flags)
+            .unwrap_or_else(|| smallest_unsigned(max));
+
+        let prefix = format!("{}_", mav_enum.name);
+        let variants = mav_enum
+            .entries
+            .iter()
+            .map(|(name, value)| {
+                let short = name.strip_prefix(&prefix).unwrap_or(name);
+                // A variant name cannot start with a digit, as in MAV_FRAME_3D, so those keep
+                // the enum name.
+                let short = match short.starts_with(|c: char| c.is_ascii_digit()) {
+                    true => name,
+                    false => short,
+                };
+                let mut variant = EnumVariant::new(type_name(short), Some(*value));
+                variant.composite = flags && !value.is_power_of_two();
+                variant
+            })
+            .collect();
+        let mut def = EnumDef::new(type_name(&mav_enum.name), underlying, variants);
+        def.flags = flags;
+        def.open = !flags;
+        def.deprecated = mav_enum.deprecated.clone();
+        def
+    }
+
+    /// Returns the type most fields naming the enum `name` have, if it holds `max` and, for
+    /// flags, is unsigned. Ties go to the type named first.
+    fn field_type(&self, name: &str, max: u64, flags: bool) -> Option<PrimitiveType> {
+        let mut counts: Vec<(PrimitiveType, usize)> = Vec::new();
+        let fields = self
+            .messages
+            .iter()
+            .flat_map(|m| m.fields.iter().chain(&m.extensions));
+        for field in fields.filter(|f| f.enum_name.as_deref() == Some(name)) {
+            if field.len.is_some() && (field.char || field.base == PrimitiveType::U8) {
+                continue;
+            }
+            match counts.iter_mut().find(|(ty, _)| *ty == field.base) {
+                Some((_, count)) => *count += 1,
+                None => counts.push((field.base, 1)),
+            }
+        }
+        let mut best: Option<(PrimitiveType, usize)> = None;
+        for (ty, count) in counts {
+            if fits(ty, max) && !(flags && ty.is_signed()) && best.is_none_or(|(_, c)| count > c) {
+                best = Some((ty, count));
+            }
+        }
+        best.map(|(ty, _)| ty)
+    }
+}
+
+/// Reads a `<message>` element.
+fn read_message(index: usize, element: &Element) -> Result<MavMessage, Diagnostic> {
+    let name = element.required("name")?;
+    let id = element.required("id")?;
+    let id = id.parse::<u32>().map_err(|_| {
+        invalid(
+            element.span,
+            format!("message '{name}' has ID '{id}', which is not a non-negative integer"),
+        )
+    })?;
+    let mut message = MavMessage {
+        name: name.to_string(),
+        id,
+        deprecated: element.children("deprecated").next().map(deprecation_note),
+        fields: Vec::new(),
+        extensions: Vec::new(),
+        document: index,
+        span: element.span,
+    };
+    let mut extensions = false;
+    for child in &element.children {
+        match child.name.as_str() {
+            "extensions" => extensions = true,
+            "field" => {
+                let field = read_field(child)?;
+                match extensions {
+                    true => message.extensions.push(field),
+                    false => message.fields.push(field),
+                }
+            }
+            _ => {}
+        }
+    }
+    if message.fields.is_empty() && message.extensions.is_empty() {
+        return Err(invalid(
+            element.span,
+            format!("message '{name}' has no fields"),
+        ));
+    }
+    Ok(message)
+}
+
+/// Reads a `<field>` element.
+fn read_field(element: &Element) -> Result<MavField, Diagnostic> {
+    let name = element.required("name")?;
+    let ty = element.required("type")?;
+    let (base, len) = match ty.strip_suffix(']').and_then(|t| t.split_once('[')) {
+        Some((base, len)) => match len.parse::<usize>() {
+            Ok(len) if len > 0 => (base, Some(len)),
+            _ => {
+                return Err(invalid(
+                    element.span,
+                    format!(
+                        "field '{name}' has type '{ty}', whose length is not a positive integer"
+                    ),
+                ));
+            }
+        },
+        None => (ty, None),
+    };
+    let primitive = match base {
+        "int8_t" => PrimitiveType::I8,
+        "uint8_t" | "char" | "uint8_t_mavlink_version" => PrimitiveType::U8,
+        "int16_t" => PrimitiveType::I16,
+        "uint16_t" => PrimitiveType::U16,
+        "int32_t" => PrimitiveType::I32,
+        "uint32_t" => PrimitiveType::U32,
+        "int64_t" => PrimitiveType::I64,
+        "uint64_t" => PrimitiveType::U64,
+        "float" => PrimitiveType::F32,
+        "double" => PrimitiveType::F64,
+        _ => {
+            return Err(invalid(
+                element.span,
+                format!("field '{name}' has type '{ty}', which is not a MAVLink type"),
+            ));
+        }
+    };
+    Ok(MavField {
+        name: name.to_string(),
+        base: primitive,
+        len,
+        char: base == "char",
+        enum_name: element.attribute("enum").map(str::to_string),
+        span: element.span,
+    })
+}
+
+/// Returns the note of a `<deprecated>` element, naming what replaces the definition.
+fn deprecation_note(element: &Element) -> String {
+    let note = match element.attribute("replaced_by") {
+        Some(replacement) if !replacement.is_empty() => format!("replaced by {replacement}"),
+        _ => "deprecated".to_string(),
+    };
+    let note = match element.attribute("since") {
+        Some(since) => format!("{note} since {since}"),
+        None => note,
+    };
+    // Notes are printed in a string literal without escapes.
+    note.replace(['"', '\\'], "")
+}
+
+/// Returns true if each entry of a bitmask enum can be a flag: a single bit not taken by
+/// an earlier entry, or a combination of the single bits.
+fn is_flags(entries: &[(String, u64)]) -> bool {
+    let mut declared = 0u64;
+    for &(_, value) in entries.iter().filter(|(_, v)| v.is_power_of_two()) {
+        if declared & value != 0 {
+            return false;
+        }
+        declared |= value;
+    }
+    entries
+        .iter()
+        .all(|&(_, value)| value != 0 && value & !declared == 0)
+}
+
+/// Returns true if `ty` is an integer type that holds `value`.
+fn fits(ty: PrimitiveType, value: u64) -> bool {
+    let bits = ty.get_bit_width() as u32 - u32::from(ty.is_signed());
+    match ty {
+        PrimitiveType::F32 | PrimitiveType::F64 | PrimitiveType::Bool => false,
+        _ => bits == 64 || value >> bits == 0,
+    }
+}
+
+/// Returns the narrowest unsigned type that holds `value`.
+fn smallest_unsigned(value: u64) -> PrimitiveType {
+    [PrimitiveType::U8, PrimitiveType::U16, PrimitiveType::U32]
+        .into_iter()
+        .find(|&ty| fits(ty, value))
+        .unwrap_or(PrimitiveType::U64)
+}
+
+/// Parses an entry value as mavgen does: decimal, hexadecimal with `0x`, binary with `0b`, or
+/// a power of two written `2**N`.
+fn parse_value(value: &str) -> Option<u64> {
+    let value = value.trim();
+    if let Some(hex) = value.strip_prefix("0x").or(value.strip_prefix("0X")) {
+        u64::from_str_radix(hex, 16).ok()
+    } else if let Some(binary) = value.strip_prefix("0b").or(value.strip_prefix("0B")) {
+        u64::from_str_radix(binary, 2).ok()
+    } else if let Some(exponent) = value.strip_prefix("2**") {
+        1u64.checked_shl(exponent.parse().ok()?)
+    } else {
+        value.parse().ok()
+    }
+}
+
+/// Converts a MAVLink name such as `GPS_RAW_INT` to UpperCamelCase, `GpsRawInt`.
+fn type_name(name: &str) -> String {
+    upper_camel_case(&name.to_ascii_lowercase())
+}
+
+/// Returns an import error at `span`.
+fn invalid(span: Span, message: String) -> Diagnostic {
+    Diagnostic::error(message)
+        .with_code(codes::INVALID_IMPORT)
+        .with_span(span)
+}
+
+// ------ XML ------
+
+/// An XML element, with the text directly inside it.
+#[derive(Debug, Default)]
+struct Element {
+    name: String,
+    attributes: Vec<(String, String)>,
+    children: Vec<Element>,
+    text: String,
+    /// The byte range of the start tag.
+    span: Span,
+}
+
+impl Element {
+    /// Returns the value of the attribute `name`.
+    fn attribute(&self, name: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Returns the value of the attribute `name`, or an error if the element lacks it.
+    fn required(&self, name: &str) -> Result<&str, Diagnostic> {
+        self.attribute(name).ok_or_else(|| {
+            invalid(
+                self.span,
+                format!("element '{}' has no '{name}' attribute", self.name),
+            )
+        })
+    }
+
+    /// Returns the child elements named `name`.
+    fn children<'e>(&'e self, name: &'e str) -> impl Iterator<Item = &'e Element> {
+        self.children.iter().filter(move |child| child.name == name)
+    }
+}
+
+/// Parses a MAVLink document and returns its `<mavlink>` element.
+fn parse_root(xml: &str) -> Result<Element, Diagnostic> {
+    let mut reader = Reader { xml, pos: 0 };
+    reader.skip_misc()?;
+    let root = reader.element()?;
+    reader.skip_misc()?;
+    if reader.pos < xml.len() {
+        return Err(reader.error("expected the end of the document"));
+    }
+    if root.name != "mavlink" {
+        return Err(invalid(
+            root.span,
+            format!("expected a 'mavlink' element, found '{}'", root.name),
+        ));
+    }
+    Ok(root)
+}
+
+/// A reader of the XML that MAVLink documents use: elements, attributes, text, comments,
+/// CDATA, and the predefined and numeric entities. Declarations and processing instructions
+/// are skipped.
+struct Reader<'x> {
+    xml: &'x str,
+    pos: usize,
+}
+
+impl<'x> Reader<'x> {
+    fn rest(&self) -> &'x str {
+        &self.xml[self.pos..]
+    }
+
+    fn error(&self, message: impl Into<String>) -> Diagnostic {
+        let end = self.rest().chars().next().map_or(0, char::len_utf8);
+        invalid(Span::new(self.pos, self.pos + end), message.into())
+    }
+
+    fn expect(&mut self, token: &str) -> Result<(), Diagnostic> {
+        match self.rest().starts_with(token) {
+            true => {
+                self.pos += token.len();
+                Ok(())
+            }
+            false => Err(self.error(format!("expected '{token}'"))),
+        }
+    }
+
+    /// Skips past `end`, as at the end of a comment.
+    fn skip_past(&mut self, end: &str) -> Result<(), Diagnostic> {
+        match self.rest().find(end) {
+            Some(at) => {
+                self.pos += at + end.len();
+                Ok(())
+            }
+            None => Err(self.error(format!("expected '{end}' before the end of the document"))),
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        let rest = self.rest();
+        self.pos += rest.len() - rest.trim_start().len();
+    }
+
+    /// Skips whitespace, comments, declarations, and processing instructions.
+    fn skip_misc(&mut self) -> Result<(), Diagnostic> {
+        loop {
+            self.skip_whitespace();
+            if self.rest().starts_with("<!--") {
+                self.skip_past("-->")?;
+            } else if self.rest().starts_with("<?") {
+                self.skip_past("?>")?;
+            } else if self.rest().starts_with("<!") {
+                self.skip_past(">")?;
+            } else {
+                return Ok(());
+            }
+        }
+    }
+
+    fn name(&mut self) -> Result<String, Diagnostic> {
+        let rest = self.rest();
+        let len = rest
+            .find(|c: char| !(c.is_alphanumeric() || matches!(c, '_' | '-' | '.' | ':')))
+            .unwrap_or(rest.len());
+        if len == 0 {
+            return Err(self.error("expected a name"));
+        }
+        self.pos += len;
+        Ok(rest[..len].to_string())
+    }
+
+    /// Reads an element, starting at its `<`.
+    fn element(&mut self) -> Result<Element, Diagnostic> {
+        let start = self.pos;
+        self.expect("<")?;
+        let mut element = Element {
+            name: self.name()?,
+            ..Element::default()
+        };
+        loop {
+            self.skip_whitespace();
+            if self.rest().starts_with("/>") {
+                self.pos += 2;
+                element.span = Span::new(start, self.pos);
+                return Ok(element);
+            }
+            if self.rest().starts_with('>') {
+                self.pos += 1;
+                break;
+            }
+            let name = self.name()?;
+            self.skip_whitespace();
+            self.expect("=")?;
+            self.skip_whitespace();
+            let quote = match self.rest().chars().next() {
+                Some(quote @ ('"' | '\'')) => quote,
+                _ => return Err(self.error("expected a quoted attribute value")),
+            };
+            self.pos += 1;
+            let len = self
+                .rest()
+                .find(quote)
+                .ok_or_else(|| self.error("expected the end of the attribute value"))?;
+            let value = self.decode(self.pos, len)?;
+            self.pos += len + 1;
+            element.attributes.push((name, value));
+        }
+        element.span = Span::new(start, self.pos);
+
+        loop {
+            let rest = self.rest();
+            if rest.starts_with("</") {
+                self.pos += 2;
+                let name = self.name()?;
+                if name != element.name {
+                    return Err(
+                        self.error(format!("expected '</{}>', found '</{name}>'", element.name))
+                    );
+                }
+                self.skip_whitespace();
+                self.expect(">")?;
+                return Ok(element);
+            } else if rest.starts_with("<![CDATA[") {
+                let len = rest
+                    .find("]]>")
+                    .ok_or_else(|| self.error("expected ']]>' before the end of the document"))?;
+                element.text.push_str(&rest["<![CDATA[".len()..len]);
+                self.pos += len + 3;
+            } else if rest.starts_with("<!--") {
+                self.skip_past("-->")?;
+            } else if rest.starts_with("<?") {
+                self.skip_past("?>")?;
+            } else if rest.starts_with('<') {
+                let child = self.element()?;
+                element.children.push(child);
+            } else if rest.is_empty() {
+                return Err(self.error(format!(
+                    "expected '</{}>' before the end of the document",
+                    element.name
+                )));
+            } else {
+                let len = rest.find('<').unwrap_or(rest.len());
+                let text = self.decode(self.pos, len)?;
+                element.text.push_str(&text);
+                self.pos += len;
+            }
+        }
+    }
+
+    /// Returns the `len` bytes of text at `start` with their entities replaced.
+    fn decode(&self, start: usize, len: usize) -> Result<String, Diagnostic> {
+        let text = &self.xml[start..start + len];
+        let mut out = String::with_capacity(text.len());
+        let mut rest = text;
+        while let Some(amp) = rest.find('&') {
+            out.push_str(&rest[..amp]);
+            let at = start + (text.len() - rest.len()) + amp;
+            let entity = rest[amp + 1..]
+                .find(';')
+                .map(|end| &rest[amp + 1..amp + 1 + end]);
+            let c = match entity {
+                Some("lt") => Some('<'),
+                Some("gt") => Some('>'),
+                Some("amp") => Some('&'),
+                Some("quot") => Some('"'),
+                Some("apos") => Some('\''),
+                Some(entity) => entity
+                    .strip_prefix("#x")
+                    .map(|hex| u32::from_str_radix(hex, 16))
+                    .or_else(|| entity.strip_prefix('#').map(str::parse))
+                    .and_then(Result::ok)
+                    .and_then(char::from_u32),
+                None => None,
+            };
+            let (Some(c), Some(entity)) = (c, entity) else {
+                return Err(invalid(
+                    Span::new(at, at + 1),
+                    "expected an entity such as '&amp;'".to_string(),
+                ));
+            };
+            out.push(c);
+            rest = &rest[amp + entity.len() + 2..];
+        }
+        out.push_str(rest);
+        Ok(out)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const COMMON: &str = r#"<?xml version="1.0"?>
+<mavlink>
+  <version>3</version>
+  <enums>
+    <enum name="MAV_MODE_FLAG" bitmask="true">
+      <description>Mode flags</description>
+      <entry value="128" name="MAV_MODE_FLAG_SAFETY_ARMED"/>
+      <entry value="64" name="MAV_MODE_FLAG_MANUAL_INPUT_ENABLED"/>
+      <entry value="192" name="MAV_MODE_FLAG_ARMED_MANUAL"/>
+    </enum>
+    <enum name="MAV_FRAME">
+      <entry value="0" name="MAV_FRAME_GLOBAL"/>
+      <entry name="MAV_FRAME_LOCAL_NED"/>
+      <entry value="0x0B" name="MAV_FRAME_3D_RELATIVE"/>
+    </enum>
+  </enums>
+  <messages>
+    <message id="0" name="HEARTBEAT">
+      <description>The heartbeat &amp; liveness message</description>
+      <field type="uint8_t" name="type">Vehicle type</field>
+      <field type="uint8_t" name="base_mode" enum="MAV_MODE_FLAG" display="bitmask">Mode</field>
+      <field type="uint32_t" name="custom_mode">Autopilot mode</field>
+      <field type="uint8_t_mavlink_version" name="mavlink_version">Version</field>
+    </message>
+  </messages>
+</mavlink>
+"#;
+
+    const DIALECT: &str = r#"<mavlink>
+  <include>common.xml</include>
+  <enums>
+    <enum name="MAV_FRAME">
+      <entry value="20" name="MAV_FRAME_BODY"/>
+    </enum>
+  </enums>
+  <messages>
+    <message id="330" name="OBSTACLE_DISTANCE">
+      <deprecated since="2024-01" replaced_by="OBSTACLE_DISTANCE_3D"/>
+      <field type="uint64_t" name="time_usec">Timestamp</field>
+      <field type="uint16_t[3]" name="distances">Distances</field>
+      <field type="char[8]" name="label">Label</field>
+      <field type="uint32_t" name="frame" enum="MAV_FRAME">Frame</field>
+      <extensions/>
+      <field type="float" name="increment_f">Increment</field>
+      <field type="uint8_t" name="frame_hint" enum="MAV_FRAME">Frame</field>
+    </message>
+    <message id="70000" name="FAR_AWAY">
+      <field type="int8_t" name="value">Value</field>
+    </message>
+  </messages>
+</mavlink>
+"#;
+
+    #[test]
+    fn test_import() {
+        assert_eq!(includes(DIALECT).unwrap(), ["common.xml"]);
+        let imported = import(&[COMMON, DIALECT]).unwrap();
+        let schema = imported.schema();
+
+        // Fields are in MAVLink wire order: largest type first, extensions last.
+        assert!(schema.contains(
+            "@id(0) message Heartbeat {\n    custom_mode u32,\n    type u8,\n    \
+             base_mode MavModeFlag,\n    mavlink_version u8,\n}"
+        ));
+        assert!(schema.contains(
+            "flags MavModeFlag : u8 {\n    SafetyArmed = 128,\n    ManualInputEnabled = 64,\n    \
+             @composite ArmedManual = 192,\n}"
+        ));
+        // Entries from the dialect are merged, and a name that would start with a digit keeps
+        // the enum name. The enum takes the type most fields naming it have.
+        assert!(schema.contains(
+            "@open enum MavFrame : u32 {\n    Global = 0,\n    LocalNed = 1,\n    \
+             MavFrame3dRelative = 11,\n    Body = 20,\n}"
+        ));
+        assert!(schema.contains(
+            "@deprecated(\"replaced by OBSTACLE_DISTANCE_3D since 2024-01\") @id(330) message \
+             ObstacleDistance {\n    time_usec u64,\n    frame MavFrame,\n    distances_0 u16,\n    \
+             distances_1 u16,\n    distances_2 u16,\n    label bytes[8],\n    increment_f f32,\n    \
+             frame_hint u8,\n}"
+        ));
+        assert!(schema.contains("message FarAway {"));
+        assert!(!schema.contains("@id(70000)"));
+
+        let warnings: Vec<&str> = imported.warnings.iter().map(|(_, w)| w.message()).collect();
+        assert_eq!(warnings.len(), 2);
+        assert!(warnings[0].contains("'frame_hint' of 'OBSTACLE_DISTANCE' is u8"));
+        assert!(warnings[1].contains("'FAR_AWAY' has ID 70000"));
+        assert_eq!(imported.warnings[1].0, 1);
+
+        let hir = crate::hir::lower(&imported.module).unwrap();
+        assert_eq!(hir.size_of(&Type::Custom("Heartbeat".into())), Some(7));
+    }
+
+    #[test]
+    fn test_import_errors() {
+        for (xml, message) in [
+            (
+                "<mavlink><messages>",
+                "expected '</messages>' before the end",
+            ),
+            (
+                "<protocol/>",
+                "expected a 'mavlink' element, found 'protocol'",
+            ),
+            (
+                "<mavlink><messages><message id=\"1\" name=\"A\"><field type=\"uint128_t\" \
+                 name=\"x\"/></message></messages></mavlink>",
+                "field 'x' has type 'uint128_t', which is not a MAVLink type",
+            ),
+            (
+                "<mavlink><messages><message id=\"1\" name=\"A_B\"><field type=\"float\" \
+                 name=\"x\"/></message><message id=\"2\" name=\"a_b\"><field type=\"float\" \
+                 name=\"x\"/></message></messages></mavlink>",
+                "'a_b' and 'A_B' would both be named 'AB'",
+            ),
+            ("<mavlink>&bogus;</mavlink>", "expected an entity"),
+        ] {
+            let (document, e) = import(&[xml]).unwrap_err();
+            assert_eq!(document, 0);
+            assert_eq!(e.code(), Some(codes::INVALID_IMPORT));
+            assert!(e.message().contains(message), "{}", e.message());
+        }
+    }
+}
@@ -0,0 +1,268 @@
+//! Renaming schemas for external distribution.
+//!
+//! [`obfuscate`] replaces every type, field, and enum variant name with an opaque identifier
+//! while keeping the wire layout: definitions, fields, and variants stay in order and keep their
+//! types, bit widths, and values. The printed schema carries no comments, doc comments, or
+//! `@deprecated` notes, and the mapping back to the original names is returned separately so
+//! it can be kept private.
+
+use std::collections::HashMap;
+
+use crate::{
+    ast::{ConditionValue, Definition, OnyxModule, Type},
+    json,
+    symbol::Symbol,
+};
+
+/// A module with opaque names, and the mapping back to the original ones.
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub struct Obfuscated {
+    /// The renamed module.
+    pub module: OnyxModule,
+    /// Pairs of opaque and original names, in schema order. Fields and variants are qualified
+    /// with their definition, e.g. `("T1.f0", "Header.version")`.
+    pub mapping: Vec<(String, String)>,
+}
+
+impl Obfuscated {
+    /// Returns the renamed schema as Onyx source.
+    pub fn schema(&self) -> String {
+        print_schema(&self.module)
+    }
+
+    /// Returns the mapping as a JSON object from opaque to original names.
+    pub fn mapping_json(&self) -> String {
+        let entries: Vec<String> = self
+            .mapping
+            .iter()
+            .map(|(opaque, original)| {
+                format!("  {}: {}", json::string(opaque), json::string(original))
+            })
+            .collect();
+        format!("{{\n{}\n}}\n", entries.join(",\n"))
+    }
+}
+
+/// Renames every name in a resolved module.
+///
+/// Definitions become `T0`, `T1`, ... in source order; fields become `f0`, `f1`, ... and enum
+/// variants `V0`, `V1`, ... within their definition. Fields are numbered across every version
+/// of the schema, so a field keeps its opaque name in each layout.
+pub fn obfuscate(module: &OnyxModule) -> Obfuscated {
+    let mut definitions: Vec<&Definition> = module.definitions.values().collect();
+    definitions.sort_by_key(|def| def.span().start);
+
+    let renamed: HashMap<Symbol, Symbol> = definitions
+        .iter()
+        .enumerate()
+        .map(|(i, def)| (def.symbol(), Symbol::intern(&format!("T{i}"))))
+        .collect();
+
+    let mut mapping = Vec::new();
+    let mut fields = HashMap::new();
+    for def in definitions {
+        let (opaque_def, original_def) = (renamed[&def.symbol()], def.name());
+        mapping.push((opaque_def.to_string(), original_def.to_string()));
+        if let Definition::Enum(e) = def {
+            for (i, variant) in e.variants.iter().enumerate() {
+                mapping.push((
+                    format!("{opaque_def}.V{i}"),
+                    format!("{original_def}.{}", variant.name),
+                ));
+            }
+        }
+        for (i, field) in module.declared_fields(def).into_iter().enumerate() {
+            let opaque = Symbol::intern(&format!("f{i}"));
+            mapping.push((
+                format!("{opaque_def}.{opaque}"),
+                format!("{original_def}.{}", field.name),
+            ));
+            fields.insert((def.symbol(), field.name), opaque);
+        }
+    }
+
+    let mut result = rename(module, &renamed, &fields);
+    result.history = module
+        .history
+        .iter()
+        .map(|old| rename(old, &renamed, &fields))
+        .collect();
+    Obfuscated {
+        module: result,
+        mapping,
+    }
+}
+
+/// Applies the opaque names to one layout of a module.
+fn rename(
+    module: &OnyxModule,
+    renamed: &HashMap<Symbol, Symbol>,
+    fields: &HashMap<(Symbol, Symbol), Symbol>,
+) -> OnyxModule {
+    let mut result = OnyxModule {
+        endianness: module.endianness,
+        order: module.order.iter().map(|id| renamed[id]).collect(),
+        version: module.version,
+        strict: module.strict,
+        packed_bools: module.packed_bools,
+        alignment: module.alignment,
+        ..OnyxModule::default()
+    };
+    for (id, def) in &module.definitions {
+        let name = renamed[id];
+        let mut def = def.clone();
+        let def_fields = match &mut def {
+            Definition::Message(m) => {
+                m.name = name;
+                m.deprecated = None;
+                m.doc = None;
+                &mut m.fields
+            }
+            Definition::Struct(s) => {
+                s.name = name;
+                s.deprecated = None;
+                s.doc = None;
+                &mut s.fields
+            }
+            Definition::Enum(e) => {
+                e.name = name;
+                e.deprecated = None;
+                e.doc = None;
+                for (i, variant) in e.variants.iter_mut().enumerate() {
+                    variant.name = format!("V{i}");
+                    variant.doc = None;
+                }
+                result.definitions.insert(name, def);
+                continue;
+            }
+        };
+        for field in def_fields {
+            field.name = fields[&(*id, field.name)];
+            field.deprecated = None;
+            field.doc = None;
+            let type_info = match &mut field.type_info {
+                Type::Vector { element, .. } => element.as_mut(),
+                other => other,
+            };
+            if let Type::Custom(name) = type_info {
+                *name = renamed[name];
+            }
+            if let Some(condition) = &mut field.condition {
+                condition.field = fields[&(*id, condition.field)];
+                if let ConditionValue::Variant { enum_name, variant } = &mut condition.value {
+                    if let Some(Definition::Enum(e)) = module.definitions.get(enum_name)
+                        && let Some(i) = e.variants.iter().position(|v| v.name == *variant)
+                    {
+                        *variant = format!("V{i}");
+                    }
+                    *enum_name = renamed[enum_name];
+                }
+            }
+        }
+        result.definitions.insert(name, def);
+    }
+    result
+}
+
+/// Prints a module as Onyx source, in source order and without comments.
+///
+/// Equivalent to [`OnyxModule::to_idl`].
+pub fn print_schema(module: &OnyxModule) -> String {
+    module.to_idl()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::Parser;
+
+    #[test]
+    fn test_obfuscate() {
+        let source = "endian = big\n\
+                      /// Internal status codes.\n\
+                      @open enum Status : u8 { Active = 1, Inactive, }\n\
+                      @deprecated(\"use User\") @packed_bits struct Header { version u32 : 4, tag Status, }\n\
+                      message User { id u64, @deprecated(\"use id\") hdr Header, tags vec<Status, 3>, }\n\
+                      flags Access : u8 { Read, Write, @composite All = 3, }";
+        let module = Parser::new(source).unwrap().parse_module().unwrap();
+        let obfuscated = obfuscate(&module);
+
+        assert_eq!(
+            obfuscated.schema(),
+            "endian = big\n\
+             \n\
+             @open enum T0 : u8 {\n    V0 = 1,\n    V1,\n}\n\
+             \n\
+             @packed_bits struct T1 {\n    f0 u32 : 4,\n    f1 T0,\n}\n\
+             \n\
+             message T2 {\n    f0 u64,\n    f1 T1,\n    f2 vec<T0, 3>,\n}\n\
+             \n\
+             flags T3 : u8 {\n    V0,\n    V1,\n    @composite V2 = 3,\n}\n"
+        );
+        assert_eq!(obfuscated.mapping[0], ("T0".into(), "Status".into()));
+        assert_eq!(
+            obfuscated.mapping[4],
+            ("T1.f0".into(), "Header.version".into())
+        );
+        assert!(
+            obfuscated
+                .mapping_json()
+                .contains("\"T2.f1\": \"User.hdr\"")
+        );
+
+        // The exported schema parses to the same layout.
+        let exported = Parser::new(&obfuscated.schema())
+            .unwrap()
+            .parse_module()
+            .unwrap();
+        for (id, def) in &module.definitions {
+            let renamed = Symbol::intern(
+                &obfuscated
+                    .mapping
+                    .iter()
+                    .find(|(_, o)| o == id.as_str())
+                    .unwrap()
+                    .0,
+            );
+            assert_eq!(exported.definitions[&renamed].size(), def.size());
+        }
+    }
+
+    #[test]
+    fn test_obfuscate_versions() {
+        let source = "version = 2\n\
+                      message User { @removed(2) name u8, id u32, @since(2) email u16, }";
+        let module = Parser::new(source).unwrap().parse_module().unwrap();
+        let obfuscated = obfuscate(&module);
+
+        assert_eq!(
+            obfuscated.schema(),
+            "endian = little\n\
+             version = 2\n\
+             \n\
+             message T0 {\n    @removed(2) f0 u8,\n    f1 u32,\n    @since(2) f2 u16,\n}\n"
+        );
+        assert_eq!(obfuscated.mapping[1], ("T0.f0".into(), "User.name".into()));
+    }
+
+    #[test]
+    fn test_obfuscate_conditions() {
+        let source = "enum Kind : u8 { Ping, Data, }\n\
+                      message Packet { kind Kind, len u8 if kind == Kind.Data, }";
+        let module = Parser::new(source).unwrap().parse_module().unwrap();
+        let obfuscated = obfuscate(&module);
+
+        assert!(
+            obfuscated
+                .schema()
+                .contains("message T1 {\n    f0 T0,\n    f1 u8 if f0 == T0.V1,\n}\n")
+        );
+        assert!(
+            Parser::new(&obfuscated.schema())
+                .unwrap()
+                .parse_module()
+                .is_ok()
+        );
+    }
+}
@@ -1,102 +1,531 @@
-use std::{collections::HashMap, fmt};
+use std::collections::{HashMap, HashSet};
 
 use crate::{
     ast::{
-        Definition, EnumDef, EnumVariant, Field, MessageDef, OnyxModule, PrimitiveType, StructDef,
-        Type, WireEndianness,
+        Alignment, Condition, ConditionValue, Definition, EnumDef, EnumVariant, Field, MessageDef,
+        OnyxModule, PrimitiveType, StructDef, Type, WireEndianness,
     },
-    color,
-    lexer::{Lexer, Token, TokenKind},
+    diagnostic::{Diagnostic, codes},
+    generators::upper_camel_case,
+    hir,
+    lexer::{self, Lexer, Token, TokenKind, TokenStream},
+    limits::ParseLimits,
+    span::{LineIndex, Span},
+    symbol::Symbol,
+    trace,
 };
 
+/// The deepest nesting the parser accepts whatever its limits, shallow enough that hostile input
+/// cannot exhaust the stack.
+const MAX_DEPTH: usize = 256;
+
 /// The `Parser` struct is responsible for parsing Onyx source code into an AST (`OnyxModule`).
 pub struct Parser<'a> {
-    lexer: Lexer<'a>,
-    current_token: Token,
+    source: &'a str,
+    tokens: TokenStream<'a>,
+    current_token: Token<'a>,
+    /// The end of the token before the current one, so that the comments between them can
+    /// be read.
+    previous_end: usize,
     module: OnyxModule,
+    /// The `const` declarations seen so far, with their values and the spans of their names.
+    constants: HashMap<&'a str, (u64, Span)>,
+    /// The errors recovered from so far.
+    errors: Vec<ParseError>,
+    /// The features enabled for `@cfg` attributes.
+    features: HashSet<String>,
+    /// Whether the definition just parsed is marked with a `@cfg` that is not satisfied.
+    cfg_excluded: bool,
+    limits: ParseLimits,
+    /// The number of tokens read so far.
+    tokens_read: usize,
+    /// How deeply the expression or predicate being parsed is nested.
+    depth: usize,
+}
+
+/// The attributes written before a field.
+#[derive(Default)]
+struct FieldAttributes {
+    since: Option<u32>,
+    removed: Option<u32>,
+    deprecated: Option<String>,
+    /// Whether a `@cfg` attribute is not satisfied.
+    excluded: bool,
 }
 
 impl<'a> Parser<'a> {
     /// Creates a new parser and grabs the first token.
     pub fn new(source: &'a str) -> Result<Self, ParseError> {
-        let mut lexer = Lexer::new(source);
+        let mut tokens = TokenStream::new(source);
         // Get the first token to start parsing
-        let current_token = lexer
+        let current_token = tokens
             .next()
-            .ok_or_else(|| ParseError("Empty source file.".to_string()))?;
+            .ok_or_else(|| Diagnostic::error("empty source file").with_code(codes::EMPTY_SOURCE))?;
+        if let Some(error) = lexer::token_error(source, &current_token) {
+            return Err(error);
+        }
 
         Ok(Parser {
-            lexer,
+            source,
+            tokens,
             current_token,
+            previous_end: 0,
             module: OnyxModule::default(),
+            constants: HashMap::new(),
+            errors: Vec::new(),
+            features: HashSet::new(),
+            cfg_excluded: false,
+            limits: ParseLimits::default(),
+            tokens_read: 1,
+            depth: 0,
         })
     }
 
+    /// Enables `features` for `@cfg` attributes. Definitions and fields whose `@cfg` is not
+    /// satisfied are left out of the module, as if they were not written; none are enabled by
+    /// default.
+    ///
+    /// ```rust
+    /// use onyx::parser::Parser;
+    /// use onyx::resolve;
+    ///
+    /// let source = "struct Fix { @cfg(feature = \"gps\") lat i32, speed u16, }";
+    /// let module = Parser::new(source).unwrap().parse_module().unwrap();
+    /// assert_eq!(resolve::size_of(&module, "Fix").unwrap(), 2);
+    ///
+    /// let module = Parser::new(source)
+    ///     .unwrap()
+    ///     .with_features(["gps"])
+    ///     .parse_module()
+    ///     .unwrap();
+    /// assert_eq!(resolve::size_of(&module, "Fix").unwrap(), 6);
+    /// ```
+    pub fn with_features<S: Into<String>>(mut self, features: impl IntoIterator<Item = S>) -> Self {
+        self.features = features.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Caps the size of the source, the tokens read, the nesting depth, and the definitions
+    /// parsed, for schemas from untrusted sources. Input over a limit is reported as an error
+    /// with code [`LIMIT_EXCEEDED`](codes::LIMIT_EXCEEDED); parsing stops at the first token
+    /// over `max_tokens`.
+    ///
+    /// ```rust
+    /// use onyx::limits::ParseLimits;
+    /// use onyx::parser::Parser;
+    ///
+    /// let mut limits = ParseLimits::default();
+    /// limits.max_tokens = Some(8);
+    /// let error = Parser::new("struct S { a u8, b u8, }")
+    ///     .unwrap()
+    ///     .with_limits(limits)
+    ///     .parse_module()
+    ///     .unwrap_err();
+    /// assert_eq!(error.message(), "schema has more than 8 tokens");
+    /// ```
+    pub fn with_limits(mut self, limits: ParseLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    /// Returns the deepest nesting allowed.
+    fn max_depth(&self) -> usize {
+        self.limits
+            .max_depth
+            .map_or(MAX_DEPTH, |max| max.min(MAX_DEPTH))
+    }
+
+    /// Interns the name `name` at `span`, failing if it is new and the symbol table is full.
+    fn symbol(&self, name: &str, span: Span) -> Result<Symbol, ParseError> {
+        let max = self.limits.max_symbol_bytes.unwrap_or(usize::MAX);
+        Symbol::intern_within(name, max).ok_or_else(|| {
+            self.error_at(
+                codes::LIMIT_EXCEEDED,
+                span,
+                format!("name '{name}' is over the limit of {max} bytes of interned names"),
+            )
+            .with_note("interned names are kept for as long as the process runs")
+        })
+    }
+
+    /// Runs `parse` one level of nesting deeper, failing at `span` if that is too deep.
+    fn nested<T>(
+        &mut self,
+        what: &str,
+        span: Span,
+        parse: impl FnOnce(&mut Self) -> Result<T, ParseError>,
+    ) -> Result<T, ParseError> {
+        let max = self.max_depth();
+        if self.depth >= max {
+            return Err(self.error_at(
+                codes::LIMIT_EXCEEDED,
+                span,
+                format!("{what} nests more than {max} deep"),
+            ));
+        }
+        self.depth += 1;
+        let result = parse(self);
+        self.depth -= 1;
+        result
+    }
+
     /// Advances the parser to the next token.
-    fn advance(&mut self) {
-        // Fetch the next token from the iterator, or use EOF if none is available
-        self.current_token = self.lexer.next().unwrap_or(Token {
+    ///
+    /// Fails if the lexer produced an error token, reporting the lexer's message at the
+    /// offending text rather than letting the token surface later as a grammar mismatch.
+    fn advance(&mut self) -> Result<(), ParseError> {
+        self.bump();
+        match lexer::token_error(self.source, &self.current_token) {
+            Some(error) => Err(error),
+            None => Ok(()),
+        }
+    }
+
+    /// Moves to the next token, even if it is an error token.
+    ///
+    /// Past `max_tokens`, records an error and stays at the end of the source.
+    fn bump(&mut self) {
+        let eof = Token {
             kind: TokenKind::Eof,
-            position: self.current_token.position, // Use the last known position
-        });
+            span: self.current_token.span, // Use the last known position
+        };
+        if let Some(max) = self.limits.max_tokens
+            && self.tokens_read >= max
+        {
+            if self.current_token.kind != TokenKind::Eof
+                && let Some(next) = self.tokens.peek()
+            {
+                let error = self.error_at(
+                    codes::LIMIT_EXCEEDED,
+                    next.span,
+                    format!("schema has more than {max} tokens"),
+                );
+                self.errors.push(error);
+            }
+            self.current_token = eof;
+            return;
+        }
+        self.tokens_read += 1;
+        self.previous_end = self.current_token.span.end;
+        // Fetch the next token from the iterator, or use EOF if none is available
+        self.current_token = self.tokens.next().unwrap_or(eof);
+    }
+
+    /// Returns the kind of the token after the current one, without advancing.
+    fn peek(&mut self) -> TokenKind<'a> {
+        self.tokens
+            .peek()
+            .map_or(TokenKind::Eof, |token| token.kind)
     }
 
     /// Checks if the current token matches an expected kind, consumes it, and advances.
     /// Returns an error if the current token does not match the expected kind.
     fn consume(&mut self, expected: TokenKind) -> Result<(), ParseError> {
         if self.current_token.kind == expected {
-            self.advance();
+            self.advance()?;
             Ok(())
         } else {
-            let msg = format!(
-                "expected {:?}, found {:?} at position {}\n{}",
-                expected,
-                self.current_token.kind,
-                self.current_token.position,
-                self.lexer.display_token_in_context(&self.current_token)
-            );
-            Err(ParseError(msg))
+            Err(self.unexpected(&format!("{expected:?}")))
         }
     }
 
+    /// Builds an error pointing at `span`, with the surrounding source attached.
+    fn error_at(&self, code: &'static str, span: Span, message: impl Into<String>) -> ParseError {
+        Diagnostic::error(message)
+            .with_code(code)
+            .with_span(span)
+            .with_source(self.source)
+    }
+
+    /// Builds an error for the current token, which did not match `expected`.
+    fn unexpected(&self, expected: &str) -> ParseError {
+        self.error_at(
+            codes::UNEXPECTED_TOKEN,
+            self.current_token.span,
+            format!("expected {expected}, found {:?}", self.current_token.kind),
+        )
+    }
+
     // --- Core Parsing Functions ---
 
     /// Parses the entire Onyx module.
     ///
     /// This is the main entry point for the parser. It parses top-level definitions
     /// (endianness, messages, structs, enums) and resolves types and sizes.
-    pub fn parse_module(mut self) -> Result<OnyxModule, ParseError> {
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "info", skip_all))]
+    pub fn parse_module(self) -> Result<OnyxModule, ParseError> {
+        let (source, max_depth) = (self.source, self.max_depth());
+        let module = self.parse_module_unresolved()?;
+        check_nesting(&module, max_depth).map_err(|(_, error)| error.with_source(source))?;
+        resolve(module).map_err(|(_, error)| error.with_source(source))
+    }
+
+    /// Parses the top-level definitions without resolving types and sizes.
+    ///
+    /// The module may use types it does not define, and its structs and messages have no size
+    /// or order yet. Tools such as formatters and linters can work on it as is; pass it to
+    /// [`resolve_module`](crate::resolve::resolve_module) to check and lay it out. Only syntax
+    /// errors are reported here.
+    pub fn parse_module_unresolved(self) -> Result<OnyxModule, ParseError> {
+        let (module, errors) = self.parse_module_recovering();
+        match errors.into_iter().next() {
+            Some(error) => Err(error),
+            None => Ok(module),
+        }
+    }
+
+    /// Parses the top-level definitions like [`parse_module_unresolved`], but carries on past
+    /// syntax errors so that tools such as language servers can work on a file being edited.
+    ///
+    /// A field that does not parse is skipped up to the next `,` or `}` of its definition,
+    /// and anything else up to the next top-level keyword. Returns what did parse, with an
+    /// error for each region skipped, in source order.
+    ///
+    /// [`parse_module_unresolved`]: Parser::parse_module_unresolved
+    pub fn parse_module_recovering(mut self) -> (OnyxModule, Vec<ParseError>) {
+        if let Some(max) = self.limits.max_source_bytes
+            && self.source.len() > max
+        {
+            let error = Diagnostic::error(format!(
+                "schema is {} bytes, over the limit of {max}",
+                self.source.len()
+            ))
+            .with_code(codes::LIMIT_EXCEEDED);
+            return (self.module, vec![error]);
+        }
         let mut endianness_set = false;
+        let mut strict_set = false;
+        let mut bool_width_set = false;
+        let mut align_set = false;
 
         while self.current_token.kind != TokenKind::Eof {
-            // A top-level definition must start with a keyword
-            // Check for endian keyword
-            if self.current_token.kind == TokenKind::Endianness && !endianness_set {
-                self.module.endianness = self.parse_endianness_directive()?;
-                endianness_set = true;
-            } else if self.current_token.kind == TokenKind::Endianness && endianness_set {
-                return Err(ParseError(format!(
-                    "expected one endianness definition, found a second at position {}\n{}",
-                    self.current_token.position,
-                    self.lexer.display_token_in_context(&self.current_token)
-                )));
+            let start = self.current_token.span;
+            if let Err(error) = self.parse_item(
+                &mut endianness_set,
+                &mut strict_set,
+                &mut bool_width_set,
+                &mut align_set,
+            ) {
+                self.errors.push(error);
+                if self.current_token.span == start {
+                    self.bump();
+                }
+                while !self.at_item_start() {
+                    self.bump();
+                }
             }
+        }
 
-            // Check for message, struct, or enum keywords
-            let def = self.parse_definition()?;
-            if !self.module.definitions.contains_key(def.name()) {
-                self.module.definitions.insert(def.name().to_string(), def);
-            } else {
-                return Err(ParseError(format!(
-                    "{} already exists, found second definition at position {}\n{}",
-                    def.name(),
-                    self.current_token.position,
-                    self.lexer.display_token_in_context(&self.current_token)
-                )));
+        (self.module, self.errors)
+    }
+
+    /// Parses a directive, `const` declaration, or definition, adding it to the module.
+    fn parse_item(
+        &mut self,
+        endianness_set: &mut bool,
+        strict_set: &mut bool,
+        bool_width_set: &mut bool,
+        align_set: &mut bool,
+    ) -> Result<(), ParseError> {
+        // A top-level definition must start with a keyword
+        // Check for endian keyword
+        if self.current_token.kind == TokenKind::Endianness {
+            if *endianness_set {
+                return Err(self
+                    .error_at(
+                        codes::INVALID_ENDIANNESS,
+                        self.current_token.span,
+                        "expected one endianness definition, found a second",
+                    )
+                    .with_help("remove the duplicate 'endian' directive"));
             }
+            self.module.endianness = self.parse_endianness_directive()?;
+            *endianness_set = true;
+            return Ok(());
         }
 
-        self.resolve_module()
+        if self.current_token.kind == TokenKind::Identifier("version") {
+            let span = self.current_token.span;
+            let version = self.parse_version_directive()?;
+            if let Some(max) = self.limits.max_version
+                && version > max
+            {
+                return Err(self.error_at(
+                    codes::LIMIT_EXCEEDED,
+                    span,
+                    format!("schema version {version} is over the limit of {max}"),
+                ));
+            }
+            if self.module.version.is_some() {
+                return Err(self
+                    .error_at(
+                        codes::INVALID_VERSION,
+                        span,
+                        "expected one version definition, found a second",
+                    )
+                    .with_help("remove the duplicate 'version' directive"));
+            }
+            self.module.version = Some(version);
+            return Ok(());
+        }
+
+        if self.current_token.kind == TokenKind::Identifier("strict") {
+            let span = self.current_token.span;
+            let strict = self.parse_strict_directive()?;
+            if *strict_set {
+                return Err(self
+                    .error_at(
+                        codes::STRICT_LAYOUT,
+                        span,
+                        "expected one strict directive, found a second",
+                    )
+                    .with_help("remove the duplicate 'strict' directive"));
+            }
+            self.module.strict = strict;
+            *strict_set = true;
+            return Ok(());
+        }
+
+        if self.current_token.kind == TokenKind::Identifier("bool_width") {
+            let span = self.current_token.span;
+            let packed_bools = self.parse_bool_width_directive()?;
+            if *bool_width_set {
+                return Err(self
+                    .error_at(
+                        codes::INVALID_BIT_FIELD,
+                        span,
+                        "expected one bool_width directive, found a second",
+                    )
+                    .with_help("remove the duplicate 'bool_width' directive"));
+            }
+            self.module.packed_bools = packed_bools;
+            *bool_width_set = true;
+            return Ok(());
+        }
+
+        if self.current_token.kind == TokenKind::Identifier("align") {
+            let span = self.current_token.span;
+            let alignment = self.parse_align_directive()?;
+            if *align_set {
+                return Err(self
+                    .error_at(
+                        codes::INVALID_ALIGNMENT,
+                        span,
+                        "expected one align directive, found a second",
+                    )
+                    .with_help("remove the duplicate 'align' directive"));
+            }
+            self.module.alignment = alignment;
+            *align_set = true;
+            return Ok(());
+        }
+
+        if self.current_token.kind == TokenKind::Identifier("const") {
+            return self.parse_const_declaration();
+        }
+
+        // Check for message, struct, or enum keywords
+        let doc = self.doc_comment();
+        let def = self.parse_definition().map(|mut def| {
+            match &mut def {
+                Definition::Message(m) => m.doc = doc,
+                Definition::Struct(s) => s.doc = doc,
+                Definition::Enum(e) => e.doc = doc,
+            }
+            def
+        });
+        if std::mem::take(&mut self.cfg_excluded) {
+            return def.map(|_| ());
+        }
+        let def = def?;
+        trace::debug!(
+            definition = def.name(),
+            start = def.span().start,
+            "parsed definition"
+        );
+        if let Some(max) = self.limits.max_definitions
+            && self.module.definitions.len() >= max
+        {
+            return Err(self.error_at(
+                codes::LIMIT_EXCEEDED,
+                def.span(),
+                format!("schema has more than {max} definitions"),
+            ));
+        }
+        if let Some(existing) = self.module.definitions.get(&def.symbol()) {
+            return Err(self
+                .error_at(
+                    codes::DUPLICATE_DEFINITION,
+                    def.span(),
+                    format!("{} already exists, found second definition", def.name()),
+                )
+                .with_note(format!(
+                    "first defined at position {}",
+                    LineIndex::new(self.source).position(existing.span().start)
+                )));
+        }
+        self.module.definitions.insert(def.symbol(), def);
+        Ok(())
+    }
+
+    /// Returns the doc comment before the current token: its `///` lines, without the markers
+    /// and the space after them, or `None` if there are none.
+    fn doc_comment(&self) -> Option<String> {
+        let gap = self
+            .source
+            .get(self.previous_end..self.current_token.span.start)?;
+        let lines: Vec<&str> = gap
+            .lines()
+            .filter_map(|line| line.trim_start().strip_prefix("///"))
+            .filter(|line| !line.starts_with('/'))
+            .map(|line| line.strip_prefix(' ').unwrap_or(line).trim_end())
+            .collect();
+        (!lines.is_empty()).then(|| lines.join("\n"))
+    }
+
+    /// Returns true at the end of the source or at a keyword that starts a top-level item.
+    fn at_item_start(&self) -> bool {
+        matches!(
+            self.current_token.kind,
+            TokenKind::Eof
+                | TokenKind::Endianness
+                | TokenKind::Message
+                | TokenKind::Struct
+                | TokenKind::Enum
+                | TokenKind::At
+                | TokenKind::Identifier(
+                    "flags" | "version" | "strict" | "bool_width" | "align" | "const"
+                )
+        )
+    }
+
+    /// Skips the rest of a field that failed to parse: up to and including the next `,` that is
+    /// not nested in brackets, or up to the `}` closing the definition.
+    fn skip_field(&mut self, start: Span) {
+        if self.current_token.span == start && self.current_token.kind != TokenKind::Eof {
+            self.bump();
+        }
+        let mut depth = 0usize;
+        loop {
+            match self.current_token.kind {
+                TokenKind::OpenAngle | TokenKind::OpenParen | TokenKind::OpenBracket => depth += 1,
+                TokenKind::CloseAngle | TokenKind::CloseParen | TokenKind::CloseBracket => {
+                    depth = depth.saturating_sub(1)
+                }
+                TokenKind::Comma if depth == 0 => {
+                    self.bump();
+                    return;
+                }
+                TokenKind::CloseBrace
+                | TokenKind::Eof
+                | TokenKind::Endianness
+                | TokenKind::Message
+                | TokenKind::Struct
+                | TokenKind::Enum => return,
+                _ => {}
+            }
+            self.bump();
+        }
     }
 
     /// Parses the endianness directive (e.g., `endian = big;`).
@@ -104,31 +533,267 @@ impl<'a> Parser<'a> {
         self.consume(TokenKind::Endianness)?;
         self.consume(TokenKind::Assign)?;
 
-        let endianness = match &self.current_token.kind {
-            TokenKind::Identifier(s) => match s.as_str() {
+        let endianness = match self.current_token.kind {
+            TokenKind::Identifier(s) => match s {
                 "big" => WireEndianness::Big,
                 "little" => WireEndianness::Little,
+                "host" => WireEndianness::Host,
                 _ => {
-                    return Err(ParseError(format!(
-                        "expected 'big' or 'little' for endianness, found '{}' at position {}\n{}",
-                        s,
-                        self.current_token.position,
-                        self.lexer.display_token_in_context(&self.current_token)
-                    )));
+                    return Err(self.error_at(
+                        codes::INVALID_ENDIANNESS,
+                        self.current_token.span,
+                        format!("expected 'big', 'little', or 'host' for endianness, found '{s}'"),
+                    ));
                 }
             },
+            _ => return Err(self.unexpected("'big', 'little', or 'host' for endianness")),
+        };
+        self.advance()?; // consume Big/Little/Host
+
+        Ok(endianness)
+    }
+
+    /// Parses the version directive (e.g., `version = 3`).
+    ///
+    /// `version` is not reserved: it only starts the directive at the top level, so fields
+    /// named `version` still work.
+    fn parse_version_directive(&mut self) -> Result<u32, ParseError> {
+        self.advance()?; // consume `version`
+        self.consume(TokenKind::Assign)?;
+        let version = self.parse_version_number()?;
+        Ok(version)
+    }
+
+    /// Parses the strict directive, `strict = true` or `strict = false`.
+    ///
+    /// Like `version`, `strict` only starts the directive at the top level.
+    fn parse_strict_directive(&mut self) -> Result<bool, ParseError> {
+        self.advance()?; // consume `strict`
+        self.consume(TokenKind::Assign)?;
+        let strict = match self.current_token.kind {
+            TokenKind::Identifier("true") => true,
+            TokenKind::Identifier("false") => false,
             _ => {
-                return Err(ParseError(format!(
-                    "expected 'big' or 'little' for endianness, found {:?} at position {}\n{}",
-                    self.current_token.kind,
-                    self.current_token.position,
-                    self.lexer.display_token_in_context(&self.current_token)
-                )));
+                return Err(self
+                    .error_at(
+                        codes::STRICT_LAYOUT,
+                        self.current_token.span,
+                        format!(
+                            "expected 'true' or 'false', found {:?}",
+                            self.current_token.kind
+                        ),
+                    )
+                    .with_help("write 'strict = true' to reject layout surprises"));
             }
         };
-        self.advance(); // consume Big/Little
+        self.advance()?;
+        Ok(strict)
+    }
 
-        Ok(endianness)
+    /// Parses the bool width directive, `bool_width = 1` or `bool_width = 8`, returning true
+    /// for one-bit bools.
+    fn parse_bool_width_directive(&mut self) -> Result<bool, ParseError> {
+        self.advance()?; // consume `bool_width`
+        self.consume(TokenKind::Assign)?;
+        let packed_bools = match self.current_token.kind {
+            TokenKind::LiteralInt(1) => true,
+            TokenKind::LiteralInt(8) => false,
+            _ => {
+                return Err(self
+                    .error_at(
+                        codes::INVALID_BIT_FIELD,
+                        self.current_token.span,
+                        format!("expected 1 or 8, found {:?}", self.current_token.kind),
+                    )
+                    .with_help(
+                        "write 'bool_width = 1' to pack bools into single bits, or \
+                         'bool_width = 8' to give each a byte",
+                    ));
+            }
+        };
+        self.advance()?;
+        Ok(packed_bools)
+    }
+
+    /// Parses the align directive, `align = packed`, `align = xcdr1`, or `align = xcdr2`.
+    ///
+    /// Like `version`, `align` only starts the directive at the top level.
+    fn parse_align_directive(&mut self) -> Result<Alignment, ParseError> {
+        self.advance()?; // consume `align`
+        self.consume(TokenKind::Assign)?;
+        let alignment = match self.current_token.kind {
+            TokenKind::Identifier("packed") => Alignment::Packed,
+            TokenKind::Identifier("xcdr1") => Alignment::Xcdr1,
+            TokenKind::Identifier("xcdr2") => Alignment::Xcdr2,
+            _ => {
+                return Err(self
+                    .error_at(
+                        codes::INVALID_ALIGNMENT,
+                        self.current_token.span,
+                        format!(
+                            "expected 'packed', 'xcdr1', or 'xcdr2', found {:?}",
+                            self.current_token.kind
+                        ),
+                    )
+                    .with_help(
+                        "write 'align = xcdr2' to pad fields as DDS does with XCDR2, or \
+                         'align = packed' for no padding",
+                    ));
+            }
+        };
+        self.advance()?;
+        Ok(alignment)
+    }
+
+    /// Parses a schema version: an integer from 1 up.
+    fn parse_version_number(&mut self) -> Result<u32, ParseError> {
+        let TokenKind::LiteralInt(value) = self.current_token.kind else {
+            return Err(self.unexpected("a version number"));
+        };
+        let Some(version) = u32::try_from(value).ok().filter(|&v| v > 0) else {
+            return Err(self
+                .error_at(
+                    codes::INVALID_VERSION,
+                    self.current_token.span,
+                    format!("version {value} is out of range"),
+                )
+                .with_help(format!("versions count up from 1 to {}", u32::MAX)));
+        };
+        self.advance()?;
+        Ok(version)
+    }
+
+    /// Parses a constant declaration (e.g., `const WIDTH = 1 << 4`).
+    ///
+    /// Like `version`, `const` is only a keyword at the top level. A constant can be used in
+    /// expressions after its declaration in the same file.
+    fn parse_const_declaration(&mut self) -> Result<(), ParseError> {
+        self.advance()?; // consume `const`
+        let span = self.current_token.span;
+        let name = self.consume_identifier()?;
+        self.consume(TokenKind::Assign)?;
+        let (value, _) = self.parse_expression("a constant value")?;
+        if let Some((_, first)) = self.constants.get(name) {
+            return Err(self
+                .error_at(
+                    codes::DUPLICATE_DEFINITION,
+                    span,
+                    format!("constant {name} already exists, found second declaration"),
+                )
+                .with_note(format!(
+                    "first declared at position {}",
+                    LineIndex::new(self.source).position(first.start)
+                )));
+        }
+        self.constants.insert(name, (value, span));
+        Ok(())
+    }
+
+    /// Parses and evaluates a constant expression: integer literals and constants combined
+    /// with `+`, `-`, `*`, `/`, `%`, `<<`, and `>>`, and grouped with parentheses.
+    ///
+    /// Shifts bind more loosely than `+` and `-`, which bind more loosely than `*`, `/`, and
+    /// `%`, as in C and Rust. Values are `u64`, and a result outside its range is an error.
+    /// `expected` describes the value for the error when no expression is found.
+    fn parse_expression(&mut self, expected: &str) -> Result<(u64, Span), ParseError> {
+        let (mut value, mut span) = self.parse_sum(expected)?;
+        while let op @ (TokenKind::ShiftLeft | TokenKind::ShiftRight) = self.current_token.kind {
+            self.advance()?;
+            let (rhs, rhs_span) = self.parse_sum(expected)?;
+            span = Span::new(span.start, rhs_span.end);
+            value = self.apply(op, value, rhs, span)?;
+        }
+        Ok((value, span))
+    }
+
+    /// Parses the operands of `+` and `-`.
+    fn parse_sum(&mut self, expected: &str) -> Result<(u64, Span), ParseError> {
+        let (mut value, mut span) = self.parse_product(expected)?;
+        while let op @ (TokenKind::Plus | TokenKind::Minus) = self.current_token.kind {
+            self.advance()?;
+            let (rhs, rhs_span) = self.parse_product(expected)?;
+            span = Span::new(span.start, rhs_span.end);
+            value = self.apply(op, value, rhs, span)?;
+        }
+        Ok((value, span))
+    }
+
+    /// Parses the operands of `*`, `/`, and `%`.
+    fn parse_product(&mut self, expected: &str) -> Result<(u64, Span), ParseError> {
+        let (mut value, mut span) = self.parse_operand(expected)?;
+        while let op @ (TokenKind::Star | TokenKind::Slash | TokenKind::Percent) =
+            self.current_token.kind
+        {
+            self.advance()?;
+            let (rhs, rhs_span) = self.parse_operand(expected)?;
+            span = Span::new(span.start, rhs_span.end);
+            value = self.apply(op, value, rhs, span)?;
+        }
+        Ok((value, span))
+    }
+
+    /// Parses an integer literal, a constant, or a parenthesized expression.
+    fn parse_operand(&mut self, expected: &str) -> Result<(u64, Span), ParseError> {
+        let span = self.current_token.span;
+        match self.current_token.kind {
+            TokenKind::LiteralInt(value) => {
+                self.advance()?;
+                Ok((value, span))
+            }
+            TokenKind::Identifier(name) | TokenKind::RawIdentifier(name) => {
+                let Some(&(value, _)) = self.constants.get(name) else {
+                    return Err(self
+                        .error_at(
+                            codes::INVALID_EXPRESSION,
+                            span,
+                            format!("constant '{name}' is not defined"),
+                        )
+                        .with_help(format!("declare it with 'const {name} = ...' before use")));
+                };
+                self.advance()?;
+                Ok((value, span))
+            }
+            TokenKind::OpenParen => {
+                self.advance()?;
+                let (value, _) =
+                    self.nested("expression", span, |p| p.parse_expression(expected))?;
+                let end = self.current_token.span.end;
+                self.consume(TokenKind::CloseParen)?;
+                Ok((value, Span::new(span.start, end)))
+            }
+            _ => Err(self.unexpected(expected)),
+        }
+    }
+
+    /// Applies a binary operator, failing if the result is not a `u64`.
+    fn apply(&self, op: TokenKind, lhs: u64, rhs: u64, span: Span) -> Result<u64, ParseError> {
+        let result = match op {
+            TokenKind::Plus => lhs.checked_add(rhs),
+            TokenKind::Minus => lhs.checked_sub(rhs),
+            TokenKind::Star => lhs.checked_mul(rhs),
+            TokenKind::Slash => lhs.checked_div(rhs),
+            TokenKind::Percent => lhs.checked_rem(rhs),
+            // Shifting bits out of the top overflows too.
+            TokenKind::ShiftLeft => u32::try_from(rhs)
+                .ok()
+                .and_then(|rhs| lhs.checked_shl(rhs))
+                .filter(|value| value >> rhs == lhs),
+            TokenKind::ShiftRight => u32::try_from(rhs).ok().and_then(|rhs| lhs.checked_shr(rhs)),
+            _ => unreachable!("{op:?} is not a binary operator"),
+        };
+        result.ok_or_else(|| {
+            let text = &self.source[span.start..span.end];
+            let message = match op {
+                TokenKind::Slash | TokenKind::Percent => format!("'{text}' divides by zero"),
+                TokenKind::Minus => format!("'{text}' is negative"),
+                TokenKind::ShiftLeft | TokenKind::ShiftRight if rhs >= 64 => {
+                    format!("'{text}' shifts by {rhs} bits, past the width of a u64")
+                }
+                _ => format!("'{text}' overflows a u64"),
+            };
+            self.error_at(codes::INVALID_EXPRESSION, span, message)
+                .with_note("constant expressions evaluate to unsigned 64-bit integers")
+        })
     }
 
     /// Parses a top-level definition: message, struct, or enum.
@@ -137,124 +802,626 @@ impl<'a> Parser<'a> {
             TokenKind::Message => self.parse_message(),
             TokenKind::Struct => self.parse_struct(),
             TokenKind::Enum => self.parse_enum(),
+            TokenKind::Identifier("flags") => self.parse_enum(),
+            TokenKind::At => self.parse_definition_attribute(),
+            _ => Err(self.unexpected("'message', 'struct', 'enum', or 'flags'")),
+        }
+    }
+
+    /// Parses a message marked `@id(N)`, a message or struct marked `@max_size(N)` or
+    /// `@packed_bits`, an enum marked `@open` or `@closed`, or a definition marked
+    /// `@deprecated("note")` or `@cfg(predicate)`.
+    fn parse_definition_attribute(&mut self) -> Result<Definition, ParseError> {
+        let attribute_span = self.current_token.span;
+        self.advance()?;
+        let attribute = self.consume_identifier()?;
+        let open = match attribute {
+            "id" => return self.parse_message_id(attribute_span),
+            "max_size" => return self.parse_max_size(attribute_span),
+            "packed_bits" => return self.parse_packed_bits(attribute_span),
+            "deprecated" => return self.parse_deprecated(attribute_span),
+            "cfg" => {
+                let enabled = self.parse_cfg_attribute()?;
+                let def = self.parse_definition()?;
+                self.cfg_excluded |= !enabled;
+                return Ok(def);
+            }
+            "open" => true,
+            "closed" => false,
             _ => {
-                let msg = format!(
-                    "expected 'message', 'struct', or 'enum', found {:?} at posiiton {}\n{}",
-                    self.current_token.kind,
-                    self.current_token.position,
-                    self.lexer.display_token_in_context(&self.current_token)
-                );
-                Err(ParseError(msg))
+                return Err(self
+                    .error_at(
+                        codes::INVALID_ATTRIBUTE,
+                        attribute_span,
+                        format!("attribute '@{attribute}' is not allowed on a definition"),
+                    )
+                    .with_help(
+                        "a message can be marked '@id(N)', a message or struct \
+                         '@max_size(N)' or '@packed_bits', an enum '@open' or '@closed', and any definition \
+                         '@deprecated(\"note\")' or '@cfg(feature = \"name\")'",
+                    ));
             }
+        };
+        if self.current_token.kind != TokenKind::Enum {
+            return Err(self
+                .error_at(
+                    codes::INVALID_ATTRIBUTE,
+                    attribute_span,
+                    format!("attribute '@{attribute}' is only allowed on an enum"),
+                )
+                .with_help("flags enums always keep undeclared bits"));
+        }
+        let Definition::Enum(mut def) = self.parse_enum()? else {
+            unreachable!("parse_enum returns an enum");
+        };
+        def.open = open;
+        if open && let Some(variant) = def.variants.iter().find(|v| v.name == "Unknown") {
+            return Err(self
+                .error_at(
+                    codes::INVALID_ATTRIBUTE,
+                    variant.span,
+                    "an open enum cannot declare a variant named 'Unknown'",
+                )
+                .with_help("open enums decode values no variant declares as 'Unknown'"));
         }
+        Ok(Definition::Enum(def))
+    }
+
+    /// Parses the `(N)` of `@id(N)` and the message it marks.
+    fn parse_message_id(&mut self, attribute_span: Span) -> Result<Definition, ParseError> {
+        self.consume(TokenKind::OpenParen)?;
+        let (value, span) = self.parse_expression("a message ID")?;
+        let Ok(id) = u16::try_from(value) else {
+            return Err(self
+                .error_at(
+                    codes::INVALID_MESSAGE_ID,
+                    span,
+                    format!("message ID {value} is out of range"),
+                )
+                .with_help(format!("message IDs go from 0 to {}", u16::MAX)));
+        };
+        self.consume(TokenKind::CloseParen)?;
+        let not_a_message = |parser: &Self| {
+            parser
+                .error_at(
+                    codes::INVALID_ATTRIBUTE,
+                    attribute_span,
+                    "attribute '@id' is only allowed on a message",
+                )
+                .with_help("structs are only sent inside messages, so they need no ID")
+        };
+        if !matches!(self.current_token.kind, TokenKind::Message | TokenKind::At) {
+            return Err(not_a_message(self));
+        }
+        let Definition::Message(mut def) = self.parse_definition()? else {
+            return Err(not_a_message(self));
+        };
+        def.id = Some(id);
+        Ok(Definition::Message(def))
+    }
+
+    /// Parses the `(N)` of `@max_size(N)` and the message or struct it marks.
+    fn parse_max_size(&mut self, attribute_span: Span) -> Result<Definition, ParseError> {
+        self.consume(TokenKind::OpenParen)?;
+        let (value, span) = self.parse_expression("a size in bytes")?;
+        let Ok(max_size) = usize::try_from(value) else {
+            return Err(self.error_at(
+                codes::INVALID_ATTRIBUTE,
+                span,
+                format!("maximum size {value} is out of range"),
+            ));
+        };
+        self.consume(TokenKind::CloseParen)?;
+        let not_allowed = |parser: &Self| {
+            parser
+                .error_at(
+                    codes::INVALID_ATTRIBUTE,
+                    attribute_span,
+                    "attribute '@max_size' is only allowed on a message or struct",
+                )
+                .with_help("enums always have the size of their underlying type")
+        };
+        if !matches!(
+            self.current_token.kind,
+            TokenKind::Message | TokenKind::Struct | TokenKind::At
+        ) {
+            return Err(not_allowed(self));
+        }
+        match self.parse_definition()? {
+            Definition::Message(mut def) => {
+                def.max_size = Some(max_size);
+                Ok(Definition::Message(def))
+            }
+            Definition::Struct(mut def) => {
+                def.max_size = Some(max_size);
+                Ok(Definition::Struct(def))
+            }
+            Definition::Enum(_) => Err(not_allowed(self)),
+        }
+    }
+
+    /// Parses the message or struct marked `@packed_bits`.
+    fn parse_packed_bits(&mut self, attribute_span: Span) -> Result<Definition, ParseError> {
+        let not_allowed = |parser: &Self| {
+            parser
+                .error_at(
+                    codes::INVALID_ATTRIBUTE,
+                    attribute_span,
+                    "attribute '@packed_bits' is only allowed on a message or struct",
+                )
+                .with_help("enums have no bit-fields")
+        };
+        if !matches!(
+            self.current_token.kind,
+            TokenKind::Message | TokenKind::Struct | TokenKind::At
+        ) {
+            return Err(not_allowed(self));
+        }
+        let mut def = self.parse_definition()?;
+        let packed_bits = match &mut def {
+            Definition::Message(def) => &mut def.packed_bits,
+            Definition::Struct(def) => &mut def.packed_bits,
+            Definition::Enum(_) => return Err(not_allowed(self)),
+        };
+        if *packed_bits {
+            return Err(self.repeated_attribute(attribute_span, "packed_bits"));
+        }
+        *packed_bits = true;
+        Ok(def)
+    }
+
+    /// Parses the `("note")` of `@deprecated("note")` and the definition it marks.
+    fn parse_deprecated(&mut self, attribute_span: Span) -> Result<Definition, ParseError> {
+        let note = self.parse_deprecation_note()?;
+        let mut def = self.parse_definition()?;
+        let deprecated = match &mut def {
+            Definition::Message(m) => &mut m.deprecated,
+            Definition::Struct(s) => &mut s.deprecated,
+            Definition::Enum(e) => &mut e.deprecated,
+        };
+        if deprecated.is_some() {
+            return Err(self.repeated_attribute(attribute_span, "deprecated"));
+        }
+        *deprecated = Some(note);
+        Ok(def)
+    }
+
+    /// Parses the `("note")` of a `@deprecated` attribute.
+    fn parse_deprecation_note(&mut self) -> Result<String, ParseError> {
+        self.consume(TokenKind::OpenParen)?;
+        let TokenKind::LiteralString(note) = self.current_token.kind else {
+            return Err(self.unexpected("a note such as \"use v2\""));
+        };
+        self.advance()?;
+        self.consume(TokenKind::CloseParen)?;
+        Ok(note.to_string())
+    }
+
+    /// Parses the `(predicate)` of a `@cfg` attribute, returning whether the enabled features
+    /// satisfy it.
+    fn parse_cfg_attribute(&mut self) -> Result<bool, ParseError> {
+        self.consume(TokenKind::OpenParen)?;
+        let enabled = self.parse_cfg_predicate()?;
+        self.consume(TokenKind::CloseParen)?;
+        Ok(enabled)
+    }
+
+    /// Parses `feature = "name"`, or `not(p)`, `all(p, ...)`, or `any(p, ...)` of other
+    /// predicates, returning whether the enabled features satisfy it.
+    fn parse_cfg_predicate(&mut self) -> Result<bool, ParseError> {
+        let span = self.current_token.span;
+        let key = self.consume_identifier()?;
+        if key == "feature" {
+            self.consume(TokenKind::Assign)?;
+            let TokenKind::LiteralString(feature) = self.current_token.kind else {
+                return Err(self.unexpected("a feature name such as \"gps\""));
+            };
+            self.advance()?;
+            return Ok(self.features.contains(feature));
+        }
+        if !matches!(key, "not" | "all" | "any") {
+            return Err(self
+                .error_at(
+                    codes::INVALID_ATTRIBUTE,
+                    span,
+                    format!("unknown '@cfg' predicate '{key}'"),
+                )
+                .with_help(
+                    "use 'feature = \"name\"', or combine predicates with 'not', 'all', and 'any'",
+                ));
+        }
+        self.consume(TokenKind::OpenParen)?;
+        let mut values = Vec::new();
+        while self.current_token.kind != TokenKind::CloseParen {
+            values.push(self.nested("'@cfg' predicate", span, Self::parse_cfg_predicate)?);
+            if self.current_token.kind != TokenKind::Comma {
+                break;
+            }
+            self.advance()?;
+        }
+        self.consume(TokenKind::CloseParen)?;
+        match (key, values.as_slice()) {
+            ("not", &[value]) => Ok(!value),
+            ("not", _) => Err(self.error_at(
+                codes::INVALID_ATTRIBUTE,
+                span,
+                format!("'not' takes one predicate, found {}", values.len()),
+            )),
+            ("all", _) => Ok(values.iter().all(|&value| value)),
+            _ => Ok(values.iter().any(|&value| value)),
+        }
+    }
+
+    /// Returns the error for an attribute given twice.
+    fn repeated_attribute(&self, span: Span, attribute: &str) -> ParseError {
+        self.error_at(
+            codes::INVALID_ATTRIBUTE,
+            span,
+            format!("expected one '@{attribute}' attribute, found a second"),
+        )
     }
 
     // --- Type Parsing ---
 
-    /// Parses a custom or primitive type name.
+    /// Parses a custom or primitive type name, a bounded vector (`vec<T, N>`), a fixed-point
+    /// number (`fixed<T, S>`), or a byte array (`bytes[N]`).
+    ///
+    /// `vec`, `fixed`, and `bytes` are not reserved: they only start a parameterized type when
+    /// followed by `<` or `[`, so types with those names still work. Written as raw identifiers,
+    /// they never do.
     fn parse_type(&mut self) -> Result<Type, ParseError> {
-        let type_name = match &self.current_token.kind {
-            TokenKind::Primitive(val) => Type::Primitive(val.clone()),
-            TokenKind::Identifier(name) => Type::Custom(name.clone()),
-            _ => {
-                return Err(ParseError(format!(
-                    "expected a type name, found {:?} at position {}\n{}",
-                    self.current_token.kind,
-                    self.current_token.position,
-                    self.lexer.display_token_in_context(&self.current_token)
-                )));
+        let parse_parameters: fn(&mut Self) -> Result<Type, ParseError> =
+            match (self.current_token.kind, self.peek()) {
+                (TokenKind::Identifier("vec"), TokenKind::OpenAngle) => Self::parse_vector,
+                (TokenKind::Identifier("fixed"), TokenKind::OpenAngle) => Self::parse_fixed,
+                (TokenKind::Identifier("bytes"), TokenKind::OpenBracket) => Self::parse_bytes,
+                _ => return self.parse_type_name(),
+            };
+        self.advance()?;
+        parse_parameters(self)
+    }
+
+    /// Parses a custom or primitive type name.
+    fn parse_type_name(&mut self) -> Result<Type, ParseError> {
+        let type_name = match self.current_token.kind {
+            TokenKind::Primitive(val) => Type::Primitive(val),
+            TokenKind::Uuid => Type::Uuid,
+            TokenKind::Identifier(name) | TokenKind::RawIdentifier(name) => {
+                Type::Custom(self.symbol(name, self.current_token.span)?)
             }
+            _ => return Err(self.unexpected("a type name")),
         };
-        self.advance();
+        self.advance()?;
         Ok(type_name)
     }
 
-    /// Extracts a PrimitiveType from the current token kind (used for enum base type).
-    fn parse_primitive_type(&mut self) -> Result<PrimitiveType, ParseError> {
-        let primitive_type = match &self.current_token.kind {
-            TokenKind::Primitive(val) => val.clone(),
+    /// Parses the `[N]` that follows `bytes` in a byte array.
+    fn parse_bytes(&mut self) -> Result<Type, ParseError> {
+        self.consume(TokenKind::OpenBracket)?;
+        let len = match self.parse_expression("a byte array length")? {
+            (0, span) => {
+                return Err(self.error_at(
+                    codes::INVALID_BYTE_ARRAY,
+                    span,
+                    "byte array length must be at least 1",
+                ));
+            }
+            (n, _) if n <= u32::MAX as u64 => n as usize,
+            (n, span) => {
+                return Err(self.error_at(
+                    codes::INVALID_BYTE_ARRAY,
+                    span,
+                    format!("byte array length {n} exceeds the maximum of {}", u32::MAX),
+                ));
+            }
+        };
+        self.consume(TokenKind::CloseBracket)?;
+        Ok(Type::Bytes(len))
+    }
+
+    /// Parses the `<T, S>` that follows `fixed` in a fixed-point type.
+    fn parse_fixed(&mut self) -> Result<Type, ParseError> {
+        self.consume(TokenKind::OpenAngle)?;
+        let base = match self.current_token.kind {
+            TokenKind::Primitive(
+                p @ (PrimitiveType::U8
+                | PrimitiveType::U16
+                | PrimitiveType::U32
+                | PrimitiveType::U64
+                | PrimitiveType::I8
+                | PrimitiveType::I16
+                | PrimitiveType::I32
+                | PrimitiveType::I64),
+            ) => p,
             _ => {
-                return Err(ParseError(format!(
-                    "expected a numeric primitive type, found {:?} at position {}\n{}",
-                    self.current_token.kind,
-                    self.current_token.position,
-                    self.lexer.display_token_in_context(&self.current_token)
-                )));
+                return Err(self
+                    .error_at(
+                        codes::INVALID_FIXED_POINT,
+                        self.current_token.span,
+                        format!(
+                            "expected an integer type for a fixed-point base, found {:?}",
+                            self.current_token.kind
+                        ),
+                    )
+                    .with_help("use one of u8, u16, u32, u64, i8, i16, i32, or i64"));
             }
         };
-        self.advance();
+        self.advance()?;
+        self.consume(TokenKind::Comma)?;
+        let scale = match self.parse_expression("a fixed-point scale")? {
+            (0, span) => {
+                return Err(self.error_at(
+                    codes::INVALID_FIXED_POINT,
+                    span,
+                    "fixed-point scale must be at least 1",
+                ));
+            }
+            (n, _) => n,
+        };
+        self.consume(TokenKind::CloseAngle)?;
+        Ok(Type::Fixed { base, scale })
+    }
+
+    /// Parses the `<T, N>` that follows `vec` in a bounded vector type.
+    fn parse_vector(&mut self) -> Result<Type, ParseError> {
+        self.consume(TokenKind::OpenAngle)?;
+        let element_span = self.current_token.span;
+        let element = self.parse_type()?;
+        if let Type::Vector { .. } | Type::Fixed { .. } | Type::Uuid | Type::Bytes(_) = element {
+            return Err(self
+                .error_at(
+                    codes::INVALID_VECTOR,
+                    element_span,
+                    format!("vector elements cannot be of type {element}"),
+                )
+                .with_help("wrap the element in a struct"));
+        }
+        self.consume(TokenKind::Comma)?;
+        let capacity = match self.parse_expression("a vector capacity")? {
+            (0, span) => {
+                return Err(self.error_at(
+                    codes::INVALID_VECTOR,
+                    span,
+                    "vector capacity must be at least 1",
+                ));
+            }
+            (n, _) if n <= u32::MAX as u64 => n as usize,
+            (n, span) => {
+                return Err(self.error_at(
+                    codes::INVALID_VECTOR,
+                    span,
+                    format!("vector capacity {n} exceeds the maximum of {}", u32::MAX),
+                ));
+            }
+        };
+        self.consume(TokenKind::CloseAngle)?;
+        Ok(Type::Vector {
+            element: Box::new(element),
+            capacity,
+        })
+    }
+
+    /// Extracts a PrimitiveType from the current token kind (used for enum base type).
+    fn parse_primitive_type(&mut self) -> Result<PrimitiveType, ParseError> {
+        let primitive_type = match self.current_token.kind {
+            TokenKind::Primitive(val) => val,
+            _ => return Err(self.unexpected("a numeric primitive type")),
+        };
+        self.advance()?;
         Ok(primitive_type)
     }
 
-    /// Parses a field definition inside a struct or message.
+    /// Parses a field definition inside a struct or message, or returns `None` if a `@cfg`
+    /// attribute leaves it out.
     /// Handles optional bit-field syntax (e.g., `name type : bits`).
-    fn parse_field(&mut self) -> Result<Field, ParseError> {
+    fn parse_field(&mut self) -> Result<Option<Field>, ParseError> {
+        let doc = self.doc_comment();
+        let attributes = self.parse_field_attributes()?;
+        let span = self.current_token.span;
         let name = self.consume_identifier()?;
         let type_info = self.parse_type()?;
 
         // Optional bit field size
         let bit_field_size: Option<usize> = if self.current_token.kind == TokenKind::Colon {
-            self.advance();
+            self.advance()?;
 
             match &type_info {
-                Type::Primitive(p) => match self.current_token.kind {
-                    TokenKind::LiteralInt(size) => {
-                        let max_bit_width = p
-                            .get_bit_width()
-                            .try_into()
-                            .map_err(|e| ParseError(format!("enexpected: {e}")))?;
-
-                        if size <= max_bit_width {
-                            self.advance();
-                            Some(size as usize)
-                        } else {
-                            return Err(ParseError(format!(
-                                "bit-field size {size} exceeds type {:?}'s width of {max_bit_width} bits at position {}\n{}",
-                                p,
-                                self.current_token.position,
-                                self.lexer.display_token_in_context(&self.current_token)
+                Type::Primitive(p) => {
+                    let (size, size_span) = self.parse_expression("a bit-field size")?;
+                    let max_bit_width = p.get_bit_width() as u64;
+
+                    if matches!(p, PrimitiveType::F32 | PrimitiveType::F64) {
+                        return Err(self
+                            .error_at(
+                                codes::INVALID_BIT_FIELD,
+                                size_span,
+                                format!("bit-field '{name}' narrows floating-point type {p}"),
+                            )
+                            .with_note(
+                                "a float is a sign, an exponent, and a mantissa, and has no \
+                                 narrower form",
+                            )
+                            .with_help(format!(
+                                "drop the bit-field size, or use an integer or a fixed-point \
+                                 type such as 'fixed<i{max_bit_width}, 100>'"
                             )));
-                        }
                     }
-                    _ => None,
-                },
+                    if *p == PrimitiveType::Bool && size != 1 {
+                        return Err(self
+                            .error_at(
+                                codes::INVALID_BIT_FIELD,
+                                size_span,
+                                format!("bit-field '{name}' gives bool {size} bits instead of 1"),
+                            )
+                            .with_help(
+                                "use 'bool : 1', or an unsigned type for a field of several bits",
+                            ));
+                    }
+                    if size == 0 {
+                        return Err(self
+                            .error_at(
+                                codes::INVALID_BIT_FIELD,
+                                size_span,
+                                format!("bit-field '{name}' has a size of 0 bits"),
+                            )
+                            .with_help(format!(
+                                "use a size between 1 and {max_bit_width}, or remove the field"
+                            )));
+                    }
+                    if size <= max_bit_width {
+                        Some(size as usize)
+                    } else {
+                        return Err(self
+                            .error_at(
+                                codes::INVALID_BIT_FIELD,
+                                size_span,
+                                format!(
+                                    "bit-field size {size} exceeds type {p:?}'s width of {max_bit_width} bits"
+                                ),
+                            )
+                            .with_help(format!(
+                                "use a size of at most {max_bit_width} or a wider type"
+                            )));
+                    }
+                }
                 _ => {
-                    return Err(ParseError(format!(
-                        "expected integer literal for bit-field size, found {:?} at position {}\n{}",
-                        self.current_token.kind,
-                        self.current_token.position,
-                        self.lexer.display_token_in_context(&self.current_token)
-                    )));
+                    return Err(self
+                        .error_at(
+                            codes::INVALID_BIT_FIELD,
+                            span,
+                            "bit-fields require a primitive type",
+                        )
+                        .with_note(format!("field '{name}' has type {type_info:?}")));
                 }
             }
         } else {
             None
         };
 
+        let condition = if self.current_token.kind == TokenKind::Identifier("if") {
+            self.advance()?;
+            Some(self.parse_condition()?)
+        } else {
+            None
+        };
+
         self.consume(TokenKind::Comma)?;
+        if attributes.excluded {
+            return Ok(None);
+        }
 
-        Ok(Field {
-            name,
+        Ok(Some(Field {
+            name: self.symbol(name, span)?,
             type_info,
             bit_field_size,
+            since: attributes.since,
+            removed: attributes.removed,
+            condition,
+            deprecated: attributes.deprecated,
+            doc,
+            span,
+        }))
+    }
+
+    /// Parses the condition after `if`: a field, `==` or `!=`, and an integer, `true`, `false`,
+    /// or an enum variant such as `Kind.Data`.
+    fn parse_condition(&mut self) -> Result<Condition, ParseError> {
+        let field_span = self.current_token.span;
+        let field = self.consume_identifier()?;
+        let negated = match self.current_token.kind {
+            TokenKind::Equal => false,
+            TokenKind::NotEqual => true,
+            _ => return Err(self.unexpected("'==' or '!='")),
+        };
+        self.advance()?;
+
+        let mut end = self.current_token.span.end;
+        let value = match self.current_token.kind {
+            TokenKind::LiteralInt(value) => {
+                self.advance()?;
+                ConditionValue::Integer(value)
+            }
+            TokenKind::Identifier("true") => {
+                self.advance()?;
+                ConditionValue::Bool(true)
+            }
+            TokenKind::Identifier("false") => {
+                self.advance()?;
+                ConditionValue::Bool(false)
+            }
+            TokenKind::Identifier(_) | TokenKind::RawIdentifier(_) => {
+                let enum_span = self.current_token.span;
+                let enum_name = self.consume_identifier()?;
+                self.consume(TokenKind::Dot)?;
+                end = self.current_token.span.end;
+                let variant = self.consume_identifier()?;
+                ConditionValue::Variant {
+                    enum_name: self.symbol(enum_name, enum_span)?,
+                    variant: variant.to_string(),
+                }
+            }
+            _ => return Err(self.unexpected("an integer, 'true', 'false', or an enum variant")),
+        };
+        Ok(Condition {
+            field: self.symbol(field, field_span)?,
+            negated,
+            value,
+            span: Span::new(field_span.start, end),
         })
     }
 
-    /// Helper to consume an Identifier and return its string value.
-    fn consume_identifier(&mut self) -> Result<String, ParseError> {
-        let name = match &self.current_token.kind {
-            TokenKind::Identifier(id) => id.clone(),
-            _ => {
-                let msg = format!(
-                    "Expected an identifier, found {:?} at position {}\n{}",
-                    self.current_token.kind,
-                    self.current_token.position,
-                    self.lexer.display_token_in_context(&self.current_token)
-                );
-                return Err(ParseError(msg));
+    /// Parses the `@since(N)`, `@removed(N)`, `@deprecated("note")`, and `@cfg(predicate)`
+    /// attributes before a field.
+    fn parse_field_attributes(&mut self) -> Result<FieldAttributes, ParseError> {
+        let mut attributes = FieldAttributes::default();
+        while self.current_token.kind == TokenKind::At {
+            let attribute_span = self.current_token.span;
+            self.advance()?;
+            let attribute = self.consume_identifier()?;
+            let slot = match attribute {
+                "since" => &mut attributes.since,
+                "removed" => &mut attributes.removed,
+                "deprecated" if attributes.deprecated.is_some() => {
+                    return Err(self.repeated_attribute(attribute_span, attribute));
+                }
+                "deprecated" => {
+                    attributes.deprecated = Some(self.parse_deprecation_note()?);
+                    continue;
+                }
+                "cfg" => {
+                    attributes.excluded |= !self.parse_cfg_attribute()?;
+                    continue;
+                }
+                _ => {
+                    return Err(self
+                        .error_at(
+                            codes::INVALID_ATTRIBUTE,
+                            attribute_span,
+                            format!("attribute '@{attribute}' is not allowed on a field"),
+                        )
+                        .with_help(
+                            "a field can be marked '@since(N)', '@removed(N)', \
+                             '@deprecated(\"note\")', or '@cfg(feature = \"name\")'",
+                        ));
+                }
+            };
+            if slot.is_some() {
+                return Err(self.repeated_attribute(attribute_span, attribute));
             }
+            self.consume(TokenKind::OpenParen)?;
+            *slot = Some(self.parse_version_number()?);
+            self.consume(TokenKind::CloseParen)?;
+        }
+        Ok(attributes)
+    }
+
+    /// Helper to consume an Identifier, plain or raw, and return its string value.
+    fn consume_identifier(&mut self) -> Result<&'a str, ParseError> {
+        let name = match self.current_token.kind {
+            TokenKind::Identifier(id) | TokenKind::RawIdentifier(id) => id,
+            _ => return Err(self.unexpected("an identifier")),
         };
-        self.advance();
+        self.advance()?;
         Ok(name)
     }
 
@@ -268,7 +1435,14 @@ impl<'a> Parser<'a> {
         while self.current_token.kind != TokenKind::CloseBrace
             && self.current_token.kind != TokenKind::Eof
         {
-            fields.push(self.parse_field()?);
+            let start = self.current_token.span;
+            match self.parse_field() {
+                Ok(field) => fields.extend(field),
+                Err(error) => {
+                    self.errors.push(error);
+                    self.skip_field(start);
+                }
+            }
         }
 
         self.consume(TokenKind::CloseBrace)?;
@@ -278,34 +1452,52 @@ impl<'a> Parser<'a> {
     /// Parses a message definition.
     fn parse_message(&mut self) -> Result<Definition, ParseError> {
         self.consume(TokenKind::Message)?;
+        let span = self.current_token.span;
         let name = self.consume_identifier()?;
         let fields = self.parse_struct_body()?;
 
         Ok(Definition::Message(MessageDef {
-            name,
+            name: self.symbol(name, span)?,
             fields,
             size: None,
+            id: None,
+            max_size: None,
+            packed_bits: false,
+            deprecated: None,
+            doc: None,
+            span,
         }))
     }
 
     /// Parses a struct definition.
     fn parse_struct(&mut self) -> Result<Definition, ParseError> {
         self.consume(TokenKind::Struct)?;
+        let span = self.current_token.span;
         let name = self.consume_identifier()?;
         let fields = self.parse_struct_body()?;
 
         Ok(Definition::Struct(StructDef {
-            name,
+            name: self.symbol(name, span)?,
             fields,
             size: None,
+            max_size: None,
+            packed_bits: false,
+            deprecated: None,
+            doc: None,
+            span,
         }))
     }
 
     // --- Enum Parsing ---
 
     /// Parses an enum definition.
+    ///
+    /// `flags` is not reserved: it only starts a flags enum at the top level, so fields named
+    /// `flags` still work.
     fn parse_enum(&mut self) -> Result<Definition, ParseError> {
-        self.consume(TokenKind::Enum)?;
+        let flags = self.current_token.kind != TokenKind::Enum;
+        self.advance()?;
+        let span = self.current_token.span;
         let name = self.consume_identifier()?;
 
         // Underlying type: 'enum Name: u32'
@@ -318,199 +1510,858 @@ impl<'a> Parser<'a> {
         while self.current_token.kind != TokenKind::CloseBrace
             && self.current_token.kind != TokenKind::Eof
         {
+            let doc = self.doc_comment();
+            let mut composite = false;
+            if self.current_token.kind == TokenKind::At {
+                let attribute_span = self.current_token.span;
+                self.advance()?;
+                let attribute = self.consume_identifier()?;
+                if attribute != "composite" || !flags {
+                    return Err(self
+                        .error_at(
+                            codes::INVALID_ATTRIBUTE,
+                            attribute_span,
+                            format!("attribute '@{attribute}' is not allowed on an enum variant"),
+                        )
+                        .with_help("only variants of a flags enum can be marked '@composite'"));
+                }
+                composite = true;
+            }
+
+            let variant_span = self.current_token.span;
             let variant_name = self.consume_identifier()?;
             let mut value = None;
 
             // Optional explicit assignment: '= 10'
             if self.current_token.kind == TokenKind::Assign {
-                self.advance();
-
-                let literal_value = match self.current_token.kind {
-                    TokenKind::LiteralInt(v) => v,
-                    _ => {
-                        return Err(ParseError(format!(
-                            "expected integer literal for enum assignment, found {:?} at position {}\n{}",
-                            self.current_token.kind,
-                            self.current_token.position,
-                            self.lexer.display_token_in_context(&self.current_token)
-                        )));
-                    }
-                };
-                self.advance();
-                value = Some(literal_value);
+                self.advance()?;
+
+                let (assigned, _) = self.parse_expression("an enum value")?;
+                value = Some(assigned);
             }
 
             self.consume(TokenKind::Comma)?;
 
             variants.push(EnumVariant {
-                name: variant_name,
+                name: variant_name.to_string(),
                 value,
+                composite,
+                doc,
+                span: variant_span,
             });
         }
 
-        self.consume(TokenKind::CloseBrace)?;
+        self.consume(TokenKind::CloseBrace)?;
+
+        let def = EnumDef {
+            name: self.symbol(name, span)?,
+            underlying_type,
+            variants,
+            flags,
+            open: false,
+            deprecated: None,
+            doc: None,
+            span,
+        };
+        if flags {
+            self.validate_flags(&def)?;
+        } else {
+            self.validate_values(&def)?;
+        }
+        Ok(Definition::Enum(def))
+    }
+
+    /// Checks that each value of an enum with an integer type fits that type. Values are never
+    /// negative, so a signed type holds up to its largest positive value.
+    fn validate_values(&self, def: &EnumDef) -> Result<(), ParseError> {
+        let ty = def.underlying_type;
+        if matches!(
+            ty,
+            PrimitiveType::Bool | PrimitiveType::F32 | PrimitiveType::F64
+        ) {
+            return Ok(());
+        }
+        let bits = ty.get_bit_width() - usize::from(ty.is_signed());
+        let max = u64::MAX >> (64 - bits);
+        for (variant, value) in def.variants.iter().zip(def.variant_values()) {
+            if value > max {
+                let implicit = match variant.value {
+                    Some(_) => "",
+                    None => ", one more than the variant before it",
+                };
+                return Err(self
+                    .error_at(
+                        codes::INVALID_ENUM_VALUE,
+                        variant.span,
+                        format!(
+                            "variant '{}' has value {value}{implicit}, which does not fit {ty}",
+                            variant.name
+                        ),
+                    )
+                    .with_help(format!(
+                        "use a value of at most {max}, or a wider underlying type"
+                    )));
+            }
+        }
+        Ok(())
+    }
+
+    /// Checks that each flag of a flags enum is its own bit of the underlying type, and that
+    /// each composite combines declared flags.
+    fn validate_flags(&self, def: &EnumDef) -> Result<(), ParseError> {
+        let invalid =
+            |span, message: String| Err(self.error_at(codes::INVALID_FLAGS, span, message));
+        let bits = match def.underlying_type {
+            PrimitiveType::U8 | PrimitiveType::U16 | PrimitiveType::U32 | PrimitiveType::U64 => {
+                def.underlying_type.get_bit_width()
+            }
+            other => {
+                return invalid(
+                    def.span,
+                    format!(
+                        "flags '{}' must have an unsigned type, found {other}",
+                        def.name
+                    ),
+                );
+            }
+        };
+
+        let values = def.variant_values();
+        let mut declared = 0u64;
+        for (variant, &value) in def.variants.iter().zip(&values) {
+            if variant.composite {
+                if variant.value.is_none() {
+                    return invalid(
+                        variant.span,
+                        format!("composite '{}' needs an explicit value", variant.name),
+                    );
+                }
+                continue;
+            }
+            if !value.is_power_of_two() || value.ilog2() as usize >= bits {
+                return Err(self
+                    .error_at(
+                        codes::INVALID_FLAGS,
+                        variant.span,
+                        format!(
+                            "flag '{}' has value {value}, which is not a single bit of {}",
+                            variant.name, def.underlying_type
+                        ),
+                    )
+                    .with_help("give it a power of two, or mark it '@composite'"));
+            }
+            if declared & value != 0 {
+                return invalid(
+                    variant.span,
+                    format!("flag '{}' reuses the bit {value}", variant.name),
+                );
+            }
+            declared |= value;
+        }
+
+        for (variant, &value) in def.variants.iter().zip(&values) {
+            if variant.composite && (value == 0 || value & !declared != 0) {
+                return Err(self
+                    .error_at(
+                        codes::INVALID_FLAGS,
+                        variant.span,
+                        format!(
+                            "composite '{}' has value {value}, which is not a combination of declared flags",
+                            variant.name
+                        ),
+                    )
+                    .with_help(format!("use a nonzero subset of the bits {declared:#x}")));
+            }
+        }
+        Ok(())
+    }
+}
+
+// ------ Core resolving logic ------
+
+/// Resolves a module whose definitions have all been parsed, calculating sizes and
+/// determining definition order. Handles circular dependency detection.
+///
+/// Errors carry a span but no source excerpt. They are paired with the definition that was
+/// being resolved, so that callers merging several files can attach the right source.
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(level = "info", skip_all, fields(definitions = module.definitions.len()))
+)]
+pub(crate) fn resolve(mut module: OnyxModule) -> Result<OnyxModule, (Symbol, ParseError)> {
+    if module.packed_bools {
+        pack_bools(&mut module);
+    }
+    check_nesting(&module, MAX_DEPTH)?;
+    check_message_ids(&module)?;
+    check_names(&module)?;
+    check_versions(&module)?;
+    let Some(version) = module.version else {
+        let module = resolve_layout(module)?;
+        check_max_sizes(&module)?;
+        check_alignment(&module)?;
+        check_cdr(&module)?;
+        check_strict(&module)?;
+        return Ok(module);
+    };
+    let mut current = resolve_layout(at_version(&module, version))?;
+    check_max_sizes(&current)?;
+    check_alignment(&current)?;
+    check_cdr(&current)?;
+    check_strict(&current)?;
+    // Only a version that adds or removes a field lays the schema out anew; every other one
+    // has the layout of the version before it.
+    let changes: HashSet<u32> = module
+        .definitions
+        .values()
+        .flat_map(|def| match def {
+            Definition::Message(m) => m.fields.as_slice(),
+            Definition::Struct(s) => s.fields.as_slice(),
+            Definition::Enum(_) => &[],
+        })
+        .flat_map(|field| [field.since, field.removed])
+        .flatten()
+        .collect();
+    let mut history: Vec<OnyxModule> = Vec::new();
+    for v in 1..version {
+        let layout = match history.last() {
+            Some(previous) if !changes.contains(&v) => OnyxModule {
+                version: Some(v),
+                ..previous.clone()
+            },
+            _ => resolve_layout(at_version(&module, v))?,
+        };
+        history.push(layout);
+    }
+    current.history = history;
+    Ok(current)
+}
+
+/// Checks that no struct or message nests more than `max` definitions deep, counting itself.
+///
+/// Resolving recurses once per level of nesting, so this runs first, without recursion.
+/// Circular definitions count as shallow here and are reported by resolving.
+fn check_nesting(module: &OnyxModule, max: usize) -> Result<(), (Symbol, ParseError)> {
+    let used = |name: &Symbol| -> Vec<Symbol> {
+        let fields = match module.definitions.get(name) {
+            Some(Definition::Struct(s)) => &s.fields,
+            Some(Definition::Message(m)) => &m.fields,
+            _ => return Vec::new(),
+        };
+        fields
+            .iter()
+            .filter_map(|field| match field.type_info.element_type() {
+                Type::Custom(used) => Some(*used),
+                _ => None,
+            })
+            .collect()
+    };
+    let mut definitions: Vec<&Definition> = module.definitions.values().collect();
+    definitions.sort_by_key(|def| def.span().start);
+
+    let mut depths: HashMap<Symbol, usize> = HashMap::new();
+    let mut visiting = HashSet::new();
+    for def in definitions {
+        // Each entry is a definition and whether the definitions it uses have been visited.
+        let mut stack = vec![(def.symbol(), false)];
+        while let Some((name, visited)) = stack.pop() {
+            if depths.contains_key(&name) {
+                continue;
+            }
+            if !visited {
+                if visiting.insert(name) {
+                    stack.push((name, true));
+                    stack.extend(used(&name).into_iter().map(|used| (used, false)));
+                }
+                continue;
+            }
+            visiting.remove(&name);
+            let inner = used(&name)
+                .iter()
+                .filter_map(|used| depths.get(used))
+                .max()
+                .copied();
+            let depth = match module.definitions.get(&name) {
+                Some(Definition::Struct(_) | Definition::Message(_)) => inner.unwrap_or(0) + 1,
+                _ => 0,
+            };
+            if depth > max {
+                let def = &module.definitions[&name];
+                let kind = match def {
+                    Definition::Message(_) => "message",
+                    _ => "struct",
+                };
+                return Err((
+                    name,
+                    Diagnostic::error(format!(
+                        "{kind} '{}' nests more than {max} deep",
+                        def.name()
+                    ))
+                    .with_code(codes::LIMIT_EXCEEDED)
+                    .with_span(def.span()),
+                ));
+            }
+            depths.insert(name, depth);
+        }
+    }
+    Ok(())
+}
+
+/// Gives every `bool` field without a bit-field size a size of one bit, for the
+/// `bool_width = 1` directive.
+fn pack_bools(module: &mut OnyxModule) {
+    for def in module.definitions.values_mut() {
+        let fields = match def {
+            Definition::Message(m) => &mut m.fields,
+            Definition::Struct(s) => &mut s.fields,
+            Definition::Enum(_) => continue,
+        };
+        for field in fields {
+            if field.type_info == Type::Primitive(PrimitiveType::Bool) {
+                field.bit_field_size.get_or_insert(1);
+            }
+        }
+    }
+}
 
-        Ok(Definition::Enum(EnumDef {
-            name,
-            underlying_type,
-            variants,
-        }))
+/// Checks that no laid-out message or struct outgrows its `@max_size`.
+fn check_max_sizes(module: &OnyxModule) -> Result<(), (Symbol, ParseError)> {
+    for def in module
+        .order
+        .iter()
+        .filter_map(|id| module.definitions.get(id))
+    {
+        let (kind, max_size) = match def {
+            Definition::Message(m) => ("message", m.max_size),
+            Definition::Struct(s) => ("struct", s.max_size),
+            Definition::Enum(_) => continue,
+        };
+        if let (Some(max_size), Some(bits)) = (max_size, def.size())
+            && bits / 8 > max_size
+        {
+            return Err((
+                def.symbol(),
+                Diagnostic::error(format!(
+                    "{kind} '{}' is {} bytes, over its '@max_size' of {max_size}",
+                    def.name(),
+                    bits / 8
+                ))
+                .with_code(codes::LIMIT_EXCEEDED)
+                .with_span(def.span())
+                .with_help("shrink its vectors and byte arrays, or raise '@max_size'"),
+            ));
+        }
     }
+    Ok(())
+}
 
-    // ------ Core resolving logic ------
+/// Checks that every run of bit-fields ends on a byte boundary, unless its definition is
+/// marked `@packed_bits` to accept the padding that fills out the last byte.
+fn check_alignment(module: &OnyxModule) -> Result<(), (Symbol, ParseError)> {
+    let hir = hir::lower_module(module)?;
+    for def in module
+        .order
+        .iter()
+        .filter_map(|id| module.definitions.get(id))
+    {
+        let (kind, packed_bits) = match def {
+            Definition::Message(m) => ("message", m.packed_bits),
+            Definition::Struct(s) => ("struct", s.packed_bits),
+            Definition::Enum(_) => continue,
+        };
+        let Some(layout) = hir.layout(def.symbol()) else {
+            continue;
+        };
+        for slot in layout.slots.iter().filter(|s| s.is_bit_field_container()) {
+            let used: usize = slot.fields.iter().map(|field| field.bits).sum();
+            if packed_bits || used.is_multiple_of(8) {
+                continue;
+            }
+            let first = slot.first();
+            let padding = slot.size * 8 - used;
+            let mut error = Diagnostic::error(format!(
+                "bit-fields from '{}' end at bit {used}, off a byte boundary",
+                first.name
+            ))
+            .with_code(codes::INVALID_BIT_FIELD)
+            .with_span(first.span)
+            .with_help(format!(
+                "declare the padding as a bit-field, such as 'reserved u8 : {padding}', \
+                 or mark the {kind} '@packed_bits' to pad it implicitly"
+            ));
+            if module.packed_bools {
+                error = error.with_note("each bool is one bit wide under 'bool_width = 1'");
+            }
+            return Err((def.symbol(), error));
+        }
+    }
+    Ok(())
+}
 
-    /// Resolves the module by calculating sizes and determining definition order.
-    /// Handles circular dependency detection.
-    fn resolve_module(mut self) -> Result<OnyxModule, ParseError> {
-        let mut type_order: Vec<String> = Vec::new();
-        let mut type_stack: Vec<String> = Vec::new();
-        let mut calculated_sizes: HashMap<String, usize> = HashMap::new();
+/// Checks that a laid-out module aligned for CDR has only fields CDR lays out the same way:
+/// no vectors, whose CDR sequences take only the elements in use, and no bit-fields whose
+/// container is not the size of a CDR bitset holder.
+fn check_cdr(module: &OnyxModule) -> Result<(), (Symbol, ParseError)> {
+    if module.alignment == Alignment::Packed {
+        return Ok(());
+    }
+    let hir = hir::lower_module(module)?;
+    for def in module
+        .order
+        .iter()
+        .filter_map(|id| module.definitions.get(id))
+    {
+        let Some(layout) = hir.layout(def.symbol()) else {
+            continue;
+        };
+        for slot in &layout.slots {
+            let first = slot.first();
+            if let Type::Vector { .. } = first.type_info {
+                let error = Diagnostic::error(format!(
+                    "vector '{}' has no CDR layout under 'align = {}'",
+                    first.name, module.alignment
+                ))
+                .with_code(codes::INVALID_ALIGNMENT)
+                .with_span(first.span)
+                .with_note("a CDR sequence takes only the elements in use, not its capacity")
+                .with_help("use a fixed number of fields, or 'bytes[N]' for bytes");
+                return Err((def.symbol(), error));
+            }
+            if slot.is_bit_field_container() && ![1, 2, 4, 8].contains(&slot.size) {
+                let error = Diagnostic::error(format!(
+                    "bit-fields from '{}' take {} bytes, which no CDR bitset holds",
+                    first.name, slot.size
+                ))
+                .with_code(codes::INVALID_ALIGNMENT)
+                .with_span(first.span)
+                .with_help("add a reserved bit-field so that the run fills 8, 16, 32, or 64 bits");
+                return Err((def.symbol(), error));
+            }
+        }
+    }
+    Ok(())
+}
 
-        // Pass 1: Calculate sizes (iterate IMMUTABLY)
-        for (id, def) in &self.module.definitions {
-            type_stack.clear();
-            if !calculated_sizes.contains_key(id) {
-                self.resolve_type_calculate(
-                    &mut type_order,
-                    &mut type_stack,
-                    &mut calculated_sizes,
+/// Checks the layout of a laid-out module with the `strict = true` directive: no bit-field
+/// narrows a signed type, no closed enum has an underlying type wider than its values need,
+/// and every run of bit-fields fills its container, even in a definition marked `@packed_bits`.
+///
+/// Open enums are left alone, since a wider type keeps room for values added later.
+fn check_strict(module: &OnyxModule) -> Result<(), (Symbol, ParseError)> {
+    if !module.strict {
+        return Ok(());
+    }
+    let hir = hir::lower_module(module)?;
+    let strict_error = |def: &Definition, span, message: String, help: String| {
+        Err((
+            def.symbol(),
+            Diagnostic::error(message)
+                .with_code(codes::STRICT_LAYOUT)
+                .with_span(span)
+                .with_help(help)
+                .with_note("the module is 'strict = true'"),
+        ))
+    };
+    for def in module
+        .order
+        .iter()
+        .filter_map(|id| module.definitions.get(id))
+    {
+        if let Definition::Enum(e) = def {
+            if e.open {
+                continue;
+            }
+            let all = e
+                .variant_values()
+                .into_iter()
+                .fold(0, |all, value| all | value);
+            let signed = e.underlying_type.is_signed();
+            let needed = [8, 16, 32, 64]
+                .into_iter()
+                .find(|&bits| all <= u64::MAX >> (64 - bits + signed as usize))
+                .unwrap_or(64);
+            if needed < e.underlying_type.get_bit_width() {
+                let narrowest = format!("{}{needed}", if signed { 'i' } else { 'u' });
+                return strict_error(
                     def,
-                )?;
+                    e.span,
+                    format!(
+                        "enum '{}' is {} but its values fit in {narrowest}",
+                        e.name, e.underlying_type
+                    ),
+                    format!(
+                        "use '{narrowest}', or mark the enum '@open' to keep room for more values"
+                    ),
+                );
             }
+            continue;
         }
-
-        // Pass 2: Assign sizes (iterate MUTABLY)
-        for (id, def) in self.module.definitions.iter_mut() {
-            if let Some(&size) = calculated_sizes.get(id) {
-                match def {
-                    Definition::Message(message_def) => message_def.size = Some(size),
-                    Definition::Struct(struct_def) => struct_def.size = Some(size),
-                    Definition::Enum(_) => {}
+        let Some(layout) = hir.layout(def.symbol()) else {
+            continue;
+        };
+        for slot in &layout.slots {
+            let first = slot.first();
+            for field in &slot.fields {
+                if let Type::Primitive(p) = field.type_info
+                    && p.is_signed()
+                    && field.bits < p.get_bit_width()
+                {
+                    return strict_error(
+                        def,
+                        field.span,
+                        format!(
+                            "bit-field '{}' narrows signed type {p} to {} bits",
+                            field.name, field.bits
+                        ),
+                        format!(
+                            "use an unsigned type, or all {} bits of {p}",
+                            p.get_bit_width()
+                        ),
+                    );
                 }
             }
+            let used: usize = slot.fields.iter().map(|field| field.bits).sum();
+            if slot.is_bit_field_container() && used < slot.size * 8 {
+                let padding = slot.size * 8 - used;
+                return strict_error(
+                    def,
+                    first.span,
+                    format!(
+                        "bit-fields from '{}' use {used} bits, leaving {padding} padding bits in \
+                         their {}-byte container",
+                        first.name, slot.size
+                    ),
+                    format!(
+                        "declare the padding as a bit-field, such as 'reserved u8 : {padding}'"
+                    ),
+                );
+            }
         }
+    }
+    Ok(())
+}
 
-        self.module.order = type_order;
-        Ok(self.module)
+/// Checks that no two messages share an `@id`.
+fn check_message_ids(module: &OnyxModule) -> Result<(), (Symbol, ParseError)> {
+    for pair in module.identified_messages().windows(2) {
+        let (first, message) = (pair[0], pair[1]);
+        if let Some(id) = message.id
+            && first.id == message.id
+        {
+            return Err((
+                message.name,
+                Diagnostic::error(format!(
+                    "message {} has ID {id}, which {} already has",
+                    message.name, first.name
+                ))
+                .with_code(codes::INVALID_MESSAGE_ID)
+                .with_span(message.span)
+                .with_help("give each message its own ID; receivers dispatch on it"),
+            ));
+        }
     }
+    Ok(())
+}
 
-    /// Recursively calculates the size of a definition and populates `type_order`.
-    /// Detects circular dependencies using `type_stack`.
-    fn resolve_type_calculate(
-        &self,
-        type_order: &mut Vec<String>,
-        type_stack: &mut Vec<String>,
-        calculated_sizes: &mut HashMap<String, usize>,
-        def: &Definition,
-    ) -> Result<usize, ParseError> {
-        // If already calculated, return the size
-        if let Some(&size) = calculated_sizes.get(def.name()) {
-            return Ok(size);
+/// Checks that generated code can tell every definition apart, and every field of a definition
+/// from the fields present alongside it.
+///
+/// Two names conflict when they differ only in case, as the Rust generator uppercases them for
+/// constants, or when they convert to the same UpperCamelCase name, as the C++ generator does
+/// for field offsets (`my_field` and `myField` both become `kMyFieldOffset`).
+fn check_names(module: &OnyxModule) -> Result<(), (Symbol, ParseError)> {
+    fn conflict(a: &str, b: &str) -> bool {
+        a.eq_ignore_ascii_case(b) || upper_camel_case(a) == upper_camel_case(b)
+    }
+    fn overlap(a: &Field, b: &Field) -> bool {
+        let range = |f: &Field| (f.since.unwrap_or(1), f.removed.unwrap_or(u32::MAX));
+        let ((a_since, a_removed), (b_since, b_removed)) = (range(a), range(b));
+        a_since < b_removed && b_since < a_removed
+    }
+    let error = |what: &str, name: &str, first: &str, span: Span| {
+        let message = match name == first {
+            true => format!("{what} '{name}' is declared more than once"),
+            false => format!(
+                "{what} '{name}' conflicts with '{first}', as generated code cannot tell them apart"
+            ),
+        };
+        Diagnostic::error(message)
+            .with_code(codes::NAME_CONFLICT)
+            .with_span(span)
+            .with_help(format!("rename one of the {what}s"))
+    };
+
+    let mut definitions: Vec<&Definition> = module.definitions.values().collect();
+    definitions.sort_by_key(|def| def.span().start);
+    for (i, def) in definitions.iter().enumerate() {
+        if let Some(first) = definitions[..i]
+            .iter()
+            .find(|d| conflict(d.name(), def.name()))
+        {
+            return Err((
+                def.symbol(),
+                error("type", def.name(), first.name(), def.span()),
+            ));
+        }
+        let fields = match def {
+            Definition::Message(m) => &m.fields,
+            Definition::Struct(s) => &s.fields,
+            Definition::Enum(_) => continue,
+        };
+        for (j, field) in fields.iter().enumerate() {
+            if let Some(first) = fields[..j]
+                .iter()
+                .find(|f| overlap(f, field) && conflict(f.name.as_str(), field.name.as_str()))
+            {
+                return Err((
+                    def.symbol(),
+                    error(
+                        "field",
+                        field.name.as_str(),
+                        first.name.as_str(),
+                        field.span,
+                    ),
+                ));
+            }
         }
+    }
+    Ok(())
+}
 
-        match def.size() {
-            Some(size) => {
-                if !type_order.contains(&def.name().to_string()) {
-                    type_order.push(def.name().to_string());
+/// Checks that the `@since` and `@removed` versions of every field fall within the schema
+/// version.
+fn check_versions(module: &OnyxModule) -> Result<(), (Symbol, ParseError)> {
+    let mut definitions: Vec<&Definition> = module.definitions.values().collect();
+    definitions.sort_by_key(|def| def.span().start);
+    for def in definitions {
+        let fields = match def {
+            Definition::Message(m) => &m.fields,
+            Definition::Struct(s) => &s.fields,
+            Definition::Enum(_) => continue,
+        };
+        for field in fields {
+            let error = |message: String, help: String| {
+                Err((
+                    def.symbol(),
+                    Diagnostic::error(message)
+                        .with_code(codes::INVALID_VERSION)
+                        .with_span(field.span)
+                        .with_help(help),
+                ))
+            };
+            let (since, removed) = (field.since.unwrap_or(1), field.removed);
+            let Some(version) = module.version else {
+                if field.since.is_some() || removed.is_some() {
+                    return error(
+                        format!(
+                            "field '{}' is versioned, but the module has no version",
+                            field.name
+                        ),
+                        "add a 'version = N' directive".to_string(),
+                    );
                 }
-                calculated_sizes.insert(def.name().to_string(), size);
-                Ok(size)
-            }
-            None => {
-                if type_stack.contains(&def.name().to_string()) {
-                    let cycle = type_stack
-                        .iter()
-                        .cloned()
-                        .chain(std::iter::once(def.name().to_string()))
-                        .collect::<Vec<String>>()
-                        .join(" -> ");
-                    return Err(ParseError(format!(
-                        "circular dependency detected: '{cycle}'."
-                    )));
+                continue;
+            };
+            for (attribute, value) in [("since", Some(since)), ("removed", removed)] {
+                if let Some(value) = value
+                    && value > version
+                {
+                    return error(
+                        format!(
+                            "field '{}' is marked '@{attribute}({value})', after the module version {version}",
+                            field.name
+                        ),
+                        format!("versions go up to {version}"),
+                    );
                 }
+            }
+            if let Some(removed) = removed
+                && removed <= since
+            {
+                return error(
+                    format!(
+                        "field '{}' is removed in version {removed} but added in version {since}, so it is never present",
+                        field.name
+                    ),
+                    "a field is present from '@since' up to, but excluding, '@removed'".to_string(),
+                );
+            }
+        }
+    }
+    Ok(())
+}
 
-                type_stack.push(def.name().to_string());
+/// Returns the unresolved module with the fields of schema version `version`.
+fn at_version(module: &OnyxModule, version: u32) -> OnyxModule {
+    let mut module = module.clone();
+    module.version = Some(version);
+    for def in module.definitions.values_mut() {
+        if let Definition::Message(MessageDef { fields, .. })
+        | Definition::Struct(StructDef { fields, .. }) = def
+        {
+            fields.retain(|field| field.present_in(version));
+        }
+    }
+    module
+}
 
-                let calculated_size = match def {
-                    Definition::Struct(s) => self.resolve_fields_calculate(
-                        type_order,
-                        type_stack,
-                        calculated_sizes,
-                        &s.fields,
+/// Checks that every condition reads an integer, `bool`, or enum field of the same definition,
+/// and compares it with a value of that field's type.
+fn check_conditions(module: &OnyxModule) -> Result<(), (Symbol, ParseError)> {
+    let mut definitions: Vec<&Definition> = module.definitions.values().collect();
+    definitions.sort_by_key(|def| def.span().start);
+    for def in definitions {
+        let fields = match def {
+            Definition::Message(m) => &m.fields,
+            Definition::Struct(s) => &s.fields,
+            Definition::Enum(_) => continue,
+        };
+        for field in fields {
+            let Some(condition) = &field.condition else {
+                continue;
+            };
+            let error = |message: String, help: String| {
+                let mut error = Diagnostic::error(message)
+                    .with_code(codes::INVALID_CONDITION)
+                    .with_span(condition.span)
+                    .with_help(help);
+                if let Some(version) = module.version {
+                    error = error.with_note(format!("in the layout of version {version}"));
+                }
+                Err((def.symbol(), error))
+            };
+            let Some(target) = fields
+                .iter()
+                .find(|f| f.name == condition.field && f.name != field.name)
+            else {
+                return error(
+                    format!(
+                        "condition of '{}' reads '{}', which is not another field of '{}'",
+                        field.name,
+                        condition.field,
+                        def.name()
                     ),
-                    Definition::Message(m) => self.resolve_fields_calculate(
-                        type_order,
-                        type_stack,
-                        calculated_sizes,
-                        &m.fields,
+                    "a condition reads a field of the same struct or message".to_string(),
+                );
+            };
+            if target.condition.is_some() {
+                return error(
+                    format!(
+                        "condition of '{}' reads '{}', which is conditional itself",
+                        field.name, target.name
                     ),
-                    // Enums are resolved during field resolution, not here
-                    _ => Err(ParseError("unexpected error!".to_string())),
-                }?;
-
-                let deepest_dep = match type_stack.pop() {
-                    Some(id) => id,
-                    None => {
-                        return Err(ParseError(
-                            "expected a custom type to be on the stack.".to_string(),
-                        ));
-                    }
-                };
-                if !type_order.contains(&deepest_dep) {
-                    type_order.push(deepest_dep);
-                }
-
-                calculated_sizes.insert(def.name().to_string(), calculated_size);
-                Ok(calculated_size)
+                    "a condition reads a field that is always present".to_string(),
+                );
+            }
+            if let Err(message) = check_condition_value(module, target, condition) {
+                return error(
+                    format!("condition of '{}' {message}", field.name),
+                    "compare an integer field with an integer, a 'bool' field with 'true' or \
+                     'false', and an enum field with one of its variants"
+                        .to_string(),
+                );
             }
         }
     }
+    Ok(())
+}
 
-    /// Calculates the total size of a list of fields.
-    fn resolve_fields_calculate(
-        &self,
-        type_order: &mut Vec<String>,
-        type_stack: &mut Vec<String>,
-        calculated_sizes: &mut HashMap<String, usize>,
-        fields: &Vec<Field>,
-    ) -> Result<usize, ParseError> {
-        let mut total_size = 0;
-        for field in fields {
-            let field_size = match field.bit_field_size {
-                Some(size) => size,
-                None => match &field.type_info {
-                    Type::Primitive(p) => p.get_bit_width(),
-                    Type::Custom(custom_name) => {
-                        if let Some(target_def) = self.module.definitions.get(custom_name) {
-                            // Recursively call type resolution to understand circular dependencies
-                            self.resolve_type_calculate(
-                                type_order,
-                                type_stack,
-                                calculated_sizes,
-                                target_def,
-                            )?
-                        } else {
-                            // You had an incomplete error message here
-                            return Err(ParseError(format!(
-                                "custom type '{custom_name}' not defined"
-                            )));
-                        }
-                    }
-                },
+/// Checks that `condition` compares `target` with a value of its type.
+fn check_condition_value(
+    module: &OnyxModule,
+    target: &Field,
+    condition: &Condition,
+) -> Result<(), String> {
+    let enum_def = match &target.type_info {
+        Type::Custom(name) => match module.definitions.get(name) {
+            Some(Definition::Enum(e)) => Some(e),
+            _ => None,
+        },
+        _ => None,
+    };
+    let primitive = match (&target.type_info, enum_def) {
+        (Type::Primitive(p), _) if !matches!(p, PrimitiveType::F32 | PrimitiveType::F64) => *p,
+        (_, Some(e)) => e.underlying_type,
+        _ => {
+            return Err(format!(
+                "reads '{}' of type {}, which is not an integer, 'bool', or enum",
+                target.name, target.type_info
+            ));
+        }
+    };
+    match (&condition.value, primitive, enum_def) {
+        (ConditionValue::Bool(_), PrimitiveType::Bool, None) => Ok(()),
+        (ConditionValue::Integer(value), p, _) if p != PrimitiveType::Bool => {
+            let bits = target.bit_field_size.unwrap_or(p.get_bit_width());
+            let bits = match p {
+                PrimitiveType::I8
+                | PrimitiveType::I16
+                | PrimitiveType::I32
+                | PrimitiveType::I64 => bits - 1,
+                _ => bits,
             };
-            total_size += field_size;
+            if bits < 64 && *value >= 1 << bits {
+                return Err(format!(
+                    "compares '{}' with {value}, which it cannot hold",
+                    target.name
+                ));
+            }
+            Ok(())
+        }
+        (ConditionValue::Variant { enum_name, variant }, _, Some(e)) if *enum_name == e.name => {
+            if e.variants.iter().any(|v| &v.name == variant) {
+                Ok(())
+            } else {
+                Err(format!(
+                    "compares '{}' with '{enum_name}.{variant}', which is not a variant",
+                    target.name
+                ))
+            }
         }
-        Ok(total_size)
+        (value, _, _) => Err(format!(
+            "compares '{}' of type {} with '{value}'",
+            target.name, target.type_info
+        )),
     }
 }
 
-/// A simple error type for parsing failures.
-#[derive(Debug)]
-pub struct ParseError(pub String);
-impl fmt::Display for ParseError {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{}error{}: {}", color::RED, color::END, self.0)
+/// Resolves the layout of a module: calculates sizes and determines definition order.
+fn resolve_layout(mut module: OnyxModule) -> Result<OnyxModule, (Symbol, ParseError)> {
+    check_conditions(&module)?;
+    let hir = hir::lower_module(&module)?;
+    let order = hir.order.clone();
+    let sizes: HashMap<Symbol, usize> = order
+        .iter()
+        .filter_map(|id| Some((*id, hir.layout(*id)?.size * 8)))
+        .collect();
+    trace::debug!(definitions = order.len(), "resolved layout");
+
+    for (id, def) in module.definitions.iter_mut() {
+        if let Some(&size) = sizes.get(id) {
+            match def {
+                Definition::Message(message_def) => message_def.size = Some(size),
+                Definition::Struct(struct_def) => struct_def.size = Some(size),
+                Definition::Enum(_) => {}
+            }
+        }
     }
+
+    module.order = order;
+    Ok(module)
+}
+
+/// The error type for parsing failures.
+///
+/// Kept as an alias of [`Diagnostic`] so existing signatures continue to compile.
+pub type ParseError = Diagnostic;
+
+/// Lexes all of `source` and returns a diagnostic for every lexical error, such as an invalid
+/// character or an oversized integer literal.
+///
+/// [`Parser`] stops at the first error it meets; run this first to report every lexical error
+/// in one pass.
+pub fn lexical_errors(source: &str) -> Vec<ParseError> {
+    Lexer::new(source).collect_errors()
 }
 
 #[cfg(test)]
@@ -523,8 +2374,8 @@ mod tests {
         let parser = Parser::new(source).unwrap();
         let module = parser.parse_module().unwrap();
 
-        assert!(module.definitions.contains_key("MyStruct"));
-        if let Definition::Struct(s) = &module.definitions["MyStruct"] {
+        assert!(module.definitions.contains_key(&"MyStruct".into()));
+        if let Definition::Struct(s) = &module.definitions[&Symbol::intern("MyStruct")] {
             assert_eq!(s.name, "MyStruct");
             assert_eq!(s.fields.len(), 2);
             assert_eq!(s.fields[0].name, "field1");
@@ -541,8 +2392,8 @@ mod tests {
         let parser = Parser::new(source).unwrap();
         let module = parser.parse_module().unwrap();
 
-        assert!(module.definitions.contains_key("MyEnum"));
-        if let Definition::Enum(e) = &module.definitions["MyEnum"] {
+        assert!(module.definitions.contains_key(&"MyEnum".into()));
+        if let Definition::Enum(e) = &module.definitions[&Symbol::intern("MyEnum")] {
             assert_eq!(e.name, "MyEnum");
             assert_eq!(e.underlying_type, PrimitiveType::U8);
             assert_eq!(e.variants.len(), 2);
@@ -555,14 +2406,248 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_enum_value_range() {
+        for source in [
+            "enum E : u8 { A = 255, }",
+            "enum E : i8 { A = 127, }",
+            "enum E : u64 { A = 18446744073709551615, }",
+            "enum E : i64 { A = 9223372036854775807, }",
+        ] {
+            assert!(
+                Parser::new(source).unwrap().parse_module().is_ok(),
+                "{source}"
+            );
+        }
+        for (source, message) in [
+            (
+                "enum E : u8 { A = 300, }",
+                "variant 'A' has value 300, which does not fit u8",
+            ),
+            (
+                "enum E : u8 { A = 1 << 8, }",
+                "variant 'A' has value 256, which does not fit u8",
+            ),
+            (
+                "enum E : i8 { A = 128, }",
+                "variant 'A' has value 128, which does not fit i8",
+            ),
+            (
+                "enum E : u16 { A = 65535, B, }",
+                "variant 'B' has value 65536, one more than the variant before it, which does \
+                 not fit u16",
+            ),
+            (
+                "enum E : i64 { A = 9223372036854775808, }",
+                "variant 'A' has value 9223372036854775808, which does not fit i64",
+            ),
+        ] {
+            let err = Parser::new(source).unwrap().parse_module().unwrap_err();
+            assert_eq!(err.code(), Some(codes::INVALID_ENUM_VALUE), "{source}");
+            assert_eq!(err.message(), message, "{source}");
+        }
+    }
+
+    #[test]
+    fn test_parse_flags() {
+        let source = "flags Access : u8 { Read = 1, Write, Exec = 8, @composite All = 11, }\n\
+                      struct File { flags Access, }";
+        let module = Parser::new(source).unwrap().parse_module().unwrap();
+        let Definition::Enum(e) = &module.definitions[&Symbol::intern("Access")] else {
+            panic!("Expected Enum definition");
+        };
+        assert!(e.flags);
+        assert!(e.variants[3].composite);
+        assert_eq!(e.variant_values(), [1, 2, 8, 11]);
+
+        for (source, code) in [
+            ("flags F : i8 { A = 1, }", codes::INVALID_FLAGS),
+            ("flags F : u8 { A = 3, }", codes::INVALID_FLAGS),
+            ("flags F : u8 { A = 256, }", codes::INVALID_FLAGS),
+            ("flags F : u8 { A = 2, B = 2, }", codes::INVALID_FLAGS),
+            (
+                "flags F : u8 { A, @composite B = 3, }",
+                codes::INVALID_FLAGS,
+            ),
+            ("flags F : u8 { A, @composite B, }", codes::INVALID_FLAGS),
+            (
+                "enum E : u8 { @composite A = 1, }",
+                codes::INVALID_ATTRIBUTE,
+            ),
+            ("flags F : u8 { @inline A = 1, }", codes::INVALID_ATTRIBUTE),
+        ] {
+            let err = Parser::new(source).unwrap().parse_module().unwrap_err();
+            assert_eq!(err.code(), Some(code), "{source}");
+        }
+    }
+
+    #[test]
+    fn test_parse_enum_policy() {
+        let source = "@open enum Status : u8 { Active = 1, }\n\
+                      @closed enum Kind : u8 { A, }\n\
+                      enum Level : u8 { Low, }";
+        let module = Parser::new(source).unwrap().parse_module().unwrap();
+        let open = |name: &str| match &module.definitions[&Symbol::intern(name)] {
+            Definition::Enum(e) => e.open,
+            _ => panic!("Expected Enum definition"),
+        };
+        assert!(open("Status"));
+        assert!(!open("Kind"));
+        assert!(!open("Level"));
+
+        for source in [
+            "@open struct S { a u8, }",
+            "@open flags F : u8 { A, }",
+            "@sealed enum E : u8 { A, }",
+            "@open enum E : u8 { A, Unknown, }",
+        ] {
+            let err = Parser::new(source).unwrap().parse_module().unwrap_err();
+            assert_eq!(err.code(), Some(codes::INVALID_ATTRIBUTE), "{source}");
+        }
+    }
+
+    #[test]
+    fn test_parse_versions() {
+        let source = "version = 3\n\
+                      struct Header { version u8, }\n\
+                      message User {\n\
+                          id u32,\n\
+                          @removed(3) name u8,\n\
+                          @since(2) hdr Header,\n\
+                          @since(2) @removed(3) legacy u16,\n\
+                      }";
+        let module = Parser::new(source).unwrap().parse_module().unwrap();
+        assert_eq!(module.version, Some(3));
+        assert_eq!(module.history.len(), 2);
+        let fields = |module: &OnyxModule| match &module.definitions[&Symbol::intern("User")] {
+            Definition::Message(m) => (
+                m.fields.iter().map(|f| f.name.as_str()).collect::<Vec<_>>(),
+                m.size,
+            ),
+            _ => panic!("Expected Message definition"),
+        };
+        assert_eq!(fields(&module), (vec!["id", "hdr"], Some(40)));
+        assert_eq!(
+            fields(module.layout(1).unwrap()),
+            (vec!["id", "name"], Some(40))
+        );
+        assert_eq!(
+            fields(module.layout(2).unwrap()),
+            (vec!["id", "name", "hdr", "legacy"], Some(64))
+        );
+        assert_eq!(module.layout(2).unwrap().version, Some(2));
+        assert!(std::ptr::eq(module.layout(3).unwrap(), &module));
+        assert!(module.layout(1).unwrap().history.is_empty());
+        assert!(module.layout(4).is_none());
+
+        // Versions that change nothing have the same layout as if laid out on their own.
+        let source = "version = 6\nmessage M { id u32, @since(4) a u8, @removed(6) b u16, }";
+        let module = Parser::new(source).unwrap().parse_module().unwrap();
+        let unresolved = Parser::new(source)
+            .unwrap()
+            .parse_module_unresolved()
+            .unwrap();
+        for v in 1..6 {
+            let alone = resolve_layout(at_version(&unresolved, v)).unwrap();
+            assert_eq!(module.layout(v), Some(&alone), "version {v}");
+        }
+        let error = Parser::new("version = 4294967295 message M { a u8, }")
+            .unwrap()
+            .parse_module()
+            .unwrap_err();
+        assert_eq!(
+            error.message(),
+            "schema version 4294967295 is over the limit of 1024"
+        );
+
+        for (source, code) in [
+            ("version = 2 version = 2", codes::INVALID_VERSION),
+            ("version = 0", codes::INVALID_VERSION),
+            ("message M { @since(2) a u8, }", codes::INVALID_VERSION),
+            (
+                "version = 2 message M { @since(3) a u8, }",
+                codes::INVALID_VERSION,
+            ),
+            (
+                "version = 2 message M { @removed(3) a u8, }",
+                codes::INVALID_VERSION,
+            ),
+            (
+                "version = 3 message M { @since(2) @removed(2) a u8, }",
+                codes::INVALID_VERSION,
+            ),
+            (
+                "version = 2 message M { @since(2) @since(2) a u8, }",
+                codes::INVALID_ATTRIBUTE,
+            ),
+            (
+                "version = 2 message M { @added(2) a u8, }",
+                codes::INVALID_ATTRIBUTE,
+            ),
+        ] {
+            let err = Parser::new(source).unwrap().parse_module().unwrap_err();
+            assert_eq!(err.code(), Some(code), "{source}");
+        }
+    }
+
+    #[test]
+    fn test_parse_conditions() {
+        let source = "enum Kind : u8 { Ping, Data = 5, }\n\
+                      struct Body { len u16, }\n\
+                      message Packet {\n\
+                          kind Kind,\n\
+                          payload Body if kind == Kind.Data,\n\
+                          ack bool : 1,\n\
+                          seq u8 : 7 if ack != true,\n\
+                          retry u8 if kind != 0,\n\
+                      }";
+        let module = Parser::new(source).unwrap().parse_module().unwrap();
+        let Definition::Message(m) = &module.definitions[&Symbol::intern("Packet")] else {
+            panic!("Expected Message definition");
+        };
+        // Conditional fields keep their slot.
+        assert_eq!(m.size, Some(40));
+        let payload = m.fields[1].condition.as_ref().unwrap();
+        assert_eq!(payload.to_string(), "kind == Kind.Data");
+        assert_eq!(payload.raw_value(&module), Some(5));
+        assert!(payload.holds(&module, 5));
+        assert!(!payload.holds(&module, 0));
+        let seq = m.fields[3].condition.as_ref().unwrap();
+        assert!(seq.negated);
+        assert_eq!(seq.value, ConditionValue::Bool(true));
+        assert!(seq.holds(&module, 0));
+
+        for source in [
+            "message M { a u8 if b == 1, }",
+            "message M { a u8 if a == 1, }",
+            "message M { b u8, c u8 if b == 1, a u8 if c == 1, }",
+            "message M { f f32, a u8 if f == 1, }",
+            "message M { b u8 : 3, a u8 if b == 8, }",
+            "message M { b i8, a u8 if b == 128, }",
+            "message M { b bool, a u8 if b == 1, }",
+            "message M { b u8, a u8 if b == true, }",
+            "enum E : u8 { X, } enum F : u8 { X, } message M { e E, a u8 if e == F.X, }",
+            "enum E : u8 { X, } message M { e E, a u8 if e == E.Y, }",
+            "version = 2 message M { @since(2) b u8, a u8 if b == 1, }",
+        ] {
+            let err = Parser::new(source).unwrap().parse_module().unwrap_err();
+            assert_eq!(err.code(), Some(codes::INVALID_CONDITION), "{source}");
+        }
+        let err = Parser::new("message M { b u8, a u8 if b = 1, }")
+            .unwrap()
+            .parse_module()
+            .unwrap_err();
+        assert_eq!(err.code(), Some(codes::UNEXPECTED_TOKEN));
+    }
+
     #[test]
     fn test_parse_message() {
         let source = "message MyMsg { id u64, }";
         let parser = Parser::new(source).unwrap();
         let module = parser.parse_module().unwrap();
 
-        assert!(module.definitions.contains_key("MyMsg"));
-        if let Definition::Message(m) = &module.definitions["MyMsg"] {
+        assert!(module.definitions.contains_key(&"MyMsg".into()));
+        if let Definition::Message(m) = &module.definitions[&Symbol::intern("MyMsg")] {
             assert_eq!(m.name, "MyMsg");
             assert_eq!(m.fields.len(), 1);
             assert_eq!(m.size, Some(64));
@@ -571,6 +2656,647 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_parse_vector() {
+        let source = "struct vec { a u8, }\n\
+                      enum Kind : u16 { A, }\n\
+                      message M { kinds vec<Kind, 300>, legacy vec, }";
+        let module = Parser::new(source).unwrap().parse_module().unwrap();
+        let Definition::Message(m) = &module.definitions[&Symbol::intern("M")] else {
+            panic!("Expected Message definition");
+        };
+        assert_eq!(
+            m.fields[0].type_info,
+            Type::Vector {
+                element: Box::new(Type::Custom("Kind".into())),
+                capacity: 300,
+            }
+        );
+        // A type named `vec` is still usable without angle brackets.
+        assert_eq!(m.fields[1].type_info, Type::Custom("vec".into()));
+        assert_eq!(m.size, Some(16 + 300 * 16 + 8));
+
+        for source in [
+            "struct S { v vec<u8, 0>, }",
+            "struct S { v vec<vec<u8, 2>, 2>, }",
+        ] {
+            let err = Parser::new(source).unwrap().parse_module().unwrap_err();
+            assert_eq!(err.code(), Some(codes::INVALID_VECTOR), "{source}");
+        }
+        let err = Parser::new("struct S { v vec<u8, 4> : 3, }")
+            .unwrap()
+            .parse_module()
+            .unwrap_err();
+        assert_eq!(err.code(), Some(codes::INVALID_BIT_FIELD));
+        let err = Parser::new("struct S { v vec<Missing, 4>, }")
+            .unwrap()
+            .parse_module()
+            .unwrap_err();
+        assert_eq!(err.code(), Some(codes::UNDEFINED_TYPE));
+    }
+
+    #[test]
+    fn test_parse_fixed() {
+        let source = "message Reading { celsius fixed<i16, 100>, fixed u8, }\n\
+                      struct fixed { a u8, }";
+        let module = Parser::new(source).unwrap().parse_module().unwrap();
+        let Definition::Message(m) = &module.definitions[&Symbol::intern("Reading")] else {
+            panic!("Expected Message definition");
+        };
+        assert_eq!(
+            m.fields[0].type_info,
+            Type::Fixed {
+                base: PrimitiveType::I16,
+                scale: 100,
+            }
+        );
+        assert_eq!(m.size, Some(24));
+
+        for (source, code) in [
+            ("struct S { f fixed<f32, 10>, }", codes::INVALID_FIXED_POINT),
+            ("struct S { f fixed<u8, 0>, }", codes::INVALID_FIXED_POINT),
+            (
+                "struct S { f vec<fixed<u8, 2>, 2>, }",
+                codes::INVALID_VECTOR,
+            ),
+            ("struct S { f fixed<u8, 2> : 4, }", codes::INVALID_BIT_FIELD),
+        ] {
+            let err = Parser::new(source).unwrap().parse_module().unwrap_err();
+            assert_eq!(err.code(), Some(code), "{source}");
+        }
+    }
+
+    #[test]
+    fn test_parse_byte_arrays() {
+        let source = "struct bytes { a u8, }\n\
+                      message Record { id uuid, key bytes[6], other bytes, }";
+        let module = Parser::new(source).unwrap().parse_module().unwrap();
+        let Definition::Message(m) = &module.definitions[&Symbol::intern("Record")] else {
+            panic!("Expected Message definition");
+        };
+        assert_eq!(m.fields[0].type_info, Type::Uuid);
+        assert_eq!(m.fields[1].type_info, Type::Bytes(6));
+        assert_eq!(m.fields[2].type_info, Type::Custom("bytes".into()));
+        assert_eq!(m.size, Some(8 * (16 + 6 + 1)));
+
+        for (source, code) in [
+            ("struct S { b bytes[0], }", codes::INVALID_BYTE_ARRAY),
+            ("struct S { b vec<uuid, 2>, }", codes::INVALID_VECTOR),
+            ("struct S { b uuid : 4, }", codes::INVALID_BIT_FIELD),
+            ("struct uuid { a u8, }", codes::UNEXPECTED_TOKEN),
+        ] {
+            let err = Parser::new(source).unwrap().parse_module().unwrap_err();
+            assert_eq!(err.code(), Some(code), "{source}");
+        }
+    }
+
+    #[test]
+    fn test_parse_expressions() {
+        let source = "const WIDTH = 1 << 2\n\
+                      const LEN = (WIDTH + 4) * 2 - 10 / 3 % 2\n\
+                      flags Flags : u32 { A = 1 << 4, B = 1 << WIDTH + 1, }\n\
+                      @packed_bits struct S { f u8 : WIDTH - 1, key bytes[LEN], list vec<u8, 16 >> WIDTH>, }";
+        let module = Parser::new(source).unwrap().parse_module().unwrap();
+        let Definition::Enum(e) = &module.definitions[&"Flags".into()] else {
+            panic!("Expected Enum definition");
+        };
+        assert_eq!(e.variants[0].value, Some(16));
+        assert_eq!(e.variants[1].value, Some(32));
+        let Definition::Struct(s) = &module.definitions[&"S".into()] else {
+            panic!("Expected Struct definition");
+        };
+        assert_eq!(s.fields[0].bit_field_size, Some(3));
+        assert_eq!(s.fields[1].type_info, Type::Bytes(15));
+        assert_eq!(
+            s.fields[2].type_info,
+            Type::Vector {
+                element: Box::new(Type::Primitive(PrimitiveType::U8)),
+                capacity: 1,
+            }
+        );
+
+        for (source, code, message) in [
+            (
+                "enum E : u8 { A = B, }",
+                codes::INVALID_EXPRESSION,
+                "constant 'B' is not defined",
+            ),
+            (
+                "struct S { f vec<u8, N>, }\nconst N = 2",
+                codes::INVALID_EXPRESSION,
+                "constant 'N' is not defined",
+            ),
+            (
+                "const N = 1 - 2",
+                codes::INVALID_EXPRESSION,
+                "'1 - 2' is negative",
+            ),
+            (
+                "const N = 4 / (2 - 2)",
+                codes::INVALID_EXPRESSION,
+                "'4 / (2 - 2)' divides by zero",
+            ),
+            (
+                "const N = 3 << 63",
+                codes::INVALID_EXPRESSION,
+                "'3 << 63' overflows a u64",
+            ),
+            (
+                "const N = 1 >> 64",
+                codes::INVALID_EXPRESSION,
+                "'1 >> 64' shifts by 64 bits, past the width of a u64",
+            ),
+            (
+                "const N = 1\nconst N = 2",
+                codes::DUPLICATE_DEFINITION,
+                "constant N already exists, found second declaration",
+            ),
+            (
+                "const N = 4\nstruct S { f bytes[N - 4], }",
+                codes::INVALID_BYTE_ARRAY,
+                "byte array length must be at least 1",
+            ),
+            (
+                "struct S { f u8 : 4 +, }",
+                codes::UNEXPECTED_TOKEN,
+                "expected a bit-field size, found Comma",
+            ),
+        ] {
+            let err = Parser::new(source).unwrap().parse_module().unwrap_err();
+            assert_eq!(err.code(), Some(code), "{source}");
+            assert_eq!(err.message(), message, "{source}");
+        }
+    }
+
+    #[test]
+    fn test_parse_message_ids() {
+        let source = "const BASE = 16\n\
+                      @id(BASE + 1) message Data { len u8, }\n\
+                      @id(2) message Ping { }\n\
+                      message Local { }";
+        let module = Parser::new(source).unwrap().parse_module().unwrap();
+        let ids: Vec<(&str, Option<u16>)> = module
+            .identified_messages()
+            .iter()
+            .map(|m| (m.name.as_str(), m.id))
+            .collect();
+        assert_eq!(ids, [("Ping", Some(2)), ("Data", Some(17))]);
+
+        for (source, code) in [
+            ("@id(65536) message M { }", codes::INVALID_MESSAGE_ID),
+            (
+                "@id(1) message A { }\n@id(1) message B { }",
+                codes::INVALID_MESSAGE_ID,
+            ),
+            ("@id(1) struct S { }", codes::INVALID_ATTRIBUTE),
+            ("@id(1) enum E : u8 { A, }", codes::INVALID_ATTRIBUTE),
+            ("@id message M { }", codes::UNEXPECTED_TOKEN),
+        ] {
+            let err = Parser::new(source).unwrap().parse_module().unwrap_err();
+            assert_eq!(err.code(), Some(code), "{source}");
+        }
+        let err = Parser::new("@id(1) message A { }\n@id(1) message B { }")
+            .unwrap()
+            .parse_module()
+            .unwrap_err();
+        assert_eq!(err.message(), "message B has ID 1, which A already has");
+    }
+
+    #[test]
+    fn test_parse_max_size() {
+        let source = "const MTU = 64\n\
+                      @max_size(8) struct Header { id u32, len u32, }\n\
+                      @id(1) @max_size(MTU) message Frame { hdr Header, payload bytes[56], }";
+        let module = Parser::new(source).unwrap().parse_module().unwrap();
+        let Some(Definition::Message(frame)) = module.definitions.get(&"Frame".into()) else {
+            panic!("Frame should be a message");
+        };
+        assert_eq!((frame.id, frame.max_size), (Some(1), Some(64)));
+        assert!(
+            module
+                .to_idl()
+                .contains("@id(1) @max_size(64) message Frame {")
+        );
+
+        for (source, code) in [
+            (
+                "@max_size(4) message M { a u32, b u8, }",
+                codes::LIMIT_EXCEEDED,
+            ),
+            ("@max_size(4) enum E : u8 { A, }", codes::INVALID_ATTRIBUTE),
+            (
+                "@max_size(4) @open enum E : u8 { A, }",
+                codes::INVALID_ATTRIBUTE,
+            ),
+            ("@id(1) @max_size(4) struct S { }", codes::INVALID_ATTRIBUTE),
+        ] {
+            let err = Parser::new(source).unwrap().parse_module().unwrap_err();
+            assert_eq!(err.code(), Some(code), "{source}");
+        }
+        let err = Parser::new("@max_size(4) struct S { a u32, b u8, }")
+            .unwrap()
+            .parse_module()
+            .unwrap_err();
+        assert_eq!(
+            err.message(),
+            "struct 'S' is 5 bytes, over its '@max_size' of 4"
+        );
+    }
+
+    #[test]
+    fn test_parse_deprecated() {
+        let source = "version = 1\n\
+                      @deprecated(\"use Status\") @open enum Old : u8 { A, }\n\
+                      @deprecated(\"use Frame\") @id(1) message Legacy { \
+                          @deprecated(\"use code\") @since(1) old Old, code u8, }";
+        let module = Parser::new(source).unwrap().parse_module().unwrap();
+        let Some(Definition::Enum(old)) = module.definitions.get(&"Old".into()) else {
+            panic!("Old should be an enum");
+        };
+        assert_eq!(
+            (old.deprecated.as_deref(), old.open),
+            (Some("use Status"), true)
+        );
+        let legacy = &module.definitions[&"Legacy".into()];
+        assert_eq!(legacy.deprecated(), Some("use Frame"));
+        let Definition::Message(legacy) = legacy else {
+            panic!("Legacy should be a message");
+        };
+        assert_eq!(legacy.id, Some(1));
+        assert_eq!(legacy.fields[0].deprecated.as_deref(), Some("use code"));
+        assert_eq!(legacy.fields[0].since, Some(1));
+        assert_eq!(legacy.fields[1].deprecated, None);
+
+        for (source, message) in [
+            (
+                "@deprecated(use) struct S { }",
+                "expected a note such as \"use v2\", found Identifier(\"use\")",
+            ),
+            (
+                "@deprecated(\"a\") @deprecated(\"b\") struct S { }",
+                "expected one '@deprecated' attribute, found a second",
+            ),
+            (
+                "struct S { @deprecated(\"a\") @deprecated(\"b\") f u8, }",
+                "expected one '@deprecated' attribute, found a second",
+            ),
+        ] {
+            let err = Parser::new(source).unwrap().parse_module().unwrap_err();
+            assert_eq!(err.message(), message, "{source}");
+        }
+    }
+
+    #[test]
+    fn test_parse_doc_comments() {
+        let source = "endian = little\n\
+                      /// How a frame is sent.\n\
+                      ///\n\
+                      /// Chosen per link.\n\
+                      enum Mode : u8 {\n\
+                          /// Best effort.\n\
+                          Fast,\n\
+                          // Not a doc comment.\n\
+                          Safe,\n\
+                      }\n\
+                      //// Not one either.\n\
+                      /// A frame on the wire.\n\
+                      @deprecated(\"use v2\") @id(1) message Frame {\n\
+                          ///   Indented text keeps all but one space.\n\
+                          mode Mode, len u8, /// The payload.\n\
+                          data bytes[4],\n\
+                      }";
+        let module = Parser::new(source).unwrap().parse_module().unwrap();
+        let Some(Definition::Enum(mode)) = module.definitions.get(&"Mode".into()) else {
+            panic!("Mode should be an enum");
+        };
+        assert_eq!(
+            mode.doc.as_deref(),
+            Some("How a frame is sent.\n\nChosen per link.")
+        );
+        assert_eq!(mode.variants[0].doc.as_deref(), Some("Best effort."));
+        assert_eq!(mode.variants[1].doc, None);
+
+        let Some(Definition::Message(frame)) = module.definitions.get(&"Frame".into()) else {
+            panic!("Frame should be a message");
+        };
+        // Attributes may come between a doc comment and what it documents.
+        assert_eq!(frame.doc.as_deref(), Some("A frame on the wire."));
+        let docs: Vec<Option<&str>> = frame.fields.iter().map(|f| f.doc.as_deref()).collect();
+        assert_eq!(
+            docs,
+            [
+                Some("  Indented text keeps all but one space."),
+                None,
+                Some("The payload.")
+            ]
+        );
+
+        // Doc comments survive printing.
+        let printed = module.to_idl();
+        assert!(printed.contains(
+            "/// How a frame is sent.\n///\n/// Chosen per link.\nenum Mode : u8 {\n    \
+             /// Best effort.\n    Fast,\n"
+        ));
+        let reparsed = Parser::new(&printed).unwrap().parse_module().unwrap();
+        assert_eq!(
+            reparsed.definitions[&"Frame".into()].doc(),
+            frame.doc.as_deref()
+        );
+    }
+
+    #[test]
+    fn test_parse_cfg() {
+        let source = "@cfg(feature = \"gps\") struct Fix { lat i32, lon i32, }\n\
+                      @id(1) @cfg(not(feature = \"gps\")) message Report { speed u16, }\n\
+                      @cfg(feature = \"gps\") @id(1) message Report { \
+                          @cfg(all(feature = \"gps\", feature = \"alt\")) alt i16, \
+                          @cfg(any(feature = \"gps\", feature = \"alt\")) fix Fix, speed u16, }";
+        let parse = |features: &[&str]| {
+            Parser::new(source)
+                .unwrap()
+                .with_features(features.iter().copied())
+                .parse_module()
+                .unwrap()
+        };
+        let report_fields = |module: &OnyxModule| match &module.definitions[&"Report".into()] {
+            Definition::Message(m) => m.fields.iter().map(|f| f.name.to_string()).collect(),
+            _ => Vec::new(),
+        };
+        let module = parse(&[]);
+        let names: Vec<&str> = module.definitions.values().map(|d| d.name()).collect();
+        assert_eq!(names, ["Report"]);
+        assert_eq!(report_fields(&module), ["speed"]);
+        assert_eq!(report_fields(&parse(&["gps"])), ["fix", "speed"]);
+        assert_eq!(parse(&["gps", "alt"]).definitions.len(), 2);
+
+        for (source, message) in [
+            (
+                "@cfg(target = \"x\") struct S { }",
+                "unknown '@cfg' predicate 'target'",
+            ),
+            (
+                "@cfg(not(feature = \"a\", feature = \"b\")) struct S { }",
+                "'not' takes one predicate, found 2",
+            ),
+            (
+                "struct S { @cfg(feature = gps) f u8, }",
+                "expected a feature name such as \"gps\", found Identifier(\"gps\")",
+            ),
+            (
+                "@cfg(feature = \"a\") struct S { f bogus[, }",
+                "expected Comma, found OpenBracket",
+            ),
+        ] {
+            let err = Parser::new(source).unwrap().parse_module().unwrap_err();
+            assert_eq!(err.message(), message, "{source}");
+        }
+    }
+
+    #[test]
+    fn test_parse_packed_bits() {
+        let source = "struct Aligned { a u8 : 3, b u16 : 13, c u32, }\n\
+                      @packed_bits struct Loose { a u8 : 3, c u32, }\n\
+                      @id(1) @packed_bits message M { loose Loose, last bool : 1, }";
+        let module = Parser::new(source).unwrap().parse_module().unwrap();
+        let Definition::Struct(loose) = &module.definitions[&"Loose".into()] else {
+            panic!("Expected Struct definition");
+        };
+        assert!(loose.packed_bits);
+        // The padding fills out the last byte of each run.
+        assert_eq!(loose.size, Some(40));
+        assert_eq!(module.definitions[&"M".into()].size(), Some(48));
+        assert!(
+            module
+                .to_idl()
+                .contains("\n@id(1) @packed_bits message M {\n")
+        );
+
+        let err = Parser::new("message M { id u16, a u8 : 3, b u8 : 2, c u32, }")
+            .unwrap()
+            .parse_module()
+            .unwrap_err();
+        assert_eq!(err.code(), Some(codes::INVALID_BIT_FIELD));
+        assert_eq!(
+            err.message(),
+            "bit-fields from 'a' end at bit 5, off a byte boundary"
+        );
+        assert_eq!(
+            err.help(),
+            [
+                "declare the padding as a bit-field, such as 'reserved u8 : 3', or mark the \
+              message '@packed_bits' to pad it implicitly"
+            ]
+        );
+        for (source, message) in [
+            (
+                "@packed_bits enum E : u8 { A, }",
+                "attribute '@packed_bits' is only allowed on a message or struct",
+            ),
+            (
+                "@packed_bits @packed_bits struct S { a u8 : 1, }",
+                "expected one '@packed_bits' attribute, found a second",
+            ),
+        ] {
+            let err = Parser::new(source).unwrap().parse_module().unwrap_err();
+            assert_eq!(err.message(), message, "{source}");
+            assert_eq!(err.code(), Some(codes::INVALID_ATTRIBUTE), "{source}");
+        }
+    }
+
+    #[test]
+    fn test_parse_bool_width() {
+        let source = "bool_width = 1\n\
+                      struct Status { ready bool, busy bool, level u8 : 6, }\n\
+                      message M { id u16, ok bool, code u8 : 7, history vec<bool, 2>, }";
+        let module = Parser::new(source).unwrap().parse_module().unwrap();
+        assert!(module.packed_bools);
+        assert_eq!(module.definitions[&"Status".into()].size(), Some(8));
+        // Vectors keep a byte per bool.
+        assert_eq!(
+            module.definitions[&"M".into()].size(),
+            Some(8 * (2 + 1 + 1 + 2))
+        );
+        let idl = module.to_idl();
+        assert!(idl.contains("bool_width = 1\n"));
+        assert!(idl.contains("    ready bool : 1,\n"));
+        assert_eq!(
+            Parser::new(&idl).unwrap().parse_module().unwrap().to_idl(),
+            idl
+        );
+
+        let wide = Parser::new("bool_width = 8\nstruct S { a bool, b bool, }").unwrap();
+        let wide = wide.parse_module().unwrap();
+        assert!(!wide.packed_bools);
+        assert_eq!(wide.definitions[&"S".into()].size(), Some(16));
+
+        let err = Parser::new("bool_width = 1\nstruct S { a bool, b u8, }")
+            .unwrap()
+            .parse_module()
+            .unwrap_err();
+        assert_eq!(
+            err.message(),
+            "bit-fields from 'a' end at bit 1, off a byte boundary"
+        );
+        assert_eq!(
+            err.notes(),
+            ["each bool is one bit wide under 'bool_width = 1'"]
+        );
+        for (source, message) in [
+            (
+                "bool_width = 4\nstruct S { }",
+                "expected 1 or 8, found LiteralInt(4)",
+            ),
+            (
+                "bool_width = 1\nbool_width = 1\nstruct S { }",
+                "expected one bool_width directive, found a second",
+            ),
+        ] {
+            let err = Parser::new(source).unwrap().parse_module().unwrap_err();
+            assert_eq!(err.message(), message, "{source}");
+            assert_eq!(err.code(), Some(codes::INVALID_BIT_FIELD), "{source}");
+        }
+    }
+
+    #[test]
+    fn test_parse_align() {
+        let source = "struct Point { x f64, tag u8, }\n\
+                      message M { kind u8, mode u16, wide u64, point Point, id uuid, \
+                      code u16 : 12, spare u16 : 4, last i64, }";
+        let offsets = |alignment: &str| {
+            let source = format!("align = {alignment}\n{source}");
+            let module = Parser::new(&source).unwrap().parse_module().unwrap();
+            let hir = hir::lower(&module).unwrap();
+            let layout = hir.layout("M").unwrap();
+            let offsets: Vec<usize> = layout.slots.iter().map(|slot| slot.offset).collect();
+            (offsets, layout.size, layout.align)
+        };
+        assert_eq!(offsets("packed"), (vec![0, 1, 3, 11, 20, 36, 38], 46, 1));
+        assert_eq!(offsets("xcdr1"), (vec![0, 2, 8, 16, 25, 42, 48], 56, 8));
+        // XCDR2 aligns eight-byte numbers, and structs holding them, to four bytes.
+        assert_eq!(offsets("xcdr2"), (vec![0, 2, 4, 12, 21, 38, 40], 48, 4));
+
+        let module = Parser::new(&format!("align = xcdr2\n{source}"))
+            .unwrap()
+            .parse_module()
+            .unwrap();
+        assert_eq!(module.alignment, Alignment::Xcdr2);
+        let idl = module.to_idl();
+        assert!(idl.contains("align = xcdr2\n"));
+        assert_eq!(
+            Parser::new(&idl).unwrap().parse_module().unwrap().to_idl(),
+            idl
+        );
+
+        for (source, message) in [
+            (
+                "align = cdr\nstruct S { }",
+                "expected 'packed', 'xcdr1', or 'xcdr2', found Identifier(\"cdr\")",
+            ),
+            (
+                "align = xcdr2\nalign = xcdr2\nstruct S { }",
+                "expected one align directive, found a second",
+            ),
+            (
+                "align = xcdr1\nstruct S { a u8, items vec<u16, 4>, }",
+                "vector 'items' has no CDR layout under 'align = xcdr1'",
+            ),
+            (
+                "align = xcdr2\nstruct S { a u8 : 8, b u16 : 16, }",
+                "bit-fields from 'a' take 3 bytes, which no CDR bitset holds",
+            ),
+        ] {
+            let err = Parser::new(source).unwrap().parse_module().unwrap_err();
+            assert_eq!(err.message(), message, "{source}");
+            assert_eq!(err.code(), Some(codes::INVALID_ALIGNMENT), "{source}");
+        }
+        // Without CDR rules, vectors and odd containers are fine.
+        assert!(
+            Parser::new("align = packed\nstruct S { a u8 : 8, b u16 : 16, c vec<u8, 2>, }")
+                .unwrap()
+                .parse_module()
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn test_parse_strict() {
+        let source = "strict = true\n\
+                      enum Kind : u8 { A, B = 127, }\n\
+                      @open enum Wide : u32 { A, }\n\
+                      flags Perm : u16 { Read, Write = 256, }\n\
+                      struct Header { level i8 : 8, version u8 : 4, flags u8 : 4, }";
+        let module = Parser::new(source).unwrap().parse_module().unwrap();
+        assert!(module.strict);
+        assert!(module.to_idl().contains("strict = true\n"));
+        // The same layouts are fine outside strict mode.
+        let lax = Parser::new("strict = false\n@packed_bits struct S { x i8 : 3, }").unwrap();
+        assert!(!lax.parse_module().unwrap().strict);
+
+        for (source, message) in [
+            (
+                "strict = true\nstruct S { x i8 : 3, y u8 : 5, }",
+                "bit-field 'x' narrows signed type i8 to 3 bits",
+            ),
+            (
+                "strict = true\nenum Kind : u16 { A, B = 255, }",
+                "enum 'Kind' is u16 but its values fit in u8",
+            ),
+            (
+                "strict = true\nenum Kind : i32 { A, B = 128, }",
+                "enum 'Kind' is i32 but its values fit in i16",
+            ),
+            (
+                "strict = true\nflags Perm : u64 { Read, Write, }",
+                "enum 'Perm' is u64 but its values fit in u8",
+            ),
+            (
+                "strict = true\n@packed_bits struct S { a u8 : 4, b u16 : 8, c u32, }",
+                "bit-fields from 'a' use 12 bits, leaving 4 padding bits in their 2-byte container",
+            ),
+            (
+                "strict = true\nstrict = true\nstruct S { }",
+                "expected one strict directive, found a second",
+            ),
+            (
+                "strict = yes\nstruct S { }",
+                "expected 'true' or 'false', found Identifier(\"yes\")",
+            ),
+        ] {
+            let err = Parser::new(source).unwrap().parse_module().unwrap_err();
+            assert_eq!(err.message(), message, "{source}");
+            assert_eq!(err.code(), Some(codes::STRICT_LAYOUT), "{source}");
+        }
+    }
+
+    #[test]
+    fn test_name_conflicts() {
+        for (source, message) in [
+            (
+                "struct A { x u8, x u16, }",
+                "field 'x' is declared more than once",
+            ),
+            (
+                "struct A { my_field u8, myField u8, }",
+                "field 'myField' conflicts with 'my_field', as generated code cannot tell them apart",
+            ),
+            (
+                "struct Point { x u8, }\nmessage POINT { y u8, }",
+                "type 'POINT' conflicts with 'Point', as generated code cannot tell them apart",
+            ),
+        ] {
+            let err = Parser::new(source).unwrap().parse_module().unwrap_err();
+            assert_eq!(err.code(), Some(codes::NAME_CONFLICT), "{source}");
+            assert_eq!(err.message(), message);
+        }
+
+        // Fields that are never present together may share a name.
+        let source = "version = 3\nmessage M { @removed(2) key u8, @since(2) Key u16, }";
+        assert!(Parser::new(source).unwrap().parse_module().is_ok());
+        // Names that only share words are distinct.
+        let source = "struct A { ab u8, a_b u8, }";
+        assert!(Parser::new(source).unwrap().parse_module().is_ok());
+    }
+
     #[test]
     fn test_endianness() {
         let source = "endian = big struct S { f u8, }";
@@ -578,6 +3304,84 @@ mod tests {
         let module = parser.parse_module().unwrap();
 
         assert_eq!(module.endianness, WireEndianness::Big);
+
+        let module = Parser::new("endian = host struct S { f u8, }")
+            .unwrap()
+            .parse_module()
+            .unwrap();
+        assert_eq!(module.endianness, WireEndianness::Host);
+        assert!(!module.endianness.is_portable());
+
+        let err = Parser::new("endian = network struct S { f u8, }")
+            .unwrap()
+            .parse_module()
+            .unwrap_err();
+        assert_eq!(
+            err.message(),
+            "expected 'big', 'little', or 'host' for endianness, found 'network'"
+        );
+    }
+
+    #[test]
+    fn test_parse_module_unresolved() {
+        let source = "message Packet { header Header, len u16, }";
+        assert_eq!(
+            Parser::new(source)
+                .unwrap()
+                .parse_module()
+                .unwrap_err()
+                .code(),
+            Some(codes::UNDEFINED_TYPE)
+        );
+
+        let module = Parser::new(source)
+            .unwrap()
+            .parse_module_unresolved()
+            .unwrap();
+        let packet = &module.definitions[&Symbol::intern("Packet")];
+        assert_eq!(packet.size(), None);
+        assert!(module.order.is_empty());
+        assert!(module.to_idl().contains("header Header,"));
+        let lints = crate::lint::lint_module(&module, &crate::lint::LintConfig::default());
+        assert!(lints.is_empty());
+
+        let (owner, error) = crate::resolve::resolve_module(module).unwrap_err();
+        assert_eq!(owner, "Packet");
+        assert_eq!(error.code(), Some(codes::UNDEFINED_TYPE));
+    }
+
+    #[test]
+    fn test_parse_module_recovering() {
+        let source = "struct A { a u8, b vec<u8 4>, c u16, }\n\
+                      message { x u8, }\n\
+                      struct B { a A, 7, }";
+        let (module, errors) = Parser::new(source).unwrap().parse_module_recovering();
+        let messages: Vec<&str> = errors.iter().map(|e| e.message()).collect();
+        assert_eq!(
+            messages,
+            [
+                "expected Comma, found LiteralInt(4)",
+                "expected an identifier, found OpenBrace",
+                "expected an identifier, found LiteralInt(7)",
+            ]
+        );
+
+        let Definition::Struct(a) = &module.definitions[&Symbol::intern("A")] else {
+            panic!("Expected Struct definition");
+        };
+        let names: Vec<&str> = a.fields.iter().map(|f| f.name.as_str()).collect();
+        assert_eq!(names, ["a", "c"]);
+        let Definition::Struct(b) = &module.definitions[&Symbol::intern("B")] else {
+            panic!("Expected Struct definition");
+        };
+        assert_eq!(b.fields.len(), 1);
+        assert_eq!(module.definitions.len(), 2);
+
+        let err = Parser::new(source)
+            .unwrap()
+            .parse_module_unresolved()
+            .unwrap_err();
+        assert_eq!(err.message(), messages[0]);
     }
 
     #[test]
@@ -588,7 +3392,7 @@ mod tests {
 
         assert!(result.is_err());
         let err = result.err().unwrap();
-        assert!(err.0.contains("circular dependency detected"));
+        assert!(err.message().contains("circular dependency detected"));
     }
 
     #[test]
@@ -599,7 +3403,123 @@ mod tests {
 
         assert!(result.is_err());
         let err = result.err().unwrap();
-        assert!(err.0.contains("already exists"));
+        assert!(err.message().contains("already exists"));
+    }
+
+    #[test]
+    fn test_parse_limits() {
+        let limited = |source: &str, limits: ParseLimits| {
+            Parser::new(source)
+                .unwrap()
+                .with_limits(limits)
+                .parse_module()
+                .map_err(|error| (error.code(), error.message().to_string()))
+        };
+        let source = "version = 3\nconst N = (2 * (1 + 1))\n\
+                      @cfg(not(feature = \"x\")) struct A { f bytes[N], }\n\
+                      message B { a A, }";
+        assert!(limited(source, ParseLimits::untrusted()).is_ok());
+
+        let limit = ParseLimits::default;
+        let cases = [
+            (
+                ParseLimits {
+                    max_source_bytes: Some(64),
+                    ..limit()
+                },
+                "schema is 104 bytes, over the limit of 64",
+            ),
+            (
+                ParseLimits {
+                    max_tokens: Some(20),
+                    ..limit()
+                },
+                "schema has more than 20 tokens",
+            ),
+            (
+                ParseLimits {
+                    max_depth: Some(1),
+                    ..limit()
+                },
+                "expression nests more than 1 deep",
+            ),
+            (
+                ParseLimits {
+                    max_definitions: Some(1),
+                    ..limit()
+                },
+                "schema has more than 1 definitions",
+            ),
+            (
+                ParseLimits {
+                    max_version: Some(2),
+                    ..limit()
+                },
+                "schema version 3 is over the limit of 2",
+            ),
+        ];
+        for (limits, message) in cases {
+            let error = limited(source, limits).unwrap_err();
+            assert_eq!(error, (Some(codes::LIMIT_EXCEEDED), message.to_string()));
+        }
+
+        // Without limits, nesting is still capped short of exhausting the stack.
+        let deep = format!("const N = {}1{}", "(".repeat(10_000), ")".repeat(10_000));
+        let error = Parser::new(&deep).unwrap().parse_module().unwrap_err();
+        assert_eq!(error.message(), "expression nests more than 256 deep");
+        let deep_cfg = format!(
+            "@cfg({}feature = \"x\"{}) struct A {{ f u8, }}",
+            "not(".repeat(10_000),
+            ")".repeat(10_000)
+        );
+        let error = Parser::new(&deep_cfg).unwrap().parse_module().unwrap_err();
+        assert_eq!(error.message(), "'@cfg' predicate nests more than 256 deep");
+
+        let mut chain: String = (0..300)
+            .map(|i| format!("struct A{i} {{ a A{}, }}\n", i + 1))
+            .collect();
+        chain.push_str("struct A300 { f u8, }");
+        let error = Parser::new(&chain).unwrap().parse_module().unwrap_err();
+        assert_eq!(error.message(), "struct 'A44' nests more than 256 deep");
+        let limits = ParseLimits {
+            max_depth: Some(2),
+            ..limit()
+        };
+        assert!(limited(source, limits.clone()).is_ok());
+        let error = limited(
+            "struct A { f u8, } struct B { a A, } message C { b B, }",
+            limits,
+        );
+        assert_eq!(error.unwrap_err().1, "message 'C' nests more than 2 deep");
+
+        // Sizes that overflow are errors, not panics.
+        let error =
+            Parser::new("struct T { a bytes[4294967295], } struct U { t vec<T, 4294967295>, }")
+                .unwrap()
+                .parse_module()
+                .unwrap_err();
+        assert_eq!(
+            error.message(),
+            "schema too large: the size of 'U' in bits overflows"
+        );
+        assert_eq!(error.code(), Some(codes::SCHEMA_TOO_LARGE));
+
+        // What parses under untrusted limits generates on every target without panicking.
+        let source = "struct A { a u64 : 64, } struct B { b i64 : 64, } message C { a A, b B, }";
+        let module = limited(source, ParseLimits::untrusted()).unwrap();
+        for target in crate::generators::TARGETS {
+            let mut generator =
+                crate::generators::generator_for_target(target, "c".into()).unwrap();
+            assert!(generator.generate(&module).is_ok(), "{target}");
+        }
+        let error = limited("struct A { a u8 : 0, }", ParseLimits::untrusted()).unwrap_err();
+        assert_eq!(
+            error,
+            (
+                Some(codes::INVALID_BIT_FIELD),
+                "bit-field 'a' has a size of 0 bits".to_string()
+            )
+        );
     }
 
     #[test]
@@ -610,7 +3530,141 @@ mod tests {
 
         assert!(result.is_err());
         let err = result.err().unwrap();
-        assert!(err.0.contains("exceeds type"));
+        assert!(err.message().contains("exceeds type"));
+    }
+
+    #[test]
+    fn test_bit_field_types() {
+        let source = "struct A { ready bool : 1, level u8 : 7, ratio f32, }";
+        assert!(Parser::new(source).unwrap().parse_module().is_ok());
+
+        for (source, message, span) in [
+            (
+                "struct A { ratio f32 : 16, }",
+                "bit-field 'ratio' narrows floating-point type f32",
+                23..25,
+            ),
+            (
+                "struct A { ratio f64 : 64, }",
+                "bit-field 'ratio' narrows floating-point type f64",
+                23..25,
+            ),
+            (
+                "struct A { ready bool : 2, }",
+                "bit-field 'ready' gives bool 2 bits instead of 1",
+                24..25,
+            ),
+            (
+                "struct A { ready bool : 0, }",
+                "bit-field 'ready' gives bool 0 bits instead of 1",
+                24..25,
+            ),
+            (
+                "struct A { level u8 : 0, }",
+                "bit-field 'level' has a size of 0 bits",
+                22..23,
+            ),
+        ] {
+            let err = Parser::new(source).unwrap().parse_module().unwrap_err();
+            assert_eq!(err.code(), Some(codes::INVALID_BIT_FIELD), "{source}");
+            assert_eq!(err.message(), message, "{source}");
+            assert_eq!(err.span().map(|s| s.start..s.end), Some(span), "{source}");
+        }
+    }
+
+    #[test]
+    fn test_diagnostic_details() {
+        let source = "struct A { f u8, }\nstruct A { g u8, }";
+        let err = Parser::new(source).unwrap().parse_module().unwrap_err();
+        assert_eq!(err.code(), Some(codes::DUPLICATE_DEFINITION));
+        assert_eq!(err.position().map(|p| p.line()), Some(1));
+        assert_eq!(
+            err.notes(),
+            vec!["first defined at position 1:8".to_string()]
+        );
+        assert!(err.to_string().contains("struct A { g u8, }"));
+
+        let source = "message A { hdr B, }";
+        let err = Parser::new(source).unwrap().parse_module().unwrap_err();
+        assert_eq!(err.code(), Some(codes::UNDEFINED_TYPE));
+        assert_eq!(
+            err.help(),
+            vec!["define 'B' or use a primitive type".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_invalid_characters() {
+        let source = "// naïve comments are fine\nstruct Café { f u8, }";
+        let err = Parser::new(source).unwrap().parse_module().unwrap_err();
+        assert_eq!(err.code(), Some(codes::INVALID_TOKEN));
+        assert_eq!(
+            err.span().map(|span| &source[span.start()..span.end()]),
+            Some("Café")
+        );
+        assert!(
+            err.message()
+                .starts_with("identifier 'Café' contains non-ASCII character 'é'")
+        );
+
+        let err = Parser::new("struct A { f u8 # }")
+            .unwrap()
+            .parse_module()
+            .unwrap_err();
+        assert_eq!(err.code(), Some(codes::INVALID_TOKEN));
+        assert_eq!(err.message(), "unrecognized character '#'");
+        assert_eq!(
+            err.position().map(|p| p.to_string()),
+            Some("1:17".to_string())
+        );
+
+        // An error token at the start of the source fails before any grammar check.
+        let err = Parser::new("$ struct A { f u8, }").err().unwrap();
+        assert_eq!(err.message(), "unrecognized character '$'");
+    }
+
+    #[test]
+    fn test_lexical_errors_collects_all() {
+        let source = "struct A {\n  f u8 : 99999999999999999999,\n  g# u8,\n}";
+        let errors = lexical_errors(source);
+
+        let messages: Vec<&str> = errors.iter().map(|e| e.message()).collect();
+        assert_eq!(
+            messages,
+            [
+                "integer literal 99999999999999999999 does not fit in a u64",
+                "unrecognized character '#'",
+            ]
+        );
+        assert!(
+            errors
+                .iter()
+                .all(|e| e.code() == Some(codes::INVALID_TOKEN))
+        );
+        assert_eq!(
+            errors[1].position().map(|p| p.to_string()),
+            Some("3:4".to_string())
+        );
+        assert!(lexical_errors("struct A { f u8, }").is_empty());
+    }
+
+    #[test]
+    fn raw_identifiers() {
+        let source = "enum `enum` : u8 { `struct`, `if`, }\n\
+                      struct `message` { `u8` u8, `vec` `enum`, `fixed` u8 if `vec` == `enum`.`if`, }";
+        let module = Parser::new(source).unwrap().parse_module().unwrap();
+        let Definition::Struct(s) = &module.definitions[&Symbol::from("message")] else {
+            panic!("expected a struct");
+        };
+        let names: Vec<&str> = s.fields.iter().map(|f| f.name.as_str()).collect();
+        assert_eq!(names, ["u8", "vec", "fixed"]);
+        assert_eq!(s.fields[1].type_info, Type::Custom("enum".into()));
+
+        let idl = module.to_idl();
+        assert!(idl.contains("enum `enum` : u8 {\n    `struct`,\n    `if`,\n}\n"));
+        assert!(idl.contains("    fixed u8 if vec == `enum`.`if`,\n"));
+        let reparsed = Parser::new(&idl).unwrap().parse_module().unwrap();
+        assert_eq!(reparsed.to_idl(), idl);
     }
 
     #[test]
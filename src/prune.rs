@@ -0,0 +1,154 @@
+//! Finding and removing definitions that no entry point uses.
+//!
+//! Schemas collect structs and enums that nothing sends any more. A definition is live if it
+//! is an entry point, or a live definition has a field or vector of its type. Fields of every
+//! version of the schema count, so a type used only by a removed field stays live while
+//! earlier layouts need it.
+//!
+//! The entry points are every message, unless given. [`dead_definitions`] lists the rest, which
+//! the `dead-definition` [lint](crate::lint) reports; [`prune`] removes them so generated code
+//! carries only what the entry points need.
+//!
+//! ```rust
+//! use onyx::parser::Parser;
+//! use onyx::prune;
+//!
+//! let module = Parser::new(
+//!     "struct Header { version u8, }
+//!      struct Legacy { flags u8, }
+//!      enum Kind : u8 { A, B, }
+//!      message Packet { header Header, }
+//!      message Debug { kind Kind, }",
+//! )
+//! .and_then(|p| p.parse_module())
+//! .unwrap();
+//!
+//! assert_eq!(prune::dead_definitions(&module, &[]).unwrap(), ["Legacy"]);
+//! assert_eq!(
+//!     prune::dead_definitions(&module, &["Packet".into()]).unwrap(),
+//!     ["Legacy", "Kind", "Debug"]
+//! );
+//!
+//! let pruned = prune::prune(&module, &["Packet".into()]).unwrap();
+//! assert_eq!(pruned.order, ["Header", "Packet"]);
+//! ```
+
+use std::collections::HashSet;
+
+use crate::{
+    ast::{Definition, OnyxModule, Type},
+    diagnostic::{Diagnostic, codes},
+    symbol::Symbol,
+};
+
+/// Returns the definitions of `module` that no entry point uses, in source order.
+///
+/// `entry_points` names the definitions to start from; when it is empty, every message is an
+/// entry point. Fails if an entry point is not defined.
+pub fn dead_definitions(
+    module: &OnyxModule,
+    entry_points: &[Symbol],
+) -> Result<Vec<Symbol>, Diagnostic> {
+    let live = live_definitions(module, entry_points)?;
+    let mut dead: Vec<&Definition> = module
+        .definitions
+        .values()
+        .filter(|def| !live.contains(&def.symbol()))
+        .collect();
+    dead.sort_by(|a, b| (a.span().start, a.name()).cmp(&(b.span().start, b.name())));
+    Ok(dead.into_iter().map(Definition::symbol).collect())
+}
+
+/// Returns `module` without the definitions that no entry point uses, at every version.
+///
+/// `entry_points` is as for [`dead_definitions`]. The definitions left use only each other,
+/// so the pruned module generates code as the original does.
+pub fn prune(module: &OnyxModule, entry_points: &[Symbol]) -> Result<OnyxModule, Diagnostic> {
+    let live = live_definitions(module, entry_points)?;
+    let retain = |layout: &mut OnyxModule| {
+        layout.definitions.retain(|name, _| live.contains(name));
+        layout.order.retain(|name| live.contains(name));
+    };
+    let mut pruned = module.clone();
+    retain(&mut pruned);
+    pruned.history.iter_mut().for_each(retain);
+    Ok(pruned)
+}
+
+/// Returns the entry points and every definition they use, directly or not.
+fn live_definitions(
+    module: &OnyxModule,
+    entry_points: &[Symbol],
+) -> Result<HashSet<Symbol>, Diagnostic> {
+    let mut pending: Vec<Symbol> = match entry_points {
+        [] => module
+            .definitions
+            .values()
+            .filter(|def| matches!(def, Definition::Message(_)))
+            .map(Definition::symbol)
+            .collect(),
+        names => names.to_vec(),
+    };
+    if let Some(name) = pending
+        .iter()
+        .find(|name| !module.definitions.contains_key(name))
+    {
+        return Err(
+            Diagnostic::error(format!("entry point '{name}' is not defined"))
+                .with_code(codes::UNDEFINED_TYPE)
+                .with_help("name a message, struct, or enum of the schema"),
+        );
+    }
+
+    let layouts: Vec<&OnyxModule> = std::iter::once(module).chain(&module.history).collect();
+    let mut live = HashSet::new();
+    while let Some(name) = pending.pop() {
+        if !live.insert(name) {
+            continue;
+        }
+        for layout in &layouts {
+            let fields = match layout.definitions.get(&name) {
+                Some(Definition::Struct(s)) => &s.fields,
+                Some(Definition::Message(m)) => &m.fields,
+                _ => continue,
+            };
+            pending.extend(fields.iter().filter_map(
+                |field| match field.type_info.element_type() {
+                    Type::Custom(used) => Some(*used),
+                    _ => None,
+                },
+            ));
+        }
+    }
+    Ok(live)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::Parser;
+
+    #[test]
+    fn test_versions() {
+        let module = Parser::new(
+            "version = 2
+             struct Old { a u8, }
+             enum Kind : u8 { A, B, }
+             enum Mode : u8 { On, Off, }
+             message M { @removed(2) old Old, mode Mode, }
+             struct Spare { kind Kind, }",
+        )
+        .and_then(|p| p.parse_module())
+        .unwrap();
+        // `Old` is only in the layout of version 1, which still needs it.
+        assert_eq!(dead_definitions(&module, &[]).unwrap(), ["Kind", "Spare"]);
+
+        let pruned = prune(&module, &[]).unwrap();
+        assert_eq!(pruned.order, ["Old", "Mode", "M"]);
+        assert_eq!(pruned.history[0].order, ["Old", "Mode", "M"]);
+
+        let error = dead_definitions(&module, &["Missing".into()]).unwrap_err();
+        assert_eq!(error.message(), "entry point 'Missing' is not defined");
+        assert_eq!(error.code(), Some(codes::UNDEFINED_TYPE));
+    }
+}
@@ -0,0 +1,220 @@
+//! Python bindings, built as the `onyx` extension module.
+//!
+//! Enabled with the `pyo3` feature; `pyproject.toml` builds the wheel with maturin.
+//!
+//! ```python
+//! import onyx
+//!
+//! module = onyx.parse("struct Point { x i32, y i32, }")
+//! module["definitions"]["Point"]["size_bits"]  # 64
+//! onyx.compile(source, "cpp", "point.onyx")    # {"point.hpp": "...", "point.cpp": "..."}
+//! ```
+
+use std::path::PathBuf;
+
+use pyo3::{
+    create_exception,
+    exceptions::PyException,
+    prelude::*,
+    types::{PyDict, PyList},
+};
+
+use crate::{
+    ast::{Definition, Field, OnyxModule, WireEndianness},
+    diagnostic::Diagnostic,
+    generators,
+    parser::Parser,
+    span::LineIndex,
+};
+
+create_exception!(
+    onyx,
+    OnyxError,
+    PyException,
+    "Raised when a schema fails to parse, resolve, or generate."
+);
+
+impl From<Diagnostic> for PyErr {
+    fn from(diagnostic: Diagnostic) -> Self {
+        OnyxError::new_err(diagnostic.summary())
+    }
+}
+
+/// Parses and resolves a schema, returning the module as nested dicts.
+#[pyfunction]
+fn parse<'py>(py: Python<'py>, source: &str) -> PyResult<Bound<'py, PyDict>> {
+    let module = Parser::new(source)?.parse_module()?;
+    module_to_dict(py, &module, &LineIndex::new(source))
+}
+
+/// Compiles a schema for `target`, one of the names in `onyx.TARGETS`, returning a dict of
+/// output path to contents. `name` is the path of the schema, used to name the outputs;
+/// nothing is written to disk.
+#[pyfunction]
+#[pyo3(signature = (source, target, name = "schema.onyx"))]
+fn compile<'py>(
+    py: Python<'py>,
+    source: &str,
+    target: &str,
+    name: &str,
+) -> PyResult<Bound<'py, PyDict>> {
+    let module = Parser::new(source)?.parse_module()?;
+    let files = generators::generator_for_target(target, PathBuf::from(name))?.generate(&module)?;
+
+    let dict = PyDict::new(py);
+    for (path, contents) in files {
+        dict.set_item(path.to_string_lossy(), contents)?;
+    }
+    Ok(dict)
+}
+
+fn module_to_dict<'py>(
+    py: Python<'py>,
+    module: &OnyxModule,
+    index: &LineIndex,
+) -> PyResult<Bound<'py, PyDict>> {
+    let definitions = PyDict::new(py);
+    for (name, def) in &module.definitions {
+        definitions.set_item(name.as_str(), definition_to_dict(py, def, index)?)?;
+    }
+
+    let endianness = match module.endianness {
+        WireEndianness::Big => "big",
+        WireEndianness::Little => "little",
+        WireEndianness::Host => "host",
+    };
+
+    let dict = PyDict::new(py);
+    dict.set_item("endianness", endianness)?;
+    let order: Vec<&str> = module.order.iter().map(|id| id.as_str()).collect();
+    dict.set_item("order", order)?;
+    dict.set_item("definitions", definitions)?;
+    dict.set_item("version", module.version)?;
+    let history = PyList::empty(py);
+    for old in &module.history {
+        history.append(module_to_dict(py, old, index)?)?;
+    }
+    dict.set_item("history", history)?;
+    Ok(dict)
+}
+
+fn definition_to_dict<'py>(
+    py: Python<'py>,
+    def: &Definition,
+    index: &LineIndex,
+) -> PyResult<Bound<'py, PyDict>> {
+    let dict = PyDict::new(py);
+    dict.set_item("name", def.name())?;
+    dict.set_item("line", index.position(def.span().start()).line() + 1)?;
+    match def {
+        Definition::Message(m) => {
+            dict.set_item("kind", "message")?;
+            dict.set_item("size_bits", m.size)?;
+            dict.set_item("id", m.id)?;
+            dict.set_item("fields", fields_to_list(py, &m.fields)?)?;
+        }
+        Definition::Struct(s) => {
+            dict.set_item("kind", "struct")?;
+            dict.set_item("size_bits", s.size)?;
+            dict.set_item("fields", fields_to_list(py, &s.fields)?)?;
+        }
+        Definition::Enum(e) => {
+            let variants = PyList::empty(py);
+            for variant in &e.variants {
+                let v = PyDict::new(py);
+                v.set_item("name", &variant.name)?;
+                v.set_item("value", variant.value)?;
+                v.set_item("composite", variant.composite)?;
+                variants.append(v)?;
+            }
+            dict.set_item("kind", "enum")?;
+            dict.set_item("underlying_type", e.underlying_type.to_string())?;
+            dict.set_item("flags", e.flags)?;
+            dict.set_item("open", e.open)?;
+            dict.set_item("variants", variants)?;
+        }
+    }
+    Ok(dict)
+}
+
+fn fields_to_list<'py>(py: Python<'py>, fields: &[Field]) -> PyResult<Bound<'py, PyList>> {
+    let list = PyList::empty(py);
+    for field in fields {
+        let f = PyDict::new(py);
+        f.set_item("name", field.name.as_str())?;
+        f.set_item("type", field.type_info.to_string())?;
+        f.set_item("bits", field.bit_field_size)?;
+        f.set_item("since", field.since)?;
+        f.set_item("removed", field.removed)?;
+        f.set_item(
+            "condition",
+            field.condition.as_ref().map(ToString::to_string),
+        )?;
+        list.append(f)?;
+    }
+    Ok(list)
+}
+
+#[pymodule(name = "onyx")]
+fn onyx_module(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_function(wrap_pyfunction!(parse, m)?)?;
+    m.add_function(wrap_pyfunction!(compile, m)?)?;
+    m.add("TARGETS", generators::TARGETS.to_vec())?;
+    m.add("OnyxError", m.py().get_type::<OnyxError>())?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_and_compile() {
+        Python::initialize();
+        Python::attach(|py| {
+            let module = parse(
+                py,
+                "endian = big @packed_bits struct P { x i32, y u8 : 3, }",
+            )
+            .unwrap();
+            let point = module
+                .get_item("definitions")
+                .unwrap()
+                .unwrap()
+                .get_item("P")
+                .unwrap();
+            assert_eq!(
+                point
+                    .get_item("size_bits")
+                    .unwrap()
+                    .extract::<usize>()
+                    .unwrap(),
+                40
+            );
+            assert_eq!(
+                point
+                    .get_item("fields")
+                    .unwrap()
+                    .get_item(1)
+                    .unwrap()
+                    .get_item("type")
+                    .unwrap()
+                    .extract::<String>()
+                    .unwrap(),
+                "u8"
+            );
+
+            let files = compile(py, "struct P { x i32, }", "rust", "p.onyx").unwrap();
+            assert!(files.contains("p.rs").unwrap());
+
+            let module = PyModule::new(py, "onyx").unwrap();
+            onyx_module(&module).unwrap();
+            let targets = module.getattr("TARGETS").unwrap();
+            assert!(targets.contains("rust").unwrap());
+
+            let err = parse(py, "message A { hdr B, }").unwrap_err();
+            assert!(err.is_instance_of::<OnyxError>(py));
+            assert!(err.to_string().contains("custom type 'B' not defined"));
+        });
+    }
+}
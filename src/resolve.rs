@@ -0,0 +1,124 @@
+//! Resolution and layout queries for modules built or edited in code.
+//!
+//! [`Parser::parse_module`](crate::parser::Parser::parse_module) resolves what it parses, but a
+//! module assembled from [`ast`](crate::ast) nodes has no sizes or definition order yet and
+//! has not been checked. [`resolve_module`] runs the same checks and layout pass the parser
+//! does. [`layout`] and [`size_of`] then answer questions about single definitions without
+//! generating code; lower the module once with [`hir::lower`] to ask many.
+//!
+//! ```rust
+//! use onyx::ast::{Definition, Field, OnyxModule, PrimitiveType, StructDef, Type};
+//! use onyx::resolve;
+//!
+//! let mut module = OnyxModule::default();
+//! let header = StructDef::new(
+//!     "Header",
+//!     vec![
+//!         Field::new("version", Type::Primitive(PrimitiveType::U8), Some(4)),
+//!         Field::new("flags", Type::Primitive(PrimitiveType::U8), Some(4)),
+//!         Field::new("length", Type::Primitive(PrimitiveType::U32), None),
+//!     ],
+//! );
+//! module.definitions.insert("Header".into(), Definition::Struct(header));
+//!
+//! let module = resolve::resolve_module(module).unwrap();
+//! assert_eq!(resolve::size_of(&module, "Header").unwrap(), 5);
+//! let layout = resolve::layout(&module, "Header").unwrap();
+//! assert_eq!(layout.field("length").unwrap().offset, 1);
+//! ```
+
+use crate::{
+    ast::{OnyxModule, Type},
+    diagnostic::{Diagnostic, codes},
+    hir::{self, Layout},
+    parser,
+    symbol::Symbol,
+};
+
+/// A resolution error, paired with the definition being resolved when it was raised.
+pub type ResolveError = (Symbol, Diagnostic);
+
+/// Checks a module and lays it out: sets the size of every struct and message, the order of
+/// definitions, and the layouts of earlier versions, as parsing does.
+///
+/// The errors carry a span but no source excerpt, since a module built in code has none.
+pub fn resolve_module(module: OnyxModule) -> Result<OnyxModule, ResolveError> {
+    parser::resolve(module)
+}
+
+/// Returns the layout of the struct or message named `name`.
+pub fn layout<'m>(module: &'m OnyxModule, name: &str) -> Result<Layout<'m>, Diagnostic> {
+    hir::lower(module)?
+        .layout(name)
+        .cloned()
+        .ok_or_else(|| undefined(name))
+}
+
+/// Returns the size in bytes of the definition named `name`.
+pub fn size_of(module: &OnyxModule, name: &str) -> Result<usize, Diagnostic> {
+    hir::lower(module)?
+        .size_of(&Type::Custom(name.into()))
+        .ok_or_else(|| undefined(name))
+}
+
+fn undefined(name: &str) -> Diagnostic {
+    Diagnostic::error(format!("no definition named '{name}'")).with_code(codes::UNDEFINED_TYPE)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::{Definition, Field, MessageDef, PrimitiveType, StructDef};
+
+    #[test]
+    fn test_resolve_errors() {
+        let mut module = OnyxModule::default();
+        let message = MessageDef::new(
+            "Packet",
+            vec![Field::new("header", Type::Custom("Header".into()), None)],
+        );
+        module
+            .definitions
+            .insert("Packet".into(), Definition::Message(message));
+
+        let (owner, error) = resolve_module(module.clone()).unwrap_err();
+        assert_eq!(owner, "Packet");
+        assert_eq!(error.code(), Some(codes::UNDEFINED_TYPE));
+        assert_eq!(
+            size_of(&module, "Packet").unwrap_err().code(),
+            Some(codes::UNDEFINED_TYPE)
+        );
+        module.definitions.clear();
+        assert!(layout(&module, "Packet").is_err());
+    }
+
+    #[test]
+    fn test_too_large() {
+        // Built in code, so nothing bounds the bit-field widths or the byte array length.
+        let bits = |name, width| Field::new(name, Type::Primitive(PrimitiveType::U8), Some(width));
+        let wide = StructDef::new("Wide", vec![bits("a", usize::MAX), bits("b", 1)]);
+        let huge = StructDef::new(
+            "Huge",
+            vec![Field::new(
+                "items",
+                Type::Vector {
+                    element: Box::new(Type::Bytes(usize::MAX / 8)),
+                    capacity: 2,
+                },
+                None,
+            )],
+        );
+        for def in [wide, huge] {
+            let name = def.name;
+            let mut module = OnyxModule::default();
+            module.definitions.insert(name, Definition::Struct(def));
+            let (owner, error) = resolve_module(module.clone()).unwrap_err();
+            assert_eq!(owner, name);
+            assert_eq!(error.code(), Some(codes::SCHEMA_TOO_LARGE));
+            assert_eq!(
+                size_of(&module, name.as_str()).unwrap_err().code(),
+                Some(codes::SCHEMA_TOO_LARGE)
+            );
+        }
+    }
+}
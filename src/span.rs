@@ -0,0 +1,151 @@
+//! Source locations.
+//!
+//! Tokens, AST nodes, and diagnostics record where they came from as a [`Span`] of byte
+//! offsets into the source. Line and column numbers are derived on demand with a
+//! [`LineIndex`], so they are always consistent with the text being displayed.
+
+use std::fmt;
+
+/// A range of bytes in the source text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Span {
+    /// The starting byte offset of the span (inclusive).
+    pub(crate) start: usize,
+    /// The ending byte offset of the span (exclusive).
+    pub(crate) end: usize,
+}
+
+impl Span {
+    /// Creates a span covering the bytes `start..end`.
+    pub fn new(start: usize, end: usize) -> Self {
+        Span { start, end }
+    }
+
+    /// Returns the starting byte offset (inclusive).
+    pub fn start(&self) -> usize {
+        self.start
+    }
+
+    /// Returns the ending byte offset (exclusive).
+    pub fn end(&self) -> usize {
+        self.end
+    }
+
+    /// Returns the length of the span in bytes.
+    pub fn len(&self) -> usize {
+        self.end - self.start
+    }
+
+    /// Returns true if the span covers no bytes.
+    pub fn is_empty(&self) -> bool {
+        self.start == self.end
+    }
+}
+
+/// A line and column in the source text, derived from a byte offset by a [`LineIndex`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Position {
+    /// The 0-indexed line number.
+    pub(crate) line: usize,
+    /// The 0-indexed column, counted in characters.
+    pub(crate) column: usize,
+}
+
+impl Position {
+    /// Returns the 0-indexed line number.
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    /// Returns the 0-indexed column, counted in characters.
+    pub fn column(&self) -> usize {
+        self.column
+    }
+}
+
+impl fmt::Display for Position {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.line + 1, self.column + 1)
+    }
+}
+
+/// Maps byte offsets in a source text to line and column positions.
+#[derive(Debug, Clone)]
+pub struct LineIndex<'a> {
+    source: &'a str,
+    /// The byte offset at which each line starts.
+    line_starts: Vec<usize>,
+}
+
+impl<'a> LineIndex<'a> {
+    /// Builds the index for `source`.
+    pub fn new(source: &'a str) -> Self {
+        let line_starts = std::iter::once(0)
+            .chain(source.match_indices('\n').map(|(i, _)| i + 1))
+            .collect();
+        LineIndex {
+            source,
+            line_starts,
+        }
+    }
+
+    /// Returns the number of lines in the source.
+    pub fn line_count(&self) -> usize {
+        self.line_starts.len()
+    }
+
+    /// Returns the text of a 0-indexed line, without its line terminator.
+    pub fn line(&self, line: usize) -> &'a str {
+        let start = self.line_starts[line];
+        let end = self
+            .line_starts
+            .get(line + 1)
+            .map_or(self.source.len(), |&next| next - 1);
+        self.source[start..end].trim_end_matches('\r')
+    }
+
+    /// Returns the line and column of a byte offset. Offsets past the end of the source
+    /// resolve to the end of the last line.
+    pub fn position(&self, offset: usize) -> Position {
+        let offset = offset.min(self.source.len());
+        let line = self.line_starts.partition_point(|&start| start <= offset) - 1;
+        let line_start = self.line_starts[line];
+        let column = self.source[line_start..]
+            .char_indices()
+            .take_while(|&(i, _)| line_start + i < offset)
+            .count();
+        Position { line, column }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_line_index_positions() {
+        let index = LineIndex::new("ab\ncd\n\nef");
+
+        assert_eq!(index.line_count(), 4);
+        assert_eq!(index.position(0), Position { line: 0, column: 0 });
+        assert_eq!(index.position(2), Position { line: 0, column: 2 });
+        assert_eq!(index.position(3), Position { line: 1, column: 0 });
+        assert_eq!(index.position(7), Position { line: 3, column: 0 });
+        assert_eq!(index.position(100), Position { line: 3, column: 2 });
+        assert_eq!(index.line(1), "cd");
+        assert_eq!(index.line(2), "");
+    }
+
+    #[test]
+    fn test_columns_count_characters() {
+        // 'é' and '→' are multi-byte, but each occupies one column.
+        let source = "// é→\nstruct";
+        let index = LineIndex::new(source);
+
+        assert_eq!(index.position(source.find('→').unwrap()).column(), 4);
+        assert_eq!(
+            index.position(source.find("struct").unwrap()).to_string(),
+            "2:1"
+        );
+    }
+}
@@ -0,0 +1,145 @@
+//! Interned names.
+//!
+//! Definition names, field names, and custom type references are stored as [`Symbol`]s: a
+//! `u32` index into a process-wide string table. Symbols are `Copy`, compare and hash as
+//! integers, and resolve back to their text with [`Symbol::as_str`].
+
+use std::{
+    collections::HashMap,
+    fmt,
+    sync::{LazyLock, Mutex},
+};
+
+/// An interned string.
+///
+/// Two symbols are equal exactly when their text is equal. Interned text lives for the rest of
+/// the process, which suits names: a schema has a bounded set of them and they are reused
+/// across compilations. A service that parses schemas from untrusted sources, each bringing
+/// names of its own, bounds the table with
+/// [`ParseLimits::max_symbol_bytes`](crate::limits::ParseLimits::max_symbol_bytes).
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Symbol(u32);
+
+#[derive(Default)]
+struct Interner {
+    ids: HashMap<&'static str, Symbol>,
+    strings: Vec<&'static str>,
+    /// The total length of `strings`.
+    bytes: usize,
+}
+
+static INTERNER: LazyLock<Mutex<Interner>> = LazyLock::new(Default::default);
+
+impl Symbol {
+    /// Returns the symbol for `text`, interning it if it has not been seen before.
+    pub fn intern(text: &str) -> Symbol {
+        Symbol::intern_within(text, usize::MAX).unwrap()
+    }
+
+    /// Returns the symbol for `text`, interning it only if that keeps the string table within
+    /// `max_bytes`. Returns `None` if `text` is new and does not fit.
+    pub fn intern_within(text: &str, max_bytes: usize) -> Option<Symbol> {
+        let mut interner = INTERNER.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(&symbol) = interner.ids.get(text) {
+            return Some(symbol);
+        }
+        let bytes = interner.bytes.checked_add(text.len())?;
+        if bytes > max_bytes {
+            return None;
+        }
+        let symbol = Symbol(interner.strings.len() as u32);
+        let text: &'static str = Box::leak(text.into());
+        interner.strings.push(text);
+        interner.ids.insert(text, symbol);
+        interner.bytes = bytes;
+        Some(symbol)
+    }
+
+    /// Returns the total length of the text interned so far, in bytes.
+    pub fn interned_bytes() -> usize {
+        INTERNER.lock().unwrap_or_else(|e| e.into_inner()).bytes
+    }
+
+    /// Returns the text of the symbol.
+    pub fn as_str(self) -> &'static str {
+        let interner = INTERNER.lock().unwrap_or_else(|e| e.into_inner());
+        interner.strings[self.0 as usize]
+    }
+
+    /// Returns the symbol's index in the string table.
+    pub fn as_u32(self) -> u32 {
+        self.0
+    }
+}
+
+impl fmt::Debug for Symbol {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}", self.as_str())
+    }
+}
+
+impl fmt::Display for Symbol {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl From<&str> for Symbol {
+    fn from(text: &str) -> Self {
+        Symbol::intern(text)
+    }
+}
+
+impl From<String> for Symbol {
+    fn from(text: String) -> Self {
+        Symbol::intern(&text)
+    }
+}
+
+impl From<&String> for Symbol {
+    fn from(text: &String) -> Self {
+        Symbol::intern(text)
+    }
+}
+
+impl AsRef<str> for Symbol {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl PartialEq<str> for Symbol {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl PartialEq<&str> for Symbol {
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == *other
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_interning() {
+        let a = Symbol::intern("Header");
+        let b = Symbol::from("Header".to_string());
+        let c = Symbol::intern("Footer");
+
+        assert_eq!(a, b);
+        assert_eq!(a.as_u32(), b.as_u32());
+        assert_ne!(a, c);
+        assert_eq!(a.as_str(), "Header");
+        assert_eq!(c, "Footer");
+        assert_eq!(format!("{a} {a:?}"), "Header \"Header\"");
+
+        // Known text is found whatever the limit; new text is interned only if it fits.
+        assert_eq!(Symbol::intern_within("Header", 0), Some(a));
+        assert_eq!(Symbol::intern_within("NeverInternedBefore", 0), None);
+        assert!(Symbol::interned_bytes() >= "HeaderFooter".len());
+    }
+}
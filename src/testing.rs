@@ -0,0 +1,318 @@
+//! Helpers for tests that compile and run generated code.
+//!
+//! Backends are checked by compiling what they generate with the toolchain of their language
+//! and running the result. These helpers find that toolchain, give each test a scratch
+//! directory, and run commands with their output captured, so a failure reports what the
+//! compiler said. A test whose toolchain is missing is skipped rather than failed, unless
+//! `ONYX_REQUIRE_TOOLCHAINS` is set, as it should be in CI.
+//!
+//! ```rust,no_run
+//! use onyx::testing::{self, TempDir, Toolchain};
+//! use std::process::Command;
+//!
+//! let Some(cxx) = testing::require(Toolchain::Cxx) else {
+//!     return;
+//! };
+//! let dir = TempDir::new("example").unwrap();
+//! let main = dir.path().join("main.cpp");
+//! std::fs::write(&main, "int main() { return 0; }\n").unwrap();
+//! testing::check(Command::new(cxx).arg(&main).arg("-o").arg(dir.path().join("main")));
+//! testing::check(&mut Command::new(dir.path().join("main")));
+//! ```
+
+use std::{
+    env, fmt, fs, io,
+    path::{Path, PathBuf},
+    process::{Command, Output},
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+/// The environment variable that makes a missing toolchain fail a test instead of skipping it.
+pub const REQUIRE_TOOLCHAINS_VAR: &str = "ONYX_REQUIRE_TOOLCHAINS";
+
+/// A compiler that generated code is built with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Toolchain {
+    /// A C++11 compiler, for the `cpp` target.
+    Cxx,
+    /// `rustc`, for the `rust` target.
+    Rustc,
+}
+
+impl Toolchain {
+    /// All toolchains, in declaration order.
+    pub const ALL: [Toolchain; 2] = [Toolchain::Cxx, Toolchain::Rustc];
+
+    /// Returns the toolchain that compiles the output of `target`, one of
+    /// [`TARGETS`](crate::generators::TARGETS).
+    pub fn for_target(target: &str) -> Option<Toolchain> {
+        match target {
+            "cpp" => Some(Toolchain::Cxx),
+            "rust" => Some(Toolchain::Rustc),
+            _ => None,
+        }
+    }
+
+    /// Returns the environment variable that names the program to use: `CXX` or `RUSTC`.
+    pub fn env_var(&self) -> &'static str {
+        match self {
+            Toolchain::Cxx => "CXX",
+            Toolchain::Rustc => "RUSTC",
+        }
+    }
+
+    /// Returns the program used when [`env_var`](Toolchain::env_var) is unset: `g++` or
+    /// `rustc`.
+    pub fn default_program(&self) -> &'static str {
+        match self {
+            Toolchain::Cxx => "g++",
+            Toolchain::Rustc => "rustc",
+        }
+    }
+
+    /// Returns the path of the program, looking up [`env_var`](Toolchain::env_var) or else
+    /// the default program on `PATH`, or `None` if it is not installed.
+    pub fn find(&self) -> Option<PathBuf> {
+        let program = env::var_os(self.env_var())
+            .filter(|program| !program.is_empty())
+            .unwrap_or_else(|| self.default_program().into());
+        find_program(Path::new(&program))
+    }
+}
+
+/// Returns the path of `program`: itself if it names a file by path, or the first match in the
+/// directories of `PATH`.
+pub fn find_program(program: &Path) -> Option<PathBuf> {
+    if program.components().count() > 1 {
+        return program.is_file().then(|| program.to_path_buf());
+    }
+    let path = env::var_os("PATH")?;
+    env::split_paths(&path).find_map(|dir| {
+        let candidate = dir.join(program);
+        if candidate.is_file() {
+            return Some(candidate);
+        }
+        let exe = candidate.with_extension(env::consts::EXE_EXTENSION);
+        exe.is_file().then_some(exe)
+    })
+}
+
+/// Returns the program of `toolchain`, or `None` after reporting that the calling test is
+/// skipped.
+///
+/// # Panics
+///
+/// Panics if the toolchain is missing and `ONYX_REQUIRE_TOOLCHAINS` is set.
+#[track_caller]
+pub fn require(toolchain: Toolchain) -> Option<PathBuf> {
+    if let Some(program) = toolchain.find() {
+        return Some(program);
+    }
+    let message = format!(
+        "{} not found; install it or set {}",
+        toolchain.default_program(),
+        toolchain.env_var()
+    );
+    if env::var_os(REQUIRE_TOOLCHAINS_VAR).is_some() {
+        panic!("{message}");
+    }
+    eprintln!("skipping: {message}");
+    None
+}
+
+/// A command that could not be run or exited unsuccessfully, with what it printed.
+#[derive(Debug)]
+pub struct CommandFailed {
+    /// The command line, for reporting.
+    pub command: String,
+    /// The exit code, or `None` if the command was killed by a signal or could not start.
+    pub code: Option<i32>,
+    /// What the command printed to stdout.
+    pub stdout: String,
+    /// What the command printed to stderr, or why it could not start.
+    pub stderr: String,
+}
+
+impl fmt::Display for CommandFailed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.code {
+            Some(code) => write!(f, "`{}` exited with status {code}", self.command)?,
+            None => write!(f, "`{}` did not run to completion", self.command)?,
+        }
+        for (name, output) in [("stdout", &self.stdout), ("stderr", &self.stderr)] {
+            if !output.trim().is_empty() {
+                write!(f, "\n--- {name} ---\n{}", output.trim_end())?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for CommandFailed {}
+
+/// Runs `command` to completion with its output captured.
+pub fn run(command: &mut Command) -> Result<Output, CommandFailed> {
+    let command_line = command_line(command);
+    let output = command.output().map_err(|e| CommandFailed {
+        command: command_line.clone(),
+        code: None,
+        stdout: String::new(),
+        stderr: e.to_string(),
+    })?;
+    if output.status.success() {
+        return Ok(output);
+    }
+    Err(CommandFailed {
+        command: command_line,
+        code: output.status.code(),
+        stdout: String::from_utf8_lossy(&output.stdout).into_owned(),
+        stderr: String::from_utf8_lossy(&output.stderr).into_owned(),
+    })
+}
+
+/// Runs `command` like [`run`] and returns its stdout.
+///
+/// # Panics
+///
+/// Panics with the command line and its output if it fails.
+#[track_caller]
+pub fn check(command: &mut Command) -> String {
+    match run(command) {
+        Ok(output) => String::from_utf8_lossy(&output.stdout).into_owned(),
+        Err(failed) => panic!("{failed}"),
+    }
+}
+
+fn command_line(command: &Command) -> String {
+    let mut line = command.get_program().to_string_lossy().into_owned();
+    for arg in command.get_args() {
+        line.push(' ');
+        line.push_str(&arg.to_string_lossy());
+    }
+    line
+}
+
+/// A fresh directory under the system temporary directory, removed with its contents when
+/// dropped.
+#[derive(Debug)]
+pub struct TempDir {
+    path: PathBuf,
+    keep: bool,
+}
+
+impl TempDir {
+    /// Creates an empty directory whose name starts with `onyx-{prefix}`.
+    pub fn new(prefix: &str) -> io::Result<TempDir> {
+        static COUNTER: AtomicUsize = AtomicUsize::new(0);
+        loop {
+            let n = COUNTER.fetch_add(1, Ordering::Relaxed);
+            let path = env::temp_dir().join(format!("onyx-{prefix}-{}-{n}", std::process::id()));
+            match fs::create_dir(&path) {
+                Ok(()) => return Ok(TempDir { path, keep: false }),
+                Err(e) if e.kind() == io::ErrorKind::AlreadyExists => continue,
+                Err(e) => return Err(e),
+            }
+        }
+    }
+
+    /// Returns the path of the directory.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Keeps the directory after the `TempDir` is dropped, such as to inspect the files of a
+    /// failing test, and returns its path.
+    pub fn keep(mut self) -> PathBuf {
+        self.keep = true;
+        self.path.clone()
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        if !self.keep {
+            let _ = fs::remove_dir_all(&self.path);
+        }
+    }
+}
+
+/// Writes generated `files` under `root`, creating their directories, and returns their paths.
+pub fn write_files(root: &Path, files: &[(PathBuf, String)]) -> io::Result<Vec<PathBuf>> {
+    let mut paths = Vec::new();
+    for (path, contents) in files {
+        let path = root.join(path);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&path, contents)?;
+        paths.push(path);
+    }
+    Ok(paths)
+}
+
+/// Returns the schemas in `dir`, the files with an `.onyx` extension, sorted by path.
+pub fn fixtures(dir: &Path) -> io::Result<Vec<PathBuf>> {
+    let mut fixtures = Vec::new();
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path
+            .extension()
+            .is_some_and(|extension| extension == "onyx")
+        {
+            fixtures.push(path);
+        }
+    }
+    fixtures.sort();
+    Ok(fixtures)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_toolchains() {
+        assert_eq!(Toolchain::for_target("cpp"), Some(Toolchain::Cxx));
+        assert_eq!(Toolchain::for_target("rust"), Some(Toolchain::Rustc));
+        assert_eq!(Toolchain::for_target("py"), None);
+        assert_eq!(find_program(Path::new("onyx-no-such-program")), None);
+        assert_eq!(find_program(Path::new("./onyx-no-such-program")), None);
+    }
+
+    #[test]
+    fn test_temp_dir() {
+        let dir = TempDir::new("unit").unwrap();
+        let other = TempDir::new("unit").unwrap();
+        assert_ne!(dir.path(), other.path());
+
+        let files = [(PathBuf::from("a/b.onyx"), "struct A { x u8, }".to_string())];
+        let paths = write_files(dir.path(), &files).unwrap();
+        assert_eq!(paths, [dir.path().join("a/b.onyx")]);
+        assert_eq!(fixtures(&dir.path().join("a")).unwrap(), paths);
+
+        let path = dir.path().to_path_buf();
+        drop(dir);
+        assert!(!path.exists());
+        let kept = other.keep();
+        assert!(kept.exists());
+        fs::remove_dir(kept).unwrap();
+    }
+
+    #[test]
+    fn test_run() {
+        let Some(rustc) = require(Toolchain::Rustc) else {
+            return;
+        };
+        assert!(check(Command::new(&rustc).arg("--version")).starts_with("rustc "));
+
+        let failed = run(Command::new(&rustc).arg("--no-such-flag")).unwrap_err();
+        assert!(failed.code.is_some_and(|code| code != 0));
+        let message = failed.to_string();
+        assert!(message.contains("--no-such-flag` exited with status"));
+        assert!(message.contains("--- stderr ---"));
+
+        let failed = run(&mut Command::new("onyx-no-such-program")).unwrap_err();
+        assert_eq!(failed.code, None);
+    }
+}
@@ -0,0 +1,20 @@
+//! Internal logging macros.
+//!
+//! These forward to the `tracing` crate when the `tracing` feature is enabled and expand to
+//! nothing otherwise, so call sites don't need their own `#[cfg]` attributes.
+
+macro_rules! trace {
+    ($($arg:tt)*) => {
+        #[cfg(feature = "tracing")]
+        ::tracing::trace!($($arg)*);
+    };
+}
+
+macro_rules! debug {
+    ($($arg:tt)*) => {
+        #[cfg(feature = "tracing")]
+        ::tracing::debug!($($arg)*);
+    };
+}
+
+pub(crate) use {debug, trace};
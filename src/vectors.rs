@@ -0,0 +1,581 @@
+//! Canonical test vectors: representative values of each struct and message, with the bytes
+//! they are encoded to.
+//!
+//! A backend that reads each vector's bytes to its value, and writes its value to its bytes,
+//! agrees on the wire with every other backend that does. [`vectors`] builds three vectors for
+//! each struct and message a module defines, encoded with the reference codec in
+//! [`dynamic`]:
+//!
+//! - `min` holds the smallest value of every field: zero or the most negative integer,
+//!   `false`, the lowest finite float, the first variant of an enum, no flags, empty vectors,
+//!   and zeroed byte arrays.
+//! - `max` holds the largest: the highest integer, `true`, the highest finite float, the last
+//!   variant of an enum, every flag, full vectors, and byte arrays of `0xFF`.
+//! - `mixed` holds a different value in each field, whose bytes differ from each other so that
+//!   a swapped or misplaced byte shows, and half-full vectors.
+//!
+//! Conditional fields are left out when their condition does not hold. Fixed-point fields stay
+//! within 32 bits, so their scaled values are exact in JSON.
+//!
+//! [`to_json`] writes the vectors as the `.vectors.json` file that the C++ and Rust generators
+//! emit, with generated tests that check them, when `set_vectors` is enabled. Each value is
+//! written as [`dynamic::from_json`] reads it, so test suites in any
+//! language can build the value and compare bytes.
+//!
+//! ```rust
+//! use onyx::dynamic::Value;
+//! use onyx::parser::Parser;
+//! use onyx::vectors;
+//!
+//! let module = Parser::new("endian = big\nstruct Point { x i16, y u8, }")
+//!     .and_then(|p| p.parse_module())
+//!     .unwrap();
+//! let vectors = vectors::vectors(&module).unwrap();
+//! assert_eq!(vectors.len(), 3);
+//! assert_eq!(vectors[0].name, "min");
+//! assert_eq!(vectors[0].bytes, [0x80, 0x00, 0x00]);
+//! assert_eq!(vectors[1].bytes, [0x7F, 0xFF, 0xFF]);
+//! assert!(vectors::to_json(&module, &vectors).unwrap().contains(r#""hex": "7fffff""#));
+//! ```
+
+use std::fmt::Write;
+
+use crate::{
+    ast::{Definition, EnumDef, Field, OnyxModule, PrimitiveType, Type},
+    diagnostic::Diagnostic,
+    dynamic::{self, Value},
+    fingerprint, hir, json,
+    symbol::Symbol,
+    wire,
+};
+
+/// A value of a struct or message and the bytes it is encoded to.
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub struct TestVector {
+    /// The struct or message the value is of.
+    pub definition: Symbol,
+    /// Which representative value this is: `min`, `max`, or `mixed`.
+    pub name: &'static str,
+    /// The value, as [`dynamic::encode`] takes it.
+    pub value: Value,
+    /// The bytes the value is encoded to, in the module's endianness. For `endian = host`,
+    /// that is the endianness of the machine that built the vectors.
+    pub bytes: Vec<u8>,
+}
+
+/// The representative values, in the order they are built.
+const SAMPLES: [Sample; 3] = [Sample::Min, Sample::Max, Sample::Mixed];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Sample {
+    Min,
+    Max,
+    Mixed,
+}
+
+impl Sample {
+    fn name(self) -> &'static str {
+        match self {
+            Sample::Min => "min",
+            Sample::Max => "max",
+            Sample::Mixed => "mixed",
+        }
+    }
+}
+
+/// The widest fixed-point raw integer in a vector, so its scaled value is an exact JSON number.
+const FIXED_BITS: usize = 32;
+
+/// Returns the test vectors of every struct and message `module` defines, in definition order.
+pub fn vectors(module: &OnyxModule) -> Result<Vec<TestVector>, Diagnostic> {
+    let mut vectors = Vec::new();
+    for id in &module.order {
+        let Some(def @ (Definition::Struct(_) | Definition::Message(_))) =
+            module.definitions.get(id)
+        else {
+            continue;
+        };
+        for sample in SAMPLES {
+            let value = Sampler {
+                module,
+                sample,
+                next: 0,
+            }
+            .record(def);
+            let bytes = dynamic::encode(module, def.name(), &value)?;
+            vectors.push(TestVector {
+                definition: *id,
+                name: sample.name(),
+                value,
+                bytes,
+            });
+        }
+    }
+    Ok(vectors)
+}
+
+/// Builds the values of one sample, counting the leaves of a `mixed` value as it goes.
+struct Sampler<'m> {
+    module: &'m OnyxModule,
+    sample: Sample,
+    next: u64,
+}
+
+impl Sampler<'_> {
+    fn record(&mut self, def: &Definition) -> Value {
+        let mut values = Vec::new();
+        for field in fields(def) {
+            let value = match (&field.type_info, field.bit_field_size) {
+                (Type::Primitive(p), Some(bits)) => self.primitive(*p, bits),
+                (type_info, _) => self.value(type_info),
+            };
+            values.push((field.name, value));
+        }
+        // Conditions read unconditional fields, so those decide which fields are kept.
+        let kept: Vec<bool> = fields(def)
+            .iter()
+            .map(|field| is_present(self.module, field, &values))
+            .collect();
+        let mut kept = kept.into_iter();
+        values.retain(|_| kept.next().unwrap());
+        Value::Record(values)
+    }
+
+    fn value(&mut self, type_info: &Type) -> Value {
+        match type_info {
+            Type::Primitive(p) => self.primitive(*p, p.get_bit_width()),
+            Type::Fixed { base, .. } => self.primitive(*base, base.get_bit_width().min(FIXED_BITS)),
+            Type::Uuid | Type::Bytes(_) => {
+                let len = type_info.byte_len().unwrap();
+                let first = self.leaf() as u8;
+                Value::Bytes(match self.sample {
+                    Sample::Min => vec![0; len],
+                    Sample::Max => vec![0xFF; len],
+                    Sample::Mixed => (0..len).map(|i| first.wrapping_add(i as u8)).collect(),
+                })
+            }
+            Type::Custom(name) => match &self.module.definitions[name] {
+                Definition::Enum(e) => self.variant(e),
+                def => self.record(def),
+            },
+            Type::Vector { element, capacity } => {
+                let len = match self.sample {
+                    Sample::Min => 0,
+                    Sample::Max => *capacity,
+                    Sample::Mixed => capacity.div_ceil(2),
+                };
+                Value::List((0..len).map(|_| self.value(element)).collect())
+            }
+        }
+    }
+
+    /// Returns a `bits`-wide value of `p`.
+    fn primitive(&mut self, p: PrimitiveType, bits: usize) -> Value {
+        let k = self.leaf();
+        match (p, self.sample) {
+            (PrimitiveType::Bool, sample) => Value::Bool(match sample {
+                Sample::Min => false,
+                Sample::Max => true,
+                Sample::Mixed => k % 2 == 1,
+            }),
+            (PrimitiveType::F32, Sample::Min) => Value::F32(f32::MIN),
+            (PrimitiveType::F32, Sample::Max) => Value::F32(f32::MAX),
+            (PrimitiveType::F32, Sample::Mixed) => Value::F32(mixed_float(k) as f32),
+            (PrimitiveType::F64, Sample::Min) => Value::F64(f64::MIN),
+            (PrimitiveType::F64, Sample::Max) => Value::F64(f64::MAX),
+            (PrimitiveType::F64, Sample::Mixed) => Value::F64(mixed_float(k)),
+            _ => {
+                let raw = match self.sample {
+                    Sample::Min if p.is_signed() => 1 << (bits - 1),
+                    Sample::Min => 0,
+                    Sample::Max if p.is_signed() => wire::mask(bits - 1),
+                    Sample::Max => wire::mask(bits),
+                    // Byte `i` of leaf `k` is `16k + i + 1`, so no two bytes are alike.
+                    Sample::Mixed => {
+                        let bytes: [u8; 8] = std::array::from_fn(|i| (16 * k + i as u64 + 1) as u8);
+                        u64::from_le_bytes(bytes) as u128 & wire::mask(bits)
+                    }
+                };
+                integer(p, raw, bits)
+            }
+        }
+    }
+
+    fn variant(&mut self, e: &EnumDef) -> Value {
+        let k = self.leaf();
+        let values = e.variant_values();
+        let raw = match (e.flags, self.sample) {
+            (true, Sample::Min) => 0,
+            (true, Sample::Max) => values.iter().fold(0, |bits, v| bits | v),
+            (true, Sample::Mixed) => values
+                .iter()
+                .enumerate()
+                .filter(|(i, _)| (*i as u64 + k).is_multiple_of(2))
+                .fold(0, |bits, (_, v)| bits | v),
+            (false, Sample::Min) => values[0],
+            (false, Sample::Max) => values[values.len() - 1],
+            (false, Sample::Mixed) => values[k as usize % values.len()],
+        };
+        let bits = e.underlying_type.get_bit_width();
+        integer(e.underlying_type, raw as u128 & wire::mask(bits), bits)
+    }
+
+    /// Returns the index of the next leaf of the value.
+    fn leaf(&mut self) -> u64 {
+        self.next += 1;
+        self.next - 1
+    }
+}
+
+/// Returns `1.5`, `-2.5`, `3.5`, and so on, which every float type holds exactly.
+fn mixed_float(k: u64) -> f64 {
+    let magnitude = k as f64 + 1.5;
+    if k % 2 == 1 { -magnitude } else { magnitude }
+}
+
+/// Returns the `bits`-wide two's complement or unsigned integer `raw` as a value of `p`.
+fn integer(p: PrimitiveType, raw: u128, bits: usize) -> Value {
+    match p.is_signed() {
+        true => Value::Signed(wire::sign_extend(raw, bits) as i64),
+        false => Value::Unsigned(raw as u64),
+    }
+}
+
+fn fields(def: &Definition) -> &[Field] {
+    match def {
+        Definition::Struct(s) => &s.fields,
+        Definition::Message(m) => &m.fields,
+        Definition::Enum(_) => &[],
+    }
+}
+
+/// Returns true if `field` is present among the fields `values` of its record.
+fn is_present(module: &OnyxModule, field: &Field, values: &[(Symbol, Value)]) -> bool {
+    let Some(condition) = &field.condition else {
+        return true;
+    };
+    let raw = match values.iter().find(|(name, _)| *name == condition.field) {
+        Some((_, Value::Bool(b))) => *b as u64,
+        Some((_, Value::Unsigned(v))) => *v,
+        Some((_, Value::Signed(v))) => *v as u64,
+        _ => 0,
+    };
+    condition.holds(module, raw)
+}
+
+/// Returns `vectors` as a JSON document: the module's endianness and
+/// [fingerprint], and each vector's definition, name, bytes as hex, and
+/// value, as [`dynamic::from_json`] reads it.
+///
+/// Integers beyond the range a JSON number holds exactly are strings of decimal digits. An
+/// enum is the name of its variant, or the names of its flags joined with `|`, and a byte
+/// array is a string of hex digits.
+pub fn to_json(module: &OnyxModule, vectors: &[TestVector]) -> Result<String, Diagnostic> {
+    let endianness = match wire::is_little(module.endianness) {
+        true => "little",
+        false => "big",
+    };
+    let fingerprint = fingerprint::module(&hir::lower(module)?);
+    let mut out = String::new();
+    writeln!(out, "{{").unwrap();
+    writeln!(out, "  \"endianness\": \"{endianness}\",").unwrap();
+    writeln!(out, "  \"fingerprint\": \"{fingerprint:016x}\",").unwrap();
+    writeln!(out, "  \"vectors\": [").unwrap();
+    for (i, vector) in vectors.iter().enumerate() {
+        let def = &module.definitions[&vector.definition];
+        let hex: String = vector.bytes.iter().map(|b| format!("{b:02x}")).collect();
+        writeln!(out, "    {{").unwrap();
+        writeln!(out, "      \"definition\": {},", json::string(def.name())).unwrap();
+        writeln!(out, "      \"name\": \"{}\",", vector.name).unwrap();
+        writeln!(out, "      \"hex\": \"{hex}\",").unwrap();
+        writeln!(
+            out,
+            "      \"value\": {}",
+            record_json(module, def, &vector.value)
+        )
+        .unwrap();
+        let comma = if i + 1 < vectors.len() { "," } else { "" };
+        writeln!(out, "    }}{comma}").unwrap();
+    }
+    writeln!(out, "  ]").unwrap();
+    writeln!(out, "}}").unwrap();
+    Ok(out)
+}
+
+fn record_json(module: &OnyxModule, def: &Definition, value: &Value) -> String {
+    let Value::Record(values) = value else {
+        unreachable!("vectors of structs and messages are records");
+    };
+    let entries: Vec<String> = values
+        .iter()
+        .map(|(name, value)| {
+            let field = fields(def).iter().find(|f| f.name == *name).unwrap();
+            let value = type_json(module, &field.type_info, value);
+            format!("{}: {value}", json::string(name.as_str()))
+        })
+        .collect();
+    format!("{{{}}}", entries.join(", "))
+}
+
+fn type_json(module: &OnyxModule, type_info: &Type, value: &Value) -> String {
+    match (type_info, value) {
+        (Type::Fixed { scale, .. }, Value::Signed(raw)) => {
+            format!("{:?}", *raw as f64 / *scale as f64)
+        }
+        (Type::Fixed { scale, .. }, Value::Unsigned(raw)) => {
+            format!("{:?}", *raw as f64 / *scale as f64)
+        }
+        (Type::Custom(name), _) => match &module.definitions[name] {
+            Definition::Enum(e) => enum_json(e, value),
+            def => record_json(module, def, value),
+        },
+        (Type::Vector { element, .. }, Value::List(items)) => {
+            let items: Vec<String> = items
+                .iter()
+                .map(|item| type_json(module, element, item))
+                .collect();
+            format!("[{}]", items.join(", "))
+        }
+        (_, value) => scalar_json(value),
+    }
+}
+
+fn scalar_json(value: &Value) -> String {
+    // Integers up to 2^53 are exact as JSON numbers.
+    const EXACT: u64 = 1 << 53;
+    match value {
+        Value::Bool(b) => b.to_string(),
+        Value::Unsigned(v) if *v <= EXACT => v.to_string(),
+        Value::Signed(v) if v.unsigned_abs() <= EXACT => v.to_string(),
+        Value::Unsigned(v) => format!("\"{v}\""),
+        Value::Signed(v) => format!("\"{v}\""),
+        Value::F32(f) => format!("{:?}", *f as f64),
+        Value::F64(f) => format!("{f:?}"),
+        Value::Bytes(bytes) => {
+            let hex: String = bytes.iter().map(|b| format!("{b:02x}")).collect();
+            format!("\"{hex}\"")
+        }
+        Value::Record(_) | Value::List(_) => unreachable!("records and lists have a type"),
+    }
+}
+
+/// Returns the variant name of an enum value, the flag names joined with `|`, or the number if
+/// no names add up to it.
+fn enum_json(e: &EnumDef, value: &Value) -> String {
+    let raw = match value {
+        Value::Signed(v) => *v as u64,
+        Value::Unsigned(v) => *v,
+        _ => unreachable!("enums are integers"),
+    };
+    let values = e.variant_values();
+    let names: Vec<&str> = match e.flags {
+        true => e
+            .variants
+            .iter()
+            .zip(&values)
+            .filter(|(variant, v)| !variant.composite && **v != 0 && *v & !raw == 0)
+            .map(|(variant, _)| variant.name.as_str())
+            .collect(),
+        false => e
+            .variants
+            .iter()
+            .zip(&values)
+            .filter(|(_, v)| **v == raw)
+            .map(|(variant, _)| variant.name.as_str())
+            .take(1)
+            .collect(),
+    };
+    let covered = names.iter().fold(0, |bits, name| {
+        let i = e.variants.iter().position(|v| v.name == *name).unwrap();
+        bits | values[i]
+    });
+    match !names.is_empty() && covered == raw {
+        true => json::string(&names.join(" | ")),
+        false => scalar_json(value),
+    }
+}
+
+/// A primitive, enum, fixed-point, or byte array field of a vector's value, or the length of
+/// one of its vectors, reached through a path of fields and vector elements. Generators turn
+/// leaves into the checks of their test suites.
+pub(crate) struct Leaf<'m> {
+    pub(crate) path: Vec<Step>,
+    pub(crate) kind: LeafKind<'m>,
+    pub(crate) value: Value,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum Step {
+    Field(Symbol),
+    Index(usize),
+}
+
+pub(crate) enum LeafKind<'m> {
+    Primitive(PrimitiveType),
+    Enum(&'m EnumDef),
+    /// A fixed-point number, whose value is its raw integer of this type.
+    Fixed(PrimitiveType),
+    /// A `uuid` or `bytes[N]`.
+    Bytes,
+    /// The length of a vector, and the name of its element type if that is a struct or message.
+    Length(Option<Symbol>),
+}
+
+/// Returns the leaves of `value`, a value of the struct or message `def`, in order. A vector's
+/// length comes before its elements.
+pub(crate) fn leaves<'m>(module: &'m OnyxModule, def: &Definition, value: &Value) -> Vec<Leaf<'m>> {
+    let mut out = Vec::new();
+    record_leaves(module, def, value, &mut Vec::new(), &mut out);
+    out
+}
+
+fn record_leaves<'m>(
+    module: &'m OnyxModule,
+    def: &Definition,
+    value: &Value,
+    path: &mut Vec<Step>,
+    out: &mut Vec<Leaf<'m>>,
+) {
+    let Value::Record(values) = value else {
+        unreachable!("vectors of structs and messages are records");
+    };
+    for (name, value) in values {
+        let field = fields(def).iter().find(|f| f.name == *name).unwrap();
+        path.push(Step::Field(*name));
+        type_leaves(module, &field.type_info, value, path, out);
+        path.pop();
+    }
+}
+
+fn type_leaves<'m>(
+    module: &'m OnyxModule,
+    type_info: &Type,
+    value: &Value,
+    path: &mut Vec<Step>,
+    out: &mut Vec<Leaf<'m>>,
+) {
+    let mut leaf = |kind| {
+        out.push(Leaf {
+            path: path.clone(),
+            kind,
+            value: value.clone(),
+        })
+    };
+    match type_info {
+        Type::Primitive(p) => leaf(LeafKind::Primitive(*p)),
+        Type::Fixed { base, .. } => leaf(LeafKind::Fixed(*base)),
+        Type::Uuid | Type::Bytes(_) => leaf(LeafKind::Bytes),
+        Type::Custom(name) => match &module.definitions[name] {
+            Definition::Enum(e) => leaf(LeafKind::Enum(e)),
+            def => record_leaves(module, def, value, path, out),
+        },
+        Type::Vector { element, .. } => {
+            let Value::List(items) = value else {
+                unreachable!("vectors are lists");
+            };
+            let record = match **element {
+                Type::Custom(name) if !matches!(module.definitions[&name], Definition::Enum(_)) => {
+                    Some(name)
+                }
+                _ => None,
+            };
+            out.push(Leaf {
+                path: path.clone(),
+                kind: LeafKind::Length(record),
+                value: Value::Unsigned(items.len() as u64),
+            });
+            for (i, item) in items.iter().enumerate() {
+                path.push(Step::Index(i));
+                type_leaves(module, element, item, path, out);
+                path.pop();
+            }
+        }
+    }
+}
+
+/// Returns the name of the variant of `e` whose value is `value`, if one is.
+pub(crate) fn variant_name<'e>(e: &'e EnumDef, value: &Value) -> Option<&'e str> {
+    let index = e
+        .variant_values()
+        .iter()
+        .position(|v| *v == raw_bits(value))?;
+    Some(e.variants[index].name.as_str())
+}
+
+/// Returns the bits of an integer, bool, or float value, sign-extended to 64 bits.
+pub(crate) fn raw_bits(value: &Value) -> u64 {
+    match value {
+        Value::Bool(b) => *b as u64,
+        Value::Unsigned(v) => *v,
+        Value::Signed(v) => *v as u64,
+        Value::F32(f) => f.to_bits() as u64,
+        Value::F64(f) => f.to_bits(),
+        Value::Record(_) | Value::List(_) | Value::Bytes(_) => {
+            unreachable!("leaves are scalars")
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::Parser;
+
+    #[test]
+    fn test_vectors() {
+        let module = Parser::new(
+            "enum Kind : i8 { Low = 1, High = 5, }
+             flags Access : u8 { Read, Write, Exec, }
+             message Packet {
+                 kind Kind,
+                 access Access,
+                 big u64,
+                 gain fixed<i64, 100>,
+                 tag bytes[2],
+                 items vec<u16, 3>,
+                 extra u8 if kind == Kind.High,
+             }",
+        )
+        .and_then(|p| p.parse_module())
+        .unwrap();
+        let vectors = vectors(&module).unwrap();
+        let names: Vec<&str> = vectors.iter().map(|v| v.name).collect();
+        assert_eq!(names, ["min", "max", "mixed"]);
+        for vector in &vectors {
+            let decoded = dynamic::decode(&module, "Packet", &vector.bytes).unwrap();
+            assert_eq!(decoded, vector.value);
+        }
+
+        // The condition holds only in `max`, whose kind is the last variant.
+        let Value::Record(min) = &vectors[0].value else {
+            unreachable!()
+        };
+        assert_eq!(min.len(), 6);
+        assert_eq!(min[4], ("tag".into(), Value::Bytes(vec![0, 0])));
+        let Value::Record(max) = &vectors[1].value else {
+            unreachable!()
+        };
+        assert_eq!(max.len(), 7);
+        assert_eq!(max[3], ("gain".into(), Value::Signed(i32::MAX as i64)));
+
+        // Every value reads back from the JSON it is written as.
+        let document = to_json(&module, &vectors).unwrap();
+        assert!(document.contains(r#""access": "Read | Write | Exec""#));
+        assert!(document.contains(r#""big": "18446744073709551615""#));
+        for vector in &vectors {
+            let start = document.find(&format!("\"hex\": \"{}", hex(&vector.bytes)));
+            let line = document[start.unwrap()..].lines().nth(1).unwrap();
+            let value = line.trim().strip_prefix("\"value\": ").unwrap();
+            assert_eq!(
+                dynamic::from_json(&module, "Packet", value).unwrap(),
+                vector.value,
+                "{value}"
+            );
+        }
+    }
+
+    fn hex(bytes: &[u8]) -> String {
+        bytes.iter().map(|b| format!("{b:02x}")).collect()
+    }
+}
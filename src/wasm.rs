@@ -0,0 +1,89 @@
+//! WebAssembly bindings for running the compiler in the browser.
+//!
+//! Build with `cargo build --lib --target wasm32-unknown-unknown --features wasm` and run
+//! `wasm-bindgen` over the output. Nothing here touches the filesystem: output paths are
+//! virtual, derived from the `name` passed by the caller.
+
+use std::path::PathBuf;
+
+use wasm_bindgen::prelude::*;
+
+use crate::{diagnostic::Diagnostic, generators, parser::Parser};
+
+/// A generated file, identified by its virtual path.
+#[wasm_bindgen]
+pub struct CompiledFile {
+    path: String,
+    contents: String,
+}
+
+#[wasm_bindgen]
+impl CompiledFile {
+    /// The virtual path of the file, e.g. `schema.hpp`.
+    #[wasm_bindgen(getter)]
+    pub fn path(&self) -> String {
+        self.path.clone()
+    }
+
+    /// The generated source code.
+    #[wasm_bindgen(getter)]
+    pub fn contents(&self) -> String {
+        self.contents.clone()
+    }
+}
+
+/// Parses and resolves a schema, returning an error describing the first problem found.
+#[wasm_bindgen]
+pub fn check(source: &str) -> Result<(), JsError> {
+    Parser::new(source)
+        .and_then(|p| p.parse_module())
+        .map(|_| ())
+        .map_err(to_js_error)
+}
+
+/// Compiles a schema for `target` (`cpp` or `rust`). `name` is the virtual path of the
+/// schema, used to name the outputs and derive include guards.
+#[wasm_bindgen]
+pub fn compile(source: &str, target: &str, name: &str) -> Result<Vec<CompiledFile>, JsError> {
+    compile_files(source, target, name).map_err(to_js_error)
+}
+
+fn compile_files(source: &str, target: &str, name: &str) -> Result<Vec<CompiledFile>, Diagnostic> {
+    let module = Parser::new(source)?.parse_module()?;
+    let files = generators::generator_for_target(target, PathBuf::from(name))?.generate(&module)?;
+
+    Ok(files
+        .into_iter()
+        .map(|(path, contents)| CompiledFile {
+            path: path.to_string_lossy().into_owned(),
+            contents,
+        })
+        .collect())
+}
+
+fn to_js_error(diagnostic: Diagnostic) -> JsError {
+    JsError::new(&diagnostic.summary())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compile_uses_virtual_paths() {
+        let files = compile_files("struct S { f u8, }", "cpp", "schemas/s.onyx").unwrap();
+        let paths: Vec<&str> = files.iter().map(|f| f.path.as_str()).collect();
+        assert_eq!(paths, ["schemas/s.hpp", "schemas/s.cpp"]);
+    }
+
+    #[test]
+    fn test_compile_errors() {
+        let err = compile_files("message A { hdr B, }", "rust", "a.onyx")
+            .err()
+            .unwrap();
+        assert_eq!(
+            err.summary(),
+            "error[E0006]: custom type 'B' not defined at position 1:13"
+        );
+    }
+}
@@ -0,0 +1,217 @@
+//! Reading and writing integers, floats, and bit ranges in a wire endianness.
+//!
+//! These are the byte and bit rules of the wire format, shared by [`dynamic`](crate::dynamic)
+//! and [`inspect`](crate::inspect) and followed by the generated code:
+//!
+//! - An integer of `n` bytes is stored in the module's endianness. [`WireEndianness::Host`]
+//!   means the endianness of the machine running this code.
+//! - A run of bit-fields shares one container integer. A bit range is addressed by its
+//!   `shift`, the position of its lowest bit within that integer, so the first field of a run
+//!   occupies the least significant bits whatever the byte order.
+//! - A float is stored as the integer holding its IEEE 754 bits, so byte order applies to it as
+//!   to an integer of its width. Its bits are copied exactly: NaN payloads, the sign of zero,
+//!   and subnormals survive a round trip.
+//!
+//! Integers are at most 16 bytes wide.
+//!
+//! ```rust
+//! use onyx::ast::WireEndianness;
+//! use onyx::wire;
+//!
+//! let mut container = [0; 2];
+//! wire::write_bits(&mut container, WireEndianness::Big, 4, 8, 0xAB);
+//! assert_eq!(container, [0x0A, 0xB0]);
+//! assert_eq!(wire::read_bits(&container, WireEndianness::Big, 4, 8), 0xAB);
+//! assert_eq!(wire::sign_extend(0xAB, 8), -0x55);
+//!
+//! let mut float = [0; 4];
+//! wire::write_f32(-0.0, WireEndianness::Big, &mut float);
+//! assert_eq!(float, [0x80, 0, 0, 0]);
+//! assert!(wire::read_f32(&float, WireEndianness::Big).is_sign_negative());
+//! ```
+
+use crate::ast::WireEndianness;
+
+/// Returns true if `endianness` stores the least significant byte first on this machine.
+pub fn is_little(endianness: WireEndianness) -> bool {
+    match endianness {
+        WireEndianness::Little => true,
+        WireEndianness::Big => false,
+        WireEndianness::Host => cfg!(target_endian = "little"),
+    }
+}
+
+/// Reads the unsigned integer stored in all of `bytes`.
+///
+/// # Panics
+///
+/// Panics if `bytes` is longer than 16 bytes.
+pub fn read_uint(bytes: &[u8], endianness: WireEndianness) -> u128 {
+    assert!(
+        bytes.len() <= 16,
+        "{}-byte integers are unsupported",
+        bytes.len()
+    );
+    let fold = |acc: u128, &b: &u8| (acc << 8) | b as u128;
+    match is_little(endianness) {
+        true => bytes.iter().rev().fold(0, fold),
+        false => bytes.iter().fold(0, fold),
+    }
+}
+
+/// Stores the low `out.len()` bytes of `value` in all of `out`.
+///
+/// # Panics
+///
+/// Panics if `out` is longer than 16 bytes.
+pub fn write_uint(value: u128, endianness: WireEndianness, out: &mut [u8]) {
+    assert!(
+        out.len() <= 16,
+        "{}-byte integers are unsupported",
+        out.len()
+    );
+    let len = out.len();
+    for (i, byte) in out.iter_mut().enumerate() {
+        let index = match is_little(endianness) {
+            true => i,
+            false => len - 1 - i,
+        };
+        *byte = (value >> (8 * index)) as u8;
+    }
+}
+
+/// Reads the `f32` whose bits are stored in `bytes`.
+pub fn read_f32(bytes: &[u8; 4], endianness: WireEndianness) -> f32 {
+    f32::from_bits(read_uint(bytes, endianness) as u32)
+}
+
+/// Stores the bits of `value` in `out`.
+pub fn write_f32(value: f32, endianness: WireEndianness, out: &mut [u8; 4]) {
+    write_uint(value.to_bits() as u128, endianness, out);
+}
+
+/// Reads the `f64` whose bits are stored in `bytes`.
+pub fn read_f64(bytes: &[u8; 8], endianness: WireEndianness) -> f64 {
+    f64::from_bits(read_uint(bytes, endianness) as u64)
+}
+
+/// Stores the bits of `value` in `out`.
+pub fn write_f64(value: f64, endianness: WireEndianness, out: &mut [u8; 8]) {
+    write_uint(value.to_bits() as u128, endianness, out);
+}
+
+/// Reads the `bits`-wide range at `shift` of the integer stored in `container`.
+pub fn read_bits(container: &[u8], endianness: WireEndianness, shift: usize, bits: usize) -> u128 {
+    (read_uint(container, endianness) >> shift) & mask(bits)
+}
+
+/// Stores the low `bits` bits of `value` in the range at `shift` of the integer stored in
+/// `container`, leaving the bits outside the range unchanged.
+pub fn write_bits(
+    container: &mut [u8],
+    endianness: WireEndianness,
+    shift: usize,
+    bits: usize,
+    value: u128,
+) {
+    let cleared = read_uint(container, endianness) & !(mask(bits) << shift);
+    write_uint(
+        cleared | (value & mask(bits)) << shift,
+        endianness,
+        container,
+    );
+}
+
+/// Returns a value with the low `bits` bits set.
+pub fn mask(bits: usize) -> u128 {
+    match bits {
+        128.. => u128::MAX,
+        _ => (1 << bits) - 1,
+    }
+}
+
+/// Interprets the low `bits` bits of `raw` as a two's complement integer.
+pub fn sign_extend(raw: u128, bits: usize) -> i128 {
+    if bits == 0 || bits >= 128 {
+        return raw as i128;
+    }
+    let shift = 128 - bits;
+    ((raw << shift) as i128) >> shift
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_uints() {
+        let mut out = [0; 3];
+        write_uint(0x010203, WireEndianness::Little, &mut out);
+        assert_eq!(out, [3, 2, 1]);
+        assert_eq!(read_uint(&out, WireEndianness::Little), 0x010203);
+        write_uint(0xFF010203, WireEndianness::Big, &mut out);
+        assert_eq!(out, [1, 2, 3]);
+        assert_eq!(read_uint(&out, WireEndianness::Big), 0x010203);
+
+        let host = match cfg!(target_endian = "little") {
+            true => WireEndianness::Little,
+            false => WireEndianness::Big,
+        };
+        write_uint(u128::MAX - 1, WireEndianness::Host, &mut out);
+        assert_eq!(read_uint(&out, host), 0xFFFFFE);
+        let mut wide = [0; 16];
+        write_uint(u128::MAX - 1, WireEndianness::Big, &mut wide);
+        assert_eq!(read_uint(&wide, WireEndianness::Big), u128::MAX - 1);
+        assert_eq!(read_uint(&[], WireEndianness::Big), 0);
+    }
+
+    #[test]
+    fn test_floats() {
+        let mut out = [0; 4];
+        write_f32(1.5, WireEndianness::Little, &mut out);
+        assert_eq!(out, [0, 0, 0xC0, 0x3F]);
+        write_f32(1.5, WireEndianness::Big, &mut out);
+        assert_eq!(out, [0x3F, 0xC0, 0, 0]);
+
+        // A signalling NaN with a payload, the smallest subnormal, and negative zero keep
+        // their exact bits in both byte orders.
+        for bits in [0x7F80_0001, 0xFFC0_1234, 0x0000_0001, 0x8000_0000] {
+            for endianness in [WireEndianness::Little, WireEndianness::Big] {
+                write_f32(f32::from_bits(bits), endianness, &mut out);
+                assert_eq!(read_f32(&out, endianness).to_bits(), bits);
+            }
+        }
+        let mut wide = [0; 8];
+        for bits in [
+            0x7FF0_0000_0000_0001,
+            0x000F_FFFF_FFFF_FFFF,
+            0x8000_0000_0000_0000,
+        ] {
+            write_f64(f64::from_bits(bits), WireEndianness::Big, &mut wide);
+            assert_eq!(wide, bits.to_be_bytes());
+            assert_eq!(read_f64(&wide, WireEndianness::Big).to_bits(), bits);
+        }
+    }
+
+    #[test]
+    fn test_bits() {
+        // A 7-bit field after a 1-bit field, in a little-endian container.
+        let mut container = [0b1010_1011, 0xFF];
+        assert_eq!(
+            read_bits(&container, WireEndianness::Little, 1, 7),
+            0b1010101
+        );
+        write_bits(&mut container, WireEndianness::Little, 1, 7, 0x1FF);
+        assert_eq!(container, [0xFF, 0xFF]);
+        write_bits(&mut container, WireEndianness::Little, 4, 8, 0);
+        assert_eq!(container, [0x0F, 0xF0]);
+
+        assert_eq!(mask(0), 0);
+        assert_eq!(mask(3), 0b111);
+        assert_eq!(mask(128), u128::MAX);
+        assert_eq!(sign_extend(0b110, 3), -2);
+        assert_eq!(sign_extend(0b010, 3), 2);
+        assert_eq!(sign_extend(u64::MAX as u128, 64), -1);
+        assert_eq!(sign_extend(u128::MAX, 128), -1);
+    }
+}
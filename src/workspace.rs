@@ -0,0 +1,407 @@
+//! Named modules that refer to each other's types.
+//!
+//! A [`Workspace`] holds several schemas, each a module with its own name and output
+//! directory. Types are resolved across all of them as one pool, so a field in one module may
+//! use a struct or enum of another, but each module generates only its own definitions. The
+//! generated files include each other: a C++ header includes the headers of the modules it
+//! uses, and a Rust file imports them. Kotlin files share one package, so they need no imports, a
+//! SystemVerilog package imports the packages it uses, and MATLAB functions define their types
+//! in the base workspace, so run those of a module's dependencies first.
+//!
+//! Generated paths are relative to one root. Compile C++ with the root on the include path.
+//! For Rust, the root also gets a `mod.rs`, as does every directory, which declares the
+//! modules below it and re-exports their items; mount it anywhere in a crate, such as with
+//! `mod generated;`.
+
+use std::path::{Path, PathBuf};
+
+use crate::{
+    ast::OnyxModule,
+    compiler::{self, Artifact},
+    diagnostic::{Diagnostic, codes},
+    generators::{
+        CodeGenerator, TARGETS,
+        arrow::ArrowGenerator,
+        cpp::CppGenerator,
+        external_types,
+        kotlin::KotlinGenerator,
+        matlab::MatlabGenerator,
+        rust::RustGenerator,
+        systemverilog::{self, SystemVerilogGenerator},
+    },
+};
+
+/// Diagnostics paired with the name of the module they refer to.
+pub type WorkspaceErrors = Vec<(String, Diagnostic)>;
+
+/// One named module of a [`Workspace`].
+#[derive(Debug, Clone)]
+struct Member {
+    name: String,
+    source: String,
+    output_dir: PathBuf,
+    /// The module with the definitions of the whole pool but only its own in `order`, once the
+    /// workspace is resolved.
+    resolved: Option<OnyxModule>,
+}
+
+/// Several named modules resolved against a shared pool of types.
+///
+/// ```rust
+/// use onyx::workspace::Workspace;
+///
+/// let mut workspace = Workspace::new();
+/// workspace.add_module("common", "common", "struct Header { version u8, }");
+/// workspace.add_module("telemetry", "telemetry", "message Packet { header Header, }");
+/// workspace.resolve().unwrap();
+/// assert_eq!(workspace.dependencies("telemetry"), ["common"]);
+///
+/// let artifacts = workspace.generate("cpp").unwrap();
+/// assert!(artifacts[2].contents.contains("#include \"common/common.hpp\""));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Workspace {
+    members: Vec<Member>,
+}
+
+impl Workspace {
+    /// Creates an empty workspace.
+    pub fn new() -> Self {
+        Workspace::default()
+    }
+
+    /// Adds a module named `name` whose files are generated in `output_dir`, relative to the
+    /// workspace root. The workspace must be [resolved](Workspace::resolve) again afterwards.
+    pub fn add_module(
+        &mut self,
+        name: impl Into<String>,
+        output_dir: impl Into<PathBuf>,
+        source: impl Into<String>,
+    ) {
+        for member in &mut self.members {
+            member.resolved = None;
+        }
+        self.members.push(Member {
+            name: name.into(),
+            source: source.into(),
+            output_dir: output_dir.into(),
+            resolved: None,
+        });
+    }
+
+    /// Returns the names of the modules, in the order they were added.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.members.iter().map(|member| member.name.as_str())
+    }
+
+    /// Parses every module and resolves their types as one pool.
+    ///
+    /// Modules must share their endianness and version, and no two may define the same type.
+    /// Every module is parsed even if another fails, so all of their errors are reported
+    /// together.
+    pub fn resolve(&mut self) -> Result<(), WorkspaceErrors> {
+        let mut errors = Vec::new();
+        let mut modules = Vec::new();
+        for member in &self.members {
+            match compiler::parse(&member.source, &[]) {
+                Ok(module) => modules.push(module),
+                Err(diagnostics) => {
+                    errors.extend(diagnostics.into_iter().map(|d| (member.name.clone(), d)))
+                }
+            }
+        }
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+
+        let names: Vec<String> = self.names().map(|name| format!("module {name}")).collect();
+        let sources: Vec<String> = self.members.iter().map(|m| m.source.clone()).collect();
+        let pool = compiler::merge(&names, &sources, &modules).map_err(|errors| {
+            errors
+                .into_iter()
+                .map(|(index, error)| (self.members[index].name.clone(), error))
+                .collect::<WorkspaceErrors>()
+        })?;
+
+        for (member, parsed) in self.members.iter_mut().zip(&modules) {
+            let own = |layout: &OnyxModule| {
+                let mut layout = layout.clone();
+                layout
+                    .order
+                    .retain(|id| parsed.definitions.contains_key(id));
+                layout.history.clear();
+                layout
+            };
+            let mut resolved = own(&pool);
+            resolved.history = pool.history.iter().map(own).collect();
+            member.resolved = Some(resolved);
+        }
+        Ok(())
+    }
+
+    /// Returns the resolved module `name`, whose definitions include the types of every other
+    /// module but whose `order` lists only its own. Returns `None` for an unknown name or
+    /// before the workspace is resolved.
+    pub fn module(&self, name: &str) -> Option<&OnyxModule> {
+        self.member(name)?.resolved.as_ref()
+    }
+
+    /// Returns the names of the other modules whose types the module `name` uses, in the
+    /// order they were added.
+    pub fn dependencies(&self, name: &str) -> Vec<&str> {
+        let Some(module) = self.module(name) else {
+            return Vec::new();
+        };
+        let used = external_types(module);
+        self.members
+            .iter()
+            .filter(|member| member.name != name)
+            .filter(|member| {
+                member
+                    .resolved
+                    .as_ref()
+                    .is_some_and(|m| m.order.iter().any(|id| used.contains(id)))
+            })
+            .map(|member| member.name.as_str())
+            .collect()
+    }
+
+    /// Generates every module for `target` (see [`TARGETS`]), after resolving the workspace
+    /// if needed.
+    ///
+    /// Each module's files are named after it in its output directory, e.g.
+    /// `telemetry/telemetry.hpp`, and refer to the files of its dependencies. Rust output ends
+    /// with the `mod.rs` files of the [module tree](RustGenerator::module_tree).
+    pub fn generate(&mut self, target: &str) -> Result<Vec<Artifact>, WorkspaceErrors> {
+        if self.members.iter().any(|member| member.resolved.is_none()) {
+            self.resolve()?;
+        }
+        let mut artifacts = Vec::new();
+        let mut errors = Vec::new();
+        for member in &self.members {
+            match self.generate_member(member, target) {
+                Ok(files) => artifacts.extend(files.into_iter().map(|(path, contents)| Artifact {
+                    target: target.to_string(),
+                    path,
+                    contents,
+                })),
+                Err(error) => errors.push((member.name.clone(), error)),
+            }
+        }
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+        if target == "rust" {
+            // Each module generates one file, so the tree is grown a module at a time to blame
+            // the first that does not fit.
+            let files: Vec<PathBuf> = artifacts.iter().map(|a| a.path.clone()).collect();
+            let mut tree = Vec::new();
+            for (index, member) in self.members.iter().enumerate() {
+                tree = RustGenerator::default()
+                    .module_tree(&files[..=index])
+                    .map_err(|error| vec![(member.name.clone(), error)])?;
+            }
+            artifacts.extend(tree.into_iter().map(|(path, contents)| Artifact {
+                target: target.to_string(),
+                path,
+                contents,
+            }));
+        }
+        Ok(artifacts)
+    }
+
+    fn member(&self, name: &str) -> Option<&Member> {
+        self.members.iter().find(|member| member.name == name)
+    }
+
+    /// Generates one module, referring to the files of its dependencies.
+    fn generate_member(
+        &self,
+        member: &Member,
+        target: &str,
+    ) -> Result<Vec<(PathBuf, String)>, Diagnostic> {
+        let file_path = member.output_dir.join(&member.name);
+        let dependencies = self.dependencies(&member.name);
+        let dependencies = dependencies.iter().filter_map(|name| self.member(name));
+        let mut generator: Box<dyn CodeGenerator> = match target {
+            "cpp" => {
+                let mut generator = CppGenerator::default();
+                generator.add_file_path(file_path)?;
+                for dependency in dependencies {
+                    let header = dependency
+                        .output_dir
+                        .join(format!("{}.hpp", dependency.name));
+                    let types = dependency.resolved.iter().flat_map(|m| &m.order);
+                    generator.add_dependency(include_path(&header), types.map(|t| t.as_str()));
+                }
+                Box::new(generator)
+            }
+            "rust" => {
+                let mut generator = RustGenerator::default();
+                generator.add_file_path(file_path)?;
+                for dependency in dependencies {
+                    generator.add_use(format!("{}::*", module_path(member, dependency)));
+                }
+                Box::new(generator)
+            }
+            "kotlin" => {
+                let mut generator = KotlinGenerator::default();
+                generator.add_file_path(file_path)?;
+                Box::new(generator)
+            }
+            "systemverilog" => {
+                let mut generator = SystemVerilogGenerator::default();
+                generator.add_file_path(file_path)?;
+                for dependency in dependencies {
+                    generator.add_import(systemverilog::package_name(&dependency.name));
+                }
+                Box::new(generator)
+            }
+            "matlab" => {
+                let mut generator = MatlabGenerator::default();
+                generator.add_file_path(file_path)?;
+                Box::new(generator)
+            }
+            "arrow" => {
+                let mut generator = ArrowGenerator::default();
+                generator.add_file_path(file_path)?;
+                Box::new(generator)
+            }
+            _ => {
+                return Err(Diagnostic::error(format!(
+                    "unknown target '{target}', expected one of: {}",
+                    TARGETS.join(", ")
+                )));
+            }
+        };
+        let module = member.resolved.as_ref().ok_or_else(|| {
+            Diagnostic::error(format!("module {} is not resolved", member.name))
+                .with_code(codes::INTERNAL)
+        })?;
+        generator.generate(module)
+    }
+}
+
+/// Returns a path relative to the workspace root as written in an `#include`, with `/`
+/// separators on every platform.
+fn include_path(path: &Path) -> String {
+    let components: Vec<_> = path.iter().map(|c| c.to_string_lossy()).collect();
+    components.join("/")
+}
+
+/// Returns the Rust path of the module `to` relative to the module `from`, through their
+/// closest common directory, e.g. `super::super::common` from `geo/shapes/shapes.rs` to
+/// `geo/common.rs`.
+fn module_path(from: &Member, to: &Member) -> String {
+    let components = |path: &Path| -> Vec<String> {
+        path.iter()
+            .map(|c| c.to_string_lossy().into_owned())
+            .collect()
+    };
+    let from = components(&from.output_dir);
+    let mut to_path = components(&to.output_dir);
+    to_path.push(to.name.clone());
+    let common = from
+        .iter()
+        .zip(&to_path)
+        .take_while(|(a, b)| a == b)
+        .count();
+    let mut path = vec!["super".to_string(); from.len() - common + 1];
+    path.extend_from_slice(&to_path[common..]);
+    path.join("::")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::symbol::Symbol;
+
+    fn sample() -> Workspace {
+        let mut workspace = Workspace::new();
+        workspace.add_module("common", "geo", "struct Point { x i16, y i16, }");
+        workspace.add_module(
+            "shapes",
+            "geo/shapes",
+            "message Path { points vec<Point, 4>, }",
+        );
+        workspace.add_module("other", "other", "enum Color : u8 { Red, }");
+        workspace
+    }
+
+    #[test]
+    fn test_resolve() {
+        let mut workspace = sample();
+        assert!(workspace.module("shapes").is_none());
+        workspace.resolve().unwrap();
+
+        let shapes = workspace.module("shapes").unwrap();
+        assert_eq!(shapes.order, [Symbol::intern("Path")]);
+        assert!(shapes.definitions.contains_key(&Symbol::intern("Point")));
+        assert_eq!(workspace.dependencies("shapes"), ["common"]);
+        assert!(workspace.dependencies("common").is_empty());
+        assert!(workspace.dependencies("missing").is_empty());
+    }
+
+    #[test]
+    fn test_generate() {
+        let mut workspace = sample();
+        let artifacts = workspace.generate("rust").unwrap();
+        let paths: Vec<_> = artifacts.iter().map(|a| a.path.clone()).collect();
+        assert_eq!(
+            paths,
+            [
+                PathBuf::from("geo/common.rs"),
+                PathBuf::from("geo/shapes/shapes.rs"),
+                PathBuf::from("other/other.rs"),
+                PathBuf::from("mod.rs"),
+                PathBuf::from("geo/mod.rs"),
+                PathBuf::from("geo/shapes/mod.rs"),
+                PathBuf::from("other/mod.rs"),
+            ]
+        );
+        assert!(
+            artifacts[1]
+                .contents
+                .contains("use super::super::common::*;")
+        );
+        assert!(
+            artifacts[5]
+                .contents
+                .ends_with("pub mod shapes;\n\n#[allow(unused_imports)]\npub use shapes::*;\n")
+        );
+        assert!(artifacts[1].contents.contains("pub struct PathView"));
+        assert!(!artifacts[1].contents.contains("pub struct PointView"));
+
+        let artifacts = workspace.generate("cpp").unwrap();
+        let shapes = &artifacts[2].contents;
+        assert!(shapes.contains("#include \"geo/common.hpp\""));
+        assert!(shapes.contains("class Path {"));
+        assert!(!shapes.contains("class Point {"));
+        assert!(!artifacts[4].contents.contains("#include \"geo"));
+    }
+
+    #[test]
+    fn test_errors_name_their_module() {
+        let mut workspace = sample();
+        workspace.add_module("broken", "broken", "struct Point { z u8, }");
+        let errors = workspace.resolve().unwrap_err();
+        assert_eq!(errors[0].0, "broken");
+        assert_eq!(errors[0].1.code(), Some(codes::DUPLICATE_DEFINITION));
+        assert_eq!(
+            errors[0].1.notes(),
+            ["first defined in module common at position 1:8"]
+        );
+
+        let mut workspace = sample();
+        workspace.add_module("big", "big", "endian = big\nstruct B { x u8, }");
+        let errors = workspace.generate("cpp").unwrap_err();
+        assert_eq!(errors[0].0, "big");
+        assert_eq!(errors[0].1.code(), Some(codes::INVALID_ENDIANNESS));
+
+        let mut workspace = sample();
+        workspace.add_module("geo", "", "struct Line { a u8, }");
+        let errors = workspace.generate("rust").unwrap_err();
+        assert_eq!(errors[0].0, "geo");
+        assert_eq!(errors[0].1.code(), Some(codes::CODEGEN));
+    }
+}
@@ -0,0 +1,788 @@
+//! Generated drivers shared by the differential and conformance tests.
+//!
+//! A case is a value of a definition, flattened to leaves: its primitive, enum, fixed-point, and
+//! byte array fields, and the lengths of its vectors. A driver is a program generated per backend
+//! for a list of cases. It reads one buffer per case from stdin as a line of hex, checks every
+//! leaf through the generated accessors, then builds the same value with the generated mutators
+//! and prints the bytes it encodes to. Bounded vectors are built by pushing their elements in
+//! order, and fixed-point fields are set through their scaled mutators. Byte arrays are also
+//! checked through their hex formatters.
+
+#![allow(dead_code)]
+
+use std::{
+    fmt::Write,
+    fs,
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
+};
+
+use onyx::{
+    Symbol,
+    ast::{Definition, EnumDef, OnyxModule, PrimitiveType, Type},
+    dynamic::Value,
+    generators::{CodeGenerator, cpp::CppGenerator, rust::RustGenerator},
+};
+
+/// A value of a definition, flattened to its leaves, and the bytes it is encoded to.
+pub struct Case {
+    pub definition: Symbol,
+    pub leaves: Vec<Leaf>,
+    pub bytes: Vec<u8>,
+}
+
+/// A primitive or enum field, or the length of a vector, reached through a path of fields and
+/// vector elements.
+pub struct Leaf {
+    pub path: Vec<Step>,
+    pub kind: LeafKind,
+    pub value: Value,
+}
+
+#[derive(Clone, Copy)]
+pub enum Step {
+    Field(Symbol),
+    Index(usize),
+}
+
+pub enum LeafKind {
+    Primitive(PrimitiveType),
+    Enum(EnumDef),
+    /// A fixed-point number; the leaf value is its raw integer.
+    Fixed {
+        base: PrimitiveType,
+        scale: u64,
+    },
+    /// A `uuid` or `bytes[N]`.
+    Bytes {
+        uuid: bool,
+    },
+    /// The length of a vector, and the name of its element type if that is a struct or message.
+    Length {
+        record: Option<Symbol>,
+    },
+}
+
+pub fn leaves(module: &OnyxModule, def: &Definition, value: &Value) -> Vec<Leaf> {
+    let mut out = Vec::new();
+    collect_leaves(module, def, value, &mut Vec::new(), &mut out);
+    out
+}
+
+pub fn collect_leaves(
+    module: &OnyxModule,
+    def: &Definition,
+    value: &Value,
+    path: &mut Vec<Step>,
+    out: &mut Vec<Leaf>,
+) {
+    let (fields, Value::Record(values)) = (fields(def), value) else {
+        unreachable!("records are only built for structs and messages");
+    };
+    for (field, (_, value)) in fields.iter().zip(values) {
+        path.push(Step::Field(field.name));
+        collect_type_leaves(module, &field.type_info, value, path, out);
+        path.pop();
+    }
+}
+
+pub fn collect_type_leaves(
+    module: &OnyxModule,
+    type_info: &Type,
+    value: &Value,
+    path: &mut Vec<Step>,
+    out: &mut Vec<Leaf>,
+) {
+    match type_info {
+        Type::Primitive(p) => out.push(Leaf {
+            path: path.clone(),
+            kind: LeafKind::Primitive(*p),
+            value: value.clone(),
+        }),
+        Type::Fixed { base, scale } => out.push(Leaf {
+            path: path.clone(),
+            kind: LeafKind::Fixed {
+                base: *base,
+                scale: *scale,
+            },
+            value: value.clone(),
+        }),
+        Type::Uuid | Type::Bytes(_) => out.push(Leaf {
+            path: path.clone(),
+            kind: LeafKind::Bytes {
+                uuid: *type_info == Type::Uuid,
+            },
+            value: value.clone(),
+        }),
+        Type::Custom(name) => match &module.definitions[name] {
+            Definition::Enum(e) => out.push(Leaf {
+                path: path.clone(),
+                kind: LeafKind::Enum(e.clone()),
+                value: value.clone(),
+            }),
+            nested => collect_leaves(module, nested, value, path, out),
+        },
+        Type::Vector { element, .. } => {
+            let Value::List(items) = value else {
+                unreachable!("lists are only built for vectors");
+            };
+            let record = match **element {
+                Type::Custom(name) if !matches!(module.definitions[&name], Definition::Enum(_)) => {
+                    Some(name)
+                }
+                _ => None,
+            };
+            out.push(Leaf {
+                path: path.clone(),
+                kind: LeafKind::Length { record },
+                value: Value::Unsigned(items.len() as u64),
+            });
+            for (i, item) in items.iter().enumerate() {
+                path.push(Step::Index(i));
+                collect_type_leaves(module, element, item, path, out);
+                path.pop();
+            }
+        }
+        other => panic!("no differential fixture uses a field of type {other}"),
+    }
+}
+
+pub fn fields(def: &Definition) -> &[onyx::ast::Field] {
+    match def {
+        Definition::Struct(s) => &s.fields,
+        Definition::Message(m) => &m.fields,
+        _ => &[],
+    }
+}
+
+// --- Random values ---
+
+/// A xorshift generator, so failures reproduce without a dependency.
+pub struct Rng(pub u64);
+
+impl Rng {
+    pub fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+}
+
+pub fn random_value(module: &OnyxModule, def: &Definition, rng: &mut Rng) -> Value {
+    match def {
+        Definition::Enum(e) if e.flags => {
+            let all = e.variant_values().into_iter().fold(0, |bits, v| bits | v);
+            integer(e.underlying_type, rng.next() & all)
+        }
+        // Half the values of an open enum are drawn from the whole underlying type.
+        Definition::Enum(e) if e.open && rng.next() & 1 == 0 => {
+            random_primitive(e.underlying_type, e.underlying_type.get_bit_width(), rng)
+        }
+        Definition::Enum(e) => {
+            let values = e.variant_values();
+            let value = values[rng.next() as usize % values.len()];
+            integer(e.underlying_type, value)
+        }
+        _ => Value::Record(
+            fields(def)
+                .iter()
+                .map(|field| {
+                    let value = match field.type_info {
+                        Type::Primitive(p) => {
+                            let bits = field.bit_field_size.unwrap_or(p.get_bit_width());
+                            random_primitive(p, bits, rng)
+                        }
+                        ref other => random_type_value(module, other, rng),
+                    };
+                    (field.name, value)
+                })
+                .collect(),
+        ),
+    }
+}
+
+pub fn random_type_value(module: &OnyxModule, type_info: &Type, rng: &mut Rng) -> Value {
+    match type_info {
+        Type::Primitive(p) | Type::Fixed { base: p, .. } => {
+            random_primitive(*p, p.get_bit_width(), rng)
+        }
+        Type::Uuid | Type::Bytes(_) => Value::Bytes(
+            (0..type_info.byte_len().unwrap())
+                .map(|_| rng.next() as u8)
+                .collect(),
+        ),
+        Type::Custom(name) => random_value(module, &module.definitions[name], rng),
+        Type::Vector { element, capacity } => {
+            let len = rng.next() as usize % (capacity + 1);
+            Value::List(
+                (0..len)
+                    .map(|_| random_type_value(module, element, rng))
+                    .collect(),
+            )
+        }
+        other => panic!("no differential fixture uses a field of type {other}"),
+    }
+}
+
+pub fn random_primitive(p: PrimitiveType, bits: usize, rng: &mut Rng) -> Value {
+    let raw = rng.next();
+    match p {
+        PrimitiveType::Bool => Value::Bool(raw & 1 != 0),
+        // Floats are drawn from exact fractions so every backend sees the same bit pattern.
+        PrimitiveType::F32 => Value::F32((raw as i32) as f32 / 1024.0),
+        PrimitiveType::F64 => Value::F64((raw as i64) as f64 / 1024.0),
+        _ if is_signed(p) => {
+            let shift = 64 - bits;
+            Value::Signed(((raw << shift) as i64) >> shift)
+        }
+        _ => Value::Unsigned(if bits >= 64 {
+            raw
+        } else {
+            raw & ((1 << bits) - 1)
+        }),
+    }
+}
+
+pub fn integer(p: PrimitiveType, value: u64) -> Value {
+    if is_signed(p) {
+        Value::Signed(value as i64)
+    } else {
+        Value::Unsigned(value)
+    }
+}
+
+pub fn is_signed(p: PrimitiveType) -> bool {
+    matches!(
+        p,
+        PrimitiveType::I8 | PrimitiveType::I16 | PrimitiveType::I32 | PrimitiveType::I64
+    )
+}
+
+/// Returns the value's bits as an unsigned integer, sign-extended to 64 bits.
+pub fn raw_bits(value: &Value) -> u64 {
+    match value {
+        Value::Bool(b) => *b as u64,
+        Value::Unsigned(v) => *v,
+        Value::Signed(v) => *v as u64,
+        Value::F32(f) => f.to_bits() as u64,
+        Value::F64(f) => f.to_bits(),
+        _ => unreachable!("leaves are never records"),
+    }
+}
+
+/// Returns the bits of the scaled value of a fixed-point leaf, as the backends compute it.
+pub fn scaled_bits(value: &Value, scale: u64) -> u64 {
+    let raw = match value {
+        Value::Signed(v) => *v as f64,
+        _ => raw_bits(value) as f64,
+    };
+    (raw / scale as f64).to_bits()
+}
+
+/// Returns the bits of a scaled value just past the range of a fixed-point field.
+pub fn out_of_range_bits(base: PrimitiveType, scale: u64) -> u64 {
+    let max = if is_signed(base) {
+        (1u64 << (base.get_bit_width() - 1)) - 1
+    } else {
+        u64::MAX >> (64 - base.get_bit_width())
+    };
+    ((max as f64 + 1.0) / scale as f64).to_bits()
+}
+
+/// Returns the bytes of a byte array leaf.
+pub fn leaf_bytes(value: &Value) -> &[u8] {
+    let Value::Bytes(bytes) = value else {
+        unreachable!("byte array leaves are always bytes");
+    };
+    bytes
+}
+
+/// Formats bytes the way the generated hex formatters do.
+pub fn hex(bytes: &[u8], uuid: bool) -> String {
+    let mut out = String::new();
+    for (i, b) in bytes.iter().enumerate() {
+        if uuid && matches!(i, 4 | 6 | 8 | 10) {
+            out.push('-');
+        }
+        write!(out, "{b:02x}").unwrap();
+    }
+    out
+}
+
+pub fn variant_name(e: &EnumDef, value: &Value) -> String {
+    let index = e
+        .variant_values()
+        .iter()
+        .position(|v| *v == raw_bits(value))
+        .unwrap();
+    e.variants[index].name.clone()
+}
+
+// --- Rust backend ---
+
+/// Generates Rust code for `module` in `dir` and compiles a driver for `cases`.
+///
+/// The driver reads one hex line per case from stdin, checks every leaf through the generated
+/// accessors, then builds the case with the generated mutators and prints its bytes as hex.
+pub fn build_rust(module: &OnyxModule, dir: &Path, cases: &[Case]) -> PathBuf {
+    let mut generator = RustGenerator::default();
+    generator.add_file_path(dir.join("schema")).unwrap();
+    write_files(generator.generate(module).unwrap());
+
+    let mut main = String::from(
+        "#[allow(dead_code, unused_imports)]\nmod schema;\n\n\
+         use std::io::BufRead;\n\n\
+         use schema::*;\n\n\
+         fn main() {\n    \
+         let input: Vec<Vec<u8>> = std::io::stdin()\n        \
+         .lock()\n        \
+         .lines()\n        \
+         .map(|line| {\n            \
+         let line = line.unwrap();\n            \
+         (0..line.len())\n                \
+         .step_by(2)\n                \
+         .map(|i| u8::from_str_radix(&line[i..i + 2], 16).unwrap())\n                \
+         .collect()\n        \
+         })\n        \
+         .collect();\n",
+    );
+    for (i, case) in cases.iter().enumerate() {
+        let name = case.definition;
+        writeln!(main, "    // Case {i}: {name}").unwrap();
+        writeln!(main, "    {{").unwrap();
+        writeln!(
+            main,
+            "        let buf: {name}Buffer = input[{i}].as_slice().try_into().unwrap();"
+        )
+        .unwrap();
+        writeln!(main, "        let view = {name}View::new(&buf);").unwrap();
+        for leaf in &case.leaves {
+            let getter = format!(
+                "view{}",
+                leaf.path
+                    .iter()
+                    .map(|step| match step {
+                        Step::Field(f) => format!(".{f}()"),
+                        Step::Index(i) => format!(".get({i}).unwrap()"),
+                    })
+                    .collect::<String>()
+            );
+            let (actual, expected) = match &leaf.kind {
+                LeafKind::Primitive(PrimitiveType::F32) => (
+                    format!("{getter}.to_bits()"),
+                    format!("{:#x}u32", raw_bits(&leaf.value)),
+                ),
+                LeafKind::Primitive(PrimitiveType::F64) => (
+                    format!("{getter}.to_bits()"),
+                    format!("{:#x}u64", raw_bits(&leaf.value)),
+                ),
+                LeafKind::Enum(e) if e.flags => (
+                    format!("{getter}.bits() as u64"),
+                    format!("{}u64", raw_bits(&leaf.value)),
+                ),
+                LeafKind::Enum(e) if e.open => (
+                    format!("{}::from({getter}) as u64", e.underlying_type),
+                    format!("{}u64", raw_bits(&leaf.value)),
+                ),
+                LeafKind::Enum(_) => (
+                    format!("{getter}.unwrap() as u64"),
+                    format!("{}u64", raw_bits(&leaf.value)),
+                ),
+                LeafKind::Length { .. } => (
+                    format!("{getter}.len()"),
+                    format!("{}usize", raw_bits(&leaf.value)),
+                ),
+                LeafKind::Fixed { base, scale } => {
+                    let raw = format!("{}_raw()", getter.strip_suffix("()").unwrap());
+                    writeln!(
+                        main,
+                        "        assert_eq!({raw}, {}, \"case {i}\");",
+                        rust_literal(*base, &leaf.value)
+                    )
+                    .unwrap();
+                    (
+                        format!("{getter}.to_bits()"),
+                        format!("{:#x}u64", scaled_bits(&leaf.value, *scale)),
+                    )
+                }
+                LeafKind::Bytes { uuid } => {
+                    let bytes = leaf_bytes(&leaf.value);
+                    writeln!(
+                        main,
+                        "        assert_eq!(format!(\"{{:?}}\", {}_hex()), {:?}, \"case {i}\");",
+                        getter.strip_suffix("()").unwrap(),
+                        hex(bytes, *uuid)
+                    )
+                    .unwrap();
+                    (getter, format!("[{}]", byte_list(bytes)))
+                }
+                LeafKind::Primitive(p) => (getter, rust_literal(*p, &leaf.value)),
+            };
+            writeln!(
+                main,
+                "        assert_eq!({actual}, {expected}, \"case {i}\");"
+            )
+            .unwrap();
+        }
+        writeln!(
+            main,
+            "        let mut out: {name}Buffer = [0; {}];",
+            case.bytes.len()
+        )
+        .unwrap();
+        writeln!(main, "        let mut view = {name}MutView::new(&mut out);").unwrap();
+        for leaf in &case.leaves {
+            let (last, parents) = leaf.path.split_last().unwrap();
+            let parents: String = parents
+                .iter()
+                .map(|step| match step {
+                    Step::Field(f) => format!(".{f}_mut()"),
+                    Step::Index(i) => format!(".get_mut({i}).unwrap()"),
+                })
+                .collect();
+            let value = match &leaf.kind {
+                LeafKind::Primitive(PrimitiveType::F32) => {
+                    format!("f32::from_bits({:#x})", raw_bits(&leaf.value))
+                }
+                LeafKind::Primitive(PrimitiveType::F64) => {
+                    format!("f64::from_bits({:#x})", raw_bits(&leaf.value))
+                }
+                LeafKind::Enum(e) if e.flags => format!(
+                    "{}::from_bits_retain({})",
+                    e.name,
+                    rust_literal(e.underlying_type, &leaf.value)
+                ),
+                LeafKind::Enum(e) if e.open => format!(
+                    "{}::from({})",
+                    e.name,
+                    rust_literal(e.underlying_type, &leaf.value)
+                ),
+                LeafKind::Enum(e) => format!("{}::{}", e.name, variant_name(e, &leaf.value)),
+                LeafKind::Primitive(p) => rust_literal(*p, &leaf.value),
+                LeafKind::Bytes { .. } => format!("[{}]", byte_list(leaf_bytes(&leaf.value))),
+                LeafKind::Fixed { base, scale } => {
+                    let Step::Field(f) = last else { unreachable!() };
+                    writeln!(
+                        main,
+                        "        view{parents}.set_{f}(f64::from_bits({:#x})).unwrap();",
+                        scaled_bits(&leaf.value, *scale)
+                    )
+                    .unwrap();
+                    writeln!(
+                        main,
+                        "        assert!(view{parents}.set_{f}(f64::from_bits({:#x})).is_err());",
+                        out_of_range_bits(*base, *scale)
+                    )
+                    .unwrap();
+                    continue;
+                }
+                // Record elements are pushed zeroed and then filled in through `get_mut`;
+                // other elements are pushed with their value.
+                LeafKind::Length { record } => {
+                    if record.is_some() {
+                        let Step::Field(f) = last else { unreachable!() };
+                        writeln!(
+                            main,
+                            "        for _ in 0..{} {{ view{parents}.{f}_mut().push().unwrap(); }}",
+                            raw_bits(&leaf.value)
+                        )
+                        .unwrap();
+                    }
+                    continue;
+                }
+            };
+            match last {
+                Step::Field(f) => {
+                    writeln!(main, "        view{parents}.set_{f}({value});").unwrap()
+                }
+                Step::Index(_) => {
+                    writeln!(main, "        view{parents}.push({value}).unwrap();").unwrap()
+                }
+            }
+        }
+        writeln!(
+            main,
+            "        println!(\"{{}}\", out.iter().map(|b| format!(\"{{b:02x}}\")).collect::<String>());"
+        )
+        .unwrap();
+        writeln!(main, "    }}").unwrap();
+    }
+    main.push_str("}\n");
+
+    let main_path = dir.join("main.rs");
+    fs::write(&main_path, main).unwrap();
+    let binary = dir.join("driver");
+    let status = Command::new("rustc")
+        .args(["--edition", "2021", "-A", "warnings"])
+        .arg(&main_path)
+        .arg("-o")
+        .arg(&binary)
+        .status()
+        .expect("Failed to run rustc");
+    assert!(status.success(), "Rust driver failed to compile");
+    binary
+}
+
+pub fn rust_literal(p: PrimitiveType, value: &Value) -> String {
+    match value {
+        Value::Bool(b) => b.to_string(),
+        Value::Signed(v) => format!("({v}i64 as {p})"),
+        _ => format!("{}{p}", raw_bits(value)),
+    }
+}
+
+// --- C++ backend ---
+
+/// Generates C++ code for `module` in `dir` and compiles a driver for `cases`, which reads and
+/// prints cases like the Rust driver.
+pub fn build_cpp(module: &OnyxModule, dir: &Path, cases: &[Case]) -> PathBuf {
+    let mut generator = CppGenerator::default();
+    generator.add_file_path(dir.join("schema")).unwrap();
+    let files = generator.generate(module).unwrap();
+    let sources: Vec<PathBuf> = files
+        .iter()
+        .map(|(path, _)| path.clone())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "cpp"))
+        .collect();
+    write_files(files);
+
+    let mut main = String::from(
+        "#include \"schema.hpp\"\n\n\
+         #include <stdio.h>\n\
+         #include <string.h>\n\n\
+         using namespace onyx;\n\n\
+         static uint32_t bits32(float f) { uint32_t b; memcpy(&b, &f, 4); return b; }\n\
+         static uint64_t bits64(double f) { uint64_t b; memcpy(&b, &f, 8); return b; }\n\
+         static float from_bits32(uint32_t b) { float f; memcpy(&f, &b, 4); return f; }\n\
+         static double from_bits64(uint64_t b) { double f; memcpy(&f, &b, 8); return f; }\n\n\
+         static bool read_case(uint8_t* buf, size_t size) {\n    \
+         for (size_t i = 0; i < size; ++i) {\n        \
+         unsigned b;\n        \
+         if (scanf(\"%2x\", &b) != 1) return false;\n        \
+         buf[i] = static_cast<uint8_t>(b);\n    \
+         }\n    \
+         return true;\n\
+         }\n\n\
+         static void print_case(const uint8_t* buf, size_t size) {\n    \
+         for (size_t i = 0; i < size; ++i) printf(\"%02x\", buf[i]);\n    \
+         printf(\"\\n\");\n\
+         }\n\n\
+         #define CHECK(cond, i) \\\n    \
+         if (!(cond)) { fprintf(stderr, \"case %d: %s\\n\", i, #cond); return 1; }\n\n\
+         int main() {\n",
+    );
+    for (i, case) in cases.iter().enumerate() {
+        let name = case.definition;
+        writeln!(main, "    // Case {i}: {name}").unwrap();
+        writeln!(main, "    {{").unwrap();
+        writeln!(main, "        {name}::Buffer buf;").unwrap();
+        writeln!(main, "        CHECK(read_case(buf, sizeof(buf)), {i});").unwrap();
+        writeln!(main, "        {name}* view = {name}::Deserialize(buf);").unwrap();
+        writeln!(main, "        CHECK(view != nullptr, {i});").unwrap();
+        for leaf in &case.leaves {
+            let getter = format!(
+                "view{}",
+                leaf.path
+                    .iter()
+                    .enumerate()
+                    .map(|(j, step)| match step {
+                        Step::Field(f) => format!("{}{f}()", if j == 0 { "->" } else { "." }),
+                        Step::Index(i) => format!(".get({i})"),
+                    })
+                    .collect::<String>()
+            );
+            if let LeafKind::Bytes { uuid } = leaf.kind {
+                let bytes = leaf_bytes(&leaf.value);
+                writeln!(
+                    main,
+                    "        CHECK(({getter} == std::array<uint8_t, {}>{{{{{}}}}}), {i});",
+                    bytes.len(),
+                    byte_list(bytes)
+                )
+                .unwrap();
+                writeln!(
+                    main,
+                    "        CHECK({}_hex() == {:?}, {i});",
+                    getter.strip_suffix("()").unwrap(),
+                    hex(bytes, uuid)
+                )
+                .unwrap();
+                continue;
+            }
+            let raw = raw_bits(&leaf.value);
+            let condition = match &leaf.kind {
+                LeafKind::Primitive(PrimitiveType::F32) => format!("bits32({getter}) == {raw}U"),
+                LeafKind::Primitive(PrimitiveType::F64) => {
+                    format!("bits64({getter}) == {raw}ULL")
+                }
+                LeafKind::Enum(_) => format!("static_cast<uint64_t>({getter}) == {raw}ULL"),
+                LeafKind::Length { .. } => format!("{getter}.len() == {raw}U"),
+                LeafKind::Fixed { base, scale } => format!(
+                    "{}_raw() == {} && bits64({getter}) == {}ULL",
+                    getter.strip_suffix("()").unwrap(),
+                    cpp_literal(*base, &leaf.value),
+                    scaled_bits(&leaf.value, *scale)
+                ),
+                LeafKind::Bytes { .. } => unreachable!("byte arrays are checked above"),
+                LeafKind::Primitive(p) => format!("{getter} == {}", cpp_literal(*p, &leaf.value)),
+            };
+            writeln!(main, "        CHECK({condition}, {i});").unwrap();
+        }
+        writeln!(main, "        {name}::Buffer out = {{0}};").unwrap();
+        // A zeroed buffer reads the same in either endianness, and may hold undeclared values
+        // of closed enums, which Deserialize would reject.
+        writeln!(main, "        {name}* obj = ({name}*)out;").unwrap();
+        for leaf in &case.leaves {
+            let (last, parents) = leaf.path.split_last().unwrap();
+            let parents = parents
+                .iter()
+                .enumerate()
+                .map(|(j, step)| match step {
+                    Step::Field(f) => format!("{}{f}()", if j == 0 { "->" } else { "." }),
+                    Step::Index(i) => format!(".get({i})"),
+                })
+                .collect::<String>();
+            let dot = if leaf.path.len() == 1 { "->" } else { "." };
+            if let LeafKind::Bytes { .. } = leaf.kind {
+                let Step::Field(f) = last else { unreachable!() };
+                let bytes = leaf_bytes(&leaf.value);
+                writeln!(
+                    main,
+                    "        obj{parents}{dot}{f}(std::array<uint8_t, {}>{{{{{}}}}});",
+                    bytes.len(),
+                    byte_list(bytes)
+                )
+                .unwrap();
+                continue;
+            }
+            let raw = raw_bits(&leaf.value);
+            let value = match &leaf.kind {
+                LeafKind::Primitive(PrimitiveType::F32) => format!("from_bits32({raw}U)"),
+                LeafKind::Primitive(PrimitiveType::F64) => format!("from_bits64({raw}ULL)"),
+                LeafKind::Enum(e) => format!("static_cast<{}>({raw}ULL)", e.name),
+                LeafKind::Primitive(p) => cpp_literal(*p, &leaf.value),
+                LeafKind::Bytes { .. } => unreachable!("byte arrays are set above"),
+                LeafKind::Fixed { base, scale } => {
+                    let Step::Field(f) = last else { unreachable!() };
+                    writeln!(
+                        main,
+                        "        CHECK(obj{parents}{dot}{f}(from_bits64({}ULL)), {i});",
+                        scaled_bits(&leaf.value, *scale)
+                    )
+                    .unwrap();
+                    writeln!(
+                        main,
+                        "        CHECK(!obj{parents}{dot}{f}(from_bits64({}ULL)), {i});",
+                        out_of_range_bits(*base, *scale)
+                    )
+                    .unwrap();
+                    continue;
+                }
+                LeafKind::Length { record } => {
+                    if let Some(record) = record {
+                        let Step::Field(f) = last else { unreachable!() };
+                        writeln!(
+                            main,
+                            "        for (int j = 0; j < {raw}; ++j) CHECK(obj{parents}{dot}{f}().push({record}()), {i});"
+                        )
+                        .unwrap();
+                    }
+                    continue;
+                }
+            };
+            match last {
+                Step::Field(f) => writeln!(main, "        obj{parents}{dot}{f}({value});").unwrap(),
+                Step::Index(_) => {
+                    writeln!(main, "        CHECK(obj{parents}.push({value}), {i});").unwrap()
+                }
+            }
+        }
+        writeln!(main, "        {name}::Buffer wire;").unwrap();
+        writeln!(main, "        obj->Serialize(wire);").unwrap();
+        writeln!(main, "        print_case(wire, sizeof(wire));").unwrap();
+        writeln!(main, "    }}").unwrap();
+    }
+    main.push_str("    return 0;\n}\n");
+
+    let main_path = dir.join("main.cpp");
+    fs::write(&main_path, main).unwrap();
+    let binary = dir.join("driver");
+    let status = Command::new("g++")
+        .arg("-std=c++11")
+        .arg(&main_path)
+        .args(&sources)
+        .arg("-o")
+        .arg(&binary)
+        .status()
+        .expect("Failed to execute 'g++' command");
+    assert!(status.success(), "C++ driver failed to compile");
+    binary
+}
+
+pub fn cpp_literal(p: PrimitiveType, value: &Value) -> String {
+    if p == PrimitiveType::Bool {
+        return (raw_bits(value) != 0).to_string();
+    }
+    // `u16` becomes `uint16_t` and `i16` becomes `int16_t`.
+    let name = p.to_string();
+    let ty = match name.strip_prefix('u') {
+        Some(bits) => format!("uint{bits}_t"),
+        None => format!("int{}_t", &name[1..]),
+    };
+    format!("static_cast<{ty}>({}ULL)", raw_bits(value))
+}
+
+// --- Helpers ---
+
+pub fn write_files(files: Vec<(PathBuf, String)>) {
+    for (path, content) in files {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, content).unwrap();
+    }
+}
+
+pub fn byte_list(bytes: &[u8]) -> String {
+    bytes
+        .iter()
+        .map(|b| format!("{b:#04x}"))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Runs a driver on `input`, one case per buffer, and returns the buffers it encodes.
+///
+/// Panics if the driver finds a leaf that does not match its case.
+pub fn run_driver(binary: &Path, input: &[Vec<u8>]) -> Vec<Vec<u8>> {
+    let input_path = binary.with_extension("in");
+    let text: String = input.iter().map(|bytes| to_hex(bytes) + "\n").collect();
+    fs::write(&input_path, text).unwrap();
+    let output = Command::new(binary)
+        .stdin(fs::File::open(&input_path).unwrap())
+        .stderr(Stdio::inherit())
+        .output()
+        .unwrap_or_else(|e| panic!("Failed to run {}: {e}", binary.display()));
+    assert!(
+        output.status.success(),
+        "{} found a mismatch",
+        binary.display()
+    );
+    String::from_utf8(output.stdout)
+        .unwrap()
+        .lines()
+        .map(from_hex)
+        .collect()
+}
+
+/// Formats bytes as unseparated lowercase hex.
+pub fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+/// Parses unseparated hex, as written by [`to_hex`].
+pub fn from_hex(text: &str) -> Vec<u8> {
+    (0..text.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&text[i..i + 2], 16).unwrap())
+        .collect()
+}
@@ -1,130 +1,658 @@
 use std::{
-    fs::{self, File},
-    io::{Read, Write},
+    fs,
     path::{Path, PathBuf},
     process::Command,
 };
 
 use onyx::{
-    generators::{CodeGenerator, cpp::CppGenerator, rust::RustGenerator},
+    generators::{
+        CodeGenerator,
+        cpp::{CppGenerator, PackageConfig},
+        rust::{CrateConfig, RustGenerator},
+    },
     parser::Parser,
+    testing::{self, TempDir, Toolchain},
+    workspace::Workspace,
 };
 
+/// The directories of schemas that every backend must compile.
+const FIXTURE_DIRS: [&str; 2] = ["tests/snapshots", "tests/conformance"];
+
+/// The backend configurations the fixtures are compiled with: C++ as a header and source
+/// pair, C++ as one amalgamated header, and Rust.
+const BACKENDS: [&str; 3] = ["cpp", "cpp-amalgamated", "rust"];
+
+#[test]
+fn compile_fixtures() {
+    for dir in FIXTURE_DIRS {
+        let fixtures = testing::fixtures(Path::new(dir)).unwrap();
+        assert!(!fixtures.is_empty(), "no schemas in {dir}");
+        for fixture in fixtures {
+            let source = fs::read_to_string(&fixture).unwrap();
+            let module = Parser::new(&source)
+                .and_then(|p| p.parse_module())
+                .unwrap_or_else(|e| panic!("{}: {e}", fixture.display()));
+            let stem = fixture.file_stem().unwrap().to_str().unwrap();
+            for backend in BACKENDS {
+                compile_backend(backend, stem, &module);
+            }
+        }
+    }
+}
+
+/// Generates `module` with `backend` in a temporary directory and compiles the output, unless
+/// the backend's toolchain is missing.
+fn compile_backend(backend: &str, stem: &str, module: &onyx::ast::OnyxModule) {
+    let toolchain = match backend {
+        "rust" => Toolchain::Rustc,
+        _ => Toolchain::Cxx,
+    };
+    let Some(program) = testing::require(toolchain) else {
+        return;
+    };
+    let dir = TempDir::new(&format!("{backend}-{stem}")).unwrap();
+    let files = match backend {
+        "rust" => {
+            let mut generator = RustGenerator::default();
+            generator.add_file_path(PathBuf::from(stem)).unwrap();
+            generator.generate(module)
+        }
+        _ => {
+            let mut generator = CppGenerator::default();
+            generator.add_file_path(PathBuf::from(stem)).unwrap();
+            generator.set_amalgamate(backend == "cpp-amalgamated");
+            generator.generate(module)
+        }
+    }
+    .unwrap_or_else(|e| panic!("{stem} for {backend}: {e}"));
+    testing::write_files(dir.path(), &files).unwrap();
+
+    let mut command = Command::new(program);
+    match backend {
+        "rust" => command
+            .args(["--crate-type", "lib", "--edition", "2021"])
+            .arg(dir.path().join(format!("{stem}.rs")))
+            .arg("--out-dir")
+            .arg(dir.path()),
+        "cpp" => command
+            .args(["-std=c++11", "-c"])
+            .arg(dir.path().join(format!("{stem}.cpp")))
+            .arg("-o")
+            .arg(dir.path().join(format!("{stem}.o"))),
+        // A header is compiled on its own as a C++ source.
+        _ => command
+            .args(["-std=c++11", "-fsyntax-only", "-x", "c++"])
+            .arg(dir.path().join(format!("{stem}.hpp"))),
+    };
+    if let Err(failed) = testing::run(&mut command) {
+        panic!("{stem} does not compile for {backend}: {failed}");
+    }
+}
+
 #[test]
 fn compile_cpp() {
-    let mut file = File::open("tests/example.onyx").unwrap();
+    let source = fs::read_to_string("tests/example.onyx").unwrap();
+    let module_ast = Parser::new(&source).and_then(|p| p.parse_module()).unwrap();
+
+    let mut cpp_generator = CppGenerator::default();
+    cpp_generator
+        .add_file_path(PathBuf::from("tests/output_cpp/example"))
+        .unwrap();
+    let files = cpp_generator.generate(&module_ast).unwrap();
+    testing::write_files(Path::new(""), &files).unwrap();
 
-    let mut source = String::new();
-    let _ = file.read_to_string(&mut source).unwrap();
+    run_cpp(
+        "tests/cpp_test_main.cpp",
+        &["tests/output_cpp/example.cpp"],
+        &[],
+        "tests/output_cpp/cpp_runner",
+    );
+}
 
-    // 1. Parse the source code
+#[test]
+fn compile_cpp_amalgamated() {
+    let source = fs::read_to_string("tests/example.onyx").unwrap();
     let module_ast = Parser::new(&source).and_then(|p| p.parse_module()).unwrap();
 
     let mut cpp_generator = CppGenerator::default();
-    let _ = cpp_generator.add_file_path(PathBuf::from("tests/output_cpp/example"));
+    let _ = cpp_generator.add_file_path(PathBuf::from("tests/output_cpp_amalgamated/example"));
+    cpp_generator.set_amalgamate(true);
 
     let files = cpp_generator.generate(&module_ast).unwrap();
+    assert_eq!(files.len(), 1);
 
-    let mut binding = Command::new("g++");
-    let command: &mut Command = binding.arg("-std=c++11");
-    command.arg("tests/cpp_test_main.cpp");
+    let dir = Path::new("tests/output_cpp_amalgamated");
+    fs::create_dir_all(dir).unwrap();
+    fs::write(&files[0].0, &files[0].1).unwrap();
 
-    for (file_path, content) in &files {
-        let parent_dir = Path::new(&file_path).parent().unwrap();
-        fs::create_dir_all(parent_dir).unwrap();
-        let mut f = File::create(file_path).unwrap();
-        let _ = f.write_all(content.as_bytes());
-        command.arg(file_path);
-    }
+    // Two translation units include the header; linking them checks that every definition
+    // in it is inline.
+    fs::write(
+        dir.join("other.cpp"),
+        "#include \"example.hpp\"\nvoid other() { onyx::Header header; (void)header; }\n",
+    )
+    .unwrap();
+    fs::write(
+        dir.join("main.cpp"),
+        "#include \"example.hpp\"\nvoid other();\nint main() { other(); return 0; }\n",
+    )
+    .unwrap();
 
-    let command_status = command
-        .spawn()
-        .expect("Failed to execute 'g++' command")
-        .wait()
-        .expect("Failed to wait for 'g++' command");
-
-    if command_status.success() {
-        println!("Compilation successful.");
-    } else {
-        panic!(
-            "Compilation failed with status: {:?}",
-            command_status.code()
-        );
-    }
+    run_cpp(
+        "tests/output_cpp_amalgamated/main.cpp",
+        &["tests/output_cpp_amalgamated/other.cpp"],
+        &[],
+        "tests/output_cpp_amalgamated/cpp_runner",
+    );
+}
+
+#[test]
+fn compile_cpp_package() {
+    let source = fs::read_to_string("tests/example.onyx").unwrap();
+    let module_ast = Parser::new(&source).and_then(|p| p.parse_module()).unwrap();
 
-    let command_status = Command::new("./a.out")
-        .spawn()
-        .expect("Failed to execute 'g++' command")
-        .wait()
-        .expect("Failed to wait for 'g++' command");
+    let mut cpp_generator = CppGenerator::default();
+    cpp_generator
+        .add_file_path(PathBuf::from("tests/output_package/example.onyx"))
+        .unwrap();
+    cpp_generator
+        .set_package(PackageConfig::new("telemetry", "1.0.0"))
+        .unwrap();
+    testing::write_files(Path::new(""), &cpp_generator.generate(&module_ast).unwrap()).unwrap();
 
-    if command_status.success() {
-        println!("Program run successful.");
-    } else {
-        panic!("Program failed with status: {:?}", command_status.code());
-    }
+    let Some(pkg_config) = testing::find_program(Path::new("pkg-config")) else {
+        eprintln!("skipping: pkg-config not found");
+        return;
+    };
+    // Fill in the pkg-config template as CMake would when installing to the project itself.
+    let dir = fs::canonicalize("tests/output_package").unwrap();
+    let pc = fs::read_to_string(dir.join("telemetry.pc.in"))
+        .unwrap()
+        .replace("@CMAKE_INSTALL_PREFIX@", dir.to_str().unwrap())
+        .replace("@CMAKE_INSTALL_INCLUDEDIR@", "include")
+        .replace("@CMAKE_INSTALL_LIBDIR@", "lib");
+    fs::write(dir.join("telemetry.pc"), pc).unwrap();
+    let cflags = testing::check(
+        Command::new(pkg_config)
+            .args(["--cflags", "telemetry"])
+            .env("PKG_CONFIG_PATH", &dir),
+    );
+    assert_eq!(cflags.trim(), format!("-I{}/include", dir.display()));
+
+    fs::write(
+        dir.join("main.cpp"),
+        "#include <telemetry/example.hpp>\nint main() { onyx::Header header; (void)header; return 0; }\n",
+    )
+    .unwrap();
+    run_cpp(
+        "tests/output_package/main.cpp",
+        &["tests/output_package/src/example.cpp"],
+        &[cflags.trim()],
+        "tests/output_package/cpp_runner",
+    );
 }
 
 #[test]
 fn compile_rust() {
-    let mut file = File::open("tests/example.onyx").unwrap();
+    let source = fs::read_to_string("tests/example.onyx").unwrap();
+    let module_ast = Parser::new(&source).and_then(|p| p.parse_module()).unwrap();
 
-    let mut source = String::new();
-    let _ = file.read_to_string(&mut source).unwrap();
+    let mut rust_generator = RustGenerator::default();
+    rust_generator
+        .add_file_path(PathBuf::from("tests/output_rust/example"))
+        .unwrap();
+    let files = rust_generator.generate(&module_ast).unwrap();
+    testing::write_files(Path::new(""), &files).unwrap();
 
+    // The module compiles as a library of its own, and the runner checks its behavior.
+    let Some(rustc) = testing::require(Toolchain::Rustc) else {
+        return;
+    };
+    testing::check(
+        Command::new(rustc)
+            .args(["--crate-type", "lib", "--edition", "2021"])
+            .arg("tests/output_rust/example.rs")
+            .args(["--out-dir", "tests/output_rust"]),
+    );
+    run_rust("tests/rust_test_main.rs.inc", "tests/output_rust/runner");
+}
+
+#[test]
+fn compile_versioned() {
+    let source = fs::read_to_string("tests/snapshots/versioned.onyx").unwrap();
     let module_ast = Parser::new(&source).and_then(|p| p.parse_module()).unwrap();
+    let output = PathBuf::from("tests/output_versioned/versioned");
 
     let mut rust_generator = RustGenerator::default();
-    let _ = rust_generator.add_file_path(PathBuf::from("tests/output_rust/example"));
+    rust_generator.add_file_path(output.clone()).unwrap();
+    let mut cpp_generator = CppGenerator::default();
+    cpp_generator.add_file_path(output).unwrap();
 
-    let files = rust_generator.generate(&module_ast).unwrap();
+    let mut files = rust_generator.generate(&module_ast).unwrap();
+    files.extend(cpp_generator.generate(&module_ast).unwrap());
+    testing::write_files(Path::new(""), &files).unwrap();
+
+    // Old buffers upgrade to the current layout in both languages.
+    run_rust(
+        "tests/rust_versioned_main.rs.inc",
+        "tests/output_versioned/rust_runner",
+    );
+
+    run_cpp(
+        "tests/cpp_versioned_main.cpp",
+        &["tests/output_versioned/versioned.cpp"],
+        &[],
+        "tests/output_versioned/cpp_runner",
+    );
+}
+
+#[test]
+fn compile_conditional() {
+    let source = fs::read_to_string("tests/snapshots/conditions.onyx").unwrap();
+    let module_ast = Parser::new(&source).and_then(|p| p.parse_module()).unwrap();
+    let output = PathBuf::from("tests/output_conditions/conditions");
+
+    let mut rust_generator = RustGenerator::default();
+    rust_generator.add_file_path(output.clone()).unwrap();
+    let mut cpp_generator = CppGenerator::default();
+    cpp_generator.add_file_path(output).unwrap();
+
+    let mut files = rust_generator.generate(&module_ast).unwrap();
+    files.extend(cpp_generator.generate(&module_ast).unwrap());
+    testing::write_files(Path::new(""), &files).unwrap();
+
+    // Presence follows the fields the conditions read in both languages.
+    run_rust(
+        "tests/rust_conditions_main.rs.inc",
+        "tests/output_conditions/rust_runner",
+    );
+
+    run_cpp(
+        "tests/cpp_conditions_main.cpp",
+        &["tests/output_conditions/conditions.cpp"],
+        &[],
+        "tests/output_conditions/cpp_runner",
+    );
+}
+
+#[test]
+fn compile_dispatch() {
+    let source = fs::read_to_string("tests/snapshots/dispatch.onyx").unwrap();
+    let module_ast = Parser::new(&source).and_then(|p| p.parse_module()).unwrap();
+    let output = PathBuf::from("tests/output_dispatch/dispatch");
+
+    let mut rust_generator = RustGenerator::default();
+    rust_generator.add_file_path(output.clone()).unwrap();
+    let mut cpp_generator = CppGenerator::default();
+    cpp_generator.add_file_path(output).unwrap();
 
-    for (file_path, content) in &files {
-        let parent_dir = Path::new(&file_path).parent().unwrap();
-        fs::create_dir_all(parent_dir).unwrap();
-        let mut f = File::create(file_path).unwrap();
-        let _ = f.write_all(content.as_bytes());
+    let mut files = rust_generator.generate(&module_ast).unwrap();
+    files.extend(cpp_generator.generate(&module_ast).unwrap());
+    testing::write_files(Path::new(""), &files).unwrap();
+
+    // Framed messages decode to the type their ID names in both languages.
+    run_rust(
+        "tests/rust_dispatch_main.rs.inc",
+        "tests/output_dispatch/rust_runner",
+    );
+
+    run_cpp(
+        "tests/cpp_dispatch_main.cpp",
+        &["tests/output_dispatch/dispatch.cpp"],
+        &[],
+        "tests/output_dispatch/cpp_runner",
+    );
+}
+
+#[test]
+fn compile_host() {
+    let source = fs::read_to_string("tests/snapshots/host.onyx").unwrap();
+    let module_ast = Parser::new(&source).and_then(|p| p.parse_module()).unwrap();
+    let output = PathBuf::from("tests/output_host/host");
+
+    let mut rust_generator = RustGenerator::default();
+    rust_generator.add_file_path(output.clone()).unwrap();
+    rust_generator.set_shared_memory(true);
+    let mut cpp_generator = CppGenerator::default();
+    cpp_generator.add_file_path(output).unwrap();
+    cpp_generator.set_shared_memory(true);
+
+    let mut files = rust_generator.generate(&module_ast).unwrap();
+    files.extend(cpp_generator.generate(&module_ast).unwrap());
+    testing::write_files(Path::new(""), &files).unwrap();
+
+    // The Rust runner writes a buffer in native byte order, which C++ maps and reads in place.
+    run_rust(
+        "tests/rust_host_main.rs.inc",
+        "tests/output_host/rust_runner",
+    );
+
+    run_cpp(
+        "tests/cpp_host_main.cpp",
+        &["tests/output_host/host.cpp"],
+        &[],
+        "tests/output_host/cpp_runner",
+    );
+}
+
+#[test]
+fn compile_traced() {
+    for stem in ["conditions", "nested"] {
+        let source = fs::read_to_string(format!("tests/snapshots/{stem}.onyx")).unwrap();
+        let module_ast = Parser::new(&source).and_then(|p| p.parse_module()).unwrap();
+        let output = PathBuf::from(format!("tests/output_traced/{stem}"));
+
+        let mut rust_generator = RustGenerator::default();
+        rust_generator.add_file_path(output.clone()).unwrap();
+        rust_generator.set_trace(true);
+        let mut cpp_generator = CppGenerator::default();
+        cpp_generator.add_file_path(output).unwrap();
+        cpp_generator.set_trace(true);
+
+        let mut files = rust_generator.generate(&module_ast).unwrap();
+        files.extend(cpp_generator.generate(&module_ast).unwrap());
+        testing::write_files(Path::new(""), &files).unwrap();
+    }
+
+    // Decoding reports each field present with its offset and value in both languages.
+    run_rust(
+        "tests/rust_traced_main.rs.inc",
+        "tests/output_traced/rust_runner",
+    );
+
+    run_cpp(
+        "tests/cpp_traced_main.cpp",
+        &[],
+        &[],
+        "tests/output_traced/cpp_runner",
+    );
+
+    // The default ONYX_TRACE_FIELD prints to stderr.
+    let Some(cxx) = testing::require(Toolchain::Cxx) else {
+        return;
+    };
+    testing::check(
+        Command::new(cxx)
+            .args(["-std=c++11", "-DONYX_TRACE", "-c"])
+            .arg("tests/output_traced/nested.cpp")
+            .args(["-o", "tests/output_traced/nested.o"]),
+    );
+}
+
+#[test]
+fn compile_arena() {
+    let source = fs::read_to_string("tests/snapshots/dispatch.onyx").unwrap();
+    let module_ast = Parser::new(&source).and_then(|p| p.parse_module()).unwrap();
+    let output = PathBuf::from("tests/output_arena/dispatch");
+
+    let mut rust_generator = RustGenerator::default();
+    rust_generator.add_file_path(output.clone()).unwrap();
+    rust_generator.set_message_arena(true);
+    let mut cpp_generator = CppGenerator::default();
+    cpp_generator.add_file_path(output).unwrap();
+    cpp_generator.set_message_arena(true);
+
+    let mut files = rust_generator.generate(&module_ast).unwrap();
+    files.extend(cpp_generator.generate(&module_ast).unwrap());
+    testing::write_files(Path::new(""), &files).unwrap();
+
+    // Slots run out, return to the arena, and come back zeroed in both languages.
+    run_rust(
+        "tests/rust_arena_main.rs.inc",
+        "tests/output_arena/rust_runner",
+    );
+
+    run_cpp(
+        "tests/cpp_arena_main.cpp",
+        &["tests/output_arena/dispatch.cpp"],
+        &[],
+        "tests/output_arena/cpp_runner",
+    );
+}
+
+#[test]
+fn compile_reflection() {
+    for stem in ["conditions", "nested"] {
+        let source = fs::read_to_string(format!("tests/snapshots/{stem}.onyx")).unwrap();
+        let module_ast = Parser::new(&source).and_then(|p| p.parse_module()).unwrap();
+
+        let output = PathBuf::from(format!("tests/output_reflection/{stem}"));
+
+        let mut rust_generator = RustGenerator::default();
+        rust_generator.add_file_path(output.clone()).unwrap();
+        rust_generator.set_reflection(true);
+        let mut cpp_generator = CppGenerator::default();
+        cpp_generator.add_file_path(output).unwrap();
+        cpp_generator.set_reflection(true);
+
+        let mut files = rust_generator.generate(&module_ast).unwrap();
+        files.extend(cpp_generator.generate(&module_ast).unwrap());
+        testing::write_files(Path::new(""), &files).unwrap();
+    }
+
+    // Reading fields through their descriptors finds the values the accessors return.
+    run_rust(
+        "tests/rust_reflection_main.rs.inc",
+        "tests/output_reflection/rust_runner",
+    );
+
+    // The tables of two headers coexist, and visiting skips absent fields.
+    run_cpp(
+        "tests/cpp_reflection_main.cpp",
+        &[
+            "tests/output_reflection/conditions.cpp",
+            "tests/output_reflection/nested.cpp",
+        ],
+        &[],
+        "tests/output_reflection/cpp_runner",
+    );
+}
+
+#[test]
+fn compile_simd() {
+    let source = fs::read_to_string("tests/snapshots/samples.onyx").unwrap();
+    let module_ast = Parser::new(&source).and_then(|p| p.parse_module()).unwrap();
+    let output = PathBuf::from("tests/output_simd/samples");
+
+    let mut cpp_generator = CppGenerator::default();
+    cpp_generator.add_file_path(output).unwrap();
+    cpp_generator.set_simd(true);
+    testing::write_files(Path::new(""), &cpp_generator.generate(&module_ast).unwrap()).unwrap();
+
+    // The vectorized and scalar swaps both produce the big-endian layout and read it back.
+    let x86 = cfg!(any(target_arch = "x86", target_arch = "x86_64"));
+    for (runner, flags) in [
+        ("cpp_runner", &[][..]),
+        ("cpp_runner_simd", &["-mssse3"][..]),
+    ] {
+        if !flags.is_empty() && !x86 {
+            continue;
+        }
+        run_cpp(
+            "tests/cpp_simd_main.cpp",
+            &["tests/output_simd/samples.cpp"],
+            flags,
+            &format!("tests/output_simd/{runner}"),
+        );
+    }
+}
+
+#[test]
+fn compile_workspace() {
+    let mut workspace = Workspace::new();
+    workspace.add_module(
+        "common",
+        "common",
+        "endian = big\nenum Kind : u8 { Idle, Busy, }\nstruct Header { version u8, kind Kind, }",
+    );
+    workspace.add_module(
+        "telemetry",
+        "telemetry",
+        "endian = big\nmessage Packet { header Header, samples vec<u16, 4>, }",
+    );
+    workspace.add_module(
+        "control",
+        "control",
+        "endian = big\nmessage Command { header Header, args vec<u32, 2>, }",
+    );
+    workspace.add_module(
+        "status",
+        "status",
+        "endian = big\nmessage Status { kind Kind, code u16, }",
+    );
+
+    // Each module is generated in its own directory and includes the headers it uses.
+    let root = PathBuf::from("tests/output_workspace");
+    for artifact in workspace.generate("cpp").unwrap() {
+        let path = root.join(&artifact.path);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, artifact.contents).unwrap();
+    }
+    run_cpp(
+        "tests/cpp_workspace_main.cpp",
+        &[
+            "tests/output_workspace/common/common.cpp",
+            "tests/output_workspace/telemetry/telemetry.cpp",
+            "tests/output_workspace/control/control.cpp",
+            "tests/output_workspace/status/status.cpp",
+        ],
+        &["-Itests/output_workspace"],
+        "tests/output_workspace/cpp_runner",
+    );
+
+    // The Rust files form one module tree, whose root re-exports every module.
+    let root = root.join("rust");
+    for artifact in workspace.generate("rust").unwrap() {
+        let path = root.join(&artifact.path);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, artifact.contents).unwrap();
     }
+    run_rust(
+        "tests/rust_workspace_main.rs.inc",
+        "tests/output_workspace/rust_runner",
+    );
+}
+
+#[test]
+fn compile_vectors() {
+    for dir in FIXTURE_DIRS {
+        for fixture in testing::fixtures(Path::new(dir)).unwrap() {
+            let source = fs::read_to_string(&fixture).unwrap();
+            let module = Parser::new(&source).and_then(|p| p.parse_module()).unwrap();
+            let stem = fixture.file_stem().unwrap().to_str().unwrap();
+            check_rust_vectors(stem, &module);
+            check_cpp_vectors(stem, &module);
+        }
+    }
+}
+
+/// Generates `module` as a crate with test vectors and runs its `tests/vectors.rs`, unless rustc
+/// is missing.
+fn check_rust_vectors(stem: &str, module: &onyx::ast::OnyxModule) {
+    let Some(rustc) = testing::require(Toolchain::Rustc) else {
+        return;
+    };
+    let dir = TempDir::new(&format!("vectors-rust-{stem}")).unwrap();
+    let mut generator = RustGenerator::default();
+    generator.add_file_path(PathBuf::from(stem)).unwrap();
+    generator
+        .set_crate(CrateConfig::new("vectors", "0.1.0"))
+        .unwrap();
+    generator.set_vectors(true);
+    let files = generator.generate(module).unwrap();
+    testing::write_files(dir.path(), &files).unwrap();
 
-    // Verify it compiles with rustc
-    let output_path = Path::new("tests/output_rust/example.rs");
-    let rustc_status = Command::new("rustc")
-        .arg("--crate-type")
-        .arg("lib")
-        .arg("--edition")
-        .arg("2021")
-        .arg(output_path)
-        .arg("--out-dir")
-        .arg("tests/output_rust")
-        .status()
-        .expect("Failed to run rustc");
-
-    assert!(
-        rustc_status.success(),
-        "Generated Rust code failed to compile"
-    );
-
-    // Run runtime verification
-    let runner_path = Path::new("tests/rust_test_main.rs.inc");
-    let runner_status = Command::new("rustc")
-        .arg("--edition")
-        .arg("2021")
-        .arg("--crate-name")
-        .arg("rust_test_main")
-        .arg(runner_path)
-        .arg("-o")
-        .arg("tests/output_rust/runner")
-        .status()
-        .expect("Failed to compile runner");
-
-    assert!(
-        runner_status.success(),
-        "Failed to compile verification runner"
-    );
-
-    let run_status = Command::new("tests/output_rust/runner")
-        .status()
-        .expect("Failed to run verification runner");
-
-    assert!(run_status.success(), "Runtime verification failed");
+    let root = dir.path();
+    testing::check(
+        Command::new(&rustc)
+            .args([
+                "--crate-type",
+                "lib",
+                "--crate-name",
+                "vectors",
+                "--edition",
+                "2021",
+            ])
+            .arg(root.join("src/lib.rs"))
+            .arg("--out-dir")
+            .arg(dir.path()),
+    );
+    let runner = dir.path().join("runner");
+    testing::check(
+        Command::new(&rustc)
+            .args(["--test", "--edition", "2021", "--extern"])
+            .arg(format!(
+                "vectors={}",
+                dir.path().join("libvectors.rlib").display()
+            ))
+            .arg(root.join("tests/vectors.rs"))
+            .arg("-o")
+            .arg(&runner),
+    );
+    testing::check(&mut Command::new(runner));
+}
+
+/// Generates `module` as a CMake package with test vectors and runs its `tests/vectors.cpp`,
+/// unless no C++ compiler is found.
+fn check_cpp_vectors(stem: &str, module: &onyx::ast::OnyxModule) {
+    let Some(cxx) = testing::require(Toolchain::Cxx) else {
+        return;
+    };
+    let dir = TempDir::new(&format!("vectors-cpp-{stem}")).unwrap();
+    let mut generator = CppGenerator::default();
+    generator.add_file_path(PathBuf::from(stem)).unwrap();
+    generator
+        .set_package(PackageConfig::new("vectors", "0.1.0"))
+        .unwrap();
+    generator.set_vectors(true);
+    let files = generator.generate(module).unwrap();
+    testing::write_files(dir.path(), &files).unwrap();
+
+    let root = dir.path();
+    let runner = dir.path().join("runner");
+    testing::check(
+        Command::new(cxx)
+            .arg("-std=c++11")
+            .arg("-I")
+            .arg(root.join("include"))
+            .arg(root.join("tests/vectors.cpp"))
+            .arg(root.join(format!("src/{stem}.cpp")))
+            .arg("-o")
+            .arg(&runner),
+    );
+    testing::check(&mut Command::new(runner));
+}
+
+/// Compiles the Rust program `main` to `runner` and runs it, unless rustc is missing.
+fn run_rust(main: &str, runner: &str) {
+    let Some(rustc) = testing::require(Toolchain::Rustc) else {
+        return;
+    };
+    // `tests/rust_x_main.rs.inc` is the crate `rust_x_main`.
+    let file_name = Path::new(main).file_name().unwrap().to_str().unwrap();
+    let crate_name = file_name.split('.').next().unwrap();
+    testing::check(
+        Command::new(rustc)
+            .args(["--edition", "2021", "--crate-name", crate_name])
+            .arg(main)
+            .args(["-o", runner]),
+    );
+    testing::check(&mut Command::new(runner));
+}
+
+/// Compiles the C++ program `main` with `sources` and `flags` to `runner` and runs it, unless no
+/// C++ compiler is found.
+fn run_cpp(main: &str, sources: &[&str], flags: &[&str], runner: &str) {
+    let Some(cxx) = testing::require(Toolchain::Cxx) else {
+        return;
+    };
+    testing::check(
+        Command::new(cxx)
+            .arg("-std=c++11")
+            .args(flags)
+            .arg(main)
+            .args(sources)
+            .args(["-o", runner]),
+    );
+    testing::check(&mut Command::new(runner));
 }
@@ -0,0 +1,211 @@
+//! Golden wire-format conformance across backends.
+//!
+//! Each schema in `tests/conformance` has a `.golden` file next to it, listing canonical
+//! messages. A message is a line with its definition and its bytes as hex, followed by an
+//! indented `path = value` line for each of its leaves. The test checks that:
+//!
+//! - the reference codec in `onyx::dynamic` decodes each message's bytes to exactly those leaves
+//!   and encodes them back to the same bytes, so the golden file pins the layout itself;
+//! - the driver of every backend (see `common`) decodes the bytes to the same leaves and encodes
+//!   the message to the golden bytes;
+//! - the driver of every backend decodes the bytes every other backend encoded.
+//!
+//! Golden files are generated from random values. After an intended layout change, or to add a
+//! schema, regenerate them with `ONYX_BLESS=1 cargo test --test conformance` and review the
+//! diff. The Python generator does not emit codecs yet, so it is not among the backends.
+
+mod common;
+
+use std::{
+    fmt::Write,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use common::{
+    Case, Leaf, LeafKind, Rng, Step, build_cpp, build_rust, from_hex, leaves, random_value,
+    run_driver, to_hex,
+};
+use onyx::{
+    ast::{Definition, OnyxModule},
+    dynamic::{self, Value},
+    parser::Parser,
+};
+
+/// Number of messages generated per definition when blessing.
+const CASES: usize = 4;
+
+const FIXTURE_DIR: &str = "tests/conformance";
+
+const OUTPUT_DIR: &str = "tests/output_conformance";
+
+type Build = fn(&OnyxModule, &Path, &[Case]) -> PathBuf;
+
+/// The backends under test, by name.
+const BACKENDS: [(&str, Build); 2] = [("rust", build_rust), ("cpp", build_cpp)];
+
+#[test]
+fn conformance() {
+    let mut fixtures: Vec<PathBuf> = fs::read_dir(FIXTURE_DIR)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "onyx"))
+        .collect();
+    fixtures.sort();
+    assert!(!fixtures.is_empty(), "no schemas in {FIXTURE_DIR}");
+    for fixture in fixtures {
+        check_fixture(&fixture);
+    }
+}
+
+fn check_fixture(fixture: &Path) {
+    let name = fixture.file_stem().unwrap().to_str().unwrap();
+    let source = fs::read_to_string(fixture).unwrap();
+    let module = Parser::new(&source)
+        .and_then(|p| p.parse_module())
+        .unwrap_or_else(|e| panic!("{}: {e}", fixture.display()));
+    let golden_path = fixture.with_extension("golden");
+
+    let cases = if std::env::var_os("ONYX_BLESS").is_some() {
+        let cases = random_cases(&module, name);
+        fs::write(&golden_path, render(&cases)).unwrap();
+        cases
+    } else {
+        let golden = fs::read_to_string(&golden_path).unwrap_or_else(|e| {
+            panic!(
+                "{}: {e}; run with ONYX_BLESS=1 to create it",
+                golden_path.display()
+            )
+        });
+        let cases = parse_golden(&module, &golden, &golden_path);
+        assert!(
+            render(&cases) == golden,
+            "the reference codec no longer decodes {} to its leaves; \
+             run with ONYX_BLESS=1 if the layout change is intended",
+            golden_path.display()
+        );
+        cases
+    };
+
+    let dir = Path::new(OUTPUT_DIR).join(name);
+    let golden: Vec<Vec<u8>> = cases.iter().map(|case| case.bytes.clone()).collect();
+    let drivers: Vec<(&str, PathBuf)> = BACKENDS
+        .iter()
+        .map(|(backend, build)| (*backend, build(&module, &dir.join(backend), &cases)))
+        .collect();
+
+    let mut encoded = Vec::new();
+    for (backend, driver) in &drivers {
+        let output = run_driver(driver, &golden);
+        for (i, (actual, case)) in output.iter().zip(&cases).enumerate() {
+            assert!(
+                *actual == case.bytes,
+                "{name}: {backend} encodes message {i} ({}) as {}, expected {}",
+                case.definition,
+                to_hex(actual),
+                to_hex(&case.bytes)
+            );
+        }
+        encoded.push((*backend, output));
+    }
+    for (consumer, driver) in &drivers {
+        for (producer, output) in &encoded {
+            if producer != consumer {
+                run_driver(driver, output);
+            }
+        }
+    }
+}
+
+/// Generates messages from random values, seeded by the schema name so blessing is repeatable.
+fn random_cases(module: &OnyxModule, name: &str) -> Vec<Case> {
+    let mut rng = Rng(name.bytes().fold(0x9E37_79B9_7F4A_7C15, |seed, b| {
+        (seed ^ b as u64).wrapping_mul(31)
+    }));
+    let mut cases = Vec::new();
+    for id in &module.order {
+        let def = &module.definitions[id];
+        if matches!(def, Definition::Enum(_)) {
+            continue;
+        }
+        for _ in 0..CASES {
+            let value = random_value(module, def, &mut rng);
+            cases.push(Case {
+                definition: def.symbol(),
+                leaves: leaves(module, def, &value),
+                bytes: dynamic::encode(module, def.name(), &value).unwrap(),
+            });
+        }
+    }
+    cases
+}
+
+/// Reads the messages of a golden file, decoding their leaves with the reference codec.
+fn parse_golden(module: &OnyxModule, golden: &str, path: &Path) -> Vec<Case> {
+    golden
+        .lines()
+        .filter(|line| !line.is_empty() && !line.starts_with([' ', '#']))
+        .map(|line| {
+            let (definition, hex) = line
+                .split_once(' ')
+                .unwrap_or_else(|| panic!("{}: malformed message '{line}'", path.display()));
+            let bytes = from_hex(hex);
+            let def = &module.definitions[&definition.into()];
+            let value = dynamic::decode(module, definition, &bytes)
+                .unwrap_or_else(|e| panic!("{}: {definition} {hex}: {e}", path.display()));
+            assert_eq!(
+                dynamic::encode(module, definition, &value).unwrap(),
+                bytes,
+                "{}: the reference codec does not round-trip {definition} {hex}",
+                path.display()
+            );
+            Case {
+                definition: def.symbol(),
+                leaves: leaves(module, def, &value),
+                bytes,
+            }
+        })
+        .collect()
+}
+
+/// Formats messages as a golden file.
+fn render(cases: &[Case]) -> String {
+    let mut out = String::from(
+        "# Canonical messages; regenerate with `ONYX_BLESS=1 cargo test --test conformance`.\n",
+    );
+    for case in cases {
+        writeln!(out, "\n{} {}", case.definition, to_hex(&case.bytes)).unwrap();
+        for leaf in &case.leaves {
+            writeln!(out, "    {} = {}", leaf_path(leaf), leaf_value(leaf)).unwrap();
+        }
+    }
+    out
+}
+
+/// Formats the path of a leaf as `field.nested[index]`, with `.len` for a vector length.
+fn leaf_path(leaf: &Leaf) -> String {
+    let mut path = String::new();
+    for step in &leaf.path {
+        match step {
+            Step::Field(f) if path.is_empty() => path.push_str(f.as_str()),
+            Step::Field(f) => write!(path, ".{f}").unwrap(),
+            Step::Index(i) => write!(path, "[{i}]").unwrap(),
+        }
+    }
+    if let LeafKind::Length { .. } = leaf.kind {
+        path.push_str(".len");
+    }
+    path
+}
+
+fn leaf_value(leaf: &Leaf) -> String {
+    match &leaf.value {
+        Value::Bool(b) => b.to_string(),
+        Value::Unsigned(v) => v.to_string(),
+        Value::Signed(v) => v.to_string(),
+        Value::F32(f) => format!("{f:?}"),
+        Value::F64(f) => format!("{f:?}"),
+        Value::Bytes(bytes) => to_hex(bytes),
+        other => unreachable!("leaves are never {other:?}"),
+    }
+}
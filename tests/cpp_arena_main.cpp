@@ -0,0 +1,33 @@
+#include "output_arena/dispatch.hpp"
+
+#include <cassert>
+
+int main() {
+  // Command is the largest message.
+  static_assert(onyx::MessageArena<1>::kSlotSize == onyx::Command::kSizeOf, "slot size");
+
+  onyx::MessageArena<2> arena;
+  onyx::Command::Buffer* first = arena.acquire<onyx::Command>();
+  assert(first != nullptr);
+  onyx::Command command;
+  command.kind(onyx::Kind::Stop);
+  command.arg(0xDEADBEEF);
+  command.Serialize(*first);
+
+  onyx::Ping::Buffer* second = arena.acquire<onyx::Ping>();
+  assert(second != nullptr);
+  assert((*second)[0] == 0 && (*second)[1] == 0);
+  assert(arena.available() == 0);
+  assert(arena.acquire<onyx::Ping>() == nullptr);
+
+  // A released buffer returns its slot, which comes back zeroed.
+  arena.release(first);
+  assert(arena.available() == 1);
+  onyx::Command::Buffer* reused = arena.acquire<onyx::Command>();
+  assert(reused == first);
+  for (size_t i = 0; i < onyx::Command::kSizeOf; ++i) assert((*reused)[i] == 0);
+  arena.release(second);
+  arena.release(reused);
+  assert(arena.available() == 2);
+  return 0;
+}
@@ -0,0 +1,35 @@
+#include "output_conditions/conditions.hpp"
+
+#include <cassert>
+
+int main() {
+  onyx::Packet::Buffer buffer = {0};
+  onyx::Packet *packet = onyx::Packet::Deserialize(buffer);
+  assert(packet != nullptr);
+  packet->kind(onyx::Kind::Data);
+  packet->payload().len(500);
+  packet->acked(true);
+  packet->level(onyx::Level::High);
+  packet->boost(3);
+  assert(packet->has_payload());
+  assert(packet->payload().len() == 500);
+  assert(!packet->has_reason());
+  assert(!packet->has_seq());
+  assert(packet->has_boost());
+  assert(!packet->has_items());
+
+  // An unknown value in an absent slot does not fail deserialization.
+  onyx::Packet::Buffer encoded;
+  packet->Serialize(encoded);
+  encoded[4] = 7;
+  packet = onyx::Packet::Deserialize(encoded);
+  assert(packet != nullptr);
+  assert(packet->has_payload());
+
+  // Once the field is present, the unknown value does.
+  packet->Serialize(encoded);
+  encoded[0] = static_cast<uint8_t>(onyx::Kind::Close);
+  encoded[4] = 7;
+  assert(onyx::Packet::Deserialize(encoded) == nullptr);
+  return 0;
+}
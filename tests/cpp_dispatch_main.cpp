@@ -0,0 +1,32 @@
+#include "output_dispatch/dispatch.hpp"
+
+#include <cassert>
+
+int main() {
+  // A Command framed by its ID.
+  onyx::Command command;
+  command.kind(onyx::Kind::Stop);
+  command.arg(0xDEADBEEF);
+  uint8_t frame[onyx::kMessageIdSize + onyx::Command::kSizeOf];
+  onyx::WriteMessageId(onyx::Command::kId, frame);
+  command.Serialize(*reinterpret_cast<onyx::Command::Buffer*>(frame + onyx::kMessageIdSize));
+  assert(frame[0] == 1 && frame[1] == 1);
+
+  onyx::AnyMessage any;
+  assert(onyx::DecodeAny(frame, sizeof(frame), any));
+  assert(any.id == onyx::MessageId::Command);
+  assert(any.as<onyx::Ping>() == nullptr);
+  onyx::Command* decoded = any.as<onyx::Command>();
+  assert(decoded != nullptr);
+  assert(decoded->kind() == onyx::Kind::Stop);
+  assert(decoded->arg() == 0xDEADBEEF);
+
+  // Truncated frames, unknown IDs, and unknown enum values are rejected.
+  uint8_t ping[] = {0, 1, 0};
+  assert(!onyx::DecodeAny(ping, sizeof(ping), any));
+  uint8_t unknown[] = {0, 9, 0, 0};
+  assert(!onyx::DecodeAny(unknown, sizeof(unknown), any));
+  uint8_t invalid[] = {1, 1, 5, 0, 0, 0, 0};
+  assert(!onyx::DecodeAny(invalid, sizeof(invalid), any));
+  return 0;
+}
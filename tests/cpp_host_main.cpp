@@ -0,0 +1,56 @@
+#include "output_host/host.hpp"
+
+#include <fcntl.h>
+#include <sys/mman.h>
+#include <unistd.h>
+
+#include <cassert>
+#include <fstream>
+#include <iterator>
+#include <vector>
+
+int main() {
+  // The buffer the Rust runner wrote in native byte order.
+  std::ifstream file("tests/output_host/ring.bin", std::ios::binary);
+  std::vector<char> bytes((std::istreambuf_iterator<char>(file)), std::istreambuf_iterator<char>());
+  assert(bytes.size() == onyx::Ring::kSizeOf);
+
+  onyx::Ring::Buffer buffer;
+  memcpy(buffer, bytes.data(), sizeof(buffer));
+  onyx::Ring* ring = onyx::Ring::Deserialize(buffer);
+  assert(ring != nullptr);
+  assert(ring->head() == 0x01020304);
+  assert(ring->ready());
+  assert(ring->epoch() == 300);
+  assert(ring->load() == 0.25);
+  assert(ring->slots().len() == 1);
+  assert(ring->slots().get(0).mode() == onyx::Mode::Busy);
+  assert(ring->slots().get(0).owner() == 9);
+
+  // Decoding leaves the bytes as they were, and encoding writes them back unchanged.
+  assert(memcmp(buffer, bytes.data(), sizeof(buffer)) == 0);
+  onyx::Ring::Buffer out;
+  ring->Serialize(out);
+  assert(memcmp(out, bytes.data(), sizeof(out)) == 0);
+
+  // An undeclared mode in a slot in use is still rejected.
+  buffer[11] = 7;
+  buffer[12] = 0;
+  assert(onyx::Ring::Deserialize(buffer) == nullptr);
+
+  // The same bytes, read in place from a shared mapping of the file.
+  int fd = open("tests/output_host/ring.bin", O_RDWR);
+  assert(fd >= 0);
+  void* mapped = mmap(nullptr, onyx::Ring::kSizeOf, PROT_READ | PROT_WRITE, MAP_SHARED, fd, 0);
+  assert(mapped != MAP_FAILED);
+  assert(onyx::Ring::from_mmap(mapped, onyx::Ring::kSizeOf - 1) == nullptr);
+  assert(onyx::Ring::from_mmap(nullptr, onyx::Ring::kSizeOf) == nullptr);
+  onyx::Ring* shared = onyx::Ring::from_mmap(mapped, onyx::Ring::kSizeOf);
+  assert(shared != nullptr);
+  assert(shared->head() == 0x01020304);
+  assert(shared->slots().get(0).owner() == 9);
+  assert(onyx::Ring::at(mapped) == shared);
+  munmap(mapped, onyx::Ring::kSizeOf);
+  close(fd);
+  return 0;
+}
@@ -0,0 +1,48 @@
+#include "output_reflection/conditions.hpp"
+#include "output_reflection/nested.hpp"
+
+#include <stdio.h>
+
+#include <cassert>
+#include <string>
+
+// Records the name of each field visited, and the value of those that are bytes.
+struct Recorder {
+  std::string visited;
+
+  template <typename T>
+  void operator()(const onyx::FieldInfo& info, const T&) {
+    visited += info.name;
+    visited += "\n";
+  }
+
+  void operator()(const onyx::FieldInfo& info, uint8_t value) {
+    visited += std::string(info.name) + "=" + std::to_string(value) + "\n";
+  }
+};
+
+int main() {
+  static_assert(onyx::kPacketFields.size() == 9, "one entry per field");
+  assert(onyx::kPacketFields[4].bit_offset == 1 && onyx::kPacketFields[4].bits == 7);
+  assert(onyx::kPathFields[2].kind == onyx::FieldKind::Vector);
+  assert(std::string(onyx::kPathFields[2].type_name) == "vec<Segment, 4>");
+
+  // kind Ping, seq 5, level High, boost 9, and no items: payload and reason are absent.
+  onyx::Packet::Buffer buffer = {0, 0, 0, 0, 0, 5 << 1, 2, 9, 0, 0, 0, 0};
+  onyx::Packet* packet = onyx::Packet::Deserialize(buffer);
+  assert(packet != nullptr);
+  Recorder recorder;
+  packet->visit_fields(recorder);
+  const char* expected =
+      "kind\n"
+      "acked\n"
+      "seq=5\n"
+      "level\n"
+      "boost=9\n"
+      "count=0\n";
+  if (recorder.visited != expected) {
+    fprintf(stderr, "unexpected fields:\n%s", recorder.visited.c_str());
+    return 1;
+  }
+  return 0;
+}
@@ -0,0 +1,34 @@
+#include "output_simd/samples.hpp"
+
+#include <cassert>
+
+int main() {
+  onyx::Samples samples;
+  memset(&samples, 0, sizeof(samples));
+  samples.id(0x0102);
+  for (uint16_t i = 0; i < 9; ++i) assert(samples.levels().push(0x1000 + i));
+  for (int32_t i = 0; i < 5; ++i) assert(samples.counts().push(-1 - i));
+  for (int i = 0; i < 3; ++i) assert(samples.readings().push(1.5 * i));
+
+  onyx::Samples::Buffer buffer;
+  samples.Serialize(buffer);
+  // id, then the levels length and items, all big-endian.
+  assert(buffer[0] == 0x01 && buffer[1] == 0x02);
+  assert(buffer[2] == 9);
+  for (int i = 0; i < 9; ++i) assert(buffer[3 + 2 * i] == 0x10 && buffer[4 + 2 * i] == i);
+  // The counts length, then -1 as 0xFFFFFFFF and -2 as 0xFFFFFFFE.
+  assert(buffer[21] == 5);
+  assert(buffer[22] == 0xFF && buffer[25] == 0xFF);
+  assert(buffer[26] == 0xFF && buffer[28] == 0xFF && buffer[29] == 0xFE);
+  // The readings length, then 1.5 as 0x3FF8000000000000.
+  assert(buffer[42] == 3);
+  assert(buffer[51] == 0x3F && buffer[52] == 0xF8 && buffer[58] == 0);
+
+  onyx::Samples* decoded = onyx::Samples::Deserialize(buffer);
+  assert(decoded->id() == 0x0102);
+  assert(decoded->levels().len() == 9);
+  for (uint16_t i = 0; i < 9; ++i) assert(decoded->levels().get(i) == 0x1000 + i);
+  for (int32_t i = 0; i < 5; ++i) assert(decoded->counts().get(i) == -1 - i);
+  for (int i = 0; i < 3; ++i) assert(decoded->readings().get(i) == 1.5 * i);
+  return 0;
+}
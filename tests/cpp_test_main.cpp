@@ -58,5 +58,11 @@ int main() {
   assert(user->hdr().checksum() == 300);
   assert(user->hdr().tag() == onyx::Status::Error);
 
+  // Status is closed, so a value no variant declares is rejected.
+  onyx::User::Buffer unknown = {0};
+  unknown[16] = 200;
+  assert(onyx::User::Deserialize(unknown) == nullptr);
+  assert(!onyx::is_known(onyx::Status(200)));
+
   return 0;
 }
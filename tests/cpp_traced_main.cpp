@@ -0,0 +1,58 @@
+#include <stdio.h>
+
+#include <cassert>
+#include <string>
+
+// Collects the trace instead of printing it. The source is included so the hook is defined
+// for it too.
+static std::string traced;
+
+static void record(const char* message, const char* field, size_t offset, uint64_t value) {
+  char line[128];
+  snprintf(line, sizeof(line), "%s.%s@%zu=%llu\n", message, field, offset, (unsigned long long)value);
+  traced += line;
+}
+
+static void record(const char* message, const char* field, size_t offset, int64_t value) {
+  char line[128];
+  snprintf(line, sizeof(line), "%s.%s@%zu=%lld\n", message, field, offset, (long long)value);
+  traced += line;
+}
+
+static void record(const char* message, const char* field, size_t offset, double value) {
+  char line[128];
+  snprintf(line, sizeof(line), "%s.%s@%zu=%g\n", message, field, offset, value);
+  traced += line;
+}
+
+static void record(const char* message, const char* field, size_t offset, const char* value) {
+  char line[128];
+  snprintf(line, sizeof(line), "%s.%s@%zu=%s\n", message, field, offset, value);
+  traced += line;
+}
+
+#define ONYX_TRACE
+#define ONYX_TRACE_FIELD(message, field, offset, value) record(message, field, offset, value)
+#include "output_traced/conditions.cpp"
+
+int main() {
+  // kind Data, payload {Close, 258}, seq 5, level High, boost 9, and one item.
+  onyx::Packet::Buffer buffer = {1, 2, 2, 1, 0, 5 << 1, 2, 9, 1, 1, 1, 0};
+  assert(onyx::Packet::Deserialize(buffer) != nullptr);
+  const char* expected =
+      "Body.kind@0=2\n"
+      "Body.len@1=258\n"
+      "Packet.kind@0=1\n"
+      "Packet.payload@1=Body\n"
+      "Packet.acked@5=0\n"
+      "Packet.seq@5=5\n"
+      "Packet.level@6=2\n"
+      "Packet.boost@7=9\n"
+      "Packet.count@8=1\n"
+      "Packet.items@9=1\n";
+  if (traced != expected) {
+    fprintf(stderr, "unexpected trace:\n%s", traced.c_str());
+    return 1;
+  }
+  return 0;
+}
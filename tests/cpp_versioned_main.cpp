@@ -0,0 +1,45 @@
+#include "output_versioned/versioned.hpp"
+
+#include <cassert>
+
+int main() {
+  // Version 1: no level or id, and points without z.
+  onyx::v1::Sample::Buffer old = {0};
+  onyx::v1::Sample *sample = onyx::v1::Sample::Deserialize(old);
+  sample->legacy(0xBEEF);
+  sample->flag(true);
+  sample->origin().x(-1);
+  sample->origin().y(2);
+  onyx::v1::Point point;
+  point.x(30);
+  point.y(40);
+  sample->path().push(onyx::v1::Point());
+  sample->path().push(point);
+
+  onyx::Sample upgraded;
+  sample->Upgrade(upgraded);
+  assert(upgraded.flag());
+  assert(upgraded.level() == 0);
+  assert(upgraded.origin().x() == -1);
+  assert(upgraded.origin().y() == 2);
+  assert(upgraded.origin().z() == 0);
+  assert(upgraded.path().len() == 2);
+  assert(upgraded.path().get(1).x() == 30);
+  assert(upgraded.path().get(1).y() == 40);
+  assert(upgraded.id() == 0);
+
+  // Version 2 adds level and id.
+  onyx::v2::Sample::Buffer old2 = {0};
+  onyx::v2::Sample *sample2 = onyx::v2::Sample::Deserialize(old2);
+  sample2->level(9);
+  sample2->id(0x01020304);
+  sample2->origin().y(7);
+
+  sample2->Upgrade(upgraded);
+  assert(!upgraded.flag());
+  assert(upgraded.level() == 9);
+  assert(upgraded.origin().y() == 7);
+  assert(upgraded.path().len() == 0);
+  assert(upgraded.id() == 0x01020304);
+  return 0;
+}
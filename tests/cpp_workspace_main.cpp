@@ -0,0 +1,43 @@
+// status.hpp only declares Kind, so it comes first to show it stands alone. The other headers
+// include common/common.hpp and use bounded vectors; the utilities they share are defined once.
+#include "status/status.hpp"
+#include "telemetry/telemetry.hpp"
+#include "control/control.hpp"
+
+#include <cassert>
+
+int main() {
+  onyx::Packet packet;
+  packet.header().version(3);
+  packet.header().kind(onyx::Kind::Busy);
+  packet.samples().clear();
+  packet.samples().push(0x0102);
+  onyx::Packet::Buffer buffer;
+  packet.Serialize(buffer);
+  // Big-endian: the header, then the vector length and the first sample.
+  assert(buffer[0] == 3 && buffer[1] == 1);
+  assert(buffer[2] == 1 && buffer[3] == 0x01 && buffer[4] == 0x02);
+
+  onyx::Packet* decoded = onyx::Packet::Deserialize(buffer);
+  assert(decoded->header().kind() == onyx::Kind::Busy);
+  assert(decoded->samples().len() == 1 && decoded->samples().get(0) == 0x0102);
+
+  onyx::Command command;
+  command.header().version(1);
+  command.args().clear();
+  command.args().push(7);
+  onyx::Command::Buffer command_buffer;
+  command.Serialize(command_buffer);
+  assert(onyx::Command::Deserialize(command_buffer)->args().get(0) == 7);
+
+  onyx::Status status;
+  status.kind(onyx::Kind::Idle);
+  status.code(0x0a0b);
+  onyx::Status::Buffer status_buffer;
+  status.Serialize(status_buffer);
+  assert(status_buffer[0] == 0 && status_buffer[1] == 0x0a && status_buffer[2] == 0x0b);
+  // Deserialize checks the kind against Kind's declared values.
+  status_buffer[0] = 7;
+  assert(onyx::Status::Deserialize(status_buffer) == nullptr);
+  return 0;
+}
@@ -0,0 +1,471 @@
+//! Cross-checks generated backends against the reference codec in `onyx::dynamic`.
+//!
+//! For each schema, random values are encoded with the reference codec. The driver of each
+//! backend (see `common`) decodes those bytes and checks every field, and must encode the values
+//! back to the same bytes.
+//!
+//! Floats are also checked with chosen values rather than random ones: NaNs with payloads,
+//! signed zeros, infinities, and subnormals, whose bits a careless byte swap or a round trip
+//! through a floating-point register can change.
+
+mod common;
+
+use std::{fs, path::Path};
+
+use common::{Case, Rng, build_cpp, build_rust, leaves, random_value, run_driver};
+use onyx::{
+    ast::{Definition, OnyxModule},
+    dynamic::{self, Value},
+    parser::Parser,
+    wire,
+};
+
+/// Number of random values checked per definition.
+const CASES: usize = 16;
+
+const OUTPUT_DIR: &str = "tests/output_differential";
+
+/// Every primitive, a wide and a signed enum, and nested structs. Bit-field groups are unsigned
+/// and fill 1, 2, or 4 byte containers, which is what the backends support today.
+const PRIMITIVES: &str = "
+    enum Mode : u16 { Off, On = 300, Auto, }
+    enum Level : i8 { Low = 1, High, }
+
+    struct Flags {
+        ready bool : 1,
+        level u8 : 3,
+        code u16 : 12,
+    }
+
+    struct Inner {
+        mode Mode,
+        flags Flags,
+        ratio f32,
+        level Level,
+    }
+
+    message Wide {
+        a u8,
+        b i8,
+        c u16,
+        d i16,
+        e u32,
+        f i32,
+        g u64,
+        h i64,
+        x f32,
+        y f64,
+        ok bool,
+        inner Inner,
+        packed u32 : 20,
+        small u16 : 12,
+    }
+";
+
+/// Signed bit-fields, which are sign-extended when read, beside unsigned ones in 1, 2, 4, and
+/// 8 byte containers. Half of the random values are negative.
+const SIGNED_BIT_FIELDS: &str = "
+    struct Reading {
+        temp i16 : 12,
+        trend i8 : 4,
+    }
+
+    message Telemetry {
+        reading Reading,
+        offset i32 : 20,
+        delta i8 : 3,
+        ready bool : 1,
+        tilt i64 : 8,
+        id u16,
+        full i8 : 8,
+        stamp u32,
+        wide i64 : 64,
+    }
+";
+
+/// Bools packed into single bits by `bool_width = 1`, alone and beside other bit-fields.
+const PACKED_BOOLS: &str = "
+    bool_width = 1
+
+    struct Status {
+        ready bool,
+        busy bool,
+        level u8 : 6,
+    }
+
+    message Report {
+        id u16,
+        status Status,
+        a bool,
+        b bool,
+        c bool,
+        d bool,
+        e bool,
+        f bool,
+        g bool,
+        h bool,
+        code u32 : 23,
+        done bool,
+        history vec<bool, 3>,
+    }
+";
+
+/// Fields padded to their CDR alignment, with the `align` directive added by each test.
+const CDR_ALIGNED: &str = "
+    enum Mode : u16 { Off, On = 300, Auto, }
+
+    struct Point {
+        x f64,
+        tag u8,
+    }
+
+    struct Flags {
+        ready bool : 1,
+        level u8 : 7,
+    }
+
+    message Sample {
+        kind u8,
+        mode Mode,
+        count u32,
+        flags Flags,
+        wide u64,
+        point Point,
+        scaled fixed<i16, 100>,
+        id uuid,
+        code u16 : 12,
+        spare u16 : 4,
+        last i8,
+        total i64,
+    }
+";
+
+/// Bounded vectors of primitives, wide enums, and structs, with one- and two-byte lengths.
+const VECTORS: &str = "
+    enum Mode : u16 { Off, On = 300, Auto, }
+
+    struct Point {
+        x i16,
+        y i16,
+        mode Mode,
+    }
+
+    struct Track {
+        id u32,
+        points vec<Point, 3>,
+    }
+
+    message Log {
+        levels vec<f32, 4>,
+        flags vec<bool, 2>,
+        modes vec<Mode, 5>,
+        track Track,
+        raw vec<u8, 260>,
+        tail u16,
+    }
+";
+
+/// Fixed-point fields of each integer width, with scales that do and do not divide evenly.
+const FIXED_POINT: &str = "
+    struct Sensor {
+        celsius fixed<i16, 100>,
+        humidity fixed<u8, 2>,
+    }
+
+    message Reading {
+        id u16,
+        sensor Sensor,
+        volts fixed<i32, 1000>,
+        total fixed<u32, 3>,
+        offset fixed<i8, 1>,
+    }
+";
+
+/// UUIDs and byte arrays, which are never byte-swapped.
+const BYTE_ARRAYS: &str = "
+    struct Key {
+        id uuid,
+        tag bytes[3],
+    }
+
+    message Record {
+        seq u16,
+        key Key,
+        blob bytes[20],
+        flag bool,
+    }
+";
+
+/// Flags enums, whose values are any combination of the declared bits.
+const FLAGS: &str = "
+    flags Access : u8 { Read, Write, Exec, @composite All = 7, }
+    flags Caps : u32 { Net = 1, Disk = 256, Gpu = 65536, }
+
+    message Grant {
+        access Access,
+        caps Caps,
+        history vec<Access, 3>,
+    }
+";
+
+/// Open and closed enums. Open enums also take values that no variant declares.
+const ENUMS: &str = "
+    @open enum Mode : u16 { Off, On = 300, }
+    @closed enum Kind : i8 { A = 1, B = 7, }
+
+    struct Setting {
+        mode Mode,
+        kind Kind,
+    }
+
+    message Config {
+        setting Setting,
+        modes vec<Mode, 3>,
+        kinds vec<Kind, 2>,
+        settings vec<Setting, 2>,
+    }
+";
+
+/// Floats alone, nested, and in a vector.
+const FLOATS: &str = "
+    struct Pair {
+        low f32,
+        high f64,
+    }
+
+    message Samples {
+        narrow f32,
+        wide f64,
+        pair Pair,
+        series vec<f32, 3>,
+    }
+";
+
+/// `f32` bit patterns that a lossy float path would change: quiet and signalling NaNs with
+/// payloads, both zeros and infinities, the extreme subnormals and normals, and values whose
+/// four bytes all differ.
+const F32_EDGE_CASES: [u32; 14] = [
+    0x7FC0_0000,
+    0x7FC0_0001,
+    0x7F80_0001,
+    0xFFBF_FFFF,
+    0x0000_0000,
+    0x8000_0000,
+    0x0000_0001,
+    0x807F_FFFF,
+    0x0080_0000,
+    0x7F7F_FFFF,
+    0x7F80_0000,
+    0xFF80_0000,
+    0x3F80_0001,
+    0x1234_5678,
+];
+
+/// The `f64` counterparts of [`F32_EDGE_CASES`].
+const F64_EDGE_CASES: [u64; 14] = [
+    0x7FF8_0000_0000_0000,
+    0x7FF8_0000_0000_0001,
+    0x7FF0_0000_0000_0001,
+    0xFFF7_FFFF_FFFF_FFFF,
+    0x0000_0000_0000_0000,
+    0x8000_0000_0000_0000,
+    0x0000_0000_0000_0001,
+    0x800F_FFFF_FFFF_FFFF,
+    0x0010_0000_0000_0000,
+    0x7FEF_FFFF_FFFF_FFFF,
+    0x7FF0_0000_0000_0000,
+    0xFFF0_0000_0000_0000,
+    0x3FF0_0000_0000_0001,
+    0x0123_4567_89AB_CDEF,
+];
+
+#[test]
+fn differential_example() {
+    let source = fs::read_to_string("tests/example.onyx").unwrap();
+    check_schema("example", &source);
+}
+
+#[test]
+fn differential_big_endian() {
+    check_schema("big_endian", &format!("endian = big\n{PRIMITIVES}"));
+}
+
+#[test]
+fn differential_little_endian() {
+    check_schema("little_endian", &format!("endian = little\n{PRIMITIVES}"));
+}
+
+#[test]
+fn differential_signed_bit_fields() {
+    check_schema(
+        "signed_big_endian",
+        &format!("endian = big\n{SIGNED_BIT_FIELDS}"),
+    );
+    check_schema(
+        "signed_little_endian",
+        &format!("endian = little\n{SIGNED_BIT_FIELDS}"),
+    );
+}
+
+#[test]
+fn differential_packed_bools() {
+    check_schema("bools_big_endian", &format!("endian = big\n{PACKED_BOOLS}"));
+    check_schema(
+        "bools_little_endian",
+        &format!("endian = little\n{PACKED_BOOLS}"),
+    );
+}
+
+#[test]
+fn differential_cdr_aligned() {
+    check_schema(
+        "xcdr1_big_endian",
+        &format!("endian = big\nalign = xcdr1\n{CDR_ALIGNED}"),
+    );
+    check_schema(
+        "xcdr2_little_endian",
+        &format!("endian = little\nalign = xcdr2\n{CDR_ALIGNED}"),
+    );
+}
+
+#[test]
+fn differential_vectors() {
+    check_schema("vectors_big_endian", &format!("endian = big\n{VECTORS}"));
+    check_schema(
+        "vectors_little_endian",
+        &format!("endian = little\n{VECTORS}"),
+    );
+}
+
+#[test]
+fn differential_fixed_point() {
+    check_schema("fixed_big_endian", &format!("endian = big\n{FIXED_POINT}"));
+    check_schema(
+        "fixed_little_endian",
+        &format!("endian = little\n{FIXED_POINT}"),
+    );
+}
+
+#[test]
+fn differential_byte_arrays() {
+    check_schema("bytes_big_endian", &format!("endian = big\n{BYTE_ARRAYS}"));
+    check_schema(
+        "bytes_little_endian",
+        &format!("endian = little\n{BYTE_ARRAYS}"),
+    );
+}
+
+#[test]
+fn differential_enums() {
+    check_schema("enums_big_endian", &format!("endian = big\n{ENUMS}"));
+    check_schema("enums_little_endian", &format!("endian = little\n{ENUMS}"));
+}
+
+#[test]
+fn differential_float_edge_cases() {
+    for endianness in ["big", "little"] {
+        let module = parse(&format!("endian = {endianness}\n{FLOATS}"));
+        let f32_at =
+            |i: usize| Value::F32(f32::from_bits(F32_EDGE_CASES[i % F32_EDGE_CASES.len()]));
+        let f64_at =
+            |i: usize| Value::F64(f64::from_bits(F64_EDGE_CASES[i % F64_EDGE_CASES.len()]));
+        let mut cases = Vec::new();
+        for i in 0..F32_EDGE_CASES.len() {
+            let value = Value::Record(vec![
+                ("narrow".into(), f32_at(i)),
+                ("wide".into(), f64_at(i)),
+                (
+                    "pair".into(),
+                    Value::Record(vec![
+                        ("low".into(), f32_at(i + 1)),
+                        ("high".into(), f64_at(i + 7)),
+                    ]),
+                ),
+                (
+                    "series".into(),
+                    Value::List((i..i + i % 4).map(|j| f32_at(j + 3)).collect()),
+                ),
+            ]);
+            let bytes = dynamic::encode(&module, "Samples", &value).unwrap();
+
+            // The reference codec stores each float as the bits `wire` gives, and keeps them
+            // through a round trip. NaN != NaN, so the round trip compares bytes, not values.
+            let endianness = module.endianness;
+            let mut narrow = [0; 4];
+            wire::write_f32(f32::from_bits(F32_EDGE_CASES[i]), endianness, &mut narrow);
+            let mut wide = [0; 8];
+            wire::write_f64(f64::from_bits(F64_EDGE_CASES[i]), endianness, &mut wide);
+            assert_eq!(bytes[..4], narrow);
+            assert_eq!(bytes[4..12], wide);
+            assert_eq!(
+                wire::read_f32(&narrow, endianness).to_bits(),
+                F32_EDGE_CASES[i]
+            );
+            let decoded = dynamic::decode(&module, "Samples", &bytes).unwrap();
+            assert_eq!(
+                dynamic::encode(&module, "Samples", &decoded).unwrap(),
+                bytes
+            );
+
+            let def = &module.definitions[&"Samples".into()];
+            cases.push(Case {
+                definition: def.symbol(),
+                leaves: leaves(&module, def, &value),
+                bytes,
+            });
+        }
+        check_cases(&format!("floats_{endianness}_endian"), &module, &cases);
+    }
+}
+
+#[test]
+fn differential_flags() {
+    check_schema("flags_big_endian", &format!("endian = big\n{FLAGS}"));
+    check_schema("flags_little_endian", &format!("endian = little\n{FLAGS}"));
+}
+
+fn check_schema(name: &str, source: &str) {
+    let module = parse(source);
+    let mut rng = Rng(0x9E37_79B9_7F4A_7C15 ^ name.len() as u64);
+    let mut cases = Vec::new();
+    for id in &module.order {
+        let def = &module.definitions[id];
+        if matches!(def, Definition::Enum(_)) {
+            continue;
+        }
+        for _ in 0..CASES {
+            let value = random_value(&module, def, &mut rng);
+            let bytes = dynamic::encode(&module, def.name(), &value).unwrap();
+            assert_eq!(dynamic::decode(&module, def.name(), &bytes).unwrap(), value);
+            cases.push(Case {
+                definition: def.symbol(),
+                leaves: leaves(&module, def, &value),
+                bytes,
+            });
+        }
+    }
+    check_cases(name, &module, &cases);
+}
+
+/// Runs the driver of each backend on `cases`, which must decode every field and encode the
+/// same bytes back.
+fn check_cases(name: &str, module: &OnyxModule, cases: &[Case]) {
+    let dir = Path::new(OUTPUT_DIR).join(name);
+    fs::create_dir_all(&dir).unwrap();
+    let input: Vec<Vec<u8>> = cases.iter().map(|case| case.bytes.clone()).collect();
+    for binary in [
+        build_rust(module, &dir.join("rust"), cases),
+        build_cpp(module, &dir.join("cpp"), cases),
+    ] {
+        assert_eq!(
+            run_driver(&binary, &input),
+            input,
+            "{} encoded differently",
+            binary.display()
+        );
+    }
+}
+
+fn parse(source: &str) -> OnyxModule {
+    Parser::new(source).and_then(|p| p.parse_module()).unwrap()
+}
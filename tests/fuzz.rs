@@ -0,0 +1,202 @@
+//! Mutation fuzzing of the parser.
+//!
+//! Each iteration takes a schema from the fixtures, mutates it a few times, and checks that
+//! parsing it reports errors instead of panicking, with and without
+//! [`ParseLimits::untrusted`], and that every generator handles whatever parses. Inputs come
+//! from a fixed seed, so failures reproduce; set `ONYX_FUZZ_ITERATIONS` to run longer and
+//! `ONYX_FUZZ_SEED` to explore other inputs.
+//!
+//! For coverage-guided fuzzing, the `cargo fuzz` targets in `fuzz/` cover the same paths and
+//! start from the schemas in `fuzz/corpus/schemas`, which seed this test too.
+
+use std::{fs, panic, path::Path};
+
+use onyx::{
+    generators::{self, TARGETS},
+    hir,
+    limits::ParseLimits,
+    parser::Parser,
+    testing,
+};
+
+/// Snippets inserted by mutations, chosen to reach deep or rarely taken parser paths.
+const DICTIONARY: &[&str] = &[
+    "struct ",
+    "message ",
+    "enum ",
+    "flags ",
+    "const ",
+    "endian = host\n",
+    "version = 3\n",
+    "strict = true\n",
+    "bool_width = 1\n",
+    "vec<",
+    "fixed<",
+    "bytes[",
+    "uuid",
+    "u128",
+    "i64",
+    "f32",
+    "f64",
+    "bool",
+    "@id(",
+    "@max_size(",
+    "@packed_bits ",
+    "@deprecated(\"x\") ",
+    "@since(",
+    "@removed(",
+    "@cfg(",
+    "not(",
+    "all(",
+    "any(",
+    "feature = \"x\"",
+    " if ",
+    " == ",
+    "Kind.A",
+    ": 3",
+    ": 64",
+    "0xFFFFFFFFFFFFFFFF",
+    "18446744073709551616",
+    "<<",
+    ">>",
+    "(((",
+    ")))",
+    "r#",
+    "\"",
+    "//",
+    "/*",
+    "*/",
+    "é",
+    "\0",
+    "{",
+    "}",
+    ",",
+    "<",
+    ">",
+    "=",
+    "@",
+    ".",
+];
+
+/// A xorshift generator, so inputs depend only on the seed.
+struct Rng(u64);
+
+impl Rng {
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    fn below(&mut self, n: usize) -> usize {
+        (self.next() % n.max(1) as u64) as usize
+    }
+}
+
+/// Applies one random edit to `input`: replacing, deleting, duplicating, or inserting bytes.
+fn mutate(input: &mut Vec<u8>, rng: &mut Rng) {
+    let at = rng.below(input.len() + 1);
+    let len = 1 + rng.below(16).min(input.len().saturating_sub(at));
+    let end = (at + len).min(input.len());
+    match rng.below(5) {
+        0 if at < input.len() => {
+            input[at] = b"{}<>()[],:=@.\"#/*-+%0123456789abcxyz \n"[rng.below(38)]
+        }
+        1 => {
+            input.drain(at..end);
+        }
+        2 => {
+            let copy = input[at..end].to_vec();
+            let to = rng.below(input.len() + 1);
+            input.splice(
+                to..to,
+                copy.iter()
+                    .copied()
+                    .cycle()
+                    .take(copy.len() * (1 + rng.below(8))),
+            );
+        }
+        _ => {
+            let word = DICTIONARY[rng.below(DICTIONARY.len())].as_bytes();
+            input.splice(at..at, word.iter().copied());
+        }
+    }
+}
+
+/// Parses `source` every way a service might, renders each diagnostic against it, and
+/// generates every target from each module that parses, returning normally if nothing panics.
+fn parse_all(source: &str) {
+    for limits in [ParseLimits::default(), ParseLimits::untrusted()] {
+        let parser = |source| Parser::new(source).map(|p| p.with_limits(limits.clone()));
+        match parser(source).and_then(|p| p.parse_module()) {
+            Ok(module) => {
+                if let Err(error) = hir::lower(&module) {
+                    let _ = error.render(source);
+                }
+                for target in TARGETS {
+                    let generator = generators::generator_for_target(target, "fuzz".into());
+                    if let Err(error) = generator.and_then(|mut g| g.generate(&module)) {
+                        let _ = error.render(source);
+                    }
+                }
+            }
+            Err(error) => {
+                let _ = error.render(source);
+            }
+        }
+        if let Ok(p) = parser(source) {
+            for error in p.parse_module_recovering().1 {
+                let _ = error.render(source);
+            }
+        }
+    }
+}
+
+#[test]
+fn fuzz_parser() {
+    let iterations: usize = std::env::var("ONYX_FUZZ_ITERATIONS").map_or(2000, |n| {
+        n.parse().expect("ONYX_FUZZ_ITERATIONS must be a number")
+    });
+    let seed: u64 = std::env::var("ONYX_FUZZ_SEED").map_or(0x5EED, |n| {
+        n.parse().expect("ONYX_FUZZ_SEED must be a number")
+    });
+    let mut corpus = Vec::new();
+    for dir in [
+        "tests/snapshots",
+        "tests/conformance",
+        "fuzz/corpus/schemas",
+    ] {
+        for fixture in testing::fixtures(Path::new(dir)).unwrap() {
+            corpus.push(fs::read(fixture).unwrap());
+        }
+    }
+    corpus.push(fs::read("tests/example.onyx").unwrap());
+
+    let mut rng = Rng(seed);
+    let hook = panic::take_hook();
+    panic::set_hook(Box::new(|_| {}));
+    let mut failures = Vec::new();
+    for _ in 0..iterations {
+        let mut input = corpus[rng.below(corpus.len())].clone();
+        for _ in 0..1 + rng.below(4) {
+            mutate(&mut input, &mut rng);
+        }
+        let source = String::from_utf8_lossy(&input).into_owned();
+        if let Err(error) = panic::catch_unwind(|| parse_all(&source)) {
+            let message = error
+                .downcast_ref::<String>()
+                .cloned()
+                .or_else(|| error.downcast_ref::<&str>().map(|s| s.to_string()))
+                .unwrap_or_default();
+            failures.push(format!("{message}\n--- input ---\n{source}"));
+        }
+    }
+    panic::set_hook(hook);
+    assert!(
+        failures.is_empty(),
+        "{} inputs panicked; the first:\n{}",
+        failures.len(),
+        failures[0]
+    );
+}
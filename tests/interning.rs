@@ -0,0 +1,63 @@
+//! Repeated parses under [`ParseLimits`], as a long-running service makes them.
+//!
+//! Names are interned for as long as the process runs, so this test has a binary of its own:
+//! nothing else interns names while it measures the table.
+
+use onyx::{Symbol, diagnostic::codes, limits::ParseLimits, parser::Parser};
+
+/// A schema whose names all end in `tag`, so that each tag brings new names.
+fn schema(tag: usize) -> String {
+    format!(
+        "enum Kind{tag} : u8 {{ A, B, }}\n\
+         struct Point{tag} {{ x{tag} i32, y{tag} i32, }}\n\
+         message Track{tag} {{ kind{tag} Kind{tag}, at{tag} Point{tag}, \
+         ready{tag} bool if kind{tag} == Kind{tag}.B, }}"
+    )
+}
+
+#[test]
+fn repeated_untrusted_parses() {
+    let parse = |source: &str, limits: ParseLimits| {
+        Parser::new(source)
+            .unwrap()
+            .with_limits(limits)
+            .parse_module()
+    };
+
+    // The same schema parsed again interns nothing new.
+    parse(&schema(0), ParseLimits::untrusted()).unwrap();
+    let before = Symbol::interned_bytes();
+    for _ in 0..100 {
+        parse(&schema(0), ParseLimits::untrusted()).unwrap();
+    }
+    assert_eq!(Symbol::interned_bytes(), before);
+
+    // Schemas with new names fill the table up to the limit and no further.
+    let mut limits = ParseLimits::untrusted();
+    let max = before + 16 * 1024;
+    limits.max_symbol_bytes = Some(max);
+    let mut tag = 1;
+    let error = loop {
+        match parse(&schema(tag), limits.clone()) {
+            Ok(_) => tag += 1,
+            Err(error) => break error,
+        }
+    };
+    assert!(tag > 100, "only {tag} schemas parsed");
+    assert_eq!(error.code(), Some(codes::LIMIT_EXCEEDED));
+    assert!(
+        error.message().ends_with(&format!(
+            "is over the limit of {max} bytes of interned names"
+        )),
+        "{}",
+        error.message()
+    );
+    for _ in 0..100 {
+        tag += 1;
+        assert!(parse(&schema(tag), limits.clone()).is_err());
+    }
+    assert!(Symbol::interned_bytes() <= max);
+
+    // Schemas whose names are all known still parse.
+    parse(&schema(1), limits).unwrap();
+}
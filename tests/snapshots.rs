@@ -0,0 +1,187 @@
+//! Snapshot tests for generator output.
+//!
+//! Each schema in `tests/snapshots` is compiled by every generator, and each output file is
+//! compared with the snapshot stored at `tests/snapshots/<schema>/<generator>/<file>`, so any
+//! change to generated code shows up as a diff in review.
+//!
+//! When an output differs, it is written next to its snapshot with a `.new` extension and the
+//! test fails. After an intended change, accept the new outputs with
+//! `ONYX_BLESS=1 cargo test --test snapshots` and review the diff.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use onyx::{
+    ast::OnyxModule,
+    generators::{
+        CodeGenerator, CompileError, arrow::ArrowGenerator, check_deterministic, cpp::CppGenerator,
+        kotlin::KotlinGenerator, matlab::MatlabGenerator, plugin::module_json, rust::RustGenerator,
+        systemverilog::SystemVerilogGenerator,
+    },
+    parser::Parser,
+    vectors,
+};
+
+const SNAPSHOT_DIR: &str = "tests/snapshots";
+
+type Generate = fn(&OnyxModule, &str) -> Result<Vec<(PathBuf, String)>, CompileError>;
+
+/// The generators under test, by snapshot directory.
+const GENERATORS: [(&str, Generate); 9] = [
+    ("cpp", |module, stem| {
+        let mut generator = CppGenerator::default();
+        generator.add_file_path(PathBuf::from(stem))?;
+        generator.generate(module)
+    }),
+    ("cpp_amalgamated", |module, stem| {
+        let mut generator = CppGenerator::default();
+        generator.add_file_path(PathBuf::from(stem))?;
+        generator.set_amalgamate(true);
+        generator.generate(module)
+    }),
+    ("rust", |module, stem| {
+        let mut generator = RustGenerator::default();
+        generator.add_file_path(PathBuf::from(stem))?;
+        generator.generate(module)
+    }),
+    ("kotlin", |module, stem| {
+        let mut generator = KotlinGenerator::default();
+        generator.add_file_path(PathBuf::from(stem))?;
+        generator.generate(module)
+    }),
+    ("systemverilog", |module, stem| {
+        let mut generator = SystemVerilogGenerator::default();
+        generator.add_file_path(PathBuf::from(stem))?;
+        generator.generate(module)
+    }),
+    ("matlab", |module, stem| {
+        let mut generator = MatlabGenerator::default();
+        generator.add_file_path(PathBuf::from(stem))?;
+        generator.generate(module)
+    }),
+    ("arrow", |module, stem| {
+        let mut generator = ArrowGenerator::default();
+        generator.add_file_path(PathBuf::from(stem))?;
+        generator.generate(module)
+    }),
+    ("plugin", |module, stem| {
+        Ok(vec![(
+            PathBuf::from(format!("{stem}.json")),
+            module_json(module),
+        )])
+    }),
+    ("vectors", |module, stem| {
+        let json = vectors::to_json(module, &vectors::vectors(module)?)?;
+        Ok(vec![(PathBuf::from(format!("{stem}.vectors.json")), json)])
+    }),
+];
+
+#[test]
+fn snapshots() {
+    let bless = std::env::var_os("ONYX_BLESS").is_some();
+    let mut schemas: Vec<PathBuf> = fs::read_dir(SNAPSHOT_DIR)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "onyx"))
+        .collect();
+    schemas.sort();
+    assert!(!schemas.is_empty(), "no schemas in {SNAPSHOT_DIR}");
+
+    let mut changed = Vec::new();
+    for schema in &schemas {
+        let stem = schema.file_stem().unwrap().to_str().unwrap();
+        let source = fs::read_to_string(schema).unwrap();
+        let module = Parser::new(&source)
+            .and_then(|p| p.parse_module())
+            .unwrap_or_else(|e| panic!("{}: {e}", schema.display()));
+        for (name, generate) in GENERATORS {
+            let dir = Path::new(SNAPSHOT_DIR).join(stem).join(name);
+            let files = generate(&module, stem)
+                .unwrap_or_else(|e| panic!("{}: {name}: {e}", schema.display()));
+            if bless {
+                let _ = fs::remove_dir_all(&dir);
+                fs::create_dir_all(&dir).unwrap();
+            }
+            for (path, content) in files {
+                let snapshot = dir.join(path.file_name().unwrap());
+                let pending = snapshot.with_extension(format!(
+                    "{}.new",
+                    snapshot.extension().unwrap().to_str().unwrap()
+                ));
+                if bless {
+                    fs::write(&snapshot, &content).unwrap();
+                    continue;
+                }
+                match fs::read_to_string(&snapshot) {
+                    Ok(expected) if expected == content => {
+                        let _ = fs::remove_file(&pending);
+                    }
+                    Ok(expected) => {
+                        changed.push(format!(
+                            "{}: {}",
+                            snapshot.display(),
+                            first_difference(&expected, &content)
+                        ));
+                        fs::write(&pending, &content).unwrap();
+                    }
+                    Err(_) => {
+                        changed.push(format!("{}: no snapshot", snapshot.display()));
+                        fs::create_dir_all(&dir).unwrap();
+                        fs::write(&pending, &content).unwrap();
+                    }
+                }
+            }
+        }
+    }
+    assert!(
+        changed.is_empty(),
+        "generator output changed; review the .new files and run with ONYX_BLESS=1 to \
+         accept them:\n{}",
+        changed.join("\n")
+    );
+}
+
+/// Describes the first line where the snapshot and the new output differ.
+fn first_difference(expected: &str, actual: &str) -> String {
+    let mut expected_lines = expected.lines();
+    let mut actual_lines = actual.lines();
+    for line in 1.. {
+        match (expected_lines.next(), actual_lines.next()) {
+            (Some(e), Some(a)) if e == a => continue,
+            (e, a) => {
+                return format!(
+                    "line {line}: expected {:?}, found {:?}",
+                    e.unwrap_or("<end of file>"),
+                    a.unwrap_or("<end of file>")
+                );
+            }
+        }
+    }
+    unreachable!()
+}
+
+/// Generates each schema on two threads, whose hash maps are seeded differently, and checks
+/// that the outputs match, so no iteration order leaks into generated code.
+#[test]
+fn deterministic() {
+    for entry in fs::read_dir(SNAPSHOT_DIR).unwrap() {
+        let schema = entry.unwrap().path();
+        if schema.extension().is_none_or(|ext| ext != "onyx") {
+            continue;
+        }
+        let stem = schema.file_stem().unwrap().to_str().unwrap();
+        let source = fs::read_to_string(&schema).unwrap();
+        for (name, generate) in GENERATORS {
+            let run = || {
+                let module = Parser::new(&source).and_then(|p| p.parse_module()).unwrap();
+                generate(&module, stem).unwrap()
+            };
+            let first = run();
+            let second = std::thread::scope(|scope| scope.spawn(run).join().unwrap());
+            check_deterministic(&first, &second)
+                .unwrap_or_else(|e| panic!("{}: {name}: {e}", schema.display()));
+        }
+    }
+}
@@ -0,0 +1,64 @@
+// Automatically generated by Onyx IDL compiler. Do not edit.
+#include "bitfields.hpp"
+
+#include <string.h>
+
+namespace onyx {
+
+Flags* Flags::Deserialize(Buffer& buffer) {
+    // Overlay the class structure onto the buffer memory. This is the zero-copy step.
+    Flags* result = (Flags*)buffer;
+
+    // SWAP: Bit-field container ready
+    *(uint16_t*)result->__raw_ready = utils::byteswap_if_needed(*(uint16_t*)result->__raw_ready);
+
+    return result;
+}
+
+void Flags::Serialize(Buffer& buffer) const {
+    // Copy the host-endian object into the buffer first.
+    memcpy(buffer, this, kSizeOf);
+    Flags* wire_format_data = (Flags*)buffer;
+
+    // SWAP: Bit-field container ready
+    *(uint16_t*)wire_format_data->__raw_ready = utils::byteswap_if_needed(*(uint16_t*)wire_format_data->__raw_ready);
+}
+
+Reading* Reading::Deserialize(Buffer& buffer) {
+    // Overlay the class structure onto the buffer memory. This is the zero-copy step.
+    Reading* result = (Reading*)buffer;
+
+    // SWAP: Primitive field id
+    result->__raw_id = utils::byteswap_if_needed(result->__raw_id);
+    Flags::Deserialize(*(Flags::Buffer*) &result->__raw_flags);
+    // SWAP: Bit-field container wide
+    if (ONYX_NETWORK_ORDER == ONYX_BIG_ENDIAN) {
+        for (size_t i = 0; i < 2; ++i) {
+            uint8_t byte = result->__raw_wide[i];
+            result->__raw_wide[i] = result->__raw_wide[4 - i];
+            result->__raw_wide[4 - i] = byte;
+        }
+    }
+
+    return result;
+}
+
+void Reading::Serialize(Buffer& buffer) const {
+    // Copy the host-endian object into the buffer first.
+    memcpy(buffer, this, kSizeOf);
+    Reading* wire_format_data = (Reading*)buffer;
+
+    // SWAP: Primitive field id
+    wire_format_data->__raw_id = utils::byteswap_if_needed(wire_format_data->__raw_id);
+    wire_format_data->__raw_flags.Serialize(*(Flags::Buffer*) &wire_format_data->__raw_flags);
+    // SWAP: Bit-field container wide
+    if (ONYX_NETWORK_ORDER == ONYX_BIG_ENDIAN) {
+        for (size_t i = 0; i < 2; ++i) {
+            uint8_t byte = wire_format_data->__raw_wide[i];
+            wire_format_data->__raw_wide[i] = wire_format_data->__raw_wide[4 - i];
+            wire_format_data->__raw_wide[4 - i] = byte;
+        }
+    }
+}
+
+} // namespace onyx
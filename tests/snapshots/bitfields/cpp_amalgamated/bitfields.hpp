@@ -0,0 +1,365 @@
+// Automatically generated by Onyx IDL compiler. Do not edit.
+#ifndef ONYX_BITFIELDS_H_
+#define ONYX_BITFIELDS_H_
+
+#include <stddef.h>
+#include <stdint.h>
+#include <string.h>
+
+#pragma pack(push, 1)
+
+namespace onyx {
+#ifndef ONYX_UTILS_ENDIANNESS_BIG_
+#define ONYX_UTILS_ENDIANNESS_BIG_
+
+namespace utils {
+
+// Define network endianness (usually Big Endian).
+#define ONYX_BIG_ENDIAN 4321
+#define ONYX_LITTLE_ENDIAN 1234
+#define ONYX_NETWORK_ORDER ONYX_BIG_ENDIAN
+
+// Determine host endianness at compile time (using standard GCC/Clang macros)
+#ifdef __BYTE_ORDER__
+    #if __BYTE_ORDER__ == __ORDER_BIG_ENDIAN__
+        #define ONYX_HOST_ORDER ONYX_BIG_ENDIAN
+    #elif __BYTE_ORDER__ == __ORDER_LITTLE_ENDIAN__
+        #define ONYX_HOST_ORDER ONYX_LITTLE_ENDIAN
+    #else
+        #error "Unsupported host endianness"
+    #endif
+#elif defined(_WIN32) || defined(__LITTLE_ENDIAN__)
+    // Fallback for Windows (always little-endian) or other platforms
+    #define ONYX_HOST_ORDER ONYX_LITTLE_ENDIAN
+#else
+    #error "Could not determine host endianness"
+#endif
+
+// Helper function to swap bytes (Explicit Overloads)
+inline uint16_t byteswap(uint16_t value) { return ((value << 8) & 0xFF00) | ((value >> 8) & 0x00FF); }
+
+inline int16_t byteswap(int16_t value) { return (int16_t)byteswap((uint16_t)value); }
+
+inline uint32_t byteswap(uint32_t value) { return (value << 24) | ((value & 0x00FF0000) >> 8) | ((value & 0x0000FF00) << 8) | (value >> 24); }
+
+inline int32_t byteswap(int32_t value) { return (int32_t)byteswap((uint32_t)value); }
+
+inline uint64_t byteswap(uint64_t value) { return (value << 56) | ((value & 0x00FF000000000000ULL) >> 40) | ((value & 0x0000FF0000000000ULL) >> 24) | ((value & 0x000000FF00000000ULL) >> 8) | ((value & 0x00000000FF000000ULL) << 8) | ((value & 0x0000000000FF0000ULL) << 24) | ((value & 0x000000000000FF00ULL) << 40) | (value >> 56); }
+
+inline int64_t byteswap(int64_t value) { return (int64_t)byteswap((uint64_t)value); }
+
+inline float byteswap(float value) {
+    uint32_t temp;
+    memcpy(&temp, &value, sizeof(value));
+    temp = byteswap(temp);
+    memcpy(&value, &temp, sizeof(value));
+    return value;
+}
+
+inline double byteswap(double value) {
+    uint64_t temp;
+    memcpy(&temp, &value, sizeof(value));
+    temp = byteswap(temp);
+    memcpy(&value, &temp, sizeof(value));
+    return value;
+}
+
+// Swaps bytes if the host order does not match the network order (Explicit Overloads)
+inline uint16_t byteswap_if_needed(uint16_t value) {
+    if (ONYX_HOST_ORDER != ONYX_NETWORK_ORDER) {
+        return byteswap(value);
+    }
+    return value;
+}
+
+inline int16_t byteswap_if_needed(int16_t value) {
+    if (ONYX_HOST_ORDER != ONYX_NETWORK_ORDER) {
+        return byteswap(value);
+    }
+    return value;
+}
+
+inline uint32_t byteswap_if_needed(uint32_t value) {
+    if (ONYX_HOST_ORDER != ONYX_NETWORK_ORDER) {
+        return byteswap(value);
+    }
+    return value;
+}
+
+inline int32_t byteswap_if_needed(int32_t value) {
+    if (ONYX_HOST_ORDER != ONYX_NETWORK_ORDER) {
+        return byteswap(value);
+    }
+    return value;
+}
+
+inline uint64_t byteswap_if_needed(uint64_t value) {
+    if (ONYX_HOST_ORDER != ONYX_NETWORK_ORDER) {
+        return byteswap(value);
+    }
+    return value;
+}
+
+inline int64_t byteswap_if_needed(int64_t value) {
+    if (ONYX_HOST_ORDER != ONYX_NETWORK_ORDER) {
+        return byteswap(value);
+    }
+    return value;
+}
+
+inline float byteswap_if_needed(float value) {
+    if (ONYX_HOST_ORDER != ONYX_NETWORK_ORDER) {
+        return byteswap(value);
+    }
+    return value;
+}
+
+inline double byteswap_if_needed(double value) {
+    if (ONYX_HOST_ORDER != ONYX_NETWORK_ORDER) {
+        return byteswap(value);
+    }
+    return value;
+}
+
+} // namespace utils
+#endif // ONYX_UTILS_ENDIANNESS_BIG_
+
+class Flags;
+class Reading;
+
+class Flags {
+private:
+    uint8_t __raw_ready[2];
+
+public:
+    static const size_t kSizeOf = 2;
+    using Buffer = uint8_t[kSizeOf];
+
+    /// Accessor for ready
+    inline const bool ready() const {
+        auto raw_value = reinterpret_cast<const uint16_t*>(&__raw_ready[0]);
+        return static_cast<const bool>(((*raw_value >> 0) & 0x1));
+    }
+
+    /// Mutator for ready
+    inline void ready(bool value) {
+        auto raw_container = reinterpret_cast<uint16_t*>(&__raw_ready[0]);
+        *raw_container &= ~(((uint16_t)0x1) << 0);
+        *raw_container |= ((((uint16_t)value) & 0x1) << 0);
+    }
+
+    /// Accessor for level
+    inline const uint8_t level() const {
+        auto raw_value = reinterpret_cast<const uint16_t*>(&__raw_ready[0]);
+        return static_cast<const uint8_t>(((*raw_value >> 1) & 0x7));
+    }
+
+    /// Mutator for level
+    inline void level(uint8_t value) {
+        auto raw_container = reinterpret_cast<uint16_t*>(&__raw_ready[0]);
+        *raw_container &= ~(((uint16_t)0x7) << 1);
+        *raw_container |= ((((uint16_t)value) & 0x7) << 1);
+    }
+
+    /// Accessor for code
+    inline const uint16_t code() const {
+        auto raw_value = reinterpret_cast<const uint16_t*>(&__raw_ready[0]);
+        return static_cast<const uint16_t>(((*raw_value >> 4) & 0xFFF));
+    }
+
+    /// Mutator for code
+    inline void code(uint16_t value) {
+        auto raw_container = reinterpret_cast<uint16_t*>(&__raw_ready[0]);
+        *raw_container &= ~(((uint16_t)0xFFF) << 4);
+        *raw_container |= ((((uint16_t)value) & 0xFFF) << 4);
+    }
+
+    /// Deserializes the network-endian buffer to Flags in-place by casting
+    /// and applying endianness correction to match the host-endianness.
+    /// NOTE: This mutates the input buffer to host-endianness.
+    /// Returns a pointer to the object within the buffer.
+    static Flags* Deserialize(Buffer& buffer);
+
+    /// Serializes the host-endian object into the provided buffer,
+    /// applying endianness correction to match the network-endianness.
+    void Serialize(Buffer& buffer) const;
+
+private:
+    /// Fails to compile if Flags does not match its Onyx layout.
+    static inline void __check_layout() {
+        static_assert(sizeof(Flags) == kSizeOf, "Flags does not match its Onyx size");
+    }
+};
+
+class Reading {
+private:
+    uint32_t __raw_id;
+    Flags __raw_flags;
+    uint8_t __raw_wide[5];
+
+public:
+    static const size_t kSizeOf = 11;
+    using Buffer = uint8_t[kSizeOf];
+
+    /// Byte offsets of the fields within the buffer.
+    static const size_t kIdOffset = 0;
+    static const size_t kFlagsOffset = 4;
+
+    /// Accessor for id
+    inline const uint32_t id() const { return __raw_id; }
+
+    /// Mutator for id
+    inline void id(const uint32_t value) { __raw_id = value; }
+
+    /// Accessor for flags
+    inline Flags& flags() { return __raw_flags; }
+
+    /// Mutator for flags
+    inline void flags(const Flags value) { __raw_flags = value; }
+
+    /// Accessor for wide
+    inline const uint32_t wide() const {
+        uint64_t raw_value = 0;
+        for (size_t i = 0; i < 5; ++i) {
+            raw_value |= static_cast<uint64_t>(__raw_wide[i]) << (8 * i);
+        }
+        return static_cast<const uint32_t>(((raw_value >> 0) & 0xFFFFF));
+    }
+
+    /// Mutator for wide
+    inline void wide(uint32_t value) {
+        uint64_t raw_value = 0;
+        for (size_t i = 0; i < 5; ++i) {
+            raw_value |= static_cast<uint64_t>(__raw_wide[i]) << (8 * i);
+        }
+        raw_value &= ~(((uint64_t)0xFFFFF) << 0);
+        raw_value |= ((((uint64_t)value) & 0xFFFFF) << 0);
+        for (size_t i = 0; i < 5; ++i) {
+            __raw_wide[i] = static_cast<uint8_t>(raw_value >> (8 * i));
+        }
+    }
+
+    /// Accessor for small
+    inline const uint16_t small() const {
+        uint64_t raw_value = 0;
+        for (size_t i = 0; i < 5; ++i) {
+            raw_value |= static_cast<uint64_t>(__raw_wide[i]) << (8 * i);
+        }
+        return static_cast<const uint16_t>(((raw_value >> 20) & 0xFFF));
+    }
+
+    /// Mutator for small
+    inline void small(uint16_t value) {
+        uint64_t raw_value = 0;
+        for (size_t i = 0; i < 5; ++i) {
+            raw_value |= static_cast<uint64_t>(__raw_wide[i]) << (8 * i);
+        }
+        raw_value &= ~(((uint64_t)0xFFF) << 20);
+        raw_value |= ((((uint64_t)value) & 0xFFF) << 20);
+        for (size_t i = 0; i < 5; ++i) {
+            __raw_wide[i] = static_cast<uint8_t>(raw_value >> (8 * i));
+        }
+    }
+
+    /// Accessor for last
+    inline const bool last() const {
+        uint64_t raw_value = 0;
+        for (size_t i = 0; i < 5; ++i) {
+            raw_value |= static_cast<uint64_t>(__raw_wide[i]) << (8 * i);
+        }
+        return static_cast<const bool>(((raw_value >> 32) & 0x1));
+    }
+
+    /// Mutator for last
+    inline void last(bool value) {
+        uint64_t raw_value = 0;
+        for (size_t i = 0; i < 5; ++i) {
+            raw_value |= static_cast<uint64_t>(__raw_wide[i]) << (8 * i);
+        }
+        raw_value &= ~(((uint64_t)0x1) << 32);
+        raw_value |= ((((uint64_t)value) & 0x1) << 32);
+        for (size_t i = 0; i < 5; ++i) {
+            __raw_wide[i] = static_cast<uint8_t>(raw_value >> (8 * i));
+        }
+    }
+
+    /// Deserializes the network-endian buffer to Reading in-place by casting
+    /// and applying endianness correction to match the host-endianness.
+    /// NOTE: This mutates the input buffer to host-endianness.
+    /// Returns a pointer to the object within the buffer.
+    static Reading* Deserialize(Buffer& buffer);
+
+    /// Serializes the host-endian object into the provided buffer,
+    /// applying endianness correction to match the network-endianness.
+    void Serialize(Buffer& buffer) const;
+
+private:
+    /// Fails to compile if Reading does not match its Onyx layout.
+    static inline void __check_layout() {
+        static_assert(sizeof(Reading) == kSizeOf, "Reading does not match its Onyx size");
+        static_assert(offsetof(Reading, __raw_id) == kIdOffset, "Reading::id does not match its Onyx offset");
+        static_assert(offsetof(Reading, __raw_flags) == kFlagsOffset, "Reading::flags does not match its Onyx offset");
+    }
+};
+
+
+inline Flags* Flags::Deserialize(Buffer& buffer) {
+    // Overlay the class structure onto the buffer memory. This is the zero-copy step.
+    Flags* result = (Flags*)buffer;
+
+    // SWAP: Bit-field container ready
+    *(uint16_t*)result->__raw_ready = utils::byteswap_if_needed(*(uint16_t*)result->__raw_ready);
+
+    return result;
+}
+
+inline void Flags::Serialize(Buffer& buffer) const {
+    // Copy the host-endian object into the buffer first.
+    memcpy(buffer, this, kSizeOf);
+    Flags* wire_format_data = (Flags*)buffer;
+
+    // SWAP: Bit-field container ready
+    *(uint16_t*)wire_format_data->__raw_ready = utils::byteswap_if_needed(*(uint16_t*)wire_format_data->__raw_ready);
+}
+
+inline Reading* Reading::Deserialize(Buffer& buffer) {
+    // Overlay the class structure onto the buffer memory. This is the zero-copy step.
+    Reading* result = (Reading*)buffer;
+
+    // SWAP: Primitive field id
+    result->__raw_id = utils::byteswap_if_needed(result->__raw_id);
+    Flags::Deserialize(*(Flags::Buffer*) &result->__raw_flags);
+    // SWAP: Bit-field container wide
+    if (ONYX_NETWORK_ORDER == ONYX_BIG_ENDIAN) {
+        for (size_t i = 0; i < 2; ++i) {
+            uint8_t byte = result->__raw_wide[i];
+            result->__raw_wide[i] = result->__raw_wide[4 - i];
+            result->__raw_wide[4 - i] = byte;
+        }
+    }
+
+    return result;
+}
+
+inline void Reading::Serialize(Buffer& buffer) const {
+    // Copy the host-endian object into the buffer first.
+    memcpy(buffer, this, kSizeOf);
+    Reading* wire_format_data = (Reading*)buffer;
+
+    // SWAP: Primitive field id
+    wire_format_data->__raw_id = utils::byteswap_if_needed(wire_format_data->__raw_id);
+    wire_format_data->__raw_flags.Serialize(*(Flags::Buffer*) &wire_format_data->__raw_flags);
+    // SWAP: Bit-field container wide
+    if (ONYX_NETWORK_ORDER == ONYX_BIG_ENDIAN) {
+        for (size_t i = 0; i < 2; ++i) {
+            uint8_t byte = wire_format_data->__raw_wide[i];
+            wire_format_data->__raw_wide[i] = wire_format_data->__raw_wide[4 - i];
+            wire_format_data->__raw_wide[4 - i] = byte;
+        }
+    }
+}
+
+} // namespace onyx
+
+#pragma pack(pop)
+#endif // ONYX_BITFIELDS_H_
@@ -0,0 +1,155 @@
+// Automatically generated by Onyx IDL compiler. Do not edit.
+package onyx
+
+import java.nio.ByteBuffer
+import java.nio.ByteOrder
+
+/** The byte order of every field in this file. */
+private val BYTE_ORDER: ByteOrder = ByteOrder.BIG_ENDIAN
+
+/** Returns the [size] bytes of the buffer from [offset] as a buffer of their own. */
+private fun ByteBuffer.view(offset: Int, size: Int): ByteBuffer =
+    (duplicate().clear().position(offset).limit(offset + size) as ByteBuffer).slice()
+
+/** Reads the [size] bytes from [offset] as an unsigned integer in [BYTE_ORDER]. */
+private fun ByteBuffer.readBits(offset: Int, size: Int): Long {
+    var raw = 0L
+    for (i in 0 until size) {
+        val index = if (BYTE_ORDER == ByteOrder.BIG_ENDIAN) i else size - 1 - i
+        raw = (raw shl 8) or (get(offset + index).toLong() and 0xFFL)
+    }
+    return raw
+}
+
+/** Writes the low [size] bytes of [raw] from [offset] in [BYTE_ORDER]. */
+private fun ByteBuffer.writeBits(offset: Int, size: Int, raw: Long) {
+    for (i in 0 until size) {
+        val shift = if (BYTE_ORDER == ByteOrder.BIG_ENDIAN) 8 * (size - 1 - i) else 8 * i
+        put(offset + i, (raw ushr shift).toByte())
+    }
+}
+
+/** Copies the [size] bytes from [offset]. */
+private fun ByteBuffer.readBytes(offset: Int, size: Int): ByteArray =
+    ByteArray(size).also { view(offset, size).get(it) }
+
+/** Writes [value], which must hold exactly [size] bytes, from [offset]. */
+private fun ByteBuffer.writeBytes(offset: Int, size: Int, value: ByteArray) {
+    require(value.size == size) { "expected $size bytes, got ${value.size}" }
+    view(offset, size).put(value)
+}
+
+/** Returns [index] if it is below [count], and throws otherwise. */
+private fun checkIndex(index: Int, count: Int): Int {
+    if (index !in 0 until count) {
+        throw IndexOutOfBoundsException("index $index is out of bounds for $count elements")
+    }
+    return index
+}
+
+/** Flags, viewed in [buffer] from its index 0. */
+@JvmInline
+value class Flags(val buffer: ByteBuffer) {
+    /** Accessor for ready. */
+    var ready: Boolean
+        get() = (buffer.readBits(READY_OFFSET, 2) and 0x1L) != 0L
+        set(value) {
+            buffer.writeBits(READY_OFFSET, 2, (buffer.readBits(READY_OFFSET, 2) and 0x1L.inv()) or ((if (value) 1L else 0L) and 0x1L))
+        }
+
+    /** Accessor for level. */
+    var level: UByte
+        get() = ((buffer.readBits(LEVEL_OFFSET, 2) ushr 1) and 0x7L).toUByte()
+        set(value) {
+            buffer.writeBits(LEVEL_OFFSET, 2, (buffer.readBits(LEVEL_OFFSET, 2) and (0x7L shl 1).inv()) or ((value.toLong() and 0x7L) shl 1))
+        }
+
+    /** Accessor for code. */
+    var code: UShort
+        get() = ((buffer.readBits(CODE_OFFSET, 2) ushr 4) and 0xFFFL).toUShort()
+        set(value) {
+            buffer.writeBits(CODE_OFFSET, 2, (buffer.readBits(CODE_OFFSET, 2) and (0xFFFL shl 4).inv()) or ((value.toLong() and 0xFFFL) shl 4))
+        }
+
+    companion object {
+        /** The size of Flags in bytes. */
+        const val SIZE_BYTES: Int = 2
+
+        /** The byte offset of the bit-field container holding ready. */
+        const val READY_OFFSET: Int = 0
+
+        /** The byte offset of the bit-field container holding level. */
+        const val LEVEL_OFFSET: Int = 0
+
+        /** The byte offset of the bit-field container holding code. */
+        const val CODE_OFFSET: Int = 0
+
+        /** Returns a view of Flags from the position of [buffer]. */
+        fun wrap(buffer: ByteBuffer): Flags = Flags(buffer.view(buffer.position(), SIZE_BYTES))
+
+        /** Returns a view of Flags in a new, zeroed buffer. */
+        fun allocate(): Flags = Flags(ByteBuffer.allocate(SIZE_BYTES))
+    }
+}
+
+/** Reading, viewed in [buffer] from its index 0. */
+@JvmInline
+value class Reading(val buffer: ByteBuffer) {
+    /** Accessor for id. */
+    var id: UInt
+        get() = buffer.readBits(ID_OFFSET, 4).toUInt()
+        set(value) {
+            buffer.writeBits(ID_OFFSET, 4, value.toLong())
+        }
+
+    /** Accessor for flags. */
+    val flags: Flags
+        get() = Flags(buffer.view(FLAGS_OFFSET, Flags.SIZE_BYTES))
+
+    /** Accessor for wide. */
+    var wide: UInt
+        get() = (buffer.readBits(WIDE_OFFSET, 5) and 0xFFFFFL).toUInt()
+        set(value) {
+            buffer.writeBits(WIDE_OFFSET, 5, (buffer.readBits(WIDE_OFFSET, 5) and 0xFFFFFL.inv()) or (value.toLong() and 0xFFFFFL))
+        }
+
+    /** Accessor for small. */
+    var small: UShort
+        get() = ((buffer.readBits(SMALL_OFFSET, 5) ushr 20) and 0xFFFL).toUShort()
+        set(value) {
+            buffer.writeBits(SMALL_OFFSET, 5, (buffer.readBits(SMALL_OFFSET, 5) and (0xFFFL shl 20).inv()) or ((value.toLong() and 0xFFFL) shl 20))
+        }
+
+    /** Accessor for last. */
+    var last: Boolean
+        get() = ((buffer.readBits(LAST_OFFSET, 5) ushr 32) and 0x1L) != 0L
+        set(value) {
+            buffer.writeBits(LAST_OFFSET, 5, (buffer.readBits(LAST_OFFSET, 5) and (0x1L shl 32).inv()) or (((if (value) 1L else 0L) and 0x1L) shl 32))
+        }
+
+    companion object {
+        /** The size of Reading in bytes. */
+        const val SIZE_BYTES: Int = 11
+
+        /** The byte offset of id. */
+        const val ID_OFFSET: Int = 0
+
+        /** The byte offset of flags. */
+        const val FLAGS_OFFSET: Int = 4
+
+        /** The byte offset of the bit-field container holding wide. */
+        const val WIDE_OFFSET: Int = 6
+
+        /** The byte offset of the bit-field container holding small. */
+        const val SMALL_OFFSET: Int = 6
+
+        /** The byte offset of the bit-field container holding last. */
+        const val LAST_OFFSET: Int = 6
+
+        /** Returns a view of Reading from the position of [buffer]. */
+        fun wrap(buffer: ByteBuffer): Reading = Reading(buffer.view(buffer.position(), SIZE_BYTES))
+
+        /** Returns a view of Reading in a new, zeroed buffer. */
+        fun allocate(): Reading = Reading(ByteBuffer.allocate(SIZE_BYTES))
+    }
+}
@@ -0,0 +1,38 @@
+% Automatically generated by Onyx IDL compiler. Do not edit.
+function bitfields()
+%BITFIELDS Defines the Simulink enums and bus objects of the schema.
+%   Run bitfields before loading a model that uses them. Each bus object is
+%   assigned in the base workspace, named after its struct or message.
+
+% Flags: 2 bytes.
+elements = Simulink.BusElement.empty;
+elements(end + 1) = element('ready', 'boolean', 1, 'byte 0, bit 0');
+elements(end + 1) = element('level', 'fixdt(0,3,0)', 1, 'byte 0, bits 1..3');
+elements(end + 1) = element('code', 'fixdt(0,12,0)', 1, 'byte 0, bits 4..15');
+bus = Simulink.Bus;
+bus.Description = 'Flags: 2 bytes.';
+bus.Elements = elements;
+assignin('base', 'Flags', bus);
+
+% Reading: 11 bytes.
+elements = Simulink.BusElement.empty;
+elements(end + 1) = element('id', 'uint32', 1, 'byte 0');
+elements(end + 1) = element('flags', 'Bus: Flags', 1, 'byte 4');
+elements(end + 1) = element('wide', 'fixdt(0,20,0)', 1, 'byte 6, bits 0..19');
+elements(end + 1) = element('small', 'fixdt(0,12,0)', 1, 'byte 6, bits 20..31');
+elements(end + 1) = element('last', 'boolean', 1, 'byte 6, bit 32');
+bus = Simulink.Bus;
+bus.Description = 'Reading: 11 bytes.';
+bus.Elements = elements;
+assignin('base', 'Reading', bus);
+
+end
+
+function e = element(name, type, dimensions, description)
+% Returns a bus element.
+e = Simulink.BusElement;
+e.Name = name;
+e.DataType = type;
+e.Dimensions = dimensions;
+e.Description = description;
+end
@@ -0,0 +1,297 @@
+// Automatically generated by Onyx IDL compiler. Do not edit.
+
+use std::convert::TryInto;
+
+
+/// Size of Flags in bytes.
+pub(crate) const FLAGS_SIZE: usize = 2;
+/// Buffer type alias for Flags.
+pub type FlagsBuffer = [u8; FLAGS_SIZE];
+
+/// Read-only view of Flags.
+#[derive(Debug, Clone, Copy)]
+pub struct FlagsView<'a> {
+    data: &'a FlagsBuffer,
+}
+
+/// Read-write view of Flags.
+#[derive(Debug)]
+pub struct FlagsMutView<'a> {
+    data: &'a mut FlagsBuffer,
+}
+
+impl<'a> FlagsView<'a> {
+    /// Size of Flags in bytes.
+    pub const SIZE_BYTES: usize = FLAGS_SIZE;
+    /// Byte offset of the bit-field container holding ready.
+    pub const FIELD_OFFSET_READY: usize = 0;
+    /// Byte offset of the bit-field container holding level.
+    pub const FIELD_OFFSET_LEVEL: usize = 0;
+    /// Byte offset of the bit-field container holding code.
+    pub const FIELD_OFFSET_CODE: usize = 0;
+
+    /// Creates a new FlagsView from a FlagsBuffer.
+    pub fn new(data: &'a FlagsBuffer) -> Self {
+        Self { data }
+    }
+
+    /// Accessor for ready.
+    pub fn ready(&self) -> bool {
+        let bytes = self.data[0..2].try_into().unwrap();
+        let raw = u16::from_be_bytes(bytes);
+        let val = (raw >> 0) & 0x1;
+        val != 0
+    }
+
+    /// Accessor for level.
+    pub fn level(&self) -> u8 {
+        let bytes = self.data[0..2].try_into().unwrap();
+        let raw = u16::from_be_bytes(bytes);
+        let val = (raw >> 1) & 0x7;
+        val as u8
+    }
+
+    /// Accessor for code.
+    pub fn code(&self) -> u16 {
+        let bytes = self.data[0..2].try_into().unwrap();
+        let raw = u16::from_be_bytes(bytes);
+        let val = (raw >> 4) & 0xFFF;
+        val as u16
+    }
+}
+
+// Fails to compile if Flags does not match its Onyx layout.
+const _: () = assert!(FlagsView::FIELD_OFFSET_READY + 2 == FlagsView::SIZE_BYTES, "Flags does not match its Onyx size");
+
+impl<'a> FlagsMutView<'a> {
+    /// Creates a new FlagsMutView from a FlagsBuffer.
+    pub fn new(data: &'a mut FlagsBuffer) -> Self {
+        Self { data }
+    }
+
+    /// Accessor for ready.
+    pub fn ready(&self) -> bool {
+        let bytes = self.data[0..2].try_into().unwrap();
+        let raw = u16::from_be_bytes(bytes);
+        let val = (raw >> 0) & 0x1;
+        val != 0
+    }
+
+    /// Mutator for ready.
+    pub fn set_ready(&mut self, value: bool) {
+        let bytes = self.data[0..2].try_into().unwrap();
+        let mut raw = u16::from_be_bytes(bytes);
+        raw &= !((0x1 as u16) << 0);
+        raw |= ((value as u16 & 0x1) as u16) << 0;
+        let bytes = raw.to_be_bytes();
+        self.data[0..2].copy_from_slice(&bytes);
+    }
+
+    /// Accessor for level.
+    pub fn level(&self) -> u8 {
+        let bytes = self.data[0..2].try_into().unwrap();
+        let raw = u16::from_be_bytes(bytes);
+        let val = (raw >> 1) & 0x7;
+        val as u8
+    }
+
+    /// Mutator for level.
+    pub fn set_level(&mut self, value: u8) {
+        let bytes = self.data[0..2].try_into().unwrap();
+        let mut raw = u16::from_be_bytes(bytes);
+        raw &= !((0x7 as u16) << 1);
+        raw |= ((value as u16 & 0x7) as u16) << 1;
+        let bytes = raw.to_be_bytes();
+        self.data[0..2].copy_from_slice(&bytes);
+    }
+
+    /// Accessor for code.
+    pub fn code(&self) -> u16 {
+        let bytes = self.data[0..2].try_into().unwrap();
+        let raw = u16::from_be_bytes(bytes);
+        let val = (raw >> 4) & 0xFFF;
+        val as u16
+    }
+
+    /// Mutator for code.
+    pub fn set_code(&mut self, value: u16) {
+        let bytes = self.data[0..2].try_into().unwrap();
+        let mut raw = u16::from_be_bytes(bytes);
+        raw &= !((0xFFF as u16) << 4);
+        raw |= ((value as u16 & 0xFFF) as u16) << 4;
+        let bytes = raw.to_be_bytes();
+        self.data[0..2].copy_from_slice(&bytes);
+    }
+}
+
+/// Size of Reading in bytes.
+pub(crate) const READING_SIZE: usize = 11;
+/// Buffer type alias for Reading.
+pub type ReadingBuffer = [u8; READING_SIZE];
+
+/// Read-only view of Reading.
+#[derive(Debug, Clone, Copy)]
+pub struct ReadingView<'a> {
+    data: &'a ReadingBuffer,
+}
+
+/// Read-write view of Reading.
+#[derive(Debug)]
+pub struct ReadingMutView<'a> {
+    data: &'a mut ReadingBuffer,
+}
+
+impl<'a> ReadingView<'a> {
+    /// Size of Reading in bytes.
+    pub const SIZE_BYTES: usize = READING_SIZE;
+    /// Byte offset of id.
+    pub const FIELD_OFFSET_ID: usize = 0;
+    /// Byte offset of flags.
+    pub const FIELD_OFFSET_FLAGS: usize = 4;
+    /// Byte offset of the bit-field container holding wide.
+    pub const FIELD_OFFSET_WIDE: usize = 6;
+    /// Byte offset of the bit-field container holding small.
+    pub const FIELD_OFFSET_SMALL: usize = 6;
+    /// Byte offset of the bit-field container holding last.
+    pub const FIELD_OFFSET_LAST: usize = 6;
+
+    /// Creates a new ReadingView from a ReadingBuffer.
+    pub fn new(data: &'a ReadingBuffer) -> Self {
+        Self { data }
+    }
+
+    /// Accessor for id.
+    pub fn id(&self) -> u32 {
+        let bytes = self.data[0..4].try_into().unwrap();
+        u32::from_be_bytes(bytes)
+    }
+
+    /// Accessor for flags.
+    pub fn flags(&self) -> FlagsView {
+        let slice = &self.data[4..6];
+        FlagsView::new(slice.try_into().unwrap())
+    }
+
+    /// Accessor for wide.
+    pub fn wide(&self) -> u32 {
+        let mut bytes = [0u8; 8];
+        bytes[3..3 + 5].copy_from_slice(&self.data[6..11]);
+        let raw = u64::from_be_bytes(bytes);
+        let val = (raw >> 0) & 0xFFFFF;
+        val as u32
+    }
+
+    /// Accessor for small.
+    pub fn small(&self) -> u16 {
+        let mut bytes = [0u8; 8];
+        bytes[3..3 + 5].copy_from_slice(&self.data[6..11]);
+        let raw = u64::from_be_bytes(bytes);
+        let val = (raw >> 20) & 0xFFF;
+        val as u16
+    }
+
+    /// Accessor for last.
+    pub fn last(&self) -> bool {
+        let mut bytes = [0u8; 8];
+        bytes[3..3 + 5].copy_from_slice(&self.data[6..11]);
+        let raw = u64::from_be_bytes(bytes);
+        let val = (raw >> 32) & 0x1;
+        val != 0
+    }
+}
+
+// Fails to compile if Reading does not match its Onyx layout.
+const _: () = assert!(ReadingView::FIELD_OFFSET_FLAGS == ReadingView::FIELD_OFFSET_ID + std::mem::size_of::<u32>(), "Reading.flags does not match its Onyx offset");
+const _: () = assert!(ReadingView::FIELD_OFFSET_WIDE == ReadingView::FIELD_OFFSET_FLAGS + FLAGS_SIZE, "Reading.wide does not match its Onyx offset");
+const _: () = assert!(ReadingView::FIELD_OFFSET_WIDE + 5 == ReadingView::SIZE_BYTES, "Reading does not match its Onyx size");
+
+impl<'a> ReadingMutView<'a> {
+    /// Creates a new ReadingMutView from a ReadingBuffer.
+    pub fn new(data: &'a mut ReadingBuffer) -> Self {
+        Self { data }
+    }
+
+    /// Accessor for id.
+    pub fn id(&self) -> u32 {
+        let bytes = self.data[0..4].try_into().unwrap();
+        u32::from_be_bytes(bytes)
+    }
+
+    /// Mutator for id.
+    pub fn set_id(&mut self, value: u32) {
+        let bytes = value.to_be_bytes();
+        self.data[0..4].copy_from_slice(&bytes);
+    }
+
+    /// Accessor for flags.
+    pub fn flags(&self) -> FlagsView {
+        let slice = &self.data[4..6];
+        FlagsView::new(slice.try_into().unwrap())
+    }
+
+    /// Mutable accessor for flags.
+    pub fn flags_mut(&mut self) -> FlagsMutView {
+        let slice = &mut self.data[4..6];
+        FlagsMutView::new(slice.try_into().unwrap())
+    }
+
+    /// Accessor for wide.
+    pub fn wide(&self) -> u32 {
+        let mut bytes = [0u8; 8];
+        bytes[3..3 + 5].copy_from_slice(&self.data[6..11]);
+        let raw = u64::from_be_bytes(bytes);
+        let val = (raw >> 0) & 0xFFFFF;
+        val as u32
+    }
+
+    /// Mutator for wide.
+    pub fn set_wide(&mut self, value: u32) {
+        let mut bytes = [0u8; 8];
+        bytes[3..3 + 5].copy_from_slice(&self.data[6..11]);
+        let mut raw = u64::from_be_bytes(bytes);
+        raw &= !((0xFFFFF as u64) << 0);
+        raw |= ((value as u64 & 0xFFFFF) as u64) << 0;
+        let bytes = raw.to_be_bytes();
+        self.data[6..11].copy_from_slice(&bytes[3..3 + 5]);
+    }
+
+    /// Accessor for small.
+    pub fn small(&self) -> u16 {
+        let mut bytes = [0u8; 8];
+        bytes[3..3 + 5].copy_from_slice(&self.data[6..11]);
+        let raw = u64::from_be_bytes(bytes);
+        let val = (raw >> 20) & 0xFFF;
+        val as u16
+    }
+
+    /// Mutator for small.
+    pub fn set_small(&mut self, value: u16) {
+        let mut bytes = [0u8; 8];
+        bytes[3..3 + 5].copy_from_slice(&self.data[6..11]);
+        let mut raw = u64::from_be_bytes(bytes);
+        raw &= !((0xFFF as u64) << 20);
+        raw |= ((value as u64 & 0xFFF) as u64) << 20;
+        let bytes = raw.to_be_bytes();
+        self.data[6..11].copy_from_slice(&bytes[3..3 + 5]);
+    }
+
+    /// Accessor for last.
+    pub fn last(&self) -> bool {
+        let mut bytes = [0u8; 8];
+        bytes[3..3 + 5].copy_from_slice(&self.data[6..11]);
+        let raw = u64::from_be_bytes(bytes);
+        let val = (raw >> 32) & 0x1;
+        val != 0
+    }
+
+    /// Mutator for last.
+    pub fn set_last(&mut self, value: bool) {
+        let mut bytes = [0u8; 8];
+        bytes[3..3 + 5].copy_from_slice(&self.data[6..11]);
+        let mut raw = u64::from_be_bytes(bytes);
+        raw &= !((0x1 as u64) << 32);
+        raw |= ((value as u64 & 0x1) as u64) << 32;
+        let bytes = raw.to_be_bytes();
+        self.data[6..11].copy_from_slice(&bytes[3..3 + 5]);
+    }
+}
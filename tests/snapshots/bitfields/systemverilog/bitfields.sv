@@ -0,0 +1,50 @@
+// Automatically generated by Onyx IDL compiler. Do not edit.
+`ifndef ONYX_BITFIELDS_PKG_SV
+`define ONYX_BITFIELDS_PKG_SV
+
+package bitfields_pkg;
+
+  // Flags: 2 bytes.
+  localparam int Flags_SIZE_BYTES = 2;
+  localparam int Flags_ready_OFFSET = 0;
+  localparam int Flags_ready_BIT_OFFSET = 0;
+  localparam int Flags_ready_WIDTH = 1;
+  localparam int Flags_level_OFFSET = 0;
+  localparam int Flags_level_BIT_OFFSET = 1;
+  localparam int Flags_level_WIDTH = 3;
+  localparam int Flags_code_OFFSET = 0;
+  localparam int Flags_code_BIT_OFFSET = 4;
+  localparam int Flags_code_WIDTH = 12;
+  typedef struct packed {
+    logic [11:0] code;
+    logic [2:0] level;
+    logic ready;
+  } Flags;
+
+  // Reading: 11 bytes.
+  localparam int Reading_SIZE_BYTES = 11;
+  localparam int Reading_id_OFFSET = 0;
+  localparam int Reading_id_WIDTH = 32;
+  localparam int Reading_flags_OFFSET = 4;
+  localparam int Reading_flags_WIDTH = 16;
+  localparam int Reading_wide_OFFSET = 6;
+  localparam int Reading_wide_BIT_OFFSET = 0;
+  localparam int Reading_wide_WIDTH = 20;
+  localparam int Reading_small_OFFSET = 6;
+  localparam int Reading_small_BIT_OFFSET = 20;
+  localparam int Reading_small_WIDTH = 12;
+  localparam int Reading_last_OFFSET = 6;
+  localparam int Reading_last_BIT_OFFSET = 32;
+  localparam int Reading_last_WIDTH = 1;
+  typedef struct packed {
+    logic [31:0] id;
+    Flags flags;
+    logic [6:0] _padding0;
+    logic last;
+    logic [11:0] small;
+    logic [19:0] wide;
+  } Reading;
+
+endpackage
+
+`endif // ONYX_BITFIELDS_PKG_SV
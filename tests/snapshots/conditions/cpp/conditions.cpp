@@ -0,0 +1,66 @@
+// Automatically generated by Onyx IDL compiler. Do not edit.
+#include "conditions.hpp"
+
+#include <string.h>
+
+namespace onyx {
+
+Body* Body::Deserialize(Buffer& buffer) {
+    // Overlay the class structure onto the buffer memory. This is the zero-copy step.
+    Body* result = (Body*)buffer;
+    bool known = true;
+
+    // SWAP: Primitive field len
+    result->__raw_len = utils::byteswap_if_needed(result->__raw_len);
+    // CHECK: Closed enum field kind
+    known = is_known(result->__raw_kind) && known;
+
+    return known ? result : nullptr;
+}
+
+void Body::Serialize(Buffer& buffer) const {
+    // Copy the host-endian object into the buffer first.
+    memcpy(buffer, this, kSizeOf);
+    Body* wire_format_data = (Body*)buffer;
+
+    // SWAP: Primitive field len
+    wire_format_data->__raw_len = utils::byteswap_if_needed(wire_format_data->__raw_len);
+}
+
+Packet* Packet::Deserialize(Buffer& buffer) {
+    // Overlay the class structure onto the buffer memory. This is the zero-copy step.
+    Packet* result = (Packet*)buffer;
+    bool known = true;
+
+    bool payload_known = Body::Deserialize(*(Body::Buffer*) &result->__raw_payload) != nullptr;
+    // INFO: Bit-field container acked (1 byte), no swap needed.
+    // INFO: Primitive field boost (1 byte), no swap needed.
+    // INFO: Primitive field count (1 byte), no swap needed.
+    // SWAP: Vector field items
+    // CHECK: Closed enum field kind
+    known = is_known(result->__raw_kind) && known;
+    // CHECK: Conditional field payload
+    known = (!result->has_payload() || payload_known) && known;
+    // CHECK: Closed enum field reason
+    known = (!result->has_reason() || is_known(result->__raw_reason)) && known;
+    // CHECK: Closed enum field items
+    for (size_t i = 0; i < (result->has_items() ? result->__raw_items.len() : 0); ++i) {
+        known = is_known(result->__raw_items.__items[i]) && known;
+    }
+
+    return known ? result : nullptr;
+}
+
+void Packet::Serialize(Buffer& buffer) const {
+    // Copy the host-endian object into the buffer first.
+    memcpy(buffer, this, kSizeOf);
+    Packet* wire_format_data = (Packet*)buffer;
+
+    wire_format_data->__raw_payload.Serialize(*(Body::Buffer*) &wire_format_data->__raw_payload);
+    // INFO: Bit-field container acked (1 byte), no swap needed.
+    // INFO: Primitive field boost (1 byte), no swap needed.
+    // INFO: Primitive field count (1 byte), no swap needed.
+    // SWAP: Vector field items
+}
+
+} // namespace onyx
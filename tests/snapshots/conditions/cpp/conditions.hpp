@@ -0,0 +1,377 @@
+// Automatically generated by Onyx IDL compiler. Do not edit.
+#ifndef ONYX_CONDITIONS_H_
+#define ONYX_CONDITIONS_H_
+
+#include <stddef.h>
+#include <stdint.h>
+#include <string.h>
+
+#pragma pack(push, 1)
+
+namespace onyx {
+#ifndef ONYX_UTILS_ENDIANNESS_LITTLE_
+#define ONYX_UTILS_ENDIANNESS_LITTLE_
+
+namespace utils {
+
+// Define network endianness (usually Big Endian).
+#define ONYX_BIG_ENDIAN 4321
+#define ONYX_LITTLE_ENDIAN 1234
+#define ONYX_NETWORK_ORDER ONYX_LITTLE_ENDIAN
+
+// Determine host endianness at compile time (using standard GCC/Clang macros)
+#ifdef __BYTE_ORDER__
+    #if __BYTE_ORDER__ == __ORDER_BIG_ENDIAN__
+        #define ONYX_HOST_ORDER ONYX_BIG_ENDIAN
+    #elif __BYTE_ORDER__ == __ORDER_LITTLE_ENDIAN__
+        #define ONYX_HOST_ORDER ONYX_LITTLE_ENDIAN
+    #else
+        #error "Unsupported host endianness"
+    #endif
+#elif defined(_WIN32) || defined(__LITTLE_ENDIAN__)
+    // Fallback for Windows (always little-endian) or other platforms
+    #define ONYX_HOST_ORDER ONYX_LITTLE_ENDIAN
+#else
+    #error "Could not determine host endianness"
+#endif
+
+// Helper function to swap bytes (Explicit Overloads)
+inline uint16_t byteswap(uint16_t value) { return ((value << 8) & 0xFF00) | ((value >> 8) & 0x00FF); }
+
+inline int16_t byteswap(int16_t value) { return (int16_t)byteswap((uint16_t)value); }
+
+inline uint32_t byteswap(uint32_t value) { return (value << 24) | ((value & 0x00FF0000) >> 8) | ((value & 0x0000FF00) << 8) | (value >> 24); }
+
+inline int32_t byteswap(int32_t value) { return (int32_t)byteswap((uint32_t)value); }
+
+inline uint64_t byteswap(uint64_t value) { return (value << 56) | ((value & 0x00FF000000000000ULL) >> 40) | ((value & 0x0000FF0000000000ULL) >> 24) | ((value & 0x000000FF00000000ULL) >> 8) | ((value & 0x00000000FF000000ULL) << 8) | ((value & 0x0000000000FF0000ULL) << 24) | ((value & 0x000000000000FF00ULL) << 40) | (value >> 56); }
+
+inline int64_t byteswap(int64_t value) { return (int64_t)byteswap((uint64_t)value); }
+
+inline float byteswap(float value) {
+    uint32_t temp;
+    memcpy(&temp, &value, sizeof(value));
+    temp = byteswap(temp);
+    memcpy(&value, &temp, sizeof(value));
+    return value;
+}
+
+inline double byteswap(double value) {
+    uint64_t temp;
+    memcpy(&temp, &value, sizeof(value));
+    temp = byteswap(temp);
+    memcpy(&value, &temp, sizeof(value));
+    return value;
+}
+
+// Swaps bytes if the host order does not match the network order (Explicit Overloads)
+inline uint16_t byteswap_if_needed(uint16_t value) {
+    if (ONYX_HOST_ORDER != ONYX_NETWORK_ORDER) {
+        return byteswap(value);
+    }
+    return value;
+}
+
+inline int16_t byteswap_if_needed(int16_t value) {
+    if (ONYX_HOST_ORDER != ONYX_NETWORK_ORDER) {
+        return byteswap(value);
+    }
+    return value;
+}
+
+inline uint32_t byteswap_if_needed(uint32_t value) {
+    if (ONYX_HOST_ORDER != ONYX_NETWORK_ORDER) {
+        return byteswap(value);
+    }
+    return value;
+}
+
+inline int32_t byteswap_if_needed(int32_t value) {
+    if (ONYX_HOST_ORDER != ONYX_NETWORK_ORDER) {
+        return byteswap(value);
+    }
+    return value;
+}
+
+inline uint64_t byteswap_if_needed(uint64_t value) {
+    if (ONYX_HOST_ORDER != ONYX_NETWORK_ORDER) {
+        return byteswap(value);
+    }
+    return value;
+}
+
+inline int64_t byteswap_if_needed(int64_t value) {
+    if (ONYX_HOST_ORDER != ONYX_NETWORK_ORDER) {
+        return byteswap(value);
+    }
+    return value;
+}
+
+inline float byteswap_if_needed(float value) {
+    if (ONYX_HOST_ORDER != ONYX_NETWORK_ORDER) {
+        return byteswap(value);
+    }
+    return value;
+}
+
+inline double byteswap_if_needed(double value) {
+    if (ONYX_HOST_ORDER != ONYX_NETWORK_ORDER) {
+        return byteswap(value);
+    }
+    return value;
+}
+
+} // namespace utils
+#endif // ONYX_UTILS_ENDIANNESS_LITTLE_
+
+#ifndef ONYX_UTILS_BOUNDED_VEC_
+#define ONYX_UTILS_BOUNDED_VEC_
+
+namespace utils {
+
+/// A bounded vector: a length followed by room for N elements, all of them inline.
+template <typename T, typename LenT, size_t N>
+class BoundedVec {
+public:
+    LenT __length;
+    T __items[N];
+
+    /// Returns the number of elements, clamped to the capacity.
+    inline size_t len() const { return __length < N ? __length : N; }
+    /// Returns the maximum number of elements.
+    static inline size_t capacity() { return N; }
+    /// Returns true if the vector has no elements.
+    inline bool empty() const { return len() == 0; }
+    /// Returns the element at `i`, which must be less than len().
+    inline const T& get(size_t i) const { return __items[i]; }
+    inline T& get(size_t i) { return __items[i]; }
+    /// Replaces the element at `i`, which must be less than len().
+    inline void set(size_t i, const T& value) { __items[i] = value; }
+    /// Appends an element. Returns false, leaving the vector unchanged, if it is full.
+    inline bool push(const T& value) {
+        size_t n = len();
+        if (n == N) return false;
+        __items[n] = value;
+        __length = static_cast<LenT>(n + 1);
+        return true;
+    }
+    /// Removes all elements.
+    inline void clear() { __length = 0; }
+
+    inline const T* begin() const { return __items; }
+    inline const T* end() const { return __items + len(); }
+    inline T* begin() { return __items; }
+    inline T* end() { return __items + len(); }
+};
+
+} // namespace utils
+
+#endif // ONYX_UTILS_BOUNDED_VEC_
+
+class Body;
+class Packet;
+
+enum class Kind : uint8_t {
+    Ping,
+    Data = 1,
+    Close = 2,
+};
+
+/// Returns true if `value` is declared by a variant of Kind.
+inline bool is_known(Kind value) {
+    return value == Kind::Ping || value == Kind::Data || value == Kind::Close;
+}
+
+enum class Level : int8_t {
+    Low = 1,
+    High = 2,
+};
+
+/// Returns true if `value` is declared by a variant of Level.
+inline bool is_known(Level value) {
+    return value == Level::Low || value == Level::High;
+}
+
+class Body {
+private:
+    Kind __raw_kind;
+    uint16_t __raw_len;
+
+public:
+    static const size_t kSizeOf = 3;
+    using Buffer = uint8_t[kSizeOf];
+
+    /// Byte offsets of the fields within the buffer.
+    static const size_t kKindOffset = 0;
+    static const size_t kLenOffset = 1;
+
+    /// Accessor for kind
+    inline const Kind kind() const { return __raw_kind; }
+
+    /// Mutator for kind
+    inline void kind(const Kind value) { __raw_kind = value; }
+
+    /// Accessor for len
+    inline const uint16_t len() const { return __raw_len; }
+
+    /// Mutator for len
+    inline void len(const uint16_t value) { __raw_len = value; }
+
+    /// Deserializes the network-endian buffer to Body in-place by casting
+    /// and applying endianness correction to match the host-endianness.
+    /// NOTE: This mutates the input buffer to host-endianness.
+    /// Returns a pointer to the object within the buffer.
+    /// Returns nullptr if a closed enum holds a value that no variant declares;
+    /// the buffer is converted either way.
+    static Body* Deserialize(Buffer& buffer);
+
+    /// Serializes the host-endian object into the provided buffer,
+    /// applying endianness correction to match the network-endianness.
+    void Serialize(Buffer& buffer) const;
+
+private:
+    /// Fails to compile if Body does not match its Onyx layout.
+    static inline void __check_layout() {
+        static_assert(sizeof(Body) == kSizeOf, "Body does not match its Onyx size");
+        static_assert(offsetof(Body, __raw_kind) == kKindOffset, "Body::kind does not match its Onyx offset");
+        static_assert(offsetof(Body, __raw_len) == kLenOffset, "Body::len does not match its Onyx offset");
+    }
+};
+
+class Packet {
+private:
+    Kind __raw_kind;
+    Body __raw_payload;
+    Kind __raw_reason;
+    uint8_t __raw_acked[1];
+    Level __raw_level;
+    uint8_t __raw_boost;
+    uint8_t __raw_count;
+    utils::BoundedVec<Kind, uint8_t, 2> __raw_items;
+
+public:
+    static const size_t kSizeOf = 12;
+    using Buffer = uint8_t[kSizeOf];
+
+    /// Byte offsets of the fields within the buffer.
+    static const size_t kKindOffset = 0;
+    static const size_t kPayloadOffset = 1;
+    static const size_t kReasonOffset = 4;
+    static const size_t kLevelOffset = 6;
+    static const size_t kBoostOffset = 7;
+    static const size_t kCountOffset = 8;
+    static const size_t kItemsOffset = 9;
+
+    /// Accessor for kind
+    inline const Kind kind() const { return __raw_kind; }
+
+    /// Mutator for kind
+    inline void kind(const Kind value) { __raw_kind = value; }
+
+    /// Returns true if payload is present: `kind == Kind.Data`
+    inline bool has_payload() const { return static_cast<uint8_t>(kind()) == static_cast<uint8_t>(1ULL); }
+
+    /// Accessor for payload
+    inline Body& payload() { return __raw_payload; }
+
+    /// Mutator for payload
+    inline void payload(const Body value) { __raw_payload = value; }
+
+    /// Returns true if reason is present: `kind == Kind.Close`
+    inline bool has_reason() const { return static_cast<uint8_t>(kind()) == static_cast<uint8_t>(2ULL); }
+
+    /// Accessor for reason
+    inline const Kind reason() const { return __raw_reason; }
+
+    /// Mutator for reason
+    inline void reason(const Kind value) { __raw_reason = value; }
+
+    /// Returns true if seq is present: `acked != true`
+    inline bool has_seq() const { return !acked(); }
+
+    /// Accessor for acked
+    inline const bool acked() const {
+        auto raw_value = reinterpret_cast<const uint8_t*>(&__raw_acked[0]);
+        return static_cast<const bool>(((*raw_value >> 0) & 0x1));
+    }
+
+    /// Mutator for acked
+    inline void acked(bool value) {
+        auto raw_container = reinterpret_cast<uint8_t*>(&__raw_acked[0]);
+        *raw_container &= ~(((uint8_t)0x1) << 0);
+        *raw_container |= ((((uint8_t)value) & 0x1) << 0);
+    }
+
+    /// Accessor for seq
+    inline const uint8_t seq() const {
+        auto raw_value = reinterpret_cast<const uint8_t*>(&__raw_acked[0]);
+        return static_cast<const uint8_t>(((*raw_value >> 1) & 0x7F));
+    }
+
+    /// Mutator for seq
+    inline void seq(uint8_t value) {
+        auto raw_container = reinterpret_cast<uint8_t*>(&__raw_acked[0]);
+        *raw_container &= ~(((uint8_t)0x7F) << 1);
+        *raw_container |= ((((uint8_t)value) & 0x7F) << 1);
+    }
+
+    /// Accessor for level
+    inline const Level level() const { return __raw_level; }
+
+    /// Mutator for level
+    inline void level(const Level value) { __raw_level = value; }
+
+    /// Returns true if boost is present: `level == Level.High`
+    inline bool has_boost() const { return static_cast<int8_t>(level()) == static_cast<int8_t>(2ULL); }
+
+    /// Accessor for boost
+    inline const uint8_t boost() const { return __raw_boost; }
+
+    /// Mutator for boost
+    inline void boost(const uint8_t value) { __raw_boost = value; }
+
+    /// Accessor for count
+    inline const uint8_t count() const { return __raw_count; }
+
+    /// Mutator for count
+    inline void count(const uint8_t value) { __raw_count = value; }
+
+    /// Returns true if items is present: `count != 0`
+    inline bool has_items() const { return count() != static_cast<uint8_t>(0ULL); }
+
+    /// Accessor for items
+    inline utils::BoundedVec<Kind, uint8_t, 2>& items() { return __raw_items; }
+
+    /// Mutator for items
+    inline void items(const utils::BoundedVec<Kind, uint8_t, 2> value) { __raw_items = value; }
+
+    /// Deserializes the network-endian buffer to Packet in-place by casting
+    /// and applying endianness correction to match the host-endianness.
+    /// NOTE: This mutates the input buffer to host-endianness.
+    /// Returns a pointer to the object within the buffer.
+    /// Returns nullptr if a closed enum holds a value that no variant declares;
+    /// the buffer is converted either way.
+    static Packet* Deserialize(Buffer& buffer);
+
+    /// Serializes the host-endian object into the provided buffer,
+    /// applying endianness correction to match the network-endianness.
+    void Serialize(Buffer& buffer) const;
+
+private:
+    /// Fails to compile if Packet does not match its Onyx layout.
+    static inline void __check_layout() {
+        static_assert(sizeof(Packet) == kSizeOf, "Packet does not match its Onyx size");
+        static_assert(offsetof(Packet, __raw_kind) == kKindOffset, "Packet::kind does not match its Onyx offset");
+        static_assert(offsetof(Packet, __raw_payload) == kPayloadOffset, "Packet::payload does not match its Onyx offset");
+        static_assert(offsetof(Packet, __raw_reason) == kReasonOffset, "Packet::reason does not match its Onyx offset");
+        static_assert(offsetof(Packet, __raw_level) == kLevelOffset, "Packet::level does not match its Onyx offset");
+        static_assert(offsetof(Packet, __raw_boost) == kBoostOffset, "Packet::boost does not match its Onyx offset");
+        static_assert(offsetof(Packet, __raw_count) == kCountOffset, "Packet::count does not match its Onyx offset");
+        static_assert(offsetof(Packet, __raw_items) == kItemsOffset, "Packet::items does not match its Onyx offset");
+    }
+};
+
+} // namespace onyx
+
+#pragma pack(pop)
+#endif // ONYX_CONDITIONS_H_
@@ -0,0 +1,264 @@
+// Automatically generated by Onyx IDL compiler. Do not edit.
+package onyx
+
+import java.nio.ByteBuffer
+import java.nio.ByteOrder
+
+/** The byte order of every field in this file. */
+private val BYTE_ORDER: ByteOrder = ByteOrder.LITTLE_ENDIAN
+
+/** Returns the [size] bytes of the buffer from [offset] as a buffer of their own. */
+private fun ByteBuffer.view(offset: Int, size: Int): ByteBuffer =
+    (duplicate().clear().position(offset).limit(offset + size) as ByteBuffer).slice()
+
+/** Reads the [size] bytes from [offset] as an unsigned integer in [BYTE_ORDER]. */
+private fun ByteBuffer.readBits(offset: Int, size: Int): Long {
+    var raw = 0L
+    for (i in 0 until size) {
+        val index = if (BYTE_ORDER == ByteOrder.BIG_ENDIAN) i else size - 1 - i
+        raw = (raw shl 8) or (get(offset + index).toLong() and 0xFFL)
+    }
+    return raw
+}
+
+/** Writes the low [size] bytes of [raw] from [offset] in [BYTE_ORDER]. */
+private fun ByteBuffer.writeBits(offset: Int, size: Int, raw: Long) {
+    for (i in 0 until size) {
+        val shift = if (BYTE_ORDER == ByteOrder.BIG_ENDIAN) 8 * (size - 1 - i) else 8 * i
+        put(offset + i, (raw ushr shift).toByte())
+    }
+}
+
+/** Copies the [size] bytes from [offset]. */
+private fun ByteBuffer.readBytes(offset: Int, size: Int): ByteArray =
+    ByteArray(size).also { view(offset, size).get(it) }
+
+/** Writes [value], which must hold exactly [size] bytes, from [offset]. */
+private fun ByteBuffer.writeBytes(offset: Int, size: Int, value: ByteArray) {
+    require(value.size == size) { "expected $size bytes, got ${value.size}" }
+    view(offset, size).put(value)
+}
+
+/** Returns [index] if it is below [count], and throws otherwise. */
+private fun checkIndex(index: Int, count: Int): Int {
+    if (index !in 0 until count) {
+        throw IndexOutOfBoundsException("index $index is out of bounds for $count elements")
+    }
+    return index
+}
+
+/** Kind, carried on the wire as u8. */
+enum class Kind(val value: UByte) {
+    Ping(0u),
+    Data(1u),
+    Close(2u),
+    ;
+
+    companion object {
+        /** The size of Kind in bytes. */
+        const val SIZE_BYTES: Int = 1
+
+        /** Returns the variant whose value is [value], or null if there is none. */
+        fun fromValue(value: UByte): Kind? = values().firstOrNull { it.value == value }
+    }
+}
+
+/** Level, carried on the wire as i8. */
+enum class Level(val value: Byte) {
+    Low(1),
+    High(2),
+    ;
+
+    companion object {
+        /** The size of Level in bytes. */
+        const val SIZE_BYTES: Int = 1
+
+        /** Returns the variant whose value is [value], or null if there is none. */
+        fun fromValue(value: Byte): Level? = values().firstOrNull { it.value == value }
+    }
+}
+
+/** Body, viewed in [buffer] from its index 0. */
+@JvmInline
+value class Body(val buffer: ByteBuffer) {
+    /** The raw value of kind, which may name no variant. */
+    var kindRaw: UByte
+        get() = buffer.readBits(KIND_OFFSET, 1).toUByte()
+        set(value) {
+            buffer.writeBits(KIND_OFFSET, 1, value.toLong())
+        }
+
+    /** Accessor for kind, or null if it names no variant. */
+    val kind: Kind?
+        get() = Kind.fromValue(kindRaw)
+
+    /** Accessor for len. */
+    var len: UShort
+        get() = buffer.readBits(LEN_OFFSET, 2).toUShort()
+        set(value) {
+            buffer.writeBits(LEN_OFFSET, 2, value.toLong())
+        }
+
+    companion object {
+        /** The size of Body in bytes. */
+        const val SIZE_BYTES: Int = 3
+
+        /** The byte offset of kind. */
+        const val KIND_OFFSET: Int = 0
+
+        /** The byte offset of len. */
+        const val LEN_OFFSET: Int = 1
+
+        /** Returns a view of Body from the position of [buffer]. */
+        fun wrap(buffer: ByteBuffer): Body = Body(buffer.view(buffer.position(), SIZE_BYTES))
+
+        /** Returns a view of Body in a new, zeroed buffer. */
+        fun allocate(): Body = Body(ByteBuffer.allocate(SIZE_BYTES))
+    }
+}
+
+/** Packet, viewed in [buffer] from its index 0. */
+@JvmInline
+value class Packet(val buffer: ByteBuffer) {
+    /** The raw value of kind, which may name no variant. */
+    var kindRaw: UByte
+        get() = buffer.readBits(KIND_OFFSET, 1).toUByte()
+        set(value) {
+            buffer.writeBits(KIND_OFFSET, 1, value.toLong())
+        }
+
+    /** Accessor for kind, or null if it names no variant. */
+    val kind: Kind?
+        get() = Kind.fromValue(kindRaw)
+
+    /** True if payload is present: `kind == Kind.Data`. */
+    val hasPayload: Boolean
+        get() = buffer.readBits(KIND_OFFSET, 1) == 1L
+
+    /** Accessor for payload. */
+    val payload: Body
+        get() = Body(buffer.view(PAYLOAD_OFFSET, Body.SIZE_BYTES))
+
+    /** True if reason is present: `kind == Kind.Close`. */
+    val hasReason: Boolean
+        get() = buffer.readBits(KIND_OFFSET, 1) == 2L
+
+    /** The raw value of reason, which may name no variant. */
+    var reasonRaw: UByte
+        get() = buffer.readBits(REASON_OFFSET, 1).toUByte()
+        set(value) {
+            buffer.writeBits(REASON_OFFSET, 1, value.toLong())
+        }
+
+    /** Accessor for reason, or null if it names no variant. */
+    val reason: Kind?
+        get() = Kind.fromValue(reasonRaw)
+
+    /** Accessor for acked. */
+    var acked: Boolean
+        get() = (buffer.readBits(ACKED_OFFSET, 1) and 0x1L) != 0L
+        set(value) {
+            buffer.writeBits(ACKED_OFFSET, 1, (buffer.readBits(ACKED_OFFSET, 1) and 0x1L.inv()) or ((if (value) 1L else 0L) and 0x1L))
+        }
+
+    /** True if seq is present: `acked != true`. */
+    val hasSeq: Boolean
+        get() = (buffer.readBits(ACKED_OFFSET, 1) and 0x1L) != 1L
+
+    /** Accessor for seq. */
+    var seq: UByte
+        get() = ((buffer.readBits(SEQ_OFFSET, 1) ushr 1) and 0x7FL).toUByte()
+        set(value) {
+            buffer.writeBits(SEQ_OFFSET, 1, (buffer.readBits(SEQ_OFFSET, 1) and (0x7FL shl 1).inv()) or ((value.toLong() and 0x7FL) shl 1))
+        }
+
+    /** The raw value of level, which may name no variant. */
+    var levelRaw: Byte
+        get() = buffer.readBits(LEVEL_OFFSET, 1).toByte()
+        set(value) {
+            buffer.writeBits(LEVEL_OFFSET, 1, value.toLong())
+        }
+
+    /** Accessor for level, or null if it names no variant. */
+    val level: Level?
+        get() = Level.fromValue(levelRaw)
+
+    /** True if boost is present: `level == Level.High`. */
+    val hasBoost: Boolean
+        get() = buffer.readBits(LEVEL_OFFSET, 1) == 2L
+
+    /** Accessor for boost. */
+    var boost: UByte
+        get() = buffer.readBits(BOOST_OFFSET, 1).toUByte()
+        set(value) {
+            buffer.writeBits(BOOST_OFFSET, 1, value.toLong())
+        }
+
+    /** Accessor for count. */
+    var count: UByte
+        get() = buffer.readBits(COUNT_OFFSET, 1).toUByte()
+        set(value) {
+            buffer.writeBits(COUNT_OFFSET, 1, value.toLong())
+        }
+
+    /** True if items is present: `count != 0`. */
+    val hasItems: Boolean
+        get() = buffer.readBits(COUNT_OFFSET, 1) != 0L
+
+    /** The number of elements of items in use. */
+    var itemsCount: Int
+        get() = buffer.readBits(ITEMS_OFFSET, 1).toInt()
+        set(value) {
+            require(value in 0..2) { "items holds at most 2 elements" }
+            buffer.writeBits(ITEMS_OFFSET, 1, value.toLong())
+        }
+
+    /** Returns the raw value of element [index] of items. */
+    fun itemsRaw(index: Int): UByte = buffer.readBits(ITEMS_OFFSET + 1 + 1 * checkIndex(index, itemsCount), 1).toUByte()
+
+    /** Sets the raw value of element [index] of items. */
+    fun setItemsRaw(index: Int, value: UByte) {
+        buffer.writeBits(ITEMS_OFFSET + 1 + 1 * checkIndex(index, itemsCount), 1, value.toLong())
+    }
+
+    /** Returns element [index] of items. */
+    fun items(index: Int): Kind? = Kind.fromValue(itemsRaw(index))
+
+    companion object {
+        /** The size of Packet in bytes. */
+        const val SIZE_BYTES: Int = 12
+
+        /** The byte offset of kind. */
+        const val KIND_OFFSET: Int = 0
+
+        /** The byte offset of payload. */
+        const val PAYLOAD_OFFSET: Int = 1
+
+        /** The byte offset of reason. */
+        const val REASON_OFFSET: Int = 4
+
+        /** The byte offset of the bit-field container holding acked. */
+        const val ACKED_OFFSET: Int = 5
+
+        /** The byte offset of the bit-field container holding seq. */
+        const val SEQ_OFFSET: Int = 5
+
+        /** The byte offset of level. */
+        const val LEVEL_OFFSET: Int = 6
+
+        /** The byte offset of boost. */
+        const val BOOST_OFFSET: Int = 7
+
+        /** The byte offset of count. */
+        const val COUNT_OFFSET: Int = 8
+
+        /** The byte offset of items. */
+        const val ITEMS_OFFSET: Int = 9
+
+        /** Returns a view of Packet from the position of [buffer]. */
+        fun wrap(buffer: ByteBuffer): Packet = Packet(buffer.view(buffer.position(), SIZE_BYTES))
+
+        /** Returns a view of Packet in a new, zeroed buffer. */
+        fun allocate(): Packet = Packet(ByteBuffer.allocate(SIZE_BYTES))
+    }
+}
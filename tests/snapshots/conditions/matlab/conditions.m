@@ -0,0 +1,49 @@
+% Automatically generated by Onyx IDL compiler. Do not edit.
+function conditions()
+%CONDITIONS Defines the Simulink enums and bus objects of the schema.
+%   Run conditions before loading a model that uses them. Each bus object is
+%   assigned in the base workspace, named after its struct or message.
+
+% Kind, carried on the wire as u8.
+Simulink.defineIntEnumType('Kind', {'Ping', 'Data', 'Close'}, [0; 1; 2], ...
+    'StorageType', 'uint8', 'Description', 'Kind, carried on the wire as u8.');
+
+% Level, carried on the wire as i8.
+% Carried as int8 in buses, as it is open.
+
+% Body: 3 bytes.
+elements = Simulink.BusElement.empty;
+elements(end + 1) = element('kind', 'Enum: Kind', 1, 'byte 0');
+elements(end + 1) = element('len', 'uint16', 1, 'byte 1');
+bus = Simulink.Bus;
+bus.Description = 'Body: 3 bytes.';
+bus.Elements = elements;
+assignin('base', 'Body', bus);
+
+% Packet: 12 bytes.
+elements = Simulink.BusElement.empty;
+elements(end + 1) = element('kind', 'Enum: Kind', 1, 'byte 0');
+elements(end + 1) = element('payload', 'Bus: Body', 1, 'byte 1; present if kind == Kind.Data');
+elements(end + 1) = element('reason', 'Enum: Kind', 1, 'byte 4; present if kind == Kind.Close');
+elements(end + 1) = element('acked', 'boolean', 1, 'byte 5, bit 0');
+elements(end + 1) = element('seq', 'fixdt(0,7,0)', 1, 'byte 5, bits 1..7; present if acked != true');
+elements(end + 1) = element('level', 'int8', 1, 'byte 6');
+elements(end + 1) = element('boost', 'uint8', 1, 'byte 7; present if level == Level.High');
+elements(end + 1) = element('count', 'uint8', 1, 'byte 8');
+elements(end + 1) = element('items_count', 'uint8', 1, 'byte 9; present if count != 0; the number of elements of items in use');
+elements(end + 1) = element('items', 'Enum: Kind', 2, 'byte 10');
+bus = Simulink.Bus;
+bus.Description = 'Packet: 12 bytes.';
+bus.Elements = elements;
+assignin('base', 'Packet', bus);
+
+end
+
+function e = element(name, type, dimensions, description)
+% Returns a bus element.
+e = Simulink.BusElement;
+e.Name = name;
+e.DataType = type;
+e.Dimensions = dimensions;
+e.Description = description;
+end
@@ -0,0 +1,536 @@
+// Automatically generated by Onyx IDL compiler. Do not edit.
+
+use std::convert::TryInto;
+
+#[repr(u8)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Kind {
+    Ping = 0,
+    Data = 1,
+    Close = 2,
+}
+
+impl Default for Kind {
+    fn default() -> Self {
+        Self::Ping
+    }
+}
+
+impl TryFrom<u8> for Kind {
+    type Error = u8;
+
+    fn try_from(raw: u8) -> Result<Self, u8> {
+        match raw {
+            0 => Ok(Self::Ping),
+            1 => Ok(Self::Data),
+            2 => Ok(Self::Close),
+            _ => Err(raw),
+        }
+    }
+}
+
+impl From<Kind> for u8 {
+    fn from(value: Kind) -> Self {
+        value as u8
+    }
+}
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Level {
+    Low,
+    High,
+    /// A value that no variant declares.
+    Unknown(i8),
+}
+
+impl Default for Level {
+    fn default() -> Self {
+        Self::Low
+    }
+}
+
+impl From<i8> for Level {
+    fn from(raw: i8) -> Self {
+        match raw {
+            1 => Self::Low,
+            2 => Self::High,
+            _ => Self::Unknown(raw),
+        }
+    }
+}
+
+impl From<Level> for i8 {
+    fn from(value: Level) -> Self {
+        match value {
+            Level::Low => 1,
+            Level::High => 2,
+            Level::Unknown(raw) => raw,
+        }
+    }
+}
+
+/// Size of Body in bytes.
+pub(crate) const BODY_SIZE: usize = 3;
+/// Buffer type alias for Body.
+pub type BodyBuffer = [u8; BODY_SIZE];
+
+/// Read-only view of Body.
+#[derive(Debug, Clone, Copy)]
+pub struct BodyView<'a> {
+    data: &'a BodyBuffer,
+}
+
+/// Read-write view of Body.
+#[derive(Debug)]
+pub struct BodyMutView<'a> {
+    data: &'a mut BodyBuffer,
+}
+
+impl<'a> BodyView<'a> {
+    /// Size of Body in bytes.
+    pub const SIZE_BYTES: usize = BODY_SIZE;
+    /// Byte offset of kind.
+    pub const FIELD_OFFSET_KIND: usize = 0;
+    /// Byte offset of len.
+    pub const FIELD_OFFSET_LEN: usize = 1;
+
+    /// Creates a new BodyView from a BodyBuffer.
+    pub fn new(data: &'a BodyBuffer) -> Self {
+        Self { data }
+    }
+
+    /// Accessor for kind.
+    pub fn kind(&self) -> Result<Kind, u8> {
+        let bytes = self.data[0..1].try_into().unwrap();
+        let val = u8::from_le_bytes(bytes);
+        Kind::try_from(val)
+    }
+
+    /// Accessor for len.
+    pub fn len(&self) -> u16 {
+        let bytes = self.data[1..3].try_into().unwrap();
+        u16::from_le_bytes(bytes)
+    }
+}
+
+// Fails to compile if Body does not match its Onyx layout.
+const _: () = assert!(BodyView::FIELD_OFFSET_LEN == BodyView::FIELD_OFFSET_KIND + std::mem::size_of::<u8>(), "Body.len does not match its Onyx offset");
+const _: () = assert!(BodyView::FIELD_OFFSET_LEN + std::mem::size_of::<u16>() == BodyView::SIZE_BYTES, "Body does not match its Onyx size");
+
+impl<'a> BodyMutView<'a> {
+    /// Creates a new BodyMutView from a BodyBuffer.
+    pub fn new(data: &'a mut BodyBuffer) -> Self {
+        Self { data }
+    }
+
+    /// Accessor for kind.
+    pub fn kind(&self) -> Result<Kind, u8> {
+        let bytes = self.data[0..1].try_into().unwrap();
+        let val = u8::from_le_bytes(bytes);
+        Kind::try_from(val)
+    }
+
+    /// Mutator for kind.
+    pub fn set_kind(&mut self, value: Kind) {
+        let val = u8::from(value);
+        let bytes = val.to_le_bytes();
+        self.data[0..1].copy_from_slice(&bytes);
+    }
+
+    /// Accessor for len.
+    pub fn len(&self) -> u16 {
+        let bytes = self.data[1..3].try_into().unwrap();
+        u16::from_le_bytes(bytes)
+    }
+
+    /// Mutator for len.
+    pub fn set_len(&mut self, value: u16) {
+        let bytes = value.to_le_bytes();
+        self.data[1..3].copy_from_slice(&bytes);
+    }
+}
+
+/// Size of Packet in bytes.
+pub(crate) const PACKET_SIZE: usize = 12;
+/// Buffer type alias for Packet.
+pub type PacketBuffer = [u8; PACKET_SIZE];
+
+/// Read-only view of Packet.
+#[derive(Debug, Clone, Copy)]
+pub struct PacketView<'a> {
+    data: &'a PacketBuffer,
+}
+
+/// Read-write view of Packet.
+#[derive(Debug)]
+pub struct PacketMutView<'a> {
+    data: &'a mut PacketBuffer,
+}
+
+impl<'a> PacketView<'a> {
+    /// Size of Packet in bytes.
+    pub const SIZE_BYTES: usize = PACKET_SIZE;
+    /// Byte offset of kind.
+    pub const FIELD_OFFSET_KIND: usize = 0;
+    /// Byte offset of payload.
+    pub const FIELD_OFFSET_PAYLOAD: usize = 1;
+    /// Byte offset of reason.
+    pub const FIELD_OFFSET_REASON: usize = 4;
+    /// Byte offset of the bit-field container holding acked.
+    pub const FIELD_OFFSET_ACKED: usize = 5;
+    /// Byte offset of the bit-field container holding seq.
+    pub const FIELD_OFFSET_SEQ: usize = 5;
+    /// Byte offset of level.
+    pub const FIELD_OFFSET_LEVEL: usize = 6;
+    /// Byte offset of boost.
+    pub const FIELD_OFFSET_BOOST: usize = 7;
+    /// Byte offset of count.
+    pub const FIELD_OFFSET_COUNT: usize = 8;
+    /// Byte offset of items.
+    pub const FIELD_OFFSET_ITEMS: usize = 9;
+
+    /// Creates a new PacketView from a PacketBuffer.
+    pub fn new(data: &'a PacketBuffer) -> Self {
+        Self { data }
+    }
+
+    /// Accessor for kind.
+    pub fn kind(&self) -> Result<Kind, u8> {
+        let bytes = self.data[0..1].try_into().unwrap();
+        let val = u8::from_le_bytes(bytes);
+        Kind::try_from(val)
+    }
+
+    /// Returns true if payload is present: `kind == Kind.Data`.
+    pub fn has_payload(&self) -> bool {
+        self.kind().map_or_else(|raw| raw, u8::from) == 1
+    }
+
+    /// Accessor for payload.
+    pub fn payload(&self) -> BodyView {
+        let slice = &self.data[1..4];
+        BodyView::new(slice.try_into().unwrap())
+    }
+
+    /// Returns true if reason is present: `kind == Kind.Close`.
+    pub fn has_reason(&self) -> bool {
+        self.kind().map_or_else(|raw| raw, u8::from) == 2
+    }
+
+    /// Accessor for reason.
+    pub fn reason(&self) -> Result<Kind, u8> {
+        let bytes = self.data[4..5].try_into().unwrap();
+        let val = u8::from_le_bytes(bytes);
+        Kind::try_from(val)
+    }
+
+    /// Returns true if seq is present: `acked != true`.
+    pub fn has_seq(&self) -> bool {
+        !self.acked()
+    }
+
+    /// Accessor for acked.
+    pub fn acked(&self) -> bool {
+        let raw = self.data[5] as u8;
+        let val = (raw >> 0) & 0x1;
+        val != 0
+    }
+
+    /// Accessor for seq.
+    pub fn seq(&self) -> u8 {
+        let raw = self.data[5] as u8;
+        let val = (raw >> 1) & 0x7F;
+        val as u8
+    }
+
+    /// Accessor for level.
+    pub fn level(&self) -> Level {
+        let bytes = self.data[6..7].try_into().unwrap();
+        let val = i8::from_le_bytes(bytes);
+        Level::from(val)
+    }
+
+    /// Returns true if boost is present: `level == Level.High`.
+    pub fn has_boost(&self) -> bool {
+        i8::from(self.level()) == 2
+    }
+
+    /// Accessor for boost.
+    pub fn boost(&self) -> u8 {
+        self.data[7] as u8
+    }
+
+    /// Accessor for count.
+    pub fn count(&self) -> u8 {
+        self.data[8] as u8
+    }
+
+    /// Returns true if items is present: `count != 0`.
+    pub fn has_items(&self) -> bool {
+        self.count() != 0
+    }
+
+    /// Accessor for items.
+    pub fn items(&self) -> PacketItemsView {
+        let slice = &self.data[9..12];
+        PacketItemsView { data: slice.try_into().unwrap() }
+    }
+}
+
+// Fails to compile if Packet does not match its Onyx layout.
+const _: () = assert!(PacketView::FIELD_OFFSET_PAYLOAD == PacketView::FIELD_OFFSET_KIND + std::mem::size_of::<u8>(), "Packet.payload does not match its Onyx offset");
+const _: () = assert!(PacketView::FIELD_OFFSET_REASON == PacketView::FIELD_OFFSET_PAYLOAD + BODY_SIZE, "Packet.reason does not match its Onyx offset");
+const _: () = assert!(PacketView::FIELD_OFFSET_ACKED == PacketView::FIELD_OFFSET_REASON + std::mem::size_of::<u8>(), "Packet.acked does not match its Onyx offset");
+const _: () = assert!(PacketView::FIELD_OFFSET_LEVEL == PacketView::FIELD_OFFSET_ACKED + 1, "Packet.level does not match its Onyx offset");
+const _: () = assert!(PacketView::FIELD_OFFSET_BOOST == PacketView::FIELD_OFFSET_LEVEL + std::mem::size_of::<i8>(), "Packet.boost does not match its Onyx offset");
+const _: () = assert!(PacketView::FIELD_OFFSET_COUNT == PacketView::FIELD_OFFSET_BOOST + std::mem::size_of::<u8>(), "Packet.count does not match its Onyx offset");
+const _: () = assert!(PacketView::FIELD_OFFSET_ITEMS == PacketView::FIELD_OFFSET_COUNT + std::mem::size_of::<u8>(), "Packet.items does not match its Onyx offset");
+const _: () = assert!(PacketView::FIELD_OFFSET_ITEMS + (std::mem::size_of::<u8>() + 2 * std::mem::size_of::<u8>()) == PacketView::SIZE_BYTES, "Packet does not match its Onyx size");
+
+impl<'a> PacketMutView<'a> {
+    /// Creates a new PacketMutView from a PacketBuffer.
+    pub fn new(data: &'a mut PacketBuffer) -> Self {
+        Self { data }
+    }
+
+    /// Accessor for kind.
+    pub fn kind(&self) -> Result<Kind, u8> {
+        let bytes = self.data[0..1].try_into().unwrap();
+        let val = u8::from_le_bytes(bytes);
+        Kind::try_from(val)
+    }
+
+    /// Mutator for kind.
+    pub fn set_kind(&mut self, value: Kind) {
+        let val = u8::from(value);
+        let bytes = val.to_le_bytes();
+        self.data[0..1].copy_from_slice(&bytes);
+    }
+
+    /// Returns true if payload is present: `kind == Kind.Data`.
+    pub fn has_payload(&self) -> bool {
+        self.kind().map_or_else(|raw| raw, u8::from) == 1
+    }
+
+    /// Accessor for payload.
+    pub fn payload(&self) -> BodyView {
+        let slice = &self.data[1..4];
+        BodyView::new(slice.try_into().unwrap())
+    }
+
+    /// Mutable accessor for payload.
+    pub fn payload_mut(&mut self) -> BodyMutView {
+        let slice = &mut self.data[1..4];
+        BodyMutView::new(slice.try_into().unwrap())
+    }
+
+    /// Returns true if reason is present: `kind == Kind.Close`.
+    pub fn has_reason(&self) -> bool {
+        self.kind().map_or_else(|raw| raw, u8::from) == 2
+    }
+
+    /// Accessor for reason.
+    pub fn reason(&self) -> Result<Kind, u8> {
+        let bytes = self.data[4..5].try_into().unwrap();
+        let val = u8::from_le_bytes(bytes);
+        Kind::try_from(val)
+    }
+
+    /// Mutator for reason.
+    pub fn set_reason(&mut self, value: Kind) {
+        let val = u8::from(value);
+        let bytes = val.to_le_bytes();
+        self.data[4..5].copy_from_slice(&bytes);
+    }
+
+    /// Returns true if seq is present: `acked != true`.
+    pub fn has_seq(&self) -> bool {
+        !self.acked()
+    }
+
+    /// Accessor for acked.
+    pub fn acked(&self) -> bool {
+        let raw = self.data[5] as u8;
+        let val = (raw >> 0) & 0x1;
+        val != 0
+    }
+
+    /// Mutator for acked.
+    pub fn set_acked(&mut self, value: bool) {
+        let mut raw = self.data[5] as u8;
+        raw &= !((0x1 as u8) << 0);
+        raw |= ((value as u8 & 0x1) as u8) << 0;
+        self.data[5] = raw as u8;
+    }
+
+    /// Accessor for seq.
+    pub fn seq(&self) -> u8 {
+        let raw = self.data[5] as u8;
+        let val = (raw >> 1) & 0x7F;
+        val as u8
+    }
+
+    /// Mutator for seq.
+    pub fn set_seq(&mut self, value: u8) {
+        let mut raw = self.data[5] as u8;
+        raw &= !((0x7F as u8) << 1);
+        raw |= ((value as u8 & 0x7F) as u8) << 1;
+        self.data[5] = raw as u8;
+    }
+
+    /// Accessor for level.
+    pub fn level(&self) -> Level {
+        let bytes = self.data[6..7].try_into().unwrap();
+        let val = i8::from_le_bytes(bytes);
+        Level::from(val)
+    }
+
+    /// Mutator for level.
+    pub fn set_level(&mut self, value: Level) {
+        let val = i8::from(value);
+        let bytes = val.to_le_bytes();
+        self.data[6..7].copy_from_slice(&bytes);
+    }
+
+    /// Returns true if boost is present: `level == Level.High`.
+    pub fn has_boost(&self) -> bool {
+        i8::from(self.level()) == 2
+    }
+
+    /// Accessor for boost.
+    pub fn boost(&self) -> u8 {
+        self.data[7] as u8
+    }
+
+    /// Mutator for boost.
+    pub fn set_boost(&mut self, value: u8) {
+        self.data[7] = value as u8;
+    }
+
+    /// Accessor for count.
+    pub fn count(&self) -> u8 {
+        self.data[8] as u8
+    }
+
+    /// Mutator for count.
+    pub fn set_count(&mut self, value: u8) {
+        self.data[8] = value as u8;
+    }
+
+    /// Returns true if items is present: `count != 0`.
+    pub fn has_items(&self) -> bool {
+        self.count() != 0
+    }
+
+    /// Accessor for items.
+    pub fn items(&self) -> PacketItemsView {
+        let slice = &self.data[9..12];
+        PacketItemsView { data: slice.try_into().unwrap() }
+    }
+
+    /// Mutable accessor for items.
+    pub fn items_mut(&mut self) -> PacketItemsMutView {
+        let slice = &mut self.data[9..12];
+        PacketItemsMutView { data: slice.try_into().unwrap() }
+    }
+}
+
+/// Read-only view of the bounded vector Packet.items.
+#[derive(Debug, Clone, Copy)]
+pub struct PacketItemsView<'a> {
+    data: &'a [u8; 3],
+}
+
+impl<'a> PacketItemsView<'a> {
+    /// The maximum number of elements.
+    pub const CAPACITY: usize = 2;
+
+    /// Returns the number of elements, clamped to the capacity.
+    pub fn len(&self) -> usize {
+        (self.data[0] as usize).min(Self::CAPACITY)
+    }
+
+    /// Returns true if the vector has no elements.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the element at `index`, or `None` if it is out of range.
+    pub fn get(&self, index: usize) -> Option<Result<Kind, u8>> {
+        if index >= self.len() {
+            return None;
+        }
+        Some(Kind::try_from(u8::from_le_bytes(self.data[1 + index * 1..1 + index * 1 + 1].try_into().unwrap())))
+    }
+
+    /// Returns an iterator over the elements.
+    pub fn iter(&self) -> impl Iterator<Item = Result<Kind, u8>> + 'a {
+        let view = *self;
+        (0..self.len()).map(move |index| view.get(index).unwrap())
+    }
+}
+
+/// Read-write view of the bounded vector Packet.items.
+#[derive(Debug)]
+pub struct PacketItemsMutView<'a> {
+    data: &'a mut [u8; 3],
+}
+
+impl<'a> PacketItemsMutView<'a> {
+    /// The maximum number of elements.
+    pub const CAPACITY: usize = 2;
+
+    /// Returns a read-only view of the vector.
+    pub fn as_view(&self) -> PacketItemsView<'_> {
+        PacketItemsView { data: self.data }
+    }
+
+    /// Returns the number of elements, clamped to the capacity.
+    pub fn len(&self) -> usize {
+        self.as_view().len()
+    }
+
+    /// Returns true if the vector has no elements.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the element at `index`, or `None` if it is out of range.
+    pub fn get(&self, index: usize) -> Option<Result<Kind, u8>> {
+        self.as_view().get(index)
+    }
+
+    /// Returns an iterator over the elements.
+    pub fn iter(&self) -> impl Iterator<Item = Result<Kind, u8>> + '_ {
+        self.as_view().iter()
+    }
+
+    fn set_len(&mut self, len: usize) {
+        self.data[0] = len as u8;
+    }
+
+    /// Removes all elements.
+    pub fn clear(&mut self) {
+        self.set_len(0);
+    }
+
+    /// Replaces the element at `index`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is not less than `len()`.
+    pub fn set(&mut self, index: usize, value: Kind) {
+        assert!(index < self.len(), "index {index} out of range");
+        self.data[1 + index * 1..1 + index * 1 + 1].copy_from_slice(&u8::from(value).to_le_bytes());
+    }
+
+    /// Appends an element, or returns it back if the vector is full.
+    pub fn push(&mut self, value: Kind) -> Result<(), Kind> {
+        let index = self.len();
+        if index == Self::CAPACITY {
+            return Err(value);
+        }
+        self.set_len(index + 1);
+        self.set(index, value);
+        Ok(())
+    }
+}
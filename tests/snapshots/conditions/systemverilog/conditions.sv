@@ -0,0 +1,53 @@
+// Automatically generated by Onyx IDL compiler. Do not edit.
+`ifndef ONYX_CONDITIONS_PKG_SV
+`define ONYX_CONDITIONS_PKG_SV
+
+package conditions_pkg;
+
+  // Kind, carried on the wire as u8.
+  typedef enum logic [7:0] {
+    Kind_Ping = 8'd0,
+    Kind_Data = 8'd1,
+    Kind_Close = 8'd2
+  } Kind;
+
+  // Level, carried on the wire as i8.
+  typedef enum logic [7:0] {
+    Level_Low = 8'd1,
+    Level_High = 8'd2
+  } Level;
+
+  // Body: 3 bytes.
+  localparam int Body_SIZE_BYTES = 3;
+  localparam int Body_kind_OFFSET = 0;
+  localparam int Body_kind_WIDTH = 8;
+  localparam int Body_len_OFFSET = 1;
+  localparam int Body_len_WIDTH = 16;
+
+  // Packet: 12 bytes.
+  localparam int Packet_SIZE_BYTES = 12;
+  localparam int Packet_kind_OFFSET = 0;
+  localparam int Packet_kind_WIDTH = 8;
+  localparam int Packet_payload_OFFSET = 1;
+  localparam int Packet_payload_WIDTH = 24;
+  localparam int Packet_reason_OFFSET = 4;
+  localparam int Packet_reason_WIDTH = 8;
+  localparam int Packet_acked_OFFSET = 5;
+  localparam int Packet_acked_BIT_OFFSET = 0;
+  localparam int Packet_acked_WIDTH = 1;
+  localparam int Packet_seq_OFFSET = 5;
+  localparam int Packet_seq_BIT_OFFSET = 1;
+  localparam int Packet_seq_WIDTH = 7;
+  localparam int Packet_level_OFFSET = 6;
+  localparam int Packet_level_WIDTH = 8;
+  localparam int Packet_boost_OFFSET = 7;
+  localparam int Packet_boost_WIDTH = 8;
+  localparam int Packet_count_OFFSET = 8;
+  localparam int Packet_count_WIDTH = 8;
+  localparam int Packet_items_OFFSET = 9;
+  localparam int Packet_items_WIDTH = 24;
+  localparam int Packet_items_CAPACITY = 2;
+
+endpackage
+
+`endif // ONYX_CONDITIONS_PKG_SV
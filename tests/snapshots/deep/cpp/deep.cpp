@@ -0,0 +1,126 @@
+// Automatically generated by Onyx IDL compiler. Do not edit.
+#include "deep.hpp"
+
+#include <string.h>
+
+namespace onyx {
+
+Leaf* Leaf::Deserialize(Buffer& buffer) {
+    // Overlay the class structure onto the buffer memory. This is the zero-copy step.
+    Leaf* result = (Leaf*)buffer;
+
+    // SWAP: Primitive field value
+    result->__raw_value = utils::byteswap_if_needed(result->__raw_value);
+
+    return result;
+}
+
+void Leaf::Serialize(Buffer& buffer) const {
+    // Copy the host-endian object into the buffer first.
+    memcpy(buffer, this, kSizeOf);
+    Leaf* wire_format_data = (Leaf*)buffer;
+
+    // SWAP: Primitive field value
+    wire_format_data->__raw_value = utils::byteswap_if_needed(wire_format_data->__raw_value);
+}
+
+Node* Node::Deserialize(Buffer& buffer) {
+    // Overlay the class structure onto the buffer memory. This is the zero-copy step.
+    Node* result = (Node*)buffer;
+
+    Leaf::Deserialize(*(Leaf::Buffer*) &result->__raw_leaf);
+
+    return result;
+}
+
+void Node::Serialize(Buffer& buffer) const {
+    // Copy the host-endian object into the buffer first.
+    memcpy(buffer, this, kSizeOf);
+    Node* wire_format_data = (Node*)buffer;
+
+    wire_format_data->__raw_leaf.Serialize(*(Leaf::Buffer*) &wire_format_data->__raw_leaf);
+}
+
+Link* Link::Deserialize(Buffer& buffer) {
+    // Overlay the class structure onto the buffer memory. This is the zero-copy step.
+    Link* result = (Link*)buffer;
+    bool known = true;
+
+    Node::Deserialize(*(Node::Buffer*) &result->__raw_node);
+    // CHECK: Closed enum field mode
+    known = is_known(result->__raw_mode) && known;
+
+    return known ? result : nullptr;
+}
+
+void Link::Serialize(Buffer& buffer) const {
+    // Copy the host-endian object into the buffer first.
+    memcpy(buffer, this, kSizeOf);
+    Link* wire_format_data = (Link*)buffer;
+
+    wire_format_data->__raw_node.Serialize(*(Node::Buffer*) &wire_format_data->__raw_node);
+}
+
+Hop* Hop::Deserialize(Buffer& buffer) {
+    // Overlay the class structure onto the buffer memory. This is the zero-copy step.
+    Hop* result = (Hop*)buffer;
+    bool known = true;
+
+    known = Link::Deserialize(*(Link::Buffer*) &result->__raw_link) != nullptr && known;
+
+    return known ? result : nullptr;
+}
+
+void Hop::Serialize(Buffer& buffer) const {
+    // Copy the host-endian object into the buffer first.
+    memcpy(buffer, this, kSizeOf);
+    Hop* wire_format_data = (Hop*)buffer;
+
+    wire_format_data->__raw_link.Serialize(*(Link::Buffer*) &wire_format_data->__raw_link);
+}
+
+Route* Route::Deserialize(Buffer& buffer) {
+    // Overlay the class structure onto the buffer memory. This is the zero-copy step.
+    Route* result = (Route*)buffer;
+    bool known = true;
+
+    // SWAP: Vector field hops
+    for (size_t i = 0; i < 2; ++i) {
+        if (!Hop::Deserialize(*(Hop::Buffer*) &result->__raw_hops.__items[i]) && i < result->__raw_hops.len()) known = false;
+    }
+
+    return known ? result : nullptr;
+}
+
+void Route::Serialize(Buffer& buffer) const {
+    // Copy the host-endian object into the buffer first.
+    memcpy(buffer, this, kSizeOf);
+    Route* wire_format_data = (Route*)buffer;
+
+    // SWAP: Vector field hops
+    for (size_t i = 0; i < 2; ++i) {
+        wire_format_data->__raw_hops.__items[i].Serialize(*(Hop::Buffer*) &wire_format_data->__raw_hops.__items[i]);
+    }
+}
+
+Frame* Frame::Deserialize(Buffer& buffer) {
+    // Overlay the class structure onto the buffer memory. This is the zero-copy step.
+    Frame* result = (Frame*)buffer;
+    bool known = true;
+
+    known = Route::Deserialize(*(Route::Buffer*) &result->__raw_route) != nullptr && known;
+    Leaf::Deserialize(*(Leaf::Buffer*) &result->__raw_tail);
+
+    return known ? result : nullptr;
+}
+
+void Frame::Serialize(Buffer& buffer) const {
+    // Copy the host-endian object into the buffer first.
+    memcpy(buffer, this, kSizeOf);
+    Frame* wire_format_data = (Frame*)buffer;
+
+    wire_format_data->__raw_route.Serialize(*(Route::Buffer*) &wire_format_data->__raw_route);
+    wire_format_data->__raw_tail.Serialize(*(Leaf::Buffer*) &wire_format_data->__raw_tail);
+}
+
+} // namespace onyx
@@ -0,0 +1,546 @@
+// Automatically generated by Onyx IDL compiler. Do not edit.
+#ifndef ONYX_DEEP_H_
+#define ONYX_DEEP_H_
+
+#include <stddef.h>
+#include <stdint.h>
+#include <string.h>
+
+#pragma pack(push, 1)
+
+namespace onyx {
+#ifndef ONYX_UTILS_ENDIANNESS_BIG_
+#define ONYX_UTILS_ENDIANNESS_BIG_
+
+namespace utils {
+
+// Define network endianness (usually Big Endian).
+#define ONYX_BIG_ENDIAN 4321
+#define ONYX_LITTLE_ENDIAN 1234
+#define ONYX_NETWORK_ORDER ONYX_BIG_ENDIAN
+
+// Determine host endianness at compile time (using standard GCC/Clang macros)
+#ifdef __BYTE_ORDER__
+    #if __BYTE_ORDER__ == __ORDER_BIG_ENDIAN__
+        #define ONYX_HOST_ORDER ONYX_BIG_ENDIAN
+    #elif __BYTE_ORDER__ == __ORDER_LITTLE_ENDIAN__
+        #define ONYX_HOST_ORDER ONYX_LITTLE_ENDIAN
+    #else
+        #error "Unsupported host endianness"
+    #endif
+#elif defined(_WIN32) || defined(__LITTLE_ENDIAN__)
+    // Fallback for Windows (always little-endian) or other platforms
+    #define ONYX_HOST_ORDER ONYX_LITTLE_ENDIAN
+#else
+    #error "Could not determine host endianness"
+#endif
+
+// Helper function to swap bytes (Explicit Overloads)
+inline uint16_t byteswap(uint16_t value) { return ((value << 8) & 0xFF00) | ((value >> 8) & 0x00FF); }
+
+inline int16_t byteswap(int16_t value) { return (int16_t)byteswap((uint16_t)value); }
+
+inline uint32_t byteswap(uint32_t value) { return (value << 24) | ((value & 0x00FF0000) >> 8) | ((value & 0x0000FF00) << 8) | (value >> 24); }
+
+inline int32_t byteswap(int32_t value) { return (int32_t)byteswap((uint32_t)value); }
+
+inline uint64_t byteswap(uint64_t value) { return (value << 56) | ((value & 0x00FF000000000000ULL) >> 40) | ((value & 0x0000FF0000000000ULL) >> 24) | ((value & 0x000000FF00000000ULL) >> 8) | ((value & 0x00000000FF000000ULL) << 8) | ((value & 0x0000000000FF0000ULL) << 24) | ((value & 0x000000000000FF00ULL) << 40) | (value >> 56); }
+
+inline int64_t byteswap(int64_t value) { return (int64_t)byteswap((uint64_t)value); }
+
+inline float byteswap(float value) {
+    uint32_t temp;
+    memcpy(&temp, &value, sizeof(value));
+    temp = byteswap(temp);
+    memcpy(&value, &temp, sizeof(value));
+    return value;
+}
+
+inline double byteswap(double value) {
+    uint64_t temp;
+    memcpy(&temp, &value, sizeof(value));
+    temp = byteswap(temp);
+    memcpy(&value, &temp, sizeof(value));
+    return value;
+}
+
+// Swaps bytes if the host order does not match the network order (Explicit Overloads)
+inline uint16_t byteswap_if_needed(uint16_t value) {
+    if (ONYX_HOST_ORDER != ONYX_NETWORK_ORDER) {
+        return byteswap(value);
+    }
+    return value;
+}
+
+inline int16_t byteswap_if_needed(int16_t value) {
+    if (ONYX_HOST_ORDER != ONYX_NETWORK_ORDER) {
+        return byteswap(value);
+    }
+    return value;
+}
+
+inline uint32_t byteswap_if_needed(uint32_t value) {
+    if (ONYX_HOST_ORDER != ONYX_NETWORK_ORDER) {
+        return byteswap(value);
+    }
+    return value;
+}
+
+inline int32_t byteswap_if_needed(int32_t value) {
+    if (ONYX_HOST_ORDER != ONYX_NETWORK_ORDER) {
+        return byteswap(value);
+    }
+    return value;
+}
+
+inline uint64_t byteswap_if_needed(uint64_t value) {
+    if (ONYX_HOST_ORDER != ONYX_NETWORK_ORDER) {
+        return byteswap(value);
+    }
+    return value;
+}
+
+inline int64_t byteswap_if_needed(int64_t value) {
+    if (ONYX_HOST_ORDER != ONYX_NETWORK_ORDER) {
+        return byteswap(value);
+    }
+    return value;
+}
+
+inline float byteswap_if_needed(float value) {
+    if (ONYX_HOST_ORDER != ONYX_NETWORK_ORDER) {
+        return byteswap(value);
+    }
+    return value;
+}
+
+inline double byteswap_if_needed(double value) {
+    if (ONYX_HOST_ORDER != ONYX_NETWORK_ORDER) {
+        return byteswap(value);
+    }
+    return value;
+}
+
+} // namespace utils
+#endif // ONYX_UTILS_ENDIANNESS_BIG_
+
+#ifndef ONYX_UTILS_BOUNDED_VEC_
+#define ONYX_UTILS_BOUNDED_VEC_
+
+namespace utils {
+
+/// A bounded vector: a length followed by room for N elements, all of them inline.
+template <typename T, typename LenT, size_t N>
+class BoundedVec {
+public:
+    LenT __length;
+    T __items[N];
+
+    /// Returns the number of elements, clamped to the capacity.
+    inline size_t len() const { return __length < N ? __length : N; }
+    /// Returns the maximum number of elements.
+    static inline size_t capacity() { return N; }
+    /// Returns true if the vector has no elements.
+    inline bool empty() const { return len() == 0; }
+    /// Returns the element at `i`, which must be less than len().
+    inline const T& get(size_t i) const { return __items[i]; }
+    inline T& get(size_t i) { return __items[i]; }
+    /// Replaces the element at `i`, which must be less than len().
+    inline void set(size_t i, const T& value) { __items[i] = value; }
+    /// Appends an element. Returns false, leaving the vector unchanged, if it is full.
+    inline bool push(const T& value) {
+        size_t n = len();
+        if (n == N) return false;
+        __items[n] = value;
+        __length = static_cast<LenT>(n + 1);
+        return true;
+    }
+    /// Removes all elements.
+    inline void clear() { __length = 0; }
+
+    inline const T* begin() const { return __items; }
+    inline const T* end() const { return __items + len(); }
+    inline T* begin() { return __items; }
+    inline T* end() { return __items + len(); }
+};
+
+} // namespace utils
+
+#endif // ONYX_UTILS_BOUNDED_VEC_
+
+class Leaf;
+class Node;
+class Link;
+class Hop;
+class Route;
+class Frame;
+
+class Leaf {
+private:
+    uint16_t __raw_value;
+
+public:
+    static const size_t kSizeOf = 2;
+    using Buffer = uint8_t[kSizeOf];
+
+    /// Byte offsets of the fields within the buffer.
+    static const size_t kValueOffset = 0;
+
+    /// Accessor for value
+    inline const uint16_t value() const { return __raw_value; }
+
+    /// Mutator for value
+    inline void value(const uint16_t value) { __raw_value = value; }
+
+    /// Deserializes the network-endian buffer to Leaf in-place by casting
+    /// and applying endianness correction to match the host-endianness.
+    /// NOTE: This mutates the input buffer to host-endianness.
+    /// Returns a pointer to the object within the buffer.
+    static Leaf* Deserialize(Buffer& buffer);
+
+    /// Serializes the host-endian object into the provided buffer,
+    /// applying endianness correction to match the network-endianness.
+    void Serialize(Buffer& buffer) const;
+
+private:
+    /// Fails to compile if Leaf does not match its Onyx layout.
+    static inline void __check_layout() {
+        static_assert(sizeof(Leaf) == kSizeOf, "Leaf does not match its Onyx size");
+        static_assert(offsetof(Leaf, __raw_value) == kValueOffset, "Leaf::value does not match its Onyx offset");
+    }
+};
+
+class Node {
+private:
+    Leaf __raw_leaf;
+
+public:
+    static const size_t kSizeOf = 2;
+    using Buffer = uint8_t[kSizeOf];
+
+    /// Byte offsets of the fields within the buffer.
+    static const size_t kLeafOffset = 0;
+
+    /// Accessor for leaf
+    inline Leaf& leaf() { return __raw_leaf; }
+
+    /// Mutator for leaf
+    inline void leaf(const Leaf value) { __raw_leaf = value; }
+
+    /// Deserializes the network-endian buffer to Node in-place by casting
+    /// and applying endianness correction to match the host-endianness.
+    /// NOTE: This mutates the input buffer to host-endianness.
+    /// Returns a pointer to the object within the buffer.
+    static Node* Deserialize(Buffer& buffer);
+
+    /// Serializes the host-endian object into the provided buffer,
+    /// applying endianness correction to match the network-endianness.
+    void Serialize(Buffer& buffer) const;
+
+private:
+    /// Fails to compile if Node does not match its Onyx layout.
+    static inline void __check_layout() {
+        static_assert(sizeof(Node) == kSizeOf, "Node does not match its Onyx size");
+        static_assert(offsetof(Node, __raw_leaf) == kLeafOffset, "Node::leaf does not match its Onyx offset");
+    }
+};
+
+enum class Mode : uint8_t {
+    Direct,
+    Relay = 1,
+};
+
+/// Returns true if `value` is declared by a variant of Mode.
+inline bool is_known(Mode value) {
+    return value == Mode::Direct || value == Mode::Relay;
+}
+
+class Link {
+private:
+    Node __raw_node;
+    Mode __raw_mode;
+
+public:
+    static const size_t kSizeOf = 3;
+    using Buffer = uint8_t[kSizeOf];
+
+    /// Byte offsets of the fields within the buffer.
+    static const size_t kNodeOffset = 0;
+    static const size_t kModeOffset = 2;
+
+    /// Accessor for node
+    inline Node& node() { return __raw_node; }
+
+    /// Mutator for node
+    inline void node(const Node value) { __raw_node = value; }
+
+    /// Accessor for mode
+    inline const Mode mode() const { return __raw_mode; }
+
+    /// Mutator for mode
+    inline void mode(const Mode value) { __raw_mode = value; }
+
+    /// Deserializes the network-endian buffer to Link in-place by casting
+    /// and applying endianness correction to match the host-endianness.
+    /// NOTE: This mutates the input buffer to host-endianness.
+    /// Returns a pointer to the object within the buffer.
+    /// Returns nullptr if a closed enum holds a value that no variant declares;
+    /// the buffer is converted either way.
+    static Link* Deserialize(Buffer& buffer);
+
+    /// Serializes the host-endian object into the provided buffer,
+    /// applying endianness correction to match the network-endianness.
+    void Serialize(Buffer& buffer) const;
+
+private:
+    /// Fails to compile if Link does not match its Onyx layout.
+    static inline void __check_layout() {
+        static_assert(sizeof(Link) == kSizeOf, "Link does not match its Onyx size");
+        static_assert(offsetof(Link, __raw_node) == kNodeOffset, "Link::node does not match its Onyx offset");
+        static_assert(offsetof(Link, __raw_mode) == kModeOffset, "Link::mode does not match its Onyx offset");
+    }
+};
+
+class Hop {
+private:
+    Link __raw_link;
+
+public:
+    static const size_t kSizeOf = 3;
+    using Buffer = uint8_t[kSizeOf];
+
+    /// Byte offsets of the fields within the buffer.
+    static const size_t kLinkOffset = 0;
+
+    /// Accessor for link
+    inline Link& link() { return __raw_link; }
+
+    /// Mutator for link
+    inline void link(const Link value) { __raw_link = value; }
+
+    /// Deserializes the network-endian buffer to Hop in-place by casting
+    /// and applying endianness correction to match the host-endianness.
+    /// NOTE: This mutates the input buffer to host-endianness.
+    /// Returns a pointer to the object within the buffer.
+    /// Returns nullptr if a closed enum holds a value that no variant declares;
+    /// the buffer is converted either way.
+    static Hop* Deserialize(Buffer& buffer);
+
+    /// Serializes the host-endian object into the provided buffer,
+    /// applying endianness correction to match the network-endianness.
+    void Serialize(Buffer& buffer) const;
+
+private:
+    /// Fails to compile if Hop does not match its Onyx layout.
+    static inline void __check_layout() {
+        static_assert(sizeof(Hop) == kSizeOf, "Hop does not match its Onyx size");
+        static_assert(offsetof(Hop, __raw_link) == kLinkOffset, "Hop::link does not match its Onyx offset");
+    }
+};
+
+class Route {
+private:
+    utils::BoundedVec<Hop, uint8_t, 2> __raw_hops;
+
+public:
+    static const size_t kSizeOf = 7;
+    using Buffer = uint8_t[kSizeOf];
+
+    /// Byte offsets of the fields within the buffer.
+    static const size_t kHopsOffset = 0;
+
+    /// Accessor for hops
+    inline utils::BoundedVec<Hop, uint8_t, 2>& hops() { return __raw_hops; }
+
+    /// Mutator for hops
+    inline void hops(const utils::BoundedVec<Hop, uint8_t, 2> value) { __raw_hops = value; }
+
+    /// Deserializes the network-endian buffer to Route in-place by casting
+    /// and applying endianness correction to match the host-endianness.
+    /// NOTE: This mutates the input buffer to host-endianness.
+    /// Returns a pointer to the object within the buffer.
+    /// Returns nullptr if a closed enum holds a value that no variant declares;
+    /// the buffer is converted either way.
+    static Route* Deserialize(Buffer& buffer);
+
+    /// Serializes the host-endian object into the provided buffer,
+    /// applying endianness correction to match the network-endianness.
+    void Serialize(Buffer& buffer) const;
+
+private:
+    /// Fails to compile if Route does not match its Onyx layout.
+    static inline void __check_layout() {
+        static_assert(sizeof(Route) == kSizeOf, "Route does not match its Onyx size");
+        static_assert(offsetof(Route, __raw_hops) == kHopsOffset, "Route::hops does not match its Onyx offset");
+    }
+};
+
+class Frame {
+private:
+    Route __raw_route;
+    Leaf __raw_tail;
+
+public:
+    static const size_t kSizeOf = 9;
+    using Buffer = uint8_t[kSizeOf];
+
+    /// Byte offsets of the fields within the buffer.
+    static const size_t kRouteOffset = 0;
+    static const size_t kTailOffset = 7;
+
+    /// Accessor for route
+    inline Route& route() { return __raw_route; }
+
+    /// Mutator for route
+    inline void route(const Route value) { __raw_route = value; }
+
+    /// Accessor for tail
+    inline Leaf& tail() { return __raw_tail; }
+
+    /// Mutator for tail
+    inline void tail(const Leaf value) { __raw_tail = value; }
+
+    /// Deserializes the network-endian buffer to Frame in-place by casting
+    /// and applying endianness correction to match the host-endianness.
+    /// NOTE: This mutates the input buffer to host-endianness.
+    /// Returns a pointer to the object within the buffer.
+    /// Returns nullptr if a closed enum holds a value that no variant declares;
+    /// the buffer is converted either way.
+    static Frame* Deserialize(Buffer& buffer);
+
+    /// Serializes the host-endian object into the provided buffer,
+    /// applying endianness correction to match the network-endianness.
+    void Serialize(Buffer& buffer) const;
+
+private:
+    /// Fails to compile if Frame does not match its Onyx layout.
+    static inline void __check_layout() {
+        static_assert(sizeof(Frame) == kSizeOf, "Frame does not match its Onyx size");
+        static_assert(offsetof(Frame, __raw_route) == kRouteOffset, "Frame::route does not match its Onyx offset");
+        static_assert(offsetof(Frame, __raw_tail) == kTailOffset, "Frame::tail does not match its Onyx offset");
+    }
+};
+
+
+inline Leaf* Leaf::Deserialize(Buffer& buffer) {
+    // Overlay the class structure onto the buffer memory. This is the zero-copy step.
+    Leaf* result = (Leaf*)buffer;
+
+    // SWAP: Primitive field value
+    result->__raw_value = utils::byteswap_if_needed(result->__raw_value);
+
+    return result;
+}
+
+inline void Leaf::Serialize(Buffer& buffer) const {
+    // Copy the host-endian object into the buffer first.
+    memcpy(buffer, this, kSizeOf);
+    Leaf* wire_format_data = (Leaf*)buffer;
+
+    // SWAP: Primitive field value
+    wire_format_data->__raw_value = utils::byteswap_if_needed(wire_format_data->__raw_value);
+}
+
+inline Node* Node::Deserialize(Buffer& buffer) {
+    // Overlay the class structure onto the buffer memory. This is the zero-copy step.
+    Node* result = (Node*)buffer;
+
+    Leaf::Deserialize(*(Leaf::Buffer*) &result->__raw_leaf);
+
+    return result;
+}
+
+inline void Node::Serialize(Buffer& buffer) const {
+    // Copy the host-endian object into the buffer first.
+    memcpy(buffer, this, kSizeOf);
+    Node* wire_format_data = (Node*)buffer;
+
+    wire_format_data->__raw_leaf.Serialize(*(Leaf::Buffer*) &wire_format_data->__raw_leaf);
+}
+
+inline Link* Link::Deserialize(Buffer& buffer) {
+    // Overlay the class structure onto the buffer memory. This is the zero-copy step.
+    Link* result = (Link*)buffer;
+    bool known = true;
+
+    Node::Deserialize(*(Node::Buffer*) &result->__raw_node);
+    // CHECK: Closed enum field mode
+    known = is_known(result->__raw_mode) && known;
+
+    return known ? result : nullptr;
+}
+
+inline void Link::Serialize(Buffer& buffer) const {
+    // Copy the host-endian object into the buffer first.
+    memcpy(buffer, this, kSizeOf);
+    Link* wire_format_data = (Link*)buffer;
+
+    wire_format_data->__raw_node.Serialize(*(Node::Buffer*) &wire_format_data->__raw_node);
+}
+
+inline Hop* Hop::Deserialize(Buffer& buffer) {
+    // Overlay the class structure onto the buffer memory. This is the zero-copy step.
+    Hop* result = (Hop*)buffer;
+    bool known = true;
+
+    known = Link::Deserialize(*(Link::Buffer*) &result->__raw_link) != nullptr && known;
+
+    return known ? result : nullptr;
+}
+
+inline void Hop::Serialize(Buffer& buffer) const {
+    // Copy the host-endian object into the buffer first.
+    memcpy(buffer, this, kSizeOf);
+    Hop* wire_format_data = (Hop*)buffer;
+
+    wire_format_data->__raw_link.Serialize(*(Link::Buffer*) &wire_format_data->__raw_link);
+}
+
+inline Route* Route::Deserialize(Buffer& buffer) {
+    // Overlay the class structure onto the buffer memory. This is the zero-copy step.
+    Route* result = (Route*)buffer;
+    bool known = true;
+
+    // SWAP: Vector field hops
+    for (size_t i = 0; i < 2; ++i) {
+        if (!Hop::Deserialize(*(Hop::Buffer*) &result->__raw_hops.__items[i]) && i < result->__raw_hops.len()) known = false;
+    }
+
+    return known ? result : nullptr;
+}
+
+inline void Route::Serialize(Buffer& buffer) const {
+    // Copy the host-endian object into the buffer first.
+    memcpy(buffer, this, kSizeOf);
+    Route* wire_format_data = (Route*)buffer;
+
+    // SWAP: Vector field hops
+    for (size_t i = 0; i < 2; ++i) {
+        wire_format_data->__raw_hops.__items[i].Serialize(*(Hop::Buffer*) &wire_format_data->__raw_hops.__items[i]);
+    }
+}
+
+inline Frame* Frame::Deserialize(Buffer& buffer) {
+    // Overlay the class structure onto the buffer memory. This is the zero-copy step.
+    Frame* result = (Frame*)buffer;
+    bool known = true;
+
+    known = Route::Deserialize(*(Route::Buffer*) &result->__raw_route) != nullptr && known;
+    Leaf::Deserialize(*(Leaf::Buffer*) &result->__raw_tail);
+
+    return known ? result : nullptr;
+}
+
+inline void Frame::Serialize(Buffer& buffer) const {
+    // Copy the host-endian object into the buffer first.
+    memcpy(buffer, this, kSizeOf);
+    Frame* wire_format_data = (Frame*)buffer;
+
+    wire_format_data->__raw_route.Serialize(*(Route::Buffer*) &wire_format_data->__raw_route);
+    wire_format_data->__raw_tail.Serialize(*(Leaf::Buffer*) &wire_format_data->__raw_tail);
+}
+
+} // namespace onyx
+
+#pragma pack(pop)
+#endif // ONYX_DEEP_H_
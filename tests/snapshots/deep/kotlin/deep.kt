@@ -0,0 +1,226 @@
+// Automatically generated by Onyx IDL compiler. Do not edit.
+package onyx
+
+import java.nio.ByteBuffer
+import java.nio.ByteOrder
+
+/** The byte order of every field in this file. */
+private val BYTE_ORDER: ByteOrder = ByteOrder.BIG_ENDIAN
+
+/** Returns the [size] bytes of the buffer from [offset] as a buffer of their own. */
+private fun ByteBuffer.view(offset: Int, size: Int): ByteBuffer =
+    (duplicate().clear().position(offset).limit(offset + size) as ByteBuffer).slice()
+
+/** Reads the [size] bytes from [offset] as an unsigned integer in [BYTE_ORDER]. */
+private fun ByteBuffer.readBits(offset: Int, size: Int): Long {
+    var raw = 0L
+    for (i in 0 until size) {
+        val index = if (BYTE_ORDER == ByteOrder.BIG_ENDIAN) i else size - 1 - i
+        raw = (raw shl 8) or (get(offset + index).toLong() and 0xFFL)
+    }
+    return raw
+}
+
+/** Writes the low [size] bytes of [raw] from [offset] in [BYTE_ORDER]. */
+private fun ByteBuffer.writeBits(offset: Int, size: Int, raw: Long) {
+    for (i in 0 until size) {
+        val shift = if (BYTE_ORDER == ByteOrder.BIG_ENDIAN) 8 * (size - 1 - i) else 8 * i
+        put(offset + i, (raw ushr shift).toByte())
+    }
+}
+
+/** Copies the [size] bytes from [offset]. */
+private fun ByteBuffer.readBytes(offset: Int, size: Int): ByteArray =
+    ByteArray(size).also { view(offset, size).get(it) }
+
+/** Writes [value], which must hold exactly [size] bytes, from [offset]. */
+private fun ByteBuffer.writeBytes(offset: Int, size: Int, value: ByteArray) {
+    require(value.size == size) { "expected $size bytes, got ${value.size}" }
+    view(offset, size).put(value)
+}
+
+/** Returns [index] if it is below [count], and throws otherwise. */
+private fun checkIndex(index: Int, count: Int): Int {
+    if (index !in 0 until count) {
+        throw IndexOutOfBoundsException("index $index is out of bounds for $count elements")
+    }
+    return index
+}
+
+/** Leaf, viewed in [buffer] from its index 0. */
+@JvmInline
+value class Leaf(val buffer: ByteBuffer) {
+    /** Accessor for value. */
+    var value: UShort
+        get() = buffer.readBits(VALUE_OFFSET, 2).toUShort()
+        set(value) {
+            buffer.writeBits(VALUE_OFFSET, 2, value.toLong())
+        }
+
+    companion object {
+        /** The size of Leaf in bytes. */
+        const val SIZE_BYTES: Int = 2
+
+        /** The byte offset of value. */
+        const val VALUE_OFFSET: Int = 0
+
+        /** Returns a view of Leaf from the position of [buffer]. */
+        fun wrap(buffer: ByteBuffer): Leaf = Leaf(buffer.view(buffer.position(), SIZE_BYTES))
+
+        /** Returns a view of Leaf in a new, zeroed buffer. */
+        fun allocate(): Leaf = Leaf(ByteBuffer.allocate(SIZE_BYTES))
+    }
+}
+
+/** Node, viewed in [buffer] from its index 0. */
+@JvmInline
+value class Node(val buffer: ByteBuffer) {
+    /** Accessor for leaf. */
+    val leaf: Leaf
+        get() = Leaf(buffer.view(LEAF_OFFSET, Leaf.SIZE_BYTES))
+
+    companion object {
+        /** The size of Node in bytes. */
+        const val SIZE_BYTES: Int = 2
+
+        /** The byte offset of leaf. */
+        const val LEAF_OFFSET: Int = 0
+
+        /** Returns a view of Node from the position of [buffer]. */
+        fun wrap(buffer: ByteBuffer): Node = Node(buffer.view(buffer.position(), SIZE_BYTES))
+
+        /** Returns a view of Node in a new, zeroed buffer. */
+        fun allocate(): Node = Node(ByteBuffer.allocate(SIZE_BYTES))
+    }
+}
+
+/** Mode, carried on the wire as u8. */
+enum class Mode(val value: UByte) {
+    Direct(0u),
+    Relay(1u),
+    ;
+
+    companion object {
+        /** The size of Mode in bytes. */
+        const val SIZE_BYTES: Int = 1
+
+        /** Returns the variant whose value is [value], or null if there is none. */
+        fun fromValue(value: UByte): Mode? = values().firstOrNull { it.value == value }
+    }
+}
+
+/** Link, viewed in [buffer] from its index 0. */
+@JvmInline
+value class Link(val buffer: ByteBuffer) {
+    /** Accessor for node. */
+    val node: Node
+        get() = Node(buffer.view(NODE_OFFSET, Node.SIZE_BYTES))
+
+    /** The raw value of mode, which may name no variant. */
+    var modeRaw: UByte
+        get() = buffer.readBits(MODE_OFFSET, 1).toUByte()
+        set(value) {
+            buffer.writeBits(MODE_OFFSET, 1, value.toLong())
+        }
+
+    /** Accessor for mode, or null if it names no variant. */
+    val mode: Mode?
+        get() = Mode.fromValue(modeRaw)
+
+    companion object {
+        /** The size of Link in bytes. */
+        const val SIZE_BYTES: Int = 3
+
+        /** The byte offset of node. */
+        const val NODE_OFFSET: Int = 0
+
+        /** The byte offset of mode. */
+        const val MODE_OFFSET: Int = 2
+
+        /** Returns a view of Link from the position of [buffer]. */
+        fun wrap(buffer: ByteBuffer): Link = Link(buffer.view(buffer.position(), SIZE_BYTES))
+
+        /** Returns a view of Link in a new, zeroed buffer. */
+        fun allocate(): Link = Link(ByteBuffer.allocate(SIZE_BYTES))
+    }
+}
+
+/** Hop, viewed in [buffer] from its index 0. */
+@JvmInline
+value class Hop(val buffer: ByteBuffer) {
+    /** Accessor for link. */
+    val link: Link
+        get() = Link(buffer.view(LINK_OFFSET, Link.SIZE_BYTES))
+
+    companion object {
+        /** The size of Hop in bytes. */
+        const val SIZE_BYTES: Int = 3
+
+        /** The byte offset of link. */
+        const val LINK_OFFSET: Int = 0
+
+        /** Returns a view of Hop from the position of [buffer]. */
+        fun wrap(buffer: ByteBuffer): Hop = Hop(buffer.view(buffer.position(), SIZE_BYTES))
+
+        /** Returns a view of Hop in a new, zeroed buffer. */
+        fun allocate(): Hop = Hop(ByteBuffer.allocate(SIZE_BYTES))
+    }
+}
+
+/** Route, viewed in [buffer] from its index 0. */
+@JvmInline
+value class Route(val buffer: ByteBuffer) {
+    /** The number of elements of hops in use. */
+    var hopsCount: Int
+        get() = buffer.readBits(HOPS_OFFSET, 1).toInt()
+        set(value) {
+            require(value in 0..2) { "hops holds at most 2 elements" }
+            buffer.writeBits(HOPS_OFFSET, 1, value.toLong())
+        }
+
+    /** Returns element [index] of hops. */
+    fun hops(index: Int): Hop = Hop(buffer.view(HOPS_OFFSET + 1 + 3 * checkIndex(index, hopsCount), Hop.SIZE_BYTES))
+
+    companion object {
+        /** The size of Route in bytes. */
+        const val SIZE_BYTES: Int = 7
+
+        /** The byte offset of hops. */
+        const val HOPS_OFFSET: Int = 0
+
+        /** Returns a view of Route from the position of [buffer]. */
+        fun wrap(buffer: ByteBuffer): Route = Route(buffer.view(buffer.position(), SIZE_BYTES))
+
+        /** Returns a view of Route in a new, zeroed buffer. */
+        fun allocate(): Route = Route(ByteBuffer.allocate(SIZE_BYTES))
+    }
+}
+
+/** Frame, viewed in [buffer] from its index 0. */
+@JvmInline
+value class Frame(val buffer: ByteBuffer) {
+    /** Accessor for route. */
+    val route: Route
+        get() = Route(buffer.view(ROUTE_OFFSET, Route.SIZE_BYTES))
+
+    /** Accessor for tail. */
+    val tail: Leaf
+        get() = Leaf(buffer.view(TAIL_OFFSET, Leaf.SIZE_BYTES))
+
+    companion object {
+        /** The size of Frame in bytes. */
+        const val SIZE_BYTES: Int = 9
+
+        /** The byte offset of route. */
+        const val ROUTE_OFFSET: Int = 0
+
+        /** The byte offset of tail. */
+        const val TAIL_OFFSET: Int = 7
+
+        /** Returns a view of Frame from the position of [buffer]. */
+        fun wrap(buffer: ByteBuffer): Frame = Frame(buffer.view(buffer.position(), SIZE_BYTES))
+
+        /** Returns a view of Frame in a new, zeroed buffer. */
+        fun allocate(): Frame = Frame(ByteBuffer.allocate(SIZE_BYTES))
+    }
+}
@@ -0,0 +1,71 @@
+% Automatically generated by Onyx IDL compiler. Do not edit.
+function deep()
+%DEEP Defines the Simulink enums and bus objects of the schema.
+%   Run deep before loading a model that uses them. Each bus object is
+%   assigned in the base workspace, named after its struct or message.
+
+% Leaf: 2 bytes.
+elements = Simulink.BusElement.empty;
+elements(end + 1) = element('value', 'uint16', 1, 'byte 0');
+bus = Simulink.Bus;
+bus.Description = 'Leaf: 2 bytes.';
+bus.Elements = elements;
+assignin('base', 'Leaf', bus);
+
+% Node: 2 bytes.
+elements = Simulink.BusElement.empty;
+elements(end + 1) = element('leaf', 'Bus: Leaf', 1, 'byte 0');
+bus = Simulink.Bus;
+bus.Description = 'Node: 2 bytes.';
+bus.Elements = elements;
+assignin('base', 'Node', bus);
+
+% Mode, carried on the wire as u8.
+Simulink.defineIntEnumType('Mode', {'Direct', 'Relay'}, [0; 1], ...
+    'StorageType', 'uint8', 'Description', 'Mode, carried on the wire as u8.');
+
+% Link: 3 bytes.
+elements = Simulink.BusElement.empty;
+elements(end + 1) = element('node', 'Bus: Node', 1, 'byte 0');
+elements(end + 1) = element('mode', 'Enum: Mode', 1, 'byte 2');
+bus = Simulink.Bus;
+bus.Description = 'Link: 3 bytes.';
+bus.Elements = elements;
+assignin('base', 'Link', bus);
+
+% Hop: 3 bytes.
+elements = Simulink.BusElement.empty;
+elements(end + 1) = element('link', 'Bus: Link', 1, 'byte 0');
+bus = Simulink.Bus;
+bus.Description = 'Hop: 3 bytes.';
+bus.Elements = elements;
+assignin('base', 'Hop', bus);
+
+% Route: 7 bytes.
+elements = Simulink.BusElement.empty;
+elements(end + 1) = element('hops_count', 'uint8', 1, 'byte 0; the number of elements of hops in use');
+elements(end + 1) = element('hops', 'Bus: Hop', 2, 'byte 1');
+bus = Simulink.Bus;
+bus.Description = 'Route: 7 bytes.';
+bus.Elements = elements;
+assignin('base', 'Route', bus);
+
+% Frame: 9 bytes.
+elements = Simulink.BusElement.empty;
+elements(end + 1) = element('route', 'Bus: Route', 1, 'byte 0');
+elements(end + 1) = element('tail', 'Bus: Leaf', 1, 'byte 7');
+bus = Simulink.Bus;
+bus.Description = 'Frame: 9 bytes.';
+bus.Elements = elements;
+assignin('base', 'Frame', bus);
+
+end
+
+function e = element(name, type, dimensions, description)
+% Returns a bus element.
+e = Simulink.BusElement;
+e.Name = name;
+e.DataType = type;
+e.Dimensions = dimensions;
+e.Description = description;
+end
@@ -0,0 +1,521 @@
+// Automatically generated by Onyx IDL compiler. Do not edit.
+
+use std::convert::TryInto;
+
+
+/// Size of Leaf in bytes.
+pub(crate) const LEAF_SIZE: usize = 2;
+/// Buffer type alias for Leaf.
+pub type LeafBuffer = [u8; LEAF_SIZE];
+
+/// Read-only view of Leaf.
+#[derive(Debug, Clone, Copy)]
+pub struct LeafView<'a> {
+    data: &'a LeafBuffer,
+}
+
+/// Read-write view of Leaf.
+#[derive(Debug)]
+pub struct LeafMutView<'a> {
+    data: &'a mut LeafBuffer,
+}
+
+impl<'a> LeafView<'a> {
+    /// Size of Leaf in bytes.
+    pub const SIZE_BYTES: usize = LEAF_SIZE;
+    /// Byte offset of value.
+    pub const FIELD_OFFSET_VALUE: usize = 0;
+
+    /// Creates a new LeafView from a LeafBuffer.
+    pub fn new(data: &'a LeafBuffer) -> Self {
+        Self { data }
+    }
+
+    /// Accessor for value.
+    pub fn value(&self) -> u16 {
+        let bytes = self.data[0..2].try_into().unwrap();
+        u16::from_be_bytes(bytes)
+    }
+}
+
+// Fails to compile if Leaf does not match its Onyx layout.
+const _: () = assert!(LeafView::FIELD_OFFSET_VALUE + std::mem::size_of::<u16>() == LeafView::SIZE_BYTES, "Leaf does not match its Onyx size");
+
+impl<'a> LeafMutView<'a> {
+    /// Creates a new LeafMutView from a LeafBuffer.
+    pub fn new(data: &'a mut LeafBuffer) -> Self {
+        Self { data }
+    }
+
+    /// Accessor for value.
+    pub fn value(&self) -> u16 {
+        let bytes = self.data[0..2].try_into().unwrap();
+        u16::from_be_bytes(bytes)
+    }
+
+    /// Mutator for value.
+    pub fn set_value(&mut self, value: u16) {
+        let bytes = value.to_be_bytes();
+        self.data[0..2].copy_from_slice(&bytes);
+    }
+}
+
+/// Size of Node in bytes.
+pub(crate) const NODE_SIZE: usize = 2;
+/// Buffer type alias for Node.
+pub type NodeBuffer = [u8; NODE_SIZE];
+
+/// Read-only view of Node.
+#[derive(Debug, Clone, Copy)]
+pub struct NodeView<'a> {
+    data: &'a NodeBuffer,
+}
+
+/// Read-write view of Node.
+#[derive(Debug)]
+pub struct NodeMutView<'a> {
+    data: &'a mut NodeBuffer,
+}
+
+impl<'a> NodeView<'a> {
+    /// Size of Node in bytes.
+    pub const SIZE_BYTES: usize = NODE_SIZE;
+    /// Byte offset of leaf.
+    pub const FIELD_OFFSET_LEAF: usize = 0;
+
+    /// Creates a new NodeView from a NodeBuffer.
+    pub fn new(data: &'a NodeBuffer) -> Self {
+        Self { data }
+    }
+
+    /// Accessor for leaf.
+    pub fn leaf(&self) -> LeafView {
+        let slice = &self.data[0..2];
+        LeafView::new(slice.try_into().unwrap())
+    }
+}
+
+// Fails to compile if Node does not match its Onyx layout.
+const _: () = assert!(NodeView::FIELD_OFFSET_LEAF + LEAF_SIZE == NodeView::SIZE_BYTES, "Node does not match its Onyx size");
+
+impl<'a> NodeMutView<'a> {
+    /// Creates a new NodeMutView from a NodeBuffer.
+    pub fn new(data: &'a mut NodeBuffer) -> Self {
+        Self { data }
+    }
+
+    /// Accessor for leaf.
+    pub fn leaf(&self) -> LeafView {
+        let slice = &self.data[0..2];
+        LeafView::new(slice.try_into().unwrap())
+    }
+
+    /// Mutable accessor for leaf.
+    pub fn leaf_mut(&mut self) -> LeafMutView {
+        let slice = &mut self.data[0..2];
+        LeafMutView::new(slice.try_into().unwrap())
+    }
+}
+#[repr(u8)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mode {
+    Direct = 0,
+    Relay = 1,
+}
+
+impl Default for Mode {
+    fn default() -> Self {
+        Self::Direct
+    }
+}
+
+impl TryFrom<u8> for Mode {
+    type Error = u8;
+
+    fn try_from(raw: u8) -> Result<Self, u8> {
+        match raw {
+            0 => Ok(Self::Direct),
+            1 => Ok(Self::Relay),
+            _ => Err(raw),
+        }
+    }
+}
+
+impl From<Mode> for u8 {
+    fn from(value: Mode) -> Self {
+        value as u8
+    }
+}
+
+/// Size of Link in bytes.
+pub(crate) const LINK_SIZE: usize = 3;
+/// Buffer type alias for Link.
+pub type LinkBuffer = [u8; LINK_SIZE];
+
+/// Read-only view of Link.
+#[derive(Debug, Clone, Copy)]
+pub struct LinkView<'a> {
+    data: &'a LinkBuffer,
+}
+
+/// Read-write view of Link.
+#[derive(Debug)]
+pub struct LinkMutView<'a> {
+    data: &'a mut LinkBuffer,
+}
+
+impl<'a> LinkView<'a> {
+    /// Size of Link in bytes.
+    pub const SIZE_BYTES: usize = LINK_SIZE;
+    /// Byte offset of node.
+    pub const FIELD_OFFSET_NODE: usize = 0;
+    /// Byte offset of mode.
+    pub const FIELD_OFFSET_MODE: usize = 2;
+
+    /// Creates a new LinkView from a LinkBuffer.
+    pub fn new(data: &'a LinkBuffer) -> Self {
+        Self { data }
+    }
+
+    /// Accessor for node.
+    pub fn node(&self) -> NodeView {
+        let slice = &self.data[0..2];
+        NodeView::new(slice.try_into().unwrap())
+    }
+
+    /// Accessor for mode.
+    pub fn mode(&self) -> Result<Mode, u8> {
+        let bytes = self.data[2..3].try_into().unwrap();
+        let val = u8::from_be_bytes(bytes);
+        Mode::try_from(val)
+    }
+}
+
+// Fails to compile if Link does not match its Onyx layout.
+const _: () = assert!(LinkView::FIELD_OFFSET_MODE == LinkView::FIELD_OFFSET_NODE + NODE_SIZE, "Link.mode does not match its Onyx offset");
+const _: () = assert!(LinkView::FIELD_OFFSET_MODE + std::mem::size_of::<u8>() == LinkView::SIZE_BYTES, "Link does not match its Onyx size");
+
+impl<'a> LinkMutView<'a> {
+    /// Creates a new LinkMutView from a LinkBuffer.
+    pub fn new(data: &'a mut LinkBuffer) -> Self {
+        Self { data }
+    }
+
+    /// Accessor for node.
+    pub fn node(&self) -> NodeView {
+        let slice = &self.data[0..2];
+        NodeView::new(slice.try_into().unwrap())
+    }
+
+    /// Mutable accessor for node.
+    pub fn node_mut(&mut self) -> NodeMutView {
+        let slice = &mut self.data[0..2];
+        NodeMutView::new(slice.try_into().unwrap())
+    }
+
+    /// Accessor for mode.
+    pub fn mode(&self) -> Result<Mode, u8> {
+        let bytes = self.data[2..3].try_into().unwrap();
+        let val = u8::from_be_bytes(bytes);
+        Mode::try_from(val)
+    }
+
+    /// Mutator for mode.
+    pub fn set_mode(&mut self, value: Mode) {
+        let val = u8::from(value);
+        let bytes = val.to_be_bytes();
+        self.data[2..3].copy_from_slice(&bytes);
+    }
+}
+
+/// Size of Hop in bytes.
+pub(crate) const HOP_SIZE: usize = 3;
+/// Buffer type alias for Hop.
+pub type HopBuffer = [u8; HOP_SIZE];
+
+/// Read-only view of Hop.
+#[derive(Debug, Clone, Copy)]
+pub struct HopView<'a> {
+    data: &'a HopBuffer,
+}
+
+/// Read-write view of Hop.
+#[derive(Debug)]
+pub struct HopMutView<'a> {
+    data: &'a mut HopBuffer,
+}
+
+impl<'a> HopView<'a> {
+    /// Size of Hop in bytes.
+    pub const SIZE_BYTES: usize = HOP_SIZE;
+    /// Byte offset of link.
+    pub const FIELD_OFFSET_LINK: usize = 0;
+
+    /// Creates a new HopView from a HopBuffer.
+    pub fn new(data: &'a HopBuffer) -> Self {
+        Self { data }
+    }
+
+    /// Accessor for link.
+    pub fn link(&self) -> LinkView {
+        let slice = &self.data[0..3];
+        LinkView::new(slice.try_into().unwrap())
+    }
+}
+
+// Fails to compile if Hop does not match its Onyx layout.
+const _: () = assert!(HopView::FIELD_OFFSET_LINK + LINK_SIZE == HopView::SIZE_BYTES, "Hop does not match its Onyx size");
+
+impl<'a> HopMutView<'a> {
+    /// Creates a new HopMutView from a HopBuffer.
+    pub fn new(data: &'a mut HopBuffer) -> Self {
+        Self { data }
+    }
+
+    /// Accessor for link.
+    pub fn link(&self) -> LinkView {
+        let slice = &self.data[0..3];
+        LinkView::new(slice.try_into().unwrap())
+    }
+
+    /// Mutable accessor for link.
+    pub fn link_mut(&mut self) -> LinkMutView {
+        let slice = &mut self.data[0..3];
+        LinkMutView::new(slice.try_into().unwrap())
+    }
+}
+
+/// Size of Route in bytes.
+pub(crate) const ROUTE_SIZE: usize = 7;
+/// Buffer type alias for Route.
+pub type RouteBuffer = [u8; ROUTE_SIZE];
+
+/// Read-only view of Route.
+#[derive(Debug, Clone, Copy)]
+pub struct RouteView<'a> {
+    data: &'a RouteBuffer,
+}
+
+/// Read-write view of Route.
+#[derive(Debug)]
+pub struct RouteMutView<'a> {
+    data: &'a mut RouteBuffer,
+}
+
+impl<'a> RouteView<'a> {
+    /// Size of Route in bytes.
+    pub const SIZE_BYTES: usize = ROUTE_SIZE;
+    /// Byte offset of hops.
+    pub const FIELD_OFFSET_HOPS: usize = 0;
+
+    /// Creates a new RouteView from a RouteBuffer.
+    pub fn new(data: &'a RouteBuffer) -> Self {
+        Self { data }
+    }
+
+    /// Accessor for hops.
+    pub fn hops(&self) -> RouteHopsView {
+        let slice = &self.data[0..7];
+        RouteHopsView { data: slice.try_into().unwrap() }
+    }
+}
+
+// Fails to compile if Route does not match its Onyx layout.
+const _: () = assert!(RouteView::FIELD_OFFSET_HOPS + (std::mem::size_of::<u8>() + 2 * HOP_SIZE) == RouteView::SIZE_BYTES, "Route does not match its Onyx size");
+
+impl<'a> RouteMutView<'a> {
+    /// Creates a new RouteMutView from a RouteBuffer.
+    pub fn new(data: &'a mut RouteBuffer) -> Self {
+        Self { data }
+    }
+
+    /// Accessor for hops.
+    pub fn hops(&self) -> RouteHopsView {
+        let slice = &self.data[0..7];
+        RouteHopsView { data: slice.try_into().unwrap() }
+    }
+
+    /// Mutable accessor for hops.
+    pub fn hops_mut(&mut self) -> RouteHopsMutView {
+        let slice = &mut self.data[0..7];
+        RouteHopsMutView { data: slice.try_into().unwrap() }
+    }
+}
+
+/// Read-only view of the bounded vector Route.hops.
+#[derive(Debug, Clone, Copy)]
+pub struct RouteHopsView<'a> {
+    data: &'a [u8; 7],
+}
+
+impl<'a> RouteHopsView<'a> {
+    /// The maximum number of elements.
+    pub const CAPACITY: usize = 2;
+
+    /// Returns the number of elements, clamped to the capacity.
+    pub fn len(&self) -> usize {
+        (self.data[0] as usize).min(Self::CAPACITY)
+    }
+
+    /// Returns true if the vector has no elements.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the element at `index`, or `None` if it is out of range.
+    pub fn get(&self, index: usize) -> Option<HopView<'a>> {
+        if index >= self.len() {
+            return None;
+        }
+        let data: &'a [u8; 7] = self.data;
+        Some(HopView::new(data[1 + index * 3..1 + index * 3 + 3].try_into().unwrap()))
+    }
+
+    /// Returns an iterator over the elements.
+    pub fn iter(&self) -> impl Iterator<Item = HopView<'a>> + 'a {
+        let view = *self;
+        (0..self.len()).map(move |index| view.get(index).unwrap())
+    }
+}
+
+/// Read-write view of the bounded vector Route.hops.
+#[derive(Debug)]
+pub struct RouteHopsMutView<'a> {
+    data: &'a mut [u8; 7],
+}
+
+impl<'a> RouteHopsMutView<'a> {
+    /// The maximum number of elements.
+    pub const CAPACITY: usize = 2;
+
+    /// Returns a read-only view of the vector.
+    pub fn as_view(&self) -> RouteHopsView<'_> {
+        RouteHopsView { data: self.data }
+    }
+
+    /// Returns the number of elements, clamped to the capacity.
+    pub fn len(&self) -> usize {
+        self.as_view().len()
+    }
+
+    /// Returns true if the vector has no elements.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the element at `index`, or `None` if it is out of range.
+    pub fn get(&self, index: usize) -> Option<HopView<'_>> {
+        self.as_view().get(index)
+    }
+
+    /// Returns an iterator over the elements.
+    pub fn iter(&self) -> impl Iterator<Item = HopView<'_>> + '_ {
+        self.as_view().iter()
+    }
+
+    fn set_len(&mut self, len: usize) {
+        self.data[0] = len as u8;
+    }
+
+    /// Removes all elements.
+    pub fn clear(&mut self) {
+        self.set_len(0);
+    }
+
+    /// Returns a mutable view of the element at `index`, or `None` if it is out of range.
+    pub fn get_mut(&mut self, index: usize) -> Option<HopMutView<'_>> {
+        if index >= self.len() {
+            return None;
+        }
+        Some(HopMutView::new((&mut self.data[1 + index * 3..1 + index * 3 + 3]).try_into().unwrap()))
+    }
+
+    /// Appends a zeroed element and returns a mutable view of it, or `None` if the vector is full.
+    pub fn push(&mut self) -> Option<HopMutView<'_>> {
+        let index = self.len();
+        if index == Self::CAPACITY {
+            return None;
+        }
+        self.set_len(index + 1);
+        self.data[1 + index * 3..1 + index * 3 + 3].fill(0);
+        self.get_mut(index)
+    }
+}
+
+/// Size of Frame in bytes.
+pub(crate) const FRAME_SIZE: usize = 9;
+/// Buffer type alias for Frame.
+pub type FrameBuffer = [u8; FRAME_SIZE];
+
+/// Read-only view of Frame.
+#[derive(Debug, Clone, Copy)]
+pub struct FrameView<'a> {
+    data: &'a FrameBuffer,
+}
+
+/// Read-write view of Frame.
+#[derive(Debug)]
+pub struct FrameMutView<'a> {
+    data: &'a mut FrameBuffer,
+}
+
+impl<'a> FrameView<'a> {
+    /// Size of Frame in bytes.
+    pub const SIZE_BYTES: usize = FRAME_SIZE;
+    /// Byte offset of route.
+    pub const FIELD_OFFSET_ROUTE: usize = 0;
+    /// Byte offset of tail.
+    pub const FIELD_OFFSET_TAIL: usize = 7;
+
+    /// Creates a new FrameView from a FrameBuffer.
+    pub fn new(data: &'a FrameBuffer) -> Self {
+        Self { data }
+    }
+
+    /// Accessor for route.
+    pub fn route(&self) -> RouteView {
+        let slice = &self.data[0..7];
+        RouteView::new(slice.try_into().unwrap())
+    }
+
+    /// Accessor for tail.
+    pub fn tail(&self) -> LeafView {
+        let slice = &self.data[7..9];
+        LeafView::new(slice.try_into().unwrap())
+    }
+}
+
+// Fails to compile if Frame does not match its Onyx layout.
+const _: () = assert!(FrameView::FIELD_OFFSET_TAIL == FrameView::FIELD_OFFSET_ROUTE + ROUTE_SIZE, "Frame.tail does not match its Onyx offset");
+const _: () = assert!(FrameView::FIELD_OFFSET_TAIL + LEAF_SIZE == FrameView::SIZE_BYTES, "Frame does not match its Onyx size");
+
+impl<'a> FrameMutView<'a> {
+    /// Creates a new FrameMutView from a FrameBuffer.
+    pub fn new(data: &'a mut FrameBuffer) -> Self {
+        Self { data }
+    }
+
+    /// Accessor for route.
+    pub fn route(&self) -> RouteView {
+        let slice = &self.data[0..7];
+        RouteView::new(slice.try_into().unwrap())
+    }
+
+    /// Mutable accessor for route.
+    pub fn route_mut(&mut self) -> RouteMutView {
+        let slice = &mut self.data[0..7];
+        RouteMutView::new(slice.try_into().unwrap())
+    }
+
+    /// Accessor for tail.
+    pub fn tail(&self) -> LeafView {
+        let slice = &self.data[7..9];
+        LeafView::new(slice.try_into().unwrap())
+    }
+
+    /// Mutable accessor for tail.
+    pub fn tail_mut(&mut self) -> LeafMutView {
+        let slice = &mut self.data[7..9];
+        LeafMutView::new(slice.try_into().unwrap())
+    }
+}
@@ -0,0 +1,71 @@
+// Automatically generated by Onyx IDL compiler. Do not edit.
+`ifndef ONYX_DEEP_PKG_SV
+`define ONYX_DEEP_PKG_SV
+
+package deep_pkg;
+
+  // Leaf: 2 bytes.
+  localparam int Leaf_SIZE_BYTES = 2;
+  localparam int Leaf_value_OFFSET = 0;
+  localparam int Leaf_value_WIDTH = 16;
+  typedef struct packed {
+    logic [15:0] value;
+  } Leaf;
+
+  // Node: 2 bytes.
+  localparam int Node_SIZE_BYTES = 2;
+  localparam int Node_leaf_OFFSET = 0;
+  localparam int Node_leaf_WIDTH = 16;
+  typedef struct packed {
+    Leaf leaf;
+  } Node;
+
+  // Mode, carried on the wire as u8.
+  typedef enum logic [7:0] {
+    Mode_Direct = 8'd0,
+    Mode_Relay = 8'd1
+  } Mode;
+
+  // Link: 3 bytes.
+  localparam int Link_SIZE_BYTES = 3;
+  localparam int Link_node_OFFSET = 0;
+  localparam int Link_node_WIDTH = 16;
+  localparam int Link_mode_OFFSET = 2;
+  localparam int Link_mode_WIDTH = 8;
+  typedef struct packed {
+    Node node;
+    Mode mode;
+  } Link;
+
+  // Hop: 3 bytes.
+  localparam int Hop_SIZE_BYTES = 3;
+  localparam int Hop_link_OFFSET = 0;
+  localparam int Hop_link_WIDTH = 24;
+  typedef struct packed {
+    Link link;
+  } Hop;
+
+  // Route: 7 bytes.
+  localparam int Route_SIZE_BYTES = 7;
+  localparam int Route_hops_OFFSET = 0;
+  localparam int Route_hops_WIDTH = 56;
+  localparam int Route_hops_CAPACITY = 2;
+  typedef struct packed {
+    logic [7:0] hops_count;
+    Hop [0:1] hops;
+  } Route;
+
+  // Frame: 9 bytes.
+  localparam int Frame_SIZE_BYTES = 9;
+  localparam int Frame_route_OFFSET = 0;
+  localparam int Frame_route_WIDTH = 56;
+  localparam int Frame_tail_OFFSET = 7;
+  localparam int Frame_tail_WIDTH = 16;
+  typedef struct packed {
+    Route route;
+    Leaf tail;
+  } Frame;
+
+endpackage
+
+`endif // ONYX_DEEP_PKG_SV
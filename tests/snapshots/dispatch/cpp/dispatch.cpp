@@ -0,0 +1,86 @@
+// Automatically generated by Onyx IDL compiler. Do not edit.
+#include "dispatch.hpp"
+
+#include <string.h>
+
+namespace onyx {
+
+Ping* Ping::Deserialize(Buffer& buffer) {
+    // Overlay the class structure onto the buffer memory. This is the zero-copy step.
+    Ping* result = (Ping*)buffer;
+
+    // SWAP: Primitive field seq
+    result->__raw_seq = utils::byteswap_if_needed(result->__raw_seq);
+
+    return result;
+}
+
+void Ping::Serialize(Buffer& buffer) const {
+    // Copy the host-endian object into the buffer first.
+    memcpy(buffer, this, kSizeOf);
+    Ping* wire_format_data = (Ping*)buffer;
+
+    // SWAP: Primitive field seq
+    wire_format_data->__raw_seq = utils::byteswap_if_needed(wire_format_data->__raw_seq);
+}
+
+Command* Command::Deserialize(Buffer& buffer) {
+    // Overlay the class structure onto the buffer memory. This is the zero-copy step.
+    Command* result = (Command*)buffer;
+    bool known = true;
+
+    // SWAP: Primitive field arg
+    result->__raw_arg = utils::byteswap_if_needed(result->__raw_arg);
+    // CHECK: Closed enum field kind
+    known = is_known(result->__raw_kind) && known;
+
+    return known ? result : nullptr;
+}
+
+void Command::Serialize(Buffer& buffer) const {
+    // Copy the host-endian object into the buffer first.
+    memcpy(buffer, this, kSizeOf);
+    Command* wire_format_data = (Command*)buffer;
+
+    // SWAP: Primitive field arg
+    wire_format_data->__raw_arg = utils::byteswap_if_needed(wire_format_data->__raw_arg);
+}
+
+Local* Local::Deserialize(Buffer& buffer) {
+    // Overlay the class structure onto the buffer memory. This is the zero-copy step.
+    Local* result = (Local*)buffer;
+
+    // INFO: Primitive field value (1 byte), no swap needed.
+
+    return result;
+}
+
+void Local::Serialize(Buffer& buffer) const {
+    // Copy the host-endian object into the buffer first.
+    memcpy(buffer, this, kSizeOf);
+    Local* wire_format_data = (Local*)buffer;
+
+    // INFO: Primitive field value (1 byte), no swap needed.
+}
+
+bool DecodeAny(uint8_t* buffer, size_t size, AnyMessage& out) {
+    if (size < kMessageIdSize) return false;
+    uint16_t raw;
+    memcpy(&raw, buffer, kMessageIdSize);
+    out.id = static_cast<MessageId>(utils::byteswap_if_needed(raw));
+    uint8_t* body = buffer + kMessageIdSize;
+    size -= kMessageIdSize;
+    switch (out.id) {
+    case MessageId::Ping:
+        if (size < Ping::kSizeOf) return false;
+        out.message = Ping::Deserialize(*reinterpret_cast<Ping::Buffer*>(body));
+        return out.message != nullptr;
+    case MessageId::Command:
+        if (size < Command::kSizeOf) return false;
+        out.message = Command::Deserialize(*reinterpret_cast<Command::Buffer*>(body));
+        return out.message != nullptr;
+    }
+    return false;
+}
+
+} // namespace onyx
@@ -0,0 +1,377 @@
+// Automatically generated by Onyx IDL compiler. Do not edit.
+#ifndef ONYX_DISPATCH_H_
+#define ONYX_DISPATCH_H_
+
+#include <stddef.h>
+#include <stdint.h>
+#include <string.h>
+
+#pragma pack(push, 1)
+
+namespace onyx {
+#ifndef ONYX_UTILS_ENDIANNESS_BIG_
+#define ONYX_UTILS_ENDIANNESS_BIG_
+
+namespace utils {
+
+// Define network endianness (usually Big Endian).
+#define ONYX_BIG_ENDIAN 4321
+#define ONYX_LITTLE_ENDIAN 1234
+#define ONYX_NETWORK_ORDER ONYX_BIG_ENDIAN
+
+// Determine host endianness at compile time (using standard GCC/Clang macros)
+#ifdef __BYTE_ORDER__
+    #if __BYTE_ORDER__ == __ORDER_BIG_ENDIAN__
+        #define ONYX_HOST_ORDER ONYX_BIG_ENDIAN
+    #elif __BYTE_ORDER__ == __ORDER_LITTLE_ENDIAN__
+        #define ONYX_HOST_ORDER ONYX_LITTLE_ENDIAN
+    #else
+        #error "Unsupported host endianness"
+    #endif
+#elif defined(_WIN32) || defined(__LITTLE_ENDIAN__)
+    // Fallback for Windows (always little-endian) or other platforms
+    #define ONYX_HOST_ORDER ONYX_LITTLE_ENDIAN
+#else
+    #error "Could not determine host endianness"
+#endif
+
+// Helper function to swap bytes (Explicit Overloads)
+inline uint16_t byteswap(uint16_t value) { return ((value << 8) & 0xFF00) | ((value >> 8) & 0x00FF); }
+
+inline int16_t byteswap(int16_t value) { return (int16_t)byteswap((uint16_t)value); }
+
+inline uint32_t byteswap(uint32_t value) { return (value << 24) | ((value & 0x00FF0000) >> 8) | ((value & 0x0000FF00) << 8) | (value >> 24); }
+
+inline int32_t byteswap(int32_t value) { return (int32_t)byteswap((uint32_t)value); }
+
+inline uint64_t byteswap(uint64_t value) { return (value << 56) | ((value & 0x00FF000000000000ULL) >> 40) | ((value & 0x0000FF0000000000ULL) >> 24) | ((value & 0x000000FF00000000ULL) >> 8) | ((value & 0x00000000FF000000ULL) << 8) | ((value & 0x0000000000FF0000ULL) << 24) | ((value & 0x000000000000FF00ULL) << 40) | (value >> 56); }
+
+inline int64_t byteswap(int64_t value) { return (int64_t)byteswap((uint64_t)value); }
+
+inline float byteswap(float value) {
+    uint32_t temp;
+    memcpy(&temp, &value, sizeof(value));
+    temp = byteswap(temp);
+    memcpy(&value, &temp, sizeof(value));
+    return value;
+}
+
+inline double byteswap(double value) {
+    uint64_t temp;
+    memcpy(&temp, &value, sizeof(value));
+    temp = byteswap(temp);
+    memcpy(&value, &temp, sizeof(value));
+    return value;
+}
+
+// Swaps bytes if the host order does not match the network order (Explicit Overloads)
+inline uint16_t byteswap_if_needed(uint16_t value) {
+    if (ONYX_HOST_ORDER != ONYX_NETWORK_ORDER) {
+        return byteswap(value);
+    }
+    return value;
+}
+
+inline int16_t byteswap_if_needed(int16_t value) {
+    if (ONYX_HOST_ORDER != ONYX_NETWORK_ORDER) {
+        return byteswap(value);
+    }
+    return value;
+}
+
+inline uint32_t byteswap_if_needed(uint32_t value) {
+    if (ONYX_HOST_ORDER != ONYX_NETWORK_ORDER) {
+        return byteswap(value);
+    }
+    return value;
+}
+
+inline int32_t byteswap_if_needed(int32_t value) {
+    if (ONYX_HOST_ORDER != ONYX_NETWORK_ORDER) {
+        return byteswap(value);
+    }
+    return value;
+}
+
+inline uint64_t byteswap_if_needed(uint64_t value) {
+    if (ONYX_HOST_ORDER != ONYX_NETWORK_ORDER) {
+        return byteswap(value);
+    }
+    return value;
+}
+
+inline int64_t byteswap_if_needed(int64_t value) {
+    if (ONYX_HOST_ORDER != ONYX_NETWORK_ORDER) {
+        return byteswap(value);
+    }
+    return value;
+}
+
+inline float byteswap_if_needed(float value) {
+    if (ONYX_HOST_ORDER != ONYX_NETWORK_ORDER) {
+        return byteswap(value);
+    }
+    return value;
+}
+
+inline double byteswap_if_needed(double value) {
+    if (ONYX_HOST_ORDER != ONYX_NETWORK_ORDER) {
+        return byteswap(value);
+    }
+    return value;
+}
+
+} // namespace utils
+#endif // ONYX_UTILS_ENDIANNESS_BIG_
+
+/// Size of the ID that precedes an identified message, in bytes.
+static const size_t kMessageIdSize = 2;
+
+/// The IDs of the messages that DecodeAny dispatches on.
+enum class MessageId : uint16_t {
+    Ping = 1,
+    Command = 257,
+};
+
+/// Writes a message ID to the first kMessageIdSize bytes of the buffer.
+inline void WriteMessageId(MessageId id, uint8_t* buffer) {
+    uint16_t raw = utils::byteswap_if_needed(static_cast<uint16_t>(id));
+    memcpy(buffer, &raw, kMessageIdSize);
+}
+
+class Ping;
+class Command;
+class Local;
+
+enum class Kind : uint8_t {
+    Start,
+    Stop = 1,
+};
+
+/// Returns true if `value` is declared by a variant of Kind.
+inline bool is_known(Kind value) {
+    return value == Kind::Start || value == Kind::Stop;
+}
+
+class Ping {
+private:
+    uint16_t __raw_seq;
+
+public:
+    static const size_t kSizeOf = 2;
+    using Buffer = uint8_t[kSizeOf];
+
+    /// Byte offsets of the fields within the buffer.
+    static const size_t kSeqOffset = 0;
+
+    /// The ID that precedes Ping on the wire.
+    static const MessageId kId = MessageId::Ping;
+
+    /// Accessor for seq
+    inline const uint16_t seq() const { return __raw_seq; }
+
+    /// Mutator for seq
+    inline void seq(const uint16_t value) { __raw_seq = value; }
+
+    /// Deserializes the network-endian buffer to Ping in-place by casting
+    /// and applying endianness correction to match the host-endianness.
+    /// NOTE: This mutates the input buffer to host-endianness.
+    /// Returns a pointer to the object within the buffer.
+    static Ping* Deserialize(Buffer& buffer);
+
+    /// Serializes the host-endian object into the provided buffer,
+    /// applying endianness correction to match the network-endianness.
+    void Serialize(Buffer& buffer) const;
+
+private:
+    /// Fails to compile if Ping does not match its Onyx layout.
+    static inline void __check_layout() {
+        static_assert(sizeof(Ping) == kSizeOf, "Ping does not match its Onyx size");
+        static_assert(offsetof(Ping, __raw_seq) == kSeqOffset, "Ping::seq does not match its Onyx offset");
+    }
+};
+
+class Command {
+private:
+    Kind __raw_kind;
+    uint32_t __raw_arg;
+
+public:
+    static const size_t kSizeOf = 5;
+    using Buffer = uint8_t[kSizeOf];
+
+    /// Byte offsets of the fields within the buffer.
+    static const size_t kKindOffset = 0;
+    static const size_t kArgOffset = 1;
+
+    /// The ID that precedes Command on the wire.
+    static const MessageId kId = MessageId::Command;
+
+    /// Accessor for kind
+    inline const Kind kind() const { return __raw_kind; }
+
+    /// Mutator for kind
+    inline void kind(const Kind value) { __raw_kind = value; }
+
+    /// Accessor for arg
+    inline const uint32_t arg() const { return __raw_arg; }
+
+    /// Mutator for arg
+    inline void arg(const uint32_t value) { __raw_arg = value; }
+
+    /// Deserializes the network-endian buffer to Command in-place by casting
+    /// and applying endianness correction to match the host-endianness.
+    /// NOTE: This mutates the input buffer to host-endianness.
+    /// Returns a pointer to the object within the buffer.
+    /// Returns nullptr if a closed enum holds a value that no variant declares;
+    /// the buffer is converted either way.
+    static Command* Deserialize(Buffer& buffer);
+
+    /// Serializes the host-endian object into the provided buffer,
+    /// applying endianness correction to match the network-endianness.
+    void Serialize(Buffer& buffer) const;
+
+private:
+    /// Fails to compile if Command does not match its Onyx layout.
+    static inline void __check_layout() {
+        static_assert(sizeof(Command) == kSizeOf, "Command does not match its Onyx size");
+        static_assert(offsetof(Command, __raw_kind) == kKindOffset, "Command::kind does not match its Onyx offset");
+        static_assert(offsetof(Command, __raw_arg) == kArgOffset, "Command::arg does not match its Onyx offset");
+    }
+};
+
+class Local {
+private:
+    uint8_t __raw_value;
+
+public:
+    static const size_t kSizeOf = 1;
+    using Buffer = uint8_t[kSizeOf];
+
+    /// Byte offsets of the fields within the buffer.
+    static const size_t kValueOffset = 0;
+
+    /// Accessor for value
+    inline const uint8_t value() const { return __raw_value; }
+
+    /// Mutator for value
+    inline void value(const uint8_t value) { __raw_value = value; }
+
+    /// Deserializes the network-endian buffer to Local in-place by casting
+    /// and applying endianness correction to match the host-endianness.
+    /// NOTE: This mutates the input buffer to host-endianness.
+    /// Returns a pointer to the object within the buffer.
+    static Local* Deserialize(Buffer& buffer);
+
+    /// Serializes the host-endian object into the provided buffer,
+    /// applying endianness correction to match the network-endianness.
+    void Serialize(Buffer& buffer) const;
+
+private:
+    /// Fails to compile if Local does not match its Onyx layout.
+    static inline void __check_layout() {
+        static_assert(sizeof(Local) == kSizeOf, "Local does not match its Onyx size");
+        static_assert(offsetof(Local, __raw_value) == kValueOffset, "Local::value does not match its Onyx offset");
+    }
+};
+
+/// A message deserialized by DecodeAny: its ID and the message within the buffer.
+struct AnyMessage {
+    MessageId id;
+    void* message;
+
+    /// Returns the message as a T, or nullptr if it is another message.
+    template <typename T>
+    T* as() const {
+        return id == T::kId ? static_cast<T*>(message) : nullptr;
+    }
+};
+
+/// Deserializes the message that follows a message ID in the buffer, in place.
+/// Returns false if the buffer is too short, no message has the ID, or the message's
+/// Deserialize returns nullptr.
+bool DecodeAny(uint8_t* buffer, size_t size, AnyMessage& out);
+
+
+inline Ping* Ping::Deserialize(Buffer& buffer) {
+    // Overlay the class structure onto the buffer memory. This is the zero-copy step.
+    Ping* result = (Ping*)buffer;
+
+    // SWAP: Primitive field seq
+    result->__raw_seq = utils::byteswap_if_needed(result->__raw_seq);
+
+    return result;
+}
+
+inline void Ping::Serialize(Buffer& buffer) const {
+    // Copy the host-endian object into the buffer first.
+    memcpy(buffer, this, kSizeOf);
+    Ping* wire_format_data = (Ping*)buffer;
+
+    // SWAP: Primitive field seq
+    wire_format_data->__raw_seq = utils::byteswap_if_needed(wire_format_data->__raw_seq);
+}
+
+inline Command* Command::Deserialize(Buffer& buffer) {
+    // Overlay the class structure onto the buffer memory. This is the zero-copy step.
+    Command* result = (Command*)buffer;
+    bool known = true;
+
+    // SWAP: Primitive field arg
+    result->__raw_arg = utils::byteswap_if_needed(result->__raw_arg);
+    // CHECK: Closed enum field kind
+    known = is_known(result->__raw_kind) && known;
+
+    return known ? result : nullptr;
+}
+
+inline void Command::Serialize(Buffer& buffer) const {
+    // Copy the host-endian object into the buffer first.
+    memcpy(buffer, this, kSizeOf);
+    Command* wire_format_data = (Command*)buffer;
+
+    // SWAP: Primitive field arg
+    wire_format_data->__raw_arg = utils::byteswap_if_needed(wire_format_data->__raw_arg);
+}
+
+inline Local* Local::Deserialize(Buffer& buffer) {
+    // Overlay the class structure onto the buffer memory. This is the zero-copy step.
+    Local* result = (Local*)buffer;
+
+    // INFO: Primitive field value (1 byte), no swap needed.
+
+    return result;
+}
+
+inline void Local::Serialize(Buffer& buffer) const {
+    // Copy the host-endian object into the buffer first.
+    memcpy(buffer, this, kSizeOf);
+    Local* wire_format_data = (Local*)buffer;
+
+    // INFO: Primitive field value (1 byte), no swap needed.
+}
+
+inline bool DecodeAny(uint8_t* buffer, size_t size, AnyMessage& out) {
+    if (size < kMessageIdSize) return false;
+    uint16_t raw;
+    memcpy(&raw, buffer, kMessageIdSize);
+    out.id = static_cast<MessageId>(utils::byteswap_if_needed(raw));
+    uint8_t* body = buffer + kMessageIdSize;
+    size -= kMessageIdSize;
+    switch (out.id) {
+    case MessageId::Ping:
+        if (size < Ping::kSizeOf) return false;
+        out.message = Ping::Deserialize(*reinterpret_cast<Ping::Buffer*>(body));
+        return out.message != nullptr;
+    case MessageId::Command:
+        if (size < Command::kSizeOf) return false;
+        out.message = Command::Deserialize(*reinterpret_cast<Command::Buffer*>(body));
+        return out.message != nullptr;
+    }
+    return false;
+}
+
+} // namespace onyx
+
+#pragma pack(pop)
+#endif // ONYX_DISPATCH_H_
@@ -0,0 +1,158 @@
+// Automatically generated by Onyx IDL compiler. Do not edit.
+package onyx
+
+import java.nio.ByteBuffer
+import java.nio.ByteOrder
+
+/** The byte order of every field in this file. */
+private val BYTE_ORDER: ByteOrder = ByteOrder.BIG_ENDIAN
+
+/** Returns the [size] bytes of the buffer from [offset] as a buffer of their own. */
+private fun ByteBuffer.view(offset: Int, size: Int): ByteBuffer =
+    (duplicate().clear().position(offset).limit(offset + size) as ByteBuffer).slice()
+
+/** Reads the [size] bytes from [offset] as an unsigned integer in [BYTE_ORDER]. */
+private fun ByteBuffer.readBits(offset: Int, size: Int): Long {
+    var raw = 0L
+    for (i in 0 until size) {
+        val index = if (BYTE_ORDER == ByteOrder.BIG_ENDIAN) i else size - 1 - i
+        raw = (raw shl 8) or (get(offset + index).toLong() and 0xFFL)
+    }
+    return raw
+}
+
+/** Writes the low [size] bytes of [raw] from [offset] in [BYTE_ORDER]. */
+private fun ByteBuffer.writeBits(offset: Int, size: Int, raw: Long) {
+    for (i in 0 until size) {
+        val shift = if (BYTE_ORDER == ByteOrder.BIG_ENDIAN) 8 * (size - 1 - i) else 8 * i
+        put(offset + i, (raw ushr shift).toByte())
+    }
+}
+
+/** Copies the [size] bytes from [offset]. */
+private fun ByteBuffer.readBytes(offset: Int, size: Int): ByteArray =
+    ByteArray(size).also { view(offset, size).get(it) }
+
+/** Writes [value], which must hold exactly [size] bytes, from [offset]. */
+private fun ByteBuffer.writeBytes(offset: Int, size: Int, value: ByteArray) {
+    require(value.size == size) { "expected $size bytes, got ${value.size}" }
+    view(offset, size).put(value)
+}
+
+/** Returns [index] if it is below [count], and throws otherwise. */
+private fun checkIndex(index: Int, count: Int): Int {
+    if (index !in 0 until count) {
+        throw IndexOutOfBoundsException("index $index is out of bounds for $count elements")
+    }
+    return index
+}
+
+/** Kind, carried on the wire as u8. */
+enum class Kind(val value: UByte) {
+    Start(0u),
+    Stop(1u),
+    ;
+
+    companion object {
+        /** The size of Kind in bytes. */
+        const val SIZE_BYTES: Int = 1
+
+        /** Returns the variant whose value is [value], or null if there is none. */
+        fun fromValue(value: UByte): Kind? = values().firstOrNull { it.value == value }
+    }
+}
+
+/** Ping, viewed in [buffer] from its index 0. */
+@JvmInline
+value class Ping(val buffer: ByteBuffer) {
+    /** Accessor for seq. */
+    var seq: UShort
+        get() = buffer.readBits(SEQ_OFFSET, 2).toUShort()
+        set(value) {
+            buffer.writeBits(SEQ_OFFSET, 2, value.toLong())
+        }
+
+    companion object {
+        /** The size of Ping in bytes. */
+        const val SIZE_BYTES: Int = 2
+
+        /** The ID of the message, which precedes it on the wire. */
+        const val ID: Int = 1
+
+        /** The byte offset of seq. */
+        const val SEQ_OFFSET: Int = 0
+
+        /** Returns a view of Ping from the position of [buffer]. */
+        fun wrap(buffer: ByteBuffer): Ping = Ping(buffer.view(buffer.position(), SIZE_BYTES))
+
+        /** Returns a view of Ping in a new, zeroed buffer. */
+        fun allocate(): Ping = Ping(ByteBuffer.allocate(SIZE_BYTES))
+    }
+}
+
+/** Command, viewed in [buffer] from its index 0. */
+@JvmInline
+value class Command(val buffer: ByteBuffer) {
+    /** The raw value of kind, which may name no variant. */
+    var kindRaw: UByte
+        get() = buffer.readBits(KIND_OFFSET, 1).toUByte()
+        set(value) {
+            buffer.writeBits(KIND_OFFSET, 1, value.toLong())
+        }
+
+    /** Accessor for kind, or null if it names no variant. */
+    val kind: Kind?
+        get() = Kind.fromValue(kindRaw)
+
+    /** Accessor for arg. */
+    var arg: UInt
+        get() = buffer.readBits(ARG_OFFSET, 4).toUInt()
+        set(value) {
+            buffer.writeBits(ARG_OFFSET, 4, value.toLong())
+        }
+
+    companion object {
+        /** The size of Command in bytes. */
+        const val SIZE_BYTES: Int = 5
+
+        /** The ID of the message, which precedes it on the wire. */
+        const val ID: Int = 257
+
+        /** The byte offset of kind. */
+        const val KIND_OFFSET: Int = 0
+
+        /** The byte offset of arg. */
+        const val ARG_OFFSET: Int = 1
+
+        /** Returns a view of Command from the position of [buffer]. */
+        fun wrap(buffer: ByteBuffer): Command = Command(buffer.view(buffer.position(), SIZE_BYTES))
+
+        /** Returns a view of Command in a new, zeroed buffer. */
+        fun allocate(): Command = Command(ByteBuffer.allocate(SIZE_BYTES))
+    }
+}
+
+/** Local, viewed in [buffer] from its index 0. */
+@JvmInline
+value class Local(val buffer: ByteBuffer) {
+    /** Accessor for value. */
+    var value: UByte
+        get() = buffer.readBits(VALUE_OFFSET, 1).toUByte()
+        set(value) {
+            buffer.writeBits(VALUE_OFFSET, 1, value.toLong())
+        }
+
+    companion object {
+        /** The size of Local in bytes. */
+        const val SIZE_BYTES: Int = 1
+
+        /** The byte offset of value. */
+        const val VALUE_OFFSET: Int = 0
+
+        /** Returns a view of Local from the position of [buffer]. */
+        fun wrap(buffer: ByteBuffer): Local = Local(buffer.view(buffer.position(), SIZE_BYTES))
+
+        /** Returns a view of Local in a new, zeroed buffer. */
+        fun allocate(): Local = Local(ByteBuffer.allocate(SIZE_BYTES))
+    }
+}
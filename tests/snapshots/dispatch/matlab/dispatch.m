@@ -0,0 +1,45 @@
+% Automatically generated by Onyx IDL compiler. Do not edit.
+function dispatch()
+%DISPATCH Defines the Simulink enums and bus objects of the schema.
+%   Run dispatch before loading a model that uses them. Each bus object is
+%   assigned in the base workspace, named after its struct or message.
+
+% Kind, carried on the wire as u8.
+Simulink.defineIntEnumType('Kind', {'Start', 'Stop'}, [0; 1], ...
+    'StorageType', 'uint8', 'Description', 'Kind, carried on the wire as u8.');
+
+% Ping: 2 bytes. Message ID 1.
+elements = Simulink.BusElement.empty;
+elements(end + 1) = element('seq', 'uint16', 1, 'byte 0');
+bus = Simulink.Bus;
+bus.Description = 'Ping: 2 bytes. Message ID 1.';
+bus.Elements = elements;
+assignin('base', 'Ping', bus);
+
+% Command: 5 bytes. Message ID 257.
+elements = Simulink.BusElement.empty;
+elements(end + 1) = element('kind', 'Enum: Kind', 1, 'byte 0');
+elements(end + 1) = element('arg', 'uint32', 1, 'byte 1');
+bus = Simulink.Bus;
+bus.Description = 'Command: 5 bytes. Message ID 257.';
+bus.Elements = elements;
+assignin('base', 'Command', bus);
+
+% Local: 1 bytes.
+elements = Simulink.BusElement.empty;
+elements(end + 1) = element('value', 'uint8', 1, 'byte 0');
+bus = Simulink.Bus;
+bus.Description = 'Local: 1 bytes.';
+bus.Elements = elements;
+assignin('base', 'Local', bus);
+
+end
+
+function e = element(name, type, dimensions, description)
+% Returns a bus element.
+e = Simulink.BusElement;
+e.Name = name;
+e.DataType = type;
+e.Dimensions = dimensions;
+e.Description = description;
+end
@@ -0,0 +1,277 @@
+// Automatically generated by Onyx IDL compiler. Do not edit.
+
+use std::convert::TryInto;
+
+#[repr(u8)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Kind {
+    Start = 0,
+    Stop = 1,
+}
+
+impl Default for Kind {
+    fn default() -> Self {
+        Self::Start
+    }
+}
+
+impl TryFrom<u8> for Kind {
+    type Error = u8;
+
+    fn try_from(raw: u8) -> Result<Self, u8> {
+        match raw {
+            0 => Ok(Self::Start),
+            1 => Ok(Self::Stop),
+            _ => Err(raw),
+        }
+    }
+}
+
+impl From<Kind> for u8 {
+    fn from(value: Kind) -> Self {
+        value as u8
+    }
+}
+
+/// Size of Ping in bytes.
+pub(crate) const PING_SIZE: usize = 2;
+/// Buffer type alias for Ping.
+pub type PingBuffer = [u8; PING_SIZE];
+
+/// Read-only view of Ping.
+#[derive(Debug, Clone, Copy)]
+pub struct PingView<'a> {
+    data: &'a PingBuffer,
+}
+
+/// Read-write view of Ping.
+#[derive(Debug)]
+pub struct PingMutView<'a> {
+    data: &'a mut PingBuffer,
+}
+
+impl<'a> PingView<'a> {
+    /// Size of Ping in bytes.
+    pub const SIZE_BYTES: usize = PING_SIZE;
+    /// Byte offset of seq.
+    pub const FIELD_OFFSET_SEQ: usize = 0;
+
+    /// Creates a new PingView from a PingBuffer.
+    pub fn new(data: &'a PingBuffer) -> Self {
+        Self { data }
+    }
+
+    /// Accessor for seq.
+    pub fn seq(&self) -> u16 {
+        let bytes = self.data[0..2].try_into().unwrap();
+        u16::from_be_bytes(bytes)
+    }
+}
+
+// Fails to compile if Ping does not match its Onyx layout.
+const _: () = assert!(PingView::FIELD_OFFSET_SEQ + std::mem::size_of::<u16>() == PingView::SIZE_BYTES, "Ping does not match its Onyx size");
+
+impl<'a> PingMutView<'a> {
+    /// Creates a new PingMutView from a PingBuffer.
+    pub fn new(data: &'a mut PingBuffer) -> Self {
+        Self { data }
+    }
+
+    /// Accessor for seq.
+    pub fn seq(&self) -> u16 {
+        let bytes = self.data[0..2].try_into().unwrap();
+        u16::from_be_bytes(bytes)
+    }
+
+    /// Mutator for seq.
+    pub fn set_seq(&mut self, value: u16) {
+        let bytes = value.to_be_bytes();
+        self.data[0..2].copy_from_slice(&bytes);
+    }
+}
+
+/// Size of Command in bytes.
+pub(crate) const COMMAND_SIZE: usize = 5;
+/// Buffer type alias for Command.
+pub type CommandBuffer = [u8; COMMAND_SIZE];
+
+/// Read-only view of Command.
+#[derive(Debug, Clone, Copy)]
+pub struct CommandView<'a> {
+    data: &'a CommandBuffer,
+}
+
+/// Read-write view of Command.
+#[derive(Debug)]
+pub struct CommandMutView<'a> {
+    data: &'a mut CommandBuffer,
+}
+
+impl<'a> CommandView<'a> {
+    /// Size of Command in bytes.
+    pub const SIZE_BYTES: usize = COMMAND_SIZE;
+    /// Byte offset of kind.
+    pub const FIELD_OFFSET_KIND: usize = 0;
+    /// Byte offset of arg.
+    pub const FIELD_OFFSET_ARG: usize = 1;
+
+    /// Creates a new CommandView from a CommandBuffer.
+    pub fn new(data: &'a CommandBuffer) -> Self {
+        Self { data }
+    }
+
+    /// Accessor for kind.
+    pub fn kind(&self) -> Result<Kind, u8> {
+        let bytes = self.data[0..1].try_into().unwrap();
+        let val = u8::from_be_bytes(bytes);
+        Kind::try_from(val)
+    }
+
+    /// Accessor for arg.
+    pub fn arg(&self) -> u32 {
+        let bytes = self.data[1..5].try_into().unwrap();
+        u32::from_be_bytes(bytes)
+    }
+}
+
+// Fails to compile if Command does not match its Onyx layout.
+const _: () = assert!(CommandView::FIELD_OFFSET_ARG == CommandView::FIELD_OFFSET_KIND + std::mem::size_of::<u8>(), "Command.arg does not match its Onyx offset");
+const _: () = assert!(CommandView::FIELD_OFFSET_ARG + std::mem::size_of::<u32>() == CommandView::SIZE_BYTES, "Command does not match its Onyx size");
+
+impl<'a> CommandMutView<'a> {
+    /// Creates a new CommandMutView from a CommandBuffer.
+    pub fn new(data: &'a mut CommandBuffer) -> Self {
+        Self { data }
+    }
+
+    /// Accessor for kind.
+    pub fn kind(&self) -> Result<Kind, u8> {
+        let bytes = self.data[0..1].try_into().unwrap();
+        let val = u8::from_be_bytes(bytes);
+        Kind::try_from(val)
+    }
+
+    /// Mutator for kind.
+    pub fn set_kind(&mut self, value: Kind) {
+        let val = u8::from(value);
+        let bytes = val.to_be_bytes();
+        self.data[0..1].copy_from_slice(&bytes);
+    }
+
+    /// Accessor for arg.
+    pub fn arg(&self) -> u32 {
+        let bytes = self.data[1..5].try_into().unwrap();
+        u32::from_be_bytes(bytes)
+    }
+
+    /// Mutator for arg.
+    pub fn set_arg(&mut self, value: u32) {
+        let bytes = value.to_be_bytes();
+        self.data[1..5].copy_from_slice(&bytes);
+    }
+}
+
+/// Size of Local in bytes.
+pub(crate) const LOCAL_SIZE: usize = 1;
+/// Buffer type alias for Local.
+pub type LocalBuffer = [u8; LOCAL_SIZE];
+
+/// Read-only view of Local.
+#[derive(Debug, Clone, Copy)]
+pub struct LocalView<'a> {
+    data: &'a LocalBuffer,
+}
+
+/// Read-write view of Local.
+#[derive(Debug)]
+pub struct LocalMutView<'a> {
+    data: &'a mut LocalBuffer,
+}
+
+impl<'a> LocalView<'a> {
+    /// Size of Local in bytes.
+    pub const SIZE_BYTES: usize = LOCAL_SIZE;
+    /// Byte offset of value.
+    pub const FIELD_OFFSET_VALUE: usize = 0;
+
+    /// Creates a new LocalView from a LocalBuffer.
+    pub fn new(data: &'a LocalBuffer) -> Self {
+        Self { data }
+    }
+
+    /// Accessor for value.
+    pub fn value(&self) -> u8 {
+        self.data[0] as u8
+    }
+}
+
+// Fails to compile if Local does not match its Onyx layout.
+const _: () = assert!(LocalView::FIELD_OFFSET_VALUE + std::mem::size_of::<u8>() == LocalView::SIZE_BYTES, "Local does not match its Onyx size");
+
+impl<'a> LocalMutView<'a> {
+    /// Creates a new LocalMutView from a LocalBuffer.
+    pub fn new(data: &'a mut LocalBuffer) -> Self {
+        Self { data }
+    }
+
+    /// Accessor for value.
+    pub fn value(&self) -> u8 {
+        self.data[0] as u8
+    }
+
+    /// Mutator for value.
+    pub fn set_value(&mut self, value: u8) {
+        self.data[0] = value as u8;
+    }
+}
+
+/// Size of the ID that precedes an identified message, in bytes.
+pub const MESSAGE_ID_SIZE: usize = 2;
+/// ID of Ping.
+pub const PING_ID: u16 = 1;
+/// ID of Command.
+pub const COMMAND_ID: u16 = 257;
+
+/// Returns the bytes of a message ID, which precede the message on the wire.
+pub fn message_id_bytes(id: u16) -> [u8; MESSAGE_ID_SIZE] {
+    id.to_be_bytes()
+}
+
+/// A message decoded by `decode_any`, by type.
+#[derive(Debug, Clone, Copy)]
+pub enum AnyMessage<'a> {
+    Ping(PingView<'a>),
+    Command(CommandView<'a>),
+}
+
+impl AnyMessage<'_> {
+    /// Returns the ID of the message.
+    pub fn id(&self) -> u16 {
+        match self {
+            AnyMessage::Ping(_) => PING_ID,
+            AnyMessage::Command(_) => COMMAND_ID,
+        }
+    }
+}
+
+/// Why `decode_any` could not decode a message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DecodeAnyError {
+    /// The buffer ends before the ID or the message it names.
+    Truncated,
+    /// No message has the ID.
+    UnknownId(u16),
+}
+
+/// Decodes a message preceded by its ID. Bytes after the message are ignored.
+pub fn decode_any(buf: &[u8]) -> Result<AnyMessage<'_>, DecodeAnyError> {
+    let id = buf.get(..MESSAGE_ID_SIZE).ok_or(DecodeAnyError::Truncated)?;
+    let id = u16::from_be_bytes(id.try_into().unwrap());
+    let body = &buf[MESSAGE_ID_SIZE..];
+    let message = match id {
+        PING_ID => body.get(..PING_SIZE).map(|data| AnyMessage::Ping(PingView::new(data.try_into().unwrap()))),
+        COMMAND_ID => body.get(..COMMAND_SIZE).map(|data| AnyMessage::Command(CommandView::new(data.try_into().unwrap()))),
+        _ => return Err(DecodeAnyError::UnknownId(id)),
+    };
+    message.ok_or(DecodeAnyError::Truncated)
+}
@@ -0,0 +1,44 @@
+// Automatically generated by Onyx IDL compiler. Do not edit.
+`ifndef ONYX_DISPATCH_PKG_SV
+`define ONYX_DISPATCH_PKG_SV
+
+package dispatch_pkg;
+
+  // Kind, carried on the wire as u8.
+  typedef enum logic [7:0] {
+    Kind_Start = 8'd0,
+    Kind_Stop = 8'd1
+  } Kind;
+
+  // Ping: 2 bytes.
+  localparam int Ping_SIZE_BYTES = 2;
+  localparam int Ping_ID = 1;
+  localparam int Ping_seq_OFFSET = 0;
+  localparam int Ping_seq_WIDTH = 16;
+  typedef struct packed {
+    logic [15:0] seq;
+  } Ping;
+
+  // Command: 5 bytes.
+  localparam int Command_SIZE_BYTES = 5;
+  localparam int Command_ID = 257;
+  localparam int Command_kind_OFFSET = 0;
+  localparam int Command_kind_WIDTH = 8;
+  localparam int Command_arg_OFFSET = 1;
+  localparam int Command_arg_WIDTH = 32;
+  typedef struct packed {
+    Kind kind;
+    logic [31:0] arg;
+  } Command;
+
+  // Local: 1 bytes.
+  localparam int Local_SIZE_BYTES = 1;
+  localparam int Local_value_OFFSET = 0;
+  localparam int Local_value_WIDTH = 8;
+  typedef struct packed {
+    logic [7:0] value;
+  } Local;
+
+endpackage
+
+`endif // ONYX_DISPATCH_PKG_SV
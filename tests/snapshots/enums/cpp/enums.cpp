@@ -0,0 +1,62 @@
+// Automatically generated by Onyx IDL compiler. Do not edit.
+#include "enums.hpp"
+
+#include <string.h>
+
+namespace onyx {
+
+Entry* Entry::Deserialize(Buffer& buffer) {
+    // Overlay the class structure onto the buffer memory. This is the zero-copy step.
+    Entry* result = (Entry*)buffer;
+    bool known = true;
+
+    // SWAP: Enum field mode
+    result->__raw_mode = static_cast<Mode>(utils::byteswap_if_needed(static_cast<uint16_t>(result->__raw_mode)));
+    // CHECK: Closed enum field mode
+    known = is_known(result->__raw_mode) && known;
+
+    return known ? result : nullptr;
+}
+
+void Entry::Serialize(Buffer& buffer) const {
+    // Copy the host-endian object into the buffer first.
+    memcpy(buffer, this, kSizeOf);
+    Entry* wire_format_data = (Entry*)buffer;
+
+    // SWAP: Enum field mode
+    wire_format_data->__raw_mode = static_cast<Mode>(utils::byteswap_if_needed(static_cast<uint16_t>(wire_format_data->__raw_mode)));
+}
+
+Policy* Policy::Deserialize(Buffer& buffer) {
+    // Overlay the class structure onto the buffer memory. This is the zero-copy step.
+    Policy* result = (Policy*)buffer;
+    bool known = true;
+
+    known = Entry::Deserialize(*(Entry::Buffer*) &result->__raw_entry) != nullptr && known;
+    // SWAP: Vector field modes
+    for (size_t i = 0; i < 3; ++i) {
+        result->__raw_modes.__items[i] = static_cast<Mode>(utils::byteswap_if_needed(static_cast<uint16_t>(result->__raw_modes.__items[i])));
+    }
+    // SWAP: Vector field levels
+    // CHECK: Closed enum field modes
+    for (size_t i = 0; i < result->__raw_modes.len(); ++i) {
+        known = is_known(result->__raw_modes.__items[i]) && known;
+    }
+
+    return known ? result : nullptr;
+}
+
+void Policy::Serialize(Buffer& buffer) const {
+    // Copy the host-endian object into the buffer first.
+    memcpy(buffer, this, kSizeOf);
+    Policy* wire_format_data = (Policy*)buffer;
+
+    wire_format_data->__raw_entry.Serialize(*(Entry::Buffer*) &wire_format_data->__raw_entry);
+    // SWAP: Vector field modes
+    for (size_t i = 0; i < 3; ++i) {
+        wire_format_data->__raw_modes.__items[i] = static_cast<Mode>(utils::byteswap_if_needed(static_cast<uint16_t>(wire_format_data->__raw_modes.__items[i])));
+    }
+    // SWAP: Vector field levels
+}
+
+} // namespace onyx
@@ -0,0 +1,344 @@
+// Automatically generated by Onyx IDL compiler. Do not edit.
+#ifndef ONYX_ENUMS_H_
+#define ONYX_ENUMS_H_
+
+#include <stddef.h>
+#include <stdint.h>
+#include <string.h>
+
+#pragma pack(push, 1)
+
+namespace onyx {
+#ifndef ONYX_UTILS_ENDIANNESS_LITTLE_
+#define ONYX_UTILS_ENDIANNESS_LITTLE_
+
+namespace utils {
+
+// Define network endianness (usually Big Endian).
+#define ONYX_BIG_ENDIAN 4321
+#define ONYX_LITTLE_ENDIAN 1234
+#define ONYX_NETWORK_ORDER ONYX_LITTLE_ENDIAN
+
+// Determine host endianness at compile time (using standard GCC/Clang macros)
+#ifdef __BYTE_ORDER__
+    #if __BYTE_ORDER__ == __ORDER_BIG_ENDIAN__
+        #define ONYX_HOST_ORDER ONYX_BIG_ENDIAN
+    #elif __BYTE_ORDER__ == __ORDER_LITTLE_ENDIAN__
+        #define ONYX_HOST_ORDER ONYX_LITTLE_ENDIAN
+    #else
+        #error "Unsupported host endianness"
+    #endif
+#elif defined(_WIN32) || defined(__LITTLE_ENDIAN__)
+    // Fallback for Windows (always little-endian) or other platforms
+    #define ONYX_HOST_ORDER ONYX_LITTLE_ENDIAN
+#else
+    #error "Could not determine host endianness"
+#endif
+
+// Helper function to swap bytes (Explicit Overloads)
+inline uint16_t byteswap(uint16_t value) { return ((value << 8) & 0xFF00) | ((value >> 8) & 0x00FF); }
+
+inline int16_t byteswap(int16_t value) { return (int16_t)byteswap((uint16_t)value); }
+
+inline uint32_t byteswap(uint32_t value) { return (value << 24) | ((value & 0x00FF0000) >> 8) | ((value & 0x0000FF00) << 8) | (value >> 24); }
+
+inline int32_t byteswap(int32_t value) { return (int32_t)byteswap((uint32_t)value); }
+
+inline uint64_t byteswap(uint64_t value) { return (value << 56) | ((value & 0x00FF000000000000ULL) >> 40) | ((value & 0x0000FF0000000000ULL) >> 24) | ((value & 0x000000FF00000000ULL) >> 8) | ((value & 0x00000000FF000000ULL) << 8) | ((value & 0x0000000000FF0000ULL) << 24) | ((value & 0x000000000000FF00ULL) << 40) | (value >> 56); }
+
+inline int64_t byteswap(int64_t value) { return (int64_t)byteswap((uint64_t)value); }
+
+inline float byteswap(float value) {
+    uint32_t temp;
+    memcpy(&temp, &value, sizeof(value));
+    temp = byteswap(temp);
+    memcpy(&value, &temp, sizeof(value));
+    return value;
+}
+
+inline double byteswap(double value) {
+    uint64_t temp;
+    memcpy(&temp, &value, sizeof(value));
+    temp = byteswap(temp);
+    memcpy(&value, &temp, sizeof(value));
+    return value;
+}
+
+// Swaps bytes if the host order does not match the network order (Explicit Overloads)
+inline uint16_t byteswap_if_needed(uint16_t value) {
+    if (ONYX_HOST_ORDER != ONYX_NETWORK_ORDER) {
+        return byteswap(value);
+    }
+    return value;
+}
+
+inline int16_t byteswap_if_needed(int16_t value) {
+    if (ONYX_HOST_ORDER != ONYX_NETWORK_ORDER) {
+        return byteswap(value);
+    }
+    return value;
+}
+
+inline uint32_t byteswap_if_needed(uint32_t value) {
+    if (ONYX_HOST_ORDER != ONYX_NETWORK_ORDER) {
+        return byteswap(value);
+    }
+    return value;
+}
+
+inline int32_t byteswap_if_needed(int32_t value) {
+    if (ONYX_HOST_ORDER != ONYX_NETWORK_ORDER) {
+        return byteswap(value);
+    }
+    return value;
+}
+
+inline uint64_t byteswap_if_needed(uint64_t value) {
+    if (ONYX_HOST_ORDER != ONYX_NETWORK_ORDER) {
+        return byteswap(value);
+    }
+    return value;
+}
+
+inline int64_t byteswap_if_needed(int64_t value) {
+    if (ONYX_HOST_ORDER != ONYX_NETWORK_ORDER) {
+        return byteswap(value);
+    }
+    return value;
+}
+
+inline float byteswap_if_needed(float value) {
+    if (ONYX_HOST_ORDER != ONYX_NETWORK_ORDER) {
+        return byteswap(value);
+    }
+    return value;
+}
+
+inline double byteswap_if_needed(double value) {
+    if (ONYX_HOST_ORDER != ONYX_NETWORK_ORDER) {
+        return byteswap(value);
+    }
+    return value;
+}
+
+} // namespace utils
+#endif // ONYX_UTILS_ENDIANNESS_LITTLE_
+
+#ifndef ONYX_UTILS_BOUNDED_VEC_
+#define ONYX_UTILS_BOUNDED_VEC_
+
+namespace utils {
+
+/// A bounded vector: a length followed by room for N elements, all of them inline.
+template <typename T, typename LenT, size_t N>
+class BoundedVec {
+public:
+    LenT __length;
+    T __items[N];
+
+    /// Returns the number of elements, clamped to the capacity.
+    inline size_t len() const { return __length < N ? __length : N; }
+    /// Returns the maximum number of elements.
+    static inline size_t capacity() { return N; }
+    /// Returns true if the vector has no elements.
+    inline bool empty() const { return len() == 0; }
+    /// Returns the element at `i`, which must be less than len().
+    inline const T& get(size_t i) const { return __items[i]; }
+    inline T& get(size_t i) { return __items[i]; }
+    /// Replaces the element at `i`, which must be less than len().
+    inline void set(size_t i, const T& value) { __items[i] = value; }
+    /// Appends an element. Returns false, leaving the vector unchanged, if it is full.
+    inline bool push(const T& value) {
+        size_t n = len();
+        if (n == N) return false;
+        __items[n] = value;
+        __length = static_cast<LenT>(n + 1);
+        return true;
+    }
+    /// Removes all elements.
+    inline void clear() { __length = 0; }
+
+    inline const T* begin() const { return __items; }
+    inline const T* end() const { return __items + len(); }
+    inline T* begin() { return __items; }
+    inline T* end() { return __items + len(); }
+};
+
+} // namespace utils
+
+#endif // ONYX_UTILS_BOUNDED_VEC_
+
+class Entry;
+class Policy;
+
+enum class Mode : uint16_t {
+    Off,
+    On = 300,
+    Auto = 301,
+};
+
+/// Returns true if `value` is declared by a variant of Mode.
+inline bool is_known(Mode value) {
+    return value == Mode::Off || value == Mode::On || value == Mode::Auto;
+}
+
+enum class Level : int8_t {
+    Low = 1,
+    Mid = 2,
+    High = 3,
+};
+
+/// Returns true if `value` is declared by a variant of Level.
+inline bool is_known(Level value) {
+    return value == Level::Low || value == Level::Mid || value == Level::High;
+}
+
+enum class Access : uint8_t {
+    Read = 1,
+    Write = 2,
+    Exec = 4,
+    All = 7,
+};
+
+inline constexpr Access operator|(Access a, Access b) {
+    return static_cast<Access>(static_cast<uint8_t>(a) | static_cast<uint8_t>(b));
+}
+
+inline Access& operator|=(Access& a, Access b) {
+    return a = a | b;
+}
+
+inline constexpr Access operator&(Access a, Access b) {
+    return static_cast<Access>(static_cast<uint8_t>(a) & static_cast<uint8_t>(b));
+}
+
+inline Access& operator&=(Access& a, Access b) {
+    return a = a & b;
+}
+
+inline constexpr Access operator^(Access a, Access b) {
+    return static_cast<Access>(static_cast<uint8_t>(a) ^ static_cast<uint8_t>(b));
+}
+
+inline Access& operator^=(Access& a, Access b) {
+    return a = a ^ b;
+}
+
+inline constexpr Access operator~(Access a) {
+    return static_cast<Access>(~static_cast<uint8_t>(a) & 7u);
+}
+
+class Entry {
+private:
+    Mode __raw_mode;
+    Level __raw_level;
+
+public:
+    static const size_t kSizeOf = 3;
+    using Buffer = uint8_t[kSizeOf];
+
+    /// Byte offsets of the fields within the buffer.
+    static const size_t kModeOffset = 0;
+    static const size_t kLevelOffset = 2;
+
+    /// Accessor for mode
+    inline const Mode mode() const { return __raw_mode; }
+
+    /// Mutator for mode
+    inline void mode(const Mode value) { __raw_mode = value; }
+
+    /// Accessor for level
+    inline const Level level() const { return __raw_level; }
+
+    /// Mutator for level
+    inline void level(const Level value) { __raw_level = value; }
+
+    /// Deserializes the network-endian buffer to Entry in-place by casting
+    /// and applying endianness correction to match the host-endianness.
+    /// NOTE: This mutates the input buffer to host-endianness.
+    /// Returns a pointer to the object within the buffer.
+    /// Returns nullptr if a closed enum holds a value that no variant declares;
+    /// the buffer is converted either way.
+    static Entry* Deserialize(Buffer& buffer);
+
+    /// Serializes the host-endian object into the provided buffer,
+    /// applying endianness correction to match the network-endianness.
+    void Serialize(Buffer& buffer) const;
+
+private:
+    /// Fails to compile if Entry does not match its Onyx layout.
+    static inline void __check_layout() {
+        static_assert(sizeof(Entry) == kSizeOf, "Entry does not match its Onyx size");
+        static_assert(offsetof(Entry, __raw_mode) == kModeOffset, "Entry::mode does not match its Onyx offset");
+        static_assert(offsetof(Entry, __raw_level) == kLevelOffset, "Entry::level does not match its Onyx offset");
+    }
+};
+
+class Policy {
+private:
+    Access __raw_access;
+    Entry __raw_entry;
+    utils::BoundedVec<Mode, uint8_t, 3> __raw_modes;
+    utils::BoundedVec<Level, uint8_t, 3> __raw_levels;
+
+public:
+    static const size_t kSizeOf = 15;
+    using Buffer = uint8_t[kSizeOf];
+
+    /// Byte offsets of the fields within the buffer.
+    static const size_t kAccessOffset = 0;
+    static const size_t kEntryOffset = 1;
+    static const size_t kModesOffset = 4;
+    static const size_t kLevelsOffset = 11;
+
+    /// Accessor for access
+    inline const Access access() const { return __raw_access; }
+
+    /// Mutator for access
+    inline void access(const Access value) { __raw_access = value; }
+
+    /// Accessor for entry
+    inline Entry& entry() { return __raw_entry; }
+
+    /// Mutator for entry
+    inline void entry(const Entry value) { __raw_entry = value; }
+
+    /// Accessor for modes
+    inline utils::BoundedVec<Mode, uint8_t, 3>& modes() { return __raw_modes; }
+
+    /// Mutator for modes
+    inline void modes(const utils::BoundedVec<Mode, uint8_t, 3> value) { __raw_modes = value; }
+
+    /// Accessor for levels
+    inline utils::BoundedVec<Level, uint8_t, 3>& levels() { return __raw_levels; }
+
+    /// Mutator for levels
+    inline void levels(const utils::BoundedVec<Level, uint8_t, 3> value) { __raw_levels = value; }
+
+    /// Deserializes the network-endian buffer to Policy in-place by casting
+    /// and applying endianness correction to match the host-endianness.
+    /// NOTE: This mutates the input buffer to host-endianness.
+    /// Returns a pointer to the object within the buffer.
+    /// Returns nullptr if a closed enum holds a value that no variant declares;
+    /// the buffer is converted either way.
+    static Policy* Deserialize(Buffer& buffer);
+
+    /// Serializes the host-endian object into the provided buffer,
+    /// applying endianness correction to match the network-endianness.
+    void Serialize(Buffer& buffer) const;
+
+private:
+    /// Fails to compile if Policy does not match its Onyx layout.
+    static inline void __check_layout() {
+        static_assert(sizeof(Policy) == kSizeOf, "Policy does not match its Onyx size");
+        static_assert(offsetof(Policy, __raw_access) == kAccessOffset, "Policy::access does not match its Onyx offset");
+        static_assert(offsetof(Policy, __raw_entry) == kEntryOffset, "Policy::entry does not match its Onyx offset");
+        static_assert(offsetof(Policy, __raw_modes) == kModesOffset, "Policy::modes does not match its Onyx offset");
+        static_assert(offsetof(Policy, __raw_levels) == kLevelsOffset, "Policy::levels does not match its Onyx offset");
+    }
+};
+
+} // namespace onyx
+
+#pragma pack(pop)
+#endif // ONYX_ENUMS_H_
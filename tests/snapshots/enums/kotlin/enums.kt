@@ -0,0 +1,222 @@
+// Automatically generated by Onyx IDL compiler. Do not edit.
+package onyx
+
+import java.nio.ByteBuffer
+import java.nio.ByteOrder
+
+/** The byte order of every field in this file. */
+private val BYTE_ORDER: ByteOrder = ByteOrder.LITTLE_ENDIAN
+
+/** Returns the [size] bytes of the buffer from [offset] as a buffer of their own. */
+private fun ByteBuffer.view(offset: Int, size: Int): ByteBuffer =
+    (duplicate().clear().position(offset).limit(offset + size) as ByteBuffer).slice()
+
+/** Reads the [size] bytes from [offset] as an unsigned integer in [BYTE_ORDER]. */
+private fun ByteBuffer.readBits(offset: Int, size: Int): Long {
+    var raw = 0L
+    for (i in 0 until size) {
+        val index = if (BYTE_ORDER == ByteOrder.BIG_ENDIAN) i else size - 1 - i
+        raw = (raw shl 8) or (get(offset + index).toLong() and 0xFFL)
+    }
+    return raw
+}
+
+/** Writes the low [size] bytes of [raw] from [offset] in [BYTE_ORDER]. */
+private fun ByteBuffer.writeBits(offset: Int, size: Int, raw: Long) {
+    for (i in 0 until size) {
+        val shift = if (BYTE_ORDER == ByteOrder.BIG_ENDIAN) 8 * (size - 1 - i) else 8 * i
+        put(offset + i, (raw ushr shift).toByte())
+    }
+}
+
+/** Copies the [size] bytes from [offset]. */
+private fun ByteBuffer.readBytes(offset: Int, size: Int): ByteArray =
+    ByteArray(size).also { view(offset, size).get(it) }
+
+/** Writes [value], which must hold exactly [size] bytes, from [offset]. */
+private fun ByteBuffer.writeBytes(offset: Int, size: Int, value: ByteArray) {
+    require(value.size == size) { "expected $size bytes, got ${value.size}" }
+    view(offset, size).put(value)
+}
+
+/** Returns [index] if it is below [count], and throws otherwise. */
+private fun checkIndex(index: Int, count: Int): Int {
+    if (index !in 0 until count) {
+        throw IndexOutOfBoundsException("index $index is out of bounds for $count elements")
+    }
+    return index
+}
+
+/** Mode, carried on the wire as u16. */
+enum class Mode(val value: UShort) {
+    Off(0u),
+    On(300u),
+    Auto(301u),
+    ;
+
+    companion object {
+        /** The size of Mode in bytes. */
+        const val SIZE_BYTES: Int = 2
+
+        /** Returns the variant whose value is [value], or null if there is none. */
+        fun fromValue(value: UShort): Mode? = values().firstOrNull { it.value == value }
+    }
+}
+
+/** Level, carried on the wire as i8. */
+enum class Level(val value: Byte) {
+    Low(1),
+    Mid(2),
+    High(3),
+    ;
+
+    companion object {
+        /** The size of Level in bytes. */
+        const val SIZE_BYTES: Int = 1
+
+        /** Returns the variant whose value is [value], or null if there is none. */
+        fun fromValue(value: Byte): Level? = values().firstOrNull { it.value == value }
+    }
+}
+
+/** Access, a set of flags carried on the wire as u8. */
+@JvmInline
+value class Access(val bits: UByte) {
+    /** Returns true if every flag of [other] is set. */
+    operator fun contains(other: Access): Boolean = (bits and other.bits) == other.bits
+
+    /** Returns the flags set in either. */
+    infix fun or(other: Access): Access = Access(bits or other.bits)
+
+    /** Returns the flags set in both. */
+    infix fun and(other: Access): Access = Access(bits and other.bits)
+
+    companion object {
+        /** The size of Access in bytes. */
+        const val SIZE_BYTES: Int = 1
+
+        val Read: Access = Access(1u)
+        val Write: Access = Access(2u)
+        val Exec: Access = Access(4u)
+        val All: Access = Access(7u)
+    }
+}
+
+/** Entry, viewed in [buffer] from its index 0. */
+@JvmInline
+value class Entry(val buffer: ByteBuffer) {
+    /** The raw value of mode, which may name no variant. */
+    var modeRaw: UShort
+        get() = buffer.readBits(MODE_OFFSET, 2).toUShort()
+        set(value) {
+            buffer.writeBits(MODE_OFFSET, 2, value.toLong())
+        }
+
+    /** Accessor for mode, or null if it names no variant. */
+    val mode: Mode?
+        get() = Mode.fromValue(modeRaw)
+
+    /** The raw value of level, which may name no variant. */
+    var levelRaw: Byte
+        get() = buffer.readBits(LEVEL_OFFSET, 1).toByte()
+        set(value) {
+            buffer.writeBits(LEVEL_OFFSET, 1, value.toLong())
+        }
+
+    /** Accessor for level, or null if it names no variant. */
+    val level: Level?
+        get() = Level.fromValue(levelRaw)
+
+    companion object {
+        /** The size of Entry in bytes. */
+        const val SIZE_BYTES: Int = 3
+
+        /** The byte offset of mode. */
+        const val MODE_OFFSET: Int = 0
+
+        /** The byte offset of level. */
+        const val LEVEL_OFFSET: Int = 2
+
+        /** Returns a view of Entry from the position of [buffer]. */
+        fun wrap(buffer: ByteBuffer): Entry = Entry(buffer.view(buffer.position(), SIZE_BYTES))
+
+        /** Returns a view of Entry in a new, zeroed buffer. */
+        fun allocate(): Entry = Entry(ByteBuffer.allocate(SIZE_BYTES))
+    }
+}
+
+/** Policy, viewed in [buffer] from its index 0. */
+@JvmInline
+value class Policy(val buffer: ByteBuffer) {
+    /** Accessor for access. */
+    var access: Access
+        get() = Access(buffer.readBits(ACCESS_OFFSET, 1).toUByte())
+        set(value) {
+            buffer.writeBits(ACCESS_OFFSET, 1, value.bits.toLong())
+        }
+
+    /** Accessor for entry. */
+    val entry: Entry
+        get() = Entry(buffer.view(ENTRY_OFFSET, Entry.SIZE_BYTES))
+
+    /** The number of elements of modes in use. */
+    var modesCount: Int
+        get() = buffer.readBits(MODES_OFFSET, 1).toInt()
+        set(value) {
+            require(value in 0..3) { "modes holds at most 3 elements" }
+            buffer.writeBits(MODES_OFFSET, 1, value.toLong())
+        }
+
+    /** Returns the raw value of element [index] of modes. */
+    fun modesRaw(index: Int): UShort = buffer.readBits(MODES_OFFSET + 1 + 2 * checkIndex(index, modesCount), 2).toUShort()
+
+    /** Sets the raw value of element [index] of modes. */
+    fun setModesRaw(index: Int, value: UShort) {
+        buffer.writeBits(MODES_OFFSET + 1 + 2 * checkIndex(index, modesCount), 2, value.toLong())
+    }
+
+    /** Returns element [index] of modes. */
+    fun modes(index: Int): Mode? = Mode.fromValue(modesRaw(index))
+
+    /** The number of elements of levels in use. */
+    var levelsCount: Int
+        get() = buffer.readBits(LEVELS_OFFSET, 1).toInt()
+        set(value) {
+            require(value in 0..3) { "levels holds at most 3 elements" }
+            buffer.writeBits(LEVELS_OFFSET, 1, value.toLong())
+        }
+
+    /** Returns the raw value of element [index] of levels. */
+    fun levelsRaw(index: Int): Byte = buffer.readBits(LEVELS_OFFSET + 1 + 1 * checkIndex(index, levelsCount), 1).toByte()
+
+    /** Sets the raw value of element [index] of levels. */
+    fun setLevelsRaw(index: Int, value: Byte) {
+        buffer.writeBits(LEVELS_OFFSET + 1 + 1 * checkIndex(index, levelsCount), 1, value.toLong())
+    }
+
+    /** Returns element [index] of levels. */
+    fun levels(index: Int): Level? = Level.fromValue(levelsRaw(index))
+
+    companion object {
+        /** The size of Policy in bytes. */
+        const val SIZE_BYTES: Int = 15
+
+        /** The byte offset of access. */
+        const val ACCESS_OFFSET: Int = 0
+
+        /** The byte offset of entry. */
+        const val ENTRY_OFFSET: Int = 1
+
+        /** The byte offset of modes. */
+        const val MODES_OFFSET: Int = 4
+
+        /** The byte offset of levels. */
+        const val LEVELS_OFFSET: Int = 11
+
+        /** Returns a view of Policy from the position of [buffer]. */
+        fun wrap(buffer: ByteBuffer): Policy = Policy(buffer.view(buffer.position(), SIZE_BYTES))
+
+        /** Returns a view of Policy in a new, zeroed buffer. */
+        fun allocate(): Policy = Policy(ByteBuffer.allocate(SIZE_BYTES))
+    }
+}
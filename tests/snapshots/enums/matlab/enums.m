@@ -0,0 +1,48 @@
+% Automatically generated by Onyx IDL compiler. Do not edit.
+function enums()
+%ENUMS Defines the Simulink enums and bus objects of the schema.
+%   Run enums before loading a model that uses them. Each bus object is
+%   assigned in the base workspace, named after its struct or message.
+
+% Mode, carried on the wire as u16.
+Simulink.defineIntEnumType('Mode', {'Off', 'On', 'Auto'}, [0; 300; 301], ...
+    'StorageType', 'uint16', 'Description', 'Mode, carried on the wire as u16.');
+
+% Level, carried on the wire as i8.
+% Carried as int8 in buses, as it is open.
+
+% Access, a set of flags carried on the wire as u8.
+% Carried as uint8 in buses, as Simulink enum values do not combine.
+
+% Entry: 3 bytes.
+elements = Simulink.BusElement.empty;
+elements(end + 1) = element('mode', 'Enum: Mode', 1, 'byte 0');
+elements(end + 1) = element('level', 'int8', 1, 'byte 2');
+bus = Simulink.Bus;
+bus.Description = 'Entry: 3 bytes.';
+bus.Elements = elements;
+assignin('base', 'Entry', bus);
+
+% Policy: 15 bytes.
+elements = Simulink.BusElement.empty;
+elements(end + 1) = element('access', 'uint8', 1, 'byte 0');
+elements(end + 1) = element('entry', 'Bus: Entry', 1, 'byte 1');
+elements(end + 1) = element('modes_count', 'uint8', 1, 'byte 4; the number of elements of modes in use');
+elements(end + 1) = element('modes', 'Enum: Mode', 3, 'byte 5');
+elements(end + 1) = element('levels_count', 'uint8', 1, 'byte 11; the number of elements of levels in use');
+elements(end + 1) = element('levels', 'int8', 3, 'byte 12');
+bus = Simulink.Bus;
+bus.Description = 'Policy: 15 bytes.';
+bus.Elements = elements;
+assignin('base', 'Policy', bus);
+
+end
+
+function e = element(name, type, dimensions, description)
+% Returns a bus element.
+e = Simulink.BusElement;
+e.Name = name;
+e.DataType = type;
+e.Dimensions = dimensions;
+e.Description = description;
+end
@@ -0,0 +1,604 @@
+// Automatically generated by Onyx IDL compiler. Do not edit.
+
+use std::convert::TryInto;
+
+#[repr(u16)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mode {
+    Off = 0,
+    On = 300,
+    Auto = 301,
+}
+
+impl Default for Mode {
+    fn default() -> Self {
+        Self::Off
+    }
+}
+
+impl TryFrom<u16> for Mode {
+    type Error = u16;
+
+    fn try_from(raw: u16) -> Result<Self, u16> {
+        match raw {
+            0 => Ok(Self::Off),
+            300 => Ok(Self::On),
+            301 => Ok(Self::Auto),
+            _ => Err(raw),
+        }
+    }
+}
+
+impl From<Mode> for u16 {
+    fn from(value: Mode) -> Self {
+        value as u16
+    }
+}
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Level {
+    Low,
+    Mid,
+    High,
+    /// A value that no variant declares.
+    Unknown(i8),
+}
+
+impl Default for Level {
+    fn default() -> Self {
+        Self::Low
+    }
+}
+
+impl From<i8> for Level {
+    fn from(raw: i8) -> Self {
+        match raw {
+            1 => Self::Low,
+            2 => Self::Mid,
+            3 => Self::High,
+            _ => Self::Unknown(raw),
+        }
+    }
+}
+
+impl From<Level> for i8 {
+    fn from(value: Level) -> Self {
+        match value {
+            Level::Low => 1,
+            Level::Mid => 2,
+            Level::High => 3,
+            Level::Unknown(raw) => raw,
+        }
+    }
+}
+#[repr(transparent)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Access(u8);
+
+#[allow(non_upper_case_globals)]
+impl Access {
+    pub const Read: Self = Self(1);
+    pub const Write: Self = Self(2);
+    pub const Exec: Self = Self(4);
+    pub const All: Self = Self(7);
+
+    pub const fn empty() -> Self {
+        Self(0)
+    }
+
+    pub const fn all() -> Self {
+        Self(7)
+    }
+
+    pub const fn bits(self) -> u8 {
+        self.0
+    }
+
+    /// Returns `None` if any bit is set that no variant declares.
+    pub const fn from_bits(bits: u8) -> Option<Self> {
+        if bits & !Self::all().0 == 0 {
+            Some(Self(bits))
+        } else {
+            None
+        }
+    }
+
+    pub const fn from_bits_truncate(bits: u8) -> Self {
+        Self(bits & Self::all().0)
+    }
+
+    pub const fn from_bits_retain(bits: u8) -> Self {
+        Self(bits)
+    }
+
+    pub const fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    pub const fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    pub const fn intersects(self, other: Self) -> bool {
+        self.0 & other.0 != 0
+    }
+
+    pub fn insert(&mut self, other: Self) {
+        self.0 |= other.0;
+    }
+
+    pub fn remove(&mut self, other: Self) {
+        self.0 &= !other.0;
+    }
+
+    pub fn toggle(&mut self, other: Self) {
+        self.0 ^= other.0;
+    }
+}
+
+impl std::ops::BitOr for Access {
+    type Output = Self;
+    fn bitor(self, rhs: Self) -> Self {
+        Self(self.0 | rhs.0)
+    }
+}
+
+impl std::ops::BitOrAssign for Access {
+    fn bitor_assign(&mut self, rhs: Self) {
+        *self = *self | rhs;
+    }
+}
+
+impl std::ops::BitAnd for Access {
+    type Output = Self;
+    fn bitand(self, rhs: Self) -> Self {
+        Self(self.0 & rhs.0)
+    }
+}
+
+impl std::ops::BitAndAssign for Access {
+    fn bitand_assign(&mut self, rhs: Self) {
+        *self = *self & rhs;
+    }
+}
+
+impl std::ops::BitXor for Access {
+    type Output = Self;
+    fn bitxor(self, rhs: Self) -> Self {
+        Self(self.0 ^ rhs.0)
+    }
+}
+
+impl std::ops::BitXorAssign for Access {
+    fn bitxor_assign(&mut self, rhs: Self) {
+        *self = *self ^ rhs;
+    }
+}
+
+impl std::ops::Sub for Access {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self {
+        Self(self.0 & !rhs.0)
+    }
+}
+
+impl std::ops::SubAssign for Access {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl std::ops::Not for Access {
+    type Output = Self;
+    fn not(self) -> Self {
+        Self::from_bits_truncate(!self.0)
+    }
+}
+
+/// Size of Entry in bytes.
+pub(crate) const ENTRY_SIZE: usize = 3;
+/// Buffer type alias for Entry.
+pub type EntryBuffer = [u8; ENTRY_SIZE];
+
+/// Read-only view of Entry.
+#[derive(Debug, Clone, Copy)]
+pub struct EntryView<'a> {
+    data: &'a EntryBuffer,
+}
+
+/// Read-write view of Entry.
+#[derive(Debug)]
+pub struct EntryMutView<'a> {
+    data: &'a mut EntryBuffer,
+}
+
+impl<'a> EntryView<'a> {
+    /// Size of Entry in bytes.
+    pub const SIZE_BYTES: usize = ENTRY_SIZE;
+    /// Byte offset of mode.
+    pub const FIELD_OFFSET_MODE: usize = 0;
+    /// Byte offset of level.
+    pub const FIELD_OFFSET_LEVEL: usize = 2;
+
+    /// Creates a new EntryView from a EntryBuffer.
+    pub fn new(data: &'a EntryBuffer) -> Self {
+        Self { data }
+    }
+
+    /// Accessor for mode.
+    pub fn mode(&self) -> Result<Mode, u16> {
+        let bytes = self.data[0..2].try_into().unwrap();
+        let val = u16::from_le_bytes(bytes);
+        Mode::try_from(val)
+    }
+
+    /// Accessor for level.
+    pub fn level(&self) -> Level {
+        let bytes = self.data[2..3].try_into().unwrap();
+        let val = i8::from_le_bytes(bytes);
+        Level::from(val)
+    }
+}
+
+// Fails to compile if Entry does not match its Onyx layout.
+const _: () = assert!(EntryView::FIELD_OFFSET_LEVEL == EntryView::FIELD_OFFSET_MODE + std::mem::size_of::<u16>(), "Entry.level does not match its Onyx offset");
+const _: () = assert!(EntryView::FIELD_OFFSET_LEVEL + std::mem::size_of::<i8>() == EntryView::SIZE_BYTES, "Entry does not match its Onyx size");
+
+impl<'a> EntryMutView<'a> {
+    /// Creates a new EntryMutView from a EntryBuffer.
+    pub fn new(data: &'a mut EntryBuffer) -> Self {
+        Self { data }
+    }
+
+    /// Accessor for mode.
+    pub fn mode(&self) -> Result<Mode, u16> {
+        let bytes = self.data[0..2].try_into().unwrap();
+        let val = u16::from_le_bytes(bytes);
+        Mode::try_from(val)
+    }
+
+    /// Mutator for mode.
+    pub fn set_mode(&mut self, value: Mode) {
+        let val = u16::from(value);
+        let bytes = val.to_le_bytes();
+        self.data[0..2].copy_from_slice(&bytes);
+    }
+
+    /// Accessor for level.
+    pub fn level(&self) -> Level {
+        let bytes = self.data[2..3].try_into().unwrap();
+        let val = i8::from_le_bytes(bytes);
+        Level::from(val)
+    }
+
+    /// Mutator for level.
+    pub fn set_level(&mut self, value: Level) {
+        let val = i8::from(value);
+        let bytes = val.to_le_bytes();
+        self.data[2..3].copy_from_slice(&bytes);
+    }
+}
+
+/// Size of Policy in bytes.
+pub(crate) const POLICY_SIZE: usize = 15;
+/// Buffer type alias for Policy.
+pub type PolicyBuffer = [u8; POLICY_SIZE];
+
+/// Read-only view of Policy.
+#[derive(Debug, Clone, Copy)]
+pub struct PolicyView<'a> {
+    data: &'a PolicyBuffer,
+}
+
+/// Read-write view of Policy.
+#[derive(Debug)]
+pub struct PolicyMutView<'a> {
+    data: &'a mut PolicyBuffer,
+}
+
+impl<'a> PolicyView<'a> {
+    /// Size of Policy in bytes.
+    pub const SIZE_BYTES: usize = POLICY_SIZE;
+    /// Byte offset of access.
+    pub const FIELD_OFFSET_ACCESS: usize = 0;
+    /// Byte offset of entry.
+    pub const FIELD_OFFSET_ENTRY: usize = 1;
+    /// Byte offset of modes.
+    pub const FIELD_OFFSET_MODES: usize = 4;
+    /// Byte offset of levels.
+    pub const FIELD_OFFSET_LEVELS: usize = 11;
+
+    /// Creates a new PolicyView from a PolicyBuffer.
+    pub fn new(data: &'a PolicyBuffer) -> Self {
+        Self { data }
+    }
+
+    /// Accessor for access.
+    pub fn access(&self) -> Access {
+        let bytes = self.data[0..1].try_into().unwrap();
+        let val = u8::from_le_bytes(bytes);
+        Access::from_bits_retain(val)
+    }
+
+    /// Accessor for entry.
+    pub fn entry(&self) -> EntryView {
+        let slice = &self.data[1..4];
+        EntryView::new(slice.try_into().unwrap())
+    }
+
+    /// Accessor for modes.
+    pub fn modes(&self) -> PolicyModesView {
+        let slice = &self.data[4..11];
+        PolicyModesView { data: slice.try_into().unwrap() }
+    }
+
+    /// Accessor for levels.
+    pub fn levels(&self) -> PolicyLevelsView {
+        let slice = &self.data[11..15];
+        PolicyLevelsView { data: slice.try_into().unwrap() }
+    }
+}
+
+// Fails to compile if Policy does not match its Onyx layout.
+const _: () = assert!(PolicyView::FIELD_OFFSET_ENTRY == PolicyView::FIELD_OFFSET_ACCESS + std::mem::size_of::<u8>(), "Policy.entry does not match its Onyx offset");
+const _: () = assert!(PolicyView::FIELD_OFFSET_MODES == PolicyView::FIELD_OFFSET_ENTRY + ENTRY_SIZE, "Policy.modes does not match its Onyx offset");
+const _: () = assert!(PolicyView::FIELD_OFFSET_LEVELS == PolicyView::FIELD_OFFSET_MODES + (std::mem::size_of::<u8>() + 3 * std::mem::size_of::<u16>()), "Policy.levels does not match its Onyx offset");
+const _: () = assert!(PolicyView::FIELD_OFFSET_LEVELS + (std::mem::size_of::<u8>() + 3 * std::mem::size_of::<i8>()) == PolicyView::SIZE_BYTES, "Policy does not match its Onyx size");
+
+impl<'a> PolicyMutView<'a> {
+    /// Creates a new PolicyMutView from a PolicyBuffer.
+    pub fn new(data: &'a mut PolicyBuffer) -> Self {
+        Self { data }
+    }
+
+    /// Accessor for access.
+    pub fn access(&self) -> Access {
+        let bytes = self.data[0..1].try_into().unwrap();
+        let val = u8::from_le_bytes(bytes);
+        Access::from_bits_retain(val)
+    }
+
+    /// Mutator for access.
+    pub fn set_access(&mut self, value: Access) {
+        let val = value.bits();
+        let bytes = val.to_le_bytes();
+        self.data[0..1].copy_from_slice(&bytes);
+    }
+
+    /// Accessor for entry.
+    pub fn entry(&self) -> EntryView {
+        let slice = &self.data[1..4];
+        EntryView::new(slice.try_into().unwrap())
+    }
+
+    /// Mutable accessor for entry.
+    pub fn entry_mut(&mut self) -> EntryMutView {
+        let slice = &mut self.data[1..4];
+        EntryMutView::new(slice.try_into().unwrap())
+    }
+
+    /// Accessor for modes.
+    pub fn modes(&self) -> PolicyModesView {
+        let slice = &self.data[4..11];
+        PolicyModesView { data: slice.try_into().unwrap() }
+    }
+
+    /// Mutable accessor for modes.
+    pub fn modes_mut(&mut self) -> PolicyModesMutView {
+        let slice = &mut self.data[4..11];
+        PolicyModesMutView { data: slice.try_into().unwrap() }
+    }
+
+    /// Accessor for levels.
+    pub fn levels(&self) -> PolicyLevelsView {
+        let slice = &self.data[11..15];
+        PolicyLevelsView { data: slice.try_into().unwrap() }
+    }
+
+    /// Mutable accessor for levels.
+    pub fn levels_mut(&mut self) -> PolicyLevelsMutView {
+        let slice = &mut self.data[11..15];
+        PolicyLevelsMutView { data: slice.try_into().unwrap() }
+    }
+}
+
+/// Read-only view of the bounded vector Policy.modes.
+#[derive(Debug, Clone, Copy)]
+pub struct PolicyModesView<'a> {
+    data: &'a [u8; 7],
+}
+
+impl<'a> PolicyModesView<'a> {
+    /// The maximum number of elements.
+    pub const CAPACITY: usize = 3;
+
+    /// Returns the number of elements, clamped to the capacity.
+    pub fn len(&self) -> usize {
+        (self.data[0] as usize).min(Self::CAPACITY)
+    }
+
+    /// Returns true if the vector has no elements.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the element at `index`, or `None` if it is out of range.
+    pub fn get(&self, index: usize) -> Option<Result<Mode, u16>> {
+        if index >= self.len() {
+            return None;
+        }
+        Some(Mode::try_from(u16::from_le_bytes(self.data[1 + index * 2..1 + index * 2 + 2].try_into().unwrap())))
+    }
+
+    /// Returns an iterator over the elements.
+    pub fn iter(&self) -> impl Iterator<Item = Result<Mode, u16>> + 'a {
+        let view = *self;
+        (0..self.len()).map(move |index| view.get(index).unwrap())
+    }
+}
+
+/// Read-write view of the bounded vector Policy.modes.
+#[derive(Debug)]
+pub struct PolicyModesMutView<'a> {
+    data: &'a mut [u8; 7],
+}
+
+impl<'a> PolicyModesMutView<'a> {
+    /// The maximum number of elements.
+    pub const CAPACITY: usize = 3;
+
+    /// Returns a read-only view of the vector.
+    pub fn as_view(&self) -> PolicyModesView<'_> {
+        PolicyModesView { data: self.data }
+    }
+
+    /// Returns the number of elements, clamped to the capacity.
+    pub fn len(&self) -> usize {
+        self.as_view().len()
+    }
+
+    /// Returns true if the vector has no elements.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the element at `index`, or `None` if it is out of range.
+    pub fn get(&self, index: usize) -> Option<Result<Mode, u16>> {
+        self.as_view().get(index)
+    }
+
+    /// Returns an iterator over the elements.
+    pub fn iter(&self) -> impl Iterator<Item = Result<Mode, u16>> + '_ {
+        self.as_view().iter()
+    }
+
+    fn set_len(&mut self, len: usize) {
+        self.data[0] = len as u8;
+    }
+
+    /// Removes all elements.
+    pub fn clear(&mut self) {
+        self.set_len(0);
+    }
+
+    /// Replaces the element at `index`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is not less than `len()`.
+    pub fn set(&mut self, index: usize, value: Mode) {
+        assert!(index < self.len(), "index {index} out of range");
+        self.data[1 + index * 2..1 + index * 2 + 2].copy_from_slice(&u16::from(value).to_le_bytes());
+    }
+
+    /// Appends an element, or returns it back if the vector is full.
+    pub fn push(&mut self, value: Mode) -> Result<(), Mode> {
+        let index = self.len();
+        if index == Self::CAPACITY {
+            return Err(value);
+        }
+        self.set_len(index + 1);
+        self.set(index, value);
+        Ok(())
+    }
+}
+
+/// Read-only view of the bounded vector Policy.levels.
+#[derive(Debug, Clone, Copy)]
+pub struct PolicyLevelsView<'a> {
+    data: &'a [u8; 4],
+}
+
+impl<'a> PolicyLevelsView<'a> {
+    /// The maximum number of elements.
+    pub const CAPACITY: usize = 3;
+
+    /// Returns the number of elements, clamped to the capacity.
+    pub fn len(&self) -> usize {
+        (self.data[0] as usize).min(Self::CAPACITY)
+    }
+
+    /// Returns true if the vector has no elements.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the element at `index`, or `None` if it is out of range.
+    pub fn get(&self, index: usize) -> Option<Level> {
+        if index >= self.len() {
+            return None;
+        }
+        Some(Level::from(i8::from_le_bytes(self.data[1 + index * 1..1 + index * 1 + 1].try_into().unwrap())))
+    }
+
+    /// Returns an iterator over the elements.
+    pub fn iter(&self) -> impl Iterator<Item = Level> + 'a {
+        let view = *self;
+        (0..self.len()).map(move |index| view.get(index).unwrap())
+    }
+}
+
+/// Read-write view of the bounded vector Policy.levels.
+#[derive(Debug)]
+pub struct PolicyLevelsMutView<'a> {
+    data: &'a mut [u8; 4],
+}
+
+impl<'a> PolicyLevelsMutView<'a> {
+    /// The maximum number of elements.
+    pub const CAPACITY: usize = 3;
+
+    /// Returns a read-only view of the vector.
+    pub fn as_view(&self) -> PolicyLevelsView<'_> {
+        PolicyLevelsView { data: self.data }
+    }
+
+    /// Returns the number of elements, clamped to the capacity.
+    pub fn len(&self) -> usize {
+        self.as_view().len()
+    }
+
+    /// Returns true if the vector has no elements.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the element at `index`, or `None` if it is out of range.
+    pub fn get(&self, index: usize) -> Option<Level> {
+        self.as_view().get(index)
+    }
+
+    /// Returns an iterator over the elements.
+    pub fn iter(&self) -> impl Iterator<Item = Level> + '_ {
+        self.as_view().iter()
+    }
+
+    fn set_len(&mut self, len: usize) {
+        self.data[0] = len as u8;
+    }
+
+    /// Removes all elements.
+    pub fn clear(&mut self) {
+        self.set_len(0);
+    }
+
+    /// Replaces the element at `index`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is not less than `len()`.
+    pub fn set(&mut self, index: usize, value: Level) {
+        assert!(index < self.len(), "index {index} out of range");
+        self.data[1 + index * 1..1 + index * 1 + 1].copy_from_slice(&i8::from(value).to_le_bytes());
+    }
+
+    /// Appends an element, or returns it back if the vector is full.
+    pub fn push(&mut self, value: Level) -> Result<(), Level> {
+        let index = self.len();
+        if index == Self::CAPACITY {
+            return Err(value);
+        }
+        self.set_len(index + 1);
+        self.set(index, value);
+        Ok(())
+    }
+}
@@ -0,0 +1,50 @@
+// Automatically generated by Onyx IDL compiler. Do not edit.
+`ifndef ONYX_ENUMS_PKG_SV
+`define ONYX_ENUMS_PKG_SV
+
+package enums_pkg;
+
+  // Mode, carried on the wire as u16.
+  typedef enum logic [15:0] {
+    Mode_Off = 16'd0,
+    Mode_On = 16'd300,
+    Mode_Auto = 16'd301
+  } Mode;
+
+  // Level, carried on the wire as i8.
+  typedef enum logic [7:0] {
+    Level_Low = 8'd1,
+    Level_Mid = 8'd2,
+    Level_High = 8'd3
+  } Level;
+
+  // Access, a set of flags carried on the wire as u8.
+  typedef logic [7:0] Access;
+  localparam Access Access_Read = 8'd1;
+  localparam Access Access_Write = 8'd2;
+  localparam Access Access_Exec = 8'd4;
+  localparam Access Access_All = 8'd7;
+
+  // Entry: 3 bytes.
+  localparam int Entry_SIZE_BYTES = 3;
+  localparam int Entry_mode_OFFSET = 0;
+  localparam int Entry_mode_WIDTH = 16;
+  localparam int Entry_level_OFFSET = 2;
+  localparam int Entry_level_WIDTH = 8;
+
+  // Policy: 15 bytes.
+  localparam int Policy_SIZE_BYTES = 15;
+  localparam int Policy_access_OFFSET = 0;
+  localparam int Policy_access_WIDTH = 8;
+  localparam int Policy_entry_OFFSET = 1;
+  localparam int Policy_entry_WIDTH = 24;
+  localparam int Policy_modes_OFFSET = 4;
+  localparam int Policy_modes_WIDTH = 56;
+  localparam int Policy_modes_CAPACITY = 3;
+  localparam int Policy_levels_OFFSET = 11;
+  localparam int Policy_levels_WIDTH = 32;
+  localparam int Policy_levels_CAPACITY = 3;
+
+endpackage
+
+`endif // ONYX_ENUMS_PKG_SV
@@ -0,0 +1,62 @@
+// Automatically generated by Onyx IDL compiler. Do not edit.
+#include "host.hpp"
+
+#include <string.h>
+
+namespace onyx {
+
+Slot* Slot::Deserialize(Buffer& buffer) {
+    // Overlay the class structure onto the buffer memory. This is the zero-copy step.
+    Slot* result = (Slot*)buffer;
+    bool known = true;
+
+    // INFO: The buffer is in host byte order, no swap needed.
+    // CHECK: Closed enum field mode
+    known = is_known(result->__raw_mode) && known;
+
+    return known ? result : nullptr;
+}
+
+void Slot::Serialize(Buffer& buffer) const {
+    // Copy the host-endian object into the buffer first.
+    memcpy(buffer, this, kSizeOf);
+    // INFO: The buffer is in host byte order, no swap needed.
+}
+
+Ring* Ring::Deserialize(Buffer& buffer) {
+    // Overlay the class structure onto the buffer memory. This is the zero-copy step.
+    Ring* result = (Ring*)buffer;
+    bool known = true;
+
+    // INFO: The buffer is in host byte order, no swap needed.
+    // CHECK: Vector field slots
+    for (size_t i = 0; i < 4; ++i) {
+        if (!Slot::Deserialize(*(Slot::Buffer*) &result->__raw_slots.__items[i]) && i < result->__raw_slots.len()) known = false;
+    }
+
+    return known ? result : nullptr;
+}
+
+void Ring::Serialize(Buffer& buffer) const {
+    // Copy the host-endian object into the buffer first.
+    memcpy(buffer, this, kSizeOf);
+    // INFO: The buffer is in host byte order, no swap needed.
+}
+
+bool DecodeAny(uint8_t* buffer, size_t size, AnyMessage& out) {
+    if (size < kMessageIdSize) return false;
+    uint16_t raw;
+    memcpy(&raw, buffer, kMessageIdSize);
+    out.id = static_cast<MessageId>(utils::byteswap_if_needed(raw));
+    uint8_t* body = buffer + kMessageIdSize;
+    size -= kMessageIdSize;
+    switch (out.id) {
+    case MessageId::Ring:
+        if (size < Ring::kSizeOf) return false;
+        out.message = Ring::Deserialize(*reinterpret_cast<Ring::Buffer*>(body));
+        return out.message != nullptr;
+    }
+    return false;
+}
+
+} // namespace onyx
@@ -0,0 +1,413 @@
+// Automatically generated by Onyx IDL compiler. Do not edit.
+#ifndef ONYX_HOST_H_
+#define ONYX_HOST_H_
+
+#include <stddef.h>
+#include <stdint.h>
+#include <string.h>
+
+#pragma pack(push, 1)
+
+namespace onyx {
+#ifndef ONYX_UTILS_ENDIANNESS_HOST_
+#define ONYX_UTILS_ENDIANNESS_HOST_
+
+namespace utils {
+
+// Define network endianness (usually Big Endian).
+#define ONYX_BIG_ENDIAN 4321
+#define ONYX_LITTLE_ENDIAN 1234
+#define ONYX_NETWORK_ORDER ONYX_HOST_ORDER
+
+// Determine host endianness at compile time (using standard GCC/Clang macros)
+#ifdef __BYTE_ORDER__
+    #if __BYTE_ORDER__ == __ORDER_BIG_ENDIAN__
+        #define ONYX_HOST_ORDER ONYX_BIG_ENDIAN
+    #elif __BYTE_ORDER__ == __ORDER_LITTLE_ENDIAN__
+        #define ONYX_HOST_ORDER ONYX_LITTLE_ENDIAN
+    #else
+        #error "Unsupported host endianness"
+    #endif
+#elif defined(_WIN32) || defined(__LITTLE_ENDIAN__)
+    // Fallback for Windows (always little-endian) or other platforms
+    #define ONYX_HOST_ORDER ONYX_LITTLE_ENDIAN
+#else
+    #error "Could not determine host endianness"
+#endif
+
+// Helper function to swap bytes (Explicit Overloads)
+inline uint16_t byteswap(uint16_t value) { return ((value << 8) & 0xFF00) | ((value >> 8) & 0x00FF); }
+
+inline int16_t byteswap(int16_t value) { return (int16_t)byteswap((uint16_t)value); }
+
+inline uint32_t byteswap(uint32_t value) { return (value << 24) | ((value & 0x00FF0000) >> 8) | ((value & 0x0000FF00) << 8) | (value >> 24); }
+
+inline int32_t byteswap(int32_t value) { return (int32_t)byteswap((uint32_t)value); }
+
+inline uint64_t byteswap(uint64_t value) { return (value << 56) | ((value & 0x00FF000000000000ULL) >> 40) | ((value & 0x0000FF0000000000ULL) >> 24) | ((value & 0x000000FF00000000ULL) >> 8) | ((value & 0x00000000FF000000ULL) << 8) | ((value & 0x0000000000FF0000ULL) << 24) | ((value & 0x000000000000FF00ULL) << 40) | (value >> 56); }
+
+inline int64_t byteswap(int64_t value) { return (int64_t)byteswap((uint64_t)value); }
+
+inline float byteswap(float value) {
+    uint32_t temp;
+    memcpy(&temp, &value, sizeof(value));
+    temp = byteswap(temp);
+    memcpy(&value, &temp, sizeof(value));
+    return value;
+}
+
+inline double byteswap(double value) {
+    uint64_t temp;
+    memcpy(&temp, &value, sizeof(value));
+    temp = byteswap(temp);
+    memcpy(&value, &temp, sizeof(value));
+    return value;
+}
+
+// Swaps bytes if the host order does not match the network order (Explicit Overloads)
+inline uint16_t byteswap_if_needed(uint16_t value) {
+    if (ONYX_HOST_ORDER != ONYX_NETWORK_ORDER) {
+        return byteswap(value);
+    }
+    return value;
+}
+
+inline int16_t byteswap_if_needed(int16_t value) {
+    if (ONYX_HOST_ORDER != ONYX_NETWORK_ORDER) {
+        return byteswap(value);
+    }
+    return value;
+}
+
+inline uint32_t byteswap_if_needed(uint32_t value) {
+    if (ONYX_HOST_ORDER != ONYX_NETWORK_ORDER) {
+        return byteswap(value);
+    }
+    return value;
+}
+
+inline int32_t byteswap_if_needed(int32_t value) {
+    if (ONYX_HOST_ORDER != ONYX_NETWORK_ORDER) {
+        return byteswap(value);
+    }
+    return value;
+}
+
+inline uint64_t byteswap_if_needed(uint64_t value) {
+    if (ONYX_HOST_ORDER != ONYX_NETWORK_ORDER) {
+        return byteswap(value);
+    }
+    return value;
+}
+
+inline int64_t byteswap_if_needed(int64_t value) {
+    if (ONYX_HOST_ORDER != ONYX_NETWORK_ORDER) {
+        return byteswap(value);
+    }
+    return value;
+}
+
+inline float byteswap_if_needed(float value) {
+    if (ONYX_HOST_ORDER != ONYX_NETWORK_ORDER) {
+        return byteswap(value);
+    }
+    return value;
+}
+
+inline double byteswap_if_needed(double value) {
+    if (ONYX_HOST_ORDER != ONYX_NETWORK_ORDER) {
+        return byteswap(value);
+    }
+    return value;
+}
+
+} // namespace utils
+#endif // ONYX_UTILS_ENDIANNESS_HOST_
+
+#ifndef ONYX_UTILS_BOUNDED_VEC_
+#define ONYX_UTILS_BOUNDED_VEC_
+
+namespace utils {
+
+/// A bounded vector: a length followed by room for N elements, all of them inline.
+template <typename T, typename LenT, size_t N>
+class BoundedVec {
+public:
+    LenT __length;
+    T __items[N];
+
+    /// Returns the number of elements, clamped to the capacity.
+    inline size_t len() const { return __length < N ? __length : N; }
+    /// Returns the maximum number of elements.
+    static inline size_t capacity() { return N; }
+    /// Returns true if the vector has no elements.
+    inline bool empty() const { return len() == 0; }
+    /// Returns the element at `i`, which must be less than len().
+    inline const T& get(size_t i) const { return __items[i]; }
+    inline T& get(size_t i) { return __items[i]; }
+    /// Replaces the element at `i`, which must be less than len().
+    inline void set(size_t i, const T& value) { __items[i] = value; }
+    /// Appends an element. Returns false, leaving the vector unchanged, if it is full.
+    inline bool push(const T& value) {
+        size_t n = len();
+        if (n == N) return false;
+        __items[n] = value;
+        __length = static_cast<LenT>(n + 1);
+        return true;
+    }
+    /// Removes all elements.
+    inline void clear() { __length = 0; }
+
+    inline const T* begin() const { return __items; }
+    inline const T* end() const { return __items + len(); }
+    inline T* begin() { return __items; }
+    inline T* end() { return __items + len(); }
+};
+
+} // namespace utils
+
+#endif // ONYX_UTILS_BOUNDED_VEC_
+
+/// Size of the ID that precedes an identified message, in bytes.
+static const size_t kMessageIdSize = 2;
+
+/// The IDs of the messages that DecodeAny dispatches on.
+enum class MessageId : uint16_t {
+    Ring = 2,
+};
+
+/// Writes a message ID to the first kMessageIdSize bytes of the buffer.
+inline void WriteMessageId(MessageId id, uint8_t* buffer) {
+    uint16_t raw = utils::byteswap_if_needed(static_cast<uint16_t>(id));
+    memcpy(buffer, &raw, kMessageIdSize);
+}
+
+class Slot;
+class Ring;
+
+enum class Mode : uint16_t {
+    Idle,
+    Busy = 1,
+};
+
+/// Returns true if `value` is declared by a variant of Mode.
+inline bool is_known(Mode value) {
+    return value == Mode::Idle || value == Mode::Busy;
+}
+
+class Slot {
+private:
+    Mode __raw_mode;
+    uint32_t __raw_owner;
+
+public:
+    static const size_t kSizeOf = 6;
+    using Buffer = uint8_t[kSizeOf];
+
+    /// Byte offsets of the fields within the buffer.
+    static const size_t kModeOffset = 0;
+    static const size_t kOwnerOffset = 2;
+
+    /// Accessor for mode
+    inline const Mode mode() const { return __raw_mode; }
+
+    /// Mutator for mode
+    inline void mode(const Mode value) { __raw_mode = value; }
+
+    /// Accessor for owner
+    inline const uint32_t owner() const { return __raw_owner; }
+
+    /// Mutator for owner
+    inline void owner(const uint32_t value) { __raw_owner = value; }
+
+    /// Deserializes the network-endian buffer to Slot in-place by casting
+    /// and applying endianness correction to match the host-endianness.
+    /// NOTE: This mutates the input buffer to host-endianness.
+    /// Returns a pointer to the object within the buffer.
+    /// Returns nullptr if a closed enum holds a value that no variant declares;
+    /// the buffer is converted either way.
+    static Slot* Deserialize(Buffer& buffer);
+
+    /// Serializes the host-endian object into the provided buffer,
+    /// applying endianness correction to match the network-endianness.
+    void Serialize(Buffer& buffer) const;
+
+private:
+    /// Fails to compile if Slot does not match its Onyx layout.
+    static inline void __check_layout() {
+        static_assert(sizeof(Slot) == kSizeOf, "Slot does not match its Onyx size");
+        static_assert(offsetof(Slot, __raw_mode) == kModeOffset, "Slot::mode does not match its Onyx offset");
+        static_assert(offsetof(Slot, __raw_owner) == kOwnerOffset, "Slot::owner does not match its Onyx offset");
+    }
+};
+
+class Ring {
+private:
+    uint32_t __raw_head;
+    uint32_t __raw_tail;
+    uint8_t __raw_ready[2];
+    utils::BoundedVec<Slot, uint8_t, 4> __raw_slots;
+    double __raw_load;
+
+public:
+    static const size_t kSizeOf = 43;
+    using Buffer = uint8_t[kSizeOf];
+
+    /// Byte offsets of the fields within the buffer.
+    static const size_t kHeadOffset = 0;
+    static const size_t kTailOffset = 4;
+    static const size_t kSlotsOffset = 10;
+    static const size_t kLoadOffset = 35;
+
+    /// The ID that precedes Ring on the wire.
+    static const MessageId kId = MessageId::Ring;
+
+    /// Accessor for head
+    inline const uint32_t head() const { return __raw_head; }
+
+    /// Mutator for head
+    inline void head(const uint32_t value) { __raw_head = value; }
+
+    /// Accessor for tail
+    inline const uint32_t tail() const { return __raw_tail; }
+
+    /// Mutator for tail
+    inline void tail(const uint32_t value) { __raw_tail = value; }
+
+    /// Accessor for ready
+    inline const bool ready() const {
+        auto raw_value = reinterpret_cast<const uint16_t*>(&__raw_ready[0]);
+        return static_cast<const bool>(((*raw_value >> 0) & 0x1));
+    }
+
+    /// Mutator for ready
+    inline void ready(bool value) {
+        auto raw_container = reinterpret_cast<uint16_t*>(&__raw_ready[0]);
+        *raw_container &= ~(((uint16_t)0x1) << 0);
+        *raw_container |= ((((uint16_t)value) & 0x1) << 0);
+    }
+
+    /// Accessor for epoch
+    inline const uint16_t epoch() const {
+        auto raw_value = reinterpret_cast<const uint16_t*>(&__raw_ready[0]);
+        return static_cast<const uint16_t>(((*raw_value >> 1) & 0x7FFF));
+    }
+
+    /// Mutator for epoch
+    inline void epoch(uint16_t value) {
+        auto raw_container = reinterpret_cast<uint16_t*>(&__raw_ready[0]);
+        *raw_container &= ~(((uint16_t)0x7FFF) << 1);
+        *raw_container |= ((((uint16_t)value) & 0x7FFF) << 1);
+    }
+
+    /// Accessor for slots
+    inline utils::BoundedVec<Slot, uint8_t, 4>& slots() { return __raw_slots; }
+
+    /// Mutator for slots
+    inline void slots(const utils::BoundedVec<Slot, uint8_t, 4> value) { __raw_slots = value; }
+
+    /// Accessor for load
+    inline const double load() const { return __raw_load; }
+
+    /// Mutator for load
+    inline void load(const double value) { __raw_load = value; }
+
+    /// Deserializes the network-endian buffer to Ring in-place by casting
+    /// and applying endianness correction to match the host-endianness.
+    /// NOTE: This mutates the input buffer to host-endianness.
+    /// Returns a pointer to the object within the buffer.
+    /// Returns nullptr if a closed enum holds a value that no variant declares;
+    /// the buffer is converted either way.
+    static Ring* Deserialize(Buffer& buffer);
+
+    /// Serializes the host-endian object into the provided buffer,
+    /// applying endianness correction to match the network-endianness.
+    void Serialize(Buffer& buffer) const;
+
+private:
+    /// Fails to compile if Ring does not match its Onyx layout.
+    static inline void __check_layout() {
+        static_assert(sizeof(Ring) == kSizeOf, "Ring does not match its Onyx size");
+        static_assert(offsetof(Ring, __raw_head) == kHeadOffset, "Ring::head does not match its Onyx offset");
+        static_assert(offsetof(Ring, __raw_tail) == kTailOffset, "Ring::tail does not match its Onyx offset");
+        static_assert(offsetof(Ring, __raw_slots) == kSlotsOffset, "Ring::slots does not match its Onyx offset");
+        static_assert(offsetof(Ring, __raw_load) == kLoadOffset, "Ring::load does not match its Onyx offset");
+    }
+};
+
+/// A message deserialized by DecodeAny: its ID and the message within the buffer.
+struct AnyMessage {
+    MessageId id;
+    void* message;
+
+    /// Returns the message as a T, or nullptr if it is another message.
+    template <typename T>
+    T* as() const {
+        return id == T::kId ? static_cast<T*>(message) : nullptr;
+    }
+};
+
+/// Deserializes the message that follows a message ID in the buffer, in place.
+/// Returns false if the buffer is too short, no message has the ID, or the message's
+/// Deserialize returns nullptr.
+bool DecodeAny(uint8_t* buffer, size_t size, AnyMessage& out);
+
+
+inline Slot* Slot::Deserialize(Buffer& buffer) {
+    // Overlay the class structure onto the buffer memory. This is the zero-copy step.
+    Slot* result = (Slot*)buffer;
+    bool known = true;
+
+    // INFO: The buffer is in host byte order, no swap needed.
+    // CHECK: Closed enum field mode
+    known = is_known(result->__raw_mode) && known;
+
+    return known ? result : nullptr;
+}
+
+inline void Slot::Serialize(Buffer& buffer) const {
+    // Copy the host-endian object into the buffer first.
+    memcpy(buffer, this, kSizeOf);
+    // INFO: The buffer is in host byte order, no swap needed.
+}
+
+inline Ring* Ring::Deserialize(Buffer& buffer) {
+    // Overlay the class structure onto the buffer memory. This is the zero-copy step.
+    Ring* result = (Ring*)buffer;
+    bool known = true;
+
+    // INFO: The buffer is in host byte order, no swap needed.
+    // CHECK: Vector field slots
+    for (size_t i = 0; i < 4; ++i) {
+        if (!Slot::Deserialize(*(Slot::Buffer*) &result->__raw_slots.__items[i]) && i < result->__raw_slots.len()) known = false;
+    }
+
+    return known ? result : nullptr;
+}
+
+inline void Ring::Serialize(Buffer& buffer) const {
+    // Copy the host-endian object into the buffer first.
+    memcpy(buffer, this, kSizeOf);
+    // INFO: The buffer is in host byte order, no swap needed.
+}
+
+inline bool DecodeAny(uint8_t* buffer, size_t size, AnyMessage& out) {
+    if (size < kMessageIdSize) return false;
+    uint16_t raw;
+    memcpy(&raw, buffer, kMessageIdSize);
+    out.id = static_cast<MessageId>(utils::byteswap_if_needed(raw));
+    uint8_t* body = buffer + kMessageIdSize;
+    size -= kMessageIdSize;
+    switch (out.id) {
+    case MessageId::Ring:
+        if (size < Ring::kSizeOf) return false;
+        out.message = Ring::Deserialize(*reinterpret_cast<Ring::Buffer*>(body));
+        return out.message != nullptr;
+    }
+    return false;
+}
+
+} // namespace onyx
+
+#pragma pack(pop)
+#endif // ONYX_HOST_H_
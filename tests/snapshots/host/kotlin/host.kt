@@ -0,0 +1,184 @@
+// Automatically generated by Onyx IDL compiler. Do not edit.
+package onyx
+
+import java.nio.ByteBuffer
+import java.nio.ByteOrder
+
+/** The byte order of every field in this file. */
+private val BYTE_ORDER: ByteOrder = ByteOrder.nativeOrder()
+
+/** Returns the [size] bytes of the buffer from [offset] as a buffer of their own. */
+private fun ByteBuffer.view(offset: Int, size: Int): ByteBuffer =
+    (duplicate().clear().position(offset).limit(offset + size) as ByteBuffer).slice()
+
+/** Reads the [size] bytes from [offset] as an unsigned integer in [BYTE_ORDER]. */
+private fun ByteBuffer.readBits(offset: Int, size: Int): Long {
+    var raw = 0L
+    for (i in 0 until size) {
+        val index = if (BYTE_ORDER == ByteOrder.BIG_ENDIAN) i else size - 1 - i
+        raw = (raw shl 8) or (get(offset + index).toLong() and 0xFFL)
+    }
+    return raw
+}
+
+/** Writes the low [size] bytes of [raw] from [offset] in [BYTE_ORDER]. */
+private fun ByteBuffer.writeBits(offset: Int, size: Int, raw: Long) {
+    for (i in 0 until size) {
+        val shift = if (BYTE_ORDER == ByteOrder.BIG_ENDIAN) 8 * (size - 1 - i) else 8 * i
+        put(offset + i, (raw ushr shift).toByte())
+    }
+}
+
+/** Copies the [size] bytes from [offset]. */
+private fun ByteBuffer.readBytes(offset: Int, size: Int): ByteArray =
+    ByteArray(size).also { view(offset, size).get(it) }
+
+/** Writes [value], which must hold exactly [size] bytes, from [offset]. */
+private fun ByteBuffer.writeBytes(offset: Int, size: Int, value: ByteArray) {
+    require(value.size == size) { "expected $size bytes, got ${value.size}" }
+    view(offset, size).put(value)
+}
+
+/** Returns [index] if it is below [count], and throws otherwise. */
+private fun checkIndex(index: Int, count: Int): Int {
+    if (index !in 0 until count) {
+        throw IndexOutOfBoundsException("index $index is out of bounds for $count elements")
+    }
+    return index
+}
+
+/** Mode, carried on the wire as u16. */
+enum class Mode(val value: UShort) {
+    Idle(0u),
+    Busy(1u),
+    ;
+
+    companion object {
+        /** The size of Mode in bytes. */
+        const val SIZE_BYTES: Int = 2
+
+        /** Returns the variant whose value is [value], or null if there is none. */
+        fun fromValue(value: UShort): Mode? = values().firstOrNull { it.value == value }
+    }
+}
+
+/** Slot, viewed in [buffer] from its index 0. */
+@JvmInline
+value class Slot(val buffer: ByteBuffer) {
+    /** The raw value of mode, which may name no variant. */
+    var modeRaw: UShort
+        get() = buffer.readBits(MODE_OFFSET, 2).toUShort()
+        set(value) {
+            buffer.writeBits(MODE_OFFSET, 2, value.toLong())
+        }
+
+    /** Accessor for mode, or null if it names no variant. */
+    val mode: Mode?
+        get() = Mode.fromValue(modeRaw)
+
+    /** Accessor for owner. */
+    var owner: UInt
+        get() = buffer.readBits(OWNER_OFFSET, 4).toUInt()
+        set(value) {
+            buffer.writeBits(OWNER_OFFSET, 4, value.toLong())
+        }
+
+    companion object {
+        /** The size of Slot in bytes. */
+        const val SIZE_BYTES: Int = 6
+
+        /** The byte offset of mode. */
+        const val MODE_OFFSET: Int = 0
+
+        /** The byte offset of owner. */
+        const val OWNER_OFFSET: Int = 2
+
+        /** Returns a view of Slot from the position of [buffer]. */
+        fun wrap(buffer: ByteBuffer): Slot = Slot(buffer.view(buffer.position(), SIZE_BYTES))
+
+        /** Returns a view of Slot in a new, zeroed buffer. */
+        fun allocate(): Slot = Slot(ByteBuffer.allocate(SIZE_BYTES))
+    }
+}
+
+/** Ring, viewed in [buffer] from its index 0. */
+@JvmInline
+value class Ring(val buffer: ByteBuffer) {
+    /** Accessor for head. */
+    var head: UInt
+        get() = buffer.readBits(HEAD_OFFSET, 4).toUInt()
+        set(value) {
+            buffer.writeBits(HEAD_OFFSET, 4, value.toLong())
+        }
+
+    /** Accessor for tail. */
+    var tail: UInt
+        get() = buffer.readBits(TAIL_OFFSET, 4).toUInt()
+        set(value) {
+            buffer.writeBits(TAIL_OFFSET, 4, value.toLong())
+        }
+
+    /** Accessor for ready. */
+    var ready: Boolean
+        get() = (buffer.readBits(READY_OFFSET, 2) and 0x1L) != 0L
+        set(value) {
+            buffer.writeBits(READY_OFFSET, 2, (buffer.readBits(READY_OFFSET, 2) and 0x1L.inv()) or ((if (value) 1L else 0L) and 0x1L))
+        }
+
+    /** Accessor for epoch. */
+    var epoch: UShort
+        get() = ((buffer.readBits(EPOCH_OFFSET, 2) ushr 1) and 0x7FFFL).toUShort()
+        set(value) {
+            buffer.writeBits(EPOCH_OFFSET, 2, (buffer.readBits(EPOCH_OFFSET, 2) and (0x7FFFL shl 1).inv()) or ((value.toLong() and 0x7FFFL) shl 1))
+        }
+
+    /** The number of elements of slots in use. */
+    var slotsCount: Int
+        get() = buffer.readBits(SLOTS_OFFSET, 1).toInt()
+        set(value) {
+            require(value in 0..4) { "slots holds at most 4 elements" }
+            buffer.writeBits(SLOTS_OFFSET, 1, value.toLong())
+        }
+
+    /** Returns element [index] of slots. */
+    fun slots(index: Int): Slot = Slot(buffer.view(SLOTS_OFFSET + 1 + 6 * checkIndex(index, slotsCount), Slot.SIZE_BYTES))
+
+    /** Accessor for load. */
+    var load: Double
+        get() = Double.fromBits(buffer.readBits(LOAD_OFFSET, 8))
+        set(value) {
+            buffer.writeBits(LOAD_OFFSET, 8, value.toRawBits())
+        }
+
+    companion object {
+        /** The size of Ring in bytes. */
+        const val SIZE_BYTES: Int = 43
+
+        /** The ID of the message, which precedes it on the wire. */
+        const val ID: Int = 2
+
+        /** The byte offset of head. */
+        const val HEAD_OFFSET: Int = 0
+
+        /** The byte offset of tail. */
+        const val TAIL_OFFSET: Int = 4
+
+        /** The byte offset of the bit-field container holding ready. */
+        const val READY_OFFSET: Int = 8
+
+        /** The byte offset of the bit-field container holding epoch. */
+        const val EPOCH_OFFSET: Int = 8
+
+        /** The byte offset of slots. */
+        const val SLOTS_OFFSET: Int = 10
+
+        /** The byte offset of load. */
+        const val LOAD_OFFSET: Int = 35
+
+        /** Returns a view of Ring from the position of [buffer]. */
+        fun wrap(buffer: ByteBuffer): Ring = Ring(buffer.view(buffer.position(), SIZE_BYTES))
+
+        /** Returns a view of Ring in a new, zeroed buffer. */
+        fun allocate(): Ring = Ring(ByteBuffer.allocate(SIZE_BYTES))
+    }
+}
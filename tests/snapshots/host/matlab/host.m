@@ -0,0 +1,43 @@
+% Automatically generated by Onyx IDL compiler. Do not edit.
+function host()
+%HOST Defines the Simulink enums and bus objects of the schema.
+%   Run host before loading a model that uses them. Each bus object is
+%   assigned in the base workspace, named after its struct or message.
+
+% Mode, carried on the wire as u16.
+Simulink.defineIntEnumType('Mode', {'Idle', 'Busy'}, [0; 1], ...
+    'StorageType', 'uint16', 'Description', 'Mode, carried on the wire as u16.');
+
+% Slot: 6 bytes.
+elements = Simulink.BusElement.empty;
+elements(end + 1) = element('mode', 'Enum: Mode', 1, 'byte 0');
+elements(end + 1) = element('owner', 'uint32', 1, 'byte 2');
+bus = Simulink.Bus;
+bus.Description = 'Slot: 6 bytes.';
+bus.Elements = elements;
+assignin('base', 'Slot', bus);
+
+% Ring: 43 bytes. Message ID 2.
+elements = Simulink.BusElement.empty;
+elements(end + 1) = element('head', 'uint32', 1, 'byte 0');
+elements(end + 1) = element('tail', 'uint32', 1, 'byte 4');
+elements(end + 1) = element('ready', 'boolean', 1, 'byte 8, bit 0');
+elements(end + 1) = element('epoch', 'fixdt(0,15,0)', 1, 'byte 8, bits 1..15');
+elements(end + 1) = element('slots_count', 'uint8', 1, 'byte 10; the number of elements of slots in use');
+elements(end + 1) = element('slots', 'Bus: Slot', 4, 'byte 11');
+elements(end + 1) = element('load', 'double', 1, 'byte 35');
+bus = Simulink.Bus;
+bus.Description = 'Ring: 43 bytes. Message ID 2.';
+bus.Elements = elements;
+assignin('base', 'Ring', bus);
+
+end
+
+function e = element(name, type, dimensions, description)
+% Returns a bus element.
+e = Simulink.BusElement;
+e.Name = name;
+e.DataType = type;
+e.Dimensions = dimensions;
+e.Description = description;
+end
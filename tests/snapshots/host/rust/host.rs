@@ -0,0 +1,438 @@
+// Automatically generated by Onyx IDL compiler. Do not edit.
+
+use std::convert::TryInto;
+
+#[repr(u16)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mode {
+    Idle = 0,
+    Busy = 1,
+}
+
+impl Default for Mode {
+    fn default() -> Self {
+        Self::Idle
+    }
+}
+
+impl TryFrom<u16> for Mode {
+    type Error = u16;
+
+    fn try_from(raw: u16) -> Result<Self, u16> {
+        match raw {
+            0 => Ok(Self::Idle),
+            1 => Ok(Self::Busy),
+            _ => Err(raw),
+        }
+    }
+}
+
+impl From<Mode> for u16 {
+    fn from(value: Mode) -> Self {
+        value as u16
+    }
+}
+
+/// Size of Slot in bytes.
+pub(crate) const SLOT_SIZE: usize = 6;
+/// Buffer type alias for Slot.
+pub type SlotBuffer = [u8; SLOT_SIZE];
+
+/// Read-only view of Slot.
+#[derive(Debug, Clone, Copy)]
+pub struct SlotView<'a> {
+    data: &'a SlotBuffer,
+}
+
+/// Read-write view of Slot.
+#[derive(Debug)]
+pub struct SlotMutView<'a> {
+    data: &'a mut SlotBuffer,
+}
+
+impl<'a> SlotView<'a> {
+    /// Size of Slot in bytes.
+    pub const SIZE_BYTES: usize = SLOT_SIZE;
+    /// Byte offset of mode.
+    pub const FIELD_OFFSET_MODE: usize = 0;
+    /// Byte offset of owner.
+    pub const FIELD_OFFSET_OWNER: usize = 2;
+
+    /// Creates a new SlotView from a SlotBuffer.
+    pub fn new(data: &'a SlotBuffer) -> Self {
+        Self { data }
+    }
+
+    /// Accessor for mode.
+    pub fn mode(&self) -> Result<Mode, u16> {
+        let bytes = self.data[0..2].try_into().unwrap();
+        let val = u16::from_ne_bytes(bytes);
+        Mode::try_from(val)
+    }
+
+    /// Accessor for owner.
+    pub fn owner(&self) -> u32 {
+        let bytes = self.data[2..6].try_into().unwrap();
+        u32::from_ne_bytes(bytes)
+    }
+}
+
+// Fails to compile if Slot does not match its Onyx layout.
+const _: () = assert!(SlotView::FIELD_OFFSET_OWNER == SlotView::FIELD_OFFSET_MODE + std::mem::size_of::<u16>(), "Slot.owner does not match its Onyx offset");
+const _: () = assert!(SlotView::FIELD_OFFSET_OWNER + std::mem::size_of::<u32>() == SlotView::SIZE_BYTES, "Slot does not match its Onyx size");
+
+impl<'a> SlotMutView<'a> {
+    /// Creates a new SlotMutView from a SlotBuffer.
+    pub fn new(data: &'a mut SlotBuffer) -> Self {
+        Self { data }
+    }
+
+    /// Accessor for mode.
+    pub fn mode(&self) -> Result<Mode, u16> {
+        let bytes = self.data[0..2].try_into().unwrap();
+        let val = u16::from_ne_bytes(bytes);
+        Mode::try_from(val)
+    }
+
+    /// Mutator for mode.
+    pub fn set_mode(&mut self, value: Mode) {
+        let val = u16::from(value);
+        let bytes = val.to_ne_bytes();
+        self.data[0..2].copy_from_slice(&bytes);
+    }
+
+    /// Accessor for owner.
+    pub fn owner(&self) -> u32 {
+        let bytes = self.data[2..6].try_into().unwrap();
+        u32::from_ne_bytes(bytes)
+    }
+
+    /// Mutator for owner.
+    pub fn set_owner(&mut self, value: u32) {
+        let bytes = value.to_ne_bytes();
+        self.data[2..6].copy_from_slice(&bytes);
+    }
+}
+
+/// Size of Ring in bytes.
+pub(crate) const RING_SIZE: usize = 43;
+/// Buffer type alias for Ring.
+pub type RingBuffer = [u8; RING_SIZE];
+
+/// Read-only view of Ring.
+#[derive(Debug, Clone, Copy)]
+pub struct RingView<'a> {
+    data: &'a RingBuffer,
+}
+
+/// Read-write view of Ring.
+#[derive(Debug)]
+pub struct RingMutView<'a> {
+    data: &'a mut RingBuffer,
+}
+
+impl<'a> RingView<'a> {
+    /// Size of Ring in bytes.
+    pub const SIZE_BYTES: usize = RING_SIZE;
+    /// Byte offset of head.
+    pub const FIELD_OFFSET_HEAD: usize = 0;
+    /// Byte offset of tail.
+    pub const FIELD_OFFSET_TAIL: usize = 4;
+    /// Byte offset of the bit-field container holding ready.
+    pub const FIELD_OFFSET_READY: usize = 8;
+    /// Byte offset of the bit-field container holding epoch.
+    pub const FIELD_OFFSET_EPOCH: usize = 8;
+    /// Byte offset of slots.
+    pub const FIELD_OFFSET_SLOTS: usize = 10;
+    /// Byte offset of load.
+    pub const FIELD_OFFSET_LOAD: usize = 35;
+
+    /// Creates a new RingView from a RingBuffer.
+    pub fn new(data: &'a RingBuffer) -> Self {
+        Self { data }
+    }
+
+    /// Accessor for head.
+    pub fn head(&self) -> u32 {
+        let bytes = self.data[0..4].try_into().unwrap();
+        u32::from_ne_bytes(bytes)
+    }
+
+    /// Accessor for tail.
+    pub fn tail(&self) -> u32 {
+        let bytes = self.data[4..8].try_into().unwrap();
+        u32::from_ne_bytes(bytes)
+    }
+
+    /// Accessor for ready.
+    pub fn ready(&self) -> bool {
+        let bytes = self.data[8..10].try_into().unwrap();
+        let raw = u16::from_ne_bytes(bytes);
+        let val = (raw >> 0) & 0x1;
+        val != 0
+    }
+
+    /// Accessor for epoch.
+    pub fn epoch(&self) -> u16 {
+        let bytes = self.data[8..10].try_into().unwrap();
+        let raw = u16::from_ne_bytes(bytes);
+        let val = (raw >> 1) & 0x7FFF;
+        val as u16
+    }
+
+    /// Accessor for slots.
+    pub fn slots(&self) -> RingSlotsView {
+        let slice = &self.data[10..35];
+        RingSlotsView { data: slice.try_into().unwrap() }
+    }
+
+    /// Accessor for load.
+    pub fn load(&self) -> f64 {
+        let bytes = self.data[35..43].try_into().unwrap();
+        f64::from_ne_bytes(bytes)
+    }
+}
+
+// Fails to compile if Ring does not match its Onyx layout.
+const _: () = assert!(RingView::FIELD_OFFSET_TAIL == RingView::FIELD_OFFSET_HEAD + std::mem::size_of::<u32>(), "Ring.tail does not match its Onyx offset");
+const _: () = assert!(RingView::FIELD_OFFSET_READY == RingView::FIELD_OFFSET_TAIL + std::mem::size_of::<u32>(), "Ring.ready does not match its Onyx offset");
+const _: () = assert!(RingView::FIELD_OFFSET_SLOTS == RingView::FIELD_OFFSET_READY + 2, "Ring.slots does not match its Onyx offset");
+const _: () = assert!(RingView::FIELD_OFFSET_LOAD == RingView::FIELD_OFFSET_SLOTS + (std::mem::size_of::<u8>() + 4 * SLOT_SIZE), "Ring.load does not match its Onyx offset");
+const _: () = assert!(RingView::FIELD_OFFSET_LOAD + std::mem::size_of::<f64>() == RingView::SIZE_BYTES, "Ring does not match its Onyx size");
+
+impl<'a> RingMutView<'a> {
+    /// Creates a new RingMutView from a RingBuffer.
+    pub fn new(data: &'a mut RingBuffer) -> Self {
+        Self { data }
+    }
+
+    /// Accessor for head.
+    pub fn head(&self) -> u32 {
+        let bytes = self.data[0..4].try_into().unwrap();
+        u32::from_ne_bytes(bytes)
+    }
+
+    /// Mutator for head.
+    pub fn set_head(&mut self, value: u32) {
+        let bytes = value.to_ne_bytes();
+        self.data[0..4].copy_from_slice(&bytes);
+    }
+
+    /// Accessor for tail.
+    pub fn tail(&self) -> u32 {
+        let bytes = self.data[4..8].try_into().unwrap();
+        u32::from_ne_bytes(bytes)
+    }
+
+    /// Mutator for tail.
+    pub fn set_tail(&mut self, value: u32) {
+        let bytes = value.to_ne_bytes();
+        self.data[4..8].copy_from_slice(&bytes);
+    }
+
+    /// Accessor for ready.
+    pub fn ready(&self) -> bool {
+        let bytes = self.data[8..10].try_into().unwrap();
+        let raw = u16::from_ne_bytes(bytes);
+        let val = (raw >> 0) & 0x1;
+        val != 0
+    }
+
+    /// Mutator for ready.
+    pub fn set_ready(&mut self, value: bool) {
+        let bytes = self.data[8..10].try_into().unwrap();
+        let mut raw = u16::from_ne_bytes(bytes);
+        raw &= !((0x1 as u16) << 0);
+        raw |= ((value as u16 & 0x1) as u16) << 0;
+        let bytes = raw.to_ne_bytes();
+        self.data[8..10].copy_from_slice(&bytes);
+    }
+
+    /// Accessor for epoch.
+    pub fn epoch(&self) -> u16 {
+        let bytes = self.data[8..10].try_into().unwrap();
+        let raw = u16::from_ne_bytes(bytes);
+        let val = (raw >> 1) & 0x7FFF;
+        val as u16
+    }
+
+    /// Mutator for epoch.
+    pub fn set_epoch(&mut self, value: u16) {
+        let bytes = self.data[8..10].try_into().unwrap();
+        let mut raw = u16::from_ne_bytes(bytes);
+        raw &= !((0x7FFF as u16) << 1);
+        raw |= ((value as u16 & 0x7FFF) as u16) << 1;
+        let bytes = raw.to_ne_bytes();
+        self.data[8..10].copy_from_slice(&bytes);
+    }
+
+    /// Accessor for slots.
+    pub fn slots(&self) -> RingSlotsView {
+        let slice = &self.data[10..35];
+        RingSlotsView { data: slice.try_into().unwrap() }
+    }
+
+    /// Mutable accessor for slots.
+    pub fn slots_mut(&mut self) -> RingSlotsMutView {
+        let slice = &mut self.data[10..35];
+        RingSlotsMutView { data: slice.try_into().unwrap() }
+    }
+
+    /// Accessor for load.
+    pub fn load(&self) -> f64 {
+        let bytes = self.data[35..43].try_into().unwrap();
+        f64::from_ne_bytes(bytes)
+    }
+
+    /// Mutator for load.
+    pub fn set_load(&mut self, value: f64) {
+        let bytes = value.to_ne_bytes();
+        self.data[35..43].copy_from_slice(&bytes);
+    }
+}
+
+/// Read-only view of the bounded vector Ring.slots.
+#[derive(Debug, Clone, Copy)]
+pub struct RingSlotsView<'a> {
+    data: &'a [u8; 25],
+}
+
+impl<'a> RingSlotsView<'a> {
+    /// The maximum number of elements.
+    pub const CAPACITY: usize = 4;
+
+    /// Returns the number of elements, clamped to the capacity.
+    pub fn len(&self) -> usize {
+        (self.data[0] as usize).min(Self::CAPACITY)
+    }
+
+    /// Returns true if the vector has no elements.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the element at `index`, or `None` if it is out of range.
+    pub fn get(&self, index: usize) -> Option<SlotView<'a>> {
+        if index >= self.len() {
+            return None;
+        }
+        let data: &'a [u8; 25] = self.data;
+        Some(SlotView::new(data[1 + index * 6..1 + index * 6 + 6].try_into().unwrap()))
+    }
+
+    /// Returns an iterator over the elements.
+    pub fn iter(&self) -> impl Iterator<Item = SlotView<'a>> + 'a {
+        let view = *self;
+        (0..self.len()).map(move |index| view.get(index).unwrap())
+    }
+}
+
+/// Read-write view of the bounded vector Ring.slots.
+#[derive(Debug)]
+pub struct RingSlotsMutView<'a> {
+    data: &'a mut [u8; 25],
+}
+
+impl<'a> RingSlotsMutView<'a> {
+    /// The maximum number of elements.
+    pub const CAPACITY: usize = 4;
+
+    /// Returns a read-only view of the vector.
+    pub fn as_view(&self) -> RingSlotsView<'_> {
+        RingSlotsView { data: self.data }
+    }
+
+    /// Returns the number of elements, clamped to the capacity.
+    pub fn len(&self) -> usize {
+        self.as_view().len()
+    }
+
+    /// Returns true if the vector has no elements.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the element at `index`, or `None` if it is out of range.
+    pub fn get(&self, index: usize) -> Option<SlotView<'_>> {
+        self.as_view().get(index)
+    }
+
+    /// Returns an iterator over the elements.
+    pub fn iter(&self) -> impl Iterator<Item = SlotView<'_>> + '_ {
+        self.as_view().iter()
+    }
+
+    fn set_len(&mut self, len: usize) {
+        self.data[0] = len as u8;
+    }
+
+    /// Removes all elements.
+    pub fn clear(&mut self) {
+        self.set_len(0);
+    }
+
+    /// Returns a mutable view of the element at `index`, or `None` if it is out of range.
+    pub fn get_mut(&mut self, index: usize) -> Option<SlotMutView<'_>> {
+        if index >= self.len() {
+            return None;
+        }
+        Some(SlotMutView::new((&mut self.data[1 + index * 6..1 + index * 6 + 6]).try_into().unwrap()))
+    }
+
+    /// Appends a zeroed element and returns a mutable view of it, or `None` if the vector is full.
+    pub fn push(&mut self) -> Option<SlotMutView<'_>> {
+        let index = self.len();
+        if index == Self::CAPACITY {
+            return None;
+        }
+        self.set_len(index + 1);
+        self.data[1 + index * 6..1 + index * 6 + 6].fill(0);
+        self.get_mut(index)
+    }
+}
+
+/// Size of the ID that precedes an identified message, in bytes.
+pub const MESSAGE_ID_SIZE: usize = 2;
+/// ID of Ring.
+pub const RING_ID: u16 = 2;
+
+/// Returns the bytes of a message ID, which precede the message on the wire.
+pub fn message_id_bytes(id: u16) -> [u8; MESSAGE_ID_SIZE] {
+    id.to_ne_bytes()
+}
+
+/// A message decoded by `decode_any`, by type.
+#[derive(Debug, Clone, Copy)]
+pub enum AnyMessage<'a> {
+    Ring(RingView<'a>),
+}
+
+impl AnyMessage<'_> {
+    /// Returns the ID of the message.
+    pub fn id(&self) -> u16 {
+        match self {
+            AnyMessage::Ring(_) => RING_ID,
+        }
+    }
+}
+
+/// Why `decode_any` could not decode a message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DecodeAnyError {
+    /// The buffer ends before the ID or the message it names.
+    Truncated,
+    /// No message has the ID.
+    UnknownId(u16),
+}
+
+/// Decodes a message preceded by its ID. Bytes after the message are ignored.
+pub fn decode_any(buf: &[u8]) -> Result<AnyMessage<'_>, DecodeAnyError> {
+    let id = buf.get(..MESSAGE_ID_SIZE).ok_or(DecodeAnyError::Truncated)?;
+    let id = u16::from_ne_bytes(id.try_into().unwrap());
+    let body = &buf[MESSAGE_ID_SIZE..];
+    let message = match id {
+        RING_ID => body.get(..RING_SIZE).map(|data| AnyMessage::Ring(RingView::new(data.try_into().unwrap()))),
+        _ => return Err(DecodeAnyError::UnknownId(id)),
+    };
+    message.ok_or(DecodeAnyError::Truncated)
+}
@@ -0,0 +1,41 @@
+// Automatically generated by Onyx IDL compiler. Do not edit.
+`ifndef ONYX_HOST_PKG_SV
+`define ONYX_HOST_PKG_SV
+
+package host_pkg;
+
+  // Mode, carried on the wire as u16.
+  typedef enum logic [15:0] {
+    Mode_Idle = 16'd0,
+    Mode_Busy = 16'd1
+  } Mode;
+
+  // Slot: 6 bytes.
+  localparam int Slot_SIZE_BYTES = 6;
+  localparam int Slot_mode_OFFSET = 0;
+  localparam int Slot_mode_WIDTH = 16;
+  localparam int Slot_owner_OFFSET = 2;
+  localparam int Slot_owner_WIDTH = 32;
+
+  // Ring: 43 bytes.
+  localparam int Ring_SIZE_BYTES = 43;
+  localparam int Ring_ID = 2;
+  localparam int Ring_head_OFFSET = 0;
+  localparam int Ring_head_WIDTH = 32;
+  localparam int Ring_tail_OFFSET = 4;
+  localparam int Ring_tail_WIDTH = 32;
+  localparam int Ring_ready_OFFSET = 8;
+  localparam int Ring_ready_BIT_OFFSET = 0;
+  localparam int Ring_ready_WIDTH = 1;
+  localparam int Ring_epoch_OFFSET = 8;
+  localparam int Ring_epoch_BIT_OFFSET = 1;
+  localparam int Ring_epoch_WIDTH = 15;
+  localparam int Ring_slots_OFFSET = 10;
+  localparam int Ring_slots_WIDTH = 200;
+  localparam int Ring_slots_CAPACITY = 4;
+  localparam int Ring_load_OFFSET = 35;
+  localparam int Ring_load_WIDTH = 64;
+
+endpackage
+
+`endif // ONYX_HOST_PKG_SV
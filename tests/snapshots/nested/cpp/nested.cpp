@@ -0,0 +1,89 @@
+// Automatically generated by Onyx IDL compiler. Do not edit.
+#include "nested.hpp"
+
+#include <string.h>
+
+namespace onyx {
+
+Point* Point::Deserialize(Buffer& buffer) {
+    // Overlay the class structure onto the buffer memory. This is the zero-copy step.
+    Point* result = (Point*)buffer;
+
+    // SWAP: Primitive field x
+    result->__raw_x = utils::byteswap_if_needed(result->__raw_x);
+    // SWAP: Primitive field y
+    result->__raw_y = utils::byteswap_if_needed(result->__raw_y);
+
+    return result;
+}
+
+void Point::Serialize(Buffer& buffer) const {
+    // Copy the host-endian object into the buffer first.
+    memcpy(buffer, this, kSizeOf);
+    Point* wire_format_data = (Point*)buffer;
+
+    // SWAP: Primitive field x
+    wire_format_data->__raw_x = utils::byteswap_if_needed(wire_format_data->__raw_x);
+    // SWAP: Primitive field y
+    wire_format_data->__raw_y = utils::byteswap_if_needed(wire_format_data->__raw_y);
+}
+
+Segment* Segment::Deserialize(Buffer& buffer) {
+    // Overlay the class structure onto the buffer memory. This is the zero-copy step.
+    Segment* result = (Segment*)buffer;
+
+    Point::Deserialize(*(Point::Buffer*) &result->__raw_start);
+    Point::Deserialize(*(Point::Buffer*) &result->__raw_end);
+
+    return result;
+}
+
+void Segment::Serialize(Buffer& buffer) const {
+    // Copy the host-endian object into the buffer first.
+    memcpy(buffer, this, kSizeOf);
+    Segment* wire_format_data = (Segment*)buffer;
+
+    wire_format_data->__raw_start.Serialize(*(Point::Buffer*) &wire_format_data->__raw_start);
+    wire_format_data->__raw_end.Serialize(*(Point::Buffer*) &wire_format_data->__raw_end);
+}
+
+Path* Path::Deserialize(Buffer& buffer) {
+    // Overlay the class structure onto the buffer memory. This is the zero-copy step.
+    Path* result = (Path*)buffer;
+
+    // INFO: Byte array field id, no swap needed.
+    Point::Deserialize(*(Point::Buffer*) &result->__raw_origin);
+    // SWAP: Vector field segments
+    for (size_t i = 0; i < 4; ++i) {
+        Segment::Deserialize(*(Segment::Buffer*) &result->__raw_segments.__items[i]);
+    }
+    // SWAP: Vector field weights
+    for (size_t i = 0; i < 8; ++i) {
+        result->__raw_weights.__items[i] = utils::byteswap_if_needed(result->__raw_weights.__items[i]);
+    }
+    // SWAP: Primitive field scale
+    result->__raw_scale = utils::byteswap_if_needed(result->__raw_scale);
+
+    return result;
+}
+
+void Path::Serialize(Buffer& buffer) const {
+    // Copy the host-endian object into the buffer first.
+    memcpy(buffer, this, kSizeOf);
+    Path* wire_format_data = (Path*)buffer;
+
+    // INFO: Byte array field id, no swap needed.
+    wire_format_data->__raw_origin.Serialize(*(Point::Buffer*) &wire_format_data->__raw_origin);
+    // SWAP: Vector field segments
+    for (size_t i = 0; i < 4; ++i) {
+        wire_format_data->__raw_segments.__items[i].Serialize(*(Segment::Buffer*) &wire_format_data->__raw_segments.__items[i]);
+    }
+    // SWAP: Vector field weights
+    for (size_t i = 0; i < 8; ++i) {
+        wire_format_data->__raw_weights.__items[i] = utils::byteswap_if_needed(wire_format_data->__raw_weights.__items[i]);
+    }
+    // SWAP: Primitive field scale
+    wire_format_data->__raw_scale = utils::byteswap_if_needed(wire_format_data->__raw_scale);
+}
+
+} // namespace onyx
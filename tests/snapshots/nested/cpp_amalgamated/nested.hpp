@@ -0,0 +1,457 @@
+// Automatically generated by Onyx IDL compiler. Do not edit.
+#ifndef ONYX_NESTED_H_
+#define ONYX_NESTED_H_
+
+#include <stddef.h>
+#include <stdint.h>
+#include <string.h>
+
+#include <array>
+#include <string>
+
+#pragma pack(push, 1)
+
+namespace onyx {
+#ifndef ONYX_UTILS_ENDIANNESS_LITTLE_
+#define ONYX_UTILS_ENDIANNESS_LITTLE_
+
+namespace utils {
+
+// Define network endianness (usually Big Endian).
+#define ONYX_BIG_ENDIAN 4321
+#define ONYX_LITTLE_ENDIAN 1234
+#define ONYX_NETWORK_ORDER ONYX_LITTLE_ENDIAN
+
+// Determine host endianness at compile time (using standard GCC/Clang macros)
+#ifdef __BYTE_ORDER__
+    #if __BYTE_ORDER__ == __ORDER_BIG_ENDIAN__
+        #define ONYX_HOST_ORDER ONYX_BIG_ENDIAN
+    #elif __BYTE_ORDER__ == __ORDER_LITTLE_ENDIAN__
+        #define ONYX_HOST_ORDER ONYX_LITTLE_ENDIAN
+    #else
+        #error "Unsupported host endianness"
+    #endif
+#elif defined(_WIN32) || defined(__LITTLE_ENDIAN__)
+    // Fallback for Windows (always little-endian) or other platforms
+    #define ONYX_HOST_ORDER ONYX_LITTLE_ENDIAN
+#else
+    #error "Could not determine host endianness"
+#endif
+
+// Helper function to swap bytes (Explicit Overloads)
+inline uint16_t byteswap(uint16_t value) { return ((value << 8) & 0xFF00) | ((value >> 8) & 0x00FF); }
+
+inline int16_t byteswap(int16_t value) { return (int16_t)byteswap((uint16_t)value); }
+
+inline uint32_t byteswap(uint32_t value) { return (value << 24) | ((value & 0x00FF0000) >> 8) | ((value & 0x0000FF00) << 8) | (value >> 24); }
+
+inline int32_t byteswap(int32_t value) { return (int32_t)byteswap((uint32_t)value); }
+
+inline uint64_t byteswap(uint64_t value) { return (value << 56) | ((value & 0x00FF000000000000ULL) >> 40) | ((value & 0x0000FF0000000000ULL) >> 24) | ((value & 0x000000FF00000000ULL) >> 8) | ((value & 0x00000000FF000000ULL) << 8) | ((value & 0x0000000000FF0000ULL) << 24) | ((value & 0x000000000000FF00ULL) << 40) | (value >> 56); }
+
+inline int64_t byteswap(int64_t value) { return (int64_t)byteswap((uint64_t)value); }
+
+inline float byteswap(float value) {
+    uint32_t temp;
+    memcpy(&temp, &value, sizeof(value));
+    temp = byteswap(temp);
+    memcpy(&value, &temp, sizeof(value));
+    return value;
+}
+
+inline double byteswap(double value) {
+    uint64_t temp;
+    memcpy(&temp, &value, sizeof(value));
+    temp = byteswap(temp);
+    memcpy(&value, &temp, sizeof(value));
+    return value;
+}
+
+// Swaps bytes if the host order does not match the network order (Explicit Overloads)
+inline uint16_t byteswap_if_needed(uint16_t value) {
+    if (ONYX_HOST_ORDER != ONYX_NETWORK_ORDER) {
+        return byteswap(value);
+    }
+    return value;
+}
+
+inline int16_t byteswap_if_needed(int16_t value) {
+    if (ONYX_HOST_ORDER != ONYX_NETWORK_ORDER) {
+        return byteswap(value);
+    }
+    return value;
+}
+
+inline uint32_t byteswap_if_needed(uint32_t value) {
+    if (ONYX_HOST_ORDER != ONYX_NETWORK_ORDER) {
+        return byteswap(value);
+    }
+    return value;
+}
+
+inline int32_t byteswap_if_needed(int32_t value) {
+    if (ONYX_HOST_ORDER != ONYX_NETWORK_ORDER) {
+        return byteswap(value);
+    }
+    return value;
+}
+
+inline uint64_t byteswap_if_needed(uint64_t value) {
+    if (ONYX_HOST_ORDER != ONYX_NETWORK_ORDER) {
+        return byteswap(value);
+    }
+    return value;
+}
+
+inline int64_t byteswap_if_needed(int64_t value) {
+    if (ONYX_HOST_ORDER != ONYX_NETWORK_ORDER) {
+        return byteswap(value);
+    }
+    return value;
+}
+
+inline float byteswap_if_needed(float value) {
+    if (ONYX_HOST_ORDER != ONYX_NETWORK_ORDER) {
+        return byteswap(value);
+    }
+    return value;
+}
+
+inline double byteswap_if_needed(double value) {
+    if (ONYX_HOST_ORDER != ONYX_NETWORK_ORDER) {
+        return byteswap(value);
+    }
+    return value;
+}
+
+} // namespace utils
+#endif // ONYX_UTILS_ENDIANNESS_LITTLE_
+
+#ifndef ONYX_UTILS_BOUNDED_VEC_
+#define ONYX_UTILS_BOUNDED_VEC_
+
+namespace utils {
+
+/// A bounded vector: a length followed by room for N elements, all of them inline.
+template <typename T, typename LenT, size_t N>
+class BoundedVec {
+public:
+    LenT __length;
+    T __items[N];
+
+    /// Returns the number of elements, clamped to the capacity.
+    inline size_t len() const { return __length < N ? __length : N; }
+    /// Returns the maximum number of elements.
+    static inline size_t capacity() { return N; }
+    /// Returns true if the vector has no elements.
+    inline bool empty() const { return len() == 0; }
+    /// Returns the element at `i`, which must be less than len().
+    inline const T& get(size_t i) const { return __items[i]; }
+    inline T& get(size_t i) { return __items[i]; }
+    /// Replaces the element at `i`, which must be less than len().
+    inline void set(size_t i, const T& value) { __items[i] = value; }
+    /// Appends an element. Returns false, leaving the vector unchanged, if it is full.
+    inline bool push(const T& value) {
+        size_t n = len();
+        if (n == N) return false;
+        __items[n] = value;
+        __length = static_cast<LenT>(n + 1);
+        return true;
+    }
+    /// Removes all elements.
+    inline void clear() { __length = 0; }
+
+    inline const T* begin() const { return __items; }
+    inline const T* end() const { return __items + len(); }
+    inline T* begin() { return __items; }
+    inline T* end() { return __items + len(); }
+};
+
+} // namespace utils
+
+#endif // ONYX_UTILS_BOUNDED_VEC_
+
+#ifndef ONYX_UTILS_TO_HEX_
+#define ONYX_UTILS_TO_HEX_
+
+namespace utils {
+
+/// Formats bytes as lowercase hex; a UUID (`uuid` true) is grouped 8-4-4-4-12.
+inline std::string to_hex(const uint8_t* data, size_t size, bool uuid) {
+    static const char digits[] = "0123456789abcdef";
+    std::string out;
+    for (size_t i = 0; i < size; ++i) {
+        if (uuid && (i == 4 || i == 6 || i == 8 || i == 10)) out += '-';
+        out += digits[data[i] >> 4];
+        out += digits[data[i] & 0xF];
+    }
+    return out;
+}
+
+} // namespace utils
+
+#endif // ONYX_UTILS_TO_HEX_
+
+class Point;
+class Segment;
+class Path;
+
+class Point {
+private:
+    int16_t __raw_x;
+    int16_t __raw_y;
+
+public:
+    static const size_t kSizeOf = 4;
+    using Buffer = uint8_t[kSizeOf];
+
+    /// Byte offsets of the fields within the buffer.
+    static const size_t kXOffset = 0;
+    static const size_t kYOffset = 2;
+
+    /// Accessor for x
+    inline const int16_t x() const { return __raw_x; }
+
+    /// Mutator for x
+    inline void x(const int16_t value) { __raw_x = value; }
+
+    /// Accessor for y
+    inline const int16_t y() const { return __raw_y; }
+
+    /// Mutator for y
+    inline void y(const int16_t value) { __raw_y = value; }
+
+    /// Deserializes the network-endian buffer to Point in-place by casting
+    /// and applying endianness correction to match the host-endianness.
+    /// NOTE: This mutates the input buffer to host-endianness.
+    /// Returns a pointer to the object within the buffer.
+    static Point* Deserialize(Buffer& buffer);
+
+    /// Serializes the host-endian object into the provided buffer,
+    /// applying endianness correction to match the network-endianness.
+    void Serialize(Buffer& buffer) const;
+
+private:
+    /// Fails to compile if Point does not match its Onyx layout.
+    static inline void __check_layout() {
+        static_assert(sizeof(Point) == kSizeOf, "Point does not match its Onyx size");
+        static_assert(offsetof(Point, __raw_x) == kXOffset, "Point::x does not match its Onyx offset");
+        static_assert(offsetof(Point, __raw_y) == kYOffset, "Point::y does not match its Onyx offset");
+    }
+};
+
+class Segment {
+private:
+    Point __raw_start;
+    Point __raw_end;
+
+public:
+    static const size_t kSizeOf = 8;
+    using Buffer = uint8_t[kSizeOf];
+
+    /// Byte offsets of the fields within the buffer.
+    static const size_t kStartOffset = 0;
+    static const size_t kEndOffset = 4;
+
+    /// Accessor for start
+    inline Point& start() { return __raw_start; }
+
+    /// Mutator for start
+    inline void start(const Point value) { __raw_start = value; }
+
+    /// Accessor for end
+    inline Point& end() { return __raw_end; }
+
+    /// Mutator for end
+    inline void end(const Point value) { __raw_end = value; }
+
+    /// Deserializes the network-endian buffer to Segment in-place by casting
+    /// and applying endianness correction to match the host-endianness.
+    /// NOTE: This mutates the input buffer to host-endianness.
+    /// Returns a pointer to the object within the buffer.
+    static Segment* Deserialize(Buffer& buffer);
+
+    /// Serializes the host-endian object into the provided buffer,
+    /// applying endianness correction to match the network-endianness.
+    void Serialize(Buffer& buffer) const;
+
+private:
+    /// Fails to compile if Segment does not match its Onyx layout.
+    static inline void __check_layout() {
+        static_assert(sizeof(Segment) == kSizeOf, "Segment does not match its Onyx size");
+        static_assert(offsetof(Segment, __raw_start) == kStartOffset, "Segment::start does not match its Onyx offset");
+        static_assert(offsetof(Segment, __raw_end) == kEndOffset, "Segment::end does not match its Onyx offset");
+    }
+};
+
+class Path {
+private:
+    std::array<uint8_t, 16> __raw_id;
+    Point __raw_origin;
+    utils::BoundedVec<Segment, uint8_t, 4> __raw_segments;
+    utils::BoundedVec<float, uint8_t, 8> __raw_weights;
+    int32_t __raw_scale;
+
+public:
+    static const size_t kSizeOf = 90;
+    using Buffer = uint8_t[kSizeOf];
+
+    /// Byte offsets of the fields within the buffer.
+    static const size_t kIdOffset = 0;
+    static const size_t kOriginOffset = 16;
+    static const size_t kSegmentsOffset = 20;
+    static const size_t kWeightsOffset = 53;
+    static const size_t kScaleOffset = 86;
+
+    /// Accessor for id
+    inline std::array<uint8_t, 16>& id() { return __raw_id; }
+
+    /// Mutator for id
+    inline void id(const std::array<uint8_t, 16> value) { __raw_id = value; }
+
+    /// Formats id as hex
+    inline std::string id_hex() const { return utils::to_hex(__raw_id.data(), 16, true); }
+
+    /// Accessor for origin
+    inline Point& origin() { return __raw_origin; }
+
+    /// Mutator for origin
+    inline void origin(const Point value) { __raw_origin = value; }
+
+    /// Accessor for segments
+    inline utils::BoundedVec<Segment, uint8_t, 4>& segments() { return __raw_segments; }
+
+    /// Mutator for segments
+    inline void segments(const utils::BoundedVec<Segment, uint8_t, 4> value) { __raw_segments = value; }
+
+    /// Accessor for weights
+    inline utils::BoundedVec<float, uint8_t, 8>& weights() { return __raw_weights; }
+
+    /// Mutator for weights
+    inline void weights(const utils::BoundedVec<float, uint8_t, 8> value) { __raw_weights = value; }
+
+    /// Accessor for the raw value of scale
+    inline const int32_t scale_raw() const { return __raw_scale; }
+
+    /// Mutator for the raw value of scale
+    inline void scale_raw(const int32_t value) { __raw_scale = value; }
+
+    /// Accessor for scale, scaled by 1/1000
+    inline double scale() const { return static_cast<double>(__raw_scale) / 1000.0; }
+
+    /// Mutator for scale, rounding to the nearest step of 1/1000
+    inline bool scale(const double value) {
+        const double scaled = value * 1000.0;
+        if (!(scaled > -2147483648.0 - 0.5 && scaled < 2147483647.0 + 0.5)) { return false; }
+        __raw_scale = static_cast<int32_t>(scaled < 0 ? scaled - 0.5 : scaled + 0.5);
+        return true;
+    }
+
+    /// Deserializes the network-endian buffer to Path in-place by casting
+    /// and applying endianness correction to match the host-endianness.
+    /// NOTE: This mutates the input buffer to host-endianness.
+    /// Returns a pointer to the object within the buffer.
+    static Path* Deserialize(Buffer& buffer);
+
+    /// Serializes the host-endian object into the provided buffer,
+    /// applying endianness correction to match the network-endianness.
+    void Serialize(Buffer& buffer) const;
+
+private:
+    /// Fails to compile if Path does not match its Onyx layout.
+    static inline void __check_layout() {
+        static_assert(sizeof(Path) == kSizeOf, "Path does not match its Onyx size");
+        static_assert(offsetof(Path, __raw_id) == kIdOffset, "Path::id does not match its Onyx offset");
+        static_assert(offsetof(Path, __raw_origin) == kOriginOffset, "Path::origin does not match its Onyx offset");
+        static_assert(offsetof(Path, __raw_segments) == kSegmentsOffset, "Path::segments does not match its Onyx offset");
+        static_assert(offsetof(Path, __raw_weights) == kWeightsOffset, "Path::weights does not match its Onyx offset");
+        static_assert(offsetof(Path, __raw_scale) == kScaleOffset, "Path::scale does not match its Onyx offset");
+    }
+};
+
+
+inline Point* Point::Deserialize(Buffer& buffer) {
+    // Overlay the class structure onto the buffer memory. This is the zero-copy step.
+    Point* result = (Point*)buffer;
+
+    // SWAP: Primitive field x
+    result->__raw_x = utils::byteswap_if_needed(result->__raw_x);
+    // SWAP: Primitive field y
+    result->__raw_y = utils::byteswap_if_needed(result->__raw_y);
+
+    return result;
+}
+
+inline void Point::Serialize(Buffer& buffer) const {
+    // Copy the host-endian object into the buffer first.
+    memcpy(buffer, this, kSizeOf);
+    Point* wire_format_data = (Point*)buffer;
+
+    // SWAP: Primitive field x
+    wire_format_data->__raw_x = utils::byteswap_if_needed(wire_format_data->__raw_x);
+    // SWAP: Primitive field y
+    wire_format_data->__raw_y = utils::byteswap_if_needed(wire_format_data->__raw_y);
+}
+
+inline Segment* Segment::Deserialize(Buffer& buffer) {
+    // Overlay the class structure onto the buffer memory. This is the zero-copy step.
+    Segment* result = (Segment*)buffer;
+
+    Point::Deserialize(*(Point::Buffer*) &result->__raw_start);
+    Point::Deserialize(*(Point::Buffer*) &result->__raw_end);
+
+    return result;
+}
+
+inline void Segment::Serialize(Buffer& buffer) const {
+    // Copy the host-endian object into the buffer first.
+    memcpy(buffer, this, kSizeOf);
+    Segment* wire_format_data = (Segment*)buffer;
+
+    wire_format_data->__raw_start.Serialize(*(Point::Buffer*) &wire_format_data->__raw_start);
+    wire_format_data->__raw_end.Serialize(*(Point::Buffer*) &wire_format_data->__raw_end);
+}
+
+inline Path* Path::Deserialize(Buffer& buffer) {
+    // Overlay the class structure onto the buffer memory. This is the zero-copy step.
+    Path* result = (Path*)buffer;
+
+    // INFO: Byte array field id, no swap needed.
+    Point::Deserialize(*(Point::Buffer*) &result->__raw_origin);
+    // SWAP: Vector field segments
+    for (size_t i = 0; i < 4; ++i) {
+        Segment::Deserialize(*(Segment::Buffer*) &result->__raw_segments.__items[i]);
+    }
+    // SWAP: Vector field weights
+    for (size_t i = 0; i < 8; ++i) {
+        result->__raw_weights.__items[i] = utils::byteswap_if_needed(result->__raw_weights.__items[i]);
+    }
+    // SWAP: Primitive field scale
+    result->__raw_scale = utils::byteswap_if_needed(result->__raw_scale);
+
+    return result;
+}
+
+inline void Path::Serialize(Buffer& buffer) const {
+    // Copy the host-endian object into the buffer first.
+    memcpy(buffer, this, kSizeOf);
+    Path* wire_format_data = (Path*)buffer;
+
+    // INFO: Byte array field id, no swap needed.
+    wire_format_data->__raw_origin.Serialize(*(Point::Buffer*) &wire_format_data->__raw_origin);
+    // SWAP: Vector field segments
+    for (size_t i = 0; i < 4; ++i) {
+        wire_format_data->__raw_segments.__items[i].Serialize(*(Segment::Buffer*) &wire_format_data->__raw_segments.__items[i]);
+    }
+    // SWAP: Vector field weights
+    for (size_t i = 0; i < 8; ++i) {
+        wire_format_data->__raw_weights.__items[i] = utils::byteswap_if_needed(wire_format_data->__raw_weights.__items[i]);
+    }
+    // SWAP: Primitive field scale
+    wire_format_data->__raw_scale = utils::byteswap_if_needed(wire_format_data->__raw_scale);
+}
+
+} // namespace onyx
+
+#pragma pack(pop)
+#endif // ONYX_NESTED_H_
@@ -0,0 +1,195 @@
+// Automatically generated by Onyx IDL compiler. Do not edit.
+package onyx
+
+import java.nio.ByteBuffer
+import java.nio.ByteOrder
+import kotlin.math.roundToLong
+
+/** The byte order of every field in this file. */
+private val BYTE_ORDER: ByteOrder = ByteOrder.LITTLE_ENDIAN
+
+/** Returns the [size] bytes of the buffer from [offset] as a buffer of their own. */
+private fun ByteBuffer.view(offset: Int, size: Int): ByteBuffer =
+    (duplicate().clear().position(offset).limit(offset + size) as ByteBuffer).slice()
+
+/** Reads the [size] bytes from [offset] as an unsigned integer in [BYTE_ORDER]. */
+private fun ByteBuffer.readBits(offset: Int, size: Int): Long {
+    var raw = 0L
+    for (i in 0 until size) {
+        val index = if (BYTE_ORDER == ByteOrder.BIG_ENDIAN) i else size - 1 - i
+        raw = (raw shl 8) or (get(offset + index).toLong() and 0xFFL)
+    }
+    return raw
+}
+
+/** Writes the low [size] bytes of [raw] from [offset] in [BYTE_ORDER]. */
+private fun ByteBuffer.writeBits(offset: Int, size: Int, raw: Long) {
+    for (i in 0 until size) {
+        val shift = if (BYTE_ORDER == ByteOrder.BIG_ENDIAN) 8 * (size - 1 - i) else 8 * i
+        put(offset + i, (raw ushr shift).toByte())
+    }
+}
+
+/** Copies the [size] bytes from [offset]. */
+private fun ByteBuffer.readBytes(offset: Int, size: Int): ByteArray =
+    ByteArray(size).also { view(offset, size).get(it) }
+
+/** Writes [value], which must hold exactly [size] bytes, from [offset]. */
+private fun ByteBuffer.writeBytes(offset: Int, size: Int, value: ByteArray) {
+    require(value.size == size) { "expected $size bytes, got ${value.size}" }
+    view(offset, size).put(value)
+}
+
+/** Returns [index] if it is below [count], and throws otherwise. */
+private fun checkIndex(index: Int, count: Int): Int {
+    if (index !in 0 until count) {
+        throw IndexOutOfBoundsException("index $index is out of bounds for $count elements")
+    }
+    return index
+}
+
+/** Point, viewed in [buffer] from its index 0. */
+@JvmInline
+value class Point(val buffer: ByteBuffer) {
+    /** Accessor for x. */
+    var x: Short
+        get() = buffer.readBits(X_OFFSET, 2).toShort()
+        set(value) {
+            buffer.writeBits(X_OFFSET, 2, value.toLong())
+        }
+
+    /** Accessor for y. */
+    var y: Short
+        get() = buffer.readBits(Y_OFFSET, 2).toShort()
+        set(value) {
+            buffer.writeBits(Y_OFFSET, 2, value.toLong())
+        }
+
+    companion object {
+        /** The size of Point in bytes. */
+        const val SIZE_BYTES: Int = 4
+
+        /** The byte offset of x. */
+        const val X_OFFSET: Int = 0
+
+        /** The byte offset of y. */
+        const val Y_OFFSET: Int = 2
+
+        /** Returns a view of Point from the position of [buffer]. */
+        fun wrap(buffer: ByteBuffer): Point = Point(buffer.view(buffer.position(), SIZE_BYTES))
+
+        /** Returns a view of Point in a new, zeroed buffer. */
+        fun allocate(): Point = Point(ByteBuffer.allocate(SIZE_BYTES))
+    }
+}
+
+/** Segment, viewed in [buffer] from its index 0. */
+@JvmInline
+value class Segment(val buffer: ByteBuffer) {
+    /** Accessor for start. */
+    val start: Point
+        get() = Point(buffer.view(START_OFFSET, Point.SIZE_BYTES))
+
+    /** Accessor for end. */
+    val end: Point
+        get() = Point(buffer.view(END_OFFSET, Point.SIZE_BYTES))
+
+    companion object {
+        /** The size of Segment in bytes. */
+        const val SIZE_BYTES: Int = 8
+
+        /** The byte offset of start. */
+        const val START_OFFSET: Int = 0
+
+        /** The byte offset of end. */
+        const val END_OFFSET: Int = 4
+
+        /** Returns a view of Segment from the position of [buffer]. */
+        fun wrap(buffer: ByteBuffer): Segment = Segment(buffer.view(buffer.position(), SIZE_BYTES))
+
+        /** Returns a view of Segment in a new, zeroed buffer. */
+        fun allocate(): Segment = Segment(ByteBuffer.allocate(SIZE_BYTES))
+    }
+}
+
+/** Path, viewed in [buffer] from its index 0. */
+@JvmInline
+value class Path(val buffer: ByteBuffer) {
+    /** Accessor for id. */
+    var id: ByteArray
+        get() = buffer.readBytes(ID_OFFSET, 16)
+        set(value) {
+            buffer.writeBytes(ID_OFFSET, 16, value)
+        }
+
+    /** Accessor for origin. */
+    val origin: Point
+        get() = Point(buffer.view(ORIGIN_OFFSET, Point.SIZE_BYTES))
+
+    /** The number of elements of segments in use. */
+    var segmentsCount: Int
+        get() = buffer.readBits(SEGMENTS_OFFSET, 1).toInt()
+        set(value) {
+            require(value in 0..4) { "segments holds at most 4 elements" }
+            buffer.writeBits(SEGMENTS_OFFSET, 1, value.toLong())
+        }
+
+    /** Returns element [index] of segments. */
+    fun segments(index: Int): Segment = Segment(buffer.view(SEGMENTS_OFFSET + 1 + 8 * checkIndex(index, segmentsCount), Segment.SIZE_BYTES))
+
+    /** The number of elements of weights in use. */
+    var weightsCount: Int
+        get() = buffer.readBits(WEIGHTS_OFFSET, 1).toInt()
+        set(value) {
+            require(value in 0..8) { "weights holds at most 8 elements" }
+            buffer.writeBits(WEIGHTS_OFFSET, 1, value.toLong())
+        }
+
+    /** Returns element [index] of weights. */
+    fun weights(index: Int): Float = Float.fromBits(buffer.readBits(WEIGHTS_OFFSET + 1 + 4 * checkIndex(index, weightsCount), 4).toInt())
+
+    /** Sets element [index] of weights. */
+    fun setWeights(index: Int, value: Float) {
+        buffer.writeBits(WEIGHTS_OFFSET + 1 + 4 * checkIndex(index, weightsCount), 4, value.toRawBits().toLong())
+    }
+
+    /** The raw value of scale, in units of 1/1000. */
+    var scaleRaw: Int
+        get() = buffer.readBits(SCALE_OFFSET, 4).toInt()
+        set(value) {
+            buffer.writeBits(SCALE_OFFSET, 4, value.toLong())
+        }
+
+    /** Accessor for scale. */
+    var scale: Double
+        get() = scaleRaw.toDouble() / 1000.0
+        set(value) {
+            scaleRaw = (value * 1000.0).roundToLong().toInt()
+        }
+
+    companion object {
+        /** The size of Path in bytes. */
+        const val SIZE_BYTES: Int = 90
+
+        /** The byte offset of id. */
+        const val ID_OFFSET: Int = 0
+
+        /** The byte offset of origin. */
+        const val ORIGIN_OFFSET: Int = 16
+
+        /** The byte offset of segments. */
+        const val SEGMENTS_OFFSET: Int = 20
+
+        /** The byte offset of weights. */
+        const val WEIGHTS_OFFSET: Int = 53
+
+        /** The byte offset of scale. */
+        const val SCALE_OFFSET: Int = 86
+
+        /** Returns a view of Path from the position of [buffer]. */
+        fun wrap(buffer: ByteBuffer): Path = Path(buffer.view(buffer.position(), SIZE_BYTES))
+
+        /** Returns a view of Path in a new, zeroed buffer. */
+        fun allocate(): Path = Path(ByteBuffer.allocate(SIZE_BYTES))
+    }
+}
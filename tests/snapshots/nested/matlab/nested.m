@@ -0,0 +1,48 @@
+% Automatically generated by Onyx IDL compiler. Do not edit.
+function nested()
+%NESTED Defines the Simulink enums and bus objects of the schema.
+%   Run nested before loading a model that uses them. Each bus object is
+%   assigned in the base workspace, named after its struct or message.
+
+% Point: 4 bytes.
+elements = Simulink.BusElement.empty;
+elements(end + 1) = element('x', 'int16', 1, 'byte 0');
+elements(end + 1) = element('y', 'int16', 1, 'byte 2');
+bus = Simulink.Bus;
+bus.Description = 'Point: 4 bytes.';
+bus.Elements = elements;
+assignin('base', 'Point', bus);
+
+% Segment: 8 bytes.
+elements = Simulink.BusElement.empty;
+elements(end + 1) = element('start', 'Bus: Point', 1, 'byte 0');
+elements(end + 1) = element('end_', 'Bus: Point', 1, 'byte 4');
+bus = Simulink.Bus;
+bus.Description = 'Segment: 8 bytes.';
+bus.Elements = elements;
+assignin('base', 'Segment', bus);
+
+% Path: 90 bytes.
+elements = Simulink.BusElement.empty;
+elements(end + 1) = element('id', 'uint8', 16, 'byte 0');
+elements(end + 1) = element('origin', 'Bus: Point', 1, 'byte 16');
+elements(end + 1) = element('segments_count', 'uint8', 1, 'byte 20; the number of elements of segments in use');
+elements(end + 1) = element('segments', 'Bus: Segment', 4, 'byte 21');
+elements(end + 1) = element('weights_count', 'uint8', 1, 'byte 53; the number of elements of weights in use');
+elements(end + 1) = element('weights', 'single', 8, 'byte 54');
+elements(end + 1) = element('scale', 'fixdt(1,32,1/1000,0)', 1, 'byte 86');
+bus = Simulink.Bus;
+bus.Description = 'Path: 90 bytes.';
+bus.Elements = elements;
+assignin('base', 'Path', bus);
+
+end
+
+function e = element(name, type, dimensions, description)
+% Returns a bus element.
+e = Simulink.BusElement;
+e.Name = name;
+e.DataType = type;
+e.Dimensions = dimensions;
+e.Description = description;
+end
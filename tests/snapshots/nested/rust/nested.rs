@@ -0,0 +1,556 @@
+// Automatically generated by Onyx IDL compiler. Do not edit.
+
+use std::convert::TryInto;
+
+
+/// Size of Point in bytes.
+pub(crate) const POINT_SIZE: usize = 4;
+/// Buffer type alias for Point.
+pub type PointBuffer = [u8; POINT_SIZE];
+
+/// Read-only view of Point.
+#[derive(Debug, Clone, Copy)]
+pub struct PointView<'a> {
+    data: &'a PointBuffer,
+}
+
+/// Read-write view of Point.
+#[derive(Debug)]
+pub struct PointMutView<'a> {
+    data: &'a mut PointBuffer,
+}
+
+impl<'a> PointView<'a> {
+    /// Size of Point in bytes.
+    pub const SIZE_BYTES: usize = POINT_SIZE;
+    /// Byte offset of x.
+    pub const FIELD_OFFSET_X: usize = 0;
+    /// Byte offset of y.
+    pub const FIELD_OFFSET_Y: usize = 2;
+
+    /// Creates a new PointView from a PointBuffer.
+    pub fn new(data: &'a PointBuffer) -> Self {
+        Self { data }
+    }
+
+    /// Accessor for x.
+    pub fn x(&self) -> i16 {
+        let bytes = self.data[0..2].try_into().unwrap();
+        i16::from_le_bytes(bytes)
+    }
+
+    /// Accessor for y.
+    pub fn y(&self) -> i16 {
+        let bytes = self.data[2..4].try_into().unwrap();
+        i16::from_le_bytes(bytes)
+    }
+}
+
+// Fails to compile if Point does not match its Onyx layout.
+const _: () = assert!(PointView::FIELD_OFFSET_Y == PointView::FIELD_OFFSET_X + std::mem::size_of::<i16>(), "Point.y does not match its Onyx offset");
+const _: () = assert!(PointView::FIELD_OFFSET_Y + std::mem::size_of::<i16>() == PointView::SIZE_BYTES, "Point does not match its Onyx size");
+
+impl<'a> PointMutView<'a> {
+    /// Creates a new PointMutView from a PointBuffer.
+    pub fn new(data: &'a mut PointBuffer) -> Self {
+        Self { data }
+    }
+
+    /// Accessor for x.
+    pub fn x(&self) -> i16 {
+        let bytes = self.data[0..2].try_into().unwrap();
+        i16::from_le_bytes(bytes)
+    }
+
+    /// Mutator for x.
+    pub fn set_x(&mut self, value: i16) {
+        let bytes = value.to_le_bytes();
+        self.data[0..2].copy_from_slice(&bytes);
+    }
+
+    /// Accessor for y.
+    pub fn y(&self) -> i16 {
+        let bytes = self.data[2..4].try_into().unwrap();
+        i16::from_le_bytes(bytes)
+    }
+
+    /// Mutator for y.
+    pub fn set_y(&mut self, value: i16) {
+        let bytes = value.to_le_bytes();
+        self.data[2..4].copy_from_slice(&bytes);
+    }
+}
+
+/// Size of Segment in bytes.
+pub(crate) const SEGMENT_SIZE: usize = 8;
+/// Buffer type alias for Segment.
+pub type SegmentBuffer = [u8; SEGMENT_SIZE];
+
+/// Read-only view of Segment.
+#[derive(Debug, Clone, Copy)]
+pub struct SegmentView<'a> {
+    data: &'a SegmentBuffer,
+}
+
+/// Read-write view of Segment.
+#[derive(Debug)]
+pub struct SegmentMutView<'a> {
+    data: &'a mut SegmentBuffer,
+}
+
+impl<'a> SegmentView<'a> {
+    /// Size of Segment in bytes.
+    pub const SIZE_BYTES: usize = SEGMENT_SIZE;
+    /// Byte offset of start.
+    pub const FIELD_OFFSET_START: usize = 0;
+    /// Byte offset of end.
+    pub const FIELD_OFFSET_END: usize = 4;
+
+    /// Creates a new SegmentView from a SegmentBuffer.
+    pub fn new(data: &'a SegmentBuffer) -> Self {
+        Self { data }
+    }
+
+    /// Accessor for start.
+    pub fn start(&self) -> PointView {
+        let slice = &self.data[0..4];
+        PointView::new(slice.try_into().unwrap())
+    }
+
+    /// Accessor for end.
+    pub fn end(&self) -> PointView {
+        let slice = &self.data[4..8];
+        PointView::new(slice.try_into().unwrap())
+    }
+}
+
+// Fails to compile if Segment does not match its Onyx layout.
+const _: () = assert!(SegmentView::FIELD_OFFSET_END == SegmentView::FIELD_OFFSET_START + POINT_SIZE, "Segment.end does not match its Onyx offset");
+const _: () = assert!(SegmentView::FIELD_OFFSET_END + POINT_SIZE == SegmentView::SIZE_BYTES, "Segment does not match its Onyx size");
+
+impl<'a> SegmentMutView<'a> {
+    /// Creates a new SegmentMutView from a SegmentBuffer.
+    pub fn new(data: &'a mut SegmentBuffer) -> Self {
+        Self { data }
+    }
+
+    /// Accessor for start.
+    pub fn start(&self) -> PointView {
+        let slice = &self.data[0..4];
+        PointView::new(slice.try_into().unwrap())
+    }
+
+    /// Mutable accessor for start.
+    pub fn start_mut(&mut self) -> PointMutView {
+        let slice = &mut self.data[0..4];
+        PointMutView::new(slice.try_into().unwrap())
+    }
+
+    /// Accessor for end.
+    pub fn end(&self) -> PointView {
+        let slice = &self.data[4..8];
+        PointView::new(slice.try_into().unwrap())
+    }
+
+    /// Mutable accessor for end.
+    pub fn end_mut(&mut self) -> PointMutView {
+        let slice = &mut self.data[4..8];
+        PointMutView::new(slice.try_into().unwrap())
+    }
+}
+
+/// Size of Path in bytes.
+pub(crate) const PATH_SIZE: usize = 90;
+/// Buffer type alias for Path.
+pub type PathBuffer = [u8; PATH_SIZE];
+
+/// Read-only view of Path.
+#[derive(Debug, Clone, Copy)]
+pub struct PathView<'a> {
+    data: &'a PathBuffer,
+}
+
+/// Read-write view of Path.
+#[derive(Debug)]
+pub struct PathMutView<'a> {
+    data: &'a mut PathBuffer,
+}
+
+impl<'a> PathView<'a> {
+    /// Size of Path in bytes.
+    pub const SIZE_BYTES: usize = PATH_SIZE;
+    /// Byte offset of id.
+    pub const FIELD_OFFSET_ID: usize = 0;
+    /// Byte offset of origin.
+    pub const FIELD_OFFSET_ORIGIN: usize = 16;
+    /// Byte offset of segments.
+    pub const FIELD_OFFSET_SEGMENTS: usize = 20;
+    /// Byte offset of weights.
+    pub const FIELD_OFFSET_WEIGHTS: usize = 53;
+    /// Byte offset of scale.
+    pub const FIELD_OFFSET_SCALE: usize = 86;
+
+    /// Creates a new PathView from a PathBuffer.
+    pub fn new(data: &'a PathBuffer) -> Self {
+        Self { data }
+    }
+
+    /// Accessor for id.
+    pub fn id(&self) -> [u8; 16] {
+        self.data[0..16].try_into().unwrap()
+    }
+
+    /// Formats id as hex.
+    pub fn id_hex(&self) -> Hex<'_> {
+        Hex { bytes: &self.data[0..16], uuid: true }
+    }
+
+    /// Accessor for origin.
+    pub fn origin(&self) -> PointView {
+        let slice = &self.data[16..20];
+        PointView::new(slice.try_into().unwrap())
+    }
+
+    /// Accessor for segments.
+    pub fn segments(&self) -> PathSegmentsView {
+        let slice = &self.data[20..53];
+        PathSegmentsView { data: slice.try_into().unwrap() }
+    }
+
+    /// Accessor for weights.
+    pub fn weights(&self) -> PathWeightsView {
+        let slice = &self.data[53..86];
+        PathWeightsView { data: slice.try_into().unwrap() }
+    }
+
+    /// Accessor for scale_raw.
+    pub fn scale_raw(&self) -> i32 {
+        let bytes = self.data[86..90].try_into().unwrap();
+        i32::from_le_bytes(bytes)
+    }
+
+    /// Accessor for scale, scaled by 1/1000.
+    pub fn scale(&self) -> f64 {
+        self.scale_raw() as f64 / 1000_f64
+    }
+}
+
+// Fails to compile if Path does not match its Onyx layout.
+const _: () = assert!(PathView::FIELD_OFFSET_ORIGIN == PathView::FIELD_OFFSET_ID + 16, "Path.origin does not match its Onyx offset");
+const _: () = assert!(PathView::FIELD_OFFSET_SEGMENTS == PathView::FIELD_OFFSET_ORIGIN + POINT_SIZE, "Path.segments does not match its Onyx offset");
+const _: () = assert!(PathView::FIELD_OFFSET_WEIGHTS == PathView::FIELD_OFFSET_SEGMENTS + (std::mem::size_of::<u8>() + 4 * SEGMENT_SIZE), "Path.weights does not match its Onyx offset");
+const _: () = assert!(PathView::FIELD_OFFSET_SCALE == PathView::FIELD_OFFSET_WEIGHTS + (std::mem::size_of::<u8>() + 8 * std::mem::size_of::<f32>()), "Path.scale does not match its Onyx offset");
+const _: () = assert!(PathView::FIELD_OFFSET_SCALE + std::mem::size_of::<i32>() == PathView::SIZE_BYTES, "Path does not match its Onyx size");
+
+impl<'a> PathMutView<'a> {
+    /// Creates a new PathMutView from a PathBuffer.
+    pub fn new(data: &'a mut PathBuffer) -> Self {
+        Self { data }
+    }
+
+    /// Accessor for id.
+    pub fn id(&self) -> [u8; 16] {
+        self.data[0..16].try_into().unwrap()
+    }
+
+    /// Formats id as hex.
+    pub fn id_hex(&self) -> Hex<'_> {
+        Hex { bytes: &self.data[0..16], uuid: true }
+    }
+
+    /// Mutator for id.
+    pub fn set_id(&mut self, value: [u8; 16]) {
+        self.data[0..16].copy_from_slice(&value);
+    }
+
+    /// Accessor for origin.
+    pub fn origin(&self) -> PointView {
+        let slice = &self.data[16..20];
+        PointView::new(slice.try_into().unwrap())
+    }
+
+    /// Mutable accessor for origin.
+    pub fn origin_mut(&mut self) -> PointMutView {
+        let slice = &mut self.data[16..20];
+        PointMutView::new(slice.try_into().unwrap())
+    }
+
+    /// Accessor for segments.
+    pub fn segments(&self) -> PathSegmentsView {
+        let slice = &self.data[20..53];
+        PathSegmentsView { data: slice.try_into().unwrap() }
+    }
+
+    /// Mutable accessor for segments.
+    pub fn segments_mut(&mut self) -> PathSegmentsMutView {
+        let slice = &mut self.data[20..53];
+        PathSegmentsMutView { data: slice.try_into().unwrap() }
+    }
+
+    /// Accessor for weights.
+    pub fn weights(&self) -> PathWeightsView {
+        let slice = &self.data[53..86];
+        PathWeightsView { data: slice.try_into().unwrap() }
+    }
+
+    /// Mutable accessor for weights.
+    pub fn weights_mut(&mut self) -> PathWeightsMutView {
+        let slice = &mut self.data[53..86];
+        PathWeightsMutView { data: slice.try_into().unwrap() }
+    }
+
+    /// Accessor for scale_raw.
+    pub fn scale_raw(&self) -> i32 {
+        let bytes = self.data[86..90].try_into().unwrap();
+        i32::from_le_bytes(bytes)
+    }
+
+    /// Mutator for scale_raw.
+    pub fn set_scale_raw(&mut self, value: i32) {
+        let bytes = value.to_le_bytes();
+        self.data[86..90].copy_from_slice(&bytes);
+    }
+
+    /// Accessor for scale, scaled by 1/1000.
+    pub fn scale(&self) -> f64 {
+        self.scale_raw() as f64 / 1000_f64
+    }
+
+    /// Mutator for scale, rounding to the nearest step of 1/1000.
+    ///
+    /// Returns the value back if it is out of range for the field.
+    pub fn set_scale(&mut self, value: f64) -> Result<(), f64> {
+        let scaled = (value * 1000_f64).round();
+        if !(scaled >= i32::MIN as f64 && scaled < i32::MAX as f64 + 1.0) {
+            return Err(value);
+        }
+        self.set_scale_raw(scaled as i32);
+        Ok(())
+    }
+}
+
+/// Read-only view of the bounded vector Path.segments.
+#[derive(Debug, Clone, Copy)]
+pub struct PathSegmentsView<'a> {
+    data: &'a [u8; 33],
+}
+
+impl<'a> PathSegmentsView<'a> {
+    /// The maximum number of elements.
+    pub const CAPACITY: usize = 4;
+
+    /// Returns the number of elements, clamped to the capacity.
+    pub fn len(&self) -> usize {
+        (self.data[0] as usize).min(Self::CAPACITY)
+    }
+
+    /// Returns true if the vector has no elements.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the element at `index`, or `None` if it is out of range.
+    pub fn get(&self, index: usize) -> Option<SegmentView<'a>> {
+        if index >= self.len() {
+            return None;
+        }
+        let data: &'a [u8; 33] = self.data;
+        Some(SegmentView::new(data[1 + index * 8..1 + index * 8 + 8].try_into().unwrap()))
+    }
+
+    /// Returns an iterator over the elements.
+    pub fn iter(&self) -> impl Iterator<Item = SegmentView<'a>> + 'a {
+        let view = *self;
+        (0..self.len()).map(move |index| view.get(index).unwrap())
+    }
+}
+
+/// Read-write view of the bounded vector Path.segments.
+#[derive(Debug)]
+pub struct PathSegmentsMutView<'a> {
+    data: &'a mut [u8; 33],
+}
+
+impl<'a> PathSegmentsMutView<'a> {
+    /// The maximum number of elements.
+    pub const CAPACITY: usize = 4;
+
+    /// Returns a read-only view of the vector.
+    pub fn as_view(&self) -> PathSegmentsView<'_> {
+        PathSegmentsView { data: self.data }
+    }
+
+    /// Returns the number of elements, clamped to the capacity.
+    pub fn len(&self) -> usize {
+        self.as_view().len()
+    }
+
+    /// Returns true if the vector has no elements.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the element at `index`, or `None` if it is out of range.
+    pub fn get(&self, index: usize) -> Option<SegmentView<'_>> {
+        self.as_view().get(index)
+    }
+
+    /// Returns an iterator over the elements.
+    pub fn iter(&self) -> impl Iterator<Item = SegmentView<'_>> + '_ {
+        self.as_view().iter()
+    }
+
+    fn set_len(&mut self, len: usize) {
+        self.data[0] = len as u8;
+    }
+
+    /// Removes all elements.
+    pub fn clear(&mut self) {
+        self.set_len(0);
+    }
+
+    /// Returns a mutable view of the element at `index`, or `None` if it is out of range.
+    pub fn get_mut(&mut self, index: usize) -> Option<SegmentMutView<'_>> {
+        if index >= self.len() {
+            return None;
+        }
+        Some(SegmentMutView::new((&mut self.data[1 + index * 8..1 + index * 8 + 8]).try_into().unwrap()))
+    }
+
+    /// Appends a zeroed element and returns a mutable view of it, or `None` if the vector is full.
+    pub fn push(&mut self) -> Option<SegmentMutView<'_>> {
+        let index = self.len();
+        if index == Self::CAPACITY {
+            return None;
+        }
+        self.set_len(index + 1);
+        self.data[1 + index * 8..1 + index * 8 + 8].fill(0);
+        self.get_mut(index)
+    }
+}
+
+/// Read-only view of the bounded vector Path.weights.
+#[derive(Debug, Clone, Copy)]
+pub struct PathWeightsView<'a> {
+    data: &'a [u8; 33],
+}
+
+impl<'a> PathWeightsView<'a> {
+    /// The maximum number of elements.
+    pub const CAPACITY: usize = 8;
+
+    /// Returns the number of elements, clamped to the capacity.
+    pub fn len(&self) -> usize {
+        (self.data[0] as usize).min(Self::CAPACITY)
+    }
+
+    /// Returns true if the vector has no elements.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the element at `index`, or `None` if it is out of range.
+    pub fn get(&self, index: usize) -> Option<f32> {
+        if index >= self.len() {
+            return None;
+        }
+        Some(f32::from_le_bytes(self.data[1 + index * 4..1 + index * 4 + 4].try_into().unwrap()))
+    }
+
+    /// Returns an iterator over the elements.
+    pub fn iter(&self) -> impl Iterator<Item = f32> + 'a {
+        let view = *self;
+        (0..self.len()).map(move |index| view.get(index).unwrap())
+    }
+}
+
+/// Read-write view of the bounded vector Path.weights.
+#[derive(Debug)]
+pub struct PathWeightsMutView<'a> {
+    data: &'a mut [u8; 33],
+}
+
+impl<'a> PathWeightsMutView<'a> {
+    /// The maximum number of elements.
+    pub const CAPACITY: usize = 8;
+
+    /// Returns a read-only view of the vector.
+    pub fn as_view(&self) -> PathWeightsView<'_> {
+        PathWeightsView { data: self.data }
+    }
+
+    /// Returns the number of elements, clamped to the capacity.
+    pub fn len(&self) -> usize {
+        self.as_view().len()
+    }
+
+    /// Returns true if the vector has no elements.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the element at `index`, or `None` if it is out of range.
+    pub fn get(&self, index: usize) -> Option<f32> {
+        self.as_view().get(index)
+    }
+
+    /// Returns an iterator over the elements.
+    pub fn iter(&self) -> impl Iterator<Item = f32> + '_ {
+        self.as_view().iter()
+    }
+
+    fn set_len(&mut self, len: usize) {
+        self.data[0] = len as u8;
+    }
+
+    /// Removes all elements.
+    pub fn clear(&mut self) {
+        self.set_len(0);
+    }
+
+    /// Replaces the element at `index`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is not less than `len()`.
+    pub fn set(&mut self, index: usize, value: f32) {
+        assert!(index < self.len(), "index {index} out of range");
+        self.data[1 + index * 4..1 + index * 4 + 4].copy_from_slice(&value.to_le_bytes());
+    }
+
+    /// Appends an element, or returns it back if the vector is full.
+    pub fn push(&mut self, value: f32) -> Result<(), f32> {
+        let index = self.len();
+        if index == Self::CAPACITY {
+            return Err(value);
+        }
+        self.set_len(index + 1);
+        self.set(index, value);
+        Ok(())
+    }
+}
+
+/// Formats a `uuid` or `bytes[N]` field as lowercase hex; a UUID is grouped 8-4-4-4-12.
+#[derive(Clone, Copy)]
+pub struct Hex<'a> {
+    bytes: &'a [u8],
+    uuid: bool,
+}
+
+impl std::fmt::Debug for Hex<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (i, b) in self.bytes.iter().enumerate() {
+            if self.uuid && matches!(i, 4 | 6 | 8 | 10) {
+                f.write_str("-")?;
+            }
+            write!(f, "{:02x}", b)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::fmt::Display for Hex<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        std::fmt::Debug::fmt(self, f)
+    }
+}
@@ -0,0 +1,39 @@
+// Automatically generated by Onyx IDL compiler. Do not edit.
+`ifndef ONYX_NESTED_PKG_SV
+`define ONYX_NESTED_PKG_SV
+
+package nested_pkg;
+
+  // Point: 4 bytes.
+  localparam int Point_SIZE_BYTES = 4;
+  localparam int Point_x_OFFSET = 0;
+  localparam int Point_x_WIDTH = 16;
+  localparam int Point_y_OFFSET = 2;
+  localparam int Point_y_WIDTH = 16;
+
+  // Segment: 8 bytes.
+  localparam int Segment_SIZE_BYTES = 8;
+  localparam int Segment_start_OFFSET = 0;
+  localparam int Segment_start_WIDTH = 32;
+  localparam int Segment_end__OFFSET = 4;
+  localparam int Segment_end__WIDTH = 32;
+
+  // Path: 90 bytes.
+  localparam int Path_SIZE_BYTES = 90;
+  localparam int Path_id_OFFSET = 0;
+  localparam int Path_id_WIDTH = 128;
+  localparam int Path_origin_OFFSET = 16;
+  localparam int Path_origin_WIDTH = 32;
+  localparam int Path_segments_OFFSET = 20;
+  localparam int Path_segments_WIDTH = 264;
+  localparam int Path_segments_CAPACITY = 4;
+  localparam int Path_weights_OFFSET = 53;
+  localparam int Path_weights_WIDTH = 264;
+  localparam int Path_weights_CAPACITY = 8;
+  localparam int Path_scale_OFFSET = 86;
+  localparam int Path_scale_WIDTH = 32;
+  localparam longint unsigned Path_scale_SCALE = 64'd1000;
+
+endpackage
+
+`endif // ONYX_NESTED_PKG_SV
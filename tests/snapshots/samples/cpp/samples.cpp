@@ -0,0 +1,51 @@
+// Automatically generated by Onyx IDL compiler. Do not edit.
+#include "samples.hpp"
+
+#include <string.h>
+
+namespace onyx {
+
+Samples* Samples::Deserialize(Buffer& buffer) {
+    // Overlay the class structure onto the buffer memory. This is the zero-copy step.
+    Samples* result = (Samples*)buffer;
+
+    // SWAP: Primitive field id
+    result->__raw_id = utils::byteswap_if_needed(result->__raw_id);
+    // SWAP: Vector field levels
+    for (size_t i = 0; i < 9; ++i) {
+        result->__raw_levels.__items[i] = utils::byteswap_if_needed(result->__raw_levels.__items[i]);
+    }
+    // SWAP: Vector field counts
+    for (size_t i = 0; i < 5; ++i) {
+        result->__raw_counts.__items[i] = utils::byteswap_if_needed(result->__raw_counts.__items[i]);
+    }
+    // SWAP: Vector field readings
+    for (size_t i = 0; i < 3; ++i) {
+        result->__raw_readings.__items[i] = utils::byteswap_if_needed(result->__raw_readings.__items[i]);
+    }
+
+    return result;
+}
+
+void Samples::Serialize(Buffer& buffer) const {
+    // Copy the host-endian object into the buffer first.
+    memcpy(buffer, this, kSizeOf);
+    Samples* wire_format_data = (Samples*)buffer;
+
+    // SWAP: Primitive field id
+    wire_format_data->__raw_id = utils::byteswap_if_needed(wire_format_data->__raw_id);
+    // SWAP: Vector field levels
+    for (size_t i = 0; i < 9; ++i) {
+        wire_format_data->__raw_levels.__items[i] = utils::byteswap_if_needed(wire_format_data->__raw_levels.__items[i]);
+    }
+    // SWAP: Vector field counts
+    for (size_t i = 0; i < 5; ++i) {
+        wire_format_data->__raw_counts.__items[i] = utils::byteswap_if_needed(wire_format_data->__raw_counts.__items[i]);
+    }
+    // SWAP: Vector field readings
+    for (size_t i = 0; i < 3; ++i) {
+        wire_format_data->__raw_readings.__items[i] = utils::byteswap_if_needed(wire_format_data->__raw_readings.__items[i]);
+    }
+}
+
+} // namespace onyx
@@ -0,0 +1,282 @@
+// Automatically generated by Onyx IDL compiler. Do not edit.
+#ifndef ONYX_SAMPLES_H_
+#define ONYX_SAMPLES_H_
+
+#include <stddef.h>
+#include <stdint.h>
+#include <string.h>
+
+#pragma pack(push, 1)
+
+namespace onyx {
+#ifndef ONYX_UTILS_ENDIANNESS_BIG_
+#define ONYX_UTILS_ENDIANNESS_BIG_
+
+namespace utils {
+
+// Define network endianness (usually Big Endian).
+#define ONYX_BIG_ENDIAN 4321
+#define ONYX_LITTLE_ENDIAN 1234
+#define ONYX_NETWORK_ORDER ONYX_BIG_ENDIAN
+
+// Determine host endianness at compile time (using standard GCC/Clang macros)
+#ifdef __BYTE_ORDER__
+    #if __BYTE_ORDER__ == __ORDER_BIG_ENDIAN__
+        #define ONYX_HOST_ORDER ONYX_BIG_ENDIAN
+    #elif __BYTE_ORDER__ == __ORDER_LITTLE_ENDIAN__
+        #define ONYX_HOST_ORDER ONYX_LITTLE_ENDIAN
+    #else
+        #error "Unsupported host endianness"
+    #endif
+#elif defined(_WIN32) || defined(__LITTLE_ENDIAN__)
+    // Fallback for Windows (always little-endian) or other platforms
+    #define ONYX_HOST_ORDER ONYX_LITTLE_ENDIAN
+#else
+    #error "Could not determine host endianness"
+#endif
+
+// Helper function to swap bytes (Explicit Overloads)
+inline uint16_t byteswap(uint16_t value) { return ((value << 8) & 0xFF00) | ((value >> 8) & 0x00FF); }
+
+inline int16_t byteswap(int16_t value) { return (int16_t)byteswap((uint16_t)value); }
+
+inline uint32_t byteswap(uint32_t value) { return (value << 24) | ((value & 0x00FF0000) >> 8) | ((value & 0x0000FF00) << 8) | (value >> 24); }
+
+inline int32_t byteswap(int32_t value) { return (int32_t)byteswap((uint32_t)value); }
+
+inline uint64_t byteswap(uint64_t value) { return (value << 56) | ((value & 0x00FF000000000000ULL) >> 40) | ((value & 0x0000FF0000000000ULL) >> 24) | ((value & 0x000000FF00000000ULL) >> 8) | ((value & 0x00000000FF000000ULL) << 8) | ((value & 0x0000000000FF0000ULL) << 24) | ((value & 0x000000000000FF00ULL) << 40) | (value >> 56); }
+
+inline int64_t byteswap(int64_t value) { return (int64_t)byteswap((uint64_t)value); }
+
+inline float byteswap(float value) {
+    uint32_t temp;
+    memcpy(&temp, &value, sizeof(value));
+    temp = byteswap(temp);
+    memcpy(&value, &temp, sizeof(value));
+    return value;
+}
+
+inline double byteswap(double value) {
+    uint64_t temp;
+    memcpy(&temp, &value, sizeof(value));
+    temp = byteswap(temp);
+    memcpy(&value, &temp, sizeof(value));
+    return value;
+}
+
+// Swaps bytes if the host order does not match the network order (Explicit Overloads)
+inline uint16_t byteswap_if_needed(uint16_t value) {
+    if (ONYX_HOST_ORDER != ONYX_NETWORK_ORDER) {
+        return byteswap(value);
+    }
+    return value;
+}
+
+inline int16_t byteswap_if_needed(int16_t value) {
+    if (ONYX_HOST_ORDER != ONYX_NETWORK_ORDER) {
+        return byteswap(value);
+    }
+    return value;
+}
+
+inline uint32_t byteswap_if_needed(uint32_t value) {
+    if (ONYX_HOST_ORDER != ONYX_NETWORK_ORDER) {
+        return byteswap(value);
+    }
+    return value;
+}
+
+inline int32_t byteswap_if_needed(int32_t value) {
+    if (ONYX_HOST_ORDER != ONYX_NETWORK_ORDER) {
+        return byteswap(value);
+    }
+    return value;
+}
+
+inline uint64_t byteswap_if_needed(uint64_t value) {
+    if (ONYX_HOST_ORDER != ONYX_NETWORK_ORDER) {
+        return byteswap(value);
+    }
+    return value;
+}
+
+inline int64_t byteswap_if_needed(int64_t value) {
+    if (ONYX_HOST_ORDER != ONYX_NETWORK_ORDER) {
+        return byteswap(value);
+    }
+    return value;
+}
+
+inline float byteswap_if_needed(float value) {
+    if (ONYX_HOST_ORDER != ONYX_NETWORK_ORDER) {
+        return byteswap(value);
+    }
+    return value;
+}
+
+inline double byteswap_if_needed(double value) {
+    if (ONYX_HOST_ORDER != ONYX_NETWORK_ORDER) {
+        return byteswap(value);
+    }
+    return value;
+}
+
+} // namespace utils
+#endif // ONYX_UTILS_ENDIANNESS_BIG_
+
+#ifndef ONYX_UTILS_BOUNDED_VEC_
+#define ONYX_UTILS_BOUNDED_VEC_
+
+namespace utils {
+
+/// A bounded vector: a length followed by room for N elements, all of them inline.
+template <typename T, typename LenT, size_t N>
+class BoundedVec {
+public:
+    LenT __length;
+    T __items[N];
+
+    /// Returns the number of elements, clamped to the capacity.
+    inline size_t len() const { return __length < N ? __length : N; }
+    /// Returns the maximum number of elements.
+    static inline size_t capacity() { return N; }
+    /// Returns true if the vector has no elements.
+    inline bool empty() const { return len() == 0; }
+    /// Returns the element at `i`, which must be less than len().
+    inline const T& get(size_t i) const { return __items[i]; }
+    inline T& get(size_t i) { return __items[i]; }
+    /// Replaces the element at `i`, which must be less than len().
+    inline void set(size_t i, const T& value) { __items[i] = value; }
+    /// Appends an element. Returns false, leaving the vector unchanged, if it is full.
+    inline bool push(const T& value) {
+        size_t n = len();
+        if (n == N) return false;
+        __items[n] = value;
+        __length = static_cast<LenT>(n + 1);
+        return true;
+    }
+    /// Removes all elements.
+    inline void clear() { __length = 0; }
+
+    inline const T* begin() const { return __items; }
+    inline const T* end() const { return __items + len(); }
+    inline T* begin() { return __items; }
+    inline T* end() { return __items + len(); }
+};
+
+} // namespace utils
+
+#endif // ONYX_UTILS_BOUNDED_VEC_
+
+class Samples;
+
+class Samples {
+private:
+    uint16_t __raw_id;
+    utils::BoundedVec<uint16_t, uint8_t, 9> __raw_levels;
+    utils::BoundedVec<int32_t, uint8_t, 5> __raw_counts;
+    utils::BoundedVec<double, uint8_t, 3> __raw_readings;
+
+public:
+    static const size_t kSizeOf = 67;
+    using Buffer = uint8_t[kSizeOf];
+
+    /// Byte offsets of the fields within the buffer.
+    static const size_t kIdOffset = 0;
+    static const size_t kLevelsOffset = 2;
+    static const size_t kCountsOffset = 21;
+    static const size_t kReadingsOffset = 42;
+
+    /// Accessor for id
+    inline const uint16_t id() const { return __raw_id; }
+
+    /// Mutator for id
+    inline void id(const uint16_t value) { __raw_id = value; }
+
+    /// Accessor for levels
+    inline utils::BoundedVec<uint16_t, uint8_t, 9>& levels() { return __raw_levels; }
+
+    /// Mutator for levels
+    inline void levels(const utils::BoundedVec<uint16_t, uint8_t, 9> value) { __raw_levels = value; }
+
+    /// Accessor for counts
+    inline utils::BoundedVec<int32_t, uint8_t, 5>& counts() { return __raw_counts; }
+
+    /// Mutator for counts
+    inline void counts(const utils::BoundedVec<int32_t, uint8_t, 5> value) { __raw_counts = value; }
+
+    /// Accessor for readings
+    inline utils::BoundedVec<double, uint8_t, 3>& readings() { return __raw_readings; }
+
+    /// Mutator for readings
+    inline void readings(const utils::BoundedVec<double, uint8_t, 3> value) { __raw_readings = value; }
+
+    /// Deserializes the network-endian buffer to Samples in-place by casting
+    /// and applying endianness correction to match the host-endianness.
+    /// NOTE: This mutates the input buffer to host-endianness.
+    /// Returns a pointer to the object within the buffer.
+    static Samples* Deserialize(Buffer& buffer);
+
+    /// Serializes the host-endian object into the provided buffer,
+    /// applying endianness correction to match the network-endianness.
+    void Serialize(Buffer& buffer) const;
+
+private:
+    /// Fails to compile if Samples does not match its Onyx layout.
+    static inline void __check_layout() {
+        static_assert(sizeof(Samples) == kSizeOf, "Samples does not match its Onyx size");
+        static_assert(offsetof(Samples, __raw_id) == kIdOffset, "Samples::id does not match its Onyx offset");
+        static_assert(offsetof(Samples, __raw_levels) == kLevelsOffset, "Samples::levels does not match its Onyx offset");
+        static_assert(offsetof(Samples, __raw_counts) == kCountsOffset, "Samples::counts does not match its Onyx offset");
+        static_assert(offsetof(Samples, __raw_readings) == kReadingsOffset, "Samples::readings does not match its Onyx offset");
+    }
+};
+
+
+inline Samples* Samples::Deserialize(Buffer& buffer) {
+    // Overlay the class structure onto the buffer memory. This is the zero-copy step.
+    Samples* result = (Samples*)buffer;
+
+    // SWAP: Primitive field id
+    result->__raw_id = utils::byteswap_if_needed(result->__raw_id);
+    // SWAP: Vector field levels
+    for (size_t i = 0; i < 9; ++i) {
+        result->__raw_levels.__items[i] = utils::byteswap_if_needed(result->__raw_levels.__items[i]);
+    }
+    // SWAP: Vector field counts
+    for (size_t i = 0; i < 5; ++i) {
+        result->__raw_counts.__items[i] = utils::byteswap_if_needed(result->__raw_counts.__items[i]);
+    }
+    // SWAP: Vector field readings
+    for (size_t i = 0; i < 3; ++i) {
+        result->__raw_readings.__items[i] = utils::byteswap_if_needed(result->__raw_readings.__items[i]);
+    }
+
+    return result;
+}
+
+inline void Samples::Serialize(Buffer& buffer) const {
+    // Copy the host-endian object into the buffer first.
+    memcpy(buffer, this, kSizeOf);
+    Samples* wire_format_data = (Samples*)buffer;
+
+    // SWAP: Primitive field id
+    wire_format_data->__raw_id = utils::byteswap_if_needed(wire_format_data->__raw_id);
+    // SWAP: Vector field levels
+    for (size_t i = 0; i < 9; ++i) {
+        wire_format_data->__raw_levels.__items[i] = utils::byteswap_if_needed(wire_format_data->__raw_levels.__items[i]);
+    }
+    // SWAP: Vector field counts
+    for (size_t i = 0; i < 5; ++i) {
+        wire_format_data->__raw_counts.__items[i] = utils::byteswap_if_needed(wire_format_data->__raw_counts.__items[i]);
+    }
+    // SWAP: Vector field readings
+    for (size_t i = 0; i < 3; ++i) {
+        wire_format_data->__raw_readings.__items[i] = utils::byteswap_if_needed(wire_format_data->__raw_readings.__items[i]);
+    }
+}
+
+} // namespace onyx
+
+#pragma pack(pop)
+#endif // ONYX_SAMPLES_H_
@@ -0,0 +1,130 @@
+// Automatically generated by Onyx IDL compiler. Do not edit.
+package onyx
+
+import java.nio.ByteBuffer
+import java.nio.ByteOrder
+
+/** The byte order of every field in this file. */
+private val BYTE_ORDER: ByteOrder = ByteOrder.BIG_ENDIAN
+
+/** Returns the [size] bytes of the buffer from [offset] as a buffer of their own. */
+private fun ByteBuffer.view(offset: Int, size: Int): ByteBuffer =
+    (duplicate().clear().position(offset).limit(offset + size) as ByteBuffer).slice()
+
+/** Reads the [size] bytes from [offset] as an unsigned integer in [BYTE_ORDER]. */
+private fun ByteBuffer.readBits(offset: Int, size: Int): Long {
+    var raw = 0L
+    for (i in 0 until size) {
+        val index = if (BYTE_ORDER == ByteOrder.BIG_ENDIAN) i else size - 1 - i
+        raw = (raw shl 8) or (get(offset + index).toLong() and 0xFFL)
+    }
+    return raw
+}
+
+/** Writes the low [size] bytes of [raw] from [offset] in [BYTE_ORDER]. */
+private fun ByteBuffer.writeBits(offset: Int, size: Int, raw: Long) {
+    for (i in 0 until size) {
+        val shift = if (BYTE_ORDER == ByteOrder.BIG_ENDIAN) 8 * (size - 1 - i) else 8 * i
+        put(offset + i, (raw ushr shift).toByte())
+    }
+}
+
+/** Copies the [size] bytes from [offset]. */
+private fun ByteBuffer.readBytes(offset: Int, size: Int): ByteArray =
+    ByteArray(size).also { view(offset, size).get(it) }
+
+/** Writes [value], which must hold exactly [size] bytes, from [offset]. */
+private fun ByteBuffer.writeBytes(offset: Int, size: Int, value: ByteArray) {
+    require(value.size == size) { "expected $size bytes, got ${value.size}" }
+    view(offset, size).put(value)
+}
+
+/** Returns [index] if it is below [count], and throws otherwise. */
+private fun checkIndex(index: Int, count: Int): Int {
+    if (index !in 0 until count) {
+        throw IndexOutOfBoundsException("index $index is out of bounds for $count elements")
+    }
+    return index
+}
+
+/** Samples, viewed in [buffer] from its index 0. */
+@JvmInline
+value class Samples(val buffer: ByteBuffer) {
+    /** Accessor for id. */
+    var id: UShort
+        get() = buffer.readBits(ID_OFFSET, 2).toUShort()
+        set(value) {
+            buffer.writeBits(ID_OFFSET, 2, value.toLong())
+        }
+
+    /** The number of elements of levels in use. */
+    var levelsCount: Int
+        get() = buffer.readBits(LEVELS_OFFSET, 1).toInt()
+        set(value) {
+            require(value in 0..9) { "levels holds at most 9 elements" }
+            buffer.writeBits(LEVELS_OFFSET, 1, value.toLong())
+        }
+
+    /** Returns element [index] of levels. */
+    fun levels(index: Int): UShort = buffer.readBits(LEVELS_OFFSET + 1 + 2 * checkIndex(index, levelsCount), 2).toUShort()
+
+    /** Sets element [index] of levels. */
+    fun setLevels(index: Int, value: UShort) {
+        buffer.writeBits(LEVELS_OFFSET + 1 + 2 * checkIndex(index, levelsCount), 2, value.toLong())
+    }
+
+    /** The number of elements of counts in use. */
+    var countsCount: Int
+        get() = buffer.readBits(COUNTS_OFFSET, 1).toInt()
+        set(value) {
+            require(value in 0..5) { "counts holds at most 5 elements" }
+            buffer.writeBits(COUNTS_OFFSET, 1, value.toLong())
+        }
+
+    /** Returns element [index] of counts. */
+    fun counts(index: Int): Int = buffer.readBits(COUNTS_OFFSET + 1 + 4 * checkIndex(index, countsCount), 4).toInt()
+
+    /** Sets element [index] of counts. */
+    fun setCounts(index: Int, value: Int) {
+        buffer.writeBits(COUNTS_OFFSET + 1 + 4 * checkIndex(index, countsCount), 4, value.toLong())
+    }
+
+    /** The number of elements of readings in use. */
+    var readingsCount: Int
+        get() = buffer.readBits(READINGS_OFFSET, 1).toInt()
+        set(value) {
+            require(value in 0..3) { "readings holds at most 3 elements" }
+            buffer.writeBits(READINGS_OFFSET, 1, value.toLong())
+        }
+
+    /** Returns element [index] of readings. */
+    fun readings(index: Int): Double = Double.fromBits(buffer.readBits(READINGS_OFFSET + 1 + 8 * checkIndex(index, readingsCount), 8))
+
+    /** Sets element [index] of readings. */
+    fun setReadings(index: Int, value: Double) {
+        buffer.writeBits(READINGS_OFFSET + 1 + 8 * checkIndex(index, readingsCount), 8, value.toRawBits())
+    }
+
+    companion object {
+        /** The size of Samples in bytes. */
+        const val SIZE_BYTES: Int = 67
+
+        /** The byte offset of id. */
+        const val ID_OFFSET: Int = 0
+
+        /** The byte offset of levels. */
+        const val LEVELS_OFFSET: Int = 2
+
+        /** The byte offset of counts. */
+        const val COUNTS_OFFSET: Int = 21
+
+        /** The byte offset of readings. */
+        const val READINGS_OFFSET: Int = 42
+
+        /** Returns a view of Samples from the position of [buffer]. */
+        fun wrap(buffer: ByteBuffer): Samples = Samples(buffer.view(buffer.position(), SIZE_BYTES))
+
+        /** Returns a view of Samples in a new, zeroed buffer. */
+        fun allocate(): Samples = Samples(ByteBuffer.allocate(SIZE_BYTES))
+    }
+}
@@ -0,0 +1,30 @@
+% Automatically generated by Onyx IDL compiler. Do not edit.
+function samples()
+%SAMPLES Defines the Simulink enums and bus objects of the schema.
+%   Run samples before loading a model that uses them. Each bus object is
+%   assigned in the base workspace, named after its struct or message.
+
+% Samples: 67 bytes.
+elements = Simulink.BusElement.empty;
+elements(end + 1) = element('id', 'uint16', 1, 'byte 0');
+elements(end + 1) = element('levels_count', 'uint8', 1, 'byte 2; the number of elements of levels in use');
+elements(end + 1) = element('levels', 'uint16', 9, 'byte 3');
+elements(end + 1) = element('counts_count', 'uint8', 1, 'byte 21; the number of elements of counts in use');
+elements(end + 1) = element('counts', 'int32', 5, 'byte 22');
+elements(end + 1) = element('readings_count', 'uint8', 1, 'byte 42; the number of elements of readings in use');
+elements(end + 1) = element('readings', 'double', 3, 'byte 43');
+bus = Simulink.Bus;
+bus.Description = 'Samples: 67 bytes.';
+bus.Elements = elements;
+assignin('base', 'Samples', bus);
+
+end
+
+function e = element(name, type, dimensions, description)
+% Returns a bus element.
+e = Simulink.BusElement;
+e.Name = name;
+e.DataType = type;
+e.Dimensions = dimensions;
+e.Description = description;
+end
@@ -0,0 +1,427 @@
+// Automatically generated by Onyx IDL compiler. Do not edit.
+
+use std::convert::TryInto;
+
+
+/// Size of Samples in bytes.
+pub(crate) const SAMPLES_SIZE: usize = 67;
+/// Buffer type alias for Samples.
+pub type SamplesBuffer = [u8; SAMPLES_SIZE];
+
+/// Read-only view of Samples.
+#[derive(Debug, Clone, Copy)]
+pub struct SamplesView<'a> {
+    data: &'a SamplesBuffer,
+}
+
+/// Read-write view of Samples.
+#[derive(Debug)]
+pub struct SamplesMutView<'a> {
+    data: &'a mut SamplesBuffer,
+}
+
+impl<'a> SamplesView<'a> {
+    /// Size of Samples in bytes.
+    pub const SIZE_BYTES: usize = SAMPLES_SIZE;
+    /// Byte offset of id.
+    pub const FIELD_OFFSET_ID: usize = 0;
+    /// Byte offset of levels.
+    pub const FIELD_OFFSET_LEVELS: usize = 2;
+    /// Byte offset of counts.
+    pub const FIELD_OFFSET_COUNTS: usize = 21;
+    /// Byte offset of readings.
+    pub const FIELD_OFFSET_READINGS: usize = 42;
+
+    /// Creates a new SamplesView from a SamplesBuffer.
+    pub fn new(data: &'a SamplesBuffer) -> Self {
+        Self { data }
+    }
+
+    /// Accessor for id.
+    pub fn id(&self) -> u16 {
+        let bytes = self.data[0..2].try_into().unwrap();
+        u16::from_be_bytes(bytes)
+    }
+
+    /// Accessor for levels.
+    pub fn levels(&self) -> SamplesLevelsView {
+        let slice = &self.data[2..21];
+        SamplesLevelsView { data: slice.try_into().unwrap() }
+    }
+
+    /// Accessor for counts.
+    pub fn counts(&self) -> SamplesCountsView {
+        let slice = &self.data[21..42];
+        SamplesCountsView { data: slice.try_into().unwrap() }
+    }
+
+    /// Accessor for readings.
+    pub fn readings(&self) -> SamplesReadingsView {
+        let slice = &self.data[42..67];
+        SamplesReadingsView { data: slice.try_into().unwrap() }
+    }
+}
+
+// Fails to compile if Samples does not match its Onyx layout.
+const _: () = assert!(SamplesView::FIELD_OFFSET_LEVELS == SamplesView::FIELD_OFFSET_ID + std::mem::size_of::<u16>(), "Samples.levels does not match its Onyx offset");
+const _: () = assert!(SamplesView::FIELD_OFFSET_COUNTS == SamplesView::FIELD_OFFSET_LEVELS + (std::mem::size_of::<u8>() + 9 * std::mem::size_of::<u16>()), "Samples.counts does not match its Onyx offset");
+const _: () = assert!(SamplesView::FIELD_OFFSET_READINGS == SamplesView::FIELD_OFFSET_COUNTS + (std::mem::size_of::<u8>() + 5 * std::mem::size_of::<i32>()), "Samples.readings does not match its Onyx offset");
+const _: () = assert!(SamplesView::FIELD_OFFSET_READINGS + (std::mem::size_of::<u8>() + 3 * std::mem::size_of::<f64>()) == SamplesView::SIZE_BYTES, "Samples does not match its Onyx size");
+
+impl<'a> SamplesMutView<'a> {
+    /// Creates a new SamplesMutView from a SamplesBuffer.
+    pub fn new(data: &'a mut SamplesBuffer) -> Self {
+        Self { data }
+    }
+
+    /// Accessor for id.
+    pub fn id(&self) -> u16 {
+        let bytes = self.data[0..2].try_into().unwrap();
+        u16::from_be_bytes(bytes)
+    }
+
+    /// Mutator for id.
+    pub fn set_id(&mut self, value: u16) {
+        let bytes = value.to_be_bytes();
+        self.data[0..2].copy_from_slice(&bytes);
+    }
+
+    /// Accessor for levels.
+    pub fn levels(&self) -> SamplesLevelsView {
+        let slice = &self.data[2..21];
+        SamplesLevelsView { data: slice.try_into().unwrap() }
+    }
+
+    /// Mutable accessor for levels.
+    pub fn levels_mut(&mut self) -> SamplesLevelsMutView {
+        let slice = &mut self.data[2..21];
+        SamplesLevelsMutView { data: slice.try_into().unwrap() }
+    }
+
+    /// Accessor for counts.
+    pub fn counts(&self) -> SamplesCountsView {
+        let slice = &self.data[21..42];
+        SamplesCountsView { data: slice.try_into().unwrap() }
+    }
+
+    /// Mutable accessor for counts.
+    pub fn counts_mut(&mut self) -> SamplesCountsMutView {
+        let slice = &mut self.data[21..42];
+        SamplesCountsMutView { data: slice.try_into().unwrap() }
+    }
+
+    /// Accessor for readings.
+    pub fn readings(&self) -> SamplesReadingsView {
+        let slice = &self.data[42..67];
+        SamplesReadingsView { data: slice.try_into().unwrap() }
+    }
+
+    /// Mutable accessor for readings.
+    pub fn readings_mut(&mut self) -> SamplesReadingsMutView {
+        let slice = &mut self.data[42..67];
+        SamplesReadingsMutView { data: slice.try_into().unwrap() }
+    }
+}
+
+/// Read-only view of the bounded vector Samples.levels.
+#[derive(Debug, Clone, Copy)]
+pub struct SamplesLevelsView<'a> {
+    data: &'a [u8; 19],
+}
+
+impl<'a> SamplesLevelsView<'a> {
+    /// The maximum number of elements.
+    pub const CAPACITY: usize = 9;
+
+    /// Returns the number of elements, clamped to the capacity.
+    pub fn len(&self) -> usize {
+        (self.data[0] as usize).min(Self::CAPACITY)
+    }
+
+    /// Returns true if the vector has no elements.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the element at `index`, or `None` if it is out of range.
+    pub fn get(&self, index: usize) -> Option<u16> {
+        if index >= self.len() {
+            return None;
+        }
+        Some(u16::from_be_bytes(self.data[1 + index * 2..1 + index * 2 + 2].try_into().unwrap()))
+    }
+
+    /// Returns an iterator over the elements.
+    pub fn iter(&self) -> impl Iterator<Item = u16> + 'a {
+        let view = *self;
+        (0..self.len()).map(move |index| view.get(index).unwrap())
+    }
+}
+
+/// Read-write view of the bounded vector Samples.levels.
+#[derive(Debug)]
+pub struct SamplesLevelsMutView<'a> {
+    data: &'a mut [u8; 19],
+}
+
+impl<'a> SamplesLevelsMutView<'a> {
+    /// The maximum number of elements.
+    pub const CAPACITY: usize = 9;
+
+    /// Returns a read-only view of the vector.
+    pub fn as_view(&self) -> SamplesLevelsView<'_> {
+        SamplesLevelsView { data: self.data }
+    }
+
+    /// Returns the number of elements, clamped to the capacity.
+    pub fn len(&self) -> usize {
+        self.as_view().len()
+    }
+
+    /// Returns true if the vector has no elements.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the element at `index`, or `None` if it is out of range.
+    pub fn get(&self, index: usize) -> Option<u16> {
+        self.as_view().get(index)
+    }
+
+    /// Returns an iterator over the elements.
+    pub fn iter(&self) -> impl Iterator<Item = u16> + '_ {
+        self.as_view().iter()
+    }
+
+    fn set_len(&mut self, len: usize) {
+        self.data[0] = len as u8;
+    }
+
+    /// Removes all elements.
+    pub fn clear(&mut self) {
+        self.set_len(0);
+    }
+
+    /// Replaces the element at `index`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is not less than `len()`.
+    pub fn set(&mut self, index: usize, value: u16) {
+        assert!(index < self.len(), "index {index} out of range");
+        self.data[1 + index * 2..1 + index * 2 + 2].copy_from_slice(&value.to_be_bytes());
+    }
+
+    /// Appends an element, or returns it back if the vector is full.
+    pub fn push(&mut self, value: u16) -> Result<(), u16> {
+        let index = self.len();
+        if index == Self::CAPACITY {
+            return Err(value);
+        }
+        self.set_len(index + 1);
+        self.set(index, value);
+        Ok(())
+    }
+}
+
+/// Read-only view of the bounded vector Samples.counts.
+#[derive(Debug, Clone, Copy)]
+pub struct SamplesCountsView<'a> {
+    data: &'a [u8; 21],
+}
+
+impl<'a> SamplesCountsView<'a> {
+    /// The maximum number of elements.
+    pub const CAPACITY: usize = 5;
+
+    /// Returns the number of elements, clamped to the capacity.
+    pub fn len(&self) -> usize {
+        (self.data[0] as usize).min(Self::CAPACITY)
+    }
+
+    /// Returns true if the vector has no elements.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the element at `index`, or `None` if it is out of range.
+    pub fn get(&self, index: usize) -> Option<i32> {
+        if index >= self.len() {
+            return None;
+        }
+        Some(i32::from_be_bytes(self.data[1 + index * 4..1 + index * 4 + 4].try_into().unwrap()))
+    }
+
+    /// Returns an iterator over the elements.
+    pub fn iter(&self) -> impl Iterator<Item = i32> + 'a {
+        let view = *self;
+        (0..self.len()).map(move |index| view.get(index).unwrap())
+    }
+}
+
+/// Read-write view of the bounded vector Samples.counts.
+#[derive(Debug)]
+pub struct SamplesCountsMutView<'a> {
+    data: &'a mut [u8; 21],
+}
+
+impl<'a> SamplesCountsMutView<'a> {
+    /// The maximum number of elements.
+    pub const CAPACITY: usize = 5;
+
+    /// Returns a read-only view of the vector.
+    pub fn as_view(&self) -> SamplesCountsView<'_> {
+        SamplesCountsView { data: self.data }
+    }
+
+    /// Returns the number of elements, clamped to the capacity.
+    pub fn len(&self) -> usize {
+        self.as_view().len()
+    }
+
+    /// Returns true if the vector has no elements.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the element at `index`, or `None` if it is out of range.
+    pub fn get(&self, index: usize) -> Option<i32> {
+        self.as_view().get(index)
+    }
+
+    /// Returns an iterator over the elements.
+    pub fn iter(&self) -> impl Iterator<Item = i32> + '_ {
+        self.as_view().iter()
+    }
+
+    fn set_len(&mut self, len: usize) {
+        self.data[0] = len as u8;
+    }
+
+    /// Removes all elements.
+    pub fn clear(&mut self) {
+        self.set_len(0);
+    }
+
+    /// Replaces the element at `index`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is not less than `len()`.
+    pub fn set(&mut self, index: usize, value: i32) {
+        assert!(index < self.len(), "index {index} out of range");
+        self.data[1 + index * 4..1 + index * 4 + 4].copy_from_slice(&value.to_be_bytes());
+    }
+
+    /// Appends an element, or returns it back if the vector is full.
+    pub fn push(&mut self, value: i32) -> Result<(), i32> {
+        let index = self.len();
+        if index == Self::CAPACITY {
+            return Err(value);
+        }
+        self.set_len(index + 1);
+        self.set(index, value);
+        Ok(())
+    }
+}
+
+/// Read-only view of the bounded vector Samples.readings.
+#[derive(Debug, Clone, Copy)]
+pub struct SamplesReadingsView<'a> {
+    data: &'a [u8; 25],
+}
+
+impl<'a> SamplesReadingsView<'a> {
+    /// The maximum number of elements.
+    pub const CAPACITY: usize = 3;
+
+    /// Returns the number of elements, clamped to the capacity.
+    pub fn len(&self) -> usize {
+        (self.data[0] as usize).min(Self::CAPACITY)
+    }
+
+    /// Returns true if the vector has no elements.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the element at `index`, or `None` if it is out of range.
+    pub fn get(&self, index: usize) -> Option<f64> {
+        if index >= self.len() {
+            return None;
+        }
+        Some(f64::from_be_bytes(self.data[1 + index * 8..1 + index * 8 + 8].try_into().unwrap()))
+    }
+
+    /// Returns an iterator over the elements.
+    pub fn iter(&self) -> impl Iterator<Item = f64> + 'a {
+        let view = *self;
+        (0..self.len()).map(move |index| view.get(index).unwrap())
+    }
+}
+
+/// Read-write view of the bounded vector Samples.readings.
+#[derive(Debug)]
+pub struct SamplesReadingsMutView<'a> {
+    data: &'a mut [u8; 25],
+}
+
+impl<'a> SamplesReadingsMutView<'a> {
+    /// The maximum number of elements.
+    pub const CAPACITY: usize = 3;
+
+    /// Returns a read-only view of the vector.
+    pub fn as_view(&self) -> SamplesReadingsView<'_> {
+        SamplesReadingsView { data: self.data }
+    }
+
+    /// Returns the number of elements, clamped to the capacity.
+    pub fn len(&self) -> usize {
+        self.as_view().len()
+    }
+
+    /// Returns true if the vector has no elements.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the element at `index`, or `None` if it is out of range.
+    pub fn get(&self, index: usize) -> Option<f64> {
+        self.as_view().get(index)
+    }
+
+    /// Returns an iterator over the elements.
+    pub fn iter(&self) -> impl Iterator<Item = f64> + '_ {
+        self.as_view().iter()
+    }
+
+    fn set_len(&mut self, len: usize) {
+        self.data[0] = len as u8;
+    }
+
+    /// Removes all elements.
+    pub fn clear(&mut self) {
+        self.set_len(0);
+    }
+
+    /// Replaces the element at `index`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is not less than `len()`.
+    pub fn set(&mut self, index: usize, value: f64) {
+        assert!(index < self.len(), "index {index} out of range");
+        self.data[1 + index * 8..1 + index * 8 + 8].copy_from_slice(&value.to_be_bytes());
+    }
+
+    /// Appends an element, or returns it back if the vector is full.
+    pub fn push(&mut self, value: f64) -> Result<(), f64> {
+        let index = self.len();
+        if index == Self::CAPACITY {
+            return Err(value);
+        }
+        self.set_len(index + 1);
+        self.set(index, value);
+        Ok(())
+    }
+}
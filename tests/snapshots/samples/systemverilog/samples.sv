@@ -0,0 +1,32 @@
+// Automatically generated by Onyx IDL compiler. Do not edit.
+`ifndef ONYX_SAMPLES_PKG_SV
+`define ONYX_SAMPLES_PKG_SV
+
+package samples_pkg;
+
+  // Samples: 67 bytes.
+  localparam int Samples_SIZE_BYTES = 67;
+  localparam int Samples_id_OFFSET = 0;
+  localparam int Samples_id_WIDTH = 16;
+  localparam int Samples_levels_OFFSET = 2;
+  localparam int Samples_levels_WIDTH = 152;
+  localparam int Samples_levels_CAPACITY = 9;
+  localparam int Samples_counts_OFFSET = 21;
+  localparam int Samples_counts_WIDTH = 168;
+  localparam int Samples_counts_CAPACITY = 5;
+  localparam int Samples_readings_OFFSET = 42;
+  localparam int Samples_readings_WIDTH = 200;
+  localparam int Samples_readings_CAPACITY = 3;
+  typedef struct packed {
+    logic [15:0] id;
+    logic [7:0] levels_count;
+    logic [0:8][15:0] levels;
+    logic [7:0] counts_count;
+    logic [0:4][31:0] counts;
+    logic [7:0] readings_count;
+    logic [0:2][63:0] readings;
+  } Samples;
+
+endpackage
+
+`endif // ONYX_SAMPLES_PKG_SV
@@ -0,0 +1,220 @@
+// Automatically generated by Onyx IDL compiler. Do not edit.
+#include "versioned.hpp"
+
+#include <string.h>
+
+namespace onyx {
+
+Point* Point::Deserialize(Buffer& buffer) {
+    // Overlay the class structure onto the buffer memory. This is the zero-copy step.
+    Point* result = (Point*)buffer;
+
+    // SWAP: Primitive field x
+    result->__raw_x = utils::byteswap_if_needed(result->__raw_x);
+    // SWAP: Primitive field y
+    result->__raw_y = utils::byteswap_if_needed(result->__raw_y);
+    // SWAP: Primitive field z
+    result->__raw_z = utils::byteswap_if_needed(result->__raw_z);
+
+    return result;
+}
+
+void Point::Serialize(Buffer& buffer) const {
+    // Copy the host-endian object into the buffer first.
+    memcpy(buffer, this, kSizeOf);
+    Point* wire_format_data = (Point*)buffer;
+
+    // SWAP: Primitive field x
+    wire_format_data->__raw_x = utils::byteswap_if_needed(wire_format_data->__raw_x);
+    // SWAP: Primitive field y
+    wire_format_data->__raw_y = utils::byteswap_if_needed(wire_format_data->__raw_y);
+    // SWAP: Primitive field z
+    wire_format_data->__raw_z = utils::byteswap_if_needed(wire_format_data->__raw_z);
+}
+
+Sample* Sample::Deserialize(Buffer& buffer) {
+    // Overlay the class structure onto the buffer memory. This is the zero-copy step.
+    Sample* result = (Sample*)buffer;
+
+    // INFO: Bit-field container flag (1 byte), no swap needed.
+    Point::Deserialize(*(Point::Buffer*) &result->__raw_origin);
+    // SWAP: Vector field path
+    for (size_t i = 0; i < 2; ++i) {
+        Point::Deserialize(*(Point::Buffer*) &result->__raw_path.__items[i]);
+    }
+    // SWAP: Primitive field id
+    result->__raw_id = utils::byteswap_if_needed(result->__raw_id);
+
+    return result;
+}
+
+void Sample::Serialize(Buffer& buffer) const {
+    // Copy the host-endian object into the buffer first.
+    memcpy(buffer, this, kSizeOf);
+    Sample* wire_format_data = (Sample*)buffer;
+
+    // INFO: Bit-field container flag (1 byte), no swap needed.
+    wire_format_data->__raw_origin.Serialize(*(Point::Buffer*) &wire_format_data->__raw_origin);
+    // SWAP: Vector field path
+    for (size_t i = 0; i < 2; ++i) {
+        wire_format_data->__raw_path.__items[i].Serialize(*(Point::Buffer*) &wire_format_data->__raw_path.__items[i]);
+    }
+    // SWAP: Primitive field id
+    wire_format_data->__raw_id = utils::byteswap_if_needed(wire_format_data->__raw_id);
+}
+
+namespace v1 {
+
+Point* Point::Deserialize(Buffer& buffer) {
+    // Overlay the class structure onto the buffer memory. This is the zero-copy step.
+    Point* result = (Point*)buffer;
+
+    // SWAP: Primitive field x
+    result->__raw_x = utils::byteswap_if_needed(result->__raw_x);
+    // SWAP: Primitive field y
+    result->__raw_y = utils::byteswap_if_needed(result->__raw_y);
+
+    return result;
+}
+
+void Point::Serialize(Buffer& buffer) const {
+    // Copy the host-endian object into the buffer first.
+    memcpy(buffer, this, kSizeOf);
+    Point* wire_format_data = (Point*)buffer;
+
+    // SWAP: Primitive field x
+    wire_format_data->__raw_x = utils::byteswap_if_needed(wire_format_data->__raw_x);
+    // SWAP: Primitive field y
+    wire_format_data->__raw_y = utils::byteswap_if_needed(wire_format_data->__raw_y);
+}
+
+void Point::Upgrade(::onyx::Point& out) const {
+    memset(static_cast<void*>(&out), 0, sizeof(out));
+    out.x(__raw_x);
+    out.y(__raw_y);
+}
+
+Sample* Sample::Deserialize(Buffer& buffer) {
+    // Overlay the class structure onto the buffer memory. This is the zero-copy step.
+    Sample* result = (Sample*)buffer;
+
+    // SWAP: Primitive field legacy
+    result->__raw_legacy = utils::byteswap_if_needed(result->__raw_legacy);
+    // INFO: Bit-field container flag (1 byte), no swap needed.
+    Point::Deserialize(*(Point::Buffer*) &result->__raw_origin);
+    // SWAP: Vector field path
+    for (size_t i = 0; i < 2; ++i) {
+        Point::Deserialize(*(Point::Buffer*) &result->__raw_path.__items[i]);
+    }
+
+    return result;
+}
+
+void Sample::Serialize(Buffer& buffer) const {
+    // Copy the host-endian object into the buffer first.
+    memcpy(buffer, this, kSizeOf);
+    Sample* wire_format_data = (Sample*)buffer;
+
+    // SWAP: Primitive field legacy
+    wire_format_data->__raw_legacy = utils::byteswap_if_needed(wire_format_data->__raw_legacy);
+    // INFO: Bit-field container flag (1 byte), no swap needed.
+    wire_format_data->__raw_origin.Serialize(*(Point::Buffer*) &wire_format_data->__raw_origin);
+    // SWAP: Vector field path
+    for (size_t i = 0; i < 2; ++i) {
+        wire_format_data->__raw_path.__items[i].Serialize(*(Point::Buffer*) &wire_format_data->__raw_path.__items[i]);
+    }
+}
+
+void Sample::Upgrade(::onyx::Sample& out) const {
+    memset(static_cast<void*>(&out), 0, sizeof(out));
+    out.flag(flag());
+    __raw_origin.Upgrade(out.origin());
+    out.path().__length = __raw_path.__length;
+    for (size_t i = 0; i < 2; ++i) {
+        __raw_path.__items[i].Upgrade(out.path().__items[i]);
+    }
+}
+
+} // namespace v1
+
+namespace v2 {
+
+Point* Point::Deserialize(Buffer& buffer) {
+    // Overlay the class structure onto the buffer memory. This is the zero-copy step.
+    Point* result = (Point*)buffer;
+
+    // SWAP: Primitive field x
+    result->__raw_x = utils::byteswap_if_needed(result->__raw_x);
+    // SWAP: Primitive field y
+    result->__raw_y = utils::byteswap_if_needed(result->__raw_y);
+
+    return result;
+}
+
+void Point::Serialize(Buffer& buffer) const {
+    // Copy the host-endian object into the buffer first.
+    memcpy(buffer, this, kSizeOf);
+    Point* wire_format_data = (Point*)buffer;
+
+    // SWAP: Primitive field x
+    wire_format_data->__raw_x = utils::byteswap_if_needed(wire_format_data->__raw_x);
+    // SWAP: Primitive field y
+    wire_format_data->__raw_y = utils::byteswap_if_needed(wire_format_data->__raw_y);
+}
+
+void Point::Upgrade(::onyx::Point& out) const {
+    memset(static_cast<void*>(&out), 0, sizeof(out));
+    out.x(__raw_x);
+    out.y(__raw_y);
+}
+
+Sample* Sample::Deserialize(Buffer& buffer) {
+    // Overlay the class structure onto the buffer memory. This is the zero-copy step.
+    Sample* result = (Sample*)buffer;
+
+    // SWAP: Primitive field legacy
+    result->__raw_legacy = utils::byteswap_if_needed(result->__raw_legacy);
+    // INFO: Bit-field container flag (1 byte), no swap needed.
+    Point::Deserialize(*(Point::Buffer*) &result->__raw_origin);
+    // SWAP: Vector field path
+    for (size_t i = 0; i < 2; ++i) {
+        Point::Deserialize(*(Point::Buffer*) &result->__raw_path.__items[i]);
+    }
+    // SWAP: Primitive field id
+    result->__raw_id = utils::byteswap_if_needed(result->__raw_id);
+
+    return result;
+}
+
+void Sample::Serialize(Buffer& buffer) const {
+    // Copy the host-endian object into the buffer first.
+    memcpy(buffer, this, kSizeOf);
+    Sample* wire_format_data = (Sample*)buffer;
+
+    // SWAP: Primitive field legacy
+    wire_format_data->__raw_legacy = utils::byteswap_if_needed(wire_format_data->__raw_legacy);
+    // INFO: Bit-field container flag (1 byte), no swap needed.
+    wire_format_data->__raw_origin.Serialize(*(Point::Buffer*) &wire_format_data->__raw_origin);
+    // SWAP: Vector field path
+    for (size_t i = 0; i < 2; ++i) {
+        wire_format_data->__raw_path.__items[i].Serialize(*(Point::Buffer*) &wire_format_data->__raw_path.__items[i]);
+    }
+    // SWAP: Primitive field id
+    wire_format_data->__raw_id = utils::byteswap_if_needed(wire_format_data->__raw_id);
+}
+
+void Sample::Upgrade(::onyx::Sample& out) const {
+    memset(static_cast<void*>(&out), 0, sizeof(out));
+    out.flag(flag());
+    out.level(level());
+    __raw_origin.Upgrade(out.origin());
+    out.path().__length = __raw_path.__length;
+    for (size_t i = 0; i < 2; ++i) {
+        __raw_path.__items[i].Upgrade(out.path().__items[i]);
+    }
+    out.id(__raw_id);
+}
+
+} // namespace v2
+
+} // namespace onyx
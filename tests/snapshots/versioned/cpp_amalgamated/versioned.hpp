@@ -0,0 +1,791 @@
+// Automatically generated by Onyx IDL compiler. Do not edit.
+#ifndef ONYX_VERSIONED_H_
+#define ONYX_VERSIONED_H_
+
+#include <stddef.h>
+#include <stdint.h>
+#include <string.h>
+
+#pragma pack(push, 1)
+
+namespace onyx {
+#ifndef ONYX_UTILS_ENDIANNESS_LITTLE_
+#define ONYX_UTILS_ENDIANNESS_LITTLE_
+
+namespace utils {
+
+// Define network endianness (usually Big Endian).
+#define ONYX_BIG_ENDIAN 4321
+#define ONYX_LITTLE_ENDIAN 1234
+#define ONYX_NETWORK_ORDER ONYX_LITTLE_ENDIAN
+
+// Determine host endianness at compile time (using standard GCC/Clang macros)
+#ifdef __BYTE_ORDER__
+    #if __BYTE_ORDER__ == __ORDER_BIG_ENDIAN__
+        #define ONYX_HOST_ORDER ONYX_BIG_ENDIAN
+    #elif __BYTE_ORDER__ == __ORDER_LITTLE_ENDIAN__
+        #define ONYX_HOST_ORDER ONYX_LITTLE_ENDIAN
+    #else
+        #error "Unsupported host endianness"
+    #endif
+#elif defined(_WIN32) || defined(__LITTLE_ENDIAN__)
+    // Fallback for Windows (always little-endian) or other platforms
+    #define ONYX_HOST_ORDER ONYX_LITTLE_ENDIAN
+#else
+    #error "Could not determine host endianness"
+#endif
+
+// Helper function to swap bytes (Explicit Overloads)
+inline uint16_t byteswap(uint16_t value) { return ((value << 8) & 0xFF00) | ((value >> 8) & 0x00FF); }
+
+inline int16_t byteswap(int16_t value) { return (int16_t)byteswap((uint16_t)value); }
+
+inline uint32_t byteswap(uint32_t value) { return (value << 24) | ((value & 0x00FF0000) >> 8) | ((value & 0x0000FF00) << 8) | (value >> 24); }
+
+inline int32_t byteswap(int32_t value) { return (int32_t)byteswap((uint32_t)value); }
+
+inline uint64_t byteswap(uint64_t value) { return (value << 56) | ((value & 0x00FF000000000000ULL) >> 40) | ((value & 0x0000FF0000000000ULL) >> 24) | ((value & 0x000000FF00000000ULL) >> 8) | ((value & 0x00000000FF000000ULL) << 8) | ((value & 0x0000000000FF0000ULL) << 24) | ((value & 0x000000000000FF00ULL) << 40) | (value >> 56); }
+
+inline int64_t byteswap(int64_t value) { return (int64_t)byteswap((uint64_t)value); }
+
+inline float byteswap(float value) {
+    uint32_t temp;
+    memcpy(&temp, &value, sizeof(value));
+    temp = byteswap(temp);
+    memcpy(&value, &temp, sizeof(value));
+    return value;
+}
+
+inline double byteswap(double value) {
+    uint64_t temp;
+    memcpy(&temp, &value, sizeof(value));
+    temp = byteswap(temp);
+    memcpy(&value, &temp, sizeof(value));
+    return value;
+}
+
+// Swaps bytes if the host order does not match the network order (Explicit Overloads)
+inline uint16_t byteswap_if_needed(uint16_t value) {
+    if (ONYX_HOST_ORDER != ONYX_NETWORK_ORDER) {
+        return byteswap(value);
+    }
+    return value;
+}
+
+inline int16_t byteswap_if_needed(int16_t value) {
+    if (ONYX_HOST_ORDER != ONYX_NETWORK_ORDER) {
+        return byteswap(value);
+    }
+    return value;
+}
+
+inline uint32_t byteswap_if_needed(uint32_t value) {
+    if (ONYX_HOST_ORDER != ONYX_NETWORK_ORDER) {
+        return byteswap(value);
+    }
+    return value;
+}
+
+inline int32_t byteswap_if_needed(int32_t value) {
+    if (ONYX_HOST_ORDER != ONYX_NETWORK_ORDER) {
+        return byteswap(value);
+    }
+    return value;
+}
+
+inline uint64_t byteswap_if_needed(uint64_t value) {
+    if (ONYX_HOST_ORDER != ONYX_NETWORK_ORDER) {
+        return byteswap(value);
+    }
+    return value;
+}
+
+inline int64_t byteswap_if_needed(int64_t value) {
+    if (ONYX_HOST_ORDER != ONYX_NETWORK_ORDER) {
+        return byteswap(value);
+    }
+    return value;
+}
+
+inline float byteswap_if_needed(float value) {
+    if (ONYX_HOST_ORDER != ONYX_NETWORK_ORDER) {
+        return byteswap(value);
+    }
+    return value;
+}
+
+inline double byteswap_if_needed(double value) {
+    if (ONYX_HOST_ORDER != ONYX_NETWORK_ORDER) {
+        return byteswap(value);
+    }
+    return value;
+}
+
+} // namespace utils
+#endif // ONYX_UTILS_ENDIANNESS_LITTLE_
+
+#ifndef ONYX_UTILS_BOUNDED_VEC_
+#define ONYX_UTILS_BOUNDED_VEC_
+
+namespace utils {
+
+/// A bounded vector: a length followed by room for N elements, all of them inline.
+template <typename T, typename LenT, size_t N>
+class BoundedVec {
+public:
+    LenT __length;
+    T __items[N];
+
+    /// Returns the number of elements, clamped to the capacity.
+    inline size_t len() const { return __length < N ? __length : N; }
+    /// Returns the maximum number of elements.
+    static inline size_t capacity() { return N; }
+    /// Returns true if the vector has no elements.
+    inline bool empty() const { return len() == 0; }
+    /// Returns the element at `i`, which must be less than len().
+    inline const T& get(size_t i) const { return __items[i]; }
+    inline T& get(size_t i) { return __items[i]; }
+    /// Replaces the element at `i`, which must be less than len().
+    inline void set(size_t i, const T& value) { __items[i] = value; }
+    /// Appends an element. Returns false, leaving the vector unchanged, if it is full.
+    inline bool push(const T& value) {
+        size_t n = len();
+        if (n == N) return false;
+        __items[n] = value;
+        __length = static_cast<LenT>(n + 1);
+        return true;
+    }
+    /// Removes all elements.
+    inline void clear() { __length = 0; }
+
+    inline const T* begin() const { return __items; }
+    inline const T* end() const { return __items + len(); }
+    inline T* begin() { return __items; }
+    inline T* end() { return __items + len(); }
+};
+
+} // namespace utils
+
+#endif // ONYX_UTILS_BOUNDED_VEC_
+
+class Point;
+class Sample;
+
+class Point {
+private:
+    int32_t __raw_x;
+    int32_t __raw_y;
+    int32_t __raw_z;
+
+public:
+    static const size_t kSizeOf = 12;
+    using Buffer = uint8_t[kSizeOf];
+
+    /// Byte offsets of the fields within the buffer.
+    static const size_t kXOffset = 0;
+    static const size_t kYOffset = 4;
+    static const size_t kZOffset = 8;
+
+    /// Accessor for x
+    inline const int32_t x() const { return __raw_x; }
+
+    /// Mutator for x
+    inline void x(const int32_t value) { __raw_x = value; }
+
+    /// Accessor for y
+    inline const int32_t y() const { return __raw_y; }
+
+    /// Mutator for y
+    inline void y(const int32_t value) { __raw_y = value; }
+
+    /// Accessor for z
+    inline const int32_t z() const { return __raw_z; }
+
+    /// Mutator for z
+    inline void z(const int32_t value) { __raw_z = value; }
+
+    /// Deserializes the network-endian buffer to Point in-place by casting
+    /// and applying endianness correction to match the host-endianness.
+    /// NOTE: This mutates the input buffer to host-endianness.
+    /// Returns a pointer to the object within the buffer.
+    static Point* Deserialize(Buffer& buffer);
+
+    /// Serializes the host-endian object into the provided buffer,
+    /// applying endianness correction to match the network-endianness.
+    void Serialize(Buffer& buffer) const;
+
+private:
+    /// Fails to compile if Point does not match its Onyx layout.
+    static inline void __check_layout() {
+        static_assert(sizeof(Point) == kSizeOf, "Point does not match its Onyx size");
+        static_assert(offsetof(Point, __raw_x) == kXOffset, "Point::x does not match its Onyx offset");
+        static_assert(offsetof(Point, __raw_y) == kYOffset, "Point::y does not match its Onyx offset");
+        static_assert(offsetof(Point, __raw_z) == kZOffset, "Point::z does not match its Onyx offset");
+    }
+};
+
+class Sample {
+private:
+    uint8_t __raw_flag[1];
+    Point __raw_origin;
+    utils::BoundedVec<Point, uint8_t, 2> __raw_path;
+    uint32_t __raw_id;
+
+public:
+    static const size_t kSizeOf = 42;
+    using Buffer = uint8_t[kSizeOf];
+
+    /// Byte offsets of the fields within the buffer.
+    static const size_t kOriginOffset = 1;
+    static const size_t kPathOffset = 13;
+    static const size_t kIdOffset = 38;
+
+    /// Accessor for flag
+    inline const bool flag() const {
+        auto raw_value = reinterpret_cast<const uint8_t*>(&__raw_flag[0]);
+        return static_cast<const bool>(((*raw_value >> 0) & 0x1));
+    }
+
+    /// Mutator for flag
+    inline void flag(bool value) {
+        auto raw_container = reinterpret_cast<uint8_t*>(&__raw_flag[0]);
+        *raw_container &= ~(((uint8_t)0x1) << 0);
+        *raw_container |= ((((uint8_t)value) & 0x1) << 0);
+    }
+
+    /// Accessor for level
+    inline const uint8_t level() const {
+        auto raw_value = reinterpret_cast<const uint8_t*>(&__raw_flag[0]);
+        return static_cast<const uint8_t>(((*raw_value >> 1) & 0xF));
+    }
+
+    /// Mutator for level
+    inline void level(uint8_t value) {
+        auto raw_container = reinterpret_cast<uint8_t*>(&__raw_flag[0]);
+        *raw_container &= ~(((uint8_t)0xF) << 1);
+        *raw_container |= ((((uint8_t)value) & 0xF) << 1);
+    }
+
+    /// Accessor for origin
+    inline Point& origin() { return __raw_origin; }
+
+    /// Mutator for origin
+    inline void origin(const Point value) { __raw_origin = value; }
+
+    /// Accessor for path
+    inline utils::BoundedVec<Point, uint8_t, 2>& path() { return __raw_path; }
+
+    /// Mutator for path
+    inline void path(const utils::BoundedVec<Point, uint8_t, 2> value) { __raw_path = value; }
+
+    /// Accessor for id
+    inline const uint32_t id() const { return __raw_id; }
+
+    /// Mutator for id
+    inline void id(const uint32_t value) { __raw_id = value; }
+
+    /// Deserializes the network-endian buffer to Sample in-place by casting
+    /// and applying endianness correction to match the host-endianness.
+    /// NOTE: This mutates the input buffer to host-endianness.
+    /// Returns a pointer to the object within the buffer.
+    static Sample* Deserialize(Buffer& buffer);
+
+    /// Serializes the host-endian object into the provided buffer,
+    /// applying endianness correction to match the network-endianness.
+    void Serialize(Buffer& buffer) const;
+
+private:
+    /// Fails to compile if Sample does not match its Onyx layout.
+    static inline void __check_layout() {
+        static_assert(sizeof(Sample) == kSizeOf, "Sample does not match its Onyx size");
+        static_assert(offsetof(Sample, __raw_origin) == kOriginOffset, "Sample::origin does not match its Onyx offset");
+        static_assert(offsetof(Sample, __raw_path) == kPathOffset, "Sample::path does not match its Onyx offset");
+        static_assert(offsetof(Sample, __raw_id) == kIdOffset, "Sample::id does not match its Onyx offset");
+    }
+};
+
+namespace v1 {
+
+class Point {
+private:
+    int32_t __raw_x;
+    int32_t __raw_y;
+
+public:
+    static const size_t kSizeOf = 8;
+    using Buffer = uint8_t[kSizeOf];
+
+    /// Byte offsets of the fields within the buffer.
+    static const size_t kXOffset = 0;
+    static const size_t kYOffset = 4;
+
+    /// Accessor for x
+    inline const int32_t x() const { return __raw_x; }
+
+    /// Mutator for x
+    inline void x(const int32_t value) { __raw_x = value; }
+
+    /// Accessor for y
+    inline const int32_t y() const { return __raw_y; }
+
+    /// Mutator for y
+    inline void y(const int32_t value) { __raw_y = value; }
+
+    /// Deserializes the network-endian buffer to Point in-place by casting
+    /// and applying endianness correction to match the host-endianness.
+    /// NOTE: This mutates the input buffer to host-endianness.
+    /// Returns a pointer to the object within the buffer.
+    static Point* Deserialize(Buffer& buffer);
+
+    /// Serializes the host-endian object into the provided buffer,
+    /// applying endianness correction to match the network-endianness.
+    void Serialize(Buffer& buffer) const;
+
+    /// Converts the host-endian object to the current version of Point.
+    /// Fields added since are zeroed.
+    void Upgrade(::onyx::Point& out) const;
+
+private:
+    /// Fails to compile if Point does not match its Onyx layout.
+    static inline void __check_layout() {
+        static_assert(sizeof(Point) == kSizeOf, "Point does not match its Onyx size");
+        static_assert(offsetof(Point, __raw_x) == kXOffset, "Point::x does not match its Onyx offset");
+        static_assert(offsetof(Point, __raw_y) == kYOffset, "Point::y does not match its Onyx offset");
+    }
+};
+
+class Sample {
+private:
+    uint16_t __raw_legacy;
+    uint8_t __raw_flag[1];
+    Point __raw_origin;
+    utils::BoundedVec<Point, uint8_t, 2> __raw_path;
+
+public:
+    static const size_t kSizeOf = 28;
+    using Buffer = uint8_t[kSizeOf];
+
+    /// Byte offsets of the fields within the buffer.
+    static const size_t kLegacyOffset = 0;
+    static const size_t kOriginOffset = 3;
+    static const size_t kPathOffset = 11;
+
+    /// Accessor for legacy
+    inline const uint16_t legacy() const { return __raw_legacy; }
+
+    /// Mutator for legacy
+    inline void legacy(const uint16_t value) { __raw_legacy = value; }
+
+    /// Accessor for flag
+    inline const bool flag() const {
+        auto raw_value = reinterpret_cast<const uint8_t*>(&__raw_flag[0]);
+        return static_cast<const bool>(((*raw_value >> 0) & 0x1));
+    }
+
+    /// Mutator for flag
+    inline void flag(bool value) {
+        auto raw_container = reinterpret_cast<uint8_t*>(&__raw_flag[0]);
+        *raw_container &= ~(((uint8_t)0x1) << 0);
+        *raw_container |= ((((uint8_t)value) & 0x1) << 0);
+    }
+
+    /// Accessor for origin
+    inline Point& origin() { return __raw_origin; }
+
+    /// Mutator for origin
+    inline void origin(const Point value) { __raw_origin = value; }
+
+    /// Accessor for path
+    inline utils::BoundedVec<Point, uint8_t, 2>& path() { return __raw_path; }
+
+    /// Mutator for path
+    inline void path(const utils::BoundedVec<Point, uint8_t, 2> value) { __raw_path = value; }
+
+    /// Deserializes the network-endian buffer to Sample in-place by casting
+    /// and applying endianness correction to match the host-endianness.
+    /// NOTE: This mutates the input buffer to host-endianness.
+    /// Returns a pointer to the object within the buffer.
+    static Sample* Deserialize(Buffer& buffer);
+
+    /// Serializes the host-endian object into the provided buffer,
+    /// applying endianness correction to match the network-endianness.
+    void Serialize(Buffer& buffer) const;
+
+    /// Converts the host-endian object to the current version of Sample.
+    /// Fields added since are zeroed.
+    void Upgrade(::onyx::Sample& out) const;
+
+private:
+    /// Fails to compile if Sample does not match its Onyx layout.
+    static inline void __check_layout() {
+        static_assert(sizeof(Sample) == kSizeOf, "Sample does not match its Onyx size");
+        static_assert(offsetof(Sample, __raw_legacy) == kLegacyOffset, "Sample::legacy does not match its Onyx offset");
+        static_assert(offsetof(Sample, __raw_origin) == kOriginOffset, "Sample::origin does not match its Onyx offset");
+        static_assert(offsetof(Sample, __raw_path) == kPathOffset, "Sample::path does not match its Onyx offset");
+    }
+};
+
+} // namespace v1
+
+namespace v2 {
+
+class Point {
+private:
+    int32_t __raw_x;
+    int32_t __raw_y;
+
+public:
+    static const size_t kSizeOf = 8;
+    using Buffer = uint8_t[kSizeOf];
+
+    /// Byte offsets of the fields within the buffer.
+    static const size_t kXOffset = 0;
+    static const size_t kYOffset = 4;
+
+    /// Accessor for x
+    inline const int32_t x() const { return __raw_x; }
+
+    /// Mutator for x
+    inline void x(const int32_t value) { __raw_x = value; }
+
+    /// Accessor for y
+    inline const int32_t y() const { return __raw_y; }
+
+    /// Mutator for y
+    inline void y(const int32_t value) { __raw_y = value; }
+
+    /// Deserializes the network-endian buffer to Point in-place by casting
+    /// and applying endianness correction to match the host-endianness.
+    /// NOTE: This mutates the input buffer to host-endianness.
+    /// Returns a pointer to the object within the buffer.
+    static Point* Deserialize(Buffer& buffer);
+
+    /// Serializes the host-endian object into the provided buffer,
+    /// applying endianness correction to match the network-endianness.
+    void Serialize(Buffer& buffer) const;
+
+    /// Converts the host-endian object to the current version of Point.
+    /// Fields added since are zeroed.
+    void Upgrade(::onyx::Point& out) const;
+
+private:
+    /// Fails to compile if Point does not match its Onyx layout.
+    static inline void __check_layout() {
+        static_assert(sizeof(Point) == kSizeOf, "Point does not match its Onyx size");
+        static_assert(offsetof(Point, __raw_x) == kXOffset, "Point::x does not match its Onyx offset");
+        static_assert(offsetof(Point, __raw_y) == kYOffset, "Point::y does not match its Onyx offset");
+    }
+};
+
+class Sample {
+private:
+    uint16_t __raw_legacy;
+    uint8_t __raw_flag[1];
+    Point __raw_origin;
+    utils::BoundedVec<Point, uint8_t, 2> __raw_path;
+    uint32_t __raw_id;
+
+public:
+    static const size_t kSizeOf = 32;
+    using Buffer = uint8_t[kSizeOf];
+
+    /// Byte offsets of the fields within the buffer.
+    static const size_t kLegacyOffset = 0;
+    static const size_t kOriginOffset = 3;
+    static const size_t kPathOffset = 11;
+    static const size_t kIdOffset = 28;
+
+    /// Accessor for legacy
+    inline const uint16_t legacy() const { return __raw_legacy; }
+
+    /// Mutator for legacy
+    inline void legacy(const uint16_t value) { __raw_legacy = value; }
+
+    /// Accessor for flag
+    inline const bool flag() const {
+        auto raw_value = reinterpret_cast<const uint8_t*>(&__raw_flag[0]);
+        return static_cast<const bool>(((*raw_value >> 0) & 0x1));
+    }
+
+    /// Mutator for flag
+    inline void flag(bool value) {
+        auto raw_container = reinterpret_cast<uint8_t*>(&__raw_flag[0]);
+        *raw_container &= ~(((uint8_t)0x1) << 0);
+        *raw_container |= ((((uint8_t)value) & 0x1) << 0);
+    }
+
+    /// Accessor for level
+    inline const uint8_t level() const {
+        auto raw_value = reinterpret_cast<const uint8_t*>(&__raw_flag[0]);
+        return static_cast<const uint8_t>(((*raw_value >> 1) & 0xF));
+    }
+
+    /// Mutator for level
+    inline void level(uint8_t value) {
+        auto raw_container = reinterpret_cast<uint8_t*>(&__raw_flag[0]);
+        *raw_container &= ~(((uint8_t)0xF) << 1);
+        *raw_container |= ((((uint8_t)value) & 0xF) << 1);
+    }
+
+    /// Accessor for origin
+    inline Point& origin() { return __raw_origin; }
+
+    /// Mutator for origin
+    inline void origin(const Point value) { __raw_origin = value; }
+
+    /// Accessor for path
+    inline utils::BoundedVec<Point, uint8_t, 2>& path() { return __raw_path; }
+
+    /// Mutator for path
+    inline void path(const utils::BoundedVec<Point, uint8_t, 2> value) { __raw_path = value; }
+
+    /// Accessor for id
+    inline const uint32_t id() const { return __raw_id; }
+
+    /// Mutator for id
+    inline void id(const uint32_t value) { __raw_id = value; }
+
+    /// Deserializes the network-endian buffer to Sample in-place by casting
+    /// and applying endianness correction to match the host-endianness.
+    /// NOTE: This mutates the input buffer to host-endianness.
+    /// Returns a pointer to the object within the buffer.
+    static Sample* Deserialize(Buffer& buffer);
+
+    /// Serializes the host-endian object into the provided buffer,
+    /// applying endianness correction to match the network-endianness.
+    void Serialize(Buffer& buffer) const;
+
+    /// Converts the host-endian object to the current version of Sample.
+    /// Fields added since are zeroed.
+    void Upgrade(::onyx::Sample& out) const;
+
+private:
+    /// Fails to compile if Sample does not match its Onyx layout.
+    static inline void __check_layout() {
+        static_assert(sizeof(Sample) == kSizeOf, "Sample does not match its Onyx size");
+        static_assert(offsetof(Sample, __raw_legacy) == kLegacyOffset, "Sample::legacy does not match its Onyx offset");
+        static_assert(offsetof(Sample, __raw_origin) == kOriginOffset, "Sample::origin does not match its Onyx offset");
+        static_assert(offsetof(Sample, __raw_path) == kPathOffset, "Sample::path does not match its Onyx offset");
+        static_assert(offsetof(Sample, __raw_id) == kIdOffset, "Sample::id does not match its Onyx offset");
+    }
+};
+
+} // namespace v2
+
+
+inline Point* Point::Deserialize(Buffer& buffer) {
+    // Overlay the class structure onto the buffer memory. This is the zero-copy step.
+    Point* result = (Point*)buffer;
+
+    // SWAP: Primitive field x
+    result->__raw_x = utils::byteswap_if_needed(result->__raw_x);
+    // SWAP: Primitive field y
+    result->__raw_y = utils::byteswap_if_needed(result->__raw_y);
+    // SWAP: Primitive field z
+    result->__raw_z = utils::byteswap_if_needed(result->__raw_z);
+
+    return result;
+}
+
+inline void Point::Serialize(Buffer& buffer) const {
+    // Copy the host-endian object into the buffer first.
+    memcpy(buffer, this, kSizeOf);
+    Point* wire_format_data = (Point*)buffer;
+
+    // SWAP: Primitive field x
+    wire_format_data->__raw_x = utils::byteswap_if_needed(wire_format_data->__raw_x);
+    // SWAP: Primitive field y
+    wire_format_data->__raw_y = utils::byteswap_if_needed(wire_format_data->__raw_y);
+    // SWAP: Primitive field z
+    wire_format_data->__raw_z = utils::byteswap_if_needed(wire_format_data->__raw_z);
+}
+
+inline Sample* Sample::Deserialize(Buffer& buffer) {
+    // Overlay the class structure onto the buffer memory. This is the zero-copy step.
+    Sample* result = (Sample*)buffer;
+
+    // INFO: Bit-field container flag (1 byte), no swap needed.
+    Point::Deserialize(*(Point::Buffer*) &result->__raw_origin);
+    // SWAP: Vector field path
+    for (size_t i = 0; i < 2; ++i) {
+        Point::Deserialize(*(Point::Buffer*) &result->__raw_path.__items[i]);
+    }
+    // SWAP: Primitive field id
+    result->__raw_id = utils::byteswap_if_needed(result->__raw_id);
+
+    return result;
+}
+
+inline void Sample::Serialize(Buffer& buffer) const {
+    // Copy the host-endian object into the buffer first.
+    memcpy(buffer, this, kSizeOf);
+    Sample* wire_format_data = (Sample*)buffer;
+
+    // INFO: Bit-field container flag (1 byte), no swap needed.
+    wire_format_data->__raw_origin.Serialize(*(Point::Buffer*) &wire_format_data->__raw_origin);
+    // SWAP: Vector field path
+    for (size_t i = 0; i < 2; ++i) {
+        wire_format_data->__raw_path.__items[i].Serialize(*(Point::Buffer*) &wire_format_data->__raw_path.__items[i]);
+    }
+    // SWAP: Primitive field id
+    wire_format_data->__raw_id = utils::byteswap_if_needed(wire_format_data->__raw_id);
+}
+
+namespace v1 {
+
+inline Point* Point::Deserialize(Buffer& buffer) {
+    // Overlay the class structure onto the buffer memory. This is the zero-copy step.
+    Point* result = (Point*)buffer;
+
+    // SWAP: Primitive field x
+    result->__raw_x = utils::byteswap_if_needed(result->__raw_x);
+    // SWAP: Primitive field y
+    result->__raw_y = utils::byteswap_if_needed(result->__raw_y);
+
+    return result;
+}
+
+inline void Point::Serialize(Buffer& buffer) const {
+    // Copy the host-endian object into the buffer first.
+    memcpy(buffer, this, kSizeOf);
+    Point* wire_format_data = (Point*)buffer;
+
+    // SWAP: Primitive field x
+    wire_format_data->__raw_x = utils::byteswap_if_needed(wire_format_data->__raw_x);
+    // SWAP: Primitive field y
+    wire_format_data->__raw_y = utils::byteswap_if_needed(wire_format_data->__raw_y);
+}
+
+inline void Point::Upgrade(::onyx::Point& out) const {
+    memset(static_cast<void*>(&out), 0, sizeof(out));
+    out.x(__raw_x);
+    out.y(__raw_y);
+}
+
+inline Sample* Sample::Deserialize(Buffer& buffer) {
+    // Overlay the class structure onto the buffer memory. This is the zero-copy step.
+    Sample* result = (Sample*)buffer;
+
+    // SWAP: Primitive field legacy
+    result->__raw_legacy = utils::byteswap_if_needed(result->__raw_legacy);
+    // INFO: Bit-field container flag (1 byte), no swap needed.
+    Point::Deserialize(*(Point::Buffer*) &result->__raw_origin);
+    // SWAP: Vector field path
+    for (size_t i = 0; i < 2; ++i) {
+        Point::Deserialize(*(Point::Buffer*) &result->__raw_path.__items[i]);
+    }
+
+    return result;
+}
+
+inline void Sample::Serialize(Buffer& buffer) const {
+    // Copy the host-endian object into the buffer first.
+    memcpy(buffer, this, kSizeOf);
+    Sample* wire_format_data = (Sample*)buffer;
+
+    // SWAP: Primitive field legacy
+    wire_format_data->__raw_legacy = utils::byteswap_if_needed(wire_format_data->__raw_legacy);
+    // INFO: Bit-field container flag (1 byte), no swap needed.
+    wire_format_data->__raw_origin.Serialize(*(Point::Buffer*) &wire_format_data->__raw_origin);
+    // SWAP: Vector field path
+    for (size_t i = 0; i < 2; ++i) {
+        wire_format_data->__raw_path.__items[i].Serialize(*(Point::Buffer*) &wire_format_data->__raw_path.__items[i]);
+    }
+}
+
+inline void Sample::Upgrade(::onyx::Sample& out) const {
+    memset(static_cast<void*>(&out), 0, sizeof(out));
+    out.flag(flag());
+    __raw_origin.Upgrade(out.origin());
+    out.path().__length = __raw_path.__length;
+    for (size_t i = 0; i < 2; ++i) {
+        __raw_path.__items[i].Upgrade(out.path().__items[i]);
+    }
+}
+
+} // namespace v1
+
+namespace v2 {
+
+inline Point* Point::Deserialize(Buffer& buffer) {
+    // Overlay the class structure onto the buffer memory. This is the zero-copy step.
+    Point* result = (Point*)buffer;
+
+    // SWAP: Primitive field x
+    result->__raw_x = utils::byteswap_if_needed(result->__raw_x);
+    // SWAP: Primitive field y
+    result->__raw_y = utils::byteswap_if_needed(result->__raw_y);
+
+    return result;
+}
+
+inline void Point::Serialize(Buffer& buffer) const {
+    // Copy the host-endian object into the buffer first.
+    memcpy(buffer, this, kSizeOf);
+    Point* wire_format_data = (Point*)buffer;
+
+    // SWAP: Primitive field x
+    wire_format_data->__raw_x = utils::byteswap_if_needed(wire_format_data->__raw_x);
+    // SWAP: Primitive field y
+    wire_format_data->__raw_y = utils::byteswap_if_needed(wire_format_data->__raw_y);
+}
+
+inline void Point::Upgrade(::onyx::Point& out) const {
+    memset(static_cast<void*>(&out), 0, sizeof(out));
+    out.x(__raw_x);
+    out.y(__raw_y);
+}
+
+inline Sample* Sample::Deserialize(Buffer& buffer) {
+    // Overlay the class structure onto the buffer memory. This is the zero-copy step.
+    Sample* result = (Sample*)buffer;
+
+    // SWAP: Primitive field legacy
+    result->__raw_legacy = utils::byteswap_if_needed(result->__raw_legacy);
+    // INFO: Bit-field container flag (1 byte), no swap needed.
+    Point::Deserialize(*(Point::Buffer*) &result->__raw_origin);
+    // SWAP: Vector field path
+    for (size_t i = 0; i < 2; ++i) {
+        Point::Deserialize(*(Point::Buffer*) &result->__raw_path.__items[i]);
+    }
+    // SWAP: Primitive field id
+    result->__raw_id = utils::byteswap_if_needed(result->__raw_id);
+
+    return result;
+}
+
+inline void Sample::Serialize(Buffer& buffer) const {
+    // Copy the host-endian object into the buffer first.
+    memcpy(buffer, this, kSizeOf);
+    Sample* wire_format_data = (Sample*)buffer;
+
+    // SWAP: Primitive field legacy
+    wire_format_data->__raw_legacy = utils::byteswap_if_needed(wire_format_data->__raw_legacy);
+    // INFO: Bit-field container flag (1 byte), no swap needed.
+    wire_format_data->__raw_origin.Serialize(*(Point::Buffer*) &wire_format_data->__raw_origin);
+    // SWAP: Vector field path
+    for (size_t i = 0; i < 2; ++i) {
+        wire_format_data->__raw_path.__items[i].Serialize(*(Point::Buffer*) &wire_format_data->__raw_path.__items[i]);
+    }
+    // SWAP: Primitive field id
+    wire_format_data->__raw_id = utils::byteswap_if_needed(wire_format_data->__raw_id);
+}
+
+inline void Sample::Upgrade(::onyx::Sample& out) const {
+    memset(static_cast<void*>(&out), 0, sizeof(out));
+    out.flag(flag());
+    out.level(level());
+    __raw_origin.Upgrade(out.origin());
+    out.path().__length = __raw_path.__length;
+    for (size_t i = 0; i < 2; ++i) {
+        __raw_path.__items[i].Upgrade(out.path().__items[i]);
+    }
+    out.id(__raw_id);
+}
+
+} // namespace v2
+
+} // namespace onyx
+
+#pragma pack(pop)
+#endif // ONYX_VERSIONED_H_
@@ -0,0 +1,159 @@
+// Automatically generated by Onyx IDL compiler. Do not edit.
+package onyx
+
+import java.nio.ByteBuffer
+import java.nio.ByteOrder
+
+/** The byte order of every field in this file. */
+private val BYTE_ORDER: ByteOrder = ByteOrder.LITTLE_ENDIAN
+
+/** Returns the [size] bytes of the buffer from [offset] as a buffer of their own. */
+private fun ByteBuffer.view(offset: Int, size: Int): ByteBuffer =
+    (duplicate().clear().position(offset).limit(offset + size) as ByteBuffer).slice()
+
+/** Reads the [size] bytes from [offset] as an unsigned integer in [BYTE_ORDER]. */
+private fun ByteBuffer.readBits(offset: Int, size: Int): Long {
+    var raw = 0L
+    for (i in 0 until size) {
+        val index = if (BYTE_ORDER == ByteOrder.BIG_ENDIAN) i else size - 1 - i
+        raw = (raw shl 8) or (get(offset + index).toLong() and 0xFFL)
+    }
+    return raw
+}
+
+/** Writes the low [size] bytes of [raw] from [offset] in [BYTE_ORDER]. */
+private fun ByteBuffer.writeBits(offset: Int, size: Int, raw: Long) {
+    for (i in 0 until size) {
+        val shift = if (BYTE_ORDER == ByteOrder.BIG_ENDIAN) 8 * (size - 1 - i) else 8 * i
+        put(offset + i, (raw ushr shift).toByte())
+    }
+}
+
+/** Copies the [size] bytes from [offset]. */
+private fun ByteBuffer.readBytes(offset: Int, size: Int): ByteArray =
+    ByteArray(size).also { view(offset, size).get(it) }
+
+/** Writes [value], which must hold exactly [size] bytes, from [offset]. */
+private fun ByteBuffer.writeBytes(offset: Int, size: Int, value: ByteArray) {
+    require(value.size == size) { "expected $size bytes, got ${value.size}" }
+    view(offset, size).put(value)
+}
+
+/** Returns [index] if it is below [count], and throws otherwise. */
+private fun checkIndex(index: Int, count: Int): Int {
+    if (index !in 0 until count) {
+        throw IndexOutOfBoundsException("index $index is out of bounds for $count elements")
+    }
+    return index
+}
+
+/** Point, viewed in [buffer] from its index 0. */
+@JvmInline
+value class Point(val buffer: ByteBuffer) {
+    /** Accessor for x. */
+    var x: Int
+        get() = buffer.readBits(X_OFFSET, 4).toInt()
+        set(value) {
+            buffer.writeBits(X_OFFSET, 4, value.toLong())
+        }
+
+    /** Accessor for y. */
+    var y: Int
+        get() = buffer.readBits(Y_OFFSET, 4).toInt()
+        set(value) {
+            buffer.writeBits(Y_OFFSET, 4, value.toLong())
+        }
+
+    /** Accessor for z. */
+    var z: Int
+        get() = buffer.readBits(Z_OFFSET, 4).toInt()
+        set(value) {
+            buffer.writeBits(Z_OFFSET, 4, value.toLong())
+        }
+
+    companion object {
+        /** The size of Point in bytes. */
+        const val SIZE_BYTES: Int = 12
+
+        /** The byte offset of x. */
+        const val X_OFFSET: Int = 0
+
+        /** The byte offset of y. */
+        const val Y_OFFSET: Int = 4
+
+        /** The byte offset of z. */
+        const val Z_OFFSET: Int = 8
+
+        /** Returns a view of Point from the position of [buffer]. */
+        fun wrap(buffer: ByteBuffer): Point = Point(buffer.view(buffer.position(), SIZE_BYTES))
+
+        /** Returns a view of Point in a new, zeroed buffer. */
+        fun allocate(): Point = Point(ByteBuffer.allocate(SIZE_BYTES))
+    }
+}
+
+/** Sample, viewed in [buffer] from its index 0. */
+@JvmInline
+value class Sample(val buffer: ByteBuffer) {
+    /** Accessor for flag. */
+    var flag: Boolean
+        get() = (buffer.readBits(FLAG_OFFSET, 1) and 0x1L) != 0L
+        set(value) {
+            buffer.writeBits(FLAG_OFFSET, 1, (buffer.readBits(FLAG_OFFSET, 1) and 0x1L.inv()) or ((if (value) 1L else 0L) and 0x1L))
+        }
+
+    /** Accessor for level. */
+    var level: UByte
+        get() = ((buffer.readBits(LEVEL_OFFSET, 1) ushr 1) and 0xFL).toUByte()
+        set(value) {
+            buffer.writeBits(LEVEL_OFFSET, 1, (buffer.readBits(LEVEL_OFFSET, 1) and (0xFL shl 1).inv()) or ((value.toLong() and 0xFL) shl 1))
+        }
+
+    /** Accessor for origin. */
+    val origin: Point
+        get() = Point(buffer.view(ORIGIN_OFFSET, Point.SIZE_BYTES))
+
+    /** The number of elements of path in use. */
+    var pathCount: Int
+        get() = buffer.readBits(PATH_OFFSET, 1).toInt()
+        set(value) {
+            require(value in 0..2) { "path holds at most 2 elements" }
+            buffer.writeBits(PATH_OFFSET, 1, value.toLong())
+        }
+
+    /** Returns element [index] of path. */
+    fun path(index: Int): Point = Point(buffer.view(PATH_OFFSET + 1 + 12 * checkIndex(index, pathCount), Point.SIZE_BYTES))
+
+    /** Accessor for id. */
+    var id: UInt
+        get() = buffer.readBits(ID_OFFSET, 4).toUInt()
+        set(value) {
+            buffer.writeBits(ID_OFFSET, 4, value.toLong())
+        }
+
+    companion object {
+        /** The size of Sample in bytes. */
+        const val SIZE_BYTES: Int = 42
+
+        /** The byte offset of the bit-field container holding flag. */
+        const val FLAG_OFFSET: Int = 0
+
+        /** The byte offset of the bit-field container holding level. */
+        const val LEVEL_OFFSET: Int = 0
+
+        /** The byte offset of origin. */
+        const val ORIGIN_OFFSET: Int = 1
+
+        /** The byte offset of path. */
+        const val PATH_OFFSET: Int = 13
+
+        /** The byte offset of id. */
+        const val ID_OFFSET: Int = 38
+
+        /** Returns a view of Sample from the position of [buffer]. */
+        fun wrap(buffer: ByteBuffer): Sample = Sample(buffer.view(buffer.position(), SIZE_BYTES))
+
+        /** Returns a view of Sample in a new, zeroed buffer. */
+        fun allocate(): Sample = Sample(ByteBuffer.allocate(SIZE_BYTES))
+    }
+}
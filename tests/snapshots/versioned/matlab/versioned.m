@@ -0,0 +1,39 @@
+% Automatically generated by Onyx IDL compiler. Do not edit.
+function versioned()
+%VERSIONED Defines the Simulink enums and bus objects of the schema.
+%   Run versioned before loading a model that uses them. Each bus object is
+%   assigned in the base workspace, named after its struct or message.
+
+% Point: 12 bytes.
+elements = Simulink.BusElement.empty;
+elements(end + 1) = element('x', 'int32', 1, 'byte 0');
+elements(end + 1) = element('y', 'int32', 1, 'byte 4');
+elements(end + 1) = element('z', 'int32', 1, 'byte 8');
+bus = Simulink.Bus;
+bus.Description = 'Point: 12 bytes.';
+bus.Elements = elements;
+assignin('base', 'Point', bus);
+
+% Sample: 42 bytes.
+elements = Simulink.BusElement.empty;
+elements(end + 1) = element('flag', 'boolean', 1, 'byte 0, bit 0');
+elements(end + 1) = element('level', 'fixdt(0,4,0)', 1, 'byte 0, bits 1..4');
+elements(end + 1) = element('origin', 'Bus: Point', 1, 'byte 1');
+elements(end + 1) = element('path_count', 'uint8', 1, 'byte 13; the number of elements of path in use');
+elements(end + 1) = element('path', 'Bus: Point', 2, 'byte 14');
+elements(end + 1) = element('id', 'uint32', 1, 'byte 38');
+bus = Simulink.Bus;
+bus.Description = 'Sample: 42 bytes.';
+bus.Elements = elements;
+assignin('base', 'Sample', bus);
+
+end
+
+function e = element(name, type, dimensions, description)
+% Returns a bus element.
+e = Simulink.BusElement;
+e.Name = name;
+e.DataType = type;
+e.Dimensions = dimensions;
+e.Description = description;
+end
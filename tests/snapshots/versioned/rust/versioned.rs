@@ -0,0 +1,1092 @@
+// Automatically generated by Onyx IDL compiler. Do not edit.
+
+use std::convert::TryInto;
+
+
+/// Size of Point in bytes.
+pub(crate) const POINT_SIZE: usize = 12;
+/// Buffer type alias for Point.
+pub type PointBuffer = [u8; POINT_SIZE];
+
+/// Read-only view of Point.
+#[derive(Debug, Clone, Copy)]
+pub struct PointView<'a> {
+    data: &'a PointBuffer,
+}
+
+/// Read-write view of Point.
+#[derive(Debug)]
+pub struct PointMutView<'a> {
+    data: &'a mut PointBuffer,
+}
+
+impl<'a> PointView<'a> {
+    /// Size of Point in bytes.
+    pub const SIZE_BYTES: usize = POINT_SIZE;
+    /// Byte offset of x.
+    pub const FIELD_OFFSET_X: usize = 0;
+    /// Byte offset of y.
+    pub const FIELD_OFFSET_Y: usize = 4;
+    /// Byte offset of z.
+    pub const FIELD_OFFSET_Z: usize = 8;
+
+    /// Creates a new PointView from a PointBuffer.
+    pub fn new(data: &'a PointBuffer) -> Self {
+        Self { data }
+    }
+
+    /// Accessor for x.
+    pub fn x(&self) -> i32 {
+        let bytes = self.data[0..4].try_into().unwrap();
+        i32::from_le_bytes(bytes)
+    }
+
+    /// Accessor for y.
+    pub fn y(&self) -> i32 {
+        let bytes = self.data[4..8].try_into().unwrap();
+        i32::from_le_bytes(bytes)
+    }
+
+    /// Accessor for z.
+    pub fn z(&self) -> i32 {
+        let bytes = self.data[8..12].try_into().unwrap();
+        i32::from_le_bytes(bytes)
+    }
+}
+
+// Fails to compile if Point does not match its Onyx layout.
+const _: () = assert!(PointView::FIELD_OFFSET_Y == PointView::FIELD_OFFSET_X + std::mem::size_of::<i32>(), "Point.y does not match its Onyx offset");
+const _: () = assert!(PointView::FIELD_OFFSET_Z == PointView::FIELD_OFFSET_Y + std::mem::size_of::<i32>(), "Point.z does not match its Onyx offset");
+const _: () = assert!(PointView::FIELD_OFFSET_Z + std::mem::size_of::<i32>() == PointView::SIZE_BYTES, "Point does not match its Onyx size");
+
+impl<'a> PointMutView<'a> {
+    /// Creates a new PointMutView from a PointBuffer.
+    pub fn new(data: &'a mut PointBuffer) -> Self {
+        Self { data }
+    }
+
+    /// Accessor for x.
+    pub fn x(&self) -> i32 {
+        let bytes = self.data[0..4].try_into().unwrap();
+        i32::from_le_bytes(bytes)
+    }
+
+    /// Mutator for x.
+    pub fn set_x(&mut self, value: i32) {
+        let bytes = value.to_le_bytes();
+        self.data[0..4].copy_from_slice(&bytes);
+    }
+
+    /// Accessor for y.
+    pub fn y(&self) -> i32 {
+        let bytes = self.data[4..8].try_into().unwrap();
+        i32::from_le_bytes(bytes)
+    }
+
+    /// Mutator for y.
+    pub fn set_y(&mut self, value: i32) {
+        let bytes = value.to_le_bytes();
+        self.data[4..8].copy_from_slice(&bytes);
+    }
+
+    /// Accessor for z.
+    pub fn z(&self) -> i32 {
+        let bytes = self.data[8..12].try_into().unwrap();
+        i32::from_le_bytes(bytes)
+    }
+
+    /// Mutator for z.
+    pub fn set_z(&mut self, value: i32) {
+        let bytes = value.to_le_bytes();
+        self.data[8..12].copy_from_slice(&bytes);
+    }
+}
+
+/// Size of Sample in bytes.
+pub(crate) const SAMPLE_SIZE: usize = 42;
+/// Buffer type alias for Sample.
+pub type SampleBuffer = [u8; SAMPLE_SIZE];
+
+/// Read-only view of Sample.
+#[derive(Debug, Clone, Copy)]
+pub struct SampleView<'a> {
+    data: &'a SampleBuffer,
+}
+
+/// Read-write view of Sample.
+#[derive(Debug)]
+pub struct SampleMutView<'a> {
+    data: &'a mut SampleBuffer,
+}
+
+impl<'a> SampleView<'a> {
+    /// Size of Sample in bytes.
+    pub const SIZE_BYTES: usize = SAMPLE_SIZE;
+    /// Byte offset of the bit-field container holding flag.
+    pub const FIELD_OFFSET_FLAG: usize = 0;
+    /// Byte offset of the bit-field container holding level.
+    pub const FIELD_OFFSET_LEVEL: usize = 0;
+    /// Byte offset of origin.
+    pub const FIELD_OFFSET_ORIGIN: usize = 1;
+    /// Byte offset of path.
+    pub const FIELD_OFFSET_PATH: usize = 13;
+    /// Byte offset of id.
+    pub const FIELD_OFFSET_ID: usize = 38;
+
+    /// Creates a new SampleView from a SampleBuffer.
+    pub fn new(data: &'a SampleBuffer) -> Self {
+        Self { data }
+    }
+
+    /// Accessor for flag.
+    pub fn flag(&self) -> bool {
+        let raw = self.data[0] as u8;
+        let val = (raw >> 0) & 0x1;
+        val != 0
+    }
+
+    /// Accessor for level.
+    pub fn level(&self) -> u8 {
+        let raw = self.data[0] as u8;
+        let val = (raw >> 1) & 0xF;
+        val as u8
+    }
+
+    /// Accessor for origin.
+    pub fn origin(&self) -> PointView {
+        let slice = &self.data[1..13];
+        PointView::new(slice.try_into().unwrap())
+    }
+
+    /// Accessor for path.
+    pub fn path(&self) -> SamplePathView {
+        let slice = &self.data[13..38];
+        SamplePathView { data: slice.try_into().unwrap() }
+    }
+
+    /// Accessor for id.
+    pub fn id(&self) -> u32 {
+        let bytes = self.data[38..42].try_into().unwrap();
+        u32::from_le_bytes(bytes)
+    }
+}
+
+// Fails to compile if Sample does not match its Onyx layout.
+const _: () = assert!(SampleView::FIELD_OFFSET_ORIGIN == SampleView::FIELD_OFFSET_FLAG + 1, "Sample.origin does not match its Onyx offset");
+const _: () = assert!(SampleView::FIELD_OFFSET_PATH == SampleView::FIELD_OFFSET_ORIGIN + POINT_SIZE, "Sample.path does not match its Onyx offset");
+const _: () = assert!(SampleView::FIELD_OFFSET_ID == SampleView::FIELD_OFFSET_PATH + (std::mem::size_of::<u8>() + 2 * POINT_SIZE), "Sample.id does not match its Onyx offset");
+const _: () = assert!(SampleView::FIELD_OFFSET_ID + std::mem::size_of::<u32>() == SampleView::SIZE_BYTES, "Sample does not match its Onyx size");
+
+impl<'a> SampleMutView<'a> {
+    /// Creates a new SampleMutView from a SampleBuffer.
+    pub fn new(data: &'a mut SampleBuffer) -> Self {
+        Self { data }
+    }
+
+    /// Accessor for flag.
+    pub fn flag(&self) -> bool {
+        let raw = self.data[0] as u8;
+        let val = (raw >> 0) & 0x1;
+        val != 0
+    }
+
+    /// Mutator for flag.
+    pub fn set_flag(&mut self, value: bool) {
+        let mut raw = self.data[0] as u8;
+        raw &= !((0x1 as u8) << 0);
+        raw |= ((value as u8 & 0x1) as u8) << 0;
+        self.data[0] = raw as u8;
+    }
+
+    /// Accessor for level.
+    pub fn level(&self) -> u8 {
+        let raw = self.data[0] as u8;
+        let val = (raw >> 1) & 0xF;
+        val as u8
+    }
+
+    /// Mutator for level.
+    pub fn set_level(&mut self, value: u8) {
+        let mut raw = self.data[0] as u8;
+        raw &= !((0xF as u8) << 1);
+        raw |= ((value as u8 & 0xF) as u8) << 1;
+        self.data[0] = raw as u8;
+    }
+
+    /// Accessor for origin.
+    pub fn origin(&self) -> PointView {
+        let slice = &self.data[1..13];
+        PointView::new(slice.try_into().unwrap())
+    }
+
+    /// Mutable accessor for origin.
+    pub fn origin_mut(&mut self) -> PointMutView {
+        let slice = &mut self.data[1..13];
+        PointMutView::new(slice.try_into().unwrap())
+    }
+
+    /// Accessor for path.
+    pub fn path(&self) -> SamplePathView {
+        let slice = &self.data[13..38];
+        SamplePathView { data: slice.try_into().unwrap() }
+    }
+
+    /// Mutable accessor for path.
+    pub fn path_mut(&mut self) -> SamplePathMutView {
+        let slice = &mut self.data[13..38];
+        SamplePathMutView { data: slice.try_into().unwrap() }
+    }
+
+    /// Accessor for id.
+    pub fn id(&self) -> u32 {
+        let bytes = self.data[38..42].try_into().unwrap();
+        u32::from_le_bytes(bytes)
+    }
+
+    /// Mutator for id.
+    pub fn set_id(&mut self, value: u32) {
+        let bytes = value.to_le_bytes();
+        self.data[38..42].copy_from_slice(&bytes);
+    }
+}
+
+/// Read-only view of the bounded vector Sample.path.
+#[derive(Debug, Clone, Copy)]
+pub struct SamplePathView<'a> {
+    data: &'a [u8; 25],
+}
+
+impl<'a> SamplePathView<'a> {
+    /// The maximum number of elements.
+    pub const CAPACITY: usize = 2;
+
+    /// Returns the number of elements, clamped to the capacity.
+    pub fn len(&self) -> usize {
+        (self.data[0] as usize).min(Self::CAPACITY)
+    }
+
+    /// Returns true if the vector has no elements.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the element at `index`, or `None` if it is out of range.
+    pub fn get(&self, index: usize) -> Option<PointView<'a>> {
+        if index >= self.len() {
+            return None;
+        }
+        let data: &'a [u8; 25] = self.data;
+        Some(PointView::new(data[1 + index * 12..1 + index * 12 + 12].try_into().unwrap()))
+    }
+
+    /// Returns an iterator over the elements.
+    pub fn iter(&self) -> impl Iterator<Item = PointView<'a>> + 'a {
+        let view = *self;
+        (0..self.len()).map(move |index| view.get(index).unwrap())
+    }
+}
+
+/// Read-write view of the bounded vector Sample.path.
+#[derive(Debug)]
+pub struct SamplePathMutView<'a> {
+    data: &'a mut [u8; 25],
+}
+
+impl<'a> SamplePathMutView<'a> {
+    /// The maximum number of elements.
+    pub const CAPACITY: usize = 2;
+
+    /// Returns a read-only view of the vector.
+    pub fn as_view(&self) -> SamplePathView<'_> {
+        SamplePathView { data: self.data }
+    }
+
+    /// Returns the number of elements, clamped to the capacity.
+    pub fn len(&self) -> usize {
+        self.as_view().len()
+    }
+
+    /// Returns true if the vector has no elements.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the element at `index`, or `None` if it is out of range.
+    pub fn get(&self, index: usize) -> Option<PointView<'_>> {
+        self.as_view().get(index)
+    }
+
+    /// Returns an iterator over the elements.
+    pub fn iter(&self) -> impl Iterator<Item = PointView<'_>> + '_ {
+        self.as_view().iter()
+    }
+
+    fn set_len(&mut self, len: usize) {
+        self.data[0] = len as u8;
+    }
+
+    /// Removes all elements.
+    pub fn clear(&mut self) {
+        self.set_len(0);
+    }
+
+    /// Returns a mutable view of the element at `index`, or `None` if it is out of range.
+    pub fn get_mut(&mut self, index: usize) -> Option<PointMutView<'_>> {
+        if index >= self.len() {
+            return None;
+        }
+        Some(PointMutView::new((&mut self.data[1 + index * 12..1 + index * 12 + 12]).try_into().unwrap()))
+    }
+
+    /// Appends a zeroed element and returns a mutable view of it, or `None` if the vector is full.
+    pub fn push(&mut self) -> Option<PointMutView<'_>> {
+        let index = self.len();
+        if index == Self::CAPACITY {
+            return None;
+        }
+        self.set_len(index + 1);
+        self.data[1 + index * 12..1 + index * 12 + 12].fill(0);
+        self.get_mut(index)
+    }
+}
+
+/// Version 1 of the schema.
+pub mod v1 {
+    #[allow(unused_imports)]
+    pub use super::*;
+
+    /// Size of Point in bytes.
+    pub(crate) const POINT_SIZE: usize = 8;
+    /// Buffer type alias for Point.
+    pub type PointBuffer = [u8; POINT_SIZE];
+
+    /// Read-only view of Point.
+    #[derive(Debug, Clone, Copy)]
+    pub struct PointView<'a> {
+        data: &'a PointBuffer,
+    }
+
+    /// Read-write view of Point.
+    #[derive(Debug)]
+    pub struct PointMutView<'a> {
+        data: &'a mut PointBuffer,
+    }
+
+    impl<'a> PointView<'a> {
+        /// Size of Point in bytes.
+        pub const SIZE_BYTES: usize = POINT_SIZE;
+        /// Byte offset of x.
+        pub const FIELD_OFFSET_X: usize = 0;
+        /// Byte offset of y.
+        pub const FIELD_OFFSET_Y: usize = 4;
+
+        /// Creates a new PointView from a PointBuffer.
+        pub fn new(data: &'a PointBuffer) -> Self {
+            Self { data }
+        }
+
+        /// Accessor for x.
+        pub fn x(&self) -> i32 {
+            let bytes = self.data[0..4].try_into().unwrap();
+            i32::from_le_bytes(bytes)
+        }
+
+        /// Accessor for y.
+        pub fn y(&self) -> i32 {
+            let bytes = self.data[4..8].try_into().unwrap();
+            i32::from_le_bytes(bytes)
+        }
+    }
+
+    // Fails to compile if Point does not match its Onyx layout.
+    const _: () = assert!(PointView::FIELD_OFFSET_Y == PointView::FIELD_OFFSET_X + std::mem::size_of::<i32>(), "Point.y does not match its Onyx offset");
+    const _: () = assert!(PointView::FIELD_OFFSET_Y + std::mem::size_of::<i32>() == PointView::SIZE_BYTES, "Point does not match its Onyx size");
+
+    impl<'a> PointMutView<'a> {
+        /// Creates a new PointMutView from a PointBuffer.
+        pub fn new(data: &'a mut PointBuffer) -> Self {
+            Self { data }
+        }
+
+        /// Accessor for x.
+        pub fn x(&self) -> i32 {
+            let bytes = self.data[0..4].try_into().unwrap();
+            i32::from_le_bytes(bytes)
+        }
+
+        /// Mutator for x.
+        pub fn set_x(&mut self, value: i32) {
+            let bytes = value.to_le_bytes();
+            self.data[0..4].copy_from_slice(&bytes);
+        }
+
+        /// Accessor for y.
+        pub fn y(&self) -> i32 {
+            let bytes = self.data[4..8].try_into().unwrap();
+            i32::from_le_bytes(bytes)
+        }
+
+        /// Mutator for y.
+        pub fn set_y(&mut self, value: i32) {
+            let bytes = value.to_le_bytes();
+            self.data[4..8].copy_from_slice(&bytes);
+        }
+    }
+
+    impl PointView<'_> {
+        /// Converts to the current layout. Fields added since are zeroed.
+        pub fn upgrade(&self) -> super::PointBuffer {
+            let mut buffer = [0; super::POINT_SIZE];
+            self.upgrade_into(&mut super::PointMutView::new(&mut buffer));
+            buffer
+        }
+
+        fn upgrade_into(&self, target: &mut super::PointMutView<'_>) {
+            target.data[0..4].copy_from_slice(&self.data[0..4]);
+            target.data[4..8].copy_from_slice(&self.data[4..8]);
+        }
+    }
+
+    /// Size of Sample in bytes.
+    pub(crate) const SAMPLE_SIZE: usize = 28;
+    /// Buffer type alias for Sample.
+    pub type SampleBuffer = [u8; SAMPLE_SIZE];
+
+    /// Read-only view of Sample.
+    #[derive(Debug, Clone, Copy)]
+    pub struct SampleView<'a> {
+        data: &'a SampleBuffer,
+    }
+
+    /// Read-write view of Sample.
+    #[derive(Debug)]
+    pub struct SampleMutView<'a> {
+        data: &'a mut SampleBuffer,
+    }
+
+    impl<'a> SampleView<'a> {
+        /// Size of Sample in bytes.
+        pub const SIZE_BYTES: usize = SAMPLE_SIZE;
+        /// Byte offset of legacy.
+        pub const FIELD_OFFSET_LEGACY: usize = 0;
+        /// Byte offset of the bit-field container holding flag.
+        pub const FIELD_OFFSET_FLAG: usize = 2;
+        /// Byte offset of origin.
+        pub const FIELD_OFFSET_ORIGIN: usize = 3;
+        /// Byte offset of path.
+        pub const FIELD_OFFSET_PATH: usize = 11;
+
+        /// Creates a new SampleView from a SampleBuffer.
+        pub fn new(data: &'a SampleBuffer) -> Self {
+            Self { data }
+        }
+
+        /// Accessor for legacy.
+        pub fn legacy(&self) -> u16 {
+            let bytes = self.data[0..2].try_into().unwrap();
+            u16::from_le_bytes(bytes)
+        }
+
+        /// Accessor for flag.
+        pub fn flag(&self) -> bool {
+            let raw = self.data[2] as u8;
+            let val = (raw >> 0) & 0x1;
+            val != 0
+        }
+
+        /// Accessor for origin.
+        pub fn origin(&self) -> PointView {
+            let slice = &self.data[3..11];
+            PointView::new(slice.try_into().unwrap())
+        }
+
+        /// Accessor for path.
+        pub fn path(&self) -> SamplePathView {
+            let slice = &self.data[11..28];
+            SamplePathView { data: slice.try_into().unwrap() }
+        }
+    }
+
+    // Fails to compile if Sample does not match its Onyx layout.
+    const _: () = assert!(SampleView::FIELD_OFFSET_FLAG == SampleView::FIELD_OFFSET_LEGACY + std::mem::size_of::<u16>(), "Sample.flag does not match its Onyx offset");
+    const _: () = assert!(SampleView::FIELD_OFFSET_ORIGIN == SampleView::FIELD_OFFSET_FLAG + 1, "Sample.origin does not match its Onyx offset");
+    const _: () = assert!(SampleView::FIELD_OFFSET_PATH == SampleView::FIELD_OFFSET_ORIGIN + POINT_SIZE, "Sample.path does not match its Onyx offset");
+    const _: () = assert!(SampleView::FIELD_OFFSET_PATH + (std::mem::size_of::<u8>() + 2 * POINT_SIZE) == SampleView::SIZE_BYTES, "Sample does not match its Onyx size");
+
+    impl<'a> SampleMutView<'a> {
+        /// Creates a new SampleMutView from a SampleBuffer.
+        pub fn new(data: &'a mut SampleBuffer) -> Self {
+            Self { data }
+        }
+
+        /// Accessor for legacy.
+        pub fn legacy(&self) -> u16 {
+            let bytes = self.data[0..2].try_into().unwrap();
+            u16::from_le_bytes(bytes)
+        }
+
+        /// Mutator for legacy.
+        pub fn set_legacy(&mut self, value: u16) {
+            let bytes = value.to_le_bytes();
+            self.data[0..2].copy_from_slice(&bytes);
+        }
+
+        /// Accessor for flag.
+        pub fn flag(&self) -> bool {
+            let raw = self.data[2] as u8;
+            let val = (raw >> 0) & 0x1;
+            val != 0
+        }
+
+        /// Mutator for flag.
+        pub fn set_flag(&mut self, value: bool) {
+            let mut raw = self.data[2] as u8;
+            raw &= !((0x1 as u8) << 0);
+            raw |= ((value as u8 & 0x1) as u8) << 0;
+            self.data[2] = raw as u8;
+        }
+
+        /// Accessor for origin.
+        pub fn origin(&self) -> PointView {
+            let slice = &self.data[3..11];
+            PointView::new(slice.try_into().unwrap())
+        }
+
+        /// Mutable accessor for origin.
+        pub fn origin_mut(&mut self) -> PointMutView {
+            let slice = &mut self.data[3..11];
+            PointMutView::new(slice.try_into().unwrap())
+        }
+
+        /// Accessor for path.
+        pub fn path(&self) -> SamplePathView {
+            let slice = &self.data[11..28];
+            SamplePathView { data: slice.try_into().unwrap() }
+        }
+
+        /// Mutable accessor for path.
+        pub fn path_mut(&mut self) -> SamplePathMutView {
+            let slice = &mut self.data[11..28];
+            SamplePathMutView { data: slice.try_into().unwrap() }
+        }
+    }
+
+    /// Read-only view of the bounded vector Sample.path.
+    #[derive(Debug, Clone, Copy)]
+    pub struct SamplePathView<'a> {
+        data: &'a [u8; 17],
+    }
+
+    impl<'a> SamplePathView<'a> {
+        /// The maximum number of elements.
+        pub const CAPACITY: usize = 2;
+
+        /// Returns the number of elements, clamped to the capacity.
+        pub fn len(&self) -> usize {
+            (self.data[0] as usize).min(Self::CAPACITY)
+        }
+
+        /// Returns true if the vector has no elements.
+        pub fn is_empty(&self) -> bool {
+            self.len() == 0
+        }
+
+        /// Returns the element at `index`, or `None` if it is out of range.
+        pub fn get(&self, index: usize) -> Option<PointView<'a>> {
+            if index >= self.len() {
+                return None;
+            }
+            let data: &'a [u8; 17] = self.data;
+            Some(PointView::new(data[1 + index * 8..1 + index * 8 + 8].try_into().unwrap()))
+        }
+
+        /// Returns an iterator over the elements.
+        pub fn iter(&self) -> impl Iterator<Item = PointView<'a>> + 'a {
+            let view = *self;
+            (0..self.len()).map(move |index| view.get(index).unwrap())
+        }
+    }
+
+    /// Read-write view of the bounded vector Sample.path.
+    #[derive(Debug)]
+    pub struct SamplePathMutView<'a> {
+        data: &'a mut [u8; 17],
+    }
+
+    impl<'a> SamplePathMutView<'a> {
+        /// The maximum number of elements.
+        pub const CAPACITY: usize = 2;
+
+        /// Returns a read-only view of the vector.
+        pub fn as_view(&self) -> SamplePathView<'_> {
+            SamplePathView { data: self.data }
+        }
+
+        /// Returns the number of elements, clamped to the capacity.
+        pub fn len(&self) -> usize {
+            self.as_view().len()
+        }
+
+        /// Returns true if the vector has no elements.
+        pub fn is_empty(&self) -> bool {
+            self.len() == 0
+        }
+
+        /// Returns the element at `index`, or `None` if it is out of range.
+        pub fn get(&self, index: usize) -> Option<PointView<'_>> {
+            self.as_view().get(index)
+        }
+
+        /// Returns an iterator over the elements.
+        pub fn iter(&self) -> impl Iterator<Item = PointView<'_>> + '_ {
+            self.as_view().iter()
+        }
+
+        fn set_len(&mut self, len: usize) {
+            self.data[0] = len as u8;
+        }
+
+        /// Removes all elements.
+        pub fn clear(&mut self) {
+            self.set_len(0);
+        }
+
+        /// Returns a mutable view of the element at `index`, or `None` if it is out of range.
+        pub fn get_mut(&mut self, index: usize) -> Option<PointMutView<'_>> {
+            if index >= self.len() {
+                return None;
+            }
+            Some(PointMutView::new((&mut self.data[1 + index * 8..1 + index * 8 + 8]).try_into().unwrap()))
+        }
+
+        /// Appends a zeroed element and returns a mutable view of it, or `None` if the vector is full.
+        pub fn push(&mut self) -> Option<PointMutView<'_>> {
+            let index = self.len();
+            if index == Self::CAPACITY {
+                return None;
+            }
+            self.set_len(index + 1);
+            self.data[1 + index * 8..1 + index * 8 + 8].fill(0);
+            self.get_mut(index)
+        }
+    }
+
+    impl SampleView<'_> {
+        /// Converts to the current layout. Fields added since are zeroed.
+        pub fn upgrade(&self) -> super::SampleBuffer {
+            let mut buffer = [0; super::SAMPLE_SIZE];
+            self.upgrade_into(&mut super::SampleMutView::new(&mut buffer));
+            buffer
+        }
+
+        fn upgrade_into(&self, target: &mut super::SampleMutView<'_>) {
+            target.set_flag(self.flag());
+            PointView::new(self.data[3..3 + 8].try_into().unwrap())
+                .upgrade_into(&mut super::PointMutView::new(
+                    (&mut target.data[1..1 + 12]).try_into().unwrap(),
+                ));
+            target.data[13..14].copy_from_slice(&self.data[11..12]);
+            for i in 0..2 {
+                let (from, to) = (12 + i * 8, 14 + i * 12);
+                PointView::new(self.data[from..from + 8].try_into().unwrap())
+                    .upgrade_into(&mut super::PointMutView::new(
+                        (&mut target.data[to..to + 12]).try_into().unwrap(),
+                    ));
+            }
+        }
+    }
+}
+
+/// Version 2 of the schema.
+pub mod v2 {
+    #[allow(unused_imports)]
+    pub use super::*;
+
+    /// Size of Point in bytes.
+    pub(crate) const POINT_SIZE: usize = 8;
+    /// Buffer type alias for Point.
+    pub type PointBuffer = [u8; POINT_SIZE];
+
+    /// Read-only view of Point.
+    #[derive(Debug, Clone, Copy)]
+    pub struct PointView<'a> {
+        data: &'a PointBuffer,
+    }
+
+    /// Read-write view of Point.
+    #[derive(Debug)]
+    pub struct PointMutView<'a> {
+        data: &'a mut PointBuffer,
+    }
+
+    impl<'a> PointView<'a> {
+        /// Size of Point in bytes.
+        pub const SIZE_BYTES: usize = POINT_SIZE;
+        /// Byte offset of x.
+        pub const FIELD_OFFSET_X: usize = 0;
+        /// Byte offset of y.
+        pub const FIELD_OFFSET_Y: usize = 4;
+
+        /// Creates a new PointView from a PointBuffer.
+        pub fn new(data: &'a PointBuffer) -> Self {
+            Self { data }
+        }
+
+        /// Accessor for x.
+        pub fn x(&self) -> i32 {
+            let bytes = self.data[0..4].try_into().unwrap();
+            i32::from_le_bytes(bytes)
+        }
+
+        /// Accessor for y.
+        pub fn y(&self) -> i32 {
+            let bytes = self.data[4..8].try_into().unwrap();
+            i32::from_le_bytes(bytes)
+        }
+    }
+
+    // Fails to compile if Point does not match its Onyx layout.
+    const _: () = assert!(PointView::FIELD_OFFSET_Y == PointView::FIELD_OFFSET_X + std::mem::size_of::<i32>(), "Point.y does not match its Onyx offset");
+    const _: () = assert!(PointView::FIELD_OFFSET_Y + std::mem::size_of::<i32>() == PointView::SIZE_BYTES, "Point does not match its Onyx size");
+
+    impl<'a> PointMutView<'a> {
+        /// Creates a new PointMutView from a PointBuffer.
+        pub fn new(data: &'a mut PointBuffer) -> Self {
+            Self { data }
+        }
+
+        /// Accessor for x.
+        pub fn x(&self) -> i32 {
+            let bytes = self.data[0..4].try_into().unwrap();
+            i32::from_le_bytes(bytes)
+        }
+
+        /// Mutator for x.
+        pub fn set_x(&mut self, value: i32) {
+            let bytes = value.to_le_bytes();
+            self.data[0..4].copy_from_slice(&bytes);
+        }
+
+        /// Accessor for y.
+        pub fn y(&self) -> i32 {
+            let bytes = self.data[4..8].try_into().unwrap();
+            i32::from_le_bytes(bytes)
+        }
+
+        /// Mutator for y.
+        pub fn set_y(&mut self, value: i32) {
+            let bytes = value.to_le_bytes();
+            self.data[4..8].copy_from_slice(&bytes);
+        }
+    }
+
+    impl PointView<'_> {
+        /// Converts to the current layout. Fields added since are zeroed.
+        pub fn upgrade(&self) -> super::PointBuffer {
+            let mut buffer = [0; super::POINT_SIZE];
+            self.upgrade_into(&mut super::PointMutView::new(&mut buffer));
+            buffer
+        }
+
+        fn upgrade_into(&self, target: &mut super::PointMutView<'_>) {
+            target.data[0..4].copy_from_slice(&self.data[0..4]);
+            target.data[4..8].copy_from_slice(&self.data[4..8]);
+        }
+    }
+
+    /// Size of Sample in bytes.
+    pub(crate) const SAMPLE_SIZE: usize = 32;
+    /// Buffer type alias for Sample.
+    pub type SampleBuffer = [u8; SAMPLE_SIZE];
+
+    /// Read-only view of Sample.
+    #[derive(Debug, Clone, Copy)]
+    pub struct SampleView<'a> {
+        data: &'a SampleBuffer,
+    }
+
+    /// Read-write view of Sample.
+    #[derive(Debug)]
+    pub struct SampleMutView<'a> {
+        data: &'a mut SampleBuffer,
+    }
+
+    impl<'a> SampleView<'a> {
+        /// Size of Sample in bytes.
+        pub const SIZE_BYTES: usize = SAMPLE_SIZE;
+        /// Byte offset of legacy.
+        pub const FIELD_OFFSET_LEGACY: usize = 0;
+        /// Byte offset of the bit-field container holding flag.
+        pub const FIELD_OFFSET_FLAG: usize = 2;
+        /// Byte offset of the bit-field container holding level.
+        pub const FIELD_OFFSET_LEVEL: usize = 2;
+        /// Byte offset of origin.
+        pub const FIELD_OFFSET_ORIGIN: usize = 3;
+        /// Byte offset of path.
+        pub const FIELD_OFFSET_PATH: usize = 11;
+        /// Byte offset of id.
+        pub const FIELD_OFFSET_ID: usize = 28;
+
+        /// Creates a new SampleView from a SampleBuffer.
+        pub fn new(data: &'a SampleBuffer) -> Self {
+            Self { data }
+        }
+
+        /// Accessor for legacy.
+        pub fn legacy(&self) -> u16 {
+            let bytes = self.data[0..2].try_into().unwrap();
+            u16::from_le_bytes(bytes)
+        }
+
+        /// Accessor for flag.
+        pub fn flag(&self) -> bool {
+            let raw = self.data[2] as u8;
+            let val = (raw >> 0) & 0x1;
+            val != 0
+        }
+
+        /// Accessor for level.
+        pub fn level(&self) -> u8 {
+            let raw = self.data[2] as u8;
+            let val = (raw >> 1) & 0xF;
+            val as u8
+        }
+
+        /// Accessor for origin.
+        pub fn origin(&self) -> PointView {
+            let slice = &self.data[3..11];
+            PointView::new(slice.try_into().unwrap())
+        }
+
+        /// Accessor for path.
+        pub fn path(&self) -> SamplePathView {
+            let slice = &self.data[11..28];
+            SamplePathView { data: slice.try_into().unwrap() }
+        }
+
+        /// Accessor for id.
+        pub fn id(&self) -> u32 {
+            let bytes = self.data[28..32].try_into().unwrap();
+            u32::from_le_bytes(bytes)
+        }
+    }
+
+    // Fails to compile if Sample does not match its Onyx layout.
+    const _: () = assert!(SampleView::FIELD_OFFSET_FLAG == SampleView::FIELD_OFFSET_LEGACY + std::mem::size_of::<u16>(), "Sample.flag does not match its Onyx offset");
+    const _: () = assert!(SampleView::FIELD_OFFSET_ORIGIN == SampleView::FIELD_OFFSET_FLAG + 1, "Sample.origin does not match its Onyx offset");
+    const _: () = assert!(SampleView::FIELD_OFFSET_PATH == SampleView::FIELD_OFFSET_ORIGIN + POINT_SIZE, "Sample.path does not match its Onyx offset");
+    const _: () = assert!(SampleView::FIELD_OFFSET_ID == SampleView::FIELD_OFFSET_PATH + (std::mem::size_of::<u8>() + 2 * POINT_SIZE), "Sample.id does not match its Onyx offset");
+    const _: () = assert!(SampleView::FIELD_OFFSET_ID + std::mem::size_of::<u32>() == SampleView::SIZE_BYTES, "Sample does not match its Onyx size");
+
+    impl<'a> SampleMutView<'a> {
+        /// Creates a new SampleMutView from a SampleBuffer.
+        pub fn new(data: &'a mut SampleBuffer) -> Self {
+            Self { data }
+        }
+
+        /// Accessor for legacy.
+        pub fn legacy(&self) -> u16 {
+            let bytes = self.data[0..2].try_into().unwrap();
+            u16::from_le_bytes(bytes)
+        }
+
+        /// Mutator for legacy.
+        pub fn set_legacy(&mut self, value: u16) {
+            let bytes = value.to_le_bytes();
+            self.data[0..2].copy_from_slice(&bytes);
+        }
+
+        /// Accessor for flag.
+        pub fn flag(&self) -> bool {
+            let raw = self.data[2] as u8;
+            let val = (raw >> 0) & 0x1;
+            val != 0
+        }
+
+        /// Mutator for flag.
+        pub fn set_flag(&mut self, value: bool) {
+            let mut raw = self.data[2] as u8;
+            raw &= !((0x1 as u8) << 0);
+            raw |= ((value as u8 & 0x1) as u8) << 0;
+            self.data[2] = raw as u8;
+        }
+
+        /// Accessor for level.
+        pub fn level(&self) -> u8 {
+            let raw = self.data[2] as u8;
+            let val = (raw >> 1) & 0xF;
+            val as u8
+        }
+
+        /// Mutator for level.
+        pub fn set_level(&mut self, value: u8) {
+            let mut raw = self.data[2] as u8;
+            raw &= !((0xF as u8) << 1);
+            raw |= ((value as u8 & 0xF) as u8) << 1;
+            self.data[2] = raw as u8;
+        }
+
+        /// Accessor for origin.
+        pub fn origin(&self) -> PointView {
+            let slice = &self.data[3..11];
+            PointView::new(slice.try_into().unwrap())
+        }
+
+        /// Mutable accessor for origin.
+        pub fn origin_mut(&mut self) -> PointMutView {
+            let slice = &mut self.data[3..11];
+            PointMutView::new(slice.try_into().unwrap())
+        }
+
+        /// Accessor for path.
+        pub fn path(&self) -> SamplePathView {
+            let slice = &self.data[11..28];
+            SamplePathView { data: slice.try_into().unwrap() }
+        }
+
+        /// Mutable accessor for path.
+        pub fn path_mut(&mut self) -> SamplePathMutView {
+            let slice = &mut self.data[11..28];
+            SamplePathMutView { data: slice.try_into().unwrap() }
+        }
+
+        /// Accessor for id.
+        pub fn id(&self) -> u32 {
+            let bytes = self.data[28..32].try_into().unwrap();
+            u32::from_le_bytes(bytes)
+        }
+
+        /// Mutator for id.
+        pub fn set_id(&mut self, value: u32) {
+            let bytes = value.to_le_bytes();
+            self.data[28..32].copy_from_slice(&bytes);
+        }
+    }
+
+    /// Read-only view of the bounded vector Sample.path.
+    #[derive(Debug, Clone, Copy)]
+    pub struct SamplePathView<'a> {
+        data: &'a [u8; 17],
+    }
+
+    impl<'a> SamplePathView<'a> {
+        /// The maximum number of elements.
+        pub const CAPACITY: usize = 2;
+
+        /// Returns the number of elements, clamped to the capacity.
+        pub fn len(&self) -> usize {
+            (self.data[0] as usize).min(Self::CAPACITY)
+        }
+
+        /// Returns true if the vector has no elements.
+        pub fn is_empty(&self) -> bool {
+            self.len() == 0
+        }
+
+        /// Returns the element at `index`, or `None` if it is out of range.
+        pub fn get(&self, index: usize) -> Option<PointView<'a>> {
+            if index >= self.len() {
+                return None;
+            }
+            let data: &'a [u8; 17] = self.data;
+            Some(PointView::new(data[1 + index * 8..1 + index * 8 + 8].try_into().unwrap()))
+        }
+
+        /// Returns an iterator over the elements.
+        pub fn iter(&self) -> impl Iterator<Item = PointView<'a>> + 'a {
+            let view = *self;
+            (0..self.len()).map(move |index| view.get(index).unwrap())
+        }
+    }
+
+    /// Read-write view of the bounded vector Sample.path.
+    #[derive(Debug)]
+    pub struct SamplePathMutView<'a> {
+        data: &'a mut [u8; 17],
+    }
+
+    impl<'a> SamplePathMutView<'a> {
+        /// The maximum number of elements.
+        pub const CAPACITY: usize = 2;
+
+        /// Returns a read-only view of the vector.
+        pub fn as_view(&self) -> SamplePathView<'_> {
+            SamplePathView { data: self.data }
+        }
+
+        /// Returns the number of elements, clamped to the capacity.
+        pub fn len(&self) -> usize {
+            self.as_view().len()
+        }
+
+        /// Returns true if the vector has no elements.
+        pub fn is_empty(&self) -> bool {
+            self.len() == 0
+        }
+
+        /// Returns the element at `index`, or `None` if it is out of range.
+        pub fn get(&self, index: usize) -> Option<PointView<'_>> {
+            self.as_view().get(index)
+        }
+
+        /// Returns an iterator over the elements.
+        pub fn iter(&self) -> impl Iterator<Item = PointView<'_>> + '_ {
+            self.as_view().iter()
+        }
+
+        fn set_len(&mut self, len: usize) {
+            self.data[0] = len as u8;
+        }
+
+        /// Removes all elements.
+        pub fn clear(&mut self) {
+            self.set_len(0);
+        }
+
+        /// Returns a mutable view of the element at `index`, or `None` if it is out of range.
+        pub fn get_mut(&mut self, index: usize) -> Option<PointMutView<'_>> {
+            if index >= self.len() {
+                return None;
+            }
+            Some(PointMutView::new((&mut self.data[1 + index * 8..1 + index * 8 + 8]).try_into().unwrap()))
+        }
+
+        /// Appends a zeroed element and returns a mutable view of it, or `None` if the vector is full.
+        pub fn push(&mut self) -> Option<PointMutView<'_>> {
+            let index = self.len();
+            if index == Self::CAPACITY {
+                return None;
+            }
+            self.set_len(index + 1);
+            self.data[1 + index * 8..1 + index * 8 + 8].fill(0);
+            self.get_mut(index)
+        }
+    }
+
+    impl SampleView<'_> {
+        /// Converts to the current layout. Fields added since are zeroed.
+        pub fn upgrade(&self) -> super::SampleBuffer {
+            let mut buffer = [0; super::SAMPLE_SIZE];
+            self.upgrade_into(&mut super::SampleMutView::new(&mut buffer));
+            buffer
+        }
+
+        fn upgrade_into(&self, target: &mut super::SampleMutView<'_>) {
+            target.set_flag(self.flag());
+            target.set_level(self.level());
+            PointView::new(self.data[3..3 + 8].try_into().unwrap())
+                .upgrade_into(&mut super::PointMutView::new(
+                    (&mut target.data[1..1 + 12]).try_into().unwrap(),
+                ));
+            target.data[13..14].copy_from_slice(&self.data[11..12]);
+            for i in 0..2 {
+                let (from, to) = (12 + i * 8, 14 + i * 12);
+                PointView::new(self.data[from..from + 8].try_into().unwrap())
+                    .upgrade_into(&mut super::PointMutView::new(
+                        (&mut target.data[to..to + 12]).try_into().unwrap(),
+                    ));
+            }
+            target.data[38..42].copy_from_slice(&self.data[28..32]);
+        }
+    }
+}
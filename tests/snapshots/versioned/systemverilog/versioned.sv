@@ -0,0 +1,34 @@
+// Automatically generated by Onyx IDL compiler. Do not edit.
+`ifndef ONYX_VERSIONED_PKG_SV
+`define ONYX_VERSIONED_PKG_SV
+
+package versioned_pkg;
+
+  // Point: 12 bytes.
+  localparam int Point_SIZE_BYTES = 12;
+  localparam int Point_x_OFFSET = 0;
+  localparam int Point_x_WIDTH = 32;
+  localparam int Point_y_OFFSET = 4;
+  localparam int Point_y_WIDTH = 32;
+  localparam int Point_z_OFFSET = 8;
+  localparam int Point_z_WIDTH = 32;
+
+  // Sample: 42 bytes.
+  localparam int Sample_SIZE_BYTES = 42;
+  localparam int Sample_flag_OFFSET = 0;
+  localparam int Sample_flag_BIT_OFFSET = 0;
+  localparam int Sample_flag_WIDTH = 1;
+  localparam int Sample_level_OFFSET = 0;
+  localparam int Sample_level_BIT_OFFSET = 1;
+  localparam int Sample_level_WIDTH = 4;
+  localparam int Sample_origin_OFFSET = 1;
+  localparam int Sample_origin_WIDTH = 96;
+  localparam int Sample_path_OFFSET = 13;
+  localparam int Sample_path_WIDTH = 200;
+  localparam int Sample_path_CAPACITY = 2;
+  localparam int Sample_id_OFFSET = 38;
+  localparam int Sample_id_WIDTH = 32;
+
+endpackage
+
+`endif // ONYX_VERSIONED_PKG_SV